@@ -0,0 +1,315 @@
+//! Admin commands over chat.
+//!
+//! Senders listed in `admin.sender_ids` as `channel:sender_id` — and, with
+//! `users` enabled, users with the `owner` role — can run a handful of
+//! commands from any channel. Entries must name the channel: a bare id would
+//! also match whoever claims it on a channel with self-chosen sender ids,
+//! such as webhooks or devices.
+//! They are handled by the agent loop before the message reaches the LLM;
+//! from anyone else they are ordinary messages.
+//!
+//! - `/status` — model, agent mode, running turns, degraded state
+//! - `/usage` — requests and tokens since the last flush, per-user quotas
+//! - `/sessions` — stored sessions, with the running ones marked
+//! - `/reset [session]` — clear a session (default: this chat's)
+//! - `/reload config` — reload `config.json` and rebuild the agent
+//!   (gateway only)
+//! - `/pause channel <name>` / `/resume channel <name>` — drop a channel's
+//!   inbound messages until resumed
+//!
+//! Replies are markdown, rendered per channel like any other reply.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::security::users::{UserRole, UsersConfig};
+
+/// Reply to `/admin`.
+pub const ADMIN_HELP: &str = "Admin commands:\n\
+- `/status`\n\
+- `/usage`\n\
+- `/sessions`\n\
+- `/reset [session]`\n\
+- `/reload config`\n\
+- `/pause channel <name>`, `/resume channel <name>`";
+
+/// Sessions listed by `/sessions`.
+pub const SESSIONS_SHOWN: usize = 20;
+
+/// Configuration for admin chat commands.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct AdminConfig {
+    /// Senders allowed to run admin commands, as `channel:sender_id`.
+    /// Entries without a channel never match.
+    pub sender_ids: Vec<String>,
+}
+
+impl AdminConfig {
+    /// Whether a sender may run admin commands.
+    pub fn is_admin(&self, users: &UsersConfig, channel: &str, sender_id: &str) -> bool {
+        if sender_id.is_empty() || channel == "subagent" {
+            return false;
+        }
+        let qualified = format!("{}:{}", channel, sender_id);
+        self.sender_ids.contains(&qualified)
+            || users
+                .resolve(channel, sender_id)
+                .is_some_and(|user| user.known && user.role == UserRole::Owner)
+    }
+}
+
+/// A parsed admin command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminCommand {
+    Help,
+    Status,
+    Usage,
+    Sessions,
+    /// Session key, or `None` for the sender's own session.
+    Reset(Option<String>),
+    ReloadConfig,
+    Pause(String),
+    Resume(String),
+}
+
+impl AdminCommand {
+    /// Parse `text` as an admin command. Bot suffixes (`/status@my_bot`) are
+    /// accepted.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut words = text.split_whitespace();
+        let command = words.next()?.strip_prefix('/')?;
+        let command = command.split('@').next().unwrap_or(command);
+        let args: Vec<&str> = words.collect();
+        let channel = |args: &[&str]| match args {
+            ["channel", name] => Some(name.to_string()),
+            _ => None,
+        };
+        match (command, args.as_slice()) {
+            ("admin", []) => Some(Self::Help),
+            ("status", []) => Some(Self::Status),
+            ("usage", []) => Some(Self::Usage),
+            ("sessions", []) => Some(Self::Sessions),
+            ("reset", []) => Some(Self::Reset(None)),
+            ("reset", [key]) => Some(Self::Reset(Some(key.to_string()))),
+            ("reload", ["config"]) => Some(Self::ReloadConfig),
+            ("pause", args) => channel(args).map(Self::Pause),
+            ("resume", args) => channel(args).map(Self::Resume),
+            _ => None,
+        }
+    }
+}
+
+/// Compact uptime for `/status`: `2d 3h 4m`, `3h 4m` or `4m`.
+pub fn format_uptime(secs: u64) -> String {
+    let (days, hours, mins) = (secs / 86_400, (secs % 86_400) / 3600, (secs % 3600) / 60);
+    if days > 0 {
+        format!("{days}d {hours}h {mins}m")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else {
+        format!("{mins}m")
+    }
+}
+
+/// Channels paused with `/pause channel`, kept in
+/// `<state dir>/admin/paused_channels.json` so they survive restarts and
+/// config reloads.
+pub struct PausedChannels {
+    path: Option<PathBuf>,
+    channels: Mutex<BTreeSet<String>>,
+}
+
+impl PausedChannels {
+    /// Load the paused set from the state dir.
+    pub fn load() -> Self {
+        Self::load_from(
+            crate::config::Config::state_dir()
+                .join("admin")
+                .join("paused_channels.json"),
+        )
+    }
+
+    pub fn load_from(path: PathBuf) -> Self {
+        let channels = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            channels: Mutex::new(channels),
+        }
+    }
+
+    /// An empty set that is never persisted.
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            channels: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    pub fn is_paused(&self, channel: &str) -> bool {
+        self.lock().contains(channel)
+    }
+
+    pub fn list(&self) -> Vec<String> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// Pause `channel`. Returns `false` if it already was.
+    pub fn pause(&self, channel: &str) -> bool {
+        let mut channels = self.lock();
+        let changed = channels.insert(channel.to_string());
+        if changed {
+            self.persist(&channels);
+        }
+        changed
+    }
+
+    /// Resume `channel`. Returns `false` if it was not paused.
+    pub fn resume(&self, channel: &str) -> bool {
+        let mut channels = self.lock();
+        let changed = channels.remove(channel);
+        if changed {
+            self.persist(&channels);
+        }
+        changed
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeSet<String>> {
+        self.channels.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn persist(&self, channels: &BTreeSet<String>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| {
+                std::fs::write(
+                    path,
+                    serde_json::to_string_pretty(channels).unwrap_or_default(),
+                )
+            });
+        if let Err(e) = result {
+            warn!(path = %path.display(), error = %e, "Failed to persist paused channels");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::users::UserEntry;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(AdminCommand::parse("/status"), Some(AdminCommand::Status));
+        assert_eq!(
+            AdminCommand::parse("/status@zepto_bot"),
+            Some(AdminCommand::Status)
+        );
+        assert_eq!(AdminCommand::parse("/usage"), Some(AdminCommand::Usage));
+        assert_eq!(
+            AdminCommand::parse("/sessions"),
+            Some(AdminCommand::Sessions)
+        );
+        assert_eq!(
+            AdminCommand::parse("/reset"),
+            Some(AdminCommand::Reset(None))
+        );
+        assert_eq!(
+            AdminCommand::parse("/reset telegram:42"),
+            Some(AdminCommand::Reset(Some("telegram:42".into())))
+        );
+        assert_eq!(
+            AdminCommand::parse(" /reload config "),
+            Some(AdminCommand::ReloadConfig)
+        );
+        assert_eq!(
+            AdminCommand::parse("/pause channel discord"),
+            Some(AdminCommand::Pause("discord".into()))
+        );
+        assert_eq!(
+            AdminCommand::parse("/resume channel discord"),
+            Some(AdminCommand::Resume("discord".into()))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_other_text() {
+        for text in [
+            "status",
+            "/statuses",
+            "/status now",
+            "/reload",
+            "/pause discord",
+            "/pause channel",
+            "what is my /status",
+        ] {
+            assert_eq!(AdminCommand::parse(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(59), "0m");
+        assert_eq!(format_uptime(3_660), "1h 1m");
+        assert_eq!(format_uptime(90_061), "1d 1h 1m");
+    }
+
+    #[test]
+    fn test_is_admin() {
+        let admin = AdminConfig {
+            sender_ids: vec!["telegram:1".into(), "slack:ops".into(), "9".into()],
+        };
+        let mut users = UsersConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        users.users.insert(
+            "anna".into(),
+            UserEntry {
+                role: UserRole::Owner,
+                identities: vec!["discord:9".into()],
+                ..Default::default()
+            },
+        );
+
+        assert!(admin.is_admin(&users, "telegram", "1"));
+        assert!(!admin.is_admin(&users, "discord", "1"));
+        assert!(admin.is_admin(&users, "slack", "ops"));
+        assert!(!admin.is_admin(&users, "webhook", "ops"));
+        // Bare entries match nothing; the owner role still does.
+        assert!(!admin.is_admin(&users, "webhook", "9"));
+        assert!(admin.is_admin(&users, "discord", "9"));
+        // Unknown senders are not owners, whatever the default role.
+        users.default_role = UserRole::Owner;
+        assert!(!admin.is_admin(&users, "discord", "10"));
+        assert!(!admin.is_admin(&users, "subagent", "ops"));
+    }
+
+    #[test]
+    fn test_paused_channels_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("admin").join("paused.json");
+        let paused = PausedChannels::load_from(path.clone());
+        assert!(paused.pause("discord"));
+        assert!(!paused.pause("discord"));
+        assert!(paused.is_paused("discord"));
+
+        let reloaded = PausedChannels::load_from(path.clone());
+        assert_eq!(reloaded.list(), vec!["discord".to_string()]);
+        assert!(reloaded.resume("discord"));
+        assert!(!reloaded.resume("discord"));
+        assert!(!PausedChannels::load_from(path).is_paused("discord"));
+    }
+}
@@ -4,9 +4,16 @@
 //! and message history for LLM conversations. It also provides `RuntimeContext`
 //! for injecting environment-awareness into the agent's system prompt.
 
+use std::collections::HashMap;
+use std::sync::RwLock;
+
 use chrono::Local;
 
-use crate::session::Message;
+use crate::agent::prompts::{self, PromptTemplates, PromptVars};
+use crate::channels::persona_switch::resolve_soul_content;
+use crate::config::ChannelPromptConfig;
+use crate::session::{Message, Role};
+use crate::utils::timezone::{Tz, USER_TIMEZONE_KEY};
 
 /// Format a timestamp envelope for a user message.
 ///
@@ -28,6 +35,8 @@ You have a longterm_memory tool. Use it proactively to:
 - Recall relevant information from past conversations by calling longterm_memory with action "search"
 - Pin critical information that should always be available
 
+When memory_set / memory_append are available, prefer them for saving facts learned in conversation: they deduplicate against existing memories and record why you remembered something.
+
 ## Scheduled & Background Messages
 
 When a message begins with `Reminder:`, it was delivered by the scheduler on behalf of the user — not typed by them now. Respond with a friendly, concise notification of the reminder content, as if you're the reminder itself notifying the user.
@@ -91,8 +100,8 @@ pub struct RuntimeContext {
     /// Names of available tools
     pub available_tools: Vec<String>,
     /// Timezone label (e.g., "Asia/Kuala_Lumpur"). When set, current time is
-    /// computed **live** in `render()` via `chrono::Local` so it is never stale.
-    /// The label is displayed in the system prompt for the LLM's awareness.
+    /// computed **live** in `render()` in that zone so it is never stale.
+    /// Labels that don't name a known zone fall back to `chrono::Local`.
     pub timezone: Option<String>,
     /// Workspace path
     pub workspace: Option<String>,
@@ -141,9 +150,9 @@ impl RuntimeContext {
 
     /// Set the timezone label and enable time display in the system prompt.
     ///
-    /// When a timezone is set, `render()` computes the current local time
-    /// dynamically via `chrono::Local` so it is always fresh. The timezone
-    /// string is shown as an informational label for the LLM.
+    /// When a timezone is set, `render()` computes the current time in it
+    /// on every call so it is always fresh. Unknown zones fall back to the
+    /// system clock.
     ///
     /// # Arguments
     /// * `tz` - Timezone label (e.g., "Asia/Kuala_Lumpur", "US/Pacific", "UTC")
@@ -218,16 +227,33 @@ impl RuntimeContext {
                 self.available_tools.join(", ")
             ));
         }
-        // Compute current time LIVE via chrono::Local (never stale).
-        // The timezone label comes from config (auto-detected IANA name),
-        // but we always show the actual system offset to avoid contradictions.
-        if self.timezone.is_some() {
-            let now = Local::now();
+        // Compute current time LIVE (never stale) in the configured zone.
+        // Labels that don't resolve to a zone fall back to the system clock,
+        // showing only its offset so the name and time never contradict.
+        if let Some(ref label) = self.timezone {
+            match Tz::load(label) {
+                Ok(tz) => {
+                    let now = tz.now();
+                    parts.push(format!(
+                        "- Current time: {}",
+                        now.format("%a %Y-%m-%d %H:%M %:z")
+                    ));
+                    parts.push(format!("- Timezone: {} ({})", tz.name(), now.format("%:z")));
+                }
+                Err(_) => {
+                    let now = Local::now();
+                    parts.push(format!(
+                        "- Current time: {}",
+                        now.format("%a %Y-%m-%d %H:%M %:z")
+                    ));
+                    parts.push(format!("- Timezone: {}", now.format("%:z")));
+                }
+            }
             parts.push(format!(
-                "- Current time: {}",
-                now.format("%a %Y-%m-%d %H:%M %:z")
+                "- Resolve relative times (\"tomorrow morning\", \"in 2 hours\") against this clock. \
+                 When the user tells you their timezone, remember it under the memory key `{}` (IANA name, e.g. Europe/Berlin).",
+                USER_TIMEZONE_KEY
             ));
-            parts.push(format!("- Timezone: {}", now.format("%:z")));
         }
         if let Some(ref workspace) = self.workspace {
             parts.push(format!("- Workspace: {}", workspace));
@@ -262,12 +288,17 @@ pub struct ContextBuilder {
     system_prompt: String,
     /// Optional SOUL.md content prepended before system prompt
     soul_prompt: Option<String>,
-    /// Optional skills content to append to system prompt
-    skills_prompt: Option<String>,
+    /// Optional skills content to append to system prompt. Swappable at
+    /// runtime so skills can be hot-reloaded.
+    skills_prompt: RwLock<Option<String>>,
     /// Optional runtime context to append to system prompt
     runtime_context: Option<RuntimeContext>,
     /// Optional memory context to append to system prompt
     memory_context: Option<String>,
+    /// Optional per-channel/per-agent prompt templates
+    prompt_templates: Option<PromptTemplates>,
+    /// Per-channel system prompt and persona (`channels.prompts`)
+    channel_prompts: HashMap<String, ChannelPromptConfig>,
 }
 
 impl ContextBuilder {
@@ -285,9 +316,11 @@ impl ContextBuilder {
         Self {
             system_prompt: DEFAULT_SYSTEM_PROMPT.to_string(),
             soul_prompt: None,
-            skills_prompt: None,
+            skills_prompt: RwLock::new(None),
             runtime_context: None,
             memory_context: None,
+            prompt_templates: None,
+            channel_prompts: HashMap::new(),
         }
     }
 
@@ -351,11 +384,30 @@ impl ContextBuilder {
     /// assert!(system.content.contains("Available Skills"));
     /// assert!(system.content.contains("/search"));
     /// ```
-    pub fn with_skills(mut self, skills_content: &str) -> Self {
-        self.skills_prompt = Some(skills_content.to_string());
+    pub fn with_skills(self, skills_content: &str) -> Self {
+        self.set_skills(Some(skills_content));
         self
     }
 
+    /// Replace the skills content in place (used by skills hot-reload).
+    ///
+    /// `None` or an empty string removes the "Available Skills" section.
+    pub fn set_skills(&self, skills_content: Option<&str>) {
+        let content = skills_content.filter(|c| !c.is_empty()).map(str::to_string);
+        match self.skills_prompt.write() {
+            Ok(mut slot) => *slot = content,
+            Err(poisoned) => *poisoned.into_inner() = content,
+        }
+    }
+
+    /// Current skills content, if any.
+    pub fn skills(&self) -> Option<String> {
+        match self.skills_prompt.read() {
+            Ok(slot) => slot.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
     /// Add runtime context to the system prompt.
     ///
     /// Runtime context provides the agent with awareness of its environment
@@ -410,6 +462,38 @@ impl ContextBuilder {
         self
     }
 
+    /// Use prompt templates for per-channel and per-agent overrides.
+    ///
+    /// If the templates include a `system.md`, it replaces the current base
+    /// system prompt. Overrides and `{{variable}}` placeholders are applied
+    /// by [`Self::build_messages_with_vars`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use zeptoclaw::agent::ContextBuilder;
+    /// use zeptoclaw::agent::prompts::PromptTemplates;
+    ///
+    /// let builder = ContextBuilder::new().with_prompt_templates(PromptTemplates::default());
+    /// assert!(builder.system_prompt().contains("ZeptoClaw"));
+    /// ```
+    pub fn with_prompt_templates(mut self, templates: PromptTemplates) -> Self {
+        if let Some(system) = templates.system() {
+            self.system_prompt = system.to_string();
+        }
+        self.prompt_templates = Some(templates);
+        self
+    }
+
+    /// Use per-channel system prompts and personas (`channels.prompts`).
+    ///
+    /// A channel's `system_prompt` takes precedence over prompt templates;
+    /// its persona is added after SOUL.md. Both apply in
+    /// [`Self::build_messages_with_vars`].
+    pub fn with_channel_prompts(mut self, prompts: HashMap<String, ChannelPromptConfig>) -> Self {
+        self.channel_prompts = prompts;
+        self
+    }
+
     /// Append a suffix to the system prompt.
     ///
     /// Used for injecting additional instructions like first-run persona prompts.
@@ -439,9 +523,9 @@ impl ContextBuilder {
             content.push_str("\n\n");
         }
         content.push_str(&self.system_prompt);
-        if let Some(ref skills) = self.skills_prompt {
+        if let Some(skills) = self.skills() {
             content.push_str("\n\n## Available Skills\n\n");
-            content.push_str(skills);
+            content.push_str(&skills);
         }
         if let Some(ref ctx) = self.runtime_context {
             if let Some(rendered) = ctx.render() {
@@ -460,16 +544,47 @@ impl ContextBuilder {
     ///
     /// When `memory_override` is `Some`, it replaces the stored
     /// `memory_context`. `Some("")` suppresses memory injection.
-    fn build_system_message_with_memory_override(&self, memory_override: Option<&str>) -> Message {
+    ///
+    /// When `vars` is set, the base prompt is replaced by a matching channel
+    /// or agent template and `{{variable}}` placeholders are rendered.
+    fn build_system_message_with_memory_override(
+        &self,
+        memory_override: Option<&str>,
+        vars: Option<&PromptVars>,
+    ) -> Message {
+        let channel_prompt = vars
+            .and_then(|vars| vars.get("channel"))
+            .and_then(|channel| self.channel_prompts.get(channel));
         let mut content = String::new();
         if let Some(ref soul) = self.soul_prompt {
             content.push_str(soul);
             content.push_str("\n\n");
         }
-        content.push_str(&self.system_prompt);
-        if let Some(ref skills) = self.skills_prompt {
+        if let Some(persona) = channel_prompt
+            .and_then(|p| p.persona.as_deref())
+            .map(resolve_soul_content)
+            .filter(|persona| !persona.trim().is_empty())
+        {
+            content.push_str(&persona);
+            content.push_str("\n\n");
+        }
+        match vars {
+            Some(vars) => {
+                let base = channel_prompt
+                    .and_then(|p| p.system_prompt.as_deref())
+                    .or_else(|| {
+                        self.prompt_templates
+                            .as_ref()
+                            .and_then(|t| t.resolve(vars.get("channel")))
+                    })
+                    .unwrap_or(&self.system_prompt);
+                content.push_str(&prompts::render(base, vars));
+            }
+            None => content.push_str(&self.system_prompt),
+        }
+        if let Some(skills) = self.skills() {
             content.push_str("\n\n## Available Skills\n\n");
-            content.push_str(skills);
+            content.push_str(&skills);
         }
         if let Some(ref ctx) = self.runtime_context {
             if let Some(rendered) = ctx.render() {
@@ -549,7 +664,33 @@ impl ContextBuilder {
         user_input: &str,
         memory_override: Option<&str>,
     ) -> Vec<Message> {
-        let mut messages = vec![self.build_system_message_with_memory_override(memory_override)];
+        self.build_messages_inner(history, user_input, memory_override, None)
+    }
+
+    /// Build the full message list with prompt-template variables.
+    ///
+    /// Works like `build_messages_with_memory_override`, but resolves
+    /// per-channel/per-agent template overrides and renders `{{variable}}`
+    /// placeholders in the system prompt using `vars`.
+    pub fn build_messages_with_vars(
+        &self,
+        history: &[Message],
+        user_input: &str,
+        memory_override: Option<&str>,
+        vars: &PromptVars,
+    ) -> Vec<Message> {
+        self.build_messages_inner(history, user_input, memory_override, Some(vars))
+    }
+
+    fn build_messages_inner(
+        &self,
+        history: &[Message],
+        user_input: &str,
+        memory_override: Option<&str>,
+        vars: Option<&PromptVars>,
+    ) -> Vec<Message> {
+        let mut messages =
+            vec![self.build_system_message_with_memory_override(memory_override, vars)];
         messages.extend(history.iter().cloned());
         if !user_input.is_empty() {
             let content = if let Some(ref ctx) = self.runtime_context {
@@ -572,6 +713,17 @@ impl ContextBuilder {
         &self.system_prompt
     }
 
+    /// Append `section` to the leading system message of `messages`.
+    ///
+    /// Used for per-message context (e.g. recent group chat) that should not
+    /// be stored on the builder. No-op when there is no system message.
+    pub fn append_to_system(messages: &mut [Message], section: &str) {
+        if let Some(system) = messages.first_mut().filter(|m| m.role == Role::System) {
+            system.content.push_str("\n\n");
+            system.content.push_str(section);
+        }
+    }
+
     /// Check if a SOUL.md identity is configured.
     pub fn has_soul(&self) -> bool {
         self.soul_prompt.is_some()
@@ -579,7 +731,12 @@ impl ContextBuilder {
 
     /// Check if skills are configured.
     pub fn has_skills(&self) -> bool {
-        self.skills_prompt.is_some()
+        self.skills().is_some()
+    }
+
+    /// Active agent name from the prompt templates, if any.
+    pub fn prompt_agent(&self) -> Option<&str> {
+        self.prompt_templates.as_ref().and_then(|t| t.agent())
     }
 }
 
@@ -613,6 +770,22 @@ mod tests {
         assert_eq!(builder.system_prompt(), "Custom prompt here");
     }
 
+    #[test]
+    fn test_set_skills_replaces_in_place() {
+        let builder = ContextBuilder::new().with_skills("- /old: Old skill");
+        builder.set_skills(Some("- /new: New skill"));
+        let system = builder.build_system_message();
+        assert!(system.content.contains("/new"));
+        assert!(!system.content.contains("/old"));
+
+        builder.set_skills(None);
+        assert!(!builder.has_skills());
+        assert!(!builder
+            .build_system_message()
+            .content
+            .contains("Available Skills"));
+    }
+
     #[test]
     fn test_context_builder_with_skills() {
         let builder = ContextBuilder::new().with_skills("- /test: Test skill");
@@ -817,13 +990,12 @@ mod tests {
         assert!(!ctx.is_empty());
         let rendered = ctx.render().unwrap();
         assert!(rendered.contains("Current time:"));
-        // Timezone label now shows the actual system UTC offset (e.g. "+08:00")
-        // instead of the config IANA name, to avoid contradictions.
         assert!(
-            rendered.contains("Timezone: +") || rendered.contains("Timezone: -"),
-            "should show UTC offset: {}",
+            rendered.contains("Timezone: Asia/Kuala_Lumpur (+08:00)"),
+            "should show the zone: {}",
             rendered
         );
+        assert!(rendered.contains("Resolve relative times"));
     }
 
     #[test]
@@ -831,11 +1003,34 @@ mod tests {
         let ctx = RuntimeContext::new().with_timezone("UTC");
         let rendered = ctx.render().unwrap();
         assert!(rendered.contains("Current time:"));
-        // Even when config says "UTC", the displayed timezone is the actual
-        // system offset from chrono::Local (e.g. "+08:00" or "+00:00").
+        assert!(rendered.contains("Timezone: UTC (+00:00)"), "{}", rendered);
+        let time_line = rendered
+            .lines()
+            .find(|l| l.contains("Current time:"))
+            .unwrap();
+        assert!(time_line.ends_with("+00:00"), "{}", time_line);
+    }
+
+    #[test]
+    fn test_runtime_context_fixed_offset_and_unknown_zone() {
+        let rendered = RuntimeContext::new()
+            .with_timezone("+05:30")
+            .render()
+            .unwrap();
+        assert!(
+            rendered.contains("Timezone: +05:30 (+05:30)"),
+            "{}",
+            rendered
+        );
+
+        // Unknown labels fall back to the system offset.
+        let rendered = RuntimeContext::new()
+            .with_timezone("Nowhere/Special")
+            .render()
+            .unwrap();
         assert!(
             rendered.contains("Timezone: +") || rendered.contains("Timezone: -"),
-            "should show UTC offset: {}",
+            "{}",
             rendered
         );
     }
@@ -1150,4 +1345,89 @@ mod tests {
         assert!(FIRST_RUN_PERSONA_PROMPT.contains("concise"));
         assert!(FIRST_RUN_PERSONA_PROMPT.contains("persona_pref"));
     }
+
+    #[test]
+    fn test_build_messages_with_vars_renders_base_prompt() {
+        let builder =
+            ContextBuilder::new().with_system_prompt("Hello {{user_name}} via {{channel}}");
+        let vars = PromptVars::default()
+            .with("user_name", "Sam")
+            .with("channel", "cli");
+        let messages = builder.build_messages_with_vars(&[], "hi", None, &vars);
+        assert_eq!(messages[0].content, "Hello Sam via cli");
+        // Without vars the template is left untouched
+        let plain = builder.build_messages(&[], "hi");
+        assert!(plain[0].content.contains("{{user_name}}"));
+    }
+
+    #[test]
+    fn test_prompt_templates_channel_override() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("system.md"), "Base prompt").unwrap();
+        std::fs::create_dir_all(temp.path().join("channels")).unwrap();
+        std::fs::write(
+            temp.path().join("channels/telegram.md"),
+            "Telegram prompt for {{user_name}}",
+        )
+        .unwrap();
+
+        let builder = ContextBuilder::new()
+            .with_prompt_templates(PromptTemplates::load(temp.path()))
+            .with_soul("Soul.");
+        assert_eq!(builder.system_prompt(), "Base prompt");
+
+        let vars = PromptVars::default()
+            .with("channel", "telegram")
+            .with("user_name", "Ana");
+        let messages = builder.build_messages_with_vars(&[], "", None, &vars);
+        assert_eq!(messages[0].content, "Soul.\n\nTelegram prompt for Ana");
+
+        let vars = PromptVars::default().with("channel", "discord");
+        let messages = builder.build_messages_with_vars(&[], "", None, &vars);
+        assert_eq!(messages[0].content, "Soul.\n\nBase prompt");
+    }
+
+    #[test]
+    fn test_channel_prompts_override_prompt_and_add_persona() {
+        let mut prompts = HashMap::new();
+        prompts.insert(
+            "slack".to_string(),
+            ChannelPromptConfig {
+                system_prompt: Some("Work assistant for {{user_name}}.".into()),
+                persona: Some("professional".into()),
+                ..Default::default()
+            },
+        );
+        prompts.insert(
+            "telegram".to_string(),
+            ChannelPromptConfig {
+                persona: Some("Call everyone mate.".into()),
+                ..Default::default()
+            },
+        );
+        let builder = ContextBuilder::new()
+            .with_system_prompt("Base prompt")
+            .with_soul("Soul.")
+            .with_channel_prompts(prompts);
+
+        let vars = PromptVars::default()
+            .with("channel", "slack")
+            .with("user_name", "Ana");
+        let messages = builder.build_messages_with_vars(&[], "", None, &vars);
+        assert!(messages[0]
+            .content
+            .starts_with("Soul.\n\nYou are professional"));
+        assert!(messages[0].content.ends_with("Work assistant for Ana."));
+
+        let vars = PromptVars::default().with("channel", "telegram");
+        let messages = builder.build_messages_with_vars(&[], "", None, &vars);
+        assert_eq!(
+            messages[0].content,
+            "Soul.\n\nCall everyone mate.\n\nBase prompt"
+        );
+
+        let vars = PromptVars::default().with("channel", "discord");
+        let messages = builder.build_messages_with_vars(&[], "", None, &vars);
+        assert_eq!(messages[0].content, "Soul.\n\nBase prompt");
+    }
 }
@@ -1,12 +1,14 @@
 //! Context monitor for token estimation and threshold detection.
 //!
-//! Provides heuristic-based token estimation from conversation messages
-//! and suggests compaction strategies when the context window is getting full.
+//! Provides token estimation from conversation messages and suggests
+//! compaction strategies when the context window is getting full.
 //!
 //! # Token Estimation
 //!
-//! Uses the heuristic: `words * 1.3 + 4` per message, where the 4 accounts
-//! for message framing overhead (role markers, delimiters).
+//! When a model is set via [`ContextMonitor::with_model`], tokens are counted
+//! with the model's tokenizer family (see [`crate::providers::tokenizer`]).
+//! Otherwise the heuristic `words * 1.3 + 4` per message is used, where the 4
+//! accounts for message framing overhead (role markers, delimiters).
 //!
 //! # Compaction Strategies
 //!
@@ -27,6 +29,7 @@
 //! assert_eq!(monitor.suggest_strategy(&messages), CompactionStrategy::None);
 //! ```
 
+use crate::providers::tokenizer;
 use crate::session::Message;
 
 /// Strategy suggested when context is getting too large.
@@ -50,9 +53,9 @@ pub enum CompactionUrgency {
 
 /// Monitors conversation context size and suggests compaction strategies.
 ///
-/// Uses heuristic token estimation to detect when the conversation is
-/// approaching the context window limit, and recommends an appropriate
-/// compaction strategy based on how full the context is.
+/// Uses model-aware (or heuristic) token counting to detect when the
+/// conversation is approaching the context window limit, and recommends an
+/// appropriate compaction strategy based on how full the context is.
 pub struct ContextMonitor {
     /// Maximum token capacity of the context window.
     context_limit: usize,
@@ -62,6 +65,8 @@ pub struct ContextMonitor {
     emergency_threshold: f64,
     /// Fraction for critical hard-trim behavior.
     critical_threshold: f64,
+    /// Model whose tokenizer is used for counting (heuristic when `None`).
+    model: Option<String>,
 }
 
 impl ContextMonitor {
@@ -86,6 +91,22 @@ impl ContextMonitor {
             threshold,
             emergency_threshold,
             critical_threshold,
+            model: None,
+        }
+    }
+
+    /// Count tokens with the given model's tokenizer instead of the heuristic.
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = Some(model.to_string()).filter(|m| !m.is_empty());
+        self
+    }
+
+    /// Count tokens for `messages` using the configured model's tokenizer,
+    /// falling back to [`Self::estimate_tokens`] when no model is set.
+    pub fn count_tokens(&self, messages: &[Message]) -> usize {
+        match self.model.as_deref() {
+            Some(model) => tokenizer::count_tokens(model, messages),
+            None => Self::estimate_tokens(messages),
         }
     }
 
@@ -117,13 +138,13 @@ impl ContextMonitor {
     /// # Returns
     /// `true` if estimated tokens exceed `threshold * context_limit`.
     pub fn needs_compaction(&self, messages: &[Message]) -> bool {
-        let estimated = Self::estimate_tokens(messages);
+        let estimated = self.count_tokens(messages);
         estimated as f64 > self.threshold * self.context_limit as f64
     }
 
     /// Determine compaction urgency tier based on fullness ratio.
     pub fn urgency(&self, messages: &[Message]) -> Option<CompactionUrgency> {
-        let estimated = Self::estimate_tokens(messages);
+        let estimated = self.count_tokens(messages);
         let ratio = estimated as f64 / self.context_limit as f64;
         if ratio <= self.threshold {
             None
@@ -147,7 +168,7 @@ impl ContextMonitor {
     /// # Arguments
     /// * `messages` - The conversation messages to evaluate
     pub fn suggest_strategy(&self, messages: &[Message]) -> CompactionStrategy {
-        let estimated = Self::estimate_tokens(messages);
+        let estimated = self.count_tokens(messages);
         let ratio = estimated as f64 / self.context_limit as f64;
 
         match self.urgency(messages) {
@@ -172,6 +193,7 @@ impl Default for ContextMonitor {
             threshold: 0.70,
             emergency_threshold: 0.90,
             critical_threshold: 0.95,
+            model: None,
         }
     }
 }
@@ -395,4 +417,24 @@ mod tests {
             CompactionStrategy::None
         );
     }
+
+    #[test]
+    fn test_with_model_uses_tokenizer() {
+        let code = "fn main(){let v=vec![1,2,3];println!(\"{:?}\",v);}";
+        let messages = vec![make_message(code)];
+        let heuristic = ContextMonitor::new(100_000, 0.80);
+        let tokenized = ContextMonitor::new(100_000, 0.80).with_model("claude-sonnet-4-5");
+        assert_eq!(
+            heuristic.count_tokens(&messages),
+            ContextMonitor::estimate_tokens(&messages)
+        );
+        assert!(tokenized.count_tokens(&messages) > heuristic.count_tokens(&messages));
+    }
+
+    #[test]
+    fn test_with_empty_model_falls_back_to_heuristic() {
+        let monitor = ContextMonitor::new(100_000, 0.80).with_model("");
+        let messages = vec![make_message("Hello world")];
+        assert_eq!(monitor.count_tokens(&messages), 6);
+    }
 }
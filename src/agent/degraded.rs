@@ -0,0 +1,271 @@
+//! Degraded mode for provider outages.
+//!
+//! When every configured provider fails with a transient error (network,
+//! 5xx, rate limit, timeout), the agent loop switches to degraded mode
+//! instead of replying with raw provider errors:
+//!
+//! - the user gets a short acknowledgement (optionally enriched with
+//!   matching long-term memories),
+//! - the message is queued and re-published to the bus on an interval,
+//!   unless the failure came after one of its tools had run (replaying the
+//!   turn would run those tools again; see [`after_tools`]),
+//! - the admin target (`degraded.notify`) is told when the outage starts
+//!   and when it recovers.
+//!
+//! Exact repeats of previously answered prompts are still served by the
+//! response cache, which is consulted before any provider call.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::bus::InboundMessage;
+use crate::error::{ProviderError, ZeptoError};
+use crate::memory::longterm::LongTermMemory;
+use crate::memory::namespace::MemoryScope;
+
+/// Metadata key carrying how many times a queued message has been retried.
+pub const DEGRADED_ATTEMPTS_METADATA_KEY: &str = "degraded_attempts";
+
+/// Maximum number of memory entries quoted in an offline answer.
+const MEMORY_ANSWER_MAX_ENTRIES: usize = 3;
+
+/// Returns `true` if the error indicates that the provider (or the network
+/// path to it) is unavailable, as opposed to a problem with the request.
+pub fn is_provider_outage(err: &ZeptoError) -> bool {
+    match err {
+        ZeptoError::ProviderTyped(pe) => matches!(
+            pe,
+            ProviderError::RateLimit(_)
+                | ProviderError::ServerError(_)
+                | ProviderError::Timeout(_)
+                | ProviderError::Overloaded(_)
+                | ProviderError::Unknown(_)
+        ),
+        ZeptoError::Http(_) => true,
+        _ => false,
+    }
+}
+
+/// Mark a provider failure that hit a turn after its tools had run.
+///
+/// Replaying such a turn would repeat the tools' side effects (messages
+/// sent, files written), so outage errors are turned into plain provider
+/// errors that are reported to the user instead of queued for retry.
+pub fn after_tools(err: ZeptoError) -> ZeptoError {
+    if is_provider_outage(&err) {
+        ZeptoError::Provider(format!(
+            "{} (tools already ran this turn, so it was not queued for retry)",
+            err
+        ))
+    } else {
+        err
+    }
+}
+
+/// Number of retry attempts already made for a message.
+pub fn attempts(msg: &InboundMessage) -> u32 {
+    msg.metadata
+        .get(DEGRADED_ATTEMPTS_METADATA_KEY)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Parse a `"channel:chat_id"` notification target.
+pub fn parse_target(target: &str) -> Option<(String, String)> {
+    let (channel, chat_id) = target.split_once(':')?;
+    if channel.is_empty() || chat_id.is_empty() {
+        return None;
+    }
+    Some((channel.to_string(), chat_id.to_string()))
+}
+
+/// Build a short offline answer from long-term memory entries matching the
+/// user's message and visible in `scope`. Returns `None` when nothing
+/// relevant is stored.
+pub fn memory_answer(ltm: &LongTermMemory, query: &str, scope: &MemoryScope) -> Option<String> {
+    let lines: Vec<String> = ltm
+        .search(query)
+        .into_iter()
+        .filter(|entry| scope.can_see(&entry.key))
+        .take(MEMORY_ANSWER_MAX_ENTRIES)
+        .map(|entry| format!("- {}: {}", scope.display_key(&entry.key), entry.value))
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "Meanwhile, here is what I remember that may help:\n{}",
+        lines.join("\n")
+    ))
+}
+
+/// Shared degraded-mode state: outage flag and the retry queue.
+pub struct DegradedState {
+    degraded: AtomicBool,
+    queue: Mutex<VecDeque<InboundMessage>>,
+    max_queue: usize,
+}
+
+impl DegradedState {
+    /// Create a new state with the given queue capacity.
+    pub fn new(max_queue: usize) -> Self {
+        Self {
+            degraded: AtomicBool::new(false),
+            queue: Mutex::new(VecDeque::new()),
+            max_queue,
+        }
+    }
+
+    /// Whether the agent is currently in degraded mode.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded.load(Ordering::SeqCst)
+    }
+
+    /// Enter degraded mode. Returns `true` on the healthy → degraded transition.
+    pub fn enter(&self) -> bool {
+        !self.degraded.swap(true, Ordering::SeqCst)
+    }
+
+    /// Leave degraded mode. Returns `true` on the degraded → healthy transition.
+    pub fn recover(&self) -> bool {
+        self.degraded.swap(false, Ordering::SeqCst)
+    }
+
+    /// Queue a message for retry, bumping its attempt counter.
+    ///
+    /// Returns `false` if the queue is full and the message was dropped.
+    pub fn enqueue(&self, mut msg: InboundMessage) -> bool {
+        let next = attempts(&msg) + 1;
+        msg.metadata
+            .insert(DEGRADED_ATTEMPTS_METADATA_KEY.to_string(), next.to_string());
+        let Ok(mut queue) = self.queue.lock() else {
+            return false;
+        };
+        if queue.len() >= self.max_queue {
+            return false;
+        }
+        queue.push_back(msg);
+        true
+    }
+
+    /// Remove and return every queued message (oldest first).
+    pub fn take_all(&self) -> Vec<InboundMessage> {
+        self.queue
+            .lock()
+            .map(|mut q| q.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    /// Number of messages waiting for retry.
+    pub fn queued(&self) -> usize {
+        self.queue.lock().map(|q| q.len()).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_provider_outage_classification() {
+        assert!(is_provider_outage(&ZeptoError::ProviderTyped(
+            ProviderError::ServerError("502".into())
+        )));
+        assert!(is_provider_outage(&ZeptoError::ProviderTyped(
+            ProviderError::Timeout("t".into())
+        )));
+        assert!(is_provider_outage(&ZeptoError::ProviderTyped(
+            ProviderError::RateLimit("429".into())
+        )));
+        assert!(!is_provider_outage(&ZeptoError::ProviderTyped(
+            ProviderError::Auth("401".into())
+        )));
+        assert!(!is_provider_outage(&ZeptoError::ProviderTyped(
+            ProviderError::InvalidRequest("400".into())
+        )));
+        assert!(!is_provider_outage(&ZeptoError::Provider(
+            "No provider configured".into()
+        )));
+        assert!(!is_provider_outage(&ZeptoError::Tool("x".into())));
+    }
+
+    #[test]
+    fn test_after_tools_is_not_an_outage() {
+        let err = after_tools(ZeptoError::ProviderTyped(ProviderError::ServerError(
+            "503".into(),
+        )));
+        assert!(!is_provider_outage(&err));
+        assert!(err.to_string().contains("503"));
+
+        let err = after_tools(ZeptoError::Tool("x".into()));
+        assert!(matches!(err, ZeptoError::Tool(_)));
+    }
+
+    #[test]
+    fn test_enqueue_bumps_attempts_and_respects_capacity() {
+        let state = DegradedState::new(2);
+        let msg = InboundMessage::new("telegram", "u", "c", "hi");
+        assert!(state.enqueue(msg.clone()));
+        assert!(state.enqueue(msg.clone()));
+        assert!(!state.enqueue(msg));
+        assert_eq!(state.queued(), 2);
+
+        let drained = state.take_all();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(attempts(&drained[0]), 1);
+        assert_eq!(state.queued(), 0);
+
+        assert!(state.enqueue(drained[0].clone()));
+        assert_eq!(attempts(&state.take_all()[0]), 2);
+    }
+
+    #[test]
+    fn test_enter_and_recover_transitions() {
+        let state = DegradedState::new(1);
+        assert!(!state.is_degraded());
+        assert!(state.enter());
+        assert!(!state.enter());
+        assert!(state.is_degraded());
+        assert!(state.recover());
+        assert!(!state.recover());
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(
+            parse_target("telegram:123"),
+            Some(("telegram".to_string(), "123".to_string()))
+        );
+        assert_eq!(
+            parse_target("slack:C1:thread"),
+            Some(("slack".to_string(), "C1:thread".to_string()))
+        );
+        assert_eq!(parse_target("nocolon"), None);
+        assert_eq!(parse_target(":x"), None);
+    }
+
+    #[tokio::test]
+    async fn test_memory_answer_uses_matching_entries() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut ltm = LongTermMemory::with_path(temp.path().join("lt.json")).unwrap();
+        let scope = MemoryScope::new(Some("anna".into()));
+        assert!(memory_answer(&ltm, "wifi password", &scope).is_none());
+
+        ltm.set("home:wifi", "wifi is called zepto-net", "fact", vec![], 1.0)
+            .await
+            .unwrap();
+        ltm.set(
+            "@bob/home:wifi",
+            "bob's wifi is bob-net",
+            "fact",
+            vec![],
+            1.0,
+        )
+        .await
+        .unwrap();
+        let answer = memory_answer(&ltm, "wifi", &scope).unwrap();
+        assert!(answer.contains("zepto-net"));
+        assert!(!answer.contains("bob-net"));
+    }
+}
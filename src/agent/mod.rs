@@ -54,20 +54,29 @@
 //! }
 //! ```
 
+pub mod admin;
 pub mod budget;
 pub mod compaction;
 mod context;
 pub mod context_monitor;
+pub mod degraded;
 pub mod facade;
 mod r#loop;
 pub mod loop_guard;
+pub mod plan;
+pub mod postprocess;
+pub mod prompts;
+pub mod replay;
 pub mod scratchpad;
+pub mod session_commands;
 pub mod tool_call_limit;
+pub mod translate;
 
 pub use budget::TokenBudget;
 pub use context::{format_message_envelope, ContextBuilder, RuntimeContext};
 pub use context_monitor::{CompactionStrategy, ContextMonitor};
 pub use facade::{ZeptoAgent, ZeptoAgentBuilder};
+pub use postprocess::{CitationFootnotes, ResponseContext, ResponsePostProcessor};
 pub use r#loop::AgentLoop;
 pub use r#loop::{ToolFeedback, ToolFeedbackPhase};
 pub use scratchpad::SwarmScratchpad;
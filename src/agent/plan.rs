@@ -0,0 +1,501 @@
+//! Plan mode — propose tool calls, run them only after approval.
+//!
+//! In plan mode (the agent's dry-run flag, or a `/plan <task>` message) tool
+//! calls are not executed. The loop records each proposed call with its
+//! arguments and a risk level, and replies with an [`ExecutionPlan`] that
+//! includes the tokens and estimated cost of the planning run.
+//!
+//! The plan is stored per session next to the session files, so it can be
+//! approved later from the CLI or any channel that shares the session
+//! (see `session::links`):
+//!
+//! - `approve plan` (or `/plan approve`) re-runs the task for real. Only the
+//!   tools named in the plan may execute.
+//! - `reject plan` (or `/plan reject`) discards it.
+//! - `/plan` shows the pending plan.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::bus::InboundMessage;
+use crate::tools::ToolCategory;
+use crate::utils::cost::estimate_cost;
+
+use super::loop_guard::truncate_utf8;
+
+/// Inbound metadata key that asks for a plan instead of execution.
+pub const PLAN_MODE_KEY: &str = "plan_mode";
+
+/// Inbound metadata key listing the tools an approved plan may run
+/// (comma-separated).
+pub const APPROVED_TOOLS_KEY: &str = "approved_plan_tools";
+
+/// Pending plans older than this are dropped.
+pub const PLAN_TTL_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// File name of the plan store inside the sessions directory.
+const PLANS_FILE: &str = ".pending_plans";
+
+/// Longest argument preview shown per step.
+const MAX_ARGS_PREVIEW: usize = 240;
+
+/// Reply when a plan command finds no pending plan.
+pub(crate) const NO_PLAN: &str = "There is no pending plan for this conversation.";
+
+/// How risky a proposed step is, derived from the tool's category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepRisk {
+    /// Reads data only.
+    Low,
+    /// Changes files, memory, or external state, or sends messages.
+    Medium,
+    /// Runs commands, touches hardware, or is destructive. Unknown tools
+    /// are treated as high risk.
+    High,
+}
+
+impl StepRisk {
+    /// Risk level for a tool category (`None` for an unknown tool).
+    pub fn for_category(category: Option<ToolCategory>) -> Self {
+        match category {
+            Some(
+                ToolCategory::FilesystemRead | ToolCategory::NetworkRead | ToolCategory::Memory,
+            ) => Self::Low,
+            Some(
+                ToolCategory::FilesystemWrite
+                | ToolCategory::NetworkWrite
+                | ToolCategory::Messaging,
+            ) => Self::Medium,
+            Some(ToolCategory::Shell | ToolCategory::Hardware | ToolCategory::Destructive)
+            | None => Self::High,
+        }
+    }
+}
+
+impl std::fmt::Display for StepRisk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Medium => write!(f, "medium"),
+            Self::High => write!(f, "high"),
+        }
+    }
+}
+
+/// One proposed tool call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanStep {
+    pub tool: String,
+    pub args: serde_json::Value,
+    /// Tool category, or `None` if the tool is not registered.
+    pub category: Option<String>,
+    pub risk: StepRisk,
+}
+
+impl PlanStep {
+    /// Step for a tool call with raw JSON `arguments`.
+    pub fn new(tool: &str, arguments: &str, category: Option<ToolCategory>) -> Self {
+        Self {
+            tool: tool.to_string(),
+            args: serde_json::from_str(arguments)
+                .unwrap_or_else(|_| serde_json::Value::String(arguments.to_string())),
+            category: category.map(|c| c.to_string()),
+            risk: StepRisk::for_category(category),
+        }
+    }
+}
+
+/// A proposed sequence of tool calls awaiting approval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionPlan {
+    pub id: String,
+    /// The task the plan carries out.
+    pub task: String,
+    pub steps: Vec<PlanStep>,
+    pub model: String,
+    /// Tokens used by the planning run, as an estimate for execution.
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Estimated cost in USD, if pricing for the model is known.
+    pub cost_usd: Option<f64>,
+    pub created_at: i64,
+}
+
+impl ExecutionPlan {
+    /// Plan for `task`, costed from the planning run's token usage.
+    pub fn new(
+        task: &str,
+        steps: Vec<PlanStep>,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+    ) -> Self {
+        let cost_usd = if input_tokens + output_tokens > 0 {
+            estimate_cost(
+                model,
+                u32::try_from(input_tokens).unwrap_or(u32::MAX),
+                u32::try_from(output_tokens).unwrap_or(u32::MAX),
+                &HashMap::new(),
+            )
+        } else {
+            None
+        };
+        Self {
+            id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            task: task.to_string(),
+            steps,
+            model: model.to_string(),
+            input_tokens,
+            output_tokens,
+            cost_usd,
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// Highest risk among the steps.
+    pub fn risk(&self) -> StepRisk {
+        self.steps
+            .iter()
+            .map(|s| s.risk)
+            .max()
+            .unwrap_or(StepRisk::Low)
+    }
+
+    /// Distinct tools the plan uses.
+    pub fn tools(&self) -> HashSet<&str> {
+        self.steps.iter().map(|s| s.tool.as_str()).collect()
+    }
+
+    /// Human-readable plan with approval instructions.
+    pub fn render(&self) -> String {
+        let mut text = format!(
+            "Plan {} ({} step{}, {} risk) — nothing has been executed yet.\n\nTask: {}\n",
+            self.id,
+            self.steps.len(),
+            if self.steps.len() == 1 { "" } else { "s" },
+            self.risk(),
+            self.task.trim()
+        );
+        for (i, step) in self.steps.iter().enumerate() {
+            let args = step.args.to_string();
+            let preview = truncate_utf8(&args, MAX_ARGS_PREVIEW);
+            let ellipsis = if preview.len() < args.len() {
+                "…"
+            } else {
+                ""
+            };
+            text.push_str(&format!(
+                "\n{}. {} [{} risk{}]\n   {}{}",
+                i + 1,
+                step.tool,
+                step.risk,
+                step.category
+                    .as_deref()
+                    .map(|c| format!(", {}", c))
+                    .unwrap_or_default(),
+                preview,
+                ellipsis
+            ));
+        }
+        let cost = self
+            .cost_usd
+            .map(|c| format!(", ~${:.4}", c))
+            .unwrap_or_default();
+        text.push_str(&format!(
+            "\n\nEstimated usage: ~{} tokens ({} in / {} out){} on {}, based on the planning run.\n\
+             Reply \"approve plan\" to run it, or \"reject plan\" to discard it.",
+            self.input_tokens + self.output_tokens,
+            self.input_tokens,
+            self.output_tokens,
+            cost,
+            self.model
+        ));
+        text
+    }
+
+    /// A copy of `msg` that carries out this plan for real.
+    pub fn approved_message(&self, msg: &InboundMessage) -> InboundMessage {
+        let steps: Vec<String> = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("{}. {} {}", i + 1, s.tool, s.args))
+            .collect();
+        let mut tools: Vec<&str> = self.tools().into_iter().collect();
+        tools.sort_unstable();
+
+        let mut approved = msg.clone();
+        approved.content = format!(
+            "{}\n\n[The user approved plan {}. Carry it out now, adapting arguments to real results where needed:\n{}]",
+            self.task.trim(),
+            self.id,
+            steps.join("\n")
+        );
+        approved.metadata.remove(PLAN_MODE_KEY);
+        approved
+            .metadata
+            .insert(APPROVED_TOOLS_KEY.to_string(), tools.join(","));
+        approved
+    }
+}
+
+/// Whether `msg` asks for a plan rather than execution.
+pub fn is_plan_request(msg: &InboundMessage) -> bool {
+    msg.metadata.get(PLAN_MODE_KEY).is_some_and(|v| v == "true")
+}
+
+/// Tools an approved plan may run, or `None` if `msg` is not an approved plan.
+pub fn approved_tools(msg: &InboundMessage) -> Option<HashSet<String>> {
+    msg.metadata.get(APPROVED_TOOLS_KEY).map(|tools| {
+        tools
+            .split(',')
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+}
+
+/// A plan command parsed from a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanCommand {
+    /// `/plan <task>` — plan the task without executing it.
+    Create(String),
+    /// `/plan` — show the pending plan.
+    Show,
+    /// `approve plan` or `/plan approve`.
+    Approve,
+    /// `reject plan` or `/plan reject`.
+    Reject,
+}
+
+impl PlanCommand {
+    /// Parse a plan command, or `None` for any other message.
+    pub fn parse(content: &str) -> Option<Self> {
+        let text = content.trim();
+        let lower = text.to_ascii_lowercase();
+        match lower.trim_end_matches(['.', '!']) {
+            "approve plan" | "/plan approve" => return Some(Self::Approve),
+            "reject plan" | "/plan reject" => return Some(Self::Reject),
+            "/plan" => return Some(Self::Show),
+            _ => {}
+        }
+        let rest = text.strip_prefix("/plan")?;
+        rest.starts_with(char::is_whitespace)
+            .then(|| Self::Create(rest.trim().to_string()))
+    }
+
+    /// `msg` rewritten as a planning request for `task`.
+    pub fn planning_message(msg: &InboundMessage, task: &str) -> InboundMessage {
+        let mut planned = msg.clone();
+        planned.content = task.to_string();
+        planned
+            .metadata
+            .insert(PLAN_MODE_KEY.to_string(), "true".to_string());
+        planned
+    }
+}
+
+/// Pending plans, one per session, shared by clones.
+#[derive(Debug, Clone)]
+pub struct PlanStore {
+    path: Option<PathBuf>,
+    state: Arc<Mutex<HashMap<String, ExecutionPlan>>>,
+}
+
+impl PlanStore {
+    /// Plan store in `sessions_dir`, or in memory only when `None`.
+    pub fn new(sessions_dir: Option<PathBuf>) -> Self {
+        Self {
+            path: sessions_dir.map(|dir| dir.join(PLANS_FILE)),
+            state: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Run `f` against the current plans, re-reading them from disk first
+    /// and saving them afterwards when `f` reports a change.
+    fn with_state<R>(&self, f: impl FnOnce(&mut HashMap<String, ExecutionPlan>) -> (R, bool)) -> R {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(path) = &self.path {
+            *state = std::fs::read_to_string(path)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default();
+        }
+        let cutoff = chrono::Utc::now().timestamp() - PLAN_TTL_SECS;
+        let before = state.len();
+        state.retain(|_, plan| plan.created_at > cutoff);
+        let (result, changed) = f(&mut state);
+        if changed || state.len() != before {
+            if let Some(path) = &self.path {
+                let saved = serde_json::to_string_pretty(&*state)
+                    .map_err(std::io::Error::other)
+                    .and_then(|json| std::fs::write(path, json));
+                if let Err(e) = saved {
+                    warn!(error = %e, "Failed to save pending plans");
+                }
+            }
+        }
+        result
+    }
+
+    /// Store `plan` as the pending plan for `session_key`, replacing any
+    /// earlier one.
+    pub fn save(&self, session_key: &str, plan: ExecutionPlan) {
+        self.with_state(|state| {
+            state.insert(session_key.to_string(), plan);
+            ((), true)
+        })
+    }
+
+    /// The pending plan for `session_key`.
+    pub fn get(&self, session_key: &str) -> Option<ExecutionPlan> {
+        self.with_state(|state| (state.get(session_key).cloned(), false))
+    }
+
+    /// Remove and return the pending plan for `session_key`.
+    pub fn take(&self, session_key: &str) -> Option<ExecutionPlan> {
+        self.with_state(|state| {
+            let plan = state.remove(session_key);
+            let changed = plan.is_some();
+            (plan, changed)
+        })
+    }
+
+    /// Reply for a `/plan`, `reject plan` command, or `None` for commands
+    /// the agent loop has to run (`Create`, and `Approve` with a plan).
+    pub fn reply_for(&self, session_key: &str, command: &PlanCommand) -> Option<String> {
+        match command {
+            PlanCommand::Show => Some(
+                self.get(session_key)
+                    .map(|plan| plan.render())
+                    .unwrap_or_else(|| NO_PLAN.to_string()),
+            ),
+            PlanCommand::Reject => Some(match self.take(session_key) {
+                Some(plan) => format!("Plan {} discarded.", plan.id),
+                None => NO_PLAN.to_string(),
+            }),
+            PlanCommand::Approve if self.get(session_key).is_none() => Some(NO_PLAN.to_string()),
+            PlanCommand::Approve | PlanCommand::Create(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_plan() -> ExecutionPlan {
+        ExecutionPlan::new(
+            "Tidy the downloads folder",
+            vec![
+                PlanStep::new(
+                    "list_dir",
+                    r#"{"path":"~/Downloads"}"#,
+                    Some(ToolCategory::FilesystemRead),
+                ),
+                PlanStep::new(
+                    "shell",
+                    r#"{"command":"rm ~/Downloads/*.tmp"}"#,
+                    Some(ToolCategory::Shell),
+                ),
+            ],
+            "gpt-4o",
+            1200,
+            300,
+        )
+    }
+
+    #[test]
+    fn test_parse_plan_commands() {
+        assert_eq!(
+            PlanCommand::parse("approve plan"),
+            Some(PlanCommand::Approve)
+        );
+        assert_eq!(
+            PlanCommand::parse(" Approve plan. "),
+            Some(PlanCommand::Approve)
+        );
+        assert_eq!(
+            PlanCommand::parse("/plan reject"),
+            Some(PlanCommand::Reject)
+        );
+        assert_eq!(PlanCommand::parse("/plan"), Some(PlanCommand::Show));
+        assert_eq!(
+            PlanCommand::parse("/plan Clean up old logs"),
+            Some(PlanCommand::Create("Clean up old logs".to_string()))
+        );
+        assert_eq!(PlanCommand::parse("/planet"), None);
+        assert_eq!(PlanCommand::parse("please approve plan B"), None);
+    }
+
+    #[test]
+    fn test_step_risk_from_category() {
+        assert_eq!(
+            StepRisk::for_category(Some(ToolCategory::NetworkRead)),
+            StepRisk::Low
+        );
+        assert_eq!(
+            StepRisk::for_category(Some(ToolCategory::Messaging)),
+            StepRisk::Medium
+        );
+        assert_eq!(StepRisk::for_category(None), StepRisk::High);
+    }
+
+    #[test]
+    fn test_render_lists_steps_risk_and_cost() {
+        let plan = sample_plan();
+        assert_eq!(plan.risk(), StepRisk::High);
+        let text = plan.render();
+        assert!(text.contains("2 steps, high risk"));
+        assert!(text.contains("1. list_dir [low risk, filesystem_read]"));
+        assert!(text.contains(r#"{"command":"rm ~/Downloads/*.tmp"}"#));
+        assert!(text.contains("~1500 tokens (1200 in / 300 out)"));
+        assert!(text.contains("approve plan"));
+    }
+
+    #[test]
+    fn test_approved_message_restricts_tools() {
+        let plan = sample_plan();
+        let mut msg = InboundMessage::new("telegram", "u1", "42", "approve plan");
+        msg.metadata
+            .insert(PLAN_MODE_KEY.to_string(), "true".to_string());
+        let approved = plan.approved_message(&msg);
+        assert!(approved.content.starts_with("Tidy the downloads folder"));
+        assert!(!is_plan_request(&approved));
+        let tools = approved_tools(&approved).unwrap();
+        assert_eq!(tools.len(), 2);
+        assert!(tools.contains("shell"));
+        assert!(approved_tools(&msg).is_none());
+    }
+
+    #[test]
+    fn test_store_persists_and_takes_plans() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PlanStore::new(Some(dir.path().to_path_buf()));
+        assert_eq!(
+            store.reply_for("cli:cli", &PlanCommand::Approve).as_deref(),
+            Some(NO_PLAN)
+        );
+
+        let plan = sample_plan();
+        let id = plan.id.clone();
+        store.save("cli:cli", plan);
+
+        // Another process (e.g. the gateway) sees the same plan.
+        let other = PlanStore::new(Some(dir.path().to_path_buf()));
+        assert_eq!(other.get("cli:cli").unwrap().id, id);
+        assert!(other.reply_for("cli:cli", &PlanCommand::Approve).is_none());
+        assert!(other.take("cli:cli").is_some());
+        assert!(store.get("cli:cli").is_none());
+
+        store.save("cli:cli", sample_plan());
+        let reply = store.reply_for("cli:cli", &PlanCommand::Reject).unwrap();
+        assert!(reply.ends_with("discarded."));
+        assert!(store.get("cli:cli").is_none());
+    }
+}
@@ -0,0 +1,162 @@
+//! Response post-processing.
+//!
+//! Post-processors run on the final response of a turn, before `after_response`
+//! hooks and before it is saved to the session. The built-in
+//! [`CitationFootnotes`] appends a numbered source list built from the
+//! citations tools attached to their output during the turn (`web_fetch`
+//! pages, `memory_search` snippets). The list is CommonMark, so each channel
+//! renders links in its own dialect.
+//!
+//! # Example
+//!
+//! ```
+//! use zeptoclaw::agent::postprocess::{
+//!     CitationFootnotes, ResponseContext, ResponsePostProcessor,
+//! };
+//! use zeptoclaw::config::MemoryCitationsMode;
+//! use zeptoclaw::tools::Citation;
+//!
+//! let citations = vec![Citation::web("https://docs.rs", Some("Docs.rs".to_string()))];
+//! let ctx = ResponseContext { channel: "telegram", citations: &citations };
+//! let out = CitationFootnotes::new(MemoryCitationsMode::Auto)
+//!     .process("Answer.".to_string(), &ctx);
+//! assert_eq!(out, "Answer.\n\n**Sources**\n1. [Docs.rs](<https://docs.rs>)");
+//! ```
+
+use crate::config::MemoryCitationsMode;
+use crate::tools::{Citation, CitationKind};
+
+/// What a post-processor knows about the turn.
+pub struct ResponseContext<'a> {
+    /// Channel the response goes to (`cli`, `telegram`, ...).
+    pub channel: &'a str,
+    /// Citations collected from tool output during the turn, in call order.
+    pub citations: &'a [Citation],
+}
+
+/// Rewrites the final response of a turn.
+pub trait ResponsePostProcessor: Send + Sync {
+    /// Name used in logs.
+    fn name(&self) -> &str;
+
+    /// Return the processed response.
+    fn process(&self, response: String, ctx: &ResponseContext<'_>) -> String;
+}
+
+/// Appends a numbered source list for the turn's citations.
+///
+/// Follows `memory.citations`: `off` adds nothing, `on` lists every source,
+/// and `auto` lists web sources everywhere but memory snippets only on the
+/// CLI (the same rule `memory_search` uses for inline citations).
+pub struct CitationFootnotes {
+    mode: MemoryCitationsMode,
+}
+
+impl CitationFootnotes {
+    /// Create the post-processor for a citation mode.
+    pub fn new(mode: MemoryCitationsMode) -> Self {
+        Self { mode }
+    }
+
+    fn includes(&self, citation: &Citation, channel: &str) -> bool {
+        match (&self.mode, citation.kind) {
+            (MemoryCitationsMode::Off, _) => false,
+            (MemoryCitationsMode::On, _) | (MemoryCitationsMode::Auto, CitationKind::Web) => true,
+            (MemoryCitationsMode::Auto, CitationKind::Memory) => channel == "cli",
+        }
+    }
+}
+
+impl ResponsePostProcessor for CitationFootnotes {
+    fn name(&self) -> &str {
+        "citations"
+    }
+
+    fn process(&self, response: String, ctx: &ResponseContext<'_>) -> String {
+        if response.trim().is_empty() {
+            return response;
+        }
+        let mut seen = std::collections::HashSet::new();
+        let entries: Vec<String> = ctx
+            .citations
+            .iter()
+            .filter(|c| self.includes(c, ctx.channel))
+            .filter(|c| seen.insert(c.source.as_str()))
+            .map(format_entry)
+            .collect();
+        if entries.is_empty() {
+            return response;
+        }
+
+        let mut out = response.trim_end().to_string();
+        out.push_str("\n\n**Sources**");
+        for (index, entry) in entries.iter().enumerate() {
+            out.push_str(&format!("\n{}. {}", index + 1, entry));
+        }
+        out
+    }
+}
+
+fn format_entry(citation: &Citation) -> String {
+    match (citation.kind, citation.title.as_deref()) {
+        (CitationKind::Web, Some(title)) => format!(
+            "[{}](<{}>)",
+            title.replace('[', "\\[").replace(']', "\\]"),
+            citation.source
+        ),
+        (CitationKind::Web, None) => format!("<{}>", citation.source),
+        (CitationKind::Memory, _) => format!("`{}`", citation.source),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn citations() -> Vec<Citation> {
+        vec![
+            Citation::web("https://a.example/x", Some("A [draft]".to_string())),
+            Citation::memory("memory/notes.md#L3-L8"),
+            Citation::web("https://b.example", None),
+            Citation::web("https://a.example/x", None),
+        ]
+    }
+
+    fn run(mode: MemoryCitationsMode, channel: &str, response: &str) -> String {
+        let citations = citations();
+        let ctx = ResponseContext {
+            channel,
+            citations: &citations,
+        };
+        CitationFootnotes::new(mode).process(response.to_string(), &ctx)
+    }
+
+    #[test]
+    fn test_citation_footnotes_modes() {
+        assert_eq!(
+            run(MemoryCitationsMode::On, "telegram", "Done.\n"),
+            "Done.\n\n**Sources**\n1. [A \\[draft\\]](<https://a.example/x>)\n\
+             2. `memory/notes.md#L3-L8`\n3. <https://b.example>"
+        );
+        assert_eq!(
+            run(MemoryCitationsMode::Auto, "telegram", "Done."),
+            "Done.\n\n**Sources**\n1. [A \\[draft\\]](<https://a.example/x>)\n\
+             2. <https://b.example>"
+        );
+        assert!(run(MemoryCitationsMode::Auto, "cli", "Done.").contains("2. `memory/notes.md"));
+        assert_eq!(run(MemoryCitationsMode::Off, "cli", "Done."), "Done.");
+    }
+
+    #[test]
+    fn test_citation_footnotes_skips_empty() {
+        assert_eq!(run(MemoryCitationsMode::On, "cli", "  "), "  ");
+        let ctx = ResponseContext {
+            channel: "cli",
+            citations: &[],
+        };
+        assert_eq!(
+            CitationFootnotes::new(MemoryCitationsMode::On).process("Hi".to_string(), &ctx),
+            "Hi"
+        );
+    }
+}
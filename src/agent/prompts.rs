@@ -0,0 +1,284 @@
+//! System-prompt templates with variables and per-channel/per-agent overrides.
+//!
+//! Templates live in `~/.zeptoclaw/prompts/`:
+//!
+//! ```text
+//! prompts/
+//! ├── system.md              # replaces the built-in system prompt
+//! ├── channels/telegram.md   # used for messages arriving on telegram
+//! └── agents/researcher.md   # used when running the `researcher` agent template
+//! ```
+//!
+//! Resolution order for a message: channel override, then agent override,
+//! then the builder's base prompt (`system.md`, config, or built-in).
+//!
+//! Templates use Handlebars-style `{{variable}}` placeholders. Supported
+//! variables: `date`, `time`, `weekday`, `channel`, `chat_id`, `user_id`,
+//! `user_name`, `workspace`, `agent`, `model`. Unknown variables render as
+//! an empty string; `\{{` produces a literal `{{`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use tracing::{debug, warn};
+
+use crate::bus::InboundMessage;
+use crate::config::Config;
+
+/// Base template file name inside the prompts directory.
+const SYSTEM_TEMPLATE_FILE: &str = "system.md";
+
+/// Values substituted into prompt templates.
+#[derive(Debug, Clone, Default)]
+pub struct PromptVars {
+    vars: HashMap<String, String>,
+}
+
+impl PromptVars {
+    /// Create an empty variable set with `date`, `time` and `weekday` filled
+    /// from the local clock.
+    pub fn new() -> Self {
+        let now = Local::now();
+        Self::default()
+            .with("date", &now.format("%Y-%m-%d").to_string())
+            .with("time", &now.format("%H:%M").to_string())
+            .with("weekday", &now.format("%A").to_string())
+    }
+
+    /// Build variables for an inbound message.
+    ///
+    /// `user_name` comes from `sender_name`/`user_name` metadata set by the
+    /// channel, falling back to the sender ID.
+    pub fn for_message(msg: &InboundMessage, config: &Config, model: &str) -> Self {
+        let user_name = msg
+            .metadata
+            .get("sender_name")
+            .or_else(|| msg.metadata.get("user_name"))
+            .filter(|v| !v.is_empty())
+            .cloned()
+            .unwrap_or_else(|| msg.sender_id.clone());
+        Self::new()
+            .with("channel", &msg.channel)
+            .with("chat_id", &msg.chat_id)
+            .with("user_id", &msg.sender_id)
+            .with("user_name", &user_name)
+            .with("workspace", &config.workspace_path().to_string_lossy())
+            .with("model", model)
+    }
+
+    /// Set a variable.
+    pub fn with(mut self, key: &str, value: &str) -> Self {
+        self.vars.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Look up a variable.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.vars.get(key).map(String::as_str)
+    }
+}
+
+/// Render `{{variable}}` placeholders in `template`.
+///
+/// Whitespace inside the braces is ignored. Unterminated placeholders are
+/// emitted verbatim.
+pub fn render(template: &str, vars: &PromptVars) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find("{{") {
+        if rest[..pos].ends_with('\\') {
+            out.push_str(&rest[..pos - 1]);
+            out.push_str("{{");
+            rest = &rest[pos + 2..];
+            continue;
+        }
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let name = after[..end].trim();
+                out.push_str(vars.get(name).unwrap_or(""));
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[pos..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Prompt templates loaded from a prompts directory.
+#[derive(Debug, Clone, Default)]
+pub struct PromptTemplates {
+    system: Option<String>,
+    channels: HashMap<String, String>,
+    agents: HashMap<String, String>,
+    /// Active agent name, used to pick an `agents/<name>.md` override.
+    agent: Option<String>,
+}
+
+impl PromptTemplates {
+    /// Default prompts directory: `~/.zeptoclaw/prompts`.
+    pub fn default_dir() -> PathBuf {
+        Config::dir().join("prompts")
+    }
+
+    /// Load templates from `dir`. A missing directory yields an empty set.
+    pub fn load(dir: &Path) -> Self {
+        let mut templates = Self {
+            system: read_template(&dir.join(SYSTEM_TEMPLATE_FILE)),
+            ..Self::default()
+        };
+        templates.channels = read_template_dir(&dir.join("channels"));
+        templates.agents = read_template_dir(&dir.join("agents"));
+        debug!(
+            dir = %dir.display(),
+            system = templates.system.is_some(),
+            channels = templates.channels.len(),
+            agents = templates.agents.len(),
+            "Loaded prompt templates"
+        );
+        templates
+    }
+
+    /// Set the active agent name for per-agent overrides.
+    pub fn with_agent(mut self, agent: &str) -> Self {
+        self.agent = Some(agent.to_string());
+        self
+    }
+
+    /// Active agent name, if any.
+    pub fn agent(&self) -> Option<&str> {
+        self.agent.as_deref()
+    }
+
+    /// The `system.md` base template, if present.
+    pub fn system(&self) -> Option<&str> {
+        self.system.as_deref()
+    }
+
+    /// Resolve the override template for a channel: channel file first, then
+    /// the active agent's file.
+    pub fn resolve(&self, channel: Option<&str>) -> Option<&str> {
+        channel
+            .and_then(|c| self.channels.get(c))
+            .or_else(|| self.agent.as_ref().and_then(|a| self.agents.get(a)))
+            .map(String::as_str)
+    }
+
+    /// Whether no templates were loaded.
+    pub fn is_empty(&self) -> bool {
+        self.system.is_none() && self.channels.is_empty() && self.agents.is_empty()
+    }
+}
+
+fn read_template(path: &Path) -> Option<String> {
+    if !path.is_file() {
+        return None;
+    }
+    match std::fs::read_to_string(path) {
+        Ok(content) => Some(content.trim().to_string()).filter(|c| !c.is_empty()),
+        Err(e) => {
+            warn!("Failed to read prompt template {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Read every `*.md` file in `dir`, keyed by file stem.
+fn read_template_dir(dir: &Path) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return map;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if let Some(content) = read_template(&path) {
+            map.insert(stem.to_string(), content);
+        }
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_substitutes_variables() {
+        let vars = PromptVars::default()
+            .with("channel", "telegram")
+            .with("user_name", "Sam");
+        assert_eq!(
+            render("Hi {{user_name}} on {{ channel }}!", &vars),
+            "Hi Sam on telegram!"
+        );
+    }
+
+    #[test]
+    fn test_render_unknown_and_escaped() {
+        let vars = PromptVars::default();
+        assert_eq!(render("a{{missing}}b", &vars), "ab");
+        assert_eq!(render(r"literal \{{x}}", &vars), "literal {{x}}");
+        assert_eq!(render("open {{never", &vars), "open {{never");
+        assert_eq!(render("no vars", &vars), "no vars");
+    }
+
+    #[test]
+    fn test_prompt_vars_new_has_date() {
+        let vars = PromptVars::new();
+        assert_eq!(vars.get("date").unwrap().len(), 10);
+        assert!(vars.get("weekday").is_some());
+    }
+
+    #[test]
+    fn test_for_message_user_name_fallback() {
+        let config = Config::default();
+        let msg = InboundMessage::new("discord", "u42", "c1", "hi");
+        let vars = PromptVars::for_message(&msg, &config, "gpt-4o");
+        assert_eq!(vars.get("user_name"), Some("u42"));
+        assert_eq!(vars.get("model"), Some("gpt-4o"));
+
+        let msg = msg.with_metadata("sender_name", "Alex");
+        let vars = PromptVars::for_message(&msg, &config, "gpt-4o");
+        assert_eq!(vars.get("user_name"), Some("Alex"));
+    }
+
+    #[test]
+    fn test_load_and_resolve_overrides() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(dir.join("system.md"), "Base for {{user_name}}").unwrap();
+        std::fs::create_dir_all(dir.join("channels")).unwrap();
+        std::fs::create_dir_all(dir.join("agents")).unwrap();
+        std::fs::write(dir.join("channels/telegram.md"), "Telegram prompt").unwrap();
+        std::fs::write(dir.join("agents/researcher.md"), "Researcher prompt").unwrap();
+        std::fs::write(dir.join("channels/notes.txt"), "ignored").unwrap();
+
+        let templates = PromptTemplates::load(dir).with_agent("researcher");
+        assert_eq!(templates.system(), Some("Base for {{user_name}}"));
+        assert_eq!(templates.resolve(Some("telegram")), Some("Telegram prompt"));
+        assert_eq!(templates.resolve(Some("slack")), Some("Researcher prompt"));
+        assert_eq!(templates.resolve(None), Some("Researcher prompt"));
+
+        let no_agent = PromptTemplates::load(dir);
+        assert_eq!(no_agent.resolve(Some("slack")), None);
+    }
+
+    #[test]
+    fn test_load_missing_dir_is_empty() {
+        let templates = PromptTemplates::load(Path::new("/nonexistent/zeptoclaw/prompts"));
+        assert!(templates.is_empty());
+        assert_eq!(templates.resolve(Some("telegram")), None);
+    }
+}
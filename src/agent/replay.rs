@@ -0,0 +1,317 @@
+//! Deterministic replay of recorded turns.
+//!
+//! With `session.ledger` enabled, every LLM response of a turn is stored in
+//! the turn ledger. [`replay_turn`] re-runs such a turn through a real
+//! [`AgentLoop`] whose provider is a [`ReplayProvider`] serving those
+//! responses in order, so tool calls, approvals and safety-layer decisions
+//! happen again without a network call. The report lists where the replayed
+//! turn diverged from the recording, which makes recordings usable as
+//! regression tests (`zeptoclaw history replay <key> --check`).
+//!
+//! The replay runs in its own `replay:<key>` session, which starts empty:
+//! the conversation history before the recorded turn is not restored.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use crate::bus::InboundMessage;
+use crate::error::{Result, ZeptoError};
+use crate::providers::ReplayProvider;
+use crate::session::ledger::{args_hash, LedgerEvent, LedgerTurn};
+use crate::session::{Message, Role};
+
+use super::{AgentLoop, ToolFeedbackPhase};
+
+/// A tool call seen while recording or replaying.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReplayToolCall {
+    pub name: String,
+    /// SHA-256 (hex) of the raw JSON arguments.
+    pub args_sha256: String,
+    pub ok: bool,
+    /// Error text for failed calls (replay only).
+    pub error: Option<String>,
+}
+
+/// Result of replaying one recorded turn.
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    /// Session the recorded turn belongs to.
+    pub session_key: String,
+    /// Final response of the replay; `Err` holds the error text.
+    pub response: std::result::Result<String, String>,
+    /// Tool calls of the recording, in order.
+    pub recorded_tools: Vec<ReplayToolCall>,
+    /// Tool calls of the replay, in completion order.
+    pub replayed_tools: Vec<ReplayToolCall>,
+    /// Tool results the model was shown during the replay, as
+    /// `(tool, result)`. Blocked and unapproved calls only show up here.
+    pub tool_results: Vec<(String, String)>,
+    /// Recorded LLM responses the replay did not use.
+    pub unused_responses: usize,
+    /// Differences between the recording and the replay.
+    pub divergences: Vec<String>,
+}
+
+impl ReplayReport {
+    /// Whether the replay reproduced the recording.
+    pub fn is_match(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Human-readable summary.
+    pub fn render(&self) -> String {
+        let mut out = format!("Replay of {}\n", self.session_key);
+        for call in &self.replayed_tools {
+            let _ = write!(
+                out,
+                "  tool {} ({}) {}",
+                call.name,
+                &call.args_sha256[..call.args_sha256.len().min(12)],
+                if call.ok { "ok" } else { "FAILED" }
+            );
+            if let Some(error) = &call.error {
+                let _ = write!(out, ": {}", error.lines().next().unwrap_or_default());
+            }
+            out.push('\n');
+        }
+        for (name, result) in &self.tool_results {
+            let _ = writeln!(
+                out,
+                "  result {}: {}",
+                name,
+                result.lines().next().unwrap_or_default()
+            );
+        }
+        match &self.response {
+            Ok(response) => {
+                let _ = writeln!(
+                    out,
+                    "  response: {}",
+                    response.lines().next().unwrap_or_default()
+                );
+            }
+            Err(e) => {
+                let _ = writeln!(out, "  error: {}", e);
+            }
+        }
+        if self.divergences.is_empty() {
+            out.push_str("Matches the recording.\n");
+        } else {
+            out.push_str("Diverges from the recording:\n");
+            for divergence in &self.divergences {
+                let _ = writeln!(out, "  - {}", divergence);
+            }
+        }
+        out
+    }
+}
+
+/// Re-run `turn` on `agent` with its recorded LLM responses.
+///
+/// Replaces the agent's provider and tool feedback sender, so use an agent
+/// dedicated to the replay.
+pub async fn replay_turn(agent: &AgentLoop, turn: &LedgerTurn) -> Result<ReplayReport> {
+    let responses = turn.llm_responses();
+    if responses.is_empty() {
+        return Err(ZeptoError::Session(format!(
+            "Turn of {} has no recorded LLM responses; enable session.ledger and record it again",
+            turn.session_key
+        )));
+    }
+
+    let model = turn.model.clone().unwrap_or_else(|| "replay".to_string());
+    let provider = std::sync::Arc::new(ReplayProvider::new(&model, responses));
+    agent.set_provider_arc(provider.clone()).await;
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    agent.set_tool_feedback(tx).await;
+
+    let mut msg = InboundMessage::new(&turn.channel, &turn.sender_id, &turn.chat_id, &turn.inbound);
+    msg.session_key = format!("replay:{}", turn.session_key);
+    let response = agent.process_message(&msg).await.map_err(|e| e.to_string());
+    let _ = agent.session_manager().delete(&msg.session_key).await;
+
+    let mut replayed_tools = Vec::new();
+    while let Ok(feedback) = rx.try_recv() {
+        let (ok, error) = match feedback.phase {
+            ToolFeedbackPhase::Done { .. } => (true, None),
+            ToolFeedbackPhase::Failed { error, .. } => (false, Some(error)),
+            _ => continue,
+        };
+        replayed_tools.push(ReplayToolCall {
+            name: feedback.tool_name,
+            args_sha256: args_hash(feedback.args_json.as_deref().unwrap_or_default()),
+            ok,
+            error,
+        });
+    }
+    let recorded_tools: Vec<ReplayToolCall> = turn
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            LedgerEvent::ToolCall {
+                name,
+                args_sha256,
+                ok,
+                ..
+            } => Some(ReplayToolCall {
+                name: name.clone(),
+                args_sha256: args_sha256.clone(),
+                ok: *ok,
+                error: None,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    let mut report = ReplayReport {
+        session_key: turn.session_key.clone(),
+        response,
+        recorded_tools,
+        replayed_tools,
+        tool_results: tool_results(&provider.calls()),
+        unused_responses: provider.remaining(),
+        divergences: Vec::new(),
+    };
+    report.divergences = divergences(turn, &report);
+    Ok(report)
+}
+
+/// Tool results in the messages of the last provider call.
+fn tool_results(calls: &[Vec<Message>]) -> Vec<(String, String)> {
+    let Some(messages) = calls.last() else {
+        return Vec::new();
+    };
+    let names: HashMap<&str, &str> = messages
+        .iter()
+        .filter_map(|m| m.tool_calls.as_ref())
+        .flatten()
+        .map(|call| (call.id.as_str(), call.name.as_str()))
+        .collect();
+    messages
+        .iter()
+        .filter(|m| m.role == Role::Tool)
+        .map(|m| {
+            let id = m.tool_call_id.as_deref().unwrap_or_default();
+            let name = names.get(id).copied().unwrap_or(id);
+            (name.to_string(), m.content.clone())
+        })
+        .collect()
+}
+
+fn divergences(turn: &LedgerTurn, report: &ReplayReport) -> Vec<String> {
+    let mut out = Vec::new();
+    // Parallel tool calls finish in any order, so compare them as sets.
+    let key = |c: &ReplayToolCall| (c.name.clone(), c.args_sha256.clone(), c.ok);
+    let mut recorded: Vec<_> = report.recorded_tools.iter().map(key).collect();
+    let mut replayed: Vec<_> = report.replayed_tools.iter().map(key).collect();
+    recorded.sort();
+    replayed.sort();
+    if recorded != replayed {
+        for (name, _, ok) in recorded.iter().filter(|c| !replayed.contains(c)) {
+            out.push(format!(
+                "recorded {} call to {} did not happen",
+                if *ok { "successful" } else { "failed" },
+                name
+            ));
+        }
+        for (name, _, ok) in replayed.iter().filter(|c| !recorded.contains(c)) {
+            out.push(format!(
+                "{} call to {} was not recorded",
+                if *ok { "successful" } else { "failed" },
+                name
+            ));
+        }
+    }
+    match (&report.response, &turn.response) {
+        (Err(e), _) => out.push(format!("replay failed: {}", e)),
+        (Ok(replayed), Some(recorded)) if replayed != recorded => {
+            out.push("final response differs".to_string())
+        }
+        (Ok(_), None) => out.push("recorded turn has no final response".to_string()),
+        _ => {}
+    }
+    if report.unused_responses > 0 {
+        out.push(format!(
+            "{} recorded LLM response(s) not used",
+            report.unused_responses
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::MessageBus;
+    use crate::config::Config;
+    use crate::providers::LLMToolCall;
+    use crate::session::ledger::{LedgerRecorder, TurnOutcome};
+    use crate::session::SessionManager;
+    use std::sync::Arc;
+
+    fn recorded_turn(echo_args: &str, response: &str) -> LedgerTurn {
+        let msg = InboundMessage::new("cli", "user", "cli", "say hi");
+        let ledger = LedgerRecorder::start(&msg);
+        let call = LLMToolCall::new("call_1", "echo", echo_args);
+        ledger.record_llm_response("", std::slice::from_ref(&call));
+        ledger.record_tool_call("echo", echo_args, 1, true);
+        ledger.record_llm_response("hi", &[]);
+        ledger.finish(Some(response), TurnOutcome::Completed)
+    }
+
+    fn agent() -> AgentLoop {
+        let mut config = Config::default();
+        // Echo is not read-only, so assistant mode would hold it for approval.
+        config.agent_mode.mode = "autonomous".into();
+        AgentLoop::new(
+            config,
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_replay_matches_recording() {
+        let agent = agent();
+        agent.register_tool(Box::new(crate::tools::EchoTool)).await;
+        let args = r#"{"message":"hi"}"#;
+        let report = replay_turn(&agent, &recorded_turn(args, "hi"))
+            .await
+            .unwrap();
+        assert!(report.is_match(), "{}", report.render());
+        assert_eq!(report.replayed_tools.len(), 1);
+        assert_eq!(report.replayed_tools[0].args_sha256, args_hash(args));
+        assert_eq!(report.tool_results.len(), 1);
+        assert_eq!(report.tool_results[0].0, "echo");
+        assert!(report.tool_results[0].1.contains("hi"));
+        assert!(report.render().contains("Matches the recording."));
+        assert!(agent
+            .session_manager()
+            .get("replay:cli:cli")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_replay_reports_divergence() {
+        let agent = agent();
+        // No echo tool registered: the replayed call fails.
+        let report = replay_turn(&agent, &recorded_turn("{}", "hello"))
+            .await
+            .unwrap();
+        assert!(!report.is_match());
+        assert!(report
+            .divergences
+            .iter()
+            .any(|d| d == "recorded successful call to echo did not happen"));
+        assert!(report
+            .divergences
+            .contains(&"final response differs".to_string()));
+
+        let empty = LedgerRecorder::start(&InboundMessage::new("cli", "u", "cli", "x"))
+            .finish(None, TurnOutcome::Cancelled);
+        assert!(replay_turn(&agent, &empty).await.is_err());
+    }
+}
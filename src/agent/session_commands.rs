@@ -0,0 +1,199 @@
+//! Conversation commands available on every channel.
+//!
+//! - `/new` — start over: clears the chat's history and summary (pins stay)
+//! - `/pin <text>` — pin a note into the session's system prompt; it
+//!   survives compaction and `/new`
+//! - `/pin` — list pins, `/unpin <n>` / `/unpin all` — remove them
+//! - `/context` — show what the system prompt currently carries: skills,
+//!   injected memory, pins and history size
+//!
+//! The agent loop answers these itself; they never reach the LLM.
+
+/// Pins kept per session.
+pub const MAX_PINS: usize = 20;
+
+/// Characters kept per pin.
+pub const MAX_PIN_CHARS: usize = 500;
+
+/// A parsed conversation command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionCommand {
+    New,
+    Pin(String),
+    ListPins,
+    /// 1-based pin number, or `None` for all pins.
+    Unpin(Option<usize>),
+    Context,
+}
+
+impl SessionCommand {
+    /// Parse `text` as a conversation command. Bot suffixes (`/new@my_bot`)
+    /// are accepted.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (command, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let command = command.strip_prefix('/')?;
+        let command = command.split('@').next().unwrap_or(command);
+        let rest = rest.trim();
+        match (command, rest) {
+            ("new", "") => Some(Self::New),
+            ("context", "") => Some(Self::Context),
+            ("pin", "") => Some(Self::ListPins),
+            ("pin", note) => Some(Self::Pin(note.to_string())),
+            ("unpin", "all") => Some(Self::Unpin(None)),
+            ("unpin", n) => n
+                .parse()
+                .ok()
+                .filter(|n| *n > 0)
+                .map(|n| Self::Unpin(Some(n))),
+            _ => None,
+        }
+    }
+}
+
+/// Add `note` to `pins`. Returns the reply.
+pub fn pin(pins: &mut Vec<String>, note: &str) -> String {
+    if pins.len() >= MAX_PINS {
+        return format!(
+            "This chat already has {} pins. Remove one with `/unpin <n>` first.",
+            MAX_PINS
+        );
+    }
+    let note: String = note.chars().take(MAX_PIN_CHARS).collect();
+    pins.push(note);
+    format!("Pinned ({} of {}).", pins.len(), MAX_PINS)
+}
+
+/// Remove pin `n` (1-based), or all pins. Returns the reply.
+pub fn unpin(pins: &mut Vec<String>, n: Option<usize>) -> String {
+    match n {
+        None => {
+            let count = pins.len();
+            pins.clear();
+            format!("Removed {} pins.", count)
+        }
+        Some(n) if n <= pins.len() => {
+            let removed = pins.remove(n - 1);
+            format!("Unpinned: {}", removed)
+        }
+        Some(n) => format!("There is no pin {}. `/pin` lists them.", n),
+    }
+}
+
+/// Numbered list of pins, for `/pin` and `/context`.
+pub fn list_pins(pins: &[String]) -> String {
+    if pins.is_empty() {
+        return "No pins. `/pin <text>` adds one.".to_string();
+    }
+    pins.iter()
+        .enumerate()
+        .map(|(i, pin)| format!("{}. {}", i + 1, pin))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// System prompt section carrying a session's pins.
+pub fn pins_section(pins: &[String]) -> Option<String> {
+    if pins.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = pins.iter().map(|pin| format!("- {}", pin)).collect();
+    Some(format!(
+        "## Pinned Notes\n\nThe user pinned these notes for this conversation; keep them in mind:\n{}",
+        lines.join("\n")
+    ))
+}
+
+/// Skill names in the skills prompt (`<name>` elements).
+pub fn skill_names(skills_prompt: &str) -> Vec<String> {
+    skills_prompt
+        .lines()
+        .filter_map(|line| {
+            line.trim()
+                .strip_prefix("<name>")
+                .and_then(|rest| rest.strip_suffix("</name>"))
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(SessionCommand::parse("/new"), Some(SessionCommand::New));
+        assert_eq!(
+            SessionCommand::parse("/new@zepto_bot"),
+            Some(SessionCommand::New)
+        );
+        assert_eq!(
+            SessionCommand::parse("/context"),
+            Some(SessionCommand::Context)
+        );
+        assert_eq!(
+            SessionCommand::parse("/pin  reply in German "),
+            Some(SessionCommand::Pin("reply in German".into()))
+        );
+        assert_eq!(
+            SessionCommand::parse("/pin"),
+            Some(SessionCommand::ListPins)
+        );
+        assert_eq!(
+            SessionCommand::parse("/unpin 2"),
+            Some(SessionCommand::Unpin(Some(2)))
+        );
+        assert_eq!(
+            SessionCommand::parse("/unpin all"),
+            Some(SessionCommand::Unpin(None))
+        );
+        for text in [
+            "/new chat",
+            "/unpin 0",
+            "/unpin x",
+            "/newer",
+            "new",
+            "/pinned x",
+        ] {
+            assert_eq!(SessionCommand::parse(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_pin_and_unpin() {
+        let mut pins = Vec::new();
+        assert_eq!(pin(&mut pins, "a"), format!("Pinned (1 of {}).", MAX_PINS));
+        pin(&mut pins, "b");
+        assert_eq!(list_pins(&pins), "1. a\n2. b");
+        assert_eq!(unpin(&mut pins, Some(1)), "Unpinned: a");
+        assert!(unpin(&mut pins, Some(5)).contains("no pin 5"));
+        assert_eq!(unpin(&mut pins, None), "Removed 1 pins.");
+        assert!(pins.is_empty());
+    }
+
+    #[test]
+    fn test_pins_are_capped() {
+        let mut pins = vec!["x".to_string(); MAX_PINS];
+        assert!(pin(&mut pins, "more").contains("already has"));
+        assert_eq!(pins.len(), MAX_PINS);
+
+        let mut pins = Vec::new();
+        pin(&mut pins, &"y".repeat(MAX_PIN_CHARS + 10));
+        assert_eq!(pins[0].chars().count(), MAX_PIN_CHARS);
+    }
+
+    #[test]
+    fn test_pins_section() {
+        assert_eq!(pins_section(&[]), None);
+        let section = pins_section(&["use metric units".to_string()]).unwrap();
+        assert!(section.starts_with("## Pinned Notes"));
+        assert!(section.ends_with("- use metric units"));
+    }
+
+    #[test]
+    fn test_skill_names() {
+        let prompt = "<skills>\n  <skill available=\"true\">\n    <name>🔍search</name>\n  </skill>\n  <skill available=\"true\">\n    <name>weather</name>\n  </skill>\n</skills>";
+        assert_eq!(skill_names(prompt), vec!["🔍search", "weather"]);
+    }
+}
@@ -58,10 +58,6 @@ impl ToolCallLimitTracker {
     }
 
     /// Resets the counter to zero. The limit remains unchanged.
-    ///
-    /// Called at the start of each `process_message` invocation so that
-    /// per-agent-run limits apply to each run independently, not across
-    /// the lifetime of the `AgentLoop` struct.
     pub fn reset(&self) {
         self.count.store(0, Ordering::Relaxed);
     }
@@ -0,0 +1,96 @@
+//! Channel auto-translation (`channels.auto_translate`).
+//!
+//! For a channel with `inbound_to`, each message is translated into that
+//! language before the turn, so the agent works in one language; the text
+//! as sent is kept on the session message (`Message::original`). With
+//! `outbound_to`, the final reply is translated before it is delivered.
+//! Each translation is one extra call to the agent's provider, counted
+//! toward the turn's tokens and the user's quota. A failed translation is
+//! logged and the untranslated text is used.
+
+use crate::error::{Result, ZeptoError};
+use crate::providers::{ChatOptions, LLMProvider, Usage};
+use crate::session::Message;
+use crate::utils::locale::Locale;
+
+/// Inbound message metadata key holding the text as the user sent it.
+pub const ORIGINAL_TEXT_KEY: &str = "original_text";
+
+/// A translated text and the tokens its provider call used.
+#[derive(Debug, Clone)]
+pub struct Translation {
+    pub text: String,
+    pub usage: Option<Usage>,
+}
+
+/// Whether a message should be translated: slash commands and empty
+/// messages are left alone.
+pub fn should_translate(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty() && !text.starts_with('/')
+}
+
+/// Language name for the prompt: "Malay (ms)" for supported locale tags,
+/// the configured value otherwise ("Tagalog", "pt-BR").
+fn language_label(language: &str) -> String {
+    match Locale::parse(language) {
+        Some(locale) => format!("{} ({})", locale.language().name(), locale.tag()),
+        None => language.trim().to_string(),
+    }
+}
+
+/// Prompt for translating a message into `language`.
+fn translation_prompt(language: &str) -> String {
+    format!(
+        "Translate the user's message into {}. Reply with the translation only, \
+         without notes or quotes. Keep formatting, code, URLs, names and numbers \
+         unchanged. If the message is already in that language, repeat it unchanged.",
+        language_label(language)
+    )
+}
+
+/// Translate `text` into `language` with one provider call.
+pub async fn translate(
+    provider: &dyn LLMProvider,
+    model: Option<&str>,
+    text: &str,
+    language: &str,
+    options: ChatOptions,
+) -> Result<Translation> {
+    let messages = vec![
+        Message::system(&translation_prompt(language)),
+        Message::user(text),
+    ];
+    let response = provider
+        .chat(messages, Vec::new(), model, options.with_temperature(0.0))
+        .await?;
+    let translated = response.content.trim();
+    if translated.is_empty() {
+        return Err(ZeptoError::Provider(
+            "Translation returned an empty response".into(),
+        ));
+    }
+    Ok(Translation {
+        text: translated.to_string(),
+        usage: response.usage,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_translate() {
+        assert!(should_translate("Apa khabar?"));
+        assert!(!should_translate("  "));
+        assert!(!should_translate("/stop"));
+    }
+
+    #[test]
+    fn test_translation_prompt_names_language() {
+        assert!(translation_prompt("ms").contains("into Malay (ms)."));
+        assert!(translation_prompt("en-gb").contains("into English (en-GB)."));
+        assert!(translation_prompt("Tagalog").contains("into Tagalog."));
+    }
+}
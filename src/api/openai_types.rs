@@ -169,6 +169,7 @@ pub fn messages_from_openai(msgs: &[ChatMessage]) -> Result<Vec<Message>, String
                 }],
                 tool_calls: None,
                 tool_call_id: None,
+                original: None,
             })
         })
         .collect()
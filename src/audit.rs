@@ -27,6 +27,8 @@ pub enum AuditCategory {
     ToolChainAlert,
     /// Taint tracking: data-flow policy violation.
     TaintViolation,
+    /// Finance write attempted while finance read-only mode is on.
+    FinanceReadOnly,
 }
 
 impl std::fmt::Display for AuditCategory {
@@ -41,6 +43,7 @@ impl std::fmt::Display for AuditCategory {
             Self::PluginIntegrity => write!(f, "plugin_integrity"),
             Self::ToolChainAlert => write!(f, "tool_chain_alert"),
             Self::TaintViolation => write!(f, "taint_violation"),
+            Self::FinanceReadOnly => write!(f, "finance_read_only"),
         }
     }
 }
@@ -140,6 +143,10 @@ mod tests {
             "tool_chain_alert"
         );
         assert_eq!(AuditCategory::TaintViolation.to_string(), "taint_violation");
+        assert_eq!(
+            AuditCategory::FinanceReadOnly.to_string(),
+            "finance_read_only"
+        );
     }
 
     #[test]
@@ -41,7 +41,7 @@ where
         &'a str,
         &'a str,
         &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<RefreshedTokens>> + 'a>>,
+    ) -> Pin<Box<dyn Future<Output = Result<RefreshedTokens>> + Send + 'a>>,
 {
     let token = store
         .load(provider)?
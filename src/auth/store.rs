@@ -36,7 +36,7 @@ impl TokenStore {
     ///
     /// The store file is located at `~/.zeptoclaw/auth/tokens.json.enc`.
     pub fn new(encryption: SecretEncryption) -> Self {
-        let path = crate::config::Config::dir()
+        let path = crate::config::Config::data_dir()
             .join("auth")
             .join("tokens.json.enc");
         Self { path, encryption }
@@ -0,0 +1,589 @@
+//! Backup and restore of ZeptoClaw state.
+//!
+//! A backup is a gzipped tarball of the config and data directories —
+//! config, profiles, sessions, memory, the cron store, skills, pairing data
+//! and credentials — encrypted with a passphrase (Argon2id +
+//! XChaCha20-Poly1305). The state directory (caches, metrics, runtime state)
+//! is left out, and so are workspaces unless asked for.
+//!
+//! Archive layout:
+//!
+//! ```text
+//! manifest.json        format version, zeptoclaw version, entries
+//! config/<entry>...    restored into the config dir
+//! data/<entry>...      restored into the data dir
+//! ```
+//!
+//! Entries are stored by the directory they belong in rather than by path,
+//! so a backup of a `~/.zeptoclaw` install restores into an XDG layout and
+//! vice versa. Restored state goes through the state migrations
+//! ([`crate::migrate::state`]), so a backup from an older version comes up
+//! in the current formats; a backup from a newer archive format is refused.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::layout::{DirKind, DirLayout};
+use crate::error::{Result, ZeptoError};
+use crate::security::encryption::{is_secret_field, PassphraseEncryption};
+
+/// Archive format written by this version.
+pub const BACKUP_FORMAT: u32 = 1;
+
+/// Leading bytes of an encrypted backup file.
+const MAGIC: &[u8] = b"ZEPTOCLAW-BACKUP\n";
+
+const MANIFEST: &str = "manifest.json";
+
+/// Top-level entries that are never backed up: installed binaries and the
+/// panel build can be reinstalled.
+const SKIPPED_ENTRIES: &[&str] = &["deps", "panel"];
+
+/// Project files; large and usually under their own version control.
+const WORKSPACE_ENTRIES: &[&str] = &["workspace", "workspaces"];
+
+/// Credential stores left out by `--exclude-secrets`.
+const SECRET_ENTRIES: &[&str] = &["auth", "tokens", "panel.token"];
+
+/// What goes into a backup.
+#[derive(Debug, Clone, Copy)]
+pub struct BackupOptions {
+    /// Keep API keys and tokens in config files and credential stores.
+    pub include_secrets: bool,
+    /// Include `workspace` and named `workspaces`.
+    pub include_workspace: bool,
+}
+
+impl Default for BackupOptions {
+    fn default() -> Self {
+        Self {
+            include_secrets: true,
+            include_workspace: false,
+        }
+    }
+}
+
+/// Describes a backup; stored as `manifest.json` in the archive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Archive format, see [`BACKUP_FORMAT`].
+    pub format: u32,
+    /// Version of zeptoclaw that wrote the backup.
+    pub version: String,
+    pub created_at: DateTime<Utc>,
+    /// Whether secrets were kept (`false` with `--exclude-secrets`).
+    pub secrets_included: bool,
+    /// Top-level archive entries, e.g. `config/config.json`, `data/sessions`.
+    pub entries: Vec<String>,
+}
+
+/// Outcome of a restore.
+#[derive(Debug, Default)]
+pub struct RestoreReport {
+    /// Paths written.
+    pub restored: Vec<PathBuf>,
+    /// Existing paths that were replaced (`force`).
+    pub replaced: Vec<PathBuf>,
+    /// State migrations run because the backup came from an older version.
+    pub upgrades: Vec<String>,
+}
+
+/// Pack the config and data dirs of `layout` into a gzipped tarball.
+pub fn create_archive(
+    layout: &DirLayout,
+    options: &BackupOptions,
+) -> Result<(BackupManifest, Vec<u8>)> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    let mut entries = Vec::new();
+
+    for (kind, name, path) in backup_entries(layout, options)? {
+        let archive_name = Path::new(prefix(kind)).join(&name);
+        append_tree(&mut builder, &path, &archive_name, options.include_secrets)?;
+        entries.push(format!("{}/{}", prefix(kind), name));
+    }
+
+    let manifest = BackupManifest {
+        format: BACKUP_FORMAT,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Utc::now(),
+        secrets_included: options.include_secrets,
+        entries,
+    };
+    let json = serde_json::to_vec_pretty(&manifest)?;
+    append_bytes(&mut builder, Path::new(MANIFEST), &json, 0o600)?;
+
+    let archive = builder.into_inner()?.finish()?;
+    Ok((manifest, archive))
+}
+
+/// Encrypt an archive for writing to disk.
+pub fn seal(archive: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut out = MAGIC.to_vec();
+    out.extend(PassphraseEncryption::new(passphrase).seal(archive)?);
+    Ok(out)
+}
+
+/// Decrypt a backup file's contents back into the archive.
+pub fn open(sealed: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let body = sealed
+        .strip_prefix(MAGIC)
+        .ok_or_else(|| ZeptoError::Config("not a zeptoclaw backup file".into()))?;
+    PassphraseEncryption::new(passphrase).open(body)
+}
+
+/// Read the manifest of an archive without unpacking it.
+pub fn read_manifest(archive: &[u8]) -> Result<BackupManifest> {
+    let mut tar = tar::Archive::new(GzDecoder::new(archive));
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.as_ref() == Path::new(MANIFEST) {
+            let mut json = Vec::new();
+            entry.read_to_end(&mut json)?;
+            return check_manifest(serde_json::from_slice(&json)?);
+        }
+    }
+    Err(ZeptoError::Config("backup has no manifest".into()))
+}
+
+/// Existing paths that restoring `manifest` into `layout` would replace.
+pub fn conflicts(manifest: &BackupManifest, layout: &DirLayout) -> Vec<PathBuf> {
+    manifest
+        .entries
+        .iter()
+        .filter_map(|entry| destination(entry, layout))
+        .filter(|path| path.symlink_metadata().is_ok())
+        .collect()
+}
+
+/// Unpack `archive` into `layout`. Existing entries are only replaced when
+/// `force` is set.
+pub fn restore_archive(archive: &[u8], layout: &DirLayout, force: bool) -> Result<RestoreReport> {
+    let manifest = read_manifest(archive)?;
+    let existing = conflicts(&manifest, layout);
+    if !existing.is_empty() && !force {
+        let list: Vec<String> = existing.iter().map(|p| p.display().to_string()).collect();
+        return Err(ZeptoError::Config(format!(
+            "restore would replace existing files (use --force): {}",
+            list.join(", ")
+        )));
+    }
+
+    // Unpack next to the destination first so a corrupt archive leaves the
+    // current state untouched, and so the final moves are renames.
+    std::fs::create_dir_all(&layout.data)?;
+    let staging = tempfile::Builder::new()
+        .prefix(".restore-")
+        .tempdir_in(&layout.data)?;
+    let mut tar = tar::Archive::new(GzDecoder::new(archive));
+    for entry in tar.entries()? {
+        // `unpack_in` refuses paths that escape the staging dir.
+        entry?.unpack_in(staging.path())?;
+    }
+
+    let mut moves = Vec::new();
+    for entry in &manifest.entries {
+        let Some(dest) = destination(entry, layout) else {
+            continue;
+        };
+        let src = staging.path().join(entry);
+        if src.symlink_metadata().is_err() {
+            return Err(ZeptoError::Config(format!(
+                "backup is missing manifest entry {}",
+                entry
+            )));
+        }
+        moves.push((src, dest));
+    }
+
+    let mut report = RestoreReport::default();
+    for (src, dest) in moves {
+        if dest.symlink_metadata().is_ok() {
+            remove_path(&dest)?;
+            report.replaced.push(dest.clone());
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        move_path(&src, &dest)?;
+        report.restored.push(dest);
+    }
+
+    report.upgrades = upgrade_restored(&manifest, layout)?;
+    Ok(report)
+}
+
+/// Bring state restored from an older version up to date with the state
+/// migrations (`migrate::state`).
+fn upgrade_restored(manifest: &BackupManifest, layout: &DirLayout) -> Result<Vec<String>> {
+    // Backups from before store versioning carry no versions file; the one
+    // left in place describes the old state, not the restored one.
+    let versions = format!("data/{}", crate::migrate::state::VERSIONS_FILE);
+    if !manifest.entries.contains(&versions) {
+        let path = layout.data.join(crate::migrate::state::VERSIONS_FILE);
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    crate::migrate::state::run(&layout.data)
+}
+
+fn check_manifest(manifest: BackupManifest) -> Result<BackupManifest> {
+    if manifest.format > BACKUP_FORMAT {
+        return Err(ZeptoError::Config(format!(
+            "backup format {} was written by zeptoclaw {}; this version reads up to format {} — update first",
+            manifest.format, manifest.version, BACKUP_FORMAT
+        )));
+    }
+    Ok(manifest)
+}
+
+fn prefix(kind: DirKind) -> &'static str {
+    match kind {
+        DirKind::Config => "config",
+        _ => "data",
+    }
+}
+
+/// Where a manifest entry (`config/<name>` or `data/<name>`) is restored.
+fn destination(entry: &str, layout: &DirLayout) -> Option<PathBuf> {
+    let (prefix, name) = entry.split_once('/')?;
+    if name.is_empty() || name.contains('/') || name == ".." || name == "." {
+        return None;
+    }
+    let kind = match prefix {
+        "config" => DirKind::Config,
+        "data" => DirKind::Data,
+        _ => return None,
+    };
+    Some(layout.dir(kind).join(name))
+}
+
+/// Top-level entries to back up: (kind, name, path), sorted by name.
+fn backup_entries(
+    layout: &DirLayout,
+    options: &BackupOptions,
+) -> Result<Vec<(DirKind, String, PathBuf)>> {
+    let mut dirs = vec![(DirKind::Config, &layout.config)];
+    if layout.data != layout.config {
+        dirs.push((DirKind::Data, &layout.data));
+    }
+
+    let mut entries = Vec::new();
+    for (dir_kind, dir) in dirs {
+        let read = match std::fs::read_dir(dir) {
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        for item in read {
+            let item = item?;
+            let name = item.file_name().to_string_lossy().into_owned();
+            // In a single-directory install the entry name decides where it
+            // belongs; otherwise the directory it was found in does.
+            let kind = if layout.is_single() {
+                DirKind::of(&name)
+            } else {
+                dir_kind
+            };
+            let skipped = kind == DirKind::State
+                || name.starts_with(".restore-")
+                || SKIPPED_ENTRIES.contains(&name.as_str())
+                || (!options.include_workspace && WORKSPACE_ENTRIES.contains(&name.as_str()))
+                || (!options.include_secrets && SECRET_ENTRIES.contains(&name.as_str()));
+            if !skipped {
+                entries.push((kind, name, item.path()));
+            }
+        }
+    }
+    entries.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(entries)
+}
+
+/// Append `src` (file or directory tree) as `name`. Symlinks are skipped.
+fn append_tree<W: Write>(
+    builder: &mut tar::Builder<W>,
+    src: &Path,
+    name: &Path,
+    include_secrets: bool,
+) -> Result<()> {
+    let meta = std::fs::symlink_metadata(src)?;
+    if meta.is_dir() {
+        builder.append_dir(name, src)?;
+        let mut children: Vec<_> = std::fs::read_dir(src)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name())
+            .collect();
+        children.sort();
+        for child in children {
+            append_tree(
+                builder,
+                &src.join(&child),
+                &name.join(&child),
+                include_secrets,
+            )?;
+        }
+    } else if meta.is_file() {
+        if !include_secrets && is_config_file(name) {
+            let mut value: Value = serde_json::from_slice(&std::fs::read(src)?)?;
+            strip_secrets(&mut value);
+            let json = serde_json::to_vec_pretty(&value)?;
+            append_bytes(builder, name, &json, 0o600)?;
+        } else {
+            builder.append_path_with_name(src, name)?;
+        }
+    }
+    Ok(())
+}
+
+fn append_bytes<W: Write>(
+    builder: &mut tar::Builder<W>,
+    name: &Path,
+    bytes: &[u8],
+    mode: u32,
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(mode);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, name, bytes)?;
+    Ok(())
+}
+
+/// `config/config.json` and `config/profiles/*.json`.
+fn is_config_file(name: &Path) -> bool {
+    name == Path::new("config/config.json")
+        || (name.starts_with("config/profiles")
+            && name.extension().is_some_and(|ext| ext == "json"))
+}
+
+/// Remove string values of secret fields (API keys, tokens) from config
+/// JSON. Returns how many were removed.
+fn strip_secrets(value: &mut Value) -> usize {
+    match value {
+        Value::Object(map) => {
+            let before = map.len();
+            map.retain(|key, val| !(is_secret_field(key) && val.is_string()));
+            let mut removed = before - map.len();
+            for val in map.values_mut() {
+                removed += strip_secrets(val);
+            }
+            removed
+        }
+        Value::Array(items) => items.iter_mut().map(strip_secrets).sum(),
+        _ => 0,
+    }
+}
+
+/// Rename, or copy when `dest` is on another filesystem (config and data
+/// dirs can be on different mounts).
+fn move_path(src: &Path, dest: &Path) -> std::io::Result<()> {
+    if std::fs::rename(src, dest).is_ok() {
+        return Ok(());
+    }
+    copy_tree(src, dest)
+}
+
+fn copy_tree(src: &Path, dest: &Path) -> std::io::Result<()> {
+    let meta = std::fs::symlink_metadata(src)?;
+    if meta.is_dir() {
+        std::fs::create_dir_all(dest)?;
+        for entry in std::fs::read_dir(src)? {
+            let entry = entry?;
+            copy_tree(&entry.path(), &dest.join(entry.file_name()))?;
+        }
+    } else if meta.is_file() {
+        std::fs::copy(src, dest)?;
+    }
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.symlink_metadata()?.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn install() -> (TempDir, DirLayout) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("zc");
+        write(
+            &root.join("config.json"),
+            r#"{"providers":{"anthropic":{"api_key":"sk-secret"}},"agents":{"defaults":{"model":"m"}}}"#,
+        );
+        write(
+            &root.join("profiles/work.json"),
+            r#"{"channels":{"telegram":{"token":"t"}}}"#,
+        );
+        write(&root.join("sessions/cli_cli.json"), "{}");
+        write(&root.join("memory/longterm.json"), "{}");
+        write(&root.join("cron/jobs.json"), r#"{"version":1,"jobs":[]}"#);
+        write(&root.join("security/paired_devices.json"), "{}");
+        write(&root.join("auth/tokens.json.enc"), "secret");
+        write(&root.join("workspace/notes.md"), "notes");
+        write(&root.join("cache/blob"), "cached");
+        write(&root.join("daemon_state.json"), "{}");
+        (dir, DirLayout::single(root))
+    }
+
+    #[test]
+    fn test_backup_entries_skip_state_and_workspace() {
+        let (_dir, layout) = install();
+        let (manifest, _) = create_archive(&layout, &BackupOptions::default()).unwrap();
+        assert_eq!(
+            manifest.entries,
+            vec![
+                "data/auth",
+                "config/config.json",
+                "data/cron",
+                "data/memory",
+                "config/profiles",
+                "data/security",
+                "data/sessions",
+            ]
+        );
+        assert!(manifest.secrets_included);
+        assert_eq!(manifest.format, BACKUP_FORMAT);
+    }
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let sealed = seal(b"archive", "pw").unwrap();
+        assert!(sealed.starts_with(MAGIC));
+        assert_eq!(open(&sealed, "pw").unwrap(), b"archive");
+        assert!(open(&sealed, "nope").is_err());
+        assert!(open(b"garbage", "pw").is_err());
+    }
+
+    #[test]
+    fn test_restore_into_xdg_layout() {
+        let (_dir, layout) = install();
+        let (_, archive) = create_archive(&layout, &BackupOptions::default()).unwrap();
+
+        let target = tempfile::tempdir().unwrap();
+        let xdg = DirLayout {
+            config: target.path().join("config"),
+            data: target.path().join("data"),
+            state: target.path().join("state"),
+        };
+        let report = restore_archive(&archive, &xdg, false).unwrap();
+        assert_eq!(report.restored.len(), 7);
+        assert!(report.replaced.is_empty());
+        assert!(xdg.config.join("profiles/work.json").is_file());
+        assert!(xdg.data.join("cron/jobs.json").is_file());
+        assert!(xdg.data.join("security/paired_devices.json").is_file());
+        let config = std::fs::read_to_string(xdg.config.join("config.json")).unwrap();
+        assert!(config.contains("sk-secret"));
+        // Staging dir is cleaned up.
+        let leftovers: Vec<_> = std::fs::read_dir(&xdg.data)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with(".restore-"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[test]
+    fn test_restore_refuses_to_overwrite_without_force() {
+        let (_dir, layout) = install();
+        let (_, archive) = create_archive(&layout, &BackupOptions::default()).unwrap();
+        std::fs::write(layout.data.join("sessions/new.json"), "{}").unwrap();
+
+        let err = restore_archive(&archive, &layout, false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        assert!(layout.data.join("sessions/new.json").exists());
+
+        let report = restore_archive(&archive, &layout, true).unwrap();
+        assert_eq!(report.replaced.len(), 7);
+        assert!(!layout.data.join("sessions/new.json").exists());
+        assert!(layout.data.join("sessions/cli_cli.json").exists());
+    }
+
+    #[test]
+    fn test_exclude_secrets() {
+        let (_dir, layout) = install();
+        let options = BackupOptions {
+            include_secrets: false,
+            include_workspace: true,
+        };
+        let (manifest, archive) = create_archive(&layout, &options).unwrap();
+        assert!(!manifest.secrets_included);
+        assert!(!manifest.entries.contains(&"data/auth".to_string()));
+        assert!(manifest.entries.contains(&"data/workspace".to_string()));
+
+        let target = tempfile::tempdir().unwrap();
+        let restored = DirLayout::single(target.path().to_path_buf());
+        restore_archive(&archive, &restored, false).unwrap();
+        let config: Value = serde_json::from_str(
+            &std::fs::read_to_string(target.path().join("config.json")).unwrap(),
+        )
+        .unwrap();
+        assert!(config["providers"]["anthropic"].get("api_key").is_none());
+        assert_eq!(config["agents"]["defaults"]["model"], "m");
+        let profile = std::fs::read_to_string(target.path().join("profiles/work.json")).unwrap();
+        assert!(!profile.contains("\"token\""));
+    }
+
+    #[test]
+    fn test_newer_format_is_refused() {
+        let manifest = BackupManifest {
+            format: BACKUP_FORMAT + 1,
+            version: "99.0.0".into(),
+            created_at: Utc::now(),
+            secrets_included: true,
+            entries: vec![],
+        };
+        let err = check_manifest(manifest).unwrap_err();
+        assert!(err.to_string().contains("update first"));
+    }
+
+    #[test]
+    fn test_destination_rejects_nested_and_unknown_entries() {
+        let layout = DirLayout::single(PathBuf::from("/zc"));
+        assert_eq!(
+            destination("data/sessions", &layout),
+            Some(PathBuf::from("/zc/sessions"))
+        );
+        assert_eq!(destination("data/../etc", &layout), None);
+        assert_eq!(destination("data/..", &layout), None);
+        assert_eq!(destination("state/cache", &layout), None);
+        assert_eq!(destination("manifest.json", &layout), None);
+    }
+
+    #[test]
+    fn test_restore_runs_state_migrations() {
+        let (_dir, layout) = install();
+        write(
+            &layout.data.join("sessions/con.json"),
+            r#"{"key":"con","messages":[]}"#,
+        );
+        let (_, archive) = create_archive(&layout, &BackupOptions::default()).unwrap();
+
+        let target = tempfile::tempdir().unwrap();
+        let restored = DirLayout::single(target.path().to_path_buf());
+        // A fresh install has already recorded current versions.
+        crate::migrate::state::run(target.path()).unwrap();
+        let report = restore_archive(&archive, &restored, true).unwrap();
+        assert!(report.upgrades.iter().any(|u| u.starts_with("sessions v2")));
+        assert!(target.path().join("sessions/%63on.json").exists());
+    }
+}
@@ -2,14 +2,26 @@
 //!
 //! This module provides types and logic for loading prompts from files
 //! (plain text or JSON/JSONL) and formatting batch results in text or JSONL format.
+//!
+//! Prompts can also be generated from a dataset (CSV, JSON array or JSONL
+//! objects) and a prompt template: each row renders one prompt, with
+//! `{{column}}` placeholders replaced by that row's values.
+//!
+//! Finished prompts are appended to a JSONL checkpoint file as they complete,
+//! so an interrupted batch can resume with only the prompts that have not
+//! succeeded yet.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
+use crate::agent::prompts::{render, PromptVars};
 use crate::error::{Result, ZeptoError};
 
 /// Configuration for batch processing.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct BatchConfig {
     /// Number of prompts to process concurrently (1 = sequential).
@@ -18,6 +30,8 @@ pub struct BatchConfig {
     pub output_format: BatchOutputFormat,
     /// Whether to stop processing on the first error.
     pub stop_on_error: bool,
+    /// Per-prompt timeout in seconds (0 = no timeout).
+    pub prompt_timeout_secs: u64,
 }
 
 impl Default for BatchConfig {
@@ -26,12 +40,13 @@ impl Default for BatchConfig {
             concurrency: 1,
             output_format: BatchOutputFormat::default(),
             stop_on_error: false,
+            prompt_timeout_secs: 0,
         }
     }
 }
 
 /// Output format for batch results.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum BatchOutputFormat {
     /// Plain text output, each response separated by a blank line.
@@ -42,7 +57,7 @@ pub enum BatchOutputFormat {
 }
 
 /// Result of processing a single prompt in a batch.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BatchResult {
     /// Zero-based index of the prompt in the batch.
     pub index: usize,
@@ -54,6 +69,22 @@ pub struct BatchResult {
     pub error: Option<String>,
     /// Time taken to process this prompt, in milliseconds.
     pub duration_ms: u64,
+    /// Input tokens consumed across all LLM calls for this prompt.
+    #[serde(default)]
+    pub input_tokens: u64,
+    /// Output tokens produced across all LLM calls for this prompt.
+    #[serde(default)]
+    pub output_tokens: u64,
+    /// Estimated cost in USD, if pricing for the model is known.
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
+}
+
+impl BatchResult {
+    /// Whether the prompt completed without error.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
 }
 
 /// Load prompts from a file.
@@ -138,6 +169,218 @@ fn parse_json_prompt(line: &str) -> Option<String> {
     None
 }
 
+/// One dataset row: column name → value.
+pub type DatasetRow = HashMap<String, String>;
+
+/// Placeholders filled from the clock rather than the dataset.
+const BUILTIN_VARS: &[&str] = &["date", "time", "weekday"];
+
+/// Load dataset rows from a CSV, JSON or JSONL file.
+///
+/// - **CSV** (`.csv`): the first record is the header. Quoted fields may
+///   contain commas, newlines and `""` escapes.
+/// - **JSON** (`.json`): an array of objects.
+/// - **JSONL** (`.jsonl`): one object per non-empty line.
+///
+/// Non-string JSON values are rendered as JSON (`null` becomes empty).
+pub fn load_dataset(path: &Path) -> Result<Vec<DatasetRow>> {
+    if !path.exists() {
+        return Err(ZeptoError::NotFound(format!(
+            "Dataset file not found: {}",
+            path.display()
+        )));
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let content = content.trim_start_matches('\u{feff}');
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let rows = match ext.as_str() {
+        "csv" => parse_csv(content)?,
+        "json" => {
+            let values: Vec<serde_json::Value> = serde_json::from_str(content)?;
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| json_row(v, i + 1))
+                .collect::<Result<_>>()?
+        }
+        "jsonl" => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| json_row(serde_json::from_str(line)?, i + 1))
+            .collect::<Result<_>>()?,
+        _ => {
+            return Err(ZeptoError::Config(format!(
+                "Unsupported dataset format '{}' (expected .csv, .json or .jsonl)",
+                path.display()
+            )))
+        }
+    };
+
+    if rows.is_empty() {
+        return Err(ZeptoError::Config("Dataset contains no rows".to_string()));
+    }
+    Ok(rows)
+}
+
+fn json_row(value: serde_json::Value, row: usize) -> Result<DatasetRow> {
+    let serde_json::Value::Object(fields) = value else {
+        return Err(ZeptoError::Config(format!(
+            "Dataset row {} is not a JSON object",
+            row
+        )));
+    };
+    Ok(fields
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(s) => s,
+                serde_json::Value::Null => String::new(),
+                other => other.to_string(),
+            };
+            (key, value)
+        })
+        .collect())
+}
+
+/// Parse CSV text into rows keyed by the header record.
+fn parse_csv(content: &str) -> Result<Vec<DatasetRow>> {
+    let mut records = csv_records(content)?.into_iter();
+    let Some(header) = records.next() else {
+        return Ok(Vec::new());
+    };
+    let header: Vec<String> = header.iter().map(|h| h.trim().to_string()).collect();
+
+    records
+        .enumerate()
+        .map(|(i, record)| {
+            if record.len() != header.len() {
+                return Err(ZeptoError::Config(format!(
+                    "CSV row {} has {} field(s), expected {}",
+                    i + 1,
+                    record.len(),
+                    header.len()
+                )));
+            }
+            Ok(header.iter().cloned().zip(record).collect())
+        })
+        .collect()
+}
+
+/// Split CSV text into records, skipping blank lines.
+fn csv_records(content: &str) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                if record.len() == 1 && record[0].trim().is_empty() {
+                    record.clear();
+                } else {
+                    records.push(std::mem::take(&mut record));
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(ZeptoError::Config(
+            "CSV ends inside a quoted field".to_string(),
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Names of the `{{placeholder}}`s used in `template`, ignoring escaped `\{{`.
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{{") {
+        let escaped = rest[..pos].ends_with('\\');
+        let after = &rest[pos + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        if !escaped {
+            names.push(after[..end].trim());
+        }
+        rest = if escaped { after } else { &after[end + 2..] };
+    }
+    names
+}
+
+/// Render one prompt per dataset row from `template`.
+///
+/// Fails if the template references a column that a row does not have, so
+/// a typo in a placeholder cannot silently send hundreds of half-empty
+/// prompts. `{{date}}`, `{{time}}` and `{{weekday}}` are also available.
+pub fn render_prompts(template: &str, rows: &[DatasetRow]) -> Result<Vec<String>> {
+    let names = placeholders(template);
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            if let Some(missing) = names
+                .iter()
+                .find(|name| !row.contains_key(**name) && !BUILTIN_VARS.contains(*name))
+            {
+                return Err(ZeptoError::Config(format!(
+                    "Dataset row {} has no column '{}' used by the prompt template",
+                    i + 1,
+                    missing
+                )));
+            }
+            let vars = row.iter().fold(PromptVars::new(), |vars, (key, value)| {
+                vars.with(key, value)
+            });
+            Ok(render(template, &vars).trim().to_string())
+        })
+        .collect()
+}
+
+/// Load a dataset and render one prompt per row from a template file.
+pub fn load_templated_prompts(dataset: &Path, template_file: &Path) -> Result<Vec<String>> {
+    let template = std::fs::read_to_string(template_file).map_err(|e| {
+        ZeptoError::Config(format!(
+            "Failed to read prompt template {}: {}",
+            template_file.display(),
+            e
+        ))
+    })?;
+    if template.trim().is_empty() {
+        return Err(ZeptoError::Config("Prompt template is empty".to_string()));
+    }
+    render_prompts(&template, &load_dataset(dataset)?)
+}
+
 /// Format batch results into a string according to the specified output format.
 ///
 /// - **Text**: Human-readable blocks separated by blank lines.
@@ -179,6 +422,78 @@ fn format_results_jsonl(results: &[BatchResult]) -> String {
         .join("\n")
 }
 
+/// Default checkpoint location for a batch input file: `<input>.checkpoint.jsonl`.
+pub fn checkpoint_path(input: &Path) -> PathBuf {
+    let mut name = input.file_name().unwrap_or_default().to_os_string();
+    name.push(".checkpoint.jsonl");
+    input.with_file_name(name)
+}
+
+/// Load the successful results recorded in a checkpoint file.
+///
+/// Only entries whose index and prompt still match `prompts` are kept, so a
+/// checkpoint left over from an edited input file cannot leak stale answers.
+/// Failed entries are dropped so those prompts are retried. A missing file
+/// yields an empty map; unparsable lines (e.g. a write cut short by a crash)
+/// are skipped. Later entries for the same index win.
+pub fn load_checkpoint(path: &Path, prompts: &[String]) -> Result<BTreeMap<usize, BatchResult>> {
+    let mut done = BTreeMap::new();
+    if !path.exists() {
+        return Ok(done);
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let Ok(result) = serde_json::from_str::<BatchResult>(line) else {
+            continue;
+        };
+        if prompts.get(result.index) != Some(&result.prompt) {
+            continue;
+        }
+        if result.is_success() {
+            done.insert(result.index, result);
+        } else {
+            done.remove(&result.index);
+        }
+    }
+    Ok(done)
+}
+
+/// Append-only writer for a batch checkpoint file.
+pub struct CheckpointWriter {
+    file: std::fs::File,
+}
+
+impl CheckpointWriter {
+    /// Open `path` for appending, or truncate it first when `fresh` is true.
+    pub fn open(path: &Path, fresh: bool) -> Result<Self> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(!fresh)
+            .truncate(fresh)
+            .open(path)?;
+        // Terminate a line cut short by an interrupted write so the next
+        // record starts on a line of its own.
+        if !fresh {
+            let existing = std::fs::read(path)?;
+            if existing.last().is_some_and(|b| *b != b'\n') {
+                writeln!(file)?;
+            }
+        }
+        Ok(Self { file })
+    }
+
+    /// Record one finished prompt. Each result is flushed immediately so an
+    /// interrupted run loses at most the prompts still in flight.
+    pub fn record(&mut self, result: &BatchResult) -> Result<()> {
+        let line = serde_json::to_string(result)?;
+        writeln!(self.file, "{}", line)?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,6 +530,7 @@ mod tests {
             response: Some("Hi there".to_string()),
             error: None,
             duration_ms: 150,
+            ..Default::default()
         };
 
         assert_eq!(result.index, 0);
@@ -346,6 +662,7 @@ mod tests {
                 response: Some("A systems programming language.".to_string()),
                 error: None,
                 duration_ms: 100,
+                ..Default::default()
             },
             BatchResult {
                 index: 1,
@@ -353,6 +670,7 @@ mod tests {
                 response: Some("The Rust package manager.".to_string()),
                 error: None,
                 duration_ms: 80,
+                ..Default::default()
             },
         ];
 
@@ -374,6 +692,7 @@ mod tests {
             response: Some("Hi".to_string()),
             error: None,
             duration_ms: 50,
+            ..Default::default()
         }];
 
         let output = format_results(&results, &BatchOutputFormat::Jsonl);
@@ -393,6 +712,7 @@ mod tests {
             response: None,
             error: Some("Provider timeout".to_string()),
             duration_ms: 30000,
+            ..Default::default()
         }];
 
         // Text format
@@ -415,6 +735,7 @@ mod tests {
             concurrency: 4,
             output_format: BatchOutputFormat::Jsonl,
             stop_on_error: true,
+            prompt_timeout_secs: 120,
         };
 
         let json = serde_json::to_string(&config).expect("Serialization failed");
@@ -424,6 +745,7 @@ mod tests {
         assert_eq!(deserialized.concurrency, 4);
         assert_eq!(deserialized.output_format, BatchOutputFormat::Jsonl);
         assert!(deserialized.stop_on_error);
+        assert_eq!(deserialized.prompt_timeout_secs, 120);
     }
 
     #[test]
@@ -448,4 +770,140 @@ mod tests {
         assert_eq!(prompts[1], "tabbed prompt");
         let _ = std::fs::remove_file(&path);
     }
+
+    fn result(index: usize, prompt: &str, error: Option<&str>) -> BatchResult {
+        BatchResult {
+            index,
+            prompt: prompt.to_string(),
+            response: error.is_none().then(|| format!("answer {}", index)),
+            error: error.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_batch_result_cost_fields_in_jsonl() {
+        let results = vec![BatchResult {
+            input_tokens: 1200,
+            output_tokens: 300,
+            cost_usd: Some(0.0081),
+            ..result(0, "Hello", None)
+        }];
+        let output = format_results(&results, &BatchOutputFormat::Jsonl);
+        assert!(output.contains("\"input_tokens\":1200"));
+        assert!(output.contains("\"output_tokens\":300"));
+        assert!(output.contains("\"cost_usd\":0.0081"));
+    }
+
+    #[test]
+    fn test_checkpoint_path() {
+        assert_eq!(
+            checkpoint_path(Path::new("/data/prompts.txt")),
+            Path::new("/data/prompts.txt.checkpoint.jsonl")
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip_keeps_only_matching_successes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("batch.checkpoint.jsonl");
+        let prompts: Vec<String> = ["a", "b", "c", "d"].map(String::from).to_vec();
+
+        let mut writer = CheckpointWriter::open(&path, true).unwrap();
+        writer.record(&result(0, "a", None)).unwrap();
+        writer.record(&result(1, "b", Some("timeout"))).unwrap();
+        writer.record(&result(2, "edited", None)).unwrap();
+        writer.record(&result(3, "d", None)).unwrap();
+        drop(writer);
+        // A line cut short by an interrupted write is ignored.
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"index\":2,\"pro")
+            .unwrap();
+
+        let done = load_checkpoint(&path, &prompts).unwrap();
+        assert_eq!(done.keys().copied().collect::<Vec<_>>(), vec![0, 3]);
+
+        // A later failure for the same prompt supersedes the earlier success.
+        let mut writer = CheckpointWriter::open(&path, false).unwrap();
+        writer.record(&result(3, "d", Some("boom"))).unwrap();
+        let done = load_checkpoint(&path, &prompts).unwrap();
+        assert_eq!(done.keys().copied().collect::<Vec<_>>(), vec![0]);
+
+        // Starting fresh discards everything.
+        CheckpointWriter::open(&path, true).unwrap();
+        assert!(load_checkpoint(&path, &prompts).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_load_checkpoint_missing_file() {
+        let done = load_checkpoint(Path::new("/tmp/zeptoclaw_no_such_checkpoint.jsonl"), &[]);
+        assert!(done.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_csv_records_quoting() {
+        let records =
+            csv_records("id,note\r\n1,\"hello, world\"\n\n2,\"line one\nsaid \"\"hi\"\"\"\n3,")
+                .unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[1], vec!["1", "hello, world"]);
+        assert_eq!(records[2], vec!["2", "line one\nsaid \"hi\""]);
+        assert_eq!(records[3], vec!["3", ""]);
+        assert!(csv_records("a,\"open").is_err());
+    }
+
+    #[test]
+    fn test_load_dataset_csv_and_jsonl() {
+        let dir = tempfile::tempdir().unwrap();
+        let csv = dir.path().join("orders.csv");
+        std::fs::write(&csv, "id, customer\n1042,Aisha\n1043,\"Lee, Jr.\"\n").unwrap();
+        let rows = load_dataset(&csv).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["customer"], "Lee, Jr.");
+
+        let jsonl = dir.path().join("orders.jsonl");
+        std::fs::write(&jsonl, "{\"id\":1042,\"paid\":true,\"note\":null}\n\n").unwrap();
+        let rows = load_dataset(&jsonl).unwrap();
+        assert_eq!(rows[0]["id"], "1042");
+        assert_eq!(rows[0]["paid"], "true");
+        assert_eq!(rows[0]["note"], "");
+
+        let ragged = dir.path().join("ragged.csv");
+        std::fs::write(&ragged, "a,b\n1\n").unwrap();
+        assert!(load_dataset(&ragged).is_err());
+        assert!(load_dataset(&dir.path().join("data.xml")).is_err());
+    }
+
+    #[test]
+    fn test_render_prompts_per_row() {
+        let rows: Vec<DatasetRow> = vec![
+            [("sku", "A-1"), ("qty", "3")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            [("sku", "B-2"), ("qty", "0")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        ];
+        let prompts = render_prompts(
+            "Check stock for {{ sku }}: {{qty}} left. \\{{literal}}\n",
+            &rows,
+        )
+        .unwrap();
+        assert_eq!(
+            prompts,
+            vec![
+                "Check stock for A-1: 3 left. {{literal}}",
+                "Check stock for B-2: 0 left. {{literal}}",
+            ]
+        );
+
+        let err = render_prompts("{{skew}} on {{date}}", &rows).unwrap_err();
+        assert!(err.to_string().contains("no column 'skew'"));
+        assert!(render_prompts("Today is {{date}}", &rows).is_ok());
+    }
 }
@@ -23,6 +23,14 @@ pub struct InboundMessage {
     pub session_key: String,
     /// Additional metadata key-value pairs
     pub metadata: HashMap<String, String>,
+    /// Workspace to run in, overriding the chat's binding
+    /// (`workspaces.bindings`) and the active workspace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    /// Sent by ZeptoClaw itself (heartbeat, cron). Channels never set this,
+    /// so it cannot be claimed through a sender ID.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub internal: bool,
 }
 
 /// Represents an outgoing message to be sent via a channel
@@ -39,10 +47,38 @@ pub struct OutboundMessage {
     /// Additional metadata key-value pairs for channel-specific delivery hints
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, String>,
+    /// Files sent with the message (e.g. a rendered chart). Channels that
+    /// can't upload files send the text only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub media: Vec<MediaAttachment>,
+}
+
+/// What the agent is doing with an inbound message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleKind {
+    /// The agent picked the message up and is working on a reply.
+    Started,
+    /// The agent finished; the reply (or error) has been published.
+    Finished,
+}
+
+/// Agent progress on one inbound message, broadcast so channels can show
+/// typing indicators and read receipts.
+#[derive(Debug, Clone)]
+pub struct LifecycleEvent {
+    pub kind: LifecycleKind,
+    /// Channel the inbound message came from
+    pub channel: String,
+    /// Chat the inbound message came from
+    pub chat_id: String,
+    /// The inbound message's metadata (platform message IDs, thread IDs)
+    pub metadata: HashMap<String, String>,
+    /// The agent's reply, on `Finished` events for successful runs
+    pub reply: Option<String>,
 }
 
 /// Represents a media attachment (image, audio, video, or document)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MediaAttachment {
     /// The type of media
     pub media_type: MediaType,
@@ -96,6 +132,8 @@ impl InboundMessage {
             media: Vec::new(),
             session_key: format!("{}:{}", channel, chat_id),
             metadata: HashMap::new(),
+            workspace: None,
+            internal: false,
         }
     }
 
@@ -134,6 +172,34 @@ impl InboundMessage {
         self
     }
 
+    /// Runs the message in a named workspace (builder pattern).
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::message::InboundMessage;
+    ///
+    /// let msg = InboundMessage::new("cli", "user", "cli", "Hi").with_workspace("acme");
+    /// assert_eq!(msg.workspace.as_deref(), Some("acme"));
+    /// ```
+    pub fn with_workspace(mut self, workspace: &str) -> Self {
+        self.workspace = Some(workspace.to_string());
+        self
+    }
+
+    /// Marks the message as sent by ZeptoClaw itself (builder pattern).
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::message::InboundMessage;
+    ///
+    /// let msg = InboundMessage::new("telegram", "cron", "chat456", "Report").mark_internal();
+    /// assert!(msg.internal);
+    /// ```
+    pub fn mark_internal(mut self) -> Self {
+        self.internal = true;
+        self
+    }
+
     /// Checks if this message has any media attached.
     pub fn has_media(&self) -> bool {
         !self.media.is_empty()
@@ -162,6 +228,7 @@ impl OutboundMessage {
             content: content.to_string(),
             reply_to: None,
             metadata: HashMap::new(),
+            media: Vec::new(),
         }
     }
 
@@ -200,6 +267,56 @@ impl OutboundMessage {
     pub fn reply_to(msg: &InboundMessage, content: &str) -> Self {
         Self::new(&msg.channel, &msg.chat_id, content)
     }
+
+    /// Attaches a file to the outbound message (builder pattern).
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::message::{MediaAttachment, MediaType, OutboundMessage};
+    ///
+    /// let chart = MediaAttachment::new(MediaType::Image)
+    ///     .with_data(vec![0x89, b'P', b'N', b'G'])
+    ///     .with_mime_type("image/png");
+    /// let msg = OutboundMessage::new("telegram", "chat456", "Usage").with_media(chart);
+    /// assert!(msg.has_media());
+    /// ```
+    pub fn with_media(mut self, media: MediaAttachment) -> Self {
+        self.media.push(media);
+        self
+    }
+
+    /// Checks if this message has any files attached.
+    pub fn has_media(&self) -> bool {
+        !self.media.is_empty()
+    }
+}
+
+impl LifecycleEvent {
+    /// Event for `kind` on the inbound message `msg`.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::message::{InboundMessage, LifecycleEvent, LifecycleKind};
+    ///
+    /// let inbound = InboundMessage::new("telegram", "user123", "chat456", "Hello");
+    /// let event = LifecycleEvent::new(LifecycleKind::Started, &inbound);
+    /// assert_eq!(event.chat_id, "chat456");
+    /// ```
+    pub fn new(kind: LifecycleKind, msg: &InboundMessage) -> Self {
+        Self {
+            kind,
+            channel: msg.channel.clone(),
+            chat_id: msg.chat_id.clone(),
+            metadata: msg.metadata.clone(),
+            reply: None,
+        }
+    }
+
+    /// Attach the agent's reply.
+    pub fn with_reply(mut self, reply: &str) -> Self {
+        self.reply = Some(reply.to_string());
+        self
+    }
 }
 
 impl MediaAttachment {
@@ -41,17 +41,51 @@
 //! ```
 
 pub mod message;
+pub mod notify;
+pub mod queue;
 
-pub use message::{InboundMessage, MediaAttachment, MediaType, OutboundMessage};
+pub use message::{
+    InboundMessage, LifecycleEvent, LifecycleKind, MediaAttachment, MediaType, OutboundMessage,
+};
+pub use notify::{NotificationOutcome, NotificationPolicy, Priority};
+pub use queue::{InboundPriority, InboundQueue, InboundQueueStats, BUS_PRIORITY_METADATA_KEY};
 
+use crate::config::{BusConfig, BusOverflowPolicy};
 use crate::error::{Result, ZeptoError};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::time::Duration;
 use tokio::sync::Mutex;
+use tokio::sync::{broadcast, mpsc};
+use tracing::warn;
 
 /// Default buffer size for message channels
 const DEFAULT_BUFFER_SIZE: usize = 100;
 
+/// Buffer size for the lifecycle broadcast; slow subscribers skip old events.
+const LIFECYCLE_BUFFER_SIZE: usize = 64;
+
+/// Reply sent when the `reject` overflow policy refuses a message.
+const REJECTED_REPLY: &str =
+    "I'm receiving too many messages right now and couldn't take yours. Please try again in a moment.";
+
+/// How the bus treats inbound messages that find the buffer full.
+#[derive(Debug, Clone)]
+struct OverflowSettings {
+    policy: BusOverflowPolicy,
+    /// Zero waits indefinitely.
+    block_timeout: Duration,
+    notify_rejected: bool,
+}
+
+/// Running totals of inbound messages lost to a full buffer.
+#[derive(Debug, Default)]
+struct OverflowCounters {
+    dropped: AtomicU64,
+    rejected: AtomicU64,
+    timed_out: AtomicU64,
+}
+
 /// The central message bus for routing messages between channels and agents.
 ///
 /// The `MessageBus` maintains two separate channels:
@@ -59,16 +93,30 @@ const DEFAULT_BUFFER_SIZE: usize = 100;
 /// - **Outbound**: Messages from agents back to channels
 ///
 /// Both channels use async MPSC (multi-producer, single-consumer) queues
-/// backed by Tokio, allowing for high-throughput message passing.
+/// backed by Tokio, allowing for high-throughput message passing. Inbound
+/// messages are additionally ordered by priority and served round-robin
+/// across channels (see [`queue`]).
 pub struct MessageBus {
     /// Sender for inbound messages
     inbound_tx: mpsc::Sender<InboundMessage>,
     /// Receiver for inbound messages (wrapped in Arc<Mutex> for shared access)
     inbound_rx: Arc<Mutex<mpsc::Receiver<InboundMessage>>>,
+    /// Inbound messages taken off the channel, waiting for their turn
+    inbound_queue: Arc<std::sync::Mutex<InboundQueue>>,
+    /// Maximum number of messages sorted into `inbound_queue` at once
+    buffer_size: usize,
+    /// What to do when the inbound buffer is full
+    overflow: OverflowSettings,
+    /// Inbound messages dropped or refused so far
+    overflow_counters: Arc<OverflowCounters>,
     /// Sender for outbound messages
     outbound_tx: mpsc::Sender<OutboundMessage>,
     /// Receiver for outbound messages (wrapped in Arc<Mutex> for shared access)
     outbound_rx: Arc<Mutex<mpsc::Receiver<OutboundMessage>>>,
+    /// Broadcast of agent progress on inbound messages
+    lifecycle_tx: broadcast::Sender<LifecycleEvent>,
+    /// Policy applied to proactive notifications, if enabled
+    notifications: Option<Arc<NotificationPolicy>>,
 }
 
 impl MessageBus {
@@ -98,17 +146,45 @@ impl MessageBus {
     /// let bus = MessageBus::with_buffer_size(500);
     /// ```
     pub fn with_buffer_size(buffer_size: usize) -> Self {
+        Self::from_config(&BusConfig {
+            buffer_size,
+            ..Default::default()
+        })
+    }
+
+    /// Creates a new `MessageBus` with the buffer size and overflow policy
+    /// from `config`.
+    pub fn from_config(config: &BusConfig) -> Self {
+        let buffer_size = config.buffer_size.max(1);
         let (inbound_tx, inbound_rx) = mpsc::channel(buffer_size);
         let (outbound_tx, outbound_rx) = mpsc::channel(buffer_size);
+        let (lifecycle_tx, _) = broadcast::channel(LIFECYCLE_BUFFER_SIZE);
 
         Self {
             inbound_tx,
             inbound_rx: Arc::new(Mutex::new(inbound_rx)),
+            inbound_queue: Arc::new(std::sync::Mutex::new(InboundQueue::new())),
+            buffer_size,
+            overflow: OverflowSettings {
+                policy: config.overflow,
+                block_timeout: Duration::from_millis(config.block_timeout_ms),
+                notify_rejected: config.notify_rejected,
+            },
+            overflow_counters: Arc::new(OverflowCounters::default()),
             outbound_tx,
             outbound_rx: Arc::new(Mutex::new(outbound_rx)),
+            lifecycle_tx,
+            notifications: None,
         }
     }
 
+    /// Applies `policy` to notifications published with
+    /// [`publish_notification`](Self::publish_notification).
+    pub fn with_notification_policy(mut self, policy: Option<NotificationPolicy>) -> Self {
+        self.notifications = policy.map(Arc::new);
+        self
+    }
+
     /// Publishes an inbound message to the bus.
     ///
     /// This is typically called by channel adapters (e.g., Telegram, Discord)
@@ -117,8 +193,14 @@ impl MessageBus {
     /// # Arguments
     /// * `msg` - The inbound message to publish
     ///
+    /// When the buffer is full, the configured [`BusOverflowPolicy`] applies:
+    /// `block` waits for room (up to the configured timeout), `drop_oldest`
+    /// discards the oldest buffered message, `reject` fails at once.
+    ///
     /// # Errors
-    /// Returns `ZeptoError::BusClosed` if the receiver has been dropped.
+    /// Returns `ZeptoError::BusClosed` if the receiver has been dropped, and
+    /// `ZeptoError::Channel` if the message was refused because the buffer
+    /// is full.
     ///
     /// # Example
     /// ```
@@ -131,16 +213,48 @@ impl MessageBus {
     ///     bus.publish_inbound(msg).await.unwrap();
     /// }
     /// ```
-    pub async fn publish_inbound(&self, msg: InboundMessage) -> Result<()> {
-        self.inbound_tx
-            .send(msg)
+    pub async fn publish_inbound(&self, mut msg: InboundMessage) -> Result<()> {
+        // Stamp the enqueue time (kept if already set, e.g. re-queued
+        // follow-ups) so the agent can measure queue wait.
+        msg.metadata
+            .entry(crate::session::timeline::ENQUEUED_AT_METADATA_KEY.to_string())
+            .or_insert_with(|| chrono::Utc::now().timestamp_millis().to_string());
+        if self.overflow.policy != BusOverflowPolicy::Block {
+            return self.try_publish_inbound(msg);
+        }
+        if self.overflow.block_timeout.is_zero() {
+            return self
+                .inbound_tx
+                .send(msg)
+                .await
+                .map_err(|_| ZeptoError::BusClosed);
+        }
+        match self
+            .inbound_tx
+            .send_timeout(msg, self.overflow.block_timeout)
             .await
-            .map_err(|_| ZeptoError::BusClosed)
+        {
+            Ok(()) => Ok(()),
+            Err(mpsc::error::SendTimeoutError::Closed(_)) => Err(ZeptoError::BusClosed),
+            Err(mpsc::error::SendTimeoutError::Timeout(msg)) => {
+                self.overflow_counters
+                    .timed_out
+                    .fetch_add(1, Ordering::Relaxed);
+                let waited_ms = self.overflow.block_timeout.as_millis();
+                warn!(channel = %msg.channel, chat_id = %msg.chat_id, waited_ms, "Inbound buffer full, message not accepted");
+                Err(ZeptoError::Channel(format!(
+                    "inbound buffer full after waiting {}ms",
+                    waited_ms
+                )))
+            }
+        }
     }
 
     /// Consumes the next inbound message from the bus.
     ///
     /// This is typically called by agents waiting for new messages to process.
+    /// Of the messages waiting, the highest-priority one is returned, with
+    /// channels of the same priority taking turns.
     ///
     /// # Returns
     /// - `Some(InboundMessage)` if a message is available
@@ -165,7 +279,80 @@ impl MessageBus {
     /// }
     /// ```
     pub async fn consume_inbound(&self) -> Option<InboundMessage> {
-        self.inbound_rx.lock().await.recv().await
+        let mut rx = self.inbound_rx.lock().await;
+        loop {
+            {
+                let mut queue = self.lock_queue();
+                while queue.len() < self.buffer_size {
+                    match rx.try_recv() {
+                        Ok(msg) => queue.push(msg),
+                        Err(_) => break,
+                    }
+                }
+                if let Some(msg) = queue.pop() {
+                    return Some(msg);
+                }
+            }
+            // Nothing waiting: block until something is published. Safe to
+            // cancel, a received message is queued before the next await.
+            let msg = rx.recv().await?;
+            self.lock_queue().push(msg);
+        }
+    }
+
+    /// Consumes the next inbound message matching `pred`, leaving the others
+    /// queued in order.
+    ///
+    /// Only the oldest `buffer_size` messages are searched; the rest stay in
+    /// the bus channel, where the overflow policy applies. While none match,
+    /// this waits for more to be published. The agent uses it to keep
+    /// handling control messages (`/stop`, admin commands) while all of its
+    /// session slots are busy.
+    pub async fn consume_inbound_matching(
+        &self,
+        pred: impl Fn(&InboundMessage) -> bool,
+    ) -> Option<InboundMessage> {
+        let mut rx = self.inbound_rx.lock().await;
+        loop {
+            let full = {
+                let mut queue = self.lock_queue();
+                while queue.len() < self.buffer_size {
+                    match rx.try_recv() {
+                        Ok(msg) => queue.push(msg),
+                        Err(_) => break,
+                    }
+                }
+                if let Some(msg) = queue.take(&pred) {
+                    return Some(msg);
+                }
+                queue.len() >= self.buffer_size
+            };
+            if full {
+                // Nothing more can be looked at until a message is consumed.
+                std::future::pending::<()>().await;
+            }
+            let msg = rx.recv().await?;
+            self.lock_queue().push(msg);
+        }
+    }
+
+    /// Depth of the inbound queue, by priority and by channel, plus buffer
+    /// utilization and overflow totals.
+    pub fn inbound_stats(&self) -> InboundQueueStats {
+        let mut stats = self.lock_queue().stats();
+        stats.capacity = self.inbound_tx.max_capacity();
+        stats.unsorted = stats.capacity - self.inbound_tx.capacity();
+        stats.utilization = stats.unsorted as f64 / stats.capacity as f64;
+        stats.dropped = self.overflow_counters.dropped.load(Ordering::Relaxed);
+        stats.rejected = self.overflow_counters.rejected.load(Ordering::Relaxed);
+        stats.timed_out = self.overflow_counters.timed_out.load(Ordering::Relaxed);
+        stats
+    }
+
+    fn lock_queue(&self) -> std::sync::MutexGuard<'_, InboundQueue> {
+        self.inbound_queue
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
     }
 
     /// Publishes an outbound message to the bus.
@@ -197,6 +384,59 @@ impl MessageBus {
             .map_err(|_| ZeptoError::BusClosed)
     }
 
+    /// Publishes a proactive notification, subject to the notification
+    /// policy. Without a policy it is delivered like any outbound message.
+    ///
+    /// # Errors
+    /// Returns `ZeptoError::BusClosed` if the receiver has been dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::{MessageBus, NotificationOutcome, OutboundMessage, Priority};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let bus = MessageBus::new();
+    ///     let msg = OutboundMessage::new("telegram", "chat456", "Backup finished");
+    ///     let outcome = bus.publish_notification(msg, Priority::Low).await.unwrap();
+    ///     assert_eq!(outcome, NotificationOutcome::Delivered);
+    /// }
+    /// ```
+    pub async fn publish_notification(
+        &self,
+        msg: OutboundMessage,
+        priority: Priority,
+    ) -> Result<NotificationOutcome> {
+        let msg = match &self.notifications {
+            Some(policy) => match policy.admit(msg, priority, std::time::Instant::now()) {
+                Some(msg) => msg,
+                None => return Ok(NotificationOutcome::Digested),
+            },
+            None => msg,
+        };
+        self.publish_outbound(msg).await?;
+        Ok(NotificationOutcome::Delivered)
+    }
+
+    /// Publishes notification digests that are due. Returns how many were
+    /// sent.
+    pub async fn flush_notifications(&self) -> Result<usize> {
+        let Some(policy) = &self.notifications else {
+            return Ok(0);
+        };
+        let due = policy.take_due(std::time::Instant::now());
+        let count = due.len();
+        for msg in due {
+            self.publish_outbound(msg).await?;
+        }
+        Ok(count)
+    }
+
+    /// Whether a notification policy is configured.
+    pub fn has_notification_policy(&self) -> bool {
+        self.notifications.is_some()
+    }
+
     /// Consumes the next outbound message from the bus.
     ///
     /// This is typically called by channel adapters waiting for
@@ -209,6 +449,29 @@ impl MessageBus {
         self.outbound_rx.lock().await.recv().await
     }
 
+    /// Broadcasts agent progress on an inbound message.
+    ///
+    /// Dropped silently when nothing is subscribed.
+    pub fn publish_lifecycle(&self, event: LifecycleEvent) {
+        let _ = self.lifecycle_tx.send(event);
+    }
+
+    /// Subscribes to agent progress events.
+    ///
+    /// # Example
+    /// ```
+    /// use zeptoclaw::bus::{InboundMessage, LifecycleEvent, LifecycleKind, MessageBus};
+    ///
+    /// let bus = MessageBus::new();
+    /// let mut events = bus.subscribe_lifecycle();
+    /// let msg = InboundMessage::new("telegram", "user123", "chat456", "Hello");
+    /// bus.publish_lifecycle(LifecycleEvent::new(LifecycleKind::Started, &msg));
+    /// assert_eq!(events.try_recv().unwrap().kind, LifecycleKind::Started);
+    /// ```
+    pub fn subscribe_lifecycle(&self) -> broadcast::Receiver<LifecycleEvent> {
+        self.lifecycle_tx.subscribe()
+    }
+
     /// Returns a clone of the inbound message sender.
     ///
     /// This is useful for giving multiple channels their own sender
@@ -248,17 +511,69 @@ impl MessageBus {
     /// This is useful in non-async contexts or when you want to
     /// avoid blocking if the buffer is full.
     ///
+    /// With the `drop_oldest` policy a full buffer makes room by discarding
+    /// its oldest message; otherwise the message is refused, and with
+    /// `reject` the sender is told so on their channel.
+    ///
     /// # Returns
     /// - `Ok(())` if the message was successfully queued
     /// - `Err(ZeptoError::BusClosed)` if the channel is closed
     /// - `Err(ZeptoError::Channel)` if the buffer is full
-    pub fn try_publish_inbound(&self, msg: InboundMessage) -> Result<()> {
-        self.inbound_tx.try_send(msg).map_err(|e| match e {
-            mpsc::error::TrySendError::Full(_) => {
-                ZeptoError::Channel("inbound buffer full".to_string())
+    pub fn try_publish_inbound(&self, mut msg: InboundMessage) -> Result<()> {
+        loop {
+            match self.inbound_tx.try_send(msg) {
+                Ok(()) => return Ok(()),
+                Err(mpsc::error::TrySendError::Closed(_)) => return Err(ZeptoError::BusClosed),
+                Err(mpsc::error::TrySendError::Full(full)) => {
+                    if self.overflow.policy == BusOverflowPolicy::DropOldest
+                        && self.drop_oldest_inbound()
+                    {
+                        msg = full;
+                        continue;
+                    }
+                    self.overflow_counters
+                        .rejected
+                        .fetch_add(1, Ordering::Relaxed);
+                    warn!(channel = %full.channel, chat_id = %full.chat_id, "Inbound buffer full, message rejected");
+                    if self.overflow.policy == BusOverflowPolicy::Reject {
+                        self.notify_rejected(&full);
+                    }
+                    return Err(ZeptoError::Channel("inbound buffer full".to_string()));
+                }
             }
-            mpsc::error::TrySendError::Closed(_) => ZeptoError::BusClosed,
-        })
+        }
+    }
+
+    /// Discards the oldest message in the inbound channel. Returns `false`
+    /// if nothing could be taken (the consumer holds the receiver).
+    fn drop_oldest_inbound(&self) -> bool {
+        let Ok(mut rx) = self.inbound_rx.try_lock() else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(oldest) => {
+                self.overflow_counters
+                    .dropped
+                    .fetch_add(1, Ordering::Relaxed);
+                warn!(channel = %oldest.channel, chat_id = %oldest.chat_id, "Inbound buffer full, dropped oldest message");
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Tells a person their message was refused. Internal senders (cron,
+    /// heartbeat, skills reloads) are not notified.
+    fn notify_rejected(&self, msg: &InboundMessage) {
+        if !self.overflow.notify_rejected
+            || InboundPriority::of(msg) != InboundPriority::Interactive
+        {
+            return;
+        }
+        let reply = OutboundMessage::reply_to(msg, REJECTED_REPLY);
+        if self.outbound_tx.try_send(reply).is_err() {
+            warn!(channel = %msg.channel, "Could not send overflow notice, outbound buffer full");
+        }
     }
 
     /// Tries to publish an outbound message without blocking.
@@ -286,8 +601,14 @@ impl Clone for MessageBus {
         Self {
             inbound_tx: self.inbound_tx.clone(),
             inbound_rx: Arc::clone(&self.inbound_rx),
+            inbound_queue: Arc::clone(&self.inbound_queue),
+            buffer_size: self.buffer_size,
+            overflow: self.overflow.clone(),
+            overflow_counters: Arc::clone(&self.overflow_counters),
             outbound_tx: self.outbound_tx.clone(),
             outbound_rx: Arc::clone(&self.outbound_rx),
+            lifecycle_tx: self.lifecycle_tx.clone(),
+            notifications: self.notifications.clone(),
         }
     }
 }
@@ -510,4 +831,161 @@ mod tests {
         assert_eq!(outgoing.chat_id, "chat456");
         assert_eq!(outgoing.content, "Hello human!");
     }
+
+    #[tokio::test]
+    async fn test_lifecycle_broadcast_reaches_clones() {
+        let bus = MessageBus::new();
+        let clone = bus.clone();
+        let mut events = clone.subscribe_lifecycle();
+
+        let inbound = InboundMessage::new("telegram", "user123", "chat456", "Hello")
+            .with_metadata("telegram_thread_id", "7");
+        bus.publish_lifecycle(LifecycleEvent::new(LifecycleKind::Started, &inbound));
+        bus.publish_lifecycle(LifecycleEvent::new(LifecycleKind::Finished, &inbound));
+
+        let started = events.recv().await.unwrap();
+        assert_eq!(started.kind, LifecycleKind::Started);
+        assert_eq!(started.channel, "telegram");
+        assert_eq!(started.metadata["telegram_thread_id"], "7");
+        assert_eq!(events.recv().await.unwrap().kind, LifecycleKind::Finished);
+    }
+
+    #[tokio::test]
+    async fn test_notification_policy_holds_low_priority() {
+        let policy = NotificationPolicy::from_config(&crate::config::NotificationsConfig {
+            enabled: true,
+            ..Default::default()
+        });
+        let bus = MessageBus::new().with_notification_policy(policy);
+        assert!(bus.has_notification_policy());
+
+        let low = OutboundMessage::new("telegram", "chat456", "FYI");
+        let outcome = bus.publish_notification(low, Priority::Low).await.unwrap();
+        assert_eq!(outcome, NotificationOutcome::Digested);
+
+        let high = OutboundMessage::new("telegram", "chat456", "Server down");
+        let outcome = bus
+            .publish_notification(high, Priority::High)
+            .await
+            .unwrap();
+        assert_eq!(outcome, NotificationOutcome::Delivered);
+        assert_eq!(bus.consume_outbound().await.unwrap().content, "Server down");
+
+        // The digest is not due yet.
+        assert_eq!(bus.flush_notifications().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_consume_inbound_matching() {
+        let bus = MessageBus::with_buffer_size(2);
+        let is_stop = |m: &InboundMessage| m.content == "/stop";
+        let wait = Duration::from_millis(20);
+        for content in ["a", "b"] {
+            let msg = InboundMessage::new("telegram", "user1", "chat1", content);
+            bus.publish_inbound(msg).await.unwrap();
+        }
+        assert!(
+            tokio::time::timeout(wait, bus.consume_inbound_matching(is_stop))
+                .await
+                .is_err()
+        );
+
+        // Beyond the search window until a message is consumed.
+        let stop = InboundMessage::new("telegram", "user1", "chat1", "/stop");
+        bus.publish_inbound(stop).await.unwrap();
+        assert!(
+            tokio::time::timeout(wait, bus.consume_inbound_matching(is_stop))
+                .await
+                .is_err()
+        );
+        assert_eq!(bus.consume_inbound().await.unwrap().content, "a");
+        let found = bus.consume_inbound_matching(is_stop).await.unwrap();
+        assert_eq!(found.content, "/stop");
+        assert_eq!(bus.consume_inbound().await.unwrap().content, "b");
+    }
+
+    #[tokio::test]
+    async fn test_inbound_priority_and_fairness() {
+        let bus = MessageBus::new();
+        for i in 0..3 {
+            let msg = InboundMessage::new("discord", "user1", "chat1", &format!("d{}", i));
+            bus.publish_inbound(msg).await.unwrap();
+        }
+        let heartbeat = InboundMessage::new("telegram", "system", "chat2", "heartbeat");
+        bus.publish_inbound(heartbeat).await.unwrap();
+        let cli = InboundMessage::new("cli", "user", "cli", "hello");
+        bus.publish_inbound(cli).await.unwrap();
+        assert_eq!(bus.inbound_stats().depth(), 5);
+
+        assert_eq!(bus.consume_inbound().await.unwrap().content, "d0");
+        let stats = bus.inbound_stats();
+        assert_eq!(stats.unsorted, 0);
+        assert_eq!(stats.by_priority["interactive"], 3);
+        assert_eq!(stats.by_priority["background"], 1);
+        assert_eq!(stats.by_channel["discord"], 2);
+
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            order.push(bus.consume_inbound().await.unwrap().content);
+        }
+        assert_eq!(order, ["hello", "d1", "d2", "heartbeat"]);
+        assert_eq!(bus.inbound_stats().dispatched, 5);
+    }
+
+    fn overflow_bus(overflow: BusOverflowPolicy) -> MessageBus {
+        MessageBus::from_config(&BusConfig {
+            buffer_size: 2,
+            overflow,
+            block_timeout_ms: 20,
+            notify_rejected: true,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_overflow_drop_oldest() {
+        let bus = overflow_bus(BusOverflowPolicy::DropOldest);
+        for content in ["m1", "m2", "m3"] {
+            let msg = InboundMessage::new("telegram", "user1", "chat1", content);
+            bus.publish_inbound(msg).await.unwrap();
+        }
+        let stats = bus.inbound_stats();
+        assert_eq!(stats.dropped, 1);
+        assert_eq!(stats.utilization, 1.0);
+        assert_eq!(bus.consume_inbound().await.unwrap().content, "m2");
+        assert_eq!(bus.consume_inbound().await.unwrap().content, "m3");
+    }
+
+    #[tokio::test]
+    async fn test_overflow_reject_notifies_sender() {
+        let bus = overflow_bus(BusOverflowPolicy::Reject);
+        for content in ["m1", "m2"] {
+            let msg = InboundMessage::new("telegram", "user1", "chat1", content);
+            bus.publish_inbound(msg).await.unwrap();
+        }
+        let msg = InboundMessage::new("telegram", "user1", "chat1", "m3");
+        let result = bus.publish_inbound(msg).await;
+        assert!(matches!(result, Err(ZeptoError::Channel(_))));
+        assert_eq!(bus.inbound_stats().rejected, 1);
+
+        let notice = bus.consume_outbound().await.unwrap();
+        assert_eq!(notice.chat_id, "chat1");
+        assert_eq!(notice.content, REJECTED_REPLY);
+
+        // Internal senders are refused silently.
+        let cron = InboundMessage::new("telegram", "cron", "chat1", "job");
+        assert!(bus.publish_inbound(cron).await.is_err());
+        let next = tokio::time::timeout(Duration::from_millis(20), bus.consume_outbound()).await;
+        assert!(next.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_overflow_block_times_out() {
+        let bus = overflow_bus(BusOverflowPolicy::Block);
+        for content in ["m1", "m2", "m3"] {
+            let msg = InboundMessage::new("telegram", "user1", "chat1", content);
+            let result = bus.publish_inbound(msg).await;
+            assert_eq!(result.is_ok(), content != "m3");
+        }
+        assert_eq!(bus.inbound_stats().timed_out, 1);
+    }
 }
@@ -0,0 +1,282 @@
+//! Notification policy for proactive messages.
+//!
+//! Proactive notifications (sent with [`MessageBus::publish_notification`])
+//! pass through a [`NotificationPolicy`] before they reach the outbound
+//! queue. Replies published with `publish_outbound` bypass it.
+//!
+//! - `High` priority is always delivered.
+//! - `Normal` is delivered unless it is quiet hours or the channel is over
+//!   its hourly rate limit, in which case it joins the chat's digest.
+//! - `Low` always joins the digest.
+//!
+//! A digest collects notifications for one chat and is sent as a single
+//! summary message once it is `digest_interval_secs` old and quiet hours
+//! are over. [`spawn_digest_flusher`] drives that from the gateway.
+//!
+//! [`MessageBus::publish_notification`]: super::MessageBus::publish_notification
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::{debug, warn};
+
+use crate::config::NotificationsConfig;
+use crate::heartbeat::QuietHours;
+
+use super::{MessageBus, OutboundMessage};
+
+/// Window for per-channel rate limits.
+const RATE_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// How often the gateway checks for due digests.
+pub const DIGEST_FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Importance of a proactive notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl Priority {
+    /// Parse "low", "normal" or "high" (case-insensitive).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "low" => Some(Self::Low),
+            "normal" => Some(Self::Normal),
+            "high" | "urgent" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+
+/// What the policy did with a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationOutcome {
+    /// Published to the outbound queue.
+    Delivered,
+    /// Held for the chat's next digest.
+    Digested,
+}
+
+#[derive(Debug)]
+struct Digest {
+    since: Instant,
+    items: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct PolicyState {
+    /// Channel → delivery times within the rate window.
+    sent: HashMap<String, VecDeque<Instant>>,
+    /// (channel, chat_id) → pending digest.
+    digests: BTreeMap<(String, String), Digest>,
+}
+
+/// Rate limits, quiet hours and digest batching for proactive messages.
+#[derive(Debug)]
+pub struct NotificationPolicy {
+    rate_limit_per_hour: u32,
+    channel_rate_limits: HashMap<String, u32>,
+    quiet_hours: Option<QuietHours>,
+    digest_interval: Duration,
+    state: Mutex<PolicyState>,
+}
+
+impl NotificationPolicy {
+    /// Policy from config, or `None` when disabled.
+    pub fn from_config(config: &NotificationsConfig) -> Option<Self> {
+        config.enabled.then(|| Self {
+            rate_limit_per_hour: config.rate_limit_per_hour,
+            channel_rate_limits: config.channel_rate_limits.clone(),
+            quiet_hours: QuietHours::from_config(&config.quiet_hours),
+            digest_interval: Duration::from_secs(config.digest_interval_secs),
+            state: Mutex::new(PolicyState::default()),
+        })
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, PolicyState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn is_quiet(&self) -> bool {
+        self.quiet_hours.is_some_and(|q| q.is_quiet_now())
+    }
+
+    /// Decide what to do with `msg`. Returns the message if it should be
+    /// delivered now; otherwise it has been added to its chat's digest.
+    pub(crate) fn admit(
+        &self,
+        msg: OutboundMessage,
+        priority: Priority,
+        now: Instant,
+    ) -> Option<OutboundMessage> {
+        let quiet = self.is_quiet();
+        let mut state = self.state();
+        let limit = self
+            .channel_rate_limits
+            .get(&msg.channel)
+            .copied()
+            .unwrap_or(self.rate_limit_per_hour) as usize;
+        let sent = state.sent.entry(msg.channel.clone()).or_default();
+        while sent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= RATE_WINDOW)
+        {
+            sent.pop_front();
+        }
+
+        let deliver = match priority {
+            Priority::High => true,
+            Priority::Normal => !quiet && sent.len() < limit,
+            Priority::Low => false,
+        };
+        if deliver {
+            sent.push_back(now);
+            return Some(msg);
+        }
+
+        debug!(
+            channel = %msg.channel,
+            ?priority,
+            quiet,
+            "Notification held for digest"
+        );
+        state
+            .digests
+            .entry((msg.channel, msg.chat_id))
+            .or_insert_with(|| Digest {
+                since: now,
+                items: Vec::new(),
+            })
+            .items
+            .push(msg.content);
+        None
+    }
+
+    /// Take the digests that are due as of `now`, one message per chat.
+    /// Nothing is due during quiet hours.
+    pub(crate) fn take_due(&self, now: Instant) -> Vec<OutboundMessage> {
+        if self.is_quiet() {
+            return Vec::new();
+        }
+        let mut state = self.state();
+        let due: Vec<(String, String)> = state
+            .digests
+            .iter()
+            .filter(|(_, d)| now.duration_since(d.since) >= self.digest_interval)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        due.into_iter()
+            .filter_map(|key| {
+                let digest = state.digests.remove(&key)?;
+                state.sent.entry(key.0.clone()).or_default().push_back(now);
+                Some(OutboundMessage::new(
+                    &key.0,
+                    &key.1,
+                    &render_digest(&digest.items),
+                ))
+            })
+            .collect()
+    }
+}
+
+/// One summary message for a batch of notifications.
+fn render_digest(items: &[String]) -> String {
+    if let [only] = items {
+        return only.clone();
+    }
+    let mut text = format!("Digest ({} notifications):", items.len());
+    for item in items {
+        text.push_str("\n\n- ");
+        text.push_str(&item.trim().replace('\n', "\n  "));
+    }
+    text
+}
+
+/// Periodically publish due digests from `bus`.
+pub fn spawn_digest_flusher(bus: Arc<MessageBus>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(DIGEST_FLUSH_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = bus.flush_notifications().await {
+                warn!(error = %e, "Failed to publish notification digest");
+                break;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(rate_limit_per_hour: u32) -> NotificationPolicy {
+        NotificationPolicy::from_config(&NotificationsConfig {
+            enabled: true,
+            rate_limit_per_hour,
+            digest_interval_secs: 600,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn note(text: &str) -> OutboundMessage {
+        OutboundMessage::new("telegram", "42", text)
+    }
+
+    #[test]
+    fn test_priority_parse() {
+        assert_eq!(Priority::parse("LOW"), Some(Priority::Low));
+        assert_eq!(Priority::parse("urgent"), Some(Priority::High));
+        assert_eq!(Priority::parse("meh"), None);
+        assert_eq!(Priority::default(), Priority::Normal);
+    }
+
+    #[test]
+    fn test_disabled_policy() {
+        assert!(NotificationPolicy::from_config(&NotificationsConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_digests_normal_but_not_high() {
+        let policy = policy(2);
+        let now = Instant::now();
+        assert!(policy.admit(note("a"), Priority::Normal, now).is_some());
+        assert!(policy.admit(note("b"), Priority::Normal, now).is_some());
+        assert!(policy.admit(note("c"), Priority::Normal, now).is_none());
+        assert!(policy.admit(note("d"), Priority::High, now).is_some());
+
+        // The window slides.
+        let later = now + RATE_WINDOW;
+        assert!(policy.admit(note("e"), Priority::Normal, later).is_some());
+    }
+
+    #[test]
+    fn test_low_priority_batched_into_one_digest() {
+        let policy = policy(10);
+        let now = Instant::now();
+        assert!(policy.admit(note("first"), Priority::Low, now).is_none());
+        assert!(policy.admit(note("second"), Priority::Low, now).is_none());
+        assert!(policy.take_due(now).is_empty());
+
+        let due = policy.take_due(now + Duration::from_secs(600));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].chat_id, "42");
+        assert_eq!(
+            due[0].content,
+            "Digest (2 notifications):\n\n- first\n\n- second"
+        );
+        assert!(policy.take_due(now + Duration::from_secs(1200)).is_empty());
+    }
+
+    #[test]
+    fn test_single_item_digest_is_sent_as_is() {
+        assert_eq!(render_digest(&["only".to_string()]), "only");
+    }
+}
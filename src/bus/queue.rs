@@ -0,0 +1,280 @@
+//! Inbound scheduling: priority levels and per-channel fairness.
+//!
+//! Published messages are sorted into three levels — system (internal
+//! control such as skills reloads), interactive (people) and background
+//! (cron jobs, heartbeats) — and always taken from the highest non-empty
+//! level. Within a level, channels take turns, so a burst from one channel
+//! does not hold up the others; each channel's own messages stay in order.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use serde::Serialize;
+
+use super::InboundMessage;
+
+/// Metadata key that overrides the inferred priority
+/// (`"system"`, `"interactive"` or `"background"`).
+pub const BUS_PRIORITY_METADATA_KEY: &str = "bus_priority";
+
+/// Scheduling level of an inbound message, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InboundPriority {
+    System,
+    Interactive,
+    Background,
+}
+
+impl InboundPriority {
+    const ALL: [InboundPriority; 3] = [Self::System, Self::Interactive, Self::Background];
+
+    /// Priority of a message: the metadata override if valid, `System` for
+    /// the internal `system` channel, `Background` for cron and heartbeat
+    /// senders, `Interactive` otherwise.
+    pub fn of(msg: &InboundMessage) -> Self {
+        if let Some(priority) = msg
+            .metadata
+            .get(BUS_PRIORITY_METADATA_KEY)
+            .and_then(|v| Self::parse(v))
+        {
+            return priority;
+        }
+        if msg.channel == "system" {
+            Self::System
+        } else if matches!(msg.sender_id.as_str(), "cron" | "system") {
+            Self::Background
+        } else {
+            Self::Interactive
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "system" => Some(Self::System),
+            "interactive" => Some(Self::Interactive),
+            "background" => Some(Self::Background),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::Interactive => "interactive",
+            Self::Background => "background",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// One priority level: a queue per channel, served round-robin.
+#[derive(Default)]
+struct Level {
+    turns: VecDeque<String>,
+    queues: HashMap<String, VecDeque<InboundMessage>>,
+    len: usize,
+}
+
+impl Level {
+    fn push(&mut self, msg: InboundMessage) {
+        let queue = self.queues.entry(msg.channel.clone()).or_default();
+        if queue.is_empty() {
+            self.turns.push_back(msg.channel.clone());
+        }
+        queue.push_back(msg);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<InboundMessage> {
+        let channel = self.turns.pop_front()?;
+        let queue = self.queues.get_mut(&channel)?;
+        let msg = queue.pop_front();
+        if queue.is_empty() {
+            self.queues.remove(&channel);
+        } else {
+            self.turns.push_back(channel);
+        }
+        self.len -= 1;
+        msg
+    }
+
+    /// Remove the first message matching `pred`, looking at channels in
+    /// turn order. Turns are not rotated.
+    fn take(&mut self, pred: &impl Fn(&InboundMessage) -> bool) -> Option<InboundMessage> {
+        let (channel, index) = self.turns.iter().find_map(|channel| {
+            let index = self.queues.get(channel)?.iter().position(pred)?;
+            Some((channel.clone(), index))
+        })?;
+        let queue = self.queues.get_mut(&channel)?;
+        let msg = queue.remove(index);
+        if queue.is_empty() {
+            self.queues.remove(&channel);
+            self.turns.retain(|c| *c != channel);
+        }
+        self.len -= 1;
+        msg
+    }
+}
+
+/// Inbound messages waiting for the agent, ordered by priority and
+/// channel turn.
+#[derive(Default)]
+pub struct InboundQueue {
+    levels: [Level; 3],
+    dispatched: u64,
+}
+
+impl InboundQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, msg: InboundMessage) {
+        let level = InboundPriority::of(&msg).index();
+        self.levels[level].push(msg);
+    }
+
+    /// Next message to process.
+    pub fn pop(&mut self) -> Option<InboundMessage> {
+        let msg = self.levels.iter_mut().find_map(Level::pop)?;
+        self.dispatched += 1;
+        Some(msg)
+    }
+
+    /// Take the highest-priority message matching `pred`, leaving the
+    /// others in place.
+    pub fn take(&mut self, pred: impl Fn(&InboundMessage) -> bool) -> Option<InboundMessage> {
+        let msg = self.levels.iter_mut().find_map(|level| level.take(&pred))?;
+        self.dispatched += 1;
+        Some(msg)
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels.iter().map(|l| l.len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queue depths; the buffer and overflow fields are filled in by the bus.
+    pub fn stats(&self) -> InboundQueueStats {
+        let mut stats = InboundQueueStats {
+            dispatched: self.dispatched,
+            ..Default::default()
+        };
+        for priority in InboundPriority::ALL {
+            let level = &self.levels[priority.index()];
+            stats.by_priority.insert(priority.as_str(), level.len);
+            for (channel, queue) in &level.queues {
+                *stats.by_channel.entry(channel.clone()).or_default() += queue.len();
+            }
+        }
+        stats
+    }
+}
+
+/// Snapshot of the inbound queue for health and metrics output.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InboundQueueStats {
+    /// Published but not yet sorted (still in the bus channel).
+    pub unsorted: usize,
+    /// Sorted and waiting, per priority level.
+    pub by_priority: BTreeMap<&'static str, usize>,
+    /// Sorted and waiting, per channel.
+    pub by_channel: BTreeMap<String, usize>,
+    /// Messages handed to the agent so far.
+    pub dispatched: u64,
+    /// Size of the bus channel.
+    pub capacity: usize,
+    /// `unsorted / capacity`: how close publishers are to overflowing.
+    pub utilization: f64,
+    /// Messages discarded by the `drop_oldest` overflow policy.
+    pub dropped: u64,
+    /// Messages refused because the buffer was full.
+    pub rejected: u64,
+    /// Messages refused after `block` waited too long.
+    pub timed_out: u64,
+}
+
+impl InboundQueueStats {
+    /// Everything waiting, sorted or not.
+    pub fn depth(&self) -> usize {
+        self.unsorted + self.by_priority.values().sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(channel: &str, sender: &str, content: &str) -> InboundMessage {
+        InboundMessage::new(channel, sender, "chat", content)
+    }
+
+    #[test]
+    fn test_priority_of() {
+        assert_eq!(
+            InboundPriority::of(&msg("system", "skills-watcher", "")),
+            InboundPriority::System
+        );
+        assert_eq!(
+            InboundPriority::of(&msg("telegram", "cron", "job")),
+            InboundPriority::Background
+        );
+        assert_eq!(
+            InboundPriority::of(&msg("telegram", "system", "heartbeat")),
+            InboundPriority::Background
+        );
+        assert_eq!(
+            InboundPriority::of(&msg("cli", "user", "hi")),
+            InboundPriority::Interactive
+        );
+        let pinned = msg("telegram", "cron", "urgent")
+            .with_metadata(BUS_PRIORITY_METADATA_KEY, "interactive");
+        assert_eq!(InboundPriority::of(&pinned), InboundPriority::Interactive);
+    }
+
+    #[test]
+    fn test_priority_then_round_robin() {
+        let mut queue = InboundQueue::new();
+        for i in 0..3 {
+            queue.push(msg("discord", "u", &format!("d{}", i)));
+        }
+        queue.push(msg("telegram", "cron", "job"));
+        queue.push(msg("cli", "user", "c0"));
+        queue.push(msg("system", "skills-watcher", "reload"));
+        queue.push(msg("cli", "user", "c1"));
+
+        let stats = queue.stats();
+        assert_eq!(stats.by_priority["interactive"], 5);
+        assert_eq!(stats.by_channel["discord"], 3);
+        assert_eq!(stats.depth(), 7);
+
+        let order: Vec<String> = std::iter::from_fn(|| queue.pop())
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(order, ["reload", "d0", "c0", "d1", "c1", "d2", "job"]);
+        assert!(queue.is_empty());
+        assert_eq!(queue.stats().dispatched, 7);
+    }
+
+    #[test]
+    fn test_take_leaves_others_in_order() {
+        let mut queue = InboundQueue::new();
+        for content in ["a1", "/stop", "a2"] {
+            queue.push(msg("discord", "u", content));
+        }
+        queue.push(msg("cli", "user", "c0"));
+
+        let stop = queue.take(|m| m.content.starts_with('/')).unwrap();
+        assert_eq!(stop.content, "/stop");
+        assert!(queue.take(|m| m.content == "missing").is_none());
+        let order: Vec<String> = std::iter::from_fn(|| queue.pop())
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(order, ["a1", "c0", "a2"]);
+    }
+}
@@ -46,9 +46,7 @@ impl ResponseCache {
     /// Loads existing entries from `~/.zeptoclaw/cache/responses.json` on disk.
     /// `max_entries` is clamped to a minimum of 1 to prevent infinite loops.
     pub fn new(ttl_secs: u64, max_entries: usize) -> Self {
-        let path = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zeptoclaw")
+        let path = crate::config::Config::state_dir()
             .join("cache")
             .join("responses.json");
         let store = Self::load_from_disk(&path);
@@ -0,0 +1,272 @@
+//! Device channel -- delivers agent replies to paired devices.
+//!
+//! Paired devices (mobile apps, scripts) talk to the gateway over its HTTP
+//! API instead of a chat platform. Their messages are published on the bus
+//! with channel `"device"`; replies routed back to that channel are
+//! broadcast on a [`DeviceHub`], which API handlers subscribe to.
+//!
+//! The hub also carries agent status for device requests (run and tool
+//! progress, streamed tokens) as [`DeviceEvent`]s, which the gateway's
+//! `/ws` endpoint pushes to connected devices.
+//!
+//! A reply to a specific request carries that request's id in
+//! [`DEVICE_REQUEST_ID_KEY`] metadata so a waiting handler can pick it out
+//! from other traffic for the same chat.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use async_trait::async_trait;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::debug;
+
+use crate::bus::{InboundMessage, OutboundMessage};
+use crate::error::Result;
+
+use super::{BaseChannelConfig, Channel};
+
+/// Channel name used for device traffic on the bus.
+pub const DEVICE_CHANNEL: &str = "device";
+
+/// Metadata key correlating a device request with its reply.
+pub const DEVICE_REQUEST_ID_KEY: &str = "device_request_id";
+
+/// Events buffered per subscriber before slow readers start losing them.
+///
+/// Token streaming produces one event per chunk, so this is sized for a
+/// few long replies in flight.
+const HUB_CAPACITY: usize = 1024;
+
+/// Something that happened in a device chat.
+///
+/// Serializes flat, e.g.
+/// `{"chat_id": "phone", "request_id": "...", "type": "tool_started", "tool": "shell"}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceEvent {
+    pub chat_id: String,
+    /// Id of the request this event belongs to, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(flatten)]
+    pub kind: DeviceEventKind,
+}
+
+/// Payload of a [`DeviceEvent`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeviceEventKind {
+    /// A message sent to the chat (replies, tool output, cron results).
+    Message { content: String },
+    /// The agent started working on a request.
+    AgentStarted,
+    /// The agent finished a request.
+    AgentDone { success: bool, latency_ms: u64 },
+    /// A tool call started.
+    ToolStarted { tool: String },
+    /// A tool call succeeded.
+    ToolDone { tool: String, duration_ms: u64 },
+    /// A tool call failed.
+    ToolFailed { tool: String, error: String },
+    /// A chunk of the streamed final response.
+    Token { delta: String },
+}
+
+/// Fan-out point for device messages and agent status events.
+#[derive(Clone)]
+pub struct DeviceHub {
+    tx: broadcast::Sender<DeviceEvent>,
+}
+
+impl DeviceHub {
+    /// Create a hub with no subscribers.
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(HUB_CAPACITY);
+        Self { tx }
+    }
+
+    /// Subscribe to every device event from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<DeviceEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Broadcast an outbound message as a [`DeviceEventKind::Message`].
+    /// Returns how many subscribers received it.
+    pub fn publish(&self, msg: OutboundMessage) -> usize {
+        self.emit(DeviceEvent {
+            request_id: msg.metadata.get(DEVICE_REQUEST_ID_KEY).cloned(),
+            chat_id: msg.chat_id,
+            kind: DeviceEventKind::Message {
+                content: msg.content,
+            },
+        })
+    }
+
+    /// Broadcast an event. Returns how many subscribers received it.
+    pub fn emit(&self, event: DeviceEvent) -> usize {
+        self.tx.send(event).unwrap_or(0)
+    }
+}
+
+impl Default for DeviceHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Emits status events for one inbound device request.
+#[derive(Clone)]
+pub struct DeviceEmitter {
+    hub: DeviceHub,
+    chat_id: String,
+    request_id: Option<String>,
+}
+
+impl DeviceEmitter {
+    /// Emitter for `msg`, or `None` when it did not come from a device.
+    pub fn for_message(hub: &DeviceHub, msg: &InboundMessage) -> Option<Self> {
+        (msg.channel == DEVICE_CHANNEL).then(|| Self {
+            hub: hub.clone(),
+            chat_id: msg.chat_id.clone(),
+            request_id: msg.metadata.get(DEVICE_REQUEST_ID_KEY).cloned(),
+        })
+    }
+
+    /// Emit an event tagged with the request's chat and id.
+    pub fn emit(&self, kind: DeviceEventKind) {
+        self.hub.emit(DeviceEvent {
+            chat_id: self.chat_id.clone(),
+            request_id: self.request_id.clone(),
+            kind,
+        });
+    }
+}
+
+/// Virtual channel that hands outbound device messages to a [`DeviceHub`].
+pub struct DeviceChannel {
+    config: BaseChannelConfig,
+    hub: DeviceHub,
+    running: AtomicBool,
+}
+
+impl DeviceChannel {
+    /// Create a device channel publishing into `hub`.
+    pub fn new(hub: DeviceHub) -> Self {
+        Self {
+            config: BaseChannelConfig::new(DEVICE_CHANNEL),
+            hub,
+            running: AtomicBool::new(false),
+        }
+    }
+}
+
+#[async_trait]
+impl Channel for DeviceChannel {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        self.running.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn send(&self, msg: OutboundMessage) -> Result<()> {
+        let chat_id = msg.chat_id.clone();
+        if self.hub.publish(msg) == 0 {
+            debug!(chat_id = %chat_id, "No device connected; dropping device message");
+        }
+        Ok(())
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn is_allowed(&self, user_id: &str) -> bool {
+        self.config.is_allowed(user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_send_reaches_subscribers() {
+        let hub = DeviceHub::new();
+        let mut rx = hub.subscribe();
+        let mut channel = DeviceChannel::new(hub.clone());
+        channel.start().await.unwrap();
+        assert!(channel.is_running());
+
+        channel
+            .send(OutboundMessage::new(DEVICE_CHANNEL, "phone", "hi"))
+            .await
+            .unwrap();
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.chat_id, "phone");
+        assert_eq!(event.request_id, None);
+        assert_eq!(
+            event.kind,
+            DeviceEventKind::Message {
+                content: "hi".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_without_subscribers_is_ok() {
+        let channel = DeviceChannel::new(DeviceHub::new());
+        assert_eq!(channel.name(), DEVICE_CHANNEL);
+        assert!(channel
+            .send(OutboundMessage::new(DEVICE_CHANNEL, "phone", "hi"))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_emitter_tags_device_requests_only() {
+        let hub = DeviceHub::new();
+        let mut rx = hub.subscribe();
+
+        let other = InboundMessage::new("telegram", "user", "chat", "hi");
+        assert!(DeviceEmitter::for_message(&hub, &other).is_none());
+
+        let msg = InboundMessage::new(DEVICE_CHANNEL, "phone", "kitchen", "hi")
+            .with_metadata(DEVICE_REQUEST_ID_KEY, "req-1");
+        let emitter = DeviceEmitter::for_message(&hub, &msg).unwrap();
+        emitter.emit(DeviceEventKind::ToolStarted {
+            tool: "shell".to_string(),
+        });
+
+        let event = rx.recv().await.unwrap();
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "chat_id": "kitchen",
+                "request_id": "req-1",
+                "type": "tool_started",
+                "tool": "shell",
+            })
+        );
+    }
+
+    #[test]
+    fn test_unit_event_serializes_with_type_only() {
+        let event = DeviceEvent {
+            chat_id: "phone".to_string(),
+            request_id: None,
+            kind: DeviceEventKind::AgentStarted,
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({ "chat_id": "phone", "type": "agent_started" })
+        );
+    }
+}
@@ -36,7 +36,8 @@ use crate::bus::{InboundMessage, MediaAttachment, MediaType, MessageBus, Outboun
 use crate::config::DiscordConfig;
 use crate::error::{Result, ZeptoError};
 
-use super::{BaseChannelConfig, Channel};
+use super::formatting::{format_message, Dialect};
+use super::{group_history, BaseChannelConfig, Channel};
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -119,6 +120,12 @@ struct MessageCreateData {
     /// File attachments on this message (images, etc.).
     #[serde(default)]
     attachments: Vec<DiscordAttachment>,
+    /// Guild (server) ID; absent for direct messages.
+    #[serde(default)]
+    guild_id: Option<String>,
+    /// Users mentioned in the message.
+    #[serde(default)]
+    mentions: Vec<MessageAuthor>,
 }
 
 /// Author of a Discord message.
@@ -129,6 +136,9 @@ struct MessageAuthor {
     /// Whether the user is a bot.
     #[serde(default)]
     bot: Option<bool>,
+    /// Display username.
+    #[serde(default)]
+    username: Option<String>,
 }
 
 /// Response from GET /gateway.
@@ -602,8 +612,17 @@ impl DiscordChannel {
             return None;
         }
 
-        let inbound = InboundMessage::new("discord", &sender_id, &channel_id, &content)
+        let mut inbound = InboundMessage::new("discord", &sender_id, &channel_id, &content)
             .with_metadata("discord_message_id", &msg.id);
+        if let Some(name) = msg.author.username.as_deref().filter(|n| !n.is_empty()) {
+            inbound = inbound.with_metadata("sender_name", name);
+        }
+        if msg.guild_id.is_some() {
+            inbound = inbound.with_metadata(group_history::IS_GROUP_METADATA_KEY, "true");
+            if msg.mentions.iter().any(|m| m.bot.unwrap_or(false)) {
+                inbound = inbound.with_metadata(group_history::ADDRESSED_METADATA_KEY, "true");
+            }
+        }
 
         Some(inbound)
     }
@@ -1270,28 +1289,36 @@ impl Channel for DiscordChannel {
             return Ok(());
         }
 
-        let payload = Self::build_send_payload(&msg)?;
         let url = format!("{}/channels/{}/messages", DISCORD_API_BASE, channel_id);
+        let chunks = format_message(&msg.content, Dialect::Discord, DISCORD_MAX_MESSAGE_LENGTH);
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            // Only the first chunk replies to the original message.
+            let part = OutboundMessage {
+                content: chunk.text,
+                reply_to: if i == 0 { msg.reply_to.clone() } else { None },
+                ..msg.clone()
+            };
+            let payload = Self::build_send_payload(&part)?;
+            let response = self
+                .http_client
+                .post(&url)
+                .header("Authorization", format!("Bot {}", token))
+                .json(&payload)
+                .send()
+                .await
+                .map_err(|e| ZeptoError::Channel(format!("Failed to call Discord API: {}", e)))?;
 
-        let response = self
-            .http_client
-            .post(&url)
-            .header("Authorization", format!("Bot {}", token))
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|e| ZeptoError::Channel(format!("Failed to call Discord API: {}", e)))?;
-
-        let status = response.status();
-        let body = response.text().await.map_err(|e| {
-            ZeptoError::Channel(format!("Failed to read Discord API response: {}", e))
-        })?;
+            let status = response.status();
+            let body = response.text().await.map_err(|e| {
+                ZeptoError::Channel(format!("Failed to read Discord API response: {}", e))
+            })?;
 
-        if !status.is_success() {
-            return Err(ZeptoError::Channel(format!(
-                "Discord API returned HTTP {}: {}",
-                status, body
-            )));
+            if !status.is_success() {
+                return Err(ZeptoError::Channel(format!(
+                    "Discord API returned HTTP {}: {}",
+                    status, body
+                )));
+            }
         }
 
         info!("Discord: message sent successfully");
@@ -1427,6 +1454,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_message_create_guild_marks_group_and_mentions() {
+        let data = json!({
+            "id": "msg-010",
+            "content": "<@999> what do you think?",
+            "channel_id": "ch-200",
+            "guild_id": "guild-1",
+            "author": { "id": "user-7", "username": "alice" },
+            "mentions": [{ "id": "999", "bot": true }]
+        });
+        let msg = DiscordChannel::parse_message_create(&data, &[], false).unwrap();
+        assert_eq!(
+            msg.metadata.get("is_group").map(String::as_str),
+            Some("true")
+        );
+        assert_eq!(
+            msg.metadata.get("addressed").map(String::as_str),
+            Some("true")
+        );
+        assert_eq!(
+            msg.metadata.get("sender_name").map(String::as_str),
+            Some("alice")
+        );
+
+        let data = json!({
+            "id": "msg-011",
+            "content": "lunch?",
+            "channel_id": "ch-200",
+            "guild_id": "guild-1",
+            "author": { "id": "user-7" }
+        });
+        let msg = DiscordChannel::parse_message_create(&data, &[], false).unwrap();
+        assert_eq!(
+            msg.metadata.get("is_group").map(String::as_str),
+            Some("true")
+        );
+        assert!(!msg.metadata.contains_key("addressed"));
+    }
+
     #[test]
     fn test_message_create_with_allowlist() {
         let data = json!({
@@ -48,6 +48,47 @@ use crate::error::{Result, ZeptoError};
 
 use super::{BaseChannelConfig, Channel};
 
+/// Authenticated IMAP session over implicit TLS.
+#[cfg(feature = "channel-email")]
+pub(crate) type ImapSession =
+    async_imap::Session<tokio_rustls::client::TlsStream<tokio::net::TcpStream>>;
+
+/// Open an implicit-TLS connection to an IMAP server, ready for login or
+/// `AUTHENTICATE`. Shared with the `email` tool.
+#[cfg(feature = "channel-email")]
+pub(crate) async fn imap_tls_client(
+    host: &str,
+    port: u16,
+) -> Result<async_imap::Client<tokio_rustls::client::TlsStream<tokio::net::TcpStream>>> {
+    use rustls::{ClientConfig as RustlsClientConfig, RootCertStore};
+    use tokio::net::TcpStream;
+    use tokio_rustls::TlsConnector;
+
+    let addr = format!("{}:{}", host, port);
+
+    let tcp = TcpStream::connect(&addr)
+        .await
+        .map_err(|e| ZeptoError::Channel(format!("IMAP TCP connect failed: {e}")))?;
+
+    let cert_store = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.into(),
+    };
+    let tls_cfg = RustlsClientConfig::builder()
+        .with_root_certificates(cert_store)
+        .with_no_client_auth();
+
+    let connector = TlsConnector::from(Arc::new(tls_cfg));
+    let sni = rustls::pki_types::ServerName::try_from(host.to_string())
+        .map_err(|e| ZeptoError::Channel(format!("Invalid IMAP hostname: {e}")))?;
+
+    let tls_stream = connector
+        .connect(sni, tcp)
+        .await
+        .map_err(|e| ZeptoError::Channel(format!("IMAP TLS handshake failed: {e}")))?;
+
+    Ok(async_imap::Client::new(tls_stream))
+}
+
 // ---------------------------------------------------------------------------
 // Channel struct (always compiled)
 // ---------------------------------------------------------------------------
@@ -157,39 +198,8 @@ impl EmailChannel {
 
     /// Connect to the IMAP server with implicit TLS (port 993) and authenticate.
     #[cfg(feature = "channel-email")]
-    async fn connect_imap(
-        &self,
-    ) -> std::result::Result<
-        async_imap::Session<tokio_rustls::client::TlsStream<tokio::net::TcpStream>>,
-        ZeptoError,
-    > {
-        use rustls::{ClientConfig as RustlsClientConfig, RootCertStore};
-        use tokio::net::TcpStream;
-        use tokio_rustls::TlsConnector;
-
-        let addr = format!("{}:{}", self.config.imap_host, self.config.imap_port);
-
-        let tcp = TcpStream::connect(&addr)
-            .await
-            .map_err(|e| ZeptoError::Channel(format!("IMAP TCP connect failed: {e}")))?;
-
-        let cert_store = RootCertStore {
-            roots: webpki_roots::TLS_SERVER_ROOTS.into(),
-        };
-        let tls_cfg = RustlsClientConfig::builder()
-            .with_root_certificates(cert_store)
-            .with_no_client_auth();
-
-        let connector = TlsConnector::from(Arc::new(tls_cfg));
-        let sni = rustls::pki_types::ServerName::try_from(self.config.imap_host.clone())
-            .map_err(|e| ZeptoError::Channel(format!("Invalid IMAP hostname: {e}")))?;
-
-        let tls_stream = connector
-            .connect(sni, tcp)
-            .await
-            .map_err(|e| ZeptoError::Channel(format!("IMAP TLS handshake failed: {e}")))?;
-
-        let client = async_imap::Client::new(tls_stream);
+    async fn connect_imap(&self) -> std::result::Result<ImapSession, ZeptoError> {
+        let client = imap_tls_client(&self.config.imap_host, self.config.imap_port).await?;
         let session = client
             .login(&self.config.username, &self.config.password)
             .await
@@ -271,7 +281,7 @@ impl EmailChannel {
     #[cfg(feature = "channel-email")]
     async fn process_unseen(
         &self,
-        session: &mut async_imap::Session<tokio_rustls::client::TlsStream<tokio::net::TcpStream>>,
+        session: &mut ImapSession,
     ) -> std::result::Result<(), ZeptoError> {
         use futures::TryStreamExt;
         use mail_parser::MessageParser;
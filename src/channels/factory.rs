@@ -13,8 +13,8 @@ use super::email_channel::EmailChannel;
 use super::lark::LarkChannel;
 use super::plugin::{default_channel_plugins_dir, discover_channel_plugins, ChannelPluginAdapter};
 use super::webhook::{WebhookChannel, WebhookChannelConfig};
-use super::WhatsAppCloudChannel;
 use super::{BaseChannelConfig, ChannelManager, DiscordChannel, SlackChannel, TelegramChannel};
+use super::{TelegramWebhook, WhatsAppCloudChannel};
 
 /// Register all configured channels that currently have implementations.
 ///
@@ -23,6 +23,17 @@ pub async fn register_configured_channels(
     manager: &ChannelManager,
     bus: Arc<MessageBus>,
     config: &Config,
+) -> usize {
+    register_configured_channels_with_webhook(manager, bus, config, None).await
+}
+
+/// Like [`register_configured_channels`], but Telegram receives updates from
+/// `telegram_webhook` (served by the gateway HTTP server) when given.
+pub async fn register_configured_channels_with_webhook(
+    manager: &ChannelManager,
+    bus: Arc<MessageBus>,
+    config: &Config,
+    telegram_webhook: Option<&TelegramWebhook>,
 ) -> usize {
     // Telegram
     if let Some(ref telegram_config) = config.channels.telegram {
@@ -30,19 +41,21 @@ pub async fn register_configured_channels(
             if telegram_config.token.is_empty() {
                 warn!("Telegram channel enabled but token is empty");
             } else {
-                manager
-                    .register(Box::new(TelegramChannel::new(
-                        telegram_config.clone(),
-                        bus.clone(),
-                        config.agents.defaults.model.clone(),
-                        configured_provider_names(config)
-                            .into_iter()
-                            .map(|name| name.to_string())
-                            .collect(),
-                        configured_provider_models(config),
-                        !matches!(config.memory.backend, MemoryBackend::Disabled),
-                    )))
-                    .await;
+                let mut channel = TelegramChannel::new(
+                    telegram_config.clone(),
+                    bus.clone(),
+                    config.agents.defaults.model.clone(),
+                    configured_provider_names(config)
+                        .into_iter()
+                        .map(|name| name.to_string())
+                        .collect(),
+                    configured_provider_models(config),
+                    !matches!(config.memory.backend, MemoryBackend::Disabled),
+                );
+                if let Some(webhook) = telegram_webhook {
+                    channel = channel.with_webhook(webhook.clone());
+                }
+                manager.register(Box::new(channel)).await;
                 info!("Registered Telegram channel");
             }
         }
@@ -0,0 +1,623 @@
+//! Platform formatting for outbound messages.
+//!
+//! The agent writes CommonMark. [`format_message`] converts it into each
+//! platform's dialect and splits it into chunks under the platform's length
+//! limit:
+//!
+//! - Splits prefer paragraph breaks, then line breaks, then spaces, and never
+//!   leave a code fence open: a fence cut by a split is closed at the end of
+//!   the chunk and reopened (with its language) at the start of the next.
+//! - Tables become an aligned monospace block, since no chat platform renders
+//!   markdown tables.
+//! - Headings become bold lines and list items get `•` / `1.` markers.
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+/// Target markup dialect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    /// Slack `mrkdwn` (`*bold*`, `_italic_`, `<url|text>`).
+    Slack,
+    /// Discord markdown (`**bold**`, `[text](url)`).
+    Discord,
+    /// WhatsApp (`*bold*`, `_italic_`, `~strike~`, no escaping).
+    WhatsApp,
+    /// Telegram `MarkdownV2`, with every reserved character escaped.
+    TelegramMarkdownV2,
+    /// Plain text with markup removed.
+    Plain,
+}
+
+impl Dialect {
+    /// Maximum characters per message on the platform.
+    pub fn max_length(self) -> usize {
+        match self {
+            // chat.postMessage accepts more, but clients truncate past 4000.
+            Dialect::Slack => 4000,
+            Dialect::Discord => 2000,
+            Dialect::WhatsApp | Dialect::TelegramMarkdownV2 => 4096,
+            Dialect::Plain => usize::MAX,
+        }
+    }
+
+    fn bold(self) -> &'static str {
+        match self {
+            Dialect::Discord => "**",
+            Dialect::Plain => "",
+            _ => "*",
+        }
+    }
+
+    fn italic(self) -> &'static str {
+        match self {
+            Dialect::Plain => "",
+            _ => "_",
+        }
+    }
+
+    fn strike(self) -> &'static str {
+        match self {
+            Dialect::Discord => "~~",
+            Dialect::Plain => "",
+            _ => "~",
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            Dialect::Plain => "",
+            _ => "`",
+        }
+    }
+
+    /// Escape ordinary text.
+    fn escape(self, text: &str) -> String {
+        match self {
+            Dialect::Slack => text
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;"),
+            Dialect::Discord => escape_chars(text, "\\*_~`|"),
+            Dialect::TelegramMarkdownV2 => escape_chars(text, "\\_*[]()~`>#+-=|{}.!"),
+            Dialect::WhatsApp | Dialect::Plain => text.to_string(),
+        }
+    }
+
+    /// Escape text inside inline code or a code block.
+    fn escape_code(self, text: &str) -> String {
+        match self {
+            Dialect::Slack => self.escape(text),
+            Dialect::TelegramMarkdownV2 => escape_chars(text, "\\`"),
+            _ => text.to_string(),
+        }
+    }
+}
+
+fn escape_chars(text: &str, special: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if special.contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// One outbound message: rendered `text` and the markdown `source` it came from.
+///
+/// Channels can fall back to `render(&chunk.source, Dialect::Plain)` when the
+/// platform rejects the markup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageChunk {
+    pub text: String,
+    pub source: String,
+}
+
+/// Render `markdown` for `dialect` and split it into chunks of at most
+/// `limit` characters. Empty input yields no chunks.
+pub fn format_message(markdown: &str, dialect: Dialect, limit: usize) -> Vec<MessageChunk> {
+    let mut chunks = Vec::new();
+    for source in split_message(markdown, limit) {
+        push_rendered(source, dialect, limit, &mut chunks);
+    }
+    chunks
+}
+
+/// Render `source`, re-splitting it smaller while escaping pushes the result
+/// past `limit`.
+fn push_rendered(source: String, dialect: Dialect, limit: usize, out: &mut Vec<MessageChunk>) {
+    let text = render(&source, dialect);
+    if text.is_empty() {
+        return;
+    }
+    let len = source.chars().count();
+    if text.chars().count() <= limit || len <= 1 {
+        out.push(MessageChunk { text, source });
+        return;
+    }
+    let pieces = split_message(&source, len / 2);
+    if pieces.iter().any(|p| p.chars().count() >= len) {
+        // Fence overhead defeats further splitting; cut the rendered text.
+        out.extend(
+            split_message(&text, limit)
+                .into_iter()
+                .map(|text| MessageChunk {
+                    text,
+                    source: source.clone(),
+                }),
+        );
+        return;
+    }
+    for piece in pieces {
+        push_rendered(piece, dialect, limit, out);
+    }
+}
+
+/// Split `text` into pieces of at most `limit` characters at the most
+/// natural boundary, keeping ``` code fences balanced in every piece.
+pub fn split_message(text: &str, limit: usize) -> Vec<String> {
+    let text = text.trim();
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    let mut open_fence: Option<String> = None;
+
+    while !rest.is_empty() {
+        let prefix = open_fence
+            .as_ref()
+            .map(|f| format!("{}\n", f))
+            .unwrap_or_default();
+        let fits = prefix.chars().count() + rest.chars().count() <= limit;
+        if fits {
+            pieces.push(format!("{}{}", prefix, rest));
+            break;
+        }
+
+        // Leave room for the reopened fence and a possible closing fence.
+        let budget = limit.saturating_sub(prefix.chars().count() + 4).max(1);
+        let cut = split_point(rest, budget);
+        let (piece, tail) = rest.split_at(cut);
+        let piece = piece.trim_end();
+        let fence_after = fence_state(open_fence.clone(), piece);
+        let mut chunk = format!("{}{}", prefix, piece);
+        if fence_after.is_some() {
+            chunk.push_str("\n```");
+        }
+        if !piece.is_empty() {
+            pieces.push(chunk);
+        }
+        open_fence = fence_after;
+        // Drop the boundary itself but keep the next line's indentation.
+        rest = tail
+            .strip_prefix(' ')
+            .unwrap_or(tail)
+            .trim_start_matches('\n');
+    }
+    pieces
+}
+
+/// Byte index to cut `text` at, within the first `budget` characters.
+fn split_point(text: &str, budget: usize) -> usize {
+    let hard = text
+        .char_indices()
+        .nth(budget)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let window = &text[..hard];
+    // Only accept a boundary in the back half so chunks stay reasonably full.
+    let min = hard / 2;
+    for boundary in ["\n\n", "\n", " "] {
+        if let Some(i) = window.rfind(boundary).filter(|&i| i > min) {
+            return i;
+        }
+    }
+    hard
+}
+
+/// Which code fence (its opening line) is open after `text`, given the
+/// fence open before it.
+fn fence_state(mut open: Option<String>, text: &str) -> Option<String> {
+    for line in text.lines() {
+        let line = line.trim_start();
+        if line.starts_with("```") {
+            open = match open {
+                Some(_) => None,
+                None => Some(line.trim_end().to_string()),
+            };
+        }
+    }
+    open
+}
+
+/// Convert `markdown` to `dialect` without splitting.
+pub fn render(markdown: &str, dialect: Dialect) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut r = Renderer {
+        dialect,
+        out: String::new(),
+        lists: Vec::new(),
+        quotes: Vec::new(),
+        links: Vec::new(),
+        code_block: false,
+        table: None,
+    };
+    for event in Parser::new_ext(markdown, options) {
+        r.event(event);
+    }
+    r.out.trim_end().to_string()
+}
+
+struct Renderer {
+    dialect: Dialect,
+    out: String,
+    /// Next number for each open ordered list (`None` = bulleted).
+    lists: Vec<Option<u64>>,
+    /// Output offsets where open block quotes start.
+    quotes: Vec<usize>,
+    /// Open links: destination and output offset of the link text.
+    links: Vec<(String, usize)>,
+    code_block: bool,
+    table: Option<Table>,
+}
+
+#[derive(Default)]
+struct Table {
+    rows: Vec<Vec<String>>,
+}
+
+impl Renderer {
+    fn event(&mut self, event: Event<'_>) {
+        if let Some(table) = self.table.as_mut() {
+            match event {
+                Event::End(TagEnd::Table) => self.end_table(),
+                Event::Start(Tag::TableHead | Tag::TableRow) => table.rows.push(Vec::new()),
+                Event::Start(Tag::TableCell) => {
+                    if let Some(row) = table.rows.last_mut() {
+                        row.push(String::new());
+                    }
+                }
+                Event::Text(text) | Event::Code(text) => {
+                    if let Some(cell) = table.rows.last_mut().and_then(|r| r.last_mut()) {
+                        cell.push_str(&text);
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        let d = self.dialect;
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Heading { .. } => {
+                    self.line_start();
+                    self.out.push_str(d.bold());
+                }
+                Tag::BlockQuote => {
+                    self.line_start();
+                    self.quotes.push(self.out.len());
+                }
+                Tag::CodeBlock(kind) => {
+                    self.line_start();
+                    self.code_block = true;
+                    if d != Dialect::Plain {
+                        self.out.push_str("```");
+                        if let CodeBlockKind::Fenced(lang) = kind {
+                            // Slack and WhatsApp would show the language as code.
+                            if matches!(d, Dialect::Discord | Dialect::TelegramMarkdownV2) {
+                                self.out.push_str(&lang);
+                            }
+                        }
+                        self.out.push('\n');
+                    }
+                }
+                Tag::List(start) => {
+                    if self.lists.is_empty() {
+                        self.line_start();
+                    }
+                    self.lists.push(start);
+                }
+                Tag::Item => {
+                    self.line_start();
+                    let depth = self.lists.len().saturating_sub(1);
+                    self.out.push_str(&"  ".repeat(depth));
+                    match self.lists.last_mut() {
+                        Some(Some(n)) => {
+                            let marker = format!("{}.", n);
+                            self.out.push_str(&d.escape(&marker));
+                            self.out.push(' ');
+                            *n += 1;
+                        }
+                        _ => self.out.push_str("• "),
+                    }
+                }
+                Tag::Strong => self.out.push_str(d.bold()),
+                Tag::Emphasis => self.out.push_str(d.italic()),
+                Tag::Strikethrough => self.out.push_str(d.strike()),
+                Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                    self.links.push((dest_url.to_string(), self.out.len()));
+                }
+                Tag::Table(_) => {
+                    self.line_start();
+                    self.table = Some(Table::default());
+                }
+                _ => {}
+            },
+            Event::End(tag) => match tag {
+                TagEnd::Paragraph => {
+                    if self.lists.is_empty() {
+                        self.out.push_str("\n\n");
+                    } else {
+                        self.out.push('\n');
+                    }
+                }
+                TagEnd::Heading { .. } => {
+                    self.out.push_str(d.bold());
+                    self.out.push_str("\n\n");
+                }
+                TagEnd::BlockQuote => {
+                    if let Some(start) = self.quotes.pop() {
+                        let body = self.out.split_off(start);
+                        for line in body.trim_end().lines() {
+                            self.out.push_str("> ");
+                            self.out.push_str(line);
+                            self.out.push('\n');
+                        }
+                        self.out.push('\n');
+                    }
+                }
+                TagEnd::CodeBlock => {
+                    self.code_block = false;
+                    self.line_start();
+                    if d != Dialect::Plain {
+                        self.out.push_str("```");
+                    }
+                    self.out.push_str("\n\n");
+                }
+                TagEnd::List(_) => {
+                    self.lists.pop();
+                    if self.lists.is_empty() {
+                        self.line_start();
+                        self.out.push('\n');
+                    }
+                }
+                TagEnd::Item => self.line_start(),
+                TagEnd::Strong => self.out.push_str(d.bold()),
+                TagEnd::Emphasis => self.out.push_str(d.italic()),
+                TagEnd::Strikethrough => self.out.push_str(d.strike()),
+                TagEnd::Link | TagEnd::Image => self.end_link(),
+                _ => {}
+            },
+            Event::Text(text) => {
+                if self.code_block {
+                    self.out.push_str(&d.escape_code(&text));
+                } else {
+                    self.out.push_str(&d.escape(&text));
+                }
+            }
+            Event::Code(text) => {
+                self.out.push_str(d.code());
+                self.out.push_str(&d.escape_code(&text));
+                self.out.push_str(d.code());
+            }
+            Event::Html(html) => self.out.push_str(&d.escape(&html)),
+            Event::SoftBreak | Event::HardBreak => self.out.push('\n'),
+            Event::Rule => {
+                self.line_start();
+                self.out.push_str("———\n\n");
+            }
+            Event::TaskListMarker(done) => self.out.push_str(if done { "☑ " } else { "☐ " }),
+            _ => {}
+        }
+    }
+
+    /// Start a new line unless already at one.
+    fn line_start(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    fn end_link(&mut self) {
+        let Some((url, start)) = self.links.pop() else {
+            return;
+        };
+        let text = self.out.split_off(start);
+        let plain_url = self.dialect.escape(&url);
+        if text.is_empty() || text == plain_url {
+            match self.dialect {
+                Dialect::Slack => self.out.push_str(&format!("<{}>", url)),
+                _ => self.out.push_str(&plain_url),
+            }
+            return;
+        }
+        let link = match self.dialect {
+            Dialect::Slack => format!("<{}|{}>", url, text),
+            Dialect::Discord => format!("[{}]({})", text, url),
+            Dialect::TelegramMarkdownV2 => {
+                format!("[{}]({})", text, escape_chars(&url, "\\)"))
+            }
+            Dialect::WhatsApp | Dialect::Plain => format!("{} ({})", text, url),
+        };
+        self.out.push_str(&link);
+    }
+
+    /// Render the buffered table as aligned monospace rows.
+    fn end_table(&mut self) {
+        let Some(table) = self.table.take() else {
+            return;
+        };
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns)
+            .map(|c| {
+                table
+                    .rows
+                    .iter()
+                    .filter_map(|r| r.get(c))
+                    .map(|cell| cell.trim().chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut lines = Vec::with_capacity(table.rows.len() + 1);
+        for (i, row) in table.rows.iter().enumerate() {
+            let cells: Vec<String> = widths
+                .iter()
+                .enumerate()
+                .map(|(c, &w)| {
+                    let cell = row.get(c).map(|s| s.trim()).unwrap_or("");
+                    format!("{:<w$}", cell, w = w)
+                })
+                .collect();
+            lines.push(cells.join(" | ").trim_end().to_string());
+            if i == 0 {
+                let rule: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+                lines.push(rule.join("-+-"));
+            }
+        }
+        let body = lines.join("\n");
+
+        let d = self.dialect;
+        if d == Dialect::Plain {
+            self.out.push_str(&body);
+        } else {
+            self.out.push_str("```\n");
+            self.out.push_str(&d.escape_code(&body));
+            self.out.push_str("\n```");
+        }
+        self.out.push_str("\n\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_markup_per_dialect() {
+        let md = "**bold** *italic* ~~gone~~ `x`";
+        assert_eq!(render(md, Dialect::Slack), "*bold* _italic_ ~gone~ `x`");
+        assert_eq!(
+            render(md, Dialect::Discord),
+            "**bold** _italic_ ~~gone~~ `x`"
+        );
+        assert_eq!(render(md, Dialect::WhatsApp), "*bold* _italic_ ~gone~ `x`");
+        assert_eq!(render(md, Dialect::Plain), "bold italic gone x");
+    }
+
+    #[test]
+    fn test_links_per_dialect() {
+        let md = "see [docs](https://example.com/a_(b))";
+        assert_eq!(
+            render(md, Dialect::Slack),
+            "see <https://example.com/a_(b)|docs>"
+        );
+        assert_eq!(
+            render(md, Dialect::TelegramMarkdownV2),
+            "see [docs](https://example.com/a_(b\\))"
+        );
+        assert_eq!(
+            render(md, Dialect::WhatsApp),
+            "see docs (https://example.com/a_(b))"
+        );
+    }
+
+    #[test]
+    fn test_markdown_v2_escapes_reserved_characters() {
+        assert_eq!(
+            render("Total: 3.5 (approx) - done!", Dialect::TelegramMarkdownV2),
+            "Total: 3\\.5 \\(approx\\) \\- done\\!"
+        );
+        assert_eq!(
+            render("1. first\n2. second", Dialect::TelegramMarkdownV2),
+            "1\\. first\n2\\. second"
+        );
+        // Only ` and \ are escaped inside code.
+        assert_eq!(
+            render("```\na.b `c`\n```", Dialect::TelegramMarkdownV2),
+            "```\na.b \\`c\\`\n```"
+        );
+    }
+
+    #[test]
+    fn test_slack_escapes_control_characters() {
+        assert_eq!(render("a < b & c", Dialect::Slack), "a &lt; b &amp; c");
+    }
+
+    #[test]
+    fn test_headings_lists_and_quotes() {
+        let md = "# Title\n\n- one\n- two\n  - nested\n\n> quoted\n> text";
+        assert_eq!(
+            render(md, Dialect::Slack),
+            "*Title*\n\n• one\n• two\n  • nested\n\n> quoted\n> text"
+        );
+    }
+
+    #[test]
+    fn test_code_block_language_kept_where_supported() {
+        let md = "```rust\nfn main() {}\n```";
+        assert_eq!(render(md, Dialect::Discord), "```rust\nfn main() {}\n```");
+        assert_eq!(render(md, Dialect::Slack), "```\nfn main() {}\n```");
+    }
+
+    #[test]
+    fn test_table_becomes_aligned_block() {
+        let md = "| Name | Qty |\n|---|---|\n| apple | 3 |\n| kiwi | 12 |";
+        assert_eq!(
+            render(md, Dialect::WhatsApp),
+            "```\nName  | Qty\n------+----\napple | 3\nkiwi  | 12\n```"
+        );
+    }
+
+    #[test]
+    fn test_split_prefers_paragraph_boundaries() {
+        let text = format!("{}\n\n{}", "a".repeat(30), "b".repeat(30));
+        let pieces = split_message(&text, 40);
+        assert_eq!(pieces, vec!["a".repeat(30), "b".repeat(30)]);
+    }
+
+    #[test]
+    fn test_split_never_exceeds_limit() {
+        let text = "word ".repeat(500);
+        for piece in split_message(&text, 100) {
+            assert!(piece.chars().count() <= 100, "{}", piece);
+            assert!(!piece.starts_with(' ') && !piece.ends_with(' '));
+        }
+        let unbroken = "x".repeat(250);
+        let pieces = split_message(&unbroken, 100);
+        assert_eq!(pieces.concat(), unbroken);
+    }
+
+    #[test]
+    fn test_split_rebalances_code_fences() {
+        let code: String = (0..40).map(|i| format!("line {}\n", i)).collect();
+        let text = format!("intro\n\n```python\n{}```", code);
+        let pieces = split_message(&text, 120);
+        assert!(pieces.len() > 1);
+        for piece in &pieces {
+            assert!(piece.chars().count() <= 120);
+            assert_eq!(piece.matches("```").count() % 2, 0, "{}", piece);
+        }
+        assert!(pieces[1].starts_with("```python\n"));
+    }
+
+    #[test]
+    fn test_format_message_keeps_source_and_limit() {
+        let md = format!("{}\n\n{}", "Hello. ".repeat(80), "World! ".repeat(80));
+        let chunks = format_message(&md, Dialect::TelegramMarkdownV2, 300);
+        assert!(chunks.len() > 2);
+        for chunk in &chunks {
+            assert!(chunk.text.chars().count() <= 300);
+            assert_eq!(
+                chunk.text,
+                render(&chunk.source, Dialect::TelegramMarkdownV2)
+            );
+        }
+        assert!(format_message("  ", Dialect::Slack, 100).is_empty());
+    }
+}
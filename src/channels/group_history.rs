@@ -0,0 +1,266 @@
+//! Recent group-chat history for context injection.
+//!
+//! Channel adapters tag group messages with [`IS_GROUP_METADATA_KEY`] and,
+//! when the bot is mentioned or replied to, [`ADDRESSED_METADATA_KEY`].
+//! With `group_history` enabled for a channel, non-addressed group messages
+//! are buffered here instead of triggering a reply; when the bot is
+//! addressed, the most recent buffered messages are rendered into the system
+//! prompt.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::bus::InboundMessage;
+use crate::config::{GroupHistoryChannelPolicy, GroupHistoryConfig};
+
+/// Metadata key set to `"true"` by adapters for group/channel chats.
+pub const IS_GROUP_METADATA_KEY: &str = "is_group";
+
+/// Metadata key set to `"true"` by adapters when the bot is mentioned or
+/// replied to.
+pub const ADDRESSED_METADATA_KEY: &str = "addressed";
+
+/// Upper bound on buffered messages per chat, regardless of config.
+const MAX_BUFFER_PER_CHAT: usize = 200;
+
+/// One buffered group message.
+#[derive(Debug, Clone)]
+pub struct GroupHistoryEntry {
+    pub sender_id: String,
+    pub sender_name: String,
+    pub content: String,
+    pub at: DateTime<Utc>,
+}
+
+/// Per-chat ring buffers of recent non-addressed group messages.
+pub struct GroupHistory {
+    config: GroupHistoryConfig,
+    buffers: Mutex<HashMap<String, VecDeque<GroupHistoryEntry>>>,
+}
+
+impl GroupHistory {
+    pub fn new(config: GroupHistoryConfig) -> Self {
+        Self {
+            config,
+            buffers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Privacy policy for `channel`, if group history is enabled there.
+    fn policy(&self, channel: &str) -> Option<&GroupHistoryChannelPolicy> {
+        if !self.config.enabled {
+            return None;
+        }
+        self.config.channels.get(channel).filter(|p| p.enabled)
+    }
+
+    /// Whether `msg` arrived in a group chat.
+    pub fn is_group(msg: &InboundMessage) -> bool {
+        msg.metadata
+            .get(IS_GROUP_METADATA_KEY)
+            .is_some_and(|v| v == "true")
+    }
+
+    /// Whether `msg` addresses the bot (adapter flag or configured trigger).
+    pub fn is_addressed(&self, msg: &InboundMessage) -> bool {
+        if msg
+            .metadata
+            .get(ADDRESSED_METADATA_KEY)
+            .is_some_and(|v| v == "true")
+        {
+            return true;
+        }
+        let content = msg.content.to_lowercase();
+        self.config
+            .triggers
+            .iter()
+            .map(|t| t.trim().to_lowercase())
+            .any(|t| !t.is_empty() && content.contains(&t))
+    }
+
+    /// Whether `msg` should be buffered rather than answered.
+    pub fn should_buffer(&self, msg: &InboundMessage) -> bool {
+        self.policy(&msg.channel).is_some() && Self::is_group(msg) && !self.is_addressed(msg)
+    }
+
+    /// Buffer a non-addressed group message. Excluded senders and empty
+    /// messages are dropped.
+    pub fn record(&self, msg: &InboundMessage) {
+        let Some(policy) = self.policy(&msg.channel) else {
+            return;
+        };
+        if msg.content.trim().is_empty() || policy.exclude_senders.contains(&msg.sender_id) {
+            return;
+        }
+        let entry = GroupHistoryEntry {
+            sender_id: msg.sender_id.clone(),
+            sender_name: msg
+                .metadata
+                .get("sender_name")
+                .or_else(|| msg.metadata.get("user_name"))
+                .filter(|v| !v.is_empty())
+                .cloned()
+                .unwrap_or_else(|| msg.sender_id.clone()),
+            content: msg.content.clone(),
+            at: Utc::now(),
+        };
+        let limit = self.limit(policy).min(MAX_BUFFER_PER_CHAT);
+        let mut buffers = self.buffers.lock().unwrap_or_else(|e| e.into_inner());
+        let buffer = buffers.entry(chat_key(msg)).or_default();
+        buffer.push_back(entry);
+        while buffer.len() > limit {
+            buffer.pop_front();
+        }
+    }
+
+    fn limit(&self, policy: &GroupHistoryChannelPolicy) -> usize {
+        policy.max_messages.unwrap_or(self.config.max_messages)
+    }
+
+    /// Recent buffered messages for the chat `msg` belongs to, oldest first,
+    /// excluding entries older than `max_age_secs`.
+    pub fn recent(&self, msg: &InboundMessage) -> Vec<GroupHistoryEntry> {
+        let Some(policy) = self.policy(&msg.channel) else {
+            return Vec::new();
+        };
+        let cutoff = Utc::now() - Duration::seconds(self.config.max_age_secs as i64);
+        let mut buffers = self.buffers.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(buffer) = buffers.get_mut(&chat_key(msg)) else {
+            return Vec::new();
+        };
+        buffer.retain(|e| e.at >= cutoff);
+        let skip = buffer.len().saturating_sub(self.limit(policy));
+        buffer.iter().skip(skip).cloned().collect()
+    }
+
+    /// Render recent group messages as a system-prompt section, or `None`
+    /// when there is nothing to inject.
+    pub fn render(&self, msg: &InboundMessage) -> Option<String> {
+        if !Self::is_group(msg) {
+            return None;
+        }
+        let entries = self.recent(msg);
+        if entries.is_empty() {
+            return None;
+        }
+        let anonymize = self.policy(&msg.channel).is_some_and(|p| p.anonymize);
+        let mut labels: HashMap<&str, String> = HashMap::new();
+        let mut lines = vec![
+            "## Recent Group Messages".to_string(),
+            String::new(),
+            "Other recent messages in this group (context only; do not reply to them \
+             individually):"
+                .to_string(),
+        ];
+        for entry in &entries {
+            let name = if anonymize {
+                let next = labels.len() + 1;
+                labels
+                    .entry(entry.sender_id.as_str())
+                    .or_insert_with(|| format!("Participant {}", next))
+                    .clone()
+            } else {
+                entry.sender_name.clone()
+            };
+            lines.push(format!(
+                "[{}] {}: {}",
+                entry.at.format("%H:%M"),
+                name,
+                entry.content
+            ));
+        }
+        Some(lines.join("\n"))
+    }
+}
+
+fn chat_key(msg: &InboundMessage) -> String {
+    format!("{}:{}", msg.channel, msg.chat_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(anonymize: bool) -> GroupHistoryConfig {
+        let mut config = GroupHistoryConfig {
+            enabled: true,
+            max_messages: 3,
+            triggers: vec!["@zepto".to_string()],
+            ..Default::default()
+        };
+        config.channels.insert(
+            "telegram".to_string(),
+            GroupHistoryChannelPolicy {
+                anonymize,
+                exclude_senders: vec!["private".to_string()],
+                ..Default::default()
+            },
+        );
+        config
+    }
+
+    fn group_msg(sender: &str, text: &str) -> InboundMessage {
+        InboundMessage::new("telegram", sender, "-100", text)
+            .with_metadata(IS_GROUP_METADATA_KEY, "true")
+            .with_metadata("sender_name", &sender.to_uppercase())
+    }
+
+    #[test]
+    fn test_should_buffer_rules() {
+        let history = GroupHistory::new(config(false));
+        assert!(history.should_buffer(&group_msg("alice", "lunch?")));
+        assert!(!history.should_buffer(&group_msg("alice", "hey @Zepto, help")));
+        assert!(!history.should_buffer(
+            &group_msg("alice", "hi").with_metadata(ADDRESSED_METADATA_KEY, "true")
+        ));
+        // Direct messages and channels without a policy are never buffered.
+        assert!(!history.should_buffer(&InboundMessage::new("telegram", "a", "1", "hi")));
+        let discord = InboundMessage::new("discord", "a", "1", "hi")
+            .with_metadata(IS_GROUP_METADATA_KEY, "true");
+        assert!(!history.should_buffer(&discord));
+        // Disabled master switch.
+        let off = GroupHistory::new(GroupHistoryConfig::default());
+        assert!(!off.should_buffer(&group_msg("alice", "lunch?")));
+    }
+
+    #[test]
+    fn test_record_keeps_last_n_and_excludes_senders() {
+        let history = GroupHistory::new(config(false));
+        for i in 0..5 {
+            history.record(&group_msg("alice", &format!("m{}", i)));
+        }
+        history.record(&group_msg("private", "secret"));
+        let recent = history.recent(&group_msg("bob", "@zepto"));
+        let contents: Vec<&str> = recent.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, vec!["m2", "m3", "m4"]);
+    }
+
+    #[test]
+    fn test_render_anonymizes() {
+        let history = GroupHistory::new(config(true));
+        history.record(&group_msg("alice", "first"));
+        history.record(&group_msg("bob", "second"));
+        history.record(&group_msg("alice", "third"));
+
+        let rendered = history
+            .render(&group_msg("carol", "@zepto summary?"))
+            .unwrap();
+        assert!(rendered.contains("Participant 1: first"));
+        assert!(rendered.contains("Participant 2: second"));
+        assert!(rendered.contains("Participant 1: third"));
+        assert!(!rendered.contains("ALICE"));
+    }
+
+    #[test]
+    fn test_render_uses_sender_names() {
+        let history = GroupHistory::new(config(false));
+        history.record(&group_msg("alice", "first"));
+        let rendered = history.render(&group_msg("bob", "@zepto")).unwrap();
+        assert!(rendered.contains("ALICE: first"));
+        assert!(history
+            .render(&InboundMessage::new("telegram", "b", "-100", "x"))
+            .is_none());
+    }
+}
@@ -4,19 +4,21 @@
 //! - Registering and managing multiple communication channels
 //! - Starting and stopping all channels
 //! - Dispatching outbound messages to the appropriate channels
+//! - Driving typing indicators and read receipts from agent lifecycle events
 //! - Supervising channel health and restarting dead channels
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
-use tokio::sync::{watch, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, watch, Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
-use crate::bus::{MessageBus, OutboundMessage};
+use crate::bus::{LifecycleEvent, LifecycleKind, MessageBus, OutboundMessage};
 use crate::config::Config;
 use crate::error::Result;
 use crate::health::{HealthCheck, HealthRegistry, HealthStatus};
+use crate::safety::SafetyLayer;
 
 use super::Channel;
 
@@ -101,6 +103,9 @@ pub struct ChannelManager {
     health_registry: Option<HealthRegistry>,
     /// Handle to the supervisor task (if running)
     supervisor_handle: Arc<RwLock<Option<JoinHandle<()>>>>,
+    /// Final safety check on outbound messages (`safety.outbound_check_enabled`,
+    /// `safety.pii.outbound`).
+    outbound_safety: Option<Arc<SafetyLayer>>,
 }
 
 impl ChannelManager {
@@ -125,6 +130,10 @@ impl ChannelManager {
     /// ```
     pub fn new(bus: Arc<MessageBus>, config: Config) -> Self {
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let safety = &config.safety;
+        let outbound_safety = (safety.enabled
+            && (safety.outbound_check_enabled || (safety.pii.enabled && safety.pii.outbound)))
+            .then(|| Arc::new(SafetyLayer::new(safety.clone())));
         Self {
             channels: Arc::new(RwLock::new(HashMap::new())),
             bus,
@@ -134,6 +143,7 @@ impl ChannelManager {
             dispatcher_handle: Arc::new(RwLock::new(None)),
             health_registry: None,
             supervisor_handle: Arc::new(RwLock::new(None)),
+            outbound_safety,
         }
     }
 
@@ -207,7 +217,8 @@ impl ChannelManager {
     ///
     /// This method:
     /// 1. Iterates over all registered channels and starts each one
-    /// 2. Spawns a background task to dispatch outbound messages
+    /// 2. Spawns a background task to dispatch outbound messages and
+    ///    lifecycle events
     /// 3. Starts the supervisor loop to monitor channel health
     ///
     /// Errors from individual channels are logged but do not prevent
@@ -270,12 +281,22 @@ impl ChannelManager {
         // Reset shutdown signal for fresh start
         let _ = self.shutdown_tx.send(false);
 
-        // Start outbound dispatcher
+        // Start outbound and lifecycle dispatchers
         let bus = self.bus.clone();
         let channels_ref = self.channels.clone();
         let shutdown_rx = self.shutdown_rx.clone();
+        let max_typing = Duration::from_secs(self.config.agents.defaults.agent_timeout_secs);
+        let outbound_safety = self.outbound_safety.clone();
         let handle = tokio::spawn(async move {
-            dispatch_outbound(bus, channels_ref, shutdown_rx).await;
+            tokio::join!(
+                dispatch_outbound(
+                    bus.clone(),
+                    channels_ref.clone(),
+                    shutdown_rx.clone(),
+                    outbound_safety
+                ),
+                dispatch_lifecycle(bus, channels_ref, shutdown_rx, max_typing),
+            );
         });
 
         // Store the handle so we can wait for it to stop
@@ -505,7 +526,8 @@ impl ChannelManager {
     /// let msg = OutboundMessage::new("telegram", "chat123", "Hello!");
     /// manager.send("telegram", msg).await?;
     /// ```
-    pub async fn send(&self, channel_name: &str, msg: OutboundMessage) -> Result<()> {
+    pub async fn send(&self, channel_name: &str, mut msg: OutboundMessage) -> Result<()> {
+        check_outbound(self.outbound_safety.as_deref(), &mut msg);
         let channel = {
             let channels = self.channels.read().await;
             channels
@@ -548,10 +570,12 @@ impl ChannelManager {
 /// * `bus` - The message bus to consume from
 /// * `channels` - The shared map of channels
 /// * `shutdown_rx` - Receiver for shutdown signals
+/// * `safety` - Safety check applied to each message before it is sent
 async fn dispatch_outbound(
     bus: Arc<MessageBus>,
     channels: Arc<RwLock<HashMap<String, SharedChannel>>>,
     mut shutdown_rx: watch::Receiver<bool>,
+    safety: Option<Arc<SafetyLayer>>,
 ) {
     info!("Outbound dispatcher started");
     loop {
@@ -565,7 +589,8 @@ async fn dispatch_outbound(
             }
             // Wait for outbound messages
             msg = bus.consume_outbound() => {
-                if let Some(msg) = msg {
+                if let Some(mut msg) = msg {
+                    check_outbound(safety.as_deref(), &mut msg);
                     let channel_name = msg.channel.clone();
                     let channel = {
                         let channels = channels.read().await;
@@ -592,9 +617,103 @@ async fn dispatch_outbound(
     info!("Outbound dispatcher stopped");
 }
 
+/// Apply the outbound safety check, if any, to `msg`'s text.
+fn check_outbound(safety: Option<&SafetyLayer>, msg: &mut OutboundMessage) {
+    if let Some(content) =
+        safety.and_then(|safety| safety.check_outbound(&msg.channel, &msg.content))
+    {
+        msg.content = content;
+    }
+}
+
+/// Background task that turns agent lifecycle events into typing indicators
+/// and read receipts on channels that support them.
+///
+/// A `Started` event marks the message read and keeps the typing indicator
+/// up (for at most `max_typing`) until the matching `Finished` event or the
+/// next `Started` event in the same chat.
+async fn dispatch_lifecycle(
+    bus: Arc<MessageBus>,
+    channels: Arc<RwLock<HashMap<String, SharedChannel>>>,
+    mut shutdown_rx: watch::Receiver<bool>,
+    max_typing: Duration,
+) {
+    let mut events = bus.subscribe_lifecycle();
+    let mut typing: HashMap<(String, String), JoinHandle<()>> = HashMap::new();
+    loop {
+        tokio::select! {
+            _ = shutdown_rx.changed() => {
+                if *shutdown_rx.borrow() {
+                    break;
+                }
+            }
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let key = (event.channel.clone(), event.chat_id.clone());
+                    if let Some(task) = typing.remove(&key) {
+                        task.abort();
+                    }
+                    if event.kind != LifecycleKind::Started {
+                        continue;
+                    }
+                    let channel = {
+                        let channels = channels.read().await;
+                        channels.get(&event.channel).cloned()
+                    };
+                    if let Some(channel) = channel {
+                        typing.insert(key, tokio::spawn(signal_progress(channel, event, max_typing)));
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!("Lifecycle dispatcher skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+        typing.retain(|_, task| !task.is_finished());
+    }
+    for task in typing.into_values() {
+        task.abort();
+    }
+}
+
+/// Mark the event's message read, then repeat the typing indicator until
+/// aborted or `max_typing` has passed.
+async fn signal_progress(channel: SharedChannel, event: LifecycleEvent, max_typing: Duration) {
+    let interval = {
+        let channel = channel.lock().await;
+        let Some(caps) = channel.capabilities() else {
+            return;
+        };
+        if let Err(e) = caps.mark_read(&event).await {
+            debug!("Failed to mark message read on {}: {}", event.channel, e);
+        }
+        caps.typing_interval()
+    };
+
+    let deadline = Instant::now() + max_typing;
+    while Instant::now() < deadline {
+        {
+            let channel = channel.lock().await;
+            let Some(caps) = channel.capabilities() else {
+                return;
+            };
+            if let Err(e) = caps.send_typing(&event).await {
+                debug!(
+                    "Failed to send typing indicator on {}: {}",
+                    event.channel, e
+                );
+                return;
+            }
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::channels::ChannelCapabilities;
     use async_trait::async_trait;
     use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
@@ -814,6 +933,22 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_filter_pii_rewrites_outbound_content() {
+        let mut config = Config::default();
+        config.safety.pii.enabled = true;
+        config.safety.pii.outbound = true;
+        let manager = ChannelManager::new(Arc::new(MessageBus::new()), config);
+
+        let mut msg = OutboundMessage::new("telegram", "chat1", "Her SSN is 123-45-6789");
+        check_outbound(manager.outbound_safety.as_deref(), &mut msg);
+        assert_eq!(msg.content, "Her SSN is [REDACTED:national_id]");
+
+        let mut msg = OutboundMessage::new("telegram", "chat1", "Her SSN is 123-45-6789");
+        check_outbound(None, &mut msg);
+        assert_eq!(msg.content, "Her SSN is 123-45-6789");
+    }
+
     #[tokio::test]
     async fn test_channel_allowlist() {
         let channel = MockChannel::with_allowlist("test", vec!["user1".to_string()]);
@@ -969,4 +1104,88 @@ mod tests {
             assert!(handle.is_none()); // Taken by stop_all
         }
     }
+
+    /// A channel that records typing indicators and read receipts.
+    struct TypingChannel {
+        typing: Arc<AtomicU32>,
+        reads: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl Channel for TypingChannel {
+        fn name(&self) -> &str {
+            "typing"
+        }
+
+        async fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send(&self, _msg: OutboundMessage) -> Result<()> {
+            Ok(())
+        }
+
+        fn is_running(&self) -> bool {
+            true
+        }
+
+        fn is_allowed(&self, _user_id: &str) -> bool {
+            true
+        }
+
+        fn capabilities(&self) -> Option<&dyn ChannelCapabilities> {
+            Some(self)
+        }
+    }
+
+    #[async_trait]
+    impl ChannelCapabilities for TypingChannel {
+        async fn send_typing(&self, _event: &LifecycleEvent) -> Result<()> {
+            self.typing.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn mark_read(&self, _event: &LifecycleEvent) -> Result<()> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn typing_interval(&self) -> Duration {
+            Duration::from_millis(10)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_events_drive_typing_and_read() {
+        let bus = Arc::new(MessageBus::new());
+        let manager = ChannelManager::new(bus.clone(), Config::default());
+        let typing = Arc::new(AtomicU32::new(0));
+        let reads = Arc::new(AtomicU32::new(0));
+        manager
+            .register(Box::new(TypingChannel {
+                typing: Arc::clone(&typing),
+                reads: Arc::clone(&reads),
+            }))
+            .await;
+        manager.start_all().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let inbound = crate::bus::InboundMessage::new("typing", "user", "chat", "hi");
+        bus.publish_lifecycle(LifecycleEvent::new(LifecycleKind::Started, &inbound));
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+        assert!(typing.load(Ordering::SeqCst) >= 2);
+
+        bus.publish_lifecycle(LifecycleEvent::new(LifecycleKind::Finished, &inbound));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let stopped_at = typing.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(typing.load(Ordering::SeqCst), stopped_at);
+
+        manager.stop_all().await.unwrap();
+    }
 }
@@ -108,9 +108,12 @@
 //! # })
 //! ```
 
+pub mod device;
 pub mod discord;
 pub mod email_channel;
 mod factory;
+pub mod formatting;
+pub mod group_history;
 pub mod lark;
 mod manager;
 pub mod model_switch;
@@ -122,16 +125,19 @@ pub mod plugin;
 pub mod serial;
 pub mod slack;
 pub mod telegram;
-pub mod telegram_markdown;
+pub mod telegram_webhook;
 mod types;
+#[cfg(feature = "voice")]
+pub mod voice;
 pub mod webhook;
 pub mod whatsapp_cloud;
 #[cfg(feature = "whatsapp-web")]
 pub mod whatsapp_web;
 
+pub use device::{DeviceChannel, DeviceHub};
 pub use discord::DiscordChannel;
 pub use email_channel::EmailChannel;
-pub use factory::register_configured_channels;
+pub use factory::{register_configured_channels, register_configured_channels_with_webhook};
 pub use lark::LarkChannel;
 pub use manager::ChannelManager;
 #[cfg(feature = "mqtt")]
@@ -141,7 +147,10 @@ pub use plugin::ChannelPluginAdapter;
 pub use serial::SerialChannel;
 pub use slack::SlackChannel;
 pub use telegram::TelegramChannel;
-pub use types::{BaseChannelConfig, Channel};
+pub use telegram_webhook::TelegramWebhook;
+pub use types::{BaseChannelConfig, Channel, ChannelCapabilities};
+#[cfg(feature = "voice")]
+pub use voice::VoiceChannel;
 pub use webhook::{WebhookChannel, WebhookChannelConfig};
 pub use whatsapp_cloud::WhatsAppCloudChannel;
 #[cfg(feature = "whatsapp-web")]
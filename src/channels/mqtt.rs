@@ -32,6 +32,12 @@
 //! ```json
 //! {"type":"response","text":"Hi!"}
 //! ```
+//!
+//! # TLS
+//!
+//! `mqtts://` brokers are reached over TLS, verified against the system
+//! roots or `ca_cert_path`. Setting `client_cert_path` + `client_key_path`
+//! enables mutual TLS.
 
 #[cfg(feature = "mqtt")]
 mod inner {
@@ -40,7 +46,7 @@ mod inner {
 
     use async_trait::async_trait;
     use futures::FutureExt;
-    use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+    use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS, TlsConfiguration, Transport};
     use serde::{Deserialize, Serialize};
     use tracing::{debug, error, info, warn};
 
@@ -68,7 +74,7 @@ mod inner {
     }
 
     /// Map a config QoS value (0, 1, 2) to `rumqttc::QoS`.
-    fn config_qos(qos: u8) -> QoS {
+    pub(crate) fn config_qos(qos: u8) -> QoS {
         match qos {
             0 => QoS::AtMostOnce,
             2 => QoS::ExactlyOnce,
@@ -122,25 +128,7 @@ mod inner {
         }
 
         async fn start(&mut self) -> Result<()> {
-            // Fail fast on mqtts:// — TLS transport is not yet configured.
-            if self.config.broker_url.starts_with("mqtts://") {
-                return Err(ZeptoError::Config(
-                    "mqtts:// requires TLS transport which is not yet supported; use mqtt:// or configure a TLS proxy".to_string(),
-                ));
-            }
-
-            // Parse broker URL — rumqttc expects host and port separately.
-            let url = &self.config.broker_url;
-            let (host, port) = parse_broker_url(url).map_err(|e| {
-                ZeptoError::Config(format!("Invalid MQTT broker URL '{}': {}", url, e))
-            })?;
-
-            let mut mqtt_options = MqttOptions::new(&self.config.client_id, &host, port);
-            mqtt_options.set_keep_alive(std::time::Duration::from_secs(30));
-
-            if !self.config.username.is_empty() {
-                mqtt_options.set_credentials(&self.config.username, &self.config.password);
-            }
+            let mqtt_options = mqtt_options(&self.config, &self.config.client_id)?;
 
             let (client, mut eventloop) = AsyncClient::new(mqtt_options, 64);
 
@@ -336,10 +324,73 @@ mod inner {
         }
     }
 
+    /// Build connection options for `config`: broker address, keep-alive,
+    /// credentials, and TLS for `mqtts://`. Shared with the `mqtt_publish`
+    /// tool, which connects with its own `client_id`.
+    pub(crate) fn mqtt_options(config: &MqttChannelConfig, client_id: &str) -> Result<MqttOptions> {
+        let url = &config.broker_url;
+        let (host, port) = parse_broker_url(url)
+            .map_err(|e| ZeptoError::Config(format!("Invalid MQTT broker URL '{}': {}", url, e)))?;
+
+        let mut mqtt_options = MqttOptions::new(client_id, &host, port);
+        mqtt_options.set_keep_alive(std::time::Duration::from_secs(30));
+
+        if !config.username.is_empty() {
+            mqtt_options.set_credentials(&config.username, &config.password);
+        }
+
+        if url.starts_with("mqtts://") {
+            mqtt_options.set_transport(tls_transport(config)?);
+        }
+
+        Ok(mqtt_options)
+    }
+
+    /// TLS transport from the configured CA bundle and optional client
+    /// certificate.
+    fn tls_transport(config: &MqttChannelConfig) -> Result<Transport> {
+        let read_pem = |path: &str, what: &str| {
+            std::fs::read(crate::config::expand_home(path)).map_err(|e| {
+                ZeptoError::Config(format!("Cannot read MQTT {} '{}': {}", what, path, e))
+            })
+        };
+
+        let client_auth = match (&config.client_cert_path, &config.client_key_path) {
+            (Some(cert), Some(key)) => Some((
+                read_pem(cert, "client certificate")?,
+                read_pem(key, "client key")?,
+            )),
+            (None, None) => None,
+            _ => {
+                return Err(ZeptoError::Config(
+                    "MQTT mutual TLS needs both client_cert_path and client_key_path".to_string(),
+                ))
+            }
+        };
+
+        match &config.ca_cert_path {
+            Some(ca) => Ok(Transport::tls_with_config(TlsConfiguration::Simple {
+                ca: read_pem(ca, "CA bundle")?,
+                alpn: None,
+                client_auth,
+            })),
+            None if client_auth.is_some() => Err(ZeptoError::Config(
+                "MQTT mutual TLS needs ca_cert_path".to_string(),
+            )),
+            None => Ok(Transport::tls_with_default_config()),
+        }
+    }
+
     /// Parse an MQTT broker URL into (host, port).
     ///
-    /// Accepts formats: `mqtt://host:port`, `host:port`, `host` (default port 1883).
+    /// Accepts formats: `mqtt://host:port`, `mqtts://host:port`, `host:port`,
+    /// `host` (default port 1883, or 8883 for `mqtts://`).
     fn parse_broker_url(url: &str) -> std::result::Result<(String, u16), String> {
+        let default_port = if url.starts_with("mqtts://") {
+            8883
+        } else {
+            1883
+        };
         let stripped = url
             .strip_prefix("mqtt://")
             .or_else(|| url.strip_prefix("mqtts://"))
@@ -352,7 +403,22 @@ mod inner {
                 .map_err(|_| format!("invalid port: {}", port_str))?;
             Ok((host.to_string(), port))
         } else {
-            Ok((stripped.to_string(), 1883))
+            Ok((stripped.to_string(), default_port))
+        }
+    }
+
+    /// Whether `topic` matches an MQTT topic filter with `+` / `#` wildcards.
+    pub(crate) fn topic_matches(filter: &str, topic: &str) -> bool {
+        let mut filter_levels = filter.split('/');
+        let mut topic_levels = topic.split('/');
+        loop {
+            match (filter_levels.next(), topic_levels.next()) {
+                (Some("#"), _) => return true,
+                (Some("+"), Some(_)) => {}
+                (Some(f), Some(t)) if f == t => {}
+                (None, None) => return true,
+                _ => return false,
+            }
         }
     }
 
@@ -519,16 +585,43 @@ mod inner {
             assert!(config.password.is_empty());
         }
 
+        #[test]
+        fn test_parse_broker_url_mqtts_default_port() {
+            let (host, port) = parse_broker_url("mqtts://secure.broker.io").unwrap();
+            assert_eq!(host, "secure.broker.io");
+            assert_eq!(port, 8883);
+        }
+
         #[tokio::test]
-        async fn test_mqtt_start_rejects_mqtts() {
+        async fn test_mqtt_start_mqtts_missing_ca_file() {
             let mut ch = make_channel(MqttChannelConfig {
                 broker_url: "mqtts://secure.broker.io:8883".to_string(),
+                ca_cert_path: Some("/nonexistent/zeptoclaw-ca.pem".to_string()),
                 ..Default::default()
             });
-            let result = ch.start().await;
-            assert!(result.is_err());
-            let err_msg = result.unwrap_err().to_string();
-            assert!(err_msg.contains("mqtts://"));
+            let err_msg = ch.start().await.unwrap_err().to_string();
+            assert!(err_msg.contains("CA bundle"));
+        }
+
+        #[test]
+        fn test_mqtt_options_rejects_half_client_auth() {
+            let config = MqttChannelConfig {
+                broker_url: "mqtts://secure.broker.io".to_string(),
+                client_cert_path: Some("/tmp/client.pem".to_string()),
+                ..Default::default()
+            };
+            let err = mqtt_options(&config, "test").unwrap_err().to_string();
+            assert!(err.contains("client_key_path"));
+        }
+
+        #[test]
+        fn test_topic_matches() {
+            assert!(topic_matches("zeptoclaw/#", "zeptoclaw/outbox/node-1"));
+            assert!(topic_matches("home/+/light", "home/kitchen/light"));
+            assert!(topic_matches("home/kitchen/light", "home/kitchen/light"));
+            assert!(!topic_matches("home/+/light", "home/kitchen/fan"));
+            assert!(!topic_matches("home/+", "home/kitchen/light"));
+            assert!(!topic_matches("home/kitchen", "home"));
         }
 
         #[test]
@@ -546,3 +639,5 @@ mod inner {
 
 #[cfg(feature = "mqtt")]
 pub use inner::MqttChannel;
+#[cfg(feature = "mqtt")]
+pub(crate) use inner::{config_qos, mqtt_options, topic_matches};
@@ -452,7 +452,7 @@ pub fn discover_channel_plugins(plugin_dir: &Path) -> Vec<(ChannelPluginManifest
 
 /// Returns the default channel plugins directory (`~/.zeptoclaw/channels/`).
 pub fn default_channel_plugins_dir() -> Option<PathBuf> {
-    dirs::home_dir().map(|home| home.join(".zeptoclaw").join("channels"))
+    Some(crate::config::Config::data_dir().join("channels"))
 }
 
 #[cfg(test)]
@@ -20,10 +20,13 @@ use crate::bus::{InboundMessage, MediaAttachment, MediaType, MessageBus, Outboun
 use crate::config::SlackConfig;
 use crate::error::{Result, ZeptoError};
 
+use super::formatting::{format_message, Dialect};
 use super::{BaseChannelConfig, Channel};
 
 const SLACK_CHAT_POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
 const SLACK_SOCKET_OPEN_URL: &str = "https://slack.com/api/apps.connections.open";
+const SLACK_GET_UPLOAD_URL: &str = "https://slack.com/api/files.getUploadURLExternal";
+const SLACK_COMPLETE_UPLOAD_URL: &str = "https://slack.com/api/files.completeUploadExternal";
 const SLACK_RECONNECT_DELAY_SECS: u64 = 2;
 
 #[derive(Debug, Deserialize)]
@@ -135,7 +138,7 @@ impl SlackChannel {
         self.config.enabled
     }
 
-    fn build_payload(msg: &OutboundMessage) -> Result<Value> {
+    fn build_payload(msg: &OutboundMessage, text: &str) -> Result<Value> {
         let channel = msg.chat_id.trim();
         if channel.is_empty() {
             return Err(ZeptoError::Channel(
@@ -145,7 +148,7 @@ impl SlackChannel {
 
         let mut payload = json!({
             "channel": channel,
-            "text": msg.content,
+            "text": text,
         });
 
         if let Some(ref reply_to) = msg.reply_to {
@@ -157,6 +160,123 @@ impl SlackChannel {
         Ok(payload)
     }
 
+    async fn post_message(&self, payload: &Value) -> Result<()> {
+        self.call_api(self.client.post(SLACK_CHAT_POST_MESSAGE_URL).json(payload))
+            .await?;
+        Ok(())
+    }
+
+    /// Upload one attachment with the external upload flow: reserve an
+    /// upload URL, send the bytes, then share the file in the channel.
+    async fn upload_file(&self, msg: &OutboundMessage, media: &MediaAttachment) -> Result<()> {
+        let Some(data) = media.data.clone() else {
+            warn!("Slack: skipping attachment without data");
+            return Ok(());
+        };
+        let filename = media
+            .filename
+            .clone()
+            .unwrap_or_else(|| "attachment".to_string());
+
+        let reserved = self
+            .call_api(self.client.post(SLACK_GET_UPLOAD_URL).form(&[
+                ("filename", filename.clone()),
+                ("length", data.len().to_string()),
+            ]))
+            .await?;
+        let (Some(upload_url), Some(file_id)) = (
+            reserved.get("upload_url").and_then(Value::as_str),
+            reserved.get("file_id").and_then(Value::as_str),
+        ) else {
+            return Err(ZeptoError::Channel(
+                "Slack upload URL response is missing upload_url/file_id".to_string(),
+            ));
+        };
+
+        let response = self
+            .client
+            .post(upload_url)
+            .body(data)
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Channel(format!("Failed to upload Slack file: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(ZeptoError::Channel(format!(
+                "Slack file upload returned HTTP {}",
+                response.status()
+            )));
+        }
+
+        self.call_api(self.client.post(SLACK_COMPLETE_UPLOAD_URL).json(
+            &Self::build_complete_upload_payload(msg, file_id, &filename)?,
+        ))
+        .await?;
+        Ok(())
+    }
+
+    fn build_complete_upload_payload(
+        msg: &OutboundMessage,
+        file_id: &str,
+        title: &str,
+    ) -> Result<Value> {
+        let channel = msg.chat_id.trim();
+        if channel.is_empty() {
+            return Err(ZeptoError::Channel(
+                "Slack channel ID cannot be empty".to_string(),
+            ));
+        }
+        let mut payload = json!({
+            "files": [{"id": file_id, "title": title}],
+            "channel_id": channel,
+        });
+        if let Some(ref reply_to) = msg.reply_to {
+            payload["thread_ts"] = Value::String(reply_to.clone());
+        }
+        Ok(payload)
+    }
+
+    /// Send an authenticated Slack Web API request and return the JSON body
+    /// once `ok` is true.
+    async fn call_api(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let response = request
+            .bearer_auth(&self.config.bot_token)
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Channel(format!("Failed to call Slack API: {}", e)))?;
+
+        let status = response.status();
+        let body = response.text().await.map_err(|e| {
+            ZeptoError::Channel(format!("Failed to read Slack API response: {}", e))
+        })?;
+
+        if !status.is_success() {
+            return Err(ZeptoError::Channel(format!(
+                "Slack API returned HTTP {}: {}",
+                status, body
+            )));
+        }
+
+        let body_json: Value = serde_json::from_str(&body)
+            .map_err(|e| ZeptoError::Channel(format!("Invalid Slack API response JSON: {}", e)))?;
+
+        if !body_json
+            .get("ok")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+        {
+            let api_error = body_json
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown_error");
+            return Err(ZeptoError::Channel(format!(
+                "Slack API returned error: {}",
+                api_error
+            )));
+        }
+
+        Ok(body_json)
+    }
+
     async fn open_socket_mode_url(client: &reqwest::Client, app_token: &str) -> Result<String> {
         let response = client
             .post(SLACK_SOCKET_OPEN_URL)
@@ -537,45 +657,17 @@ impl Channel for SlackChannel {
             return Err(ZeptoError::Config("Slack bot token is empty".to_string()));
         }
 
-        let payload = Self::build_payload(&msg)?;
-
-        let response = self
-            .client
-            .post(SLACK_CHAT_POST_MESSAGE_URL)
-            .bearer_auth(&self.config.bot_token)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|e| ZeptoError::Channel(format!("Failed to call Slack API: {}", e)))?;
-
-        let status = response.status();
-        let body = response.text().await.map_err(|e| {
-            ZeptoError::Channel(format!("Failed to read Slack API response: {}", e))
-        })?;
-
-        if !status.is_success() {
-            return Err(ZeptoError::Channel(format!(
-                "Slack API returned HTTP {}: {}",
-                status, body
-            )));
+        for media in &msg.media {
+            self.upload_file(&msg, media).await?;
+        }
+        if msg.has_media() && msg.content.trim().is_empty() {
+            return Ok(());
         }
 
-        let body_json: Value = serde_json::from_str(&body)
-            .map_err(|e| ZeptoError::Channel(format!("Invalid Slack API response JSON: {}", e)))?;
-
-        if !body_json
-            .get("ok")
-            .and_then(Value::as_bool)
-            .unwrap_or(false)
-        {
-            let api_error = body_json
-                .get("error")
-                .and_then(Value::as_str)
-                .unwrap_or("unknown_error");
-            return Err(ZeptoError::Channel(format!(
-                "Slack API returned error: {}",
-                api_error
-            )));
+        let chunks = format_message(&msg.content, Dialect::Slack, Dialect::Slack.max_length());
+        for chunk in chunks {
+            let payload = Self::build_payload(&msg, &chunk.text)?;
+            self.post_message(&payload).await?;
         }
 
         info!("Slack: Message sent successfully");
@@ -729,13 +821,29 @@ mod tests {
     #[test]
     fn test_slack_payload_with_reply() {
         let msg = OutboundMessage::new("slack", "C123", "hello").with_reply("173401.000200");
-        let payload = SlackChannel::build_payload(&msg).expect("payload should build");
+        let payload =
+            SlackChannel::build_payload(&msg, &msg.content).expect("payload should build");
 
         assert_eq!(payload["channel"], "C123");
         assert_eq!(payload["text"], "hello");
         assert_eq!(payload["thread_ts"], "173401.000200");
     }
 
+    #[test]
+    fn test_slack_complete_upload_payload() {
+        let msg = OutboundMessage::new("slack", "C123", "").with_reply("173401.000200");
+        let payload = SlackChannel::build_complete_upload_payload(&msg, "F42", "chart.png")
+            .expect("payload should build");
+
+        assert_eq!(payload["channel_id"], "C123");
+        assert_eq!(payload["files"][0]["id"], "F42");
+        assert_eq!(payload["files"][0]["title"], "chart.png");
+        assert_eq!(payload["thread_ts"], "173401.000200");
+
+        let no_channel = OutboundMessage::new("slack", " ", "");
+        assert!(SlackChannel::build_complete_upload_payload(&no_channel, "F42", "x").is_err());
+    }
+
     #[test]
     fn test_parse_socket_message_extracts_inbound_and_ack() {
         let raw = r#"{
@@ -43,7 +43,9 @@ use std::time::Duration;
 use tokio::sync::{mpsc, Mutex};
 use tracing::{error, info, warn};
 
-use crate::bus::{InboundMessage, MediaAttachment, MediaType, MessageBus, OutboundMessage};
+use crate::bus::{
+    InboundMessage, LifecycleEvent, MediaAttachment, MediaType, MessageBus, OutboundMessage,
+};
 use crate::config::Config;
 use crate::config::TelegramConfig;
 use crate::error::{Result, ZeptoError};
@@ -57,12 +59,14 @@ const BASE_RETRY_DELAY_SECS: u64 = 2;
 /// Maximum delay (in seconds) for exponential backoff on startup retries.
 const MAX_RETRY_DELAY_SECS: u64 = 120;
 
+use super::formatting::{format_message, render, Dialect};
 use super::model_switch::{
     format_current_model, format_model_list, hydrate_overrides, new_override_store,
     parse_model_command, persist_single, remove_single, ModelCommand, ModelOverrideStore,
 };
 use super::persona_switch::{self, PersonaCommand, PersonaOverrideStore};
-use super::{BaseChannelConfig, Channel};
+use super::telegram_webhook::{self, TelegramWebhook};
+use super::{BaseChannelConfig, Channel, ChannelCapabilities};
 
 /// Newtype wrappers to disambiguate `Vec<String>` / `String` in dptree's
 /// type-based DI. Without these, the last registered value of a given type
@@ -86,6 +90,16 @@ struct OverridesDep {
     persona: PersonaOverrideStore,
 }
 
+/// Whether Telegram can show an attachment as a photo; anything else (SVG,
+/// PDF, ...) goes out as a document.
+fn is_telegram_photo(media: &MediaAttachment) -> bool {
+    media.media_type == MediaType::Image
+        && matches!(
+            media.mime_type.as_deref(),
+            None | Some("image/png" | "image/jpeg" | "image/webp")
+        )
+}
+
 fn is_numeric_allowlist_entry(entry: &str) -> bool {
     let trimmed = entry.trim();
     !trimmed.is_empty() && trimmed.bytes().all(|b| b.is_ascii_digit())
@@ -121,6 +135,8 @@ fn telegram_allowlist_allows(
 /// - Receiving text messages from users
 /// - Sending text responses
 /// - Allowlist-based access control
+/// - Long polling, or webhook delivery through the gateway HTTP server
+///   (see [`TelegramChannel::with_webhook`])
 /// - Graceful shutdown
 ///
 /// # Configuration
@@ -153,6 +169,8 @@ pub struct TelegramChannel {
     configured_models: Vec<(String, String)>,
     /// Long-term memory backing store for model overrides (optional)
     longterm_memory: Option<Arc<Mutex<LongTermMemory>>>,
+    /// Gateway webhook route feeding updates in webhook mode (None = long polling)
+    webhook: Option<TelegramWebhook>,
 }
 
 impl TelegramChannel {
@@ -210,7 +228,7 @@ impl TelegramChannel {
         let longterm_memory = if memory_enabled {
             // Use a dedicated file to avoid conflicts with the agent loop's longterm.json.
             // Two LongTermMemory instances writing to the same file can cause data loss.
-            let ltm_path = Config::dir().join("memory").join("model_prefs.json");
+            let ltm_path = Config::data_dir().join("memory").join("model_prefs.json");
             match LongTermMemory::with_path_and_searcher(ltm_path, Arc::new(BuiltinSearcher)) {
                 Ok(ltm) => Some(Arc::new(Mutex::new(ltm))),
                 Err(e) => {
@@ -237,9 +255,18 @@ impl TelegramChannel {
             configured_providers,
             configured_models,
             longterm_memory,
+            webhook: None,
         }
     }
 
+    /// Receive updates from the gateway's webhook route instead of long polling.
+    ///
+    /// `webhook` must be the instance the gateway HTTP server delivers to.
+    pub fn with_webhook(mut self, webhook: TelegramWebhook) -> Self {
+        self.webhook = Some(webhook);
+        self
+    }
+
     /// Returns a reference to the Telegram configuration.
     pub fn telegram_config(&self) -> &TelegramConfig {
         &self.config
@@ -262,6 +289,46 @@ impl TelegramChannel {
     ///
     /// We disable automatic system proxy detection to avoid macOS dynamic-store
     /// crashes seen in some sandboxed/runtime environments.
+    /// Upload attachments with raw data: images as photos, the rest as
+    /// documents.
+    async fn send_media(
+        bot: &teloxide::Bot,
+        chat_id: teloxide::types::ChatId,
+        thread_id: Option<teloxide::types::ThreadId>,
+        media: &[MediaAttachment],
+    ) -> Result<()> {
+        use teloxide::prelude::*;
+        use teloxide::types::InputFile;
+
+        for attachment in media {
+            let Some(data) = attachment.data.clone() else {
+                warn!("Telegram: skipping attachment without data");
+                continue;
+            };
+            let mut file = InputFile::memory(data);
+            if let Some(name) = &attachment.filename {
+                file = file.file_name(name.clone());
+            }
+            let sent = if is_telegram_photo(attachment) {
+                let mut req = bot.send_photo(chat_id, file);
+                if let Some(tid) = thread_id {
+                    req = req.message_thread_id(tid);
+                }
+                req.await.map(|_| ())
+            } else {
+                let mut req = bot.send_document(chat_id, file);
+                if let Some(tid) = thread_id {
+                    req = req.message_thread_id(tid);
+                }
+                req.await.map(|_| ())
+            };
+            sent.map_err(|e| {
+                ZeptoError::Channel(format!("Failed to send Telegram attachment: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+
     fn build_bot(token: &str) -> Result<teloxide::Bot> {
         let client = teloxide::net::default_reqwest_settings()
             .no_proxy()
@@ -335,6 +402,15 @@ impl Channel for TelegramChannel {
         let longterm_memory = self.longterm_memory.clone();
         // Share the same running flag with the spawned task so state stays in sync
         let running_clone = Arc::clone(&self.running);
+        if self.config.webhook.is_some() && self.webhook.is_none() {
+            warn!("Telegram webhook configured but the gateway route is not available; using long polling");
+        }
+        let webhook = self.webhook.clone();
+        let public_url = self
+            .config
+            .webhook
+            .as_ref()
+            .and_then(|w| w.public_url.clone());
 
         let bot = match Self::build_bot(&token) {
             Ok(bot) => bot,
@@ -410,6 +486,23 @@ impl Channel for TelegramChannel {
                     }
                 }
 
+                // Register the webhook ourselves when a fixed public URL is
+                // configured; otherwise the tunnel supervisor registers it.
+                if let (Some(hook), Some(public_url)) = (&webhook, &public_url) {
+                    let url = crate::tunnel::webhooks::callback_url(public_url, hook.path());
+                    match telegram_webhook::set_webhook(
+                        &reqwest::Client::new(),
+                        &token,
+                        &url,
+                        hook.secret(),
+                    )
+                    .await
+                    {
+                        Ok(()) => info!("Telegram webhook registered at {}", url),
+                        Err(e) => error!("Failed to register Telegram webhook: {}", e),
+                    }
+                }
+
                 // Create the handler for incoming messages
                 // Note: dptree injects dependencies separately, not as tuples
                 let handler =
@@ -687,6 +780,31 @@ impl Channel for TelegramChannel {
                                         inbound.with_metadata("telegram_thread_id", tid);
                                 }
 
+                                // Group chats: tag for group-history buffering and
+                                // mark replies to the bot as addressed.
+                                if !msg.chat.is_private() {
+                                    inbound = inbound.with_metadata(
+                                        crate::channels::group_history::IS_GROUP_METADATA_KEY,
+                                        "true",
+                                    );
+                                    let replied_to_bot = msg
+                                        .reply_to_message()
+                                        .and_then(|r| r.from.as_ref())
+                                        .is_some_and(|u| u.is_bot);
+                                    if replied_to_bot {
+                                        inbound = inbound.with_metadata(
+                                            crate::channels::group_history::ADDRESSED_METADATA_KEY,
+                                            "true",
+                                        );
+                                    }
+                                }
+                                if let Some(name) = user
+                                    .map(|u| u.first_name.clone())
+                                    .filter(|n| !n.is_empty())
+                                {
+                                    inbound = inbound.with_metadata("sender_name", &name);
+                                }
+
                                 let override_entry = {
                                     let overrides = model_overrides.read().await;
                                     overrides.get(&override_key).cloned()
@@ -781,9 +899,26 @@ impl Channel for TelegramChannel {
 
                 info!("Telegram bot dispatcher started, waiting for messages...");
 
+                let run = async {
+                    match webhook {
+                        Some(hook) => {
+                            dispatcher
+                                .dispatch_with_listener(
+                                    hook.listener(),
+                                    LoggingErrorHandler::with_custom_text(
+                                        "Telegram webhook listener error",
+                                    ),
+                                )
+                                .await
+                        }
+                        // Polling deletes any webhook left from webhook mode.
+                        None => dispatcher.dispatch().await,
+                    }
+                };
+
                 // Run until shutdown signal
                 tokio::select! {
-                    _ = dispatcher.dispatch() => {
+                    _ = run => {
                         info!("Telegram dispatcher completed");
                     }
                     _ = shutdown_rx.recv() => {
@@ -824,6 +959,11 @@ impl Channel for TelegramChannel {
             }
         }
 
+        // Stop accepting webhook deliveries
+        if let Some(hook) = &self.webhook {
+            hook.close();
+        }
+
         // Clear cached bot
         self.bot = None;
 
@@ -866,33 +1006,56 @@ impl Channel for TelegramChannel {
             .as_ref()
             .ok_or_else(|| ZeptoError::Channel("Telegram bot not initialized".to_string()))?;
 
-        // Chunk and render markdown into Telegram-supported HTML blocks
-        let chunks = super::telegram_markdown::render_and_chunk_telegram_markdown(
-            &msg.content,
-            self.config.chunk_size,
-        );
-
-        for chunk in chunks {
-            if chunk.is_empty() {
-                continue;
+        let limit = self
+            .config
+            .chunk_size
+            .min(Dialect::TelegramMarkdownV2.max_length());
+        let thread_id = msg
+            .metadata
+            .get("telegram_thread_id")
+            .and_then(|tid| tid.parse::<i32>().ok())
+            .map(|tid| teloxide::types::ThreadId(teloxide::types::MessageId(tid)));
+
+        if msg.has_media() {
+            Self::send_media(bot, ChatId(chat_id), thread_id, &msg.media).await?;
+            if msg.content.trim().is_empty() {
+                return Ok(());
             }
+        }
 
+        for chunk in format_message(&msg.content, Dialect::TelegramMarkdownV2, limit) {
             let mut req = bot
-                .send_message(ChatId(chat_id), chunk)
-                .parse_mode(ParseMode::Html);
-
+                .send_message(ChatId(chat_id), &chunk.text)
+                .parse_mode(ParseMode::MarkdownV2);
             // Route reply to the correct forum topic when thread metadata is present.
-            if let Some(thread_id_str) = msg.metadata.get("telegram_thread_id") {
-                if let Ok(tid) = thread_id_str.parse::<i32>() {
-                    req = req.message_thread_id(teloxide::types::ThreadId(
-                        teloxide::types::MessageId(tid),
-                    ));
-                }
+            if let Some(tid) = thread_id {
+                req = req.message_thread_id(tid);
             }
 
-            req.await.map_err(|e| {
-                ZeptoError::Channel(format!("Failed to send Telegram message chunk: {}", e))
-            })?;
+            match req.await {
+                Ok(_) => {}
+                // Telegram rejected the markup; resend this chunk as plain text.
+                Err(e) if e.to_string().contains("can't parse entities") => {
+                    warn!(
+                        "Telegram: MarkdownV2 rejected ({}), resending as plain text",
+                        e
+                    );
+                    let mut req =
+                        bot.send_message(ChatId(chat_id), render(&chunk.source, Dialect::Plain));
+                    if let Some(tid) = thread_id {
+                        req = req.message_thread_id(tid);
+                    }
+                    req.await.map_err(|e| {
+                        ZeptoError::Channel(format!("Failed to send Telegram message chunk: {}", e))
+                    })?;
+                }
+                Err(e) => {
+                    return Err(ZeptoError::Channel(format!(
+                        "Failed to send Telegram message chunk: {}",
+                        e
+                    )));
+                }
+            }
         }
 
         info!("Telegram: Message sent successfully to chat {}", chat_id);
@@ -910,12 +1073,60 @@ impl Channel for TelegramChannel {
     fn is_allowed(&self, user_id: &str) -> bool {
         self.base_config.is_allowed(user_id)
     }
+
+    fn capabilities(&self) -> Option<&dyn ChannelCapabilities> {
+        Some(self)
+    }
+}
+
+/// Telegram shows "typing…" via `sendChatAction`; bots cannot mark
+/// messages read, so `mark_read` keeps the default no-op.
+#[async_trait]
+impl ChannelCapabilities for TelegramChannel {
+    async fn send_typing(&self, event: &LifecycleEvent) -> Result<()> {
+        use teloxide::prelude::*;
+        use teloxide::types::{ChatAction, ChatId};
+
+        if !self.running.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        let Some(bot) = self.bot.as_ref() else {
+            return Ok(());
+        };
+        let chat_id: i64 = event.chat_id.parse().map_err(|_| {
+            ZeptoError::Channel(format!("Invalid Telegram chat ID: {}", event.chat_id))
+        })?;
+
+        let mut req = bot.send_chat_action(ChatId(chat_id), ChatAction::Typing);
+        if let Some(tid) = event
+            .metadata
+            .get("telegram_thread_id")
+            .and_then(|tid| tid.parse::<i32>().ok())
+        {
+            req = req.message_thread_id(teloxide::types::ThreadId(teloxide::types::MessageId(tid)));
+        }
+        req.await.map_err(|e| {
+            ZeptoError::Channel(format!("Failed to send Telegram chat action: {}", e))
+        })?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_telegram_photo() {
+        let png = MediaAttachment::new(MediaType::Image).with_mime_type("image/png");
+        let svg = MediaAttachment::new(MediaType::Image).with_mime_type("image/svg+xml");
+        let pdf = MediaAttachment::new(MediaType::Document).with_mime_type("application/pdf");
+        assert!(is_telegram_photo(&png));
+        assert!(is_telegram_photo(&MediaAttachment::new(MediaType::Image)));
+        assert!(!is_telegram_photo(&svg));
+        assert!(!is_telegram_photo(&pdf));
+    }
+
     #[test]
     fn test_telegram_channel_creation() {
         let config = TelegramConfig {
@@ -0,0 +1,237 @@
+//! Telegram webhook receive mode.
+//!
+//! When `channels.telegram.webhook` is set, the gateway HTTP server accepts
+//! Telegram's update POSTs at the configured path and hands them to the
+//! running [`TelegramChannel`](super::TelegramChannel) through a
+//! [`TelegramWebhook`], instead of the channel long polling `getUpdates`.
+//! Each POST must carry the secret token registered with `setWebhook`.
+
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use teloxide::stop::{mk_stop_token, StopToken};
+use teloxide::types::Update;
+use teloxide::update_listeners::{StatefulListener, UpdateListener};
+
+use crate::config::TelegramConfig;
+use crate::error::{Result, ZeptoError};
+
+/// Header Telegram uses to echo the webhook secret.
+pub const SECRET_HEADER: &str = "x-telegram-bot-api-secret-token";
+
+/// Telegram Bot API base URL.
+const TELEGRAM_API_BASE: &str = "https://api.telegram.org";
+
+/// Outcome of one webhook delivery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookDelivery {
+    /// Update queued for the channel.
+    Accepted,
+    /// Missing or wrong secret token.
+    Unauthorized,
+    /// Body is not a Telegram update.
+    BadRequest,
+    /// The Telegram channel is not running in webhook mode.
+    Unavailable,
+}
+
+type UpdateSender = UnboundedSender<std::result::Result<Update, Infallible>>;
+type UpdateReceiver = UnboundedReceiver<std::result::Result<Update, Infallible>>;
+
+/// Hands updates from the gateway's webhook route to the Telegram channel.
+///
+/// Cheap to clone; the gateway and the channel share one instance.
+#[derive(Clone)]
+pub struct TelegramWebhook {
+    path: String,
+    secret: String,
+    sender: Arc<Mutex<Option<UpdateSender>>>,
+}
+
+impl TelegramWebhook {
+    /// Build the webhook for `config`, or `None` when webhook mode is off.
+    pub fn from_config(config: &TelegramConfig) -> Option<Self> {
+        let webhook = config.webhook.as_ref()?;
+        Some(Self {
+            path: webhook.path.clone(),
+            secret: webhook_secret(config),
+            sender: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// URL path the gateway serves this webhook on.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Secret token Telegram must send with each update.
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    /// Validate and queue one update POST.
+    pub fn deliver(&self, secret: Option<&str>, body: &[u8]) -> WebhookDelivery {
+        let authorized =
+            secret.is_some_and(|s| bool::from(s.as_bytes().ct_eq(self.secret.as_bytes())));
+        if !authorized {
+            return WebhookDelivery::Unauthorized;
+        }
+        let Ok(update) = serde_json::from_slice::<Update>(body) else {
+            return WebhookDelivery::BadRequest;
+        };
+        match self.lock().as_ref() {
+            Some(tx) if tx.unbounded_send(Ok(update)).is_ok() => WebhookDelivery::Accepted,
+            _ => WebhookDelivery::Unavailable,
+        }
+    }
+
+    /// Start routing deliveries to a new listener for the dispatcher.
+    ///
+    /// Replaces any previous listener, whose stream then ends.
+    pub(crate) fn listener(&self) -> impl UpdateListener<Err = Infallible> {
+        let (tx, rx) = unbounded();
+        *self.lock() = Some(tx);
+        let (stop_token, _stop_flag) = mk_stop_token();
+
+        fn updates(state: &mut (UpdateReceiver, StopToken)) -> &mut UpdateReceiver {
+            &mut state.0
+        }
+        StatefulListener::new(
+            (rx, stop_token),
+            updates,
+            |state: &mut (UpdateReceiver, StopToken)| state.1.clone(),
+        )
+    }
+
+    /// Stop accepting deliveries.
+    pub(crate) fn close(&self) {
+        self.lock().take();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<UpdateSender>> {
+        self.sender.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The configured secret token, or one derived from the bot token so the
+/// gateway, the channel and the tunnel registrar agree without shared state.
+pub fn webhook_secret(config: &TelegramConfig) -> String {
+    if let Some(secret) = config
+        .webhook
+        .as_ref()
+        .and_then(|w| w.secret_token.as_deref())
+        .filter(|s| !s.is_empty())
+    {
+        return secret.to_string();
+    }
+    let digest = Sha256::digest(format!("zeptoclaw-telegram-webhook:{}", config.token));
+    hex::encode(&digest[..24])
+}
+
+/// Point the bot's webhook at `url` with `secret` via `setWebhook`.
+pub async fn set_webhook(
+    client: &reqwest::Client,
+    token: &str,
+    url: &str,
+    secret: &str,
+) -> Result<()> {
+    let response = client
+        .post(format!("{}/bot{}/setWebhook", TELEGRAM_API_BASE, token))
+        .json(&json!({
+            "url": url,
+            "secret_token": secret,
+            "allowed_updates": ["message"],
+        }))
+        .send()
+        .await
+        .map_err(|e| ZeptoError::Channel(format!("Telegram setWebhook: {}", e)))?;
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() || body["ok"] != json!(true) {
+        return Err(ZeptoError::Channel(format!(
+            "Telegram setWebhook failed ({}): {}",
+            status,
+            body["description"].as_str().unwrap_or("unknown error")
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TelegramWebhookConfig;
+
+    fn config(secret: Option<&str>) -> TelegramConfig {
+        TelegramConfig {
+            enabled: true,
+            token: "123:abc".to_string(),
+            webhook: Some(TelegramWebhookConfig {
+                secret_token: secret.map(str::to_string),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    const UPDATE: &str = r#"{"update_id":1,"message":{"message_id":7,"date":1700000000,
+        "chat":{"id":42,"type":"private","first_name":"A"},
+        "from":{"id":42,"is_bot":false,"first_name":"A"},"text":"hi"}}"#;
+
+    #[test]
+    fn test_polling_mode_has_no_webhook() {
+        let config = TelegramConfig {
+            token: "123:abc".to_string(),
+            ..Default::default()
+        };
+        assert!(TelegramWebhook::from_config(&config).is_none());
+    }
+
+    #[test]
+    fn test_secret_is_configured_or_derived() {
+        assert_eq!(webhook_secret(&config(Some("s3cret"))), "s3cret");
+        let derived = webhook_secret(&config(None));
+        assert_eq!(derived.len(), 48);
+        assert!(derived
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'));
+        assert_eq!(derived, webhook_secret(&config(Some(""))));
+    }
+
+    #[test]
+    fn test_deliver_checks_secret_and_listener() {
+        let hook = TelegramWebhook::from_config(&config(Some("s3cret"))).unwrap();
+        assert_eq!(hook.path(), "/telegram/webhook");
+        let body = UPDATE.as_bytes();
+
+        assert_eq!(hook.deliver(None, body), WebhookDelivery::Unauthorized);
+        assert_eq!(
+            hook.deliver(Some("wrong"), body),
+            WebhookDelivery::Unauthorized
+        );
+        assert_eq!(
+            hook.deliver(Some("s3cret"), body),
+            WebhookDelivery::Unavailable
+        );
+
+        let _listener = hook.listener();
+        assert_eq!(
+            hook.deliver(Some("s3cret"), b"not json"),
+            WebhookDelivery::BadRequest
+        );
+        assert_eq!(
+            hook.deliver(Some("s3cret"), body),
+            WebhookDelivery::Accepted
+        );
+
+        hook.close();
+        assert_eq!(
+            hook.deliver(Some("s3cret"), body),
+            WebhookDelivery::Unavailable
+        );
+    }
+}
@@ -3,9 +3,11 @@
 //! This module defines the `Channel` trait that all communication channels
 //! (Telegram, Discord, Slack, etc.) must implement, along with supporting types.
 
+use std::time::Duration;
+
 use async_trait::async_trait;
 
-use crate::bus::OutboundMessage;
+use crate::bus::{LifecycleEvent, OutboundMessage};
 use crate::error::Result;
 
 /// The `Channel` trait defines the interface for all communication channels.
@@ -107,6 +109,44 @@ pub trait Channel: Send + Sync {
     ///
     /// `true` if the user is allowed, `false` otherwise.
     fn is_allowed(&self, user_id: &str) -> bool;
+
+    /// Returns the channel's optional capabilities (typing indicators, read
+    /// receipts), or `None` if it supports neither.
+    ///
+    /// Channels that implement [`ChannelCapabilities`] return `Some(self)`.
+    fn capabilities(&self) -> Option<&dyn ChannelCapabilities> {
+        None
+    }
+}
+
+/// Optional channel features driven by agent lifecycle events.
+///
+/// The [`ChannelManager`](super::ChannelManager) subscribes to the bus's
+/// [`LifecycleEvent`]s: when the agent starts on a message it calls
+/// [`mark_read`](Self::mark_read) once, then
+/// [`send_typing`](Self::send_typing) every
+/// [`typing_interval`](Self::typing_interval) until the agent finishes.
+/// Every method defaults to a no-op, so channels implement only what their
+/// platform supports.
+#[async_trait]
+pub trait ChannelCapabilities: Send + Sync {
+    /// Shows a "typing" indicator in the event's chat.
+    async fn send_typing(&self, _event: &LifecycleEvent) -> Result<()> {
+        Ok(())
+    }
+
+    /// Marks the inbound message described by the event as read.
+    async fn mark_read(&self, _event: &LifecycleEvent) -> Result<()> {
+        Ok(())
+    }
+
+    /// How often to repeat the typing indicator while the agent works.
+    ///
+    /// Platforms expire the indicator on their own (Telegram after about
+    /// five seconds), so it must be refreshed.
+    fn typing_interval(&self) -> Duration {
+        Duration::from_secs(4)
+    }
 }
 
 /// Base configuration shared by all channels.
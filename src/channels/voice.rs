@@ -0,0 +1,730 @@
+//! Local voice channel for `zeptoclaw listen`.
+//!
+//! Microphone audio comes from an external recorder writing raw 16 kHz mono
+//! signed 16-bit PCM to stdout. An energy-based VAD cuts it into utterances,
+//! each utterance is transcribed with the configured transcription backend,
+//! and utterances that start with a wake word (or arrive within the
+//! follow-up window after a reply) are published to the agent. Replies are
+//! printed and spoken through an external TTS command that reads stdin.
+//!
+//! Wake-word spotting runs on the transcript, so with the `whisper-cpp`
+//! backend nothing leaves the machine.
+//!
+//! # Feature Gate
+//!
+//! This module requires the `voice` feature:
+//! ```bash
+//! cargo build --features voice
+//! ```
+
+use std::collections::VecDeque;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, warn};
+
+use crate::bus::{InboundMessage, MessageBus, OutboundMessage};
+use crate::channels::types::Channel;
+use crate::config::VoiceChannelConfig;
+use crate::error::{Result, ZeptoError};
+use crate::transcription::TranscriberService;
+
+/// Channel name, also used as the chat ID.
+pub const VOICE_CHANNEL: &str = "voice";
+/// Capture sample rate in Hz.
+pub const SAMPLE_RATE: u32 = 16_000;
+
+const FRAME_MS: u64 = 30;
+const FRAME_SAMPLES: usize = (SAMPLE_RATE as u64 * FRAME_MS / 1000) as usize;
+/// Audio kept from before speech onset so the first syllable isn't clipped.
+const PREROLL_FRAMES: usize = 10;
+/// RMS below which a frame is never speech, whatever the noise floor.
+const MIN_SPEECH_RMS: f32 = 300.0;
+/// Utterances waiting for transcription before new ones are dropped.
+const UTTERANCE_QUEUE: usize = 4;
+/// Upper bound for speaking one reply.
+const TTS_TIMEOUT: Duration = Duration::from_secs(120);
+
+static MARKDOWN_LINK_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[([^\]]+)\]\([^)]*\)").unwrap());
+
+// ---------------------------------------------------------------------------
+// Voice activity detection
+// ---------------------------------------------------------------------------
+
+/// Energy-based voice activity detector over 30 ms frames.
+///
+/// A frame is voiced when its RMS exceeds both an absolute minimum and
+/// `vad_threshold` times the noise floor, which follows the background
+/// level while nobody is speaking.
+#[derive(Debug)]
+pub struct Vad {
+    threshold: f32,
+    noise_floor: Option<f32>,
+    preroll: VecDeque<Vec<i16>>,
+    utterance: Vec<i16>,
+    in_speech: bool,
+    voiced_frames: usize,
+    silent_frames: usize,
+    hangover_frames: usize,
+    min_voiced_frames: usize,
+    max_samples: usize,
+}
+
+impl Vad {
+    pub fn new(config: &VoiceChannelConfig) -> Self {
+        let frames = |ms: u64| (ms / FRAME_MS).max(1) as usize;
+        Self {
+            threshold: config.vad_threshold.max(1.0),
+            noise_floor: None,
+            preroll: VecDeque::with_capacity(PREROLL_FRAMES + 1),
+            utterance: Vec::new(),
+            in_speech: false,
+            voiced_frames: 0,
+            silent_frames: 0,
+            hangover_frames: frames(config.silence_ms),
+            min_voiced_frames: frames(config.min_speech_ms),
+            max_samples: (config.max_utterance_secs.max(1) * SAMPLE_RATE as u64) as usize,
+        }
+    }
+
+    /// Feed one frame. Returns the utterance once trailing silence (or the
+    /// length cap) ends it; too-short bursts are dropped.
+    pub fn push(&mut self, frame: &[i16]) -> Option<Vec<i16>> {
+        let energy = rms(frame);
+        let floor = *self.noise_floor.get_or_insert(energy.max(1.0));
+        let voiced = energy >= MIN_SPEECH_RMS && energy > floor * self.threshold;
+
+        if !self.in_speech {
+            if voiced {
+                self.in_speech = true;
+                self.utterance = self.preroll.drain(..).flatten().collect();
+                self.utterance.extend_from_slice(frame);
+                self.voiced_frames = 1;
+                self.silent_frames = 0;
+            } else {
+                self.noise_floor = Some((floor * 0.95 + energy * 0.05).max(1.0));
+                self.preroll.push_back(frame.to_vec());
+                if self.preroll.len() > PREROLL_FRAMES {
+                    self.preroll.pop_front();
+                }
+            }
+            return None;
+        }
+
+        self.utterance.extend_from_slice(frame);
+        if voiced {
+            self.voiced_frames += 1;
+            self.silent_frames = 0;
+        } else {
+            self.silent_frames += 1;
+        }
+        if self.silent_frames < self.hangover_frames && self.utterance.len() < self.max_samples {
+            return None;
+        }
+
+        let long_enough = self.voiced_frames >= self.min_voiced_frames;
+        let utterance = std::mem::take(&mut self.utterance);
+        self.reset();
+        long_enough.then_some(utterance)
+    }
+
+    /// Drop any partial utterance (e.g. while the TTS is speaking). The
+    /// noise floor is kept.
+    pub fn reset(&mut self) {
+        self.in_speech = false;
+        self.utterance.clear();
+        self.preroll.clear();
+        self.voiced_frames = 0;
+        self.silent_frames = 0;
+    }
+}
+
+fn rms(frame: &[i16]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = frame.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    (sum / frame.len() as f64).sqrt() as f32
+}
+
+/// Wrap mono signed 16-bit PCM in a WAV container.
+pub fn wav_bytes(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+// ---------------------------------------------------------------------------
+// Wake word
+// ---------------------------------------------------------------------------
+
+/// Lowercase alphanumeric form of one word ("Zepto!" → "zepto").
+fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// The text after a leading wake word, or `None` if the transcript doesn't
+/// start with one. Case and punctuation are ignored ("Hey, Zepto!" matches
+/// `hey zepto`). With no wake words every transcript passes unchanged.
+pub fn strip_wake_word<'a>(transcript: &'a str, wake_words: &[String]) -> Option<&'a str> {
+    if wake_words.is_empty() {
+        return Some(transcript.trim());
+    }
+    // Words with their end offsets, skipping pure punctuation.
+    let words: Vec<(String, usize)> = transcript
+        .split_whitespace()
+        .map(|w| {
+            let end = w.as_ptr() as usize - transcript.as_ptr() as usize + w.len();
+            (normalize_word(w), end)
+        })
+        .filter(|(w, _)| !w.is_empty())
+        .collect();
+
+    wake_words.iter().find_map(|wake| {
+        let wake: Vec<String> = wake
+            .split_whitespace()
+            .map(normalize_word)
+            .filter(|w| !w.is_empty())
+            .collect();
+        if wake.is_empty() || words.len() < wake.len() {
+            return None;
+        }
+        if words
+            .iter()
+            .zip(&wake)
+            .any(|((word, _), wake)| word != wake)
+        {
+            return None;
+        }
+        let end = words[wake.len() - 1].1;
+        Some(transcript[end..].trim_start_matches(|c: char| !c.is_alphanumeric()))
+    })
+}
+
+/// What an utterance means to the wake-word gate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Heard {
+    /// Forward this text to the agent.
+    Command(String),
+    /// A bare wake word; the next utterance needs none.
+    Wake,
+    /// Not addressed to the agent.
+    Ignored,
+}
+
+/// Wake-word gate with a follow-up window.
+#[derive(Debug)]
+pub struct WakeGate {
+    wake_words: Vec<String>,
+    follow_up: Duration,
+    open_until: Option<Instant>,
+}
+
+impl WakeGate {
+    pub fn new(wake_words: Vec<String>, follow_up: Duration) -> Self {
+        Self {
+            wake_words,
+            follow_up,
+            open_until: None,
+        }
+    }
+
+    /// Classify a transcript heard at `now`.
+    pub fn hear(&mut self, transcript: &str, now: Instant) -> Heard {
+        let transcript = transcript.trim();
+        // Whisper renders noise as "[BLANK_AUDIO]", "(music)" and the like.
+        let annotation = (transcript.starts_with('[') && transcript.ends_with(']'))
+            || (transcript.starts_with('(') && transcript.ends_with(')'));
+        if transcript.is_empty() || annotation {
+            return Heard::Ignored;
+        }
+
+        let open = self.open_until.is_some_and(|until| now < until);
+        match strip_wake_word(transcript, &self.wake_words) {
+            Some("") => {
+                self.open_until = Some(now + self.follow_up);
+                Heard::Wake
+            }
+            Some(rest) => {
+                self.open_until = None;
+                Heard::Command(rest.to_string())
+            }
+            None if open => {
+                self.open_until = None;
+                Heard::Command(transcript.to_string())
+            }
+            None => Heard::Ignored,
+        }
+    }
+
+    /// Accept the next utterance without a wake word until the follow-up
+    /// window after `now` closes.
+    pub fn open(&mut self, now: Instant) {
+        if !self.follow_up.is_zero() {
+            self.open_until = Some(now + self.follow_up);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Text to speech
+// ---------------------------------------------------------------------------
+
+/// Reply text with Markdown that reads badly aloud removed: code blocks,
+/// emphasis markers, headings and link targets.
+pub fn speakable(text: &str) -> String {
+    let mut in_code = false;
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        let line = line.trim_start_matches(['#', '>', ' ']);
+        let line = MARKDOWN_LINK_RE.replace_all(line, "$1");
+        let line: String = line.chars().filter(|c| !matches!(c, '*' | '`')).collect();
+        if !line.trim().is_empty() {
+            lines.push(line.trim().to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+fn default_capture_command() -> Vec<String> {
+    let argv: &[&str] = if cfg!(target_os = "linux") {
+        &[
+            "arecord", "-q", "-t", "raw", "-f", "S16_LE", "-r", "16000", "-c", "1",
+        ]
+    } else {
+        &[
+            "rec",
+            "-q",
+            "-t",
+            "raw",
+            "-r",
+            "16000",
+            "-e",
+            "signed-integer",
+            "-b",
+            "16",
+            "-c",
+            "1",
+            "-",
+        ]
+    };
+    argv.iter().map(|s| s.to_string()).collect()
+}
+
+fn default_tts_command() -> Vec<String> {
+    let argv: &[&str] = if cfg!(target_os = "macos") {
+        &["say"]
+    } else {
+        &["espeak-ng", "--stdin"]
+    };
+    argv.iter().map(|s| s.to_string()).collect()
+}
+
+async fn speak(argv: &[String], text: &str) -> Result<()> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| ZeptoError::Channel("Empty TTS command".to_string()))?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| ZeptoError::Channel(format!("Failed to run TTS '{}': {}", program, e)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .await
+            .map_err(|e| ZeptoError::Channel(format!("TTS write error: {}", e)))?;
+    }
+    let status = tokio::time::timeout(TTS_TIMEOUT, child.wait())
+        .await
+        .map_err(|_| ZeptoError::Channel("TTS timed out".to_string()))?
+        .map_err(|e| ZeptoError::Channel(format!("TTS error: {}", e)))?;
+    if !status.success() {
+        return Err(ZeptoError::Channel(format!(
+            "TTS '{}' failed ({})",
+            program, status
+        )));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Channel
+// ---------------------------------------------------------------------------
+
+/// Microphone in, speaker out.
+pub struct VoiceChannel {
+    config: VoiceChannelConfig,
+    transcriber: Arc<TranscriberService>,
+    bus: Arc<MessageBus>,
+    running: Arc<AtomicBool>,
+    /// Set while the TTS speaks so the agent doesn't hear itself.
+    speaking: Arc<AtomicBool>,
+    gate: Arc<Mutex<WakeGate>>,
+    shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+impl VoiceChannel {
+    pub fn new(
+        config: VoiceChannelConfig,
+        transcriber: TranscriberService,
+        bus: Arc<MessageBus>,
+    ) -> Self {
+        let gate = WakeGate::new(
+            config.wake_words.clone(),
+            Duration::from_secs(config.follow_up_secs),
+        );
+        Self {
+            config,
+            transcriber: Arc::new(transcriber),
+            bus,
+            running: Arc::new(AtomicBool::new(false)),
+            speaking: Arc::new(AtomicBool::new(false)),
+            gate: Arc::new(Mutex::new(gate)),
+            shutdown_tx: None,
+        }
+    }
+
+    fn capture_command(&self) -> Vec<String> {
+        if self.config.capture_command.is_empty() {
+            default_capture_command()
+        } else {
+            self.config.capture_command.clone()
+        }
+    }
+
+    fn tts_command(&self) -> Vec<String> {
+        if self.config.tts_command.is_empty() {
+            default_tts_command()
+        } else {
+            self.config.tts_command.clone()
+        }
+    }
+}
+
+#[async_trait]
+impl Channel for VoiceChannel {
+    fn name(&self) -> &str {
+        VOICE_CHANNEL
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        let argv = self.capture_command();
+        let (program, args) = argv
+            .split_first()
+            .ok_or_else(|| ZeptoError::Config("Empty voice capture_command".to_string()))?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                ZeptoError::Channel(format!("Failed to start capture '{}': {}", program, e))
+            })?;
+        let mut stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| ZeptoError::Channel("Capture command has no stdout".to_string()))?;
+        self.running.store(true, Ordering::SeqCst);
+
+        let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        self.shutdown_tx = Some(shutdown_tx);
+
+        // Transcription runs apart from capture so a slow backend never
+        // stalls the recorder's pipe.
+        let (utterance_tx, mut utterance_rx) = mpsc::channel::<Vec<i16>>(UTTERANCE_QUEUE);
+        let transcriber = self.transcriber.clone();
+        let gate = self.gate.clone();
+        let bus = self.bus.clone();
+        tokio::spawn(async move {
+            while let Some(pcm) = utterance_rx.recv().await {
+                let wav = wav_bytes(&pcm, SAMPLE_RATE);
+                let Some(transcript) = transcriber.transcribe_detailed(wav, "audio/wav").await
+                else {
+                    continue;
+                };
+                let heard = gate
+                    .lock()
+                    .expect("wake gate lock poisoned")
+                    .hear(&transcript.text, Instant::now());
+                match heard {
+                    Heard::Command(text) => {
+                        println!("you: {}", text);
+                        let msg = InboundMessage::new(VOICE_CHANNEL, "local", VOICE_CHANNEL, &text);
+                        if let Err(e) = bus.publish_inbound(msg).await {
+                            error!("Voice: failed to publish inbound message: {}", e);
+                        }
+                    }
+                    Heard::Wake => println!("(listening)"),
+                    Heard::Ignored => debug!("Voice: ignoring {:?}", transcript.text),
+                }
+            }
+        });
+
+        let mut vad = Vad::new(&self.config);
+        let running = self.running.clone();
+        let speaking = self.speaking.clone();
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; FRAME_SAMPLES * 2];
+            loop {
+                let read = tokio::select! {
+                    result = stdout.read_exact(&mut buf) => result,
+                    _ = &mut shutdown_rx => {
+                        info!("Voice channel shutdown signal received");
+                        break;
+                    }
+                };
+                if let Err(e) = read {
+                    error!("Voice capture ended: {}", e);
+                    break;
+                }
+                if speaking.load(Ordering::SeqCst) {
+                    vad.reset();
+                    continue;
+                }
+                let frame: Vec<i16> = buf
+                    .chunks_exact(2)
+                    .map(|b| i16::from_le_bytes([b[0], b[1]]))
+                    .collect();
+                if let Some(utterance) = vad.push(&frame) {
+                    if utterance_tx.try_send(utterance).is_err() {
+                        warn!("Voice: transcription is behind, dropping an utterance");
+                    }
+                }
+            }
+            let _ = child.kill().await;
+            running.store(false, Ordering::SeqCst);
+            info!("Voice channel stopped");
+        });
+
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(tx) = self.shutdown_tx.take() {
+            if tx.send(()).is_err() {
+                warn!("Voice shutdown receiver already dropped");
+            }
+        }
+        Ok(())
+    }
+
+    async fn send(&self, msg: OutboundMessage) -> Result<()> {
+        let text = speakable(&msg.content);
+        if text.is_empty() {
+            return Ok(());
+        }
+        println!("zeptoclaw: {}", msg.content.trim());
+
+        let result = if self.config.tts_enabled {
+            self.speaking.store(true, Ordering::SeqCst);
+            let result = speak(&self.tts_command(), &text).await;
+            self.speaking.store(false, Ordering::SeqCst);
+            result
+        } else {
+            Ok(())
+        };
+        self.gate
+            .lock()
+            .expect("wake gate lock poisoned")
+            .open(Instant::now());
+        result
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn is_allowed(&self, _user_id: &str) -> bool {
+        // Only the local microphone can reach this channel.
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(amplitude: i16) -> Vec<i16> {
+        (0..FRAME_SAMPLES)
+            .map(|i| if i % 2 == 0 { amplitude } else { -amplitude })
+            .collect()
+    }
+
+    fn config() -> VoiceChannelConfig {
+        VoiceChannelConfig {
+            silence_ms: 90,
+            min_speech_ms: 90,
+            max_utterance_secs: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_vad_detects_utterance_after_silence() {
+        let mut vad = Vad::new(&config());
+        for _ in 0..20 {
+            assert!(vad.push(&tone(50)).is_none());
+        }
+        for _ in 0..10 {
+            assert!(vad.push(&tone(5000)).is_none());
+        }
+        let mut utterance = None;
+        for _ in 0..3 {
+            utterance = utterance.or(vad.push(&tone(50)));
+        }
+        let utterance = utterance.expect("utterance after trailing silence");
+        // Pre-roll + speech + trailing silence.
+        assert_eq!(utterance.len(), (PREROLL_FRAMES + 10 + 3) * FRAME_SAMPLES);
+    }
+
+    #[test]
+    fn test_vad_drops_short_bursts_and_caps_length() {
+        let mut vad = Vad::new(&config());
+        vad.push(&tone(50));
+        vad.push(&tone(5000));
+        for _ in 0..3 {
+            assert!(vad.push(&tone(50)).is_none());
+        }
+
+        // 1 s cap = 33.3 frames; speech never stops.
+        let mut vad = Vad::new(&config());
+        vad.push(&tone(50));
+        let cut = (0..40).find_map(|_| vad.push(&tone(5000)));
+        let cut = cut.expect("utterance cut at the cap");
+        assert!(cut.len() >= SAMPLE_RATE as usize);
+    }
+
+    #[test]
+    fn test_vad_ignores_quiet_rooms_and_resets() {
+        let mut vad = Vad::new(&config());
+        // Below the absolute minimum, however it compares to the floor.
+        vad.push(&tone(1));
+        for _ in 0..10 {
+            assert!(vad.push(&tone(200)).is_none());
+        }
+        assert!(!vad.in_speech);
+
+        vad.push(&tone(5000));
+        assert!(vad.in_speech);
+        vad.reset();
+        assert!(!vad.in_speech);
+        assert!(vad.utterance.is_empty());
+    }
+
+    #[test]
+    fn test_wav_header() {
+        let wav = wav_bytes(&[1, -1], SAMPLE_RATE);
+        assert_eq!(wav.len(), 48);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 4);
+        assert_eq!(&wav[44..], &[1, 0, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn test_strip_wake_word() {
+        let wake = vec!["hey zepto".to_string(), "computer".to_string()];
+        assert_eq!(
+            strip_wake_word("Hey, Zepto! What's the time?", &wake),
+            Some("What's the time?")
+        );
+        assert_eq!(
+            strip_wake_word("Computer, lights off", &wake),
+            Some("lights off")
+        );
+        assert_eq!(strip_wake_word("Hey zepto.", &wake), Some(""));
+        assert_eq!(strip_wake_word("hey there zepto", &wake), None);
+        assert_eq!(strip_wake_word("Hey", &wake), None);
+        assert_eq!(strip_wake_word(" anything ", &[]), Some("anything"));
+    }
+
+    #[test]
+    fn test_wake_gate_follow_up_window() {
+        let mut gate = WakeGate::new(vec!["hey zepto".to_string()], Duration::from_secs(5));
+        let t0 = Instant::now();
+
+        assert_eq!(gate.hear("what time is it", t0), Heard::Ignored);
+        assert_eq!(gate.hear("[BLANK_AUDIO]", t0), Heard::Ignored);
+        assert_eq!(gate.hear("Hey Zepto", t0), Heard::Wake);
+        assert_eq!(
+            gate.hear("what time is it", t0 + Duration::from_secs(2)),
+            Heard::Command("what time is it".to_string())
+        );
+        // The window is used up by that command.
+        assert_eq!(gate.hear("and tomorrow", t0), Heard::Ignored);
+
+        gate.open(t0);
+        assert_eq!(
+            gate.hear("and tomorrow", t0 + Duration::from_secs(4)),
+            Heard::Command("and tomorrow".to_string())
+        );
+        gate.open(t0);
+        assert_eq!(
+            gate.hear("too late", t0 + Duration::from_secs(6)),
+            Heard::Ignored
+        );
+    }
+
+    #[test]
+    fn test_speakable_strips_markdown() {
+        let reply =
+            "# Weather\n\n**Sunny**, see [the forecast](https://x.test).\n```\ncode\n```\n> `done`";
+        assert_eq!(speakable(reply), "Weather\nSunny, see the forecast.\ndone");
+    }
+
+    #[test]
+    fn test_voice_channel_basics() {
+        let bus = Arc::new(MessageBus::new());
+        let channel = VoiceChannel::new(
+            VoiceChannelConfig::default(),
+            TranscriberService::new(Vec::new(), None),
+            bus,
+        );
+        assert_eq!(channel.name(), "voice");
+        assert!(!channel.is_running());
+        assert!(channel.is_allowed("anyone"));
+        assert!(!channel.capture_command().is_empty());
+        assert!(!channel.tts_command().is_empty());
+    }
+}
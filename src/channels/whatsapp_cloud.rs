@@ -8,9 +8,18 @@
 //! - `GET /whatsapp` — Webhook verification (Meta sends challenge)
 //! - `POST /whatsapp` — Inbound message/status notifications
 //!
+//! # Inbound
+//!
+//! Text, audio (optionally transcribed), image, video, sticker and document
+//! messages are accepted. Media is downloaded through the Graph API into a
+//! [`MediaAttachment`]. While the agent works on a message it is marked
+//! read and a typing indicator is shown, when `read_receipts` is on.
+//!
 //! # Outbound
 //!
-//! Sends replies via `https://graph.facebook.com/v18.0/{phone_number_id}/messages`
+//! Sends replies via `https://graph.facebook.com/v18.0/{phone_number_id}/messages`.
+//! Free-form replies are only allowed within 24 hours of the user's last
+//! message; after that, `fallback_template` (if configured) is sent instead.
 
 use async_trait::async_trait;
 use futures::FutureExt;
@@ -20,17 +29,21 @@ use serde_json::{json, Value};
 use sha2::Digest;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpListener;
 use tracing::{debug, error, info, warn};
 
-use crate::bus::{InboundMessage, MessageBus, OutboundMessage};
+use crate::bus::{
+    InboundMessage, LifecycleEvent, MediaAttachment, MediaType, MessageBus, OutboundMessage,
+};
 use crate::config::WhatsAppCloudConfig;
 use crate::error::{Result, ZeptoError};
 
-use super::{BaseChannelConfig, Channel};
+use super::formatting::{format_message, Dialect};
+use super::{BaseChannelConfig, Channel, ChannelCapabilities};
 
-const WHATSAPP_API_BASE: &str = "https://graph.facebook.com/v18.0";
+pub(crate) const WHATSAPP_API_BASE: &str = "https://graph.facebook.com/v18.0";
 
 /// Maximum allowed request body size (1 MB).
 const MAX_BODY_SIZE: usize = 1_048_576;
@@ -40,6 +53,12 @@ const MAX_HEADER_SIZE: usize = 8_192;
 
 /// WhatsApp text message character limit.
 const MAX_MESSAGE_LENGTH: usize = 4096;
+
+/// Largest inbound media file downloaded into an attachment (16 MB).
+const MAX_MEDIA_SIZE: usize = 16 * 1024 * 1024;
+
+/// Graph API error code for a free-form message outside the 24-hour window.
+const REENGAGEMENT_ERROR_CODE: i64 = 131047;
 const SHA256_BLOCK_SIZE: usize = 64;
 const SHA256_OUTPUT_SIZE: usize = 32;
 
@@ -109,6 +128,31 @@ struct WebhookMessage {
     /// Audio content (present when type = "audio").
     #[serde(default)]
     audio: Option<AudioContent>,
+    /// Image content (present when type = "image").
+    #[serde(default)]
+    image: Option<MediaContent>,
+    /// Video content (present when type = "video").
+    #[serde(default)]
+    video: Option<MediaContent>,
+    /// Document content (present when type = "document").
+    #[serde(default)]
+    document: Option<MediaContent>,
+    /// Sticker content (present when type = "sticker").
+    #[serde(default)]
+    sticker: Option<MediaContent>,
+}
+
+impl WebhookMessage {
+    /// The media object and attachment type for image/video/document/sticker messages.
+    fn media(&self) -> Option<(&MediaContent, MediaType)> {
+        match self.msg_type.as_str() {
+            "image" => self.image.as_ref().map(|m| (m, MediaType::Image)),
+            "sticker" => self.sticker.as_ref().map(|m| (m, MediaType::Image)),
+            "video" => self.video.as_ref().map(|m| (m, MediaType::Video)),
+            "document" => self.document.as_ref().map(|m| (m, MediaType::Document)),
+            _ => None,
+        }
+    }
 }
 
 /// Text content within a message.
@@ -128,6 +172,20 @@ struct AudioContent {
     mime_type: String,
 }
 
+/// Image, video, document or sticker content within a WhatsApp message.
+#[derive(Debug, Clone, Deserialize)]
+struct MediaContent {
+    /// Media object ID — used to fetch download URL from Meta API.
+    id: String,
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default)]
+    caption: Option<String>,
+    /// Original filename (documents only).
+    #[serde(default)]
+    filename: Option<String>,
+}
+
 /// Contact info from the webhook.
 #[derive(Debug, Deserialize)]
 struct WebhookContact {
@@ -256,16 +314,11 @@ fn parse_http_request(raw: &[u8]) -> Result<ParsedHttpRequest> {
     })
 }
 
-/// Extract a query parameter value by name from a query string.
-fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
-    query.split('&').find_map(|pair| {
-        let (key, value) = pair.split_once('=')?;
-        if key == name {
-            Some(value)
-        } else {
-            None
-        }
-    })
+/// Extract a percent-decoded query parameter value by name from a query string.
+fn query_param(query: &str, name: &str) -> Option<String> {
+    url::form_urlencoded::parse(query.as_bytes())
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
 }
 
 /// Extract `Content-Length` from headers.
@@ -356,51 +409,67 @@ fn extract_text_messages(
     messages
 }
 
-/// Download media URL and transcribe audio; returns transcript or None on any failure.
-async fn fetch_and_transcribe(
-    svc: &crate::transcription::TranscriberService,
-    media_id: &str,
-    mime_type: &str,
-    token: &str,
-    client: &reqwest::Client,
-) -> Option<String> {
+/// Download a media object by ID via the Graph API.
+///
+/// Returns `None` (after logging) when the lookup or download fails or the
+/// file exceeds [`MAX_MEDIA_SIZE`].
+async fn download_media(media_id: &str, token: &str, client: &reqwest::Client) -> Option<Vec<u8>> {
     // Resolve media download URL via Meta Graph API
-    let meta_url = format!("https://graph.facebook.com/v18.0/{}", media_id);
+    let meta_url = format!("{}/{}", WHATSAPP_API_BASE, media_id);
     let url_resp = client.get(&meta_url).bearer_auth(token).send().await.ok()?;
     let url_json: serde_json::Value = url_resp.json().await.ok()?;
     let media_url = url_json.get("url")?.as_str()?.to_string();
+    if url_json
+        .get("file_size")
+        .and_then(Value::as_u64)
+        .is_some_and(|size| size as usize > MAX_MEDIA_SIZE)
+    {
+        warn!("WhatsApp Cloud: media {} exceeds size limit", media_id);
+        return None;
+    }
 
-    // Download audio bytes
-    let audio_resp = client
+    let resp = client
         .get(&media_url)
         .bearer_auth(token)
         .send()
         .await
         .ok()?;
-    if !audio_resp.status().is_success() {
+    if !resp.status().is_success() {
         warn!(
-            "WhatsApp Cloud: failed to download audio HTTP {}",
-            audio_resp.status()
+            "WhatsApp Cloud: failed to download media HTTP {}",
+            resp.status()
         );
         return None;
     }
-    let bytes = audio_resp.bytes().await.ok()?.to_vec();
+    let bytes = resp.bytes().await.ok()?;
+    if bytes.len() > MAX_MEDIA_SIZE {
+        warn!("WhatsApp Cloud: media {} exceeds size limit", media_id);
+        return None;
+    }
+    Some(bytes.to_vec())
+}
+
+/// Download media URL and transcribe audio; returns transcript or None on any failure.
+async fn fetch_and_transcribe(
+    svc: &crate::transcription::TranscriberService,
+    media_id: &str,
+    mime_type: &str,
+    token: &str,
+    client: &reqwest::Client,
+) -> Option<crate::transcription::Transcript> {
+    let bytes = download_media(media_id, token, client).await?;
 
     // Strip codec params from MIME (e.g. "audio/ogg; codecs=opus" -> "audio/ogg")
     let base_mime = mime_type.split(';').next().unwrap_or("audio/ogg").trim();
 
-    let transcript = svc.transcribe(bytes, base_mime).await;
-    if transcript == "[Voice Message]" {
-        None
-    } else {
-        Some(transcript)
-    }
+    svc.transcribe_detailed(bytes, base_mime).await
 }
 
 /// Extract audio messages from a webhook notification, optionally transcribing them.
 ///
 /// Returns one `InboundMessage` per audio message with content `[Voice: <transcript>]`
-/// or `[Voice Message]` when no transcriber is available or transcription fails.
+/// (with language and confidence when the backend reports them) or
+/// `[Voice Message]` when no transcriber is available or transcription fails.
 async fn extract_audio_messages(
     notification: &WebhookNotification,
     allowlist: &[String],
@@ -446,11 +515,11 @@ async fn extract_audio_messages(
                         match fetch_and_transcribe(svc, &audio.id, &audio.mime_type, token, client)
                             .await
                         {
-                            Some(t) => format!("[Voice: {}]", t),
-                            None => "[Voice Message]".to_string(),
+                            Some(t) => t.to_message(),
+                            None => crate::transcription::VOICE_FALLBACK.to_string(),
                         }
                     }
-                    _ => "[Voice Message]".to_string(),
+                    _ => crate::transcription::VOICE_FALLBACK.to_string(),
                 };
 
                 let mut inbound = InboundMessage::new("whatsapp_cloud", &from, &from, &content);
@@ -467,18 +536,182 @@ async fn extract_audio_messages(
     messages
 }
 
-/// Truncate a message to the WhatsApp character limit.
-fn truncate_message(content: &str) -> String {
-    if content.chars().count() <= MAX_MESSAGE_LENGTH {
-        content.to_string()
-    } else {
-        let suffix = "...(truncated)";
-        let cut_chars = MAX_MESSAGE_LENGTH.saturating_sub(suffix.len());
-        let prefix: String = content.chars().take(cut_chars).collect();
-        format!("{}{}", prefix, suffix)
+/// Placeholder content for a media message without a caption.
+fn media_placeholder(msg_type: &str, filename: Option<&str>) -> String {
+    match (msg_type, filename) {
+        ("document", Some(name)) => format!("[Document: {}]", name),
+        ("document", None) => "[Document]".to_string(),
+        ("video", _) => "[Video]".to_string(),
+        ("sticker", _) => "[Sticker]".to_string(),
+        _ => "[Image]".to_string(),
     }
 }
 
+/// Extract image, video, sticker and document messages, downloading each
+/// file into a [`MediaAttachment`].
+///
+/// The caption (or a placeholder such as `[Image]`) becomes the content; if
+/// the download fails the message is still delivered without the attachment.
+async fn extract_media_messages(
+    notification: &WebhookNotification,
+    allowlist: &[String],
+    deny_by_default: bool,
+    token: &str,
+    client: &reqwest::Client,
+) -> Vec<InboundMessage> {
+    let mut messages = Vec::new();
+
+    for entry in &notification.entry {
+        for change in &entry.changes {
+            let value = match &change.value {
+                Some(v) => v,
+                None => continue,
+            };
+
+            for msg in &value.messages {
+                let Some((media, media_type)) = msg.media() else {
+                    continue;
+                };
+                let from = msg.from.trim().to_string();
+                if from.is_empty() {
+                    continue;
+                }
+
+                // Allowlist check (same logic as extract_text_messages)
+                let allowed = if allowlist.is_empty() {
+                    !deny_by_default
+                } else {
+                    allowlist.contains(&from)
+                };
+                if !allowed {
+                    info!(
+                        "WhatsApp Cloud: user {} not in allowlist, ignoring {}",
+                        from, msg.msg_type
+                    );
+                    continue;
+                }
+
+                let content = media
+                    .caption
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| media_placeholder(&msg.msg_type, media.filename.as_deref()));
+
+                let mut inbound = InboundMessage::new("whatsapp_cloud", &from, &from, &content);
+                if let Some(data) = download_media(&media.id, token, client).await {
+                    let mut attachment = MediaAttachment::new(media_type).with_data(data);
+                    let mime = media.mime_type.split(';').next().unwrap_or("").trim();
+                    if !mime.is_empty() {
+                        attachment = attachment.with_mime_type(mime);
+                    }
+                    if let Some(ref name) = media.filename {
+                        attachment = attachment.with_filename(name);
+                    }
+                    inbound = inbound.with_media(attachment);
+                }
+                if !msg.id.is_empty() {
+                    inbound = inbound.with_metadata("whatsapp_message_id", &msg.id);
+                }
+                if !msg.timestamp.is_empty() {
+                    inbound = inbound.with_metadata("timestamp", &msg.timestamp);
+                }
+                messages.push(inbound);
+            }
+        }
+    }
+    messages
+}
+
+/// Payload marking an inbound message as read.
+fn read_receipt_payload(message_id: &str) -> Value {
+    json!({
+        "messaging_product": "whatsapp",
+        "status": "read",
+        "message_id": message_id,
+    })
+}
+
+/// Payload marking an inbound message as read and showing a typing
+/// indicator until the reply is sent (or 25 seconds pass).
+fn typing_indicator_payload(message_id: &str) -> Value {
+    let mut payload = read_receipt_payload(message_id);
+    payload["typing_indicator"] = json!({ "type": "text" });
+    payload
+}
+
+/// Payload for a parameterless template message.
+fn template_payload(to: &str, name: &str, language: &str) -> Value {
+    json!({
+        "messaging_product": "whatsapp",
+        "recipient_type": "individual",
+        "to": to,
+        "type": "template",
+        "template": {
+            "name": name,
+            "language": { "code": language }
+        }
+    })
+}
+
+/// An error returned by the Graph messages endpoint.
+#[derive(Debug)]
+struct GraphError {
+    status: reqwest::StatusCode,
+    code: Option<i64>,
+    message: String,
+}
+
+impl GraphError {
+    /// Whether the message was rejected because the 24-hour window is closed.
+    fn is_window_closed(&self) -> bool {
+        self.code == Some(REENGAGEMENT_ERROR_CODE)
+    }
+}
+
+/// POST a payload to the phone number's messages endpoint.
+async fn post_message(
+    client: &Client,
+    config: &WhatsAppCloudConfig,
+    payload: &Value,
+) -> Result<std::result::Result<(), GraphError>> {
+    let endpoint = format!("{}/{}/messages", WHATSAPP_API_BASE, config.phone_number_id);
+    let response = client
+        .post(&endpoint)
+        .header("Authorization", format!("Bearer {}", config.access_token))
+        .header("Content-Type", "application/json")
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| ZeptoError::Channel(format!("WhatsApp Cloud API request failed: {}", e)))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(Ok(()));
+    }
+    let body: Value = response.json().await.unwrap_or_default();
+    let error = body.get("error");
+    Ok(Err(GraphError {
+        status,
+        code: error.and_then(|e| e.get("code")).and_then(Value::as_i64),
+        message: error
+            .and_then(|e| e.get("message"))
+            .and_then(Value::as_str)
+            .unwrap_or("Unknown API error")
+            .to_string(),
+    }))
+}
+
+/// Render `content` as WhatsApp text and split it into messages under the
+/// character limit.
+fn outbound_chunks(content: &str) -> Vec<String> {
+    format_message(content, Dialect::WhatsApp, MAX_MESSAGE_LENGTH)
+        .into_iter()
+        .map(|chunk| chunk.text)
+        .collect()
+}
+
 // --- WhatsAppCloudChannel ---
 
 /// WhatsApp Cloud API channel.
@@ -524,17 +757,17 @@ impl WhatsAppCloudChannel {
     /// Handle webhook verification GET request.
     /// Meta sends: GET /whatsapp?hub.mode=subscribe&hub.verify_token=TOKEN&hub.challenge=CHALLENGE
     /// We must return the challenge value as plain text if the token matches.
+    /// An empty configured token never verifies.
     fn handle_verification(query: &str, verify_token: &str) -> Option<String> {
         let mode = query_param(query, "hub.mode")?;
         if mode != "subscribe" {
             return None;
         }
         let token = query_param(query, "hub.verify_token")?;
-        if token != verify_token {
+        if verify_token.is_empty() || !constant_time_eq(&token, verify_token) {
             return None;
         }
-        let challenge = query_param(query, "hub.challenge")?;
-        Some(challenge.to_string())
+        query_param(query, "hub.challenge").filter(|c| !c.is_empty())
     }
 
     fn validate_signature(
@@ -694,7 +927,21 @@ impl WhatsAppCloudChannel {
                 )
                 .await;
 
-                for inbound in text_messages.into_iter().chain(audio_messages) {
+                // Extract and publish image/video/sticker/document messages
+                let media_messages = extract_media_messages(
+                    &notification,
+                    &base_config.allowlist,
+                    base_config.deny_by_default,
+                    &config.access_token,
+                    client,
+                )
+                .await;
+
+                for inbound in text_messages
+                    .into_iter()
+                    .chain(audio_messages)
+                    .chain(media_messages)
+                {
                     info!(
                         "WhatsApp Cloud: received message from {} in chat {}",
                         inbound.sender_id, inbound.chat_id
@@ -710,6 +957,21 @@ impl WhatsAppCloudChannel {
             }
         }
     }
+
+    /// POST a read/typing status update.
+    async fn post_status(&self, payload: &Value) -> Result<()> {
+        if !self.running.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        post_message(&self.client, &self.config, payload)
+            .await?
+            .map_err(|e| {
+                ZeptoError::Channel(format!(
+                    "WhatsApp Cloud status update rejected ({}): {}",
+                    e.status, e.message
+                ))
+            })
+    }
 }
 
 #[async_trait]
@@ -843,51 +1105,49 @@ impl Channel for WhatsAppCloudChannel {
             ));
         }
 
-        let content = truncate_message(&msg.content);
-
-        let payload = json!({
-            "messaging_product": "whatsapp",
-            "recipient_type": "individual",
-            "to": to,
-            "type": "text",
-            "text": {
-                "preview_url": false,
-                "body": content
-            }
-        });
-
-        let endpoint = format!(
-            "{}/{}/messages",
-            WHATSAPP_API_BASE, self.config.phone_number_id
-        );
-        let response = self
-            .client
-            .post(&endpoint)
-            .header(
-                "Authorization",
-                format!("Bearer {}", self.config.access_token),
-            )
-            .header("Content-Type", "application/json")
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|e| {
-                ZeptoError::Channel(format!("WhatsApp Cloud API request failed: {}", e))
-            })?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let body: Value = response.json().await.unwrap_or_default();
-            let detail = body
-                .get("error")
-                .and_then(Value::as_object)
-                .and_then(|err| err.get("message"))
-                .and_then(Value::as_str)
-                .unwrap_or("Unknown API error");
-            warn!("WhatsApp Cloud API error {}: {}", status, detail);
+        for body in outbound_chunks(&msg.content) {
+            let payload = json!({
+                "messaging_product": "whatsapp",
+                "recipient_type": "individual",
+                "to": to,
+                "type": "text",
+                "text": {
+                    "preview_url": false,
+                    "body": body
+                }
+            });
+
+            let error = match post_message(&self.client, &self.config, &payload).await? {
+                Ok(()) => continue,
+                // Later chunks would hit the same closed window, so the
+                // template replaces the rest of the message.
+                Err(e) if e.is_window_closed() => match self.config.fallback_template.as_deref() {
+                    Some(template) => {
+                        info!(
+                            "WhatsApp Cloud: 24-hour window closed for {}, sending template '{}'",
+                            to, template
+                        );
+                        let payload = template_payload(
+                            &to,
+                            template,
+                            &self.config.fallback_template_language,
+                        );
+                        match post_message(&self.client, &self.config, &payload).await? {
+                            Ok(()) => break,
+                            Err(e) => e,
+                        }
+                    }
+                    None => e,
+                },
+                Err(e) => e,
+            };
+            warn!(
+                "WhatsApp Cloud API error {}: {}",
+                error.status, error.message
+            );
             return Err(ZeptoError::Channel(format!(
                 "WhatsApp Cloud API error {}: {}",
-                status, detail
+                error.status, error.message
             )));
         }
 
@@ -902,6 +1162,38 @@ impl Channel for WhatsAppCloudChannel {
     fn is_allowed(&self, user_id: &str) -> bool {
         self.base_config.is_allowed(user_id)
     }
+
+    fn capabilities(&self) -> Option<&dyn ChannelCapabilities> {
+        Some(self)
+    }
+}
+
+/// Read receipts and typing indicators both go through the Graph API's
+/// `status: read` call, so both are gated on `read_receipts`.
+#[async_trait]
+impl ChannelCapabilities for WhatsAppCloudChannel {
+    async fn send_typing(&self, event: &LifecycleEvent) -> Result<()> {
+        match event.metadata.get("whatsapp_message_id") {
+            Some(id) if self.config.read_receipts => {
+                self.post_status(&typing_indicator_payload(id)).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn mark_read(&self, event: &LifecycleEvent) -> Result<()> {
+        match event.metadata.get("whatsapp_message_id") {
+            Some(id) if self.config.read_receipts => {
+                self.post_status(&read_receipt_payload(id)).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The indicator lasts 25 seconds on WhatsApp's side.
+    fn typing_interval(&self) -> Duration {
+        Duration::from_secs(20)
+    }
 }
 
 // ===========================================================================
@@ -923,11 +1215,15 @@ mod tests {
             access_token: "test-token".to_string(),
             webhook_verify_token: "verify-secret".to_string(),
             app_secret: None,
+            app_id: None,
             bind_address: "127.0.0.1".to_string(),
             port: 0,
             path: "/whatsapp".to_string(),
             allow_from: vec!["60123456789".to_string()],
             deny_by_default: false,
+            read_receipts: false,
+            fallback_template: None,
+            fallback_template_language: "en_US".to_string(),
         }
     }
 
@@ -980,6 +1276,12 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_verification_rejects_empty_configured_token() {
+        let query = "hub.mode=subscribe&hub.verify_token=&hub.challenge=challenge123";
+        assert!(WhatsAppCloudChannel::handle_verification(query, "").is_none());
+    }
+
     #[test]
     fn test_verification_missing_challenge() {
         let query = "hub.mode=subscribe&hub.verify_token=verify-secret";
@@ -1163,27 +1465,29 @@ mod tests {
     }
 
     // -----------------------------------------------------------------------
-    // 6. Outbound truncation
+    // 6. Outbound chunking
     // -----------------------------------------------------------------------
 
     #[test]
-    fn test_truncate_message_short() {
-        let msg = "Hello!";
-        assert_eq!(truncate_message(msg), "Hello!");
+    fn test_outbound_chunks_short() {
+        assert_eq!(outbound_chunks("**Hello!**"), vec!["*Hello!*"]);
     }
 
     #[test]
-    fn test_truncate_message_at_limit() {
+    fn test_outbound_chunks_at_limit() {
         let msg = "a".repeat(MAX_MESSAGE_LENGTH);
-        assert_eq!(truncate_message(&msg).len(), MAX_MESSAGE_LENGTH);
+        assert_eq!(outbound_chunks(&msg), vec![msg]);
     }
 
     #[test]
-    fn test_truncate_message_over_limit() {
-        let msg = "a".repeat(MAX_MESSAGE_LENGTH + 100);
-        let result = truncate_message(&msg);
-        assert!(result.len() <= MAX_MESSAGE_LENGTH);
-        assert!(result.ends_with("...(truncated)"));
+    fn test_outbound_chunks_over_limit() {
+        let msg = "word ".repeat(MAX_MESSAGE_LENGTH / 4);
+        let chunks = outbound_chunks(&msg);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks
+            .iter()
+            .all(|c| c.chars().count() <= MAX_MESSAGE_LENGTH));
+        assert_eq!(chunks.join(" "), msg.trim());
     }
 
     // -----------------------------------------------------------------------
@@ -1194,7 +1498,15 @@ mod tests {
     fn test_query_param_found() {
         assert_eq!(
             query_param("hub.mode=subscribe&hub.challenge=abc", "hub.challenge"),
-            Some("abc")
+            Some("abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_query_param_percent_decoded() {
+        assert_eq!(
+            query_param("hub.verify_token=a%20b%2Bc", "hub.verify_token"),
+            Some("a b+c".to_string())
         );
     }
 
@@ -1548,4 +1860,88 @@ mod tests {
         let msgs = extract_audio_messages(&notification, &[], false, None, "token", &client).await;
         assert!(msgs.is_empty());
     }
+
+    // -----------------------------------------------------------------------
+    // 12. Media, read receipts and template fallback
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_webhook_message_media_parsed() {
+        let notification: WebhookNotification = serde_json::from_value(serde_json::json!({
+            "object": "whatsapp_business_account",
+            "entry": [{"changes": [{"value": {
+                "messages": [
+                    {
+                        "from": "60123", "id": "wamid.img", "timestamp": "1", "type": "image",
+                        "image": {"id": "media_img", "mime_type": "image/jpeg", "caption": "look"}
+                    },
+                    {
+                        "from": "60123", "id": "wamid.doc", "timestamp": "2", "type": "document",
+                        "document": {"id": "media_doc", "mime_type": "application/pdf", "filename": "a.pdf"}
+                    },
+                    {
+                        "from": "60123", "id": "wamid.txt", "timestamp": "3", "type": "text",
+                        "text": {"body": "hi"}
+                    }
+                ]
+            }}]}]
+        }))
+        .unwrap();
+        let messages = &notification.entry[0].changes[0]
+            .value
+            .as_ref()
+            .unwrap()
+            .messages;
+
+        let (image, kind) = messages[0].media().unwrap();
+        assert_eq!(kind, MediaType::Image);
+        assert_eq!(image.id, "media_img");
+        assert_eq!(image.caption.as_deref(), Some("look"));
+
+        let (doc, kind) = messages[1].media().unwrap();
+        assert_eq!(kind, MediaType::Document);
+        assert_eq!(doc.filename.as_deref(), Some("a.pdf"));
+
+        assert!(messages[2].media().is_none());
+    }
+
+    #[test]
+    fn test_media_placeholder() {
+        assert_eq!(media_placeholder("image", None), "[Image]");
+        assert_eq!(media_placeholder("video", None), "[Video]");
+        assert_eq!(media_placeholder("sticker", None), "[Sticker]");
+        assert_eq!(
+            media_placeholder("document", Some("a.pdf")),
+            "[Document: a.pdf]"
+        );
+    }
+
+    #[test]
+    fn test_read_receipt_payload() {
+        let payload = read_receipt_payload("wamid.1");
+        assert_eq!(payload["status"], "read");
+        assert_eq!(payload["message_id"], "wamid.1");
+        assert!(payload.get("typing_indicator").is_none());
+
+        let typing = typing_indicator_payload("wamid.1");
+        assert_eq!(typing["status"], "read");
+        assert_eq!(typing["typing_indicator"]["type"], "text");
+    }
+
+    #[test]
+    fn test_template_payload_and_window_detection() {
+        let payload = template_payload("60123", "follow_up", "en_US");
+        assert_eq!(payload["type"], "template");
+        assert_eq!(payload["template"]["name"], "follow_up");
+        assert_eq!(payload["template"]["language"]["code"], "en_US");
+
+        let error = |code| GraphError {
+            status: reqwest::StatusCode::BAD_REQUEST,
+            code,
+            message: String::new(),
+        };
+        assert!(error(Some(REENGAGEMENT_ERROR_CODE)).is_window_closed());
+        assert!(!error(Some(100)).is_window_closed());
+        assert!(!error(None).is_window_closed());
+    }
 }
@@ -21,63 +21,11 @@ use wa_rs::{Client, Jid};
 use wa_rs_tokio_transport::TokioWebSocketTransportFactory;
 use wa_rs_ureq_http::UreqHttpClient;
 
-use qrcode::QrCode;
-
 use crate::bus::{InboundMessage, MessageBus, OutboundMessage};
 use crate::channels::types::{BaseChannelConfig, Channel};
 use crate::config::WhatsAppWebConfig;
 use crate::error::{Result, ZeptoError};
-
-/// Render a QR code string as unicode block characters in the terminal.
-///
-/// Uses 2x1 half-block characters for compact display:
-/// - `█` (U+2588) = both pixels dark
-/// - `▀` (U+2580) = top dark, bottom light
-/// - `▄` (U+2584) = top light, bottom dark
-/// - ` ` (space)  = both pixels light
-fn render_qr_terminal(data: &str) -> Option<String> {
-    let code = QrCode::new(data.as_bytes()).ok()?;
-    let width = code.width();
-    let colors: Vec<bool> = code
-        .into_colors()
-        .into_iter()
-        .map(|c| c == qrcode::Color::Dark)
-        .collect();
-
-    // Add 1-module quiet zone on each side
-    let padded_width = width + 2;
-    let padded_height = width + 2;
-
-    let pixel = |row: usize, col: usize| -> bool {
-        if row == 0 || row > width || col == 0 || col > width {
-            false // quiet zone
-        } else {
-            colors[(row - 1) * width + (col - 1)]
-        }
-    };
-
-    let mut output = String::new();
-    let mut y = 0;
-    while y < padded_height {
-        for x in 0..padded_width {
-            let top = pixel(y, x);
-            let bottom = if y + 1 < padded_height {
-                pixel(y + 1, x)
-            } else {
-                false
-            };
-            output.push(match (top, bottom) {
-                (true, true) => '\u{2588}',
-                (true, false) => '\u{2580}',
-                (false, true) => '\u{2584}',
-                (false, false) => ' ',
-            });
-        }
-        output.push('\n');
-        y += 2;
-    }
-    Some(output)
-}
+use crate::utils::qr::render_qr_terminal;
 
 fn normalize_phone(phone: &str) -> String {
     phone
@@ -91,10 +39,10 @@ fn normalize_phone(phone: &str) -> String {
 }
 
 fn expand_auth_dir(path: &str) -> String {
-    if let Some(rest) = path.strip_prefix("~/") {
-        if let Some(home) = dirs::home_dir() {
-            return home.join(rest).to_string_lossy().to_string();
-        }
+    if path.starts_with("~/") {
+        return crate::config::expand_home(path)
+            .to_string_lossy()
+            .to_string();
     }
     path.to_string()
 }
@@ -7,6 +7,7 @@ use anyhow::{Context, Result};
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
 
+use zeptoclaw::agent::AgentLoop;
 use zeptoclaw::bus::{InboundMessage, MessageBus};
 use zeptoclaw::channels::model_switch::ModelOverride;
 use zeptoclaw::config::Config;
@@ -155,7 +156,7 @@ pub(crate) async fn cmd_agent(
     // Enable dry-run mode if requested
     if dry_run {
         agent.set_dry_run(true);
-        eprintln!("[DRY RUN] Tool execution disabled — showing what would happen");
+        eprintln!("[PLAN] Tool calls are proposed as a plan; reply \"approve plan\" to run it");
     }
 
     // Set up tool execution feedback (shows progress on stderr)
@@ -328,8 +329,9 @@ pub(crate) async fn cmd_agent(
                 Ok(mut editor) => {
                     editor.set_helper(Some(SlashHelper::new()));
                     // Persist history across sessions
-                    let history_path =
-                        dirs::home_dir().map(|h| h.join(".zeptoclaw/state/repl_history"));
+                    let history_path = Some(zeptoclaw::config::expand_home(
+                        "~/.zeptoclaw/state/repl_history",
+                    ));
                     if let Some(ref path) = history_path {
                         let _ = editor.load_history(path);
                     }
@@ -541,6 +543,19 @@ pub(crate) async fn cmd_agent(
                         }
                         continue;
                     }
+                    // Plan commands are handled by the agent loop.
+                    _ if cmd == "plan" || cmd.starts_with("plan ") => {}
+                    _ if cmd == "link" || cmd.starts_with("link ") || cmd == "unlink" => {
+                        match zeptoclaw::session::links::handle_link_command(
+                            agent.session_manager().links(),
+                            &cli_session_key(),
+                            input,
+                        ) {
+                            Some(reply) => println!("{}", reply),
+                            None => println!("Usage: /link, /link <code>, /link confirm, /unlink"),
+                        }
+                        continue;
+                    }
                     "trust" => {
                         if interactive_cli {
                             let status = if trusted_session { "ON" } else { "OFF" };
@@ -606,32 +621,35 @@ pub(crate) async fn cmd_agent(
 
             if streaming {
                 use zeptoclaw::providers::StreamEvent;
-                match agent.process_message_streaming(&inbound).await {
-                    Ok(mut rx) => {
-                        println!();
-                        while let Some(event) = rx.recv().await {
-                            match event {
-                                StreamEvent::Delta(text) => {
-                                    print!("{}", text);
-                                    let _ = io::stdout().flush();
-                                }
-                                StreamEvent::Done { .. } => break,
-                                StreamEvent::Error(e) => {
-                                    eprintln!("{}", format_cli_error(&e));
+                let turn = async {
+                    match agent.process_message_streaming(&inbound).await {
+                        Ok(mut rx) => {
+                            println!();
+                            while let Some(event) = rx.recv().await {
+                                match event {
+                                    StreamEvent::Delta(text) => {
+                                        print!("{}", text);
+                                        let _ = io::stdout().flush();
+                                    }
+                                    StreamEvent::Done { .. } => break,
+                                    StreamEvent::Error(e) => {
+                                        eprintln!("{}", format_cli_error(&e));
+                                    }
+                                    StreamEvent::ToolCalls(_) => {}
                                 }
-                                StreamEvent::ToolCalls(_) => {}
                             }
+                            println!();
+                            println!();
+                        }
+                        Err(e) => {
+                            eprintln!("{}", format_cli_error(&e));
+                            eprintln!();
                         }
-                        println!();
-                        println!();
-                    }
-                    Err(e) => {
-                        eprintln!("{}", format_cli_error(&e));
-                        eprintln!();
                     }
-                }
+                };
+                cancel_on_ctrl_c(&agent, turn).await;
             } else {
-                match agent.process_message(&inbound).await {
+                match cancel_on_ctrl_c(&agent, agent.process_message(&inbound)).await {
                     Ok(response) => {
                         println!();
                         println!("{}", response);
@@ -727,10 +745,25 @@ pub(crate) async fn cmd_agent_stdin() -> Result<()> {
 }
 
 /// Format agent errors with actionable guidance for CLI users.
+/// Drive one agent turn to completion; Ctrl+C cancels it instead of
+/// killing the CLI, so the session records where the turn stopped.
+async fn cancel_on_ctrl_c<T>(agent: &AgentLoop, turn: impl std::future::Future<Output = T>) -> T {
+    tokio::pin!(turn);
+    tokio::select! {
+        out = &mut turn => return out,
+        _ = tokio::signal::ctrl_c() => {
+            agent.cancel_turn(&cli_session_key());
+        }
+    }
+    turn.await
+}
+
 fn format_cli_error(e: &dyn std::fmt::Display) -> String {
     let msg = e.to_string();
 
-    if msg.contains("Authentication error") {
+    if msg == "Cancelled" {
+        "Stopped.".to_string()
+    } else if msg.contains("Authentication error") {
         format!(
             "{}\n\n  Fix: Check your API key. Run 'zeptoclaw auth status' to verify.\n  Or:  Set ZEPTOCLAW_PROVIDERS_ANTHROPIC_API_KEY=sk-ant-...",
             msg
@@ -0,0 +1,146 @@
+//! Backup command handlers.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use zeptoclaw::backup::{self, BackupOptions};
+use zeptoclaw::config::layout::DirLayout;
+
+use super::BackupAction;
+
+/// Handle backup subcommands.
+pub(crate) async fn cmd_backup(action: BackupAction) -> Result<()> {
+    match action {
+        BackupAction::Create {
+            output,
+            exclude_secrets,
+            include_workspace,
+        } => {
+            let options = BackupOptions {
+                include_secrets: !exclude_secrets,
+                include_workspace,
+            };
+            cmd_backup_create(output, &options)
+        }
+        BackupAction::Restore {
+            path,
+            force,
+            dry_run,
+        } => cmd_backup_restore(&path, force, dry_run),
+    }
+}
+
+fn cmd_backup_create(output: Option<PathBuf>, options: &BackupOptions) -> Result<()> {
+    let layout = DirLayout::resolve();
+    let output = output.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "zeptoclaw-backup-{}.tar.gz.enc",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ))
+    });
+    if output.exists() {
+        anyhow::bail!("{} already exists", output.display());
+    }
+
+    let (manifest, archive) =
+        backup::create_archive(&layout, options).map_err(|e| anyhow::anyhow!("{e}"))?;
+    if manifest.entries.is_empty() {
+        anyhow::bail!("Nothing to back up in {}", layout.data.display());
+    }
+    let passphrase = passphrase(true)?;
+    let sealed = backup::seal(&archive, &passphrase).map_err(|e| anyhow::anyhow!("{e}"))?;
+    write_private(&output, &sealed)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    println!("Backed up {} entries:", manifest.entries.len());
+    for entry in &manifest.entries {
+        println!("  {}", entry);
+    }
+    if !manifest.secrets_included {
+        println!("API keys and tokens were left out (--exclude-secrets).");
+    }
+    println!("Wrote {} ({} bytes)", output.display(), sealed.len());
+    Ok(())
+}
+
+fn cmd_backup_restore(path: &Path, force: bool, dry_run: bool) -> Result<()> {
+    let sealed =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let passphrase = passphrase(false)?;
+    let archive = backup::open(&sealed, &passphrase).map_err(|e| anyhow::anyhow!("{e}"))?;
+    let manifest = backup::read_manifest(&archive).map_err(|e| anyhow::anyhow!("{e}"))?;
+    let layout = DirLayout::resolve();
+
+    println!(
+        "Backup from zeptoclaw {} ({}), {} entries{}",
+        manifest.version,
+        manifest.created_at.format("%Y-%m-%d %H:%M UTC"),
+        manifest.entries.len(),
+        if manifest.secrets_included {
+            ""
+        } else {
+            ", without secrets"
+        }
+    );
+    let conflicts = backup::conflicts(&manifest, &layout);
+    if dry_run {
+        for entry in &manifest.entries {
+            println!("  {}", entry);
+        }
+        if !conflicts.is_empty() {
+            println!("Would replace:");
+            for path in &conflicts {
+                println!("  {}", path.display());
+            }
+        }
+        return Ok(());
+    }
+
+    let report =
+        backup::restore_archive(&archive, &layout, force).map_err(|e| anyhow::anyhow!("{e}"))?;
+    for path in &report.replaced {
+        println!("  replaced {}", path.display());
+    }
+    for step in &report.upgrades {
+        println!("  upgraded: {}", step);
+    }
+    println!("Restored {} entries.", report.restored.len());
+    if !manifest.secrets_included {
+        println!("Add API keys and tokens again (zeptoclaw onboard or config.json).");
+    }
+    Ok(())
+}
+
+/// `ZEPTOCLAW_BACKUP_PASSPHRASE`, or a prompt (confirmed when creating).
+fn passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var("ZEPTOCLAW_BACKUP_PASSPHRASE") {
+        if !passphrase.is_empty() {
+            return Ok(passphrase);
+        }
+    }
+    let passphrase = rpassword::prompt_password("Backup passphrase: ")
+        .map_err(|e| anyhow::anyhow!("failed to read passphrase: {e}"))?;
+    if passphrase.is_empty() {
+        anyhow::bail!("passphrase cannot be empty");
+    }
+    if confirm {
+        let again = rpassword::prompt_password("Confirm backup passphrase: ")
+            .map_err(|e| anyhow::anyhow!("failed to read passphrase: {e}"))?;
+        if passphrase != again {
+            anyhow::bail!("passphrases do not match");
+        }
+    }
+    Ok(passphrase)
+}
+
+fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    std::io::Write::write_all(&mut options.open(path)?, bytes)
+}
@@ -1,19 +1,44 @@
 //! Batch command handler.
 
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::path::PathBuf;
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
+use tokio::sync::mpsc;
 
-use zeptoclaw::batch::{format_results, load_prompts, BatchOutputFormat, BatchResult};
+use zeptoclaw::agent::AgentLoop;
+use zeptoclaw::batch::{
+    checkpoint_path, format_results, load_checkpoint, load_prompts, load_templated_prompts,
+    BatchOutputFormat, BatchResult, CheckpointWriter,
+};
 use zeptoclaw::bus::{InboundMessage, MessageBus};
 use zeptoclaw::config::Config;
 use zeptoclaw::providers::StreamEvent;
+use zeptoclaw::utils::cost::estimate_cost;
 
 use super::common::{create_agent, create_agent_with_template, resolve_template};
 use super::BatchFormat;
 
+/// Prompt source, concurrency, timeout and checkpoint controls for `zeptoclaw batch`.
+pub(crate) struct BatchRunOptions {
+    /// Prompt template rendered per row of the input dataset.
+    pub template_file: Option<PathBuf>,
+    /// Prompts in flight at once (defaults to `batch.concurrency`).
+    pub concurrency: Option<usize>,
+    /// Per-prompt timeout in seconds (defaults to `batch.prompt_timeout_secs`).
+    pub timeout_secs: Option<u64>,
+    /// Checkpoint file (defaults to `<input>.checkpoint.jsonl`).
+    pub checkpoint: Option<PathBuf>,
+    /// Ignore an existing checkpoint and start over.
+    pub fresh: bool,
+}
+
+/// Prompts waiting for a worker, in input order.
+type PromptQueue = Arc<Mutex<VecDeque<(usize, String)>>>;
+
 /// Process prompts from a file.
 pub(crate) async fn cmd_batch(
     input: PathBuf,
@@ -22,69 +47,106 @@ pub(crate) async fn cmd_batch(
     stop_on_error: bool,
     stream: bool,
     template: Option<String>,
+    run: BatchRunOptions,
 ) -> Result<()> {
-    let prompts = load_prompts(&input).with_context(|| {
-        format!(
-            "Failed to load prompts from batch input file {}",
-            input.display()
-        )
-    })?;
+    let prompts = match run.template_file.as_deref() {
+        Some(template_file) => {
+            load_templated_prompts(&input, template_file).with_context(|| {
+                format!(
+                    "Failed to render prompts from dataset {} with template {}",
+                    input.display(),
+                    template_file.display()
+                )
+            })?
+        }
+        None => load_prompts(&input).with_context(|| {
+            format!(
+                "Failed to load prompts from batch input file {}",
+                input.display()
+            )
+        })?,
+    };
 
     let config = Config::load().with_context(|| "Failed to load configuration")?;
     // Batch mode does not inherit the streaming default — only stream when
     // explicitly requested via --stream, to avoid session-save race conditions.
     let use_streaming = stream;
+    let timeout_secs = run.timeout_secs.unwrap_or(config.batch.prompt_timeout_secs);
+    let timeout = (timeout_secs > 0).then(|| Duration::from_secs(timeout_secs));
 
-    let bus = Arc::new(MessageBus::new());
-    let agent = if let Some(name) = template.as_deref() {
-        let tpl = resolve_template(name)?;
-        create_agent_with_template(config, bus, Some(tpl)).await?
+    let checkpoint = run.checkpoint.unwrap_or_else(|| checkpoint_path(&input));
+    let mut results = if run.fresh {
+        BTreeMap::new()
     } else {
-        create_agent(config, bus).await?
+        load_checkpoint(&checkpoint, &prompts)
+            .with_context(|| format!("Failed to read batch checkpoint {}", checkpoint.display()))?
     };
+    if !results.is_empty() {
+        eprintln!(
+            "Resuming batch: {} of {} prompt(s) already done (checkpoint {})",
+            results.len(),
+            prompts.len(),
+            checkpoint.display()
+        );
+    }
+    let mut writer = CheckpointWriter::open(&checkpoint, run.fresh)
+        .with_context(|| format!("Failed to open batch checkpoint {}", checkpoint.display()))?;
 
-    let mut results = Vec::with_capacity(prompts.len());
-    let mut failed = 0usize;
-
-    for (index, prompt) in prompts.into_iter().enumerate() {
-        let start = Instant::now();
-        let mut inbound = InboundMessage::new("cli", "batch", &format!("batch-{}", index), &prompt);
-        inbound.metadata.insert("is_batch".into(), "true".into());
+    let pending: VecDeque<(usize, String)> = prompts
+        .iter()
+        .cloned()
+        .enumerate()
+        .filter(|(index, _)| !results.contains_key(index))
+        .collect();
+    let workers = run
+        .concurrency
+        .unwrap_or(config.batch.concurrency)
+        .clamp(1, pending.len().max(1));
 
-        let response = if use_streaming {
-            process_streaming(&agent, &inbound).await
-        } else {
-            agent
-                .process_message(&inbound)
-                .await
-                .map_err(anyhow::Error::from)
+    // Each worker gets its own agent so per-run token counters (and therefore
+    // the usage and cost reported per prompt) are never shared between
+    // prompts that are in flight at the same time.
+    let tpl = template.as_deref().map(resolve_template).transpose()?;
+    let bus = Arc::new(MessageBus::new());
+    let queue: PromptQueue = Arc::new(Mutex::new(pending));
+    let stop = Arc::new(AtomicBool::new(false));
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut handles = Vec::with_capacity(workers);
+    for _ in 0..workers {
+        let agent = match tpl.clone() {
+            Some(tpl) => create_agent_with_template(config.clone(), bus.clone(), Some(tpl)).await?,
+            None => create_agent(config.clone(), bus.clone()).await?,
         };
+        handles.push(tokio::spawn(run_worker(
+            agent,
+            queue.clone(),
+            stop.clone(),
+            tx.clone(),
+            use_streaming,
+            timeout,
+        )));
+    }
+    drop(tx);
 
-        let duration_ms = start.elapsed().as_millis() as u64;
-        match response {
-            Ok(content) => results.push(BatchResult {
-                index,
-                prompt,
-                response: Some(content),
-                error: None,
-                duration_ms,
-            }),
-            Err(err) => {
-                failed += 1;
-                results.push(BatchResult {
-                    index,
-                    prompt,
-                    response: None,
-                    error: Some(err.to_string()),
-                    duration_ms,
-                });
-                if stop_on_error {
-                    break;
-                }
+    let mut failed = 0usize;
+    while let Some(result) = rx.recv().await {
+        writer.record(&result).with_context(|| {
+            format!("Failed to write batch checkpoint {}", checkpoint.display())
+        })?;
+        if !result.is_success() {
+            failed += 1;
+            if stop_on_error {
+                stop.store(true, Ordering::SeqCst);
             }
         }
+        results.insert(result.index, result);
+    }
+    for handle in handles {
+        handle.await.context("Batch worker panicked")?;
     }
 
+    // Results arrive in completion order; output is always in input order.
+    let results: Vec<BatchResult> = results.into_values().collect();
     let output_format = match format {
         BatchFormat::Text => BatchOutputFormat::Text,
         BatchFormat::Jsonl => BatchOutputFormat::Jsonl,
@@ -104,16 +166,112 @@ pub(crate) async fn cmd_batch(
     }
 
     if failed > 0 {
-        anyhow::bail!("{} prompt(s) failed during batch processing", failed);
+        let unfinished = prompts.len() - results.iter().filter(|r| r.is_success()).count();
+        anyhow::bail!(
+            "{} prompt(s) failed during batch processing; rerun to retry {} unfinished prompt(s) from checkpoint {}",
+            failed,
+            unfinished,
+            checkpoint.display()
+        );
     }
 
+    drop(writer);
+    let _ = std::fs::remove_file(&checkpoint);
     Ok(())
 }
 
-async fn process_streaming(
-    agent: &Arc<zeptoclaw::agent::AgentLoop>,
-    inbound: &InboundMessage,
-) -> Result<String> {
+/// Take prompts off `queue` until it is empty or `stop` is set, sending one
+/// result per prompt.
+async fn run_worker(
+    agent: Arc<AgentLoop>,
+    queue: PromptQueue,
+    stop: Arc<AtomicBool>,
+    tx: mpsc::UnboundedSender<BatchResult>,
+    use_streaming: bool,
+    timeout: Option<Duration>,
+) {
+    loop {
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+        let next = queue.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+        let Some((index, prompt)) = next else {
+            break;
+        };
+        let result = process_prompt(&agent, index, prompt, use_streaming, timeout).await;
+        if tx.send(result).is_err() {
+            break;
+        }
+    }
+}
+
+async fn process_prompt(
+    agent: &Arc<AgentLoop>,
+    index: usize,
+    prompt: String,
+    use_streaming: bool,
+    timeout: Option<Duration>,
+) -> BatchResult {
+    let start = Instant::now();
+    // Each worker has its own agent, so its token totals grow by this
+    // prompt's usage only.
+    let metrics = agent.metrics_collector();
+    let (input_before, output_before) = metrics.total_tokens();
+    let mut inbound = InboundMessage::new("cli", "batch", &format!("batch-{}", index), &prompt);
+    inbound.metadata.insert("is_batch".into(), "true".into());
+
+    let run = async {
+        if use_streaming {
+            process_streaming(agent, &inbound).await
+        } else {
+            agent
+                .process_message(&inbound)
+                .await
+                .map_err(anyhow::Error::from)
+        }
+    };
+    let response = match timeout {
+        Some(limit) => match tokio::time::timeout(limit, run).await {
+            Ok(response) => response,
+            Err(_) => Err(anyhow::anyhow!("timed out after {}s", limit.as_secs())),
+        },
+        None => run.await,
+    };
+
+    let duration_ms = start.elapsed().as_millis() as u64;
+    let (input_after, output_after) = metrics.total_tokens();
+    let (input_tokens, output_tokens) = (
+        input_after.saturating_sub(input_before),
+        output_after.saturating_sub(output_before),
+    );
+    let cost_usd = if input_tokens + output_tokens > 0 {
+        estimate_cost(
+            &agent.resolve_model_for_message(&inbound),
+            u32::try_from(input_tokens).unwrap_or(u32::MAX),
+            u32::try_from(output_tokens).unwrap_or(u32::MAX),
+            &HashMap::new(),
+        )
+    } else {
+        None
+    };
+
+    let (response, error) = match response {
+        Ok(content) => (Some(content), None),
+        Err(err) => (None, Some(err.to_string())),
+    };
+    BatchResult {
+        index,
+        prompt,
+        response,
+        error,
+        duration_ms,
+        input_tokens,
+        output_tokens,
+        cost_usd,
+    }
+}
+
+async fn process_streaming(agent: &Arc<AgentLoop>, inbound: &InboundMessage) -> Result<String> {
     let mut response = String::new();
     let mut rx = agent.process_message_streaming(inbound).await?;
     while let Some(event) = rx.recv().await {
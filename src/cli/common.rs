@@ -7,6 +7,7 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use tracing::{info, warn};
 
+use zeptoclaw::agent::prompts::PromptTemplates;
 use zeptoclaw::agent::{AgentLoop, ContextBuilder, RuntimeContext};
 use zeptoclaw::bus::MessageBus;
 use zeptoclaw::config::templates::{AgentTemplate, TemplateRegistry};
@@ -19,6 +20,7 @@ use zeptoclaw::session::SessionManager;
 use zeptoclaw::skills::SkillsLoader;
 use zeptoclaw::tools::approval::ApprovalPolicyConfig;
 use zeptoclaw::tools::delegate::DelegateTool;
+use zeptoclaw::tools::session_link::SessionLinkTool;
 use zeptoclaw::tools::spawn::SpawnTool;
 
 /// Read a line from stdin, trimming whitespace.
@@ -37,9 +39,12 @@ pub(crate) fn read_secret() -> Result<String> {
         .with_context(|| "Failed to read secret input")
 }
 
-/// Expand `~/` prefix to the user's home directory.
+/// Expand `~/` (or `~\` on Windows) prefix to the user's home directory.
 pub(crate) fn expand_tilde(path: &str) -> PathBuf {
-    if let Some(stripped) = path.strip_prefix("~/") {
+    let stripped = path
+        .strip_prefix("~/")
+        .or_else(|| path.strip_prefix("~\\").filter(|_| cfg!(windows)));
+    if let Some(stripped) = stripped {
         if let Some(home) = dirs::home_dir() {
             return home.join(stripped);
         }
@@ -51,6 +56,21 @@ pub(crate) fn expand_tilde(path: &str) -> PathBuf {
     PathBuf::from(path)
 }
 
+/// Editor for `--edit` flags: `$VISUAL`, then `$EDITOR`, then the platform default.
+pub(crate) fn default_editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "nano".to_string()
+            }
+        })
+}
+
 pub(crate) fn memory_backend_label(backend: &MemoryBackend) -> &'static str {
     match backend {
         MemoryBackend::Disabled => "none",
@@ -72,13 +92,7 @@ pub(crate) fn memory_citations_label(mode: &MemoryCitationsMode) -> &'static str
 }
 
 pub(crate) fn skills_loader_from_config(config: &Config) -> SkillsLoader {
-    let workspace_dir = config
-        .skills
-        .workspace_dir
-        .as_deref()
-        .map(expand_tilde)
-        .unwrap_or_else(|| Config::dir().join("skills"));
-    SkillsLoader::new(workspace_dir, None)
+    SkillsLoader::new(config.skills_dir(), None)
 }
 
 pub(crate) fn load_template_registry() -> Result<TemplateRegistry> {
@@ -288,6 +302,25 @@ pub(crate) async fn create_agent_with_template(
         }
     }
 
+    // Load prompt templates (~/.zeptoclaw/prompts). A system.md replaces the
+    // built-in prompt; explicit config/template/hand prompts still win below.
+    let mut prompt_templates = PromptTemplates::load(&PromptTemplates::default_dir());
+    if let Some(tpl) = &template {
+        prompt_templates = prompt_templates.with_agent(&tpl.name);
+    } else if let Some(hand) = active_hand.as_ref() {
+        prompt_templates = prompt_templates.with_agent(&hand.manifest.name);
+    }
+    if !prompt_templates.is_empty() {
+        info!(
+            "Loaded prompt templates from {}",
+            PromptTemplates::default_dir().display()
+        );
+    }
+    context_builder = context_builder.with_prompt_templates(prompt_templates);
+    if !config.channels.prompts.is_empty() {
+        context_builder = context_builder.with_channel_prompts(config.channels.prompts.clone());
+    }
+
     if let Some(sp) = &config.agents.defaults.system_prompt {
         context_builder = context_builder.with_system_prompt(sp);
     } else if let Some(tpl) = &template {
@@ -321,6 +354,9 @@ pub(crate) async fn create_agent_with_template(
         agent_loop.set_taint(Arc::clone(taint));
         info!("Wired shared taint engine into agent loop");
     }
+    if let Some(ref cron) = kernel.cron {
+        agent_loop.set_cron_service(Arc::clone(cron));
+    }
     let agent = Arc::new(agent_loop);
 
     // Transfer kernel tools + MCP clients into agent
@@ -342,6 +378,13 @@ pub(crate) async fn create_agent_with_template(
             )))
             .await;
     }
+    if filter.is_enabled("session_link") {
+        agent
+            .register_tool(Box::new(SessionLinkTool::new(
+                agent.session_manager().links().clone(),
+            )))
+            .await;
+    }
 
     // Register Google Workspace tool (deferred from kernel registrar because it
     // needs async OAuth token resolution).
@@ -360,6 +403,15 @@ pub(crate) async fn create_agent_with_template(
         }
     }
 
+    // Skills hot-reload: rebuild the skills prompt from disk on watcher
+    // notifications. An active hand pins its own skill, so skip reload there.
+    if config.skills.enabled && config.skills.hot_reload && active_hand.is_none() {
+        let reload_config = config.clone();
+        agent
+            .set_skills_reloader(move || build_skills_prompt(&reload_config))
+            .await;
+    }
+
     info!("Registered {} tools", agent.tool_count().await);
 
     // Set provider from kernel (already assembled: base → fallback → retry → quota)
@@ -629,7 +681,7 @@ pub(crate) fn friendly_api_error(provider: &str, status: u16, body: &str) -> Str
 #[cfg(feature = "google")]
 async fn resolve_google_token(config: &Config) -> Option<String> {
     // 1. Try stored OAuth token
-    let token_path = Config::dir().join("tokens").join("google.json");
+    let token_path = Config::data_dir().join("tokens").join("google.json");
     if let Ok(data) = tokio::fs::read_to_string(&token_path).await {
         if let Ok(token_set) = serde_json::from_str::<zeptoclaw::auth::OAuthTokenSet>(&data) {
             if !token_set.is_expired() {
@@ -1,7 +1,8 @@
-//! Config check and reset command handlers.
+//! Config check, schema, directory migration and reset command handlers.
 
 use anyhow::{Context, Result};
 
+use zeptoclaw::config::layout::{migration_plan, DirLayout};
 use zeptoclaw::config::Config;
 
 use super::ConfigAction;
@@ -10,6 +11,8 @@ use super::ConfigAction;
 pub(crate) async fn cmd_config(action: ConfigAction) -> Result<()> {
     match action {
         ConfigAction::Check => cmd_config_check().await,
+        ConfigAction::Schema => cmd_config_schema(),
+        ConfigAction::MigrateDirs { dry_run } => cmd_config_migrate_dirs(dry_run),
         ConfigAction::Reset { force } => cmd_config_reset(force),
     }
 }
@@ -18,36 +21,30 @@ pub(crate) async fn cmd_config(action: ConfigAction) -> Result<()> {
 async fn cmd_config_check() -> Result<()> {
     let config_path = Config::path();
     println!("Config file: {}", config_path.display());
+    let profile = Config::active_profile();
 
-    if !config_path.exists() {
+    let (mut errors, mut warnings) = if config_path.exists() {
+        check_config_file(&config_path)?
+    } else if profile.is_none() {
         println!("[OK] No config file found (using defaults)");
         return Ok(());
-    }
-
-    let content = std::fs::read_to_string(&config_path).context("Failed to read config file")?;
-
-    let raw: serde_json::Value = match serde_json::from_str(&content) {
-        Ok(v) => v,
-        Err(e) => {
-            println!("[ERROR] Invalid JSON: {}", e);
-            anyhow::bail!("Configuration file is not valid JSON");
-        }
+    } else {
+        println!("[OK] No config file found (profile layered over defaults)");
+        (0, 0)
     };
 
-    let diagnostics = zeptoclaw::config::validate::validate_config(&raw);
-    for diag in &diagnostics {
-        println!("{}", diag);
+    if let Some(name) = profile {
+        let profile_path = Config::profile_path(&name);
+        println!("\nProfile '{}': {}", name, profile_path.display());
+        if !profile_path.exists() {
+            println!("[ERROR] Profile file not found");
+            anyhow::bail!("Profile '{}' not found", name);
+        }
+        let (profile_errors, profile_warnings) = check_config_file(&profile_path)?;
+        errors += profile_errors;
+        warnings += profile_warnings;
     }
 
-    let mut errors = diagnostics
-        .iter()
-        .filter(|d| d.level == zeptoclaw::config::validate::DiagnosticLevel::Error)
-        .count();
-    let mut warnings = diagnostics
-        .iter()
-        .filter(|d| d.level == zeptoclaw::config::validate::DiagnosticLevel::Warn)
-        .count();
-
     // Validate custom tool definitions
     let config = Config::load().unwrap_or_default();
     let tool_warnings = zeptoclaw::config::validate::validate_custom_tools(&config);
@@ -92,6 +89,102 @@ async fn cmd_config_check() -> Result<()> {
     Ok(())
 }
 
+/// Print the schema and semantic diagnostics of one config file (base or
+/// profile). Returns (errors, warnings).
+fn check_config_file(path: &std::path::Path) -> Result<(usize, usize)> {
+    let content = std::fs::read_to_string(path).context("Failed to read config file")?;
+
+    let raw: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("[ERROR] Invalid JSON: {}", e);
+            anyhow::bail!("Configuration file is not valid JSON");
+        }
+    };
+
+    let mut diagnostics = zeptoclaw::config::validate::validate_config(&raw);
+    zeptoclaw::config::validate::locate_lines(&mut diagnostics, &content);
+    for diag in &diagnostics {
+        println!("{}", diag);
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.level == zeptoclaw::config::validate::DiagnosticLevel::Error)
+        .count();
+    let warnings = diagnostics
+        .iter()
+        .filter(|d| d.level == zeptoclaw::config::validate::DiagnosticLevel::Warn)
+        .count();
+    Ok((errors, warnings))
+}
+
+/// Print the config JSON Schema.
+fn cmd_config_schema() -> Result<()> {
+    let schema = zeptoclaw::config::schema::config_schema();
+    println!("{}", serde_json::to_string_pretty(schema)?);
+    Ok(())
+}
+
+/// Move the entries of a legacy `~/.zeptoclaw` into the layout selected by
+/// `ZEPTOCLAW_HOME` or the XDG variables.
+fn cmd_config_migrate_dirs(dry_run: bool) -> Result<()> {
+    let home = dirs::home_dir().context("Could not determine home directory")?;
+    let legacy = home.join(".zeptoclaw");
+    if !legacy.is_dir() {
+        println!("No {} to migrate.", legacy.display());
+        return Ok(());
+    }
+
+    let target = DirLayout::resolve_with(|key| std::env::var(key).ok(), &home, false);
+    if target == DirLayout::single(legacy.clone()) {
+        anyhow::bail!(
+            "Nothing to migrate to: set ZEPTOCLAW_HOME or XDG_CONFIG_HOME/XDG_DATA_HOME/XDG_STATE_HOME \
+             (and keep it set in your shell profile), then re-run"
+        );
+    }
+
+    let plan = migration_plan(&legacy, &target)
+        .with_context(|| format!("Failed to read {}", legacy.display()))?;
+    let conflicts: Vec<_> = plan.iter().filter(|(_, to)| to.exists()).collect();
+    if !conflicts.is_empty() {
+        for (_, to) in &conflicts {
+            println!("[ERROR] Already exists: {}", to.display());
+        }
+        anyhow::bail!("Refusing to overwrite {} existing path(s)", conflicts.len());
+    }
+
+    println!("Config dir: {}", target.config.display());
+    println!("Data dir:   {}", target.data.display());
+    println!("State dir:  {}", target.state.display());
+    println!();
+    for (from, to) in &plan {
+        println!("  {} -> {}", from.display(), to.display());
+        if dry_run {
+            continue;
+        }
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::rename(from, to).with_context(|| {
+            format!(
+                "Failed to move {} (across filesystems? move it by hand)",
+                from.display()
+            )
+        })?;
+    }
+
+    if dry_run {
+        println!("\nDry run: {} path(s) would be moved.", plan.len());
+    } else {
+        // Only removes the directory if everything was moved out of it.
+        let _ = std::fs::remove_dir(&legacy);
+        println!("\nMoved {} path(s).", plan.len());
+    }
+    Ok(())
+}
+
 /// Reset configuration to defaults, backing up the existing file.
 fn cmd_config_reset(force: bool) -> Result<()> {
     let config_path = Config::path();
@@ -1,25 +1,56 @@
 //! Daemon — supervised long-running agent with auto-restart.
+//!
+//! The gateway runs as a child process (`zeptoclaw gateway`). When it exits
+//! with an error the supervisor keeps the tail of its output, reports the
+//! crash (see [`DaemonConfig`]) and restarts it with exponential backoff.
 
+use std::collections::VecDeque;
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::Duration;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 
-use zeptoclaw::config::Config;
+use zeptoclaw::config::{Config, DaemonConfig};
 
 pub const INITIAL_BACKOFF_MS: u64 = 1_000;
 pub const MAX_BACKOFF_MS: u64 = 300_000; // 5 minutes
 
+/// How long a gateway gets to exit on its own after Ctrl-C before it is killed.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+/// Upper bound on the log excerpt included in a chat alert.
+const ALERT_LOG_MAX_CHARS: usize = 3_000;
+
 pub fn compute_backoff(current_ms: u64) -> u64 {
     current_ms.saturating_mul(2).min(MAX_BACKOFF_MS)
 }
 
+/// Delay before the next restart: the current backoff while the gateway
+/// keeps crashing within `stable_after`, back to the initial delay once it
+/// stayed up longer than that.
+pub fn restart_delay(current_ms: u64, uptime: Duration, stable_after: Duration) -> u64 {
+    if uptime >= stable_after {
+        INITIAL_BACKOFF_MS
+    } else {
+        current_ms
+    }
+}
+
 pub fn daemon_state_path() -> PathBuf {
-    Config::dir().join("daemon_state.json")
+    Config::state_dir().join("daemon_state.json")
+}
+
+/// Crash report waiting for the restarted gateway to deliver to `daemon.alert`.
+pub fn pending_alert_path() -> PathBuf {
+    Config::state_dir().join("daemon_crash_alert.json")
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +67,105 @@ pub struct ComponentState {
     pub running: bool,
     pub restart_count: u64,
     pub last_error: Option<String>,
+    /// Last lines of output before the most recent crash.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub log_tail: Vec<String>,
+}
+
+/// One gateway crash, as sent to the alert channel and webhook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub crashed_at: String,
+    /// Exit status as reported by the OS (e.g. "exit status: 101").
+    pub exit: String,
+    pub uptime_secs: u64,
+    pub restart_count: u64,
+    pub restart_in_ms: u64,
+    pub log_tail: Vec<String>,
+}
+
+impl CrashReport {
+    /// Chat-friendly summary with as much of the log tail as fits.
+    pub fn summary(&self) -> String {
+        let mut text = format!(
+            "ZeptoClaw gateway crashed ({}) after {}s; restart #{} in {}s.",
+            self.exit,
+            self.uptime_secs,
+            self.restart_count,
+            self.restart_in_ms / 1000
+        );
+        let mut excerpt: Vec<&str> = Vec::new();
+        let mut used = 0;
+        for line in self.log_tail.iter().rev() {
+            used += line.len() + 1;
+            if used > ALERT_LOG_MAX_CHARS {
+                break;
+            }
+            excerpt.push(line);
+        }
+        if !excerpt.is_empty() {
+            excerpt.reverse();
+            text.push_str("\n\nLast log lines:\n```\n");
+            text.push_str(&excerpt.join("\n"));
+            text.push_str("\n```");
+        }
+        text
+    }
+}
+
+/// Ring buffer of the most recent gateway output lines.
+#[derive(Debug, Clone)]
+pub struct LogTail {
+    lines: Arc<Mutex<VecDeque<String>>>,
+    capacity: usize,
+}
+
+impl LogTail {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    pub fn push(&self, line: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.len() == self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(strip_ansi(line));
+    }
+
+    pub fn snapshot(&self) -> Vec<String> {
+        let lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        lines.iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        self.lines.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+/// Remove ANSI colour sequences (`ESC [ ... letter`) from a log line.
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip the CSI sequence up to and including its final letter.
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
 }
 
 /// Write daemon state to disk.
@@ -63,6 +193,86 @@ pub fn remove_state() {
     let _ = std::fs::remove_file(daemon_state_path());
 }
 
+/// Store a crash report for the restarted gateway to deliver.
+fn write_pending_alert(report: &CrashReport) -> Result<()> {
+    let path = pending_alert_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(report)?)?;
+    Ok(())
+}
+
+/// Take the pending crash report, if any (called by the gateway once its
+/// channels are up).
+pub fn take_pending_alert() -> Option<CrashReport> {
+    let path = pending_alert_path();
+    let content = std::fs::read_to_string(&path).ok()?;
+    let _ = std::fs::remove_file(&path);
+    serde_json::from_str(&content).ok()
+}
+
+/// Report a crash: POST to `alert_webhook` now, queue the chat alert for the
+/// restarted gateway (channels are down until then).
+async fn send_crash_alerts(config: &DaemonConfig, report: &CrashReport) {
+    if let Some(url) = config.alert_webhook.as_deref() {
+        let payload = serde_json::json!({
+            "event": "gateway_crash",
+            "report": report,
+            "text": report.summary(),
+        });
+        let result = reqwest::Client::new()
+            .post(url)
+            .timeout(Duration::from_secs(10))
+            .json(&payload)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status());
+        if let Err(e) = result {
+            warn!("Crash alert webhook failed: {}", e);
+        }
+    }
+    if config.alert.is_some() {
+        if let Err(e) = write_pending_alert(report) {
+            warn!("Failed to queue crash alert: {}", e);
+        }
+    }
+}
+
+/// Spawn `zeptoclaw gateway` with piped output.
+fn spawn_gateway() -> Result<Child> {
+    let exe = std::env::current_exe().with_context(|| "Failed to locate zeptoclaw executable")?;
+    let mut cmd = Command::new(exe);
+    cmd.arg("gateway")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    if let Some(profile) = Config::active_profile() {
+        cmd.env("ZEPTOCLAW_PROFILE", profile);
+    }
+    cmd.spawn()
+        .with_context(|| "Failed to start gateway process")
+}
+
+/// Forward child output to our own stdout/stderr while recording it in `tail`.
+fn pump<R>(reader: R, tail: LogTail, stderr: bool) -> JoinHandle<()>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if stderr {
+                eprintln!("{}", line);
+            } else {
+                println!("{}", line);
+            }
+            tail.push(&line);
+        }
+    })
+}
+
 /// CLI entry point for `zeptoclaw daemon`.
 pub(crate) async fn cmd_daemon() -> Result<()> {
     println!("Starting ZeptoClaw Daemon...");
@@ -71,20 +281,22 @@ pub(crate) async fn cmd_daemon() -> Result<()> {
 
     let started_at = chrono::Utc::now().to_rfc3339();
     let gateway_addr = format!("{}:{}", config.gateway.host, config.gateway.port);
+    let stable_after = Duration::from_secs(config.daemon.stable_after_secs);
 
-    let shutdown = Arc::new(AtomicBool::new(false));
-    let shutdown_clone = Arc::clone(&shutdown);
+    let shutdown = CancellationToken::new();
+    let shutdown_clone = shutdown.clone();
 
     tokio::spawn(async move {
         tokio::signal::ctrl_c().await.ok();
         info!("Received shutdown signal");
-        shutdown_clone.store(true, Ordering::SeqCst);
+        shutdown_clone.cancel();
     });
 
-    let restart_count = Arc::new(AtomicU64::new(0));
+    let tail = LogTail::new(config.daemon.log_tail_lines);
+    let mut restart_count: u64 = 0;
     let mut backoff_ms = INITIAL_BACKOFF_MS;
 
-    while !shutdown.load(Ordering::SeqCst) {
+    while !shutdown.is_cancelled() {
         let state = DaemonState {
             status: "running".into(),
             started_at: started_at.clone(),
@@ -92,44 +304,92 @@ pub(crate) async fn cmd_daemon() -> Result<()> {
             components: vec![ComponentState {
                 name: "gateway".into(),
                 running: true,
-                restart_count: restart_count.load(Ordering::Relaxed),
+                restart_count,
                 last_error: None,
+                log_tail: Vec::new(),
             }],
         };
         let _ = write_state(&state);
 
         info!("Starting gateway component");
-        match super::gateway::cmd_gateway(None, None).await {
-            Ok(()) => {
-                info!("Gateway exited cleanly");
+        tail.clear();
+        let started = Instant::now();
+        let mut child = spawn_gateway()?;
+        let mut pumps = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            pumps.push(pump(stdout, tail.clone(), false));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            pumps.push(pump(stderr, tail.clone(), true));
+        }
+
+        let status = tokio::select! {
+            status = child.wait() => status,
+            _ = shutdown.cancelled() => {
+                // Ctrl-C reaches the gateway too; give it time to stop cleanly.
+                if tokio::time::timeout(SHUTDOWN_GRACE, child.wait()).await.is_err() {
+                    warn!("Gateway did not stop within {:?}, killing it", SHUTDOWN_GRACE);
+                    let _ = child.kill().await;
+                }
                 break;
             }
-            Err(e) => {
-                let count = restart_count.fetch_add(1, Ordering::Relaxed) + 1;
-                error!("Gateway failed (attempt {}): {}", count, e);
-
-                let err_state = DaemonState {
-                    status: "restarting".into(),
-                    started_at: started_at.clone(),
-                    gateway: gateway_addr.clone(),
-                    components: vec![ComponentState {
-                        name: "gateway".into(),
-                        running: false,
-                        restart_count: count,
-                        last_error: Some(e.to_string()),
-                    }],
-                };
-                let _ = write_state(&err_state);
-
-                if shutdown.load(Ordering::SeqCst) {
-                    break;
-                }
+        };
+        for handle in pumps {
+            let _ = handle.await;
+        }
 
-                warn!("Restarting in {}ms (attempt {})", backoff_ms, count);
-                tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
-                backoff_ms = compute_backoff(backoff_ms);
+        let exit = match status {
+            Ok(status) if status.success() => {
+                info!("Gateway exited cleanly");
+                break;
             }
+            Ok(status) => status.to_string(),
+            Err(e) => format!("wait failed: {}", e),
+        };
+
+        restart_count += 1;
+        let uptime = started.elapsed();
+        let delay_ms = restart_delay(backoff_ms, uptime, stable_after);
+        let report = CrashReport {
+            crashed_at: chrono::Utc::now().to_rfc3339(),
+            exit: exit.clone(),
+            uptime_secs: uptime.as_secs(),
+            restart_count,
+            restart_in_ms: delay_ms,
+            log_tail: tail.snapshot(),
+        };
+        error!(
+            "Gateway crashed ({}) after {}s (attempt {})",
+            exit,
+            uptime.as_secs(),
+            restart_count
+        );
+
+        let err_state = DaemonState {
+            status: "restarting".into(),
+            started_at: started_at.clone(),
+            gateway: gateway_addr.clone(),
+            components: vec![ComponentState {
+                name: "gateway".into(),
+                running: false,
+                restart_count,
+                last_error: Some(exit),
+                log_tail: report.log_tail.clone(),
+            }],
+        };
+        let _ = write_state(&err_state);
+        send_crash_alerts(&config.daemon, &report).await;
+
+        if shutdown.is_cancelled() {
+            break;
+        }
+
+        warn!("Restarting in {}ms (attempt {})", delay_ms, restart_count);
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(delay_ms)) => {}
+            _ = shutdown.cancelled() => break,
         }
+        backoff_ms = compute_backoff(delay_ms);
     }
 
     info!("Daemon shutting down");
@@ -140,8 +400,9 @@ pub(crate) async fn cmd_daemon() -> Result<()> {
         components: vec![ComponentState {
             name: "gateway".into(),
             running: false,
-            restart_count: restart_count.load(Ordering::Relaxed),
+            restart_count,
             last_error: None,
+            log_tail: Vec::new(),
         }],
     };
     let _ = write_state(&final_state);
@@ -171,6 +432,82 @@ mod tests {
         assert_eq!(compute_backoff(INITIAL_BACKOFF_MS), INITIAL_BACKOFF_MS * 2);
     }
 
+    #[test]
+    fn test_restart_delay_keeps_backoff_in_crash_loop() {
+        let stable = Duration::from_secs(300);
+        assert_eq!(restart_delay(8_000, Duration::from_secs(5), stable), 8_000);
+    }
+
+    #[test]
+    fn test_restart_delay_resets_after_stable_run() {
+        let stable = Duration::from_secs(300);
+        assert_eq!(
+            restart_delay(64_000, Duration::from_secs(301), stable),
+            INITIAL_BACKOFF_MS
+        );
+    }
+
+    #[test]
+    fn test_log_tail_keeps_last_lines() {
+        let tail = LogTail::new(2);
+        tail.push("one");
+        tail.push("two");
+        tail.push("three");
+        assert_eq!(tail.snapshot(), vec!["two", "three"]);
+        tail.clear();
+        assert!(tail.snapshot().is_empty());
+    }
+
+    #[test]
+    fn test_log_tail_zero_capacity() {
+        let tail = LogTail::new(0);
+        tail.push("ignored");
+        assert!(tail.snapshot().is_empty());
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi("\x1b[2m2026-02-22\x1b[0m \x1b[31mERROR\x1b[0m boom"),
+            "2026-02-22 ERROR boom"
+        );
+        assert_eq!(strip_ansi("plain"), "plain");
+    }
+
+    #[test]
+    fn test_crash_report_summary() {
+        let report = CrashReport {
+            crashed_at: "2026-02-22T10:00:00Z".into(),
+            exit: "exit status: 101".into(),
+            uptime_secs: 12,
+            restart_count: 3,
+            restart_in_ms: 4_000,
+            log_tail: vec!["panicked at src/main.rs".into()],
+        };
+        let text = report.summary();
+        assert!(text.contains("exit status: 101"));
+        assert!(text.contains("restart #3 in 4s"));
+        assert!(text.contains("panicked at src/main.rs"));
+    }
+
+    #[test]
+    fn test_crash_report_summary_truncates_log() {
+        let report = CrashReport {
+            crashed_at: String::new(),
+            exit: "exit status: 1".into(),
+            uptime_secs: 0,
+            restart_count: 1,
+            restart_in_ms: 1_000,
+            log_tail: (0..500)
+                .map(|i| format!("line {:04} {}", i, "x".repeat(40)))
+                .collect(),
+        };
+        let text = report.summary();
+        assert!(text.len() < ALERT_LOG_MAX_CHARS + 200);
+        assert!(text.contains("line 0499"));
+        assert!(!text.contains("line 0000"));
+    }
+
     #[test]
     fn test_daemon_state_serialize() {
         let state = DaemonState {
@@ -182,11 +519,13 @@ mod tests {
                 running: true,
                 restart_count: 0,
                 last_error: None,
+                log_tail: Vec::new(),
             }],
         };
         let json = serde_json::to_string_pretty(&state).unwrap();
         assert!(json.contains("telegram"));
         assert!(json.contains("running"));
+        assert!(!json.contains("log_tail"));
     }
 
     #[test]
@@ -195,6 +534,7 @@ mod tests {
         let state: DaemonState = serde_json::from_str(json).unwrap();
         assert_eq!(state.components.len(), 1);
         assert_eq!(state.components[0].restart_count, 2);
+        assert!(state.components[0].log_tail.is_empty());
     }
 
     #[test]
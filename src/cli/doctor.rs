@@ -222,7 +222,7 @@ pub fn check_channels(config: &Config, diags: &mut Vec<DiagItem>) {
 }
 
 pub fn check_memory(diags: &mut Vec<DiagItem>) {
-    let ltm_path = Config::dir().join("memory").join("longterm.json");
+    let ltm_path = Config::data_dir().join("memory").join("longterm.json");
     if ltm_path.exists() {
         match std::fs::read_to_string(&ltm_path) {
             Ok(_) => {
@@ -0,0 +1,72 @@
+//! Eval command handler.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+
+use zeptoclaw::bus::MessageBus;
+use zeptoclaw::config::Config;
+use zeptoclaw::eval::{load_scenarios, run_scenario, EvalReport};
+
+use super::common::{create_agent, create_agent_with_template, resolve_template};
+
+/// Run a scenario suite and print the scored report.
+pub(crate) async fn cmd_eval(
+    path: PathBuf,
+    mock: bool,
+    filter: Option<String>,
+    template: Option<String>,
+    json: bool,
+    min_score: Option<f64>,
+) -> Result<()> {
+    let mut scenarios = load_scenarios(&path)
+        .with_context(|| format!("Failed to load scenarios from {}", path.display()))?;
+    if let Some(filter) = filter.as_deref() {
+        scenarios.retain(|s| s.name.contains(filter));
+    }
+    if scenarios.is_empty() {
+        anyhow::bail!("No scenarios found in {}", path.display());
+    }
+
+    let mut config = Config::load().with_context(|| "Failed to load configuration")?;
+    // Keep eval turns out of the ledger and usage reports of real sessions.
+    config.session.ledger = false;
+    let template = template.as_deref().map(resolve_template).transpose()?;
+
+    let mut report = EvalReport::default();
+    for scenario in &scenarios {
+        if !json {
+            eprintln!("Running {}...", scenario.name);
+        }
+        // A fresh agent per scenario: mock providers and fake tools stay on it.
+        let bus = Arc::new(MessageBus::new());
+        let agent = match &template {
+            Some(template) => {
+                create_agent_with_template(config.clone(), bus, Some(template.clone())).await?
+            }
+            None => create_agent(config.clone(), bus).await?,
+        };
+        if !mock && agent.provider().await.is_none() {
+            anyhow::bail!("No provider configured; configure one or run with --mock");
+        }
+        report
+            .results
+            .push(run_scenario(&agent, scenario, mock).await);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", report.render());
+    }
+
+    let passed = match min_score {
+        Some(min) => report.score() * 100.0 >= min,
+        None => report.passed() == report.results.len(),
+    };
+    if !passed {
+        anyhow::bail!("Evaluation failed");
+    }
+    Ok(())
+}
@@ -7,20 +7,27 @@ use anyhow::{Context, Result};
 use tokio::sync::{mpsc, watch};
 use tracing::{error, info, warn};
 
-use zeptoclaw::bus::MessageBus;
-use zeptoclaw::channels::{register_configured_channels, ChannelManager};
+use zeptoclaw::bus::notify::spawn_digest_flusher;
+use zeptoclaw::bus::{MessageBus, NotificationPolicy, OutboundMessage};
+use zeptoclaw::channels::{
+    register_configured_channels_with_webhook, ChannelManager, DeviceChannel, DeviceHub,
+    TelegramWebhook,
+};
 use zeptoclaw::config::watcher::ConfigWatcher;
 use zeptoclaw::config::{Config, ContainerAgentBackend};
+use zeptoclaw::gateway::{start_api_server, GatewayApi};
 use zeptoclaw::health::{
     health_port, start_health_server, start_health_server_legacy, start_periodic_usage_flush,
-    HealthRegistry, UsageMetrics,
+    tool_usage_dir, HealthRegistry, UsageMetrics,
 };
-use zeptoclaw::heartbeat::{ensure_heartbeat_file, HeartbeatService};
+use zeptoclaw::heartbeat::{ensure_heartbeat_file, HeartbeatService, QuietHours};
 use zeptoclaw::providers::{
     configured_provider_names, resolve_runtime_provider, RUNTIME_SUPPORTED_PROVIDERS,
 };
+use zeptoclaw::session::SessionManager;
+use zeptoclaw::skills::watcher::SkillsWatcher;
 
-use super::common::create_agent;
+use super::common::{create_agent, skills_loader_from_config};
 use super::heartbeat::heartbeat_file_path;
 
 /// Start multi-channel gateway.
@@ -82,30 +89,46 @@ pub(crate) async fn cmd_gateway(
         }
     }
 
-    // Start tunnel if requested
-    let mut _tunnel: Option<Box<dyn zeptoclaw::tunnel::TunnelProvider>> = None;
+    // Start tunnel if requested; the supervisor keeps it up until shutdown.
+    let mut tunnel: Option<zeptoclaw::tunnel::TunnelSupervisor> = None;
     let tunnel_provider = tunnel_flag.or(config.tunnel.provider.clone());
     if let Some(ref provider) = tunnel_provider {
         let mut tunnel_config = config.tunnel.clone();
         tunnel_config.provider = Some(provider.clone());
-        let mut t = zeptoclaw::tunnel::create_tunnel(&tunnel_config)
+        let t = zeptoclaw::tunnel::create_tunnel(&tunnel_config)
             .with_context(|| format!("Failed to create {} tunnel", provider))?;
 
-        let gateway_port = config.gateway.port;
-        let tunnel_url = t
-            .start(gateway_port)
+        let local_port = tunnel_config.port.unwrap_or(config.gateway.port);
+        let registrars = if tunnel_config.register_webhooks {
+            zeptoclaw::tunnel::configured_registrars(&config, local_port)
+        } else {
+            Vec::new()
+        };
+        let options = zeptoclaw::tunnel::TunnelOptions {
+            local_port,
+            check_interval: Duration::from_secs(tunnel_config.health_check_interval_secs),
+            max_failures: tunnel_config.max_failures,
+        };
+        let supervisor = zeptoclaw::tunnel::TunnelSupervisor::start(t, options, registrars)
             .await
             .with_context(|| format!("Failed to start {} tunnel", provider))?;
 
-        println!("Tunnel active: {}", tunnel_url);
-        _tunnel = Some(t);
+        println!("Tunnel active: {}", supervisor.url());
+        tunnel = Some(supervisor);
     }
 
     // Create message bus
-    let bus = Arc::new(MessageBus::new());
+    let bus = Arc::new(
+        MessageBus::from_config(&config.bus)
+            .with_notification_policy(NotificationPolicy::from_config(&config.notifications)),
+    );
+    let _digest_handle = bus
+        .has_notification_policy()
+        .then(|| spawn_digest_flusher(Arc::clone(&bus)));
 
     // Create usage metrics tracker
     let metrics = Arc::new(UsageMetrics::new());
+    metrics.set_tool_usage_dir(tool_usage_dir());
 
     // Start legacy health check server (liveness + readiness via UsageMetrics)
     let hp = health_port();
@@ -126,6 +149,7 @@ pub(crate) async fn cmd_gateway(
     // Create HealthRegistry (shared between health server and channel supervisor)
     let health_registry = HealthRegistry::new();
     health_registry.set_metrics(Arc::clone(&metrics));
+    health_registry.set_message_bus((*bus).clone());
 
     // Start HealthRegistry-based server if config.health.enabled
     if config.health.enabled {
@@ -153,6 +177,64 @@ pub(crate) async fn cmd_gateway(
         );
     }
 
+    // Gateway API: devices exchange a code from `zeptoclaw pair new` for a
+    // token at /pair, then drive the agent through /api/v1. The same server
+    // receives Telegram updates in webhook mode.
+    let device_hub = DeviceHub::new();
+    let devices_enabled = config.pairing.enabled;
+    let telegram_webhook = config
+        .channels
+        .telegram
+        .as_ref()
+        .filter(|c| c.enabled)
+        .and_then(TelegramWebhook::from_config);
+    let gateway_api = if devices_enabled || telegram_webhook.is_some() {
+        let pairing = Arc::new(std::sync::Mutex::new(zeptoclaw::PairingManager::new(
+            config.pairing.max_attempts,
+            config.pairing.lockout_secs,
+        )));
+        let sessions = SessionManager::new().unwrap_or_else(|_| {
+            warn!("Failed to open session store for gateway API, using in-memory");
+            SessionManager::new_memory()
+        });
+        let mut api = GatewayApi::new(
+            pairing,
+            bus.clone(),
+            device_hub.clone(),
+            health_registry.clone(),
+            sessions,
+        );
+        let rl = &config.gateway.rate_limit;
+        if rl.pair_per_min > 0 {
+            api = api.with_rate_limiter(Arc::new(zeptoclaw::gateway::GatewayRateLimiter::new(
+                rl.pair_per_min,
+                rl.webhook_per_min,
+                Duration::from_secs(60),
+            )));
+        }
+        if !devices_enabled {
+            api = api.without_pairing();
+        }
+        if let Some(hook) = &telegram_webhook {
+            api = api.with_telegram_webhook(hook.clone());
+        }
+        Some(Arc::new(api))
+    } else {
+        None
+    };
+    let api_handle = if let Some(api) = &gateway_api {
+        let (host, port) = (&config.gateway.host, config.gateway.port);
+        match start_api_server(host, port, Arc::clone(api)).await {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                warn!(error = %e, "Failed to start gateway API (non-fatal)");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     // Create shutdown watch channel for periodic usage flush
     let (usage_shutdown_tx, usage_shutdown_rx) = tokio::sync::watch::channel(false);
     let usage_flush_handle = start_periodic_usage_flush(Arc::clone(&metrics), usage_shutdown_rx);
@@ -293,6 +375,9 @@ pub(crate) async fn cmd_gateway(
     let mut agent = if !containerized {
         let agent = create_agent(config.clone(), bus.clone()).await?;
         agent.set_usage_metrics(Arc::clone(&metrics)).await;
+        if let Some(api) = &gateway_api {
+            api.attach_agent(&agent).await;
+        }
         Some(agent)
     } else {
         None
@@ -303,7 +388,13 @@ pub(crate) async fn cmd_gateway(
     channel_manager.set_health_registry(health_registry.clone());
 
     // Register channels via factory.
-    let channel_count = register_configured_channels(&channel_manager, bus.clone(), &config).await;
+    let channel_count = register_configured_channels_with_webhook(
+        &channel_manager,
+        bus.clone(),
+        &config,
+        telegram_webhook.as_ref(),
+    )
+    .await;
     if channel_count == 0 {
         warn!(
             "No channels configured. Enable channels in {:?}",
@@ -313,6 +404,11 @@ pub(crate) async fn cmd_gateway(
     } else {
         info!("Registered {} channel(s)", channel_count);
     }
+    if gateway_api.is_some() && devices_enabled {
+        channel_manager
+            .register(Box::new(DeviceChannel::new(device_hub.clone())))
+            .await;
+    }
 
     // Start all channels
     channel_manager
@@ -320,6 +416,18 @@ pub(crate) async fn cmd_gateway(
         .await
         .with_context(|| "Failed to start channels")?;
 
+    // Deliver the crash report queued by `zeptoclaw daemon` before this restart
+    if let Some((alert_channel, alert_chat_id)) =
+        config.daemon.alert.as_deref().and_then(parse_deliver_to)
+    {
+        if let Some(report) = super::daemon::take_pending_alert() {
+            let msg = OutboundMessage::new(&alert_channel, &alert_chat_id, &report.summary());
+            if let Err(e) = channel_manager.send(&alert_channel, msg).await {
+                warn!("Failed to deliver daemon crash alert: {}", e);
+            }
+        }
+    }
+
     let heartbeat_service = if config.heartbeat.enabled {
         let hb_path = heartbeat_file_path(&config);
         match ensure_heartbeat_file(&hb_path).await {
@@ -345,13 +453,17 @@ pub(crate) async fn cmd_gateway(
             })
             .unwrap_or_else(|| ("heartbeat".to_string(), "system".to_string()));
 
-        let service = Arc::new(HeartbeatService::new(
-            hb_path,
-            config.heartbeat.interval_secs,
-            bus.clone(),
-            &hb_channel,
-            &hb_chat_id,
-        ));
+        let service = Arc::new(
+            HeartbeatService::new(
+                hb_path,
+                config.heartbeat.interval_secs,
+                bus.clone(),
+                &hb_channel,
+                &hb_chat_id,
+            )
+            .with_quiet_hours(QuietHours::from_config(&config.heartbeat.quiet_hours))
+            .with_adaptive(config.heartbeat.adaptive.clone()),
+        );
         service.start().await?;
         Some(service)
     } else {
@@ -373,6 +485,28 @@ pub(crate) async fn cmd_gateway(
         }
     };
 
+    // Start notes sync (Obsidian / Notion) if configured
+    let _notes_sync_handle = config.memory.sync.enabled.then(|| {
+        zeptoclaw::memory::notes_sync::start_notes_sync_scheduler(
+            config.workspace_path(),
+            config.memory.sync.clone(),
+        )
+    });
+
+    // Start usage report routine if configured
+    let _usage_report_handle = config
+        .routines
+        .usage_report
+        .enabled
+        .then(|| {
+            zeptoclaw::routines::usage_report::start_usage_report_scheduler(
+                Arc::clone(&bus),
+                tool_usage_dir(),
+                config.routines.usage_report.clone(),
+            )
+        })
+        .flatten();
+
     // Start device service if configured
     // TODO: publish to MessageBus for channel delivery once InboundMessage wrapping is settled
     let _device_handle =
@@ -436,10 +570,36 @@ pub(crate) async fn cmd_gateway(
     // Config watcher (30s polling) for hot-reload.
     let (reload_tx, mut reload_rx) = mpsc::unbounded_channel::<Config>();
     let (reload_shutdown_tx, reload_shutdown_rx) = watch::channel(false);
+    // `/reload config` from an admin feeds the same channel.
+    if let Some(ref running_agent) = agent {
+        running_agent
+            .set_config_reload_sender(reload_tx.clone())
+            .await;
+    }
     let watcher_handle = tokio::spawn(
-        ConfigWatcher::default_path(Duration::from_secs(30)).watch(reload_tx, reload_shutdown_rx),
+        ConfigWatcher::default_path(Duration::from_secs(30))
+            .watch(reload_tx.clone(), reload_shutdown_rx.clone()),
     );
 
+    // Skills watcher: notifies the in-process agent over the bus when
+    // SKILL.md files are added, edited, or removed.
+    let skills_watcher_handle =
+        if config.skills.enabled && config.skills.hot_reload && !containerized {
+            let skills_dir = skills_loader_from_config(&config)
+                .workspace_dir()
+                .to_path_buf();
+            info!("Watching {} for skill changes", skills_dir.display());
+            Some(tokio::spawn(
+                SkillsWatcher::new(
+                    skills_dir,
+                    Duration::from_secs(config.skills.hot_reload_interval_secs.max(1)),
+                )
+                .watch(bus.clone(), reload_shutdown_rx),
+            ))
+        } else {
+            None
+        };
+
     loop {
         tokio::select! {
             _ = tokio::signal::ctrl_c() => {
@@ -476,6 +636,10 @@ pub(crate) async fn cmd_gateway(
                     match create_agent(config.clone(), bus.clone()).await {
                         Ok(new_agent) => {
                             new_agent.set_usage_metrics(Arc::clone(&metrics)).await;
+                            new_agent.set_config_reload_sender(reload_tx.clone()).await;
+                            if let Some(api) = &gateway_api {
+                                api.attach_agent(&new_agent).await;
+                            }
                             let agent_clone = Arc::clone(&new_agent);
                             let agent_metrics = Arc::clone(&metrics);
                             let agent_guard = guard.clone();
@@ -513,10 +677,21 @@ pub(crate) async fn cmd_gateway(
                     }
                     let mut new_manager = ChannelManager::new(bus.clone(), config.clone());
                     new_manager.set_health_registry(health_registry.clone());
-                    let count = register_configured_channels(&new_manager, bus.clone(), &config).await;
+                    let count = register_configured_channels_with_webhook(
+                        &new_manager,
+                        bus.clone(),
+                        &config,
+                        telegram_webhook.as_ref(),
+                    )
+                    .await;
                     if count == 0 {
                         warn!("No channels configured after hot-reload");
                     }
+                    if gateway_api.is_some() && devices_enabled {
+                        new_manager
+                            .register(Box::new(DeviceChannel::new(device_hub.clone())))
+                            .await;
+                    }
                     if let Err(e) = new_manager.start_all().await {
                         config = old_config;
                         warn!("Failed to start channels after hot-reload, keeping previous config: {}", e);
@@ -560,6 +735,9 @@ pub(crate) async fn cmd_gateway(
     // Stop config watcher
     let _ = reload_shutdown_tx.send(true);
     let _ = tokio::time::timeout(Duration::from_secs(2), watcher_handle).await;
+    if let Some(handle) = skills_watcher_handle {
+        let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+    }
 
     // Wait for agent/proxy to stop
     if let Some(handle) = agent_handle {
@@ -573,6 +751,14 @@ pub(crate) async fn cmd_gateway(
     if let Some(handle) = health_handle {
         handle.abort();
     }
+    if let Some(handle) = api_handle {
+        handle.abort();
+    }
+
+    // Stop the tunnel last so in-flight webhook deliveries can drain
+    if let Some(tunnel) = tunnel {
+        tunnel.stop().await;
+    }
 
     println!("Gateway stopped.");
     Ok(())
@@ -7,9 +7,11 @@ use anyhow::{Context, Result};
 
 use zeptoclaw::bus::{InboundMessage, MessageBus};
 use zeptoclaw::config::Config;
-use zeptoclaw::heartbeat::{ensure_heartbeat_file, HeartbeatService, HEARTBEAT_PROMPT};
+use zeptoclaw::heartbeat::{
+    ensure_heartbeat_file, sync_heartbeat_file, HeartbeatService, HEARTBEAT_PROMPT,
+};
 
-use super::common::{create_agent, expand_tilde};
+use super::common::{create_agent, default_editor, expand_tilde};
 
 /// Resolve the heartbeat file path from config.
 pub(crate) fn heartbeat_file_path(config: &Config) -> PathBuf {
@@ -18,7 +20,7 @@ pub(crate) fn heartbeat_file_path(config: &Config) -> PathBuf {
         .file_path
         .as_deref()
         .map(expand_tilde)
-        .unwrap_or_else(|| Config::dir().join("HEARTBEAT.md"))
+        .unwrap_or_else(|| Config::data_dir().join("HEARTBEAT.md"))
 }
 
 /// Heartbeat utility command.
@@ -37,7 +39,7 @@ pub(crate) async fn cmd_heartbeat(show: bool, edit: bool) -> Result<()> {
     }
 
     if edit {
-        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "nano".to_string());
+        let editor = default_editor();
         let status = std::process::Command::new(editor)
             .arg(&hb_path)
             .status()
@@ -48,9 +50,7 @@ pub(crate) async fn cmd_heartbeat(show: bool, edit: bool) -> Result<()> {
         return Ok(());
     }
 
-    let content = tokio::fs::read_to_string(&hb_path)
-        .await
-        .unwrap_or_default();
+    let content = sync_heartbeat_file(&hb_path).await.unwrap_or_default();
     if HeartbeatService::is_empty(&content) {
         println!("Heartbeat file has no actionable tasks.");
         return Ok(());
@@ -61,5 +61,7 @@ pub(crate) async fn cmd_heartbeat(show: bool, edit: bool) -> Result<()> {
     let inbound = InboundMessage::new("cli", "heartbeat", "heartbeat:cli", HEARTBEAT_PROMPT);
     let response = agent.process_message(&inbound).await?;
     println!("{}", response);
+    // Stamp anything the agent ticked off during this run.
+    sync_heartbeat_file(&hb_path).await?;
     Ok(())
 }
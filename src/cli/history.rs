@@ -1,9 +1,18 @@
 //! Conversation history command handler.
 
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
 
+use zeptoclaw::agent::replay::replay_turn;
+use zeptoclaw::bus::MessageBus;
+use zeptoclaw::config::Config;
+use zeptoclaw::session::history::ConversationEntry;
+use zeptoclaw::session::ledger::{render_trace, LedgerStore, LedgerTurn};
+use zeptoclaw::session::timeline::{render_html, TimelineStore};
 use zeptoclaw::session::{ConversationHistory, Role, SessionManager};
 
+use super::common::create_agent;
 use super::HistoryAction;
 
 /// Manage CLI conversation history.
@@ -22,10 +31,25 @@ pub(crate) async fn cmd_history(action: HistoryAction) -> Result<()> {
             let shown = entries.len().min(limit);
             println!("Showing {} of {} conversation(s):", shown, entries.len());
             for entry in entries.iter().take(limit) {
-                println!(
-                    "- {} | {} msgs | {} | {}",
-                    entry.session_key, entry.message_count, entry.last_updated, entry.title
-                );
+                print_entry(entry);
+            }
+        }
+        HistoryAction::Search { query, limit } => {
+            let entries = history.search_conversations(&query)?;
+            if entries.is_empty() {
+                println!("No conversations match '{}'.", query);
+                return Ok(());
+            }
+
+            let shown = entries.len().min(limit);
+            println!(
+                "Showing {} of {} conversation(s) matching '{}':",
+                shown,
+                entries.len(),
+                query
+            );
+            for entry in entries.iter().take(limit) {
+                print_entry(entry);
             }
         }
         HistoryAction::Show { query } => {
@@ -41,6 +65,10 @@ pub(crate) async fn cmd_history(action: HistoryAction) -> Result<()> {
                 );
             };
 
+            println!("Title: {}", entry.title);
+            if !entry.tags.is_empty() {
+                println!("Tags: {}", entry.tags.join(", "));
+            }
             println!("Session: {}", session.key);
             println!("Updated: {}", session.updated_at.to_rfc3339());
             println!("Messages: {}", session.messages.len());
@@ -55,6 +83,89 @@ pub(crate) async fn cmd_history(action: HistoryAction) -> Result<()> {
                 println!();
             }
         }
+        HistoryAction::Timeline { key, html, last } => {
+            let store = TimelineStore::new().with_context(|| "Failed to open timeline store")?;
+
+            // Exact session keys work for any channel; fall back to the CLI
+            // history index for title lookups.
+            let mut session_key = key.clone();
+            let mut turns = store.load(&session_key)?;
+            if turns.is_empty() {
+                if let Some(entry) = history.find_conversation(&key)? {
+                    session_key = entry.session_key;
+                    turns = store.load(&session_key)?;
+                }
+            }
+            if turns.is_empty() {
+                anyhow::bail!(
+                    "No timeline recorded for '{}' (enable session.timeline to record them)",
+                    key
+                );
+            }
+            if let Some(n) = last {
+                let skip = turns.len().saturating_sub(n);
+                turns = turns.split_off(skip);
+            }
+
+            println!("{}", serde_json::to_string_pretty(&turns)?);
+
+            if let Some(path) = html {
+                std::fs::write(&path, render_html(&session_key, &turns))
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                eprintln!("Wrote HTML timeline to {}", path.display());
+            }
+        }
+        HistoryAction::Trace {
+            key,
+            turn,
+            last,
+            json,
+        } => {
+            let mut turns = load_ledger(&history, &key)?;
+            let mut first = 1;
+            if let Some(n) = turn {
+                if n == 0 || n > turns.len() {
+                    anyhow::bail!("Turn {} not found ({} recorded)", n, turns.len());
+                }
+                turns = vec![turns.swap_remove(n - 1)];
+                first = n;
+            } else if let Some(n) = last {
+                let skip = turns.len().saturating_sub(n);
+                turns = turns.split_off(skip);
+                first = skip + 1;
+            }
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&turns)?);
+            } else {
+                print!("{}", render_trace(&turns, first));
+            }
+        }
+        HistoryAction::Replay {
+            key,
+            turn,
+            dry_run,
+            check,
+        } => {
+            let mut turns = load_ledger(&history, &key)?;
+            let n = turn.unwrap_or(turns.len());
+            if n == 0 || n > turns.len() {
+                anyhow::bail!("Turn {} not found ({} recorded)", n, turns.len());
+            }
+            let recorded = turns.swap_remove(n - 1);
+
+            let mut config = Config::load().with_context(|| "Failed to load configuration")?;
+            // Keep the replay out of the ledger it reads from.
+            config.session.ledger = false;
+            let agent = create_agent(config, Arc::new(MessageBus::new())).await?;
+            agent.set_dry_run(dry_run);
+            let report = replay_turn(&agent, &recorded).await?;
+            println!("Turn {}:", n);
+            print!("{}", report.render());
+            if check && !report.is_match() {
+                anyhow::bail!("Replay diverged from the recording");
+            }
+        }
         HistoryAction::Cleanup { keep } => {
             let deleted = history.cleanup_old(keep)?;
             println!(
@@ -67,6 +178,38 @@ pub(crate) async fn cmd_history(action: HistoryAction) -> Result<()> {
     Ok(())
 }
 
+/// Ledger turns for a session key, or for the conversation whose title
+/// matches `key`.
+fn load_ledger(history: &ConversationHistory, key: &str) -> Result<Vec<LedgerTurn>> {
+    let store = LedgerStore::new().with_context(|| "Failed to open ledger store")?;
+
+    let mut turns = store.load(key)?;
+    if turns.is_empty() {
+        if let Some(entry) = history.find_conversation(key)? {
+            turns = store.load(&entry.session_key)?;
+        }
+    }
+    if turns.is_empty() {
+        anyhow::bail!(
+            "No ledger recorded for '{}' (enable session.ledger to record turns)",
+            key
+        );
+    }
+    Ok(turns)
+}
+
+fn print_entry(entry: &ConversationEntry) {
+    let tags = if entry.tags.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", entry.tags.join(", "))
+    };
+    println!(
+        "- {}{} | {} msgs | {} | {}",
+        entry.title, tags, entry.message_count, entry.last_updated, entry.session_key
+    );
+}
+
 fn role_label(role: &Role) -> &'static str {
     match role {
         Role::System => "system",
@@ -0,0 +1,74 @@
+//! Listen command handler (local microphone voice mode).
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use tracing::error;
+
+use zeptoclaw::bus::MessageBus;
+use zeptoclaw::channels::{ChannelManager, VoiceChannel};
+use zeptoclaw::config::Config;
+use zeptoclaw::transcription::TranscriberService;
+
+use super::common::create_agent;
+
+/// Talk to the agent through the microphone until Ctrl+C.
+pub(crate) async fn cmd_listen() -> Result<()> {
+    let config = Config::load().with_context(|| "Failed to load configuration")?;
+    let voice = config.channels.voice.clone().unwrap_or_default();
+    let transcriber = TranscriberService::from_config(&config).ok_or_else(|| {
+        anyhow!(
+            "Voice mode needs transcription. Set transcription.enabled and configure a \
+             backend (e.g. `whisper_cpp` for fully local audio) in {:?}",
+            Config::path()
+        )
+    })?;
+    let backends = transcriber.backend_names().join(", ");
+
+    let bus = Arc::new(MessageBus::new());
+    let agent = create_agent(config.clone(), bus.clone()).await?;
+
+    let channel_manager = ChannelManager::new(bus.clone(), config.clone());
+    channel_manager
+        .register(Box::new(VoiceChannel::new(
+            voice.clone(),
+            transcriber,
+            bus.clone(),
+        )))
+        .await;
+    channel_manager
+        .start_all()
+        .await
+        .with_context(|| "Failed to start voice channel")?;
+
+    let agent_clone = Arc::clone(&agent);
+    let agent_handle = tokio::spawn(async move {
+        if let Err(e) = agent_clone.start().await {
+            error!("Agent loop error: {}", e);
+        }
+    });
+
+    println!();
+    println!("Listening (transcription: {})", backends);
+    match voice.wake_words.first() {
+        Some(wake) => println!("Say \"{}\" followed by your request.", wake),
+        None => println!("No wake word configured; every utterance goes to the agent."),
+    }
+    println!("Press Ctrl+C to stop.");
+    println!();
+
+    tokio::signal::ctrl_c()
+        .await
+        .with_context(|| "Failed to listen for Ctrl+C")?;
+    println!();
+
+    agent.stop();
+    agent.shutdown_mcp_clients().await;
+    channel_manager
+        .stop_all()
+        .await
+        .with_context(|| "Failed to stop voice channel")?;
+    let _ = tokio::time::timeout(Duration::from_secs(5), agent_handle).await;
+    Ok(())
+}
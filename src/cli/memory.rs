@@ -1,8 +1,11 @@
 //! Memory CLI command handlers.
 
 use anyhow::{Context, Result};
+use zeptoclaw::config::Config;
+use zeptoclaw::memory::audit::MemoryAudit;
+use zeptoclaw::memory::extraction::{self, PendingMemories};
 use zeptoclaw::memory::longterm::LongTermMemory;
-use zeptoclaw::memory::snapshot;
+use zeptoclaw::memory::{hygiene, notes_sync, snapshot};
 
 use super::MemoryAction;
 
@@ -21,6 +24,15 @@ pub(crate) async fn cmd_memory(action: MemoryAction) -> Result<()> {
         MemoryAction::Cleanup { threshold } => cmd_memory_cleanup(threshold).await,
         MemoryAction::Export { output } => cmd_memory_export(output).await,
         MemoryAction::Import { path, overwrite } => cmd_memory_import(path, overwrite).await,
+        MemoryAction::Audit { limit } => cmd_memory_audit(limit).await,
+        MemoryAction::Sync { dry_run } => cmd_memory_sync(dry_run).await,
+        MemoryAction::Consolidate { dry_run, resolve } => {
+            cmd_memory_consolidate(dry_run, resolve).await
+        }
+        MemoryAction::Review {
+            approve_all,
+            reject_all,
+        } => cmd_memory_review(approve_all, reject_all).await,
     }
 }
 
@@ -135,7 +147,7 @@ async fn cmd_memory_stats() -> Result<()> {
         }
     }
 
-    let path = zeptoclaw::config::Config::dir()
+    let path = zeptoclaw::config::Config::data_dir()
         .join("memory")
         .join("longterm.json");
     if path.exists() {
@@ -196,6 +208,193 @@ async fn cmd_memory_import(path: std::path::PathBuf, overwrite: bool) -> Result<
     Ok(())
 }
 
+async fn cmd_memory_audit(limit: usize) -> Result<()> {
+    let records = MemoryAudit::default()
+        .recent(limit)
+        .with_context(|| "Failed to read memory audit trail")?;
+    if records.is_empty() {
+        println!("No memory writes recorded yet.");
+        return Ok(());
+    }
+
+    println!("Memory audit trail (last {})", records.len());
+    println!("{}", "-".repeat(60));
+    for record in &records {
+        let confidence = record
+            .confidence
+            .map(|c| format!(", confidence {:.2}", c))
+            .unwrap_or_default();
+        println!(
+            "  {} {} {} ({}{})",
+            record.timestamp.get(..19).unwrap_or(&record.timestamp),
+            record.action,
+            record.key,
+            record.category,
+            confidence
+        );
+        println!("    {}", truncate_value(&record.value, 80));
+        println!("    why: {}", truncate_value(&record.reason, 80));
+    }
+    Ok(())
+}
+
+async fn cmd_memory_sync(dry_run: bool) -> Result<()> {
+    let config = Config::load().with_context(|| "Failed to load configuration")?;
+    let stores = notes_sync::configured_stores(&config.memory.sync)?;
+    if stores.is_empty() {
+        anyhow::bail!(
+            "No sync target configured. Set memory.sync.obsidian.vault_path or \
+             memory.sync.notion (token, database_id)"
+        );
+    }
+    let workspace = config.workspace_path();
+    for store in &stores {
+        let state_path = notes_sync::default_state_path(store.name());
+        let report = notes_sync::sync_notes(
+            &workspace,
+            store.as_ref(),
+            &config.memory.sync,
+            &state_path,
+            dry_run,
+        )
+        .await
+        .with_context(|| format!("Failed to sync with {}", store.name()))?;
+        println!(
+            "{}{}: {}",
+            store.name(),
+            if dry_run { " (dry run)" } else { "" },
+            report.summary()
+        );
+        let sections = [
+            ("push", &report.pushed),
+            ("pull", &report.pulled),
+            ("delete local", &report.deleted_local),
+            ("delete remote", &report.deleted_remote),
+            ("conflict", &report.conflicts),
+            ("skipped", &report.skipped),
+            ("error", &report.errors),
+        ];
+        for (label, items) in sections {
+            for item in items {
+                println!("  {}: {}", label, item);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn cmd_memory_consolidate(dry_run: bool, resolve: bool) -> Result<()> {
+    let config = Config::load().with_context(|| "Failed to load configuration")?;
+    let mut hygiene_config = config.memory.hygiene.clone();
+    hygiene_config.resolve_conflicts |= resolve;
+    let mut mem = LongTermMemory::new().with_context(|| "Failed to open long-term memory")?;
+    let report = hygiene::consolidate(&mut mem, &hygiene_config, dry_run)
+        .await
+        .with_context(|| "Failed to consolidate memory")?;
+
+    println!(
+        "{}{}",
+        report.summary(),
+        if dry_run { " (dry run)" } else { "" }
+    );
+    if report.is_empty() {
+        return Ok(());
+    }
+    println!("{}", "-".repeat(60));
+    for merge in &report.merged {
+        println!(
+            "  merge {} -> {} ({:.0}% similar)",
+            merge.removed,
+            merge.kept,
+            merge.similarity * 100.0
+        );
+    }
+    for conflict in &report.conflicts {
+        match &conflict.resolved_to {
+            Some(kept) => println!("  conflict (keeping {}):", kept),
+            None => println!("  conflict:"),
+        }
+        for (key, value) in &conflict.entries {
+            println!("    {} = {}", key, truncate_value(value, 70));
+        }
+    }
+    if report.unresolved().next().is_some() {
+        println!();
+        println!("Fix with `zeptoclaw memory delete <key>`, or rerun with --resolve.");
+    }
+    Ok(())
+}
+
+async fn cmd_memory_review(approve_all: bool, reject_all: bool) -> Result<()> {
+    let pending = PendingMemories::default();
+    let items = pending
+        .list()
+        .with_context(|| "Failed to read pending memories")?;
+    if items.is_empty() {
+        println!("No pending memories to review.");
+        return Ok(());
+    }
+
+    let mut mem = LongTermMemory::new().with_context(|| "Failed to open long-term memory")?;
+    let audit = MemoryAudit::default();
+    let (mut approved, mut rejected) = (0, 0);
+    println!("Pending memories ({})", items.len());
+    println!("{}", "-".repeat(60));
+    for item in &items {
+        let fact = &item.fact;
+        let decision = if approve_all {
+            'a'
+        } else if reject_all {
+            'r'
+        } else {
+            let owner = item
+                .namespace
+                .as_deref()
+                .map(|ns| format!(", for {}", ns))
+                .unwrap_or_default();
+            println!(
+                "  {} ({}, confidence {:.2}{})",
+                fact.key, fact.category, fact.confidence, owner
+            );
+            println!("    {}", fact.value);
+            if !fact.reason.is_empty() {
+                println!("    why: {}", truncate_value(&fact.reason, 80));
+            }
+            print!("  [a]pprove, [r]eject, [s]kip, [q]uit? ");
+            use std::io::Write;
+            std::io::stdout().flush()?;
+            let mut input = String::new();
+            std::io::stdin().read_line(&mut input)?;
+            input
+                .trim()
+                .chars()
+                .next()
+                .unwrap_or('s')
+                .to_ascii_lowercase()
+        };
+        match decision {
+            'a' => {
+                if let Some(item) = pending.take(&item.id)? {
+                    extraction::approve(&mut mem, &audit, &item)
+                        .await
+                        .with_context(|| format!("Failed to store memory '{}'", fact.key))?;
+                    approved += 1;
+                }
+            }
+            'r' if pending.take(&item.id)?.is_some() => rejected += 1,
+            'q' => break,
+            _ => {}
+        }
+    }
+
+    let left = pending.list()?.len();
+    println!(
+        "Approved {}, rejected {}, {} still pending.",
+        approved, rejected, left
+    );
+    Ok(())
+}
+
 fn truncate_value(s: &str, max: usize) -> String {
     if s.len() <= max {
         s.to_string()
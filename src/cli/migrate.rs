@@ -149,7 +149,7 @@ pub(crate) async fn cmd_migrate(from: Option<String>, yes: bool, dry_run: bool)
     };
 
     if do_skills {
-        let dest_dir = Config::dir().join("skills");
+        let dest_dir = Config::data_dir().join("skills");
 
         if !dry_run {
             let (copied, skipped) = migrate::skills::copy_skills(&skill_dirs, &dest_dir)?;
@@ -3,16 +3,20 @@
 //! All CLI logic lives here. `main.rs` calls `cli::run()`.
 
 pub mod agent;
+pub mod backup;
 pub mod batch;
 pub mod channel;
 pub mod common;
 pub mod config;
 pub mod daemon;
 pub mod doctor;
+pub mod eval;
 pub mod gateway;
 pub mod hand;
 pub mod heartbeat;
 pub mod history;
+#[cfg(feature = "voice")]
+pub mod listen;
 pub mod memory;
 pub mod migrate;
 pub mod onboard;
@@ -30,9 +34,11 @@ pub mod slash;
 pub mod status;
 pub mod template;
 pub mod tools;
+pub mod undo;
 pub mod uninstall;
 pub mod update;
 pub mod watch;
+pub mod workspace;
 
 use anyhow::Result;
 use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
@@ -42,6 +48,11 @@ use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 #[command(version)]
 #[command(about = "Ultra-lightweight personal AI assistant", long_about = None)]
 struct Cli {
+    /// Config profile to layer over config.json (~/.zeptoclaw/profiles/<name>.json).
+    /// Defaults to ZEPTOCLAW_PROFILE.
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -68,7 +79,7 @@ enum Commands {
         /// Disable streaming (streaming is on by default)
         #[arg(long)]
         no_stream: bool,
-        /// Show what tools would be called without executing them
+        /// Plan mode: propose tool calls with a cost estimate and wait for "approve plan"
         #[arg(long)]
         dry_run: bool,
         /// Agent mode: observer (read-only), assistant (read/write + approval), autonomous (full access)
@@ -77,9 +88,12 @@ enum Commands {
     },
     /// Process prompts from a file
     Batch {
-        /// Input file (.txt, .json, or .jsonl)
+        /// Input file (.txt, .json, or .jsonl), or a dataset (.csv, .json, .jsonl) with --template-file
         #[arg(long)]
         input: std::path::PathBuf,
+        /// Prompt template rendered once per dataset row (`{{column}}` placeholders)
+        #[arg(long)]
+        template_file: Option<std::path::PathBuf>,
         /// Optional output file (prints to stdout if omitted)
         #[arg(long)]
         output: Option<std::path::PathBuf>,
@@ -95,6 +109,38 @@ enum Commands {
         /// Apply an agent template to all prompts
         #[arg(long)]
         template: Option<String>,
+        /// Number of prompts to process in parallel (default: batch.concurrency)
+        #[arg(long)]
+        concurrency: Option<usize>,
+        /// Per-prompt timeout in seconds (default: batch.prompt_timeout_secs, 0 = none)
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// Checkpoint file for resuming (default: <input>.checkpoint.jsonl)
+        #[arg(long)]
+        checkpoint: Option<std::path::PathBuf>,
+        /// Ignore any existing checkpoint and process every prompt again
+        #[arg(long)]
+        fresh: bool,
+    },
+    /// Run evaluation scenarios (YAML/JSON file or directory) and score the agent
+    Eval {
+        /// Scenario file or directory of scenario files
+        path: std::path::PathBuf,
+        /// Serve each scenario's scripted `mock` responses instead of calling the provider
+        #[arg(long)]
+        mock: bool,
+        /// Only run scenarios whose name contains this text
+        #[arg(long)]
+        filter: Option<String>,
+        /// Apply an agent template to every scenario
+        #[arg(long)]
+        template: Option<String>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+        /// Minimum suite score (0-100) to exit successfully; default requires every scenario to pass
+        #[arg(long)]
+        min_score: Option<f64>,
     },
     /// Start multi-channel gateway
     Gateway {
@@ -141,6 +187,11 @@ enum Commands {
         #[command(subcommand)]
         action: HandAction,
     },
+    /// Manage project workspaces
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceAction,
+    },
     /// Manage and discover tools
     Tools {
         #[command(subcommand)]
@@ -165,6 +216,11 @@ enum Commands {
         #[command(subcommand)]
         action: ConfigAction,
     },
+    /// Back up or restore config, sessions, memory, cron jobs, skills and pairing data
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
     /// Manage secret encryption
     Secrets {
         #[command(subcommand)]
@@ -186,6 +242,18 @@ enum Commands {
         #[command(subcommand)]
         action: PairAction,
     },
+    /// Revert file changes the agent made in recent turns
+    Undo {
+        /// Number of most recent turns to revert
+        #[arg(long, default_value_t = 1)]
+        turns: usize,
+        /// List the turns that can be undone instead of reverting
+        #[arg(long)]
+        list: bool,
+        /// Workspace to undo in (default: current directory, or the configured workspace)
+        #[arg(long)]
+        workspace: Option<std::path::PathBuf>,
+    },
     /// Show or reset per-provider quota usage
     Quota {
         #[command(subcommand)]
@@ -249,6 +317,19 @@ enum Commands {
         /// Force re-download even if already on latest
         #[arg(long)]
         force: bool,
+        /// Release channel, overriding `update.channel` [stable, beta]
+        #[arg(long, value_parser = ["stable", "beta"])]
+        channel: Option<String>,
+        /// Install a local binary instead of downloading (air-gapped hosts);
+        /// `.sha256`, `.minisig` and `.sig` files next to it are verified
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["check", "version"])]
+        file: Option<std::path::PathBuf>,
+        /// Restore the binary replaced by the last update
+        #[arg(long, conflicts_with_all = ["check", "version", "force", "file"])]
+        rollback: bool,
+        /// Install even when no signing key is available to verify the binary
+        #[arg(long, conflicts_with = "rollback")]
+        allow_unsigned: bool,
     },
     /// Remove ZeptoClaw state and optionally the current binary
     Uninstall {
@@ -274,6 +355,9 @@ enum Commands {
         #[arg(long, default_value = "127.0.0.1")]
         bind: String,
     },
+    #[cfg(feature = "voice")]
+    /// Talk to the agent through the microphone (wake word, local VAD, spoken replies)
+    Listen,
     /// Start MCP server (expose tools to Claude Desktop, VS Code, Cursor)
     McpServer {
         /// Listen on HTTP address instead of stdio (e.g., ":3000", "127.0.0.1:3000")
@@ -335,6 +419,36 @@ pub enum MemoryAction {
         #[arg(long)]
         overwrite: bool,
     },
+    /// Show what the agent remembered and why
+    Audit {
+        /// Number of most recent writes to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Sync workspace memory notes with Obsidian or Notion (memory.sync)
+    Sync {
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Merge duplicate memories and report conflicting ones
+    Consolidate {
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Keep only the most recent entry of each conflict
+        #[arg(long)]
+        resolve: bool,
+    },
+    /// Approve or reject automatically extracted memories (memory.extraction)
+    Review {
+        /// Approve every pending memory without prompting
+        #[arg(long, conflicts_with = "reject_all")]
+        approve_all: bool,
+        /// Reject every pending memory without prompting
+        #[arg(long)]
+        reject_all: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -371,6 +485,47 @@ pub enum SkillsAction {
         #[arg(long)]
         github: Option<String>,
     },
+    /// Package a skill directory (manifest + checksum) for publishing
+    Publish {
+        /// Skill directory containing SKILL.md
+        path: std::path::PathBuf,
+        /// Output directory for the archive (default: current directory)
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+        /// Upload the package to ClawHub (requires an auth token)
+        #[arg(long)]
+        upload: bool,
+    },
+    /// Update installed skills to newer versions recorded in the lockfile
+    Update {
+        /// Skill to update (omit with --all)
+        name: Option<String>,
+        /// Update every locked skill
+        #[arg(long)]
+        all: bool,
+        /// Allow upgrades across major versions
+        #[arg(long)]
+        allow_major: bool,
+        /// Show available updates without installing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show locked skill versions and verify installed checksums
+    Lock,
+    /// Plan (and optionally install) missing skill requirements
+    Provision {
+        /// Skill to provision (omit with --all)
+        name: Option<String>,
+        /// Provision every skill with missing requirements
+        #[arg(long)]
+        all: bool,
+        /// Run the install commands (default: print them only)
+        #[arg(long)]
+        apply: bool,
+        /// Target the Docker runtime image instead of the host
+        #[arg(long)]
+        docker: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -388,6 +543,31 @@ pub enum HandAction {
     Status,
 }
 
+#[derive(Subcommand)]
+pub enum WorkspaceAction {
+    /// List registered workspaces and chat bindings
+    List,
+    /// Register a new workspace
+    Create {
+        /// Workspace name (letters, digits, '-' and '_')
+        name: String,
+        /// Workspace directory (default: ~/.zeptoclaw/workspaces/<name>)
+        #[arg(long)]
+        path: Option<String>,
+        /// Short description shown in `workspace list`
+        #[arg(long)]
+        description: Option<String>,
+        /// Make the new workspace active
+        #[arg(long)]
+        switch: bool,
+    },
+    /// Set the active workspace ("default" returns to agents.defaults.workspace)
+    Switch {
+        /// Workspace name
+        name: String,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum ToolsAction {
     /// List all available tools with status
@@ -397,6 +577,15 @@ pub enum ToolsAction {
         /// Tool name
         name: String,
     },
+    /// Show per-tool usage analytics (calls, error rate, latency, arg sizes)
+    Stats {
+        /// Number of days to aggregate, ending today
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -426,6 +615,14 @@ pub enum AuthAction {
 pub enum ConfigAction {
     /// Check configuration for errors and warnings
     Check,
+    /// Print the config file's JSON Schema (for editor autocomplete)
+    Schema,
+    /// Move a legacy ~/.zeptoclaw into the ZEPTOCLAW_HOME / XDG directories
+    MigrateDirs {
+        /// Show the planned moves without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Reset configuration to defaults (backs up existing config first)
     Reset {
         /// Skip confirmation prompt
@@ -463,12 +660,61 @@ pub enum HistoryAction {
         /// Session key (exact) or title substring (case-insensitive)
         query: String,
     },
+    /// Search CLI conversations by title, tag or session key
+    Search {
+        /// Substring to match (case-insensitive)
+        query: String,
+        /// Maximum number of conversations to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
     /// Remove old CLI conversations
     Cleanup {
         /// Keep this many most-recent conversations
         #[arg(long, default_value_t = 50)]
         keep: usize,
     },
+    /// Export per-turn timing (queue wait, provider latency, tool durations) as JSON
+    Timeline {
+        /// Session key (exact) or title substring (case-insensitive)
+        key: String,
+        /// Also write an HTML Gantt view to this path
+        #[arg(long)]
+        html: Option<std::path::PathBuf>,
+        /// Only include the most recent N turns
+        #[arg(long)]
+        last: Option<usize>,
+    },
+    /// Replay the turn ledger: inbound message, model, tool calls,
+    /// compactions, response and cost per turn (requires `session.ledger`)
+    Trace {
+        /// Session key (exact) or title substring (case-insensitive)
+        key: String,
+        /// Only show turn N (1-based)
+        #[arg(long, conflicts_with = "last")]
+        turn: Option<usize>,
+        /// Only include the most recent N turns
+        #[arg(long)]
+        last: Option<usize>,
+        /// Print the raw ledger entries as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Re-run a recorded turn against its recorded LLM responses (no network
+    /// calls) and report where it diverges (requires `session.ledger`)
+    Replay {
+        /// Session key (exact) or title substring (case-insensitive)
+        key: String,
+        /// Turn to replay (1-based); defaults to the most recent
+        #[arg(long)]
+        turn: Option<usize>,
+        /// Describe tool calls instead of executing them
+        #[arg(long)]
+        dry_run: bool,
+        /// Exit with an error when the replay diverges (for regression tests)
+        #[arg(long)]
+        check: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -482,6 +728,33 @@ pub enum TemplateAction {
     },
 }
 
+#[derive(Subcommand)]
+pub enum BackupAction {
+    /// Write an encrypted backup (passphrase from ZEPTOCLAW_BACKUP_PASSPHRASE or a prompt)
+    Create {
+        /// Output file (default: ./zeptoclaw-backup-<timestamp>.tar.gz.enc)
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+        /// Leave API keys, tokens and credential stores out
+        #[arg(long)]
+        exclude_secrets: bool,
+        /// Also back up the workspace and named workspaces
+        #[arg(long)]
+        include_workspace: bool,
+    },
+    /// Restore a backup into the config and data dirs
+    Restore {
+        /// Backup file
+        path: std::path::PathBuf,
+        /// Replace existing files
+        #[arg(long)]
+        force: bool,
+        /// Show what would be restored and replaced
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum SecretsAction {
     /// Encrypt all plaintext secrets in config
@@ -495,7 +768,20 @@ pub enum SecretsAction {
 #[derive(Subcommand)]
 pub enum PairAction {
     /// Generate a new 6-digit pairing code
-    New,
+    New {
+        /// Scope granted to the device: full or read-only
+        #[arg(long, default_value = "full")]
+        scope: String,
+        /// Gateway URL the device should call (defaults to gateway.host:gateway.port)
+        #[arg(long)]
+        url: Option<String>,
+        /// Print a QR code for a mobile app to scan (requires the pairing-qr feature)
+        #[arg(long)]
+        qr: bool,
+        /// Also write the QR code as a PNG to this path (requires the pairing-qr feature)
+        #[arg(long)]
+        png: Option<std::path::PathBuf>,
+    },
     /// List all paired devices
     List,
     /// Revoke a paired device
@@ -545,6 +831,9 @@ pub async fn run() -> Result<()> {
     // Load config early so we can respect the logging settings; fall back to
     // defaults if the config file is missing or unreadable.
     let cli = Cli::parse();
+    if let Some(profile) = cli.profile.as_deref() {
+        zeptoclaw::config::Config::set_profile(profile)?;
+    }
 
     let mut logging_cfg = zeptoclaw::config::Config::load()
         .map(|c| c.logging)
@@ -555,7 +844,7 @@ pub async fn run() -> Result<()> {
     // Users can still override with RUST_LOG=info.
     if matches!(
         cli.command,
-        Some(Commands::Agent { .. } | Commands::Batch { .. })
+        Some(Commands::Agent { .. } | Commands::Batch { .. } | Commands::Eval { .. })
     ) && std::env::var("RUST_LOG").is_err()
     {
         logging_cfg.level = "warn".to_string();
@@ -586,13 +875,35 @@ pub async fn run() -> Result<()> {
         }
         Some(Commands::Batch {
             input,
+            template_file,
             output,
             format,
             stop_on_error,
             stream,
             template,
+            concurrency,
+            timeout,
+            checkpoint,
+            fresh,
+        }) => {
+            let run = batch::BatchRunOptions {
+                template_file,
+                concurrency,
+                timeout_secs: timeout,
+                checkpoint,
+                fresh,
+            };
+            batch::cmd_batch(input, output, format, stop_on_error, stream, template, run).await?;
+        }
+        Some(Commands::Eval {
+            path,
+            mock,
+            filter,
+            template,
+            json,
+            min_score,
         }) => {
-            batch::cmd_batch(input, output, format, stop_on_error, stream, template).await?;
+            eval::cmd_eval(path, mock, filter, template, json, min_score).await?;
         }
         Some(Commands::Gateway {
             containerized,
@@ -621,6 +932,9 @@ pub async fn run() -> Result<()> {
         Some(Commands::Hand { action }) => {
             hand::cmd_hand(action).await?;
         }
+        Some(Commands::Workspace { action }) => {
+            workspace::cmd_workspace(action).await?;
+        }
         Some(Commands::Tools { action }) => {
             tools::cmd_tools(action).await?;
         }
@@ -636,6 +950,9 @@ pub async fn run() -> Result<()> {
         Some(Commands::Config { action }) => {
             config::cmd_config(action).await?;
         }
+        Some(Commands::Backup { action }) => {
+            backup::cmd_backup(action).await?;
+        }
         Some(Commands::Secrets { action }) => {
             secrets::cmd_secrets(action).await?;
         }
@@ -649,6 +966,13 @@ pub async fn run() -> Result<()> {
         Some(Commands::Pair { action }) => {
             pair::cmd_pair(action).await?;
         }
+        Some(Commands::Undo {
+            turns,
+            list,
+            workspace,
+        }) => {
+            undo::cmd_undo(turns, list, workspace).await?;
+        }
         Some(Commands::Quota { action }) => {
             quota::cmd_quota(action)?;
         }
@@ -681,8 +1005,25 @@ pub async fn run() -> Result<()> {
             check,
             version,
             force,
+            channel,
+            file,
+            rollback,
+            allow_unsigned,
         }) => {
-            update::cmd_update(check, version, force).await?;
+            let channel = channel.map(|c| match c.as_str() {
+                "beta" => zeptoclaw::config::UpdateChannel::Beta,
+                _ => zeptoclaw::config::UpdateChannel::Stable,
+            });
+            update::cmd_update(
+                check,
+                version,
+                force,
+                channel,
+                file,
+                rollback,
+                allow_unsigned,
+            )
+            .await?;
         }
         Some(Commands::Uninstall { remove_binary, yes }) => {
             uninstall::cmd_uninstall(remove_binary, yes).await?;
@@ -694,6 +1035,10 @@ pub async fn run() -> Result<()> {
         Some(Commands::Serve { port, bind }) => {
             serve::cmd_serve(port, bind).await?;
         }
+        #[cfg(feature = "voice")]
+        Some(Commands::Listen) => {
+            listen::cmd_listen().await?;
+        }
         Some(Commands::McpServer { http }) => {
             cmd_mcp_server(http).await?;
         }
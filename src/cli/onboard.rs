@@ -216,11 +216,11 @@ pub(crate) async fn cmd_onboard(full: bool) -> Result<()> {
     std::fs::create_dir_all(&config_dir)
         .with_context(|| format!("Failed to create config directory: {:?}", config_dir))?;
 
-    let workspace_dir = config_dir.join("workspace");
+    let workspace_dir = Config::data_dir().join("workspace");
     std::fs::create_dir_all(&workspace_dir)
         .with_context(|| format!("Failed to create workspace directory: {:?}", workspace_dir))?;
 
-    let sessions_dir = config_dir.join("sessions");
+    let sessions_dir = Config::data_dir().join("sessions");
     std::fs::create_dir_all(&sessions_dir)
         .with_context(|| format!("Failed to create sessions directory: {:?}", sessions_dir))?;
 
@@ -1,8 +1,10 @@
 //! CLI commands for device pairing management.
 
-use anyhow::Result;
+use std::path::Path;
+
+use anyhow::{Context, Result};
 use zeptoclaw::config::Config;
-use zeptoclaw::security::PairingManager;
+use zeptoclaw::security::{DeviceScope, PairingManager};
 
 use super::PairAction;
 
@@ -11,41 +13,108 @@ pub(crate) async fn cmd_pair(action: PairAction) -> Result<()> {
     let config = Config::load()?;
 
     match action {
-        PairAction::New => cmd_pair_new(&config).await,
+        PairAction::New {
+            scope,
+            url,
+            qr,
+            png,
+        } => {
+            let scope: DeviceScope = scope.parse().map_err(anyhow::Error::msg)?;
+            cmd_pair_new(&config, scope, url, qr, png.as_deref()).await
+        }
         PairAction::List => cmd_pair_list(&config).await,
         PairAction::Revoke { device } => cmd_pair_revoke(&config, &device).await,
     }
 }
 
 /// Generate a new pairing code and display it.
-async fn cmd_pair_new(config: &Config) -> Result<()> {
+async fn cmd_pair_new(
+    config: &Config,
+    scope: DeviceScope,
+    url: Option<String>,
+    qr: bool,
+    png: Option<&Path>,
+) -> Result<()> {
     let mut mgr = PairingManager::new(config.pairing.max_attempts, config.pairing.lockout_secs);
 
-    let code = mgr.generate_pairing_code();
+    let base_url = url.unwrap_or_else(|| default_gateway_url(config));
+    let code = mgr.generate_scoped_pairing_code(scope);
     println!("Pairing code: {}", code);
+    println!("Scope: {}", scope);
     println!();
     println!("This code is valid for 5 minutes.");
     println!("Use it to pair a device by sending:");
     println!("  Authorization: Bearer <token-from-pairing>");
     println!();
 
+    if qr || png.is_some() {
+        show_qr(&pairing_uri(&base_url, &code, scope)?, qr, png)?;
+    }
+
     // Wait for the device to complete pairing (interactive mode)
     println!("Waiting for device to pair (press Ctrl+C to cancel)...");
     println!();
     println!("To complete pairing from another terminal or device:");
-    println!(
-        "  curl -X POST http://localhost:{}/pair \\",
-        config.gateway.port
-    );
+    println!("  curl -X POST {}/pair \\", base_url.trim_end_matches('/'));
     println!("    -H 'Content-Type: application/json' \\");
     println!(
         "    -d '{{\"code\": \"{}\", \"device_name\": \"my-device\"}}'",
         code
     );
+    if !config.pairing.enabled {
+        println!();
+        println!("Note: pairing.enabled is false; the gateway will not serve /pair.");
+    }
 
     Ok(())
 }
 
+/// Gateway URL from config; a wildcard bind address is shown as localhost.
+fn default_gateway_url(config: &Config) -> String {
+    let host = match config.gateway.host.as_str() {
+        "0.0.0.0" | "::" | "" => "localhost",
+        host => host,
+    };
+    format!("http://{}:{}", host, config.gateway.port)
+}
+
+/// URI encoded in the pairing QR code, for the mobile app to scan.
+fn pairing_uri(base_url: &str, code: &str, scope: DeviceScope) -> Result<String> {
+    let mut uri = url::Url::parse("zeptoclaw://pair").context("Invalid pairing URI")?;
+    uri.query_pairs_mut()
+        .append_pair("url", base_url)
+        .append_pair("code", code)
+        .append_pair("scope", scope.as_str());
+    Ok(uri.into())
+}
+
+#[cfg(feature = "pairing-qr")]
+fn show_qr(uri: &str, terminal: bool, png: Option<&Path>) -> Result<()> {
+    use zeptoclaw::utils::qr::{render_qr_png, render_qr_terminal};
+
+    if terminal {
+        let art = render_qr_terminal(uri).context("Failed to encode QR code")?;
+        println!("Scan with the ZeptoClaw app:");
+        println!("{}", art);
+    }
+    if let Some(path) = png {
+        let bytes = render_qr_png(uri, 8).context("Failed to encode QR code")?;
+        std::fs::write(path, bytes)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("QR code written to {}", path.display());
+        println!();
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "pairing-qr"))]
+fn show_qr(uri: &str, _terminal: bool, _png: Option<&Path>) -> Result<()> {
+    println!("QR codes require the 'pairing-qr' feature (cargo build --features pairing-qr).");
+    println!("Pairing URI: {}", uri);
+    println!();
+    Ok(())
+}
+
 /// List all paired devices.
 async fn cmd_pair_list(config: &Config) -> Result<()> {
     let mgr = PairingManager::new(config.pairing.max_attempts, config.pairing.lockout_secs);
@@ -56,12 +125,18 @@ async fn cmd_pair_list(config: &Config) -> Result<()> {
         return Ok(());
     }
 
-    println!("{:<20} {:<24} {:<24}", "DEVICE", "PAIRED AT", "LAST SEEN");
-    println!("{}", "-".repeat(68));
+    println!(
+        "{:<20} {:<10} {:<24} {:<24}",
+        "DEVICE", "SCOPE", "PAIRED AT", "LAST SEEN"
+    );
+    println!("{}", "-".repeat(79));
     for device in &devices {
         let paired = format_timestamp(device.paired_at);
         let seen = format_timestamp(device.last_seen);
-        println!("{:<20} {:<24} {:<24}", device.name, paired, seen);
+        println!(
+            "{:<20} {:<10} {:<24} {:<24}",
+            device.name, device.scope, paired, seen
+        );
     }
     println!();
     println!("{} device(s) paired.", devices.len());
@@ -111,6 +186,26 @@ fn format_timestamp(ts: u64) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pairing_uri_encodes_url() {
+        let uri = pairing_uri(
+            "https://claw.example.com:8443",
+            "012345",
+            DeviceScope::ReadOnly,
+        )
+        .unwrap();
+        assert_eq!(
+            uri,
+            "zeptoclaw://pair?url=https%3A%2F%2Fclaw.example.com%3A8443&code=012345&scope=read_only"
+        );
+    }
+
+    #[test]
+    fn test_default_gateway_url_replaces_wildcard_host() {
+        let config = Config::default();
+        assert_eq!(default_gateway_url(&config), "http://localhost:8080");
+    }
+
     #[test]
     fn test_format_timestamp_zero() {
         assert_eq!(format_timestamp(0), "never");
@@ -73,11 +73,9 @@ fn resolve_panel_dir() -> Option<PathBuf> {
     if local.join("index.html").exists() {
         return Some(local);
     }
-    if let Some(home) = dirs::home_dir() {
-        let global = home.join(".zeptoclaw/panel/dist");
-        if global.join("index.html").exists() {
-            return Some(global);
-        }
+    let global = zeptoclaw::config::Config::data_dir().join("panel/dist");
+    if global.join("index.html").exists() {
+        return Some(global);
     }
     None
 }
@@ -92,20 +90,16 @@ fn resolve_panel_source_dir() -> Option<PathBuf> {
     if local.join("package.json").exists() {
         return Some(local);
     }
-    if let Some(home) = dirs::home_dir() {
-        let global = home.join(".zeptoclaw/panel");
-        if global.join("package.json").exists() {
-            return Some(global);
-        }
+    let global = zeptoclaw::config::Config::data_dir().join("panel");
+    if global.join("package.json").exists() {
+        return Some(global);
     }
     None
 }
 
 /// Get the token file path.
 fn token_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".zeptoclaw/panel.token")
+    zeptoclaw::config::Config::data_dir().join("panel.token")
 }
 
 /// Ensure API token exists, generating one if needed.
@@ -184,7 +178,7 @@ async fn cmd_start(
     let mut state = AppState::new(api_token.clone(), event_bus);
 
     // Wire in the TaskStore so kanban endpoints return real data.
-    let task_store_path = Config::dir().join("tasks.json");
+    let task_store_path = Config::data_dir().join("tasks.json");
     let task_store = std::sync::Arc::new(zeptoclaw::api::tasks::TaskStore::new(task_store_path));
     if let Err(e) = task_store.load().await {
         tracing::warn!("Failed to load task store: {e}");
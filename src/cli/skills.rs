@@ -1,8 +1,17 @@
 //! Skills management command handler.
 
 use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
 
 use zeptoclaw::config::Config;
+use zeptoclaw::skills::lockfile::{
+    checksum_dir, read_skill_version, LockSource, SemVer, SkillLockfile,
+};
+use zeptoclaw::skills::provision::{
+    binary_available, dockerfile as provision_dockerfile, plan_for_skill, verify_command,
+    PackageManager,
+};
+use zeptoclaw::skills::registry::{ClawHubRegistry, SearchCache};
 use zeptoclaw::skills::{EnvSpec, Skill, SkillsLoader};
 
 use super::common::skills_loader_from_config;
@@ -184,6 +193,28 @@ Describe usage and concrete command examples.
         SkillsAction::Install { name, github } => {
             cmd_skills_install(&name, github.as_deref()).await?;
         }
+        SkillsAction::Publish { path, out, upload } => {
+            cmd_skills_publish(&config, &path, out.as_deref(), upload).await?;
+        }
+        SkillsAction::Update {
+            name,
+            all,
+            allow_major,
+            dry_run,
+        } => {
+            cmd_skills_update(&config, name.as_deref(), all, allow_major, dry_run).await?;
+        }
+        SkillsAction::Lock => {
+            cmd_skills_lock()?;
+        }
+        SkillsAction::Provision {
+            name,
+            all,
+            apply,
+            docker,
+        } => {
+            cmd_skills_provision(&config, &loader, name.as_deref(), all, apply, docker)?;
+        }
     }
 
     Ok(())
@@ -261,7 +292,9 @@ fn validate_skill_name(name: &str) -> Result<()> {
 async fn cmd_skills_install(name: &str, github: Option<&str>) -> Result<()> {
     validate_skill_name(name)?;
 
-    let skills_dir = zeptoclaw::config::Config::dir().join("skills");
+    let skills_dir = zeptoclaw::config::Config::load()
+        .unwrap_or_default()
+        .skills_dir();
     std::fs::create_dir_all(&skills_dir)?;
     let target_dir = skills_dir.join(name);
 
@@ -299,6 +332,450 @@ async fn cmd_skills_install(name: &str, github: Option<&str>) -> Result<()> {
         install_from_multi_skill_repo(COMMUNITY_REPO, name, &target_dir).await?;
     }
 
+    let source = match github.map(normalize_github_repo) {
+        Some(repo) => {
+            let segments: Vec<&str> = repo.split('/').collect();
+            LockSource::Github {
+                repo: format!("{}/{}", segments[0], segments[1]),
+                path: (segments.len() > 2).then(|| segments[2..].join("/")),
+            }
+        }
+        None => LockSource::Github {
+            repo: COMMUNITY_REPO.to_string(),
+            path: Some(name.to_string()),
+        },
+    };
+    let mut lock = SkillLockfile::load(&skills_dir)?;
+    lock.record(name, &target_dir, source)?;
+    lock.save()?;
+
+    Ok(())
+}
+
+/// Build a ClawHub client from config.
+fn clawhub_registry(config: &Config) -> ClawHubRegistry {
+    let clawhub = &config.tools.skills.clawhub;
+    let cache = std::sync::Arc::new(SearchCache::new(
+        config.tools.skills.search_cache.max_size,
+        std::time::Duration::from_secs(config.tools.skills.search_cache.ttl_seconds),
+    ));
+    ClawHubRegistry::with_allowed_hosts(
+        clawhub.base_url.clone(),
+        clawhub.auth_token.clone(),
+        cache,
+        clawhub.allowed_hosts.clone(),
+    )
+}
+
+async fn cmd_skills_publish(
+    config: &Config,
+    path: &std::path::Path,
+    out: Option<&std::path::Path>,
+    upload: bool,
+) -> Result<()> {
+    let out_dir = out
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or(std::env::current_dir()?);
+    let packaged = zeptoclaw::skills::package::package_skill(path, &out_dir)
+        .with_context(|| format!("Failed to package skill at {}", path.display()))?;
+
+    println!(
+        "Packaged {} v{} ({} files)",
+        packaged.manifest.name,
+        packaged.manifest.version,
+        packaged.manifest.files.len()
+    );
+    println!("  archive:  {}", packaged.archive_path.display());
+    println!("  sha256:   {}", packaged.archive_sha256);
+    println!("  checksum: {}", packaged.manifest.checksum);
+
+    if upload {
+        let archive = std::fs::read(&packaged.archive_path)?;
+        clawhub_registry(config)
+            .publish(
+                &packaged.manifest.name,
+                &packaged.manifest.version,
+                &packaged.archive_sha256,
+                archive,
+            )
+            .await?;
+        println!(
+            "Published {} v{} to {}",
+            packaged.manifest.name, packaged.manifest.version, config.tools.skills.clawhub.base_url
+        );
+    }
+    Ok(())
+}
+
+/// Whether `candidate` should replace `installed` under semver rules.
+///
+/// Falls back to checksum comparison when either side has no valid version.
+fn is_update_available(
+    installed: Option<&str>,
+    candidate: Option<&str>,
+    installed_checksum: &str,
+    candidate_checksum: Option<&str>,
+    allow_major: bool,
+) -> bool {
+    match (
+        installed.and_then(SemVer::parse),
+        candidate.and_then(SemVer::parse),
+    ) {
+        (Some(cur), Some(new)) => cur.is_upgrade_to(&new, allow_major),
+        _ => candidate_checksum.is_some_and(|c| c != installed_checksum),
+    }
+}
+
+async fn cmd_skills_update(
+    config: &Config,
+    name: Option<&str>,
+    all: bool,
+    allow_major: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let skills_dir = Config::load().unwrap_or_default().skills_dir();
+    let mut lock = SkillLockfile::load(&skills_dir)?;
+
+    let targets: Vec<String> = match (name, all) {
+        (Some(name), _) => {
+            if lock.get(name).is_none() {
+                anyhow::bail!(
+                    "Skill '{}' is not in {}. Reinstall it to start tracking versions.",
+                    name,
+                    lock.path().display()
+                );
+            }
+            vec![name.to_string()]
+        }
+        (None, true) => lock.skills.keys().cloned().collect(),
+        (None, false) => anyhow::bail!("Specify a skill name or --all"),
+    };
+
+    if targets.is_empty() {
+        println!("No locked skills to update.");
+        return Ok(());
+    }
+
+    let registry = clawhub_registry(config);
+    let mut updated = 0usize;
+    for skill in targets {
+        let Some(entry) = lock.get(&skill).cloned() else {
+            continue;
+        };
+        let target_dir = skills_dir.join(&skill);
+        let current = entry.version.as_deref().unwrap_or("unversioned");
+
+        match &entry.source {
+            LockSource::Local => {
+                println!("  {} ({}): local skill, skipped", skill, current);
+            }
+            LockSource::Github { repo, path } => {
+                let staging = tempfile::tempdir()?;
+                let fetched = staging.path().join("skill");
+                if let Err(e) = fetch_github_skill(repo, path.as_deref(), &fetched).await {
+                    eprintln!("  {} ({}): fetch failed: {}", skill, current, e);
+                    continue;
+                }
+                let new_version = read_skill_version(&fetched);
+                let new_checksum = checksum_dir(&fetched)?;
+                if !is_update_available(
+                    entry.version.as_deref(),
+                    new_version.as_deref(),
+                    &entry.checksum,
+                    Some(&new_checksum),
+                    allow_major,
+                ) {
+                    println!("  {} ({}): up to date", skill, current);
+                    continue;
+                }
+                let next = new_version.as_deref().unwrap_or("unversioned");
+                if dry_run {
+                    println!("  {} {} -> {} (available)", skill, current, next);
+                    continue;
+                }
+                if target_dir.exists() {
+                    std::fs::remove_dir_all(&target_dir)?;
+                }
+                copy_dir_recursive(&fetched, &target_dir)?;
+                lock.record(&skill, &target_dir, entry.source.clone())?;
+                updated += 1;
+                println!("  {} {} -> {}", skill, current, next);
+            }
+            LockSource::Clawhub { slug } => {
+                let latest = match registry.latest_version(slug).await {
+                    Ok(v) => v,
+                    Err(e) => {
+                        eprintln!("  {} ({}): registry lookup failed: {}", skill, current, e);
+                        continue;
+                    }
+                };
+                if !is_update_available(
+                    entry.version.as_deref(),
+                    latest.as_deref(),
+                    &entry.checksum,
+                    None,
+                    allow_major,
+                ) {
+                    println!("  {} ({}): up to date", skill, current);
+                    continue;
+                }
+                let next = latest.as_deref().unwrap_or("unversioned");
+                if dry_run {
+                    println!("  {} {} -> {} (available)", skill, current, next);
+                    continue;
+                }
+                let backup = skills_dir.join(format!(".{}.bak", skill));
+                if target_dir.exists() {
+                    std::fs::rename(&target_dir, &backup)?;
+                }
+                match registry
+                    .download_and_install(slug, &skills_dir.to_string_lossy())
+                    .await
+                {
+                    Ok(path) => {
+                        let _ = std::fs::remove_dir_all(&backup);
+                        lock.record(&skill, std::path::Path::new(&path), entry.source.clone())?;
+                        updated += 1;
+                        println!("  {} {} -> {}", skill, current, next);
+                    }
+                    Err(e) => {
+                        let _ = std::fs::remove_dir_all(&target_dir);
+                        if backup.exists() {
+                            std::fs::rename(&backup, &target_dir)?;
+                        }
+                        eprintln!("  {} ({}): update failed: {}", skill, current, e);
+                    }
+                }
+            }
+        }
+    }
+
+    if updated > 0 {
+        lock.save()?;
+        println!(
+            "Updated {} skill(s). Lockfile: {}",
+            updated,
+            lock.path().display()
+        );
+    }
+    Ok(())
+}
+
+fn cmd_skills_lock() -> Result<()> {
+    let skills_dir = Config::load().unwrap_or_default().skills_dir();
+    let lock = SkillLockfile::load(&skills_dir)?;
+    if lock.skills.is_empty() {
+        println!("No locked skills ({}).", lock.path().display());
+        return Ok(());
+    }
+
+    println!("Locked skills ({}):", lock.path().display());
+    for (name, entry) in &lock.skills {
+        let dir = skills_dir.join(name);
+        let status = if !dir.is_dir() {
+            "missing"
+        } else if checksum_dir(&dir)
+            .map(|c| c == entry.checksum)
+            .unwrap_or(false)
+        {
+            "ok"
+        } else {
+            "modified"
+        };
+        println!(
+            "  {} {} [{}] {}",
+            name,
+            entry.version.as_deref().unwrap_or("unversioned"),
+            entry.source,
+            status
+        );
+    }
+    Ok(())
+}
+
+/// Whether `bin` is on `PATH` inside `image`.
+fn docker_image_has_bin(image: &str, bin: &str) -> bool {
+    std::process::Command::new("docker")
+        .args(["run", "--rm", "--entrypoint", "sh", image, "-c"])
+        .arg(verify_command(&[bin]))
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+fn cmd_skills_provision(
+    config: &Config,
+    loader: &SkillsLoader,
+    name: Option<&str>,
+    all: bool,
+    apply: bool,
+    docker: bool,
+) -> Result<()> {
+    let names: Vec<String> = match (name, all) {
+        (Some(name), false) => vec![name.to_string()],
+        (None, true) => loader
+            .list_skills(false)
+            .into_iter()
+            .map(|info| info.name)
+            .collect(),
+        _ => anyhow::bail!("Specify a skill name or --all"),
+    };
+
+    let image = config.runtime.docker.image.clone();
+    let manager = if docker {
+        PackageManager::for_docker_image(&image)
+    } else {
+        PackageManager::detect_host().ok_or_else(|| {
+            anyhow::anyhow!("No supported package manager found (apk, apt-get, brew)")
+        })?
+    };
+    let bin_present = |bin: &str| {
+        if docker {
+            docker_image_has_bin(&image, bin)
+        } else {
+            binary_available(bin)
+        }
+    };
+
+    let mut plans = Vec::new();
+    for skill_name in &names {
+        let skill = loader
+            .load_skill(skill_name)
+            .ok_or_else(|| anyhow::anyhow!("Skill '{}' not found", skill_name))?;
+        let meta = loader.zepto_metadata(&skill);
+        let plan = plan_for_skill(&skill.name, &meta, manager, !docker, bin_present);
+        if plan.is_satisfied() {
+            if name.is_some() {
+                println!("{}: all requirements satisfied", skill.name);
+            }
+            continue;
+        }
+        plans.push(plan);
+    }
+
+    if plans.is_empty() {
+        if all {
+            println!("All skill requirements satisfied.");
+        }
+        return Ok(());
+    }
+
+    let target = if docker {
+        format!("docker image {} ({})", image, manager)
+    } else {
+        format!("host ({})", manager)
+    };
+    println!("Provisioning plan for {}:", target);
+    for plan in &plans {
+        println!("  {}:", plan.skill);
+        for step in &plan.steps {
+            let note = if step.guessed {
+                "  # guessed package"
+            } else {
+                ""
+            };
+            println!("    {}{}", step.command(), note);
+        }
+        for bin in &plan.unresolved {
+            println!("    # no install option for '{}' on this target", bin);
+        }
+        for env in &plan.missing_env {
+            println!("    # set environment variable {}", env);
+        }
+    }
+
+    if !apply {
+        println!();
+        println!("Dry run. Re-run with --apply to install.");
+        return Ok(());
+    }
+
+    let bins: Vec<&str> = plans.iter().flat_map(|p| p.bins()).collect();
+    if docker {
+        let dockerfile = provision_dockerfile(&image, &plans);
+        let digest = hex::encode(Sha256::digest(dockerfile.as_bytes()));
+        let tag = format!("zeptoclaw-skills:{}", &digest[..12]);
+        let build_dir = tempfile::tempdir()?;
+        std::fs::write(build_dir.path().join("Dockerfile"), &dockerfile)?;
+        println!("Building {}...", tag);
+        let status = std::process::Command::new("docker")
+            .args(["build", "-t", &tag])
+            .arg(build_dir.path())
+            .status()
+            .with_context(|| "Failed to run docker build")?;
+        if !status.success() {
+            anyhow::bail!("docker build failed for {}", tag);
+        }
+        let missing: Vec<&str> = bins
+            .iter()
+            .copied()
+            .filter(|b| !docker_image_has_bin(&tag, b))
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!("Image {} is still missing: {}", tag, missing.join(", "));
+        }
+        println!("Built and verified {}.", tag);
+        println!(
+            "Set runtime.docker.image = \"{}\" in config.json to use it.",
+            tag
+        );
+    } else {
+        for step in plans.iter().flat_map(|p| p.steps.iter()) {
+            let command = step.command();
+            println!("$ {}", command);
+            let status = std::process::Command::new("sh")
+                .args(["-c", &command])
+                .status()
+                .with_context(|| format!("Failed to run '{}'", command))?;
+            if !status.success() {
+                eprintln!("  failed ({})", status);
+            }
+        }
+        let missing: Vec<&str> = bins
+            .iter()
+            .copied()
+            .filter(|b| !binary_available(b))
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!("Still missing after install: {}", missing.join(", "));
+        }
+        println!("Installed and verified {} binary(ies).", bins.len());
+    }
+    Ok(())
+}
+
+/// Fetch a skill from GitHub into `dest` (root SKILL.md repo or subdirectory).
+async fn fetch_github_skill(repo: &str, path: Option<&str>, dest: &std::path::Path) -> Result<()> {
+    let checkout = tempfile::tempdir()?;
+    let output = tokio::process::Command::new("git")
+        .args([
+            "clone",
+            "--depth",
+            "1",
+            &format!("https://github.com/{}.git", repo),
+        ])
+        .arg(checkout.path().join("repo"))
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .output()
+        .await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git clone failed for {}: {}", repo, stderr.trim());
+    }
+
+    let repo_dir = checkout.path().join("repo");
+    let skill_src = match path {
+        Some(p) => repo_dir.join(p),
+        None => repo_dir,
+    };
+    if !skill_src.join("SKILL.md").is_file() {
+        anyhow::bail!("No SKILL.md found in github.com/{}", repo);
+    }
+    copy_dir_recursive(&skill_src, dest)?;
+    let _ = std::fs::remove_dir_all(dest.join(".git"));
     Ok(())
 }
 
@@ -646,4 +1123,43 @@ Describe usage and concrete command examples.
         let segments: Vec<&str> = COMMUNITY_REPO.split('/').collect();
         assert_eq!(segments.len(), 2);
     }
+
+    #[test]
+    fn test_is_update_available_semver() {
+        assert!(is_update_available(
+            Some("1.0.0"),
+            Some("1.1.0"),
+            "a",
+            None,
+            false
+        ));
+        assert!(!is_update_available(
+            Some("1.1.0"),
+            Some("1.0.0"),
+            "a",
+            None,
+            false
+        ));
+        assert!(!is_update_available(
+            Some("1.0.0"),
+            Some("2.0.0"),
+            "a",
+            None,
+            false
+        ));
+        assert!(is_update_available(
+            Some("1.0.0"),
+            Some("2.0.0"),
+            "a",
+            None,
+            true
+        ));
+    }
+
+    #[test]
+    fn test_is_update_available_checksum_fallback() {
+        assert!(is_update_available(None, None, "a", Some("b"), false));
+        assert!(!is_update_available(None, None, "a", Some("a"), false));
+        assert!(!is_update_available(Some("1.0.0"), None, "a", None, false));
+    }
 }
@@ -70,6 +70,26 @@ pub fn builtin_commands() -> Vec<SlashCommand> {
             name: "trust off",
             description: "Disable trusted-session bypass",
         },
+        SlashCommand {
+            name: "link",
+            description: "Continue this conversation on another channel",
+        },
+        SlashCommand {
+            name: "unlink",
+            description: "Detach from a linked conversation",
+        },
+        SlashCommand {
+            name: "plan",
+            description: "Show the pending plan, or /plan <task> to plan without executing",
+        },
+        SlashCommand {
+            name: "plan approve",
+            description: "Run the pending plan",
+        },
+        SlashCommand {
+            name: "plan reject",
+            description: "Discard the pending plan",
+        },
         SlashCommand {
             name: "clear",
             description: "Clear conversation context",
@@ -11,6 +11,7 @@ use zeptoclaw::providers::{
     configured_unsupported_provider_names, resolve_runtime_provider, RUNTIME_SUPPORTED_PROVIDERS,
 };
 use zeptoclaw::runtime::available_runtimes;
+use zeptoclaw::utils::locale::{self, Locale, Text};
 
 use super::common::{memory_backend_label, memory_citations_label, skills_loader_from_config};
 use super::heartbeat::heartbeat_file_path;
@@ -178,7 +179,7 @@ fn cmd_auth_logout(provider: Option<String>) -> Result<()> {
         Ok(enc) => enc,
         Err(_) => {
             println!("No encryption key available. If you have stored tokens,");
-            let path = Config::dir().join("auth").join("tokens.json.enc");
+            let path = Config::data_dir().join("auth").join("tokens.json.enc");
             #[cfg(windows)]
             println!("delete them manually: del {}", path.display());
             #[cfg(not(windows))]
@@ -493,12 +494,24 @@ fn provider_status(provider: &Option<ProviderConfig>) -> &'static str {
         .unwrap_or("not set")
 }
 
+/// Print a status section heading, translated for the configured language.
+fn print_heading(locale: Option<&Locale>, text: Text, underline: char) {
+    let title = locale::text(locale, text);
+    println!("{}", title);
+    println!("{}", underline.to_string().repeat(title.chars().count()));
+}
+
 /// Show system status.
 pub(crate) async fn cmd_status() -> Result<()> {
     let config = Config::load().unwrap_or_default();
-
-    println!("ZeptoClaw Status");
-    println!("================");
+    let locale = config
+        .agents
+        .defaults
+        .language
+        .as_deref()
+        .and_then(Locale::parse);
+
+    print_heading(locale.as_ref(), Text::StatusTitle, '=');
     println!();
 
     // Version
@@ -506,25 +519,33 @@ pub(crate) async fn cmd_status() -> Result<()> {
     println!();
 
     // Configuration
-    println!("Configuration");
-    println!("-------------");
+    print_heading(locale.as_ref(), Text::StatusConfiguration, '-');
     println!("  Config directory: {:?}", Config::dir());
+    if Config::data_dir() != Config::dir() || Config::state_dir() != Config::dir() {
+        println!("  Data directory:   {:?}", Config::data_dir());
+        println!("  State directory:  {:?}", Config::state_dir());
+    }
     println!("  Config file:      {:?}", Config::path());
     println!("  Config exists:    {}", Config::path().exists());
+    if let Some(profile) = Config::active_profile() {
+        println!(
+            "  Profile:          {} ({:?})",
+            profile,
+            Config::profile_path(&profile)
+        );
+    }
     println!();
 
     // Workspace
-    println!("Workspace");
-    println!("---------");
+    print_heading(locale.as_ref(), Text::StatusWorkspace, '-');
     let workspace_path = config.workspace_path();
     println!("  Path:   {:?}", workspace_path);
     println!("  Exists: {}", workspace_path.exists());
     println!();
 
     // Sessions
-    println!("Sessions");
-    println!("--------");
-    let sessions_path = Config::dir().join("sessions");
+    print_heading(locale.as_ref(), Text::StatusSessions, '-');
+    let sessions_path = Config::data_dir().join("sessions");
     println!("  Path:   {:?}", sessions_path);
     println!("  Exists: {}", sessions_path.exists());
     if sessions_path.exists() {
@@ -536,8 +557,7 @@ pub(crate) async fn cmd_status() -> Result<()> {
     println!();
 
     // Agent defaults
-    println!("Agent Defaults");
-    println!("--------------");
+    print_heading(locale.as_ref(), Text::StatusAgentDefaults, '-');
     println!("  Model:              {}", config.agents.defaults.model);
     println!(
         "  Max tokens:         {}",
@@ -551,18 +571,45 @@ pub(crate) async fn cmd_status() -> Result<()> {
         "  Max tool iterations: {}",
         config.agents.defaults.max_tool_iterations
     );
+    println!(
+        "  Language:           {}",
+        locale.as_ref().map_or("en (default)", |l| l.tag())
+    );
     println!();
 
     // Gateway
-    println!("Gateway");
-    println!("-------");
+    print_heading(locale.as_ref(), Text::StatusGateway, '-');
     println!("  Host: {}", config.gateway.host);
     println!("  Port: {}", config.gateway.port);
     println!();
 
+    // Tunnel
+    print_heading(locale.as_ref(), Text::StatusTunnel, '-');
+    match zeptoclaw::tunnel::read_tunnel_state() {
+        Some(state) => {
+            let health = if state.healthy {
+                "healthy"
+            } else {
+                "unhealthy"
+            };
+            println!("  URL:        {}", state.url);
+            println!("  Provider:   {} ({})", state.provider, health);
+            println!("  Local port: {}", state.local_port);
+            println!("  Started at: {}", state.started_at);
+            if let Some(ref checked) = state.last_checked_at {
+                println!("  Checked at: {}", checked);
+            }
+            println!("  Restarts:   {}", state.restarts);
+        }
+        None => {
+            let provider = config.tunnel.provider.as_deref().unwrap_or("none");
+            println!("  Not running (configured provider: {})", provider);
+        }
+    }
+    println!();
+
     // Daemon
-    println!("Daemon");
-    println!("------");
+    print_heading(locale.as_ref(), Text::StatusDaemon, '-');
     match super::daemon::read_state() {
         Some(state) => {
             println!("  Status:     {}", state.status);
@@ -586,8 +633,7 @@ pub(crate) async fn cmd_status() -> Result<()> {
     println!();
 
     // Container Agent
-    println!("Container Agent");
-    println!("---------------");
+    print_heading(locale.as_ref(), Text::StatusContainerAgent, '-');
     let backend_label = match config.container_agent.backend {
         ContainerAgentBackend::Auto => "auto",
         ContainerAgentBackend::Docker => "docker",
@@ -609,8 +655,7 @@ pub(crate) async fn cmd_status() -> Result<()> {
     println!();
 
     // Runtime info
-    println!("Runtime");
-    println!("-------");
+    print_heading(locale.as_ref(), Text::StatusRuntime, '-');
     println!("  Type: {:?}", config.runtime.runtime_type);
     println!(
         "  Native fallback: {}",
@@ -625,8 +670,7 @@ pub(crate) async fn cmd_status() -> Result<()> {
     println!();
 
     // Memory
-    println!("Memory");
-    println!("------");
+    print_heading(locale.as_ref(), Text::StatusMemory, '-');
     println!(
         "  Backend: {}",
         memory_backend_label(&config.memory.backend)
@@ -647,7 +691,7 @@ pub(crate) async fn cmd_status() -> Result<()> {
     println!("  Min score: {}", config.memory.min_score);
 
     // Long-term memory stats
-    let ltm_path = Config::dir().join("memory").join("longterm.json");
+    let ltm_path = Config::data_dir().join("memory").join("longterm.json");
     if ltm_path.exists() {
         match zeptoclaw::memory::longterm::LongTermMemory::new() {
             Ok(mem) => {
@@ -668,8 +712,7 @@ pub(crate) async fn cmd_status() -> Result<()> {
     println!();
 
     // Heartbeat
-    println!("Heartbeat");
-    println!("---------");
+    print_heading(locale.as_ref(), Text::StatusHeartbeat, '-');
     println!(
         "  Enabled: {}",
         if config.heartbeat.enabled {
@@ -683,8 +726,7 @@ pub(crate) async fn cmd_status() -> Result<()> {
     println!();
 
     // Skills
-    println!("Skills");
-    println!("------");
+    print_heading(locale.as_ref(), Text::StatusSkills, '-');
     println!(
         "  Enabled: {}",
         if config.skills.enabled { "yes" } else { "no" }
@@ -718,8 +760,7 @@ pub(crate) async fn cmd_status() -> Result<()> {
     println!();
 
     // Available tools (dynamic)
-    println!("Available Tools");
-    println!("---------------");
+    print_heading(locale.as_ref(), Text::StatusTools, '-');
     super::tools::print_tools_summary(&config);
     println!();
 
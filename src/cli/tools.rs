@@ -1,7 +1,8 @@
-//! Tools CLI command handlers — tool discovery and info.
+//! Tools CLI command handlers — tool discovery, info, and usage stats.
 
-use anyhow::Result;
-use zeptoclaw::config::Config;
+use anyhow::{Context, Result};
+use zeptoclaw::config::{CalendarProvider, Config, EmailAuthMethod};
+use zeptoclaw::health::{load_tool_usage_range, tool_usage_dir, ToolUsageStats};
 
 use super::ToolsAction;
 
@@ -50,6 +51,20 @@ const TOOLS: &[ToolInfo] = &[
         config_hint: "",
         opt_in: false,
     },
+    ToolInfo {
+        name: "apply_patch",
+        description: "Apply a multi-file unified diff (all hunks or nothing)",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "undo",
+        description: "Revert file changes from recent turns",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
     ToolInfo {
         name: "shell",
         description: "Execute shell commands (with runtime isolation)",
@@ -92,6 +107,20 @@ const TOOLS: &[ToolInfo] = &[
         config_hint: "",
         opt_in: false,
     },
+    ToolInfo {
+        name: "memory_set",
+        description: "Remember a fact with category and confidence (deduplicated, audited)",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "memory_append",
+        description: "Append a line to a memory entry (deduplicated, audited)",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
     ToolInfo {
         name: "message",
         description: "Send proactive messages to channels",
@@ -113,6 +142,13 @@ const TOOLS: &[ToolInfo] = &[
         config_hint: "",
         opt_in: false,
     },
+    ToolInfo {
+        name: "session_link",
+        description: "Continue a conversation on another channel",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
     ToolInfo {
         name: "delegate",
         description: "Delegate to specialized sub-agents",
@@ -134,6 +170,13 @@ const TOOLS: &[ToolInfo] = &[
         config_hint: "Set tools.google_sheets.access_token",
         opt_in: false,
     },
+    ToolInfo {
+        name: "google_drive",
+        description: "Google Drive files and Docs (list, search, export, download, upload)",
+        requires_config: true,
+        config_hint: "Set tools.google_drive.service_account_base64 (or reuse tools.google_sheets)",
+        opt_in: false,
+    },
     ToolInfo {
         name: "google",
         description: "Gmail + Calendar (search, read, send, schedule)",
@@ -141,6 +184,20 @@ const TOOLS: &[ToolInfo] = &[
         config_hint: "Run `zeptoclaw auth login google` or set tools.google.access_token",
         opt_in: false,
     },
+    ToolInfo {
+        name: "calendar",
+        description: "Calendar events + free/busy (Google Calendar or CalDAV)",
+        requires_config: true,
+        config_hint: "Run `zeptoclaw auth login google`, or set tools.calendar.provider = \"caldav\" + caldav_url",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "email",
+        description: "Mailbox search/read + drafted replies sent after user confirmation",
+        requires_config: true,
+        config_hint: "Set tools.email.imap_host, smtp_host, username + password (or auth = \"oauth\")",
+        opt_in: false,
+    },
     ToolInfo {
         name: "r8r",
         description: "Execute R8r deterministic workflows",
@@ -148,6 +205,41 @@ const TOOLS: &[ToolInfo] = &[
         config_hint: "Set R8R_API_URL env var",
         opt_in: false,
     },
+    ToolInfo {
+        name: "rank",
+        description: "Score and rank item lists by weighted criteria, with trend history",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "time",
+        description: "Current time in any timezone, timezone conversion",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "weather",
+        description: "Current weather and forecast (Open-Meteo, no key)",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "news",
+        description: "Top headlines and news search (Google News, NewsAPI, GNews)",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "calc",
+        description: "Calculator, unit and currency conversion",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
     ToolInfo {
         name: "reminder",
         description: "Persistent reminders (add/complete/snooze/overdue)",
@@ -155,6 +247,76 @@ const TOOLS: &[ToolInfo] = &[
         config_hint: "",
         opt_in: false,
     },
+    ToolInfo {
+        name: "contacts",
+        description: "Address book with name/alias resolution, vCard import/export",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "rss",
+        description: "RSS/Atom subscriptions with deduped, scheduled digests",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "spreadsheet",
+        description: "Read, query and write local CSV/TSV/XLSX files",
+        requires_config: false,
+        config_hint: "",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "sql",
+        description: "Read-only SQL queries and schema (Postgres/MySQL/SQLite)",
+        requires_config: true,
+        config_hint: "Add tools.sql.connections (build with --features tool-sql)",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "sql_execute",
+        description: "Approval-gated SQL writes on connections with allow_writes",
+        requires_config: true,
+        config_hint: "Set allow_writes on a tools.sql.connections entry",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "github",
+        description: "GitHub issues, PR diffs, review threads and notifications",
+        requires_config: true,
+        config_hint: "Set tools.github.token (or tools.github.app)",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "github_write",
+        description: "Approval-gated GitHub issue creation and review replies",
+        requires_config: true,
+        config_hint: "Set tools.github.token (or tools.github.app)",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "issues",
+        description: "Jira/Linear issue search, updates, transitions and sprint summaries",
+        requires_config: true,
+        config_hint: "Set tools.issues.jira or tools.issues.linear",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "kubectl",
+        description: "Inspect Kubernetes and diagnose failing pods (read-only verbs)",
+        requires_config: true,
+        config_hint: "Set tools.kubernetes.enabled (kubectl must be on PATH in the runtime)",
+        opt_in: false,
+    },
+    ToolInfo {
+        name: "kubectl_write",
+        description: "Approval-gated Kubernetes apply/delete/scale/rollout",
+        requires_config: true,
+        config_hint: "Set tools.kubernetes.enabled and keep tools.kubernetes.write_verbs non-empty",
+        opt_in: false,
+    },
     ToolInfo {
         name: "grep",
         description: "Search file contents by regex pattern",
@@ -175,9 +337,91 @@ pub(crate) async fn cmd_tools(action: ToolsAction) -> Result<()> {
     match action {
         ToolsAction::List => cmd_tools_list().await,
         ToolsAction::Info { name } => cmd_tools_info(name).await,
+        ToolsAction::Stats { days, json } => cmd_tools_stats(days, json),
     }
 }
 
+fn cmd_tools_stats(days: u32, json: bool) -> Result<()> {
+    let dir = tool_usage_dir();
+    let today = chrono::Local::now().date_naive();
+    let usage = load_tool_usage_range(&dir, today, days)
+        .with_context(|| format!("Failed to read tool usage from {}", dir.display()))?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&usage)?);
+        return Ok(());
+    }
+
+    let config = Config::load().unwrap_or_default();
+    let rows = format_tool_stats(&usage);
+    if rows.is_empty() {
+        println!(
+            "No tool usage recorded in the last {} day(s) ({}).",
+            days,
+            dir.display()
+        );
+    } else {
+        println!("Tool usage, last {} day(s)", days);
+        println!("{}", "=".repeat(78));
+        for row in rows {
+            println!("{}", row);
+        }
+    }
+
+    let unused = unused_tools(&config, &usage);
+    if !unused.is_empty() {
+        println!();
+        println!("Never used ({}): {}", unused.len(), unused.join(", "));
+        println!("Consider removing them via `tools.deny` to shrink the tool schema.");
+    }
+    Ok(())
+}
+
+/// Format usage as table rows, most-called tools first.
+fn format_tool_stats(usage: &std::collections::HashMap<String, ToolUsageStats>) -> Vec<String> {
+    if usage.is_empty() {
+        return Vec::new();
+    }
+    let mut tools: Vec<_> = usage.iter().collect();
+    tools.sort_by(|a, b| b.1.calls.cmp(&a.1.calls).then_with(|| a.0.cmp(b.0)));
+
+    let ms = |v: Option<u64>| v.map(|v| format!("{}ms", v)).unwrap_or_else(|| "-".into());
+    let mut rows = vec![format!(
+        "{:<20} {:>7} {:>7} {:>8} {:>8} {:>8} {:>9} {:>9}",
+        "TOOL", "CALLS", "ERR%", "P50", "P95", "P99", "AVG ARGS", "MAX ARGS"
+    )];
+    for (name, stats) in tools {
+        rows.push(format!(
+            "{:<20} {:>7} {:>6.1}% {:>8} {:>8} {:>8} {:>8}B {:>8}B",
+            name,
+            stats.calls,
+            stats.error_rate() * 100.0,
+            ms(stats.latency_percentile(50.0)),
+            ms(stats.latency_percentile(95.0)),
+            ms(stats.latency_percentile(99.0)),
+            stats.avg_arg_bytes(),
+            stats.max_arg_bytes,
+        ));
+    }
+    rows
+}
+
+/// Available tools with no recorded calls.
+fn unused_tools(
+    config: &Config,
+    usage: &std::collections::HashMap<String, ToolUsageStats>,
+) -> Vec<&'static str> {
+    let coding_on = is_coding_tools_on(config);
+    TOOLS
+        .iter()
+        .filter(|t| !t.opt_in || coding_on)
+        .filter(|t| !t.requires_config || is_tool_configured(config, t.name))
+        .filter(|t| !config.tools.deny.iter().any(|d| d == t.name))
+        .filter(|t| usage.get(t.name).is_none_or(|s| s.calls == 0))
+        .map(|t| t.name)
+        .collect()
+}
+
 async fn cmd_tools_list() -> Result<()> {
     let config = Config::load().unwrap_or_default();
     let coding_on = is_coding_tools_on(&config);
@@ -301,6 +545,48 @@ fn is_tool_configured(config: &Config, name: &str) -> bool {
                     .as_ref()
                     .is_some_and(|v| !v.trim().is_empty())
         }
+        "google_drive" => [
+            &config.tools.google_drive.access_token,
+            &config.tools.google_drive.service_account_base64,
+            &config.tools.google_sheets.access_token,
+            &config.tools.google_sheets.service_account_base64,
+        ]
+        .iter()
+        .any(|v| v.as_ref().is_some_and(|v| !v.trim().is_empty())),
+        "calendar" => match config.tools.calendar.provider {
+            CalendarProvider::Caldav => config
+                .tools
+                .calendar
+                .caldav_url
+                .as_ref()
+                .is_some_and(|v| !v.trim().is_empty()),
+            CalendarProvider::Google => {
+                config
+                    .tools
+                    .google
+                    .access_token
+                    .as_ref()
+                    .is_some_and(|v| !v.trim().is_empty())
+                    || config
+                        .tools
+                        .google
+                        .client_id
+                        .as_ref()
+                        .is_some_and(|v| !v.trim().is_empty())
+            }
+        },
+        "email" => {
+            let email = &config.tools.email;
+            !email.imap_host.trim().is_empty()
+                && !email.username.trim().is_empty()
+                && match email.auth {
+                    EmailAuthMethod::Password => {
+                        email.password.as_ref().is_some_and(|v| !v.is_empty())
+                    }
+                    // OAuth tokens may live in the encrypted auth store.
+                    EmailAuthMethod::Oauth => true,
+                }
+        }
         "google" => {
             config
                 .tools
@@ -320,6 +606,19 @@ fn is_tool_configured(config: &Config, name: &str) -> bool {
                 || config.channels.slack.as_ref().is_some_and(|c| c.enabled)
                 || config.channels.discord.as_ref().is_some_and(|c| c.enabled)
         }
+        "sql" => !config.tools.sql.connections.is_empty(),
+        "sql_execute" => config
+            .tools
+            .sql
+            .connections
+            .values()
+            .any(|c| c.allow_writes),
+        "github" | "github_write" => config.tools.github.has_credentials(),
+        "issues" => config.tools.issues.is_configured(),
+        "kubectl" => config.tools.kubernetes.enabled,
+        "kubectl_write" => {
+            config.tools.kubernetes.enabled && !config.tools.kubernetes.write_verbs.is_empty()
+        }
         "r8r" => std::env::var("R8R_API_URL").is_ok(),
         _ => true,
     }
@@ -362,7 +661,7 @@ mod tests {
 
     #[test]
     fn test_tools_list_count() {
-        assert_eq!(TOOLS.len(), 22);
+        assert_eq!(TOOLS.len(), 45);
     }
 
     #[test]
@@ -374,6 +673,40 @@ mod tests {
         assert_eq!(names.len(), original_len, "Duplicate tool names found");
     }
 
+    #[test]
+    fn test_format_tool_stats_sorted_by_calls() {
+        let mut usage = std::collections::HashMap::new();
+        let mut shell = ToolUsageStats::default();
+        shell.record(5, true, 10);
+        let mut echo = ToolUsageStats::default();
+        echo.record(1, true, 10);
+        echo.record(2, false, 10);
+        usage.insert("shell".to_string(), shell);
+        usage.insert("echo".to_string(), echo);
+
+        let rows = format_tool_stats(&usage);
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].starts_with("TOOL"));
+        assert!(rows[1].starts_with("echo"));
+        assert!(rows[1].contains("50.0%"));
+        assert!(format_tool_stats(&Default::default()).is_empty());
+    }
+
+    #[test]
+    fn test_unused_tools_excludes_called_and_denied() {
+        let mut config = Config::default();
+        config.tools.deny = vec!["shell".to_string()];
+        let mut usage = std::collections::HashMap::new();
+        let mut stats = ToolUsageStats::default();
+        stats.record(1, true, 1);
+        usage.insert("echo".to_string(), stats);
+
+        let unused = unused_tools(&config, &usage);
+        assert!(!unused.contains(&"echo"));
+        assert!(!unused.contains(&"shell"));
+        assert!(unused.contains(&"read_file"));
+    }
+
     #[test]
     fn test_is_tool_configured_default_tools() {
         let config = Config::default();
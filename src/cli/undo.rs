@@ -0,0 +1,38 @@
+//! Undo command handler.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use zeptoclaw::config::Config;
+use zeptoclaw::tools::undo::{format_turns, format_undone, UndoJournal};
+
+/// Revert (or list) file changes the agent made in recent turns.
+pub(crate) async fn cmd_undo(turns: usize, list: bool, workspace: Option<PathBuf>) -> Result<()> {
+    let workspace = match workspace {
+        Some(path) => path,
+        None => {
+            let config = Config::load().with_context(|| "Failed to load configuration")?;
+            // Same default as `zeptoclaw agent`: the CLI works in the CWD
+            // unless a workspace is configured.
+            if config.agents.defaults.workspace == "~/.zeptoclaw/workspace" {
+                std::env::current_dir().with_context(|| "Failed to read current directory")?
+            } else {
+                config.workspace_path()
+            }
+        }
+    };
+
+    let journal = UndoJournal::new(&workspace);
+    if list {
+        println!("{}", format_turns(&journal.turns()));
+        return Ok(());
+    }
+
+    let undone = journal
+        .undo(turns.max(1), None, None)
+        .await
+        .with_context(|| format!("Failed to undo changes in {}", workspace.display()))?;
+    println!("{}", format_undone(&undone));
+    Ok(())
+}
@@ -17,15 +17,16 @@ enum BinaryRemovalPlan {
 }
 
 pub(crate) async fn cmd_uninstall(remove_binary: bool, yes: bool) -> Result<()> {
-    let state_dir = Config::dir();
-    let state_exists = state_dir.exists();
+    let mut state_dirs = vec![Config::dir(), Config::data_dir(), Config::state_dir()];
+    state_dirs.dedup();
+    let state_exists = state_dirs.iter().any(|dir| dir.exists());
     let binary_plan = if remove_binary {
         current_binary_removal_plan()?
     } else {
         BinaryRemovalPlan::Keep
     };
 
-    print_uninstall_plan(&state_dir, state_exists, &binary_plan, remove_binary);
+    print_uninstall_plan(&state_dirs, &binary_plan, remove_binary);
 
     let will_remove_binary = matches!(&binary_plan, BinaryRemovalPlan::Remove(_));
     let has_destructive_action = state_exists || will_remove_binary;
@@ -52,13 +53,17 @@ pub(crate) async fn cmd_uninstall(remove_binary: bool, yes: bool) -> Result<()>
         return Ok(());
     }
 
-    if state_exists {
-        tokio::fs::remove_dir_all(&state_dir)
-            .await
-            .with_context(|| format!("failed to remove state directory {}", state_dir.display()))?;
-        println!("Removed state directory: {}", state_dir.display());
-    } else {
-        println!("State directory not found: {}", state_dir.display());
+    for state_dir in &state_dirs {
+        if state_dir.exists() {
+            tokio::fs::remove_dir_all(state_dir)
+                .await
+                .with_context(|| {
+                    format!("failed to remove state directory {}", state_dir.display())
+                })?;
+            println!("Removed state directory: {}", state_dir.display());
+        } else {
+            println!("State directory not found: {}", state_dir.display());
+        }
     }
 
     match binary_plan {
@@ -100,17 +105,18 @@ fn confirm_uninstall() -> Result<bool> {
 }
 
 fn print_uninstall_plan(
-    state_dir: &Path,
-    state_exists: bool,
+    state_dirs: &[PathBuf],
     binary_plan: &BinaryRemovalPlan,
     remove_binary: bool,
 ) {
     println!("ZeptoClaw uninstall");
     println!();
-    if state_exists {
-        println!("State directory to remove: {}", state_dir.display());
-    } else {
-        println!("State directory not found: {}", state_dir.display());
+    for state_dir in state_dirs {
+        if state_dir.exists() {
+            println!("State directory to remove: {}", state_dir.display());
+        } else {
+            println!("State directory not found: {}", state_dir.display());
+        }
     }
 
     match binary_plan {
@@ -1,14 +1,28 @@
 //! Self-update command.
 //!
-//! Downloads the latest ZeptoClaw binary from GitHub Releases,
-//! verifies its SHA256 checksum, and atomically replaces the
-//! running executable.
-
+//! Downloads a ZeptoClaw binary from GitHub Releases (or takes a local file
+//! on air-gapped hosts), verifies its SHA256 checksum and its minisign or
+//! cosign signature, and atomically replaces the running executable. Release
+//! builds carry the project's minisign key; a binary without a valid
+//! signature is refused unless `--allow-unsigned` is given. The previous
+//! binary is kept next to it for `update --rollback`.
+
+use std::cmp::Ordering;
 use std::path::{Path, PathBuf};
 
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1, ED25519};
 use sha2::{Digest, Sha256};
 
+use zeptoclaw::config::{Config, UpdateChannel, UpdateConfig};
+
+const RELEASES_API: &str = "https://api.github.com/repos/qhkm/zeptoclaw/releases";
+
+/// The project's minisign public key, compiled into release builds by the
+/// release workflow. Used when `update.minisign_public_key` is not set.
+const RELEASE_MINISIGN_PUBLIC_KEY: Option<&str> = option_env!("ZEPTOCLAW_MINISIGN_PUBLIC_KEY");
+
 // ============================================================================
 // GitHub API types
 // ============================================================================
@@ -16,6 +30,8 @@ use sha2::{Digest, Sha256};
 #[derive(serde::Deserialize)]
 struct GitHubRelease {
     tag_name: String,
+    #[serde(default)]
+    draft: bool,
     assets: Vec<GitHubAsset>,
 }
 
@@ -25,6 +41,12 @@ struct GitHubAsset {
     browser_download_url: String,
 }
 
+impl GitHubRelease {
+    fn asset(&self, name: &str) -> Option<&GitHubAsset> {
+        self.assets.iter().find(|a| a.name == name)
+    }
+}
+
 // ============================================================================
 // Platform detection
 // ============================================================================
@@ -40,7 +62,10 @@ fn platform_asset_name() -> Result<&'static str> {
     } else if cfg!(target_os = "linux") {
         "linux"
     } else {
-        bail!("unsupported OS for self-update; only macOS and Linux are supported");
+        bail!(
+            "unsupported OS for self-update; only macOS and Linux release assets exist \
+             (use `zeptoclaw update --file <path>` with a locally built binary)"
+        );
     };
 
     // Arch
@@ -62,22 +87,10 @@ fn platform_asset_name() -> Result<&'static str> {
 // GitHub Release API
 // ============================================================================
 
-async fn fetch_release(version: Option<&str>) -> Result<GitHubRelease> {
-    let url = match version {
-        Some(v) => {
-            let tag = if v.starts_with('v') {
-                v.to_string()
-            } else {
-                format!("v{v}")
-            };
-            format!("https://api.github.com/repos/qhkm/zeptoclaw/releases/tags/{tag}")
-        }
-        None => "https://api.github.com/repos/qhkm/zeptoclaw/releases/latest".to_string(),
-    };
-
+async fn github_get(url: &str) -> Result<reqwest::Response> {
     let client = reqwest::Client::new();
     let resp = client
-        .get(&url)
+        .get(url)
         .header("User-Agent", "zeptoclaw-self-update")
         .header("Accept", "application/vnd.github+json")
         .send()
@@ -89,20 +102,69 @@ async fn fetch_release(version: Option<&str>) -> Result<GitHubRelease> {
         let body = resp.text().await.unwrap_or_default();
         bail!("GitHub API returned {status}: {body}");
     }
+    Ok(resp)
+}
 
-    resp.json::<GitHubRelease>()
-        .await
-        .context("failed to parse GitHub release response")
+/// Fetch the release to install: an explicit `version`, else the newest
+/// release on `channel`.
+async fn fetch_release(version: Option<&str>, channel: UpdateChannel) -> Result<GitHubRelease> {
+    if let Some(v) = version {
+        let tag = if v.starts_with('v') {
+            v.to_string()
+        } else {
+            format!("v{v}")
+        };
+        return github_get(&format!("{RELEASES_API}/tags/{tag}"))
+            .await?
+            .json::<GitHubRelease>()
+            .await
+            .context("failed to parse GitHub release response");
+    }
+
+    match channel {
+        // GitHub's "latest" never points at a pre-release.
+        UpdateChannel::Stable => github_get(&format!("{RELEASES_API}/latest"))
+            .await?
+            .json::<GitHubRelease>()
+            .await
+            .context("failed to parse GitHub release response"),
+        UpdateChannel::Beta => {
+            let releases = github_get(&format!("{RELEASES_API}?per_page=30"))
+                .await?
+                .json::<Vec<GitHubRelease>>()
+                .await
+                .context("failed to parse GitHub releases response")?;
+            newest_semver_release(releases).context("no release with a version tag found")
+        }
+    }
+}
+
+/// Newest non-draft release whose tag is a version (CI build tags are skipped).
+fn newest_semver_release(releases: Vec<GitHubRelease>) -> Option<GitHubRelease> {
+    releases
+        .into_iter()
+        .filter(|r| !r.draft && parse_semver(&r.tag_name).is_some())
+        .max_by(|a, b| compare_versions(&a.tag_name, &b.tag_name).unwrap_or(Ordering::Equal))
 }
 
 // ============================================================================
 // Version comparison
 // ============================================================================
 
+/// Split `v1.2.3-beta.1+build` into the `1.2.3` core and the `beta.1` pre-release.
+fn split_version(v: &str) -> (&str, Option<&str>) {
+    let v = v.strip_prefix('v').unwrap_or(v);
+    let v = v.split('+').next().unwrap_or(v);
+    match v.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (v, None),
+    }
+}
+
 /// Parse a semver-ish string into (major, minor, patch).
 fn parse_semver(v: &str) -> Option<(u64, u64, u64)> {
-    let v = v.strip_prefix('v').unwrap_or(v);
-    let parts: Vec<&str> = v.split('.').collect();
+    let (core, _) = split_version(v);
+    let parts: Vec<&str> = core.split('.').collect();
     if parts.len() != 3 {
         return None;
     }
@@ -113,46 +175,66 @@ fn parse_semver(v: &str) -> Option<(u64, u64, u64)> {
     ))
 }
 
+/// Semver pre-release precedence: a release outranks its pre-releases,
+/// numeric identifiers compare numerically and rank below alphanumeric ones.
+fn compare_prerelease(a: Option<&str>, b: Option<&str>) -> Ordering {
+    let (a, b) = match (a, b) {
+        (None, None) => return Ordering::Equal,
+        (None, Some(_)) => return Ordering::Greater,
+        (Some(_), None) => return Ordering::Less,
+        (Some(a), Some(b)) => (a, b),
+    };
+    let mut a_ids = a.split('.');
+    let mut b_ids = b.split('.');
+    loop {
+        let ord = match (a_ids.next(), b_ids.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            },
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+}
+
+/// Compare two versions; `None` if either does not parse.
+fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let ord = parse_semver(a)?.cmp(&parse_semver(b)?);
+    Some(ord.then_with(|| compare_prerelease(split_version(a).1, split_version(b).1)))
+}
+
 /// Returns `true` if `latest` is strictly newer than `current`.
 fn is_newer(current: &str, latest: &str) -> bool {
-    match (parse_semver(current), parse_semver(latest)) {
-        (Some(c), Some(l)) => l > c,
-        _ => false,
-    }
+    compare_versions(latest, current) == Some(Ordering::Greater)
 }
 
 // ============================================================================
 // Download + SHA256 verification
 // ============================================================================
 
-async fn download_and_verify(asset_url: &str, checksum_url: &str, dest: &Path) -> Result<()> {
-    let client = reqwest::Client::new();
-
-    // Download binary
-    println!("  Downloading binary...");
-    let binary_bytes = client
-        .get(asset_url)
+async fn download(client: &reqwest::Client, url: &str, what: &str) -> Result<Vec<u8>> {
+    let bytes = client
+        .get(url)
         .header("User-Agent", "zeptoclaw-self-update")
         .send()
         .await
-        .context("failed to download binary")?
+        .and_then(|resp| resp.error_for_status())
+        .with_context(|| format!("failed to download {what}"))?
         .bytes()
         .await
-        .context("failed to read binary response")?;
-
-    // Download checksum sidecar
-    println!("  Downloading checksum...");
-    let checksum_text = client
-        .get(checksum_url)
-        .header("User-Agent", "zeptoclaw-self-update")
-        .send()
-        .await
-        .context("failed to download checksum")?
-        .text()
-        .await
-        .context("failed to read checksum response")?;
+        .with_context(|| format!("failed to read {what} response"))?;
+    Ok(bytes.to_vec())
+}
 
-    // Parse expected hash (format: "<hex>  <filename>" or just "<hex>")
+/// Check `data` against a `sha256sum` line (`"<hex>  <filename>"` or just `"<hex>"`).
+fn verify_checksum(data: &[u8], checksum_text: &str) -> Result<()> {
     let expected_hex = checksum_text
         .split_whitespace()
         .next()
@@ -166,9 +248,8 @@ async fn download_and_verify(asset_url: &str, checksum_url: &str, dest: &Path) -
         );
     }
 
-    // Compute SHA256
     let mut hasher = Sha256::new();
-    hasher.update(&binary_bytes);
+    hasher.update(data);
     let actual_hex = format!("{:x}", hasher.finalize());
 
     if actual_hex != expected_hex {
@@ -177,49 +258,283 @@ async fn download_and_verify(asset_url: &str, checksum_url: &str, dest: &Path) -
              The downloaded binary may be corrupted. Aborting."
         );
     }
-    println!("  Checksum verified.");
+    Ok(())
+}
+
+// ============================================================================
+// Signature verification
+// ============================================================================
+
+/// Detached signatures shipped next to an artifact.
+#[derive(Debug, Default)]
+struct Signatures {
+    /// Contents of `<artifact>.minisig`.
+    minisign: Option<String>,
+    /// Contents of `<artifact>.sig` (base64 cosign signature).
+    cosign: Option<String>,
+}
 
-    // Write to destination
-    std::fs::write(dest, &binary_bytes).context("failed to write downloaded binary")?;
+/// Fall back to the release key when no minisign key is configured.
+fn with_release_key(mut config: UpdateConfig, release_key: Option<&str>) -> UpdateConfig {
+    if config.minisign_public_key.is_none() {
+        config.minisign_public_key = release_key
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string);
+    }
+    config
+}
 
+/// Verify every signature for which a public key is configured.
+///
+/// A configured key whose signature is missing is an error, and so is
+/// having no key at all unless `allow_unsigned` is set.
+fn verify_signatures(
+    data: &[u8],
+    config: &UpdateConfig,
+    sigs: &Signatures,
+    allow_unsigned: bool,
+) -> Result<()> {
+    let mut verified = false;
+    if let Some(key) = config.minisign_public_key.as_deref() {
+        let sig = sigs.minisign.as_deref().context(
+            "artifact has no .minisig signature but update.minisign_public_key is set; refusing to install",
+        )?;
+        let trusted_comment = verify_minisign(data, sig, key)?;
+        println!("  minisign signature verified ({trusted_comment}).");
+        verified = true;
+    }
+    if let Some(key) = config.cosign_public_key.as_deref() {
+        let sig = sigs.cosign.as_deref().context(
+            "artifact has no .sig signature but update.cosign_public_key is set; refusing to install",
+        )?;
+        verify_cosign(data, sig, key)?;
+        println!("  cosign signature verified.");
+        verified = true;
+    }
+    if !verified {
+        if !allow_unsigned {
+            bail!(
+                "no signing key to verify the binary with (this build has no release key); \
+                 set update.minisign_public_key or update.cosign_public_key, or pass \
+                 --allow-unsigned to install it unverified"
+            );
+        }
+        println!("  Signature not verified (--allow-unsigned).");
+    }
     Ok(())
 }
 
+/// Verify a minisign signature (prehashed `ED` or legacy `Ed`) and the
+/// global signature over its trusted comment. Returns the trusted comment.
+fn verify_minisign(data: &[u8], sig_text: &str, public_key: &str) -> Result<String> {
+    let b64 = base64::engine::general_purpose::STANDARD;
+
+    // Accept the bare key line or the whole `.pub` file.
+    let key_line = public_key
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty() && !l.starts_with("untrusted comment:"))
+        .context("minisign public key is empty")?;
+    let key = b64
+        .decode(key_line)
+        .context("minisign public key is not valid base64")?;
+    if key.len() != 42 || &key[..2] != b"Ed" {
+        bail!("minisign public key is malformed");
+    }
+    let (key_id, public_key) = (&key[2..10], UnparsedPublicKey::new(&ED25519, &key[10..]));
+
+    let mut lines = sig_text
+        .lines()
+        .map(str::trim_end)
+        .filter(|l| !l.is_empty());
+    if !lines
+        .next()
+        .is_some_and(|l| l.starts_with("untrusted comment:"))
+    {
+        bail!("minisign signature is malformed (missing untrusted comment)");
+    }
+    let sig = lines
+        .next()
+        .and_then(|l| b64.decode(l).ok())
+        .filter(|sig| sig.len() == 74)
+        .context("minisign signature is malformed")?;
+    let trusted_comment = lines
+        .next()
+        .and_then(|l| l.strip_prefix("trusted comment: "))
+        .context("minisign signature has no trusted comment")?;
+    let global_sig = lines
+        .next()
+        .and_then(|l| b64.decode(l).ok())
+        .context("minisign signature has no global signature")?;
+
+    let (algorithm, sig_key_id, signature) = (&sig[..2], &sig[2..10], &sig[10..]);
+    if sig_key_id != key_id {
+        bail!("minisign signature was made with a different key than update.minisign_public_key");
+    }
+    let checked = match algorithm {
+        b"ED" => public_key.verify(&blake2::Blake2b512::digest(data), signature),
+        b"Ed" => public_key.verify(data, signature),
+        _ => bail!("unsupported minisign signature algorithm"),
+    };
+    checked.map_err(|_| {
+        anyhow!("minisign signature does not match the binary; refusing to install")
+    })?;
+
+    let mut global_message = signature.to_vec();
+    global_message.extend_from_slice(trusted_comment.as_bytes());
+    public_key
+        .verify(&global_message, &global_sig)
+        .map_err(|_| anyhow!("minisign trusted comment signature is invalid"))?;
+
+    Ok(trusted_comment.to_string())
+}
+
+/// DER header of an ECDSA P-256 SubjectPublicKeyInfo; the 65-byte
+/// uncompressed point follows it.
+const P256_SPKI_PREFIX: [u8; 26] = [
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+
+/// Verify a `cosign sign-blob --key` signature (base64 ASN.1 ECDSA P-256/SHA-256).
+fn verify_cosign(data: &[u8], sig_b64: &str, public_key_pem: &str) -> Result<()> {
+    let b64 = base64::engine::general_purpose::STANDARD;
+
+    let body: String = public_key_pem
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.starts_with("-----"))
+        .collect();
+    let der = b64
+        .decode(body)
+        .context("cosign public key is not valid PEM")?;
+    let point = der
+        .strip_prefix(&P256_SPKI_PREFIX[..])
+        .filter(|point| point.len() == 65)
+        .context("cosign public key must be an ECDSA P-256 key")?;
+
+    let signature = b64
+        .decode(sig_b64.trim())
+        .context("cosign signature is not valid base64")?;
+    UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, point)
+        .verify(data, &signature)
+        .map_err(|_| anyhow!("cosign signature does not match the binary; refusing to install"))
+}
+
 // ============================================================================
 // Atomic binary replacement
 // ============================================================================
 
-fn replace_binary(new_binary: &Path) -> Result<PathBuf> {
+fn current_exe_path() -> Result<PathBuf> {
     let current_exe =
         std::env::current_exe().context("failed to determine current executable path")?;
 
     // Resolve symlinks so we replace the actual file
-    let current_exe = current_exe
+    Ok(current_exe
         .canonicalize()
-        .unwrap_or_else(|_| current_exe.clone());
+        .unwrap_or_else(|_| current_exe.clone()))
+}
+
+/// Where the previous binary is kept for `update --rollback`.
+fn backup_path(exe: &Path) -> PathBuf {
+    exe.with_extension("old")
+}
 
-    let backup = current_exe.with_extension("old");
+/// `<path>.<ext>`, keeping any extension `path` already has.
+fn sidecar_path(path: &Path, ext: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(ext);
+    PathBuf::from(name)
+}
+
+/// Run the staged binary with `--version` so a wrong-platform or truncated
+/// file is caught before it replaces the working one.
+fn smoke_test(binary: &Path) -> Result<String> {
+    let output = std::process::Command::new(binary)
+        .arg("--version")
+        .output()
+        .context("new binary failed to start")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || !stdout.starts_with("zeptoclaw ") {
+        bail!("new binary did not report a zeptoclaw version; refusing to install");
+    }
+    Ok(stdout.trim().to_string())
+}
+
+fn replace_binary(current_exe: &Path, new_binary: &Path) -> Result<PathBuf> {
+    let backup = backup_path(current_exe);
+    let _ = std::fs::remove_file(&backup);
 
     // Rename current → backup
-    std::fs::rename(&current_exe, &backup)
+    std::fs::rename(current_exe, &backup)
         .with_context(|| format!("failed to backup current binary to {}", backup.display()))?;
 
     // Rename new → current
-    if let Err(e) = std::fs::rename(new_binary, &current_exe) {
+    if let Err(e) = std::fs::rename(new_binary, current_exe) {
         // Rollback: restore backup
-        let _ = std::fs::rename(&backup, &current_exe);
+        let _ = std::fs::rename(&backup, current_exe);
         return Err(e).context("failed to install new binary (rolled back)");
     }
 
+    Ok(backup)
+}
+
+/// Stage `bytes` next to the running executable (same filesystem, so the
+/// final rename is atomic), smoke-test it and swap it in.
+fn install_binary(bytes: &[u8]) -> Result<()> {
+    let current_exe = current_exe_path()?;
+    let staged = current_exe.with_extension("new");
+    std::fs::write(&staged, bytes)
+        .with_context(|| format!("failed to write {}", staged.display()))?;
+
     // Set executable permissions
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
         let perms = std::fs::Permissions::from_mode(0o755);
-        std::fs::set_permissions(&current_exe, perms)
-            .context("failed to set executable permissions")?;
+        std::fs::set_permissions(&staged, perms).context("failed to set executable permissions")?;
     }
 
+    let version = match smoke_test(&staged) {
+        Ok(version) => version,
+        Err(e) => {
+            let _ = std::fs::remove_file(&staged);
+            return Err(e);
+        }
+    };
+    println!("  New binary reports {version}.");
+
+    println!("  Replacing binary...");
+    let backup = replace_binary(&current_exe, &staged)?;
+    println!("  Previous binary: {}", backup.display());
+    Ok(())
+}
+
+/// Swap the running executable with the backup left by the last update.
+///
+/// The replaced binary becomes the new backup, so a second rollback undoes
+/// the first.
+fn rollback_binary() -> Result<PathBuf> {
+    let current_exe = current_exe_path()?;
+    let backup = backup_path(&current_exe);
+    if !backup.is_file() {
+        bail!(
+            "no previous binary at {}; nothing to roll back to",
+            backup.display()
+        );
+    }
+
+    let swap = current_exe.with_extension("rollback");
+    std::fs::rename(&current_exe, &swap)
+        .with_context(|| format!("failed to move {} aside", current_exe.display()))?;
+    if let Err(e) = std::fs::rename(&backup, &current_exe) {
+        let _ = std::fs::rename(&swap, &current_exe);
+        return Err(e).context("failed to restore previous binary (nothing changed)");
+    }
+    std::fs::rename(&swap, &backup)
+        .with_context(|| format!("failed to keep replaced binary at {}", backup.display()))?;
     Ok(backup)
 }
 
@@ -227,16 +542,77 @@ fn replace_binary(new_binary: &Path) -> Result<PathBuf> {
 // Command handler
 // ============================================================================
 
-pub(crate) async fn cmd_update(check: bool, version: Option<String>, force: bool) -> Result<()> {
+/// Air-gapped install: verify `path` against its `.sha256`, `.minisig` and
+/// `.sig` sidecars (when present) and install it.
+fn install_from_file(path: &Path, config: &UpdateConfig, allow_unsigned: bool) -> Result<()> {
+    println!("Installing from {}...", path.display());
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let sidecar = |ext: &str| std::fs::read_to_string(sidecar_path(path, ext)).ok();
+
+    match sidecar("sha256") {
+        Some(checksum) => {
+            verify_checksum(&bytes, &checksum)?;
+            println!("  Checksum verified.");
+        }
+        None => println!("  No .sha256 file next to the binary; skipping checksum."),
+    }
+
+    let sigs = Signatures {
+        minisign: sidecar("minisig"),
+        cosign: sidecar("sig"),
+    };
+    verify_signatures(&bytes, config, &sigs, allow_unsigned)?;
+
+    install_binary(&bytes)?;
+    println!("\nInstalled {}.", path.display());
+    println!("  Restart to use the new version.");
+    Ok(())
+}
+
+pub(crate) async fn cmd_update(
+    check: bool,
+    version: Option<String>,
+    force: bool,
+    channel: Option<UpdateChannel>,
+    file: Option<PathBuf>,
+    rollback: bool,
+    allow_unsigned: bool,
+) -> Result<()> {
+    if rollback {
+        let backup = rollback_binary()?;
+        println!("Rolled back to the previous binary.");
+        println!(
+            "  The replaced binary is now at {} (run `zeptoclaw update --rollback` again to undo).",
+            backup.display()
+        );
+        return Ok(());
+    }
+
+    let config = Config::load()
+        .context("failed to load config (needed for update signature keys)")?
+        .update;
+    let mut update_config = with_release_key(config, RELEASE_MINISIGN_PUBLIC_KEY);
+    if let Some(channel) = channel {
+        update_config.channel = channel;
+    }
+
+    if let Some(path) = file {
+        return install_from_file(&path, &update_config, allow_unsigned);
+    }
+
     let current = env!("CARGO_PKG_VERSION");
     println!("Current version: v{current}");
 
     // Fetch release
-    let release = fetch_release(version.as_deref()).await?;
+    let release = fetch_release(version.as_deref(), update_config.channel).await?;
     let latest = &release.tag_name;
     let latest_bare = latest.strip_prefix('v').unwrap_or(latest);
 
-    println!("Latest release:  {latest}");
+    match update_config.channel {
+        UpdateChannel::Stable => println!("Latest release:  {latest}"),
+        UpdateChannel::Beta => println!("Latest release:  {latest} (beta channel)"),
+    }
 
     // Compare
     if !force && !is_newer(current, latest_bare) {
@@ -253,39 +629,43 @@ pub(crate) async fn cmd_update(check: bool, version: Option<String>, force: bool
 
     // Resolve platform asset
     let asset_name = platform_asset_name()?;
-    let checksum_name = format!("{asset_name}.sha256");
-
     let binary_asset = release
-        .assets
-        .iter()
-        .find(|a| a.name == asset_name)
+        .asset(asset_name)
         .with_context(|| format!("release {latest} has no asset named '{asset_name}'"))?;
-
+    let checksum_name = format!("{asset_name}.sha256");
     let checksum_asset = release
-        .assets
-        .iter()
-        .find(|a| a.name == checksum_name)
+        .asset(&checksum_name)
         .with_context(|| format!("release {latest} has no checksum asset '{checksum_name}'"))?;
 
     println!("\nDownloading {asset_name} from {latest}...");
 
-    // Download to temp file
-    let tmp_dir = tempfile::tempdir().context("failed to create temp directory")?;
-    let tmp_binary = tmp_dir.path().join(asset_name);
+    let client = reqwest::Client::new();
+    println!("  Downloading binary...");
+    let binary = download(&client, &binary_asset.browser_download_url, "binary").await?;
+    println!("  Downloading checksum...");
+    let checksum = download(&client, &checksum_asset.browser_download_url, "checksum").await?;
+    verify_checksum(&binary, &String::from_utf8_lossy(&checksum))?;
+    println!("  Checksum verified.");
 
-    download_and_verify(
-        &binary_asset.browser_download_url,
-        &checksum_asset.browser_download_url,
-        &tmp_binary,
-    )
-    .await?;
+    let mut sigs = Signatures::default();
+    if update_config.minisign_public_key.is_some() {
+        if let Some(asset) = release.asset(&format!("{asset_name}.minisig")) {
+            let sig = download(&client, &asset.browser_download_url, "minisign signature").await?;
+            sigs.minisign = Some(String::from_utf8_lossy(&sig).into_owned());
+        }
+    }
+    if update_config.cosign_public_key.is_some() {
+        if let Some(asset) = release.asset(&format!("{asset_name}.sig")) {
+            let sig = download(&client, &asset.browser_download_url, "cosign signature").await?;
+            sigs.cosign = Some(String::from_utf8_lossy(&sig).into_owned());
+        }
+    }
+    verify_signatures(&binary, &update_config, &sigs, allow_unsigned)?;
 
-    // Replace
-    println!("  Replacing binary...");
-    let backup = replace_binary(&tmp_binary)?;
+    install_binary(&binary)?;
 
     println!("\nUpdated to {latest}!");
-    println!("  Backup: {}", backup.display());
+    println!("  Undo with `zeptoclaw update --rollback`.");
     println!("  Restart to use the new version.");
 
     Ok(())
@@ -299,6 +679,20 @@ pub(crate) async fn cmd_update(check: bool, version: Option<String>, force: bool
 mod tests {
     use super::*;
 
+    /// Test key and signatures over [`SIGNED`], generated with a throwaway key.
+    const SIGNED: &[u8] = b"zeptoclaw test binary\n";
+    const MINISIGN_PUB: &str = "untrusted comment: minisign public key 0123456789ABCDEF\n\
+        RWQBI0VniavN72pz1257bMRiQ7MtLjDYm4QQBwPIWijyxx37Y+8S/Y7k\n";
+    const MINISIG: &str = "untrusted comment: signature from minisign secret key\n\
+        RUQBI0VniavN74z+xs+aKsO9XZIG7RQSaO14u3IXJ4b3nQPJRdNM3XKptwtAStO3IF6aGKxxmlPfAyUmuh2Z4Ny3x7Zh0nJALgI=\n\
+        trusted comment: timestamp:1767225600\tfile:zeptoclaw-linux-x86_64\n\
+        zWBmlkvKMSVgdDX/R6mHYJjDFUBbhp39/l+CI3s6TpWCcot2HoK3Z0nHBCY9qtnKQBlzQk79wW4QkqJAnqocBg==\n";
+    const COSIGN_PUB: &str = "-----BEGIN PUBLIC KEY-----\n\
+        MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEz4fLk4MWYU8gnVuOBoLd/ypB1003\n\
+        1/BFEkUEyeXQK/eOP6pDrba7wpMu4UiKE54kU3M+1DxbVOu0G4bN0d6Paw==\n\
+        -----END PUBLIC KEY-----\n";
+    const COSIGN_SIG: &str = "MEUCIQCx7todySG7ZMlbGynQIlEAVS7zKyXyx7EpPQo7wSZgAAIgKUz5V7rpAn9ZKpMWBsawkajWmQaA4CsbV2/rJLXazgE=";
+
     // -- parse_semver ---------------------------------------------------------
 
     #[test]
@@ -316,6 +710,13 @@ mod tests {
         assert_eq!(parse_semver("not-a-version"), None);
         assert_eq!(parse_semver("1.2"), None);
         assert_eq!(parse_semver(""), None);
+        assert_eq!(parse_semver("build-20260101-120000-r42"), None);
+    }
+
+    #[test]
+    fn test_parse_semver_prerelease() {
+        assert_eq!(parse_semver("v0.8.0-beta.1"), Some((0, 8, 0)));
+        assert_eq!(parse_semver("0.8.0+build.5"), Some((0, 8, 0)));
     }
 
     // -- is_newer -------------------------------------------------------------
@@ -351,6 +752,164 @@ mod tests {
         assert!(!is_newer("0.5.0", "bad"));
     }
 
+    #[test]
+    fn test_is_newer_prerelease_precedence() {
+        assert!(is_newer("0.7.6", "0.8.0-beta.1"));
+        assert!(is_newer("0.8.0-beta.1", "0.8.0-beta.2"));
+        assert!(is_newer("0.8.0-beta.2", "0.8.0-beta.10"));
+        assert!(is_newer("0.8.0-alpha.3", "0.8.0-beta.1"));
+        assert!(is_newer("0.8.0-beta.2", "0.8.0"));
+        assert!(!is_newer("0.8.0", "0.8.0-beta.2"));
+    }
+
+    // -- release selection ----------------------------------------------------
+
+    fn release(tag: &str, draft: bool) -> GitHubRelease {
+        GitHubRelease {
+            tag_name: tag.to_string(),
+            draft,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_newest_semver_release_skips_build_tags_and_drafts() {
+        let releases = vec![
+            release("build-20260301-101010-r90", false),
+            release("v0.9.0-beta.1", true),
+            release("v0.8.0-beta.2", false),
+            release("v0.7.6", false),
+        ];
+        let newest = newest_semver_release(releases).unwrap();
+        assert_eq!(newest.tag_name, "v0.8.0-beta.2");
+    }
+
+    #[test]
+    fn test_newest_semver_release_none() {
+        let releases = vec![release("build-20260301-101010-r90", false)];
+        assert!(newest_semver_release(releases).is_none());
+    }
+
+    // -- checksum -------------------------------------------------------------
+
+    #[test]
+    fn test_verify_checksum() {
+        let hex = format!("{:x}", Sha256::digest(SIGNED));
+        assert!(verify_checksum(SIGNED, &format!("{hex}  zeptoclaw-linux-x86_64")).is_ok());
+        assert!(verify_checksum(b"tampered", &hex).is_err());
+        assert!(verify_checksum(SIGNED, "abc").is_err());
+    }
+
+    // -- signatures -----------------------------------------------------------
+
+    #[test]
+    fn test_verify_minisign_valid() {
+        let comment = verify_minisign(SIGNED, MINISIG, MINISIGN_PUB).unwrap();
+        assert!(comment.contains("file:zeptoclaw-linux-x86_64"));
+        // Bare key line works too
+        let bare = MINISIGN_PUB.lines().nth(1).unwrap();
+        assert!(verify_minisign(SIGNED, MINISIG, bare).is_ok());
+    }
+
+    #[test]
+    fn test_verify_minisign_rejects_tampered_data() {
+        assert!(verify_minisign(b"zeptoclaw evil binary\n", MINISIG, MINISIGN_PUB).is_err());
+    }
+
+    #[test]
+    fn test_verify_minisign_rejects_tampered_trusted_comment() {
+        let forged = MINISIG.replace("timestamp:1767225600", "timestamp:1767225601");
+        let err = verify_minisign(SIGNED, &forged, MINISIGN_PUB).unwrap_err();
+        assert!(err.to_string().contains("trusted comment"));
+    }
+
+    #[test]
+    fn test_verify_minisign_rejects_other_key_id() {
+        // Same key bytes, different key id
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let mut key = b64.decode(MINISIGN_PUB.lines().nth(1).unwrap()).unwrap();
+        key[2] ^= 0xff;
+        let err = verify_minisign(SIGNED, MINISIG, &b64.encode(key)).unwrap_err();
+        assert!(err.to_string().contains("different key"));
+    }
+
+    #[test]
+    fn test_verify_cosign_valid() {
+        assert!(verify_cosign(SIGNED, COSIGN_SIG, COSIGN_PUB).is_ok());
+    }
+
+    #[test]
+    fn test_verify_cosign_rejects_tampered_data() {
+        assert!(verify_cosign(b"tampered", COSIGN_SIG, COSIGN_PUB).is_err());
+    }
+
+    #[test]
+    fn test_verify_signatures_requires_signature_for_configured_key() {
+        let config = UpdateConfig {
+            minisign_public_key: Some(MINISIGN_PUB.to_string()),
+            ..Default::default()
+        };
+        // A missing signature is refused even with --allow-unsigned.
+        let err = verify_signatures(SIGNED, &config, &Signatures::default(), true).unwrap_err();
+        assert!(err.to_string().contains(".minisig"));
+
+        let sigs = Signatures {
+            minisign: Some(MINISIG.to_string()),
+            cosign: None,
+        };
+        verify_signatures(SIGNED, &config, &sigs, false).unwrap();
+    }
+
+    #[test]
+    fn test_verify_signatures_without_keys_fails_closed() {
+        let config = UpdateConfig::default();
+        let err = verify_signatures(SIGNED, &config, &Signatures::default(), false).unwrap_err();
+        assert!(err.to_string().contains("--allow-unsigned"));
+        verify_signatures(SIGNED, &config, &Signatures::default(), true).unwrap();
+    }
+
+    #[test]
+    fn test_with_release_key() {
+        let config = with_release_key(UpdateConfig::default(), Some(MINISIGN_PUB));
+        assert_eq!(
+            config.minisign_public_key.as_deref(),
+            Some(MINISIGN_PUB.trim())
+        );
+        assert!(with_release_key(UpdateConfig::default(), Some(" "))
+            .minisign_public_key
+            .is_none());
+
+        // A configured key wins over the release key.
+        let configured = UpdateConfig {
+            minisign_public_key: Some("configured".to_string()),
+            ..Default::default()
+        };
+        let config = with_release_key(configured, Some(MINISIGN_PUB));
+        assert_eq!(config.minisign_public_key.as_deref(), Some("configured"));
+    }
+
+    // -- paths ----------------------------------------------------------------
+
+    #[test]
+    fn test_sidecar_path_keeps_extension() {
+        assert_eq!(
+            sidecar_path(Path::new("/tmp/zeptoclaw-linux-x86_64"), "minisig"),
+            PathBuf::from("/tmp/zeptoclaw-linux-x86_64.minisig")
+        );
+        assert_eq!(
+            sidecar_path(Path::new("C:/dl/zeptoclaw.exe"), "sha256"),
+            PathBuf::from("C:/dl/zeptoclaw.exe.sha256")
+        );
+    }
+
+    #[test]
+    fn test_backup_path() {
+        assert_eq!(
+            backup_path(Path::new("/usr/local/bin/zeptoclaw")),
+            PathBuf::from("/usr/local/bin/zeptoclaw.old")
+        );
+    }
+
     // -- platform_asset_name --------------------------------------------------
 
     #[test]
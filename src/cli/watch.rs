@@ -50,7 +50,7 @@ fn url_hash(url: &str) -> u64 {
 /// Get path for storing last snapshot of a watched URL.
 fn snapshot_path(url: &str) -> PathBuf {
     let hash = format!("{:x}", url_hash(url));
-    zeptoclaw::config::Config::dir()
+    zeptoclaw::config::Config::state_dir()
         .join("watch")
         .join(format!("{}.txt", hash))
 }
@@ -109,7 +109,7 @@ pub(crate) async fn cmd_watch(url: String, interval: String, notify: Option<Stri
     println!();
 
     // Create watch directory
-    let watch_dir = zeptoclaw::config::Config::dir().join("watch");
+    let watch_dir = zeptoclaw::config::Config::state_dir().join("watch");
     std::fs::create_dir_all(&watch_dir)
         .with_context(|| format!("Failed to create watch directory: {:?}", watch_dir))?;
 
@@ -0,0 +1,200 @@
+//! Workspace command handlers.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use zeptoclaw::config::Config;
+
+use super::WorkspaceAction;
+
+/// Handle workspace subcommands.
+pub(crate) async fn cmd_workspace(action: WorkspaceAction) -> Result<()> {
+    match action {
+        WorkspaceAction::List => cmd_workspace_list(),
+        WorkspaceAction::Create {
+            name,
+            path,
+            description,
+            switch,
+        } => cmd_workspace_create(&name, path, description, switch),
+        WorkspaceAction::Switch { name } => cmd_workspace_switch(&name),
+    }
+}
+
+fn cmd_workspace_list() -> Result<()> {
+    let config = Config::load().with_context(|| "Failed to load configuration")?;
+    let workspaces = &config.workspaces;
+    if workspaces.projects.is_empty() {
+        println!("No workspaces registered. Create one with `zeptoclaw workspace create <name>`.");
+        println!("Default workspace: {}", config.workspace_path().display());
+        return Ok(());
+    }
+
+    let active = workspaces
+        .active
+        .as_deref()
+        .filter(|name| workspaces.projects.contains_key(*name));
+    println!("Workspaces:");
+    println!(
+        "  {} default  {}",
+        if active.is_none() { "*" } else { " " },
+        zeptoclaw::config::expand_home(&config.agents.defaults.workspace).display()
+    );
+    for (name, entry) in &workspaces.projects {
+        let marker = if active == Some(name.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        let mut bound: Vec<&str> = workspaces
+            .bindings
+            .iter()
+            .filter(|(_, workspace)| *workspace == name)
+            .map(|(chat, _)| chat.as_str())
+            .collect();
+        bound.sort_unstable();
+        print!("  {} {}  {}", marker, name, entry.path);
+        if let Some(description) = &entry.description {
+            print!(" \u{2014} {}", description);
+        }
+        if !bound.is_empty() {
+            print!(" (bound: {})", bound.join(", "));
+        }
+        println!();
+    }
+    Ok(())
+}
+
+fn cmd_workspace_create(
+    name: &str,
+    path: Option<String>,
+    description: Option<String>,
+    switch: bool,
+) -> Result<()> {
+    validate_workspace_name(name)?;
+    let path = path.unwrap_or_else(|| format!("~/.zeptoclaw/workspaces/{}", name));
+    let dir = zeptoclaw::config::expand_home(&path);
+    std::fs::create_dir_all(dir.join("skills"))
+        .with_context(|| format!("Failed to create workspace directory {:?}", dir))?;
+
+    update_config_file(|raw| {
+        let workspaces = section(raw);
+        let projects = workspaces
+            .as_object_mut()
+            .map(|w| w.entry("projects").or_insert_with(|| json!({})))
+            .and_then(Value::as_object_mut)
+            .context("`workspaces.projects` must be an object")?;
+        if projects.contains_key(name) {
+            anyhow::bail!("Workspace '{}' already exists", name);
+        }
+        let mut entry = json!({ "path": path });
+        if let Some(description) = &description {
+            entry["description"] = json!(description);
+        }
+        projects.insert(name.to_string(), entry);
+        if switch {
+            workspaces["active"] = json!(name);
+        }
+        Ok(())
+    })?;
+
+    println!("Created workspace '{}' at {}", name, dir.display());
+    if switch {
+        println!("Switched to workspace '{}'", name);
+    }
+    Ok(())
+}
+
+fn cmd_workspace_switch(name: &str) -> Result<()> {
+    let config = Config::load().with_context(|| "Failed to load configuration")?;
+    if name != "default" && !config.workspaces.projects.contains_key(name) {
+        anyhow::bail!(
+            "Unknown workspace '{}'; see `zeptoclaw workspace list`",
+            name
+        );
+    }
+
+    update_config_file(|raw| {
+        let workspaces = section(raw);
+        workspaces["active"] = if name == "default" {
+            Value::Null
+        } else {
+            json!(name)
+        };
+        Ok(())
+    })?;
+
+    if name == "default" {
+        println!("Switched to the default workspace");
+    } else {
+        println!("Switched to workspace '{}'", name);
+    }
+    Ok(())
+}
+
+/// Workspace names: letters, digits, `-` and `_`; `default` is reserved.
+fn validate_workspace_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || name == "default"
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!(
+            "Invalid workspace name '{}' (use letters, digits, '-' and '_'; 'default' is reserved)",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// The `workspaces` object of a raw config, created if missing.
+fn section(raw: &mut Value) -> &mut Value {
+    if !raw["workspaces"].is_object() {
+        raw["workspaces"] = json!({});
+    }
+    &mut raw["workspaces"]
+}
+
+/// Edit config.json as raw JSON, so profiles, environment overrides and
+/// encrypted values are not written back.
+fn update_config_file(edit: impl FnOnce(&mut Value) -> Result<()>) -> Result<()> {
+    let path: PathBuf = Config::path();
+    let mut raw: Value = if path.exists() {
+        let content = std::fs::read_to_string(&path).context("Failed to read config file")?;
+        serde_json::from_str(&content).context("Config file is not valid JSON")?
+    } else {
+        json!({})
+    };
+    if !raw.is_object() {
+        anyhow::bail!("Config file must be a JSON object");
+    }
+    edit(&mut raw)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&raw)?)
+        .context("Failed to write config file")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_names() {
+        assert!(validate_workspace_name("acme-2").is_ok());
+        assert!(validate_workspace_name("default").is_err());
+        assert!(validate_workspace_name("../x").is_err());
+    }
+
+    #[test]
+    fn test_section_replaces_non_objects() {
+        let mut raw = json!({"workspaces": null});
+        section(&mut raw)["active"] = json!("acme");
+        assert_eq!(raw, json!({"workspaces": {"active": "acme"}}));
+    }
+}
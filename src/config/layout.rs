@@ -0,0 +1,258 @@
+//! Directory layout: where ZeptoClaw keeps configuration, data and state.
+//!
+//! Resolution order:
+//! 1. `ZEPTOCLAW_HOME` — everything in one relocatable directory.
+//! 2. An existing `~/.zeptoclaw` — the legacy single-directory layout.
+//! 3. Any of `XDG_CONFIG_HOME` / `XDG_DATA_HOME` / `XDG_STATE_HOME` set —
+//!    the XDG layout (`<config>/zeptoclaw`, `<data>/zeptoclaw`,
+//!    `<state>/zeptoclaw`, unset variables use the spec defaults).
+//! 4. Otherwise `~/.zeptoclaw`.
+//!
+//! `zeptoclaw config migrate-dirs` moves a legacy install into the layout
+//! the environment asks for; see [`migration_plan`].
+
+use std::path::{Path, PathBuf};
+
+/// Top-level entries of a legacy `~/.zeptoclaw` that belong in the config dir.
+const CONFIG_ENTRIES: &[&str] = &["config.json", "profiles", "prompts", "templates", "hands"];
+
+/// Top-level entries that belong in the state dir (caches, runtime state).
+const STATE_ENTRIES: &[&str] = &[
+    "cache",
+    "crash_guard.json",
+    "daemon_state.json",
+    "logs",
+    "metrics",
+    "quota",
+    "tunnel_state.json",
+    "watch",
+];
+
+/// Which of the three directories a top-level entry belongs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirKind {
+    Config,
+    Data,
+    State,
+}
+
+impl DirKind {
+    /// Classify a top-level entry of a single-directory install.
+    pub fn of(entry: &str) -> Self {
+        if CONFIG_ENTRIES.contains(&entry) {
+            Self::Config
+        } else if STATE_ENTRIES.contains(&entry) {
+            Self::State
+        } else {
+            Self::Data
+        }
+    }
+}
+
+/// Resolved ZeptoClaw directories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirLayout {
+    /// `config.json`, profiles, prompt templates, agent templates, hands.
+    pub config: PathBuf,
+    /// Sessions, memory, credentials, skills, workspaces — everything else.
+    pub data: PathBuf,
+    /// Caches, metrics, quota counters and daemon/tunnel runtime state.
+    pub state: PathBuf,
+}
+
+impl DirLayout {
+    /// Resolve the layout from the process environment.
+    pub fn resolve() -> Self {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        let legacy = home.join(".zeptoclaw").is_dir();
+        Self::resolve_with(|key| std::env::var(key).ok(), &home, legacy)
+    }
+
+    /// Resolve the layout from `env`, `home` and whether a legacy
+    /// `~/.zeptoclaw` exists.
+    pub fn resolve_with(
+        env: impl Fn(&str) -> Option<String>,
+        home: &Path,
+        legacy_exists: bool,
+    ) -> Self {
+        let var = |key: &str| env(key).filter(|value| !value.trim().is_empty());
+
+        if let Some(root) = var("ZEPTOCLAW_HOME") {
+            return Self::single(expand_tilde(root.trim(), home));
+        }
+        let xdg = ["XDG_CONFIG_HOME", "XDG_DATA_HOME", "XDG_STATE_HOME"];
+        if legacy_exists || !xdg.iter().any(|key| var(key).is_some()) {
+            return Self::single(home.join(".zeptoclaw"));
+        }
+
+        // The XDG spec ignores relative paths.
+        let base = |key: &str, default: &str| {
+            var(key)
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute())
+                .unwrap_or_else(|| home.join(default))
+                .join("zeptoclaw")
+        };
+        Self {
+            config: base("XDG_CONFIG_HOME", ".config"),
+            data: base("XDG_DATA_HOME", ".local/share"),
+            state: base("XDG_STATE_HOME", ".local/state"),
+        }
+    }
+
+    /// All three directories at `root`.
+    pub fn single(root: PathBuf) -> Self {
+        Self {
+            config: root.clone(),
+            data: root.clone(),
+            state: root,
+        }
+    }
+
+    /// Whether config, data and state share one directory.
+    pub fn is_single(&self) -> bool {
+        self.config == self.data && self.data == self.state
+    }
+
+    /// Destination directory for a top-level entry of a single-directory
+    /// install.
+    pub fn dir_for(&self, entry: &str) -> &Path {
+        self.dir(DirKind::of(entry))
+    }
+
+    /// The directory of `kind`.
+    pub fn dir(&self, kind: DirKind) -> &Path {
+        match kind {
+            DirKind::Config => &self.config,
+            DirKind::Data => &self.data,
+            DirKind::State => &self.state,
+        }
+    }
+}
+
+/// Moves (`from`, `to`) that relocate the entries of the single-directory
+/// install at `legacy` into `target`. Entries already at their destination
+/// are skipped.
+pub fn migration_plan(
+    legacy: &Path,
+    target: &DirLayout,
+) -> std::io::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut names: Vec<String> = std::fs::read_dir(legacy)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    Ok(names
+        .into_iter()
+        .map(|name| (legacy.join(&name), target.dir_for(&name).join(&name)))
+        .filter(|(from, to)| from != to)
+        .collect())
+}
+
+fn expand_tilde(path: &str, home: &Path) -> PathBuf {
+    match path.strip_prefix('~') {
+        Some("") => home.to_path_buf(),
+        Some(rest) if rest.starts_with('/') || (cfg!(windows) && rest.starts_with('\\')) => {
+            home.join(&rest[1..])
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn resolve(vars: &[(&str, &str)], legacy: bool) -> DirLayout {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        DirLayout::resolve_with(|key| vars.get(key).cloned(), Path::new("/home/u"), legacy)
+    }
+
+    #[test]
+    fn test_expand_tilde_separators() {
+        let home = Path::new("/home/u");
+        assert_eq!(expand_tilde("~", home), home);
+        assert_eq!(expand_tilde("~/data", home), home.join("data"));
+        assert_eq!(
+            expand_tilde("~user/data", home),
+            PathBuf::from("~user/data")
+        );
+        if cfg!(windows) {
+            assert_eq!(expand_tilde("~\\data", home), home.join("data"));
+        }
+    }
+
+    #[test]
+    fn test_default_is_legacy_dir() {
+        let layout = resolve(&[], false);
+        assert_eq!(
+            layout,
+            DirLayout::single(PathBuf::from("/home/u/.zeptoclaw"))
+        );
+        assert!(layout.is_single());
+    }
+
+    #[test]
+    fn test_zeptoclaw_home_wins() {
+        let layout = resolve(
+            &[("ZEPTOCLAW_HOME", "~/zc"), ("XDG_CONFIG_HOME", "/x")],
+            true,
+        );
+        assert_eq!(layout, DirLayout::single(PathBuf::from("/home/u/zc")));
+    }
+
+    #[test]
+    fn test_xdg_layout() {
+        let layout = resolve(
+            &[("XDG_CONFIG_HOME", "/cfg"), ("XDG_STATE_HOME", "rel")],
+            false,
+        );
+        assert_eq!(layout.config, PathBuf::from("/cfg/zeptoclaw"));
+        assert_eq!(layout.data, PathBuf::from("/home/u/.local/share/zeptoclaw"));
+        assert_eq!(
+            layout.state,
+            PathBuf::from("/home/u/.local/state/zeptoclaw")
+        );
+        assert!(!layout.is_single());
+    }
+
+    #[test]
+    fn test_existing_install_keeps_legacy_dir() {
+        let layout = resolve(&[("XDG_DATA_HOME", "/data")], true);
+        assert_eq!(
+            layout,
+            DirLayout::single(PathBuf::from("/home/u/.zeptoclaw"))
+        );
+    }
+
+    #[test]
+    fn test_migration_plan_splits_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let legacy = tmp.path().join(".zeptoclaw");
+        for dir in ["sessions", "profiles", "cache"] {
+            std::fs::create_dir_all(legacy.join(dir)).unwrap();
+        }
+        std::fs::write(legacy.join("config.json"), "{}").unwrap();
+
+        let target = DirLayout {
+            config: tmp.path().join("c"),
+            data: tmp.path().join("d"),
+            state: tmp.path().join("s"),
+        };
+        let plan = migration_plan(&legacy, &target).unwrap();
+        let dests: Vec<PathBuf> = plan.into_iter().map(|(_, to)| to).collect();
+        assert_eq!(
+            dests,
+            vec![
+                tmp.path().join("s/cache"),
+                tmp.path().join("c/config.json"),
+                tmp.path().join("c/profiles"),
+                tmp.path().join("d/sessions"),
+            ]
+        );
+    }
+}
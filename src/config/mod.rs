@@ -2,7 +2,10 @@
 //!
 //! This module provides configuration loading, saving, and global state management.
 //! Configuration is loaded from `~/.zeptoclaw/config.json` with environment variable overrides.
+//! The directories themselves are resolved by [`layout::DirLayout`] (`ZEPTOCLAW_HOME`, XDG).
 
+pub mod layout;
+pub mod schema;
 pub mod templates;
 mod types;
 pub mod validate;
@@ -19,12 +22,25 @@ use std::sync::RwLock;
 /// Global configuration instance
 static CONFIG: OnceCell<RwLock<Config>> = OnceCell::new();
 
+/// Profile selected with `--profile`
+static PROFILE: OnceCell<String> = OnceCell::new();
+
 impl Config {
     /// Returns the ZeptoClaw configuration directory path (~/.zeptoclaw)
     pub fn dir() -> PathBuf {
-        dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zeptoclaw")
+        layout::DirLayout::resolve().config
+    }
+
+    /// Returns the data directory: sessions, memory, credentials, skills
+    /// (~/.zeptoclaw, or `$XDG_DATA_HOME/zeptoclaw`)
+    pub fn data_dir() -> PathBuf {
+        layout::DirLayout::resolve().data
+    }
+
+    /// Returns the state directory: caches, metrics, runtime state
+    /// (~/.zeptoclaw, or `$XDG_STATE_HOME/zeptoclaw`)
+    pub fn state_dir() -> PathBuf {
+        layout::DirLayout::resolve().state
     }
 
     /// Returns the path to the config file (~/.zeptoclaw/config.json)
@@ -32,13 +48,48 @@ impl Config {
         Self::dir().join("config.json")
     }
 
+    /// Returns the directory of named profiles (~/.zeptoclaw/profiles)
+    pub fn profiles_dir() -> PathBuf {
+        Self::dir().join("profiles")
+    }
+
+    /// Returns the path of profile `name` (~/.zeptoclaw/profiles/<name>.json)
+    pub fn profile_path(name: &str) -> PathBuf {
+        Self::profiles_dir().join(format!("{}.json", name))
+    }
+
+    /// Select the profile for this process (`--profile`). It takes
+    /// precedence over `ZEPTOCLAW_PROFILE`; only the first call has an effect.
+    pub fn set_profile(name: &str) -> Result<()> {
+        validate_profile_name(name)?;
+        let _ = PROFILE.set(name.to_string());
+        Ok(())
+    }
+
+    /// The active profile: `--profile`, else `ZEPTOCLAW_PROFILE`.
+    pub fn active_profile() -> Option<String> {
+        PROFILE.get().cloned().or_else(|| {
+            std::env::var("ZEPTOCLAW_PROFILE")
+                .ok()
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+        })
+    }
+
     /// Load configuration from the default path with environment overrides.
     ///
     /// If the config file doesn't exist, returns default configuration.
-    /// Environment variables can override config values using the pattern:
+    /// The active profile, if any, is layered over the file. Environment
+    /// variables can override config values using the pattern:
     /// `ZEPTOCLAW_SECTION_SUBSECTION_KEY`
     pub fn load() -> Result<Self> {
-        Self::load_from_path(&Self::path())
+        match Self::active_profile() {
+            Some(name) => {
+                validate_profile_name(&name)?;
+                Self::load_with_profile(&Self::path(), &Self::profile_path(&name))
+            }
+            None => Self::load_from_path(&Self::path()),
+        }
     }
 
     /// Load configuration from a specific path with environment overrides.
@@ -48,20 +99,48 @@ impl Config {
     /// The master key is resolved via `ZEPTOCLAW_MASTER_KEY` env var or, when
     /// running in an interactive terminal, an interactive passphrase prompt.
     pub fn load_from_path(path: &PathBuf) -> Result<Self> {
-        let mut config = if path.exists() {
-            let content = std::fs::read_to_string(path)?;
-            let mut raw: serde_json::Value = serde_json::from_str(&content)?;
-
-            // Decrypt ENC[...] values if present
-            if has_encrypted_values(&raw) {
-                let interactive = std::io::stdin().is_terminal();
-                let enc = crate::security::encryption::resolve_master_key(interactive)?;
-                decrypt_config_values(&mut raw, &enc)?;
-            }
+        let raw = if path.exists() {
+            Some(read_raw(path)?)
+        } else {
+            None
+        };
+        Self::from_raw(raw)
+    }
 
-            serde_json::from_value(raw)?
+    /// Load configuration from `path` with the profile at `profile_path`
+    /// layered over it, then environment overrides.
+    ///
+    /// Profile objects are merged key by key into the base config; any other
+    /// profile value (including arrays and `null`) replaces the base value.
+    pub fn load_with_profile(path: &PathBuf, profile_path: &PathBuf) -> Result<Self> {
+        if !profile_path.exists() {
+            return Err(ZeptoError::Config(format!(
+                "Profile not found: {}",
+                profile_path.display()
+            )));
+        }
+        let mut raw = if path.exists() {
+            read_raw(path)?
         } else {
-            Config::default()
+            serde_json::Value::Object(Default::default())
+        };
+        merge_profile(&mut raw, read_raw(profile_path)?);
+        Self::from_raw(Some(raw))
+    }
+
+    fn from_raw(raw: Option<serde_json::Value>) -> Result<Self> {
+        let mut config = match raw {
+            Some(mut raw) => {
+                // Decrypt ENC[...] values if present
+                if has_encrypted_values(&raw) {
+                    let interactive = std::io::stdin().is_terminal();
+                    let enc = crate::security::encryption::resolve_master_key(interactive)?;
+                    decrypt_config_values(&mut raw, &enc)?;
+                }
+
+                serde_json::from_value(raw)?
+            }
+            None => Config::default(),
         };
 
         // Apply environment variable overrides
@@ -113,6 +192,11 @@ impl Config {
                 self.agents.defaults.agent_timeout_secs = v;
             }
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_TIMEOUT_WRAP_UP_SECS") {
+            if let Ok(v) = val.parse() {
+                self.agents.defaults.timeout_wrap_up_secs = v;
+            }
+        }
         if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_TOOL_TIMEOUT_SECS") {
             if let Ok(v) = val.parse() {
                 self.agents.defaults.tool_timeout_secs = v;
@@ -130,6 +214,11 @@ impl Config {
                 _ => {}
             }
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_MAX_CONCURRENT_SESSIONS") {
+            if let Ok(v) = val.parse() {
+                self.agents.defaults.max_concurrent_sessions = v;
+            }
+        }
         if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_COMPACT_TOOLS") {
             self.agents.defaults.compact_tools = val == "true" || val == "1";
         }
@@ -145,6 +234,9 @@ impl Config {
         if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_TIMEZONE") {
             self.agents.defaults.timezone = val;
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_LANGUAGE") {
+            self.agents.defaults.language = if val.is_empty() { None } else { Some(val) };
+        }
         if let Ok(val) = std::env::var("ZEPTOCLAW_AGENTS_DEFAULTS_LOOP_GUARD_ENABLED") {
             self.agents.defaults.loop_guard.enabled = val == "true" || val == "1";
         }
@@ -279,19 +371,67 @@ impl Config {
 
         // Cache
         self.apply_cache_env_overrides();
+        self.apply_degraded_env_overrides();
+        self.apply_bus_env_overrides();
 
         // Agent mode
         if let Ok(val) = std::env::var("ZEPTOCLAW_SECURITY_AGENT_MODE") {
             self.agent_mode.mode = val;
         }
 
+        // Users
+        if let Ok(val) = std::env::var("ZEPTOCLAW_USERS_ENABLED") {
+            self.users.enabled = val.parse().unwrap_or(false);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_RBAC_ENABLED") {
+            self.rbac.enabled = val.parse().unwrap_or(false);
+        }
+
         // Device pairing
         self.apply_pairing_env_overrides();
 
+        // Daemon
+        if let Ok(val) = std::env::var("ZEPTOCLAW_DAEMON_ALERT") {
+            let val = val.trim();
+            self.daemon.alert = (!val.is_empty()).then(|| val.to_string());
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_DAEMON_ALERT_WEBHOOK") {
+            let val = val.trim();
+            self.daemon.alert_webhook = (!val.is_empty()).then(|| val.to_string());
+        }
+
+        // Update
+        if let Ok(val) = std::env::var("ZEPTOCLAW_UPDATE_CHANNEL") {
+            match val.trim().to_ascii_lowercase().as_str() {
+                "stable" => self.update.channel = UpdateChannel::Stable,
+                "beta" => self.update.channel = UpdateChannel::Beta,
+                _ => {}
+            }
+        }
+
+        // Workspaces
+        if let Ok(val) = std::env::var("ZEPTOCLAW_WORKSPACES_ACTIVE") {
+            let val = val.trim();
+            self.workspaces.active = (!val.is_empty()).then(|| val.to_string());
+        }
+
         // Session
         if let Ok(val) = std::env::var("ZEPTOCLAW_SESSION_AUTO_REPAIR") {
             self.session.auto_repair = val.eq_ignore_ascii_case("true") || val == "1";
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SESSION_TIMELINE") {
+            self.session.timeline = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SESSION_LEDGER") {
+            self.session.ledger = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SESSION_TITLES_ENABLED") {
+            self.session.titles.enabled = val.parse().unwrap_or(false);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SESSION_TITLES_MODEL") {
+            let val = val.trim();
+            self.session.titles.model = (!val.is_empty()).then(|| val.to_string());
+        }
 
         // Transcription
         if let Ok(val) = std::env::var("ZEPTOCLAW_TRANSCRIPTION_MODEL") {
@@ -300,6 +440,32 @@ impl Config {
         if let Ok(val) = std::env::var("ZEPTOCLAW_TRANSCRIPTION_ENABLED") {
             self.transcription.enabled = val == "true" || val == "1";
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TRANSCRIPTION_BACKEND") {
+            match val.trim().to_ascii_lowercase().as_str() {
+                "openai" => self.transcription.backend = TranscriptionProvider::Openai,
+                "deepgram" => self.transcription.backend = TranscriptionProvider::Deepgram,
+                "whisper_cpp" | "whisper-cpp" => {
+                    self.transcription.backend = TranscriptionProvider::WhisperCpp
+                }
+                _ => {}
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TRANSCRIPTION_LANGUAGE") {
+            let val = val.trim();
+            self.transcription.language = (!val.is_empty()).then(|| val.to_string());
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TRANSCRIPTION_DEEPGRAM_API_KEY") {
+            self.transcription.deepgram_api_key = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TRANSCRIPTION_DEEPGRAM_MODEL") {
+            self.transcription.deepgram_model = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TRANSCRIPTION_WHISPER_CPP_BINARY") {
+            self.transcription.whisper_cpp_binary = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TRANSCRIPTION_WHISPER_CPP_MODEL") {
+            self.transcription.whisper_cpp_model = Some(val);
+        }
 
         // Panel (env overrides always applied — PanelConfig is always present in Config)
         if let Ok(val) = std::env::var("ZEPTOCLAW_PANEL_ENABLED") {
@@ -937,6 +1103,28 @@ impl Config {
             channel.enabled = enabled;
         }
 
+        // Voice
+        if let Ok(val) = std::env::var("ZEPTOCLAW_CHANNELS_VOICE_WAKE_WORDS") {
+            let channel = self
+                .channels
+                .voice
+                .get_or_insert_with(VoiceChannelConfig::default);
+            channel.wake_words = val
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+        if let Ok(Ok(enabled)) =
+            std::env::var("ZEPTOCLAW_CHANNELS_VOICE_TTS_ENABLED").map(|v| v.parse::<bool>())
+        {
+            let channel = self
+                .channels
+                .voice
+                .get_or_insert_with(VoiceChannelConfig::default);
+            channel.tts_enabled = enabled;
+        }
+
         // Runtime: Apple Container
         if let Ok(val) = std::env::var("ZEPTOCLAW_RUNTIME_APPLE_ALLOW_EXPERIMENTAL") {
             if let Ok(v) = val.parse() {
@@ -944,6 +1132,17 @@ impl Config {
             }
         }
 
+        // Runtime: Native
+        if let Ok(val) = std::env::var("ZEPTOCLAW_RUNTIME_NATIVE_SHELL") {
+            match val.trim().to_ascii_lowercase().as_str() {
+                "auto" => self.runtime.native_shell = NativeShell::Auto,
+                "sh" => self.runtime.native_shell = NativeShell::Sh,
+                "cmd" => self.runtime.native_shell = NativeShell::Cmd,
+                "powershell" | "pwsh" => self.runtime.native_shell = NativeShell::Powershell,
+                _ => {}
+            }
+        }
+
         // Runtime: Docker
         if let Ok(v) = std::env::var("ZEPTOCLAW_RUNTIME_DOCKER_PIDS_LIMIT") {
             if let Ok(n) = v.parse::<u32>() {
@@ -1014,6 +1213,30 @@ impl Config {
         {
             self.tools.google_sheets.service_account_base64 = Some(val);
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_GOOGLE_SHEETS_SCOPES") {
+            self.tools.google_sheets.scopes = val
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+
+        // Google Drive tool configuration
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_GOOGLE_DRIVE_ACCESS_TOKEN") {
+            self.tools.google_drive.access_token = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_GOOGLE_DRIVE_SERVICE_ACCOUNT_BASE64") {
+            self.tools.google_drive.service_account_base64 = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_GOOGLE_DRIVE_SCOPES") {
+            self.tools.google_drive.scopes = val
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
 
         // Google Workspace tool
         if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_GOOGLE_ACCESS_TOKEN") {
@@ -1026,6 +1249,115 @@ impl Config {
             self.tools.google.client_secret = Some(val);
         }
 
+        // Calendar tool
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_CALENDAR_PROVIDER") {
+            match val.trim().to_lowercase().as_str() {
+                "google" => self.tools.calendar.provider = CalendarProvider::Google,
+                "caldav" => self.tools.calendar.provider = CalendarProvider::Caldav,
+                _ => {}
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_CALENDAR_CALDAV_URL") {
+            self.tools.calendar.caldav_url = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_CALENDAR_CALDAV_USERNAME") {
+            self.tools.calendar.caldav_username = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_CALENDAR_CALDAV_PASSWORD") {
+            self.tools.calendar.caldav_password = Some(val);
+        }
+
+        // Contacts tool
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_CONTACTS_CARDDAV_URL") {
+            self.tools.contacts.carddav_url = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_CONTACTS_CARDDAV_USERNAME") {
+            self.tools.contacts.carddav_username = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_CONTACTS_CARDDAV_PASSWORD") {
+            self.tools.contacts.carddav_password = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_CONTACTS_DEFAULT_COUNTRY_CODE") {
+            self.tools.contacts.default_country_code = Some(val);
+        }
+
+        // Email tool
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_EMAIL_IMAP_HOST") {
+            self.tools.email.imap_host = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_EMAIL_SMTP_HOST") {
+            self.tools.email.smtp_host = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_EMAIL_USERNAME") {
+            self.tools.email.username = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_EMAIL_PASSWORD") {
+            self.tools.email.password = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_EMAIL_AUTH") {
+            match val.trim().to_lowercase().as_str() {
+                "password" => self.tools.email.auth = EmailAuthMethod::Password,
+                "oauth" => self.tools.email.auth = EmailAuthMethod::Oauth,
+                _ => {}
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_EMAIL_ACCESS_TOKEN") {
+            self.tools.email.access_token = Some(val);
+        }
+
+        // RSS tool
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_RSS_DIGEST_ENABLED") {
+            self.tools.rss.digest.enabled = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_RSS_DIGEST_SCHEDULE") {
+            self.tools.rss.digest.schedule = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_RSS_DIGEST_CHANNEL") {
+            self.tools.rss.digest.channel = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_RSS_DIGEST_CHAT_ID") {
+            self.tools.rss.digest.chat_id = val;
+        }
+
+        // Weather tool
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_WEATHER_DEFAULT_LOCATION") {
+            self.tools.weather.default_location = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_WEATHER_UNITS") {
+            match val.trim().to_lowercase().as_str() {
+                "metric" => self.tools.weather.units = WeatherUnits::Metric,
+                "imperial" => self.tools.weather.units = WeatherUnits::Imperial,
+                _ => {}
+            }
+        }
+
+        // Calculator tool
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_CALC_FAST_PATH") {
+            self.tools.calc.fast_path = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_CALC_FX_URL") {
+            self.tools.calc.fx_url = val;
+        }
+
+        // News tool
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_NEWS_PROVIDER") {
+            match val.trim().to_lowercase().as_str() {
+                "google" => self.tools.news.provider = NewsProvider::Google,
+                "newsapi" => self.tools.news.provider = NewsProvider::NewsApi,
+                "gnews" => self.tools.news.provider = NewsProvider::GNews,
+                _ => {}
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_NEWS_API_KEY") {
+            self.tools.news.api_key = Some(val);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_NEWS_COUNTRY") {
+            self.tools.news.country = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_TOOLS_NEWS_LANGUAGE") {
+            self.tools.news.language = val;
+        }
+
         if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_TRANSCRIBE_GROQ_API_KEY") {
             self.tools.transcribe.groq_api_key = Some(v);
         }
@@ -1035,6 +1367,46 @@ impl Config {
         if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_CODING_TOOLS") {
             self.tools.coding_tools = v == "true" || v == "1";
         }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_GIT_SNAPSHOTS_ENABLED") {
+            self.tools.git_snapshots.enabled = v == "true" || v == "1";
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_GITHUB_TOKEN") {
+            self.tools.github.token = Some(v);
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_GITHUB_DEFAULT_REPO") {
+            self.tools.github.default_repo = Some(v);
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_ISSUES_JIRA_URL") {
+            self.tools
+                .issues
+                .jira
+                .get_or_insert_with(Default::default)
+                .url = v;
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_ISSUES_JIRA_EMAIL") {
+            self.tools
+                .issues
+                .jira
+                .get_or_insert_with(Default::default)
+                .email = Some(v);
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_ISSUES_JIRA_TOKEN") {
+            self.tools
+                .issues
+                .jira
+                .get_or_insert_with(Default::default)
+                .token = v;
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_ISSUES_LINEAR_API_KEY") {
+            self.tools
+                .issues
+                .linear
+                .get_or_insert_with(Default::default)
+                .api_key = v;
+        }
+        if let Ok(v) = std::env::var("ZEPTOCLAW_TOOLS_KUBERNETES_ENABLED") {
+            self.tools.kubernetes.enabled = v == "true" || v == "1";
+        }
     }
 
     /// Apply memory-specific environment variable overrides.
@@ -1118,6 +1490,46 @@ impl Config {
                 self.memory.hygiene.max_entries = n;
             }
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_HYGIENE_RESOLVE_CONFLICTS") {
+            self.memory.hygiene.resolve_conflicts = val.parse().unwrap_or(false);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_SYNC_ENABLED") {
+            self.memory.sync.enabled = val.parse().unwrap_or(false);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_SYNC_OBSIDIAN_VAULT") {
+            self.memory
+                .sync
+                .obsidian
+                .get_or_insert_with(Default::default)
+                .vault_path = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_SYNC_NOTION_TOKEN") {
+            self.memory
+                .sync
+                .notion
+                .get_or_insert_with(Default::default)
+                .token = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_SYNC_NOTION_DATABASE_ID") {
+            self.memory
+                .sync
+                .notion
+                .get_or_insert_with(Default::default)
+                .database_id = val;
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_NAMESPACES_ENABLED") {
+            self.memory.namespaces.enabled = val.parse().unwrap_or(false);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_EXTRACTION_ENABLED") {
+            self.memory.extraction.enabled = val.parse().unwrap_or(false);
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_EXTRACTION_MODEL") {
+            let val = val.trim();
+            self.memory.extraction.model = (!val.is_empty()).then(|| val.to_string());
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_MEMORY_EXTRACTION_REQUIRE_REVIEW") {
+            self.memory.extraction.require_review = val.parse().unwrap_or(true);
+        }
     }
 
     /// Apply heartbeat-specific environment variable overrides.
@@ -1143,6 +1555,23 @@ impl Config {
         if let Ok(v) = std::env::var("ZEPTOCLAW_HEARTBEAT_DELIVER_TO") {
             self.heartbeat.deliver_to = if v.is_empty() { None } else { Some(v) };
         }
+
+        if let Ok(val) = std::env::var("ZEPTOCLAW_HEARTBEAT_QUIET_HOURS") {
+            let val = val.trim();
+            if val.is_empty() || val.eq_ignore_ascii_case("off") {
+                self.heartbeat.quiet_hours.enabled = false;
+            } else if let Some((start, end)) = val.split_once('-') {
+                self.heartbeat.quiet_hours.enabled = true;
+                self.heartbeat.quiet_hours.start = start.trim().to_string();
+                self.heartbeat.quiet_hours.end = end.trim().to_string();
+            }
+        }
+
+        if let Ok(val) = std::env::var("ZEPTOCLAW_HEARTBEAT_ADAPTIVE") {
+            if let Ok(v) = val.parse::<bool>() {
+                self.heartbeat.adaptive.enabled = v;
+            }
+        }
     }
 
     /// Apply skills-specific environment variable overrides.
@@ -1176,6 +1605,12 @@ impl Config {
                 .map(str::to_string)
                 .collect();
         }
+
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SKILLS_HOT_RELOAD") {
+            if let Ok(v) = val.parse::<bool>() {
+                self.skills.hot_reload = v;
+            }
+        }
     }
 
     /// Apply safety-layer environment variable overrides.
@@ -1194,6 +1629,34 @@ impl Config {
                 self.safety.max_output_length = v.clamp(1_000, 10_000_000);
             }
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SAFETY_OUTBOUND_CHECK_ENABLED") {
+            self.safety.outbound_check_enabled = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SAFETY_MAX_OUTBOUND_LENGTH") {
+            if let Ok(v) = val.parse::<usize>() {
+                self.safety.max_outbound_length = v;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SAFETY_PII_ENABLED") {
+            self.safety.pii.enabled = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SAFETY_PII_ACTION") {
+            match val.trim().to_ascii_lowercase().as_str() {
+                "warn" => self.safety.pii.action = crate::safety::pii::PiiAction::Warn,
+                "redact" => self.safety.pii.action = crate::safety::pii::PiiAction::Redact,
+                "block" => self.safety.pii.action = crate::safety::pii::PiiAction::Block,
+                _ => {}
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SAFETY_PII_OUTBOUND") {
+            self.safety.pii.outbound = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SAFETY_QUARANTINE_ENABLED") {
+            self.safety.quarantine.enabled = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_SAFETY_FINANCE_READ_ONLY") {
+            self.safety.finance_read_only = val.eq_ignore_ascii_case("true") || val == "1";
+        }
         if let Ok(val) = std::env::var("ZEPTOCLAW_SAFETY_TAINT_ENABLED") {
             self.safety.taint.enabled = val.eq_ignore_ascii_case("true") || val == "1";
         }
@@ -1286,6 +1749,19 @@ impl Config {
                 _ => {}
             }
         }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_ROUTINES_USAGE_REPORT_ENABLED") {
+            self.routines.usage_report.enabled = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_ROUTINES_USAGE_REPORT_DELIVER_TO") {
+            self.routines.usage_report.deliver_to = if val.is_empty() { None } else { Some(val) };
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_ROUTINES_USAGE_REPORT_PERIOD") {
+            match val.to_lowercase().as_str() {
+                "daily" => self.routines.usage_report.period = UsageReportPeriod::Daily,
+                "weekly" => self.routines.usage_report.period = UsageReportPeriod::Weekly,
+                _ => {}
+            }
+        }
         if let Ok(v) = std::env::var("ZEPTOCLAW_HEALTH_ENABLED") {
             self.health.enabled = v == "true" || v == "1";
         }
@@ -1334,6 +1810,46 @@ impl Config {
         }
     }
 
+    /// Apply degraded-mode environment variable overrides.
+    fn apply_degraded_env_overrides(&mut self) {
+        if let Ok(val) = std::env::var("ZEPTOCLAW_DEGRADED_ENABLED") {
+            self.degraded.enabled = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_DEGRADED_RETRY_INTERVAL_SECS") {
+            if let Ok(n) = val.parse::<u64>() {
+                self.degraded.retry_interval_secs = n;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_DEGRADED_NOTIFY") {
+            let val = val.trim();
+            if !val.is_empty() {
+                self.degraded.notify = Some(val.to_string());
+            }
+        }
+    }
+
+    /// Apply message bus environment variable overrides.
+    fn apply_bus_env_overrides(&mut self) {
+        if let Ok(val) = std::env::var("ZEPTOCLAW_BUS_BUFFER_SIZE") {
+            if let Ok(n) = val.parse::<usize>() {
+                self.bus.buffer_size = n;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_BUS_OVERFLOW") {
+            if let Some(policy) = BusOverflowPolicy::parse(&val) {
+                self.bus.overflow = policy;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_BUS_BLOCK_TIMEOUT_MS") {
+            if let Ok(n) = val.parse::<u64>() {
+                self.bus.block_timeout_ms = n;
+            }
+        }
+        if let Ok(val) = std::env::var("ZEPTOCLAW_BUS_NOTIFY_REJECTED") {
+            self.bus.notify_rejected = val.eq_ignore_ascii_case("true") || val == "1";
+        }
+    }
+
     /// Apply device pairing environment variable overrides.
     fn apply_pairing_env_overrides(&mut self) {
         if let Ok(val) = std::env::var("ZEPTOCLAW_SECURITY_PAIRING_ENABLED") {
@@ -1351,8 +1867,18 @@ impl Config {
         }
     }
 
-    /// Save configuration to the default path
+    /// Save configuration to the default path.
+    ///
+    /// Refused while a profile is active: the loaded config includes the
+    /// profile, which must not leak into the base file.
     pub fn save(&self) -> Result<()> {
+        if let Some(name) = Self::active_profile() {
+            return Err(ZeptoError::Config(format!(
+                "Profile '{}' is active; edit {} or run without a profile to change config.json",
+                name,
+                Self::profile_path(&name).display()
+            )));
+        }
         self.save_to_path(&Self::path())
     }
 
@@ -1416,9 +1942,39 @@ impl Config {
         Ok(())
     }
 
-    /// Returns the expanded workspace path (resolves ~ to home directory)
+    /// Returns the expanded workspace path (resolves ~ to home directory):
+    /// the active workspace's directory, else `agents.defaults.workspace`.
     pub fn workspace_path(&self) -> PathBuf {
-        expand_home(&self.agents.defaults.workspace)
+        self.workspaces
+            .active_path()
+            .unwrap_or_else(|| expand_home(&self.agents.defaults.workspace))
+    }
+
+    /// Locale for messages on `channel`: the channel's language in
+    /// `channels.prompts`, else `agents.defaults.language`. `None` when
+    /// neither is set (or the tag is not supported).
+    pub fn locale(&self, channel: &str) -> Option<crate::utils::locale::Locale> {
+        self.channels
+            .prompts
+            .get(channel)
+            .and_then(|p| p.language.as_deref())
+            .or(self.agents.defaults.language.as_deref())
+            .and_then(crate::utils::locale::Locale::parse)
+    }
+
+    /// Returns the workspace skills directory: `skills.workspace_dir`, else
+    /// the active workspace's `skills/`, else `~/.zeptoclaw/skills`.
+    pub fn skills_dir(&self) -> PathBuf {
+        self.skills
+            .workspace_dir
+            .as_deref()
+            .map(expand_home)
+            .or_else(|| {
+                self.workspaces
+                    .active_path()
+                    .map(|path| path.join("skills"))
+            })
+            .unwrap_or_else(|| Self::data_dir().join("skills"))
     }
 
     /// Get the first available API key from configured providers.
@@ -1490,6 +2046,45 @@ impl Config {
     }
 }
 
+/// Read a config file as JSON.
+fn read_raw(path: &PathBuf) -> Result<serde_json::Value> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Profile names are file stems: letters, digits, `-` and `_`.
+fn validate_profile_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ZeptoError::Config(format!(
+            "Invalid profile name '{}' (use letters, digits, '-' and '_')",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Merge a profile into the base config: objects key by key, anything else
+/// replaced.
+fn merge_profile(base: &mut serde_json::Value, profile: serde_json::Value) {
+    match (base, profile) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(profile)) => {
+            for (key, value) in profile {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_profile(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, profile) => *base = profile,
+    }
+}
+
 /// Check if any string value in the JSON tree starts with `ENC[`.
 fn has_encrypted_values(value: &serde_json::Value) -> bool {
     match value {
@@ -1528,7 +2123,10 @@ fn decrypt_config_values(
     Ok(())
 }
 
-/// Expand ~ to home directory in a path string
+/// Expand ~ to home directory in a path string.
+///
+/// `~/.zeptoclaw/...` (the built-in defaults) resolves into the data
+/// directory when `ZEPTOCLAW_HOME` or the XDG layout relocates it.
 pub fn expand_home(path: &str) -> PathBuf {
     if path.is_empty() {
         return PathBuf::from(path);
@@ -1536,6 +2134,12 @@ pub fn expand_home(path: &str) -> PathBuf {
 
     if path.starts_with('~') {
         if let Some(home) = dirs::home_dir() {
+            if let Some(rest) = path.strip_prefix("~/.zeptoclaw") {
+                let data = Config::data_dir();
+                if data != home.join(".zeptoclaw") && (rest.is_empty() || rest.starts_with('/')) {
+                    return data.join(rest.trim_start_matches('/'));
+                }
+            }
             if path.len() > 1 && path.chars().nth(1) == Some('/') {
                 return home.join(&path[2..]);
             }
@@ -1757,6 +2361,8 @@ mod tests {
         env::set_var("ZEPTOCLAW_HEARTBEAT_ENABLED", "true");
         env::set_var("ZEPTOCLAW_HEARTBEAT_INTERVAL_SECS", "900");
         env::set_var("ZEPTOCLAW_HEARTBEAT_FILE_PATH", "/tmp/heartbeat.md");
+        env::set_var("ZEPTOCLAW_HEARTBEAT_QUIET_HOURS", "23:00-06:30");
+        env::set_var("ZEPTOCLAW_HEARTBEAT_ADAPTIVE", "true");
         env::set_var("ZEPTOCLAW_SKILLS_ENABLED", "false");
         env::set_var("ZEPTOCLAW_SKILLS_ALWAYS_LOAD", "github,weather");
         env::set_var("ZEPTOCLAW_SKILLS_DISABLED", "experimental");
@@ -1789,6 +2395,10 @@ mod tests {
             config.heartbeat.file_path,
             Some("/tmp/heartbeat.md".to_string())
         );
+        assert!(config.heartbeat.quiet_hours.enabled);
+        assert_eq!(config.heartbeat.quiet_hours.start, "23:00");
+        assert_eq!(config.heartbeat.quiet_hours.end, "06:30");
+        assert!(config.heartbeat.adaptive.enabled);
         assert!(!config.skills.enabled);
         assert_eq!(
             config.skills.always_load,
@@ -1822,6 +2432,8 @@ mod tests {
         env::remove_var("ZEPTOCLAW_HEARTBEAT_ENABLED");
         env::remove_var("ZEPTOCLAW_HEARTBEAT_INTERVAL_SECS");
         env::remove_var("ZEPTOCLAW_HEARTBEAT_FILE_PATH");
+        env::remove_var("ZEPTOCLAW_HEARTBEAT_QUIET_HOURS");
+        env::remove_var("ZEPTOCLAW_HEARTBEAT_ADAPTIVE");
         env::remove_var("ZEPTOCLAW_SKILLS_ENABLED");
         env::remove_var("ZEPTOCLAW_SKILLS_ALWAYS_LOAD");
         env::remove_var("ZEPTOCLAW_SKILLS_DISABLED");
@@ -2246,4 +2858,56 @@ mod tests {
         std::env::remove_var("ZEPTOCLAW_AGENTS_DEFAULTS_LOOP_GUARD_OUTCOME_BLOCK_THRESHOLD");
         std::env::remove_var("ZEPTOCLAW_AGENTS_DEFAULTS_LOOP_GUARD_WINDOW_SIZE");
     }
+
+    #[test]
+    fn test_load_with_profile_layers_over_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("config.json");
+        let profile = dir.path().join("work.json");
+        std::fs::write(
+            &base,
+            r#"{"tunnel": {"provider": "ngrok", "port": 8080, "max_failures": 3}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            &profile,
+            r#"{"tunnel": {"provider": "tailscale", "port": null}}"#,
+        )
+        .unwrap();
+
+        let config = Config::load_with_profile(&base, &profile).unwrap();
+        assert_eq!(config.tunnel.provider.as_deref(), Some("tailscale"));
+        assert_eq!(config.tunnel.port, None);
+        assert_eq!(config.tunnel.max_failures, 3);
+
+        // A profile alone is layered over defaults.
+        let missing = dir.path().join("none.json");
+        let config = Config::load_with_profile(&missing, &profile).unwrap();
+        assert_eq!(config.tunnel.provider.as_deref(), Some("tailscale"));
+
+        let err = Config::load_with_profile(&base, &missing).unwrap_err();
+        assert!(err.to_string().contains("Profile not found"));
+    }
+
+    #[test]
+    fn test_merge_profile_replaces_arrays() {
+        let mut base = serde_json::json!({"skills": {"disabled": ["a", "b"], "enabled": true}});
+        merge_profile(
+            &mut base,
+            serde_json::json!({"skills": {"disabled": ["c"]}, "extra": 1}),
+        );
+        assert_eq!(
+            base,
+            serde_json::json!({"skills": {"disabled": ["c"], "enabled": true}, "extra": 1})
+        );
+    }
+
+    #[test]
+    fn test_profile_names() {
+        assert!(validate_profile_name("work").is_ok());
+        assert!(validate_profile_name("home_2-dev").is_ok());
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("../config").is_err());
+        assert!(Config::profile_path("work").ends_with("profiles/work.json"));
+    }
 }
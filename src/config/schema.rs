@@ -0,0 +1,689 @@
+//! JSON Schema for the config file.
+//!
+//! The schema is generated from the config types with `schemars`, so it
+//! follows the structs instead of a hand-kept field list. It backs
+//! `zeptoclaw config schema` (editor autocomplete) and the unknown-field,
+//! type and deprecation checks of `zeptoclaw config check`.
+
+use std::collections::HashMap;
+
+use once_cell::sync::OnceCell;
+use serde_json::{Map, Value};
+
+use super::validate::suggest_field;
+use super::Config;
+
+/// Keys still accepted under an old name: (definition, old key, new key).
+///
+/// `schemars` ignores `#[serde(alias)]`, so these are added to the schema
+/// by hand and flagged as deprecated.
+const DEPRECATED_KEYS: &[(&str, &str, &str)] = &[("ChannelsConfig", "whatsapp", "whatsapp_web")];
+
+/// The config JSON Schema (draft 2020-12).
+pub fn config_schema() -> &'static Value {
+    static SCHEMA: OnceCell<Value> = OnceCell::new();
+    SCHEMA.get_or_init(|| {
+        let mut schema = serde_json::to_value(schemars::schema_for!(Config)).unwrap_or_default();
+        add_deprecated_keys(&mut schema);
+        schema
+    })
+}
+
+fn add_deprecated_keys(schema: &mut Value) {
+    for (definition, old, new) in DEPRECATED_KEYS {
+        let pointer = format!("/$defs/{}/properties", definition);
+        let Some(properties) = schema.pointer_mut(&pointer).and_then(Value::as_object_mut) else {
+            continue;
+        };
+        let mut property = match properties.get(*new) {
+            Some(Value::Object(property)) => property.clone(),
+            _ => Map::new(),
+        };
+        property.insert("deprecated".into(), Value::Bool(true));
+        property.insert(
+            "description".into(),
+            Value::String(format!("Deprecated: renamed to `{}`.", new)),
+        );
+        properties.insert(old.to_string(), Value::Object(property));
+    }
+}
+
+/// Kind of a [`SchemaIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaIssueKind {
+    /// A key the schema does not define; serde ignores it.
+    UnknownField,
+    /// A key kept only for compatibility.
+    Deprecated,
+    /// A value the config loader would reject (wrong type, bad enum value,
+    /// missing required field).
+    Invalid,
+}
+
+/// One mismatch between a config value and the schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaIssue {
+    pub kind: SchemaIssueKind,
+    /// Dotted path, e.g. `agents.defaults.model` or `custom_tools[0].name`.
+    pub path: String,
+    pub message: String,
+}
+
+/// Check a raw config value against [`config_schema`].
+pub fn check_config(raw: &Value) -> Vec<SchemaIssue> {
+    check_against(config_schema(), raw)
+}
+
+/// Check `value` against `schema`. Only local `#/$defs/...` references are
+/// followed.
+pub fn check_against(schema: &Value, value: &Value) -> Vec<SchemaIssue> {
+    let walker = Walker {
+        defs: schema.get("$defs").and_then(Value::as_object),
+    };
+    let mut issues = Vec::new();
+    walker.check(value, schema, "", false, &mut issues);
+    issues
+}
+
+struct Walker<'a> {
+    defs: Option<&'a Map<String, Value>>,
+}
+
+impl<'a> Walker<'a> {
+    fn resolve(&self, mut schema: &'a Value) -> &'a Value {
+        // Bounded in case of reference cycles.
+        for _ in 0..32 {
+            let target = schema
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix("#/$defs/"))
+                .and_then(|name| self.defs?.get(name));
+            match target {
+                Some(target) => schema = target,
+                None => break,
+            }
+        }
+        schema
+    }
+
+    /// Property names defined by `schema`, including those of flattened
+    /// subschemas (`allOf`/`anyOf`/`oneOf`).
+    fn known_keys(&self, schema: &'a Value, keys: &mut Vec<&'a str>) {
+        let schema = self.resolve(schema);
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            keys.extend(properties.keys().map(String::as_str));
+        }
+        for combinator in ["allOf", "anyOf", "oneOf"] {
+            for branch in schema
+                .get(combinator)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                self.known_keys(branch, keys);
+            }
+        }
+    }
+
+    /// `lenient` skips unknown-key checks; set for subschemas that only
+    /// describe part of an object (flattened fields).
+    fn check(
+        &self,
+        value: &Value,
+        schema: &'a Value,
+        path: &str,
+        lenient: bool,
+        out: &mut Vec<SchemaIssue>,
+    ) {
+        let schema = self.resolve(schema);
+        let obj = match schema {
+            Value::Object(obj) => obj,
+            Value::Bool(false) => {
+                out.push(invalid(path, "Not allowed here".to_string()));
+                return;
+            }
+            _ => return,
+        };
+        let partial = lenient || obj.contains_key("properties");
+
+        for branch in obj
+            .get("allOf")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            self.check(value, branch, path, true, out);
+        }
+
+        if let Some(branches) = obj
+            .get("anyOf")
+            .or_else(|| obj.get("oneOf"))
+            .and_then(Value::as_array)
+        {
+            let mut trials: Vec<Vec<SchemaIssue>> = branches
+                .iter()
+                .map(|branch| {
+                    let mut issues = Vec::new();
+                    self.check(value, branch, path, partial, &mut issues);
+                    issues
+                })
+                .collect();
+            let matched = trials
+                .iter()
+                .position(|t| t.iter().all(|i| i.kind != SchemaIssueKind::Invalid));
+            let non_null: Vec<usize> = (0..branches.len())
+                .filter(|&i| self.resolve(&branches[i]).get("type") != Some(&Value::from("null")))
+                .collect();
+            match matched {
+                Some(index) => out.append(&mut trials[index]),
+                // `Option<T>`: report why the value is not a `T`.
+                None if non_null.len() == 1 => out.append(&mut trials[non_null[0]]),
+                None => match (value.as_object(), self.union_tag(branches)) {
+                    // Internally tagged enum: point at the tag, then keep
+                    // checking the object's own fields.
+                    (Some(map), Some((tag, tags))) => {
+                        let found = map.get(tag);
+                        match tags.iter().position(|t| Some(*t) == found) {
+                            Some(index) => out.append(&mut trials[index]),
+                            None => out.push(invalid(
+                                &join(path, tag),
+                                match found {
+                                    Some(found) => format!(
+                                        "Invalid value {} \u{2014} expected one of: {}",
+                                        found,
+                                        join_values(tags.into_iter())
+                                    ),
+                                    None => format!(
+                                        "Missing required field '{}' (one of: {})",
+                                        tag,
+                                        join_values(tags.into_iter())
+                                    ),
+                                },
+                            )),
+                        }
+                    }
+                    _ => {
+                        out.push(invalid(
+                            path,
+                            format!(
+                                "Expected {}, found {}",
+                                self.describe_union(branches),
+                                describe_value(value)
+                            ),
+                        ));
+                        return;
+                    }
+                },
+            }
+        }
+
+        if let Some(types) = obj.get("type") {
+            let types: Vec<&str> = match types {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !types.is_empty() && !types.iter().any(|t| type_matches(value, t)) {
+                out.push(invalid(
+                    path,
+                    format!("Expected {}, found {}", types.join(" or "), kind(value)),
+                ));
+                return;
+            }
+        }
+        if let Some(allowed) = obj.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                out.push(invalid(
+                    path,
+                    format!(
+                        "Invalid value {} \u{2014} expected one of: {}",
+                        value,
+                        join_values(allowed.iter())
+                    ),
+                ));
+                return;
+            }
+        }
+        if let Some(expected) = obj.get("const") {
+            if expected != value {
+                out.push(invalid(
+                    path,
+                    format!("Invalid value {} \u{2014} expected {}", value, expected),
+                ));
+                return;
+            }
+        }
+        if let Some(n) = value.as_f64() {
+            if let Some(min) = obj.get("minimum").and_then(Value::as_f64) {
+                if n < min {
+                    out.push(invalid(path, format!("Must be at least {}", min)));
+                }
+            }
+            if let Some(max) = obj.get("maximum").and_then(Value::as_f64) {
+                if n > max {
+                    out.push(invalid(path, format!("Must be at most {}", max)));
+                }
+            }
+        }
+
+        if let Some(map) = value.as_object() {
+            self.check_object(map, obj, schema, path, partial, lenient, out);
+        }
+        if let (Some(items), Some(item_schema)) = (value.as_array(), obj.get("items")) {
+            for (i, item) in items.iter().enumerate() {
+                self.check(item, item_schema, &format!("{}[{}]", path, i), false, out);
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn check_object(
+        &self,
+        map: &Map<String, Value>,
+        obj: &'a Map<String, Value>,
+        schema: &'a Value,
+        path: &str,
+        partial: bool,
+        lenient: bool,
+        out: &mut Vec<SchemaIssue>,
+    ) {
+        for key in obj
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !map.contains_key(key) {
+                out.push(invalid(path, format!("Missing required field '{}'", key)));
+            }
+        }
+
+        let properties = obj.get("properties").and_then(Value::as_object);
+        let additional = obj.get("additionalProperties");
+        let mut known = Vec::new();
+        if partial {
+            self.known_keys(schema, &mut known);
+        }
+        for (key, child) in map {
+            let child_path = join(path, key);
+            if let Some(property) = properties.and_then(|p| p.get(key)) {
+                if property.get("deprecated") == Some(&Value::Bool(true)) {
+                    let reason = property
+                        .get("description")
+                        .and_then(Value::as_str)
+                        .unwrap_or("Deprecated");
+                    out.push(SchemaIssue {
+                        kind: SchemaIssueKind::Deprecated,
+                        path: child_path.clone(),
+                        message: reason.to_string(),
+                    });
+                }
+                self.check(child, property, &child_path, false, out);
+                continue;
+            }
+            match additional {
+                // Maps: every value follows one schema.
+                Some(extra @ Value::Object(_)) => {
+                    self.check(child, extra, &child_path, false, out);
+                }
+                Some(Value::Bool(false)) => {
+                    out.push(invalid(&child_path, unknown_message(key, &known)));
+                }
+                Some(Value::Bool(true)) => {}
+                _ if lenient || known.is_empty() || known.contains(&key.as_str()) => {}
+                _ => out.push(SchemaIssue {
+                    kind: SchemaIssueKind::UnknownField,
+                    path: child_path,
+                    message: unknown_message(key, &known),
+                }),
+            }
+        }
+    }
+
+    /// The property every branch pins with a `const`, with those values.
+    fn union_tag(&self, branches: &'a [Value]) -> Option<(&'a str, Vec<&'a Value>)> {
+        let first = self
+            .resolve(branches.first()?)
+            .get("properties")?
+            .as_object()?;
+        first.iter().find_map(|(tag, _)| {
+            let tags: Option<Vec<&Value>> = branches
+                .iter()
+                .map(|b| {
+                    self.resolve(self.resolve(b).get("properties")?.get(tag)?)
+                        .get("const")
+                })
+                .collect();
+            Some((tag.as_str(), tags?))
+        })
+    }
+
+    fn describe_union(&self, branches: &'a [Value]) -> String {
+        let mut values = Vec::new();
+        let mut types = Vec::new();
+        for branch in branches {
+            let branch = self.resolve(branch);
+            if let Some(value) = branch.get("const") {
+                values.push(value);
+            } else if let Some(allowed) = branch.get("enum").and_then(Value::as_array) {
+                values.extend(allowed);
+            } else if let Some(t) = branch.get("type").and_then(Value::as_str) {
+                types.push(t.to_string());
+            } else {
+                types.push("object".to_string());
+            }
+        }
+        types.dedup();
+        match (values.is_empty(), types.is_empty()) {
+            (false, true) => format!("one of: {}", join_values(values.into_iter())),
+            (false, false) => format!(
+                "one of: {}, or {}",
+                join_values(values.into_iter()),
+                types.join(" or ")
+            ),
+            _ => types.join(" or "),
+        }
+    }
+}
+
+fn invalid(path: &str, message: String) -> SchemaIssue {
+    SchemaIssue {
+        kind: SchemaIssueKind::Invalid,
+        path: path.to_string(),
+        message,
+    }
+}
+
+fn unknown_message(key: &str, known: &[&str]) -> String {
+    match suggest_field(key, known) {
+        Some(suggestion) => format!("Unknown field '{}' \u{2014} {}", key, suggestion),
+        None => format!("Unknown field '{}'", key),
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn join_values<'v>(values: impl Iterator<Item = &'v Value>) -> String {
+    values.map(Value::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn type_matches(value: &Value, schema_type: &str) -> bool {
+    match schema_type {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::String(_) | Value::Number(_) | Value::Bool(_) => value.to_string(),
+        _ => kind(value).to_string(),
+    }
+}
+
+/// Map dotted paths (as in [`SchemaIssue::path`]) to 1-based line numbers
+/// in the JSON text `source`.
+///
+/// Object members map to the line of their key, array elements to the line
+/// where the element starts. Input that is not valid JSON gives a partial
+/// map.
+pub fn line_index(source: &str) -> HashMap<String, usize> {
+    struct Frame {
+        path: String,
+        index: Option<usize>,
+        key: Option<String>,
+    }
+
+    let mut lines = HashMap::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut expecting_key = false;
+    let mut line = 1;
+    let mut chars = source.chars();
+
+    // Path of a value starting now, recording array elements.
+    let value_start =
+        |stack: &[Frame], lines: &mut HashMap<String, usize>, line: usize| match stack.last() {
+            Some(Frame {
+                path,
+                index: Some(i),
+                ..
+            }) => {
+                let path = format!("{}[{}]", path, i);
+                lines.entry(path.clone()).or_insert(line);
+                path
+            }
+            Some(Frame { path, key, .. }) => join(path, key.as_deref().unwrap_or_default()),
+            None => String::new(),
+        };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            '"' => {
+                let start = line;
+                let mut text = String::new();
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    match c {
+                        '\n' => line += 1,
+                        '\\' if !escaped => {
+                            escaped = true;
+                            continue;
+                        }
+                        '"' if !escaped => break,
+                        _ => {}
+                    }
+                    text.push(c);
+                    escaped = false;
+                }
+                let frame = stack.last_mut().filter(|f| f.index.is_none());
+                match frame {
+                    Some(frame) if expecting_key => {
+                        lines.insert(join(&frame.path, &text), start);
+                        frame.key = Some(text);
+                        expecting_key = false;
+                    }
+                    _ => {
+                        value_start(&stack, &mut lines, start);
+                    }
+                }
+            }
+            '{' | '[' => {
+                let path = value_start(&stack, &mut lines, line);
+                stack.push(Frame {
+                    path,
+                    index: (c == '[').then_some(0),
+                    key: None,
+                });
+                expecting_key = c == '{';
+            }
+            '}' | ']' => {
+                stack.pop();
+                expecting_key = false;
+            }
+            ',' => match stack.last_mut() {
+                Some(Frame { index: Some(i), .. }) => *i += 1,
+                _ => expecting_key = true,
+            },
+            c if c.is_whitespace() || c == ':' => {}
+            // Numbers, booleans and null.
+            _ => {
+                value_start(&stack, &mut lines, line);
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issues(raw: Value) -> Vec<(SchemaIssueKind, String)> {
+        check_config(&raw)
+            .into_iter()
+            .map(|i| (i.kind, i.path))
+            .collect()
+    }
+
+    #[test]
+    fn test_schema_covers_config_sections() {
+        let schema = config_schema();
+        assert_eq!(schema["title"], "Config");
+        let properties = schema["properties"].as_object().unwrap();
+        for section in [
+            "agents",
+            "channels",
+            "providers",
+            "safety",
+            "hooks",
+            "session",
+        ] {
+            assert!(properties.contains_key(section), "missing {}", section);
+        }
+        assert_eq!(
+            schema["$defs"]["ChannelsConfig"]["properties"]["whatsapp"]["deprecated"],
+            true
+        );
+    }
+
+    #[test]
+    fn test_default_config_matches_schema() {
+        let raw = serde_json::to_value(Config::default()).unwrap();
+        assert_eq!(check_config(&raw), Vec::new());
+    }
+
+    #[test]
+    fn test_unknown_fields_with_suggestions() {
+        let found = check_config(&json!({
+            "agents": {"defaults": {"modle": "gpt-4"}},
+            "gateway": {"port": 8080, "hots": "0.0.0.0"}
+        }));
+        assert_eq!(found.len(), 2);
+        assert!(found
+            .iter()
+            .all(|i| i.kind == SchemaIssueKind::UnknownField));
+        assert_eq!(found[0].path, "agents.defaults.modle");
+        assert!(found[0].message.contains("did you mean 'model'?"));
+        assert_eq!(found[1].path, "gateway.hots");
+    }
+
+    #[test]
+    fn test_type_mismatches() {
+        let found = check_config(&json!({
+            "gateway": {"port": "8080"},
+            "agents": {"defaults": {"max_tokens": -1, "temperature": "hot"}},
+            "custom_tools": [{"name": "x", "description": "y", "command": 5}]
+        }));
+        let paths: Vec<&str> = found.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "agents.defaults.max_tokens",
+                "agents.defaults.temperature",
+                "custom_tools[0].command",
+                "gateway.port",
+            ]
+        );
+        assert!(found.iter().all(|i| i.kind == SchemaIssueKind::Invalid));
+        assert_eq!(found[3].message, "Expected integer, found string");
+    }
+
+    #[test]
+    fn test_enum_values_and_optional_sections() {
+        let found = check_config(&json!({
+            "safety": {"policy_rules": [{"name": "r", "pattern": "x", "action": "explode"}]},
+            "channels": {"telegram": {"enabled": "yes"}}
+        }));
+        let action = found
+            .iter()
+            .find(|i| i.path == "safety.policy_rules[0].action")
+            .unwrap();
+        assert!(action.message.contains("\"explode\""));
+        assert!(found
+            .iter()
+            .any(|i| i.path == "channels.telegram.enabled" && i.kind == SchemaIssueKind::Invalid));
+        assert!(check_config(&json!({"channels": {"telegram": null}})).is_empty());
+    }
+
+    #[test]
+    fn test_deprecated_alias() {
+        assert_eq!(
+            issues(json!({"channels": {"whatsapp": {"enabled": false}}})),
+            vec![(SchemaIssueKind::Deprecated, "channels.whatsapp".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_flattened_and_tagged_fields() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"name": {"type": "string"}},
+            "oneOf": [
+                {"type": "object", "properties": {"type": {"const": "gpio"}, "pin": {"type": "integer"}}, "required": ["type"]},
+                {"type": "object", "properties": {"type": {"const": "i2c"}, "bus": {"type": "integer"}}, "required": ["type"]}
+            ]
+        });
+        let ok = json!({"name": "led", "type": "gpio", "pin": 4});
+        assert!(check_against(&schema, &ok).is_empty());
+
+        let found = check_against(&schema, &json!({"type": "i2c", "bus": "one"}));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, "bus");
+
+        let found = check_against(&schema, &json!({"name": "led", "type": "spi", "pinn": 4}));
+        assert_eq!(found.len(), 2, "{:?}", found);
+        assert!(found[0].message.contains("\"gpio\", \"i2c\""));
+        assert_eq!(found[1].kind, SchemaIssueKind::UnknownField);
+        assert!(found[1].message.contains("did you mean 'pin'?"));
+    }
+
+    #[test]
+    fn test_line_index() {
+        let source = r#"{
+  "agents": {
+    "defaults": {"model": "gpt-4"}
+  },
+  "custom_tools": [
+    {"name": "a"},
+    {
+      "name": "b\"q"
+    }
+  ],
+  "port": 8080
+}"#;
+        let lines = line_index(source);
+        assert_eq!(lines["agents"], 2);
+        assert_eq!(lines["agents.defaults.model"], 3);
+        assert_eq!(lines["custom_tools[0]"], 6);
+        assert_eq!(lines["custom_tools[1]"], 7);
+        assert_eq!(lines["custom_tools[1].name"], 8);
+        assert_eq!(lines["port"], 11);
+    }
+}
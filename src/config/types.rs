@@ -3,11 +3,12 @@
 //! This module defines all configuration structs used throughout the framework.
 //! All types implement serde traits for JSON serialization and have sensible defaults.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Project management backend selection.
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ProjectBackend {
     /// GitHub Issues REST API.
@@ -20,7 +21,7 @@ pub enum ProjectBackend {
 }
 
 /// Project management tool configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ProjectConfig {
     /// Backend to use (github, jira, linear).
@@ -51,7 +52,7 @@ impl Default for ProjectConfig {
 }
 
 /// Main configuration struct for ZeptoClaw
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 #[derive(Default)]
 pub struct Config {
@@ -135,6 +136,39 @@ pub struct Config {
     /// r8r workflow-engine bridge configuration.
     #[serde(default)]
     pub r8r_bridge: R8rBridgeConfig,
+    /// Graceful degradation when all providers are unavailable.
+    #[serde(default)]
+    pub degraded: DegradedConfig,
+    /// Recent group-chat context injection.
+    #[serde(default)]
+    pub group_history: GroupHistoryConfig,
+    /// Policy for proactive notifications (rate limits, quiet hours, digests).
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    /// Message bus buffer size and overflow behaviour.
+    #[serde(default)]
+    pub bus: BusConfig,
+    /// Linux SBC GPIO/I2C device manifest (feature `peripheral-linux`).
+    #[serde(default)]
+    pub peripherals: PeripheralsConfig,
+    /// Named project workspaces and chat bindings (`zeptoclaw workspace`).
+    #[serde(default)]
+    pub workspaces: WorkspacesConfig,
+    /// Supervisor settings for `zeptoclaw daemon`.
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+    /// Release channel and signature keys for `zeptoclaw update`.
+    #[serde(default)]
+    pub update: UpdateConfig,
+    /// Named users with roles, tool permissions and quotas.
+    #[serde(default)]
+    pub users: crate::security::users::UsersConfig,
+    /// Roles with allowed tool categories, assigned to users and channels.
+    #[serde(default)]
+    pub rbac: crate::security::rbac::RbacConfig,
+    /// Senders allowed to run admin chat commands (`/status`, `/reload config`, ...).
+    #[serde(default)]
+    pub admin: crate::agent::admin::AdminConfig,
 }
 
 // ============================================================================
@@ -142,7 +176,7 @@ pub struct Config {
 // ============================================================================
 
 /// Log output format.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum LogFormat {
     /// Default tracing pretty-print.
@@ -162,7 +196,7 @@ fn default_log_level() -> String {
 }
 
 /// Logging configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LoggingConfig {
     /// Log output format (default: component).
     #[serde(default = "default_log_format")]
@@ -190,7 +224,7 @@ impl Default for LoggingConfig {
 // ============================================================================
 
 /// Device event monitoring configuration.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct DevicesConfig {
     /// Enable device event monitoring (default: false).
     #[serde(default)]
@@ -200,12 +234,132 @@ pub struct DevicesConfig {
     pub monitor_usb: bool,
 }
 
+/// Linux single-board-computer peripherals (gpiod + i2c-dev).
+///
+/// Only devices listed in `devices` are reachable from the agent; each maps
+/// a friendly name ("desk LED", "temp sensor") to a GPIO line or an I2C
+/// register.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PeripheralsConfig {
+    /// GPIO chip used when a device does not name one (e.g. "gpiochip0")
+    pub gpio_chip: String,
+    /// I2C bus used when a device does not name one (`/dev/i2c-N`)
+    pub i2c_bus: u8,
+    /// Device manifest
+    pub devices: Vec<PeripheralDeviceConfig>,
+}
+
+impl Default for PeripheralsConfig {
+    fn default() -> Self {
+        Self {
+            gpio_chip: "gpiochip0".to_string(),
+            i2c_bus: 1,
+            devices: Vec::new(),
+        }
+    }
+}
+
+/// One named device in the peripherals manifest.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PeripheralDeviceConfig {
+    /// Friendly name used by the agent (matched case-insensitively)
+    pub name: String,
+    /// Optional description shown in `list`
+    #[serde(default)]
+    pub description: Option<String>,
+    /// GPIO line or I2C sensor wiring
+    #[serde(flatten)]
+    pub kind: PeripheralKind,
+}
+
+/// Direction of a GPIO line.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GpioDirection {
+    /// Readable only.
+    #[default]
+    Input,
+    /// Writable (and readable).
+    Output,
+}
+
+/// How raw I2C register bytes are decoded into a number.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum I2cValueFormat {
+    /// Unsigned byte.
+    U8,
+    /// Signed byte.
+    I8,
+    /// Unsigned 16-bit, big-endian.
+    #[default]
+    U16be,
+    /// Unsigned 16-bit, little-endian.
+    U16le,
+    /// Signed 16-bit, big-endian.
+    I16be,
+    /// Signed 16-bit, little-endian.
+    I16le,
+}
+
+/// Wiring of a manifest device.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PeripheralKind {
+    /// A single GPIO line.
+    Gpio {
+        /// Line offset on the chip (BCM number on a Raspberry Pi)
+        line: u32,
+        /// Chip override (defaults to `peripherals.gpio_chip`)
+        #[serde(default)]
+        chip: Option<String>,
+        /// Whether the agent may drive the line
+        #[serde(default)]
+        direction: GpioDirection,
+        /// Invert the logical value (e.g. LEDs wired to 3V3)
+        #[serde(default)]
+        active_low: bool,
+    },
+    /// A register on an I2C sensor, converted as
+    /// `((raw >> shift) * scale) + offset`.
+    I2c {
+        /// 7-bit device address (e.g. 0x48)
+        address: u16,
+        /// Register to read
+        #[serde(default)]
+        register: u8,
+        /// Bus override (defaults to `peripherals.i2c_bus`)
+        #[serde(default)]
+        bus: Option<u8>,
+        /// Raw value encoding
+        #[serde(default)]
+        format: I2cValueFormat,
+        /// Right shift applied to the raw value
+        #[serde(default)]
+        shift: u8,
+        /// Multiplier applied after the shift
+        #[serde(default = "default_i2c_scale")]
+        scale: f64,
+        /// Added after scaling
+        #[serde(default)]
+        offset: f64,
+        /// Unit appended to readings (e.g. "°C")
+        #[serde(default)]
+        unit: Option<String>,
+    },
+}
+
+fn default_i2c_scale() -> f64 {
+    1.0
+}
+
 // ============================================================================
 // Panel Configuration
 // ============================================================================
 
 /// Authentication mode for the panel.
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum AuthMode {
     /// Bearer token auth (default) — no login screen.
@@ -218,7 +372,7 @@ pub enum AuthMode {
 }
 
 /// Panel (control panel) configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct PanelConfig {
     /// Whether the panel is enabled.
@@ -250,7 +404,7 @@ impl Default for PanelConfig {
 // ============================================================================
 
 /// Channel target for routing r8r events to a specific messaging channel.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ChannelTarget {
     /// Channel name (e.g. "telegram", "slack").
     pub channel: String,
@@ -263,7 +417,7 @@ pub struct ChannelTarget {
 /// When enabled, ZeptoClaw connects to an r8r instance over WebSocket to
 /// receive workflow events (approvals, execution results, health) and send
 /// back decisions and workflow triggers.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct R8rBridgeConfig {
     /// Whether the r8r bridge is enabled.
@@ -323,7 +477,7 @@ impl Default for R8rBridgeConfig {
 /// When enabled, caches LLM responses keyed by SHA-256 of
 /// `(model, system_prompt, user_prompt)`. Supports TTL expiry and LRU eviction.
 /// Persists to `~/.zeptoclaw/cache/responses.json`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct CacheConfig {
     /// Whether the response cache is enabled.
@@ -344,6 +498,116 @@ impl Default for CacheConfig {
     }
 }
 
+// ============================================================================
+// Degraded Mode Configuration
+// ============================================================================
+
+/// Graceful degradation when every provider is down.
+///
+/// When enabled, provider outages (network errors, 5xx, rate limits,
+/// timeouts) no longer surface as raw errors. The user receives
+/// `ack_message`, the message is queued and retried every
+/// `retry_interval_secs`, and `notify` (a `"channel:chat_id"` target) is
+/// told when the outage starts and ends.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DegradedConfig {
+    /// Whether degraded mode is enabled.
+    pub enabled: bool,
+    /// Acknowledgement sent to the user when their message is queued.
+    pub ack_message: String,
+    /// Seconds between retries of queued messages.
+    pub retry_interval_secs: u64,
+    /// Maximum number of messages held for retry.
+    pub max_queue: usize,
+    /// Retries per message before giving up.
+    pub max_retries: u32,
+    /// Append matching long-term memory entries to the acknowledgement.
+    pub answer_from_memory: bool,
+    /// Admin notification target in `"channel:chat_id"` format.
+    pub notify: Option<String>,
+}
+
+impl Default for DegradedConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ack_message: "I can't reach my language model right now. Your message is queued \
+                          and I'll reply as soon as it's back."
+                .to_string(),
+            retry_interval_secs: 60,
+            max_queue: 50,
+            max_retries: 10,
+            answer_from_memory: true,
+            notify: None,
+        }
+    }
+}
+
+// ============================================================================
+// Group History Configuration
+// ============================================================================
+
+/// Recent group-chat context injection.
+///
+/// When enabled for a channel, group messages that are not addressed to the
+/// bot (no mention, no reply to the bot) are buffered instead of answered.
+/// When the bot is addressed, the last `max_messages` buffered messages are
+/// injected into the system prompt so the agent can follow the conversation.
+/// Channels are opt-in: only channels listed in `channels` are buffered.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct GroupHistoryConfig {
+    /// Master switch.
+    pub enabled: bool,
+    /// Messages injected when the bot is addressed.
+    pub max_messages: usize,
+    /// Buffered messages older than this are dropped.
+    pub max_age_secs: u64,
+    /// Extra trigger words (case-insensitive) that count as addressing the
+    /// bot, e.g. `["@zeptoclaw", "zepto"]`.
+    pub triggers: Vec<String>,
+    /// Per-channel privacy policy, keyed by channel name.
+    pub channels: HashMap<String, GroupHistoryChannelPolicy>,
+}
+
+impl Default for GroupHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_messages: 20,
+            max_age_secs: 3600,
+            triggers: Vec::new(),
+            channels: HashMap::new(),
+        }
+    }
+}
+
+/// Per-channel privacy controls for group history.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct GroupHistoryChannelPolicy {
+    /// Buffer group messages on this channel.
+    pub enabled: bool,
+    /// Replace sender names with stable "Participant N" labels.
+    pub anonymize: bool,
+    /// Override of the global `max_messages` for this channel.
+    pub max_messages: Option<usize>,
+    /// Sender IDs whose messages are never buffered.
+    pub exclude_senders: Vec<String>,
+}
+
+impl Default for GroupHistoryChannelPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            anonymize: false,
+            max_messages: None,
+            exclude_senders: Vec::new(),
+        }
+    }
+}
+
 // ============================================================================
 // Pairing Configuration
 // ============================================================================
@@ -353,7 +617,7 @@ impl Default for CacheConfig {
 /// When enabled, the gateway requires a valid bearer token from paired devices.
 /// Devices are paired via a 6-digit one-time code exchanged for a bearer token.
 /// Tokens are stored as SHA-256 hashes for security.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct PairingConfig {
     /// Whether device pairing is required for gateway access.
@@ -375,16 +639,30 @@ impl Default for PairingConfig {
 }
 
 /// Session validation and auto-repair configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct SessionConfig {
     /// Automatically repair malformed conversation histories when loaded.
     pub auto_repair: bool,
+    /// Record per-turn timelines (provider latency, tool durations, queue
+    /// wait) for `zeptoclaw history timeline`. Off by default: the files
+    /// grow with every turn and are never rotated.
+    pub timeline: bool,
+    /// Record a per-turn event ledger (inbound message, model, tool calls,
+    /// compactions, response, cost) for `zeptoclaw history trace`.
+    pub ledger: bool,
+    /// Generated session titles and topic tags.
+    pub titles: crate::session::titles::TitlesConfig,
 }
 
 impl Default for SessionConfig {
     fn default() -> Self {
-        Self { auto_repair: true }
+        Self {
+            auto_repair: true,
+            timeline: false,
+            ledger: false,
+            titles: crate::session::titles::TitlesConfig::default(),
+        }
     }
 }
 
@@ -401,7 +679,7 @@ fn default_health_port() -> u16 {
 }
 
 /// HTTP health server configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HealthConfig {
     /// Whether the health HTTP server is enabled (default: false).
     #[serde(default)]
@@ -424,12 +702,79 @@ impl Default for HealthConfig {
     }
 }
 
+// ============================================================================
+// Daemon Configuration
+// ============================================================================
+
+/// Supervisor settings for `zeptoclaw daemon`.
+///
+/// The daemon runs the gateway as a child process and restarts it when it
+/// exits with an error. Crashes within `stable_after_secs` of a start double
+/// the restart delay (1s up to 5 min); a longer run resets it. Each crash is
+/// reported with the last `log_tail_lines` lines of gateway output to
+/// `alert_webhook` before the restart and to `alert` (a `"channel:chat_id"`
+/// target) once the restarted gateway's channels are up.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DaemonConfig {
+    /// Gateway output lines kept for crash reports.
+    pub log_tail_lines: usize,
+    /// Uptime after which a crash no longer counts as part of a crash loop.
+    pub stable_after_secs: u64,
+    /// Crash alert target in `"channel:chat_id"` format.
+    pub alert: Option<String>,
+    /// URL receiving a JSON crash report via POST.
+    pub alert_webhook: Option<String>,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            log_tail_lines: 50,
+            stable_after_secs: 300,
+            alert: None,
+            alert_webhook: None,
+        }
+    }
+}
+
+// ============================================================================
+// Update Configuration
+// ============================================================================
+
+/// Release channel followed by `zeptoclaw update`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// Latest full release.
+    #[default]
+    Stable,
+    /// Newest release including pre-releases (`vX.Y.Z-beta.N`).
+    Beta,
+}
+
+/// Self-update settings.
+///
+/// When a public key is set, release artifacts must carry a matching
+/// signature (`<asset>.minisig` for minisign, `<asset>.sig` for cosign) or
+/// the update is refused.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct UpdateConfig {
+    /// Release channel (default: stable).
+    pub channel: UpdateChannel,
+    /// minisign public key: the base64 key line or the whole `.pub` file.
+    pub minisign_public_key: Option<String>,
+    /// cosign public key (PEM, ECDSA P-256) as produced by `cosign generate-key-pair`.
+    pub cosign_public_key: Option<String>,
+}
+
 // ============================================================================
 // Compaction Configuration
 // ============================================================================
 
 /// Context compaction configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct CompactionConfig {
     /// Whether automatic context compaction is enabled.
@@ -461,7 +806,7 @@ impl Default for CompactionConfig {
 // ============================================================================
 
 /// MCP (Model Context Protocol) server configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct McpConfig {
     /// MCP server definitions.
@@ -469,7 +814,7 @@ pub struct McpConfig {
 }
 
 /// Configuration for a single MCP server.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct McpServerConfig {
     /// Human-readable server name (used as tool name prefix).
     pub name: String,
@@ -495,7 +840,7 @@ fn default_mcp_timeout() -> u64 {
 // ============================================================================
 
 /// Routines (event/webhook/cron triggers) configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct RoutinesConfig {
     /// Whether the routines engine is enabled.
@@ -510,6 +855,12 @@ pub struct RoutinesConfig {
     /// Policy for missed schedules when process restarts.
     #[serde(default)]
     pub on_miss: crate::cron::OnMiss,
+    /// Scheduled usage report posted to a channel.
+    pub usage_report: UsageReportConfig,
+    /// Named report templates; a cron job whose message is
+    /// `routine:<name>` runs the template instead (see `cron::template`).
+    #[serde(default)]
+    pub templates: HashMap<String, String>,
 }
 
 impl Default for RoutinesConfig {
@@ -520,6 +871,50 @@ impl Default for RoutinesConfig {
             max_concurrent: 3,
             jitter_ms: 0,
             on_miss: crate::cron::OnMiss::Skip,
+            usage_report: UsageReportConfig::default(),
+            templates: HashMap::new(),
+        }
+    }
+}
+
+/// How often the usage report is posted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageReportPeriod {
+    /// Every day, covering the previous day.
+    Daily,
+    /// Once a week on `weekday`, covering the previous seven days.
+    #[default]
+    Weekly,
+}
+
+/// Usage report routine configuration (`routines.usage_report`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct UsageReportConfig {
+    /// Post usage reports from the gateway.
+    pub enabled: bool,
+    /// Destination in "channel:chat_id" format (e.g., "telegram:123456789").
+    pub deliver_to: Option<String>,
+    /// Report period.
+    pub period: UsageReportPeriod,
+    /// Day weekly reports are posted ("mon" ... "sun").
+    pub weekday: String,
+    /// Local hour (0-23) reports are posted.
+    pub hour: u32,
+    /// Tools and sessions listed in each ranking.
+    pub top: usize,
+}
+
+impl Default for UsageReportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            deliver_to: None,
+            period: UsageReportPeriod::Weekly,
+            weekday: "mon".to_string(),
+            hour: 9,
+            top: 5,
         }
     }
 }
@@ -529,7 +924,7 @@ impl Default for RoutinesConfig {
 // ============================================================================
 
 /// Stripe payment integration configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct StripeConfig {
     /// Stripe secret key (sk_live_... or sk_test_...). Supports ENC[...] encryption.
@@ -555,11 +950,19 @@ impl Default for StripeConfig {
 // ============================================================================
 
 /// Tunnel configuration for exposing local ports via public URLs.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct TunnelConfig {
     /// Tunnel provider name ("cloudflare", "ngrok", "tailscale", or "auto").
     pub provider: Option<String>,
+    /// Local port to expose. Defaults to `gateway.port`.
+    pub port: Option<u16>,
+    /// Seconds between public URL health checks while the gateway runs (0 = disabled).
+    pub health_check_interval_secs: u64,
+    /// Consecutive failed health checks before the tunnel is restarted.
+    pub max_failures: u32,
+    /// Re-register channel webhooks (Telegram, WhatsApp Cloud) whenever the public URL changes.
+    pub register_webhooks: bool,
     /// Cloudflare Tunnel configuration.
     pub cloudflare: Option<CloudflareTunnelConfig>,
     /// ngrok tunnel configuration.
@@ -568,8 +971,23 @@ pub struct TunnelConfig {
     pub tailscale: Option<TailscaleTunnelConfig>,
 }
 
+impl Default for TunnelConfig {
+    fn default() -> Self {
+        Self {
+            provider: None,
+            port: None,
+            health_check_interval_secs: 60,
+            max_failures: 3,
+            register_webhooks: true,
+            cloudflare: None,
+            ngrok: None,
+            tailscale: None,
+        }
+    }
+}
+
 /// Cloudflare Tunnel provider configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct CloudflareTunnelConfig {
     /// Cloudflare Tunnel token for named tunnels. If omitted, uses quick tunnel (trycloudflare.com).
@@ -577,7 +995,7 @@ pub struct CloudflareTunnelConfig {
 }
 
 /// ngrok tunnel provider configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct NgrokTunnelConfig {
     /// ngrok authtoken for authenticated tunnels.
@@ -587,7 +1005,7 @@ pub struct NgrokTunnelConfig {
 }
 
 /// Tailscale Funnel/Serve provider configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct TailscaleTunnelConfig {
     /// Use Tailscale Funnel (public) instead of Serve (tailnet-only). Default: true.
@@ -609,27 +1027,59 @@ fn default_true() -> bool {
 // Transcription Configuration
 // ============================================================================
 
+/// Transcription backend selection.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionProvider {
+    /// OpenAI-compatible `/audio/transcriptions` of the configured providers.
+    #[default]
+    Openai,
+    /// Deepgram `/v1/listen`.
+    Deepgram,
+    /// Local whisper.cpp (`whisper-cli`); requires the `whisper-cpp` feature.
+    WhisperCpp,
+}
+
 /// Configuration for audio transcription (voice messages).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct TranscriptionConfig {
     /// Whether to transcribe audio messages (default: true).
     pub enabled: bool,
-    /// Whisper-compatible model name (default: "whisper-1").
+    /// Backend to use (openai, deepgram, whisper_cpp).
+    pub backend: TranscriptionProvider,
+    /// Whisper-compatible model name for the openai backend (default: "whisper-1").
     pub model: String,
+    /// Expected spoken language (ISO 639-1, e.g. "de"). Defaults to the
+    /// primary subtag of `agents.defaults.language`; unset lets the backend detect it.
+    pub language: Option<String>,
+    /// Deepgram API key.
+    pub deepgram_api_key: Option<String>,
+    /// Deepgram model (default: "nova-2").
+    pub deepgram_model: String,
+    /// whisper.cpp CLI binary (default: "whisper-cli").
+    pub whisper_cpp_binary: String,
+    /// Path to the whisper.cpp ggml model file.
+    pub whisper_cpp_model: Option<String>,
 }
 
 impl Default for TranscriptionConfig {
     fn default() -> Self {
         Self {
             enabled: true,
+            backend: TranscriptionProvider::Openai,
             model: "whisper-1".to_string(),
+            language: None,
+            deepgram_api_key: None,
+            deepgram_model: "nova-2".to_string(),
+            whisper_cpp_binary: "whisper-cli".to_string(),
+            whisper_cpp_model: None,
         }
     }
 }
 
 /// Agent configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 #[derive(Default)]
 pub struct AgentConfig {
@@ -641,7 +1091,7 @@ pub struct AgentConfig {
 ///
 /// Controls ping-pong detection, outcome-aware blocking, poll relaxation,
 /// graduated responses, and backoff scheduling for repeated tool calls.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct LoopGuardConfig {
     /// Master switch to enable/disable the loop guard.
@@ -688,7 +1138,7 @@ impl Default for LoopGuardConfig {
 }
 
 /// Default agent settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct AgentDefaults {
     /// Workspace directory path
@@ -703,10 +1153,17 @@ pub struct AgentDefaults {
     pub max_tool_iterations: u32,
     /// Maximum wall-clock time (seconds) for a single agent run.
     pub agent_timeout_secs: u64,
+    /// Extra time (seconds) after `agent_timeout_secs` for a wrap-up: no new
+    /// tool calls, the model summarizes what it has and the reply is marked
+    /// partial. 0 = fail with a timeout error instead.
+    pub timeout_wrap_up_secs: u64,
     /// Maximum wall-clock time (seconds) for a single tool call. 0 = use agent_timeout_secs.
     pub tool_timeout_secs: u64,
     /// How to handle messages arriving during an active run.
     pub message_queue_mode: MessageQueueMode,
+    /// Sessions the agent loop processes at the same time. Messages of one
+    /// session are always handled in order. 1 = one message at a time.
+    pub max_concurrent_sessions: usize,
     /// Whether to stream the final LLM response token-by-token in CLI mode.
     pub streaming: bool,
     /// Per-session token budget (input + output). 0 = unlimited.
@@ -739,6 +1196,11 @@ pub struct AgentDefaults {
     /// mode where the system prompt must come from config, not CLI flags.
     #[serde(default)]
     pub system_prompt: Option<String>,
+    /// Locale tag (e.g. "de", "en-GB") for replies, status strings and
+    /// dates. Overridden per channel in `channels.prompts`. None = English
+    /// with ISO dates.
+    #[serde(default)]
+    pub language: Option<String>,
 }
 
 /// Detect the system's IANA timezone.
@@ -782,8 +1244,10 @@ impl Default for AgentDefaults {
             temperature: 0.7,
             max_tool_iterations: 20,
             agent_timeout_secs: 300,
+            timeout_wrap_up_secs: 30,
             tool_timeout_secs: 0,
             message_queue_mode: MessageQueueMode::default(),
+            max_concurrent_sessions: 1,
             streaming: true,
             token_budget: 0,
             compact_tools: false,
@@ -794,12 +1258,13 @@ impl Default for AgentDefaults {
             max_tool_result_bytes: default_max_tool_result_bytes(),
             max_tool_calls: None,
             system_prompt: None,
+            language: None,
         }
     }
 }
 
 /// How to handle messages that arrive while an agent run is active.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum MessageQueueMode {
     /// Buffer messages, concatenate into one when current run finishes.
@@ -814,7 +1279,7 @@ pub enum MessageQueueMode {
 // ============================================================================
 
 /// All channel configurations
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct ChannelsConfig {
     /// Telegram bot configuration
@@ -846,13 +1311,52 @@ pub struct ChannelsConfig {
     pub serial: Option<SerialChannelConfig>,
     /// MQTT channel configuration. Requires `mqtt` feature.
     pub mqtt: Option<MqttChannelConfig>,
+    /// Local microphone channel for `zeptoclaw listen`. Requires `voice` feature.
+    pub voice: Option<VoiceChannelConfig>,
     /// Directory for channel plugins (default: ~/.zeptoclaw/channels/)
     #[serde(default)]
     pub channel_plugins_dir: Option<String>,
+    /// Channel name → system prompt / persona for messages on that channel.
+    #[serde(default)]
+    pub prompts: HashMap<String, ChannelPromptConfig>,
+    /// Channel name → automatic translation of messages and replies.
+    #[serde(default)]
+    pub auto_translate: HashMap<String, AutoTranslateConfig>,
+}
+
+/// System prompt and persona for one channel.
+///
+/// Merged into the global prompt by the context builder: `system_prompt`
+/// replaces the base prompt (SOUL.md, skills and memory stay), and
+/// `persona` is added after SOUL.md.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ChannelPromptConfig {
+    /// Replaces the base system prompt. `{{variable}}` placeholders are
+    /// rendered like in prompt templates.
+    pub system_prompt: Option<String>,
+    /// A persona preset (`concise`, `friendly`, `professional`, `creative`,
+    /// `technical`) or free-form persona text.
+    pub persona: Option<String>,
+    /// Locale tag for this channel, overriding `agents.defaults.language`.
+    pub language: Option<String>,
+}
+
+/// Automatic translation for one channel (see `agent::translate`).
+///
+/// Languages are locale tags (`en`, `ms`, `zh-CN`) or language names.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct AutoTranslateConfig {
+    /// Translate incoming messages into this language before the agent
+    /// sees them. The original text is kept in the session.
+    pub inbound_to: Option<String>,
+    /// Translate replies into this language before they are sent.
+    pub outbound_to: Option<String>,
 }
 
 /// Serial (UART) channel configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct SerialChannelConfig {
     /// Whether the channel is enabled.
@@ -882,7 +1386,7 @@ impl Default for SerialChannelConfig {
 }
 
 /// MQTT channel configuration for IoT device communication.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct MqttChannelConfig {
     /// Whether the channel is enabled.
@@ -909,6 +1413,19 @@ pub struct MqttChannelConfig {
     /// Deny all senders unless in allowlist.
     #[serde(default)]
     pub deny_by_default: bool,
+    /// PEM CA bundle for `mqtts://` brokers (system roots when unset).
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    /// PEM client certificate for mutual TLS (requires `client_key_path`).
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    /// PEM private key for mutual TLS.
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+    /// Topic filters the `mqtt_publish` tool may publish to (MQTT `+`/`#`
+    /// wildcards). Empty allows only topics under `publish_prefix`.
+    #[serde(default)]
+    pub tool_publish_topics: Vec<String>,
 }
 
 impl Default for MqttChannelConfig {
@@ -924,6 +1441,10 @@ impl Default for MqttChannelConfig {
             password: String::new(),
             allow_from: Vec::new(),
             deny_by_default: false,
+            ca_cert_path: None,
+            client_cert_path: None,
+            client_key_path: None,
+            tool_publish_topics: Vec::new(),
         }
     }
 }
@@ -941,12 +1462,64 @@ impl std::fmt::Debug for MqttChannelConfig {
             .field("password", &"[redacted]")
             .field("allow_from", &self.allow_from)
             .field("deny_by_default", &self.deny_by_default)
+            .field("ca_cert_path", &self.ca_cert_path)
+            .field("client_cert_path", &self.client_cert_path)
+            .field("client_key_path", &self.client_key_path)
+            .field("tool_publish_topics", &self.tool_publish_topics)
             .finish()
     }
 }
 
+/// Local microphone channel used by `zeptoclaw listen`. Requires `voice` feature.
+///
+/// Audio is captured and spoken through external commands, so any recorder
+/// that writes raw 16 kHz mono signed 16-bit PCM to stdout, and any TTS
+/// engine that reads text from stdin, can be plugged in.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct VoiceChannelConfig {
+    /// Phrases that must start an utterance for it to reach the agent
+    /// (case and punctuation are ignored). Empty means always listening.
+    pub wake_words: Vec<String>,
+    /// Seconds after a reply (or a bare wake word) during which the next
+    /// utterance needs no wake word.
+    pub follow_up_secs: u64,
+    /// Capture command (argv). Empty picks `arecord` on Linux, `rec` (SoX)
+    /// elsewhere.
+    pub capture_command: Vec<String>,
+    /// TTS command (argv); the reply text is written to its stdin. Empty
+    /// picks `say` on macOS, `espeak-ng` elsewhere.
+    pub tts_command: Vec<String>,
+    /// Speak replies. When false replies are only printed.
+    pub tts_enabled: bool,
+    /// Speech threshold as a multiple of the tracked noise floor.
+    pub vad_threshold: f32,
+    /// Trailing silence that ends an utterance, in milliseconds.
+    pub silence_ms: u64,
+    /// Utterances with less voiced audio than this are dropped, in milliseconds.
+    pub min_speech_ms: u64,
+    /// Hard cap on one utterance, in seconds.
+    pub max_utterance_secs: u64,
+}
+
+impl Default for VoiceChannelConfig {
+    fn default() -> Self {
+        Self {
+            wake_words: vec!["hey zepto".to_string()],
+            follow_up_secs: 8,
+            capture_command: Vec::new(),
+            tts_command: Vec::new(),
+            tts_enabled: true,
+            vad_threshold: 3.0,
+            silence_ms: 800,
+            min_speech_ms: 300,
+            max_utterance_secs: 30,
+        }
+    }
+}
+
 /// Webhook inbound channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WebhookConfig {
     /// Whether the channel is enabled
     #[serde(default)]
@@ -1031,7 +1604,7 @@ fn default_telegram_allow_usernames() -> bool {
 }
 
 /// Telegram channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct TelegramConfig {
     /// Whether the channel is enabled
@@ -1056,6 +1629,10 @@ pub struct TelegramConfig {
     /// New configs should keep this disabled and use numeric Telegram user IDs only.
     #[serde(default = "default_telegram_allow_usernames")]
     pub allow_usernames: bool,
+    /// Receive updates through a webhook on the gateway HTTP server instead
+    /// of long polling. Absent = long polling.
+    #[serde(default)]
+    pub webhook: Option<TelegramWebhookConfig>,
 }
 
 impl Default for TelegramConfig {
@@ -1067,12 +1644,37 @@ impl Default for TelegramConfig {
             allow_from: Vec::new(),
             deny_by_default: false,
             allow_usernames: default_telegram_allow_usernames(),
+            webhook: None,
+        }
+    }
+}
+
+/// Telegram webhook receive mode, served by the gateway HTTP server.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct TelegramWebhookConfig {
+    /// URL path Telegram posts updates to.
+    pub path: String,
+    /// Secret Telegram echoes in `X-Telegram-Bot-Api-Secret-Token`
+    /// (1-256 chars of `A-Z a-z 0-9 _ -`). Derived from the bot token if unset.
+    pub secret_token: Option<String>,
+    /// Public base URL the gateway is reachable at (e.g. behind a reverse
+    /// proxy). When unset, the webhook is registered at the tunnel URL.
+    pub public_url: Option<String>,
+}
+
+impl Default for TelegramWebhookConfig {
+    fn default() -> Self {
+        Self {
+            path: "/telegram/webhook".to_string(),
+            secret_token: None,
+            public_url: None,
         }
     }
 }
 
 /// Discord channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct DiscordConfig {
     /// Whether the channel is enabled
     #[serde(default)]
@@ -1088,7 +1690,7 @@ pub struct DiscordConfig {
 }
 
 /// Slack channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct SlackConfig {
     /// Whether the channel is enabled
     #[serde(default)]
@@ -1109,7 +1711,7 @@ pub struct SlackConfig {
 ///
 /// Uses Meta's webhook system for inbound messages and the Cloud API
 /// for outbound replies. Does not require the whatsmeow-rs bridge.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WhatsAppCloudConfig {
     /// Whether the channel is enabled.
     #[serde(default)]
@@ -1126,6 +1728,10 @@ pub struct WhatsAppCloudConfig {
     /// Optional Meta app secret used to verify `X-Hub-Signature-256` callback signatures.
     #[serde(default)]
     pub app_secret: Option<String>,
+    /// Meta app ID. With `app_secret`, lets the gateway re-register the webhook
+    /// callback when its tunnel URL changes.
+    #[serde(default)]
+    pub app_id: Option<String>,
     /// Address to bind the webhook HTTP server to.
     #[serde(default = "default_whatsapp_cloud_bind")]
     pub bind_address: String,
@@ -1141,6 +1747,20 @@ pub struct WhatsAppCloudConfig {
     /// When true, empty `allow_from` rejects all senders (strict mode).
     #[serde(default)]
     pub deny_by_default: bool,
+    /// Mark accepted inbound messages as read (blue ticks).
+    #[serde(default = "default_true")]
+    pub read_receipts: bool,
+    /// Approved template sent instead of a reply once the 24-hour customer
+    /// service window has closed. Without it, such replies fail.
+    #[serde(default)]
+    pub fallback_template: Option<String>,
+    /// Language code of `fallback_template`.
+    #[serde(default = "default_whatsapp_template_language")]
+    pub fallback_template_language: String,
+}
+
+fn default_whatsapp_template_language() -> String {
+    "en_US".to_string()
 }
 
 fn default_whatsapp_cloud_bind() -> String {
@@ -1163,11 +1783,15 @@ impl Default for WhatsAppCloudConfig {
             access_token: String::new(),
             webhook_verify_token: String::new(),
             app_secret: None,
+            app_id: None,
             bind_address: default_whatsapp_cloud_bind(),
             port: default_whatsapp_cloud_port(),
             path: default_whatsapp_cloud_path(),
             allow_from: Vec::new(),
             deny_by_default: false,
+            read_receipts: true,
+            fallback_template: None,
+            fallback_template_language: default_whatsapp_template_language(),
         }
     }
 }
@@ -1177,7 +1801,7 @@ impl Default for WhatsAppCloudConfig {
 /// Uses wa-rs for direct WhatsApp Web protocol support. No Meta Business
 /// account required — pairs via QR code like WhatsApp Desktop.
 /// Requires: `--features whatsapp-web`
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WhatsAppWebConfig {
     /// Whether the channel is enabled.
     #[serde(default)]
@@ -1211,7 +1835,7 @@ impl Default for WhatsAppWebConfig {
 }
 
 /// Feishu (Lark) channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct FeishuConfig {
     /// Whether the channel is enabled
     #[serde(default)]
@@ -1238,7 +1862,7 @@ pub struct FeishuConfig {
 ///
 /// Uses the Lark WS long-connection (pbbp2) for receiving events —
 /// no public HTTPS endpoint required.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct LarkConfig {
     /// Whether the channel is enabled
     #[serde(default)]
@@ -1262,7 +1886,7 @@ pub struct LarkConfig {
 }
 
 /// MaixCam channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MaixCamConfig {
     /// Whether the channel is enabled
     #[serde(default)]
@@ -1302,7 +1926,7 @@ impl Default for MaixCamConfig {
 }
 
 /// QQ channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct QQConfig {
     /// Whether the channel is enabled
     #[serde(default)]
@@ -1320,7 +1944,7 @@ pub struct QQConfig {
 }
 
 /// DingTalk channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct DingTalkConfig {
     /// Whether the channel is enabled
     #[serde(default)]
@@ -1342,7 +1966,7 @@ pub struct DingTalkConfig {
 // ============================================================================
 
 /// All LLM provider configurations
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct ProvidersConfig {
     /// Anthropic Claude configuration
@@ -1391,7 +2015,7 @@ pub struct ProvidersConfig {
 }
 
 /// Generic provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct ProviderConfig {
     /// API key for authentication
     #[serde(default)]
@@ -1440,7 +2064,7 @@ impl ProviderConfig {
 ///   }
 /// }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProviderPluginConfig {
     /// Unique provider name. Plugin providers activate when no built-in provider (Anthropic/OpenAI) is configured.
     pub name: String,
@@ -1452,7 +2076,7 @@ pub struct ProviderPluginConfig {
 }
 
 /// Retry behavior for runtime provider calls.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct RetryConfig {
     /// Enable automatic retry for transient provider errors.
@@ -1480,7 +2104,7 @@ impl Default for RetryConfig {
 }
 
 /// Fallback behavior across multiple configured runtime providers.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct FallbackConfig {
     /// Enable provider fallback (primary -> secondary) when possible.
@@ -1490,7 +2114,7 @@ pub struct FallbackConfig {
 }
 
 /// Provider rotation configuration for 3+ health-aware providers.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct RotationConfig {
     /// Enable provider rotation.
@@ -1535,7 +2159,7 @@ impl Default for RotationConfig {
 // ============================================================================
 
 /// Rate limiting configuration for gateway endpoints.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct RateLimitConfig {
     /// Max pairing requests per minute per IP (0 = unlimited).
@@ -1545,7 +2169,7 @@ pub struct RateLimitConfig {
 }
 
 /// Startup guard configuration — degrade after consecutive crashes.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct StartupGuardConfig {
     /// Enable startup guard (default: true).
@@ -1567,7 +2191,7 @@ impl Default for StartupGuardConfig {
 }
 
 /// Gateway server configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct GatewayConfig {
     /// Host to bind to
@@ -1598,7 +2222,7 @@ impl Default for GatewayConfig {
 // ============================================================================
 
 /// Voice transcription tool configuration.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct TranscribeConfig {
     /// Enable the transcribe tool
@@ -1616,7 +2240,7 @@ fn default_transcribe_model() -> String {
 }
 
 /// Tools configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct ToolsConfig {
     /// Web tools configuration
@@ -1625,9 +2249,51 @@ pub struct ToolsConfig {
     pub whatsapp: WhatsAppToolConfig,
     /// Google Sheets tool configuration
     pub google_sheets: GoogleSheetsToolConfig,
+    /// Google Drive tool configuration (files and Docs)
+    #[serde(default)]
+    pub google_drive: GoogleDriveToolConfig,
     /// Google Workspace tool configuration (Gmail + Calendar)
     #[serde(default)]
     pub google: GoogleToolConfig,
+    /// Calendar tool configuration (CalDAV or Google Calendar)
+    #[serde(default)]
+    pub calendar: CalendarToolConfig,
+    /// Contacts tool configuration (optional CardDAV sync)
+    #[serde(default)]
+    pub contacts: ContactsToolConfig,
+    /// Email tool configuration (IMAP search/read, SMTP send)
+    #[serde(default)]
+    pub email: EmailToolConfig,
+    /// RSS/Atom feed tool configuration (subscriptions + digest routine)
+    #[serde(default)]
+    pub rss: RssToolConfig,
+    /// Ranking tool configuration (named scoring rubrics + history)
+    #[serde(default)]
+    pub ranking: RankingToolConfig,
+    /// Weather tool configuration (Open-Meteo; default location + units)
+    #[serde(default)]
+    pub weather: WeatherToolConfig,
+    /// News tool configuration (headline provider, region + language)
+    #[serde(default)]
+    pub news: NewsToolConfig,
+    /// Calculator tool configuration (FX rates + pre-LLM fast path)
+    #[serde(default)]
+    pub calc: CalcToolConfig,
+    /// Serial/UART tool configuration (requires `hardware` feature)
+    #[serde(default)]
+    pub serial: SerialToolConfig,
+    /// SQL database tool configuration (requires `tool-sql` feature)
+    #[serde(default)]
+    pub sql: SqlToolConfig,
+    /// Kubernetes (kubectl) tool configuration
+    #[serde(default)]
+    pub kubernetes: KubernetesToolConfig,
+    /// GitHub tool configuration (issues, pull requests, notifications)
+    #[serde(default)]
+    pub github: GitHubToolConfig,
+    /// Issue tracker tool configuration (Jira, Linear)
+    #[serde(default)]
+    pub issues: IssuesToolConfig,
     /// HTTP request tool configuration
     pub http_request: Option<HttpRequestConfig>,
     /// Voice transcription tool configuration
@@ -1646,13 +2312,105 @@ pub struct ToolsConfig {
     /// Example: `"tools": { "coding_tools": true }`
     #[serde(default)]
     pub coding_tools: bool,
+    /// Commit workspace changes to a local git repo after each agent turn
+    #[serde(default)]
+    pub git_snapshots: GitSnapshotConfig,
     /// Tools to deny (disable). Set by startup guard in degraded mode.
     #[serde(default)]
     pub deny: Vec<String>,
+    /// Per-tool time limit and retry overrides, keyed by tool name.
+    ///
+    /// Example: `"limits": { "web_fetch": { "timeout_secs": 20, "retries": 2 } }`
+    #[serde(default)]
+    pub limits: HashMap<String, ToolLimitsConfig>,
+    /// Network egress policy for HTTP-based tools.
+    #[serde(default)]
+    pub egress: EgressConfig,
+}
+
+/// Network egress policy shared by every HTTP-based tool (`tools.egress`).
+///
+/// Enforced by the client factory in `utils::http`: host allow/deny lists
+/// are checked on every request and redirect hop, and DNS answers pointing
+/// at private or local addresses are refused.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct EgressConfig {
+    /// Hosts tools may reach. Empty = any public host. `*.example.com`
+    /// matches the domain and its subdomains.
+    pub allowed_domains: Vec<String>,
+    /// Hosts tools may never reach, checked before `allowed_domains`.
+    pub denied_domains: Vec<String>,
+    /// Refuse hosts that resolve to private, loopback or link-local
+    /// addresses. Default: true.
+    pub block_private_networks: bool,
+    /// Hosts exempt from `block_private_networks`, for self-hosted services
+    /// such as a LAN CalDAV server. Default: `["localhost"]`.
+    pub private_hosts: Vec<String>,
+    /// Request timeout in seconds. Default: 30.
+    pub timeout_secs: u64,
+    /// Maximum redirects followed per request. Default: 5.
+    pub max_redirects: usize,
+    /// Maximum response body size in bytes. Default: 10MB.
+    pub max_response_bytes: usize,
+}
+
+impl Default for EgressConfig {
+    fn default() -> Self {
+        Self {
+            allowed_domains: Vec::new(),
+            denied_domains: Vec::new(),
+            block_private_networks: true,
+            private_hosts: vec!["localhost".to_string()],
+            timeout_secs: 30,
+            max_redirects: 5,
+            max_response_bytes: 10 * 1024 * 1024,
+        }
+    }
+}
+
+/// Time limit and retries for one tool, overriding the tool's own values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ToolLimitsConfig {
+    /// Seconds a single call may run before it is abandoned.
+    pub timeout_secs: Option<u64>,
+    /// Extra attempts after a call fails or times out.
+    pub retries: Option<u32>,
+}
+
+/// Git-backed workspace snapshots.
+///
+/// When enabled, every agent turn that ran tools ends with a commit of all
+/// workspace changes (the `.zeptoclaw/undo` journal excluded), so `git log`
+/// and `git diff` show everything the agent touched.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct GitSnapshotConfig {
+    /// Commit after each turn. Default: false.
+    pub enabled: bool,
+    /// Run `git init` when the workspace is not inside a repository yet.
+    /// Default: true.
+    pub init_repo: bool,
+    /// Commit author name. Default: "ZeptoClaw".
+    pub author_name: String,
+    /// Commit author email. Default: "zeptoclaw@localhost".
+    pub author_email: String,
+}
+
+impl Default for GitSnapshotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            init_repo: true,
+            author_name: "ZeptoClaw".to_string(),
+            author_email: "zeptoclaw@localhost".to_string(),
+        }
+    }
 }
 
 /// Configuration for the HTTP request tool.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct HttpRequestConfig {
     /// Allowlist of domains the agent may call. Required — tool fails fast if empty.
     #[serde(default)]
@@ -1663,6 +2421,28 @@ pub struct HttpRequestConfig {
     /// Maximum response body size in bytes. Default: 512KB.
     #[serde(default = "default_http_request_max_bytes")]
     pub max_response_bytes: usize,
+    /// OpenAPI specs keyed by API name. Each operation becomes a typed tool
+    /// named `{api}_{operation_id}`.
+    #[serde(default)]
+    pub apis: HashMap<String, ApiSpecConfig>,
+}
+
+/// An OpenAPI 3 spec exposed as tools (`tools.http_request.apis.<name>`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ApiSpecConfig {
+    /// Path to the spec file (JSON or YAML).
+    pub spec: String,
+    /// Base URL for requests. Default: the spec's first `servers` entry.
+    pub base_url: Option<String>,
+    /// Header carrying credentials. Default: `Authorization`.
+    pub auth_header: Option<String>,
+    /// Header value, e.g. `Bearer <token>`.
+    pub auth_value: Option<String>,
+    /// Environment variable holding the header value (overrides `auth_value`).
+    pub auth_env: Option<String>,
+    /// Operation IDs to expose. Empty = all operations.
+    pub operations: Vec<String>,
 }
 
 fn default_http_request_timeout() -> u64 {
@@ -1674,7 +2454,7 @@ fn default_http_request_max_bytes() -> usize {
 }
 
 /// Web tools configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct WebToolsConfig {
     /// Web search configuration
@@ -1682,7 +2462,7 @@ pub struct WebToolsConfig {
 }
 
 /// Web search configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct WebSearchConfig {
     /// Search provider: "brave", "searxng", "ddg" (default: auto-detect)
@@ -1710,7 +2490,7 @@ impl Default for WebSearchConfig {
 }
 
 /// WhatsApp Cloud API tool configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct WhatsAppToolConfig {
     /// WhatsApp Business account ID (optional, informational)
@@ -1742,7 +2522,7 @@ impl Default for WhatsAppToolConfig {
 }
 
 /// Google Sheets tool configuration.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct GoogleSheetsToolConfig {
     /// OAuth bearer access token (recommended for tool usage)
@@ -1751,10 +2531,31 @@ pub struct GoogleSheetsToolConfig {
     /// Optional service account JSON encoded as base64
     #[serde(default)]
     pub service_account_base64: Option<String>,
+    /// OAuth scopes requested for the service account, as full URLs or
+    /// short names like `spreadsheets.readonly` (default: `spreadsheets`)
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// Google Drive tool configuration. Without credentials of its own the
+/// tool uses the Google Sheets ones.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(default)]
+pub struct GoogleDriveToolConfig {
+    /// OAuth bearer access token
+    #[serde(default)]
+    pub access_token: Option<String>,
+    /// Optional service account JSON encoded as base64
+    #[serde(default)]
+    pub service_account_base64: Option<String>,
+    /// OAuth scopes requested for the service account (default:
+    /// `drive.readonly` and `drive.file`)
+    #[serde(default)]
+    pub scopes: Vec<String>,
 }
 
 /// Google Workspace tool configuration (Gmail + Calendar).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct GoogleToolConfig {
     /// OAuth bearer access token (fallback when no stored OAuth session)
@@ -1784,47 +2585,677 @@ impl Default for GoogleToolConfig {
     }
 }
 
-// ============================================================================
-// Memory Configuration
-// ============================================================================
-
-/// Memory backend selection.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+/// Calendar tool backend.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
-pub enum MemoryBackend {
-    /// Disable memory tools.
-    #[serde(rename = "none")]
-    Disabled,
-    /// Built-in substring search (default, zero cost).
+pub enum CalendarProvider {
+    /// Google Calendar via OAuth (`zeptoclaw auth login google`) or
+    /// `tools.google.access_token`.
     #[default]
-    Builtin,
-    /// BM25 keyword scoring (feature: memory-bm25).
-    Bm25,
-    /// LLM embedding + cosine similarity (feature: memory-embedding).
-    Embedding,
-    /// HNSW approximate nearest neighbor (feature: memory-hnsw).
-    Hnsw,
-    /// Tantivy full-text search engine (feature: memory-tantivy).
-    Tantivy,
-    /// QMD backend (falls back safely when unavailable).
-    Qmd,
+    Google,
+    /// Any CalDAV server (Nextcloud, Fastmail, iCloud, Radicale, ...).
+    Caldav,
 }
 
-/// Memory citation mode.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
-pub enum MemoryCitationsMode {
-    /// Show citations depending on channel context.
-    #[default]
-    Auto,
-    /// Always include citations in snippets.
+/// Calendar tool configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CalendarToolConfig {
+    /// Backend to use
+    pub provider: CalendarProvider,
+    /// Google calendar ID. Falls back to `tools.google.default_calendar`.
+    pub calendar_id: Option<String>,
+    /// CalDAV calendar collection URL
+    pub caldav_url: Option<String>,
+    /// CalDAV username (basic auth)
+    pub caldav_username: Option<String>,
+    /// CalDAV password or app password (basic auth)
+    pub caldav_password: Option<String>,
+    /// Default window for listing upcoming events, in days
+    pub lookahead_days: u32,
+    /// Maximum events returned by list
+    pub max_results: u32,
+}
+
+impl Default for CalendarToolConfig {
+    fn default() -> Self {
+        Self {
+            provider: CalendarProvider::Google,
+            calendar_id: None,
+            caldav_url: None,
+            caldav_username: None,
+            caldav_password: None,
+            lookahead_days: 7,
+            max_results: 25,
+        }
+    }
+}
+
+/// Contacts tool configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ContactsToolConfig {
+    /// CardDAV address book collection URL (pull-only sync)
+    pub carddav_url: Option<String>,
+    /// CardDAV username (basic auth)
+    pub carddav_username: Option<String>,
+    /// CardDAV password or app password (basic auth)
+    pub carddav_password: Option<String>,
+    /// Country calling code applied to local numbers starting with 0 (e.g. "60")
+    pub default_country_code: Option<String>,
+}
+
+/// How the email tool authenticates to IMAP and SMTP.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailAuthMethod {
+    /// Username + (app) password.
+    #[default]
+    Password,
+    /// OAuth access token via XOAUTH2.
+    Oauth,
+}
+
+/// Email tool configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct EmailToolConfig {
+    /// IMAP server hostname (e.g. `imap.gmail.com`)
+    pub imap_host: String,
+    /// IMAP server port (implicit TLS)
+    pub imap_port: u16,
+    /// SMTP server hostname (e.g. `smtp.gmail.com`)
+    pub smtp_host: String,
+    /// SMTP server port (STARTTLS)
+    pub smtp_port: u16,
+    /// Login username, also used as the From address
+    pub username: String,
+    /// Authentication method
+    pub auth: EmailAuthMethod,
+    /// App password (auth = "password")
+    pub password: Option<String>,
+    /// Auth store provider holding the OAuth token (auth = "oauth")
+    pub oauth_provider: String,
+    /// Static OAuth access token, used when the auth store has none
+    pub access_token: Option<String>,
+    /// Mailbox searched and read by default
+    pub mailbox: String,
+    /// Mailbox that drafts are also saved to (e.g. "Drafts"); unset keeps
+    /// drafts in memory only
+    pub drafts_mailbox: Option<String>,
+    /// Optional display name for the From header
+    pub display_name: Option<String>,
+    /// Maximum messages returned by search
+    pub max_results: u32,
+    /// Message bodies longer than this are truncated by read
+    pub max_body_chars: usize,
+}
+
+impl Default for EmailToolConfig {
+    fn default() -> Self {
+        Self {
+            imap_host: String::new(),
+            imap_port: 993,
+            smtp_host: String::new(),
+            smtp_port: 587,
+            username: String::new(),
+            auth: EmailAuthMethod::Password,
+            password: None,
+            oauth_provider: "google".to_string(),
+            access_token: None,
+            mailbox: "INBOX".to_string(),
+            drafts_mailbox: None,
+            display_name: None,
+            max_results: 20,
+            max_body_chars: 8000,
+        }
+    }
+}
+
+/// Serial/UART tool configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SerialToolConfig {
+    /// Baud rate used when a port is opened without an explicit rate
+    pub default_baud_rate: u32,
+    /// Restrict the tool to these port paths (empty = any USB serial device)
+    pub allowed_ports: Vec<String>,
+}
+
+impl Default for SerialToolConfig {
+    fn default() -> Self {
+        Self {
+            default_baud_rate: 115_200,
+            allowed_ports: Vec::new(),
+        }
+    }
+}
+
+/// SQL database tool configuration.
+///
+/// Queries run read-only through the `sql` tool. Connections with
+/// `allow_writes` also get the `sql_execute` tool, which is in the default
+/// approval list.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SqlToolConfig {
+    /// Named connections, e.g. `{"analytics": {"url": "postgres://..."}}`
+    pub connections: HashMap<String, SqlConnectionConfig>,
+    /// Maximum rows returned by a query
+    pub max_rows: usize,
+    /// Query output longer than this many bytes is truncated
+    pub max_output_bytes: usize,
+    /// Per-statement timeout in seconds
+    pub timeout_secs: u64,
+}
+
+impl Default for SqlToolConfig {
+    fn default() -> Self {
+        Self {
+            connections: HashMap::new(),
+            max_rows: 200,
+            max_output_bytes: 32 * 1024,
+            timeout_secs: 30,
+        }
+    }
+}
+
+/// One named SQL connection.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SqlConnectionConfig {
+    /// Connection URL: `postgres://`, `mysql://` or `sqlite:` (file path)
+    pub url: String,
+    /// Allow write statements through `sql_execute`. Default: false.
+    pub allow_writes: bool,
+    /// What the database holds, shown to the agent
+    pub description: String,
+}
+
+/// Kubernetes tool configuration.
+///
+/// `kubectl` runs read verbs (get, describe, logs, ...) through the container
+/// runtime. Write verbs go through `kubectl_write`, which is in the default
+/// approval list.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct KubernetesToolConfig {
+    /// Register the kubectl tools. Default: false.
+    pub enabled: bool,
+    /// kubectl binary inside the runtime
+    pub kubectl_path: String,
+    /// kubeconfig file; mounted read-only and exported as `KUBECONFIG`
+    pub kubeconfig: Option<String>,
+    /// kubeconfig context to use instead of the current one
+    pub context: Option<String>,
+    /// Namespaces the agent may touch; empty allows any namespace
+    pub allowed_namespaces: Vec<String>,
+    /// Namespace used when a call names none
+    pub default_namespace: String,
+    /// Verbs allowed through `kubectl`
+    pub read_verbs: Vec<String>,
+    /// Verbs allowed through `kubectl_write`; empty disables the tool
+    pub write_verbs: Vec<String>,
+    /// Per-command timeout in seconds
+    pub timeout_secs: u64,
+    /// Output longer than this many characters is trimmed
+    pub max_output_chars: usize,
+}
+
+impl Default for KubernetesToolConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kubectl_path: "kubectl".to_string(),
+            kubeconfig: None,
+            context: None,
+            allowed_namespaces: Vec::new(),
+            default_namespace: "default".to_string(),
+            read_verbs: ["get", "describe", "logs", "top", "events", "explain"]
+                .iter()
+                .map(|v| v.to_string())
+                .collect(),
+            write_verbs: ["apply", "delete", "scale", "rollout"]
+                .iter()
+                .map(|v| v.to_string())
+                .collect(),
+            timeout_secs: 30,
+            max_output_chars: 12_000,
+        }
+    }
+}
+
+/// GitHub tool configuration.
+///
+/// Authenticates with a personal access token, or as a GitHub App
+/// installation when `app` is set (notifications need a user token).
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct GitHubToolConfig {
+    /// Personal access token (classic or fine-grained)
+    pub token: Option<String>,
+    /// GitHub App credentials, used when no token is set
+    pub app: Option<GitHubAppConfig>,
+    /// REST API base URL; `https://ghe.example.com/api/v3` for Enterprise Server
+    pub api_url: String,
+    /// Repository (`owner/repo`) used when a call names none
+    pub default_repo: Option<String>,
+    /// Maximum items returned by list actions
+    pub max_items: usize,
+    /// Maximum pages fetched per list call
+    pub max_pages: usize,
+    /// PR diffs longer than this many characters are truncated
+    pub max_diff_chars: usize,
+}
+
+impl Default for GitHubToolConfig {
+    fn default() -> Self {
+        Self {
+            token: None,
+            app: None,
+            api_url: "https://api.github.com".to_string(),
+            default_repo: None,
+            max_items: 30,
+            max_pages: 5,
+            max_diff_chars: 20_000,
+        }
+    }
+}
+
+impl GitHubToolConfig {
+    /// Whether a token or GitHub App is configured.
+    pub fn has_credentials(&self) -> bool {
+        self.token.as_deref().is_some_and(|t| !t.trim().is_empty()) || self.app.is_some()
+    }
+}
+
+impl std::fmt::Debug for GitHubToolConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitHubToolConfig")
+            .field("token", &self.token.as_ref().map(|_| "[REDACTED]"))
+            .field("app", &self.app)
+            .field("api_url", &self.api_url)
+            .field("default_repo", &self.default_repo)
+            .field("max_items", &self.max_items)
+            .field("max_pages", &self.max_pages)
+            .field("max_diff_chars", &self.max_diff_chars)
+            .finish()
+    }
+}
+
+/// GitHub App installation credentials.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct GitHubAppConfig {
+    /// App ID (or client ID) from the app's settings page
+    pub app_id: String,
+    /// Installation ID for the account or organization
+    pub installation_id: u64,
+    /// Path to the app's PEM private key
+    pub private_key_path: String,
+}
+
+/// Issue tracker tool configuration.
+///
+/// Each configured backend becomes a tracker the `issues` tool can address;
+/// `default_tracker` picks one when both are set.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct IssuesToolConfig {
+    /// Jira Cloud or Data Center
+    pub jira: Option<JiraTrackerConfig>,
+    /// Linear
+    pub linear: Option<LinearTrackerConfig>,
+    /// Tracker used when a call names none ("jira" or "linear")
+    pub default_tracker: Option<String>,
+    /// Maximum issues returned by search
+    pub max_results: usize,
+}
+
+impl Default for IssuesToolConfig {
+    fn default() -> Self {
+        Self {
+            jira: None,
+            linear: None,
+            default_tracker: None,
+            max_results: 25,
+        }
+    }
+}
+
+impl IssuesToolConfig {
+    /// Whether any tracker is configured.
+    pub fn is_configured(&self) -> bool {
+        self.jira.is_some() || self.linear.is_some()
+    }
+}
+
+/// Jira credentials and defaults.
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct JiraTrackerConfig {
+    /// Site URL, e.g. `https://acme.atlassian.net`
+    pub url: String,
+    /// Account email; with it `token` is a Cloud API token (basic auth),
+    /// without it a Data Center personal access token (bearer)
+    pub email: Option<String>,
+    /// API token or personal access token
+    pub token: String,
+    /// Project key used when a call names none
+    pub default_project: Option<String>,
+    /// Agile board for sprint summaries; found from the project when unset
+    pub board_id: Option<u64>,
+    /// Custom field holding story points, e.g. `customfield_10016`
+    pub story_points_field: Option<String>,
+    /// Issue type for new issues
+    pub issue_type: String,
+}
+
+impl Default for JiraTrackerConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            email: None,
+            token: String::new(),
+            default_project: None,
+            board_id: None,
+            story_points_field: None,
+            issue_type: "Task".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Debug for JiraTrackerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JiraTrackerConfig")
+            .field("url", &self.url)
+            .field("email", &self.email)
+            .field("token", &"[REDACTED]")
+            .field("default_project", &self.default_project)
+            .field("board_id", &self.board_id)
+            .field("story_points_field", &self.story_points_field)
+            .field("issue_type", &self.issue_type)
+            .finish()
+    }
+}
+
+/// Linear credentials and defaults.
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LinearTrackerConfig {
+    /// Personal API key
+    pub api_key: String,
+    /// Team key (e.g. `ENG`) used when a call names none
+    pub default_team: Option<String>,
+}
+
+impl std::fmt::Debug for LinearTrackerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinearTrackerConfig")
+            .field("api_key", &"[REDACTED]")
+            .field("default_team", &self.default_team)
+            .finish()
+    }
+}
+
+/// RSS/Atom feed tool configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RssToolConfig {
+    /// Maximum number of subscribed feeds
+    pub max_feeds: usize,
+    /// Entries shown per feed by `latest` and `digest`
+    pub max_items_per_feed: usize,
+    /// Scheduled digest delivery
+    pub digest: RssDigestConfig,
+}
+
+impl Default for RssToolConfig {
+    fn default() -> Self {
+        Self {
+            max_feeds: 50,
+            max_items_per_feed: 10,
+            digest: RssDigestConfig::default(),
+        }
+    }
+}
+
+/// Scheduled RSS digest, delivered as a cron job to one chat.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RssDigestConfig {
+    /// Create the digest cron job at startup
+    pub enabled: bool,
+    /// Cron expression or recurring phrase ("every day at 8am")
+    pub schedule: String,
+    /// Channel the digest is delivered to (e.g. "telegram")
+    pub channel: String,
+    /// Chat ID the digest is delivered to
+    pub chat_id: String,
+}
+
+impl Default for RssDigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedule: "0 8 * * *".to_string(),
+            channel: String::new(),
+            chat_id: String::new(),
+        }
+    }
+}
+
+/// Ranking tool configuration (`tools.ranking`).
+///
+/// Example: `"ranking": { "rubrics": { "vendors": [
+///   { "field": "price", "weight": 2, "direction": "lower" },
+///   { "field": "rating", "min": 0, "max": 5 } ] } }`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RankingToolConfig {
+    /// Named rubrics: the criteria items are scored against
+    pub rubrics: HashMap<String, Vec<RankingCriterion>>,
+    /// Score records kept in the workspace history (oldest dropped first)
+    pub max_history: usize,
+}
+
+impl Default for RankingToolConfig {
+    fn default() -> Self {
+        Self {
+            rubrics: HashMap::new(),
+            max_history: 5000,
+        }
+    }
+}
+
+/// Whether larger or smaller values of a criterion rank higher.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RankingDirection {
+    /// Larger values score higher.
+    #[default]
+    Higher,
+    /// Smaller values score higher (prices, latencies).
+    Lower,
+}
+
+/// One scoring criterion of a ranking rubric.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RankingCriterion {
+    /// Item field holding the value; dots reach into nested objects
+    pub field: String,
+    /// Relative weight of this criterion (default: 1)
+    #[serde(default = "default_ranking_weight")]
+    pub weight: f64,
+    /// Whether larger or smaller values rank higher (default: higher)
+    #[serde(default)]
+    pub direction: RankingDirection,
+    /// Value that scores 0 (default: the lowest value in the batch)
+    #[serde(default)]
+    pub min: Option<f64>,
+    /// Value that scores 1 (default: the highest value in the batch)
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Numbers for text values, e.g. `{"high": 3, "medium": 2, "low": 1}`
+    #[serde(default)]
+    pub values: HashMap<String, f64>,
+}
+
+fn default_ranking_weight() -> f64 {
+    1.0
+}
+
+/// Calculator tool configuration (`tools.calc`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CalcToolConfig {
+    /// Answer plain arithmetic and unit conversions without an LLM call
+    /// (default: false)
+    pub fast_path: bool,
+    /// Exchange-rate endpoint returning `{"base", "date", "rates"}`
+    /// (Frankfurter / ECB by default)
+    pub fx_url: String,
+    /// How long fetched exchange rates are reused, in seconds
+    pub fx_cache_secs: u64,
+}
+
+impl Default for CalcToolConfig {
+    fn default() -> Self {
+        Self {
+            fast_path: false,
+            fx_url: "https://api.frankfurter.dev/v1/latest".to_string(),
+            fx_cache_secs: 6 * 60 * 60,
+        }
+    }
+}
+
+/// Unit system for weather reports.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WeatherUnits {
+    /// °C, km/h, mm.
+    #[default]
+    Metric,
+    /// °F, mph, inches.
+    Imperial,
+}
+
+/// Weather tool configuration (`tools.weather`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct WeatherToolConfig {
+    /// Location used when a request names none and memory holds none
+    pub default_location: Option<String>,
+    /// Long-term memory key holding the user's location
+    pub location_memory_key: String,
+    /// Units for temperatures, wind and precipitation
+    pub units: WeatherUnits,
+}
+
+impl Default for WeatherToolConfig {
+    fn default() -> Self {
+        Self {
+            default_location: None,
+            location_memory_key: "user:location".to_string(),
+            units: WeatherUnits::Metric,
+        }
+    }
+}
+
+/// Headline source for the news tool.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NewsProvider {
+    /// Google News RSS feeds (no API key).
+    #[default]
+    Google,
+    /// NewsAPI.org (requires `api_key`).
+    NewsApi,
+    /// GNews.io (requires `api_key`).
+    GNews,
+}
+
+/// News tool configuration (`tools.news`).
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct NewsToolConfig {
+    /// Headline provider
+    pub provider: NewsProvider,
+    /// API key for `newsapi` and `gnews`
+    pub api_key: Option<String>,
+    /// Two-letter country for top headlines (e.g. "us", "my")
+    pub country: String,
+    /// Two-letter language code (e.g. "en")
+    pub language: String,
+    /// Articles returned per call
+    pub max_results: usize,
+}
+
+impl std::fmt::Debug for NewsToolConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NewsToolConfig")
+            .field("provider", &self.provider)
+            .field("api_key", &self.api_key.as_ref().map(|_| "[REDACTED]"))
+            .field("country", &self.country)
+            .field("language", &self.language)
+            .field("max_results", &self.max_results)
+            .finish()
+    }
+}
+
+impl Default for NewsToolConfig {
+    fn default() -> Self {
+        Self {
+            provider: NewsProvider::Google,
+            api_key: None,
+            country: "us".to_string(),
+            language: "en".to_string(),
+            max_results: 10,
+        }
+    }
+}
+
+// ============================================================================
+// Memory Configuration
+// ============================================================================
+
+/// Memory backend selection.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryBackend {
+    /// Disable memory tools.
+    #[serde(rename = "none")]
+    Disabled,
+    /// Built-in substring search (default, zero cost).
+    #[default]
+    Builtin,
+    /// BM25 keyword scoring (feature: memory-bm25).
+    Bm25,
+    /// LLM embedding + cosine similarity (feature: memory-embedding).
+    Embedding,
+    /// HNSW approximate nearest neighbor (feature: memory-hnsw).
+    Hnsw,
+    /// Tantivy full-text search engine (feature: memory-tantivy).
+    Tantivy,
+    /// QMD backend (falls back safely when unavailable).
+    Qmd,
+}
+
+/// Memory citation mode.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryCitationsMode {
+    /// Show citations depending on channel context.
+    #[default]
+    Auto,
+    /// Always include citations in snippets.
     On,
     /// Never include citations in snippets.
     Off,
 }
 
 /// Memory configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct MemoryConfig {
     /// Memory backend to use.
@@ -1857,6 +3288,15 @@ pub struct MemoryConfig {
     /// Memory hygiene scheduler configuration.
     #[serde(default)]
     pub hygiene: crate::memory::hygiene::HygieneConfig,
+    /// Two-way sync of workspace memory with Obsidian or Notion.
+    #[serde(default)]
+    pub sync: crate::memory::notes_sync::NotesSyncConfig,
+    /// Automatic extraction of durable facts after each turn.
+    #[serde(default)]
+    pub extraction: crate::memory::extraction::ExtractionConfig,
+    /// Per-contact memory namespaces.
+    #[serde(default)]
+    pub namespaces: crate::memory::namespace::MemoryNamespaceConfig,
 }
 
 impl Default for MemoryConfig {
@@ -1874,6 +3314,9 @@ impl Default for MemoryConfig {
             hnsw_index_path: None,
             tantivy_index_path: None,
             hygiene: crate::memory::hygiene::HygieneConfig::default(),
+            sync: crate::memory::notes_sync::NotesSyncConfig::default(),
+            extraction: crate::memory::extraction::ExtractionConfig::default(),
+            namespaces: crate::memory::namespace::MemoryNamespaceConfig::default(),
         }
     }
 }
@@ -1883,7 +3326,7 @@ impl Default for MemoryConfig {
 // ============================================================================
 
 /// Heartbeat background service configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct HeartbeatConfig {
     /// Enable or disable heartbeat service.
@@ -1899,6 +3342,10 @@ pub struct HeartbeatConfig {
     /// "heartbeat:system" pseudo-channel (no outbound delivery).
     #[serde(default)]
     pub deliver_to: Option<String>,
+    /// Local-time window with no heartbeats or proactive messages.
+    pub quiet_hours: QuietHoursConfig,
+    /// Back off or tighten the interval depending on heartbeat results.
+    pub adaptive: AdaptiveHeartbeatConfig,
 }
 
 impl Default for HeartbeatConfig {
@@ -1908,6 +3355,138 @@ impl Default for HeartbeatConfig {
             interval_secs: 30 * 60,
             file_path: None,
             deliver_to: None,
+            quiet_hours: QuietHoursConfig::default(),
+            adaptive: AdaptiveHeartbeatConfig::default(),
+        }
+    }
+}
+
+/// Quiet hours: heartbeats are skipped and the message tool refuses
+/// proactive sends to other chats between `start` and `end` (local time,
+/// "HH:MM"). The window may wrap past midnight.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct QuietHoursConfig {
+    /// Enable quiet hours.
+    pub enabled: bool,
+    /// Start of the quiet window, "HH:MM" local time.
+    pub start: String,
+    /// End of the quiet window, "HH:MM" local time.
+    pub end: String,
+}
+
+impl Default for QuietHoursConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "22:00".to_string(),
+            end: "07:00".to_string(),
+        }
+    }
+}
+
+/// Policy for proactive notifications sent with the `message` tool.
+///
+/// Ordinary replies are never affected. `high` priority notifications are
+/// always delivered; `low` ones, and `normal` ones during quiet hours or over
+/// a channel's rate limit, are collected into one digest message per chat.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    /// Enable the notification policy.
+    pub enabled: bool,
+    /// Notifications per channel per hour before normal ones are digested.
+    pub rate_limit_per_hour: u32,
+    /// Per-channel overrides of `rate_limit_per_hour`.
+    pub channel_rate_limits: HashMap<String, u32>,
+    /// Local-time window in which only high-priority notifications go out.
+    pub quiet_hours: QuietHoursConfig,
+    /// How long a digest collects notifications before it is sent.
+    pub digest_interval_secs: u64,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rate_limit_per_hour: 10,
+            channel_rate_limits: HashMap::new(),
+            quiet_hours: QuietHoursConfig::default(),
+            digest_interval_secs: 60 * 60,
+        }
+    }
+}
+
+/// What the bus does with an inbound message when its buffer is full.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BusOverflowPolicy {
+    /// Wait up to `block_timeout_ms` for room, then fail.
+    #[default]
+    Block,
+    /// Discard the oldest waiting message to make room.
+    DropOldest,
+    /// Fail immediately and tell the sender to try again later.
+    Reject,
+}
+
+impl BusOverflowPolicy {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "block" => Some(Self::Block),
+            "drop_oldest" => Some(Self::DropOldest),
+            "reject" => Some(Self::Reject),
+            _ => None,
+        }
+    }
+}
+
+/// Inbound message bus sizing and backpressure.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct BusConfig {
+    /// Messages buffered per direction before overflow handling kicks in.
+    pub buffer_size: usize,
+    /// What to do with an inbound message when the buffer is full.
+    pub overflow: BusOverflowPolicy,
+    /// How long `block` waits for room before failing.
+    pub block_timeout_ms: u64,
+    /// With `reject`, reply on the sender's channel that the message was
+    /// not accepted.
+    pub notify_rejected: bool,
+}
+
+impl Default for BusConfig {
+    fn default() -> Self {
+        Self {
+            buffer_size: 100,
+            overflow: BusOverflowPolicy::Block,
+            block_timeout_ms: 5_000,
+            notify_rejected: true,
+        }
+    }
+}
+
+/// Adaptive heartbeat scheduling. Each consecutive heartbeat with nothing
+/// to do doubles the interval up to `max_interval_secs`; while checklist
+/// tasks are pending the interval is halved, down to `min_interval_secs`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct AdaptiveHeartbeatConfig {
+    /// Enable adaptive intervals.
+    pub enabled: bool,
+    /// Shortest interval in seconds (floor 30).
+    pub min_interval_secs: u64,
+    /// Longest interval in seconds.
+    pub max_interval_secs: u64,
+}
+
+impl Default for AdaptiveHeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_interval_secs: 5 * 60,
+            max_interval_secs: 4 * 60 * 60,
         }
     }
 }
@@ -1919,7 +3498,7 @@ impl Default for HeartbeatConfig {
 // ============================================================================
 
 /// Skills marketplace (ClawHub) tool configuration.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct SkillsMarketplaceConfig {
     /// Enable skills marketplace tools (find_skills, install_skill).
@@ -1934,7 +3513,7 @@ pub struct SkillsMarketplaceConfig {
 }
 
 /// ClawHub registry connection settings.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ClawHubConfig {
     /// Enable the ClawHub registry (requires skills.enabled too).
@@ -1971,7 +3550,7 @@ impl Default for ClawHubConfig {
 }
 
 /// In-memory search result cache settings.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct SearchCacheConfig {
     /// Maximum number of cached queries.
@@ -2003,7 +3582,7 @@ impl Default for SearchCacheConfig {
 // ============================================================================
 
 /// Skills system configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct SkillsConfig {
     /// Enable or disable the skills system.
@@ -2017,6 +3596,11 @@ pub struct SkillsConfig {
     /// Built-in or workspace skills to disable by name.
     #[serde(default)]
     pub disabled: Vec<String>,
+    /// Watch the skills directory and reload changed skills without a
+    /// restart (gateway mode).
+    pub hot_reload: bool,
+    /// Poll interval for the skills watcher, in seconds.
+    pub hot_reload_interval_secs: u64,
 }
 
 impl Default for SkillsConfig {
@@ -2026,6 +3610,8 @@ impl Default for SkillsConfig {
             workspace_dir: None,
             always_load: Vec::new(),
             disabled: Vec::new(),
+            hot_reload: true,
+            hot_reload_interval_secs: 5,
         }
     }
 }
@@ -2035,7 +3621,7 @@ impl Default for SkillsConfig {
 // ============================================================================
 
 /// Swarm / multi-agent delegation configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct SwarmConfig {
     /// Whether delegation is enabled.
@@ -2060,7 +3646,7 @@ impl Default for SwarmConfig {
 }
 
 /// A pre-defined sub-agent role with system prompt and tool whitelist.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct SwarmRole {
     /// System prompt for this role.
@@ -2074,7 +3660,7 @@ pub struct SwarmRole {
 // ============================================================================
 
 /// Container runtime type for shell command execution
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum RuntimeType {
     /// Native execution (no container isolation)
@@ -2096,14 +3682,31 @@ pub enum RuntimeType {
     Bubblewrap,
 }
 
+/// Shell used by the native runtime to interpret commands
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NativeShell {
+    /// `cmd.exe` on Windows, `sh` everywhere else
+    #[default]
+    Auto,
+    /// POSIX `sh -c`
+    Sh,
+    /// Windows `cmd.exe /C`
+    Cmd,
+    /// PowerShell with `-Command` (`powershell.exe` on Windows, `pwsh` elsewhere)
+    Powershell,
+}
+
 /// Runtime configuration for shell execution
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct RuntimeConfig {
     /// Type of container runtime to use
     pub runtime_type: RuntimeType,
     /// Whether to fall back to native runtime if configured runtime is unavailable
     pub allow_fallback_to_native: bool,
+    /// Shell used by the native runtime (`auto` picks `cmd` on Windows, `sh` elsewhere)
+    pub native_shell: NativeShell,
     /// Path to JSON allowlist used to validate runtime extra mounts
     #[serde(default = "default_mount_allowlist_path")]
     pub mount_allowlist_path: String,
@@ -2128,6 +3731,7 @@ impl Default for RuntimeConfig {
         Self {
             runtime_type: RuntimeType::Native,
             allow_fallback_to_native: false,
+            native_shell: NativeShell::Auto,
             mount_allowlist_path: default_mount_allowlist_path(),
             docker: DockerConfig::default(),
             apple: AppleContainerConfig::default(),
@@ -2139,7 +3743,7 @@ impl Default for RuntimeConfig {
 }
 
 /// Docker runtime configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct DockerConfig {
     /// Docker image to use for shell execution
@@ -2180,7 +3784,7 @@ impl Default for DockerConfig {
 }
 
 /// Apple Container runtime configuration (macOS only)
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct AppleContainerConfig {
     /// Container image/bundle path
@@ -2200,7 +3804,7 @@ pub struct AppleContainerConfig {
 ///
 /// Restricts filesystem access at the kernel level using the Linux Landlock LSM.
 /// Requires Linux kernel 5.13+. Degrades gracefully on older kernels.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct LandlockConfig {
     /// Directories the sandboxed process may read (recursive).
@@ -2236,7 +3840,7 @@ impl Default for LandlockConfig {
 ///
 /// Wraps commands with `firejail` using Linux namespaces + seccomp.
 /// Requires the `firejail` binary on PATH.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct FirejailConfig {
     /// Path to a custom firejail profile file.
@@ -2250,7 +3854,7 @@ pub struct FirejailConfig {
 ///
 /// Wraps commands with `bwrap` (bubblewrap), a lightweight OCI-compatible sandbox.
 /// Requires the `bwrap` binary on PATH.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct BubblewrapConfig {
     /// Read-only bind mounts (each entry is a host path bound at the same container path).
@@ -2281,12 +3885,80 @@ impl Default for BubblewrapConfig {
     }
 }
 
+// ============================================================================
+// Workspaces Configuration
+// ============================================================================
+
+/// A registered project workspace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(default)]
+pub struct WorkspaceEntry {
+    /// Workspace directory (`~` is expanded). Skills load from its
+    /// `skills/` subdirectory.
+    pub path: String,
+    /// Short description shown by `zeptoclaw workspace list`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Named project workspaces.
+///
+/// A message runs in the workspace bound to its chat, else in `active`.
+/// The workspace sets the directory tools work in, the skills directory, a
+/// memory namespace (`workspace.<name>`) and the `workspace` tag of the
+/// session. With no workspace, `agents.defaults.workspace` is used as before.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct WorkspacesConfig {
+    /// Workspace for messages without a binding (`zeptoclaw workspace switch`).
+    pub active: Option<String>,
+    /// Workspace name → directory and description.
+    pub projects: BTreeMap<String, WorkspaceEntry>,
+    /// Chat → workspace name, matched as `channel:chat_id` first and then as
+    /// the bare `channel` (e.g. `"telegram:-100123": "acme"`, `"slack": "work"`).
+    pub bindings: HashMap<String, String>,
+}
+
+impl WorkspacesConfig {
+    /// Workspace for a chat: its binding, else `active`. Names that are not
+    /// registered in `projects` are ignored.
+    pub fn resolve(&self, channel: &str, chat_id: &str) -> Option<&str> {
+        self.bindings
+            .get(&format!("{}:{}", channel, chat_id))
+            .or_else(|| self.bindings.get(channel))
+            .or(self.active.as_ref())
+            .map(String::as_str)
+            .filter(|name| self.projects.contains_key(*name))
+    }
+
+    /// Directory of a registered workspace.
+    pub fn path(&self, name: &str) -> Option<std::path::PathBuf> {
+        self.projects
+            .get(name)
+            .map(|entry| super::expand_home(&entry.path))
+    }
+
+    /// Directory of the active workspace, if one is set and registered.
+    pub fn active_path(&self) -> Option<std::path::PathBuf> {
+        self.path(self.active.as_deref()?)
+    }
+
+    /// Memory namespace of a workspace, nested with the sender's namespace
+    /// when `memory.namespaces` gives one.
+    pub fn memory_namespace(workspace: &str, sender: Option<String>) -> String {
+        match sender {
+            Some(sender) => format!("workspace.{}.{}", workspace, sender),
+            None => format!("workspace.{}", workspace),
+        }
+    }
+}
+
 // ============================================================================
 // Containerized Agent Configuration
 // ============================================================================
 
 /// Container backend for the containerized agent proxy.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ContainerAgentBackend {
     /// Auto-detect: on macOS try Apple Container first, then Docker.
@@ -2304,7 +3976,7 @@ pub enum ContainerAgentBackend {
 ///
 /// When running with `--containerized`, the gateway spawns each agent
 /// in an isolated container for multi-user safety.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ContainerAgentConfig {
     /// Container backend to use (auto, docker, apple).
@@ -2365,7 +4037,7 @@ impl Default for ContainerAgentConfig {
 /// };
 /// assert_eq!(def.name, "cpu_temp");
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CustomToolDef {
     /// Tool name (alphanumeric + underscore only, used by LLM to invoke).
     pub name: String,
@@ -2472,6 +4144,38 @@ mod tests {
         assert!(config.deliver_to.is_none());
     }
 
+    #[test]
+    fn test_heartbeat_config_quiet_hours_and_adaptive() {
+        let config = HeartbeatConfig::default();
+        assert!(!config.quiet_hours.enabled);
+        assert_eq!(config.quiet_hours.start, "22:00");
+        assert_eq!(config.quiet_hours.end, "07:00");
+        assert!(!config.adaptive.enabled);
+
+        let json = r#"{"quiet_hours": {"enabled": true, "start": "23:30"}, "adaptive": {"enabled": true, "max_interval_secs": 7200}}"#;
+        let config: HeartbeatConfig = serde_json::from_str(json).unwrap();
+        assert!(config.quiet_hours.enabled);
+        assert_eq!(config.quiet_hours.start, "23:30");
+        assert_eq!(config.quiet_hours.end, "07:00");
+        assert_eq!(config.adaptive.max_interval_secs, 7200);
+        assert_eq!(config.adaptive.min_interval_secs, 300);
+    }
+
+    #[test]
+    fn test_notifications_config_deserialize() {
+        let config = NotificationsConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.rate_limit_per_hour, 10);
+        assert_eq!(config.digest_interval_secs, 3600);
+
+        let json = r#"{"notifications": {"enabled": true, "channel_rate_limits": {"telegram": 3}, "quiet_hours": {"enabled": true}}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.notifications.enabled);
+        assert_eq!(config.notifications.channel_rate_limits["telegram"], 3);
+        assert!(config.notifications.quiet_hours.enabled);
+        assert_eq!(config.notifications.quiet_hours.start, "22:00");
+    }
+
     #[test]
     fn test_custom_tool_def_deserialize() {
         let json = r#"{
@@ -2748,6 +4452,8 @@ mod tests {
         let config = Config::default();
         assert_eq!(config.transcription.model, "whisper-1");
         assert!(config.transcription.enabled);
+        assert_eq!(config.transcription.backend, TranscriptionProvider::Openai);
+        assert_eq!(config.transcription.deepgram_model, "nova-2");
     }
 
     #[test]
@@ -3007,7 +4713,7 @@ fn default_email_idle_timeout_secs() -> u64 {
 ///
 /// Stored under `channels.email` in `config.json`.
 /// The channel is only functional when built with `--features channel-email`.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EmailConfig {
     /// IMAP server hostname (e.g. `imap.gmail.com`)
     pub imap_host: String,
@@ -1,84 +1,15 @@
-//! Configuration validation with unknown field detection.
+//! Configuration validation: unknown fields, types and deprecated keys from
+//! the config schema, plus semantic checks.
 
-use serde_json::Value;
 use std::collections::HashSet;
 
-/// Known top-level config field names.
-const KNOWN_TOP_LEVEL: &[&str] = &[
-    "agents",
-    "channels",
-    "providers",
-    "gateway",
-    "tools",
-    "memory",
-    "heartbeat",
-    "skills",
-    "runtime",
-    "container_agent",
-    "swarm",
-    "approval",
-    "plugins",
-    "telemetry",
-    "cost",
-    "batch",
-    "hooks",
-    "safety",
-    "compaction",
-    "mcp",
-    "routines",
-    "tunnel",
-    "stripe",
-    "custom_tools",
-    "transcription",
-    "tool_profiles",
-    "project",
-    "cache",
-    "agent_mode",
-    "pairing",
-    "session",
-    "panel",
-    "health",
-    "devices",
-    "logging",
-    "r8r_bridge",
-];
-
-/// Known fields for each section. Nested as section.field.
-const KNOWN_AGENTS_DEFAULTS: &[&str] = &[
-    "workspace",
-    "model",
-    "max_tokens",
-    "temperature",
-    "max_tool_iterations",
-    "agent_timeout_secs",
-    "tool_timeout_secs",
-    "message_queue_mode",
-    "streaming",
-    "token_budget",
-    "compact_tools",
-    "tool_profile",
-    "active_hand",
-    "timezone",
-    "loop_guard",
-    "max_tool_result_bytes",
-    "max_tool_calls",
-    "system_prompt",
-];
-
-const KNOWN_LOOP_GUARD: &[&str] = &[
-    "enabled",
-    "warn_threshold",
-    "block_threshold",
-    "global_circuit_breaker",
-    "ping_pong_min_repeats",
-    "poll_multiplier",
-    "outcome_warn_threshold",
-    "outcome_block_threshold",
-    "window_size",
-];
-
-#[allow(dead_code)]
-const KNOWN_GATEWAY: &[&str] = &["host", "port", "rate_limit", "startup_guard"];
+use serde_json::Value;
+
+use super::schema::{self, SchemaIssueKind};
+
+/// Sections where an unknown key is an error rather than a warning: a
+/// misspelled section or agent default silently drops the setting.
+const STRICT_SECTIONS: &[&str] = &["", "agents.defaults"];
 
 /// A validation diagnostic.
 #[derive(Debug)]
@@ -86,6 +17,8 @@ pub struct Diagnostic {
     pub level: DiagnosticLevel,
     pub path: String,
     pub message: String,
+    /// Line in the config file, when the source text is known.
+    pub line: Option<usize>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -102,10 +35,16 @@ impl std::fmt::Display for Diagnostic {
             DiagnosticLevel::Warn => "[WARN]",
             DiagnosticLevel::Error => "[ERROR]",
         };
-        if self.path.is_empty() {
-            write!(f, "{} {}", prefix, self.message)
-        } else {
-            write!(f, "{} {}: {}", prefix, self.path, self.message)
+        match (self.path.is_empty(), self.line) {
+            (true, _) => write!(f, "{} {}", prefix, self.message),
+            (false, Some(line)) => {
+                write!(
+                    f,
+                    "{} {} (line {}): {}",
+                    prefix, self.path, line, self.message
+                )
+            }
+            (false, None) => write!(f, "{} {}: {}", prefix, self.path, self.message),
         }
     }
 }
@@ -145,7 +84,8 @@ pub fn suggest_field(unknown: &str, known: &[&str]) -> Option<String> {
         .map(|(k, _)| format!("did you mean '{}'?", k))
 }
 
-/// Validate a raw JSON config value against known field names.
+/// Validate a raw JSON config value against the config schema, then run
+/// semantic checks.
 pub fn validate_config(raw: &Value) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
 
@@ -157,6 +97,7 @@ pub fn validate_config(raw: &Value) -> Vec<Diagnostic> {
                 level: DiagnosticLevel::Error,
                 path: String::new(),
                 message: "Config must be a JSON object".to_string(),
+                line: None,
             });
             return diagnostics;
         }
@@ -166,77 +107,41 @@ pub fn validate_config(raw: &Value) -> Vec<Diagnostic> {
         level: DiagnosticLevel::Ok,
         path: String::new(),
         message: "Valid JSON".to_string(),
+        line: None,
     });
 
-    // Check top-level keys
-    let known_set: HashSet<&str> = KNOWN_TOP_LEVEL.iter().copied().collect();
-    let mut has_unknown = false;
-    for key in obj.keys() {
-        if !known_set.contains(key.as_str()) {
-            has_unknown = true;
-            let suggestion = suggest_field(key, KNOWN_TOP_LEVEL).unwrap_or_default();
-            let msg = if suggestion.is_empty() {
-                format!("Unknown field '{}'", key)
-            } else {
-                format!("Unknown field '{}' \u{2014} {}", key, suggestion)
-            };
-            diagnostics.push(Diagnostic {
-                level: DiagnosticLevel::Error,
-                path: key.clone(),
-                message: msg,
-            });
-        }
-    }
-
-    // Check agents.defaults keys
-    if let Some(agents) = obj.get("agents").and_then(|v| v.as_object()) {
-        if let Some(defaults) = agents.get("defaults").and_then(|v| v.as_object()) {
-            let known_set: HashSet<&str> = KNOWN_AGENTS_DEFAULTS.iter().copied().collect();
-            for key in defaults.keys() {
-                if !known_set.contains(key.as_str()) {
-                    has_unknown = true;
-                    let suggestion = suggest_field(key, KNOWN_AGENTS_DEFAULTS).unwrap_or_default();
-                    let msg = if suggestion.is_empty() {
-                        format!("Unknown field '{}'", key)
-                    } else {
-                        format!("Unknown field '{}' \u{2014} {}", key, suggestion)
-                    };
-                    diagnostics.push(Diagnostic {
-                        level: DiagnosticLevel::Error,
-                        path: format!("agents.defaults.{}", key),
-                        message: msg,
-                    });
-                }
-            }
-
-            // Check agents.defaults.loop_guard nested keys
-            if let Some(lg) = defaults.get("loop_guard").and_then(|v| v.as_object()) {
-                let lg_known: HashSet<&str> = KNOWN_LOOP_GUARD.iter().copied().collect();
-                for key in lg.keys() {
-                    if !lg_known.contains(key.as_str()) {
-                        has_unknown = true;
-                        let suggestion = suggest_field(key, KNOWN_LOOP_GUARD).unwrap_or_default();
-                        let msg = if suggestion.is_empty() {
-                            format!("Unknown field '{}'", key)
-                        } else {
-                            format!("Unknown field '{}' \u{2014} {}", key, suggestion)
-                        };
-                        diagnostics.push(Diagnostic {
-                            level: DiagnosticLevel::Warn,
-                            path: format!("agents.defaults.loop_guard.{}", key),
-                            message: msg,
-                        });
-                    }
-                }
+    // Unknown keys, type mismatches and deprecated keys, from the schema.
+    // Policy rules are parsed and reported per rule below.
+    let issues: Vec<_> = schema::check_config(raw)
+        .into_iter()
+        .filter(|issue| !issue.path.starts_with("safety.policy_rules["))
+        .collect();
+    for issue in &issues {
+        let parent = issue.path.rsplit_once('.').map_or("", |(parent, _)| parent);
+        let level = match issue.kind {
+            SchemaIssueKind::Invalid => DiagnosticLevel::Error,
+            SchemaIssueKind::UnknownField if STRICT_SECTIONS.contains(&parent) => {
+                DiagnosticLevel::Error
             }
-        }
+            SchemaIssueKind::UnknownField | SchemaIssueKind::Deprecated => DiagnosticLevel::Warn,
+        };
+        diagnostics.push(Diagnostic {
+            level,
+            path: issue.path.clone(),
+            message: issue.message.clone(),
+            line: None,
+        });
     }
 
-    if !has_unknown {
+    if issues
+        .iter()
+        .all(|issue| issue.kind == SchemaIssueKind::Deprecated)
+    {
         diagnostics.push(Diagnostic {
             level: DiagnosticLevel::Ok,
             path: String::new(),
             message: "All fields recognized".to_string(),
+            line: None,
         });
     }
 
@@ -259,6 +164,7 @@ pub fn validate_config(raw: &Value) -> Vec<Diagnostic> {
                         level: DiagnosticLevel::Warn,
                         path: format!("channels.{}.allow_from", name),
                         message: "Empty \u{2014} anyone can message the bot".to_string(),
+                        line: None,
                     });
                 }
 
@@ -273,6 +179,7 @@ pub fn validate_config(raw: &Value) -> Vec<Diagnostic> {
                         level: DiagnosticLevel::Warn,
                         path: "channels.email.allowed_senders".to_string(),
                         message: "Email sender allowlists trust the parsed From header only; enforce SPF/DKIM/DMARC upstream if sender authenticity matters".to_string(),
+                        line: None,
                     });
                 }
 
@@ -305,6 +212,7 @@ pub fn validate_config(raw: &Value) -> Vec<Diagnostic> {
                             } else {
                                 "Telegram allow_from contains non-numeric entries, but allow_usernames=false so they will never match".to_string()
                             },
+                            line: None,
                         });
                     }
                 }
@@ -312,6 +220,172 @@ pub fn validate_config(raw: &Value) -> Vec<Diagnostic> {
         }
     }
 
+    // Custom safety policy rules
+    if let Some(rules) = obj
+        .get("safety")
+        .and_then(|v| v.get("policy_rules"))
+        .and_then(|v| v.as_array())
+    {
+        for (i, raw_rule) in rules.iter().enumerate() {
+            let path = format!("safety.policy_rules[{}]", i);
+            let rule = match serde_json::from_value::<crate::safety::policy::PolicyRuleConfig>(
+                raw_rule.clone(),
+            ) {
+                Ok(rule) => rule,
+                Err(e) => {
+                    diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Error,
+                        path,
+                        message: format!("Invalid policy rule: {}", e),
+                        line: None,
+                    });
+                    continue;
+                }
+            };
+            let problem = if rule.name.trim().is_empty() || rule.pattern.is_empty() {
+                Some("Policy rule needs a name and a pattern".to_string())
+            } else {
+                regex::Regex::new(&rule.regex_source())
+                    .err()
+                    .map(|e| format!("Invalid pattern: {}", e))
+            };
+            if let Some(message) = problem {
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Error,
+                    path,
+                    message,
+                    line: None,
+                });
+            }
+        }
+    }
+
+    // Users: an identity can only belong to one user
+    if let Some(raw) = obj.get("users") {
+        if let Ok(users) =
+            serde_json::from_value::<crate::security::users::UsersConfig>(raw.clone())
+        {
+            for message in users.validate() {
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Error,
+                    path: "users.users".to_string(),
+                    message,
+                    line: None,
+                });
+            }
+        }
+    }
+
+    // Admin senders must name their channel
+    if let Some(ids) = obj
+        .get("admin")
+        .and_then(|a| a.get("sender_ids"))
+        .and_then(|v| v.as_array())
+    {
+        for (i, id) in ids.iter().enumerate() {
+            if let Some(id) = id.as_str().filter(|id| !id.contains(':')) {
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warn,
+                    path: format!("admin.sender_ids[{}]", i),
+                    message: format!(
+                        "'{}' has no channel and is ignored; use channel:sender_id",
+                        id
+                    ),
+                    line: None,
+                });
+            }
+        }
+    }
+
+    // RBAC: assignments must name defined roles
+    if let Some(raw) = obj.get("rbac") {
+        if let Ok(rbac) = serde_json::from_value::<crate::security::rbac::RbacConfig>(raw.clone()) {
+            for message in rbac.validate() {
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Error,
+                    path: "rbac".to_string(),
+                    message,
+                    line: None,
+                });
+            }
+        }
+    }
+
+    // Locale tags must name a supported language
+    let mut languages: Vec<(String, &str)> = Vec::new();
+    if let Some(tag) = obj
+        .get("agents")
+        .and_then(|a| a.get("defaults"))
+        .and_then(|d| d.get("language"))
+        .and_then(|l| l.as_str())
+    {
+        languages.push(("agents.defaults.language".to_string(), tag));
+    }
+    if let Some(prompts) = obj
+        .get("channels")
+        .and_then(|c| c.get("prompts"))
+        .and_then(|p| p.as_object())
+    {
+        for (channel, prompt) in prompts {
+            if let Some(tag) = prompt.get("language").and_then(|l| l.as_str()) {
+                languages.push((format!("channels.prompts.{}.language", channel), tag));
+            }
+        }
+    }
+    for (path, tag) in languages {
+        if crate::utils::locale::Locale::parse(tag).is_none() {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                path,
+                message: format!(
+                    "Unsupported language '{}' (supported: {})",
+                    tag,
+                    crate::utils::locale::SUPPORTED_LANGUAGES
+                ),
+                line: None,
+            });
+        }
+    }
+
+    // Transcription backends need their credentials / model
+    if let Some(transcription) = obj.get("transcription").and_then(|v| v.as_object()) {
+        let enabled = transcription
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let backend = transcription.get("backend").and_then(|v| v.as_str());
+        let missing = |key: &str| {
+            transcription
+                .get(key)
+                .and_then(|v| v.as_str())
+                .is_none_or(|v| v.trim().is_empty())
+        };
+        let problem = match backend {
+            _ if !enabled => None,
+            Some("deepgram") if missing("deepgram_api_key") => Some((
+                "transcription.deepgram_api_key",
+                "Deepgram backend selected without deepgram_api_key; voice messages will not be transcribed",
+            )),
+            Some("whisper_cpp") if !cfg!(feature = "whisper-cpp") => Some((
+                "transcription.backend",
+                "whisper_cpp backend requires a build with the `whisper-cpp` feature",
+            )),
+            Some("whisper_cpp") if missing("whisper_cpp_model") => Some((
+                "transcription.whisper_cpp_model",
+                "whisper_cpp backend selected without whisper_cpp_model (path to a ggml model)",
+            )),
+            _ => None,
+        };
+        if let Some((path, message)) = problem {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warn,
+                path: path.to_string(),
+                message: message.to_string(),
+                line: None,
+            });
+        }
+    }
+
     // r8r bridge warnings
     if let Some(r8r) = obj.get("r8r_bridge").and_then(|v| v.as_object()) {
         let enabled = r8r
@@ -329,6 +403,7 @@ pub fn validate_config(raw: &Value) -> Vec<Diagnostic> {
                         level: DiagnosticLevel::Warn,
                         path: "r8r_bridge.endpoint".to_string(),
                         message: "Plaintext ws:// on non-loopback address \u{2014} bearer token will be sent in cleartext; use wss://".to_string(),
+                        line: None,
                     });
                 }
             }
@@ -338,6 +413,15 @@ pub fn validate_config(raw: &Value) -> Vec<Diagnostic> {
     diagnostics
 }
 
+/// Set the line of each diagnostic from `source`, the config file text the
+/// diagnostics were produced from.
+pub fn locate_lines(diagnostics: &mut [Diagnostic], source: &str) {
+    let lines = schema::line_index(source);
+    for diagnostic in diagnostics {
+        diagnostic.line = lines.get(&diagnostic.path).copied();
+    }
+}
+
 /// Check if a model name looks compatible with a provider backend.
 ///
 /// Returns `None` when the combination is fine, or `Some(message)` describing
@@ -392,6 +476,7 @@ pub fn validate_model_provider_compat(config: &crate::config::Config) -> Vec<Dia
             path: "providers".to_string(),
             message: "No AI provider resolved \u{2014} set an API key or run 'zeptoclaw onboard'"
                 .to_string(),
+            line: None,
         });
         return diags;
     }
@@ -425,6 +510,7 @@ pub fn validate_model_provider_compat(config: &crate::config::Config) -> Vec<Dia
             level,
             path: "agents.defaults.model".to_string(),
             message,
+            line: None,
         });
     }
 
@@ -436,6 +522,7 @@ pub fn validate_model_provider_compat(config: &crate::config::Config) -> Vec<Dia
                     level: DiagnosticLevel::Error,
                     path: format!("providers.{}.model", sel.name),
                     message: msg,
+                    line: None,
                 });
             }
         }
@@ -449,6 +536,7 @@ pub fn validate_model_provider_compat(config: &crate::config::Config) -> Vec<Dia
                 "Model '{}' compatible with primary provider '{}'",
                 default_model, primary.name
             ),
+            line: None,
         });
     }
 
@@ -467,6 +555,8 @@ pub fn validate_custom_tools(config: &crate::config::Config) -> Vec<String> {
         "write_file",
         "list_dir",
         "edit_file",
+        "apply_patch",
+        "undo",
         "shell",
         "web_search",
         "web_fetch",
@@ -474,12 +564,31 @@ pub fn validate_custom_tools(config: &crate::config::Config) -> Vec<String> {
         "memory_search",
         "memory_get",
         "longterm_memory",
+        "memory_set",
+        "memory_append",
         "whatsapp_send",
         "google_sheets",
+        "google_drive",
+        "calendar",
+        "contacts",
+        "email",
+        "rss",
+        "weather",
+        "news",
+        "calc",
+        "sql",
+        "sql_execute",
+        "kubectl",
+        "kubectl_write",
+        "github",
+        "github_write",
+        "issues",
         "cron",
         "spawn",
+        "session_link",
         "delegate",
         "r8r",
+        "rank",
     ]
     .iter()
     .copied()
@@ -534,14 +643,14 @@ mod tests {
 
     #[test]
     fn test_suggest_field_match() {
-        let result = suggest_field("gatway", KNOWN_TOP_LEVEL);
+        let result = suggest_field("gatway", &["agents", "channels", "gateway"]);
         assert!(result.is_some());
         assert!(result.unwrap().contains("gateway"));
     }
 
     #[test]
     fn test_suggest_field_no_match() {
-        let result = suggest_field("xyzabc", KNOWN_TOP_LEVEL);
+        let result = suggest_field("xyzabc", &["agents", "channels", "gateway"]);
         assert!(result.is_none());
     }
 
@@ -555,6 +664,26 @@ mod tests {
         assert!(diags.iter().all(|d| d.level != DiagnosticLevel::Error));
     }
 
+    #[test]
+    fn test_validate_policy_rules() {
+        let raw = json!({
+            "safety": {"policy_rules": [
+                {"name": "ok", "pattern": "CUST-\\d+", "action": "redact"},
+                {"name": "bad_regex", "pattern": "("},
+                {"name": "bad_action", "pattern": "x", "action": "explode"}
+            ]}
+        });
+        let errors: Vec<String> = validate_config(&raw)
+            .into_iter()
+            .filter(|d| d.level == DiagnosticLevel::Error)
+            .map(|d| d.path)
+            .collect();
+        assert_eq!(
+            errors,
+            vec!["safety.policy_rules[1]", "safety.policy_rules[2]"]
+        );
+    }
+
     #[test]
     fn test_validate_accepts_tunnel_r8r_bridge_and_agent_default_fields() {
         let raw = json!({
@@ -897,6 +1026,56 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn test_validate_rejects_unsupported_languages() {
+        let raw = json!({
+            "agents": {"defaults": {"language": "de-AT"}},
+            "channels": {"prompts": {
+                "slack": {"language": "en-GB"},
+                "discord": {"language": "klingon"}
+            }}
+        });
+        let errors: Vec<_> = validate_config(&raw)
+            .into_iter()
+            .filter(|d| d.level == DiagnosticLevel::Error)
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "channels.prompts.discord.language");
+        assert!(errors[0].message.contains("klingon"));
+    }
+
+    #[test]
+    fn test_validate_warns_on_incomplete_transcription_backend() {
+        let warnings = |transcription: Value| -> Vec<String> {
+            validate_config(&json!({ "transcription": transcription }))
+                .into_iter()
+                .filter(|d| d.level == DiagnosticLevel::Warn)
+                .map(|d| d.path)
+                .collect()
+        };
+        assert_eq!(
+            warnings(json!({"backend": "deepgram"})),
+            vec!["transcription.deepgram_api_key"]
+        );
+        assert!(warnings(json!({"backend": "deepgram", "deepgram_api_key": "dg"})).is_empty());
+        assert!(warnings(json!({"backend": "deepgram", "enabled": false})).is_empty());
+        let whisper = warnings(json!({"backend": "whisper_cpp"}));
+        assert_eq!(whisper.len(), 1);
+        assert!(whisper[0].starts_with("transcription."));
+    }
+
+    #[test]
+    fn test_validate_warns_on_bare_admin_sender_ids() {
+        let raw = json!({"admin": {"sender_ids": ["telegram:1", "ops"]}});
+        let warnings: Vec<_> = validate_config(&raw)
+            .into_iter()
+            .filter(|d| d.level == DiagnosticLevel::Warn)
+            .collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, "admin.sender_ids[1]");
+        assert!(warnings[0].message.contains("channel:sender_id"));
+    }
+
     #[test]
     fn test_validate_r8r_bridge_ws_localhost_no_warn() {
         let raw = json!({
@@ -932,4 +1111,41 @@ mod tests {
         let diags = validate_config(&raw);
         assert!(!diags.iter().any(|d| d.path == "r8r_bridge.endpoint"));
     }
+
+    #[test]
+    fn test_validate_type_mismatch_and_deprecated_key() {
+        let raw = json!({
+            "gateway": {"port": "8080", "hots": "0.0.0.0"},
+            "channels": {"whatsapp": {"enabled": false}}
+        });
+        let diags = validate_config(&raw);
+        assert!(diags
+            .iter()
+            .any(|d| d.path == "gateway.port" && d.level == DiagnosticLevel::Error));
+        assert!(diags
+            .iter()
+            .any(|d| d.path == "gateway.hots" && d.level == DiagnosticLevel::Warn));
+        assert!(diags.iter().any(|d| {
+            d.path == "channels.whatsapp"
+                && d.level == DiagnosticLevel::Warn
+                && d.message.contains("whatsapp_web")
+        }));
+    }
+
+    #[test]
+    fn test_locate_lines() {
+        let source = "{\n  \"agents\": {\n    \"defaults\": {\"modle\": \"gpt-4\"}\n  }\n}";
+        let raw: Value = serde_json::from_str(source).unwrap();
+        let mut diags = validate_config(&raw);
+        locate_lines(&mut diags, source);
+        let typo = diags
+            .iter()
+            .find(|d| d.path == "agents.defaults.modle")
+            .unwrap();
+        assert_eq!(typo.level, DiagnosticLevel::Error);
+        assert_eq!(typo.line, Some(3));
+        assert!(typo
+            .to_string()
+            .starts_with("[ERROR] agents.defaults.modle (line 3): "));
+    }
 }
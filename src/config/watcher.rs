@@ -11,21 +11,42 @@ use crate::config::Config;
 /// Polling-based config watcher.
 pub struct ConfigWatcher {
     path: PathBuf,
+    /// Profile layered over `path`; watched too.
+    profile: Option<PathBuf>,
     poll_interval: Duration,
-    last_mtime: Option<SystemTime>,
+    last_mtime: [Option<SystemTime>; 2],
 }
 
 impl ConfigWatcher {
     pub fn new(path: PathBuf, poll_interval: Duration) -> Self {
         Self {
             path,
+            profile: None,
             poll_interval,
-            last_mtime: None,
+            last_mtime: [None; 2],
         }
     }
 
+    /// Watch the default path and the active profile, if any.
     pub fn default_path(poll_interval: Duration) -> Self {
-        Self::new(Config::path(), poll_interval)
+        let watcher = Self::new(Config::path(), poll_interval);
+        match Config::active_profile() {
+            Some(name) => watcher.with_profile(Config::profile_path(&name)),
+            None => watcher,
+        }
+    }
+
+    /// Layer the profile at `profile` over the config on reload.
+    pub fn with_profile(mut self, profile: PathBuf) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    fn mtimes(&self) -> [Option<SystemTime>; 2] {
+        [
+            read_mtime(&self.path),
+            self.profile.as_ref().and_then(read_mtime),
+        ]
     }
 
     pub async fn watch(
@@ -33,7 +54,7 @@ impl ConfigWatcher {
         tx: mpsc::UnboundedSender<Config>,
         mut shutdown_rx: watch::Receiver<bool>,
     ) {
-        self.last_mtime = read_mtime(&self.path);
+        self.last_mtime = self.mtimes();
         loop {
             tokio::select! {
                 _ = shutdown_rx.changed() => {
@@ -49,18 +70,22 @@ impl ConfigWatcher {
                 return;
             }
 
-            let current = read_mtime(&self.path);
-            let changed = match (self.last_mtime, current) {
+            let current = self.mtimes();
+            let changed = self.last_mtime.iter().zip(&current).any(|pair| match pair {
                 (Some(prev), Some(next)) => next != prev,
                 (None, Some(_)) => true,
                 _ => false,
-            };
+            });
             if !changed {
                 continue;
             }
 
             self.last_mtime = current;
-            match Config::load_from_path(&self.path) {
+            let loaded = match &self.profile {
+                Some(profile) => Config::load_with_profile(&self.path, profile),
+                None => Config::load_from_path(&self.path),
+            };
+            match loaded {
                 Ok(config) => {
                     debug!(path = %self.path.display(), "Config file changed, reloading");
                     if tx.send(config).is_err() {
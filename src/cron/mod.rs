@@ -1,10 +1,14 @@
 //! Cron service for scheduling background agent turns.
 
+pub mod natural;
+pub mod template;
+
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
@@ -56,6 +60,17 @@ pub struct CronJob {
     pub timeout_secs: Option<u64>,
 }
 
+/// Changes for [`CronService::update_job`]; `None` fields are kept.
+#[derive(Debug, Clone, Default)]
+pub struct CronJobUpdate {
+    pub name: Option<String>,
+    pub schedule: Option<CronSchedule>,
+    pub delete_after_run: Option<bool>,
+    pub message: Option<String>,
+    pub channel: Option<String>,
+    pub chat_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct CronStore {
     version: u32,
@@ -75,11 +90,24 @@ fn now_ms() -> i64 {
     Utc::now().timestamp_millis()
 }
 
+/// Inbound metadata key carrying the id of the job that sent a message.
+pub const CRON_JOB_ID_KEY: &str = "cron_job_id";
+
+/// The agent message for a job run.
+fn job_message(job_id: &str, payload: &CronPayload) -> InboundMessage {
+    InboundMessage::new(&payload.channel, "cron", &payload.chat_id, &payload.message)
+        .mark_internal()
+        .with_metadata(CRON_JOB_ID_KEY, job_id)
+}
+
 #[cfg(test)]
 const DEFAULT_DISPATCH_TIMEOUT_MS: u64 = 50;
 #[cfg(not(test))]
 const DEFAULT_DISPATCH_TIMEOUT_MS: u64 = 5_000;
 
+/// Upper bound on enabled jobs accepted by the cron tool and gateway API.
+pub const MAX_ACTIVE_JOBS: usize = 50;
+
 /// Deduplication window: if a job's `last_run_at_ms` is within this window of
 /// `next_run_at_ms`, we consider it already dispatched (crash recovery guard).
 const DEDUP_WINDOW_MS: i64 = 60_000;
@@ -187,6 +215,11 @@ pub fn is_valid_cron_expr(expr: &str) -> bool {
     next_run_from_cron_expr(expr, now_ms()).is_some()
 }
 
+/// Next time `schedule` fires from now, in epoch milliseconds.
+pub fn next_run_ms(schedule: &CronSchedule) -> Option<i64> {
+    next_run_at(schedule, now_ms())
+}
+
 fn next_run_at(schedule: &CronSchedule, now: i64) -> Option<i64> {
     match schedule {
         CronSchedule::At { at_ms } => {
@@ -221,7 +254,7 @@ fn jitter_delay(max_ms: u64) -> std::time::Duration {
 }
 
 /// Policy for handling missed schedules (jobs due while process was down).
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum OnMiss {
     /// Skip missed runs, reschedule to next future time (default).
@@ -266,12 +299,12 @@ impl CronService {
         }
 
         let loaded = self.load_store().await?;
-        let missed_payloads: Vec<CronPayload>;
+        let missed_jobs: Vec<(String, CronPayload)>;
         {
             let mut store = self.store.write().await;
             *store = loaded;
             let now = now_ms();
-            let mut missed: Vec<CronPayload> = Vec::new();
+            let mut missed: Vec<(String, CronPayload)> = Vec::new();
             for job in &mut store.jobs {
                 if job.enabled {
                     if let Some(next) = job.state.next_run_at_ms {
@@ -294,7 +327,7 @@ impl CronService {
                                         );
                                     } else {
                                         info!(job_id = %job.id, job_name = %job.name, "Queueing missed schedule for immediate run");
-                                        missed.push(job.payload.clone());
+                                        missed.push((job.id.clone(), job.payload.clone()));
                                     }
                                 }
                             }
@@ -307,14 +340,12 @@ impl CronService {
                     }
                 }
             }
-            missed_payloads = missed;
+            missed_jobs = missed;
         }
 
         // Dispatch missed jobs outside the lock
-        for payload in &missed_payloads {
-            let inbound =
-                InboundMessage::new(&payload.channel, "cron", &payload.chat_id, &payload.message);
-            if let Err(e) = self.bus.publish_inbound(inbound).await {
+        for (job_id, payload) in &missed_jobs {
+            if let Err(e) = self.bus.publish_inbound(job_message(job_id, payload)).await {
                 error!("Failed to dispatch missed job: {}", e);
             }
         }
@@ -432,6 +463,114 @@ impl CronService {
         Ok(removed)
     }
 
+    /// Get a job by id.
+    pub async fn get_job(&self, job_id: &str) -> Option<CronJob> {
+        let store = self.store.read().await;
+        store.jobs.iter().find(|job| job.id == job_id).cloned()
+    }
+
+    /// Pause a job: it keeps its schedule and payload but does not run
+    /// until resumed. Returns `None` if no job has that id.
+    pub async fn pause_job(&self, job_id: &str) -> Result<Option<CronJob>> {
+        self.modify_job(job_id, |job, _| {
+            job.enabled = false;
+            job.state.next_run_at_ms = None;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Resume a paused job from its next scheduled time. Fails for one-shot
+    /// jobs whose time has passed.
+    pub async fn resume_job(&self, job_id: &str) -> Result<Option<CronJob>> {
+        self.modify_job(job_id, |job, now| {
+            let next = next_run_at(&job.schedule, now).ok_or_else(|| {
+                ZeptoError::Tool(format!(
+                    "Cron job {} has no future run; update its schedule first",
+                    job.id
+                ))
+            })?;
+            job.enabled = true;
+            job.state.next_run_at_ms = Some(next);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Change a job in place, keeping its id and run history. A new
+    /// schedule takes effect from now (for enabled jobs).
+    pub async fn update_job(&self, job_id: &str, update: CronJobUpdate) -> Result<Option<CronJob>> {
+        self.modify_job(job_id, |job, now| {
+            if let Some(schedule) = update.schedule {
+                let next = next_run_at(&schedule, now);
+                if job.enabled && next.is_none() {
+                    return Err(ZeptoError::Tool(
+                        "The new schedule has no future run".to_string(),
+                    ));
+                }
+                job.schedule = schedule;
+                if job.enabled {
+                    job.state.next_run_at_ms = next;
+                }
+            }
+            if let Some(delete_after_run) = update.delete_after_run {
+                job.delete_after_run = delete_after_run;
+            }
+            if let Some(name) = update.name {
+                job.name = name;
+            }
+            if let Some(message) = update.message {
+                job.payload.message = message;
+            }
+            if let Some(channel) = update.channel {
+                job.payload.channel = channel;
+            }
+            if let Some(chat_id) = update.chat_id {
+                job.payload.chat_id = chat_id;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Run a job now, outside its schedule; paused jobs can be run too. The
+    /// run is recorded but the next scheduled run is unchanged. Returns
+    /// `None` if no job has that id.
+    pub async fn run_job_now(&self, job_id: &str) -> Result<Option<CronJob>> {
+        let Some(job) = self.get_job(job_id).await else {
+            return Ok(None);
+        };
+        let started_at = now_ms();
+        let sent = dispatch_job(&self.bus, &job).await;
+        let ended_at = now_ms();
+        self.modify_job(job_id, |job, _| {
+            record_run(job, sent.err(), started_at, ended_at);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Apply `change` to a job and save. Returns the updated job, or `None`
+    /// if no job has that id.
+    async fn modify_job(
+        &self,
+        job_id: &str,
+        change: impl FnOnce(&mut CronJob, i64) -> Result<()>,
+    ) -> Result<Option<CronJob>> {
+        let job = {
+            let mut store = self.store.write().await;
+            let Some(job) = store.jobs.iter_mut().find(|job| job.id == job_id) else {
+                return Ok(None);
+            };
+            let now = now_ms();
+            change(job, now)?;
+            job.updated_at_ms = now;
+            job.clone()
+        };
+        self.save_store().await?;
+        Ok(Some(job))
+    }
+
     async fn load_store(&self) -> Result<CronStore> {
         if !self.store_path.exists() {
             return Ok(CronStore::default());
@@ -460,6 +599,35 @@ impl Drop for CronService {
     }
 }
 
+/// Publish a job's message, bounded by its dispatch timeout.
+async fn dispatch_job(bus: &MessageBus, job: &CronJob) -> std::result::Result<(), String> {
+    let timeout_ms = dispatch_timeout_ms(job.timeout_secs);
+    match tokio::time::timeout(
+        std::time::Duration::from_millis(timeout_ms),
+        bus.publish_inbound(job_message(&job.id, &job.payload)),
+    )
+    .await
+    {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err("cron dispatch timed out".to_string()),
+    }
+}
+
+/// Record a run's outcome in the job state (`error` is `None` on success).
+fn record_run(job: &mut CronJob, error: Option<String>, started_at: i64, ended_at: i64) {
+    job.state.last_run_at_ms = Some(started_at);
+    job.state.last_duration_ms = Some((ended_at - started_at).max(0));
+    job.state.last_status = Some(if error.is_none() { "ok" } else { "error" }.to_string());
+    if error.is_none() {
+        job.state.consecutive_errors = 0;
+    } else {
+        job.state.consecutive_errors = job.state.consecutive_errors.saturating_add(1);
+    }
+    job.state.last_error = error;
+    job.updated_at_ms = ended_at;
+}
+
 async fn tick(
     store: &Arc<RwLock<CronStore>>,
     store_path: &PathBuf,
@@ -486,56 +654,25 @@ async fn tick(
     let mut results: Vec<(String, bool, Option<String>, i64, i64)> = Vec::new();
     for job in &due_jobs {
         let started_at = now_ms();
-        let inbound = InboundMessage::new(
-            &job.payload.channel,
-            "cron",
-            &job.payload.chat_id,
-            &job.payload.message,
-        );
         if jitter_ms > 0 {
             tokio::time::sleep(jitter_delay(jitter_ms)).await;
         }
-        let timeout_ms = dispatch_timeout_ms(job.timeout_secs);
-        let send_result = tokio::time::timeout(
-            std::time::Duration::from_millis(timeout_ms),
-            bus.publish_inbound(inbound),
-        )
-        .await;
+        let sent = dispatch_job(bus, job).await;
         let ended_at = now_ms();
-        match send_result {
-            Ok(Ok(())) => results.push((job.id.clone(), true, None, started_at, ended_at)),
-            Ok(Err(e)) => results.push((
-                job.id.clone(),
-                false,
-                Some(e.to_string()),
-                started_at,
-                ended_at,
-            )),
-            Err(_) => results.push((
-                job.id.clone(),
-                false,
-                Some("cron dispatch timed out".to_string()),
-                started_at,
-                ended_at,
-            )),
-        }
+        results.push((
+            job.id.clone(),
+            sent.is_ok(),
+            sent.err(),
+            started_at,
+            ended_at,
+        ));
     }
 
     {
         let mut store_guard = store.write().await;
         for (job_id, ok, err, started_at, ended_at) in results {
             if let Some(job) = store_guard.jobs.iter_mut().find(|j| j.id == job_id) {
-                job.state.last_run_at_ms = Some(started_at);
-                job.state.last_duration_ms = Some((ended_at - started_at).max(0));
-                job.state.last_status = Some(if ok { "ok" } else { "error" }.to_string());
-                job.state.last_error = err;
-                job.updated_at_ms = ended_at;
-                if ok {
-                    job.state.consecutive_errors = 0;
-                } else {
-                    job.state.consecutive_errors = job.state.consecutive_errors.saturating_add(1);
-                }
-
+                record_run(job, err, started_at, ended_at);
                 match job.schedule {
                     CronSchedule::At { .. } => {
                         job.enabled = false;
@@ -592,6 +729,65 @@ pub fn parse_at_datetime_ms(input: &str) -> Result<i64> {
     )))
 }
 
+/// Build a schedule from exactly one of `every_seconds`, `cron_expr`, or `at`.
+///
+/// Returns the schedule and whether the job should be deleted after it runs
+/// (true for one-shot `at` jobs).
+pub fn parse_schedule(
+    every_seconds: Option<i64>,
+    cron_expr: Option<&str>,
+    at: Option<&str>,
+) -> Result<(CronSchedule, bool)> {
+    let schedule_count = [every_seconds.is_some(), cron_expr.is_some(), at.is_some()]
+        .iter()
+        .filter(|set| **set)
+        .count();
+    if schedule_count != 1 {
+        return Err(ZeptoError::Tool(
+            "Specify exactly one of: every_seconds, cron_expr, at".to_string(),
+        ));
+    }
+
+    if let Some(seconds) = every_seconds {
+        // Minimum interval rate limiting
+        if seconds < 60 {
+            return Err(ZeptoError::Tool(
+                "Minimum interval is 60 seconds".to_string(),
+            ));
+        }
+        Ok((
+            CronSchedule::Every {
+                every_ms: seconds * 1_000,
+            },
+            false,
+        ))
+    } else if let Some(expr) = cron_expr {
+        if !is_valid_cron_expr(expr) {
+            return Err(ZeptoError::Tool(format!(
+                "Invalid or non-runnable cron expression '{}'",
+                expr
+            )));
+        }
+        Ok((
+            CronSchedule::Cron {
+                expr: expr.to_string(),
+            },
+            false,
+        ))
+    } else {
+        let at_ms = parse_at_datetime_ms(at.unwrap_or_default())?;
+        Ok((CronSchedule::At { at_ms }, true))
+    }
+}
+
+/// Default job name: the message, truncated to 30 characters.
+pub fn default_job_name(message: &str) -> String {
+    match message.char_indices().nth(30) {
+        Some((end, _)) => format!("{}...", &message[..end]),
+        None => message.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -611,6 +807,31 @@ mod tests {
         assert!(ms > 0);
     }
 
+    #[test]
+    fn test_parse_schedule() {
+        let (schedule, once) = parse_schedule(Some(120), None, None).unwrap();
+        assert!(matches!(
+            schedule,
+            CronSchedule::Every { every_ms: 120_000 }
+        ));
+        assert!(!once);
+
+        let (_, once) = parse_schedule(None, None, Some("2026-02-12T12:34:56Z")).unwrap();
+        assert!(once);
+
+        assert!(parse_schedule(Some(10), None, None).is_err());
+        assert!(parse_schedule(None, Some("not cron"), None).is_err());
+        assert!(parse_schedule(Some(120), Some("0 9 * * *"), None).is_err());
+        assert!(parse_schedule(None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_default_job_name_truncates() {
+        assert_eq!(default_job_name("short"), "short");
+        let long = "é".repeat(40);
+        assert_eq!(default_job_name(&long), format!("{}...", "é".repeat(30)));
+    }
+
     #[tokio::test]
     async fn test_add_list_remove_job() {
         let temp = tempdir().unwrap();
@@ -639,6 +860,94 @@ mod tests {
         assert!(service.list_jobs(true).await.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_pause_resume_update_and_run_now() {
+        let temp = tempdir().unwrap();
+        let bus = Arc::new(MessageBus::new());
+        let service = CronService::new(temp.path().join("jobs.json"), bus.clone());
+        let job = service
+            .add_job(
+                "report".to_string(),
+                CronSchedule::Every { every_ms: 60_000 },
+                CronPayload {
+                    message: "hello".to_string(),
+                    channel: "cli".to_string(),
+                    chat_id: "cli".to_string(),
+                },
+                false,
+            )
+            .await
+            .unwrap();
+
+        let paused = service.pause_job(&job.id).await.unwrap().unwrap();
+        assert!(!paused.enabled);
+        assert_eq!(paused.state.next_run_at_ms, None);
+        assert!(service.list_jobs(false).await.is_empty());
+
+        let resumed = service.resume_job(&job.id).await.unwrap().unwrap();
+        assert!(resumed.enabled);
+        assert!(resumed.state.next_run_at_ms.unwrap() > now_ms());
+
+        let updated = service
+            .update_job(
+                &job.id,
+                CronJobUpdate {
+                    schedule: Some(CronSchedule::Cron {
+                        expr: "0 9 * * *".to_string(),
+                    }),
+                    message: Some("morning report".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.id, job.id);
+        assert_eq!(updated.name, "report");
+        assert_eq!(updated.payload.message, "morning report");
+        assert!(matches!(updated.schedule, CronSchedule::Cron { .. }));
+        assert!(updated.state.next_run_at_ms.is_some());
+
+        let ran = service.run_job_now(&job.id).await.unwrap().unwrap();
+        assert_eq!(ran.state.last_status.as_deref(), Some("ok"));
+        assert_eq!(ran.state.next_run_at_ms, updated.state.next_run_at_ms);
+        let msg = bus.consume_inbound().await.unwrap();
+        assert_eq!(msg.content, "morning report");
+        assert_eq!(
+            msg.metadata.get(CRON_JOB_ID_KEY).map(String::as_str),
+            Some(job.id.as_str())
+        );
+
+        // Persisted, and unknown ids are reported as missing.
+        let reloaded = CronService::new(temp.path().join("jobs.json"), bus);
+        let store = reloaded.load_store().await.unwrap();
+        assert_eq!(store.jobs[0].payload.message, "morning report");
+        assert!(service.pause_job("nope").await.unwrap().is_none());
+        assert!(service.run_job_now("nope").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resume_expired_one_shot_fails() {
+        let temp = tempdir().unwrap();
+        let service = CronService::new(temp.path().join("jobs.json"), Arc::new(MessageBus::new()));
+        let job = service
+            .add_job(
+                "once".to_string(),
+                CronSchedule::At { at_ms: 1 },
+                CronPayload {
+                    message: "late".to_string(),
+                    channel: "cli".to_string(),
+                    chat_id: "cli".to_string(),
+                },
+                true,
+            )
+            .await
+            .unwrap();
+        service.pause_job(&job.id).await.unwrap();
+        let err = service.resume_job(&job.id).await.unwrap_err();
+        assert!(err.to_string().contains("no future run"));
+    }
+
     #[test]
     fn test_jitter_delay_zero() {
         let d = jitter_delay(0);
@@ -755,6 +1064,10 @@ mod tests {
             .expect("should receive dispatched missed job within timeout")
             .expect("bus should have a message");
         assert_eq!(msg.content, "run_once_check");
+        assert_eq!(
+            msg.metadata.get(CRON_JOB_ID_KEY).map(String::as_str),
+            Some("missed2")
+        );
 
         // Job should still be rescheduled to the future
         let jobs = service.list_jobs(true).await;
@@ -0,0 +1,603 @@
+//! Natural-language schedule parsing.
+//!
+//! Turns phrases such as `"in 45 min"`, `"tomorrow at 9"`,
+//! `"next Tuesday at 9:30pm"`, `"every weekday at 8am"` or `"every 2 hours"`
+//! into either a one-shot local datetime or a [`CronSchedule`].
+//!
+//! Parsing is purely local (no network, no LLM). Times are interpreted in
+//! the offset of the supplied `now`; recurring times are converted to the
+//! UTC cron expressions the scheduler runs on. Recurring schedules use the
+//! offset at creation time, so they drift by an hour across DST changes.
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveTime, TimeZone, Timelike,
+    Weekday,
+};
+
+use super::CronSchedule;
+use crate::error::{Result, ZeptoError};
+
+/// Default time of day when only a day is given ("tomorrow", "next friday").
+const DEFAULT_HOUR: u32 = 9;
+
+/// Result of parsing a natural-language schedule.
+#[derive(Debug, Clone)]
+pub enum When {
+    /// A single point in time.
+    Once(DateTime<FixedOffset>),
+    /// A repeating schedule.
+    Recurring {
+        schedule: CronSchedule,
+        /// Human-readable summary in local time, e.g. "every weekday at 08:00".
+        description: String,
+    },
+}
+
+/// Parse `input` relative to the local clock.
+pub fn parse_when_local(input: &str) -> Result<When> {
+    parse_when(input, Local::now().fixed_offset())
+}
+
+/// Parse `input` relative to `now`.
+pub fn parse_when(input: &str, now: DateTime<FixedOffset>) -> Result<When> {
+    let normalized = normalize(input);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    if tokens.is_empty() {
+        return Err(unparseable(input));
+    }
+
+    let recurring = matches!(
+        tokens[0],
+        "every" | "each" | "daily" | "hourly" | "weekdays" | "weekends"
+    );
+    let parsed = if recurring {
+        parse_recurring(&tokens, now)
+    } else {
+        parse_once(&tokens, now).map(When::Once)
+    };
+    parsed.ok_or_else(|| unparseable(input))
+}
+
+fn unparseable(input: &str) -> ZeptoError {
+    ZeptoError::Tool(format!(
+        "Could not understand time '{}'. Try 'in 45 min', 'tomorrow at 9', \
+         'next tuesday at 6pm', or 'every weekday at 8am'",
+        input.trim()
+    ))
+}
+
+fn normalize(input: &str) -> String {
+    input
+        .to_lowercase()
+        .replace("a.m.", "am")
+        .replace("p.m.", "pm")
+        .replace([',', ';'], " ")
+        .split_whitespace()
+        .map(|t| t.trim_end_matches(['.', '!', '?']))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ---------------------------------------------------------------------------
+// One-shot
+// ---------------------------------------------------------------------------
+
+fn parse_once(tokens: &[&str], now: DateTime<FixedOffset>) -> Option<DateTime<FixedOffset>> {
+    if tokens == ["now"] {
+        return Some(now);
+    }
+    // "in 45 min", "in an hour and a half"
+    if tokens[0] == "in" {
+        let duration = parse_duration(&tokens[1..])?;
+        return Some(now + duration);
+    }
+    // "45 min from now"
+    if tokens.len() > 2 && tokens[tokens.len() - 2..] == ["from", "now"] {
+        let duration = parse_duration(&tokens[..tokens.len() - 2])?;
+        return Some(now + duration);
+    }
+
+    let today = now.date_naive();
+    let mut day: Option<NaiveDate> = None;
+    let mut time: Option<NaiveTime> = None;
+    // A bare weekday that already passed today means next week.
+    let mut roll_week = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        let tok = tokens[i];
+        let prev = if i > 0 { tokens[i - 1] } else { "" };
+        match tok {
+            "at" | "@" | "on" | "by" | "the" => {}
+            "today" => day = Some(today),
+            "tonight" => {
+                day = Some(today);
+                time = time.or_else(|| NaiveTime::from_hms_opt(20, 0, 0));
+            }
+            "tomorrow" | "tmrw" | "tmr" => day = Some(today + Duration::days(1)),
+            "next" | "this" => {
+                let next = *tokens.get(i + 1)?;
+                if next == "week" {
+                    day = Some(today + Duration::days(7));
+                } else {
+                    let weekday = parse_weekday(next)?;
+                    day = Some(upcoming(today, weekday, tok == "next"));
+                    roll_week = tok == "this";
+                }
+                i += 1;
+            }
+            _ => {
+                if let Some(weekday) = parse_weekday(tok) {
+                    day = Some(upcoming(today, weekday, false));
+                    roll_week = true;
+                } else if let Some(t) = part_of_day(tok) {
+                    time = time.or(Some(t));
+                } else if let Some((t, consumed)) =
+                    parse_time(tok, tokens.get(i + 1).copied(), prev == "at" || prev == "@")
+                {
+                    time = Some(t);
+                    i += consumed - 1;
+                } else {
+                    return None;
+                }
+            }
+        }
+        i += 1;
+    }
+
+    let (date, time) = match (day, time) {
+        (None, None) => return None,
+        (Some(d), t) => (
+            d,
+            t.unwrap_or_else(|| NaiveTime::from_hms_opt(DEFAULT_HOUR, 0, 0).unwrap()),
+        ),
+        (None, Some(t)) => {
+            // Time only: today if still ahead, otherwise tomorrow.
+            let candidate = local(now, today, t)?;
+            if candidate > now {
+                return Some(candidate);
+            }
+            (today + Duration::days(1), t)
+        }
+    };
+    let mut at = local(now, date, time)?;
+    if at <= now && roll_week {
+        at += Duration::days(7);
+    }
+    Some(at)
+}
+
+/// Next date falling on `weekday`. `strictly_after` excludes today.
+fn upcoming(today: NaiveDate, weekday: Weekday, strictly_after: bool) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() as i64
+        - today.weekday().num_days_from_monday() as i64)
+        .rem_euclid(7);
+    let ahead = if ahead == 0 && strictly_after {
+        7
+    } else {
+        ahead
+    };
+    today + Duration::days(ahead)
+}
+
+fn local(
+    now: DateTime<FixedOffset>,
+    date: NaiveDate,
+    time: NaiveTime,
+) -> Option<DateTime<FixedOffset>> {
+    now.offset()
+        .from_local_datetime(&date.and_time(time))
+        .single()
+}
+
+// ---------------------------------------------------------------------------
+// Recurring
+// ---------------------------------------------------------------------------
+
+fn parse_recurring(tokens: &[&str], now: DateTime<FixedOffset>) -> Option<When> {
+    let mut rest = tokens;
+    if matches!(rest[0], "every" | "each") {
+        rest = &rest[1..];
+    }
+    if rest.is_empty() {
+        return None;
+    }
+
+    // Interval schedules: "every 15 minutes", "every hour", "hourly".
+    let interval = match rest {
+        ["hourly"] | ["hour"] => Some(Duration::hours(1)),
+        ["minute"] => Some(Duration::minutes(1)),
+        _ => parse_duration(rest).filter(|_| rest[0].parse::<u32>().is_ok() || rest.len() == 1),
+    };
+    if let Some(every) = interval {
+        if every < Duration::minutes(1) {
+            return None;
+        }
+        return Some(When::Recurring {
+            schedule: CronSchedule::Every {
+                every_ms: every.num_milliseconds(),
+            },
+            description: format!("every {}", describe_duration(every)),
+        });
+    }
+
+    // Day-of-week schedules with an optional time.
+    let mut days: Vec<Weekday> = Vec::new();
+    let mut label = String::new();
+    let mut time: Option<NaiveTime> = None;
+    let mut i = 0;
+    while i < rest.len() {
+        let tok = rest[i];
+        let prev = if i > 0 { rest[i - 1] } else { "" };
+        match tok {
+            "at" | "@" | "and" | "&" | "on" | "in" | "the" => {}
+            "day" | "days" | "daily" => label = "day".into(),
+            "weekday" | "weekdays" => {
+                label = "weekday".into();
+                days.extend([
+                    Weekday::Mon,
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri,
+                ]);
+            }
+            "weekend" | "weekends" => {
+                label = "weekend day".into();
+                days.extend([Weekday::Sat, Weekday::Sun]);
+            }
+            _ => {
+                if let Some(weekday) = parse_weekday(tok) {
+                    days.push(weekday);
+                } else if let Some(t) = part_of_day(tok) {
+                    time = time.or(Some(t));
+                    if label.is_empty() && days.is_empty() {
+                        label = "day".into();
+                    }
+                } else if let Some((t, consumed)) =
+                    parse_time(tok, rest.get(i + 1).copied(), prev == "at" || prev == "@")
+                {
+                    time = Some(t);
+                    i += consumed - 1;
+                } else {
+                    return None;
+                }
+            }
+        }
+        i += 1;
+    }
+    if label.is_empty() && days.is_empty() {
+        return None;
+    }
+    days.sort_by_key(|d| d.num_days_from_monday());
+    days.dedup();
+
+    let time = time.unwrap_or_else(|| NaiveTime::from_hms_opt(DEFAULT_HOUR, 0, 0).unwrap());
+    let expr = cron_expr_utc(time, &days, now.offset().local_minus_utc() / 60);
+    let which = if !label.is_empty() && label != "day" {
+        label
+    } else if days.is_empty() {
+        "day".to_string()
+    } else {
+        days.iter()
+            .map(|d| format!("{:?}", d))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    Some(When::Recurring {
+        schedule: CronSchedule::Cron { expr },
+        description: format!("every {} at {}", which, time.format("%H:%M")),
+    })
+}
+
+/// Build a UTC cron expression for local `time` on `days` (empty = daily).
+fn cron_expr_utc(time: NaiveTime, days: &[Weekday], offset_minutes: i32) -> String {
+    let local_minutes = (time.hour() * 60 + time.minute()) as i32;
+    let utc_minutes = local_minutes - offset_minutes;
+    let day_shift = utc_minutes.div_euclid(24 * 60);
+    let utc_minutes = utc_minutes.rem_euclid(24 * 60);
+
+    let dow = if days.is_empty() {
+        "*".to_string()
+    } else {
+        let mut utc_days: Vec<i32> = days
+            .iter()
+            .map(|d| (d.num_days_from_sunday() as i32 + day_shift).rem_euclid(7))
+            .collect();
+        utc_days.sort_unstable();
+        utc_days.dedup();
+        utc_days
+            .iter()
+            .map(i32::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    format!("{} {} * * {}", utc_minutes % 60, utc_minutes / 60, dow)
+}
+
+// ---------------------------------------------------------------------------
+// Tokens
+// ---------------------------------------------------------------------------
+
+fn parse_weekday(tok: &str) -> Option<Weekday> {
+    let tok = tok
+        .strip_suffix('s')
+        .filter(|t| t.len() >= 3)
+        .unwrap_or(tok);
+    match tok {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "weds" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn part_of_day(tok: &str) -> Option<NaiveTime> {
+    let hour = match tok {
+        "morning" | "mornings" => 8,
+        "noon" | "midday" | "lunchtime" => 12,
+        "afternoon" | "afternoons" => 15,
+        "evening" | "evenings" => 18,
+        "night" | "nights" => 21,
+        "midnight" => 0,
+        _ => return None,
+    };
+    NaiveTime::from_hms_opt(hour, 0, 0)
+}
+
+/// Parse a time of day from `tok` (and possibly an `am`/`pm` in `next`).
+/// Returns the time and the number of tokens consumed. Bare hours ("9") are
+/// only accepted when `after_at` is set ("at 9").
+fn parse_time(tok: &str, next: Option<&str>, after_at: bool) -> Option<(NaiveTime, usize)> {
+    let (body, mut meridiem) = if let Some(b) = tok.strip_suffix("am") {
+        (b, Some(false))
+    } else if let Some(b) = tok.strip_suffix("pm") {
+        (b, Some(true))
+    } else {
+        (tok, None)
+    };
+    let mut consumed = 1;
+    if meridiem.is_none() {
+        match next {
+            Some("am") => {
+                meridiem = Some(false);
+                consumed = 2;
+            }
+            Some("pm") => {
+                meridiem = Some(true);
+                consumed = 2;
+            }
+            _ => {}
+        }
+    }
+
+    let (h, m, has_minutes) = match body.split_once([':', '.']) {
+        Some((h, m)) if m.len() == 2 => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?, true),
+        Some(_) => return None,
+        None => (body.parse::<u32>().ok()?, 0, false),
+    };
+    if meridiem.is_none() && !has_minutes && !after_at {
+        return None;
+    }
+    let hour = match meridiem {
+        Some(pm) => {
+            if !(1..=12).contains(&h) {
+                return None;
+            }
+            match (pm, h) {
+                (false, 12) => 0,
+                (true, 12) => 12,
+                (true, h) => h + 12,
+                (false, h) => h,
+            }
+        }
+        None => h,
+    };
+    NaiveTime::from_hms_opt(hour, m, 0).map(|t| (t, consumed))
+}
+
+/// Parse a duration such as "45 min", "an hour and a half", "2h30m",
+/// "1 hour and 15 minutes", or "half an hour".
+fn parse_duration(tokens: &[&str]) -> Option<Duration> {
+    let mut total = Duration::zero();
+    let mut matched = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        let tok = tokens[i];
+        match tok {
+            "and" | "&" => {
+                if tokens.get(i + 1..i + 3) == Some(&["a", "half"][..]) {
+                    // "an hour and a half": half of the preceding unit
+                    total += total / 2;
+                    i += 3;
+                    continue;
+                }
+            }
+            "half" => {
+                // "half an hour", "half hour"
+                let skip = if matches!(tokens.get(i + 1), Some(&"a" | &"an")) {
+                    2
+                } else {
+                    1
+                };
+                let unit = unit_duration(tokens.get(i + skip)?)?;
+                total += unit / 2;
+                matched = true;
+                i += skip + 1;
+                continue;
+            }
+            "a" | "an" | "one" => {
+                total += unit_duration(tokens.get(i + 1)?)?;
+                matched = true;
+                i += 2;
+                continue;
+            }
+            _ => {
+                if let Ok(n) = tok.parse::<i64>() {
+                    total += unit_duration(tokens.get(i + 1)?)? * n as i32;
+                    matched = true;
+                    i += 2;
+                    continue;
+                }
+                total += parse_compact_duration(tok)?;
+                matched = true;
+            }
+        }
+        i += 1;
+    }
+    (matched && total > Duration::zero()).then_some(total)
+}
+
+/// "45min", "2h", "1h30m", "90s".
+fn parse_compact_duration(tok: &str) -> Option<Duration> {
+    let mut total = Duration::zero();
+    let mut rest = tok;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        if digits == 0 {
+            return None;
+        }
+        let n: i32 = rest[..digits].parse().ok()?;
+        let after = &rest[digits..];
+        let unit_len = after
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(after.len());
+        total += unit_duration(&after[..unit_len])? * n;
+        rest = &after[unit_len..];
+    }
+    Some(total)
+}
+
+fn unit_duration(unit: &str) -> Option<Duration> {
+    match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(Duration::seconds(1)),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(Duration::minutes(1)),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(Duration::hours(1)),
+        "d" | "day" | "days" => Some(Duration::days(1)),
+        "w" | "wk" | "wks" | "week" | "weeks" => Some(Duration::weeks(1)),
+        _ => None,
+    }
+}
+
+fn describe_duration(d: Duration) -> String {
+    let minutes = d.num_minutes();
+    if minutes % (24 * 60) == 0 {
+        plural(minutes / (24 * 60), "day")
+    } else if minutes % 60 == 0 {
+        plural(minutes / 60, "hour")
+    } else {
+        plural(minutes, "minute")
+    }
+}
+
+fn plural(n: i64, unit: &str) -> String {
+    if n == 1 {
+        unit.to_string()
+    } else {
+        format!("{} {}s", n, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wednesday 2026-03-04 10:00 at UTC+8.
+    fn now() -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(8 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2026, 3, 4, 10, 0, 0)
+            .unwrap()
+    }
+
+    fn once(input: &str) -> String {
+        match parse_when(input, now()).unwrap() {
+            When::Once(at) => at.format("%a %Y-%m-%d %H:%M").to_string(),
+            other => panic!("expected one-shot for {input}, got {other:?}"),
+        }
+    }
+
+    fn cron(input: &str) -> (String, String) {
+        match parse_when(input, now()).unwrap() {
+            When::Recurring {
+                schedule: CronSchedule::Cron { expr },
+                description,
+            } => (expr, description),
+            other => panic!("expected cron for {input}, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_relative_durations() {
+        assert_eq!(once("in 45 min"), "Wed 2026-03-04 10:45");
+        assert_eq!(once("in an hour and a half"), "Wed 2026-03-04 11:30");
+        assert_eq!(once("in half an hour"), "Wed 2026-03-04 10:30");
+        assert_eq!(once("in 2h30m"), "Wed 2026-03-04 12:30");
+        assert_eq!(once("in 1 hour and 15 minutes"), "Wed 2026-03-04 11:15");
+        assert_eq!(once("3 days from now"), "Sat 2026-03-07 10:00");
+    }
+
+    #[test]
+    fn test_days_and_times() {
+        assert_eq!(once("tomorrow"), "Thu 2026-03-05 09:00");
+        assert_eq!(once("tomorrow at 9:30pm"), "Thu 2026-03-05 21:30");
+        assert_eq!(once("next Tuesday at 9"), "Tue 2026-03-10 09:00");
+        assert_eq!(once("next wednesday"), "Wed 2026-03-11 09:00");
+        assert_eq!(once("friday evening"), "Fri 2026-03-06 18:00");
+        assert_eq!(once("tonight"), "Wed 2026-03-04 20:00");
+        assert_eq!(once("at 3pm"), "Wed 2026-03-04 15:00");
+        assert_eq!(once("8am"), "Thu 2026-03-05 08:00");
+        assert_eq!(once("at 17:45"), "Wed 2026-03-04 17:45");
+        // Bare weekday equal to today but already passed rolls a week.
+        assert_eq!(once("wednesday at 9am"), "Wed 2026-03-11 09:00");
+    }
+
+    #[test]
+    fn test_recurring_cron_in_utc() {
+        // 08:00 at UTC+8 is 00:00 UTC the same day.
+        assert_eq!(
+            cron("every weekday at 8am"),
+            ("0 0 * * 1,2,3,4,5".into(), "every weekday at 08:00".into())
+        );
+        // 07:30 at UTC+8 is 23:30 UTC the previous day.
+        assert_eq!(cron("every monday and thursday at 7:30").0, "30 23 * * 0,3");
+        assert_eq!(cron("every day at 9pm").0, "0 13 * * *");
+        assert_eq!(cron("daily").0, "0 1 * * *");
+        assert_eq!(cron("every morning").0, "0 0 * * *");
+        assert_eq!(cron("every weekend at 10am").0, "0 2 * * 0,6");
+    }
+
+    #[test]
+    fn test_recurring_intervals() {
+        match parse_when("every 15 minutes", now()).unwrap() {
+            When::Recurring {
+                schedule: CronSchedule::Every { every_ms },
+                description,
+            } => {
+                assert_eq!(every_ms, 15 * 60 * 1000);
+                assert_eq!(description, "every 15 minutes");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(matches!(
+            parse_when("hourly", now()).unwrap(),
+            When::Recurring {
+                schedule: CronSchedule::Every {
+                    every_ms: 3_600_000
+                },
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_unparseable() {
+        assert!(parse_when("", now()).is_err());
+        assert!(parse_when("whenever you like", now()).is_err());
+        assert!(parse_when("in a while", now()).is_err());
+        assert!(parse_when("every blue moon", now()).is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! Report templates for cron job messages.
+//!
+//! A job message of the form `routine:<name>` is replaced by the template
+//! `<name>` from `routines.templates`. Messages (and templates) may embed
+//! `{{tool key=value ...}}` placeholders, e.g.
+//! `{{web_fetch url=https://status.example.com}}`; the agent runs those
+//! tools before the turn and splices in their output, so a recurring report
+//! starts with its data instead of the model re-deriving and fetching it on
+//! every run. Only read-only tools may be used in placeholders.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use crate::error::{Result, ZeptoError};
+use crate::tools::ToolCategory;
+
+/// Prefix naming a template in `routines.templates`.
+pub const ROUTINE_PREFIX: &str = "routine:";
+
+static PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([a-z][a-z0-9_]*)\b([^{}]*)\}\}").unwrap());
+
+/// One `key=value` or `key="quoted value"` argument at the start of a string.
+static ARGUMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^([A-Za-z_][A-Za-z0-9_]*)=(?:"([^"]*)"|([^\s"]+))(?:\s+|$)"#).unwrap()
+});
+
+/// A `{{tool key=value ...}}` placeholder in a message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    /// Byte range of the whole placeholder.
+    pub range: Range<usize>,
+    /// Tool to run.
+    pub tool: String,
+    /// Tool arguments. Numbers and booleans are typed, everything else is
+    /// a string; quote values containing spaces (`query="rust news"`).
+    pub args: Value,
+}
+
+/// Expand `routine:<name>` to its template. Other messages are returned
+/// unchanged.
+pub fn resolve_routine<'a>(
+    message: &'a str,
+    templates: &'a HashMap<String, String>,
+) -> Result<Cow<'a, str>> {
+    let Some(name) = message.trim().strip_prefix(ROUTINE_PREFIX) else {
+        return Ok(Cow::Borrowed(message));
+    };
+    let name = name.trim();
+    templates
+        .get(name)
+        .map(|template| Cow::Borrowed(template.as_str()))
+        .ok_or_else(|| {
+            ZeptoError::Config(format!(
+                "Unknown routine '{}' (define it in routines.templates)",
+                name
+            ))
+        })
+}
+
+/// Placeholders in `message`, in order. Braces whose content is not a
+/// name followed by `key=value` arguments (`{{hello world}}`) are not
+/// placeholders; the agent also leaves placeholders naming unknown tools
+/// as written, so prompt variables like `{{date}}` survive.
+pub fn placeholders(message: &str) -> Vec<Placeholder> {
+    PLACEHOLDER
+        .captures_iter(message)
+        .filter_map(|caps| {
+            Some(Placeholder {
+                range: caps.get(0)?.range(),
+                tool: caps[1].to_string(),
+                args: parse_args(&caps[2])?,
+            })
+        })
+        .collect()
+}
+
+fn parse_args(raw: &str) -> Option<Value> {
+    let mut args = Map::new();
+    let mut rest = raw.trim();
+    while !rest.is_empty() {
+        let arg = ARGUMENT.captures(rest)?;
+        let value = match (arg.get(2), arg.get(3)) {
+            (Some(quoted), _) => Value::String(quoted.as_str().to_string()),
+            (None, Some(bare)) => typed_value(bare.as_str()),
+            _ => return None,
+        };
+        args.insert(arg[1].to_string(), value);
+        rest = &rest[arg.get(0)?.end()..];
+    }
+    Some(Value::Object(args))
+}
+
+fn typed_value(raw: &str) -> Value {
+    if let Ok(n) = raw.parse::<i64>() {
+        return Value::from(n);
+    }
+    if let Ok(n) = raw.parse::<f64>() {
+        return Value::from(n);
+    }
+    match raw {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => Value::String(raw.to_string()),
+    }
+}
+
+/// Tools that may run in a template: they run without approval, so only
+/// read-only categories qualify.
+pub fn allowed_in_template(category: ToolCategory) -> bool {
+    matches!(
+        category,
+        ToolCategory::FilesystemRead | ToolCategory::NetworkRead
+    )
+}
+
+/// Replace `placeholders` (as returned by [`placeholders`]) with `outputs`.
+pub fn render(message: &str, placeholders: &[Placeholder], outputs: &[String]) -> String {
+    let mut rendered = String::with_capacity(message.len());
+    let mut last = 0;
+    for (placeholder, output) in placeholders.iter().zip(outputs) {
+        rendered.push_str(&message[last..placeholder.range.start]);
+        rendered.push_str(output);
+        last = placeholder.range.end;
+    }
+    rendered.push_str(&message[last..]);
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_resolve_routine() {
+        let templates = HashMap::from([(
+            "daily_brief".to_string(),
+            "Summarize {{news query=rust}}".to_string(),
+        )]);
+        assert_eq!(
+            resolve_routine("routine:daily_brief", &templates).unwrap(),
+            "Summarize {{news query=rust}}"
+        );
+        assert_eq!(
+            resolve_routine("Water the plants", &templates).unwrap(),
+            "Water the plants"
+        );
+        let err = resolve_routine("routine:weekly", &templates).unwrap_err();
+        assert!(err.to_string().contains("Unknown routine 'weekly'"));
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let message = "Status: {{web_fetch url=https://example.com/status max_chars=2000}}\n\
+                       News: {{ news query=\"rust lang\" }} for {{date}}";
+        let found = placeholders(message);
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].tool, "web_fetch");
+        assert_eq!(
+            found[0].args,
+            json!({"url": "https://example.com/status", "max_chars": 2000})
+        );
+        assert_eq!(found[1].tool, "news");
+        assert_eq!(found[1].args, json!({"query": "rust lang"}));
+        assert_eq!(
+            &message[found[1].range.clone()],
+            "{{ news query=\"rust lang\" }}"
+        );
+        assert_eq!(found[2].tool, "date");
+        assert_eq!(found[2].args, json!({}));
+
+        assert_eq!(placeholders("{{name}} says {{hello world}}").len(), 1);
+        assert!(placeholders("no templates here").is_empty());
+    }
+
+    #[test]
+    fn test_render() {
+        let message = "A {{time}} B {{weather location=Paris}} C";
+        let found = placeholders(message);
+        let rendered = render(message, &found, &["12:00".into(), "Sunny".into()]);
+        assert_eq!(rendered, "A 12:00 B Sunny C");
+    }
+
+    #[test]
+    fn test_allowed_in_template() {
+        assert!(allowed_in_template(ToolCategory::NetworkRead));
+        assert!(allowed_in_template(ToolCategory::FilesystemRead));
+        assert!(!allowed_in_template(ToolCategory::Shell));
+        assert!(!allowed_in_template(ToolCategory::NetworkWrite));
+    }
+}
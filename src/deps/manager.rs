@@ -63,9 +63,7 @@ impl DepManager {
 
     /// Default deps directory.
     pub fn default_dir() -> PathBuf {
-        dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zeptoclaw/deps")
+        crate::config::Config::data_dir().join("deps")
     }
 
     /// Save registry to disk.
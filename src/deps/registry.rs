@@ -111,9 +111,7 @@ impl Registry {
 
     /// Default registry file path.
     pub fn default_path() -> PathBuf {
-        dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zeptoclaw/deps/registry.json")
+        crate::config::Config::data_dir().join("deps/registry.json")
     }
 }
 
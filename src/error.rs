@@ -182,6 +182,10 @@ pub enum ZeptoError {
     /// Provider quota exceeded and the configured action is "reject" (no fallback).
     #[error("Quota rejected: {0}")]
     QuotaRejected(String),
+
+    /// The agent turn was cancelled (`/stop`, Ctrl+C).
+    #[error("Cancelled")]
+    Cancelled,
 }
 
 /// A specialized `Result` type for ZeptoClaw operations.
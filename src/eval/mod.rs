@@ -0,0 +1,498 @@
+//! Agent evaluation with scenario suites.
+//!
+//! A scenario is a prompt plus expectations about the turn it produces:
+//! tools that must (or must not) be called, optionally with argument
+//! subsets and order, and assertions on the final text. Scenarios live in
+//! YAML or JSON files, either one per file or as a `scenarios:` list, and a
+//! directory of such files forms a suite.
+//!
+//! [`runner::run_scenario`] executes a scenario on an [`AgentLoop`](crate::agent::AgentLoop),
+//! against the configured provider or, in mock mode, against the scenario's
+//! scripted `mock` responses. `mock_tools` replaces the named tools with
+//! fakes returning fixed output in both modes, so live runs see
+//! deterministic tool results. [`EvalReport`] scores the results:
+//! each scenario's score is the share of its checks that passed.
+//!
+//! ```yaml
+//! name: weather lookup
+//! prompt: What's the weather in Lisbon?
+//! expect:
+//!   tools:
+//!     - name: web_search
+//!       args: { query: Lisbon weather }
+//!   forbidden_tools: [shell]
+//!   contains: [Lisbon]
+//! mock:
+//!   - tool_call: { name: web_search, args: { query: Lisbon weather } }
+//!   - text: Sunny in Lisbon, 24°C.
+//! mock_tools:
+//!   web_search: "Lisbon: sunny, 24°C"
+//! ```
+
+pub mod runner;
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Result, ZeptoError};
+
+pub use runner::run_scenario;
+
+/// Default time limit for one scenario, in seconds.
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+/// One evaluation scenario.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    /// User message sent to the agent.
+    pub prompt: String,
+    /// Channel the message appears to come from.
+    #[serde(default = "default_channel")]
+    pub channel: String,
+    /// Time limit for the turn, in seconds.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub expect: Expectations,
+    /// Scripted provider responses, one per LLM call (mock mode).
+    #[serde(default)]
+    pub mock: Vec<MockStep>,
+    /// Tools replaced by fakes returning this output.
+    #[serde(default)]
+    pub mock_tools: BTreeMap<String, String>,
+    /// File the scenario was loaded from.
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+fn default_channel() -> String {
+    "eval".to_string()
+}
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_TIMEOUT_SECS
+}
+
+/// What a scenario's turn must do.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Expectations {
+    /// Tools that must be called.
+    pub tools: Vec<ToolCallSpec>,
+    /// Whether `tools` must be called in the listed order.
+    pub ordered: bool,
+    /// Tools that must not be called.
+    pub forbidden_tools: Vec<String>,
+    /// Most tool calls allowed.
+    pub max_tool_calls: Option<usize>,
+    /// Substrings the final response must contain.
+    pub contains: Vec<String>,
+    /// Substrings the final response must not contain.
+    pub not_contains: Vec<String>,
+    /// Regular expressions the final response must match.
+    pub matches: Vec<String>,
+    /// Compare `contains`/`not_contains` case-insensitively.
+    pub ignore_case: bool,
+}
+
+/// A tool call: the tool name and, optionally, arguments. Expected
+/// arguments match when they are a subset of the actual ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCallSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<Value>,
+}
+
+/// One scripted provider response. Set exactly one field.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MockStep {
+    pub text: Option<String>,
+    pub tool_call: Option<ToolCallSpec>,
+    pub tool_calls: Vec<ToolCallSpec>,
+    /// Provider error message.
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScenarioFile {
+    Suite { scenarios: Vec<Scenario> },
+    Single(Box<Scenario>),
+}
+
+/// Load the scenarios in a file, or in every `.yaml`/`.yml`/`.json` file
+/// of a directory (sorted by path).
+pub fn load_scenarios(path: &Path) -> Result<Vec<Scenario>> {
+    let mut files = Vec::new();
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            let file = entry?.path();
+            let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+            if matches!(ext, "yaml" | "yml" | "json") {
+                files.push(file);
+            }
+        }
+        files.sort();
+    } else {
+        files.push(path.to_path_buf());
+    }
+
+    let mut scenarios = Vec::new();
+    for file in files {
+        let raw = std::fs::read_to_string(&file)?;
+        // YAML is a superset of JSON, so one parser covers both.
+        let parsed: ScenarioFile = serde_yaml::from_str(&raw).map_err(|e| {
+            ZeptoError::Config(format!("Invalid scenario file {}: {}", file.display(), e))
+        })?;
+        let batch = match parsed {
+            ScenarioFile::Suite { scenarios } => scenarios,
+            ScenarioFile::Single(scenario) => vec![*scenario],
+        };
+        for mut scenario in batch {
+            scenario.source = Some(file.clone());
+            scenarios.push(scenario);
+        }
+    }
+    Ok(scenarios)
+}
+
+/// A tool call observed during a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObservedToolCall {
+    pub name: String,
+    pub args: Value,
+    pub ok: bool,
+}
+
+/// One evaluated assertion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Check {
+    pub description: String,
+    pub passed: bool,
+    /// Why the check failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl Check {
+    fn new(description: String, failure: Option<String>) -> Self {
+        Self {
+            description,
+            passed: failure.is_none(),
+            detail: failure,
+        }
+    }
+}
+
+/// Outcome of one scenario.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioResult {
+    pub name: String,
+    /// Final response, when the turn completed.
+    pub response: Option<String>,
+    /// Error that ended the turn.
+    pub error: Option<String>,
+    pub tool_calls: Vec<ObservedToolCall>,
+    pub checks: Vec<Check>,
+    pub duration_ms: u64,
+}
+
+impl ScenarioResult {
+    /// Whether every check passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.passed)
+    }
+
+    /// Share of checks that passed (0.0 – 1.0).
+    pub fn score(&self) -> f64 {
+        if self.checks.is_empty() {
+            return 1.0;
+        }
+        self.checks.iter().filter(|c| c.passed).count() as f64 / self.checks.len() as f64
+    }
+}
+
+/// Whether `expected` is a subset of `actual`: objects match key by key
+/// (recursively), everything else must be equal.
+pub fn args_match(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => expected
+            .iter()
+            .all(|(key, value)| actual.get(key).is_some_and(|a| args_match(value, a))),
+        _ => expected == actual,
+    }
+}
+
+fn spec_matches(spec: &ToolCallSpec, call: &ObservedToolCall) -> bool {
+    spec.name == call.name
+        && spec
+            .args
+            .as_ref()
+            .is_none_or(|args| args_match(args, &call.args))
+}
+
+fn describe(spec: &ToolCallSpec) -> String {
+    match &spec.args {
+        Some(args) => format!("{} {}", spec.name, args),
+        None => spec.name.clone(),
+    }
+}
+
+/// Evaluate `expect` against a finished turn. The first check is always
+/// that the turn completed.
+pub fn evaluate(
+    expect: &Expectations,
+    response: Option<&str>,
+    error: Option<&str>,
+    calls: &[ObservedToolCall],
+) -> Vec<Check> {
+    let mut checks = vec![Check::new(
+        "turn completes".to_string(),
+        error.map(str::to_string),
+    )];
+
+    for spec in &expect.tools {
+        let called = calls.iter().any(|c| spec_matches(spec, c));
+        checks.push(Check::new(
+            format!("calls {}", describe(spec)),
+            (!called).then(|| {
+                let names: Vec<&str> = calls.iter().map(|c| c.name.as_str()).collect();
+                format!("called: [{}]", names.join(", "))
+            }),
+        ));
+    }
+    if expect.ordered && expect.tools.len() > 1 {
+        let mut remaining = calls.iter();
+        let in_order = expect
+            .tools
+            .iter()
+            .all(|spec| remaining.any(|c| spec_matches(spec, c)));
+        checks.push(Check::new(
+            "calls tools in order".to_string(),
+            (!in_order).then(|| "expected tools were not called in the listed order".to_string()),
+        ));
+    }
+    for name in &expect.forbidden_tools {
+        let count = calls.iter().filter(|c| &c.name == name).count();
+        checks.push(Check::new(
+            format!("does not call {}", name),
+            (count > 0).then(|| format!("called {} time(s)", count)),
+        ));
+    }
+    if let Some(max) = expect.max_tool_calls {
+        checks.push(Check::new(
+            format!("at most {} tool call(s)", max),
+            (calls.len() > max).then(|| format!("made {}", calls.len())),
+        ));
+    }
+
+    let text = response.unwrap_or_default();
+    let fold = |s: &str| {
+        if expect.ignore_case {
+            s.to_lowercase()
+        } else {
+            s.to_string()
+        }
+    };
+    let haystack = fold(text);
+    for needle in &expect.contains {
+        checks.push(Check::new(
+            format!("response contains {:?}", needle),
+            (!haystack.contains(&fold(needle))).then(|| "not found".to_string()),
+        ));
+    }
+    for needle in &expect.not_contains {
+        checks.push(Check::new(
+            format!("response does not contain {:?}", needle),
+            haystack
+                .contains(&fold(needle))
+                .then(|| "found".to_string()),
+        ));
+    }
+    for pattern in &expect.matches {
+        let failure = match regex::Regex::new(pattern) {
+            Ok(re) => (!re.is_match(text)).then(|| "no match".to_string()),
+            Err(e) => Some(format!("invalid pattern: {}", e)),
+        };
+        checks.push(Check::new(
+            format!("response matches /{}/", pattern),
+            failure,
+        ));
+    }
+    checks
+}
+
+/// Results of a suite run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvalReport {
+    pub results: Vec<ScenarioResult>,
+}
+
+impl EvalReport {
+    /// Scenarios whose checks all passed.
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.passed()).count()
+    }
+
+    /// Mean scenario score (0.0 – 1.0); 1.0 for an empty suite.
+    pub fn score(&self) -> f64 {
+        if self.results.is_empty() {
+            return 1.0;
+        }
+        self.results.iter().map(ScenarioResult::score).sum::<f64>() / self.results.len() as f64
+    }
+
+    /// Human-readable report listing failed checks.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for result in &self.results {
+            let _ = writeln!(
+                out,
+                "{} {} ({:.0}%, {} ms)",
+                if result.passed() { "PASS" } else { "FAIL" },
+                result.name,
+                result.score() * 100.0,
+                result.duration_ms
+            );
+            for check in result.checks.iter().filter(|c| !c.passed) {
+                match &check.detail {
+                    Some(detail) => {
+                        let _ = writeln!(out, "  ✗ {}: {}", check.description, detail);
+                    }
+                    None => {
+                        let _ = writeln!(out, "  ✗ {}", check.description);
+                    }
+                }
+            }
+        }
+        let _ = writeln!(
+            out,
+            "\n{}/{} scenarios passed, score {:.1}%",
+            self.passed(),
+            self.results.len(),
+            self.score() * 100.0
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(name: &str, args: Value) -> ObservedToolCall {
+        ObservedToolCall {
+            name: name.to_string(),
+            args,
+            ok: true,
+        }
+    }
+
+    #[test]
+    fn test_load_scenarios_yaml_and_json() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.yaml"),
+            "scenarios:\n  - name: one\n    prompt: hi\n    expect:\n      contains: [hello]\n\
+             \x20 - name: two\n    prompt: search\n    mock:\n      - tool_call: { name: s, args: { q: x } }\n      - text: done\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("b.json"),
+            r#"{"name": "three", "prompt": "p", "mock_tools": {"s": "out"}, "timeout_secs": 5}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let scenarios = load_scenarios(dir.path()).unwrap();
+        let names: Vec<&str> = scenarios.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["one", "two", "three"]);
+        assert_eq!(scenarios[0].expect.contains, vec!["hello"]);
+        assert_eq!(scenarios[0].channel, "eval");
+        assert_eq!(scenarios[1].mock.len(), 2);
+        assert_eq!(
+            scenarios[1].mock[0].tool_call.as_ref().unwrap().args,
+            Some(json!({"q": "x"}))
+        );
+        assert_eq!(scenarios[2].mock_tools["s"], "out");
+        assert_eq!(scenarios[2].timeout_secs, 5);
+        assert!(scenarios[2].source.as_ref().unwrap().ends_with("b.json"));
+
+        std::fs::write(dir.path().join("c.yaml"), "prompt: no name").unwrap();
+        assert!(load_scenarios(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_args_match_subset() {
+        let actual = json!({"query": "rust", "opts": {"limit": 5, "lang": "en"}});
+        assert!(args_match(&json!({"query": "rust"}), &actual));
+        assert!(args_match(&json!({"opts": {"limit": 5}}), &actual));
+        assert!(!args_match(&json!({"opts": {"limit": 6}}), &actual));
+        assert!(!args_match(&json!({"missing": 1}), &actual));
+    }
+
+    #[test]
+    fn test_evaluate_checks_and_scores() {
+        let expect = Expectations {
+            tools: vec![
+                ToolCallSpec {
+                    name: "search".into(),
+                    args: Some(json!({"q": "rust"})),
+                },
+                ToolCallSpec {
+                    name: "fetch".into(),
+                    args: None,
+                },
+            ],
+            ordered: true,
+            forbidden_tools: vec!["shell".into()],
+            max_tool_calls: Some(2),
+            contains: vec!["RUST".into()],
+            not_contains: vec!["error".into()],
+            matches: vec![r"\d+ results".into()],
+            ignore_case: true,
+        };
+        let calls = vec![
+            call("fetch", json!({})),
+            call("search", json!({"q": "rust", "n": 3})),
+        ];
+        let checks = evaluate(&expect, Some("Rust: 3 results"), None, &calls);
+        let failed: Vec<&str> = checks
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.description.as_str())
+            .collect();
+        assert_eq!(failed, vec!["calls tools in order"]);
+
+        let result = ScenarioResult {
+            name: "s".into(),
+            response: Some("Rust: 3 results".into()),
+            error: None,
+            tool_calls: calls,
+            checks,
+            duration_ms: 10,
+        };
+        assert!(!result.passed());
+        assert!((result.score() - 8.0 / 9.0).abs() < 1e-9);
+
+        let errored = evaluate(&Expectations::default(), None, Some("timed out"), &[]);
+        assert_eq!(errored.len(), 1);
+        assert_eq!(errored[0].detail.as_deref(), Some("timed out"));
+
+        let report = EvalReport {
+            results: vec![result],
+        };
+        let text = report.render();
+        assert!(text.starts_with("FAIL s (89%"));
+        assert!(text.contains("✗ calls tools in order"));
+        assert!(text.contains("0/1 scenarios passed"));
+    }
+}
@@ -0,0 +1,216 @@
+//! Runs scenarios on an agent.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::agent::{AgentLoop, ToolFeedbackPhase};
+use crate::bus::InboundMessage;
+use crate::error::ZeptoError;
+use crate::providers::MockProvider;
+use crate::tools::FakeTool;
+
+use super::{evaluate, MockStep, ObservedToolCall, Scenario, ScenarioResult};
+
+/// Provider scripted from a scenario's `mock` steps.
+fn mock_provider(steps: &[MockStep]) -> MockProvider {
+    let mut provider = MockProvider::new().with_name("eval-mock");
+    for step in steps {
+        let calls: Vec<_> = step.tool_call.iter().chain(&step.tool_calls).collect();
+        provider = if let Some(error) = &step.error {
+            provider.with_error(ZeptoError::Provider(error.clone()))
+        } else if !calls.is_empty() {
+            provider.with_tool_calls(
+                calls
+                    .into_iter()
+                    .map(|c| {
+                        let args = c.args.clone().unwrap_or_else(|| serde_json::json!({}));
+                        (c.name.as_str(), args)
+                    })
+                    .collect(),
+            )
+        } else {
+            provider.with_text(step.text.as_deref().unwrap_or_default())
+        };
+    }
+    provider
+}
+
+/// Run `scenario` on `agent` and check its expectations.
+///
+/// In `mock` mode the agent's provider is replaced by the scenario's
+/// scripted responses; a scenario without any fails. Tools named in
+/// `mock_tools` are replaced by fakes in both modes. Both changes stay on
+/// the agent, so use a fresh agent per scenario.
+pub async fn run_scenario(agent: &AgentLoop, scenario: &Scenario, mock: bool) -> ScenarioResult {
+    let started = Instant::now();
+    let result = |error: String| ScenarioResult {
+        name: scenario.name.clone(),
+        response: None,
+        error: Some(error.clone()),
+        tool_calls: Vec::new(),
+        checks: evaluate(&scenario.expect, None, Some(&error), &[]),
+        duration_ms: 0,
+    };
+
+    if mock {
+        if scenario.mock.is_empty() {
+            return result("scenario has no mock responses".to_string());
+        }
+        agent
+            .set_provider_arc(Arc::new(mock_provider(&scenario.mock)))
+            .await;
+    }
+    for (name, output) in &scenario.mock_tools {
+        let mut fake = FakeTool::new(name).returning(output);
+        if let Some(definition) = agent.tool_definition(name).await {
+            fake = fake
+                .with_description(&definition.description)
+                .with_parameters(definition.parameters);
+        }
+        agent.register_tool(Box::new(fake)).await;
+    }
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    agent.set_tool_feedback(tx).await;
+    let mut msg = InboundMessage::new(&scenario.channel, "eval", "eval", &scenario.prompt);
+    msg.session_key = format!("eval:{}", scenario.name);
+    let _ = agent.session_manager().delete(&msg.session_key).await;
+
+    let outcome = tokio::time::timeout(
+        Duration::from_secs(scenario.timeout_secs.max(1)),
+        agent.process_message(&msg),
+    )
+    .await;
+    let _ = agent.session_manager().delete(&msg.session_key).await;
+    let (response, error) = match outcome {
+        Ok(Ok(response)) => (Some(response), None),
+        Ok(Err(e)) => (None, Some(e.to_string())),
+        Err(_) => (
+            None,
+            Some(format!("timed out after {}s", scenario.timeout_secs)),
+        ),
+    };
+
+    // Arguments arrive with `Starting`; the outcome with `Done`/`Failed`.
+    let mut tool_calls: Vec<ObservedToolCall> = Vec::new();
+    let mut settled: Vec<bool> = Vec::new();
+    while let Ok(feedback) = rx.try_recv() {
+        let ok = match feedback.phase {
+            ToolFeedbackPhase::Starting => {
+                let args = feedback
+                    .args_json
+                    .as_deref()
+                    .and_then(|raw| serde_json::from_str(raw).ok())
+                    .unwrap_or(Value::Null);
+                tool_calls.push(ObservedToolCall {
+                    name: feedback.tool_name,
+                    args,
+                    ok: false,
+                });
+                settled.push(false);
+                continue;
+            }
+            ToolFeedbackPhase::Done { .. } => true,
+            ToolFeedbackPhase::Failed { .. } => false,
+            _ => continue,
+        };
+        if let Some(index) =
+            (0..tool_calls.len()).find(|&i| !settled[i] && tool_calls[i].name == feedback.tool_name)
+        {
+            settled[index] = true;
+            tool_calls[index].ok = ok;
+        }
+    }
+
+    let checks = evaluate(
+        &scenario.expect,
+        response.as_deref(),
+        error.as_deref(),
+        &tool_calls,
+    );
+    ScenarioResult {
+        name: scenario.name.clone(),
+        response,
+        error,
+        tool_calls,
+        checks,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::MessageBus;
+    use crate::config::Config;
+    use crate::eval::{load_scenarios, EvalReport};
+    use crate::session::SessionManager;
+
+    fn agent() -> AgentLoop {
+        AgentLoop::new(
+            Config::default(),
+            SessionManager::new_memory(),
+            Arc::new(MessageBus::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_run_scenarios_in_mock_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("suite.yaml");
+        std::fs::write(
+            &path,
+            r#"
+scenarios:
+  - name: lookup
+    prompt: Weather in Lisbon?
+    expect:
+      tools:
+        - name: lookup
+          args: { city: Lisbon }
+      forbidden_tools: [shell]
+      contains: [sunny]
+    mock:
+      - tool_call: { name: lookup, args: { city: Lisbon } }
+      - text: It is sunny.
+    mock_tools:
+      lookup: "sunny, 24C"
+  - name: regression
+    prompt: Hi
+    expect:
+      contains: [hello]
+    mock:
+      - text: Bye.
+  - name: unscripted
+    prompt: Hi
+"#,
+        )
+        .unwrap();
+
+        let mut report = EvalReport::default();
+        for scenario in load_scenarios(&path).unwrap() {
+            report
+                .results
+                .push(run_scenario(&agent(), &scenario, true).await);
+        }
+
+        let lookup = &report.results[0];
+        assert!(lookup.passed(), "{}", report.render());
+        assert_eq!(lookup.tool_calls.len(), 1);
+        assert!(lookup.tool_calls[0].ok);
+        assert_eq!(lookup.tool_calls[0].args["city"], "Lisbon");
+
+        let regression = &report.results[1];
+        assert!(!regression.passed());
+        assert_eq!(regression.score(), 0.5);
+
+        let unscripted = &report.results[2];
+        assert_eq!(
+            unscripted.error.as_deref(),
+            Some("scenario has no mock responses")
+        );
+        assert_eq!(report.passed(), 1);
+    }
+}
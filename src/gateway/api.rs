@@ -0,0 +1,1180 @@
+//! Gateway control API -- bearer-token REST endpoints for paired devices.
+//!
+//! Served together with `POST /pair` on `gateway.host:gateway.port` when
+//! `pairing.enabled` is true, so a mobile app or script can drive the agent
+//! without a chat platform. Every `/api/v1` request must carry
+//! `Authorization: Bearer <token>` with a token issued by `/pair`:
+//!
+//! - `GET    /api/v1/health`         -- status, uptime, usage counters, checks
+//! - `GET    /api/v1/sessions`       -- session keys
+//! - `GET    /api/v1/sessions/{key}` -- one session's history
+//! - `POST   /api/v1/messages`       -- `{"content", "chat_id"?, "wait"?, "timeout_secs"?}`
+//! - `GET    /api/v1/cron`           -- scheduled jobs
+//! - `POST   /api/v1/cron`           -- `{"message", "every_seconds" | "cron_expr" | "at", ...}`
+//! - `DELETE /api/v1/cron/{id}`      -- remove a job
+//! - `GET    /ws`                    -- WebSocket push of device events
+//!
+//! With the `webui` feature the server also serves the embedded web UI at
+//! `/` and adds remote tool approval:
+//!
+//! - `GET    /api/v1/approvals`      -- gated tool calls awaiting a decision
+//! - `POST   /api/v1/approvals/{id}` -- `{"approve": true | false, "reason"?}`
+//!
+//! Messages enter the bus on the `device` channel and, by default, the
+//! request waits for the agent's reply. Read-only devices may use every
+//! route except changing cron jobs; their messages run in observer mode.
+//!
+//! `/ws` streams every [`DeviceEvent`] -- replies, agent and tool progress,
+//! response tokens -- as JSON text frames, optionally limited to one chat
+//! with `?chat_id=`. Browsers cannot set headers on a WebSocket, so the
+//! token may also be passed as `?token=`.
+//!
+//! When Telegram runs in webhook mode the same server accepts its update
+//! POSTs at `channels.telegram.webhook.path`, checked against the
+//! `X-Telegram-Bot-Api-Secret-Token` header. With pairing disabled, that is
+//! the only route served.
+
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, RwLock};
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::WebSocketStream;
+use tracing::{debug, info, warn};
+
+use crate::agent::AgentLoop;
+use crate::bus::{InboundMessage, MessageBus};
+use crate::channels::device::{
+    DeviceEvent, DeviceEventKind, DeviceHub, DEVICE_CHANNEL, DEVICE_REQUEST_ID_KEY,
+};
+use crate::channels::telegram_webhook::{TelegramWebhook, WebhookDelivery, SECRET_HEADER};
+use crate::cron::{default_job_name, parse_schedule, CronPayload, CronService, MAX_ACTIVE_JOBS};
+use crate::health::HealthRegistry;
+use crate::security::pairing::{DeviceScope, PairingManager};
+use crate::session::SessionManager;
+
+#[cfg(feature = "webui")]
+use super::approvals::ApprovalQueue;
+use super::http::{error_body, percent_decode, read_request, write_json, HttpRequest};
+use super::pairing_server::handle_pair;
+use super::rate_limit::GatewayRateLimiter;
+
+/// Default time `POST /api/v1/messages` waits for a reply.
+const DEFAULT_WAIT_SECS: u64 = 120;
+
+/// Longest a client may ask to wait for a reply.
+const MAX_WAIT_SECS: u64 = 600;
+
+/// Longest accepted `chat_id`.
+const MAX_CHAT_ID_LEN: usize = 128;
+
+type Response = (&'static str, String);
+
+/// An authenticated paired device.
+struct Caller {
+    token: String,
+    name: String,
+    scope: DeviceScope,
+}
+
+#[derive(Deserialize)]
+struct SendMessageRequest {
+    content: String,
+    #[serde(default)]
+    chat_id: Option<String>,
+    #[serde(default = "default_wait")]
+    wait: bool,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+}
+
+fn default_wait() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct CreateJobRequest {
+    message: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    every_seconds: Option<i64>,
+    #[serde(default)]
+    cron_expr: Option<String>,
+    #[serde(default)]
+    at: Option<String>,
+    #[serde(default)]
+    channel: Option<String>,
+    #[serde(default)]
+    chat_id: Option<String>,
+}
+
+#[cfg(feature = "webui")]
+#[derive(Deserialize)]
+struct ApprovalDecision {
+    approve: bool,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// Shared state behind the gateway's HTTP endpoints.
+pub struct GatewayApi {
+    pairing: Arc<Mutex<PairingManager>>,
+    rate_limiter: Option<Arc<GatewayRateLimiter>>,
+    bus: Arc<MessageBus>,
+    hub: DeviceHub,
+    health: HealthRegistry,
+    sessions: SessionManager,
+    cron: RwLock<Option<Arc<CronService>>>,
+    pairing_enabled: bool,
+    telegram: Option<TelegramWebhook>,
+    #[cfg(feature = "webui")]
+    approvals: Arc<ApprovalQueue>,
+}
+
+impl GatewayApi {
+    /// Create the API state. `hub` must be the one behind the registered
+    /// device channel, or message requests will never see a reply.
+    pub fn new(
+        pairing: Arc<Mutex<PairingManager>>,
+        bus: Arc<MessageBus>,
+        hub: DeviceHub,
+        health: HealthRegistry,
+        sessions: SessionManager,
+    ) -> Self {
+        Self {
+            pairing,
+            rate_limiter: None,
+            bus,
+            hub,
+            health,
+            sessions,
+            cron: RwLock::new(None),
+            pairing_enabled: true,
+            telegram: None,
+            #[cfg(feature = "webui")]
+            approvals: Arc::new(ApprovalQueue::default()),
+        }
+    }
+
+    /// Serve only channel webhooks: `/pair`, `/api/v1` and `/ws` answer 404.
+    pub fn without_pairing(mut self) -> Self {
+        self.pairing_enabled = false;
+        self
+    }
+
+    /// Accept Telegram updates at the webhook's path.
+    pub fn with_telegram_webhook(mut self, webhook: TelegramWebhook) -> Self {
+        self.telegram = Some(webhook);
+        self
+    }
+
+    /// Rate limit `POST /pair` per IP.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<GatewayRateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Connect a newly built agent: cron routes use its scheduler, device
+    /// requests report progress on the hub, and (with `webui`) gated tool
+    /// calls wait for a decision from `/api/v1/approvals`.
+    ///
+    /// Called again whenever the agent is rebuilt.
+    pub async fn attach_agent(&self, agent: &AgentLoop) {
+        if !self.pairing_enabled {
+            return;
+        }
+        self.set_cron_service(agent.cron_service()).await;
+        agent.set_device_hub(self.hub.clone()).await;
+        #[cfg(feature = "webui")]
+        {
+            let approvals = Arc::clone(&self.approvals);
+            agent
+                .set_approval_handler(move |request| {
+                    let approvals = Arc::clone(&approvals);
+                    async move { approvals.request(request).await }
+                })
+                .await;
+        }
+    }
+
+    /// Point the cron routes at the running agent's scheduler.
+    ///
+    /// Called again whenever the agent is rebuilt; `None` disables the routes.
+    pub async fn set_cron_service(&self, cron: Option<Arc<CronService>>) {
+        *self.cron.write().await = cron;
+    }
+
+    /// Route one request and produce `(status line, JSON body)`.
+    pub(crate) async fn handle(&self, request: &HttpRequest, ip: IpAddr) -> Response {
+        if let Some(hook) = self.telegram.as_ref().filter(|h| request.path == h.path()) {
+            return self.telegram_update(hook, request, ip);
+        }
+        if !self.pairing_enabled {
+            return ("404 Not Found", error_body("not_found"));
+        }
+        if request.path == "/pair" {
+            return handle_pair(request, ip, &self.pairing, self.rate_limiter.as_deref());
+        }
+        let Some(route) = request.path.strip_prefix("/api/v1/") else {
+            return ("404 Not Found", error_body("not_found"));
+        };
+
+        let caller = match self.authenticate(request, ip) {
+            Ok(caller) => caller,
+            Err(response) => return response,
+        };
+
+        let segments: Vec<&str> = route.trim_end_matches('/').split('/').collect();
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", ["health"]) => ("200 OK", self.health.render_health_json()),
+            ("GET", ["sessions"]) => self.list_sessions().await,
+            ("GET", ["sessions", key]) => self.get_session(key).await,
+            ("POST", ["messages"]) => self.send_message(request, &caller).await,
+            ("GET", ["cron"]) => self.list_jobs().await,
+            ("POST", ["cron"]) => self.create_job(request, &caller).await,
+            ("DELETE", ["cron", id]) => self.delete_job(id, &caller).await,
+            #[cfg(feature = "webui")]
+            ("GET", ["approvals"]) => (
+                "200 OK",
+                json!({ "approvals": self.approvals.pending() }).to_string(),
+            ),
+            #[cfg(feature = "webui")]
+            ("POST", ["approvals", id]) => self.resolve_approval(request, id, &caller),
+            #[cfg(feature = "webui")]
+            (_, ["approvals"] | ["approvals", _]) => {
+                ("405 Method Not Allowed", error_body("method not allowed"))
+            }
+            (
+                _,
+                ["health"] | ["sessions"] | ["sessions", _] | ["messages"] | ["cron"] | ["cron", _],
+            ) => ("405 Method Not Allowed", error_body("method not allowed")),
+            _ => ("404 Not Found", error_body("not_found")),
+        }
+    }
+
+    /// `POST {webhook path}` -- queue one Telegram update for the channel.
+    fn telegram_update(
+        &self,
+        hook: &TelegramWebhook,
+        request: &HttpRequest,
+        ip: IpAddr,
+    ) -> Response {
+        if request.method != "POST" {
+            return ("405 Method Not Allowed", error_body("method not allowed"));
+        }
+        if let Some(limiter) = &self.rate_limiter {
+            if !limiter.check_webhook(ip) {
+                return ("429 Too Many Requests", error_body("rate limit exceeded"));
+            }
+        }
+        match hook.deliver(request.header(SECRET_HEADER), &request.body) {
+            WebhookDelivery::Accepted => ("200 OK", json!({ "ok": true }).to_string()),
+            WebhookDelivery::Unauthorized => {
+                ("401 Unauthorized", error_body("invalid secret token"))
+            }
+            WebhookDelivery::BadRequest => ("400 Bad Request", error_body("invalid update")),
+            WebhookDelivery::Unavailable => (
+                "503 Service Unavailable",
+                error_body("telegram channel not running"),
+            ),
+        }
+    }
+
+    /// Validate the bearer token. Failures count toward the per-IP lockout.
+    fn authenticate(&self, request: &HttpRequest, ip: IpAddr) -> Result<Caller, Response> {
+        self.authenticate_token(request.bearer_token(), ip)
+    }
+
+    fn authenticate_token(&self, token: Option<&str>, ip: IpAddr) -> Result<Caller, Response> {
+        let Some(token) = token else {
+            return Err(("401 Unauthorized", error_body("missing bearer token")));
+        };
+        let validated = match self.pairing.lock() {
+            Ok(mut mgr) => mgr.validate_token_scope(token, &ip.to_string()),
+            Err(_) => {
+                return Err((
+                    "500 Internal Server Error",
+                    error_body("pairing unavailable"),
+                ))
+            }
+        };
+        match validated {
+            Some((name, scope)) => Ok(Caller {
+                token: token.to_string(),
+                name,
+                scope,
+            }),
+            None => Err(("401 Unauthorized", error_body("invalid or revoked token"))),
+        }
+    }
+
+    async fn list_sessions(&self) -> Response {
+        // The agent owns session writes; drop our cache so reads come from disk.
+        self.sessions.clear_cache().await;
+        match self.sessions.list().await {
+            Ok(keys) => ("200 OK", json!({ "sessions": keys }).to_string()),
+            Err(e) => ("500 Internal Server Error", error_body(&e.to_string())),
+        }
+    }
+
+    async fn get_session(&self, raw_key: &str) -> Response {
+        let Some(key) = percent_decode(raw_key) else {
+            return ("400 Bad Request", error_body("malformed session key"));
+        };
+        self.sessions.clear_cache().await;
+        match self.sessions.get(&key).await {
+            Ok(Some(session)) => match serde_json::to_string(&session) {
+                Ok(body) => ("200 OK", body),
+                Err(e) => ("500 Internal Server Error", error_body(&e.to_string())),
+            },
+            Ok(None) => ("404 Not Found", error_body("session not found")),
+            Err(e) => ("500 Internal Server Error", error_body(&e.to_string())),
+        }
+    }
+
+    async fn send_message(&self, request: &HttpRequest, caller: &Caller) -> Response {
+        let req: SendMessageRequest = match serde_json::from_slice(&request.body) {
+            Ok(req) => req,
+            Err(_) => {
+                return (
+                    "400 Bad Request",
+                    error_body("expected JSON with 'content'"),
+                )
+            }
+        };
+        if req.content.trim().is_empty() {
+            return ("400 Bad Request", error_body("content must not be empty"));
+        }
+        let chat_id = req.chat_id.unwrap_or_else(|| caller.name.clone());
+        if let Err(e) = validate_chat_id(&chat_id) {
+            return ("400 Bad Request", error_body(e));
+        }
+
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let inbound = InboundMessage::new(DEVICE_CHANNEL, &caller.name, &chat_id, &req.content)
+            .with_metadata("auth_token", &caller.token)
+            .with_metadata(DEVICE_REQUEST_ID_KEY, &request_id);
+
+        // Subscribe before publishing so a fast reply cannot be missed.
+        let mut replies = self.hub.subscribe();
+        if let Err(e) = self.bus.publish_inbound(inbound).await {
+            return ("503 Service Unavailable", error_body(&e.to_string()));
+        }
+        info!(device = %caller.name, chat_id = %chat_id, "Accepted device message");
+
+        if !req.wait {
+            return (
+                "202 Accepted",
+                json!({ "status": "queued", "request_id": request_id, "chat_id": chat_id })
+                    .to_string(),
+            );
+        }
+
+        let timeout = req
+            .timeout_secs
+            .unwrap_or(DEFAULT_WAIT_SECS)
+            .clamp(1, MAX_WAIT_SECS);
+        match tokio::time::timeout(
+            Duration::from_secs(timeout),
+            wait_for_reply(&mut replies, &request_id),
+        )
+        .await
+        {
+            Ok(Some(reply)) => (
+                "200 OK",
+                json!({
+                    "reply": reply,
+                    "request_id": request_id,
+                    "chat_id": chat_id,
+                })
+                .to_string(),
+            ),
+            _ => (
+                "504 Gateway Timeout",
+                json!({
+                    "error": "timed out waiting for reply",
+                    "request_id": request_id,
+                })
+                .to_string(),
+            ),
+        }
+    }
+
+    /// Serve `GET /ws`: authenticate, upgrade, then push hub events until
+    /// the device disconnects.
+    pub(crate) async fn serve_websocket(
+        &self,
+        mut stream: TcpStream,
+        request: &HttpRequest,
+        ip: IpAddr,
+    ) {
+        if request.method != "GET" {
+            write_json(
+                &mut stream,
+                "405 Method Not Allowed",
+                &error_body("use GET"),
+            )
+            .await;
+            return;
+        }
+        let Some(accept) = request.websocket_accept() else {
+            write_json(
+                &mut stream,
+                "400 Bad Request",
+                &error_body("expected a WebSocket upgrade"),
+            )
+            .await;
+            return;
+        };
+        let token = request
+            .bearer_token()
+            .map(str::to_string)
+            .or_else(|| request.query_param("token"));
+        let caller = match self.authenticate_token(token.as_deref(), ip) {
+            Ok(caller) => caller,
+            Err((status, body)) => {
+                write_json(&mut stream, status, &body).await;
+                return;
+            }
+        };
+        let chat_filter = request.query_param("chat_id");
+
+        // Subscribe before completing the handshake so nothing is missed
+        // between the client seeing 101 and the first recv.
+        let mut events = self.hub.subscribe();
+        let handshake = format!(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+            accept
+        );
+        if stream.write_all(handshake.as_bytes()).await.is_err() {
+            return;
+        }
+        let ws = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+        let (mut sink, mut source) = ws.split();
+        info!(device = %caller.name, chat_id = ?chat_filter, "Device connected to event stream");
+
+        let ready =
+            json!({ "type": "ready", "device": caller.name, "scope": caller.scope }).to_string();
+        if sink.send(WsMessage::Text(ready.into())).await.is_err() {
+            return;
+        }
+        loop {
+            tokio::select! {
+                event = events.recv() => {
+                    let frame = match event {
+                        Ok(event) => {
+                            if chat_filter.as_deref().is_some_and(|chat| chat != event.chat_id) {
+                                continue;
+                            }
+                            match serde_json::to_string(&event) {
+                                Ok(frame) => frame,
+                                Err(_) => continue,
+                            }
+                        }
+                        // Tell the client it missed events so it can refetch history.
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            json!({ "type": "lagged", "skipped": skipped }).to_string()
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    };
+                    if sink.send(WsMessage::Text(frame.into())).await.is_err() {
+                        break;
+                    }
+                }
+                frame = source.next() => match frame {
+                    // Pings are answered by tungstenite; other client frames are ignored.
+                    Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+        let _ = sink.close().await;
+        debug!(device = %caller.name, "Device event stream closed");
+    }
+
+    #[cfg(feature = "webui")]
+    fn resolve_approval(&self, request: &HttpRequest, id: &str, caller: &Caller) -> Response {
+        use crate::tools::approval::ApprovalResponse;
+
+        if let Err(response) = require_full_scope(caller) {
+            return response;
+        }
+        let decision: ApprovalDecision = match serde_json::from_slice(&request.body) {
+            Ok(decision) => decision,
+            Err(_) => {
+                return (
+                    "400 Bad Request",
+                    error_body("expected JSON with 'approve'"),
+                )
+            }
+        };
+        let response = if decision.approve {
+            ApprovalResponse::Approved
+        } else {
+            ApprovalResponse::Denied(
+                decision
+                    .reason
+                    .unwrap_or_else(|| format!("Denied by {}.", caller.name)),
+            )
+        };
+        if self.approvals.resolve(id, response) {
+            info!(device = %caller.name, approval_id = %id, approved = decision.approve, "Tool approval decided via API");
+            ("200 OK", json!({ "resolved": id }).to_string())
+        } else {
+            ("404 Not Found", error_body("approval not pending"))
+        }
+    }
+
+    async fn cron_service(&self) -> Result<Arc<CronService>, Response> {
+        self.cron.read().await.clone().ok_or_else(|| {
+            (
+                "503 Service Unavailable",
+                error_body("cron is not available in this gateway mode"),
+            )
+        })
+    }
+
+    async fn list_jobs(&self) -> Response {
+        let cron = match self.cron_service().await {
+            Ok(cron) => cron,
+            Err(response) => return response,
+        };
+        let jobs = cron.list_jobs(true).await;
+        ("200 OK", json!({ "jobs": jobs }).to_string())
+    }
+
+    async fn create_job(&self, request: &HttpRequest, caller: &Caller) -> Response {
+        if let Err(response) = require_full_scope(caller) {
+            return response;
+        }
+        let cron = match self.cron_service().await {
+            Ok(cron) => cron,
+            Err(response) => return response,
+        };
+        let req: CreateJobRequest = match serde_json::from_slice(&request.body) {
+            Ok(req) => req,
+            Err(_) => {
+                return (
+                    "400 Bad Request",
+                    error_body("expected JSON with 'message' and a schedule"),
+                )
+            }
+        };
+        if req.message.trim().is_empty() {
+            return ("400 Bad Request", error_body("message must not be empty"));
+        }
+        let (schedule, delete_after_run) = match parse_schedule(
+            req.every_seconds,
+            req.cron_expr.as_deref(),
+            req.at.as_deref(),
+        ) {
+            Ok(parsed) => parsed,
+            Err(e) => return ("400 Bad Request", error_body(&e.to_string())),
+        };
+        let chat_id = req.chat_id.unwrap_or_else(|| caller.name.clone());
+        if let Err(e) = validate_chat_id(&chat_id) {
+            return ("400 Bad Request", error_body(e));
+        }
+        if cron.list_jobs(false).await.len() >= MAX_ACTIVE_JOBS {
+            return (
+                "409 Conflict",
+                error_body("maximum number of active cron jobs reached"),
+            );
+        }
+
+        let name = req.name.unwrap_or_else(|| default_job_name(&req.message));
+        let payload = CronPayload {
+            message: req.message,
+            channel: req.channel.unwrap_or_else(|| DEVICE_CHANNEL.to_string()),
+            chat_id,
+        };
+        match cron
+            .add_job(name, schedule, payload, delete_after_run)
+            .await
+        {
+            Ok(job) => {
+                info!(device = %caller.name, job_id = %job.id, "Cron job created via API");
+                ("201 Created", json!(job).to_string())
+            }
+            Err(e) => ("500 Internal Server Error", error_body(&e.to_string())),
+        }
+    }
+
+    async fn delete_job(&self, id: &str, caller: &Caller) -> Response {
+        if let Err(response) = require_full_scope(caller) {
+            return response;
+        }
+        let cron = match self.cron_service().await {
+            Ok(cron) => cron,
+            Err(response) => return response,
+        };
+        match cron.remove_job(id).await {
+            Ok(true) => {
+                info!(device = %caller.name, job_id = %id, "Cron job removed via API");
+                ("200 OK", json!({ "removed": id }).to_string())
+            }
+            Ok(false) => ("404 Not Found", error_body("job not found")),
+            Err(e) => ("500 Internal Server Error", error_body(&e.to_string())),
+        }
+    }
+}
+
+fn require_full_scope(caller: &Caller) -> Result<(), Response> {
+    if caller.scope == DeviceScope::Full {
+        Ok(())
+    } else {
+        Err((
+            "403 Forbidden",
+            error_body("this device is paired read-only"),
+        ))
+    }
+}
+
+fn validate_chat_id(chat_id: &str) -> Result<(), &'static str> {
+    if chat_id.is_empty()
+        || chat_id.len() > MAX_CHAT_ID_LEN
+        || chat_id.chars().any(char::is_control)
+    {
+        return Err("chat_id must be 1-128 printable characters");
+    }
+    Ok(())
+}
+
+/// Wait for the device-channel reply tagged with `request_id` and return
+/// its content.
+async fn wait_for_reply(
+    events: &mut broadcast::Receiver<DeviceEvent>,
+    request_id: &str,
+) -> Option<String> {
+    loop {
+        match events.recv().await {
+            Ok(DeviceEvent {
+                request_id: Some(id),
+                kind: DeviceEventKind::Message { content },
+                ..
+            }) if id == request_id => return Some(content),
+            Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return None,
+        }
+    }
+}
+
+/// Start the gateway HTTP server (`/pair`, `/api/v1`, and `/ws`). Returns a
+/// handle callers can abort on shutdown.
+pub async fn start_api_server(
+    host: &str,
+    port: u16,
+    api: Arc<GatewayApi>,
+) -> std::io::Result<tokio::task::JoinHandle<()>> {
+    let addr = format!("{}:{}", host, port);
+    let listener = TcpListener::bind(&addr).await?;
+    info!(addr = %addr, "Gateway API listening on http://{}", addr);
+
+    let handle = tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((mut stream, peer)) => {
+                    let api = Arc::clone(&api);
+                    tokio::spawn(async move {
+                        let raw = match tokio::time::timeout(
+                            Duration::from_secs(5),
+                            read_request(&mut stream),
+                        )
+                        .await
+                        {
+                            Ok(Some(raw)) => raw,
+                            _ => return,
+                        };
+                        let Some(request) = HttpRequest::parse(&raw) else {
+                            write_json(
+                                &mut stream,
+                                "400 Bad Request",
+                                &error_body("malformed request"),
+                            )
+                            .await;
+                            return;
+                        };
+                        #[cfg(feature = "webui")]
+                        if api.pairing_enabled && request.method == "GET" {
+                            if let Some((content_type, body)) = super::webui::asset(&request.path) {
+                                super::http::write_response(
+                                    &mut stream,
+                                    "200 OK",
+                                    content_type,
+                                    body,
+                                )
+                                .await;
+                                return;
+                            }
+                        }
+                        if api.pairing_enabled && request.path == "/ws" {
+                            api.serve_websocket(stream, &request, peer.ip()).await;
+                            return;
+                        }
+                        let (status, body) = api.handle(&request, peer.ip()).await;
+                        write_json(&mut stream, status, &body).await;
+                    });
+                }
+                Err(e) => {
+                    warn!(error = %e, "Gateway API accept error");
+                }
+            }
+        }
+    });
+
+    Ok(handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::OutboundMessage;
+    use crate::channels::{Channel, DeviceChannel};
+
+    struct Fixture {
+        api: GatewayApi,
+        bus: Arc<MessageBus>,
+        hub: DeviceHub,
+        dir: tempfile::TempDir,
+    }
+
+    fn fixture() -> Fixture {
+        let dir = tempfile::tempdir().unwrap();
+        let pairing = Arc::new(Mutex::new(PairingManager::with_path(
+            dir.path().join("paired_devices.json"),
+            5,
+            300,
+        )));
+        let bus = Arc::new(MessageBus::new());
+        let hub = DeviceHub::new();
+        let sessions = SessionManager::with_path(dir.path().join("sessions")).unwrap();
+        let api = GatewayApi::new(
+            pairing,
+            Arc::clone(&bus),
+            hub.clone(),
+            HealthRegistry::new(),
+            sessions,
+        );
+        Fixture { api, bus, hub, dir }
+    }
+
+    fn pair(api: &GatewayApi, name: &str, scope: DeviceScope) -> String {
+        let mut mgr = api.pairing.lock().unwrap();
+        let code = mgr.generate_scoped_pairing_code(scope);
+        mgr.complete_scoped_pairing(&code, name, "127.0.0.1", scope)
+            .unwrap()
+            .0
+    }
+
+    fn request(method: &str, path: &str, token: Option<&str>, body: &str) -> HttpRequest {
+        let auth = token
+            .map(|t| format!("Authorization: Bearer {}\r\n", t))
+            .unwrap_or_default();
+        let raw = format!(
+            "{} {} HTTP/1.1\r\nHost: localhost\r\n{}Content-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            auth,
+            body.len(),
+            body
+        );
+        HttpRequest::parse(raw.as_bytes()).unwrap()
+    }
+
+    fn ip() -> IpAddr {
+        "127.0.0.1".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_requires_valid_token() {
+        let f = fixture();
+        let (status, _) = f
+            .api
+            .handle(&request("GET", "/api/v1/health", None, ""), ip())
+            .await;
+        assert_eq!(status, "401 Unauthorized");
+
+        let (status, _) = f
+            .api
+            .handle(&request("GET", "/api/v1/health", Some("bogus"), ""), ip())
+            .await;
+        assert_eq!(status, "401 Unauthorized");
+
+        let token = pair(&f.api, "phone", DeviceScope::Full);
+        let (status, body) = f
+            .api
+            .handle(&request("GET", "/api/v1/health", Some(&token), ""), ip())
+            .await;
+        assert_eq!(status, "200 OK");
+        assert!(body.contains("uptime_secs"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_routes_and_methods() {
+        let f = fixture();
+        let token = pair(&f.api, "phone", DeviceScope::Full);
+        let (status, _) = f
+            .api
+            .handle(&request("GET", "/api/v1/nope", Some(&token), ""), ip())
+            .await;
+        assert_eq!(status, "404 Not Found");
+        let (status, _) = f
+            .api
+            .handle(&request("DELETE", "/api/v1/health", Some(&token), ""), ip())
+            .await;
+        assert_eq!(status, "405 Method Not Allowed");
+        let (status, _) = f.api.handle(&request("GET", "/", None, ""), ip()).await;
+        assert_eq!(status, "404 Not Found");
+    }
+
+    #[tokio::test]
+    async fn test_sessions_list_and_get() {
+        let f = fixture();
+        let token = pair(&f.api, "phone", DeviceScope::ReadOnly);
+        let mut session = crate::session::Session::new("device:phone");
+        session.add_message(crate::session::Message::user("hello"));
+        f.api.sessions.save(&session).await.unwrap();
+
+        let (status, body) = f
+            .api
+            .handle(&request("GET", "/api/v1/sessions", Some(&token), ""), ip())
+            .await;
+        assert_eq!(status, "200 OK");
+        assert!(body.contains("device:phone"));
+
+        let (status, body) = f
+            .api
+            .handle(
+                &request("GET", "/api/v1/sessions/device%3Aphone", Some(&token), ""),
+                ip(),
+            )
+            .await;
+        assert_eq!(status, "200 OK");
+        assert!(body.contains("hello"));
+
+        let (status, _) = f
+            .api
+            .handle(
+                &request("GET", "/api/v1/sessions/missing", Some(&token), ""),
+                ip(),
+            )
+            .await;
+        assert_eq!(status, "404 Not Found");
+    }
+
+    #[tokio::test]
+    async fn test_send_message_waits_for_tagged_reply() {
+        let f = fixture();
+        let token = pair(&f.api, "phone", DeviceScope::Full);
+        let channel = DeviceChannel::new(f.hub.clone());
+
+        // Fake agent: answer the first inbound message on the device channel.
+        let bus = Arc::clone(&f.bus);
+        let agent = tokio::spawn(async move {
+            let inbound = bus.consume_inbound().await.unwrap();
+            assert_eq!(inbound.channel, DEVICE_CHANNEL);
+            assert_eq!(inbound.sender_id, "phone");
+            assert!(inbound.metadata.contains_key("auth_token"));
+            // Untagged traffic for the same chat is not the reply.
+            channel
+                .send(OutboundMessage::new(
+                    DEVICE_CHANNEL,
+                    &inbound.chat_id,
+                    "progress",
+                ))
+                .await
+                .unwrap();
+            let mut reply = OutboundMessage::new(DEVICE_CHANNEL, &inbound.chat_id, "pong");
+            reply.metadata.insert(
+                DEVICE_REQUEST_ID_KEY.to_string(),
+                inbound.metadata[DEVICE_REQUEST_ID_KEY].clone(),
+            );
+            channel.send(reply).await.unwrap();
+        });
+
+        let (status, body) = f
+            .api
+            .handle(
+                &request(
+                    "POST",
+                    "/api/v1/messages",
+                    Some(&token),
+                    r#"{"content": "ping", "timeout_secs": 5}"#,
+                ),
+                ip(),
+            )
+            .await;
+        agent.await.unwrap();
+        assert_eq!(status, "200 OK");
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["reply"], "pong");
+        assert_eq!(body["chat_id"], "phone");
+    }
+
+    #[tokio::test]
+    async fn test_send_message_without_wait_is_queued() {
+        let f = fixture();
+        let token = pair(&f.api, "phone", DeviceScope::ReadOnly);
+        let (status, body) = f
+            .api
+            .handle(
+                &request(
+                    "POST",
+                    "/api/v1/messages",
+                    Some(&token),
+                    r#"{"content": "hi", "chat_id": "kitchen", "wait": false}"#,
+                ),
+                ip(),
+            )
+            .await;
+        assert_eq!(status, "202 Accepted");
+        assert!(body.contains("request_id"));
+        let inbound = f.bus.consume_inbound().await.unwrap();
+        assert_eq!(inbound.chat_id, "kitchen");
+
+        let (status, _) = f
+            .api
+            .handle(
+                &request(
+                    "POST",
+                    "/api/v1/messages",
+                    Some(&token),
+                    r#"{"content": " "}"#,
+                ),
+                ip(),
+            )
+            .await;
+        assert_eq!(status, "400 Bad Request");
+    }
+
+    #[tokio::test]
+    async fn test_cron_routes() {
+        let f = fixture();
+        let full = pair(&f.api, "phone", DeviceScope::Full);
+        let read_only = pair(&f.api, "tablet", DeviceScope::ReadOnly);
+
+        let (status, _) = f
+            .api
+            .handle(&request("GET", "/api/v1/cron", Some(&full), ""), ip())
+            .await;
+        assert_eq!(status, "503 Service Unavailable");
+
+        let cron = Arc::new(CronService::new(
+            f.dir.path().join("jobs.json"),
+            Arc::clone(&f.bus),
+        ));
+        f.api.set_cron_service(Some(Arc::clone(&cron))).await;
+
+        let create = r#"{"message": "stretch", "every_seconds": 3600}"#;
+        let (status, _) = f
+            .api
+            .handle(
+                &request("POST", "/api/v1/cron", Some(&read_only), create),
+                ip(),
+            )
+            .await;
+        assert_eq!(status, "403 Forbidden");
+
+        let (status, _) = f
+            .api
+            .handle(
+                &request(
+                    "POST",
+                    "/api/v1/cron",
+                    Some(&full),
+                    r#"{"message": "x", "every_seconds": 5}"#,
+                ),
+                ip(),
+            )
+            .await;
+        assert_eq!(status, "400 Bad Request");
+
+        let (status, body) = f
+            .api
+            .handle(&request("POST", "/api/v1/cron", Some(&full), create), ip())
+            .await;
+        assert_eq!(status, "201 Created");
+        let job: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(job["payload"]["channel"], DEVICE_CHANNEL);
+        assert_eq!(job["payload"]["chat_id"], "phone");
+        let id = job["id"].as_str().unwrap().to_string();
+
+        let (status, body) = f
+            .api
+            .handle(&request("GET", "/api/v1/cron", Some(&read_only), ""), ip())
+            .await;
+        assert_eq!(status, "200 OK");
+        assert!(body.contains(&id));
+
+        let path = format!("/api/v1/cron/{}", id);
+        let (status, _) = f
+            .api
+            .handle(&request("DELETE", &path, Some(&full), ""), ip())
+            .await;
+        assert_eq!(status, "200 OK");
+        let (status, _) = f
+            .api
+            .handle(&request("DELETE", &path, Some(&full), ""), ip())
+            .await;
+        assert_eq!(status, "404 Not Found");
+        assert!(cron.list_jobs(true).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_websocket_pushes_device_events() {
+        let f = fixture();
+        let token = pair(&f.api, "phone", DeviceScope::ReadOnly);
+        let api = Arc::new(f.api);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, peer)) = listener.accept().await {
+                let raw = read_request(&mut stream).await.unwrap();
+                let request = HttpRequest::parse(&raw).unwrap();
+                let api = Arc::clone(&api);
+                tokio::spawn(async move { api.serve_websocket(stream, &request, peer.ip()).await });
+            }
+        });
+
+        let unauthorized = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr)).await;
+        assert!(unauthorized.is_err());
+
+        let url = format!("ws://{}/ws?token={}&chat_id=phone", addr, token);
+        let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        let frame = ws.next().await.unwrap().unwrap();
+        let ready: serde_json::Value = serde_json::from_str(frame.to_text().unwrap()).unwrap();
+        assert_eq!(ready["type"], "ready");
+        assert_eq!(ready["scope"], "read_only");
+
+        // Events for other chats are filtered out.
+        f.hub.emit(DeviceEvent {
+            chat_id: "kitchen".to_string(),
+            request_id: None,
+            kind: DeviceEventKind::AgentStarted,
+        });
+        f.hub
+            .publish(OutboundMessage::new(DEVICE_CHANNEL, "phone", "hello"));
+
+        let frame = ws.next().await.unwrap().unwrap();
+        let event: serde_json::Value = serde_json::from_str(frame.to_text().unwrap()).unwrap();
+        assert_eq!(event["type"], "message");
+        assert_eq!(event["chat_id"], "phone");
+        assert_eq!(event["content"], "hello");
+    }
+
+    #[cfg(feature = "webui")]
+    #[tokio::test]
+    async fn test_approval_routes() {
+        use crate::tools::approval::{ApprovalRequest, ApprovalResponse};
+
+        let f = fixture();
+        let full = pair(&f.api, "phone", DeviceScope::Full);
+        let read_only = pair(&f.api, "tablet", DeviceScope::ReadOnly);
+        let approvals = Arc::clone(&f.api.approvals);
+        let waiting = tokio::spawn(async move {
+            approvals
+                .request(ApprovalRequest::new(
+                    "shell".to_string(),
+                    json!({"command": "ls"}),
+                    0,
+                ))
+                .await
+        });
+
+        let id = loop {
+            let (status, body) = f
+                .api
+                .handle(
+                    &request("GET", "/api/v1/approvals", Some(&read_only), ""),
+                    ip(),
+                )
+                .await;
+            assert_eq!(status, "200 OK");
+            let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+            if let Some(pending) = body["approvals"].as_array().and_then(|a| a.first()) {
+                assert_eq!(pending["tool_name"], "shell");
+                break pending["id"].as_str().unwrap().to_string();
+            }
+            tokio::task::yield_now().await;
+        };
+
+        let path = format!("/api/v1/approvals/{}", id);
+        let deny = r#"{"approve": false}"#;
+        let (status, _) = f
+            .api
+            .handle(&request("POST", &path, Some(&read_only), deny), ip())
+            .await;
+        assert_eq!(status, "403 Forbidden");
+
+        let (status, _) = f
+            .api
+            .handle(&request("POST", &path, Some(&full), deny), ip())
+            .await;
+        assert_eq!(status, "200 OK");
+        assert_eq!(
+            waiting.await.unwrap(),
+            ApprovalResponse::Denied("Denied by phone.".to_string())
+        );
+
+        let (status, _) = f
+            .api
+            .handle(&request("POST", &path, Some(&full), deny), ip())
+            .await;
+        assert_eq!(status, "404 Not Found");
+    }
+
+    #[tokio::test]
+    async fn test_telegram_webhook_route() {
+        let telegram = crate::config::TelegramConfig {
+            token: "123:abc".to_string(),
+            webhook: Some(crate::config::TelegramWebhookConfig {
+                secret_token: Some("s3cret".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let hook = TelegramWebhook::from_config(&telegram).unwrap();
+        let api = fixture()
+            .api
+            .without_pairing()
+            .with_telegram_webhook(hook.clone());
+        let update = |secret: &str| {
+            let body = r#"{"update_id":1,"message":{"message_id":7,"date":1700000000,"chat":{"id":42,"type":"private","first_name":"A"},"text":"hi"}}"#;
+            let raw = format!(
+                "POST /telegram/webhook HTTP/1.1\r\nX-Telegram-Bot-Api-Secret-Token: {}\r\nContent-Length: {}\r\n\r\n{}",
+                secret,
+                body.len(),
+                body
+            );
+            HttpRequest::parse(raw.as_bytes()).unwrap()
+        };
+
+        let (status, _) = api
+            .handle(&request("POST", "/pair", None, "{}"), ip())
+            .await;
+        assert_eq!(status, "404 Not Found");
+        let (status, _) = api.handle(&update("wrong"), ip()).await;
+        assert_eq!(status, "401 Unauthorized");
+        let (status, _) = api.handle(&update("s3cret"), ip()).await;
+        assert_eq!(status, "503 Service Unavailable");
+
+        let _listener = hook.listener();
+        let (status, _) = api.handle(&update("s3cret"), ip()).await;
+        assert_eq!(status, "200 OK");
+    }
+
+    #[tokio::test]
+    async fn test_pair_route_is_served() {
+        let f = fixture();
+        let (status, _) = f.api.handle(&request("GET", "/pair", None, ""), ip()).await;
+        assert_eq!(status, "405 Method Not Allowed");
+    }
+}
@@ -0,0 +1,177 @@
+//! Pending tool approvals resolved remotely through the gateway API.
+//!
+//! With the `webui` feature the gateway installs an approval handler on the
+//! agent that parks each gated tool call here until a paired device approves
+//! or denies it (`POST /api/v1/approvals/{id}`). This applies to gated calls
+//! from every channel, not only device chats. Requests with an auto-approve
+//! deadline (`approval.auto_approve_timeout_secs`) are approved when it
+//! passes; others time out and the tool is not run.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::Serialize;
+use tokio::sync::oneshot;
+
+use crate::tools::approval::{ApprovalRequest, ApprovalResponse};
+
+/// How long a gated call waits for a decision when it has no auto-approve
+/// deadline.
+pub const DEFAULT_APPROVAL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A tool call awaiting a decision, as listed by the API.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingApproval {
+    pub id: String,
+    #[serde(flatten)]
+    pub request: ApprovalRequest,
+}
+
+struct Waiter {
+    request: ApprovalRequest,
+    reply: oneshot::Sender<ApprovalResponse>,
+}
+
+/// Tool calls waiting for a remote approve/deny.
+pub struct ApprovalQueue {
+    pending: Mutex<HashMap<String, Waiter>>,
+    timeout: Duration,
+}
+
+impl ApprovalQueue {
+    /// Create an empty queue; undecided requests time out after `timeout`.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            timeout,
+        }
+    }
+
+    /// Park `request` until it is resolved, auto-approved, or times out.
+    pub async fn request(&self, request: ApprovalRequest) -> ApprovalResponse {
+        let (wait, on_timeout) = match request.auto_approve_at {
+            Some(deadline) => (
+                (deadline - Utc::now()).to_std().unwrap_or_default(),
+                ApprovalResponse::Approved,
+            ),
+            None => (self.timeout, ApprovalResponse::TimedOut),
+        };
+        let id = uuid::Uuid::new_v4().to_string();
+        let (reply, decision) = oneshot::channel();
+        self.lock().insert(id.clone(), Waiter { request, reply });
+
+        let response = match tokio::time::timeout(wait, decision).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => ApprovalResponse::Denied("approval request was dropped".into()),
+            Err(_) => on_timeout,
+        };
+        self.lock().remove(&id);
+        response
+    }
+
+    /// Requests still waiting, oldest first.
+    pub fn pending(&self) -> Vec<PendingApproval> {
+        let mut pending: Vec<PendingApproval> = self
+            .lock()
+            .iter()
+            .map(|(id, waiter)| PendingApproval {
+                id: id.clone(),
+                request: waiter.request.clone(),
+            })
+            .collect();
+        pending.sort_by_key(|p| p.request.timestamp);
+        pending
+    }
+
+    /// Deliver a decision. Returns `false` when `id` is not pending.
+    pub fn resolve(&self, id: &str, response: ApprovalResponse) -> bool {
+        match self.lock().remove(id) {
+            Some(waiter) => waiter.reply.send(response).is_ok(),
+            None => false,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Waiter>> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ApprovalQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_APPROVAL_TIMEOUT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn request(tool: &str, auto_approve_secs: u64) -> ApprovalRequest {
+        ApprovalRequest::new(
+            tool.to_string(),
+            serde_json::json!({"command": "ls"}),
+            auto_approve_secs,
+        )
+    }
+
+    async fn wait_for_pending(queue: &ApprovalQueue) -> PendingApproval {
+        loop {
+            if let Some(p) = queue.pending().into_iter().next() {
+                return p;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_delivers_decision() {
+        let queue = Arc::new(ApprovalQueue::default());
+        let waiting = tokio::spawn({
+            let queue = Arc::clone(&queue);
+            async move { queue.request(request("shell", 0)).await }
+        });
+
+        let pending = wait_for_pending(&queue).await;
+        assert_eq!(pending.request.tool_name, "shell");
+        assert!(queue.resolve(&pending.id, ApprovalResponse::Denied("no".into())));
+        assert_eq!(
+            waiting.await.unwrap(),
+            ApprovalResponse::Denied("no".into())
+        );
+        assert!(queue.pending().is_empty());
+        assert!(!queue.resolve(&pending.id, ApprovalResponse::Approved));
+    }
+
+    #[tokio::test]
+    async fn test_undecided_request_times_out() {
+        let queue = ApprovalQueue::new(Duration::from_millis(10));
+        assert_eq!(
+            queue.request(request("shell", 0)).await,
+            ApprovalResponse::TimedOut
+        );
+        assert!(queue.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_auto_approve_deadline_approves() {
+        let queue = ApprovalQueue::new(Duration::from_millis(10));
+        let mut req = request("shell", 1);
+        req.auto_approve_at = Some(Utc::now());
+        assert_eq!(queue.request(req).await, ApprovalResponse::Approved);
+    }
+
+    #[test]
+    fn test_pending_serializes_flat() {
+        let pending = PendingApproval {
+            id: "a1".to_string(),
+            request: request("shell", 0),
+        };
+        let json = serde_json::to_value(&pending).unwrap();
+        assert_eq!(json["id"], "a1");
+        assert_eq!(json["tool_name"], "shell");
+        assert_eq!(json["arguments"]["command"], "ls");
+    }
+}
@@ -17,11 +17,12 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::bus::{InboundMessage, MessageBus, OutboundMessage};
+use crate::channels::device::DEVICE_REQUEST_ID_KEY;
 use crate::config::{Config, ContainerAgentBackend, ContainerAgentConfig};
 use crate::error::{Result, ZeptoError};
 use crate::health::UsageMetrics;
 use crate::security::mount::validate_mount_not_blocked;
-use crate::security::pairing::PairingManager;
+use crate::security::pairing::{PairingManager, DEVICE_SCOPE_METADATA_KEY};
 use crate::session::SessionManager;
 
 use super::idempotency::IdempotencyStore;
@@ -188,7 +189,7 @@ impl ContainerAgentProxy {
                 }
                 msg = self.bus.consume_inbound() => {
                     match msg {
-                        Some(inbound) => {
+                        Some(mut inbound) => {
                             // Rate limit check (by source_ip metadata if present)
                             if let Some(ref limiter) = self.rate_limiter {
                                 if let Some(ip_str) = inbound.metadata.get("source_ip") {
@@ -230,16 +231,24 @@ impl ContainerAgentProxy {
                             if let Some(ref pairing_mutex) = self.pairing {
                                 let identifier = inbound.sender_id.clone();
                                 let token = inbound.metadata.get("auth_token").cloned();
-                                let valid = match token {
+                                let scope = match token {
                                     Some(raw_token) => {
                                         match pairing_mutex.lock() {
-                                            Ok(mut mgr) => mgr.validate_token(&raw_token, &identifier).is_some(),
-                                            Err(_) => false,
+                                            Ok(mut mgr) => mgr
+                                                .validate_token_scope(&raw_token, &identifier)
+                                                .map(|(_, scope)| scope),
+                                            Err(_) => None,
                                         }
                                     }
-                                    None => false,
+                                    None => None,
                                 };
-                                if !valid {
+                                // Forwarded to the container so its agent loop
+                                // applies the device's scope.
+                                inbound.metadata.insert(
+                                    DEVICE_SCOPE_METADATA_KEY.to_string(),
+                                    scope.unwrap_or_default().to_string(),
+                                );
+                                if scope.is_none() {
                                     warn!(
                                         sender = %inbound.sender_id,
                                         channel = %inbound.channel,
@@ -262,7 +271,12 @@ impl ContainerAgentProxy {
                                 Ok(permit) => {
                                     let proxy = Arc::clone(&self);
                                     tokio::spawn(async move {
-                                        let response = proxy.process_in_container(&inbound).await;
+                                        let mut response = proxy.process_in_container(&inbound).await;
+                                        if let Some(id) = inbound.metadata.get(DEVICE_REQUEST_ID_KEY) {
+                                            response
+                                                .metadata
+                                                .insert(DEVICE_REQUEST_ID_KEY.to_string(), id.clone());
+                                        }
                                         if let Err(e) = proxy.bus.publish_outbound(response).await {
                                             error!("Failed to publish response: {}", e);
                                         }
@@ -325,7 +339,12 @@ impl ContainerAgentProxy {
                 if let (Some(metrics), Some(usage)) =
                     (usage_metrics.as_ref(), response.usage.as_ref())
                 {
-                    metrics.record_tokens(usage.input_tokens, usage.output_tokens);
+                    metrics.record_session_tokens(
+                        &message.session_key,
+                        &self.config.agents.defaults.model,
+                        usage.input_tokens,
+                        usage.output_tokens,
+                    );
                     metrics.record_tool_calls(usage.tool_calls);
                     if usage.errors > 0 {
                         metrics
@@ -416,10 +435,10 @@ impl ContainerAgentProxy {
 
     /// Spawn a container and communicate via stdin/stdout.
     async fn spawn_container(&self, request: &AgentRequest) -> Result<AgentResponse> {
-        let config_root = dirs::home_dir().unwrap_or_default().join(".zeptoclaw");
-        let workspace_dir = config_root.join("workspace");
-        let sessions_dir = config_root.join("sessions");
-        let config_path = config_root.join("config.json");
+        let config_root = Config::dir();
+        let workspace_dir = Config::data_dir().join("workspace");
+        let sessions_dir = Config::data_dir().join("sessions");
+        let config_path = Config::path();
 
         tokio::fs::create_dir_all(&workspace_dir)
             .await
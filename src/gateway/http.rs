@@ -0,0 +1,243 @@
+//! Minimal HTTP/1.1 request parsing for the gateway's raw-TCP endpoints.
+//!
+//! Like the health server, the gateway speaks just enough HTTP to serve
+//! small JSON requests: one request per connection, `Content-Length`
+//! bodies only, `Connection: close` on every response. The one exception
+//! is a WebSocket upgrade, which hands the connection over to tungstenite.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Largest request (headers + body) accepted.
+pub(crate) const MAX_REQUEST_BYTES: usize = 64 * 1024;
+
+/// A parsed request.
+#[derive(Debug, Clone)]
+pub(crate) struct HttpRequest {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Raw query string (without the leading `?`)
+    pub query: String,
+    headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Parse a complete request as returned by [`read_request`].
+    pub fn parse(raw: &[u8]) -> Option<Self> {
+        let header_end = find_header_end(raw)?;
+        let head = std::str::from_utf8(&raw[..header_end]).ok()?;
+        let mut lines = head.split("\r\n");
+        let mut parts = lines.next()?.split_whitespace();
+        let method = parts.next()?.to_string();
+        let target = parts.next()?;
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let headers = lines
+            .filter_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                Some((name.trim().to_ascii_lowercase(), value.trim().to_string()))
+            })
+            .collect();
+
+        Some(Self {
+            method,
+            path: path.to_string(),
+            query: query.to_string(),
+            headers,
+            body: raw[header_end + 4..].to_vec(),
+        })
+    }
+
+    /// Case-insensitive header lookup.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Token from an `Authorization: Bearer <token>` header.
+    pub fn bearer_token(&self) -> Option<&str> {
+        let value = self.header("authorization")?;
+        let (scheme, token) = value.split_once(' ')?;
+        let token = token.trim();
+        (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+    }
+
+    /// `Sec-WebSocket-Accept` value for a version-13 WebSocket upgrade, or
+    /// `None` when this is not one.
+    pub fn websocket_accept(&self) -> Option<String> {
+        let upgrade = self.header("upgrade")?;
+        let version = self.header("sec-websocket-version")?;
+        let key = self.header("sec-websocket-key")?;
+        (upgrade.eq_ignore_ascii_case("websocket") && version == "13")
+            .then(|| tokio_tungstenite::tungstenite::handshake::derive_accept_key(key.as_bytes()))
+    }
+
+    /// First value of a query parameter, percent-decoded.
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        url::form_urlencoded::parse(self.query.as_bytes())
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    }
+}
+
+/// Read one request: headers, then `Content-Length` bytes of body.
+///
+/// Returns `None` when the request exceeds [`MAX_REQUEST_BYTES`] or the
+/// connection fails.
+pub(crate) async fn read_request(stream: &mut TcpStream) -> Option<Vec<u8>> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    loop {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return Some(buf);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if buf.len() > MAX_REQUEST_BYTES {
+            return None;
+        }
+        if let Some(header_end) = find_header_end(&buf) {
+            let headers = String::from_utf8_lossy(&buf[..header_end]);
+            let body_len = content_length(&headers).unwrap_or(0);
+            if header_end + 4 + body_len > MAX_REQUEST_BYTES {
+                return None;
+            }
+            if buf.len() >= header_end + 4 + body_len {
+                return Some(buf);
+            }
+        }
+    }
+}
+
+/// Write a JSON response and close the connection.
+pub(crate) async fn write_json(stream: &mut TcpStream, status: &str, body: &str) {
+    write_response(stream, status, "application/json", body.as_bytes()).await;
+}
+
+/// Write a response with the given content type and close the connection.
+pub(crate) async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) {
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nX-Content-Type-Options: nosniff\r\nX-Frame-Options: DENY\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(body).await;
+    let _ = stream.shutdown().await;
+}
+
+/// JSON error body: `{"error": "<message>"}`.
+pub(crate) fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+/// Decode `%XX` escapes in a path segment.
+pub(crate) fn percent_decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = segment.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+fn find_header_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n")
+}
+
+fn content_length(headers: &str) -> Option<usize> {
+    headers.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("content-length")
+            .then(|| value.trim().parse().ok())
+            .flatten()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let raw = b"POST /api/v1/messages?wait=false&x=a%20b HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer abc123\r\nContent-Length: 2\r\n\r\n{}";
+        let req = HttpRequest::parse(raw).unwrap();
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/api/v1/messages");
+        assert_eq!(req.query_param("wait").as_deref(), Some("false"));
+        assert_eq!(req.query_param("x").as_deref(), Some("a b"));
+        assert_eq!(req.header("HOST"), Some("localhost"));
+        assert_eq!(req.bearer_token(), Some("abc123"));
+        assert_eq!(req.body, b"{}");
+    }
+
+    #[test]
+    fn test_bearer_token_requires_scheme() {
+        let req =
+            HttpRequest::parse(b"GET / HTTP/1.1\r\nAuthorization: Basic abc\r\n\r\n").unwrap();
+        assert_eq!(req.bearer_token(), None);
+        let req = HttpRequest::parse(b"GET / HTTP/1.1\r\nAuthorization: Bearer \r\n\r\n").unwrap();
+        assert_eq!(req.bearer_token(), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_incomplete_head() {
+        assert!(HttpRequest::parse(b"GET / HTTP/1.1\r\nHost: x").is_none());
+        assert!(HttpRequest::parse(b"\r\n\r\n").is_none());
+    }
+
+    #[test]
+    fn test_websocket_accept() {
+        // Example handshake from RFC 6455 section 1.3.
+        let req = HttpRequest::parse(
+            b"GET /ws HTTP/1.1\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(
+            req.websocket_accept().as_deref(),
+            Some("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=")
+        );
+
+        let plain = HttpRequest::parse(b"GET /ws HTTP/1.1\r\nHost: x\r\n\r\n").unwrap();
+        assert_eq!(plain.websocket_accept(), None);
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(
+            percent_decode("device%3Aphone").as_deref(),
+            Some("device:phone")
+        );
+        assert_eq!(percent_decode("plain").as_deref(), Some("plain"));
+        assert_eq!(percent_decode("bad%zz"), None);
+        assert_eq!(percent_decode("trunc%3"), None);
+    }
+
+    #[test]
+    fn test_content_length_parsing() {
+        assert_eq!(
+            content_length("POST / HTTP/1.1\r\ncontent-length: 12"),
+            Some(12)
+        );
+        assert_eq!(content_length("GET / HTTP/1.1\r\nHost: x"), None);
+    }
+}
@@ -16,9 +16,17 @@ pub use container_agent::{
 pub use ipc::{parse_marked_response, AgentRequest, AgentResponse, AgentResult, UsageSnapshot};
 pub use ipc::{RESPONSE_END_MARKER, RESPONSE_START_MARKER};
 
+pub mod api;
+#[cfg(feature = "webui")]
+pub mod approvals;
+mod http;
 pub mod idempotency;
+pub mod pairing_server;
 pub mod rate_limit;
 pub mod startup_guard;
+#[cfg(feature = "webui")]
+mod webui;
+pub use api::{start_api_server, GatewayApi};
 pub use idempotency::IdempotencyStore;
 pub use rate_limit::{GatewayRateLimiter, SlidingWindowRateLimiter};
 pub use startup_guard::StartupGuard;
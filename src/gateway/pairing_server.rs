@@ -0,0 +1,208 @@
+//! Pairing endpoint -- exchanges a pairing code for a bearer token.
+//!
+//! Served by the gateway API server when `pairing.enabled` is true:
+//!
+//! - `POST /pair` with `{"code": "123456", "device_name": "my-phone", "scope": "read_only"}`
+//!   → 200 `{"token": "...", "device_name": "my-phone", "scope": "read_only"}`
+//!
+//! `scope` is optional; a device can only narrow the scope the code was issued
+//! with. Invalid, expired, and locked-out attempts all get the same 403 so the
+//! response does not reveal which check failed. Requests are rate limited per
+//! IP through `gateway.rate_limit.pair_per_min`.
+
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+use serde::Deserialize;
+use serde_json::json;
+use tracing::warn;
+
+use crate::security::pairing::{DeviceScope, PairingManager};
+
+use super::http::{error_body, HttpRequest};
+use super::rate_limit::GatewayRateLimiter;
+
+/// Longest accepted device name.
+const MAX_DEVICE_NAME_LEN: usize = 64;
+
+#[derive(Deserialize)]
+struct PairRequest {
+    code: String,
+    device_name: String,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+/// Handle `POST /pair` and produce `(status line, JSON body)`.
+pub(crate) fn handle_pair(
+    request: &HttpRequest,
+    ip: IpAddr,
+    pairing: &Mutex<PairingManager>,
+    rate_limiter: Option<&GatewayRateLimiter>,
+) -> (&'static str, String) {
+    if request.method != "POST" {
+        return ("405 Method Not Allowed", error_body("use POST"));
+    }
+
+    if let Some(limiter) = rate_limiter {
+        if !limiter.check_pair(ip) {
+            warn!(ip = %ip, "Rate limited pairing request");
+            return ("429 Too Many Requests", error_body("rate limit exceeded"));
+        }
+    }
+
+    let req: PairRequest = match serde_json::from_slice(&request.body) {
+        Ok(req) => req,
+        Err(_) => {
+            return (
+                "400 Bad Request",
+                error_body("expected JSON with 'code' and 'device_name'"),
+            )
+        }
+    };
+
+    let device_name = req.device_name.trim();
+    if device_name.is_empty()
+        || device_name.len() > MAX_DEVICE_NAME_LEN
+        || device_name.chars().any(char::is_control)
+    {
+        return (
+            "400 Bad Request",
+            error_body("device_name must be 1-64 printable characters"),
+        );
+    }
+    let requested = match req.scope.as_deref() {
+        None => DeviceScope::Full,
+        Some(scope) => match scope.parse::<DeviceScope>() {
+            Ok(scope) => scope,
+            Err(e) => return ("400 Bad Request", error_body(&e)),
+        },
+    };
+
+    let result = match pairing.lock() {
+        Ok(mut mgr) => {
+            mgr.complete_scoped_pairing(req.code.trim(), device_name, &ip.to_string(), requested)
+        }
+        Err(_) => {
+            return (
+                "500 Internal Server Error",
+                error_body("pairing unavailable"),
+            )
+        }
+    };
+
+    match result {
+        Some((token, scope)) => (
+            "200 OK",
+            json!({
+                "token": token,
+                "device_name": device_name,
+                "scope": scope,
+            })
+            .to_string(),
+        ),
+        None => (
+            "403 Forbidden",
+            error_body("invalid or expired pairing code"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager() -> Mutex<PairingManager> {
+        let dir =
+            std::env::temp_dir().join(format!("zeptoclaw-pairing-server-{}", uuid::Uuid::new_v4()));
+        Mutex::new(PairingManager::with_path(
+            dir.join("paired_devices.json"),
+            5,
+            300,
+        ))
+    }
+
+    fn post(body: &str) -> HttpRequest {
+        let raw = format!(
+            "POST /pair HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        HttpRequest::parse(raw.as_bytes()).unwrap()
+    }
+
+    fn ip() -> IpAddr {
+        "127.0.0.1".parse().unwrap()
+    }
+
+    #[test]
+    fn test_pair_exchanges_code_for_token() {
+        let pairing = manager();
+        let code = pairing
+            .lock()
+            .unwrap()
+            .generate_scoped_pairing_code(DeviceScope::ReadOnly);
+        let body = format!(r#"{{"code": "{}", "device_name": "phone"}}"#, code);
+
+        let (status, resp) = handle_pair(&post(&body), ip(), &pairing, None);
+        assert_eq!(status, "200 OK");
+        let resp: serde_json::Value = serde_json::from_str(&resp).unwrap();
+        assert_eq!(resp["scope"], "read_only");
+        let token = resp["token"].as_str().unwrap();
+        assert_eq!(
+            pairing
+                .lock()
+                .unwrap()
+                .validate_token_scope(token, "127.0.0.1"),
+            Some(("phone".to_string(), DeviceScope::ReadOnly))
+        );
+
+        // Code is single-use.
+        let (status, _) = handle_pair(&post(&body), ip(), &pairing, None);
+        assert_eq!(status, "403 Forbidden");
+    }
+
+    #[test]
+    fn test_pair_rejects_bad_input() {
+        let pairing = manager();
+        let (status, _) = handle_pair(&post("not json"), ip(), &pairing, None);
+        assert_eq!(status, "400 Bad Request");
+
+        let (status, _) = handle_pair(
+            &post(r#"{"code": "1", "device_name": "  "}"#),
+            ip(),
+            &pairing,
+            None,
+        );
+        assert_eq!(status, "400 Bad Request");
+
+        let (status, _) = handle_pair(
+            &post(r#"{"code": "1", "device_name": "x", "scope": "admin"}"#),
+            ip(),
+            &pairing,
+            None,
+        );
+        assert_eq!(status, "400 Bad Request");
+    }
+
+    #[test]
+    fn test_method_and_rate_limit() {
+        let pairing = manager();
+        let get = HttpRequest::parse(b"GET /pair HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(
+            handle_pair(&get, ip(), &pairing, None).0,
+            "405 Method Not Allowed"
+        );
+
+        let limiter = GatewayRateLimiter::new(1, 0, std::time::Duration::from_secs(60));
+        let req = post(r#"{"code": "000000", "device_name": "x"}"#);
+        assert_eq!(
+            handle_pair(&req, ip(), &pairing, Some(&limiter)).0,
+            "403 Forbidden"
+        );
+        assert_eq!(
+            handle_pair(&req, ip(), &pairing, Some(&limiter)).0,
+            "429 Too Many Requests"
+        );
+    }
+}
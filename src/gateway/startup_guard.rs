@@ -34,10 +34,7 @@ pub struct StartupGuard {
 impl StartupGuard {
     /// Create a guard using the default path `~/.zeptoclaw/crash_guard.json`.
     pub fn new(threshold: u32, window_secs: u64) -> Self {
-        let path = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zeptoclaw")
-            .join("crash_guard.json");
+        let path = crate::config::Config::state_dir().join("crash_guard.json");
         Self::with_path(path, threshold, window_secs)
     }
 
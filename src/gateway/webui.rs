@@ -0,0 +1,44 @@
+//! Embedded web chat UI (feature `webui`).
+//!
+//! A single page served by the gateway API server at `/`. It pairs with a
+//! code from `zeptoclaw pair new` (or takes an existing token) and then acts
+//! like any other paired device, using the `/api/v1` routes and the `/ws`
+//! event stream: chat with streamed replies, session history, pending tool
+//! approvals, and health/usage. Assets are compiled into the binary.
+
+const INDEX_HTML: &[u8] = include_bytes!("webui/index.html");
+const APP_JS: &[u8] = include_bytes!("webui/app.js");
+const APP_CSS: &[u8] = include_bytes!("webui/app.css");
+
+/// Embedded asset for a request path: `(content type, bytes)`.
+pub(crate) fn asset(path: &str) -> Option<(&'static str, &'static [u8])> {
+    match path {
+        "/" | "/index.html" => Some(("text/html; charset=utf-8", INDEX_HTML)),
+        "/app.js" => Some(("text/javascript; charset=utf-8", APP_JS)),
+        "/app.css" => Some(("text/css; charset=utf-8", APP_CSS)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assets_are_served() {
+        let (content_type, index) = asset("/").unwrap();
+        assert!(content_type.starts_with("text/html"));
+        let index = std::str::from_utf8(index).unwrap();
+        assert!(index.contains("/app.js"));
+        assert!(index.contains("/app.css"));
+        assert_eq!(asset("/index.html").unwrap().1.len(), INDEX_HTML.len());
+        assert!(asset("/app.js").unwrap().0.starts_with("text/javascript"));
+        assert!(asset("/app.css").is_some());
+    }
+
+    #[test]
+    fn test_unknown_paths_are_not_assets() {
+        assert!(asset("/api/v1/health").is_none());
+        assert!(asset("/../Cargo.toml").is_none());
+    }
+}
@@ -0,0 +1,381 @@
+// ZeptoClaw web UI: a paired device built on /pair, /api/v1 and /ws.
+'use strict';
+
+const TOKEN_KEY = 'zeptoclaw.token';
+const APPROVAL_POLL_MS = 3000;
+const STATUS_POLL_MS = 10000;
+
+const $ = (id) => document.getElementById(id);
+
+let token = localStorage.getItem(TOKEN_KEY);
+let device = null;
+let scope = null;
+let socket = null;
+let reconnectDelay = 1000;
+let reply = null; // { requestId, el } for the reply currently streaming
+let timers = [];
+
+// ---------------------------------------------------------------------------
+// HTTP helpers
+// ---------------------------------------------------------------------------
+
+async function api(method, path, body) {
+  const headers = { Authorization: 'Bearer ' + token };
+  if (body !== undefined) headers['Content-Type'] = 'application/json';
+  const res = await fetch(path, {
+    method,
+    headers,
+    body: body === undefined ? undefined : JSON.stringify(body),
+  });
+  const data = await res.json().catch(() => ({}));
+  if (res.status === 401) {
+    signOut('This device is no longer paired.');
+    throw new Error('unauthorized');
+  }
+  if (!res.ok) throw new Error(data.error || res.statusText);
+  return data;
+}
+
+function el(tag, className, text) {
+  const node = document.createElement(tag);
+  if (className) node.className = className;
+  if (text !== undefined) node.textContent = text;
+  return node;
+}
+
+// ---------------------------------------------------------------------------
+// Sign in / out
+// ---------------------------------------------------------------------------
+
+function signIn(newToken) {
+  token = newToken;
+  localStorage.setItem(TOKEN_KEY, token);
+  start();
+}
+
+function signOut(message) {
+  localStorage.removeItem(TOKEN_KEY);
+  token = null;
+  timers.forEach(clearInterval);
+  timers = [];
+  if (socket) {
+    socket.onclose = null;
+    socket.close();
+    socket = null;
+  }
+  $('app').hidden = true;
+  $('login').hidden = false;
+  $('login-error').textContent = message || '';
+}
+
+$('pair-form').addEventListener('submit', async (event) => {
+  event.preventDefault();
+  $('login-error').textContent = '';
+  try {
+    const res = await fetch('/pair', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({
+        code: $('pair-code').value.trim(),
+        device_name: $('pair-name').value.trim(),
+      }),
+    });
+    const data = await res.json().catch(() => ({}));
+    if (!res.ok) throw new Error(data.error || res.statusText);
+    signIn(data.token);
+  } catch (err) {
+    $('login-error').textContent = err.message;
+  }
+});
+
+$('token-form').addEventListener('submit', (event) => {
+  event.preventDefault();
+  signIn($('token-input').value.trim());
+});
+
+$('logout').addEventListener('click', () => signOut());
+
+// ---------------------------------------------------------------------------
+// Tabs
+// ---------------------------------------------------------------------------
+
+document.querySelectorAll('nav button[data-tab]').forEach((button) => {
+  button.addEventListener('click', () => showTab(button.dataset.tab));
+});
+
+function showTab(name) {
+  document.querySelectorAll('nav button[data-tab]').forEach((b) => {
+    b.classList.toggle('active', b.dataset.tab === name);
+  });
+  document.querySelectorAll('.tab').forEach((tab) => {
+    tab.hidden = tab.id !== 'tab-' + name;
+  });
+  if (name === 'history') loadSessions();
+  if (name === 'approvals') loadApprovals();
+  if (name === 'status') loadStatus();
+}
+
+// ---------------------------------------------------------------------------
+// Chat
+// ---------------------------------------------------------------------------
+
+function addMessage(container, className, text) {
+  const node = el('div', 'msg ' + className, text);
+  container.appendChild(node);
+  container.scrollTop = container.scrollHeight;
+  return node;
+}
+
+function setActivity(text) {
+  $('activity').textContent = text || '';
+}
+
+$('chat-form').addEventListener('submit', async (event) => {
+  event.preventDefault();
+  const input = $('chat-input');
+  const content = input.value.trim();
+  if (!content) return;
+  input.value = '';
+  addMessage($('messages'), 'user', content);
+  try {
+    const res = await api('POST', '/api/v1/messages', { content, wait: false });
+    reply = { requestId: res.request_id, el: null };
+    setActivity('Queued…');
+  } catch (err) {
+    addMessage($('messages'), 'note error', 'Send failed: ' + err.message);
+  }
+});
+
+$('chat-input').addEventListener('keydown', (event) => {
+  if (event.key === 'Enter' && !event.shiftKey) {
+    event.preventDefault();
+    $('chat-form').requestSubmit();
+  }
+});
+
+function isCurrent(event) {
+  return reply && event.request_id && event.request_id === reply.requestId;
+}
+
+function handleEvent(event) {
+  if (event.type === 'ready') {
+    device = event.device;
+    scope = event.scope;
+    return;
+  }
+  if (event.type === 'lagged') {
+    addMessage($('messages'), 'note', 'Missed ' + event.skipped + ' updates; check History.');
+    return;
+  }
+  // Replies default to the chat named after this device.
+  if (event.chat_id !== device) return;
+
+  const messages = $('messages');
+  switch (event.type) {
+    case 'agent_started':
+      if (isCurrent(event)) setActivity('Thinking…');
+      break;
+    case 'tool_started':
+      setActivity('Running ' + event.tool + '…');
+      break;
+    case 'tool_done':
+      addMessage(messages, 'note', '✓ ' + event.tool + ' (' + event.duration_ms + ' ms)');
+      setActivity('Thinking…');
+      break;
+    case 'tool_failed':
+      addMessage(messages, 'note error', '✗ ' + event.tool + ': ' + event.error);
+      setActivity('Thinking…');
+      break;
+    case 'token':
+      if (!isCurrent(event)) break;
+      if (!reply.el) reply.el = addMessage(messages, 'assistant', '');
+      reply.el.textContent += event.delta;
+      messages.scrollTop = messages.scrollHeight;
+      break;
+    case 'message':
+      if (isCurrent(event) && reply.el) {
+        reply.el.textContent = event.content;
+      } else {
+        addMessage(messages, 'assistant', event.content);
+      }
+      break;
+    case 'agent_done':
+      if (isCurrent(event)) {
+        reply = null;
+        setActivity('');
+      }
+      break;
+  }
+}
+
+function connect() {
+  const proto = location.protocol === 'https:' ? 'wss:' : 'ws:';
+  socket = new WebSocket(proto + '//' + location.host + '/ws?token=' + encodeURIComponent(token));
+  socket.onopen = () => {
+    reconnectDelay = 1000;
+    $('conn').classList.add('up');
+  };
+  socket.onmessage = (msg) => {
+    try {
+      handleEvent(JSON.parse(msg.data));
+    } catch (err) {
+      console.warn('Bad event', err);
+    }
+  };
+  socket.onclose = () => {
+    $('conn').classList.remove('up');
+    if (!token) return;
+    setTimeout(connect, reconnectDelay);
+    reconnectDelay = Math.min(reconnectDelay * 2, 30000);
+  };
+}
+
+// ---------------------------------------------------------------------------
+// History
+// ---------------------------------------------------------------------------
+
+async function loadSessions() {
+  const list = $('session-list');
+  try {
+    const data = await api('GET', '/api/v1/sessions');
+    list.replaceChildren();
+    data.sessions.sort().forEach((key) => {
+      const item = el('li', null, key);
+      item.addEventListener('click', () => loadSession(key));
+      list.appendChild(item);
+    });
+    if (!data.sessions.length) list.appendChild(el('li', 'muted', 'No sessions yet.'));
+  } catch (err) {
+    list.replaceChildren(el('li', 'error', err.message));
+  }
+}
+
+async function loadSession(key) {
+  const view = $('session-view');
+  view.replaceChildren();
+  try {
+    const session = await api('GET', '/api/v1/sessions/' + encodeURIComponent(key));
+    session.messages.forEach((m) => {
+      if (m.role === 'system') return;
+      if (m.role === 'tool') {
+        addMessage(view, 'note', 'tool result: ' + m.content.slice(0, 200));
+        return;
+      }
+      if (m.content) addMessage(view, m.role === 'user' ? 'user' : 'assistant', m.content);
+      (m.tool_calls || []).forEach((call) => addMessage(view, 'note', '→ ' + call.name));
+    });
+  } catch (err) {
+    addMessage(view, 'note error', err.message);
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Approvals
+// ---------------------------------------------------------------------------
+
+async function loadApprovals() {
+  let data;
+  try {
+    data = await api('GET', '/api/v1/approvals');
+  } catch (err) {
+    return;
+  }
+  const pending = data.approvals || [];
+  $('approval-count').textContent = pending.length ? String(pending.length) : '';
+
+  const list = $('approval-list');
+  list.replaceChildren();
+  if (!pending.length) {
+    list.appendChild(el('li', 'muted', 'Nothing waiting.'));
+    return;
+  }
+  pending.forEach((approval) => {
+    const item = el('li');
+    item.appendChild(el('strong', null, approval.tool_name));
+    item.appendChild(el('span', 'muted', ' · ' + new Date(approval.timestamp).toLocaleTimeString()));
+    item.appendChild(el('pre', null, JSON.stringify(approval.arguments, null, 2)));
+    const actions = el('div', 'actions');
+    const approve = el('button', 'approve', 'Approve');
+    const deny = el('button', 'deny', 'Deny');
+    [approve, deny].forEach((b) => {
+      b.type = 'button';
+      b.disabled = scope !== 'full';
+    });
+    approve.addEventListener('click', () => decide(approval.id, true));
+    deny.addEventListener('click', () => decide(approval.id, false));
+    actions.append(approve, deny);
+    item.appendChild(actions);
+    list.appendChild(item);
+  });
+}
+
+async function decide(id, approve) {
+  try {
+    await api('POST', '/api/v1/approvals/' + encodeURIComponent(id), {
+      approve,
+      reason: approve ? undefined : 'Denied from web UI',
+    });
+  } catch (err) {
+    alert('Could not record decision: ' + err.message);
+  }
+  loadApprovals();
+}
+
+// ---------------------------------------------------------------------------
+// Status
+// ---------------------------------------------------------------------------
+
+function formatUptime(secs) {
+  const h = Math.floor(secs / 3600);
+  const m = Math.floor((secs % 3600) / 60);
+  return h + 'h ' + m + 'm';
+}
+
+async function loadStatus() {
+  const status = $('status');
+  try {
+    const health = await api('GET', '/api/v1/health');
+    const rows = [
+      ['Status', health.status],
+      ['Version', health.version],
+      ['Uptime', formatUptime(health.uptime_secs)],
+    ];
+    if (health.memory) rows.push(['Memory', health.memory.rss_mb + ' MB']);
+    if (health.usage) {
+      const u = health.usage;
+      rows.push(['Requests', u.requests], ['Tool calls', u.tool_calls]);
+      rows.push(['Tokens', u.input_tokens + ' in / ' + u.output_tokens + ' out']);
+      rows.push(['Errors', u.errors]);
+    }
+    Object.entries(health.checks || {}).forEach(([name, check]) => {
+      rows.push([name, typeof check === 'object' ? check.status || JSON.stringify(check) : check]);
+    });
+    status.replaceChildren();
+    rows.forEach(([label, value]) => {
+      status.append(el('dt', null, label), el('dd', null, String(value)));
+    });
+  } catch (err) {
+    status.replaceChildren(el('dd', 'error', err.message));
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Startup
+// ---------------------------------------------------------------------------
+
+function start() {
+  $('login').hidden = true;
+  $('app').hidden = false;
+  connect();
+  loadApprovals();
+  timers.push(setInterval(loadApprovals, APPROVAL_POLL_MS));
+  timers.push(setInterval(() => {
+    if (!$('tab-status').hidden) loadStatus();
+  }, STATUS_POLL_MS));
+}
+
+if (token) {
+  start();
+} else {
+  signOut();
+}
@@ -0,0 +1,385 @@
+//! Firmware flashing -- map a board to an external flasher and run it.
+//!
+//! Flashing is delegated to the standard tool for each board family,
+//! invoked through the configured container runtime:
+//!
+//! - Nucleo (STM32) -- `probe-rs download --chip <chip> <elf>`
+//! - Arduino (AVR) -- `avrdude -p <mcu> -c <programmer> -P <port> -U flash:w:<hex>:i`
+//! - ESP32 -- `esptool.py --port <port> write_flash 0x0 <bin>`
+//!
+//! A [`FlashPlan`] is built first so the exact commands can be shown to the
+//! user for approval before anything touches the board. This module is
+//! always compiled: it only spawns processes and needs no USB stack.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::error::{Result, ZeptoError};
+use crate::runtime::{CommandOutput, ContainerConfig, ContainerRuntime};
+
+/// Default timeout for the build step (seconds).
+pub const BUILD_TIMEOUT_SECS: u64 = 600;
+
+/// Default timeout for the flash step (seconds).
+pub const FLASH_TIMEOUT_SECS: u64 = 180;
+
+/// External program used to write firmware.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "flasher", rename_all = "snake_case")]
+pub enum Flasher {
+    /// probe-rs CLI over an ST-Link/CMSIS-DAP probe.
+    ProbeRs { chip: &'static str },
+    /// avrdude over the board's serial bootloader.
+    Avrdude {
+        mcu: &'static str,
+        programmer: &'static str,
+        baud: u32,
+    },
+    /// Espressif esptool over the USB-UART bridge.
+    Esptool,
+}
+
+impl Flasher {
+    /// Flasher for a board name from the registry, if supported.
+    pub fn for_board(board: &str) -> Option<Self> {
+        match board {
+            "nucleo-f401re" => Some(Self::ProbeRs {
+                chip: "STM32F401RETx",
+            }),
+            "nucleo-f411re" => Some(Self::ProbeRs {
+                chip: "STM32F411RETx",
+            }),
+            "arduino-uno" => Some(Self::Avrdude {
+                mcu: "atmega328p",
+                programmer: "arduino",
+                baud: 115_200,
+            }),
+            "arduino-mega" => Some(Self::Avrdude {
+                mcu: "atmega2560",
+                programmer: "wiring",
+                baud: 115_200,
+            }),
+            "esp32" => Some(Self::Esptool),
+            _ => None,
+        }
+    }
+
+    /// Whether the flasher talks to the board through a serial port.
+    pub fn needs_port(&self) -> bool {
+        !matches!(self, Self::ProbeRs { .. })
+    }
+
+    /// Artifact extensions this flasher accepts.
+    pub fn artifact_extensions(&self) -> &'static [&'static str] {
+        match self {
+            Self::ProbeRs { .. } => &["elf", "hex", "bin"],
+            Self::Avrdude { .. } => &["hex"],
+            Self::Esptool => &["bin"],
+        }
+    }
+
+    /// Shell command that writes `artifact` to the board.
+    pub fn command(&self, artifact: &Path, port: Option<&str>) -> String {
+        let artifact = shell_escape(&artifact.to_string_lossy());
+        let port = shell_escape(port.unwrap_or_default());
+        match self {
+            Self::ProbeRs { chip } => {
+                format!("probe-rs download --chip {} {}", chip, artifact)
+            }
+            Self::Avrdude {
+                mcu,
+                programmer,
+                baud,
+            } => format!(
+                "avrdude -p {} -c {} -P {} -b {} -D -U flash:w:{}:i",
+                mcu, programmer, port, baud, artifact
+            ),
+            Self::Esptool => format!("esptool.py --port {} write_flash 0x0 {}", port, artifact),
+        }
+    }
+}
+
+/// Shell-escape a value by wrapping in single quotes.
+fn shell_escape(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// A build + flash sequence awaiting approval.
+#[derive(Debug, Clone, Serialize)]
+pub struct FlashPlan {
+    /// Registry board name (e.g. "arduino-uno")
+    pub board: String,
+    /// Flasher chosen for the board
+    #[serde(flatten)]
+    pub flasher: Flasher,
+    /// Serial port for serial-bootloader flashers
+    pub port: Option<String>,
+    /// Firmware image, inside the workspace
+    pub artifact: PathBuf,
+    /// Optional build command run in the workspace first
+    pub build_command: Option<String>,
+    /// Flash command
+    pub flash_command: String,
+    /// Working directory for both steps
+    pub workdir: PathBuf,
+}
+
+impl FlashPlan {
+    /// Human-readable summary of what will run.
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "Flash {} with {}",
+            self.board,
+            self.artifact.display()
+        )];
+        if let Some(port) = &self.port {
+            lines.push(format!("Port: {}", port));
+        }
+        if let Some(build) = &self.build_command {
+            lines.push(format!("1. Build: {}", build));
+            lines.push(format!("2. Flash: {}", self.flash_command));
+        } else {
+            lines.push(format!("Flash: {}", self.flash_command));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Outcome of running a plan.
+#[derive(Debug, Clone)]
+pub struct FlashOutcome {
+    /// Build step output, when a build command was given
+    pub build: Option<CommandOutput>,
+    /// Flash step output (absent when the build failed)
+    pub flash: Option<CommandOutput>,
+}
+
+impl FlashOutcome {
+    /// Whether every step that ran succeeded.
+    pub fn success(&self) -> bool {
+        self.build.as_ref().is_none_or(CommandOutput::success)
+            && self.flash.as_ref().is_some_and(CommandOutput::success)
+    }
+}
+
+/// Build a plan for `board`, validating the port and artifact type.
+///
+/// `artifact` must already be resolved inside the workspace; it may not
+/// exist yet when `build_command` produces it.
+pub fn plan_flash(
+    board: &str,
+    artifact: PathBuf,
+    port: Option<&str>,
+    build_command: Option<&str>,
+    workdir: PathBuf,
+) -> Result<FlashPlan> {
+    let flasher = Flasher::for_board(board).ok_or_else(|| {
+        ZeptoError::Tool(format!(
+            "No flasher known for board '{}'. Supported: nucleo-f401re, nucleo-f411re, arduino-uno, arduino-mega, esp32",
+            board
+        ))
+    })?;
+
+    let port = port.map(str::trim).filter(|p| !p.is_empty());
+    if flasher.needs_port() {
+        let port = port.ok_or_else(|| {
+            ZeptoError::Tool(format!(
+                "Board '{}' flashes over serial: 'port' is required",
+                board
+            ))
+        })?;
+        crate::peripherals::validate_serial_path(port).map_err(ZeptoError::SecurityViolation)?;
+    }
+
+    let ext = artifact
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    if !flasher.artifact_extensions().contains(&ext.as_str()) {
+        return Err(ZeptoError::Tool(format!(
+            "Artifact {} has the wrong type for {} (expected .{})",
+            artifact.display(),
+            board,
+            flasher.artifact_extensions().join(", .")
+        )));
+    }
+
+    if let Some(build) = build_command {
+        crate::security::ShellSecurityConfig::new().validate_command(build)?;
+    }
+
+    let flash_command = flasher.command(&artifact, port);
+    Ok(FlashPlan {
+        board: board.to_string(),
+        port: if flasher.needs_port() {
+            port.map(String::from)
+        } else {
+            None
+        },
+        flasher,
+        artifact,
+        build_command: build_command.map(String::from),
+        flash_command,
+        workdir,
+    })
+}
+
+/// Run a plan: build (if requested), check the artifact, then flash.
+pub async fn run_flash(plan: &FlashPlan, runtime: &dyn ContainerRuntime) -> Result<FlashOutcome> {
+    let container = |timeout| {
+        ContainerConfig::new()
+            .with_timeout(timeout)
+            .with_workdir(plan.workdir.clone())
+            .with_mount(plan.workdir.clone(), plan.workdir.clone(), false)
+    };
+
+    let build = match &plan.build_command {
+        Some(command) => {
+            let output = runtime
+                .execute(command, &container(BUILD_TIMEOUT_SECS))
+                .await
+                .map_err(|e| ZeptoError::Tool(format!("Build failed to run: {}", e)))?;
+            if !output.success() {
+                return Ok(FlashOutcome {
+                    build: Some(output),
+                    flash: None,
+                });
+            }
+            Some(output)
+        }
+        None => None,
+    };
+
+    if !plan.artifact.is_file() {
+        return Err(ZeptoError::Tool(format!(
+            "Firmware artifact {} does not exist",
+            plan.artifact.display()
+        )));
+    }
+
+    let flash = runtime
+        .execute(&plan.flash_command, &container(FLASH_TIMEOUT_SECS))
+        .await
+        .map_err(|e| ZeptoError::Tool(format!("Flasher failed to run: {}", e)))?;
+    Ok(FlashOutcome {
+        build,
+        flash: Some(flash),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flasher_for_known_boards() {
+        assert!(matches!(
+            Flasher::for_board("nucleo-f401re"),
+            Some(Flasher::ProbeRs { .. })
+        ));
+        assert!(matches!(
+            Flasher::for_board("arduino-uno"),
+            Some(Flasher::Avrdude { .. })
+        ));
+        assert_eq!(Flasher::for_board("esp32"), Some(Flasher::Esptool));
+        assert!(Flasher::for_board("cp2102").is_none());
+    }
+
+    #[test]
+    fn test_avrdude_command_quotes_paths() {
+        let flasher = Flasher::for_board("arduino-uno").unwrap();
+        let cmd = flasher.command(Path::new("/ws/blink it.hex"), Some("/dev/ttyACM0"));
+        assert_eq!(
+            cmd,
+            "avrdude -p atmega328p -c arduino -P '/dev/ttyACM0' -b 115200 -D -U flash:w:'/ws/blink it.hex':i"
+        );
+    }
+
+    #[test]
+    fn test_plan_requires_port_for_serial_boards() {
+        let err = plan_flash(
+            "arduino-uno",
+            PathBuf::from("/ws/a.hex"),
+            None,
+            None,
+            PathBuf::from("/ws"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("port"));
+
+        let err = plan_flash(
+            "arduino-uno",
+            PathBuf::from("/ws/a.hex"),
+            Some("/etc/passwd"),
+            None,
+            PathBuf::from("/ws"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not allowed"));
+    }
+
+    #[test]
+    fn test_plan_checks_artifact_type() {
+        let err = plan_flash(
+            "esp32",
+            PathBuf::from("/ws/fw.hex"),
+            Some("/dev/ttyUSB0"),
+            None,
+            PathBuf::from("/ws"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(".bin"));
+    }
+
+    #[test]
+    fn test_probe_rs_plan_ignores_port() {
+        let plan = plan_flash(
+            "nucleo-f401re",
+            PathBuf::from("/ws/target/fw.elf"),
+            Some("/dev/ttyACM0"),
+            Some("cargo build --release"),
+            PathBuf::from("/ws"),
+        )
+        .unwrap();
+        assert!(plan.port.is_none());
+        assert_eq!(
+            plan.flash_command,
+            "probe-rs download --chip STM32F401RETx '/ws/target/fw.elf'"
+        );
+        let summary = plan.summary();
+        assert!(summary.contains("1. Build: cargo build --release"));
+        assert!(summary.contains("2. Flash: probe-rs"));
+    }
+
+    #[test]
+    fn test_plan_rejects_blocked_build_command() {
+        let err = plan_flash(
+            "nucleo-f401re",
+            PathBuf::from("/ws/fw.elf"),
+            None,
+            Some("rm -rf /"),
+            PathBuf::from("/ws"),
+        );
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_flash_stops_on_build_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = plan_flash(
+            "nucleo-f401re",
+            dir.path().join("fw.elf"),
+            None,
+            Some("exit 3"),
+            dir.path().to_path_buf(),
+        )
+        .unwrap();
+        let runtime = crate::runtime::NativeRuntime::new();
+        let outcome = run_flash(&plan, &runtime).await.unwrap();
+        assert!(!outcome.success());
+        assert!(outcome.flash.is_none());
+        assert_eq!(outcome.build.unwrap().exit_code, Some(3));
+    }
+}
@@ -5,9 +5,11 @@
 //! - **Board registry** (`registry`): Static VID/PID to board name mapping (always compiled)
 //! - **USB discovery** (`discover`): Enumerate connected USB devices (feature-gated: `hardware`)
 //! - **Introspection** (`introspect`): Correlate serial paths with USB devices (feature-gated: `hardware`)
+//! - **Flashing** (`flash`): Build and flash firmware via probe-rs/avrdude/esptool (always compiled)
 //!
 //! The `HardwareManager` orchestrator ties these together for the agent tool and CLI.
 
+pub mod flash;
 pub mod registry;
 
 #[cfg(all(
@@ -22,8 +24,13 @@ pub mod discover;
 ))]
 pub mod introspect;
 
+use std::path::PathBuf;
+
 use serde::Serialize;
 
+use crate::error::Result;
+use crate::runtime::ContainerRuntime;
+
 /// A hardware device discovered during auto-scan.
 ///
 /// This is the unified device representation used by the CLI and agent tool.
@@ -136,6 +143,33 @@ impl HardwareManager {
         let devices = self.discover_devices();
         devices.into_iter().find(|d| d.name == query)
     }
+
+    /// Plan a firmware flash for a board (registry name or "VID:PID").
+    ///
+    /// Nothing runs until the plan is passed to [`HardwareManager::flash`].
+    pub fn flash_plan(
+        &self,
+        device: &str,
+        artifact: PathBuf,
+        port: Option<&str>,
+        build_command: Option<&str>,
+        workdir: PathBuf,
+    ) -> Result<flash::FlashPlan> {
+        let board = self
+            .device_info(device)
+            .map(|d| d.name)
+            .unwrap_or_else(|| device.to_string());
+        flash::plan_flash(&board, artifact, port, build_command, workdir)
+    }
+
+    /// Run an approved flash plan through the container runtime.
+    pub async fn flash(
+        &self,
+        plan: &flash::FlashPlan,
+        runtime: &dyn ContainerRuntime,
+    ) -> Result<flash::FlashOutcome> {
+        flash::run_flash(plan, runtime).await
+    }
 }
 
 impl Default for HardwareManager {
@@ -220,4 +254,20 @@ mod tests {
         let info = mgr.device_info("ZZZZ:YYYY");
         assert!(info.is_none());
     }
+
+    #[test]
+    fn test_hardware_manager_flash_plan_by_vid_pid() {
+        let mgr = HardwareManager::new();
+        let plan = mgr
+            .flash_plan(
+                "2341:0043",
+                PathBuf::from("/ws/blink.hex"),
+                Some("/dev/ttyACM0"),
+                None,
+                PathBuf::from("/ws"),
+            )
+            .unwrap();
+        assert_eq!(plan.board, "arduino-uno");
+        assert!(plan.flash_command.starts_with("avrdude -p atmega328p"));
+    }
 }
@@ -5,6 +5,10 @@
 //!
 //! Also provides:
 //! - [`UsageMetrics`] for lock-free per-request counters
+//! - [`ToolUsageStats`] per-tool analytics, persisted daily to
+//!   `~/.zeptoclaw/metrics/tools-YYYY-MM-DD.json`
+//! - [`DailyUsage`] token, cost and per-session aggregates, persisted daily to
+//!   `~/.zeptoclaw/metrics/usage-YYYY-MM-DD.json`
 //! - [`start_periodic_usage_flush`] for periodic metric emission
 //! - [`health_port`] helper for legacy env-only port resolution
 //!
@@ -12,14 +16,20 @@
 //! preserving the ultra-light binary footprint (4MB design goal).
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
 
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
 use tokio::io::AsyncWriteExt;
 use tokio::net::TcpListener;
 use tracing::{info, warn};
 
+use crate::bus::MessageBus;
+
 // ============================================================================
 // Default health check port
 // ============================================================================
@@ -204,6 +214,7 @@ pub struct HealthRegistry {
     checks: Arc<RwLock<HashMap<String, HealthCheck>>>,
     start_time: Instant,
     metrics: Arc<RwLock<Option<Arc<UsageMetrics>>>>,
+    bus: Arc<RwLock<Option<MessageBus>>>,
 }
 
 impl HealthRegistry {
@@ -213,6 +224,7 @@ impl HealthRegistry {
             checks: Arc::new(RwLock::new(HashMap::new())),
             start_time: Instant::now(),
             metrics: Arc::new(RwLock::new(None)),
+            bus: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -221,6 +233,11 @@ impl HealthRegistry {
         *self.metrics.write().unwrap() = Some(metrics);
     }
 
+    /// Attach the message bus so health responses include inbound queue depth.
+    pub fn set_message_bus(&self, bus: MessageBus) {
+        *self.bus.write().unwrap() = Some(bus);
+    }
+
     /// Register a new named check. Replaces any existing check with the same name.
     pub fn register(&self, check: HealthCheck) {
         self.checks
@@ -338,6 +355,18 @@ impl HealthRegistry {
             ));
         }
 
+        // queue section — only when the bus is attached
+        if let Some(ref bus) = *self.bus.read().unwrap() {
+            let stats = bus.inbound_stats();
+            if let Ok(stats_json) = serde_json::to_string(&stats) {
+                json.push_str(&format!(
+                    ",\"queue\":{{\"depth\":{},\"inbound\":{}}}",
+                    stats.depth(),
+                    stats_json
+                ));
+            }
+        }
+
         json.push_str(&format!(",\"checks\":{}}}", checks_json));
         json
     }
@@ -368,6 +397,12 @@ pub struct UsageMetrics {
     pub errors: AtomicU64,
     /// Whether the gateway is ready to accept requests.
     pub ready: AtomicBool,
+    /// Per-tool analytics accumulated since the last flush.
+    tools: Mutex<HashMap<String, ToolUsageStats>>,
+    /// Token, cost and session aggregates accumulated since the last flush.
+    daily: Mutex<DailyUsage>,
+    /// Directory for daily tool usage files; `None` disables persistence.
+    tool_usage_dir: RwLock<Option<PathBuf>>,
 }
 
 impl UsageMetrics {
@@ -380,12 +415,19 @@ impl UsageMetrics {
             output_tokens: AtomicU64::new(0),
             errors: AtomicU64::new(0),
             ready: AtomicBool::new(false),
+            tools: Mutex::new(HashMap::new()),
+            daily: Mutex::new(DailyUsage::default()),
+            tool_usage_dir: RwLock::new(None),
         }
     }
 
     /// Increment the request counter.
     pub fn record_request(&self) {
         self.requests.fetch_add(1, Ordering::Relaxed);
+        self.daily
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .requests += 1;
     }
 
     /// Increment the tool call counter.
@@ -399,9 +441,92 @@ impl UsageMetrics {
         self.output_tokens.fetch_add(output, Ordering::Relaxed);
     }
 
+    /// Record token usage from an LLM call made for `session` with `model`,
+    /// adding it (and its estimated cost) to the daily usage aggregates.
+    pub fn record_session_tokens(&self, session: &str, model: &str, input: u64, output: u64) {
+        self.record_tokens(input, output);
+        let cost = crate::utils::cost::estimate_cost(
+            model,
+            input.min(u32::MAX as u64) as u32,
+            output.min(u32::MAX as u64) as u32,
+            &HashMap::new(),
+        )
+        .unwrap_or(0.0);
+        self.daily
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .record(session, input, output, cost);
+    }
+
     /// Increment the error counter.
     pub fn record_error(&self) {
         self.errors.fetch_add(1, Ordering::Relaxed);
+        self.daily.lock().unwrap_or_else(|e| e.into_inner()).errors += 1;
+    }
+
+    /// Record one tool invocation for per-tool analytics.
+    pub fn record_tool_usage(&self, tool: &str, latency_ms: u64, success: bool, arg_bytes: usize) {
+        let mut tools = self.tools.lock().unwrap_or_else(|e| e.into_inner());
+        tools
+            .entry(tool.to_string())
+            .or_default()
+            .record(latency_ms, success, arg_bytes as u64);
+    }
+
+    /// Snapshot of per-tool analytics accumulated since the last flush.
+    pub fn tool_usage(&self) -> HashMap<String, ToolUsageStats> {
+        self.tools.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Snapshot of the daily usage aggregates accumulated since the last flush.
+    pub fn daily_usage(&self) -> DailyUsage {
+        self.daily.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Persist per-tool analytics and daily usage to `dir` on every flush.
+    pub fn set_tool_usage_dir(&self, dir: PathBuf) {
+        if let Ok(mut slot) = self.tool_usage_dir.write() {
+            *slot = Some(dir);
+        }
+    }
+
+    /// Merge accumulated per-tool analytics into today's file and reset the
+    /// in-memory counters. No-op when persistence is not configured.
+    pub fn flush_tool_usage(&self) {
+        let Some(dir) = self.tool_usage_dir.read().ok().and_then(|d| d.clone()) else {
+            return;
+        };
+        let pending = std::mem::take(&mut *self.tools.lock().unwrap_or_else(|e| e.into_inner()));
+        if pending.is_empty() {
+            return;
+        }
+        if let Err(e) = merge_tool_usage_file(&dir, Local::now().date_naive(), &pending) {
+            warn!(error = %e, "Failed to persist tool usage");
+            // Put the counters back so the next flush retries.
+            let mut tools = self.tools.lock().unwrap_or_else(|e| e.into_inner());
+            for (name, stats) in pending {
+                tools.entry(name).or_default().merge(&stats);
+            }
+        }
+    }
+
+    /// Merge the accumulated daily usage aggregates into today's file and
+    /// reset them. No-op when persistence is not configured.
+    pub fn flush_daily_usage(&self) {
+        let Some(dir) = self.tool_usage_dir.read().ok().and_then(|d| d.clone()) else {
+            return;
+        };
+        let pending = std::mem::take(&mut *self.daily.lock().unwrap_or_else(|e| e.into_inner()));
+        if pending.is_empty() {
+            return;
+        }
+        if let Err(e) = merge_daily_usage_file(&dir, Local::now().date_naive(), &pending) {
+            warn!(error = %e, "Failed to persist daily usage");
+            self.daily
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .merge(&pending);
+        }
     }
 
     /// Set the ready flag.
@@ -430,6 +555,303 @@ impl Default for UsageMetrics {
     }
 }
 
+// ============================================================================
+// Per-tool usage analytics
+// ============================================================================
+
+/// Latency samples kept per tool per day for percentile estimates.
+const TOOL_LATENCY_SAMPLES: usize = 1000;
+
+/// Upper bounds (exclusive) of the argument-size buckets, in bytes. The last
+/// bucket collects everything at or above the final bound.
+pub const ARG_SIZE_BUCKETS: [u64; 4] = [128, 1024, 8192, 65536];
+
+/// Invocation statistics for a single tool.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolUsageStats {
+    pub calls: u64,
+    pub errors: u64,
+    pub total_latency_ms: u64,
+    /// Most recent latencies (bounded) used for percentile estimates.
+    pub latencies_ms: Vec<u64>,
+    pub total_arg_bytes: u64,
+    pub max_arg_bytes: u64,
+    /// Argument-size histogram; see [`ARG_SIZE_BUCKETS`].
+    pub arg_size_buckets: [u64; 5],
+}
+
+impl ToolUsageStats {
+    /// Record one invocation.
+    pub fn record(&mut self, latency_ms: u64, success: bool, arg_bytes: u64) {
+        self.calls += 1;
+        if !success {
+            self.errors += 1;
+        }
+        self.total_latency_ms += latency_ms;
+        self.push_latency(latency_ms);
+        self.total_arg_bytes += arg_bytes;
+        self.max_arg_bytes = self.max_arg_bytes.max(arg_bytes);
+        let bucket = ARG_SIZE_BUCKETS
+            .iter()
+            .position(|&bound| arg_bytes < bound)
+            .unwrap_or(ARG_SIZE_BUCKETS.len());
+        self.arg_size_buckets[bucket] += 1;
+    }
+
+    fn push_latency(&mut self, latency_ms: u64) {
+        if self.latencies_ms.len() >= TOOL_LATENCY_SAMPLES {
+            self.latencies_ms.remove(0);
+        }
+        self.latencies_ms.push(latency_ms);
+    }
+
+    /// Fold `other` into `self`.
+    pub fn merge(&mut self, other: &ToolUsageStats) {
+        self.calls += other.calls;
+        self.errors += other.errors;
+        self.total_latency_ms += other.total_latency_ms;
+        for &latency in &other.latencies_ms {
+            self.push_latency(latency);
+        }
+        self.total_arg_bytes += other.total_arg_bytes;
+        self.max_arg_bytes = self.max_arg_bytes.max(other.max_arg_bytes);
+        for (mine, theirs) in self
+            .arg_size_buckets
+            .iter_mut()
+            .zip(other.arg_size_buckets.iter())
+        {
+            *mine += theirs;
+        }
+    }
+
+    /// Fraction of calls that failed (0.0 when never called).
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.calls as f64
+        }
+    }
+
+    /// Latency percentile (`p` in 0..=100) over the sample, nearest-rank.
+    pub fn latency_percentile(&self, p: f64) -> Option<u64> {
+        if self.latencies_ms.is_empty() {
+            return None;
+        }
+        let mut sorted = self.latencies_ms.clone();
+        sorted.sort_unstable();
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+
+    /// Mean argument size in bytes.
+    pub fn avg_arg_bytes(&self) -> u64 {
+        self.total_arg_bytes.checked_div(self.calls).unwrap_or(0)
+    }
+}
+
+/// Default directory for daily tool usage files: `~/.zeptoclaw/metrics`.
+pub fn tool_usage_dir() -> PathBuf {
+    crate::config::Config::state_dir().join("metrics")
+}
+
+/// Path of the tool usage file for `date`.
+pub fn tool_usage_path(dir: &Path, date: NaiveDate) -> PathBuf {
+    dir.join(format!("tools-{}.json", date.format("%Y-%m-%d")))
+}
+
+/// Load the tool usage file for `date` (empty when missing).
+pub fn load_tool_usage(
+    dir: &Path,
+    date: NaiveDate,
+) -> std::io::Result<HashMap<String, ToolUsageStats>> {
+    let path = tool_usage_path(dir, date);
+    if !path.is_file() {
+        return Ok(HashMap::new());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Aggregate tool usage over the `days` days ending at `end` (inclusive).
+pub fn load_tool_usage_range(
+    dir: &Path,
+    end: NaiveDate,
+    days: u32,
+) -> std::io::Result<HashMap<String, ToolUsageStats>> {
+    let mut total: HashMap<String, ToolUsageStats> = HashMap::new();
+    for offset in 0..days.max(1) {
+        let Some(date) = end.checked_sub_days(chrono::Days::new(offset as u64)) else {
+            break;
+        };
+        for (name, stats) in load_tool_usage(dir, date)? {
+            total.entry(name).or_default().merge(&stats);
+        }
+    }
+    Ok(total)
+}
+
+fn merge_tool_usage_file(
+    dir: &Path,
+    date: NaiveDate,
+    pending: &HashMap<String, ToolUsageStats>,
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let mut day = load_tool_usage(dir, date)?;
+    for (name, stats) in pending {
+        day.entry(name.clone()).or_default().merge(stats);
+    }
+    write_json_atomic(&tool_usage_path(dir, date), &day)
+}
+
+fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)
+}
+
+// ============================================================================
+// Daily usage aggregates
+// ============================================================================
+
+/// Token and cost totals for one session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionUsage {
+    pub llm_calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl SessionUsage {
+    /// Input plus output tokens.
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+/// Usage totals for one day (or, after [`DailyUsage::merge`], several).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyUsage {
+    pub requests: u64,
+    pub errors: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Estimated cost in USD; calls to models without known pricing add 0.
+    pub cost_usd: f64,
+    /// Per-session totals keyed by session key.
+    pub sessions: HashMap<String, SessionUsage>,
+}
+
+impl DailyUsage {
+    /// Record one LLM call for `session`.
+    pub fn record(&mut self, session: &str, input: u64, output: u64, cost_usd: f64) {
+        self.input_tokens += input;
+        self.output_tokens += output;
+        self.cost_usd += cost_usd;
+        let entry = self.sessions.entry(session.to_string()).or_default();
+        entry.llm_calls += 1;
+        entry.input_tokens += input;
+        entry.output_tokens += output;
+        entry.cost_usd += cost_usd;
+    }
+
+    /// Fold `other` into `self`.
+    pub fn merge(&mut self, other: &DailyUsage) {
+        self.requests += other.requests;
+        self.errors += other.errors;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cost_usd += other.cost_usd;
+        for (key, usage) in &other.sessions {
+            let entry = self.sessions.entry(key.clone()).or_default();
+            entry.llm_calls += usage.llm_calls;
+            entry.input_tokens += usage.input_tokens;
+            entry.output_tokens += usage.output_tokens;
+            entry.cost_usd += usage.cost_usd;
+        }
+    }
+
+    /// Whether nothing was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.requests == 0
+            && self.errors == 0
+            && self.input_tokens == 0
+            && self.output_tokens == 0
+            && self.sessions.is_empty()
+    }
+
+    /// Input plus output tokens.
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    /// Up to `limit` sessions with the most tokens, busiest first.
+    pub fn busiest_sessions(&self, limit: usize) -> Vec<(&str, &SessionUsage)> {
+        let mut sessions: Vec<(&str, &SessionUsage)> = self
+            .sessions
+            .iter()
+            .map(|(key, usage)| (key.as_str(), usage))
+            .collect();
+        sessions.sort_by(|a, b| {
+            b.1.total_tokens()
+                .cmp(&a.1.total_tokens())
+                .then_with(|| a.0.cmp(b.0))
+        });
+        sessions.truncate(limit);
+        sessions
+    }
+}
+
+/// Path of the daily usage file for `date`.
+pub fn daily_usage_path(dir: &Path, date: NaiveDate) -> PathBuf {
+    dir.join(format!("usage-{}.json", date.format("%Y-%m-%d")))
+}
+
+/// Load the daily usage file for `date` (empty when missing).
+pub fn load_daily_usage(dir: &Path, date: NaiveDate) -> std::io::Result<DailyUsage> {
+    let path = daily_usage_path(dir, date);
+    if !path.is_file() {
+        return Ok(DailyUsage::default());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Aggregate daily usage over the `days` days ending at `end` (inclusive).
+pub fn load_daily_usage_range(
+    dir: &Path,
+    end: NaiveDate,
+    days: u32,
+) -> std::io::Result<DailyUsage> {
+    let mut total = DailyUsage::default();
+    for offset in 0..days.max(1) {
+        let Some(date) = end.checked_sub_days(chrono::Days::new(offset as u64)) else {
+            break;
+        };
+        total.merge(&load_daily_usage(dir, date)?);
+    }
+    Ok(total)
+}
+
+fn merge_daily_usage_file(
+    dir: &Path,
+    date: NaiveDate,
+    pending: &DailyUsage,
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let mut day = load_daily_usage(dir, date)?;
+    day.merge(pending);
+    write_json_atomic(&daily_usage_path(dir, date), &day)
+}
+
 // ============================================================================
 // Health server (raw TCP, no axum — preserves binary size)
 // ============================================================================
@@ -619,7 +1041,9 @@ pub async fn start_health_server_legacy(
 // Periodic usage flush
 // ============================================================================
 
-/// Start a background task that emits usage metrics every 60 seconds.
+/// Start a background task that emits usage metrics every 60 seconds and
+/// persists per-tool analytics and daily usage when a tool usage directory
+/// is configured.
 ///
 /// Emits a final `shutdown` summary when `shutdown_rx` signals `true`.
 pub fn start_periodic_usage_flush(
@@ -634,10 +1058,14 @@ pub fn start_periodic_usage_flush(
             tokio::select! {
                 _ = interval.tick() => {
                     metrics.emit_usage("periodic");
+                    metrics.flush_tool_usage();
+                    metrics.flush_daily_usage();
                 }
                 _ = shutdown_rx.changed() => {
                     if *shutdown_rx.borrow() {
                         metrics.emit_usage("shutdown");
+                        metrics.flush_tool_usage();
+                        metrics.flush_daily_usage();
                         break;
                     }
                 }
@@ -1237,6 +1665,21 @@ mod tests {
         assert!(!json.contains("\"usage\""));
     }
 
+    #[tokio::test]
+    async fn test_registry_with_message_bus_reports_queue_depth() {
+        let reg = HealthRegistry::new();
+        assert!(!reg.render_health_json().contains("\"queue\""));
+
+        let bus = MessageBus::new();
+        let msg = crate::bus::InboundMessage::new("telegram", "user1", "chat1", "hi");
+        bus.publish_inbound(msg).await.unwrap();
+        reg.set_message_bus(bus);
+
+        let json = reg.render_health_json();
+        assert!(json.contains("\"queue\":{\"depth\":1"));
+        assert!(json.contains("\"unsorted\":1"));
+    }
+
     #[test]
     fn test_render_health_json_has_version() {
         let reg = HealthRegistry::new();
@@ -1274,6 +1717,81 @@ mod tests {
         assert!(json.contains("\"last_error\":\"timeout\""));
     }
 
+    // --- Tool usage analytics tests ---
+
+    #[test]
+    fn test_tool_usage_stats_record() {
+        let mut stats = ToolUsageStats::default();
+        for latency in [10, 20, 30, 40] {
+            stats.record(latency, true, 50);
+        }
+        stats.record(100, false, 2000);
+        assert_eq!(stats.calls, 5);
+        assert_eq!(stats.errors, 1);
+        assert!((stats.error_rate() - 0.2).abs() < f64::EPSILON);
+        assert_eq!(stats.latency_percentile(50.0), Some(30));
+        assert_eq!(stats.latency_percentile(99.0), Some(100));
+        assert_eq!(stats.arg_size_buckets, [4, 0, 1, 0, 0]);
+        assert_eq!(stats.max_arg_bytes, 2000);
+        assert_eq!(stats.avg_arg_bytes(), 440);
+    }
+
+    #[test]
+    fn test_tool_usage_flush_merges_daily_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let metrics = UsageMetrics::new();
+        metrics.record_tool_usage("shell", 12, true, 30);
+        // Without a directory, flush keeps counters in memory.
+        metrics.flush_tool_usage();
+        assert_eq!(metrics.tool_usage()["shell"].calls, 1);
+
+        metrics.set_tool_usage_dir(tmp.path().to_path_buf());
+        metrics.flush_tool_usage();
+        assert!(metrics.tool_usage().is_empty());
+
+        metrics.record_tool_usage("shell", 8, false, 30);
+        metrics.flush_tool_usage();
+
+        let today = Local::now().date_naive();
+        let day = load_tool_usage(tmp.path(), today).unwrap();
+        assert_eq!(day["shell"].calls, 2);
+        assert_eq!(day["shell"].errors, 1);
+
+        let range = load_tool_usage_range(tmp.path(), today, 7).unwrap();
+        assert_eq!(range["shell"].calls, 2);
+    }
+
+    #[test]
+    fn test_daily_usage_flush_and_busiest_sessions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let metrics = UsageMetrics::new();
+        metrics.set_tool_usage_dir(tmp.path().to_path_buf());
+        metrics.record_request();
+        metrics.record_session_tokens("telegram:1", "claude-sonnet-4-5-20250929", 1000, 200);
+        metrics.record_session_tokens("cli:a", "unknown-model", 50, 10);
+        metrics.flush_daily_usage();
+        assert!(metrics.daily_usage().is_empty());
+        assert_eq!(metrics.input_tokens.load(Ordering::Relaxed), 1050);
+
+        metrics.record_request();
+        metrics.record_error();
+        metrics.record_session_tokens("cli:a", "unknown-model", 20, 5);
+        metrics.flush_daily_usage();
+
+        let today = Local::now().date_naive();
+        let usage = load_daily_usage_range(tmp.path(), today, 7).unwrap();
+        assert_eq!(usage.requests, 2);
+        assert_eq!(usage.errors, 1);
+        assert_eq!(usage.total_tokens(), 1285);
+        assert_eq!(usage.sessions["cli:a"].llm_calls, 2);
+        assert_eq!(usage.sessions["cli:a"].cost_usd, 0.0);
+        assert!(usage.cost_usd > 0.0);
+
+        let busiest = usage.busiest_sessions(1);
+        assert_eq!(busiest.len(), 1);
+        assert_eq!(busiest[0].0, "telegram:1");
+    }
+
     // --- get_rss_bytes tests ---
 
     #[test]
@@ -0,0 +1,328 @@
+//! Checkbox task tracking for HEARTBEAT.md.
+//!
+//! Checklist items (`- [ ] task`) are one-off tasks: the agent ticks them
+//! (`- [x] task`) when done, and the heartbeat service then stamps them with
+//! the completion time. On the first sync of a new day, items completed on
+//! earlier days are moved out of the checklist into a dated report under
+//! [`REPORTS_HEADING`], which also lists the still-open items that rolled
+//! over. Plain bullets without a checkbox stay standing instructions.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+/// Heading of the section that holds daily reports. Everything below it is
+/// ignored when looking for tasks.
+pub const REPORTS_HEADING: &str = "## Heartbeat Reports";
+
+/// Separator between a completed task and its completion stamp.
+const DONE_MARKER: &str = " — done ";
+
+/// Timestamp format used in completion stamps.
+const STAMP_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// A checkbox task parsed from the heartbeat file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartbeatTask {
+    /// Zero-based line index in the file.
+    pub line: usize,
+    /// Task text, without checkbox or completion stamp.
+    pub text: String,
+    /// Whether the checkbox is ticked.
+    pub done: bool,
+    /// When the service recorded the completion.
+    pub completed_at: Option<NaiveDateTime>,
+}
+
+/// Split a checklist line into (prefix up to and including the checkbox,
+/// done, rest of the line).
+fn split_checkbox(line: &str) -> Option<(&str, bool, &str)> {
+    let indent = line.len() - line.trim_start().len();
+    let body = &line[indent..];
+    if !(body.starts_with("- [") || body.starts_with("* [")) {
+        return None;
+    }
+    let done = match body.as_bytes().get(3) {
+        Some(b' ') => false,
+        Some(b'x') | Some(b'X') => true,
+        _ => return None,
+    };
+    let rest = body.get(4..)?.strip_prefix(']')?;
+    let prefix_len = indent + 5;
+    Some((&line[..prefix_len], done, rest.trim()))
+}
+
+/// Lines above the reports section that are not inside an HTML comment,
+/// with their line index.
+pub(crate) fn task_lines(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut in_comment = false;
+    content
+        .lines()
+        .enumerate()
+        .take_while(|(_, line)| line.trim() != REPORTS_HEADING)
+        .filter(move |(_, line)| {
+            let line = line.trim();
+            if in_comment {
+                in_comment = !line.contains("-->");
+                return false;
+            }
+            if line.starts_with("<!--") {
+                in_comment = !line.contains("-->");
+                return false;
+            }
+            true
+        })
+}
+
+/// Parse checkbox tasks above the reports section, outside HTML comments.
+///
+/// Empty checkboxes (the template's `- [ ]` placeholder) are skipped.
+pub fn parse_tasks(content: &str) -> Vec<HeartbeatTask> {
+    task_lines(content)
+        .filter_map(|(idx, line)| {
+            let (_, done, rest) = split_checkbox(line)?;
+            if rest.is_empty() {
+                return None;
+            }
+            let (text, completed_at) = match rest.rsplit_once(DONE_MARKER) {
+                Some((text, stamp)) => match NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT) {
+                    Ok(at) => (text.trim_end(), Some(at)),
+                    Err(_) => (rest, None),
+                },
+                None => (rest, None),
+            };
+            Some(HeartbeatTask {
+                line: idx,
+                text: text.to_string(),
+                done,
+                completed_at: completed_at.filter(|_| done),
+            })
+        })
+        .collect()
+}
+
+/// Whether `line` is a ticked task that already has its completion stamp.
+pub(crate) fn is_stamped(line: &str) -> bool {
+    split_checkbox(line).is_some_and(|(_, done, rest)| {
+        done && rest
+            .rsplit_once(DONE_MARKER)
+            .is_some_and(|(_, stamp)| NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT).is_ok())
+    })
+}
+
+/// Number of unticked tasks.
+pub fn pending_count(content: &str) -> usize {
+    parse_tasks(content).iter().filter(|t| !t.done).count()
+}
+
+/// Render one daily report.
+fn render_report(date: NaiveDate, completed: &[&HeartbeatTask], carried: &[&str]) -> String {
+    let mut report = format!("### {}\n\n", date.format("%Y-%m-%d"));
+    report.push_str(&format!("Completed ({}):\n", completed.len()));
+    for task in completed {
+        let time = task
+            .completed_at
+            .map(|at| at.format("%H:%M").to_string())
+            .unwrap_or_default();
+        report.push_str(&format!("- {} ({})\n", task.text, time));
+    }
+    if !carried.is_empty() {
+        report.push_str(&format!("\nCarried over ({}):\n", carried.len()));
+        for text in carried {
+            report.push_str(&format!("- {}\n", text));
+        }
+    }
+    report
+}
+
+/// Bring the checklist up to date as of `now` (local time).
+///
+/// Stamps newly ticked tasks, and moves tasks completed before today into
+/// daily reports. Returns the new content, or `None` if nothing changed.
+pub fn update_checklist(content: &str, now: NaiveDateTime) -> Option<String> {
+    let tasks = parse_tasks(content);
+    let today = now.date();
+    let mut lines: Vec<Option<String>> = content.lines().map(|l| Some(l.to_string())).collect();
+    let mut changed = false;
+
+    // Stamp ticked tasks that have no completion time yet.
+    let mut stamped = Vec::with_capacity(tasks.len());
+    for mut task in tasks {
+        if task.done && task.completed_at.is_none() {
+            let line = lines[task.line].as_deref().unwrap_or_default();
+            if let Some((prefix, _, _)) = split_checkbox(line) {
+                let line = format!(
+                    "{} {}{}{}",
+                    prefix,
+                    task.text,
+                    DONE_MARKER,
+                    now.format(STAMP_FORMAT)
+                );
+                lines[task.line] = Some(line);
+                task.completed_at = Some(now);
+                changed = true;
+            }
+        }
+        stamped.push(task);
+    }
+
+    // Roll over: report and remove tasks completed on earlier days.
+    let mut by_day: BTreeMap<NaiveDate, Vec<&HeartbeatTask>> = BTreeMap::new();
+    for task in &stamped {
+        if let Some(at) = task.completed_at {
+            if at.date() < today {
+                by_day.entry(at.date()).or_default().push(task);
+            }
+        }
+    }
+    let mut reports = Vec::new();
+    if !by_day.is_empty() {
+        let carried: Vec<&str> = stamped
+            .iter()
+            .filter(|t| !t.done)
+            .map(|t| t.text.as_str())
+            .collect();
+        let last_day = by_day.keys().next_back().copied();
+        // Newest report first.
+        for (date, completed) in by_day.iter().rev() {
+            let carried: &[&str] = if Some(*date) == last_day {
+                &carried
+            } else {
+                &[]
+            };
+            reports.push(render_report(*date, completed, carried));
+            for task in completed {
+                lines[task.line] = None;
+            }
+        }
+        changed = true;
+    }
+
+    if !changed {
+        return None;
+    }
+
+    let mut out: Vec<String> = lines.into_iter().flatten().collect();
+    if !reports.is_empty() {
+        let heading = match out.iter().position(|l| l.trim() == REPORTS_HEADING) {
+            Some(idx) => idx,
+            None => {
+                while out.last().is_some_and(|l| l.trim().is_empty()) {
+                    out.pop();
+                }
+                out.push(String::new());
+                out.push(REPORTS_HEADING.to_string());
+                out.len() - 1
+            }
+        };
+        // Newest report first, directly below the heading.
+        let at = heading + 1;
+        while out.get(at).is_some_and(|l| l.trim().is_empty()) {
+            out.remove(at);
+        }
+        let mut insert = vec![String::new()];
+        insert.extend(reports.join("\n").lines().map(str::to_string));
+        if at < out.len() {
+            insert.push(String::new());
+        }
+        out.splice(at..at, insert);
+    }
+
+    let mut updated = out.join("\n");
+    if content.ends_with('\n') || !reports.is_empty() {
+        updated.push('\n');
+    }
+    Some(updated)
+}
+
+/// Read the heartbeat file at `path`, update its checklist as of the
+/// current local time, and write it back if anything changed.
+///
+/// Returns the up-to-date content.
+pub async fn sync_heartbeat_file(path: &Path) -> std::io::Result<String> {
+    let content = tokio::fs::read_to_string(path).await?;
+    match update_checklist(&content, chrono::Local::now().naive_local()) {
+        Some(updated) => {
+            tokio::fs::write(path, &updated).await?;
+            Ok(updated)
+        }
+        None => Ok(content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, STAMP_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn test_parse_tasks() {
+        let content = "# Tasks\n- [ ] Water plants\n* [x] Send invoice — done 2026-10-15 09:30\n- [ ]\n- Standing order\n  - [X] Nested\n";
+        let tasks = parse_tasks(content);
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].text, "Water plants");
+        assert!(!tasks[0].done);
+        assert_eq!(tasks[1].text, "Send invoice");
+        assert_eq!(tasks[1].completed_at, Some(at("2026-10-15 09:30")));
+        assert!(tasks[2].done);
+        assert_eq!(tasks[2].completed_at, None);
+        assert_eq!(pending_count(content), 1);
+    }
+
+    #[test]
+    fn test_tasks_under_reports_or_in_comments_are_ignored() {
+        let content = format!(
+            "- [ ] Open\n<!-- Example:\n- [ ] Sample\n-->\n{}\n### 2026-10-14\n- [ ] Old\n",
+            REPORTS_HEADING
+        );
+        let tasks = parse_tasks(&content);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].text, "Open");
+    }
+
+    #[test]
+    fn test_update_stamps_completed_tasks() {
+        let content = "## Active Tasks\n- [ ] Open\n- [x] Finished\n";
+        let updated = update_checklist(content, at("2026-10-16 08:05")).unwrap();
+        assert_eq!(
+            updated,
+            "## Active Tasks\n- [ ] Open\n- [x] Finished — done 2026-10-16 08:05\n"
+        );
+        // Already stamped: nothing to do on the same day.
+        assert!(update_checklist(&updated, at("2026-10-16 09:00")).is_none());
+    }
+
+    #[test]
+    fn test_update_rolls_over_to_daily_report() {
+        let content = "## Active Tasks\n- [ ] Open\n- [x] Finished — done 2026-10-15 17:40\n";
+        let updated = update_checklist(content, at("2026-10-16 07:00")).unwrap();
+        assert_eq!(
+            updated,
+            "## Active Tasks\n- [ ] Open\n\n## Heartbeat Reports\n\n### 2026-10-15\n\nCompleted (1):\n- Finished (17:40)\n\nCarried over (1):\n- Open\n"
+        );
+
+        // The next day's report goes above the previous one.
+        let next = updated.replace("- [ ] Open", "- [x] Open — done 2026-10-16 12:00");
+        let rolled = update_checklist(&next, at("2026-10-17 07:00")).unwrap();
+        let newer = rolled.find("### 2026-10-16").unwrap();
+        let older = rolled.find("### 2026-10-15").unwrap();
+        assert!(newer < older);
+        assert!(rolled.contains("- Open (12:00)\n\n### 2026-10-15"));
+        assert!(parse_tasks(&rolled).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sync_heartbeat_file_writes_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("HEARTBEAT.md");
+        tokio::fs::write(&path, "- [x] Done\n").await.unwrap();
+
+        let content = sync_heartbeat_file(&path).await.unwrap();
+        assert!(content.contains(DONE_MARKER));
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), content);
+    }
+}
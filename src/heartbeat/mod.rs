@@ -1,7 +1,11 @@
 //! Heartbeat service - periodic agent wake-up for background tasks.
 
+pub mod checklist;
+pub mod schedule;
 mod service;
 mod template;
 
-pub use service::{HeartbeatResult, HeartbeatService, HEARTBEAT_PROMPT};
+pub use checklist::{parse_tasks, sync_heartbeat_file, HeartbeatTask};
+pub use schedule::{AdaptiveInterval, QuietHours};
+pub use service::{HeartbeatResult, HeartbeatService, HEARTBEAT_PROMPT, HEARTBEAT_TICK_KEY};
 pub use template::{ensure_heartbeat_file, HEARTBEAT_TEMPLATE};
@@ -0,0 +1,223 @@
+//! Quiet hours and adaptive heartbeat intervals.
+
+use std::time::Duration;
+
+use chrono::NaiveTime;
+use tracing::warn;
+
+use crate::config::{AdaptiveHeartbeatConfig, QuietHoursConfig};
+
+/// Reply the agent gives when a heartbeat found nothing to do.
+pub const HEARTBEAT_OK: &str = "HEARTBEAT_OK";
+
+/// Never back off by more than 2^this.
+const MAX_BACKOFF_SHIFT: u32 = 16;
+
+/// A daily local-time window, possibly wrapping past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Parse "HH:MM" bounds. Returns `None` if either is invalid or they are
+    /// equal (an empty window).
+    pub fn parse(start: &str, end: &str) -> Option<Self> {
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?;
+        (start != end).then_some(Self { start, end })
+    }
+
+    /// Quiet hours from config, or `None` when disabled or invalid.
+    pub fn from_config(config: &QuietHoursConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let quiet = Self::parse(&config.start, &config.end);
+        if quiet.is_none() {
+            warn!(
+                start = %config.start,
+                end = %config.end,
+                "Ignoring invalid quiet hours (expected distinct HH:MM times)"
+            );
+        }
+        quiet
+    }
+
+    /// Whether `time` falls inside the window.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// Time left until the window ends, or `None` if `time` is outside it.
+    pub fn remaining(&self, time: NaiveTime) -> Option<Duration> {
+        if !self.contains(time) {
+            return None;
+        }
+        let left = self.end.signed_duration_since(time);
+        let left = if left <= chrono::Duration::zero() {
+            left + chrono::Duration::days(1)
+        } else {
+            left
+        };
+        left.to_std().ok()
+    }
+
+    /// Whether it is quiet hours now, in local time.
+    pub fn is_quiet_now(&self) -> bool {
+        self.contains(chrono::Local::now().time())
+    }
+
+    /// End of the window, formatted "HH:MM".
+    pub fn end_label(&self) -> String {
+        self.end.format("%H:%M").to_string()
+    }
+}
+
+impl std::fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}–{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+/// Whether a heartbeat reply means there was nothing to do.
+pub fn is_idle_reply(reply: &str) -> bool {
+    reply.contains(HEARTBEAT_OK)
+}
+
+/// Heartbeat interval that adapts to results.
+#[derive(Debug, Clone)]
+pub struct AdaptiveInterval {
+    base: Duration,
+    min: Duration,
+    max: Duration,
+    enabled: bool,
+    idle_streak: u32,
+}
+
+impl AdaptiveInterval {
+    /// Interval around `base`, adapting only if `config.enabled`.
+    pub fn new(base: Duration, config: &AdaptiveHeartbeatConfig) -> Self {
+        let min = Duration::from_secs(config.min_interval_secs.max(30)).min(base);
+        let max = Duration::from_secs(config.max_interval_secs).max(base);
+        Self {
+            base,
+            min,
+            max,
+            enabled: config.enabled,
+            idle_streak: 0,
+        }
+    }
+
+    /// Record whether the last heartbeat had nothing to do.
+    pub fn record(&mut self, idle: bool) {
+        self.idle_streak = if idle {
+            self.idle_streak.saturating_add(1)
+        } else {
+            0
+        };
+    }
+
+    /// Consecutive heartbeats with nothing to do.
+    pub fn idle_streak(&self) -> u32 {
+        self.idle_streak
+    }
+
+    /// Delay before the next heartbeat.
+    pub fn next(&self, pending_tasks: usize) -> Duration {
+        if !self.enabled {
+            return self.base;
+        }
+        if pending_tasks > 0 {
+            return (self.base / 2).max(self.min);
+        }
+        let factor = 1u32 << self.idle_streak.min(MAX_BACKOFF_SHIFT);
+        self.base.saturating_mul(factor).min(self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_quiet_hours_wrapping_midnight() {
+        let quiet = QuietHours::parse("22:00", "07:00").unwrap();
+        assert!(quiet.contains(t("22:00")));
+        assert!(quiet.contains(t("03:15")));
+        assert!(!quiet.contains(t("07:00")));
+        assert!(!quiet.contains(t("12:00")));
+        assert_eq!(
+            quiet.remaining(t("23:00")),
+            Some(Duration::from_secs(8 * 3600))
+        );
+        assert_eq!(quiet.remaining(t("06:30")), Some(Duration::from_secs(1800)));
+        assert_eq!(quiet.remaining(t("12:00")), None);
+        assert_eq!(quiet.to_string(), "22:00–07:00");
+    }
+
+    #[test]
+    fn test_quiet_hours_same_day_and_invalid() {
+        let quiet = QuietHours::parse("13:00", "14:00").unwrap();
+        assert!(quiet.contains(t("13:30")));
+        assert!(!quiet.contains(t("22:00")));
+
+        assert!(QuietHours::parse("25:00", "07:00").is_none());
+        assert!(QuietHours::parse("07:00", "07:00").is_none());
+        assert!(QuietHours::from_config(&QuietHoursConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_adaptive_interval_backs_off_and_tightens() {
+        let config = AdaptiveHeartbeatConfig {
+            enabled: true,
+            min_interval_secs: 300,
+            max_interval_secs: 4 * 3600,
+        };
+        let base = Duration::from_secs(1800);
+        let mut interval = AdaptiveInterval::new(base, &config);
+        assert_eq!(interval.next(0), base);
+
+        interval.record(true);
+        assert_eq!(interval.next(0), base * 2);
+        for _ in 0..10 {
+            interval.record(true);
+        }
+        assert_eq!(interval.next(0), Duration::from_secs(4 * 3600));
+        assert_eq!(interval.next(3), Duration::from_secs(900));
+
+        interval.record(false);
+        assert_eq!(interval.idle_streak(), 0);
+        assert_eq!(interval.next(0), base);
+    }
+
+    #[test]
+    fn test_adaptive_interval_disabled_keeps_base() {
+        let base = Duration::from_secs(600);
+        let mut interval = AdaptiveInterval::new(base, &AdaptiveHeartbeatConfig::default());
+        interval.record(true);
+        assert_eq!(interval.next(0), base);
+        assert_eq!(interval.next(5), base);
+    }
+
+    #[test]
+    fn test_is_idle_reply() {
+        assert!(is_idle_reply("HEARTBEAT_OK"));
+        assert!(is_idle_reply("  HEARTBEAT_OK\n"));
+        assert!(!is_idle_reply("Sent 3 order confirmations."));
+    }
+}
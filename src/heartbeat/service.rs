@@ -3,18 +3,29 @@
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::RecvError};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
-use crate::bus::{InboundMessage, MessageBus};
+use crate::bus::{InboundMessage, LifecycleEvent, LifecycleKind, MessageBus};
+use crate::config::AdaptiveHeartbeatConfig;
 use crate::error::Result;
 
+use super::checklist::{is_stamped, pending_count, sync_heartbeat_file, task_lines};
+use super::schedule::{is_idle_reply, AdaptiveInterval, QuietHours};
+
+/// Inbound metadata key tagging a heartbeat prompt with its tick timestamp,
+/// so the service can match the agent's reply to it.
+pub const HEARTBEAT_TICK_KEY: &str = "heartbeat_tick";
+
 /// Prompt sent to the agent when heartbeat is triggered.
 pub const HEARTBEAT_PROMPT: &str = r#"Read HEARTBEAT.md in your workspace (if it exists).
 Follow any actionable items listed there.
+When you finish a checklist item (`- [ ] task`), tick it (`- [x] task`) in HEARTBEAT.md;
+leave unfinished items unticked. Do not edit completion stamps or the reports section.
 If nothing needs attention, reply with: HEARTBEAT_OK"#;
 
 /// Structured result from a heartbeat tick.
@@ -26,6 +37,9 @@ pub struct HeartbeatResult {
     pub file_found: bool,
     /// Whether actionable content was present.
     pub actionable: bool,
+    /// Number of unticked checklist tasks.
+    #[serde(default)]
+    pub pending_tasks: usize,
     /// Whether the message was successfully published.
     pub delivered: bool,
     /// Error message if the tick failed.
@@ -46,6 +60,7 @@ impl HeartbeatResult {
             timestamp: Self::now(),
             file_found,
             actionable,
+            pending_tasks: 0,
             delivered,
             error: None,
         }
@@ -57,6 +72,7 @@ impl HeartbeatResult {
             timestamp: Self::now(),
             file_found: false,
             actionable: false,
+            pending_tasks: 0,
             delivered: false,
             error: Some(msg.to_string()),
         }
@@ -75,6 +91,10 @@ pub struct HeartbeatService {
     pub(crate) consecutive_failures: Arc<AtomicU32>,
     /// Threshold before warning about missed heartbeats.
     failure_alert_threshold: u32,
+    /// Window in which ticks are skipped.
+    quiet_hours: Option<QuietHours>,
+    /// Adaptive interval settings.
+    adaptive: AdaptiveHeartbeatConfig,
 }
 
 impl HeartbeatService {
@@ -95,9 +115,23 @@ impl HeartbeatService {
             channel: channel.to_string(),
             consecutive_failures: Arc::new(AtomicU32::new(0)),
             failure_alert_threshold: 3,
+            quiet_hours: None,
+            adaptive: AdaptiveHeartbeatConfig::default(),
         }
     }
 
+    /// Skip ticks during `quiet_hours`.
+    pub fn with_quiet_hours(mut self, quiet_hours: Option<QuietHours>) -> Self {
+        self.quiet_hours = quiet_hours;
+        self
+    }
+
+    /// Adapt the interval to heartbeat results.
+    pub fn with_adaptive(mut self, adaptive: AdaptiveHeartbeatConfig) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
     /// Start heartbeat loop in the background.
     pub async fn start(&self) -> Result<()> {
         {
@@ -117,26 +151,43 @@ impl HeartbeatService {
         let channel = self.channel.clone();
         let consecutive_failures = Arc::clone(&self.consecutive_failures);
         let failure_threshold = self.failure_alert_threshold;
+        let quiet_hours = self.quiet_hours;
+        let mut schedule = AdaptiveInterval::new(interval_duration, &self.adaptive);
+        // Replies only matter when the interval adapts to them.
+        let mut replies = self.adaptive.enabled.then(|| bus.subscribe_lifecycle());
 
         info!(
-            "Heartbeat service started (interval={}s, file={:?})",
+            "Heartbeat service started (interval={}s, file={:?}, adaptive={}, quiet_hours={})",
             interval_duration.as_secs(),
-            file_path
+            file_path,
+            self.adaptive.enabled,
+            quiet_hours.map_or_else(|| "off".to_string(), |q| q.to_string())
         );
 
         let running_clone = Arc::clone(&running);
         tokio::spawn(async move {
-            let mut ticker = tokio::time::interval(interval_duration);
-            ticker.tick().await;
+            let mut delay = interval_duration;
 
             loop {
-                ticker.tick().await;
+                tokio::time::sleep(delay).await;
 
                 if !*running.read().await {
                     info!("Heartbeat service stopped");
                     break;
                 }
 
+                if let Some(left) =
+                    quiet_hours.and_then(|q| q.remaining(chrono::Local::now().time()))
+                {
+                    debug!(
+                        resume_in_secs = left.as_secs(),
+                        "Heartbeat skipped during quiet hours"
+                    );
+                    delay = left;
+                    continue;
+                }
+
+                let started = Instant::now();
                 let result = Self::tick(&file_path, &bus, &channel, &chat_id).await;
 
                 if result.error.is_some() {
@@ -149,7 +200,25 @@ impl HeartbeatService {
                     }
                 } else {
                     consecutive_failures.store(0, Ordering::Relaxed);
+                    if !result.actionable {
+                        schedule.record(true);
+                    } else if let Some(replies) = replies.as_mut() {
+                        let tick = result.timestamp.to_string();
+                        let limit = schedule.next(result.pending_tasks);
+                        if let Some(reply) = Self::await_reply(replies, &tick, limit).await {
+                            schedule.record(is_idle_reply(&reply));
+                        }
+                    }
                 }
+
+                let next = schedule.next(result.pending_tasks);
+                debug!(
+                    next_secs = next.as_secs(),
+                    idle_streak = schedule.idle_streak(),
+                    pending_tasks = result.pending_tasks,
+                    "Heartbeat scheduled"
+                );
+                delay = next.saturating_sub(started.elapsed());
             }
             let mut r = running_clone.write().await;
             *r = false;
@@ -158,6 +227,30 @@ impl HeartbeatService {
         Ok(())
     }
 
+    /// Wait up to `limit` for the agent's reply to heartbeat `tick`.
+    async fn await_reply(
+        replies: &mut broadcast::Receiver<LifecycleEvent>,
+        tick: &str,
+        limit: Duration,
+    ) -> Option<String> {
+        let wait = async {
+            loop {
+                match replies.recv().await {
+                    Ok(event)
+                        if event.kind == LifecycleKind::Finished
+                            && event.metadata.get(HEARTBEAT_TICK_KEY).map(String::as_str)
+                                == Some(tick) =>
+                    {
+                        return event.reply;
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        };
+        tokio::time::timeout(limit, wait).await.ok().flatten()
+    }
+
     /// Stop heartbeat loop.
     pub async fn stop(&self) {
         let mut running = self.running.write().await;
@@ -184,14 +277,15 @@ impl HeartbeatService {
         self.consecutive_failures() < self.failure_alert_threshold
     }
 
-    /// Whether heartbeat content is actionable.
+    /// Whether heartbeat content is actionable. Comments, stamped completed
+    /// tasks and the reports section are ignored.
     pub fn is_empty(content: &str) -> bool {
-        for raw in content.lines() {
+        for (_, raw) in task_lines(content) {
             let line = raw.trim();
-            if line.is_empty() || line.starts_with('#') || line.starts_with("<!--") {
+            if line.is_empty() || line.starts_with('#') {
                 continue;
             }
-            if line == "- [ ]" || line == "* [ ]" {
+            if line == "- [ ]" || line == "* [ ]" || is_stamped(line) {
                 continue;
             }
             return false;
@@ -205,15 +299,15 @@ impl HeartbeatService {
         channel: &str,
         chat_id: &str,
     ) -> HeartbeatResult {
-        let content = match tokio::fs::read_to_string(file_path).await {
+        let content = match sync_heartbeat_file(file_path).await {
             Ok(content) => content,
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
                 debug!("Heartbeat file missing at {:?}, skipping tick", file_path);
                 return HeartbeatResult::ok(false, false, false);
             }
             Err(e) => {
-                warn!("Failed to read heartbeat file {:?}: {}", file_path, e);
-                return HeartbeatResult::err(&format!("Failed to read file: {e}"));
+                warn!("Failed to sync heartbeat file {:?}: {}", file_path, e);
+                return HeartbeatResult::err(&format!("Failed to sync file: {e}"));
             }
         };
 
@@ -222,11 +316,18 @@ impl HeartbeatService {
             return HeartbeatResult::ok(true, false, false);
         }
 
-        let message = InboundMessage::new(channel, "system", chat_id, HEARTBEAT_PROMPT);
+        let pending_tasks = pending_count(&content);
+        let result = HeartbeatResult::ok(true, true, true);
+        let message = InboundMessage::new(channel, "system", chat_id, HEARTBEAT_PROMPT)
+            .mark_internal()
+            .with_metadata(HEARTBEAT_TICK_KEY, &result.timestamp.to_string());
         match bus.publish_inbound(message).await {
             Ok(_) => {
-                info!("Heartbeat delivered to bus");
-                HeartbeatResult::ok(true, true, true)
+                info!(pending_tasks, "Heartbeat delivered to bus");
+                HeartbeatResult {
+                    pending_tasks,
+                    ..result
+                }
             }
             Err(e) => {
                 error!("Failed to publish heartbeat: {}", e);
@@ -245,6 +346,9 @@ mod tests {
         assert!(HeartbeatService::is_empty(""));
         assert!(HeartbeatService::is_empty("# Header\n## Tasks"));
         assert!(HeartbeatService::is_empty("<!-- comment -->\n\n- [ ]"));
+        assert!(HeartbeatService::is_empty(
+            "- [x] Done — done 2026-10-16 09:00"
+        ));
     }
 
     #[test]
@@ -272,6 +376,15 @@ mod tests {
         assert_eq!(result.error, Some("test error".to_string()));
     }
 
+    #[test]
+    fn test_is_empty_ignores_reports() {
+        let content = format!(
+            "# Tasks\n- [ ]\n\n{}\n\n### 2026-10-15\n\nCompleted (1):\n- Done (09:00)\n",
+            crate::heartbeat::checklist::REPORTS_HEADING
+        );
+        assert!(HeartbeatService::is_empty(&content));
+    }
+
     #[tokio::test]
     async fn test_heartbeat_tick_missing_file() {
         let bus = Arc::new(MessageBus::new());
@@ -317,6 +430,54 @@ mod tests {
         assert!(result.delivered);
     }
 
+    #[tokio::test]
+    async fn test_heartbeat_reply_matched_by_tick() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("HEARTBEAT.md");
+        tokio::fs::write(&file, "- Check orders\n").await.unwrap();
+
+        let bus = Arc::new(MessageBus::new());
+        let mut replies = bus.subscribe_lifecycle();
+        let result = HeartbeatService::tick(&file, &bus, "heartbeat", "test-chat").await;
+        let inbound = bus.consume_inbound().await.unwrap();
+        let tick = result.timestamp.to_string();
+        assert_eq!(inbound.metadata.get(HEARTBEAT_TICK_KEY), Some(&tick));
+
+        let other = InboundMessage::new("telegram", "user", "chat", "hi");
+        bus.publish_lifecycle(
+            LifecycleEvent::new(LifecycleKind::Finished, &other).with_reply("Hello!"),
+        );
+        bus.publish_lifecycle(
+            LifecycleEvent::new(LifecycleKind::Finished, &inbound).with_reply("HEARTBEAT_OK"),
+        );
+        let reply =
+            HeartbeatService::await_reply(&mut replies, &tick, Duration::from_secs(1)).await;
+        assert_eq!(reply.as_deref(), Some("HEARTBEAT_OK"));
+
+        let none =
+            HeartbeatService::await_reply(&mut replies, &tick, Duration::from_millis(10)).await;
+        assert!(none.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_tick_tracks_checklist() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("HEARTBEAT.md");
+        tokio::fs::write(
+            &file,
+            "- [ ] Call supplier\n- [x] Send invoice\n- [ ] Restock\n",
+        )
+        .await
+        .unwrap();
+
+        let bus = Arc::new(MessageBus::new());
+        let result = HeartbeatService::tick(&file, &bus, "heartbeat", "test-chat").await;
+        assert!(result.delivered);
+        assert_eq!(result.pending_tasks, 2);
+        let content = tokio::fs::read_to_string(&file).await.unwrap();
+        assert!(content.contains("- [x] Send invoice — done "));
+    }
+
     #[test]
     fn test_heartbeat_health_tracking() {
         let bus = Arc::new(MessageBus::new());
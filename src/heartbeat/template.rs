@@ -11,15 +11,15 @@ Add background tasks below. Keep tasks actionable and specific.
 ## Active Tasks
 
 <!-- Add your periodic tasks below this line -->
-<!-- Examples:
+<!-- Plain bullets run on every heartbeat:
 - Check Google Sheet for new orders and send WhatsApp confirmations
 - Alert me if any inventory item is below minimum stock
-- Send shipping reminder for orders older than 24 hours
 -->
-
-## Completed
-
-<!-- Move completed tasks here or delete them -->
+<!-- Checkbox items are one-off tasks. The agent ticks them when done,
+     the heartbeat stamps the completion time, and the next day they move
+     into a daily report below; open items roll over:
+- [ ] Send shipping reminder for orders older than 24 hours
+-->
 "#;
 
 /// Ensure heartbeat file exists, creating it with default template if needed.
@@ -0,0 +1,262 @@
+//! External hook actions: `exec` runs a command, `webhook` POSTs the event.
+//!
+//! Both receive the same JSON [`HookEvent`]. A command reads it on stdin and
+//! also gets `ZEPTOCLAW_HOOK`, `ZEPTOCLAW_HOOK_TOOL`, `ZEPTOCLAW_HOOK_CHANNEL`
+//! and `ZEPTOCLAW_HOOK_CHAT_ID` in its environment. Like a git hook, a
+//! non-zero exit in `before_tool` blocks the call, with the command's output
+//! as the reason. In `before_llm` and `after_response` an `exec` command is a
+//! filter: what it prints replaces the messages or the reply.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+
+use crate::config::NativeShell;
+
+use super::{HookAction, HookRule};
+
+/// Time limit for `exec` and `webhook` actions without `timeout_secs`.
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 10;
+
+/// Longest block reason taken from a command's output.
+const MAX_REASON_CHARS: usize = 500;
+
+/// Event passed to `exec` and `webhook` actions.
+#[derive(Debug, Clone, Serialize)]
+pub struct HookEvent {
+    /// Hook point: `before_tool`, `after_tool` or `on_error`.
+    pub hook: &'static str,
+    /// Empty in `before_llm` and `after_response`.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub tool: String,
+    pub channel: String,
+    pub chat_id: String,
+    /// Tool arguments.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<serde_json::Value>,
+    /// Tool result (`after_tool`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// Error text (`on_error`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Messages about to be sent to the model (`before_llm`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<serde_json::Value>,
+    /// Final reply text (`after_response`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_ms: Option<u64>,
+}
+
+impl HookEvent {
+    pub fn new(hook: &'static str, tool: &str, channel: &str, chat_id: &str) -> Self {
+        Self {
+            hook,
+            tool: tool.to_string(),
+            channel: channel.to_string(),
+            chat_id: chat_id.to_string(),
+            args: None,
+            result: None,
+            error: None,
+            messages: None,
+            response: None,
+            elapsed_ms: None,
+        }
+    }
+}
+
+/// What an external action decided.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalOutcome {
+    /// Ran and let the call through.
+    Allowed,
+    /// The command exited non-zero; carries its output.
+    Denied(String),
+    /// Could not run, timed out, or the webhook did not answer 2xx.
+    Failed(String),
+}
+
+/// Run the `exec` or `webhook` action of `rule` for `event`.
+pub async fn run(rule: &HookRule, event: &HookEvent, http: &reqwest::Client) -> ExternalOutcome {
+    let timeout = Duration::from_secs(
+        rule.timeout_secs
+            .unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS)
+            .max(1),
+    );
+    match (&rule.action, &rule.command, &rule.url) {
+        (HookAction::Exec, Some(command), _) => match run_exec(command, event, timeout).await {
+            Err(error) => ExternalOutcome::Failed(error),
+            Ok(output) if output.status.success() => ExternalOutcome::Allowed,
+            Ok(output) => ExternalOutcome::Denied(denial_reason(&output)),
+        },
+        (HookAction::Webhook, _, Some(url)) => {
+            match post_webhook(http, url, event, timeout).await {
+                Ok(()) => ExternalOutcome::Allowed,
+                Err(error) => ExternalOutcome::Failed(error),
+            }
+        }
+        (HookAction::Exec, None, _) => ExternalOutcome::Failed("exec hook has no command".into()),
+        (HookAction::Webhook, _, None) => ExternalOutcome::Failed("webhook hook has no url".into()),
+        _ => ExternalOutcome::Allowed,
+    }
+}
+
+/// Run the `exec` action of `rule` as a filter and return what it printed,
+/// or `None` when it printed nothing (keep the input as is).
+pub async fn filter(rule: &HookRule, event: &HookEvent) -> Result<Option<String>, String> {
+    let Some(command) = rule.command.as_deref() else {
+        return Err("exec hook has no command".into());
+    };
+    let timeout = Duration::from_secs(
+        rule.timeout_secs
+            .unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS)
+            .max(1),
+    );
+    let output = run_exec(command, event, timeout).await?;
+    if !output.status.success() {
+        return Err(format!(
+            "hook command exited with {}: {}",
+            output.status,
+            denial_reason(&output)
+        ));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok((!stdout.trim().is_empty()).then(|| stdout.trim_end_matches('\n').to_string()))
+}
+
+fn denial_reason(output: &std::process::Output) -> String {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let reason = if stdout.trim().is_empty() {
+        stderr.trim()
+    } else {
+        stdout.trim()
+    };
+    reason.chars().take(MAX_REASON_CHARS).collect()
+}
+
+async fn run_exec(
+    command: &str,
+    event: &HookEvent,
+    timeout: Duration,
+) -> Result<std::process::Output, String> {
+    let payload = serde_json::to_vec(event).unwrap_or_default();
+    let mut cmd = crate::runtime::shell_command(NativeShell::Auto, command);
+    cmd.env("ZEPTOCLAW_HOOK", event.hook)
+        .env("ZEPTOCLAW_HOOK_TOOL", &event.tool)
+        .env("ZEPTOCLAW_HOOK_CHANNEL", &event.channel)
+        .env("ZEPTOCLAW_HOOK_CHAT_ID", &event.chat_id)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => return Err(format!("failed to run hook command: {}", e)),
+    };
+
+    let stdin = child.stdin.take();
+    let write = async move {
+        if let Some(mut stdin) = stdin {
+            // Commands that ignore stdin may close it early; that is fine.
+            let _ = stdin.write_all(&payload).await;
+        }
+    };
+    let run = async {
+        let ((), output) = tokio::join!(write, child.wait_with_output());
+        output
+    };
+    match tokio::time::timeout(timeout, run).await {
+        Err(_) => Err(format!(
+            "hook command timed out after {}s",
+            timeout.as_secs()
+        )),
+        Ok(Err(e)) => Err(format!("hook command failed: {}", e)),
+        Ok(Ok(output)) => Ok(output),
+    }
+}
+
+async fn post_webhook(
+    http: &reqwest::Client,
+    url: &str,
+    event: &HookEvent,
+    timeout: Duration,
+) -> Result<(), String> {
+    match http.post(url).timeout(timeout).json(event).send().await {
+        Ok(response) if response.status().is_success() => Ok(()),
+        Ok(response) => Err(format!("webhook returned {}", response.status())),
+        Err(e) => Err(format!("webhook failed: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exec(command: &str) -> HookRule {
+        HookRule {
+            action: HookAction::Exec,
+            command: Some(command.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exec_receives_event_and_exit_code_decides() {
+        let mut event = HookEvent::new("before_tool", "shell", "telegram", "chat1");
+        event.args = Some(serde_json::json!({"command": "ls"}));
+        let http = reqwest::Client::new();
+
+        let reads_stdin = exec(r#"grep -q '"command":"ls"' && [ "$ZEPTOCLAW_HOOK_TOOL" = shell ]"#);
+        assert_eq!(
+            run(&reads_stdin, &event, &http).await,
+            ExternalOutcome::Allowed
+        );
+
+        let denies = exec("echo 'not during a freeze'; exit 1");
+        assert_eq!(
+            run(&denies, &event, &http).await,
+            ExternalOutcome::Denied("not during a freeze".into())
+        );
+
+        let slow = HookRule {
+            timeout_secs: Some(1),
+            ..exec("sleep 5")
+        };
+        assert!(matches!(
+            run(&slow, &event, &http).await,
+            ExternalOutcome::Failed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_filter_returns_command_output() {
+        let mut event = HookEvent::new("after_response", "", "cli", "cli");
+        event.response = Some("hello".to_string());
+
+        let exclaim = exec(r#"sed -n 's/.*"response":"\([^"]*\)".*/\1!/p'"#);
+        assert_eq!(
+            filter(&exclaim, &event).await,
+            Ok(Some("hello!".to_string()))
+        );
+        assert_eq!(filter(&exec("true"), &event).await, Ok(None));
+        assert!(filter(&exec("exit 3"), &event).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_without_url_fails() {
+        let rule = HookRule {
+            action: HookAction::Webhook,
+            ..Default::default()
+        };
+        let event = HookEvent::new("after_tool", "shell", "cli", "cli");
+        assert!(matches!(
+            run(&rule, &event, &reqwest::Client::new()).await,
+            ExternalOutcome::Failed(_)
+        ));
+    }
+}
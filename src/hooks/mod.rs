@@ -5,6 +5,26 @@
 //! - `before_tool` — before tool execution (can log or block)
 //! - `after_tool` — after tool execution (can log)
 //! - `on_error` — when a tool fails (can log)
+//! - `before_llm` — before each model call (can log or rewrite the messages)
+//! - `after_response` — on the final reply text (can log or rewrite it)
+//!
+//! Besides `log`, `block` and `notify`, a rule can `exec` a command or POST
+//! to a `webhook` (see [`external`]). In `before_tool` these run before the
+//! tool and a non-zero exit blocks it; elsewhere they run in the background.
+//! `timeout_secs` (default 10) bounds them and `on_failure` (`continue` or
+//! `block`) decides what a hook that could not run does to the tool call.
+//!
+//! `args` narrows a rule to calls whose arguments match: each entry names an
+//! argument (`command`, a dotted path like `options.path`, or a JSON pointer)
+//! and a `matches` and/or `not_matches` regex. All entries must hold, and a
+//! missing argument never matches.
+//!
+//! `before_llm` and `after_response` ignore `tools`; their `args` conditions
+//! test `content`, the latest user message or the reply. `redact` replaces
+//! `pattern` matches with `replacement` (default `[REDACTED]`), `append` adds
+//! `message` to the reply, and an `exec` command's output replaces the
+//! messages (a JSON array) or the reply. `notify` with a `channel` sends a
+//! copy of the reply elsewhere, e.g. an archive channel.
 //!
 //! # Configuration
 //!
@@ -14,13 +34,23 @@
 //!         "enabled": true,
 //!         "before_tool": [
 //!             { "action": "log", "tools": ["shell"], "level": "warn" },
-//!             { "action": "block", "tools": ["shell"], "channels": ["telegram"], "message": "Shell disabled on Telegram" }
+//!             { "action": "block", "tools": ["shell"], "channels": ["telegram"], "message": "Shell disabled on Telegram" },
+//!             { "action": "block", "tools": ["shell"], "args": [{ "path": "command", "matches": "rm|mkfs|curl.*\\|" }] },
+//!             { "action": "block", "tools": ["write_file"], "args": [{ "path": "path", "not_matches": "^(src|docs)/" }] }
 //!         ],
 //!         "after_tool": [
 //!             { "action": "log", "tools": ["*"], "level": "info" }
 //!         ],
 //!         "on_error": [
-//!             { "action": "log", "level": "error" }
+//!             { "action": "log", "level": "error" },
+//!             { "action": "webhook", "tools": ["*"], "url": "https://ops.example.com/zeptoclaw" }
+//!         ],
+//!         "before_llm": [
+//!             { "action": "redact", "pattern": "\\b\\d{3}-\\d{2}-\\d{4}\\b" }
+//!         ],
+//!         "after_response": [
+//!             { "action": "append", "channels": ["whatsapp"], "message": "_Automated reply._" },
+//!             { "action": "notify", "channel": "slack", "chat_id": "C0ARCHIVE" }
 //!         ]
 //!     }
 //! }
@@ -43,22 +73,34 @@
 //!     ..Default::default()
 //! };
 //! let engine = HookEngine::new(config);
-//! let result = engine.before_tool("shell", &serde_json::json!({}), "telegram", "chat-1");
+//! # tokio_test::block_on(async {
+//! let result = engine
+//!     .before_tool("shell", &serde_json::json!({}), "telegram", "chat-1")
+//!     .await;
 //! assert!(matches!(result, HookResult::Block(_)));
+//! # });
 //! ```
 
-use std::sync::Arc;
+pub mod external;
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
+use regex::Regex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::bus::{MessageBus, OutboundMessage};
+use crate::session::{ContentPart, Message, Role};
+
+use self::external::{ExternalOutcome, HookEvent};
 
 // ---------------------------------------------------------------------------
 // Hook action enum
 // ---------------------------------------------------------------------------
 
 /// What a hook rule does when triggered.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum HookAction {
     /// Log the event via tracing.
@@ -67,6 +109,67 @@ pub enum HookAction {
     Block,
     /// Send a notification message via the message bus.
     Notify,
+    /// Run `command` with the event as JSON on stdin; a non-zero exit
+    /// blocks the tool (before_tool only).
+    Exec,
+    /// POST the event as JSON to `url`.
+    Webhook,
+    /// Replace `pattern` matches in the messages or reply (before_llm and
+    /// after_response only).
+    Redact,
+    /// Append `message` to the reply (after_response only).
+    Append,
+}
+
+/// What a `before_tool` exec or webhook that fails to run (spawn error,
+/// timeout, non-2xx reply) does to the tool call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HookFailurePolicy {
+    /// Log the failure and let the tool run.
+    #[default]
+    Continue,
+    /// Block the tool.
+    Block,
+}
+
+// ---------------------------------------------------------------------------
+// Argument matcher
+// ---------------------------------------------------------------------------
+
+/// Condition on one tool argument.
+///
+/// Strings are tested as-is; other values are tested as their JSON text.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ArgMatcher {
+    /// Argument to test: a key (`command`), a dotted path (`options.path`,
+    /// `files.0`) or a JSON pointer (`/options/path`).
+    pub path: String,
+    /// Regex the value must match.
+    pub matches: Option<String>,
+    /// Regex the value must not match.
+    pub not_matches: Option<String>,
+}
+
+impl ArgMatcher {
+    /// Look up the argument this matcher tests.
+    pub fn value<'a>(&self, args: &'a serde_json::Value) -> Option<&'a serde_json::Value> {
+        let path = self.path.trim();
+        if path.starts_with('/') {
+            return args.pointer(path);
+        }
+        let path = path.strip_prefix('$').unwrap_or(path);
+        let path = path.strip_prefix('.').unwrap_or(path);
+        if path.is_empty() {
+            return Some(args);
+        }
+        path.split('.').try_fold(args, |value, key| match value {
+            serde_json::Value::Object(map) => map.get(key),
+            serde_json::Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -77,7 +180,7 @@ pub enum HookAction {
 ///
 /// Rules are evaluated in order. For `before_tool`, the first `Block` rule
 /// that matches wins. `Log` rules always execute (no short-circuit).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct HookRule {
     /// Action to perform.
@@ -86,6 +189,8 @@ pub struct HookRule {
     pub tools: Vec<String>,
     /// Channel names to match. Empty = match all channels.
     pub channels: Vec<String>,
+    /// Argument conditions; all must hold. Empty = any arguments.
+    pub args: Vec<ArgMatcher>,
     /// Log level for `Log` action (trace/debug/info/warn/error).
     pub level: Option<String>,
     /// Custom message for `Block` action.
@@ -96,6 +201,18 @@ pub struct HookRule {
     /// Optional target chat ID for `Notify` action.
     /// Falls back to current tool call chat_id when unset.
     pub chat_id: Option<String>,
+    /// Shell command for `Exec` action.
+    pub command: Option<String>,
+    /// Endpoint for `Webhook` action.
+    pub url: Option<String>,
+    /// Time limit (seconds) for `Exec` and `Webhook`. Default: 10.
+    pub timeout_secs: Option<u64>,
+    /// What a failing `Exec` or `Webhook` does in `before_tool`.
+    pub on_failure: HookFailurePolicy,
+    /// Regex for `Redact`.
+    pub pattern: Option<String>,
+    /// Replacement for `Redact` (`$1` refers to groups). Default: `[REDACTED]`.
+    pub replacement: Option<String>,
 }
 
 impl Default for HookRule {
@@ -104,10 +221,17 @@ impl Default for HookRule {
             action: HookAction::Log,
             tools: vec![],
             channels: vec![],
+            args: vec![],
             level: None,
             message: None,
             channel: None,
             chat_id: None,
+            command: None,
+            url: None,
+            timeout_secs: None,
+            on_failure: HookFailurePolicy::Continue,
+            pattern: None,
+            replacement: None,
         }
     }
 }
@@ -137,7 +261,7 @@ impl HookRule {
 ///
 /// - `enabled`: `false`
 /// - All rule lists: empty
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct HooksConfig {
     /// Master switch for hooks.
@@ -148,6 +272,10 @@ pub struct HooksConfig {
     pub after_tool: Vec<HookRule>,
     /// Rules evaluated when a tool returns an error.
     pub on_error: Vec<HookRule>,
+    /// Rules evaluated on the messages before each model call.
+    pub before_llm: Vec<HookRule>,
+    /// Rules evaluated on the final reply text.
+    pub after_response: Vec<HookRule>,
 }
 
 // ---------------------------------------------------------------------------
@@ -169,19 +297,61 @@ pub enum HookResult {
 
 /// Runtime hook engine that evaluates rules from HooksConfig.
 ///
-/// Created once per agent loop iteration and called at 3 points:
+/// Created once per agent loop and called at 5 points:
 /// 1. `before_tool` — before approval gate + tool execution
 /// 2. `after_tool` — after successful tool execution
 /// 3. `on_error` — after failed tool execution
+/// 4. `before_llm` — on the messages of every model call in a turn
+/// 5. `after_response` — on the final reply
 pub struct HookEngine {
     config: HooksConfig,
     bus: Option<Arc<MessageBus>>,
+    /// HTTP client for `webhook` actions, built on first use.
+    http: OnceLock<reqwest::Client>,
+    /// Compiled `args` and `pattern` regexes, keyed by pattern. Invalid
+    /// patterns are left out, so conditions using them never hold.
+    patterns: HashMap<String, Regex>,
 }
 
 impl HookEngine {
     /// Create a new HookEngine from configuration.
     pub fn new(config: HooksConfig) -> Self {
-        Self { config, bus: None }
+        let mut patterns = HashMap::new();
+        let rules = config
+            .before_tool
+            .iter()
+            .chain(&config.after_tool)
+            .chain(&config.on_error)
+            .chain(&config.before_llm)
+            .chain(&config.after_response);
+        let sources = rules.flat_map(|rule| {
+            rule.args
+                .iter()
+                .flat_map(|matcher| [&matcher.matches, &matcher.not_matches])
+                .chain([&rule.pattern])
+                .flatten()
+        });
+        for pattern in sources {
+            if patterns.contains_key(pattern) {
+                continue;
+            }
+            match Regex::new(pattern) {
+                Ok(regex) => {
+                    patterns.insert(pattern.clone(), regex);
+                }
+                Err(error) => tracing::warn!(
+                    pattern = %pattern,
+                    error = %error,
+                    "Invalid hook pattern; rule will not match"
+                ),
+            }
+        }
+        Self {
+            config,
+            bus: None,
+            http: OnceLock::new(),
+            patterns,
+        }
     }
 
     /// Attach a message bus for `notify` actions.
@@ -266,14 +436,99 @@ impl HookEngine {
         }
     }
 
+    /// Whether `rule` applies to this call: tool, channel and every `args`
+    /// condition.
+    fn rule_matches(
+        &self,
+        rule: &HookRule,
+        tool_name: &str,
+        channel: &str,
+        args: &serde_json::Value,
+    ) -> bool {
+        rule.matches_tool(tool_name) && rule.matches_channel(channel) && self.args_match(rule, args)
+    }
+
+    /// Whether `rule` applies at `before_llm`/`after_response`, where there
+    /// is no tool and `args` see `{"content": ...}`.
+    fn stage_matches(&self, rule: &HookRule, channel: &str, content: &str) -> bool {
+        rule.matches_channel(channel)
+            && (rule.args.is_empty()
+                || self.args_match(rule, &serde_json::json!({ "content": content })))
+    }
+
+    fn args_match(&self, rule: &HookRule, args: &serde_json::Value) -> bool {
+        rule.args.iter().all(|matcher| {
+            let Some(value) = matcher.value(args) else {
+                return false;
+            };
+            let text = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            let is_match = |pattern: &String| {
+                self.patterns
+                    .get(pattern)
+                    .map(|regex| regex.is_match(&text))
+            };
+            let matches_ok = matcher.matches.as_ref().map_or(Some(true), is_match);
+            let not_matches_ok = matcher
+                .not_matches
+                .as_ref()
+                .map_or(Some(true), |p| is_match(p).map(|m| !m));
+            matches_ok == Some(true) && not_matches_ok == Some(true)
+        })
+    }
+
+    /// Apply a `Redact` rule to `text`; `None` when nothing matched.
+    fn redact(&self, rule: &HookRule, text: &str) -> Option<String> {
+        let regex = self.patterns.get(rule.pattern.as_ref()?)?;
+        let replacement = rule.replacement.as_deref().unwrap_or("[REDACTED]");
+        match regex.replace_all(text, replacement) {
+            std::borrow::Cow::Borrowed(_) => None,
+            std::borrow::Cow::Owned(redacted) => Some(redacted),
+        }
+    }
+
+    fn http(&self) -> &reqwest::Client {
+        self.http.get_or_init(reqwest::Client::new)
+    }
+
+    /// Run an `exec`/`webhook` rule in the background (after_tool, on_error,
+    /// and webhooks in before_llm/after_response).
+    fn spawn_external(&self, rule: &HookRule, event: HookEvent) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::debug!(
+                hook = event.hook,
+                tool = %event.tool,
+                "Hook skipped: no async runtime"
+            );
+            return;
+        };
+        let rule = rule.clone();
+        let http = self.http().clone();
+        runtime.spawn(async move {
+            if let ExternalOutcome::Denied(error) | ExternalOutcome::Failed(error) =
+                external::run(&rule, &event, &http).await
+            {
+                tracing::warn!(
+                    hook = event.hook,
+                    tool = %event.tool,
+                    error = %error,
+                    "External hook failed"
+                );
+            }
+        });
+    }
+
     /// Evaluate before_tool hooks. Returns Block if any matching rule blocks.
     ///
     /// Rules are evaluated in order. `Log` rules execute without stopping.
-    /// The first `Block` rule that matches returns immediately.
-    pub fn before_tool(
+    /// The first `Block` rule that matches returns immediately, as does an
+    /// `Exec` whose command exits non-zero.
+    pub async fn before_tool(
         &self,
         tool_name: &str,
-        _args: &serde_json::Value,
+        args: &serde_json::Value,
         channel: &str,
         chat_id: &str,
     ) -> HookResult {
@@ -282,7 +537,7 @@ impl HookEngine {
         }
 
         for rule in &self.config.before_tool {
-            if !rule.matches_tool(tool_name) || !rule.matches_channel(channel) {
+            if !self.rule_matches(rule, tool_name, channel, args) {
                 continue;
             }
 
@@ -344,6 +599,41 @@ impl HookEngine {
                     });
                     self.emit_notify("before_tool", tool_name, rule, channel, chat_id, message);
                 }
+                HookAction::Redact | HookAction::Append => {} // Only for LLM stages
+                HookAction::Exec | HookAction::Webhook => {
+                    let mut event = HookEvent::new("before_tool", tool_name, channel, chat_id);
+                    event.args = Some(args.clone());
+                    match external::run(rule, &event, self.http()).await {
+                        ExternalOutcome::Allowed => {}
+                        ExternalOutcome::Denied(reason) => {
+                            tracing::info!(
+                                hook = "before_tool",
+                                tool = tool_name,
+                                channel = channel,
+                                "Hook: command blocked tool"
+                            );
+                            let msg = rule.message.clone().unwrap_or_else(|| {
+                                if reason.is_empty() {
+                                    "hook command exited with an error".to_string()
+                                } else {
+                                    reason
+                                }
+                            });
+                            return HookResult::Block(msg);
+                        }
+                        ExternalOutcome::Failed(error) => {
+                            tracing::warn!(
+                                hook = "before_tool",
+                                tool = tool_name,
+                                error = %error,
+                                "External hook failed"
+                            );
+                            if rule.on_failure == HookFailurePolicy::Block {
+                                return HookResult::Block(format!("hook failed ({})", error));
+                            }
+                        }
+                    }
+                }
             }
         }
 
@@ -354,7 +644,8 @@ impl HookEngine {
     pub fn after_tool(
         &self,
         tool_name: &str,
-        _result: &str,
+        args: &serde_json::Value,
+        result: &str,
         elapsed: std::time::Duration,
         channel: &str,
         chat_id: &str,
@@ -364,7 +655,7 @@ impl HookEngine {
         }
 
         for rule in &self.config.after_tool {
-            if !rule.matches_tool(tool_name) || !rule.matches_channel(channel) {
+            if !self.rule_matches(rule, tool_name, channel, args) {
                 continue;
             }
 
@@ -387,7 +678,8 @@ impl HookEngine {
                         }
                     }
                 }
-                HookAction::Block => {} // Block is a no-op in after_tool
+                // Block, Redact and Append are no-ops in after_tool
+                HookAction::Block | HookAction::Redact | HookAction::Append => {}
                 HookAction::Notify => {
                     let ms = elapsed.as_millis();
                     let message = rule.message.clone().unwrap_or_else(|| {
@@ -398,18 +690,32 @@ impl HookEngine {
                     });
                     self.emit_notify("after_tool", tool_name, rule, channel, chat_id, message);
                 }
+                HookAction::Exec | HookAction::Webhook => {
+                    let mut event = HookEvent::new("after_tool", tool_name, channel, chat_id);
+                    event.args = Some(args.clone());
+                    event.result = Some(result.to_string());
+                    event.elapsed_ms = Some(elapsed.as_millis() as u64);
+                    self.spawn_external(rule, event);
+                }
             }
         }
     }
 
     /// Evaluate on_error hooks (logging only, no blocking).
-    pub fn on_error(&self, tool_name: &str, error: &str, channel: &str, chat_id: &str) {
+    pub fn on_error(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+        error: &str,
+        channel: &str,
+        chat_id: &str,
+    ) {
         if !self.config.enabled {
             return;
         }
 
         for rule in &self.config.on_error {
-            if !rule.matches_tool(tool_name) || !rule.matches_channel(channel) {
+            if !self.rule_matches(rule, tool_name, channel, args) {
                 continue;
             }
 
@@ -437,7 +743,8 @@ impl HookEngine {
                         ),
                     }
                 }
-                HookAction::Block => {} // Block is a no-op in on_error
+                // Block, Redact and Append are no-ops in on_error
+                HookAction::Block | HookAction::Redact | HookAction::Append => {}
                 HookAction::Notify => {
                     let message = rule.message.clone().unwrap_or_else(|| {
                         format!(
@@ -447,8 +754,166 @@ impl HookEngine {
                     });
                     self.emit_notify("on_error", tool_name, rule, channel, chat_id, message);
                 }
+                HookAction::Exec | HookAction::Webhook => {
+                    let mut event = HookEvent::new("on_error", tool_name, channel, chat_id);
+                    event.args = Some(args.clone());
+                    event.error = Some(error.to_string());
+                    self.spawn_external(rule, event);
+                }
+            }
+        }
+    }
+
+    /// Evaluate before_llm hooks on the messages of a model call and return
+    /// them, possibly rewritten.
+    pub async fn before_llm(
+        &self,
+        mut messages: Vec<Message>,
+        channel: &str,
+        chat_id: &str,
+    ) -> Vec<Message> {
+        if !self.config.enabled {
+            return messages;
+        }
+
+        for rule in &self.config.before_llm {
+            let latest = messages
+                .iter()
+                .rev()
+                .find(|m| m.role == Role::User)
+                .map(|m| m.content.clone())
+                .unwrap_or_default();
+            if !self.stage_matches(rule, channel, &latest) {
+                continue;
+            }
+
+            match rule.action {
+                HookAction::Log => log_stage(
+                    rule.level.as_deref().unwrap_or("info"),
+                    "before_llm",
+                    channel,
+                    messages.len(),
+                ),
+                // Block and Append are no-ops in before_llm
+                HookAction::Block | HookAction::Append => {}
+                HookAction::Notify => {
+                    let message = rule.message.clone().unwrap_or_else(|| {
+                        format!(
+                            "Hook notify (before_llm): {} messages sent to the model ({}:{})",
+                            messages.len(),
+                            channel,
+                            chat_id
+                        )
+                    });
+                    self.emit_notify("before_llm", "", rule, channel, chat_id, message);
+                }
+                HookAction::Redact => {
+                    for message in &mut messages {
+                        if let Some(redacted) = self.redact(rule, &message.content) {
+                            message.content = redacted;
+                        }
+                        for part in &mut message.content_parts {
+                            if let ContentPart::Text { text } = part {
+                                if let Some(redacted) = self.redact(rule, text) {
+                                    *text = redacted;
+                                }
+                            }
+                        }
+                    }
+                }
+                HookAction::Webhook => {
+                    let mut event = HookEvent::new("before_llm", "", channel, chat_id);
+                    event.messages = serde_json::to_value(&messages).ok();
+                    self.spawn_external(rule, event);
+                }
+                HookAction::Exec => {
+                    let mut event = HookEvent::new("before_llm", "", channel, chat_id);
+                    event.messages = serde_json::to_value(&messages).ok();
+                    let rewritten = external::filter(rule, &event).await.and_then(|output| {
+                        output
+                            .map(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+                            .transpose()
+                    });
+                    match rewritten {
+                        Ok(Some(rewritten)) => messages = rewritten,
+                        Ok(None) => {}
+                        Err(error) => tracing::warn!(
+                            hook = "before_llm",
+                            error = %error,
+                            "External hook failed; messages left unchanged"
+                        ),
+                    }
+                }
+            }
+        }
+
+        messages
+    }
+
+    /// Evaluate after_response hooks on the final reply and return it,
+    /// possibly rewritten.
+    pub async fn after_response(
+        &self,
+        mut content: String,
+        channel: &str,
+        chat_id: &str,
+    ) -> String {
+        if !self.config.enabled {
+            return content;
+        }
+
+        for rule in &self.config.after_response {
+            if !self.stage_matches(rule, channel, &content) {
+                continue;
+            }
+
+            match rule.action {
+                HookAction::Log => log_stage(
+                    rule.level.as_deref().unwrap_or("info"),
+                    "after_response",
+                    channel,
+                    content.len(),
+                ),
+                HookAction::Block => {} // Block is a no-op in after_response
+                HookAction::Notify => {
+                    let message = rule.message.clone().unwrap_or_else(|| content.clone());
+                    self.emit_notify("after_response", "", rule, channel, chat_id, message);
+                }
+                HookAction::Redact => {
+                    if let Some(redacted) = self.redact(rule, &content) {
+                        content = redacted;
+                    }
+                }
+                HookAction::Append => {
+                    if let Some(message) = rule.message.as_deref() {
+                        if !content.is_empty() {
+                            content.push_str("\n\n");
+                        }
+                        content.push_str(message);
+                    }
+                }
+                HookAction::Webhook => {
+                    let mut event = HookEvent::new("after_response", "", channel, chat_id);
+                    event.response = Some(content.clone());
+                    self.spawn_external(rule, event);
+                }
+                HookAction::Exec => {
+                    let mut event = HookEvent::new("after_response", "", channel, chat_id);
+                    event.response = Some(content.clone());
+                    match external::filter(rule, &event).await {
+                        Ok(Some(rewritten)) => content = rewritten,
+                        Ok(None) => {}
+                        Err(error) => tracing::warn!(
+                            hook = "after_response",
+                            error = %error,
+                            "External hook failed; reply left unchanged"
+                        ),
+                    }
+                }
             }
         }
+
+        content
     }
 
     /// Whether hooks are enabled.
@@ -457,6 +922,43 @@ impl HookEngine {
     }
 }
 
+/// `Log` action for `before_llm` (`size` = message count) and
+/// `after_response` (`size` = reply length).
+fn log_stage(level: &str, hook: &'static str, channel: &str, size: usize) {
+    match level {
+        "error" => tracing::error!(
+            hook = hook,
+            channel = channel,
+            size = size,
+            "Hook: model I/O"
+        ),
+        "warn" => tracing::warn!(
+            hook = hook,
+            channel = channel,
+            size = size,
+            "Hook: model I/O"
+        ),
+        "debug" => tracing::debug!(
+            hook = hook,
+            channel = channel,
+            size = size,
+            "Hook: model I/O"
+        ),
+        "trace" => tracing::trace!(
+            hook = hook,
+            channel = channel,
+            size = size,
+            "Hook: model I/O"
+        ),
+        _ => tracing::info!(
+            hook = hook,
+            channel = channel,
+            size = size,
+            "Hook: model I/O"
+        ),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -507,6 +1009,7 @@ mod tests {
                 ..Default::default()
             }],
             on_error: vec![],
+            ..Default::default()
         };
         let json = serde_json::to_string(&config).unwrap();
         let deserialized: HooksConfig = serde_json::from_str(&json).unwrap();
@@ -573,18 +1076,111 @@ mod tests {
         assert!(rule.matches_channel("cli"));
     }
 
+    #[test]
+    fn test_arg_matcher_value_paths() {
+        let args = serde_json::json!({"path": "a.txt", "opts": {"files": ["x", "y"]}});
+        let at = |path: &str| {
+            ArgMatcher {
+                path: path.to_string(),
+                ..Default::default()
+            }
+            .value(&args)
+            .cloned()
+        };
+        assert_eq!(at("path"), Some(serde_json::json!("a.txt")));
+        assert_eq!(at("opts.files.1"), Some(serde_json::json!("y")));
+        assert_eq!(at("$.opts.files.0"), Some(serde_json::json!("x")));
+        assert_eq!(at("/opts/files/1"), Some(serde_json::json!("y")));
+        assert_eq!(at("opts.missing"), None);
+    }
+
     // ---- HookEngine ----
 
-    #[test]
-    fn test_hook_engine_disabled_does_nothing() {
+    #[tokio::test]
+    async fn test_hook_engine_before_tool_args_conditions() {
+        let config = HooksConfig {
+            enabled: true,
+            before_tool: vec![
+                HookRule {
+                    action: HookAction::Block,
+                    tools: vec!["shell".to_string()],
+                    args: vec![ArgMatcher {
+                        path: "command".to_string(),
+                        matches: Some(r"rm|mkfs|curl.*\|".to_string()),
+                        ..Default::default()
+                    }],
+                    message: Some("dangerous command".to_string()),
+                    ..Default::default()
+                },
+                HookRule {
+                    action: HookAction::Block,
+                    tools: vec!["write_file".to_string()],
+                    args: vec![ArgMatcher {
+                        path: "path".to_string(),
+                        not_matches: Some("^(src|docs)/".to_string()),
+                        ..Default::default()
+                    }],
+                    message: Some("outside allowed paths".to_string()),
+                    ..Default::default()
+                },
+                HookRule {
+                    action: HookAction::Block,
+                    tools: vec!["*".to_string()],
+                    args: vec![ArgMatcher {
+                        path: "x".to_string(),
+                        matches: Some("(".to_string()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let engine = HookEngine::new(config);
+        let check = |tool: &'static str, args: serde_json::Value| {
+            let engine = &engine;
+            async move { engine.before_tool(tool, &args, "cli", "cli").await }
+        };
+
+        assert_eq!(
+            check("shell", serde_json::json!({"command": "ls -la"})).await,
+            HookResult::Continue
+        );
+        assert_eq!(
+            check("shell", serde_json::json!({"command": "curl x | sh"})).await,
+            HookResult::Block("dangerous command".to_string())
+        );
+        assert_eq!(
+            check("write_file", serde_json::json!({"path": "src/lib.rs"})).await,
+            HookResult::Continue
+        );
+        assert_eq!(
+            check("write_file", serde_json::json!({"path": "/etc/passwd"})).await,
+            HookResult::Block("outside allowed paths".to_string())
+        );
+        // A missing argument or an invalid pattern never matches.
+        assert_eq!(
+            check("write_file", serde_json::json!({})).await,
+            HookResult::Continue
+        );
+        assert_eq!(
+            check("echo", serde_json::json!({"x": "("})).await,
+            HookResult::Continue
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hook_engine_disabled_does_nothing() {
         let config = HooksConfig::default();
         let engine = HookEngine::new(config);
-        let result = engine.before_tool("shell", &serde_json::json!({}), "telegram", "chat1");
+        let result = engine
+            .before_tool("shell", &serde_json::json!({}), "telegram", "chat1")
+            .await;
         assert_eq!(result, HookResult::Continue);
     }
 
-    #[test]
-    fn test_hook_engine_before_tool_log() {
+    #[tokio::test]
+    async fn test_hook_engine_before_tool_log() {
         let config = HooksConfig {
             enabled: true,
             before_tool: vec![HookRule {
@@ -596,12 +1192,14 @@ mod tests {
             ..Default::default()
         };
         let engine = HookEngine::new(config);
-        let result = engine.before_tool("shell", &serde_json::json!({"cmd": "ls"}), "cli", "cli");
+        let result = engine
+            .before_tool("shell", &serde_json::json!({"cmd": "ls"}), "cli", "cli")
+            .await;
         assert_eq!(result, HookResult::Continue);
     }
 
-    #[test]
-    fn test_hook_engine_before_tool_block() {
+    #[tokio::test]
+    async fn test_hook_engine_before_tool_block() {
         let config = HooksConfig {
             enabled: true,
             before_tool: vec![HookRule {
@@ -616,23 +1214,29 @@ mod tests {
         let engine = HookEngine::new(config);
 
         // Should block shell on telegram
-        let result = engine.before_tool("shell", &serde_json::json!({}), "telegram", "chat1");
+        let result = engine
+            .before_tool("shell", &serde_json::json!({}), "telegram", "chat1")
+            .await;
         assert!(matches!(result, HookResult::Block(_)));
         if let HookResult::Block(msg) = result {
             assert_eq!(msg, "Shell disabled on Telegram");
         }
 
         // Should NOT block shell on CLI
-        let result = engine.before_tool("shell", &serde_json::json!({}), "cli", "chat1");
+        let result = engine
+            .before_tool("shell", &serde_json::json!({}), "cli", "chat1")
+            .await;
         assert_eq!(result, HookResult::Continue);
 
         // Should NOT block echo on telegram
-        let result = engine.before_tool("echo", &serde_json::json!({}), "telegram", "chat1");
+        let result = engine
+            .before_tool("echo", &serde_json::json!({}), "telegram", "chat1")
+            .await;
         assert_eq!(result, HookResult::Continue);
     }
 
-    #[test]
-    fn test_hook_engine_before_tool_block_default_message() {
+    #[tokio::test]
+    async fn test_hook_engine_before_tool_block_default_message() {
         let config = HooksConfig {
             enabled: true,
             before_tool: vec![HookRule {
@@ -643,7 +1247,9 @@ mod tests {
             ..Default::default()
         };
         let engine = HookEngine::new(config);
-        let result = engine.before_tool("shell", &serde_json::json!({}), "cli", "chat1");
+        let result = engine
+            .before_tool("shell", &serde_json::json!({}), "cli", "chat1")
+            .await;
         if let HookResult::Block(msg) = result {
             assert!(msg.contains("shell"));
             assert!(msg.contains("blocked by hook"));
@@ -652,8 +1258,8 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_hook_engine_multiple_rules_first_block_wins() {
+    #[tokio::test]
+    async fn test_hook_engine_multiple_rules_first_block_wins() {
         let config = HooksConfig {
             enabled: true,
             before_tool: vec![
@@ -673,7 +1279,9 @@ mod tests {
             ..Default::default()
         };
         let engine = HookEngine::new(config);
-        let result = engine.before_tool("shell", &serde_json::json!({}), "cli", "chat1");
+        let result = engine
+            .before_tool("shell", &serde_json::json!({}), "cli", "chat1")
+            .await;
         assert!(matches!(result, HookResult::Block(_)));
     }
 
@@ -692,6 +1300,7 @@ mod tests {
         let engine = HookEngine::new(config);
         engine.after_tool(
             "shell",
+            &serde_json::json!({}),
             "result text",
             std::time::Duration::from_millis(50),
             "cli",
@@ -712,7 +1321,122 @@ mod tests {
             ..Default::default()
         };
         let engine = HookEngine::new(config);
-        engine.on_error("shell", "command not found", "cli", "chat1");
+        engine.on_error(
+            "shell",
+            &serde_json::json!({}),
+            "command not found",
+            "cli",
+            "chat1",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hook_engine_before_tool_exec_and_failure_policy() {
+        let config = HooksConfig {
+            enabled: true,
+            before_tool: vec![
+                HookRule {
+                    action: HookAction::Exec,
+                    tools: vec!["shell".to_string()],
+                    command: Some("echo 'deploy freeze'; exit 2".to_string()),
+                    ..Default::default()
+                },
+                HookRule {
+                    action: HookAction::Webhook,
+                    tools: vec!["write_file".to_string()],
+                    url: Some("http://127.0.0.1:1/hook".to_string()),
+                    on_failure: HookFailurePolicy::Block,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let engine = HookEngine::new(config);
+
+        let result = engine
+            .before_tool("shell", &serde_json::json!({}), "cli", "chat1")
+            .await;
+        assert_eq!(result, HookResult::Block("deploy freeze".to_string()));
+
+        let result = engine
+            .before_tool("write_file", &serde_json::json!({}), "cli", "chat1")
+            .await;
+        assert!(matches!(result, HookResult::Block(msg) if msg.starts_with("hook failed")));
+
+        let result = engine
+            .before_tool("read_file", &serde_json::json!({}), "cli", "chat1")
+            .await;
+        assert_eq!(result, HookResult::Continue);
+    }
+
+    #[tokio::test]
+    async fn test_hook_engine_llm_stages() {
+        let config = HooksConfig {
+            enabled: true,
+            before_llm: vec![
+                HookRule {
+                    action: HookAction::Redact,
+                    pattern: Some(r"[\w.]+@[\w.]+".to_string()),
+                    replacement: Some("<email>".to_string()),
+                    ..Default::default()
+                },
+                HookRule {
+                    action: HookAction::Exec,
+                    command: Some(r#"echo '[{"role":"user","content":"rewritten"}]'"#.to_string()),
+                    args: vec![ArgMatcher {
+                        path: "content".to_string(),
+                        matches: Some("rewrite me".to_string()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ],
+            after_response: vec![
+                HookRule {
+                    action: HookAction::Append,
+                    channels: vec!["whatsapp".to_string()],
+                    message: Some("_Automated reply._".to_string()),
+                    ..Default::default()
+                },
+                HookRule {
+                    action: HookAction::Exec,
+                    command: Some("tr -d '\\n' | wc -c | tr -d ' '".to_string()),
+                    args: vec![ArgMatcher {
+                        path: "content".to_string(),
+                        matches: Some("^count".to_string()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let engine = HookEngine::new(config);
+
+        let messages = engine
+            .before_llm(vec![Message::user("mail a@b.com")], "cli", "cli")
+            .await;
+        assert_eq!(messages[0].content, "mail <email>");
+        assert_eq!(
+            messages[0].content_parts,
+            vec![ContentPart::Text {
+                text: "mail <email>".to_string()
+            }]
+        );
+        let messages = engine
+            .before_llm(vec![Message::user("rewrite me")], "cli", "cli")
+            .await;
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].content, "rewritten");
+
+        assert_eq!(engine.after_response("hi".into(), "cli", "cli").await, "hi");
+        assert_eq!(
+            engine.after_response("hi".into(), "whatsapp", "c1").await,
+            "hi\n\n_Automated reply._"
+        );
+        // The exec filter sees the event JSON and its output replaces the reply.
+        let counted = engine.after_response("count".into(), "cli", "cli").await;
+        assert!(counted.parse::<usize>().unwrap() > "count".len());
     }
 
     #[test]
@@ -744,7 +1468,9 @@ mod tests {
         };
         let engine = HookEngine::new(config).with_bus(Arc::clone(&bus));
 
-        let result = engine.before_tool("shell", &serde_json::json!({}), "telegram", "chat77");
+        let result = engine
+            .before_tool("shell", &serde_json::json!({}), "telegram", "chat77")
+            .await;
         assert_eq!(result, HookResult::Continue);
 
         let outbound = timeout(Duration::from_millis(300), bus.consume_outbound())
@@ -777,6 +1503,7 @@ mod tests {
 
         engine.after_tool(
             "echo",
+            &serde_json::json!({}),
             "ok",
             std::time::Duration::from_millis(15),
             "telegram",
@@ -808,7 +1535,13 @@ mod tests {
         };
         let engine = HookEngine::new(config).with_bus(Arc::clone(&bus));
 
-        engine.on_error("shell", "permission denied", "telegram", "chat77");
+        engine.on_error(
+            "shell",
+            &serde_json::json!({}),
+            "permission denied",
+            "telegram",
+            "chat77",
+        );
 
         let outbound = timeout(Duration::from_millis(300), bus.consume_outbound())
             .await
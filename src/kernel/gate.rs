@@ -37,9 +37,20 @@ fn scan_input_segment(
     safety_layer.scan_with_options(content, CheckDirection::Input, options)
 }
 
-fn scan_tool_input(safety_layer: &SafetyLayer, name: &str, input: &Value) -> Option<SafetyResult> {
+fn scan_tool_input(
+    safety_layer: &SafetyLayer,
+    name: &str,
+    input: &Value,
+    channel: Option<&str>,
+) -> Option<SafetyResult> {
+    let scoped = ScanOptions {
+        tool: Some(name),
+        channel,
+        ..Default::default()
+    };
     let file_body_options = ScanOptions {
         ignored_policy_rules: FILE_BODY_IGNORED_POLICY_RULES,
+        ..scoped.clone()
     };
 
     let check = |content: &str, options: &ScanOptions<'_>| {
@@ -57,7 +68,7 @@ fn scan_tool_input(safety_layer: &SafetyLayer, name: &str, input: &Value) -> Opt
                 );
             }
 
-            if let Some(result) = check(path.unwrap_or_default(), &ScanOptions::default()) {
+            if let Some(result) = check(path.unwrap_or_default(), &scoped) {
                 return Some(result);
             }
 
@@ -75,7 +86,7 @@ fn scan_tool_input(safety_layer: &SafetyLayer, name: &str, input: &Value) -> Opt
                 );
             }
 
-            if let Some(result) = check(path.unwrap_or_default(), &ScanOptions::default()) {
+            if let Some(result) = check(path.unwrap_or_default(), &scoped) {
                 return Some(result);
             }
 
@@ -86,7 +97,7 @@ fn scan_tool_input(safety_layer: &SafetyLayer, name: &str, input: &Value) -> Opt
         }
         _ => {
             let input_str = serde_json::to_string(input).unwrap_or_default();
-            check(&input_str, &ScanOptions::default())
+            check(&input_str, &scoped)
         }
     }
 }
@@ -99,7 +110,8 @@ fn scan_tool_input(safety_layer: &SafetyLayer, name: &str, input: &Value) -> Opt
 /// 3. Tool lookup + execute
 /// 4. Safety check on output (when safety enabled)
 /// 5. Taint label — auto-label output based on tool name and content
-/// 6. Metrics recording
+/// 6. Quarantine — wrap untrusted web/email output (when configured)
+/// 7. Metrics recording
 ///
 /// This is the core execution path. Per-session gates (hooks, approval,
 /// dry-run) are handled by the agent loop wrapper.
@@ -120,7 +132,7 @@ pub async fn execute_tool(
     // full safety pipeline while file bodies only suppress the shell_injection
     // rule that false-positives on legitimate code snippets.
     if let Some(safety_layer) = safety {
-        if let Some(result) = scan_tool_input(safety_layer, name, &input) {
+        if let Some(result) = scan_tool_input(safety_layer, name, &input, ctx.channel.as_deref()) {
             metrics.record_tool_call(name, start.elapsed(), false);
             return Ok(blocked_input_output(name, result));
         }
@@ -139,8 +151,13 @@ pub async fn execute_tool(
         }
     }
 
+    // Quarantine needs the arguments (e.g. the fetched URL) after execution.
+    let quarantine_args = safety
+        .filter(|s| s.quarantines(name))
+        .map(|_| input.clone());
+
     // Step 3: Execute
-    let output = match registry.execute_with_context(name, input, ctx).await {
+    let mut output = match registry.execute_with_context(name, input, ctx).await {
         Ok(output) => output,
         Err(e) => {
             metrics.record_tool_call(name, start.elapsed(), false);
@@ -150,7 +167,13 @@ pub async fn execute_tool(
 
     // Step 4: Safety check on output
     if let Some(safety_layer) = safety {
-        let result = safety_layer.scan(&output.for_llm, CheckDirection::Output);
+        let options = ScanOptions {
+            tool: Some(name),
+            channel: ctx.channel.as_deref(),
+            ..Default::default()
+        };
+        let result =
+            safety_layer.scan_with_options(&output.for_llm, CheckDirection::Output, &options);
         if result.blocked {
             metrics.record_tool_call(name, start.elapsed(), false);
             return Ok(ToolOutput::error(format!(
@@ -159,6 +182,9 @@ pub async fn execute_tool(
                 result.warnings.join("; ")
             )));
         }
+        if let Some(redacted) = safety_layer.apply_redactions(&output.for_llm, &options) {
+            output.for_llm = redacted;
+        }
     }
 
     // Step 5: Taint label — auto-label output based on tool name and content (write)
@@ -168,7 +194,17 @@ pub async fn execute_tool(
         }
     }
 
-    // Step 6: Record metrics
+    // Step 6: Quarantine — after taint labeling, which matches the raw content
+    if let (Some(safety_layer), Some(args)) = (safety, quarantine_args) {
+        if !output.is_error {
+            if let Some((wrapped, source)) = safety_layer.quarantine(name, &args, &output.for_llm) {
+                output.for_llm = wrapped;
+                output.untrusted = Some(source);
+            }
+        }
+    }
+
+    // Step 7: Record metrics
     metrics.record_tool_call(name, start.elapsed(), !output.is_error);
 
     Ok(output)
@@ -285,6 +321,109 @@ mod tests {
         assert_eq!(result.unwrap().for_llm, "hello world");
     }
 
+    #[tokio::test]
+    async fn test_execute_tool_quarantines_untrusted_output() {
+        use crate::safety::quarantine::QuarantineConfig;
+
+        let registry = setup_registry();
+        let metrics = MetricsCollector::new();
+        let safety = SafetyLayer::new(SafetyConfig {
+            quarantine: QuarantineConfig {
+                enabled: true,
+                tools: vec!["echo".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let output = execute_tool(
+            &registry,
+            "echo",
+            json!({"message": "page text", "url": "https://news.example.com/a"}),
+            &ToolContext::default(),
+            Some(&safety),
+            &metrics,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(output.for_llm.starts_with(
+            "<untrusted_content source=\"news.example.com\" tool=\"echo\" trust=\"0.00\">\npage text\n</untrusted_content>"
+        ));
+        let source = output.untrusted.expect("output should be quarantined");
+        assert_eq!(source.source, "news.example.com");
+        assert!(source.requires_approval);
+
+        let plain = execute_tool(
+            &registry,
+            "echo",
+            json!({"message": "page text"}),
+            &ToolContext::default(),
+            None,
+            &metrics,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(plain.for_llm, "page text");
+        assert!(plain.untrusted.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_execute_tool_applies_custom_policy_rules() {
+        use crate::safety::policy::{PolicyAction, PolicyRuleConfig};
+
+        let registry = setup_registry();
+        let metrics = MetricsCollector::new();
+        let safety = SafetyLayer::new(SafetyConfig {
+            policy_rules: vec![
+                PolicyRuleConfig {
+                    name: "internal_host".to_string(),
+                    pattern: r"\w+\.corp\.example".to_string(),
+                    action: PolicyAction::Redact,
+                    tools: vec!["echo".to_string()],
+                    ..Default::default()
+                },
+                PolicyRuleConfig {
+                    name: "no_payroll".to_string(),
+                    pattern: "payroll".to_string(),
+                    action: PolicyAction::Block,
+                    channels: vec!["telegram".to_string()],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        });
+
+        let output = execute_tool(
+            &registry,
+            "echo",
+            json!({"message": "reach git.corp.example"}),
+            &ToolContext::default(),
+            Some(&safety),
+            &metrics,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(output.for_llm, "reach [REDACTED:internal_host]");
+
+        let telegram = ToolContext::new().with_channel("telegram", "chat1");
+        let blocked = execute_tool(
+            &registry,
+            "echo",
+            json!({"message": "payroll"}),
+            &telegram,
+            Some(&safety),
+            &metrics,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(blocked.is_error);
+        assert!(blocked.for_llm.contains("no_payroll"));
+    }
+
     #[tokio::test]
     async fn test_write_file_allows_shell_like_code_in_content() {
         let dir = tempdir().unwrap();
@@ -52,6 +52,9 @@ pub struct ZeptoKernel {
     pub mcp_clients: Vec<Arc<McpClient>>,
     /// Shared long-term memory for both per-message injection and tool access.
     pub ltm: Option<Arc<tokio::sync::Mutex<LongTermMemory>>>,
+    /// Cron scheduler shared with the cron tool, so the gateway API can
+    /// manage the same jobs. `None` for kernels not assembled by `boot`.
+    pub cron: Option<Arc<CronService>>,
     /// Taint tracking engine for data-flow-aware security.
     /// `None` when taint tracking is disabled.
     pub taint: Option<Arc<std::sync::RwLock<TaintEngine>>>,
@@ -70,9 +73,17 @@ impl ZeptoKernel {
         template: Option<&crate::config::templates::AgentTemplate>,
         hand: Option<&HandManifest>,
     ) -> anyhow::Result<Self> {
+        // 0. Bring on-disk state formats up to date before anything reads them
+        if let Err(e) = crate::migrate::state::run(&Config::data_dir()) {
+            warn!("State migration failed: {}", e);
+        }
+
         // 1. Build tool filter from config/template/hand
         let filter = ToolFilter::from_config(&config, template, hand);
 
+        // 1b. Network egress policy for HTTP-based tools
+        crate::utils::http::set_policy(config.tools.egress.clone());
+
         // 2. Build provider chain
         let provider: Option<Arc<dyn LLMProvider>> =
             if let Some((chain, names)) = provider::build_provider_chain(&config).await {
@@ -119,7 +130,7 @@ impl ZeptoKernel {
 
         let ltm: Option<Arc<tokio::sync::Mutex<LongTermMemory>>> =
             if !matches!(config.memory.backend, MemoryBackend::Disabled) {
-                let ltm_path = Config::dir().join("memory").join("longterm.json");
+                let ltm_path = Config::data_dir().join("memory").join("longterm.json");
                 match LongTermMemory::with_path_and_searcher(ltm_path, memory_searcher.clone()) {
                     Ok(ltm) => Some(Arc::new(tokio::sync::Mutex::new(ltm))),
                     Err(e) => {
@@ -143,7 +154,7 @@ impl ZeptoKernel {
                         "Failed to create configured runtime: {}. Falling back to native.",
                         e
                     );
-                    Arc::new(NativeRuntime::new())
+                    Arc::new(NativeRuntime::new().with_shell(config.runtime.native_shell))
                 } else {
                     return Err(anyhow::anyhow!(
                         "Configured runtime '{:?}' unavailable: {}. \
@@ -156,7 +167,7 @@ impl ZeptoKernel {
         };
 
         // 7. Cron service
-        let cron_store_path = Config::dir().join("cron").join("jobs.json");
+        let cron_store_path = Config::data_dir().join("cron").join("jobs.json");
         let cron_service = Arc::new(CronService::with_jitter(
             cron_store_path,
             bus.clone(),
@@ -169,7 +180,7 @@ impl ZeptoKernel {
         let deps = registrar::ToolDeps {
             runtime,
             bus,
-            cron_service,
+            cron_service: Arc::clone(&cron_service),
             memory_searcher,
             shared_ltm: ltm.clone(),
             template: template.cloned(),
@@ -188,6 +199,7 @@ impl ZeptoKernel {
             hooks,
             mcp_clients,
             ltm,
+            cron: Some(cron_service),
             taint,
         })
     }
@@ -235,6 +247,7 @@ mod tests {
             hooks: Arc::new(HookEngine::new(config.hooks.clone())),
             mcp_clients: vec![],
             ltm: None,
+            cron: None,
             taint: if config.safety.enabled && config.safety.taint.enabled {
                 Some(Arc::new(std::sync::RwLock::new(TaintEngine::new(
                     config.safety.taint.clone(),
@@ -284,6 +297,7 @@ mod tests {
             hooks: Arc::new(HookEngine::new(config.hooks.clone())),
             mcp_clients: vec![],
             ltm: None,
+            cron: None,
             taint: None,
         };
         assert!(kernel.safety.is_none());
@@ -302,6 +316,7 @@ mod tests {
             hooks: Arc::new(HookEngine::new(config.hooks.clone())),
             mcp_clients: vec![],
             ltm: None,
+            cron: None,
             taint: Some(Arc::new(std::sync::RwLock::new(TaintEngine::new(
                 config.safety.taint.clone(),
             )))),
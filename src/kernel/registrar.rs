@@ -216,6 +216,8 @@ pub async fn register_all_tools(
     use crate::tools::filesystem::{EditFileTool, ListDirTool, ReadFileTool, WriteFileTool};
     use crate::tools::shell::ShellTool;
 
+    registry.set_finance_read_only(config.safety.finance_read_only);
+
     // Build shared shell security config from template (once, then cloned per tool)
     let shell_config = build_shell_config(deps.template.as_ref());
 
@@ -235,6 +237,12 @@ pub async fn register_all_tools(
     if filter.is_enabled("edit_file") {
         registry.register(Box::new(EditFileTool));
     }
+    if filter.is_enabled("apply_patch") {
+        registry.register(Box::new(crate::tools::ApplyPatchTool));
+    }
+    if filter.is_enabled("undo") {
+        registry.register(Box::new(crate::tools::UndoTool));
+    }
 
     // --- Group 1b: Coding tools (default-off, enabled by "coding" template tag) ---
     // These are laptop/server workload tools that assume bash/filesystem access.
@@ -357,18 +365,96 @@ pub async fn register_all_tools(
         info!("Registered web_fetch tool");
     }
 
-    // --- Group 5: HTTP request ---
-    if filter.is_enabled("http_request") {
-        if let Some(http_cfg) = &config.tools.http_request {
-            if !http_cfg.allowed_domains.is_empty() {
-                registry.register(Box::new(crate::tools::HttpRequestTool::new(
-                    http_cfg.allowed_domains.clone(),
-                    http_cfg.timeout_secs,
-                    http_cfg.max_response_bytes,
-                )));
-                info!("Registered http_request tool");
+    // --- Group 5: HTTP request + OpenAPI operations ---
+    if let Some(http_cfg) = &config.tools.http_request {
+        if filter.is_enabled("http_request") && !http_cfg.allowed_domains.is_empty() {
+            registry.register(Box::new(crate::tools::HttpRequestTool::new(
+                http_cfg.allowed_domains.clone(),
+                http_cfg.timeout_secs,
+                http_cfg.max_response_bytes,
+            )));
+            info!("Registered http_request tool");
+        }
+        for (api, api_cfg) in &http_cfg.apis {
+            match crate::tools::openapi::load_api_tools(
+                api,
+                api_cfg,
+                http_cfg.timeout_secs,
+                http_cfg.max_response_bytes,
+            ) {
+                Ok(tools) => {
+                    let mut registered_count = 0usize;
+                    for tool in tools {
+                        if !filter.is_enabled(tool.tool_name()) {
+                            continue;
+                        }
+                        registry.register(Box::new(tool));
+                        registered_count += 1;
+                    }
+                    info!(api = %api, tools = registered_count, "Registered OpenAPI tools");
+                }
+                Err(e) => {
+                    warn!(api = %api, error = %e, "Failed to load OpenAPI spec, skipping");
+                }
+            }
+        }
+    }
+
+    // --- Group 5b: SQL databases ---
+    if !config.tools.sql.connections.is_empty() {
+        let sql = crate::tools::SqlTool::new(config.tools.sql.clone());
+        if filter.is_enabled("sql_execute") {
+            if let Some(execute) = sql.execute_tool() {
+                registry.register(Box::new(execute));
+                info!("Registered sql_execute tool");
+            }
+        }
+        if filter.is_enabled("sql") {
+            registry.register(Box::new(sql));
+            info!(
+                "Registered sql tool ({} connections)",
+                config.tools.sql.connections.len()
+            );
+        }
+    }
+
+    // --- Group 5c: GitHub ---
+    if config.tools.github.has_credentials() {
+        let github = crate::tools::GitHubTool::new(config.tools.github.clone());
+        if filter.is_enabled("github_write") {
+            registry.register(Box::new(github.write_tool()));
+            info!("Registered github_write tool");
+        }
+        if filter.is_enabled("github") {
+            registry.register(Box::new(github));
+            info!("Registered github tool");
+        }
+    }
+
+    // --- Group 5d: Issue trackers ---
+    if config.tools.issues.is_configured() && filter.is_enabled("issues") {
+        registry.register(Box::new(crate::tools::IssuesTool::new(
+            config.tools.issues.clone(),
+        )));
+        info!("Registered issues tool");
+    }
+
+    // --- Group 5e: Kubernetes ---
+    if config.tools.kubernetes.enabled {
+        let kubectl = crate::tools::KubectlTool::new(
+            config.tools.kubernetes.clone(),
+            Arc::clone(&deps.runtime),
+        );
+        if filter.is_enabled("kubectl_write") {
+            if let Some(write) = kubectl.write_tool() {
+                registry.register(Box::new(write));
+                info!("Registered kubectl_write tool");
             }
         }
+        if filter.is_enabled("kubectl") {
+            registry.register(Box::new(kubectl));
+            info!("Registered kubectl tool");
+        }
     }
 
     // --- Group 6: Document tools ---
@@ -382,14 +468,34 @@ pub async fn register_all_tools(
         registry.register(Box::new(crate::tools::DocxReadTool::new(workspace_str)));
         info!("Registered docx_read tool");
     }
+    if filter.is_enabled("spreadsheet") {
+        registry.register(Box::new(crate::tools::SpreadsheetTool));
+        info!("Registered spreadsheet tool");
+    }
+    if filter.is_enabled("rank") {
+        registry.register(Box::new(crate::tools::RankTool::new(
+            config.tools.ranking.clone(),
+        )));
+        info!(
+            "Registered rank tool ({} rubrics)",
+            config.tools.ranking.rubrics.len()
+        );
+    }
 
     // --- Group 7: Channel/messaging tools ---
     if filter.is_enabled("message") {
-        registry.register(Box::new(crate::tools::MessageTool::new(Arc::clone(
-            &deps.bus,
-        ))));
+        registry.register(Box::new(
+            crate::tools::MessageTool::new(Arc::clone(&deps.bus)).with_quiet_hours(
+                crate::heartbeat::QuietHours::from_config(&config.heartbeat.quiet_hours),
+            ),
+        ));
         info!("Registered message tool");
     }
+    #[cfg(feature = "tool-chart")]
+    if filter.is_enabled("chart") {
+        registry.register(Box::new(crate::tools::ChartTool));
+        info!("Registered chart tool");
+    }
     if filter.is_enabled("whatsapp_send") {
         if let (Some(phone_number_id), Some(access_token)) = (
             config.tools.whatsapp.phone_number_id.as_deref(),
@@ -417,7 +523,9 @@ pub async fn register_all_tools(
         } else if let Some(encoded) = config.tools.google_sheets.service_account_base64.as_deref() {
             match crate::tools::GoogleSheetsTool::from_service_account(encoded.trim()) {
                 Ok(tool) => {
-                    registry.register(Box::new(tool));
+                    registry.register(Box::new(
+                        tool.with_scopes(&config.tools.google_sheets.scopes),
+                    ));
                     info!("Registered google_sheets tool from base64 payload");
                 }
                 Err(e) => warn!("Failed to initialize google_sheets tool: {}", e),
@@ -425,6 +533,62 @@ pub async fn register_all_tools(
         }
     }
 
+    if filter.is_enabled("google_drive") {
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        // Without credentials of its own, Drive uses the Sheets ones.
+        let drive = &config.tools.google_drive;
+        let sheets = &config.tools.google_sheets;
+        let (access_token, service_account) =
+            if drive.access_token.is_some() || drive.service_account_base64.is_some() {
+                (&drive.access_token, &drive.service_account_base64)
+            } else {
+                (&sheets.access_token, &sheets.service_account_base64)
+            };
+        let tool = match (non_empty(access_token), non_empty(service_account)) {
+            (Some(token), _) => Some(Ok(crate::tools::GoogleDriveTool::new(&token))),
+            (None, Some(encoded)) => Some(crate::tools::GoogleDriveTool::from_service_account(
+                &encoded,
+            )),
+            (None, None) => None,
+        };
+        match tool {
+            Some(Ok(tool)) => {
+                registry.register(Box::new(tool.with_scopes(&drive.scopes)));
+                info!("Registered google_drive tool");
+            }
+            Some(Err(e)) => warn!("Failed to initialize google_drive tool: {}", e),
+            None => {}
+        }
+    }
+
+    if filter.is_enabled("calendar") {
+        match crate::tools::CalendarTool::from_config(config) {
+            Ok(Some(tool)) => {
+                registry.register(Box::new(tool));
+                info!("Registered calendar tool");
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to initialize calendar tool: {}", e),
+        }
+    }
+
+    if filter.is_enabled("email") {
+        match crate::tools::EmailTool::from_config(config) {
+            Ok(Some(tool)) => {
+                registry.register(Box::new(tool));
+                info!("Registered email tool");
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to initialize email tool: {}", e),
+        }
+    }
+
     // NOTE: Google Workspace tool (feature = "google") is NOT registered here.
     // It requires async OAuth token resolution that depends on stored credentials,
     // which is handled in `cli/common.rs` after kernel boot. See the
@@ -460,6 +624,14 @@ pub async fn register_all_tools(
                 warn!("longterm_memory tool enabled but LTM failed to initialize");
             }
         }
+        if let Some(ref ltm) = deps.shared_ltm {
+            if filter.is_enabled("memory_set") {
+                registry.register(Box::new(crate::tools::MemorySetTool::new(ltm.clone())));
+            }
+            if filter.is_enabled("memory_append") {
+                registry.register(Box::new(crate::tools::MemoryAppendTool::new(ltm.clone())));
+            }
+        }
         info!(
             "Registered memory tools (backend: {})",
             deps.memory_searcher.name()
@@ -472,6 +644,29 @@ pub async fn register_all_tools(
     if filter.is_enabled("ask_clarification") {
         registry.register(Box::new(crate::tools::clarification::AskClarificationTool));
     }
+    if filter.is_enabled("time") {
+        registry.register(Box::new(crate::tools::TimeTool));
+    }
+    if filter.is_enabled("weather") {
+        let mut weather = crate::tools::WeatherTool::new(config.tools.weather.clone());
+        if let Some(ref ltm) = deps.shared_ltm {
+            weather = weather.with_memory(ltm.clone());
+        }
+        registry.register(Box::new(weather));
+        info!("Registered weather tool");
+    }
+    if filter.is_enabled("news") {
+        registry.register(Box::new(crate::tools::NewsTool::new(
+            config.tools.news.clone(),
+        )));
+        info!("Registered news tool");
+    }
+    if filter.is_enabled("calc") {
+        registry.register(Box::new(crate::tools::CalcTool::new(
+            config.tools.calc.clone(),
+        )));
+        info!("Registered calc tool");
+    }
 
     // --- Group 11: Scheduling/cron ---
     if filter.is_enabled("cron") {
@@ -484,6 +679,8 @@ pub async fn register_all_tools(
     }
 
     // --- Group 11: Project management ---
+    // Always registered: the local PROJECT.md task actions need no backend
+    // credentials. Remote issue actions report the missing token when used.
     if filter.is_enabled("project") {
         let project_config = config.project.clone();
         let has_token = match project_config.backend {
@@ -503,12 +700,14 @@ pub async fn register_all_tools(
                 .filter(|k| !k.is_empty())
                 .is_some(),
         };
+        registry.register(Box::new(crate::tools::ProjectTool::new(project_config)));
         if has_token {
-            registry.register(Box::new(crate::tools::ProjectTool::new(project_config)));
             info!(
                 "Registered project tool ({:?} backend)",
                 config.project.backend
             );
+        } else {
+            info!("Registered project tool (local PROJECT.md tasks only)");
         }
     }
 
@@ -541,6 +740,40 @@ pub async fn register_all_tools(
         }
     }
 
+    if filter.is_enabled("contacts") {
+        let workspace = config.workspace_path();
+        match crate::tools::ContactsTool::new(
+            &workspace.to_string_lossy(),
+            config.tools.contacts.clone(),
+        ) {
+            Ok(tool) => {
+                registry.register(Box::new(tool));
+                info!("Registered contacts tool");
+            }
+            Err(e) => warn!("Failed to initialize contacts tool: {}", e),
+        }
+    }
+
+    if filter.is_enabled("rss") {
+        let workspace = config.workspace_path();
+        match crate::tools::RssTool::new(
+            &workspace.to_string_lossy(),
+            config.tools.rss.clone(),
+            Some(Arc::clone(&deps.cron_service)),
+        ) {
+            Ok(tool) => {
+                match tool.ensure_digest_job().await {
+                    Ok(Some(job_id)) => info!("Scheduled RSS digest (cron job {})", job_id),
+                    Ok(None) => {}
+                    Err(e) => warn!("Failed to schedule RSS digest: {}", e),
+                }
+                registry.register(Box::new(tool));
+                info!("Registered rss tool");
+            }
+            Err(e) => warn!("Failed to initialize rss tool: {}", e),
+        }
+    }
+
     // --- Group 14: ClawHub skills marketplace ---
     if config.tools.skills.enabled && config.tools.skills.clawhub.enabled {
         use crate::skills::registry::{ClawHubRegistry, SearchCache};
@@ -563,12 +796,7 @@ pub async fn register_all_tools(
             info!("Registered find_skills tool");
         }
         if filter.is_enabled("install_skill") {
-            let skills_dir = config
-                .skills
-                .workspace_dir
-                .as_deref()
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| Config::dir().join("skills").to_string_lossy().into_owned());
+            let skills_dir = config.skills_dir().to_string_lossy().into_owned();
             registry.register(Box::new(crate::tools::InstallSkillTool::new(
                 Arc::clone(&clawhub),
                 skills_dir,
@@ -584,6 +812,46 @@ pub async fn register_all_tools(
         info!("Registered android tool");
     }
 
+    #[cfg(all(feature = "peripheral-linux", target_os = "linux"))]
+    if filter.is_enabled("peripheral") && !config.peripherals.devices.is_empty() {
+        use crate::peripherals::Peripheral;
+        let sbc =
+            crate::peripherals::linux_sbc::LinuxSbcPeripheral::new(config.peripherals.clone());
+        for tool in sbc.tools() {
+            registry.register(tool);
+        }
+        info!(
+            "Registered peripheral tool ({} devices)",
+            config.peripherals.devices.len()
+        );
+    }
+
+    #[cfg(feature = "hardware")]
+    if filter.is_enabled("hardware") {
+        registry.register(Box::new(
+            crate::tools::HardwareTool::new().with_runtime(Arc::clone(&deps.runtime)),
+        ));
+        info!("Registered hardware tool");
+    }
+
+    #[cfg(feature = "hardware")]
+    if filter.is_enabled("serial") {
+        registry.register(Box::new(crate::tools::SerialTool::new(
+            config.tools.serial.clone(),
+        )));
+        info!("Registered serial tool");
+    }
+
+    #[cfg(feature = "mqtt")]
+    if let Some(ref mqtt_config) = config.channels.mqtt {
+        if filter.is_enabled("mqtt_publish") && !mqtt_config.broker_url.is_empty() {
+            registry.register(Box::new(crate::tools::mqtt::MqttPublishTool::new(
+                mqtt_config.clone(),
+            )));
+            info!("Registered mqtt_publish tool");
+        }
+    }
+
     // --- Group 16: Plugin tools ---
     if config.plugins.enabled {
         let plugin_dirs: Vec<PathBuf> = config
@@ -5,6 +5,7 @@ pub mod agent;
 pub mod api;
 pub mod audit;
 pub mod auth;
+pub mod backup;
 pub mod batch;
 pub mod bus;
 pub mod cache;
@@ -14,6 +15,7 @@ pub mod cron;
 pub mod deps;
 pub mod devices;
 pub mod error;
+pub mod eval;
 pub mod gateway;
 pub mod hands;
 pub mod hardware;
@@ -81,8 +83,8 @@ pub use health::{
 pub use runtime::AppleContainerRuntime;
 pub use security::{
     validate_extra_mounts, validate_path_in_workspace, AgentMode, AgentModeConfig,
-    CategoryPermission, DeviceInfo, ModePolicy, PairedDevice, PairingManager, SafePath,
-    ShellAllowlistMode, ShellSecurityConfig,
+    CategoryPermission, DeviceInfo, DeviceScope, ModePolicy, PairedDevice, PairingManager,
+    SafePath, ShellAllowlistMode, ShellSecurityConfig,
 };
 pub use session::{Message, Role, Session, SessionManager, ToolCall};
 #[cfg(feature = "screenshot")]
@@ -93,8 +95,9 @@ pub use tools::AndroidTool;
 pub use tools::GoogleTool;
 pub use tools::{
     composed::CreateToolTool, cron::CronTool, custom::CustomTool, delegate::DelegateTool,
-    spawn::SpawnTool, BinaryPluginTool, DocxReadTool, EchoTool, FindTool, GitTool,
-    GoogleSheetsTool, GrepTool, HardwareTool, HttpRequestTool, MemoryGetTool, MemorySearchTool,
-    MessageTool, PdfReadTool, ProjectTool, R8rTool, ReminderTool, SearxngSearchTool, StripeTool,
-    Tool, ToolCategory, ToolContext, ToolRegistry, WebFetchTool, WebSearchTool, WhatsAppTool,
+    spawn::SpawnTool, BinaryPluginTool, CalendarTool, ContactsTool, DocxReadTool, EchoTool,
+    EmailTool, FindTool, GitTool, GoogleSheetsTool, GrepTool, HardwareTool, HttpRequestTool,
+    MemoryGetTool, MemorySearchTool, MessageTool, PdfReadTool, ProjectTool, R8rTool, ReminderTool,
+    RssTool, SearxngSearchTool, StripeTool, Tool, ToolCategory, ToolContext, ToolRegistry,
+    WebFetchTool, WebSearchTool, WhatsAppTool,
 };
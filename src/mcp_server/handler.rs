@@ -224,6 +224,7 @@ mod tests {
             hooks: Arc::new(HookEngine::new(config.hooks.clone())),
             mcp_clients: vec![],
             ltm: None,
+            cron: None,
             taint: None,
         }
     }
@@ -407,6 +408,7 @@ mod tests {
             hooks: Arc::new(HookEngine::new(config.hooks.clone())),
             mcp_clients: vec![],
             ltm: None,
+            cron: None,
             taint: None,
         };
 
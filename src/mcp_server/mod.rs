@@ -221,6 +221,7 @@ mod tests {
             hooks: Arc::new(HookEngine::new(config.hooks.clone())),
             mcp_clients: vec![],
             ltm: None,
+            cron: None,
             taint: None,
         })
     }
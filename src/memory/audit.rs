@@ -0,0 +1,144 @@
+//! Memory audit trail — what the agent remembered, when and why.
+//!
+//! Every write made by the `memory_set` and `memory_append` tools is appended
+//! as one JSON line to `~/.zeptoclaw/memory/audit.jsonl`. View it with
+//! `zeptoclaw memory audit`.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::error::Result;
+
+/// Kind of memory write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// New entry.
+    Created,
+    /// Existing key overwritten.
+    Updated,
+    /// Folded into an existing entry with the same or a near-identical value.
+    Merged,
+    /// Text added to an existing entry.
+    Appended,
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Merged => "merged",
+            Self::Appended => "appended",
+        };
+        f.write_str(s)
+    }
+}
+
+/// One audit trail line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// RFC 3339 time of the write.
+    pub timestamp: String,
+    pub action: AuditAction,
+    /// Key actually written (differs from the requested key after a merge).
+    pub key: String,
+    pub category: String,
+    /// Value or appended text as given by the agent.
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    /// The agent's stated reason for remembering this.
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+}
+
+/// Append-only JSONL audit log.
+#[derive(Debug, Clone)]
+pub struct MemoryAudit {
+    path: PathBuf,
+}
+
+impl Default for MemoryAudit {
+    fn default() -> Self {
+        Self::new(Config::data_dir().join("memory").join("audit.jsonl"))
+    }
+}
+
+impl MemoryAudit {
+    /// Audit log at a custom path. Useful for testing.
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Append a record.
+    pub fn record(&self, record: &AuditRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+
+    /// The last `limit` records, oldest first. Unparseable lines are skipped.
+    pub fn recent(&self, limit: usize) -> Result<Vec<AuditRecord>> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let records: Vec<AuditRecord> = content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let skip = records.len().saturating_sub(limit);
+        Ok(records.into_iter().skip(skip).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_recent() {
+        let dir = TempDir::new().unwrap();
+        let audit = MemoryAudit::new(dir.path().join("memory").join("audit.jsonl"));
+        assert!(audit.recent(10).unwrap().is_empty());
+
+        for (i, action) in [AuditAction::Created, AuditAction::Appended]
+            .into_iter()
+            .enumerate()
+        {
+            audit
+                .record(&AuditRecord {
+                    timestamp: format!("2026-10-16T10:0{}:00Z", i),
+                    action,
+                    key: "user:city".into(),
+                    category: "user".into(),
+                    value: "Berlin".into(),
+                    confidence: Some(0.9),
+                    reason: "user said so".into(),
+                    channel: None,
+                    session: None,
+                })
+                .unwrap();
+        }
+
+        let recent = audit.recent(1).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].action, AuditAction::Appended);
+        assert_eq!(audit.recent(10).unwrap().len(), 2);
+    }
+}
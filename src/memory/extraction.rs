@@ -0,0 +1,542 @@
+//! Automatic memory extraction — durable facts pulled from finished turns.
+//!
+//! When `memory.extraction.enabled` is set, the agent loop hands each
+//! completed exchange to a [`MemoryExtractor`], which asks a (preferably
+//! cheap) model for facts worth keeping. Facts below `min_confidence` or
+//! already in long-term memory are dropped. The rest wait in
+//! `~/.zeptoclaw/memory/pending.json` until approved with
+//! `zeptoclaw memory review`, which stores them as pinned memories; with
+//! `require_review = false` they go straight into long-term memory instead.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::error::{Result, ZeptoError};
+use crate::providers::{ChatOptions, LLMProvider};
+use crate::session::Message;
+use crate::tools::memory_write::MEMORY_CATEGORIES;
+
+use super::audit::{AuditAction, AuditRecord, MemoryAudit};
+use super::longterm::{value_similarity, LongTermMemory};
+use super::namespace::MemoryScope;
+
+/// Values at least this similar to a stored or pending memory are dropped.
+const DUPLICATE_THRESHOLD: f32 = 0.85;
+
+/// Longest slice of each side of the exchange sent to the extractor.
+const MAX_EXCHANGE_CHARS: usize = 4000;
+
+const EXTRACTION_PROMPT: &str = "You extract durable memories from a conversation turn. \
+Return ONLY a JSON array (possibly empty) of objects with fields: \
+\"key\" (namespaced, e.g. \"user:city\", \"preference:editor\"), \
+\"value\" (one self-contained sentence), \
+\"category\" (one of user, preference, fact, learning, project, contact), \
+\"confidence\" (0.0-1.0; 1.0 only if the user stated it directly), \
+\"reason\" (why it will matter in future conversations). \
+Only include stable facts about the user, their preferences, projects, people and decisions. \
+Skip small talk, one-off requests, anything temporary, and anything already listed as known.";
+
+/// Configuration for automatic memory extraction.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ExtractionConfig {
+    /// Run extraction after each completed turn.
+    pub enabled: bool,
+    /// Model for extraction; the agent's model when unset.
+    pub model: Option<String>,
+    /// Most facts kept from one turn.
+    pub max_facts: usize,
+    /// Facts below this confidence are dropped.
+    pub min_confidence: f32,
+    /// Queue facts for `zeptoclaw memory review` instead of storing them.
+    pub require_review: bool,
+    /// Timeout for the extraction call (seconds).
+    pub timeout_secs: u64,
+}
+
+impl Default for ExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: None,
+            max_facts: 5,
+            min_confidence: 0.6,
+            require_review: true,
+            timeout_secs: 20,
+        }
+    }
+}
+
+/// A fact proposed by the extraction model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedFact {
+    pub key: String,
+    pub value: String,
+    #[serde(default = "default_category")]
+    pub category: String,
+    #[serde(default)]
+    pub confidence: f32,
+    #[serde(default)]
+    pub reason: String,
+}
+
+fn default_category() -> String {
+    "fact".to_string()
+}
+
+/// Facts from the model's reply. Tolerates prose or code fences around the
+/// JSON array and skips malformed items.
+pub fn parse_extracted(text: &str) -> Vec<ExtractedFact> {
+    let (Some(start), Some(end)) = (text.find('['), text.rfind(']')) else {
+        return Vec::new();
+    };
+    if end < start {
+        return Vec::new();
+    }
+    let Ok(items) = serde_json::from_str::<Vec<serde_json::Value>>(&text[start..=end]) else {
+        return Vec::new();
+    };
+    items
+        .into_iter()
+        .filter_map(|item| serde_json::from_value::<ExtractedFact>(item).ok())
+        .map(|mut fact| {
+            fact.key = fact.key.trim().to_string();
+            fact.value = fact.value.trim().to_string();
+            fact.category = fact.category.trim().to_lowercase();
+            if !MEMORY_CATEGORIES.contains(&fact.category.as_str()) || fact.category == "pinned" {
+                fact.category = default_category();
+            }
+            fact.confidence = fact.confidence.clamp(0.0, 1.0);
+            fact
+        })
+        .filter(|fact| !fact.key.is_empty() && !fact.value.is_empty())
+        .collect()
+}
+
+fn clip(text: &str) -> String {
+    if text.chars().count() <= MAX_EXCHANGE_CHARS {
+        text.to_string()
+    } else {
+        let clipped: String = text.chars().take(MAX_EXCHANGE_CHARS).collect();
+        format!("{}…", clipped)
+    }
+}
+
+/// A fact waiting for review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingMemory {
+    /// Short ID used by `memory review`.
+    pub id: String,
+    #[serde(flatten)]
+    pub fact: ExtractedFact,
+    /// Channel and chat the fact came from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Memory namespace of the sender, when `memory.namespaces` is on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// RFC 3339 time of extraction.
+    pub created_at: String,
+}
+
+/// Review queue persisted as JSON.
+#[derive(Debug, Clone)]
+pub struct PendingMemories {
+    path: PathBuf,
+}
+
+impl Default for PendingMemories {
+    fn default() -> Self {
+        Self::new(Config::data_dir().join("memory").join("pending.json"))
+    }
+}
+
+impl PendingMemories {
+    /// Queue at a custom path. Useful for testing.
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// All pending memories, oldest first.
+    pub fn list(&self) -> Result<Vec<PendingMemory>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(text) if text.trim().is_empty() => Ok(Vec::new()),
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, pending: &[PendingMemory]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(pending)?)?;
+        Ok(())
+    }
+
+    /// Queue facts, skipping ones already pending for the same namespace.
+    /// Returns how many were added.
+    pub fn add(
+        &self,
+        facts: Vec<ExtractedFact>,
+        source: Option<&str>,
+        namespace: Option<&str>,
+    ) -> Result<usize> {
+        let mut pending = self.list()?;
+        let mut added = 0;
+        for fact in facts {
+            let duplicate = pending.iter().any(|p| {
+                p.namespace.as_deref() == namespace
+                    && value_similarity(&p.fact.value, &fact.value) >= DUPLICATE_THRESHOLD
+            });
+            if duplicate {
+                continue;
+            }
+            let now = chrono::Utc::now();
+            pending.push(PendingMemory {
+                id: format!("{:x}", now.timestamp_millis() + added as i64),
+                fact,
+                source: source.map(str::to_string),
+                namespace: namespace.map(str::to_string),
+                created_at: now.to_rfc3339(),
+            });
+            added += 1;
+        }
+        if added > 0 {
+            self.save(&pending)?;
+        }
+        Ok(added)
+    }
+
+    /// Remove and return a pending memory by ID.
+    pub fn take(&self, id: &str) -> Result<Option<PendingMemory>> {
+        let mut pending = self.list()?;
+        let Some(index) = pending.iter().position(|p| p.id == id) else {
+            return Ok(None);
+        };
+        let taken = pending.remove(index);
+        self.save(&pending)?;
+        Ok(Some(taken))
+    }
+}
+
+/// Store an approved memory as a pinned long-term entry in the namespace
+/// it came from. The original category is kept as a tag.
+pub async fn approve(
+    memory: &mut LongTermMemory,
+    audit: &MemoryAudit,
+    pending: &PendingMemory,
+) -> Result<()> {
+    let fact = &pending.fact;
+    let key = MemoryScope::new(pending.namespace.clone()).storage_key(&fact.key, false)?;
+    let tags = vec![fact.category.clone(), "auto-extracted".to_string()];
+    memory.set(&key, &fact.value, "pinned", tags, 1.0).await?;
+    memory.set_confidence(&key, fact.confidence)?;
+    audit.record(&AuditRecord {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        action: AuditAction::Created,
+        key,
+        category: "pinned".to_string(),
+        value: fact.value.clone(),
+        confidence: Some(fact.confidence),
+        reason: format!("approved in review: {}", fact.reason),
+        channel: pending.source.clone(),
+        session: None,
+    })
+}
+
+/// What one extraction run did.
+#[derive(Debug, Default)]
+pub struct ExtractionReport {
+    /// Facts proposed by the model.
+    pub proposed: usize,
+    /// Facts queued for review.
+    pub queued: usize,
+    /// Facts written straight to long-term memory.
+    pub stored: usize,
+}
+
+/// Runs extraction for finished turns.
+pub struct MemoryExtractor {
+    provider: Arc<dyn LLMProvider>,
+    model: String,
+    memory: Arc<Mutex<LongTermMemory>>,
+    pending: PendingMemories,
+    audit: MemoryAudit,
+    scope: MemoryScope,
+    config: ExtractionConfig,
+}
+
+impl MemoryExtractor {
+    /// Extractor using `config.model`, else `default_model`.
+    pub fn new(
+        provider: Arc<dyn LLMProvider>,
+        default_model: &str,
+        memory: Arc<Mutex<LongTermMemory>>,
+        config: ExtractionConfig,
+    ) -> Self {
+        Self {
+            provider,
+            model: config
+                .model
+                .clone()
+                .unwrap_or_else(|| default_model.to_string()),
+            memory,
+            pending: PendingMemories::default(),
+            audit: MemoryAudit::default(),
+            scope: MemoryScope::shared(),
+            config,
+        }
+    }
+
+    /// Read and write memories in the sender's namespace.
+    pub fn with_scope(mut self, scope: MemoryScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Use a custom review queue and audit log.
+    pub fn with_stores(mut self, pending: PendingMemories, audit: MemoryAudit) -> Self {
+        self.pending = pending;
+        self.audit = audit;
+        self
+    }
+
+    /// Ask the model for facts in one exchange.
+    async fn propose(
+        &self,
+        user: &str,
+        assistant: &str,
+        known: &[String],
+    ) -> Result<Vec<ExtractedFact>> {
+        let known = if known.is_empty() {
+            "(nothing yet)".to_string()
+        } else {
+            known.join("\n")
+        };
+        let messages = vec![
+            Message::system(EXTRACTION_PROMPT),
+            Message::user(&format!(
+                "Already known:\n{}\n\nUser:\n{}\n\nAssistant:\n{}",
+                known,
+                clip(user),
+                clip(assistant)
+            )),
+        ];
+        let options = ChatOptions::new()
+            .with_max_tokens(800)
+            .with_temperature(0.0);
+        let response = tokio::time::timeout(
+            Duration::from_secs(self.config.timeout_secs.max(1)),
+            self.provider
+                .chat(messages, vec![], Some(self.model.as_str()), options),
+        )
+        .await
+        .map_err(|_| ZeptoError::Tool("Memory extraction timed out".into()))??;
+        Ok(parse_extracted(&response.content))
+    }
+
+    /// Extract facts from one exchange and queue or store them.
+    pub async fn run(
+        &self,
+        user: &str,
+        assistant: &str,
+        source: Option<&str>,
+    ) -> Result<ExtractionReport> {
+        let known: Vec<String> = {
+            let memory = self.memory.lock().await;
+            memory
+                .list_all()
+                .iter()
+                .filter(|e| self.scope.can_see(&e.key))
+                .take(50)
+                .map(|e| format!("- {}", e.value))
+                .collect()
+        };
+        let proposed = self.propose(user, assistant, &known).await?;
+        let mut report = ExtractionReport {
+            proposed: proposed.len(),
+            ..Default::default()
+        };
+
+        let mut facts = Vec::new();
+        {
+            let memory = self.memory.lock().await;
+            for fact in proposed {
+                if fact.confidence < self.config.min_confidence
+                    || memory
+                        .find_similar_in(&fact.value, DUPLICATE_THRESHOLD, &self.scope)
+                        .is_some()
+                {
+                    continue;
+                }
+                facts.push(fact);
+                if facts.len() >= self.config.max_facts {
+                    break;
+                }
+            }
+        }
+
+        if self.config.require_review {
+            report.queued = self.pending.add(facts, source, self.scope.namespace())?;
+            return Ok(report);
+        }
+
+        let mut memory = self.memory.lock().await;
+        for fact in facts {
+            let Ok(key) = self.scope.storage_key(&fact.key, false) else {
+                continue;
+            };
+            if let Err(e) = memory
+                .set(
+                    &key,
+                    &fact.value,
+                    &fact.category,
+                    vec!["auto-extracted".into()],
+                    1.0,
+                )
+                .await
+            {
+                warn!(key = %key, error = %e, "memory extraction: store failed");
+                continue;
+            }
+            memory.set_confidence(&key, fact.confidence)?;
+            self.audit.record(&AuditRecord {
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                action: AuditAction::Created,
+                key,
+                category: fact.category.clone(),
+                value: fact.value.clone(),
+                confidence: Some(fact.confidence),
+                reason: format!("auto-extracted: {}", fact.reason),
+                channel: source.map(str::to_string),
+                session: None,
+            })?;
+            report.stored += 1;
+        }
+        Ok(report)
+    }
+
+    /// Run in the background, logging the outcome. Never fails the turn.
+    pub fn spawn(self, user: String, assistant: String, source: Option<String>) {
+        tokio::spawn(async move {
+            match self.run(&user, &assistant, source.as_deref()).await {
+                Ok(report) if report.queued + report.stored > 0 => info!(
+                    queued = report.queued,
+                    stored = report.stored,
+                    "memory extraction: new memories"
+                ),
+                Ok(report) => debug!(proposed = report.proposed, "memory extraction: nothing new"),
+                Err(e) => warn!(error = %e, "memory extraction failed"),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{LLMResponse, ToolDefinition};
+    use async_trait::async_trait;
+    use tempfile::TempDir;
+
+    struct CannedProvider(&'static str);
+
+    #[async_trait]
+    impl LLMProvider for CannedProvider {
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            Ok(LLMResponse::text(self.0))
+        }
+
+        fn default_model(&self) -> &str {
+            "canned"
+        }
+
+        fn name(&self) -> &str {
+            "canned"
+        }
+    }
+
+    const REPLY: &str = "Here you go:\n```json\n[\
+        {\"key\": \"user:city\", \"value\": \"The user lives in Lisbon\", \"category\": \"user\", \"confidence\": 0.95, \"reason\": \"location for local advice\"},\
+        {\"key\": \"pref:theme\", \"value\": \"User prefers dark mode\", \"category\": \"preference\", \"confidence\": 0.9, \"reason\": \"ui\"},\
+        {\"key\": \"guess\", \"value\": \"Maybe likes jazz\", \"confidence\": 0.3},\
+        {\"value\": \"no key\"}\
+    ]\n```";
+
+    #[test]
+    fn test_parse_extracted() {
+        let facts = parse_extracted(REPLY);
+        assert_eq!(facts.len(), 3);
+        assert_eq!(facts[0].key, "user:city");
+        assert_eq!(facts[2].category, "fact");
+        assert!(parse_extracted("nothing to remember").is_empty());
+        assert!(parse_extracted("[not json]").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_extraction_queues_new_facts_for_review() {
+        let dir = TempDir::new().unwrap();
+        let memory = Arc::new(Mutex::new(
+            LongTermMemory::with_path(dir.path().join("longterm.json")).unwrap(),
+        ));
+        memory
+            .lock()
+            .await
+            .set(
+                "pref:theme",
+                "User prefers dark mode",
+                "preference",
+                vec![],
+                1.0,
+            )
+            .await
+            .unwrap();
+        let pending = PendingMemories::new(dir.path().join("pending.json"));
+        let audit = MemoryAudit::new(dir.path().join("audit.jsonl"));
+        let extractor = MemoryExtractor::new(
+            Arc::new(CannedProvider(REPLY)),
+            "model",
+            memory.clone(),
+            ExtractionConfig::default(),
+        )
+        .with_stores(pending.clone(), audit.clone());
+
+        let report = extractor
+            .run("I just moved to Lisbon", "Welcome!", Some("telegram:1"))
+            .await
+            .unwrap();
+        // Known fact and low-confidence guess are dropped.
+        assert_eq!((report.proposed, report.queued), (3, 1));
+        // A second run does not queue the same fact again.
+        let report = extractor.run("again", "ok", None).await.unwrap();
+        assert_eq!(report.queued, 0);
+
+        let queued = pending.list().unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].source.as_deref(), Some("telegram:1"));
+
+        let item = pending.take(&queued[0].id).unwrap().unwrap();
+        let mut memory = memory.lock().await;
+        approve(&mut memory, &audit, &item).await.unwrap();
+        let entry = memory.get_readonly("user:city").unwrap();
+        assert_eq!(entry.category, "pinned");
+        assert_eq!(entry.tags, vec!["user", "auto-extracted"]);
+        assert!(pending.list().unwrap().is_empty());
+        assert_eq!(audit.recent(10).unwrap().len(), 1);
+    }
+}
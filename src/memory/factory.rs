@@ -56,7 +56,7 @@ pub fn create_searcher_with_provider(
             #[cfg(feature = "memory-embedding")]
             {
                 if let Some(p) = provider {
-                    let path = crate::config::Config::dir()
+                    let path = crate::config::Config::data_dir()
                         .join("memory")
                         .join("embeddings.json");
                     Arc::new(super::embedding_searcher::EmbeddingSearcher::new(p, path))
@@ -76,7 +76,7 @@ pub fn create_searcher_with_provider(
             #[cfg(feature = "memory-hnsw")]
             {
                 if let Some(p) = provider {
-                    let path = crate::config::Config::dir()
+                    let path = crate::config::Config::data_dir()
                         .join("memory")
                         .join("hnsw_vectors.json");
                     Arc::new(super::hnsw_searcher::HnswSearcher::new(p, path))
@@ -2,18 +2,27 @@
 //!
 //! Provides [`start_hygiene_scheduler`] to run automated cleanup in a background
 //! Tokio task, and [`run_hygiene_cycle_memory_only`] for one-shot use.
+//!
+//! Each cycle also consolidates memory ([`consolidate`]): entries whose keys
+//! differ only in case or separators (`user:timezone`, `User:Time-Zone`) and
+//! hold different values are reported as conflicts, and near-duplicate values
+//! are merged into one entry.
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 use tracing::{info, warn};
 
-use crate::memory::longterm::LongTermMemory;
+use crate::error::Result;
+use crate::memory::longterm::{word_similarity, words, LongTermMemory, MemoryEntry};
+use crate::memory::namespace::owner;
 
 /// Configuration for the memory hygiene scheduler.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct HygieneConfig {
     /// Whether the scheduler is enabled.
@@ -26,6 +35,13 @@ pub struct HygieneConfig {
     pub max_entries: usize,
     /// Maximum recent CLI conversations to keep (reserved for future use).
     pub conversation_keep: usize,
+    /// Merge near-duplicates and look for conflicting entries each cycle.
+    pub consolidate: bool,
+    /// Word-set similarity at which two values count as duplicates.
+    pub merge_threshold: f32,
+    /// Keep only the most recently written or used entry of a conflict
+    /// instead of just reporting it.
+    pub resolve_conflicts: bool,
 }
 
 impl Default for HygieneConfig {
@@ -36,24 +52,245 @@ impl Default for HygieneConfig {
             expired_threshold: 0.1,
             max_entries: 1000,
             conversation_keep: 50,
+            consolidate: true,
+            merge_threshold: 0.85,
+            resolve_conflicts: false,
         }
     }
 }
 
 /// Summary of one hygiene cycle.
+#[derive(Debug, Default)]
 pub struct HygieneReport {
     pub expired_removed: usize,
     pub least_used_removed: usize,
     pub conversations_pruned: usize,
+    pub consolidation: ConsolidationReport,
 }
 
 impl HygieneReport {
     /// Total entries removed across all categories.
     pub fn total(&self) -> usize {
-        self.expired_removed + self.least_used_removed + self.conversations_pruned
+        self.expired_removed
+            + self.least_used_removed
+            + self.conversations_pruned
+            + self.consolidation.removed()
+    }
+}
+
+/// Near-duplicate folded into another entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedDuplicate {
+    pub kept: String,
+    pub removed: String,
+    pub similarity: f32,
+}
+
+/// Entries that store different values for the same key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryConflict {
+    /// `(key, value)` pairs, most recently written or used first.
+    pub entries: Vec<(String, String)>,
+    /// Key that was kept when `resolve_conflicts` is on.
+    pub resolved_to: Option<String>,
+}
+
+/// What [`consolidate`] found (and, unless a dry run, changed).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsolidationReport {
+    pub merged: Vec<MergedDuplicate>,
+    pub conflicts: Vec<MemoryConflict>,
+}
+
+impl ConsolidationReport {
+    /// Entries removed by merging or resolving conflicts.
+    pub fn removed(&self) -> usize {
+        let resolved: usize = self
+            .conflicts
+            .iter()
+            .filter(|c| c.resolved_to.is_some())
+            .map(|c| c.entries.len() - 1)
+            .sum();
+        self.merged.len() + resolved
+    }
+
+    /// Conflicts left for the user to settle.
+    pub fn unresolved(&self) -> impl Iterator<Item = &MemoryConflict> {
+        self.conflicts.iter().filter(|c| c.resolved_to.is_none())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.merged.is_empty() && self.conflicts.is_empty()
+    }
+
+    /// One-line summary.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} duplicate(s) merged, {} conflict(s) found ({} unresolved)",
+            self.merged.len(),
+            self.conflicts.len(),
+            self.unresolved().count()
+        )
+    }
+}
+
+/// Key with case and separators inside each `:` segment dropped, so
+/// `User:Time-Zone` and `user:timezone` compare equal.
+pub fn normalize_key(key: &str) -> String {
+    key.split(':')
+        .map(|segment| {
+            segment
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Grouping key: owning namespace plus the normalized key, so contacts'
+/// entries are never compared with each other.
+fn group_key(stored_key: &str) -> String {
+    match owner(stored_key) {
+        Some(namespace) => {
+            let key = &stored_key[namespace.len() + 2..];
+            format!("@{}/{}", namespace, normalize_key(key))
+        }
+        None => normalize_key(stored_key),
     }
 }
 
+/// Values need this many words before entries under different keys are
+/// merged; shorter values ("Alice", "UTC") match by accident too often.
+const MIN_MERGE_WORDS: usize = 3;
+
+/// Pinned first, then most recently written or used.
+fn keep_order(a: &MemoryEntry, b: &MemoryEntry) -> std::cmp::Ordering {
+    let pinned = |e: &MemoryEntry| e.category.eq_ignore_ascii_case("pinned");
+    pinned(b)
+        .cmp(&pinned(a))
+        .then(b.last_accessed.cmp(&a.last_accessed))
+        .then(b.access_count.cmp(&a.access_count))
+        .then(a.key.cmp(&b.key))
+}
+
+/// Work out merges and conflicts without touching anything.
+pub fn plan_consolidation(entries: &[&MemoryEntry], config: &HygieneConfig) -> ConsolidationReport {
+    let mut entries: Vec<&MemoryEntry> = entries
+        .iter()
+        .copied()
+        .filter(|e| e.category != "system" && !e.key.starts_with("system:"))
+        .collect();
+    entries.sort_by(|a, b| keep_order(a, b));
+    let word_sets: Vec<HashSet<String>> = entries.iter().map(|e| words(&e.value)).collect();
+
+    let mut report = ConsolidationReport::default();
+    let mut removed = HashSet::new();
+
+    // 1. Entries under the same normalized key: duplicates or conflicts.
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut group_order = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let name = group_key(&entry.key);
+        let group = groups.entry(name.clone()).or_default();
+        if group.is_empty() {
+            group_order.push(name);
+        }
+        group.push(i);
+    }
+    for name in &group_order {
+        // Each distinct value heads a cluster; restatements merge into it.
+        let mut heads: Vec<usize> = Vec::new();
+        for &other in &groups[name] {
+            let similar = heads
+                .iter()
+                .map(|&head| (head, word_similarity(&word_sets[head], &word_sets[other])))
+                .find(|(_, similarity)| *similarity >= config.merge_threshold);
+            match similar {
+                Some((head, similarity)) => {
+                    report.merged.push(MergedDuplicate {
+                        kept: entries[head].key.clone(),
+                        removed: entries[other].key.clone(),
+                        similarity,
+                    });
+                    removed.insert(other);
+                }
+                None => heads.push(other),
+            }
+        }
+        if heads.len() < 2 {
+            continue;
+        }
+        if config.resolve_conflicts {
+            removed.extend(heads.iter().skip(1).copied());
+        }
+        report.conflicts.push(MemoryConflict {
+            entries: heads
+                .iter()
+                .map(|&i| (entries[i].key.clone(), entries[i].value.clone()))
+                .collect(),
+            resolved_to: config
+                .resolve_conflicts
+                .then(|| entries[heads[0]].key.clone()),
+        });
+    }
+
+    // 2. Near-identical values under different keys in the same category
+    //    and namespace.
+    for keep in 0..entries.len() {
+        if removed.contains(&keep) || word_sets[keep].len() < MIN_MERGE_WORDS {
+            continue;
+        }
+        for other in keep + 1..entries.len() {
+            if removed.contains(&other)
+                || word_sets[other].len() < MIN_MERGE_WORDS
+                || owner(&entries[keep].key) != owner(&entries[other].key)
+                || !entries[keep]
+                    .category
+                    .eq_ignore_ascii_case(&entries[other].category)
+            {
+                continue;
+            }
+            let similarity = word_similarity(&word_sets[keep], &word_sets[other]);
+            if similarity >= config.merge_threshold {
+                report.merged.push(MergedDuplicate {
+                    kept: entries[keep].key.clone(),
+                    removed: entries[other].key.clone(),
+                    similarity,
+                });
+                removed.insert(other);
+            }
+        }
+    }
+
+    report
+}
+
+/// Merge near-duplicates and detect (optionally resolve) conflicting
+/// entries. With `dry_run` the report is computed but memory is unchanged.
+pub async fn consolidate(
+    memory: &mut LongTermMemory,
+    config: &HygieneConfig,
+    dry_run: bool,
+) -> Result<ConsolidationReport> {
+    let report = plan_consolidation(&memory.list_all(), config);
+    if dry_run {
+        return Ok(report);
+    }
+    for merge in &report.merged {
+        memory.merge_into(&merge.kept, &merge.removed).await?;
+    }
+    for conflict in &report.conflicts {
+        if conflict.resolved_to.is_some() {
+            for (key, _) in conflict.entries.iter().skip(1) {
+                memory.delete(key).await?;
+            }
+        }
+    }
+    Ok(report)
+}
+
 const LAST_HYGIENE_KEY: &str = "system:last_hygiene_at";
 
 /// Return true if hygiene should run given the last run timestamp and interval.
@@ -80,11 +317,7 @@ pub async fn run_hygiene_cycle_memory_only(
     memory: &mut LongTermMemory,
     config: &HygieneConfig,
 ) -> HygieneReport {
-    let mut report = HygieneReport {
-        expired_removed: 0,
-        least_used_removed: 0,
-        conversations_pruned: 0,
-    };
+    let mut report = HygieneReport::default();
 
     // 1. Remove expired entries (decay score below threshold)
     match memory.cleanup_expired(config.expired_threshold) {
@@ -100,7 +333,15 @@ pub async fn run_hygiene_cycle_memory_only(
         }
     }
 
-    // 3. Record last run timestamp
+    // 3. Merge duplicates and look for conflicting entries
+    if config.consolidate {
+        match consolidate(memory, config, false).await {
+            Ok(consolidation) => report.consolidation = consolidation,
+            Err(e) => warn!("Hygiene: consolidation failed: {}", e),
+        }
+    }
+
+    // 4. Record last run timestamp
     let now_str = chrono::Utc::now().timestamp().to_string();
     if let Err(e) = memory
         .set(LAST_HYGIENE_KEY, &now_str, "system", vec![], 0.1)
@@ -147,6 +388,17 @@ pub fn start_hygiene_scheduler(
                         report.expired_removed, report.least_used_removed
                     );
                 }
+                if !report.consolidation.is_empty() {
+                    info!("Hygiene: {}", report.consolidation.summary());
+                }
+                for conflict in report.consolidation.unresolved() {
+                    let keys: Vec<&str> =
+                        conflict.entries.iter().map(|(k, _)| k.as_str()).collect();
+                    warn!(
+                        "Hygiene: conflicting memories {} (run `zeptoclaw memory consolidate`)",
+                        keys.join(", ")
+                    );
+                }
             }
 
             // Re-check every hour
@@ -175,6 +427,8 @@ mod tests {
         assert!((config.expired_threshold - 0.1).abs() < f32::EPSILON);
         assert_eq!(config.max_entries, 1000);
         assert_eq!(config.conversation_keep, 50);
+        assert!(config.consolidate);
+        assert!(!config.resolve_conflicts);
     }
 
     #[test]
@@ -241,6 +495,7 @@ mod tests {
             expired_removed: 3,
             least_used_removed: 5,
             conversations_pruned: 2,
+            ..Default::default()
         };
         assert_eq!(report.total(), 10);
     }
@@ -253,4 +508,72 @@ mod tests {
         run_hygiene_cycle_memory_only(&mut mem, &config).await;
         assert!(last_run_timestamp(&mem).is_some());
     }
+
+    #[test]
+    fn test_normalize_key() {
+        assert_eq!(normalize_key("User:Time-Zone"), "user:timezone");
+        assert_eq!(normalize_key("user:time_zone"), "user:timezone");
+        assert_ne!(
+            normalize_key("user:timezone"),
+            normalize_key("work:timezone")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_consolidate_merges_and_reports_conflicts() {
+        let (mut mem, _dir) = temp_memory();
+        mem.set("user:timezone", "UTC+1", "user", vec![], 1.0)
+            .await
+            .unwrap();
+        mem.set("User:Time-Zone", "America/New_York", "user", vec![], 1.0)
+            .await
+            .unwrap();
+        mem.set(
+            "pref:editor",
+            "User edits code in Neovim",
+            "preference",
+            vec![],
+            1.0,
+        )
+        .await
+        .unwrap();
+        mem.set(
+            "pref:vim",
+            "user edits code in neovim.",
+            "preference",
+            vec![],
+            1.0,
+        )
+        .await
+        .unwrap();
+        mem.set("user:name", "Alice", "user", vec![], 1.0)
+            .await
+            .unwrap();
+        mem.set("contact:sister", "Alice", "contact", vec![], 1.0)
+            .await
+            .unwrap();
+
+        let config = HygieneConfig::default();
+        let preview = consolidate(&mut mem, &config, true).await.unwrap();
+        assert_eq!(preview.merged.len(), 1);
+        assert_eq!(preview.conflicts.len(), 1);
+        assert_eq!(mem.count(), 6);
+
+        let report = consolidate(&mut mem, &config, false).await.unwrap();
+        assert_eq!(report.removed(), 1);
+        assert_eq!(mem.count(), 5);
+        assert_eq!(report.conflicts[0].entries.len(), 2);
+        assert!(report.conflicts[0].resolved_to.is_none());
+
+        let config = HygieneConfig {
+            resolve_conflicts: true,
+            ..Default::default()
+        };
+        let report = consolidate(&mut mem, &config, false).await.unwrap();
+        assert!(report.merged.is_empty());
+        let kept = report.conflicts[0].resolved_to.clone().unwrap();
+        assert!(mem.get_readonly(&kept).is_some());
+        assert_eq!(mem.count(), 4);
+        assert!(mem.get_readonly("contact:sister").is_some());
+    }
 }
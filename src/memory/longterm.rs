@@ -16,6 +16,7 @@ use crate::error::{Result, ZeptoError};
 use crate::safety::sanitizer;
 
 use super::builtin_searcher::BuiltinSearcher;
+use super::namespace::MemoryScope;
 use super::traits::MemorySearcher;
 
 /// Returns the current unix epoch timestamp in seconds.
@@ -51,6 +52,10 @@ pub struct MemoryEntry {
     /// Importance weight (0.0-1.0+, default 1.0). Higher values decay slower.
     #[serde(default = "default_importance")]
     pub importance: f32,
+    /// How sure the agent was when storing this (0.0-1.0). `None` for entries
+    /// written without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
 }
 
 impl MemoryEntry {
@@ -68,6 +73,33 @@ impl MemoryEntry {
     }
 }
 
+/// Lowercased words of `text`, for duplicate detection.
+pub(crate) fn words(text: &str) -> std::collections::HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Word-set (Jaccard) similarity of two memory values in `[0.0, 1.0]`.
+/// Case, punctuation and word order are ignored, so restatements of the
+/// same fact score 1.0.
+pub fn value_similarity(a: &str, b: &str) -> f32 {
+    word_similarity(&words(a), &words(b))
+}
+
+/// Jaccard similarity of two word sets from [`words`].
+pub(crate) fn word_similarity(
+    a: &std::collections::HashSet<String>,
+    b: &std::collections::HashSet<String>,
+) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let shared = a.intersection(b).count();
+    shared as f32 / (a.len() + b.len() - shared) as f32
+}
+
 /// Long-term memory store persisted as JSON.
 pub struct LongTermMemory {
     entries: HashMap<String, MemoryEntry>,
@@ -90,7 +122,7 @@ impl LongTermMemory {
     /// (`~/.zeptoclaw/memory/longterm.json`). Creates the file and parent
     /// directories if they do not exist.
     pub fn new() -> Result<Self> {
-        let path = Config::dir().join("memory").join("longterm.json");
+        let path = Config::data_dir().join("memory").join("longterm.json");
         Self::with_path(path)
     }
 
@@ -160,6 +192,7 @@ impl LongTermMemory {
                 access_count: 0,
                 tags: tags.clone(),
                 importance,
+                confidence: None,
             };
             self.entries.insert(key.to_string(), entry);
         }
@@ -190,6 +223,88 @@ impl LongTermMemory {
         self.entries.get(key)
     }
 
+    /// Record how confident the agent is in an entry. Returns `false` if the
+    /// key does not exist. Saves to disk.
+    pub fn set_confidence(&mut self, key: &str, confidence: f32) -> Result<bool> {
+        let Some(entry) = self.entries.get_mut(key) else {
+            return Ok(false);
+        };
+        entry.confidence = Some(confidence.clamp(0.0, 1.0));
+        self.save()?;
+        Ok(true)
+    }
+
+    /// The entry whose value is most similar to `value` (see
+    /// [`value_similarity`]), if any reaches `threshold`.
+    pub fn find_similar(&self, value: &str, threshold: f32) -> Option<(&MemoryEntry, f32)> {
+        self.entries
+            .values()
+            .map(|entry| (entry, value_similarity(&entry.value, value)))
+            .filter(|(_, score)| *score >= threshold)
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    /// [`find_similar`](Self::find_similar) limited to entries visible in `scope`.
+    pub fn find_similar_in(
+        &self,
+        value: &str,
+        threshold: f32,
+        scope: &MemoryScope,
+    ) -> Option<(&MemoryEntry, f32)> {
+        self.entries
+            .values()
+            .filter(|entry| scope.can_see(&entry.key))
+            .map(|entry| (entry, value_similarity(&entry.value, value)))
+            .filter(|(_, score)| *score >= threshold)
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    /// Fold the entry at `other` into the entry at `keep` and delete `other`.
+    /// The kept value is unchanged; tags are unioned, usage counts summed and
+    /// importance/confidence take the higher of the two. Returns `false` if
+    /// either key is missing.
+    pub async fn merge_into(&mut self, keep: &str, other: &str) -> Result<bool> {
+        if keep == other || !self.entries.contains_key(keep) {
+            return Ok(false);
+        }
+        let Some(absorbed) = self.entries.remove(other) else {
+            return Ok(false);
+        };
+        let Some(entry) = self.entries.get_mut(keep) else {
+            return Ok(false);
+        };
+        for tag in absorbed.tags {
+            if !entry.tags.contains(&tag) {
+                entry.tags.push(tag);
+            }
+        }
+        entry.access_count += absorbed.access_count;
+        entry.created_at = entry.created_at.min(absorbed.created_at);
+        entry.last_accessed = entry.last_accessed.max(absorbed.last_accessed);
+        entry.importance = entry.importance.max(absorbed.importance);
+        entry.confidence = match (entry.confidence, absorbed.confidence) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        let searchable = format!(
+            "{} {} {} {}",
+            entry.key,
+            entry.value,
+            entry.category,
+            entry.tags.join(" ")
+        );
+
+        self.save()?;
+        self.searcher.remove(other).await?;
+        self.searcher.index(keep, &searchable).await?;
+        Ok(true)
+    }
+
+    /// Path of the JSON file backing this store.
+    pub fn storage_path(&self) -> &std::path::Path {
+        &self.storage_path
+    }
+
     /// Delete a memory entry by key. Returns `true` if the entry existed
     /// (and was removed), `false` otherwise. Saves to disk and removes from
     /// searcher index on deletion.
@@ -425,6 +540,7 @@ mod tests {
             access_count: 5,
             tags: vec!["identity".to_string()],
             importance: 1.0,
+            confidence: None,
         };
 
         assert_eq!(entry.key, "user:name");
@@ -1034,4 +1150,82 @@ mod tests {
             .await;
         assert!(result.is_err(), "Should reject role marker injection");
     }
+
+    #[test]
+    fn test_value_similarity() {
+        assert_eq!(
+            value_similarity("User prefers dark mode.", "user PREFERS dark-mode"),
+            1.0
+        );
+        assert!(value_similarity("User lives in Berlin", "User lives in Paris") < 0.85);
+        assert_eq!(value_similarity("tea", "coffee"), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_and_set_confidence() {
+        let (mut mem, _dir) = temp_memory();
+        mem.set(
+            "pref:theme",
+            "User prefers dark mode",
+            "preference",
+            vec![],
+            1.0,
+        )
+        .await
+        .unwrap();
+        mem.set("user:city", "User lives in Berlin", "user", vec![], 1.0)
+            .await
+            .unwrap();
+
+        let (entry, score) = mem.find_similar("user prefers Dark Mode!", 0.85).unwrap();
+        assert_eq!(entry.key, "pref:theme");
+        assert_eq!(score, 1.0);
+        assert!(mem.find_similar("User lives in Paris", 0.85).is_none());
+
+        assert!(mem.set_confidence("user:city", 1.5).unwrap());
+        assert!(!mem.set_confidence("missing", 0.5).unwrap());
+        let reloaded = LongTermMemory::with_path(mem.storage_path().to_path_buf()).unwrap();
+        assert_eq!(
+            reloaded.get_readonly("user:city").unwrap().confidence,
+            Some(1.0)
+        );
+        assert_eq!(
+            reloaded.get_readonly("pref:theme").unwrap().confidence,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_into() {
+        let (mut mem, _dir) = temp_memory();
+        mem.set(
+            "pref:theme",
+            "Dark mode",
+            "preference",
+            vec!["ui".into()],
+            0.5,
+        )
+        .await
+        .unwrap();
+        mem.set(
+            "pref:colors",
+            "dark mode",
+            "preference",
+            vec!["look".into()],
+            0.9,
+        )
+        .await
+        .unwrap();
+        mem.set_confidence("pref:colors", 0.7).unwrap();
+
+        assert!(mem.merge_into("pref:theme", "pref:colors").await.unwrap());
+        assert!(!mem.merge_into("pref:theme", "pref:colors").await.unwrap());
+        assert!(mem.get_readonly("pref:colors").is_none());
+        let kept = mem.get_readonly("pref:theme").unwrap();
+        assert_eq!(kept.value, "Dark mode");
+        assert_eq!(kept.tags, vec!["ui", "look"]);
+        assert_eq!(kept.importance, 0.9);
+        assert_eq!(kept.confidence, Some(0.7));
+        assert_eq!(mem.search("look").len(), 1);
+    }
 }
@@ -1,15 +1,19 @@
 //! Workspace memory utilities (OpenClaw-style markdown memory).
 
+pub mod audit;
 #[cfg(feature = "memory-bm25")]
 pub mod bm25_searcher;
 pub mod builtin_searcher;
 #[cfg(feature = "memory-embedding")]
 pub mod embedding_searcher;
+pub mod extraction;
 pub mod factory;
 #[cfg(feature = "memory-hnsw")]
 pub mod hnsw_searcher;
 pub mod hygiene;
 pub mod longterm;
+pub mod namespace;
+pub mod notes_sync;
 pub mod snapshot;
 pub mod traits;
 
@@ -280,11 +284,29 @@ fn read_workspace_memory_sync(
 ///
 /// Collects pinned memories first (always included), then query-matched
 /// results from the user's message. Stops when budget_chars is reached.
-/// Returns empty string if no memories qualify.
+/// Returns empty string if no memories qualify. Only shared entries are
+/// used; see [`build_memory_injection_scoped`] for per-contact memory.
 pub fn build_memory_injection(
     ltm: &crate::memory::longterm::LongTermMemory,
     user_message: &str,
     budget_chars: usize,
+) -> String {
+    build_memory_injection_scoped(
+        ltm,
+        user_message,
+        budget_chars,
+        &namespace::MemoryScope::shared(),
+    )
+}
+
+/// Like [`build_memory_injection`], limited to entries visible in `scope`
+/// (the sender's own namespace plus shared entries). Namespaced keys are
+/// shown without their prefix.
+pub fn build_memory_injection_scoped(
+    ltm: &crate::memory::longterm::LongTermMemory,
+    user_message: &str,
+    budget_chars: usize,
+    scope: &namespace::MemoryScope,
 ) -> String {
     let mut parts = Vec::new();
     let mut used_chars = 0usize;
@@ -293,8 +315,8 @@ pub fn build_memory_injection(
     // 1. Always inject pinned memories
     let pinned = ltm.list_by_category("pinned");
     let mut pinned_lines = Vec::new();
-    for entry in pinned {
-        let line = format!("- {}: {}", entry.key, entry.value);
+    for entry in pinned.into_iter().filter(|e| scope.can_see(&e.key)) {
+        let line = format!("- {}: {}", scope.display_key(&entry.key), entry.value);
         if used_chars + line.len() + 1 > budget_chars {
             break;
         }
@@ -307,11 +329,11 @@ pub fn build_memory_injection(
     let mut relevant_lines = Vec::new();
     if !user_message.trim().is_empty() {
         let results = ltm.search(user_message);
-        for entry in results.iter().take(5) {
+        for entry in results.iter().filter(|e| scope.can_see(&e.key)).take(5) {
             if seen_keys.contains(&entry.key) {
                 continue;
             }
-            let line = format!("- {}: {}", entry.key, entry.value);
+            let line = format!("- {}: {}", scope.display_key(&entry.key), entry.value);
             if used_chars + line.len() + 1 > budget_chars {
                 break;
             }
@@ -663,4 +685,36 @@ mod tests {
         assert!(result.contains("user:name: Alice"));
         assert!(result.contains("fact:rust: Rust is fast"));
     }
+
+    #[tokio::test]
+    async fn test_build_memory_injection_scoped_hides_other_contacts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("lt.json");
+        let mut ltm = crate::memory::longterm::LongTermMemory::with_path(path).unwrap();
+        ltm.set("@anna/user:name", "Anna", "pinned", vec![], 1.0)
+            .await
+            .unwrap();
+        ltm.set("@bob/user:name", "Bob", "pinned", vec![], 1.0)
+            .await
+            .unwrap();
+        ltm.set(
+            "fact:wifi",
+            "Wifi password is on the fridge",
+            "pinned",
+            vec![],
+            1.0,
+        )
+        .await
+        .unwrap();
+
+        let anna = namespace::MemoryScope::new(Some("anna".into()));
+        let result = build_memory_injection_scoped(&ltm, "", 2000, &anna);
+        assert!(result.contains("- user:name: Anna"));
+        assert!(!result.contains("Bob"));
+        assert!(result.contains("fact:wifi"));
+
+        let shared = build_memory_injection(&ltm, "", 2000);
+        assert!(!shared.contains("Anna") && !shared.contains("Bob"));
+        assert!(shared.contains("fact:wifi"));
+    }
 }
@@ -0,0 +1,198 @@
+//! Per-contact memory namespaces.
+//!
+//! When several people talk to the same bot (a family sharing a Telegram
+//! bot, say), "my name is Anna" from one of them must not end up in the
+//! context of the others. With `memory.namespaces.enabled`, each sender gets
+//! a namespace — `channel:sender_id`, or a name from `memory.namespaces.aliases`
+//! so the same person is recognised across channels — and long-term memory
+//! entries written on their behalf are stored under `@<namespace>/<key>`.
+//!
+//! A [`MemoryScope`] decides what a turn can see: its own namespaced entries
+//! plus shared (un-prefixed) ones. Heartbeat, cron and CLI turns have no
+//! namespace and only see shared entries.
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, ZeptoError};
+
+/// Configuration for sender-scoped memory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct MemoryNamespaceConfig {
+    /// Give each sender their own memory namespace.
+    pub enabled: bool,
+    /// Sender → namespace name, matched as `channel:sender_id` first and then
+    /// as the bare `sender_id` (e.g. `"telegram:12345": "anna"`,
+    /// `"whatsapp:+4915112345678": "anna"`).
+    pub aliases: HashMap<String, String>,
+}
+
+impl MemoryNamespaceConfig {
+    /// Namespace for a message sender, or `None` when namespaces are off or
+    /// the sender is a subagent. ZeptoClaw's own messages (heartbeat, cron)
+    /// are kept out by the agent loop.
+    pub fn resolve(&self, channel: &str, sender_id: &str) -> Option<String> {
+        if !self.enabled || sender_id.is_empty() || channel == "subagent" {
+            return None;
+        }
+        let qualified = format!("{}:{}", channel, sender_id);
+        let namespace = self
+            .aliases
+            .get(&qualified)
+            .or_else(|| self.aliases.get(sender_id))
+            .map(|alias| alias.trim().to_string())
+            .filter(|alias| !alias.is_empty())
+            .unwrap_or(qualified);
+        // `/` ends the namespace in a stored key.
+        Some(namespace.replace('/', "_"))
+    }
+}
+
+/// Namespace that owns a stored key, if any.
+pub fn owner(stored_key: &str) -> Option<&str> {
+    stored_key
+        .strip_prefix('@')
+        .and_then(|rest| rest.split_once('/'))
+        .map(|(namespace, _)| namespace)
+}
+
+/// What one turn can read and where its writes go.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryScope {
+    namespace: Option<String>,
+}
+
+impl MemoryScope {
+    /// Scope for a namespace; `None` is the shared scope.
+    pub fn new(namespace: Option<String>) -> Self {
+        Self { namespace }
+    }
+
+    /// The shared scope: sees only shared entries.
+    pub fn shared() -> Self {
+        Self::default()
+    }
+
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    fn prefix(&self) -> Option<String> {
+        self.namespace.as_ref().map(|ns| format!("@{}/", ns))
+    }
+
+    /// Stored key for a write. Personal unless `shared` is set or there is
+    /// no namespace. Keys of other namespaces are rejected.
+    pub fn storage_key(&self, key: &str, shared: bool) -> Result<String> {
+        let key = key.trim();
+        if owner(key).is_some() {
+            return match self.prefix() {
+                Some(prefix) if key.starts_with(&prefix) => Ok(key.to_string()),
+                _ => Err(ZeptoError::Tool(format!(
+                    "Memory '{}' belongs to another person",
+                    key
+                ))),
+            };
+        }
+        Ok(match self.prefix() {
+            Some(prefix) if !shared => format!("{}{}", prefix, key),
+            _ => key.to_string(),
+        })
+    }
+
+    /// Stored keys to try when reading `key`: personal first, then shared.
+    pub fn lookup_keys(&self, key: &str) -> Result<Vec<String>> {
+        let personal = self.storage_key(key, false)?;
+        let shared = key.trim().to_string();
+        Ok(if personal == shared || owner(&shared).is_some() {
+            vec![personal]
+        } else {
+            vec![personal, shared]
+        })
+    }
+
+    /// Whether an entry stored under `stored_key` is visible in this scope.
+    pub fn can_see(&self, stored_key: &str) -> bool {
+        match owner(stored_key) {
+            None => true,
+            Some(namespace) => self.namespace.as_deref() == Some(namespace),
+        }
+    }
+
+    /// `stored_key` without this scope's namespace prefix.
+    pub fn display_key<'a>(&self, stored_key: &'a str) -> &'a str {
+        self.prefix()
+            .and_then(|prefix| stored_key.strip_prefix(prefix.as_str()))
+            .unwrap_or(stored_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> MemoryNamespaceConfig {
+        MemoryNamespaceConfig {
+            enabled: true,
+            aliases: HashMap::from([
+                ("telegram:111".to_string(), "anna".to_string()),
+                ("+4915100000".to_string(), "anna".to_string()),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let config = config();
+        assert_eq!(config.resolve("telegram", "111").as_deref(), Some("anna"));
+        assert_eq!(
+            config.resolve("whatsapp", "+4915100000").as_deref(),
+            Some("anna")
+        );
+        assert_eq!(
+            config.resolve("telegram", "222").as_deref(),
+            Some("telegram:222")
+        );
+        assert_eq!(config.resolve("subagent", "x"), None);
+        assert_eq!(
+            config.resolve("telegram", "system").as_deref(),
+            Some("telegram:system")
+        );
+        assert_eq!(
+            MemoryNamespaceConfig::default().resolve("telegram", "111"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scope_keys_and_visibility() {
+        let anna = MemoryScope::new(Some("anna".into()));
+        let shared = MemoryScope::shared();
+
+        assert_eq!(
+            anna.storage_key("user:name", false).unwrap(),
+            "@anna/user:name"
+        );
+        assert_eq!(anna.storage_key("fact:wifi", true).unwrap(), "fact:wifi");
+        assert_eq!(
+            anna.storage_key("@anna/user:name", false).unwrap(),
+            "@anna/user:name"
+        );
+        assert!(anna.storage_key("@bob/user:name", false).is_err());
+        assert_eq!(shared.storage_key("user:name", false).unwrap(), "user:name");
+        assert_eq!(
+            anna.lookup_keys("user:name").unwrap(),
+            vec!["@anna/user:name", "user:name"]
+        );
+
+        assert!(anna.can_see("@anna/user:name"));
+        assert!(anna.can_see("fact:wifi"));
+        assert!(!anna.can_see("@bob/user:name"));
+        assert!(!shared.can_see("@anna/user:name"));
+        assert_eq!(anna.display_key("@anna/user:name"), "user:name");
+        assert_eq!(owner("@telegram:222/user:name"), Some("telegram:222"));
+    }
+}
@@ -0,0 +1,755 @@
+//! Notes sync — mirror workspace markdown memory into an external note system.
+//!
+//! `MEMORY.md` and `memory/**/*.md` are synced both ways with an Obsidian vault
+//! folder ([`ObsidianStore`]) or a Notion database ([`NotionStore`]). Each run
+//! is a three-way merge against the state recorded by the previous run: a note
+//! changed on one side only is copied to the other, a note deleted on one side
+//! and untouched on the other is deleted, and a note changed on both sides is a
+//! conflict resolved by [`ConflictPolicy`].
+//!
+//! Provides [`sync_notes`] for one-shot use (`zeptoclaw memory sync`) and
+//! [`start_notes_sync_scheduler`] for the gateway.
+
+mod notion;
+mod obsidian;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::error::{Result, ZeptoError};
+
+pub use notion::NotionStore;
+pub use obsidian::ObsidianStore;
+
+/// Marker in the file name of conflict copies; such files are never synced.
+const CONFLICT_MARKER: &str = ".conflict-";
+
+/// What to do when a note changed on both sides since the last sync.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Keep the local note, save the remote version next to it as a
+    /// `.conflict-<target>-<timestamp>.md` copy and push the local note.
+    #[default]
+    KeepBoth,
+    /// Overwrite the remote note with the local one.
+    PreferLocal,
+    /// Overwrite the local note with the remote one.
+    PreferRemote,
+}
+
+/// Configuration for notes sync.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct NotesSyncConfig {
+    /// Whether the gateway runs sync in the background.
+    pub enabled: bool,
+    /// Minutes between background sync runs.
+    pub interval_minutes: u64,
+    /// Conflict resolution when both sides changed.
+    pub conflict: ConflictPolicy,
+    /// Delete a note on one side when it was deleted on the other.
+    pub propagate_deletes: bool,
+    /// Obsidian vault target.
+    pub obsidian: Option<ObsidianSyncConfig>,
+    /// Notion database target.
+    pub notion: Option<NotionSyncConfig>,
+}
+
+impl Default for NotesSyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: 30,
+            conflict: ConflictPolicy::KeepBoth,
+            propagate_deletes: true,
+            obsidian: None,
+            notion: None,
+        }
+    }
+}
+
+/// Obsidian vault target.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ObsidianSyncConfig {
+    /// Vault directory (`~` is expanded).
+    pub vault_path: String,
+    /// Folder inside the vault that mirrors the workspace memory.
+    pub folder: String,
+}
+
+impl Default for ObsidianSyncConfig {
+    fn default() -> Self {
+        Self {
+            vault_path: String::new(),
+            folder: "ZeptoClaw".to_string(),
+        }
+    }
+}
+
+/// Notion database target. One page per note, titled with its path.
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct NotionSyncConfig {
+    /// Internal integration token (the database must be shared with it).
+    pub token: String,
+    /// Database ID.
+    pub database_id: String,
+    /// Title property holding the note path.
+    pub title_property: String,
+}
+
+impl Default for NotionSyncConfig {
+    fn default() -> Self {
+        Self {
+            token: String::new(),
+            database_id: String::new(),
+            title_property: "Name".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Debug for NotionSyncConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotionSyncConfig")
+            .field("token", &"[REDACTED]")
+            .field("database_id", &self.database_id)
+            .field("title_property", &self.title_property)
+            .finish()
+    }
+}
+
+/// A note as listed by a [`NotesStore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteNote {
+    /// Workspace-relative path, `/`-separated.
+    pub path: String,
+    /// Store-specific ID (vault path, Notion page ID).
+    pub id: String,
+    /// Changes whenever the note changes (content hash, edit time).
+    pub version: String,
+}
+
+/// An external note system that memory is synced with.
+#[async_trait]
+pub trait NotesStore: Send + Sync {
+    /// Target name ("obsidian", "notion"), also used for the state file.
+    fn name(&self) -> &'static str;
+
+    /// All notes with their current versions.
+    async fn list(&self) -> Result<Vec<RemoteNote>>;
+
+    /// Markdown content of a note.
+    async fn read(&self, note: &RemoteNote) -> Result<String>;
+
+    /// Create or replace the note at `path`; `existing` is the ID from the
+    /// last sync. Returns the note as stored.
+    async fn write(&self, path: &str, existing: Option<&str>, content: &str) -> Result<RemoteNote>;
+
+    /// Delete a note (into the store's trash where it has one).
+    async fn delete(&self, note: &RemoteNote) -> Result<()>;
+}
+
+/// Stores for every configured target.
+pub fn configured_stores(config: &NotesSyncConfig) -> Result<Vec<Box<dyn NotesStore>>> {
+    let mut stores: Vec<Box<dyn NotesStore>> = Vec::new();
+    if let Some(obsidian) = &config.obsidian {
+        stores.push(Box::new(ObsidianStore::new(obsidian)?));
+    }
+    if let Some(notion) = &config.notion {
+        stores.push(Box::new(NotionStore::new(notion.clone())?));
+    }
+    Ok(stores)
+}
+
+/// Default state file for a target.
+pub fn default_state_path(store: &str) -> PathBuf {
+    crate::config::Config::data_dir()
+        .join("memory")
+        .join(format!("notes_sync_{}.json", store))
+}
+
+/// Hex SHA-256 of note content.
+pub fn content_hash(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// Whether a relative path may be synced: `MEMORY.md`/`memory.md` or a
+/// markdown file under `memory/`, without `..` and not a conflict copy.
+pub fn is_syncable_path(path: &str) -> bool {
+    if path.contains('\\') || path.contains(CONFLICT_MARKER) || !path.ends_with(".md") {
+        return false;
+    }
+    let parts: Vec<&str> = path.split('/').collect();
+    if parts
+        .iter()
+        .any(|p| p.is_empty() || *p == "." || *p == ".." || p.starts_with('.'))
+    {
+        return false;
+    }
+    match parts.as_slice() {
+        [file] => *file == "MEMORY.md" || *file == "memory.md",
+        [dir, ..] => *dir == "memory",
+        [] => false,
+    }
+}
+
+/// Sync state of one note after the last successful run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncedNote {
+    pub local_hash: String,
+    pub remote_id: String,
+    pub remote_version: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    notes: BTreeMap<String, SyncedNote>,
+}
+
+impl SyncState {
+    fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| {
+                ZeptoError::Config(format!("Invalid notes sync state {:?}: {}", path, e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Step decided for one note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    Push,
+    Pull,
+    DeleteLocal,
+    DeleteRemote,
+    Conflict,
+    /// Gone on both sides; drop the state entry.
+    Forget,
+}
+
+/// Decide what to do with one note from its local hash, remote version and
+/// last synced state. `None` means it is already in sync.
+pub fn plan(
+    local: Option<&str>,
+    remote: Option<&str>,
+    synced: Option<&SyncedNote>,
+    propagate_deletes: bool,
+) -> Option<SyncAction> {
+    match (local, remote, synced) {
+        (None, None, Some(_)) => Some(SyncAction::Forget),
+        (None, None, None) => None,
+        (Some(_), None, None) => Some(SyncAction::Push),
+        (None, Some(_), None) => Some(SyncAction::Pull),
+        (Some(l), None, Some(s)) => {
+            if propagate_deletes && l == s.local_hash {
+                Some(SyncAction::DeleteLocal)
+            } else {
+                Some(SyncAction::Push)
+            }
+        }
+        (None, Some(r), Some(s)) => {
+            if propagate_deletes && r == s.remote_version {
+                Some(SyncAction::DeleteRemote)
+            } else {
+                Some(SyncAction::Pull)
+            }
+        }
+        (Some(_), Some(_), None) => Some(SyncAction::Conflict),
+        (Some(l), Some(r), Some(s)) => match (l != s.local_hash, r != s.remote_version) {
+            (false, false) => None,
+            (true, false) => Some(SyncAction::Push),
+            (false, true) => Some(SyncAction::Pull),
+            (true, true) => Some(SyncAction::Conflict),
+        },
+    }
+}
+
+/// Summary of one sync run.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub pushed: Vec<String>,
+    pub pulled: Vec<String>,
+    pub deleted_local: Vec<String>,
+    pub deleted_remote: Vec<String>,
+    /// Conflicts and how they were resolved.
+    pub conflicts: Vec<String>,
+    /// Remote notes ignored because their path is not syncable.
+    pub skipped: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl SyncReport {
+    /// Notes changed on either side.
+    pub fn total_changes(&self) -> usize {
+        self.pushed.len()
+            + self.pulled.len()
+            + self.deleted_local.len()
+            + self.deleted_remote.len()
+            + self.conflicts.len()
+    }
+
+    /// One-line summary.
+    pub fn summary(&self) -> String {
+        let mut line = format!(
+            "{} pushed, {} pulled, {} deleted locally, {} deleted remotely, {} conflicts",
+            self.pushed.len(),
+            self.pulled.len(),
+            self.deleted_local.len(),
+            self.deleted_remote.len(),
+            self.conflicts.len()
+        );
+        if !self.skipped.is_empty() {
+            line.push_str(&format!(", {} skipped", self.skipped.len()));
+        }
+        if !self.errors.is_empty() {
+            line.push_str(&format!(", {} errors", self.errors.len()));
+        }
+        line
+    }
+}
+
+/// Workspace memory notes by relative path.
+fn local_notes(workspace: &Path) -> BTreeMap<String, PathBuf> {
+    let workspace_str = workspace.to_string_lossy().to_string();
+    let mut files = Vec::new();
+    super::collect_if_markdown(&workspace.join("MEMORY.md"), &workspace_str, &mut files);
+    super::collect_if_markdown(&workspace.join("memory.md"), &workspace_str, &mut files);
+    super::collect_markdown_dir(&workspace.join("memory"), &workspace_str, &mut files);
+
+    files
+        .into_iter()
+        .filter_map(|file| {
+            let rel = file.strip_prefix(workspace).ok()?;
+            let rel = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            is_syncable_path(&rel).then_some((rel, file))
+        })
+        .collect()
+}
+
+/// Conflict copy path for `rel`, e.g. `memory/a.conflict-notion-202610161200.md`.
+fn conflict_copy_path(rel: &str, store: &str) -> String {
+    let stem = rel.strip_suffix(".md").unwrap_or(rel);
+    format!(
+        "{}{}{}-{}.md",
+        stem,
+        CONFLICT_MARKER,
+        store,
+        chrono::Local::now().format("%Y%m%d%H%M")
+    )
+}
+
+fn write_local(workspace: &Path, rel: &str, content: &str) -> Result<()> {
+    let path = workspace.join(rel);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Run one sync between the workspace memory and `store`.
+///
+/// With `dry_run` the report lists what would change and nothing is written.
+/// Per-note failures are collected in the report; the state of notes that
+/// failed is left as it was so they are retried next run.
+pub async fn sync_notes(
+    workspace: &Path,
+    store: &dyn NotesStore,
+    config: &NotesSyncConfig,
+    state_path: &Path,
+    dry_run: bool,
+) -> Result<SyncReport> {
+    let mut state = SyncState::load(state_path)?;
+    let mut report = SyncReport::default();
+
+    let local = local_notes(workspace);
+    let mut remote = BTreeMap::new();
+    for note in store.list().await? {
+        if is_syncable_path(&note.path) {
+            remote.insert(note.path.clone(), note);
+        } else {
+            report.skipped.push(note.path);
+        }
+    }
+
+    let paths: BTreeSet<String> = local
+        .keys()
+        .chain(remote.keys())
+        .chain(state.notes.keys())
+        .cloned()
+        .collect();
+
+    for path in paths {
+        let local_content = match local.get(&path).map(std::fs::read_to_string).transpose() {
+            Ok(content) => content,
+            Err(e) => {
+                report.errors.push(format!("{}: {}", path, e));
+                continue;
+            }
+        };
+        let local_hash = local_content.as_deref().map(content_hash);
+        let remote_note = remote.get(&path);
+        let synced = state.notes.get(&path).cloned();
+
+        let Some(action) = plan(
+            local_hash.as_deref(),
+            remote_note.map(|n| n.version.as_str()),
+            synced.as_ref(),
+            config.propagate_deletes,
+        ) else {
+            continue;
+        };
+
+        if dry_run {
+            match action {
+                SyncAction::Push => report.pushed.push(path),
+                SyncAction::Pull => report.pulled.push(path),
+                SyncAction::DeleteLocal => report.deleted_local.push(path),
+                SyncAction::DeleteRemote => report.deleted_remote.push(path),
+                SyncAction::Conflict => report.conflicts.push(path),
+                SyncAction::Forget => {}
+            }
+            continue;
+        }
+
+        let existing_id = synced
+            .as_ref()
+            .map(|s| s.remote_id.as_str())
+            .or(remote_note.map(|n| n.id.as_str()));
+        let result = apply(
+            workspace,
+            store,
+            config.conflict,
+            &path,
+            action,
+            local_content.as_deref(),
+            remote_note,
+            existing_id,
+            &mut report,
+        )
+        .await;
+        match result {
+            Ok(Some(entry)) => {
+                state.notes.insert(path, entry);
+            }
+            Ok(None) => {
+                state.notes.remove(&path);
+            }
+            Err(e) => report.errors.push(format!("{}: {}", path, e)),
+        }
+    }
+
+    if !dry_run {
+        state.save(state_path)?;
+    }
+    Ok(report)
+}
+
+/// Carry out one action; returns the note's new state entry, or `None` when
+/// it no longer exists on either side.
+#[allow(clippy::too_many_arguments)]
+async fn apply(
+    workspace: &Path,
+    store: &dyn NotesStore,
+    policy: ConflictPolicy,
+    path: &str,
+    action: SyncAction,
+    local: Option<&str>,
+    remote: Option<&RemoteNote>,
+    existing_id: Option<&str>,
+    report: &mut SyncReport,
+) -> Result<Option<SyncedNote>> {
+    let push = |content: &str| {
+        let content = content.to_string();
+        async move {
+            let stored = store.write(path, existing_id, &content).await?;
+            Ok::<_, ZeptoError>(SyncedNote {
+                local_hash: content_hash(&content),
+                remote_id: stored.id,
+                remote_version: stored.version,
+            })
+        }
+    };
+
+    match (action, local, remote) {
+        (SyncAction::Push, Some(content), _) => {
+            let entry = push(content).await?;
+            report.pushed.push(path.to_string());
+            Ok(Some(entry))
+        }
+        (SyncAction::Pull, _, Some(note)) => {
+            let content = store.read(note).await?;
+            write_local(workspace, path, &content)?;
+            report.pulled.push(path.to_string());
+            Ok(Some(SyncedNote {
+                local_hash: content_hash(&content),
+                remote_id: note.id.clone(),
+                remote_version: note.version.clone(),
+            }))
+        }
+        (SyncAction::DeleteLocal, Some(_), _) => {
+            std::fs::remove_file(workspace.join(path))?;
+            report.deleted_local.push(path.to_string());
+            Ok(None)
+        }
+        (SyncAction::DeleteRemote, _, Some(note)) => {
+            store.delete(note).await?;
+            report.deleted_remote.push(path.to_string());
+            Ok(None)
+        }
+        (SyncAction::Conflict, Some(local), Some(note)) => {
+            let theirs = store.read(note).await?;
+            let entry = SyncedNote {
+                local_hash: content_hash(local),
+                remote_id: note.id.clone(),
+                remote_version: note.version.clone(),
+            };
+            if theirs == local {
+                return Ok(Some(entry));
+            }
+            match policy {
+                ConflictPolicy::PreferLocal => {
+                    let entry = push(local).await?;
+                    report.conflicts.push(format!("{} (kept local)", path));
+                    Ok(Some(entry))
+                }
+                ConflictPolicy::PreferRemote => {
+                    write_local(workspace, path, &theirs)?;
+                    report
+                        .conflicts
+                        .push(format!("{} (kept {})", path, store.name()));
+                    Ok(Some(SyncedNote {
+                        local_hash: content_hash(&theirs),
+                        ..entry
+                    }))
+                }
+                ConflictPolicy::KeepBoth => {
+                    let copy = conflict_copy_path(path, store.name());
+                    write_local(workspace, &copy, &theirs)?;
+                    let entry = push(local).await?;
+                    report.conflicts.push(format!(
+                        "{} ({} version saved as {})",
+                        path,
+                        store.name(),
+                        copy
+                    ));
+                    Ok(Some(entry))
+                }
+            }
+        }
+        (SyncAction::Forget, _, _) => Ok(None),
+        (action, _, _) => Err(ZeptoError::Tool(format!(
+            "Inconsistent notes sync step {:?}",
+            action
+        ))),
+    }
+}
+
+/// Start notes sync as a background task.
+///
+/// Syncs every configured target each `interval_minutes`. Returns
+/// immediately if `config.enabled` is false or no target is configured.
+pub fn start_notes_sync_scheduler(
+    workspace: PathBuf,
+    config: NotesSyncConfig,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if !config.enabled {
+            info!("Notes sync disabled");
+            return;
+        }
+        let stores = match configured_stores(&config) {
+            Ok(stores) if !stores.is_empty() => stores,
+            Ok(_) => {
+                warn!("Notes sync enabled but no obsidian or notion target configured");
+                return;
+            }
+            Err(e) => {
+                warn!("Notes sync not started: {}", e);
+                return;
+            }
+        };
+
+        let interval = Duration::from_secs(config.interval_minutes.max(1) * 60);
+        loop {
+            for store in &stores {
+                let state_path = default_state_path(store.name());
+                match sync_notes(&workspace, store.as_ref(), &config, &state_path, false).await {
+                    Ok(report) => {
+                        if report.total_changes() > 0 || !report.errors.is_empty() {
+                            info!("Notes sync ({}): {}", store.name(), report.summary());
+                        }
+                        for error in &report.errors {
+                            warn!("Notes sync ({}): {}", store.name(), error);
+                        }
+                    }
+                    Err(e) => warn!("Notes sync ({}) failed: {}", store.name(), e),
+                }
+            }
+            tokio::time::sleep(interval).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn synced(local: &str, remote: &str) -> SyncedNote {
+        SyncedNote {
+            local_hash: local.into(),
+            remote_id: "id".into(),
+            remote_version: remote.into(),
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let s = synced("l1", "r1");
+        assert_eq!(plan(Some("l1"), Some("r1"), Some(&s), true), None);
+        assert_eq!(
+            plan(Some("l2"), Some("r1"), Some(&s), true),
+            Some(SyncAction::Push)
+        );
+        assert_eq!(
+            plan(Some("l1"), Some("r2"), Some(&s), true),
+            Some(SyncAction::Pull)
+        );
+        assert_eq!(
+            plan(Some("l2"), Some("r2"), Some(&s), true),
+            Some(SyncAction::Conflict)
+        );
+        // Deleted remotely: delete locally only if untouched here.
+        assert_eq!(
+            plan(Some("l1"), None, Some(&s), true),
+            Some(SyncAction::DeleteLocal)
+        );
+        assert_eq!(
+            plan(Some("l2"), None, Some(&s), true),
+            Some(SyncAction::Push)
+        );
+        assert_eq!(
+            plan(Some("l1"), None, Some(&s), false),
+            Some(SyncAction::Push)
+        );
+        assert_eq!(
+            plan(None, Some("r1"), Some(&s), true),
+            Some(SyncAction::DeleteRemote)
+        );
+        assert_eq!(plan(None, None, Some(&s), true), Some(SyncAction::Forget));
+        assert_eq!(
+            plan(Some("l"), Some("r"), None, true),
+            Some(SyncAction::Conflict)
+        );
+    }
+
+    #[test]
+    fn test_is_syncable_path() {
+        assert!(is_syncable_path("MEMORY.md"));
+        assert!(is_syncable_path("memory/2026-10-16.md"));
+        assert!(is_syncable_path("memory/people/ana.md"));
+        assert!(!is_syncable_path("notes.md"));
+        assert!(!is_syncable_path("memory/../secrets.md"));
+        assert!(!is_syncable_path("/memory/a.md"));
+        assert!(!is_syncable_path("memory/.hidden.md"));
+        assert!(!is_syncable_path("memory/a.txt"));
+        assert!(!is_syncable_path(
+            "memory/a.conflict-notion-202610161200.md"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sync_with_obsidian_vault() {
+        let workspace = TempDir::new().unwrap();
+        let vault = TempDir::new().unwrap();
+        let state = workspace.path().join("state.json");
+        let store = ObsidianStore::new(&ObsidianSyncConfig {
+            vault_path: vault.path().to_string_lossy().to_string(),
+            folder: "Agent".into(),
+        })
+        .unwrap();
+        let config = NotesSyncConfig::default();
+        let notes = vault.path().join("Agent");
+
+        std::fs::create_dir_all(workspace.path().join("memory")).unwrap();
+        std::fs::write(workspace.path().join("MEMORY.md"), "# Facts\n").unwrap();
+        std::fs::write(workspace.path().join("memory/day.md"), "went well\n").unwrap();
+
+        let report = sync_notes(workspace.path(), &store, &config, &state, false)
+            .await
+            .unwrap();
+        assert_eq!(report.pushed.len(), 2);
+        assert_eq!(
+            std::fs::read_to_string(notes.join("memory/day.md")).unwrap(),
+            "went well\n"
+        );
+
+        // Edited in the vault, pulled back; a second run is a no-op.
+        std::fs::write(notes.join("MEMORY.md"), "# Facts\n- likes tea\n").unwrap();
+        std::fs::write(notes.join("memory/new.md"), "from phone\n").unwrap();
+        let report = sync_notes(workspace.path(), &store, &config, &state, false)
+            .await
+            .unwrap();
+        assert_eq!(report.pulled, vec!["MEMORY.md", "memory/new.md"]);
+        assert_eq!(
+            std::fs::read_to_string(workspace.path().join("MEMORY.md")).unwrap(),
+            "# Facts\n- likes tea\n"
+        );
+        let report = sync_notes(workspace.path(), &store, &config, &state, false)
+            .await
+            .unwrap();
+        assert_eq!(report.total_changes(), 0);
+
+        // Both sides changed: local wins, the vault version is kept as a copy.
+        std::fs::write(workspace.path().join("memory/day.md"), "local edit\n").unwrap();
+        std::fs::write(notes.join("memory/day.md"), "vault edit\n").unwrap();
+        // Deleted in the vault while untouched locally.
+        std::fs::remove_file(notes.join("memory/new.md")).unwrap();
+        let report = sync_notes(workspace.path(), &store, &config, &state, false)
+            .await
+            .unwrap();
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.deleted_local, vec!["memory/new.md"]);
+        assert_eq!(
+            std::fs::read_to_string(notes.join("memory/day.md")).unwrap(),
+            "local edit\n"
+        );
+        let copies: Vec<_> = std::fs::read_dir(workspace.path().join("memory"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| name.contains(CONFLICT_MARKER))
+            .collect();
+        assert_eq!(copies.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(workspace.path().join("memory").join(&copies[0])).unwrap(),
+            "vault edit\n"
+        );
+        assert!(!workspace.path().join("memory/new.md").exists());
+    }
+}
@@ -0,0 +1,383 @@
+//! Notion target: one database page per note, titled with the note path.
+//!
+//! Markdown maps line by line onto blocks (headings, lists, to-dos, quotes,
+//! code fences, paragraphs; blank lines become empty paragraphs) so a note
+//! survives a round trip unchanged. Inline formatting is kept as literal text.
+//! Notion has no "replace page content" call, so a changed note archives the
+//! old page and creates a new one.
+
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use serde_json::{json, Value};
+
+use crate::error::{Result, ZeptoError};
+
+use super::{NotesStore, NotionSyncConfig, RemoteNote};
+
+const NOTION_API_URL: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+
+/// Notion limits: characters per rich text object, blocks per request.
+const MAX_TEXT_CHARS: usize = 2000;
+const MAX_BLOCKS_PER_REQUEST: usize = 100;
+
+/// Code languages passed through to Notion; anything else is "plain text".
+const CODE_LANGUAGES: &[&str] = &[
+    "bash",
+    "c",
+    "c++",
+    "css",
+    "go",
+    "html",
+    "java",
+    "javascript",
+    "json",
+    "markdown",
+    "python",
+    "ruby",
+    "rust",
+    "shell",
+    "sql",
+    "toml",
+    "typescript",
+    "yaml",
+];
+
+fn rich_text(text: &str) -> Value {
+    let chars: Vec<char> = text.chars().collect();
+    let parts: Vec<Value> = chars
+        .chunks(MAX_TEXT_CHARS)
+        .map(|chunk| {
+            json!({ "type": "text", "text": { "content": chunk.iter().collect::<String>() } })
+        })
+        .collect();
+    Value::Array(parts)
+}
+
+fn block(kind: &str, text: &str) -> Value {
+    json!({ "object": "block", "type": kind, kind: { "rich_text": rich_text(text) } })
+}
+
+/// Notion blocks for a markdown note.
+pub fn markdown_to_blocks(markdown: &str) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    let mut lines = markdown.lines();
+    while let Some(line) = lines.next() {
+        if let Some(lang) = line.strip_prefix("```") {
+            let mut code = Vec::new();
+            for inner in lines.by_ref() {
+                if inner.starts_with("```") {
+                    break;
+                }
+                code.push(inner);
+            }
+            let lang = lang.trim().to_lowercase();
+            let language = if CODE_LANGUAGES.contains(&lang.as_str()) {
+                lang
+            } else {
+                "plain text".to_string()
+            };
+            blocks.push(json!({ "object": "block", "type": "code", "code": {
+                "rich_text": rich_text(&code.join("\n")),
+                "language": language,
+            } }));
+            continue;
+        }
+        let todo = line
+            .strip_prefix("- [ ] ")
+            .map(|t| (t, false))
+            .or_else(|| line.strip_prefix("- [x] ").map(|t| (t, true)));
+        if let Some((text, checked)) = todo {
+            blocks.push(json!({ "object": "block", "type": "to_do", "to_do": {
+                "rich_text": rich_text(text),
+                "checked": checked,
+            } }));
+        } else if let Some(text) = line.strip_prefix("### ") {
+            blocks.push(block("heading_3", text));
+        } else if let Some(text) = line.strip_prefix("## ") {
+            blocks.push(block("heading_2", text));
+        } else if let Some(text) = line.strip_prefix("# ") {
+            blocks.push(block("heading_1", text));
+        } else if let Some(text) = line.strip_prefix("- ") {
+            blocks.push(block("bulleted_list_item", text));
+        } else if let Some(text) = line.strip_prefix("1. ") {
+            blocks.push(block("numbered_list_item", text));
+        } else if let Some(text) = line.strip_prefix("> ") {
+            blocks.push(block("quote", text));
+        } else {
+            blocks.push(block("paragraph", line));
+        }
+    }
+    blocks
+}
+
+/// Markdown for a page's blocks; unsupported block types are skipped.
+pub fn blocks_to_markdown(blocks: &[Value]) -> String {
+    let mut lines = Vec::new();
+    for block in blocks {
+        let kind = block["type"].as_str().unwrap_or("");
+        let text: String = block[kind]["rich_text"]
+            .as_array()
+            .map(|parts| {
+                parts
+                    .iter()
+                    .filter_map(|p| p["plain_text"].as_str().or(p["text"]["content"].as_str()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let line = match kind {
+            "paragraph" => text,
+            "heading_1" => format!("# {}", text),
+            "heading_2" => format!("## {}", text),
+            "heading_3" => format!("### {}", text),
+            "bulleted_list_item" => format!("- {}", text),
+            "numbered_list_item" => format!("1. {}", text),
+            "quote" => format!("> {}", text),
+            "to_do" => {
+                let mark = if block["to_do"]["checked"].as_bool() == Some(true) {
+                    "x"
+                } else {
+                    " "
+                };
+                format!("- [{}] {}", mark, text)
+            }
+            "code" => {
+                let lang = block["code"]["language"].as_str().unwrap_or("");
+                let lang = if lang == "plain text" { "" } else { lang };
+                format!("```{}\n{}\n```", lang, text)
+            }
+            "divider" => "---".to_string(),
+            _ => continue,
+        };
+        lines.push(line);
+    }
+    let mut markdown = lines.join("\n");
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+    markdown
+}
+
+pub struct NotionStore {
+    client: Client,
+    config: NotionSyncConfig,
+}
+
+impl NotionStore {
+    pub fn new(config: NotionSyncConfig) -> Result<Self> {
+        if config.token.trim().is_empty() || config.database_id.trim().is_empty() {
+            return Err(ZeptoError::Config(
+                "memory.sync.notion needs token and database_id".into(),
+            ));
+        }
+        Ok(Self {
+            client: Client::new(),
+            config,
+        })
+    }
+
+    async fn call(&self, method: Method, path: &str, body: Option<Value>) -> Result<Value> {
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", NOTION_API_URL, path))
+            .bearer_auth(self.config.token.trim())
+            .header("Notion-Version", NOTION_VERSION);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        if !status.is_success() {
+            return Err(ZeptoError::Tool(format!(
+                "Notion API error {}: {}",
+                status.as_u16(),
+                body["message"].as_str().unwrap_or("unknown error")
+            )));
+        }
+        Ok(body)
+    }
+
+    fn title(&self, page: &Value) -> String {
+        page["properties"][&self.config.title_property]["title"]
+            .as_array()
+            .map(|parts| {
+                parts
+                    .iter()
+                    .filter_map(|p| p["plain_text"].as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    async fn archive(&self, page_id: &str) -> Result<()> {
+        self.call(
+            Method::PATCH,
+            &format!("/pages/{}", page_id),
+            Some(json!({ "archived": true })),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotesStore for NotionStore {
+    fn name(&self) -> &'static str {
+        "notion"
+    }
+
+    async fn list(&self) -> Result<Vec<RemoteNote>> {
+        let mut notes = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut body = json!({ "page_size": 100 });
+            if let Some(cursor) = &cursor {
+                body["start_cursor"] = json!(cursor);
+            }
+            let page = self
+                .call(
+                    Method::POST,
+                    &format!("/databases/{}/query", self.config.database_id.trim()),
+                    Some(body),
+                )
+                .await?;
+            for result in page["results"].as_array().into_iter().flatten() {
+                let path = self.title(result);
+                if path.is_empty() || result["archived"].as_bool() == Some(true) {
+                    continue;
+                }
+                notes.push(RemoteNote {
+                    path,
+                    id: result["id"].as_str().unwrap_or_default().to_string(),
+                    version: result["last_edited_time"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                });
+            }
+            cursor = page["next_cursor"].as_str().map(str::to_string);
+            if page["has_more"].as_bool() != Some(true) || cursor.is_none() {
+                break;
+            }
+        }
+        Ok(notes)
+    }
+
+    async fn read(&self, note: &RemoteNote) -> Result<String> {
+        let mut blocks = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut path = format!("/blocks/{}/children?page_size=100", note.id);
+            if let Some(cursor) = &cursor {
+                path.push_str(&format!("&start_cursor={}", cursor));
+            }
+            let page = self.call(Method::GET, &path, None).await?;
+            blocks.extend(page["results"].as_array().cloned().unwrap_or_default());
+            cursor = page["next_cursor"].as_str().map(str::to_string);
+            if page["has_more"].as_bool() != Some(true) || cursor.is_none() {
+                break;
+            }
+        }
+        Ok(blocks_to_markdown(&blocks))
+    }
+
+    async fn write(&self, path: &str, existing: Option<&str>, content: &str) -> Result<RemoteNote> {
+        if let Some(id) = existing {
+            // Already gone (deleted in Notion) is fine; anything else is not.
+            if let Err(e) = self.archive(id).await {
+                if !e.to_string().contains("404") {
+                    return Err(e);
+                }
+            }
+        }
+
+        let blocks = markdown_to_blocks(content);
+        let mut batches = blocks.chunks(MAX_BLOCKS_PER_REQUEST);
+        let first = batches.next().unwrap_or(&[]);
+        let created = self
+            .call(
+                Method::POST,
+                "/pages",
+                Some(json!({
+                    "parent": { "database_id": self.config.database_id.trim() },
+                    "properties": {
+                        self.config.title_property.as_str(): {
+                            "title": [{ "type": "text", "text": { "content": path } }]
+                        }
+                    },
+                    "children": first,
+                })),
+            )
+            .await?;
+        let id = created["id"]
+            .as_str()
+            .ok_or_else(|| ZeptoError::Tool("Notion returned no page id".into()))?
+            .to_string();
+        let mut version = created["last_edited_time"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        let mut appended = false;
+        for batch in batches {
+            self.call(
+                Method::PATCH,
+                &format!("/blocks/{}/children", id),
+                Some(json!({ "children": batch })),
+            )
+            .await?;
+            appended = true;
+        }
+        if appended {
+            let page = self
+                .call(Method::GET, &format!("/pages/{}", id), None)
+                .await?;
+            version = page["last_edited_time"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+        }
+
+        Ok(RemoteNote {
+            path: path.to_string(),
+            id,
+            version,
+        })
+    }
+
+    async fn delete(&self, note: &RemoteNote) -> Result<()> {
+        self.archive(&note.id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let markdown = "# Facts\n\n- likes tea\n- [x] booked flight\n- [ ] renew passport\n\
+                        1. first\n> quoted\n```rust\nfn main() {}\n```\nplain line\n";
+        let blocks = markdown_to_blocks(markdown);
+        assert_eq!(blocks[0]["type"], "heading_1");
+        assert_eq!(blocks[1]["type"], "paragraph");
+        assert_eq!(blocks[3]["to_do"]["checked"], true);
+        assert_eq!(blocks[7]["code"]["language"], "rust");
+        assert_eq!(blocks_to_markdown(&blocks), markdown);
+    }
+
+    #[test]
+    fn test_long_text_is_split() {
+        let long = "a".repeat(MAX_TEXT_CHARS + 10);
+        let blocks = markdown_to_blocks(&long);
+        assert_eq!(
+            blocks[0]["paragraph"]["rich_text"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(blocks_to_markdown(&blocks), format!("{}\n", long));
+    }
+}
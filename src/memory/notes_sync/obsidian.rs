@@ -0,0 +1,131 @@
+//! Obsidian target: plain markdown files in a vault folder.
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use crate::config::expand_home;
+use crate::error::{Result, ZeptoError};
+
+use super::{content_hash, NotesStore, ObsidianSyncConfig, RemoteNote};
+
+const MAX_DIR_DEPTH: usize = 10;
+
+pub struct ObsidianStore {
+    vault: PathBuf,
+    root: PathBuf,
+}
+
+impl ObsidianStore {
+    pub fn new(config: &ObsidianSyncConfig) -> Result<Self> {
+        if config.vault_path.trim().is_empty() {
+            return Err(ZeptoError::Config(
+                "memory.sync.obsidian.vault_path is not set".into(),
+            ));
+        }
+        let vault = expand_home(config.vault_path.trim());
+        if !vault.is_dir() {
+            return Err(ZeptoError::Config(format!(
+                "Obsidian vault {:?} does not exist",
+                vault
+            )));
+        }
+        let folder = config.folder.trim().trim_matches('/');
+        if folder.split('/').any(|p| p == "..") {
+            return Err(ZeptoError::Config(
+                "memory.sync.obsidian.folder must stay inside the vault".into(),
+            ));
+        }
+        Ok(Self {
+            root: vault.join(folder),
+            vault,
+        })
+    }
+
+    fn collect(&self, dir: &Path, depth: usize, notes: &mut Vec<RemoteNote>) -> Result<()> {
+        if depth > MAX_DIR_DEPTH || !dir.is_dir() {
+            return Ok(());
+        }
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let hidden = path
+                .file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if path.is_dir() {
+                self.collect(&path, depth + 1, notes)?;
+            } else if path.extension().is_some_and(|e| e == "md") {
+                let Ok(rel) = path.strip_prefix(&self.root) else {
+                    continue;
+                };
+                let rel = rel
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let content = std::fs::read_to_string(&path)?;
+                notes.push(RemoteNote {
+                    id: rel.clone(),
+                    path: rel,
+                    version: content_hash(&content),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotesStore for ObsidianStore {
+    fn name(&self) -> &'static str {
+        "obsidian"
+    }
+
+    async fn list(&self) -> Result<Vec<RemoteNote>> {
+        let mut notes = Vec::new();
+        self.collect(&self.root, 0, &mut notes)?;
+        Ok(notes)
+    }
+
+    async fn read(&self, note: &RemoteNote) -> Result<String> {
+        Ok(std::fs::read_to_string(self.root.join(&note.path))?)
+    }
+
+    async fn write(
+        &self,
+        path: &str,
+        _existing: Option<&str>,
+        content: &str,
+    ) -> Result<RemoteNote> {
+        let file = self.root.join(path);
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&file, content)?;
+        Ok(RemoteNote {
+            path: path.to_string(),
+            id: path.to_string(),
+            version: content_hash(content),
+        })
+    }
+
+    /// Moves the note into the vault's `.trash`, like Obsidian's own delete.
+    async fn delete(&self, note: &RemoteNote) -> Result<()> {
+        let file = self.root.join(&note.path);
+        let trash = self.vault.join(".trash");
+        std::fs::create_dir_all(&trash)?;
+        let name = note.path.replace('/', "-");
+        let mut target = trash.join(&name);
+        if target.exists() {
+            target = trash.join(format!(
+                "{}-{}",
+                chrono::Local::now().format("%Y%m%d%H%M%S"),
+                name
+            ));
+        }
+        std::fs::rename(&file, &target)?;
+        Ok(())
+    }
+}
@@ -1,12 +1,15 @@
 //! Memory snapshot — export/import longterm memory as JSON.
 //!
 //! Provides [`export_snapshot`] and [`import_snapshot`] for backup and migration.
+//!
+//! Snapshots are `{"version": 2, "entries": [...]}`. Version 1 snapshots, a
+//! bare array of entries, are still read; see [`parse_snapshot`].
 
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
-use crate::error::Result;
+use crate::error::{Result, ZeptoError};
 use crate::memory::longterm::LongTermMemory;
 
 /// A single entry in a memory snapshot file.
@@ -25,13 +28,42 @@ fn default_importance() -> f32 {
     1.0
 }
 
+/// Snapshot format written by [`export_snapshot`].
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// A snapshot file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub entries: Vec<SnapshotEntry>,
+}
+
+/// Parse a snapshot of any supported version.
+pub fn parse_snapshot(content: &str) -> Result<Snapshot> {
+    let value: serde_json::Value = serde_json::from_str(content)?;
+    if value.is_array() {
+        return Ok(Snapshot {
+            version: 1,
+            entries: serde_json::from_value(value)?,
+        });
+    }
+    let snapshot: Snapshot = serde_json::from_value(value)?;
+    if snapshot.version > SNAPSHOT_VERSION {
+        return Err(ZeptoError::Config(format!(
+            "memory snapshot version {} is newer than this zeptoclaw supports ({})",
+            snapshot.version, SNAPSHOT_VERSION
+        )));
+    }
+    Ok(snapshot)
+}
+
 /// Export all longterm memory entries to a JSON snapshot file.
 ///
 /// Returns the number of entries exported. Creates parent directories if needed.
 pub fn export_snapshot(memory: &LongTermMemory, path: &Path) -> Result<usize> {
     let entries = memory.list_all();
 
-    let snapshot: Vec<SnapshotEntry> = entries
+    let entries: Vec<SnapshotEntry> = entries
         .iter()
         .map(|entry| SnapshotEntry {
             key: entry.key.clone(),
@@ -42,6 +74,11 @@ pub fn export_snapshot(memory: &LongTermMemory, path: &Path) -> Result<usize> {
         })
         .collect();
 
+    let count = entries.len();
+    let snapshot = Snapshot {
+        version: SNAPSHOT_VERSION,
+        entries,
+    };
     let json = serde_json::to_string_pretty(&snapshot)?;
 
     if let Some(parent) = path.parent() {
@@ -49,7 +86,7 @@ pub fn export_snapshot(memory: &LongTermMemory, path: &Path) -> Result<usize> {
     }
     std::fs::write(path, json)?;
 
-    Ok(snapshot.len())
+    Ok(count)
 }
 
 /// Import entries from a JSON snapshot file into memory.
@@ -63,7 +100,7 @@ pub async fn import_snapshot(
     overwrite: bool,
 ) -> Result<(usize, usize)> {
     let content = std::fs::read_to_string(path)?;
-    let entries: Vec<SnapshotEntry> = serde_json::from_str(&content)?;
+    let entries = parse_snapshot(&content)?.entries;
 
     let mut imported = 0;
     let mut skipped = 0;
@@ -92,7 +129,7 @@ pub async fn import_snapshot(
 
 /// Default snapshot path: `~/.zeptoclaw/memory/snapshot.json`.
 pub fn default_snapshot_path() -> std::path::PathBuf {
-    crate::config::Config::dir()
+    crate::config::Config::data_dir()
         .join("memory")
         .join("snapshot.json")
 }
@@ -116,7 +153,9 @@ mod tests {
         let count = export_snapshot(&mem, &temp_path).unwrap();
         assert_eq!(count, 0);
         let content = std::fs::read_to_string(&temp_path).unwrap();
-        assert_eq!(content.trim(), "[]");
+        let snapshot: Snapshot = serde_json::from_str(&content).unwrap();
+        assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+        assert!(snapshot.entries.is_empty());
         let _ = std::fs::remove_file(&temp_path);
     }
 
@@ -129,8 +168,8 @@ mod tests {
         let count = export_snapshot(&mem, &temp_path).unwrap();
         assert_eq!(count, 2);
         let content = std::fs::read_to_string(&temp_path).unwrap();
-        let entries: Vec<SnapshotEntry> = serde_json::from_str(&content).unwrap();
-        assert_eq!(entries.len(), 2);
+        let snapshot: Snapshot = serde_json::from_str(&content).unwrap();
+        assert_eq!(snapshot.entries.len(), 2);
         let _ = std::fs::remove_file(&temp_path);
     }
 
@@ -174,6 +213,19 @@ mod tests {
         let _ = std::fs::remove_file(&temp_path);
     }
 
+    #[test]
+    fn test_parse_snapshot_versions() {
+        let v1 = parse_snapshot(r#"[{"key": "k", "value": "v", "category": "user"}]"#).unwrap();
+        assert_eq!(v1.version, 1);
+        assert_eq!(v1.entries[0].importance, 1.0);
+
+        let v2 = parse_snapshot(r#"{"version": 2, "entries": []}"#).unwrap();
+        assert_eq!(v2.version, 2);
+
+        let err = parse_snapshot(r#"{"version": 99, "entries": []}"#).unwrap_err();
+        assert!(err.to_string().contains("newer"));
+    }
+
     #[tokio::test]
     async fn test_import_malformed_json() {
         let (mut mem, _dir) = temp_memory();
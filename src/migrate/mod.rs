@@ -1,10 +1,12 @@
 //! OpenClaw → ZeptoClaw migration module.
 //!
 //! Handles detection of OpenClaw installations, config conversion,
-//! and skill directory copying.
+//! and skill directory copying. [`state`] versions ZeptoClaw's own on-disk
+//! formats and migrates them between releases.
 
 pub mod config;
 pub mod skills;
+pub mod state;
 
 use std::path::{Path, PathBuf};
 
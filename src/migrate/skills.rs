@@ -113,9 +113,12 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Expand `~/` prefix to the user's home directory.
+/// Expand `~/` (or `~\` on Windows) prefix to the user's home directory.
 fn expand_tilde(path: &str) -> PathBuf {
-    if let Some(stripped) = path.strip_prefix("~/") {
+    let stripped = path
+        .strip_prefix("~/")
+        .or_else(|| path.strip_prefix("~\\").filter(|_| cfg!(windows)));
+    if let Some(stripped) = stripped {
         if let Some(home) = dirs::home_dir() {
             return home.join(stripped);
         }
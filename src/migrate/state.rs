@@ -0,0 +1,286 @@
+//! Versioned on-disk state formats.
+//!
+//! Every store in the data dir (sessions, cron store, memory) has a format
+//! version, recorded in `state_versions.json`. [`run`] applies the
+//! [`MIGRATIONS`] each store is missing, in order, and records the new
+//! version after every step. It runs when the kernel boots and after
+//! `zeptoclaw backup restore`.
+//!
+//! A store missing from the versions file predates versioning and is at
+//! version 1; a store that does not exist yet starts at its current version.
+//! Migrations must be idempotent: a crash between a migration and the
+//! version write re-runs it on the next start.
+//!
+//! To change a format, bump it with a new [`Migration`] at the end of
+//! [`MIGRATIONS`] rather than teaching the loader every old shape.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::error::Result;
+
+/// File in the data dir that records store versions.
+pub const VERSIONS_FILE: &str = "state_versions.json";
+
+/// A versioned store in the data dir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Store {
+    /// `sessions/*.json`
+    Sessions,
+    /// `cron/jobs.json`
+    Cron,
+    /// `memory/` — long-term memory and its snapshot
+    Memory,
+}
+
+impl Store {
+    pub const ALL: [Store; 3] = [Store::Sessions, Store::Cron, Store::Memory];
+
+    /// Directory of the store, relative to the data dir.
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            Store::Sessions => "sessions",
+            Store::Cron => "cron",
+            Store::Memory => "memory",
+        }
+    }
+
+    /// Format version this build reads and writes.
+    pub fn current_version(self) -> u32 {
+        MIGRATIONS
+            .iter()
+            .filter(|m| m.store == self)
+            .map(|m| m.to)
+            .max()
+            .unwrap_or(1)
+    }
+}
+
+/// One format change: brings `store` from version `to - 1` to `to`.
+pub struct Migration {
+    pub store: Store,
+    pub to: u32,
+    pub description: &'static str,
+    /// Applied to the store's directory.
+    run: fn(&Path) -> Result<()>,
+}
+
+/// All migrations, in the order they are applied.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        store: Store::Sessions,
+        to: 2,
+        description: "rename session files to the NTFS-safe key encoding",
+        run: sessions_v2,
+    },
+    Migration {
+        store: Store::Memory,
+        to: 2,
+        description: "wrap the memory snapshot in a versioned envelope",
+        run: memory_v2,
+    },
+];
+
+/// Recorded store versions.
+type Versions = BTreeMap<Store, u32>;
+
+/// Bring every store under `data_dir` to its current version. Returns a
+/// line per applied migration.
+pub fn run(data_dir: &Path) -> Result<Vec<String>> {
+    let path = data_dir.join(VERSIONS_FILE);
+    let mut versions: Versions = match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Versions::default(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut applied = Vec::new();
+    let mut changed = false;
+    for store in Store::ALL {
+        let dir = data_dir.join(store.dir_name());
+        let current = store.current_version();
+        let recorded = match versions.get(&store) {
+            Some(version) => *version,
+            None if dir.exists() => 1,
+            None => current,
+        };
+        if recorded > current {
+            warn!(
+                store = store.dir_name(),
+                version = recorded,
+                supported = current,
+                "State was written by a newer zeptoclaw; leaving it as is"
+            );
+            continue;
+        }
+
+        let mut version = recorded;
+        for migration in MIGRATIONS
+            .iter()
+            .filter(|m| m.store == store && m.to > recorded)
+        {
+            if dir.exists() {
+                (migration.run)(&dir)?;
+            }
+            version = migration.to;
+            versions.insert(store, version);
+            write_versions(&path, &versions)?;
+            info!(
+                store = store.dir_name(),
+                version, "Migrated: {}", migration.description
+            );
+            applied.push(format!(
+                "{} v{}: {}",
+                store.dir_name(),
+                version,
+                migration.description
+            ));
+        }
+        if versions.get(&store) != Some(&version) {
+            versions.insert(store, version);
+            changed = true;
+        }
+    }
+
+    if changed && data_dir.exists() {
+        write_versions(&path, &versions)?;
+    }
+    Ok(applied)
+}
+
+fn write_versions(path: &Path, versions: &Versions) -> Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(versions)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Session files are named after the sanitized session key; control
+/// characters and Windows device names are now percent-encoded too.
+fn sessions_v2(dir: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Some(key) = serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .and_then(|v| v.get("key").and_then(|k| k.as_str()).map(str::to_string))
+        else {
+            continue;
+        };
+        let target = dir.join(format!(
+            "{}.json",
+            crate::session::SessionManager::sanitize_key(&key)
+        ));
+        if target != path && !target.exists() {
+            std::fs::rename(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// `memory/snapshot.json` was a bare array of entries.
+fn memory_v2(dir: &Path) -> Result<()> {
+    let path = dir.join("snapshot.json");
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Ok(());
+    };
+    let snapshot = crate::memory::snapshot::parse_snapshot(&content)?;
+    if snapshot.version < 2 {
+        let upgraded = crate::memory::snapshot::Snapshot {
+            version: 2,
+            entries: snapshot.entries,
+        };
+        std::fs::write(&path, serde_json::to_string_pretty(&upgraded)?)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_versions(dir: &Path) -> BTreeMap<Store, u32> {
+        let content = std::fs::read_to_string(dir.join(VERSIONS_FILE)).unwrap();
+        serde_json::from_str::<Versions>(&content).unwrap()
+    }
+
+    #[test]
+    fn test_fresh_install_records_current_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(run(dir.path()).unwrap().is_empty());
+        let versions = read_versions(dir.path());
+        for store in Store::ALL {
+            assert_eq!(versions[&store], store.current_version());
+        }
+        // Nothing left to do on the next start.
+        assert!(run(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unversioned_stores_are_migrated_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let sessions = dir.path().join("sessions");
+        std::fs::create_dir_all(&sessions).unwrap();
+        std::fs::write(sessions.join("con.json"), r#"{"key":"con","messages":[]}"#).unwrap();
+        std::fs::write(
+            sessions.join("a%3Ab.json"),
+            r#"{"key":"a:b","messages":[]}"#,
+        )
+        .unwrap();
+        let memory = dir.path().join("memory");
+        std::fs::create_dir_all(&memory).unwrap();
+        std::fs::write(
+            memory.join("snapshot.json"),
+            r#"[{"key":"k","value":"v","category":"user"}]"#,
+        )
+        .unwrap();
+
+        let applied = run(dir.path()).unwrap();
+        assert_eq!(applied.len(), 2);
+        assert!(applied[0].starts_with("sessions v2"));
+        assert!(applied[1].starts_with("memory v2"));
+
+        assert!(sessions.join("%63on.json").exists());
+        assert!(!sessions.join("con.json").exists());
+        assert!(sessions.join("a%3Ab.json").exists());
+        let snapshot = std::fs::read_to_string(memory.join("snapshot.json")).unwrap();
+        let snapshot = crate::memory::snapshot::parse_snapshot(&snapshot).unwrap();
+        assert_eq!((snapshot.version, snapshot.entries.len()), (2, 1));
+
+        let versions = read_versions(dir.path());
+        assert_eq!(versions[&Store::Sessions], 2);
+        assert_eq!(versions[&Store::Cron], 1);
+        assert!(run(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_newer_versions_are_left_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("sessions")).unwrap();
+        std::fs::write(dir.path().join(VERSIONS_FILE), r#"{"sessions": 99}"#).unwrap();
+        assert!(run(dir.path()).unwrap().is_empty());
+        assert_eq!(read_versions(dir.path())[&Store::Sessions], 99);
+    }
+
+    #[test]
+    fn test_migrations_are_ordered_and_contiguous() {
+        for store in Store::ALL {
+            let steps: Vec<u32> = MIGRATIONS
+                .iter()
+                .filter(|m| m.store == store)
+                .map(|m| m.to)
+                .collect();
+            let expected: Vec<u32> = (2..2 + steps.len() as u32).collect();
+            assert_eq!(steps, expected, "{:?}", store);
+        }
+    }
+}
@@ -0,0 +1,658 @@
+//! Linux SBC peripherals -- GPIO character devices and i2c-dev.
+//!
+//! Only compiled when the `peripheral-linux` feature is enabled on Linux.
+//! Works on any board with a mainline kernel (Raspberry Pi, Orange Pi,
+//! BeagleBone, Rock Pi, ...) because it talks to `/dev/gpiochipN` and
+//! `/dev/i2c-N` directly instead of SoC registers.
+//!
+//! The agent never sees raw pins: every reachable device comes from the
+//! `peripherals.devices` manifest, which maps a friendly name to a GPIO line
+//! or an I2C sensor register:
+//!
+//! ```json
+//! {"peripherals": {"devices": [
+//!   {"name": "desk LED", "type": "gpio", "line": 17, "direction": "output"},
+//!   {"name": "temp sensor", "type": "i2c", "address": 72, "register": 0,
+//!    "format": "i16be", "shift": 4, "scale": 0.0625, "unit": "°C"}
+//! ]}}
+//! ```
+
+#![cfg(all(feature = "peripheral-linux", target_os = "linux"))]
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use super::traits::Peripheral;
+use crate::config::{
+    GpioDirection, I2cValueFormat, PeripheralDeviceConfig, PeripheralKind, PeripheralsConfig,
+};
+use crate::error::{Result, ZeptoError};
+use crate::tools::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+// ---------------------------------------------------------------------------
+// Kernel ABI (linux/gpio.h v1 line handles, linux/i2c-dev.h)
+// ---------------------------------------------------------------------------
+
+const GPIOHANDLES_MAX: usize = 64;
+const GPIOHANDLE_REQUEST_INPUT: u32 = 1 << 0;
+const GPIOHANDLE_REQUEST_OUTPUT: u32 = 1 << 1;
+const GPIOHANDLE_REQUEST_ACTIVE_LOW: u32 = 1 << 2;
+
+#[repr(C)]
+struct GpioHandleRequest {
+    line_offsets: [u32; GPIOHANDLES_MAX],
+    flags: u32,
+    default_values: [u8; GPIOHANDLES_MAX],
+    consumer_label: [u8; 32],
+    lines: u32,
+    fd: libc::c_int,
+}
+
+#[repr(C)]
+struct GpioHandleData {
+    values: [u8; GPIOHANDLES_MAX],
+}
+
+/// `_IOWR(type, nr, size)` from `asm-generic/ioctl.h`. Request numbers are
+/// cast at the call site because glibc and musl type them differently.
+const fn iowr(ty: u32, nr: u32, size: usize) -> libc::c_ulong {
+    ((3u32 << 30) | ((size as u32) << 16) | (ty << 8) | nr) as libc::c_ulong
+}
+
+const GPIO_GET_LINEHANDLE_IOCTL: libc::c_ulong =
+    iowr(0xB4, 0x03, std::mem::size_of::<GpioHandleRequest>());
+const GPIOHANDLE_GET_LINE_VALUES_IOCTL: libc::c_ulong =
+    iowr(0xB4, 0x08, std::mem::size_of::<GpioHandleData>());
+const GPIOHANDLE_SET_LINE_VALUES_IOCTL: libc::c_ulong =
+    iowr(0xB4, 0x09, std::mem::size_of::<GpioHandleData>());
+const I2C_SLAVE: libc::c_ulong = 0x0703;
+
+/// Largest I2C read we perform for a sensor value.
+const MAX_I2C_READ: usize = 2;
+
+/// Resolve a chip name ("gpiochip0" or "/dev/gpiochip0") to its device path.
+fn chip_path(chip: &str) -> Result<String> {
+    let name = chip.strip_prefix("/dev/").unwrap_or(chip);
+    let valid = name
+        .strip_prefix("gpiochip")
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+    if !valid {
+        return Err(ZeptoError::Config(format!(
+            "Invalid GPIO chip '{}' (expected gpiochipN)",
+            chip
+        )));
+    }
+    Ok(format!("/dev/{}", name))
+}
+
+/// Request a line handle for one GPIO line.
+fn request_line(chip: &str, line: u32, output: bool, active_low: bool, value: u8) -> Result<File> {
+    let chip_file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(chip_path(chip)?)
+        .map_err(|e| ZeptoError::Tool(format!("GPIO open {}: {}", chip, e)))?;
+
+    let mut req = GpioHandleRequest {
+        line_offsets: [0; GPIOHANDLES_MAX],
+        flags: if output {
+            GPIOHANDLE_REQUEST_OUTPUT
+        } else {
+            GPIOHANDLE_REQUEST_INPUT
+        },
+        default_values: [0; GPIOHANDLES_MAX],
+        consumer_label: [0; 32],
+        lines: 1,
+        fd: -1,
+    };
+    req.line_offsets[0] = line;
+    req.default_values[0] = value;
+    if active_low {
+        req.flags |= GPIOHANDLE_REQUEST_ACTIVE_LOW;
+    }
+    let label = b"zeptoclaw";
+    req.consumer_label[..label.len()].copy_from_slice(label);
+
+    // SAFETY: `req` is a properly initialised `gpiohandle_request` and the
+    // chip fd is open for the duration of the call.
+    let rc = unsafe {
+        libc::ioctl(
+            chip_file.as_raw_fd(),
+            GPIO_GET_LINEHANDLE_IOCTL as _,
+            &mut req,
+        )
+    };
+    if rc < 0 || req.fd < 0 {
+        return Err(ZeptoError::Tool(format!(
+            "GPIO request line {} on {}: {}",
+            line,
+            chip,
+            std::io::Error::last_os_error()
+        )));
+    }
+    // SAFETY: the kernel returned a new, owned file descriptor.
+    Ok(unsafe { File::from_raw_fd(req.fd) })
+}
+
+fn line_get(handle: &File) -> Result<u8> {
+    let mut data = GpioHandleData {
+        values: [0; GPIOHANDLES_MAX],
+    };
+    // SAFETY: `handle` is a line handle fd and `data` is a valid buffer.
+    let rc = unsafe {
+        libc::ioctl(
+            handle.as_raw_fd(),
+            GPIOHANDLE_GET_LINE_VALUES_IOCTL as _,
+            &mut data,
+        )
+    };
+    if rc < 0 {
+        return Err(ZeptoError::Tool(format!(
+            "GPIO read: {}",
+            std::io::Error::last_os_error()
+        )));
+    }
+    Ok(data.values[0])
+}
+
+fn line_set(handle: &File, value: u8) -> Result<()> {
+    let mut data = GpioHandleData {
+        values: [0; GPIOHANDLES_MAX],
+    };
+    data.values[0] = value;
+    // SAFETY: `handle` is an output line handle fd and `data` is a valid buffer.
+    let rc = unsafe {
+        libc::ioctl(
+            handle.as_raw_fd(),
+            GPIOHANDLE_SET_LINE_VALUES_IOCTL as _,
+            &mut data,
+        )
+    };
+    if rc < 0 {
+        return Err(ZeptoError::Tool(format!(
+            "GPIO write: {}",
+            std::io::Error::last_os_error()
+        )));
+    }
+    Ok(())
+}
+
+/// Read `len` bytes from `register` of the device at `address` on `/dev/i2c-{bus}`.
+fn i2c_read_register(bus: u8, address: u16, register: u8, len: usize) -> Result<Vec<u8>> {
+    if address > 0x7f {
+        return Err(ZeptoError::Config(format!(
+            "I2C address 0x{:02x} is not a 7-bit address",
+            address
+        )));
+    }
+    let path = format!("/dev/i2c-{}", bus);
+    let mut dev = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&path)
+        .map_err(|e| ZeptoError::Tool(format!("I2C open {}: {}", path, e)))?;
+    // SAFETY: I2C_SLAVE takes the address by value on an open i2c-dev fd.
+    let rc = unsafe {
+        libc::ioctl(
+            dev.as_raw_fd(),
+            I2C_SLAVE as _,
+            libc::c_ulong::from(address),
+        )
+    };
+    if rc < 0 {
+        return Err(ZeptoError::Tool(format!(
+            "I2C select 0x{:02x} on {}: {}",
+            address,
+            path,
+            std::io::Error::last_os_error()
+        )));
+    }
+    dev.write_all(&[register])
+        .map_err(|e| ZeptoError::Tool(format!("I2C write register 0x{:02x}: {}", register, e)))?;
+    let mut buf = vec![0u8; len];
+    dev.read_exact(&mut buf)
+        .map_err(|e| ZeptoError::Tool(format!("I2C read 0x{:02x}: {}", address, e)))?;
+    Ok(buf)
+}
+
+// ---------------------------------------------------------------------------
+// Value decoding and manifest lookup
+// ---------------------------------------------------------------------------
+
+/// Bytes to read for a value format.
+fn format_len(format: I2cValueFormat) -> usize {
+    match format {
+        I2cValueFormat::U8 | I2cValueFormat::I8 => 1,
+        _ => MAX_I2C_READ,
+    }
+}
+
+/// Decode raw register bytes and apply `((raw >> shift) * scale) + offset`.
+fn decode_value(bytes: &[u8], format: I2cValueFormat, shift: u8, scale: f64, offset: f64) -> f64 {
+    let pair = || [bytes[0], bytes.get(1).copied().unwrap_or(0)];
+    let raw: i64 = match format {
+        I2cValueFormat::U8 => i64::from(bytes[0]),
+        I2cValueFormat::I8 => i64::from(bytes[0] as i8),
+        I2cValueFormat::U16be => i64::from(u16::from_be_bytes(pair())),
+        I2cValueFormat::U16le => i64::from(u16::from_le_bytes(pair())),
+        I2cValueFormat::I16be => i64::from(i16::from_be_bytes(pair())),
+        I2cValueFormat::I16le => i64::from(i16::from_le_bytes(pair())),
+    };
+    ((raw >> shift) as f64) * scale + offset
+}
+
+/// Find a manifest device by case-insensitive name.
+fn find_device<'a>(
+    config: &'a PeripheralsConfig,
+    name: &str,
+) -> Option<&'a PeripheralDeviceConfig> {
+    let wanted = name.trim().to_lowercase();
+    config
+        .devices
+        .iter()
+        .find(|d| d.name.to_lowercase() == wanted)
+}
+
+fn describe(config: &PeripheralsConfig, device: &PeripheralDeviceConfig) -> Value {
+    let wiring = match &device.kind {
+        PeripheralKind::Gpio {
+            line,
+            chip,
+            direction,
+            ..
+        } => json!({
+            "type": "gpio",
+            "chip": chip.as_deref().unwrap_or(&config.gpio_chip),
+            "line": line,
+            "writable": *direction == GpioDirection::Output,
+        }),
+        PeripheralKind::I2c {
+            address, bus, unit, ..
+        } => json!({
+            "type": "i2c",
+            "bus": bus.unwrap_or(config.i2c_bus),
+            "address": format!("0x{:02x}", address),
+            "unit": unit,
+        }),
+    };
+    json!({
+        "name": device.name,
+        "description": device.description,
+        "wiring": wiring,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Peripheral + tool
+// ---------------------------------------------------------------------------
+
+/// Output line handles kept open so driven values persist between calls,
+/// keyed by device name.
+type HeldLines = Arc<Mutex<HashMap<String, File>>>;
+
+/// Linux SBC peripheral built from the `peripherals` manifest.
+pub struct LinuxSbcPeripheral {
+    config: Arc<PeripheralsConfig>,
+    held: HeldLines,
+}
+
+impl LinuxSbcPeripheral {
+    /// Create a peripheral serving the devices in `config`.
+    pub fn new(config: PeripheralsConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            held: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl Peripheral for LinuxSbcPeripheral {
+    fn name(&self) -> &str {
+        "linux-sbc"
+    }
+
+    fn board_type(&self) -> &str {
+        "linux-sbc"
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        // Dropping the handles releases the lines.
+        self.held.lock().map(|mut h| h.clear()).ok();
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        let chip = chip_path(&self.config.gpio_chip).unwrap_or_default();
+        std::path::Path::new(&chip).exists()
+            || std::path::Path::new(&format!("/dev/i2c-{}", self.config.i2c_bus)).exists()
+    }
+
+    fn tools(&self) -> Vec<Box<dyn Tool>> {
+        vec![Box::new(PeripheralTool {
+            config: Arc::clone(&self.config),
+            held: Arc::clone(&self.held),
+        })]
+    }
+}
+
+/// Tool: list, read, and write manifest devices by friendly name.
+pub struct PeripheralTool {
+    config: Arc<PeripheralsConfig>,
+    held: HeldLines,
+}
+
+impl PeripheralTool {
+    fn read_device(&self, device: &PeripheralDeviceConfig) -> Result<String> {
+        match &device.kind {
+            PeripheralKind::Gpio {
+                line,
+                chip,
+                active_low,
+                ..
+            } => {
+                let held = self
+                    .held
+                    .lock()
+                    .ok()
+                    .and_then(|h| h.get(&device.name).map(line_get));
+                let value = match held {
+                    // An output we are driving: read back through its handle.
+                    Some(value) => value?,
+                    // Not driven by us: sample the line as an input.
+                    None => {
+                        let chip = chip.as_deref().unwrap_or(&self.config.gpio_chip);
+                        line_get(&request_line(chip, *line, false, *active_low, 0)?)?
+                    }
+                };
+                Ok(format!(
+                    "{} = {}",
+                    device.name,
+                    if value != 0 { "on (1)" } else { "off (0)" }
+                ))
+            }
+            PeripheralKind::I2c {
+                address,
+                register,
+                bus,
+                format,
+                shift,
+                scale,
+                offset,
+                unit,
+            } => {
+                let bus = bus.unwrap_or(self.config.i2c_bus);
+                let bytes = i2c_read_register(bus, *address, *register, format_len(*format))?;
+                let value = decode_value(&bytes, *format, *shift, *scale, *offset);
+                Ok(format!(
+                    "{} = {}{}",
+                    device.name,
+                    (value * 1000.0).round() / 1000.0,
+                    unit.as_deref()
+                        .map(|u| format!(" {}", u))
+                        .unwrap_or_default()
+                ))
+            }
+        }
+    }
+
+    fn write_device(&self, device: &PeripheralDeviceConfig, value: u8) -> Result<String> {
+        let PeripheralKind::Gpio {
+            line,
+            chip,
+            direction,
+            active_low,
+        } = &device.kind
+        else {
+            return Err(ZeptoError::Tool(format!(
+                "'{}' is a sensor and cannot be written",
+                device.name
+            )));
+        };
+        if *direction != GpioDirection::Output {
+            return Err(ZeptoError::Tool(format!(
+                "'{}' is an input (set direction = \"output\" in peripherals.devices to allow writes)",
+                device.name
+            )));
+        }
+        let mut held = self
+            .held
+            .lock()
+            .map_err(|_| ZeptoError::Tool("GPIO handle lock poisoned".into()))?;
+        match held.get(&device.name) {
+            Some(handle) => line_set(handle, value)?,
+            None => {
+                let chip = chip.as_deref().unwrap_or(&self.config.gpio_chip);
+                let handle = request_line(chip, *line, true, *active_low, value)?;
+                held.insert(device.name.clone(), handle);
+            }
+        }
+        Ok(format!(
+            "{} set {}",
+            device.name,
+            if value != 0 { "on (1)" } else { "off (0)" }
+        ))
+    }
+}
+
+#[async_trait]
+impl Tool for PeripheralTool {
+    fn name(&self) -> &str {
+        "peripheral"
+    }
+
+    fn description(&self) -> &str {
+        "Control devices wired to this board by name (LEDs, relays, buttons, I2C sensors). \
+         Actions: list, read (name), write (name, value 0/1 for outputs)."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Board GPIO/I2C devices"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Hardware
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "read", "write"],
+                    "description": "The peripheral action to perform"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Device name from the manifest, e.g. \"desk LED\" (for read, write)"
+                },
+                "value": {
+                    "type": "integer",
+                    "description": "1 = on, 0 = off (for write)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'action' parameter".into()))?;
+
+        if action == "list" {
+            if self.config.devices.is_empty() {
+                return Ok(ToolOutput::llm_only(
+                    "No devices configured. Add them under peripherals.devices in config."
+                        .to_string(),
+                ));
+            }
+            let list: Vec<Value> = self
+                .config
+                .devices
+                .iter()
+                .map(|d| describe(&self.config, d))
+                .collect();
+            return serde_json::to_string_pretty(&list)
+                .map(ToolOutput::llm_only)
+                .map_err(|e| ZeptoError::Tool(format!("JSON serialize error: {e}")));
+        }
+
+        let name = args
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZeptoError::Tool(format!("Missing 'name' parameter for {}", action)))?;
+        let device = find_device(&self.config, name).cloned().ok_or_else(|| {
+            ZeptoError::Tool(format!(
+                "Unknown device '{}'. Use action 'list' to see configured devices",
+                name
+            ))
+        })?;
+
+        let output = match action {
+            "read" => {
+                let tool = self.clone_handles();
+                tokio::task::spawn_blocking(move || tool.read_device(&device))
+                    .await
+                    .map_err(|e| ZeptoError::Tool(format!("Peripheral read join error: {e}")))??
+            }
+            "write" => {
+                let value = args
+                    .get("value")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| ZeptoError::Tool("Missing 'value' parameter".into()))?;
+                let tool = self.clone_handles();
+                tokio::task::spawn_blocking(move || {
+                    tool.write_device(&device, u8::from(value != 0))
+                })
+                .await
+                .map_err(|e| ZeptoError::Tool(format!("Peripheral write join error: {e}")))??
+            }
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown peripheral action: '{}'. Valid actions: list, read, write",
+                    other
+                )))
+            }
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+impl PeripheralTool {
+    /// Cheap clone sharing config and held handles, for blocking tasks.
+    fn clone_handles(&self) -> Self {
+        Self {
+            config: Arc::clone(&self.config),
+            held: Arc::clone(&self.held),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> PeripheralsConfig {
+        serde_json::from_value(json!({
+            "devices": [
+                {"name": "Desk LED", "type": "gpio", "line": 17, "direction": "output"},
+                {"name": "button", "type": "gpio", "line": 27},
+                {"name": "temp sensor", "type": "i2c", "address": 72,
+                 "format": "i16be", "shift": 4, "scale": 0.0625, "unit": "°C"}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_manifest_parses_and_defaults() {
+        let config = manifest();
+        assert_eq!(config.gpio_chip, "gpiochip0");
+        assert_eq!(config.i2c_bus, 1);
+        assert!(matches!(
+            config.devices[1].kind,
+            PeripheralKind::Gpio {
+                direction: GpioDirection::Input,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_find_device_case_insensitive() {
+        let config = manifest();
+        assert!(find_device(&config, "desk led").is_some());
+        assert!(find_device(&config, "  TEMP SENSOR ").is_some());
+        assert!(find_device(&config, "lamp").is_none());
+    }
+
+    #[test]
+    fn test_decode_value_tmp102() {
+        // 0x1900 >> 4 = 400 * 0.0625 = 25.0 °C
+        assert_eq!(
+            decode_value(&[0x19, 0x00], I2cValueFormat::I16be, 4, 0.0625, 0.0),
+            25.0
+        );
+        // Negative temperature: 0xE700 >> 4 = -400 → -25.0 °C
+        assert_eq!(
+            decode_value(&[0xE7, 0x00], I2cValueFormat::I16be, 4, 0.0625, 0.0),
+            -25.0
+        );
+        assert_eq!(
+            decode_value(&[0x34, 0x12], I2cValueFormat::U16le, 0, 1.0, 0.0),
+            4660.0
+        );
+        assert_eq!(decode_value(&[0xFF], I2cValueFormat::I8, 0, 1.0, 10.0), 9.0);
+    }
+
+    #[test]
+    fn test_chip_path_validation() {
+        assert_eq!(chip_path("gpiochip0").unwrap(), "/dev/gpiochip0");
+        assert_eq!(chip_path("/dev/gpiochip4").unwrap(), "/dev/gpiochip4");
+        assert!(chip_path("../etc/passwd").is_err());
+        assert!(chip_path("gpiochip").is_err());
+    }
+
+    #[test]
+    fn test_ioctl_numbers_match_kernel() {
+        assert_eq!(GPIO_GET_LINEHANDLE_IOCTL, 0xC16C_B403);
+        assert_eq!(GPIOHANDLE_GET_LINE_VALUES_IOCTL, 0xC040_B408);
+        assert_eq!(GPIOHANDLE_SET_LINE_VALUES_IOCTL, 0xC040_B409);
+    }
+
+    #[tokio::test]
+    async fn test_write_rejects_inputs_and_sensors() {
+        let peripheral = LinuxSbcPeripheral::new(manifest());
+        let tool = peripheral.tools().remove(0);
+        let ctx = ToolContext::new();
+        let err = tool
+            .execute(
+                json!({"action": "write", "name": "button", "value": 1}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("input"));
+        let err = tool
+            .execute(
+                json!({"action": "write", "name": "temp sensor", "value": 1}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("sensor"));
+    }
+}
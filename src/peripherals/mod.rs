@@ -8,6 +8,8 @@
 //!
 //! - `hardware`: Enables serial peripherals (STM32, Arduino, ESP32)
 //! - `peripheral-rpi`: Enables Raspberry Pi GPIO (Linux only, via rppal)
+//! - `peripheral-linux`: Enables generic SBC GPIO/I2C via `/dev/gpiochipN` and
+//!   `/dev/i2c-N`, driven by the `peripherals.devices` manifest (Linux only)
 //!
 //! Without feature flags, only the `Peripheral` trait and stub factory are compiled.
 
@@ -38,6 +40,9 @@ pub mod rpi_i2c;
 #[cfg(feature = "peripheral-esp32")]
 pub mod esp32;
 
+#[cfg(all(feature = "peripheral-linux", target_os = "linux"))]
+pub mod linux_sbc;
+
 pub use traits::Peripheral;
 
 use crate::tools::Tool;
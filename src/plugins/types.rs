@@ -4,6 +4,7 @@
 //! manifest structures for parsing `plugin.json` files, plugin configuration,
 //! and the runtime plugin representation.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -190,7 +191,7 @@ impl Plugin {
 ///
 /// Controls whether the plugin system is active, which directories are
 /// scanned for plugins, and which plugins are allowed or blocked.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PluginConfig {
     /// Whether the plugin system is enabled. Defaults to false.
     #[serde(default)]
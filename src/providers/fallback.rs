@@ -257,6 +257,7 @@ impl LLMProvider for FallbackProvider {
                 let should_fallback = match &primary_err {
                     crate::error::ZeptoError::ProviderTyped(pe) => pe.should_fallback(),
                     crate::error::ZeptoError::QuotaRejected(_) => false,
+                    crate::error::ZeptoError::Cancelled => false,
                     _ => true, // Legacy errors always fallback
                 };
 
@@ -334,6 +335,7 @@ impl LLMProvider for FallbackProvider {
                 let should_fallback = match &primary_err {
                     crate::error::ZeptoError::ProviderTyped(pe) => pe.should_fallback(),
                     crate::error::ZeptoError::QuotaRejected(_) => false,
+                    crate::error::ZeptoError::Cancelled => false,
                     _ => true, // Legacy errors always fallback
                 };
 
@@ -0,0 +1,312 @@
+//! Scriptable provider for tests.
+//!
+//! [`MockProvider`] answers from a script of responses, tool-call sequences
+//! and injected errors, one entry per call, and records every request it
+//! receives. It makes no network calls, so code embedding [`AgentLoop`] can
+//! run whole turns in integration tests. Pair it with
+//! [`FakeTool`](crate::tools::FakeTool) for tool calls.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//! use zeptoclaw::agent::AgentLoop;
+//! use zeptoclaw::bus::{InboundMessage, MessageBus};
+//! use zeptoclaw::config::Config;
+//! use zeptoclaw::providers::MockProvider;
+//! use zeptoclaw::session::SessionManager;
+//! use zeptoclaw::tools::FakeTool;
+//!
+//! # tokio_test::block_on(async {
+//! let provider = Arc::new(
+//!     MockProvider::new()
+//!         .with_tool_call("lookup", serde_json::json!({"city": "Lisbon"}))
+//!         .with_text("It is sunny in Lisbon."),
+//! );
+//! let lookup = FakeTool::new("lookup").returning("sunny, 24°C");
+//!
+//! let agent = AgentLoop::new(
+//!     Config::default(),
+//!     SessionManager::new_memory(),
+//!     Arc::new(MessageBus::new()),
+//! );
+//! agent.set_provider_arc(provider.clone()).await;
+//! agent.register_tool(Box::new(lookup.clone())).await;
+//!
+//! let msg = InboundMessage::new("test", "user", "chat", "Weather in Lisbon?");
+//! let reply = agent.process_message(&msg).await.unwrap();
+//! assert_eq!(reply, "It is sunny in Lisbon.");
+//! assert_eq!(lookup.calls()[0]["city"], "Lisbon");
+//! assert_eq!(provider.call_count(), 2);
+//! # });
+//! ```
+//!
+//! [`AgentLoop`]: crate::agent::AgentLoop
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::error::{Result, ZeptoError};
+use crate::session::Message;
+
+use super::{ChatOptions, LLMProvider, LLMResponse, LLMToolCall, ToolDefinition};
+
+/// One scripted answer.
+enum MockReply {
+    Response(LLMResponse),
+    Error(ZeptoError),
+}
+
+/// A request received by a [`MockProvider`].
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// Messages sent, including the system prompt.
+    pub messages: Vec<Message>,
+    /// Names of the tools offered.
+    pub tools: Vec<String>,
+    /// Model requested, if any.
+    pub model: Option<String>,
+}
+
+impl MockRequest {
+    /// Content of the last user message.
+    pub fn last_user_message(&self) -> Option<&str> {
+        self.messages
+            .iter()
+            .rev()
+            .find(|m| m.role == crate::session::Role::User)
+            .map(|m| m.content.as_str())
+    }
+}
+
+/// Provider that answers from a script. Calls past the end of the script
+/// return the fallback text, or an error when none is set.
+pub struct MockProvider {
+    name: String,
+    model: String,
+    script: Mutex<VecDeque<MockReply>>,
+    fallback: Option<String>,
+    latency: Option<Duration>,
+    requests: Mutex<Vec<MockRequest>>,
+    next_call_id: usize,
+}
+
+impl Default for MockProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockProvider {
+    /// Provider named `mock` with an empty script.
+    pub fn new() -> Self {
+        Self {
+            name: "mock".to_string(),
+            model: "mock-model".to_string(),
+            script: Mutex::new(VecDeque::new()),
+            fallback: None,
+            latency: None,
+            requests: Mutex::new(Vec::new()),
+            next_call_id: 0,
+        }
+    }
+
+    /// Answer every call with `text`.
+    pub fn text(text: &str) -> Self {
+        Self::new().with_fallback(text)
+    }
+
+    /// Set the provider name.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the default model.
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = model.to_string();
+        self
+    }
+
+    /// Queue a response.
+    pub fn with_response(mut self, response: LLMResponse) -> Self {
+        self.push(MockReply::Response(response));
+        self
+    }
+
+    /// Queue a text response.
+    pub fn with_text(self, text: &str) -> Self {
+        self.with_response(LLMResponse::text(text))
+    }
+
+    /// Queue a response calling one tool.
+    pub fn with_tool_call(self, name: &str, args: serde_json::Value) -> Self {
+        self.with_tool_calls(vec![(name, args)])
+    }
+
+    /// Queue a response calling several tools at once. Call IDs are
+    /// generated (`call_1`, `call_2`, ...).
+    pub fn with_tool_calls(mut self, calls: Vec<(&str, serde_json::Value)>) -> Self {
+        let calls = calls
+            .into_iter()
+            .map(|(name, args)| {
+                self.next_call_id += 1;
+                let id = format!("call_{}", self.next_call_id);
+                LLMToolCall::new(&id, name, &args.to_string())
+            })
+            .collect();
+        self.push(MockReply::Response(LLMResponse::with_tools("", calls)));
+        self
+    }
+
+    /// Queue an error, e.g. `ZeptoError::ProviderTyped(ProviderError::RateLimit(..))`.
+    pub fn with_error(mut self, error: ZeptoError) -> Self {
+        self.push(MockReply::Error(error));
+        self
+    }
+
+    /// Answer with `text` once the script is used up.
+    pub fn with_fallback(mut self, text: &str) -> Self {
+        self.fallback = Some(text.to_string());
+        self
+    }
+
+    /// Wait `latency` before answering each call.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    fn push(&mut self, reply: MockReply) {
+        if let Ok(script) = self.script.get_mut() {
+            script.push_back(reply);
+        }
+    }
+
+    /// Requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().map(|r| r.clone()).unwrap_or_default()
+    }
+
+    /// Number of calls received so far.
+    pub fn call_count(&self) -> usize {
+        self.requests.lock().map(|r| r.len()).unwrap_or(0)
+    }
+
+    /// Scripted replies not used yet.
+    pub fn remaining(&self) -> usize {
+        self.script.lock().map(|s| s.len()).unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl LLMProvider for MockProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn default_model(&self) -> &str {
+        &self.model
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        _options: ChatOptions,
+    ) -> Result<LLMResponse> {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(MockRequest {
+                messages,
+                tools: tools.into_iter().map(|t| t.name).collect(),
+                model: model.map(str::to_string),
+            });
+        }
+        if let Some(latency) = self.latency {
+            tokio::time::sleep(latency).await;
+        }
+        let reply = self.script.lock().ok().and_then(|mut s| s.pop_front());
+        match (reply, &self.fallback) {
+            (Some(MockReply::Response(response)), _) => Ok(response),
+            (Some(MockReply::Error(error)), _) => Err(error),
+            (None, Some(text)) => Ok(LLMResponse::text(text)),
+            (None, None) => Err(ZeptoError::Provider("MockProvider script exhausted".into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ProviderError;
+
+    #[tokio::test]
+    async fn test_mock_provider_script() {
+        let provider = MockProvider::new()
+            .with_tool_calls(vec![
+                ("a", serde_json::json!({"x": 1})),
+                ("b", serde_json::json!({})),
+            ])
+            .with_error(ZeptoError::ProviderTyped(ProviderError::RateLimit(
+                "slow down".into(),
+            )))
+            .with_text("done");
+        assert_eq!(provider.remaining(), 3);
+
+        let tools = vec![ToolDefinition::new("a", "A", serde_json::json!({}))];
+        let first = provider
+            .chat(
+                vec![Message::user("go")],
+                tools,
+                Some("m"),
+                ChatOptions::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.tool_calls[0].id, "call_1");
+        assert_eq!(first.tool_calls[1].id, "call_2");
+        assert_eq!(first.tool_calls[0].arguments, r#"{"x":1}"#);
+
+        let err = provider
+            .chat(vec![], vec![], None, ChatOptions::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ZeptoError::ProviderTyped(ProviderError::RateLimit(_))
+        ));
+        let last = provider
+            .chat(vec![], vec![], None, ChatOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(last.content, "done");
+        assert!(provider
+            .chat(vec![], vec![], None, ChatOptions::new())
+            .await
+            .is_err());
+
+        let requests = provider.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].tools, vec!["a"]);
+        assert_eq!(requests[0].model.as_deref(), Some("m"));
+        assert_eq!(requests[0].last_user_message(), Some("go"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_provider_fallback() {
+        let provider = MockProvider::text("same").with_name("fixed");
+        for _ in 0..3 {
+            let response = provider
+                .chat(vec![], vec![], None, ChatOptions::new())
+                .await
+                .unwrap();
+            assert_eq!(response.content, "same");
+        }
+        assert_eq!(provider.name(), "fixed");
+        assert_eq!(provider.call_count(), 3);
+    }
+}
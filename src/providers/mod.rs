@@ -26,13 +26,16 @@ pub mod cooldown;
 pub mod error_classifier;
 pub mod fallback;
 pub mod gemini;
+pub mod mock;
 pub mod openai;
 pub mod plugin;
 pub mod quota;
 mod registry;
+pub mod replay;
 pub mod retry;
 pub mod rotation;
 pub mod structured;
+pub mod tokenizer;
 mod types;
 
 /// Provider IDs currently supported by the runtime.
@@ -61,6 +64,7 @@ pub use cooldown::{CooldownTracker, FailoverReason};
 pub use error_classifier::classify_error_message;
 pub use fallback::FallbackProvider;
 pub use gemini::GeminiProvider;
+pub use mock::{MockProvider, MockRequest};
 pub use openai::OpenAIProvider;
 pub use plugin::ProviderPlugin;
 pub use quota::{
@@ -71,6 +75,7 @@ pub use registry::{
     provider_config_by_name, resolve_runtime_provider, resolve_runtime_providers, ProviderSpec,
     RuntimeProviderSelection, PROVIDER_REGISTRY,
 };
+pub use replay::{RecordingProvider, ReplayProvider};
 pub use retry::RetryProvider;
 pub use rotation::{RotationProvider, RotationStrategy};
 pub use structured::{validate_json_response, OutputFormat};
@@ -34,10 +34,11 @@ use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use chrono::Utc;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Reset cadence for quota counters.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum QuotaPeriod {
     /// Counters reset at the start of each calendar month (default).
@@ -48,7 +49,7 @@ pub enum QuotaPeriod {
 }
 
 /// Action to take when a provider's quota is exceeded.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum QuotaAction {
     /// Reject the request with `ZeptoError::QuotaExceeded` (default).
@@ -64,7 +65,7 @@ pub enum QuotaAction {
 ///
 /// All fields are optional — an unconfigured `QuotaConfig` (all `None` limits)
 /// always returns `QuotaCheckResult::Ok`.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct QuotaConfig {
     /// Maximum spend in USD for the period. `None` means no cost limit.
     pub max_cost_usd: Option<f64>,
@@ -467,8 +468,9 @@ impl crate::providers::LLMProvider for QuotaProvider {
 
 /// Canonical path for the usage file: `~/.zeptoclaw/quota/usage.json`.
 fn dirs_path() -> PathBuf {
-    let base = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    base.join(".zeptoclaw").join("quota").join("usage.json")
+    crate::config::Config::state_dir()
+        .join("quota")
+        .join("usage.json")
 }
 
 /// Load `HashMap<String, QuotaUsage>` from JSON; returns empty map on error.
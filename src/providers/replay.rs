@@ -0,0 +1,204 @@
+//! Recording and replaying provider responses.
+//!
+//! [`RecordingProvider`] wraps the turn's provider when `session.ledger` is
+//! enabled and records every response in the turn ledger.
+//! [`ReplayProvider`] serves those responses back in order without a network
+//! call, so a recorded turn can be re-run deterministically (see
+//! [`crate::agent::replay`]).
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::error::{Result, ZeptoError};
+use crate::session::ledger::LedgerRecorder;
+use crate::session::Message;
+
+use super::{ChatOptions, LLMProvider, LLMResponse, StreamEvent, ToolDefinition};
+
+/// Records the responses of an inner provider in a turn ledger.
+pub struct RecordingProvider {
+    inner: Arc<dyn LLMProvider>,
+    ledger: Arc<LedgerRecorder>,
+}
+
+impl RecordingProvider {
+    /// Wrap `inner`, recording its responses in `ledger`.
+    pub fn new(inner: Arc<dyn LLMProvider>, ledger: Arc<LedgerRecorder>) -> Self {
+        Self { inner, ledger }
+    }
+}
+
+#[async_trait]
+impl LLMProvider for RecordingProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn default_model(&self) -> &str {
+        self.inner.default_model()
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<LLMResponse> {
+        let response = self.inner.chat(messages, tools, model, options).await?;
+        self.ledger
+            .record_llm_response(&response.content, &response.tool_calls);
+        Ok(response)
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<Message>,
+        tools: Vec<ToolDefinition>,
+        model: Option<&str>,
+        options: ChatOptions,
+    ) -> Result<tokio::sync::mpsc::Receiver<StreamEvent>> {
+        let mut inner_rx = self
+            .inner
+            .chat_stream(messages, tools, model, options)
+            .await?;
+        let (tx, rx) = tokio::sync::mpsc::channel(32);
+        let ledger = Arc::clone(&self.ledger);
+        tokio::spawn(async move {
+            while let Some(event) = inner_rx.recv().await {
+                match &event {
+                    StreamEvent::Done { content, .. } => ledger.record_llm_response(content, &[]),
+                    StreamEvent::ToolCalls(calls) => ledger.record_llm_response("", calls),
+                    _ => {}
+                }
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    fn count_tokens(&self, model: &str, messages: &[Message]) -> usize {
+        self.inner.count_tokens(model, messages)
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.inner.embed(texts).await
+    }
+}
+
+/// Serves recorded responses in order. Each call takes the next response;
+/// a call after the last one fails, which means the replayed turn asked the
+/// model more often than the recorded one did.
+pub struct ReplayProvider {
+    model: String,
+    responses: Mutex<VecDeque<LLMResponse>>,
+    /// The messages of each call, for inspecting what the replayed turn sent.
+    calls: Mutex<Vec<Vec<Message>>>,
+}
+
+impl ReplayProvider {
+    /// Serve `responses` (in order) as `model`.
+    pub fn new(model: &str, responses: Vec<LLMResponse>) -> Self {
+        Self {
+            model: model.to_string(),
+            responses: Mutex::new(responses.into()),
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Recorded responses not served yet.
+    pub fn remaining(&self) -> usize {
+        self.responses.lock().map(|r| r.len()).unwrap_or(0)
+    }
+
+    /// The messages sent with each call so far.
+    pub fn calls(&self) -> Vec<Vec<Message>> {
+        self.calls.lock().map(|c| c.clone()).unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl LLMProvider for ReplayProvider {
+    fn name(&self) -> &str {
+        "replay"
+    }
+
+    fn default_model(&self) -> &str {
+        &self.model
+    }
+
+    async fn chat(
+        &self,
+        messages: Vec<Message>,
+        _tools: Vec<ToolDefinition>,
+        _model: Option<&str>,
+        _options: ChatOptions,
+    ) -> Result<LLMResponse> {
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.push(messages);
+        }
+        self.responses
+            .lock()
+            .ok()
+            .and_then(|mut r| r.pop_front())
+            .ok_or_else(|| {
+                ZeptoError::Provider(
+                    "Replay exhausted: the turn requested more LLM responses than were recorded"
+                        .into(),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::InboundMessage;
+    use crate::providers::LLMToolCall;
+    use crate::session::ledger::TurnOutcome;
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let msg = InboundMessage::new("cli", "user", "cli", "hi");
+        let ledger = Arc::new(LedgerRecorder::start(&msg));
+        let script = ReplayProvider::new(
+            "m",
+            vec![
+                LLMResponse::with_tools("", vec![LLMToolCall::new("1", "echo", "{}")]),
+                LLMResponse::text("done"),
+            ],
+        );
+        let recording = RecordingProvider::new(Arc::new(script), Arc::clone(&ledger));
+        for _ in 0..2 {
+            recording
+                .chat(vec![Message::user("hi")], vec![], None, ChatOptions::new())
+                .await
+                .unwrap();
+        }
+        let turn = ledger.finish(Some("done"), TurnOutcome::Completed);
+
+        let replay = ReplayProvider::new("m", turn.llm_responses());
+        let first = replay
+            .chat(vec![Message::user("hi")], vec![], None, ChatOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(first.tool_calls[0].name, "echo");
+        let mut rx = replay
+            .chat_stream(vec![], vec![], None, ChatOptions::new())
+            .await
+            .unwrap();
+        assert!(
+            matches!(rx.recv().await, Some(StreamEvent::Done { content, .. }) if content == "done")
+        );
+        assert_eq!(replay.remaining(), 0);
+        assert_eq!(replay.calls().len(), 2);
+        assert!(replay
+            .chat(vec![], vec![], None, ChatOptions::new())
+            .await
+            .is_err());
+    }
+}
@@ -292,6 +292,9 @@ impl LLMProvider for RetryProvider {
                     );
                 }
                 delay_with_jitter(attempt - 1, self.base_delay_ms, self.max_delay_ms).await;
+                if options.is_cancelled() {
+                    return Err(ZeptoError::Cancelled);
+                }
             }
 
             match self
@@ -330,6 +333,9 @@ impl LLMProvider for RetryProvider {
                 );
             }
             delay_with_jitter(self.max_retries - 1, self.base_delay_ms, self.max_delay_ms).await;
+            if options.is_cancelled() {
+                return Err(ZeptoError::Cancelled);
+            }
         }
         self.inner.chat(messages, tools, model, options).await
     }
@@ -366,6 +372,9 @@ impl LLMProvider for RetryProvider {
                     );
                 }
                 delay_with_jitter(attempt - 1, self.base_delay_ms, self.max_delay_ms).await;
+                if options.is_cancelled() {
+                    return Err(ZeptoError::Cancelled);
+                }
             }
 
             match self
@@ -404,6 +413,9 @@ impl LLMProvider for RetryProvider {
                 );
             }
             delay_with_jitter(self.max_retries - 1, self.base_delay_ms, self.max_delay_ms).await;
+            if options.is_cancelled() {
+                return Err(ZeptoError::Cancelled);
+            }
         }
         self.inner
             .chat_stream(messages, tools, model, options)
@@ -738,6 +750,28 @@ mod tests {
         assert_eq!(result.unwrap().content, "recovered");
     }
 
+    #[tokio::test]
+    async fn test_retry_provider_stops_when_cancelled() {
+        let inner = FailThenSucceedProvider::new(1, "HTTP 429 Too Many Requests");
+        let provider = RetryProvider::new(Box::new(inner))
+            .with_max_retries(3)
+            .with_base_delay_ms(1)
+            .with_max_delay_ms(10);
+        let cancel = tokio_util::sync::CancellationToken::new();
+        cancel.cancel();
+
+        let result = provider
+            .chat(
+                vec![],
+                vec![],
+                None,
+                ChatOptions::default().with_cancellation(cancel),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ZeptoError::Cancelled)));
+    }
+
     #[tokio::test]
     async fn test_retry_provider_no_retry_on_401() {
         let inner = FailThenSucceedProvider::new(1, "HTTP 401 Unauthorized");
@@ -26,6 +26,7 @@ use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
@@ -39,7 +40,7 @@ use super::{ChatOptions, LLMProvider, LLMResponse, StreamEvent, ToolDefinition};
 // ============================================================================
 
 /// Rotation strategy for selecting among healthy providers.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum RotationStrategy {
     /// Use providers in configured order, skip unhealthy ones.
@@ -0,0 +1,271 @@
+//! Model-aware token counting.
+//!
+//! Picks a tokenizer family from the model name and counts tokens for a
+//! slice of session messages, including per-message framing overhead and
+//! tool-call arguments.
+//!
+//! With the `tokenizer-tiktoken` feature, OpenAI models are counted exactly
+//! using `tiktoken-rs` (`o200k_base` / `cl100k_base`). All other families use
+//! calibrated characters-per-token tables, which track real tokenizers far
+//! more closely than word counts, especially for code and CJK text.
+//!
+//! # Example
+//!
+//! ```rust
+//! use zeptoclaw::providers::tokenizer::{count_tokens, TokenizerFamily};
+//! use zeptoclaw::session::Message;
+//!
+//! let messages = vec![Message::user("Hello, world!")];
+//! assert_eq!(TokenizerFamily::for_model("gpt-4o"), TokenizerFamily::O200k);
+//! assert!(count_tokens("claude-sonnet-4-5", &messages) > 0);
+//! ```
+
+use crate::session::Message;
+
+/// Tokenizer family used to count tokens for a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenizerFamily {
+    /// OpenAI `o200k_base` (gpt-4o, gpt-4.1, gpt-5, o-series).
+    O200k,
+    /// OpenAI `cl100k_base` (gpt-4, gpt-3.5-turbo, text-embedding-3).
+    Cl100k,
+    /// Anthropic Claude models.
+    Claude,
+    /// Google Gemini models.
+    Gemini,
+    /// Llama / Mistral / Qwen and other SentencePiece-style models.
+    SentencePiece,
+    /// Unknown model: conservative approximation.
+    Generic,
+}
+
+impl TokenizerFamily {
+    /// Select the tokenizer family for a model identifier.
+    ///
+    /// Provider prefixes such as `openai/` or `anthropic/` are ignored.
+    pub fn for_model(model: &str) -> Self {
+        let model = model.to_lowercase();
+        let name = model.rsplit('/').next().unwrap_or(&model);
+
+        if name.starts_with("gpt-4o")
+            || name.starts_with("gpt-4.1")
+            || name.starts_with("gpt-5")
+            || name.starts_with("chatgpt-4o")
+            || (name.starts_with('o') && name[1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            Self::O200k
+        } else if name.starts_with("gpt-") || name.starts_with("text-embedding") {
+            Self::Cl100k
+        } else if name.starts_with("claude") {
+            Self::Claude
+        } else if name.starts_with("gemini") || name.starts_with("gemma") {
+            Self::Gemini
+        } else if (name.contains("llama") && !name.starts_with("ollama"))
+            || name.contains("mistral")
+            || name.contains("mixtral")
+            || name.contains("qwen")
+            || name.contains("deepseek")
+        {
+            Self::SentencePiece
+        } else {
+            Self::Generic
+        }
+    }
+
+    /// Average characters per token for ASCII-dominant text.
+    fn chars_per_token(self) -> f64 {
+        match self {
+            Self::O200k => 4.2,
+            Self::Cl100k => 4.0,
+            Self::Claude => 3.5,
+            Self::Gemini => 4.0,
+            Self::SentencePiece => 3.6,
+            Self::Generic => 3.3,
+        }
+    }
+
+    /// Framing tokens added per message (role markers, separators).
+    fn message_overhead(self) -> usize {
+        match self {
+            Self::O200k | Self::Cl100k => 3,
+            Self::Claude => 4,
+            _ => 4,
+        }
+    }
+
+    /// Tokens added once per request to prime the assistant reply.
+    fn reply_priming(self) -> usize {
+        match self {
+            Self::O200k | Self::Cl100k => 3,
+            _ => 0,
+        }
+    }
+
+    /// Count tokens for a single piece of text.
+    pub fn count_text(self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        #[cfg(feature = "tokenizer-tiktoken")]
+        {
+            match self {
+                Self::O200k => {
+                    return tiktoken_rs::o200k_base_singleton()
+                        .encode_ordinary(text)
+                        .len()
+                }
+                Self::Cl100k => {
+                    return tiktoken_rs::cl100k_base_singleton()
+                        .encode_ordinary(text)
+                        .len()
+                }
+                _ => {}
+            }
+        }
+        self.approximate(text)
+    }
+
+    /// Character-table approximation. Non-ASCII characters (CJK, emoji,
+    /// accented text) are counted as roughly one token each since BPE
+    /// vocabularies rarely merge them.
+    fn approximate(self, text: &str) -> usize {
+        let mut ascii = 0usize;
+        let mut other = 0usize;
+        for c in text.chars() {
+            if c.is_ascii() {
+                ascii += 1;
+            } else {
+                other += 1;
+            }
+        }
+        let tokens = (ascii as f64 / self.chars_per_token()).ceil() as usize + other;
+        tokens.max(1)
+    }
+}
+
+/// Count the tokens a model would see for `messages`.
+///
+/// Includes message content, tool-call names and arguments, tool-call IDs,
+/// and per-message framing overhead.
+pub fn count_tokens(model: &str, messages: &[Message]) -> usize {
+    let family = TokenizerFamily::for_model(model);
+    if messages.is_empty() {
+        return 0;
+    }
+    let body: usize = messages
+        .iter()
+        .map(|msg| {
+            let mut tokens = family.message_overhead() + family.count_text(&msg.content);
+            if let Some(calls) = &msg.tool_calls {
+                for call in calls {
+                    tokens += family.count_text(&call.name) + family.count_text(&call.arguments);
+                }
+            }
+            if let Some(id) = &msg.tool_call_id {
+                tokens += family.count_text(id);
+            }
+            tokens
+        })
+        .sum();
+    body + family.reply_priming()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::ToolCall;
+
+    #[test]
+    fn test_family_for_model() {
+        assert_eq!(
+            TokenizerFamily::for_model("gpt-4o-mini"),
+            TokenizerFamily::O200k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("o3-mini"),
+            TokenizerFamily::O200k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("openai/gpt-5"),
+            TokenizerFamily::O200k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("gpt-4-turbo"),
+            TokenizerFamily::Cl100k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("gpt-3.5-turbo"),
+            TokenizerFamily::Cl100k
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("claude-sonnet-4-5-20250929"),
+            TokenizerFamily::Claude
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("anthropic/claude-3-haiku"),
+            TokenizerFamily::Claude
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("gemini-2.0-flash"),
+            TokenizerFamily::Gemini
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("meta-llama/llama-3.1-70b"),
+            TokenizerFamily::SentencePiece
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("ollama-custom"),
+            TokenizerFamily::Generic
+        );
+        assert_eq!(
+            TokenizerFamily::for_model("openrouter"),
+            TokenizerFamily::Generic
+        );
+    }
+
+    #[test]
+    fn test_count_empty() {
+        assert_eq!(count_tokens("gpt-4o", &[]), 0);
+        assert_eq!(TokenizerFamily::Claude.count_text(""), 0);
+    }
+
+    #[test]
+    fn test_claude_counts_more_than_openai_for_same_text() {
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(20);
+        let claude = TokenizerFamily::Claude.count_text(&text);
+        let openai = TokenizerFamily::Cl100k.count_text(&text);
+        assert!(claude > 0 && openai > 0);
+        assert!(claude >= openai);
+    }
+
+    #[test]
+    fn test_non_ascii_counted_per_char() {
+        let tokens = TokenizerFamily::Claude.count_text("你好世界");
+        assert_eq!(tokens, 4);
+    }
+
+    #[test]
+    fn test_tool_calls_and_overhead_counted() {
+        let plain = vec![Message::assistant("done")];
+        let with_call = vec![Message::assistant_with_tools(
+            "done",
+            vec![ToolCall::new(
+                "call_1",
+                "web_search",
+                r#"{"query":"rust tokenizers"}"#,
+            )],
+        )];
+        assert!(count_tokens("claude-3-opus", &with_call) > count_tokens("claude-3-opus", &plain));
+        // One message: overhead (4) + "done" (≥1)
+        assert!(count_tokens("claude-3-opus", &plain) >= 5);
+    }
+
+    #[test]
+    fn test_code_counts_higher_than_word_heuristic() {
+        let code = "fn main(){let x=vec![1,2,3];println!(\"{:?}\",x.iter().map(|v|v*2).collect::<Vec<_>>());}";
+        let messages = vec![Message::user(code)];
+        let words = code.split_whitespace().count();
+        let heuristic = (words as f64 * 1.3 + 4.0) as usize;
+        assert!(count_tokens("gpt-4", &messages) > heuristic);
+    }
+}
@@ -5,6 +5,7 @@
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
 
 use crate::error::{Result, ZeptoError};
 use crate::providers::structured::OutputFormat;
@@ -134,6 +135,15 @@ pub trait LLMProvider: Send + Sync {
         Ok(rx)
     }
 
+    /// Count the tokens `messages` would consume for `model`.
+    ///
+    /// The default implementation selects a tokenizer from the model name
+    /// (see [`crate::providers::tokenizer`]). Providers with a native
+    /// counting API may override this.
+    fn count_tokens(&self, model: &str, messages: &[Message]) -> usize {
+        crate::providers::tokenizer::count_tokens(model, messages)
+    }
+
     /// Embed texts into vector representations.
     ///
     /// Returns one embedding vector per input text. The dimensionality depends on
@@ -163,6 +173,8 @@ pub struct ChatOptions {
     pub stop: Option<Vec<String>>,
     /// Output format (text, JSON, or JSON schema)
     pub output_format: OutputFormat,
+    /// Cancelled when the agent turn is stopped; wrappers stop retrying
+    pub cancel: Option<CancellationToken>,
 }
 
 impl ChatOptions {
@@ -233,6 +245,17 @@ impl ChatOptions {
         self
     }
 
+    /// Attach the token that signals cancellation of the agent turn.
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Whether the request's agent turn has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|c| c.is_cancelled())
+    }
+
     /// Set stop sequences that will halt generation.
     ///
     /// # Arguments
@@ -351,7 +374,7 @@ impl LLMResponse {
 ///
 /// This represents the LLM's request to execute a specific tool
 /// with given arguments.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LLMToolCall {
     /// Unique identifier for this tool call
     pub id: String,
@@ -2,9 +2,11 @@
 //!
 //! Routines extend beyond simple cron jobs by supporting event triggers
 //! (regex matching on incoming messages), webhook triggers (HTTP POST
-//! path matching), and manual triggers.
+//! path matching), and manual triggers. [`usage_report`] posts scheduled
+//! usage reports to a channel.
 
 pub mod engine;
+pub mod usage_report;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -0,0 +1,250 @@
+//! Usage report routine.
+//!
+//! Builds a report from the daily usage files the gateway persists under
+//! `~/.zeptoclaw/metrics` (tokens, estimated cost, tool calls and busiest
+//! sessions) and posts it to the channel in `routines.usage_report.deliver_to`
+//! every day or once a week.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use tracing::{info, warn};
+
+use crate::bus::{MessageBus, OutboundMessage, Priority};
+use crate::config::{UsageReportConfig, UsageReportPeriod};
+use crate::health::{load_daily_usage_range, load_tool_usage_range, DailyUsage, ToolUsageStats};
+
+/// Usage aggregated over a date range.
+#[derive(Debug, Clone)]
+pub struct UsageReport {
+    /// First day covered.
+    pub start: NaiveDate,
+    /// Last day covered (inclusive).
+    pub end: NaiveDate,
+    /// Token, cost and session totals.
+    pub usage: DailyUsage,
+    /// Per-tool totals.
+    pub tools: HashMap<String, ToolUsageStats>,
+}
+
+impl UsageReport {
+    /// Load the `days` days ending at `end` from the metrics directory.
+    pub fn load(dir: &Path, end: NaiveDate, days: u32) -> std::io::Result<Self> {
+        let days = days.max(1);
+        Ok(Self {
+            start: end - Duration::days(days as i64 - 1),
+            end,
+            usage: load_daily_usage_range(dir, end, days)?,
+            tools: load_tool_usage_range(dir, end, days)?,
+        })
+    }
+
+    /// Whether nothing was recorded in the range.
+    pub fn is_empty(&self) -> bool {
+        self.usage.is_empty() && self.tools.is_empty()
+    }
+
+    /// Render the report as CommonMark, listing up to `top` tools and sessions.
+    pub fn render(&self, top: usize) -> String {
+        let heading = if self.start == self.end {
+            format!("**Usage report** ({})", self.end)
+        } else {
+            format!("**Usage report** ({} – {})", self.start, self.end)
+        };
+        let usage = &self.usage;
+        let mut out = format!(
+            "{}\nRequests: {} ({} errors)\nTokens: {} in / {} out\nEstimated cost: ${:.2}",
+            heading,
+            usage.requests,
+            usage.errors,
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cost_usd
+        );
+
+        let mut tools: Vec<(&String, &ToolUsageStats)> = self.tools.iter().collect();
+        tools.sort_by(|a, b| b.1.calls.cmp(&a.1.calls).then_with(|| a.0.cmp(b.0)));
+        if !tools.is_empty() {
+            out.push_str("\n\n**Top tools**");
+            for (index, (name, stats)) in tools.iter().take(top).enumerate() {
+                out.push_str(&format!(
+                    "\n{}. `{}` — {} calls, {:.0}% errors",
+                    index + 1,
+                    name,
+                    stats.calls,
+                    stats.error_rate() * 100.0
+                ));
+            }
+        }
+
+        let sessions = usage.busiest_sessions(top);
+        if !sessions.is_empty() {
+            out.push_str("\n\n**Busiest sessions**");
+            for (index, (key, session)) in sessions.iter().enumerate() {
+                out.push_str(&format!(
+                    "\n{}. `{}` — {} tokens, ${:.2}",
+                    index + 1,
+                    key,
+                    session.total_tokens(),
+                    session.cost_usd
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Days covered by one report.
+fn period_days(period: UsageReportPeriod) -> u32 {
+    match period {
+        UsageReportPeriod::Daily => 1,
+        UsageReportPeriod::Weekly => 7,
+    }
+}
+
+/// The first scheduled run strictly after `now` (local time).
+pub fn next_run(config: &UsageReportConfig, now: NaiveDateTime) -> NaiveDateTime {
+    let time = NaiveTime::from_hms_opt(config.hour.min(23), 0, 0).unwrap_or_default();
+    let mut date = now.date();
+    if date.and_time(time) <= now {
+        date = date.succ_opt().unwrap_or(date);
+    }
+    if config.period == UsageReportPeriod::Weekly {
+        let weekday = config.weekday.parse::<Weekday>().unwrap_or(Weekday::Mon);
+        while date.weekday() != weekday {
+            date = date.succ_opt().unwrap_or(date);
+        }
+    }
+    date.and_time(time)
+}
+
+/// Start the usage report routine as a background task. Returns `None`
+/// (with a warning) when `deliver_to` is missing or malformed.
+pub fn start_usage_report_scheduler(
+    bus: Arc<MessageBus>,
+    dir: PathBuf,
+    config: UsageReportConfig,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let Some((channel, chat_id)) = config
+        .deliver_to
+        .as_deref()
+        .and_then(|s| s.split_once(':'))
+        .filter(|(channel, chat_id)| !channel.is_empty() && !chat_id.is_empty())
+        .map(|(channel, chat_id)| (channel.to_string(), chat_id.to_string()))
+    else {
+        warn!("routines.usage_report.deliver_to is not in 'channel:chat_id' format; not started");
+        return None;
+    };
+
+    Some(tokio::spawn(async move {
+        loop {
+            let now = Local::now().naive_local();
+            let run_at = next_run(&config, now);
+            let wait = (run_at - now).to_std().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            // Cover complete days only: the period ending yesterday.
+            let end = run_at.date().pred_opt().unwrap_or(run_at.date());
+            let report = match UsageReport::load(&dir, end, period_days(config.period)) {
+                Ok(report) => report,
+                Err(e) => {
+                    warn!(error = %e, "Failed to load usage for report");
+                    continue;
+                }
+            };
+            if report.is_empty() {
+                info!("Usage report skipped: no usage recorded");
+                continue;
+            }
+            let msg = OutboundMessage::new(&channel, &chat_id, &report.render(config.top));
+            if let Err(e) = bus.publish_notification(msg, Priority::Normal).await {
+                warn!(error = %e, "Failed to deliver usage report");
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::UsageMetrics;
+
+    fn at(date: &str, hour: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_next_run() {
+        let weekly = UsageReportConfig::default();
+        // 2026-10-14 is a Wednesday.
+        assert_eq!(
+            next_run(&weekly, at("2026-10-14", 12, 0)),
+            at("2026-10-19", 9, 0)
+        );
+        assert_eq!(
+            next_run(&weekly, at("2026-10-19", 8, 59)),
+            at("2026-10-19", 9, 0)
+        );
+        assert_eq!(
+            next_run(&weekly, at("2026-10-19", 9, 0)),
+            at("2026-10-26", 9, 0)
+        );
+
+        let daily = UsageReportConfig {
+            period: UsageReportPeriod::Daily,
+            hour: 18,
+            ..Default::default()
+        };
+        assert_eq!(
+            next_run(&daily, at("2026-10-14", 12, 0)),
+            at("2026-10-14", 18, 0)
+        );
+        assert_eq!(
+            next_run(&daily, at("2026-10-14", 19, 0)),
+            at("2026-10-15", 18, 0)
+        );
+    }
+
+    #[test]
+    fn test_report_load_and_render() {
+        let tmp = tempfile::tempdir().unwrap();
+        let metrics = UsageMetrics::new();
+        metrics.set_tool_usage_dir(tmp.path().to_path_buf());
+        metrics.record_request();
+        metrics.record_session_tokens("telegram:1", "claude-sonnet-4-5-20250929", 1000, 200);
+        metrics.record_session_tokens("cli:a", "unknown-model", 50, 10);
+        metrics.record_tool_usage("web_fetch", 120, true, 40);
+        metrics.record_tool_usage("shell", 30, true, 20);
+        metrics.record_tool_usage("shell", 30, false, 20);
+        metrics.flush_tool_usage();
+        metrics.flush_daily_usage();
+
+        let today = Local::now().date_naive();
+        let report = UsageReport::load(tmp.path(), today, 7).unwrap();
+        assert_eq!(report.end - report.start, Duration::days(6));
+        let text = report.render(1);
+        assert!(text.starts_with("**Usage report** ("));
+        assert!(text.contains("Requests: 1 (0 errors)"));
+        assert!(text.contains("Tokens: 1050 in / 210 out"));
+        assert!(text.contains("1. `shell` — 2 calls, 50% errors"));
+        assert!(!text.contains("web_fetch"));
+        assert!(text.contains("1. `telegram:1` — 1200 tokens"));
+        assert!(!text.contains("cli:a"));
+
+        let empty = UsageReport {
+            start: today,
+            end: today,
+            usage: DailyUsage::default(),
+            tools: HashMap::new(),
+        };
+        assert!(empty.is_empty());
+        assert!(empty
+            .render(5)
+            .starts_with(&format!("**Usage report** ({})", today)));
+    }
+}
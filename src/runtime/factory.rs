@@ -15,7 +15,9 @@ use super::apple::AppleContainerRuntime;
 /// Create a container runtime from configuration
 pub async fn create_runtime(config: &RuntimeConfig) -> RuntimeResult<Arc<dyn ContainerRuntime>> {
     match config.runtime_type {
-        RuntimeType::Native => Ok(Arc::new(NativeRuntime::new())),
+        RuntimeType::Native => Ok(Arc::new(
+            NativeRuntime::new().with_shell(config.native_shell),
+        )),
         RuntimeType::Docker => {
             let extra_mounts =
                 validate_extra_mounts(&config.docker.extra_mounts, &config.mount_allowlist_path)
@@ -26,5 +26,5 @@ pub use docker::DockerRuntime;
 pub use factory::{available_runtimes, create_runtime};
 pub use firejail::FirejailRuntime;
 pub use landlock::LandlockRuntime;
-pub use native::NativeRuntime;
+pub use native::{shell_command, NativeRuntime};
 pub use types::{CommandOutput, ContainerConfig, ContainerRuntime, RuntimeError, RuntimeResult};
@@ -8,16 +8,80 @@ use std::process::Stdio;
 use std::time::Duration;
 use tokio::process::Command;
 
+use crate::config::NativeShell;
+
 use super::types::{CommandOutput, ContainerConfig, ContainerRuntime, RuntimeError, RuntimeResult};
 
 /// Native runtime that executes commands directly on the host
 #[derive(Debug, Clone, Default)]
-pub struct NativeRuntime;
+pub struct NativeRuntime {
+    shell: NativeShell,
+}
 
 impl NativeRuntime {
     /// Create a new native runtime
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Set the shell used to interpret commands
+    pub fn with_shell(mut self, shell: NativeShell) -> Self {
+        self.shell = shell;
+        self
+    }
+}
+
+/// Build a host shell invocation for `command` using `shell`.
+///
+/// Used by the native runtime and by hooks/plugins that run user commands
+/// directly on the host.
+pub fn shell_command(shell: NativeShell, command: &str) -> Command {
+    let (program, args) = shell_invocation(shell, command);
+    let mut cmd = Command::new(program);
+    // cmd.exe does not split its command line by the MSVC rules `args`
+    // quotes for, so quotes inside the command would arrive as `\"`.
+    // `/S` strips exactly the outer pair added here.
+    #[cfg(windows)]
+    if resolve_shell(shell) == NativeShell::Cmd {
+        cmd.args(&args[..args.len() - 1]);
+        cmd.raw_arg(format!("\"{}\"", command));
+        return cmd;
+    }
+    cmd.args(args);
+    cmd
+}
+
+/// Resolve `Auto` to the platform default shell.
+fn resolve_shell(shell: NativeShell) -> NativeShell {
+    match shell {
+        NativeShell::Auto if cfg!(windows) => NativeShell::Cmd,
+        NativeShell::Auto => NativeShell::Sh,
+        other => other,
+    }
+}
+
+/// Program and argument list used to run `command` under `shell`.
+fn shell_invocation(shell: NativeShell, command: &str) -> (String, Vec<String>) {
+    match resolve_shell(shell) {
+        NativeShell::Cmd => (
+            "cmd".to_string(),
+            vec!["/D".into(), "/S".into(), "/C".into(), command.to_string()],
+        ),
+        NativeShell::Powershell => {
+            // Windows PowerShell ships with every Windows install; elsewhere
+            // only PowerShell Core (`pwsh`) exists.
+            let program = if cfg!(windows) { "powershell" } else { "pwsh" };
+            (
+                program.to_string(),
+                vec![
+                    "-NoProfile".into(),
+                    "-NonInteractive".into(),
+                    "-Command".into(),
+                    command.to_string(),
+                ],
+            )
+        }
+        _ => ("sh".to_string(), vec!["-c".into(), command.to_string()]),
     }
 }
 
@@ -32,13 +96,16 @@ impl ContainerRuntime for NativeRuntime {
         true
     }
 
+    fn shell(&self) -> NativeShell {
+        resolve_shell(self.shell)
+    }
+
     async fn execute(
         &self,
         command: &str,
         config: &ContainerConfig,
     ) -> RuntimeResult<CommandOutput> {
-        let mut cmd = Command::new("sh");
-        cmd.arg("-c").arg(command);
+        let mut cmd = shell_command(self.shell, command);
 
         // Set working directory if specified
         if let Some(ref workdir) = config.workdir {
@@ -83,6 +150,58 @@ mod tests {
         assert_eq!(runtime.name(), "native");
     }
 
+    #[test]
+    fn test_shell_invocation_sh() {
+        let (program, args) = shell_invocation(NativeShell::Sh, "echo hi");
+        assert_eq!(program, "sh");
+        assert_eq!(args, vec!["-c", "echo hi"]);
+    }
+
+    #[test]
+    fn test_shell_invocation_cmd() {
+        let (program, args) = shell_invocation(NativeShell::Cmd, "dir");
+        assert_eq!(program, "cmd");
+        assert_eq!(args, vec!["/D", "/S", "/C", "dir"]);
+    }
+
+    #[test]
+    fn test_shell_invocation_powershell() {
+        let (program, args) = shell_invocation(NativeShell::Powershell, "Get-Date");
+        assert!(program == "pwsh" || program == "powershell");
+        assert_eq!(args.last().map(String::as_str), Some("Get-Date"));
+        assert!(args.contains(&"-NoProfile".to_string()));
+    }
+
+    #[test]
+    fn test_native_runtime_reports_resolved_shell() {
+        let runtime = NativeRuntime::new().with_shell(NativeShell::Powershell);
+        assert_eq!(runtime.shell(), NativeShell::Powershell);
+        assert_eq!(
+            NativeRuntime::new().shell(),
+            resolve_shell(NativeShell::Auto)
+        );
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn test_cmd_keeps_quotes_in_command() {
+        let output = shell_command(NativeShell::Cmd, r#"echo "a b""#)
+            .output()
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), r#""a b""#);
+    }
+
+    #[test]
+    fn test_auto_shell_matches_platform() {
+        let expected = if cfg!(windows) {
+            NativeShell::Cmd
+        } else {
+            NativeShell::Sh
+        };
+        assert_eq!(resolve_shell(NativeShell::Auto), expected);
+    }
+
     #[tokio::test]
     async fn test_native_runtime_echo() {
         let runtime = NativeRuntime::new();
@@ -4,6 +4,8 @@ use async_trait::async_trait;
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::config::NativeShell;
+
 /// Errors that can occur during runtime operations
 #[derive(Error, Debug)]
 pub enum RuntimeError {
@@ -144,6 +146,12 @@ pub trait ContainerRuntime: Send + Sync {
         command: &str,
         config: &ContainerConfig,
     ) -> RuntimeResult<CommandOutput>;
+
+    /// Shell that interprets commands passed to [`Self::execute`], so
+    /// callers can quote for it. Sandboxes and containers run `sh -c`.
+    fn shell(&self) -> NativeShell {
+        NativeShell::Sh
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,323 @@
+//! Personal data (PII) detection and redaction.
+//!
+//! Finds email addresses, phone numbers, national ID numbers (US SSN, UK
+//! National Insurance, Malaysian MyKad) and payment card numbers (Luhn
+//! checked) in text. The safety layer runs it on tool output, and with
+//! `outbound` set the channel manager runs it on replies before they leave.
+//!
+//! # Example
+//!
+//! ```
+//! use zeptoclaw::safety::pii::{PiiConfig, PiiDetector, PiiKind};
+//!
+//! let detector = PiiDetector::new(&PiiConfig::default());
+//! let (redacted, found) = detector.redact("card 4111 1111 1111 1111, mail a@b.io");
+//! assert_eq!(redacted, "card [REDACTED:credit_card], mail [REDACTED:email]");
+//! assert_eq!(found.len(), 2);
+//! assert_eq!(found[0].kind, PiiKind::CreditCard);
+//! ```
+
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Kind of personal data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    Email,
+    Phone,
+    /// US SSN, UK National Insurance number, Malaysian MyKad number.
+    NationalId,
+    /// Payment card number that passes the Luhn check.
+    CreditCard,
+}
+
+impl PiiKind {
+    /// Every kind, in detection priority order.
+    pub const ALL: [PiiKind; 4] = [
+        PiiKind::CreditCard,
+        PiiKind::NationalId,
+        PiiKind::Email,
+        PiiKind::Phone,
+    ];
+
+    /// Name used in config and redaction markers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PiiKind::Email => "email",
+            PiiKind::Phone => "phone",
+            PiiKind::NationalId => "national_id",
+            PiiKind::CreditCard => "credit_card",
+        }
+    }
+}
+
+/// What to do when personal data is found.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiAction {
+    /// Log a warning and pass the text through.
+    Warn,
+    /// Replace each match with `[REDACTED:<kind>]`.
+    #[default]
+    Redact,
+    /// Reject the text.
+    Block,
+}
+
+/// PII detection configuration (`safety.pii`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PiiConfig {
+    /// Whether PII detection runs on tool output.
+    pub enabled: bool,
+    /// What to do with matches.
+    pub action: PiiAction,
+    /// Kinds to detect.
+    pub kinds: Vec<PiiKind>,
+    /// Also check outbound channel messages.
+    pub outbound: bool,
+}
+
+impl Default for PiiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: PiiAction::Redact,
+            kinds: PiiKind::ALL.to_vec(),
+            outbound: false,
+        }
+    }
+}
+
+/// Sent instead of an outbound message that `block` stopped.
+pub const PII_WITHHELD_MESSAGE: &str =
+    "This reply was withheld because it contained personal data.";
+
+// ---------------------------------------------------------------------------
+// Detector
+// ---------------------------------------------------------------------------
+
+/// A piece of personal data found in text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiiMatch {
+    pub kind: PiiKind,
+    /// Byte range in the scanned text.
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Compiled PII detector. Construct once and reuse.
+pub struct PiiDetector {
+    action: PiiAction,
+    patterns: Vec<(PiiKind, Regex)>,
+}
+
+impl PiiDetector {
+    /// Build a detector for the kinds and action in `config`.
+    pub fn new(config: &PiiConfig) -> Self {
+        let patterns = PiiKind::ALL
+            .iter()
+            .filter(|kind| config.kinds.contains(kind))
+            .map(|kind| {
+                let source = match kind {
+                    PiiKind::Email => r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b",
+                    PiiKind::Phone => {
+                        r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{1,4}\)[\s.-]?|\b\d{1,4}[\s.-]|\b)\d{2,4}[\s.-]\d{3,4}(?:[\s.-]?\d{3,4})?\b"
+                    }
+                    PiiKind::NationalId => {
+                        r"\b(?:\d{3}-\d{2}-\d{4}|\d{6}-\d{2}-\d{4}|[A-CEGHJ-PR-TW-Z]{2} ?\d{2} ?\d{2} ?\d{2} ?[A-D])\b"
+                    }
+                    PiiKind::CreditCard => r"\b\d(?:[ -]?\d){12,18}\b",
+                };
+                (
+                    *kind,
+                    Regex::new(source).expect("static PII patterns must compile"),
+                )
+            })
+            .collect();
+        Self {
+            action: config.action,
+            patterns,
+        }
+    }
+
+    /// Configured action.
+    pub fn action(&self) -> PiiAction {
+        self.action
+    }
+
+    /// Find personal data in `text`, in order, without overlaps.
+    pub fn scan(&self, text: &str) -> Vec<PiiMatch> {
+        let mut found: Vec<PiiMatch> = Vec::new();
+        for (kind, pattern) in &self.patterns {
+            for m in pattern.find_iter(text) {
+                if !Self::plausible(*kind, m.as_str()) {
+                    continue;
+                }
+                let overlaps = found.iter().any(|f| m.start() < f.end && f.start < m.end());
+                if !overlaps {
+                    found.push(PiiMatch {
+                        kind: *kind,
+                        start: m.start(),
+                        end: m.end(),
+                    });
+                }
+            }
+        }
+        found.sort_by_key(|f| f.start);
+        found
+    }
+
+    /// Replace every match with `[REDACTED:<kind>]`.
+    pub fn redact(&self, text: &str) -> (String, Vec<PiiMatch>) {
+        let found = self.scan(text);
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for f in &found {
+            out.push_str(&text[last..f.start]);
+            out.push_str(&format!("[REDACTED:{}]", f.kind.as_str()));
+            last = f.end;
+        }
+        out.push_str(&text[last..]);
+        (out, found)
+    }
+
+    /// Apply the configured action to an outbound message: the text to send
+    /// instead, or `None` to send it unchanged.
+    pub fn filter_outbound(&self, channel: &str, text: &str) -> Option<String> {
+        let found = self.scan(text);
+        if found.is_empty() {
+            return None;
+        }
+        let kinds = kind_list(&found);
+        match self.action {
+            PiiAction::Warn => {
+                warn!(channel = channel, kinds = %kinds, "Outbound message contains PII");
+                None
+            }
+            PiiAction::Redact => {
+                warn!(channel = channel, kinds = %kinds, "Redacted PII from outbound message");
+                Some(self.redact(text).0)
+            }
+            PiiAction::Block => {
+                warn!(channel = channel, kinds = %kinds, "Withheld outbound message containing PII");
+                Some(PII_WITHHELD_MESSAGE.to_string())
+            }
+        }
+    }
+
+    /// Checks a regex can't express: digit counts and the Luhn checksum.
+    fn plausible(kind: PiiKind, matched: &str) -> bool {
+        let digits: Vec<u32> = matched.chars().filter_map(|c| c.to_digit(10)).collect();
+        match kind {
+            PiiKind::Phone => (7..=15).contains(&digits.len()),
+            PiiKind::CreditCard => (13..=19).contains(&digits.len()) && luhn_valid(&digits),
+            PiiKind::Email | PiiKind::NationalId => true,
+        }
+    }
+}
+
+/// Comma-separated kinds found, for warnings (`email, phone`).
+pub fn kind_list(found: &[PiiMatch]) -> String {
+    let mut kinds: Vec<&str> = Vec::new();
+    for f in found {
+        if !kinds.contains(&f.kind.as_str()) {
+            kinds.push(f.kind.as_str());
+        }
+    }
+    kinds.join(", ")
+}
+
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> PiiDetector {
+        PiiDetector::new(&PiiConfig::default())
+    }
+
+    fn kinds(text: &str) -> Vec<PiiKind> {
+        detector().scan(text).into_iter().map(|f| f.kind).collect()
+    }
+
+    #[test]
+    fn test_detects_each_kind() {
+        assert_eq!(kinds("write to jane.doe@example.com"), vec![PiiKind::Email]);
+        assert_eq!(kinds("call +60 12-345 6789"), vec![PiiKind::Phone]);
+        assert_eq!(kinds("call (415) 555-0132"), vec![PiiKind::Phone]);
+        assert_eq!(kinds("SSN 123-45-6789"), vec![PiiKind::NationalId]);
+        assert_eq!(kinds("IC 900101-14-5678"), vec![PiiKind::NationalId]);
+        assert_eq!(kinds("NINO AB 12 34 56 C"), vec![PiiKind::NationalId]);
+        assert_eq!(kinds("card 4111-1111-1111-1111"), vec![PiiKind::CreditCard]);
+    }
+
+    #[test]
+    fn test_ignores_non_pii_numbers() {
+        // Fails the Luhn check.
+        assert!(kinds("order 4111 1111 1111 1112").is_empty());
+        assert!(kinds("version 1.2.3, 42 items, 2026-10-16").is_empty());
+        assert!(kinds("plain text with no personal data").is_empty());
+    }
+
+    #[test]
+    fn test_kinds_filter_and_redact() {
+        let config = PiiConfig {
+            kinds: vec![PiiKind::Email],
+            ..Default::default()
+        };
+        let detector = PiiDetector::new(&config);
+        let (redacted, found) = detector.redact("a@b.io or 123-45-6789");
+        assert_eq!(redacted, "[REDACTED:email] or 123-45-6789");
+        assert_eq!(kind_list(&found), "email");
+    }
+
+    #[test]
+    fn test_filter_outbound_per_action() {
+        let text = "reach me at a@b.io";
+        let with = |action| {
+            PiiDetector::new(&PiiConfig {
+                action,
+                ..Default::default()
+            })
+            .filter_outbound("telegram", text)
+        };
+        assert_eq!(with(PiiAction::Warn), None);
+        assert_eq!(
+            with(PiiAction::Redact).as_deref(),
+            Some("reach me at [REDACTED:email]")
+        );
+        assert_eq!(
+            with(PiiAction::Block).as_deref(),
+            Some(PII_WITHHELD_MESSAGE)
+        );
+        assert_eq!(detector().filter_outbound("telegram", "hello"), None);
+    }
+}
@@ -7,15 +7,22 @@
 //!
 //! The engine is designed to be constructed once and reused across many
 //! invocations -- all regex patterns are compiled at construction time.
+//!
+//! Besides the built-in rules, `safety.policy_rules` in the config adds
+//! organization-specific rules ([`PolicyRuleConfig`]), optionally scoped to
+//! tools and channels. A custom rule can also `redact` what it matches.
 
 use regex::{Regex, RegexSet};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 // ---------------------------------------------------------------------------
 // Public types
 // ---------------------------------------------------------------------------
 
 /// How severe a policy violation is.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum PolicySeverity {
     /// Must be addressed immediately -- processing should stop.
     Critical,
@@ -28,7 +35,8 @@ pub enum PolicySeverity {
 }
 
 /// What the caller should do about a violation.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum PolicyAction {
     /// Stop processing and return an error.
     Block,
@@ -36,6 +44,66 @@ pub enum PolicyAction {
     Sanitize,
     /// Log a warning but allow processing to continue.
     Warn,
+    /// Replace every match with the rule's replacement and continue.
+    Redact,
+}
+
+/// How a custom rule's `pattern` is interpreted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyMatcher {
+    /// A regular expression (use `(?i)` for case-insensitive matching).
+    #[default]
+    Regex,
+    /// Plain text, matched case-insensitively.
+    Literal,
+}
+
+/// A config-defined policy rule (`safety.policy_rules`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PolicyRuleConfig {
+    /// Rule name reported in violations; also usable in ignored-rule lists.
+    pub name: String,
+    /// What to match, interpreted per `matcher`.
+    pub pattern: String,
+    pub matcher: PolicyMatcher,
+    pub action: PolicyAction,
+    pub severity: PolicySeverity,
+    /// Why this is a violation. Default: `Custom policy rule '<name>'`.
+    pub message: Option<String>,
+    /// Text that replaces matches for `redact`. Default: `[REDACTED:<name>]`.
+    pub replacement: Option<String>,
+    /// Tools the rule applies to. Empty = all.
+    pub tools: Vec<String>,
+    /// Channels the rule applies to. Empty = all.
+    pub channels: Vec<String>,
+}
+
+impl Default for PolicyRuleConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            pattern: String::new(),
+            matcher: PolicyMatcher::Regex,
+            action: PolicyAction::Warn,
+            severity: PolicySeverity::Medium,
+            message: None,
+            replacement: None,
+            tools: Vec::new(),
+            channels: Vec::new(),
+        }
+    }
+}
+
+impl PolicyRuleConfig {
+    /// The regex source for this rule.
+    pub fn regex_source(&self) -> String {
+        match self.matcher {
+            PolicyMatcher::Regex => self.pattern.clone(),
+            PolicyMatcher::Literal => format!("(?i){}", regex::escape(&self.pattern)),
+        }
+    }
 }
 
 /// A single policy violation detected by the engine.
@@ -57,14 +125,32 @@ pub struct PolicyViolation {
 // Internal rule definition
 // ---------------------------------------------------------------------------
 
-/// A compiled policy rule. Constructed once inside `PolicyEngine::new()`.
+/// A compiled policy rule. Constructed once inside `PolicyEngine::new()`
+/// or `PolicyEngine::with_rules()`.
 struct CompiledRule {
-    name: &'static str,
+    name: String,
     severity: PolicySeverity,
     action: PolicyAction,
-    description: &'static str,
+    description: String,
     /// Individual compiled regex used to extract the matched text.
     pattern: Regex,
+    /// Replacement text for `Redact`.
+    replacement: Option<String>,
+    /// Tool scope; empty = all.
+    tools: Vec<String>,
+    /// Channel scope; empty = all.
+    channels: Vec<String>,
+}
+
+impl CompiledRule {
+    /// Whether the rule applies to a scan for `tool` on `channel`. Scoped
+    /// rules never apply when the scan does not say which tool or channel.
+    fn in_scope(&self, tool: Option<&str>, channel: Option<&str>) -> bool {
+        let matches = |scope: &[String], value: Option<&str>| {
+            scope.is_empty() || value.is_some_and(|v| scope.iter().any(|s| s == "*" || s == v))
+        };
+        matches(&self.tools, tool) && matches(&self.channels, channel)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -159,26 +245,69 @@ impl PolicyEngine {
     /// silently skipped -- this mirrors the approach used by the existing
     /// `ShellSecurityConfig`.
     pub fn new() -> Self {
-        let patterns: Vec<&str> = RULE_DEFS.iter().map(|(_, _, _, _, pat)| *pat).collect();
-
-        let set = RegexSet::new(&patterns).expect("static policy patterns must compile");
-
         let rules: Vec<CompiledRule> = RULE_DEFS
             .iter()
             .filter_map(|(name, sev, act, desc, pat)| {
                 Regex::new(pat).ok().map(|regex| CompiledRule {
-                    name,
+                    name: name.to_string(),
                     severity: sev.clone(),
                     action: act.clone(),
-                    description: desc,
+                    description: desc.to_string(),
                     pattern: regex,
+                    replacement: None,
+                    tools: Vec::new(),
+                    channels: Vec::new(),
                 })
             })
             .collect();
+        let set = RegexSet::new(rules.iter().map(|r| r.pattern.as_str()))
+            .expect("static policy patterns must compile");
 
         Self { set, rules }
     }
 
+    /// Add config-defined rules after the built-in ones.
+    ///
+    /// Rules with an empty name or pattern, or a pattern that does not
+    /// compile, are skipped with a warning (`config check` reports them).
+    pub fn with_rules(mut self, custom: &[PolicyRuleConfig]) -> Self {
+        let builtin = self.rules.len();
+        for rule in custom {
+            if rule.name.trim().is_empty() || rule.pattern.is_empty() {
+                tracing::warn!(rule = %rule.name, "Skipping policy rule without name or pattern");
+                continue;
+            }
+            let pattern = match Regex::new(&rule.regex_source()) {
+                Ok(pattern) => pattern,
+                Err(error) => {
+                    tracing::warn!(rule = %rule.name, error = %error, "Skipping invalid policy rule");
+                    continue;
+                }
+            };
+            self.rules.push(CompiledRule {
+                name: rule.name.clone(),
+                severity: rule.severity.clone(),
+                action: rule.action.clone(),
+                description: rule
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| format!("Custom policy rule '{}'", rule.name)),
+                pattern,
+                replacement: rule.replacement.clone(),
+                tools: rule.tools.clone(),
+                channels: rule.channels.clone(),
+            });
+        }
+        match RegexSet::new(self.rules.iter().map(|r| r.pattern.as_str())) {
+            Ok(set) => self.set = set,
+            Err(error) => {
+                tracing::warn!(error = %error, "Custom policy rules too large; using built-in rules only");
+                self.rules.truncate(builtin);
+            }
+        }
+        self
+    }
+
     /// Check `input` against all policy rules.
     ///
     /// Returns a (possibly empty) list of violations. Multiple rules can
@@ -192,6 +321,18 @@ impl PolicyEngine {
         &self,
         input: &str,
         ignored_rules: &[&str],
+    ) -> Vec<PolicyViolation> {
+        self.check_in_scope(input, ignored_rules, None, None)
+    }
+
+    /// Like [`check_with_ignored_rules`](Self::check_with_ignored_rules),
+    /// also applying rules scoped to `tool` and `channel`.
+    pub fn check_in_scope(
+        &self,
+        input: &str,
+        ignored_rules: &[&str],
+        tool: Option<&str>,
+        channel: Option<&str>,
     ) -> Vec<PolicyViolation> {
         // Fast path: if no patterns match, return immediately.
         let matches: Vec<usize> = self.set.matches(input).into_iter().collect();
@@ -203,22 +344,36 @@ impl PolicyEngine {
 
         for idx in matches {
             let rule = &self.rules[idx];
-            if ignored_rules.contains(&rule.name) {
+            if ignored_rules.contains(&rule.name.as_str()) || !rule.in_scope(tool, channel) {
                 continue;
             }
             let matched_text = rule.pattern.find(input).map(|m| m.as_str().to_string());
 
             violations.push(PolicyViolation {
-                rule_name: rule.name.to_string(),
+                rule_name: rule.name.clone(),
                 severity: rule.severity.clone(),
                 action: rule.action.clone(),
-                description: rule.description.to_string(),
+                description: rule.description.clone(),
                 matched_text,
             });
         }
 
         violations
     }
+
+    /// Replace every match of the rule named `rule_name` in `input`.
+    pub fn redact(&self, input: &str, rule_name: &str) -> String {
+        let Some(rule) = self.rules.iter().find(|r| r.name == rule_name) else {
+            return input.to_string();
+        };
+        let replacement = rule
+            .replacement
+            .clone()
+            .unwrap_or_else(|| format!("[REDACTED:{}]", rule.name));
+        rule.pattern
+            .replace_all(input, regex::NoExpand(&replacement))
+            .into_owned()
+    }
 }
 
 impl Default for PolicyEngine {
@@ -385,6 +540,61 @@ mod tests {
         );
     }
 
+    // -- Custom rules ------------------------------------------------------
+
+    #[test]
+    fn test_custom_rules_scoped_and_redacted() {
+        let engine = PolicyEngine::new().with_rules(&[
+            PolicyRuleConfig {
+                name: "project_codename".to_string(),
+                pattern: "Bluebird".to_string(),
+                matcher: PolicyMatcher::Literal,
+                action: PolicyAction::Redact,
+                replacement: Some("[codename]".to_string()),
+                ..Default::default()
+            },
+            PolicyRuleConfig {
+                name: "prod_hosts".to_string(),
+                pattern: r"db\d+\.prod\.internal".to_string(),
+                action: PolicyAction::Block,
+                tools: vec!["shell".to_string()],
+                channels: vec!["telegram".to_string()],
+                ..Default::default()
+            },
+            PolicyRuleConfig {
+                name: "broken".to_string(),
+                pattern: "(".to_string(),
+                ..Default::default()
+            },
+        ]);
+
+        let v = engine.check("ship BLUEBIRD today");
+        let hit = v
+            .iter()
+            .find(|v| v.rule_name == "project_codename")
+            .unwrap();
+        assert_eq!(hit.action, PolicyAction::Redact);
+        assert_eq!(
+            engine.redact("ship BLUEBIRD today", "project_codename"),
+            "ship [codename] today"
+        );
+
+        let input = "ssh db1.prod.internal";
+        assert!(engine.check(input).is_empty());
+        assert!(engine
+            .check_in_scope(input, &[], Some("shell"), Some("cli"))
+            .is_empty());
+        let v = engine.check_in_scope(input, &[], Some("shell"), Some("telegram"));
+        assert_eq!(v[0].rule_name, "prod_hosts");
+        assert_eq!(v[0].action, PolicyAction::Block);
+
+        // Built-in rules still apply alongside custom ones.
+        assert!(engine
+            .check("cat /etc/passwd")
+            .iter()
+            .any(|v| v.rule_name == "system_file_access"));
+    }
+
     // -- Case insensitivity ------------------------------------------------
 
     #[test]
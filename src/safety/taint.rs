@@ -33,6 +33,7 @@
 
 use std::collections::{HashMap, HashSet};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
@@ -43,7 +44,7 @@ use crate::audit::{log_audit_event, AuditCategory, AuditSeverity};
 // ---------------------------------------------------------------------------
 
 /// Taint tracking configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct TaintConfig {
     /// Whether taint tracking is enabled.
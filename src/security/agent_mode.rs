@@ -10,6 +10,7 @@
 
 use std::collections::HashSet;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::tools::ToolCategory;
@@ -129,7 +130,7 @@ impl ModePolicy {
 }
 
 /// Configuration for agent mode.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct AgentModeConfig {
     /// The agent mode: "observer", "assistant", or "autonomous".
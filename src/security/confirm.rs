@@ -0,0 +1,84 @@
+//! One-time confirmation codes for actions the user approves out of band.
+//!
+//! Tools that send email or flash hardware show the user a code the agent
+//! never sees, and only act once the agent repeats the call with it. Codes
+//! are six digits, so a pending action is dropped after
+//! [`MAX_CONFIRMATION_ATTEMPTS`] wrong codes instead of letting the model
+//! guess.
+
+/// Wrong codes accepted before the pending action is discarded.
+pub const MAX_CONFIRMATION_ATTEMPTS: u32 = 3;
+
+/// Outcome of checking a code against a [`ConfirmationCode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeCheck {
+    /// The code matches; the action may run.
+    Matched,
+    /// Wrong code; this many attempts are left.
+    Wrong { remaining: u32 },
+    /// Wrong code and no attempts left; discard the action.
+    Exhausted,
+}
+
+/// A six-digit one-time code and the wrong guesses made against it.
+#[derive(Debug, Clone)]
+pub struct ConfirmationCode {
+    code: String,
+    failures: u32,
+}
+
+impl ConfirmationCode {
+    /// Generate a fresh random code.
+    pub fn new() -> Self {
+        let n = u128::from_le_bytes(*uuid::Uuid::new_v4().as_bytes());
+        Self {
+            code: format!("{:06}", n % 1_000_000),
+            failures: 0,
+        }
+    }
+
+    /// The code to show the user.
+    pub fn as_str(&self) -> &str {
+        &self.code
+    }
+
+    /// Check `given`, counting a wrong code against the attempt limit.
+    pub fn check(&mut self, given: &str) -> CodeCheck {
+        if given == self.code {
+            return CodeCheck::Matched;
+        }
+        self.failures += 1;
+        match MAX_CONFIRMATION_ATTEMPTS.saturating_sub(self.failures) {
+            0 => CodeCheck::Exhausted,
+            remaining => CodeCheck::Wrong { remaining },
+        }
+    }
+}
+
+impl Default for ConfirmationCode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_format() {
+        let code = ConfirmationCode::new();
+        assert_eq!(code.as_str().len(), 6);
+        assert!(code.as_str().chars().all(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_wrong_codes_exhaust_attempts() {
+        let mut code = ConfirmationCode::new();
+        let right = code.as_str().to_string();
+        assert_eq!(code.check("x"), CodeCheck::Wrong { remaining: 2 });
+        assert_eq!(code.check(&right), CodeCheck::Matched);
+        assert_eq!(code.check("x"), CodeCheck::Wrong { remaining: 1 });
+        assert_eq!(code.check("x"), CodeCheck::Exhausted);
+    }
+}
@@ -292,6 +292,42 @@ impl PassphraseEncryption {
         String::from_utf8(plaintext)
             .map_err(|e| ZeptoError::Config(format!("decrypted value is not valid UTF-8: {e}")))
     }
+
+    /// Encrypt binary data (e.g. a backup archive) with a fresh salt and
+    /// nonce. The output is `salt || nonce || ciphertext`.
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let salt = SecretEncryption::random_bytes::<ARGON2_SALT_LEN>();
+        let key = SecretEncryption::derive_key(&self.passphrase, &salt)?;
+
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|e| ZeptoError::Config(format!("encryption failed: {e}")))?;
+
+        let mut out = Vec::with_capacity(salt.len() + nonce.len() + ciphertext.len());
+        out.extend_from_slice(&salt);
+        out.extend_from_slice(nonce.as_slice());
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
+    }
+
+    /// Decrypt data produced by [`seal`](Self::seal).
+    pub fn open(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        if sealed.len() < ARGON2_SALT_LEN + XCHACHA_NONCE_LEN {
+            return Err(ZeptoError::Config("encrypted data is truncated".into()));
+        }
+        let (salt, rest) = sealed.split_at(ARGON2_SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(XCHACHA_NONCE_LEN);
+
+        let key = SecretEncryption::derive_key(&self.passphrase, salt)?;
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                ZeptoError::Config("decryption failed: wrong passphrase or corrupted data".into())
+            })
+    }
 }
 
 // ============================================================================
@@ -537,6 +573,21 @@ mod tests {
         assert!(!ct.is_empty());
     }
 
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let enc = PassphraseEncryption::new("backup passphrase");
+        let data = b"\x00\x01 binary archive bytes \xff";
+        let sealed = enc.seal(data).unwrap();
+        assert_eq!(
+            sealed.len(),
+            ARGON2_SALT_LEN + XCHACHA_NONCE_LEN + data.len() + 16
+        );
+        assert_eq!(enc.open(&sealed).unwrap(), data);
+
+        assert!(PassphraseEncryption::new("wrong").open(&sealed).is_err());
+        assert!(enc.open(&sealed[..20]).is_err());
+    }
+
     #[test]
     fn test_secret_field_names() {
         // Positive cases — all of these should be detected as secret fields
@@ -4,18 +4,24 @@
 //! and command filtering to prevent malicious tool execution.
 
 pub mod agent_mode;
+pub mod confirm;
 pub mod encryption;
 pub mod mount;
 pub mod pairing;
 pub mod path;
+pub mod rbac;
 pub mod shell;
+pub mod users;
 
 pub use agent_mode::{AgentMode, AgentModeConfig, CategoryPermission, ModePolicy};
+pub use confirm::{CodeCheck, ConfirmationCode};
 pub use encryption::{is_secret_field, resolve_master_key, SecretEncryption};
 pub use mount::{validate_extra_mounts, validate_mount_not_blocked, DEFAULT_BLOCKED_PATTERNS};
-pub use pairing::{DeviceInfo, PairedDevice, PairingManager};
+pub use pairing::{DeviceInfo, DeviceScope, PairedDevice, PairingManager};
 pub use path::{
     check_hardlink_write, ensure_directory_chain_secure, revalidate_path,
     validate_path_in_workspace, SafePath,
 };
+pub use rbac::{RbacConfig, RoleDefinition, RolePolicy};
 pub use shell::{ShellAllowlistMode, ShellSecurityConfig};
+pub use users::{User, UserRole, UsersConfig};
@@ -167,7 +167,7 @@ pub fn validate_extra_mounts(mounts: &[String], allowlist_path: &str) -> Result<
         return Ok(Vec::new());
     }
 
-    let allowlist_path = expand_path(allowlist_path);
+    let allowlist_path = crate::config::expand_home(allowlist_path);
     let allowlist = load_allowlist(&allowlist_path)?;
     if allowlist.allowed_roots.is_empty() {
         return Err(ZeptoError::SecurityViolation(
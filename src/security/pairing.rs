@@ -5,7 +5,12 @@
 //! Failed validation attempts are tracked per identifier; after `max_attempts`, the identifier
 //! is locked out for `lockout_secs`.
 //!
-//! Persists paired devices to `~/.zeptoclaw/security/paired_devices.json`.
+//! Each code carries a [`DeviceScope`] chosen by the operator; the device that redeems it is
+//! recorded with that scope. `ReadOnly` devices are served in observer mode.
+//!
+//! Persists paired devices and the pending code (hashed) to
+//! `~/.zeptoclaw/security/paired_devices.json`, so a code issued by `zeptoclaw pair new` can be
+//! redeemed against a running gateway. The file is re-read whenever another process changes it.
 //!
 //! # Security notes
 //!
@@ -23,6 +28,66 @@ use subtle::ConstantTimeEq;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+/// Inbound message metadata key carrying the paired device's scope.
+///
+/// Set by the pairing check after a token validates; the agent loop caps a
+/// `read_only` message at observer mode.
+pub const DEVICE_SCOPE_METADATA_KEY: &str = "device_scope";
+
+/// Lifetime of a pairing code in seconds.
+const CODE_TTL_SECS: u64 = 300;
+
+/// What a paired device is allowed to do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceScope {
+    /// Read-only: the agent runs in observer mode for this device.
+    ReadOnly,
+    /// Full access under the configured agent mode.
+    #[default]
+    Full,
+}
+
+impl DeviceScope {
+    /// Stable string form, also used as the metadata value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::Full => "full",
+        }
+    }
+
+    /// The narrower of two scopes.
+    pub fn min(self, other: Self) -> Self {
+        if self == Self::ReadOnly || other == Self::ReadOnly {
+            Self::ReadOnly
+        } else {
+            Self::Full
+        }
+    }
+}
+
+impl std::fmt::Display for DeviceScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for DeviceScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "read_only" | "readonly" | "read" => Ok(Self::ReadOnly),
+            "full" => Ok(Self::Full),
+            other => Err(format!(
+                "Unknown device scope '{}'. Use: read-only or full",
+                other
+            )),
+        }
+    }
+}
+
 /// A paired device record (persisted to JSON).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PairedDevice {
@@ -34,6 +99,9 @@ pub struct PairedDevice {
     pub paired_at: u64,
     /// Unix timestamp of the most recent successful token validation.
     pub last_seen: u64,
+    /// Capability scope granted at pairing time (records without one are `full`).
+    #[serde(default)]
+    pub scope: DeviceScope,
 }
 
 /// Device info returned by `list_devices()` — never includes the raw token or hash.
@@ -45,17 +113,24 @@ pub struct DeviceInfo {
     pub paired_at: u64,
     /// Unix timestamp of the most recent successful token validation.
     pub last_seen: u64,
+    /// Capability scope granted at pairing time.
+    pub scope: DeviceScope,
 }
 
 #[derive(Serialize, Deserialize, Default)]
 struct PairingStore {
     devices: Vec<PairedDevice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending: Option<PendingCode>,
 }
 
-/// An in-memory pending pairing code (not persisted).
+/// The outstanding pairing code. Only its SHA-256 hash is persisted.
+#[derive(Serialize, Deserialize)]
 struct PendingCode {
-    code: String,
-    expires_at: Instant,
+    code_hash: String,
+    /// Unix timestamp after which the code is rejected.
+    expires_at: u64,
+    scope: DeviceScope,
 }
 
 /// Per-identifier brute-force lockout entry (not persisted).
@@ -68,7 +143,8 @@ struct LockoutEntry {
 pub struct PairingManager {
     store: PairingStore,
     path: PathBuf,
-    pending_code: Option<PendingCode>,
+    /// Modification time of the store file as last loaded or written by us.
+    loaded_mtime: Option<SystemTime>,
     lockouts: HashMap<String, LockoutEntry>,
     max_attempts: u32,
     lockout_duration: Duration,
@@ -77,16 +153,15 @@ pub struct PairingManager {
 impl PairingManager {
     /// Create a new `PairingManager`, loading any existing paired devices from disk.
     pub fn new(max_attempts: u32, lockout_secs: u64) -> Self {
-        let path = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zeptoclaw")
+        let path = crate::config::Config::data_dir()
             .join("security")
             .join("paired_devices.json");
         let store = Self::load_from_disk(&path);
+        let loaded_mtime = Self::file_mtime(&path);
         Self {
             store,
             path,
-            pending_code: None,
+            loaded_mtime,
             lockouts: HashMap::new(),
             max_attempts,
             lockout_duration: Duration::from_secs(lockout_secs),
@@ -95,29 +170,38 @@ impl PairingManager {
 
     /// Create a `PairingManager` with a custom storage path (useful for testing).
     #[cfg(test)]
-    fn with_path(path: PathBuf, max_attempts: u32, lockout_secs: u64) -> Self {
+    pub(crate) fn with_path(path: PathBuf, max_attempts: u32, lockout_secs: u64) -> Self {
         let store = Self::load_from_disk(&path);
+        let loaded_mtime = Self::file_mtime(&path);
         Self {
             store,
             path,
-            pending_code: None,
+            loaded_mtime,
             lockouts: HashMap::new(),
             max_attempts,
             lockout_duration: Duration::from_secs(lockout_secs),
         }
     }
 
-    /// Generate a new 6-digit pairing code valid for 5 minutes.
+    /// Generate a new 6-digit pairing code valid for 5 minutes, granting full access.
     ///
     /// Only one code can be active at a time. Generating a new code invalidates any previous one.
     /// Returns the 6-digit code as a zero-padded string.
     pub fn generate_pairing_code(&mut self) -> String {
+        self.generate_scoped_pairing_code(DeviceScope::Full)
+    }
+
+    /// Generate a new 6-digit pairing code whose device will be granted `scope`.
+    pub fn generate_scoped_pairing_code(&mut self, scope: DeviceScope) -> String {
+        self.refresh_from_disk();
         let code = Self::random_6_digit_code();
-        self.pending_code = Some(PendingCode {
-            code: code.clone(),
-            expires_at: Instant::now() + Duration::from_secs(300),
+        self.store.pending = Some(PendingCode {
+            code_hash: Self::hash_token(&code),
+            expires_at: Self::now_secs() + CODE_TTL_SECS,
+            scope,
         });
-        info!("New pairing code generated (valid for 5 minutes)");
+        self.save_to_disk();
+        info!(%scope, "New pairing code generated (valid for 5 minutes)");
         code
     }
 
@@ -132,33 +216,43 @@ impl PairingManager {
         device_name: &str,
         identifier: &str,
     ) -> Option<String> {
+        self.complete_scoped_pairing(code, device_name, identifier, DeviceScope::Full)
+            .map(|(token, _)| token)
+    }
+
+    /// Like [`complete_pairing`](Self::complete_pairing), with the device asking for at most
+    /// `requested` scope. The granted scope is the narrower of the request and the code's scope.
+    pub fn complete_scoped_pairing(
+        &mut self,
+        code: &str,
+        device_name: &str,
+        identifier: &str,
+        requested: DeviceScope,
+    ) -> Option<(String, DeviceScope)> {
         // Check lockout
         if self.is_locked_out(identifier) {
             warn!(identifier, "Pairing attempt rejected: locked out");
             return None;
         }
 
-        let valid = self
-            .pending_code
-            .as_ref()
-            .map(|pc| {
-                let code_bytes = pc.code.as_bytes();
-                let input_bytes = code.as_bytes();
-                // Constant-time comparison to prevent timing attacks on pairing codes
-                let codes_match = code_bytes.len() == input_bytes.len()
-                    && bool::from(code_bytes.ct_eq(input_bytes));
-                codes_match && Instant::now() < pc.expires_at
-            })
-            .unwrap_or(false);
+        self.refresh_from_disk();
+        let input_hash = Self::hash_token(code);
+        let now = Self::now_secs();
+        let granted = self.store.pending.as_ref().and_then(|pc| {
+            // Constant-time comparison to prevent timing attacks on pairing codes
+            let codes_match = bool::from(pc.code_hash.as_bytes().ct_eq(input_hash.as_bytes()));
+            (codes_match && now < pc.expires_at).then_some(pc.scope)
+        });
 
-        if !valid {
+        let Some(code_scope) = granted else {
             self.record_failed_attempt(identifier);
             warn!(identifier, "Invalid or expired pairing code");
             return None;
-        }
+        };
+        let scope = code_scope.min(requested);
 
         // Code is valid — consume it
-        self.pending_code = None;
+        self.store.pending = None;
         self.clear_lockout(identifier);
 
         // Generate bearer token
@@ -174,12 +268,13 @@ impl PairingManager {
             token_hash,
             paired_at: now,
             last_seen: now,
+            scope,
         });
 
         self.save_to_disk();
-        info!(device = device_name, "Device paired successfully");
+        info!(device = device_name, %scope, "Device paired successfully");
 
-        Some(raw_token)
+        Some((raw_token, scope))
     }
 
     /// Validate a raw bearer token against stored SHA-256 hashes.
@@ -192,12 +287,27 @@ impl PairingManager {
     ///
     /// On failure, records a failed attempt for the identifier.
     pub fn validate_token(&mut self, raw_token: &str, identifier: &str) -> Option<String> {
+        self.validate_token_scope(raw_token, identifier)
+            .map(|(name, _)| name)
+    }
+
+    /// Validate a raw bearer token, returning the device name and its scope.
+    ///
+    /// Same semantics as [`validate_token`](Self::validate_token).
+    pub fn validate_token_scope(
+        &mut self,
+        raw_token: &str,
+        identifier: &str,
+    ) -> Option<(String, DeviceScope)> {
         // Check lockout
         if self.is_locked_out(identifier) {
             warn!(identifier, "Token validation rejected: locked out");
             return None;
         }
 
+        // Pick up devices paired or revoked by another process.
+        self.refresh_from_disk();
+
         let hash = Self::hash_token(raw_token);
         let hash_bytes = hash.as_bytes();
         let now = Self::now_secs();
@@ -219,9 +329,10 @@ impl PairingManager {
         if let Some(idx) = matched_idx {
             self.store.devices[idx].last_seen = now;
             let name = self.store.devices[idx].name.clone();
+            let scope = self.store.devices[idx].scope;
             // Deferred disk write — flushed on next complete_pairing/revoke/clear
             self.clear_lockout(identifier);
-            Some(name)
+            Some((name, scope))
         } else {
             self.record_failed_attempt(identifier);
             None
@@ -232,6 +343,7 @@ impl PairingManager {
     ///
     /// Returns `true` if a device was found and removed.
     pub fn revoke(&mut self, device_name: &str) -> bool {
+        self.refresh_from_disk();
         let initial_len = self.store.devices.len();
         self.store.devices.retain(|d| d.name != device_name);
         let removed = self.store.devices.len() < initial_len;
@@ -251,6 +363,7 @@ impl PairingManager {
                 name: d.name.clone(),
                 paired_at: d.paired_at,
                 last_seen: d.last_seen,
+                scope: d.scope,
             })
             .collect()
     }
@@ -387,7 +500,7 @@ impl PairingManager {
         }
     }
 
-    fn save_to_disk(&self) {
+    fn save_to_disk(&mut self) {
         if let Some(parent) = self.path.parent() {
             let _ = std::fs::create_dir_all(parent);
         }
@@ -396,6 +509,39 @@ impl PairingManager {
                 warn!("Failed to save paired devices: {}", e);
             }
         }
+        self.loaded_mtime = Self::file_mtime(&self.path);
+    }
+
+    fn file_mtime(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    /// Reload the store if another process wrote it since we last did.
+    ///
+    /// In-memory `last_seen` updates (not yet flushed) are kept for devices
+    /// that still exist on disk.
+    fn refresh_from_disk(&mut self) {
+        let mtime = Self::file_mtime(&self.path);
+        if mtime.is_none() || mtime == self.loaded_mtime {
+            return;
+        }
+        let mut disk = Self::load_from_disk(&self.path);
+        for device in &mut disk.devices {
+            if let Some(mem) = self
+                .store
+                .devices
+                .iter()
+                .find(|d| d.token_hash == device.token_hash)
+            {
+                device.last_seen = device.last_seen.max(mem.last_seen);
+            }
+        }
+        debug!(
+            devices = disk.devices.len(),
+            "Reloaded paired devices from disk"
+        );
+        self.store = disk;
+        self.loaded_mtime = mtime;
     }
 }
 
@@ -412,7 +558,7 @@ mod tests {
         PairingManager {
             store: PairingStore::default(),
             path: PathBuf::from(format!("/tmp/zeptoclaw-test-pairing-{tid:?}-{id}.json")),
-            pending_code: None,
+            loaded_mtime: None,
             lockouts: HashMap::new(),
             max_attempts: 5,
             lockout_duration: Duration::from_secs(300),
@@ -472,8 +618,8 @@ mod tests {
         let mut mgr = test_manager();
         let code = mgr.generate_pairing_code();
         // Manually expire the pending code
-        if let Some(ref mut pc) = mgr.pending_code {
-            pc.expires_at = Instant::now() - Duration::from_secs(1);
+        if let Some(ref mut pc) = mgr.store.pending {
+            pc.expires_at = PairingManager::now_secs() - 1;
         }
         let token = mgr.complete_pairing(&code, "device", "127.0.0.1");
         assert!(token.is_none());
@@ -771,6 +917,66 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn test_scoped_pairing_grants_narrower_scope() {
+        let mut mgr = test_manager();
+        let code = mgr.generate_scoped_pairing_code(DeviceScope::ReadOnly);
+        let (token, scope) = mgr
+            .complete_scoped_pairing(&code, "kiosk", "127.0.0.1", DeviceScope::Full)
+            .unwrap();
+        assert_eq!(scope, DeviceScope::ReadOnly);
+        assert_eq!(
+            mgr.validate_token_scope(&token, "127.0.0.1"),
+            Some(("kiosk".to_string(), DeviceScope::ReadOnly))
+        );
+        assert_eq!(mgr.list_devices()[0].scope, DeviceScope::ReadOnly);
+
+        let code = mgr.generate_pairing_code();
+        let (_, scope) = mgr
+            .complete_scoped_pairing(&code, "phone", "127.0.0.1", DeviceScope::ReadOnly)
+            .unwrap();
+        assert_eq!(scope, DeviceScope::ReadOnly);
+    }
+
+    #[test]
+    fn test_device_scope_parse_and_legacy_default() {
+        assert_eq!(
+            "read-only".parse::<DeviceScope>(),
+            Ok(DeviceScope::ReadOnly)
+        );
+        assert_eq!("FULL".parse::<DeviceScope>(), Ok(DeviceScope::Full));
+        assert!("admin".parse::<DeviceScope>().is_err());
+
+        let json = r#"{"name":"old","token_hash":"x","paired_at":1,"last_seen":1}"#;
+        let device: PairedDevice = serde_json::from_str(json).unwrap();
+        assert_eq!(device.scope, DeviceScope::Full);
+    }
+
+    #[test]
+    fn test_code_issued_by_another_process_is_redeemable() {
+        let dir = std::env::temp_dir().join(format!("zeptoclaw-pairing-xproc-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("paired_devices.json");
+
+        // Gateway loads first, then the CLI issues a code.
+        let mut gateway = PairingManager::with_path(path.clone(), 5, 300);
+        std::thread::sleep(Duration::from_millis(20));
+        let mut cli = PairingManager::with_path(path.clone(), 5, 300);
+        let code = cli.generate_pairing_code();
+
+        let token = gateway
+            .complete_pairing(&code, "phone", "127.0.0.1")
+            .unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains(&code));
+
+        // CLI revokes; the gateway notices on the next validation.
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(cli.revoke("phone"));
+        assert!(gateway.validate_token(&token, "127.0.0.1").is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_load_from_disk_missing_file() {
         let path = PathBuf::from("/tmp/zeptoclaw-pairing-nonexistent-12345.json");
@@ -83,6 +83,22 @@ pub fn validate_path_in_workspace(path: &str, workspace: &str) -> Result<SafePat
         )));
     }
 
+    // Windows: `C:foo` is relative to the drive's current directory and
+    // `\\?\` / `\\.\` address raw devices; neither can be anchored to the workspace.
+    if cfg!(windows) && is_ambiguous_windows_path(path) {
+        log_audit_event(
+            AuditCategory::PathSecurity,
+            AuditSeverity::Critical,
+            "path_escape",
+            &format!("Drive-relative or device path rejected: {}", path),
+            true,
+        );
+        return Err(ZeptoError::SecurityViolation(format!(
+            "Drive-relative or device path rejected: {}",
+            path
+        )));
+    }
+
     let workspace_path = Path::new(workspace);
     let target_path = Path::new(path);
 
@@ -98,16 +114,15 @@ pub fn validate_path_in_workspace(path: &str, workspace: &str) -> Result<SafePat
 
     // Get the canonical workspace path for comparison
     // If workspace doesn't exist, use the normalized workspace path
-    let canonical_workspace = workspace_path
-        .canonicalize()
-        .unwrap_or_else(|_| normalize_path(workspace_path));
+    let canonical_workspace =
+        canonicalize(workspace_path).unwrap_or_else(|_| normalize_path(workspace_path));
 
     // SECURITY: Check for symlink escapes in existing ancestor directories
     // This prevents attacks where a subdir is a symlink to outside workspace
     check_symlink_escape(&normalized_path, &canonical_workspace)?;
 
     // Check if the normalized path starts with the workspace
-    if !normalized_path.starts_with(&canonical_workspace) {
+    if !is_within(&normalized_path, &canonical_workspace) {
         log_audit_event(
             AuditCategory::PathSecurity,
             AuditSeverity::Critical,
@@ -143,9 +158,9 @@ fn check_symlink_escape(path: &Path, canonical_workspace: &Path) -> Result<()> {
     // (e.g., /var -> /private/var on macOS)
 
     // Get the relative path from workspace to target
-    let relative = match path.strip_prefix(canonical_workspace) {
-        Ok(rel) => rel,
-        Err(_) => {
+    let relative = match strip_workspace_prefix(path, canonical_workspace) {
+        Some(rel) => rel,
+        None => {
             // Path doesn't start with workspace - try with non-canonical
             // This handles cases where normalize_path returns a non-canonical path
             return Ok(());
@@ -165,10 +180,10 @@ fn check_symlink_escape(path: &Path, canonical_workspace: &Path) -> Result<()> {
             Ok(meta) => {
                 if meta.file_type().is_symlink() {
                     // It's a symlink — try to canonicalize to check where it points
-                    match current.canonicalize() {
+                    match canonicalize(&current) {
                         Ok(canonical) => {
                             // Symlink resolves — check if target is within workspace
-                            if !canonical.starts_with(canonical_workspace) {
+                            if !is_within(&canonical, canonical_workspace) {
                                 log_audit_event(
                                     AuditCategory::PathSecurity,
                                     AuditSeverity::Critical,
@@ -209,8 +224,8 @@ fn check_symlink_escape(path: &Path, canonical_workspace: &Path) -> Result<()> {
                     }
                 } else if meta.is_dir() {
                     // Regular directory — canonicalize to check for nested symlinks
-                    if let Ok(canonical) = current.canonicalize() {
-                        if !canonical.starts_with(canonical_workspace) {
+                    if let Ok(canonical) = canonicalize(&current) {
+                        if !is_within(&canonical, canonical_workspace) {
                             log_audit_event(
                                 AuditCategory::PathSecurity,
                                 AuditSeverity::Critical,
@@ -253,16 +268,15 @@ fn check_symlink_escape(path: &Path, canonical_workspace: &Path) -> Result<()> {
 /// 2. Workspace boundary check via canonicalization
 pub fn revalidate_path(path: &Path, workspace: &str) -> Result<()> {
     let workspace_path = Path::new(workspace);
-    let canonical_workspace = workspace_path
-        .canonicalize()
-        .unwrap_or_else(|_| normalize_path(workspace_path));
+    let canonical_workspace =
+        canonicalize(workspace_path).unwrap_or_else(|_| normalize_path(workspace_path));
 
     // Re-check symlink escapes (components may have changed since initial validation)
     check_symlink_escape(path, &canonical_workspace)?;
 
     // If the path now exists, verify its canonical form is still within workspace
-    if let Ok(canonical) = path.canonicalize() {
-        if !canonical.starts_with(&canonical_workspace) {
+    if let Ok(canonical) = canonicalize(path) {
+        if !is_within(&canonical, &canonical_workspace) {
             log_audit_event(
                 AuditCategory::PathSecurity,
                 AuditSeverity::Critical,
@@ -291,12 +305,11 @@ pub fn revalidate_path(path: &Path, workspace: &str) -> Result<()> {
 /// workspace boundary and rejecting symlinked or non-directory ancestors.
 pub fn ensure_directory_chain_secure(path: &Path, workspace: &str) -> Result<()> {
     let workspace_path = Path::new(workspace);
-    let canonical_workspace = workspace_path
-        .canonicalize()
-        .unwrap_or_else(|_| normalize_path(workspace_path));
+    let canonical_workspace =
+        canonicalize(workspace_path).unwrap_or_else(|_| normalize_path(workspace_path));
     let normalized_path = normalize_path(path);
 
-    if !normalized_path.starts_with(&canonical_workspace) {
+    if !is_within(&normalized_path, &canonical_workspace) {
         return Err(ZeptoError::SecurityViolation(format!(
             "Directory path escapes workspace: '{}' is not within '{}'",
             normalized_path.display(),
@@ -304,9 +317,8 @@ pub fn ensure_directory_chain_secure(path: &Path, workspace: &str) -> Result<()>
         )));
     }
 
-    let relative = normalized_path
-        .strip_prefix(&canonical_workspace)
-        .map_err(|_| {
+    let relative =
+        strip_workspace_prefix(&normalized_path, &canonical_workspace).ok_or_else(|| {
             ZeptoError::SecurityViolation(format!(
                 "Directory path escapes workspace: '{}' is not within '{}'",
                 normalized_path.display(),
@@ -371,8 +383,8 @@ pub fn ensure_directory_chain_secure(path: &Path, workspace: &str) -> Result<()>
             }
         }
 
-        if let Ok(canonical) = current.canonicalize() {
-            if !canonical.starts_with(&canonical_workspace) {
+        if let Ok(canonical) = canonicalize(&current) {
+            if !is_within(&canonical, &canonical_workspace) {
                 return Err(ZeptoError::SecurityViolation(format!(
                     "Directory '{}' resolves outside workspace to '{}'",
                     current.display(),
@@ -393,6 +405,14 @@ pub fn ensure_directory_chain_secure(path: &Path, workspace: &str) -> Result<()>
 /// allowing writes to escape workspace boundaries.
 ///
 /// Returns Ok(()) if the file doesn't exist (new file creation) or has exactly 1 link.
+///
+/// Link counts are not exposed on stable Rust outside Unix, so this is a no-op there.
+#[cfg(not(unix))]
+pub fn check_hardlink_write(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
 pub fn check_hardlink_write(path: &Path) -> Result<()> {
     use std::os::unix::fs::MetadataExt;
 
@@ -458,7 +478,68 @@ fn normalize_path(path: &Path) -> PathBuf {
     }
 
     // Try to canonicalize if the path exists
-    normalized.canonicalize().unwrap_or(normalized)
+    canonicalize(&normalized).unwrap_or(normalized)
+}
+
+/// `Path::canonicalize` without the Windows verbatim prefix.
+fn canonicalize(path: &Path) -> std::io::Result<PathBuf> {
+    path.canonicalize().map(strip_verbatim_prefix)
+}
+
+/// Removes the `\\?\` verbatim prefix that `canonicalize` adds on Windows.
+///
+/// Canonical paths come back as `\\?\C:\ws\file` (or `\\?\UNC\server\share`)
+/// while paths that do not exist yet stay as `C:\ws\new`, so the two would never
+/// compare equal without this.
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    let Some(raw) = path.to_str() else {
+        return path;
+    };
+    if let Some(rest) = raw.strip_prefix(r"\\?\UNC\") {
+        PathBuf::from(format!(r"\\{}", rest))
+    } else if let Some(rest) = raw.strip_prefix(r"\\?\") {
+        PathBuf::from(rest)
+    } else {
+        path
+    }
+}
+
+/// Whether `path` lies within `base`.
+///
+/// NTFS is case-insensitive, so on Windows `C:\Work\a` is inside `c:\work`.
+fn is_within(path: &Path, base: &Path) -> bool {
+    strip_workspace_prefix(path, base).is_some()
+}
+
+/// Strips `base` from the front of `path`, component-wise and case-insensitively
+/// on Windows.
+fn strip_workspace_prefix<'a>(path: &'a Path, base: &Path) -> Option<&'a Path> {
+    if !cfg!(windows) {
+        return path.strip_prefix(base).ok();
+    }
+    let mut rest = path.components();
+    for expected in base.components() {
+        let actual = rest.next()?;
+        if !actual
+            .as_os_str()
+            .to_string_lossy()
+            .eq_ignore_ascii_case(&expected.as_os_str().to_string_lossy())
+        {
+            return None;
+        }
+    }
+    Some(rest.as_path())
+}
+
+/// Detects Windows paths that cannot be anchored to a workspace: drive-relative
+/// paths (`C:foo`) and verbatim/device namespaces (`\\?\`, `\\.\`).
+fn is_ambiguous_windows_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    let drive_relative = bytes.len() >= 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && !matches!(bytes.get(2), Some(b'\\') | Some(b'/'));
+    drive_relative || path.starts_with(r"\\?\") || path.starts_with(r"\\.\")
 }
 
 /// Checks if a path string contains common traversal patterns.
@@ -485,6 +566,7 @@ fn contains_traversal_pattern(path: &str) -> bool {
 mod tests {
     use super::*;
     use std::fs;
+    #[cfg(unix)]
     use std::os::unix::fs::symlink;
     use tempfile::tempdir;
 
@@ -662,6 +744,7 @@ mod tests {
     // ==================== SYMLINK ESCAPE TESTS (NEW) ====================
 
     #[test]
+    #[cfg(unix)]
     fn test_symlink_escape_to_outside() {
         let temp = tempdir().unwrap();
         let outside = tempdir().unwrap();
@@ -687,6 +770,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_symlink_within_workspace_allowed() {
         let temp = tempdir().unwrap();
         let workspace = temp.path().to_str().unwrap();
@@ -705,6 +789,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_nested_symlink_escape() {
         let temp = tempdir().unwrap();
         let outside = tempdir().unwrap();
@@ -720,6 +805,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_symlink_to_parent_blocked() {
         let temp = tempdir().unwrap();
         let workspace = temp.path().to_str().unwrap();
@@ -735,6 +821,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_new_file_in_symlinked_dir_blocked() {
         let temp = tempdir().unwrap();
         let outside = tempdir().unwrap();
@@ -756,6 +843,7 @@ mod tests {
     // ==================== DANGLING SYMLINK TESTS ====================
 
     #[test]
+    #[cfg(unix)]
     fn test_dangling_symlink_rejected() {
         let temp = tempdir().unwrap();
         // Use canonical workspace to avoid macOS /var -> /private/var mismatch
@@ -785,6 +873,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_dangling_symlink_to_outside_workspace() {
         let temp = tempdir().unwrap();
         let canonical = temp.path().canonicalize().unwrap();
@@ -802,6 +891,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_nested_dangling_symlink() {
         let temp = tempdir().unwrap();
         let canonical = temp.path().canonicalize().unwrap();
@@ -817,6 +907,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_dangling_symlink_direct_access() {
         let temp = tempdir().unwrap();
         let canonical = temp.path().canonicalize().unwrap();
@@ -864,6 +955,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_revalidate_path_symlink_escape() {
         let temp = tempdir().unwrap();
         let outside = tempdir().unwrap();
@@ -895,6 +987,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_ensure_directory_chain_secure_rejects_symlink_parent() {
         let temp = tempdir().unwrap();
         let outside = tempdir().unwrap();
@@ -922,6 +1015,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_hardlink_write_multiple_links() {
         let temp = tempdir().unwrap();
         let original = temp.path().join("original.txt");
@@ -954,4 +1048,61 @@ mod tests {
         let result = check_hardlink_write(&nonexistent);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_strip_verbatim_prefix() {
+        assert_eq!(
+            strip_verbatim_prefix(PathBuf::from(r"\\?\C:\ws\file.txt")),
+            PathBuf::from(r"C:\ws\file.txt")
+        );
+        assert_eq!(
+            strip_verbatim_prefix(PathBuf::from(r"\\?\UNC\server\share\ws")),
+            PathBuf::from(r"\\server\share\ws")
+        );
+        assert_eq!(
+            strip_verbatim_prefix(PathBuf::from("/home/u/ws")),
+            PathBuf::from("/home/u/ws")
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_windows_paths() {
+        assert!(is_ambiguous_windows_path("C:secrets.txt"));
+        assert!(is_ambiguous_windows_path("d:"));
+        assert!(is_ambiguous_windows_path(r"\\?\C:\Windows"));
+        assert!(is_ambiguous_windows_path(r"\\.\PhysicalDrive0"));
+        assert!(!is_ambiguous_windows_path(r"C:\ws\file.txt"));
+        assert!(!is_ambiguous_windows_path("C:/ws/file.txt"));
+        assert!(!is_ambiguous_windows_path("src/main.rs"));
+    }
+
+    #[test]
+    fn test_is_within_component_boundary() {
+        let temp = tempdir().unwrap();
+        let ws = temp.path().join("ws");
+        assert!(is_within(&ws.join("a.txt"), &ws));
+        // `ws-evil` shares a string prefix with `ws` but is a sibling
+        assert!(!is_within(&temp.path().join("ws-evil"), &ws));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_is_within_case_insensitive_drive() {
+        assert!(is_within(
+            Path::new(r"c:\Users\Me\WS\file.txt"),
+            Path::new(r"C:\users\me\ws")
+        ));
+        assert!(!is_within(
+            Path::new(r"D:\users\me\ws\file.txt"),
+            Path::new(r"C:\users\me\ws")
+        ));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_drive_relative_path_rejected() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().to_str().unwrap();
+        assert!(validate_path_in_workspace("C:notes.txt", workspace).is_err());
+    }
 }
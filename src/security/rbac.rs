@@ -0,0 +1,219 @@
+//! Role-based access control for tool categories.
+//!
+//! Roles are defined in `rbac.roles` as the tool categories they may use
+//! freely and the ones that need approval on every call; every other
+//! category is blocked. A message gets the role assigned to its sender in
+//! `rbac.users` (by user name from `users`, `channel:sender_id` or bare
+//! `sender_id`), else to its channel in `rbac.channels`, else
+//! `rbac.default_role`.
+//!
+//! The role applies on top of the agent mode: a tool runs only if both allow
+//! it. Blocked tools are also left out of the definitions sent to the model,
+//! so it does not try to call them.
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::security::CategoryPermission;
+use crate::tools::ToolCategory;
+
+/// What one role may do.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RoleDefinition {
+    /// Categories the role uses without approval.
+    pub allowed_categories: Vec<ToolCategory>,
+    /// Categories that need approval on every call.
+    pub approval_categories: Vec<ToolCategory>,
+}
+
+/// Configuration for role-based access control.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RbacConfig {
+    /// Enforce roles.
+    pub enabled: bool,
+    /// Role name → definition.
+    pub roles: HashMap<String, RoleDefinition>,
+    /// User name (`users`), `channel:sender_id` or bare `sender_id` → role.
+    pub users: HashMap<String, String>,
+    /// Channel name → role.
+    pub channels: HashMap<String, String>,
+    /// Role of everyone else. `None` leaves them unrestricted.
+    pub default_role: Option<String>,
+}
+
+impl RbacConfig {
+    /// Role policy for a message sender, or `None` when RBAC is off, no role
+    /// applies, or the sender is the CLI or a subagent. ZeptoClaw's own
+    /// messages (heartbeat, cron) are kept out by the agent loop.
+    pub fn resolve(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        user: Option<&str>,
+    ) -> Option<RolePolicy> {
+        if !self.enabled || matches!(channel, "cli" | "subagent") {
+            return None;
+        }
+        let qualified = format!("{}:{}", channel, sender_id);
+        let name = user
+            .and_then(|user| self.users.get(user))
+            .or_else(|| self.users.get(&qualified))
+            .or_else(|| self.users.get(sender_id))
+            .or_else(|| self.channels.get(channel))
+            .or(self.default_role.as_ref())?;
+        // An unknown role name grants nothing.
+        let role = self.roles.get(name).cloned().unwrap_or_default();
+        Some(RolePolicy {
+            name: name.clone(),
+            role,
+        })
+    }
+
+    /// Configuration errors: assignments to roles that are not defined.
+    pub fn validate(&self) -> Vec<String> {
+        let mut assigned: Vec<(&str, &String)> = self
+            .users
+            .iter()
+            .chain(&self.channels)
+            .map(|(who, role)| (who.as_str(), role))
+            .chain(
+                self.default_role
+                    .as_ref()
+                    .map(|role| ("default_role", role)),
+            )
+            .collect();
+        assigned.sort();
+        assigned
+            .into_iter()
+            .filter(|(_, role)| !self.roles.contains_key(*role))
+            .map(|(who, role)| format!("'{}' is assigned unknown role '{}'", who, role))
+            .collect()
+    }
+}
+
+/// The role one message runs under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolePolicy {
+    name: String,
+    role: RoleDefinition,
+}
+
+impl RolePolicy {
+    pub fn new(name: impl Into<String>, role: RoleDefinition) -> Self {
+        Self {
+            name: name.into(),
+            role,
+        }
+    }
+
+    /// Role name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Check what permission a tool category has under this role.
+    pub fn check(&self, category: ToolCategory) -> CategoryPermission {
+        if self.role.allowed_categories.contains(&category) {
+            CategoryPermission::Allowed
+        } else if self.role.approval_categories.contains(&category) {
+            CategoryPermission::RequiresApproval
+        } else {
+            CategoryPermission::Blocked
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RbacConfig {
+        let mut config = RbacConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        config.roles.insert(
+            "reader".into(),
+            RoleDefinition {
+                allowed_categories: vec![ToolCategory::FilesystemRead, ToolCategory::NetworkRead],
+                approval_categories: vec![ToolCategory::Memory],
+            },
+        );
+        config.roles.insert(
+            "operator".into(),
+            RoleDefinition {
+                allowed_categories: ToolCategory::all().to_vec(),
+                approval_categories: vec![],
+            },
+        );
+        config.users.insert("anna".into(), "operator".into());
+        config.users.insert("telegram:7".into(), "operator".into());
+        config.channels.insert("discord".into(), "reader".into());
+        config
+    }
+
+    #[test]
+    fn test_disabled_and_system_senders_have_no_role() {
+        let mut config = config();
+        config.default_role = Some("reader".into());
+        assert!(config.resolve("cli", "user", None).is_none());
+        assert!(config.resolve("subagent", "x", None).is_none());
+        // A sender ID is no proof of being the system.
+        assert_eq!(
+            config.resolve("telegram", "cron", None).unwrap().name(),
+            "reader"
+        );
+        config.enabled = false;
+        assert!(config.resolve("discord", "1", None).is_none());
+    }
+
+    #[test]
+    fn test_assignment_precedence() {
+        let config = config();
+        // User name beats channel.
+        let role = config.resolve("discord", "1", Some("anna")).unwrap();
+        assert_eq!(role.name(), "operator");
+        assert_eq!(
+            config.resolve("telegram", "7", None).unwrap().name(),
+            "operator"
+        );
+        assert_eq!(
+            config.resolve("discord", "1", None).unwrap().name(),
+            "reader"
+        );
+        // No assignment and no default role: unrestricted.
+        assert!(config.resolve("telegram", "1", None).is_none());
+    }
+
+    #[test]
+    fn test_role_checks_categories() {
+        let role = config().resolve("discord", "1", None).unwrap();
+        assert_eq!(
+            role.check(ToolCategory::FilesystemRead),
+            CategoryPermission::Allowed
+        );
+        assert_eq!(
+            role.check(ToolCategory::Memory),
+            CategoryPermission::RequiresApproval
+        );
+        assert_eq!(role.check(ToolCategory::Shell), CategoryPermission::Blocked);
+    }
+
+    #[test]
+    fn test_unknown_role_blocks_everything() {
+        let mut config = config();
+        config.default_role = Some("missing".into());
+        let role = config.resolve("telegram", "1", None).unwrap();
+        assert!(ToolCategory::all()
+            .into_iter()
+            .all(|c| role.check(c) == CategoryPermission::Blocked));
+        assert_eq!(
+            config.validate(),
+            vec!["'default_role' is assigned unknown role 'missing'".to_string()]
+        );
+    }
+}
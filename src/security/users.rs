@@ -0,0 +1,359 @@
+//! Multi-user registry — who is talking to the bot, and what they may do.
+//!
+//! Without a registry everyone who can reach a channel is effectively the
+//! owner. With `users.enabled`, each sender is matched against the named
+//! users in `users.users` (by `channel:sender_id` or bare `sender_id`) and
+//! gets that user's role; anyone else gets `users.default_role`.
+//!
+//! A role caps the agent mode for the turn:
+//! - **owner**: the configured `agent_mode`
+//! - **family**: at most `assistant`
+//! - **guest**: `observer`
+//!
+//! On top of the role, a user can have tool allow/deny lists and a daily
+//! token quota, and everyone but the owner gets a private memory namespace
+//! named after them. The CLI, heartbeat, cron and subagents are not people
+//! and are never restricted.
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::providers::quota::{QuotaCheckResult, QuotaConfig, QuotaPeriod, QuotaStore};
+use crate::security::AgentMode;
+
+/// What a user is trusted with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UserRole {
+    /// Full access, within the configured agent mode.
+    Owner,
+    /// Day-to-day use; dangerous tools need approval.
+    Family,
+    /// Read-only.
+    #[default]
+    Guest,
+}
+
+impl UserRole {
+    /// Agent mode for a turn of this role, given the configured mode.
+    pub fn cap(self, mode: AgentMode) -> AgentMode {
+        match (self, mode) {
+            (Self::Owner, mode) => mode,
+            (Self::Family, AgentMode::Autonomous) => AgentMode::Assistant,
+            (Self::Family, mode) => mode,
+            (Self::Guest, _) => AgentMode::Observer,
+        }
+    }
+}
+
+impl std::fmt::Display for UserRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Owner => write!(f, "owner"),
+            Self::Family => write!(f, "family"),
+            Self::Guest => write!(f, "guest"),
+        }
+    }
+}
+
+/// One named user.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct UserEntry {
+    pub role: UserRole,
+    /// Senders that are this user, as `channel:sender_id` or a bare
+    /// `sender_id` (e.g. `"telegram:12345"`, `"whatsapp:+4915112345678"`).
+    pub identities: Vec<String>,
+    /// Only these tools may run for this user. `None` allows every tool the
+    /// role allows.
+    pub allowed_tools: Option<Vec<String>>,
+    /// Tools that never run for this user.
+    pub denied_tools: Vec<String>,
+    /// Input + output tokens this user may spend per day (UTC). `None` is
+    /// unlimited.
+    pub daily_token_quota: Option<u64>,
+}
+
+/// Configuration for the user registry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct UsersConfig {
+    /// Resolve senders to users and enforce their roles.
+    pub enabled: bool,
+    /// Role of senders that match no user.
+    pub default_role: UserRole,
+    /// Daily token quota of senders that match no user, counted per sender.
+    pub default_daily_token_quota: Option<u64>,
+    /// User name → user.
+    pub users: HashMap<String, UserEntry>,
+}
+
+/// The user behind one message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+    /// The user's name, or `channel:sender_id` for an unknown sender.
+    pub name: String,
+    /// Whether the sender matched a configured user.
+    pub known: bool,
+    pub role: UserRole,
+    pub allowed_tools: Option<Vec<String>>,
+    pub denied_tools: Vec<String>,
+    pub daily_token_quota: Option<u64>,
+}
+
+impl UsersConfig {
+    /// User for a message sender, or `None` when the registry is off or the
+    /// sender is the CLI or a subagent. ZeptoClaw's own messages (heartbeat,
+    /// cron) are recognised by `InboundMessage::internal`, never by sender ID.
+    pub fn resolve(&self, channel: &str, sender_id: &str) -> Option<User> {
+        if !self.enabled || sender_id.is_empty() || matches!(channel, "cli" | "subagent") {
+            return None;
+        }
+        let qualified = format!("{}:{}", channel, sender_id);
+        let matches = |entry: &UserEntry, id: &str| entry.identities.iter().any(|i| i == id);
+        let found = self
+            .users
+            .iter()
+            .find(|(_, entry)| matches(entry, &qualified))
+            .or_else(|| {
+                self.users
+                    .iter()
+                    .find(|(_, entry)| matches(entry, sender_id))
+            });
+        Some(match found {
+            Some((name, entry)) => User {
+                name: name.clone(),
+                known: true,
+                role: entry.role,
+                allowed_tools: entry.allowed_tools.clone(),
+                denied_tools: entry.denied_tools.clone(),
+                daily_token_quota: entry.daily_token_quota,
+            },
+            None => User {
+                name: qualified,
+                known: false,
+                role: self.default_role,
+                allowed_tools: None,
+                denied_tools: Vec::new(),
+                daily_token_quota: self.default_daily_token_quota,
+            },
+        })
+    }
+
+    /// Configuration errors: identities claimed by two users.
+    pub fn validate(&self) -> Vec<String> {
+        let mut seen: HashMap<&str, &str> = HashMap::new();
+        let mut names: Vec<&String> = self.users.keys().collect();
+        names.sort();
+        let mut errors = Vec::new();
+        for name in names {
+            for identity in &self.users[name].identities {
+                if let Some(other) = seen.insert(identity.as_str(), name.as_str()) {
+                    errors.push(format!(
+                        "Identity '{}' belongs to both '{}' and '{}'",
+                        identity, other, name
+                    ));
+                }
+            }
+        }
+        errors
+    }
+}
+
+impl User {
+    /// Whether this user may run `tool`.
+    pub fn may_use(&self, tool: &str) -> bool {
+        !self.denied_tools.iter().any(|t| t == tool)
+            && self
+                .allowed_tools
+                .as_ref()
+                .is_none_or(|allowed| allowed.iter().any(|t| t == tool))
+    }
+
+    /// Private memory namespace, or `None` for the owner, who uses shared
+    /// memory.
+    pub fn memory_namespace(&self) -> Option<String> {
+        // `/` ends the namespace in a stored key.
+        (self.role != UserRole::Owner).then(|| self.name.replace('/', "_"))
+    }
+
+    fn quota(&self) -> Option<QuotaConfig> {
+        self.daily_token_quota.map(|max| QuotaConfig {
+            max_tokens: Some(max),
+            period: QuotaPeriod::Daily,
+            ..Default::default()
+        })
+    }
+
+    /// Error message when the user has used up today's tokens.
+    pub fn check_quota(&self, usage: &QuotaStore) -> Option<String> {
+        let quota = self.quota()?;
+        match usage.check(&self.name, &quota) {
+            QuotaCheckResult::Exceeded => Some(format!(
+                "Daily token quota of {} reached for {}; it resets at midnight UTC",
+                quota.max_tokens.unwrap_or_default(),
+                self.name
+            )),
+            _ => None,
+        }
+    }
+
+    /// Count tokens spent on this user's turn.
+    pub fn record_usage(&self, usage: &QuotaStore, tokens: u64) {
+        if tokens > 0 && self.daily_token_quota.is_some() {
+            usage.record(&self.name, &QuotaPeriod::Daily, 0.0, tokens);
+        }
+    }
+}
+
+/// Per-user token usage, in `<state dir>/users/usage.json`.
+pub fn usage_store() -> QuotaStore {
+    QuotaStore::load_from_dir(crate::config::Config::state_dir().join("users"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> UsersConfig {
+        let mut users = HashMap::new();
+        users.insert(
+            "anna".to_string(),
+            UserEntry {
+                role: UserRole::Owner,
+                identities: vec!["telegram:1".into(), "+4915112345678".into()],
+                ..Default::default()
+            },
+        );
+        users.insert(
+            "ben".to_string(),
+            UserEntry {
+                role: UserRole::Family,
+                identities: vec!["telegram:2".into()],
+                denied_tools: vec!["shell".into()],
+                daily_token_quota: Some(100),
+                ..Default::default()
+            },
+        );
+        UsersConfig {
+            enabled: true,
+            users,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_disabled_resolves_nobody() {
+        let config = UsersConfig {
+            enabled: false,
+            ..config()
+        };
+        assert!(config.resolve("telegram", "1").is_none());
+    }
+
+    #[test]
+    fn test_resolve_by_qualified_and_bare_identity() {
+        let config = config();
+        let anna = config.resolve("telegram", "1").unwrap();
+        assert_eq!((anna.name.as_str(), anna.role), ("anna", UserRole::Owner));
+        let anna = config.resolve("whatsapp", "+4915112345678").unwrap();
+        assert_eq!(anna.name, "anna");
+        // Same id on another channel is someone else.
+        assert!(!config.resolve("discord", "1").unwrap().known);
+    }
+
+    #[test]
+    fn test_unknown_sender_gets_default_role() {
+        let user = config().resolve("telegram", "99").unwrap();
+        assert!(!user.known);
+        assert_eq!(user.name, "telegram:99");
+        assert_eq!(user.role, UserRole::Guest);
+        assert_eq!(user.memory_namespace().as_deref(), Some("telegram:99"));
+    }
+
+    #[test]
+    fn test_system_senders_are_not_users() {
+        let config = config();
+        assert!(config.resolve("cli", "user").is_none());
+        assert!(config.resolve("subagent", "x").is_none());
+        // Sender IDs are chosen by whoever sends: no name is exempt.
+        for sender in ["cron", "system"] {
+            let user = config.resolve("telegram", sender).unwrap();
+            assert!(!user.known);
+            assert_eq!(user.role, config.default_role);
+        }
+    }
+
+    #[test]
+    fn test_role_caps_agent_mode() {
+        use AgentMode::*;
+        assert_eq!(UserRole::Owner.cap(Autonomous), Autonomous);
+        assert_eq!(UserRole::Family.cap(Autonomous), Assistant);
+        assert_eq!(UserRole::Family.cap(Observer), Observer);
+        assert_eq!(UserRole::Guest.cap(Autonomous), Observer);
+    }
+
+    #[test]
+    fn test_tool_permissions() {
+        let mut ben = config().resolve("telegram", "2").unwrap();
+        assert!(!ben.may_use("shell"));
+        assert!(ben.may_use("web_search"));
+        ben.allowed_tools = Some(vec!["web_search".into(), "shell".into()]);
+        assert!(ben.may_use("web_search"));
+        assert!(!ben.may_use("shell"));
+        assert!(!ben.may_use("memory"));
+    }
+
+    #[test]
+    fn test_owner_uses_shared_memory() {
+        let config = config();
+        assert_eq!(
+            config.resolve("telegram", "1").unwrap().memory_namespace(),
+            None
+        );
+        assert_eq!(
+            config
+                .resolve("telegram", "2")
+                .unwrap()
+                .memory_namespace()
+                .as_deref(),
+            Some("ben")
+        );
+    }
+
+    #[test]
+    fn test_daily_quota() {
+        let dir = tempfile::tempdir().unwrap();
+        let usage = QuotaStore::load_from_dir(dir.path());
+        let config = config();
+        let ben = config.resolve("telegram", "2").unwrap();
+        let anna = config.resolve("telegram", "1").unwrap();
+
+        assert!(ben.check_quota(&usage).is_none());
+        ben.record_usage(&usage, 60);
+        assert!(ben.check_quota(&usage).is_none());
+        ben.record_usage(&usage, 40);
+        assert!(ben.check_quota(&usage).unwrap().contains("ben"));
+
+        // No quota: nothing is recorded or enforced.
+        anna.record_usage(&usage, 1_000_000);
+        assert!(anna.check_quota(&usage).is_none());
+        assert!(!usage.snapshot().contains_key("anna"));
+    }
+
+    #[test]
+    fn test_validate_rejects_shared_identities() {
+        let mut config = config();
+        config
+            .users
+            .get_mut("ben")
+            .unwrap()
+            .identities
+            .push("telegram:1".into());
+        let errors = config.validate();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("telegram:1"));
+    }
+}
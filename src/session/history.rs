@@ -19,8 +19,12 @@ use crate::session::{Message, Role, Session};
 pub struct ConversationEntry {
     /// Unique session key (e.g., "cli:1739523000")
     pub session_key: String,
-    /// Human-readable title (first user message, truncated to 80 chars)
+    /// Human-readable title (generated title, else first user message
+    /// truncated to 80 chars)
     pub title: String,
+    /// Generated topic tags
+    #[serde(default)]
+    pub tags: Vec<String>,
     /// Number of messages in the conversation
     pub message_count: usize,
     /// When the conversation was last updated (ISO 8601)
@@ -47,7 +51,7 @@ impl ConversationHistory {
     ///
     /// Returns an error if the sessions directory cannot be created.
     pub fn new() -> Result<Self> {
-        let storage_path = Config::dir().join("sessions");
+        let storage_path = Config::data_dir().join("sessions");
         std::fs::create_dir_all(&storage_path)?;
         Ok(Self { storage_path })
     }
@@ -112,7 +116,11 @@ impl ConversationHistory {
 
             entries.push(ConversationEntry {
                 session_key: session.key.clone(),
-                title: Self::extract_title(&session.messages),
+                title: session
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| Self::extract_title(&session.messages)),
+                tags: session.tags.clone(),
                 message_count: session.messages.len(),
                 last_updated: session.updated_at.to_rfc3339(),
                 file_size,
@@ -158,6 +166,25 @@ impl ConversationHistory {
             .find(|e| e.title.to_lowercase().contains(&query_lower)))
     }
 
+    /// Search CLI conversations by title, tag or session key
+    /// (case-insensitive substring), newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing conversations fails.
+    pub fn search_conversations(&self, query: &str) -> Result<Vec<ConversationEntry>> {
+        let query_lower = query.trim().to_lowercase();
+        Ok(self
+            .list_conversations()?
+            .into_iter()
+            .filter(|e| {
+                e.title.to_lowercase().contains(&query_lower)
+                    || e.session_key.to_lowercase().contains(&query_lower)
+                    || e.tags.iter().any(|t| t.contains(&query_lower))
+            })
+            .collect())
+    }
+
     /// Generate a unique CLI session key using the current unix timestamp.
     ///
     /// Format: `cli:<unix_epoch_seconds>`
@@ -217,7 +244,7 @@ impl ConversationHistory {
         let mut deleted = 0;
 
         for entry in to_delete {
-            let sanitized = Self::sanitize_key(&entry.session_key);
+            let sanitized = super::SessionManager::sanitize_key(&entry.session_key);
             let file_path = self.storage_path.join(format!("{}.json", sanitized));
             if file_path.exists() {
                 std::fs::remove_file(&file_path).map_err(|e| {
@@ -233,27 +260,6 @@ impl ConversationHistory {
 
         Ok(deleted)
     }
-
-    /// Sanitize a session key for use as a filename (matches `SessionManager::sanitize_key`).
-    fn sanitize_key(key: &str) -> String {
-        let mut result = String::with_capacity(key.len() * 3);
-        for c in key.chars() {
-            match c {
-                '/' => result.push_str("%2F"),
-                '\\' => result.push_str("%5C"),
-                ':' => result.push_str("%3A"),
-                '*' => result.push_str("%2A"),
-                '?' => result.push_str("%3F"),
-                '"' => result.push_str("%22"),
-                '<' => result.push_str("%3C"),
-                '>' => result.push_str("%3E"),
-                '|' => result.push_str("%7C"),
-                '%' => result.push_str("%25"),
-                c => result.push(c),
-            }
-        }
-        result
-    }
 }
 
 #[cfg(test)]
@@ -281,11 +287,40 @@ mod tests {
         std::fs::write(path, serde_json::to_string(&session_json).unwrap()).unwrap();
     }
 
+    #[test]
+    fn test_generated_title_and_tags_used_and_searchable() {
+        let tmp = TempDir::new().unwrap();
+        write_test_session(tmp.path(), "cli:100", "hello", "2025-01-01T00:00:00Z");
+        let path = tmp.path().join("cli%3A200.json");
+        let session_json = serde_json::json!({
+            "key": "cli:200",
+            "messages": [{"role": "user", "content": "how do I file taxes"}],
+            "created_at": "2025-01-02T00:00:00Z",
+            "updated_at": "2025-01-02T00:00:00Z",
+            "title": "Portuguese tax filing",
+            "tags": ["taxes", "portugal"]
+        });
+        std::fs::write(path, session_json.to_string()).unwrap();
+
+        let history = ConversationHistory::with_path(tmp.path().to_path_buf()).unwrap();
+        let conversations = history.list_conversations().unwrap();
+        assert_eq!(conversations[0].title, "Portuguese tax filing");
+        assert_eq!(conversations[0].tags, vec!["taxes", "portugal"]);
+        assert_eq!(conversations[1].title, "hello");
+
+        let found = history.search_conversations("PORTUGAL").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].session_key, "cli:200");
+        assert_eq!(history.search_conversations("cli:").unwrap().len(), 2);
+        assert!(history.search_conversations("cooking").unwrap().is_empty());
+    }
+
     #[test]
     fn test_conversation_entry_creation() {
         let entry = ConversationEntry {
             session_key: "cli:1700000000".to_string(),
             title: "Hello world".to_string(),
+            tags: Vec::new(),
             message_count: 3,
             last_updated: "2025-11-14T22:13:20+00:00".to_string(),
             file_size: 256,
@@ -0,0 +1,498 @@
+//! Turn ledger: a structured event log of what the agent did in each turn.
+//!
+//! With `session.ledger` enabled, every turn is appended as one JSON line to
+//! `~/.zeptoclaw/ledger/<key>.jsonl`: the inbound message, the model used,
+//! each LLM response, each tool call (name, SHA-256 of its arguments,
+//! duration, outcome), context compactions, the final response, token usage
+//! and estimated cost. `zeptoclaw history trace <key>` prints the recorded
+//! turns; `zeptoclaw history replay <key>` re-runs one against the recorded
+//! LLM responses (see [`crate::agent::replay`]).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::bus::InboundMessage;
+use crate::config::Config;
+use crate::error::Result;
+use crate::providers::{LLMResponse, LLMToolCall};
+
+/// Characters of the inbound message and response shown per turn in
+/// [`render_trace`].
+const TRACE_PREVIEW_CHARS: usize = 200;
+
+/// Something that happened during a turn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LedgerEvent {
+    /// A provider response, recorded so the turn can be replayed.
+    Llm {
+        /// Offset from the start of the turn, in milliseconds.
+        at_ms: u64,
+        content: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tool_calls: Vec<LLMToolCall>,
+    },
+    /// A tool execution.
+    ToolCall {
+        /// Offset from the start of the turn, in milliseconds.
+        at_ms: u64,
+        name: String,
+        /// SHA-256 (hex) of the raw JSON arguments.
+        args_sha256: String,
+        duration_ms: u64,
+        ok: bool,
+    },
+    /// Context compaction before the provider call.
+    Compaction {
+        /// Offset from the start of the turn, in milliseconds.
+        at_ms: u64,
+        /// Recovery tier applied (see `agent::compaction`).
+        tier: u8,
+        messages_before: usize,
+        messages_after: usize,
+    },
+}
+
+impl LedgerEvent {
+    /// Offset from the start of the turn, in milliseconds.
+    pub fn at_ms(&self) -> u64 {
+        match self {
+            LedgerEvent::Llm { at_ms, .. }
+            | LedgerEvent::ToolCall { at_ms, .. }
+            | LedgerEvent::Compaction { at_ms, .. } => *at_ms,
+        }
+    }
+}
+
+/// How a turn ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TurnOutcome {
+    /// The agent produced a final response.
+    Completed,
+    /// The turn was cancelled (`/stop`, Ctrl+C).
+    Cancelled,
+}
+
+/// The ledger entry of one agent turn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerTurn {
+    /// Session the turn belongs to.
+    pub session_key: String,
+    /// When processing of the turn started.
+    pub started_at: DateTime<Utc>,
+    /// Turn duration in milliseconds.
+    pub duration_ms: u64,
+    pub channel: String,
+    #[serde(default)]
+    pub chat_id: String,
+    pub sender_id: String,
+    /// Inbound message text.
+    pub inbound: String,
+    /// Model chosen for the turn.
+    pub model: Option<String>,
+    /// Tool calls and compactions, in order.
+    pub events: Vec<LedgerEvent>,
+    /// Final response, when the turn produced one.
+    pub response: Option<String>,
+    pub outcome: TurnOutcome,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Estimated cost in USD; `None` when the model's pricing is unknown.
+    pub cost_usd: Option<f64>,
+}
+
+#[derive(Default, Clone)]
+struct LedgerState {
+    model: Option<String>,
+    events: Vec<LedgerEvent>,
+    input_tokens: u64,
+    output_tokens: u64,
+}
+
+/// Collects ledger events while a turn is running.
+///
+/// Uses interior mutability so it can be shared (via `Arc`) with tool
+/// futures that run in parallel.
+pub struct LedgerRecorder {
+    session_key: String,
+    channel: String,
+    chat_id: String,
+    sender_id: String,
+    inbound: String,
+    started_at: DateTime<Utc>,
+    origin: Instant,
+    state: Mutex<LedgerState>,
+}
+
+impl LedgerRecorder {
+    /// Start recording the turn for `msg`.
+    pub fn start(msg: &InboundMessage) -> Self {
+        Self {
+            session_key: msg.session_key.clone(),
+            channel: msg.channel.clone(),
+            chat_id: msg.chat_id.clone(),
+            sender_id: msg.sender_id.clone(),
+            inbound: msg.content.clone(),
+            started_at: Utc::now(),
+            origin: Instant::now(),
+            state: Mutex::new(LedgerState::default()),
+        }
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.origin.elapsed().as_millis() as u64
+    }
+
+    /// Record the model chosen for the turn.
+    pub fn set_model(&self, model: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state.model = Some(model.to_string());
+        }
+    }
+
+    /// Record a provider response.
+    pub fn record_llm_response(&self, content: &str, tool_calls: &[LLMToolCall]) {
+        let event = LedgerEvent::Llm {
+            at_ms: self.elapsed_ms(),
+            content: content.to_string(),
+            tool_calls: tool_calls.to_vec(),
+        };
+        if let Ok(mut state) = self.state.lock() {
+            state.events.push(event);
+        }
+    }
+
+    /// Record a tool call that took `duration_ms` and ends now.
+    pub fn record_tool_call(&self, name: &str, raw_args: &str, duration_ms: u64, ok: bool) {
+        let event = LedgerEvent::ToolCall {
+            at_ms: self.elapsed_ms().saturating_sub(duration_ms),
+            name: name.to_string(),
+            args_sha256: args_hash(raw_args),
+            duration_ms,
+            ok,
+        };
+        if let Ok(mut state) = self.state.lock() {
+            state.events.push(event);
+        }
+    }
+
+    /// Record a context compaction.
+    pub fn record_compaction(&self, tier: u8, messages_before: usize, messages_after: usize) {
+        let event = LedgerEvent::Compaction {
+            at_ms: self.elapsed_ms(),
+            tier,
+            messages_before,
+            messages_after,
+        };
+        if let Ok(mut state) = self.state.lock() {
+            state.events.push(event);
+        }
+    }
+
+    /// Add the token usage of one provider call.
+    pub fn record_tokens(&self, input: u64, output: u64) {
+        if let Ok(mut state) = self.state.lock() {
+            state.input_tokens += input;
+            state.output_tokens += output;
+        }
+    }
+
+    /// Finish the turn and return its ledger entry.
+    pub fn finish(&self, response: Option<&str>, outcome: TurnOutcome) -> LedgerTurn {
+        let state = self.state.lock().map(|s| s.clone()).unwrap_or_default();
+        let mut events = state.events;
+        events.sort_by_key(LedgerEvent::at_ms);
+        let cost_usd = state.model.as_deref().and_then(|model| {
+            crate::utils::cost::estimate_cost(
+                model,
+                state.input_tokens.min(u32::MAX as u64) as u32,
+                state.output_tokens.min(u32::MAX as u64) as u32,
+                &std::collections::HashMap::new(),
+            )
+        });
+        LedgerTurn {
+            session_key: self.session_key.clone(),
+            started_at: self.started_at,
+            duration_ms: self.elapsed_ms(),
+            channel: self.channel.clone(),
+            chat_id: self.chat_id.clone(),
+            sender_id: self.sender_id.clone(),
+            inbound: self.inbound.clone(),
+            model: state.model,
+            events,
+            response: response.map(str::to_string),
+            outcome,
+            input_tokens: state.input_tokens,
+            output_tokens: state.output_tokens,
+            cost_usd,
+        }
+    }
+}
+
+impl LedgerTurn {
+    /// The recorded provider responses, in order.
+    pub fn llm_responses(&self) -> Vec<LLMResponse> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                LedgerEvent::Llm {
+                    content,
+                    tool_calls,
+                    ..
+                } => Some(LLMResponse::with_tools(content, tool_calls.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// SHA-256 (hex) of raw tool arguments.
+pub fn args_hash(raw_args: &str) -> String {
+    hex::encode(Sha256::digest(raw_args.as_bytes()))
+}
+
+/// Append-only JSONL store of ledger turns, one file per session.
+pub struct LedgerStore {
+    dir: PathBuf,
+}
+
+impl LedgerStore {
+    /// Open the default store at `~/.zeptoclaw/ledger/`.
+    pub fn new() -> Result<Self> {
+        Self::with_path(Config::data_dir().join("ledger"))
+    }
+
+    /// Open a store rooted at a custom directory.
+    pub fn with_path(dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Open the store that sits next to a sessions directory
+    /// (`<parent>/ledger/`).
+    pub fn for_sessions_dir(sessions_dir: &Path) -> Result<Self> {
+        let parent = sessions_dir.parent().unwrap_or(sessions_dir);
+        Self::with_path(parent.join("ledger"))
+    }
+
+    fn file_for(&self, session_key: &str) -> PathBuf {
+        self.dir.join(format!(
+            "{}.jsonl",
+            crate::session::SessionManager::sanitize_key(session_key)
+        ))
+    }
+
+    /// Append one turn to the session's ledger file.
+    pub async fn append(&self, turn: &LedgerTurn) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut line = serde_json::to_string(turn)?;
+        line.push('\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.file_for(&turn.session_key))
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        // tokio finishes file writes in the background; flush so the turn is
+        // on disk before the next append or load.
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// Load all recorded turns for a session (oldest first). Malformed lines
+    /// are skipped.
+    pub fn load(&self, session_key: &str) -> Result<Vec<LedgerTurn>> {
+        let path = self.file_for(session_key);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect())
+    }
+}
+
+fn preview(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match line.char_indices().nth(TRACE_PREVIEW_CHARS) {
+        Some((idx, _)) => format!("{}…", &line[..idx]),
+        None => line,
+    }
+}
+
+/// Render turns as a plain-text replay. `first_number` is the 1-based
+/// number of the first turn in `turns`.
+pub fn render_trace(turns: &[LedgerTurn], first_number: usize) -> String {
+    let mut out = String::new();
+    for (index, turn) in turns.iter().enumerate() {
+        if index > 0 {
+            out.push('\n');
+        }
+        let outcome = match turn.outcome {
+            TurnOutcome::Completed => "completed",
+            TurnOutcome::Cancelled => "cancelled",
+        };
+        out.push_str(&format!(
+            "Turn {} — {} — {} in {} ms — model {}\n",
+            first_number + index,
+            turn.started_at.to_rfc3339(),
+            outcome,
+            turn.duration_ms,
+            turn.model.as_deref().unwrap_or("-")
+        ));
+        out.push_str(&format!(
+            "  > {} ({}): {}\n",
+            turn.sender_id,
+            turn.channel,
+            preview(&turn.inbound)
+        ));
+        for event in &turn.events {
+            match event {
+                LedgerEvent::Llm {
+                    at_ms,
+                    content,
+                    tool_calls,
+                } => {
+                    let names: Vec<&str> = tool_calls.iter().map(|c| c.name.as_str()).collect();
+                    let summary = if names.is_empty() {
+                        preview(content)
+                    } else {
+                        format!("calls {}", names.join(", "))
+                    };
+                    out.push_str(&format!("  +{:>6} ms  llm: {}\n", at_ms, summary));
+                }
+                LedgerEvent::ToolCall {
+                    at_ms,
+                    name,
+                    args_sha256,
+                    duration_ms,
+                    ok,
+                } => out.push_str(&format!(
+                    "  +{:>6} ms  tool {} (args {}) {} ms {}\n",
+                    at_ms,
+                    name,
+                    &args_sha256[..args_sha256.len().min(12)],
+                    duration_ms,
+                    if *ok { "ok" } else { "FAILED" }
+                )),
+                LedgerEvent::Compaction {
+                    at_ms,
+                    tier,
+                    messages_before,
+                    messages_after,
+                } => out.push_str(&format!(
+                    "  +{:>6} ms  compaction tier {}: {} -> {} messages\n",
+                    at_ms, tier, messages_before, messages_after
+                )),
+            }
+        }
+        if let Some(response) = &turn.response {
+            out.push_str(&format!("  < {}\n", preview(response)));
+        }
+        let cost = turn
+            .cost_usd
+            .map(|c| format!(", ~${:.4}", c))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "  tokens: {} in / {} out{}\n",
+            turn.input_tokens, turn.output_tokens, cost
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder() -> LedgerRecorder {
+        let msg = InboundMessage::new("telegram", "alice", "42", "What's the weather in Lisbon?");
+        LedgerRecorder::start(&msg)
+    }
+
+    #[test]
+    fn test_recorder_collects_events_and_cost() {
+        let ledger = recorder();
+        ledger.set_model("claude-sonnet-4-5-20250929");
+        ledger.record_compaction(1, 40, 12);
+        let call = LLMToolCall::new("call_1", "web_fetch", r#"{"url":"https://example.com"}"#);
+        ledger.record_llm_response("", &[call]);
+        ledger.record_tool_call("web_fetch", r#"{"url":"https://example.com"}"#, 0, true);
+        ledger.record_llm_response("Sunny, 24°C.", &[]);
+        ledger.record_tokens(1000, 200);
+        ledger.record_tokens(500, 100);
+        let turn = ledger.finish(Some("Sunny, 24°C."), TurnOutcome::Completed);
+
+        assert_eq!(turn.session_key, "telegram:42");
+        assert_eq!(turn.sender_id, "alice");
+        assert_eq!(turn.chat_id, "42");
+        assert_eq!(turn.events.len(), 4);
+        assert!(matches!(
+            turn.events[0],
+            LedgerEvent::Compaction { tier: 1, .. }
+        ));
+        match &turn.events[2] {
+            LedgerEvent::ToolCall {
+                name, args_sha256, ..
+            } => {
+                assert_eq!(name, "web_fetch");
+                assert_eq!(args_sha256, &args_hash(r#"{"url":"https://example.com"}"#));
+                assert_eq!(args_sha256.len(), 64);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        let responses = turn.llm_responses();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].tool_calls[0].name, "web_fetch");
+        assert_eq!(responses[1].content, "Sunny, 24°C.");
+        assert_eq!((turn.input_tokens, turn.output_tokens), (1500, 300));
+        assert!(turn.cost_usd.unwrap() > 0.0);
+
+        let unknown = recorder();
+        unknown.set_model("local-model");
+        assert!(unknown
+            .finish(None, TurnOutcome::Cancelled)
+            .cost_usd
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_store_append_load_and_render() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = LedgerStore::for_sessions_dir(&temp.path().join("sessions")).unwrap();
+        assert!(temp.path().join("ledger").is_dir());
+
+        let ledger = recorder();
+        ledger.record_tool_call("shell", "{}", 5, false);
+        store
+            .append(&ledger.finish(Some("Done."), TurnOutcome::Completed))
+            .await
+            .unwrap();
+        store
+            .append(&recorder().finish(None, TurnOutcome::Cancelled))
+            .await
+            .unwrap();
+
+        let turns = store.load("telegram:42").unwrap();
+        assert_eq!(turns.len(), 2);
+        assert!(store.load("telegram:other").unwrap().is_empty());
+
+        let trace = render_trace(&turns, 1);
+        assert!(trace.contains("Turn 1 — "));
+        assert!(trace.contains("completed in"));
+        assert!(trace.contains("> alice (telegram): What's the weather in Lisbon?"));
+        assert!(trace.contains("tool shell (args 44136fa355b3) 5 ms FAILED"));
+        assert!(trace.contains("< Done."));
+        assert!(trace.contains("Turn 2 — "));
+        assert!(trace.contains("cancelled in"));
+    }
+}
@@ -0,0 +1,362 @@
+//! Session links — continue one conversation from several channels.
+//!
+//! A link aliases a destination session key (e.g. `telegram:123`) to a
+//! source session (e.g. `cli:cli`), so messages on either side read and
+//! write the same history. Only the session is shared: each message keeps
+//! its own channel, so the destination's memory and citation settings
+//! still apply.
+//!
+//! Linking is an explicit handshake:
+//!
+//! 1. `/link` (or the `session_link` tool) on the source issues a one-time
+//!    code, valid for [`LINK_CODE_TTL_SECS`].
+//! 2. `/link <code>` on the destination asks for confirmation.
+//! 3. `/link confirm` on the destination creates the alias.
+//!
+//! `/unlink` removes the alias; the destination's own history, which is
+//! never touched, comes back. Links are stored next to the session files
+//! so the CLI and the gateway see the same links.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::bus::InboundMessage;
+use crate::error::{Result, ZeptoError};
+
+/// How long a link code stays valid.
+pub const LINK_CODE_TTL_SECS: i64 = 600;
+
+/// File name of the link store inside the sessions directory. Session
+/// files all end in `.json`, so this cannot collide with one.
+const LINKS_FILE: &str = ".session_links";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingLink {
+    /// Session the destination will join.
+    session_key: String,
+    expires_at: i64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LinkState {
+    /// Destination session key → source session key.
+    #[serde(default)]
+    aliases: HashMap<String, String>,
+    /// Issued codes, not yet redeemed.
+    #[serde(default)]
+    codes: HashMap<String, PendingLink>,
+    /// Destination session key → link awaiting `/link confirm`.
+    #[serde(default)]
+    confirmations: HashMap<String, PendingLink>,
+}
+
+impl LinkState {
+    fn prune(&mut self, now: i64) {
+        self.codes.retain(|_, p| p.expires_at > now);
+        self.confirmations.retain(|_, p| p.expires_at > now);
+    }
+
+    fn resolve(&self, key: &str) -> String {
+        self.aliases
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+}
+
+/// Persistent session alias table, shared by clones.
+#[derive(Debug, Clone)]
+pub struct SessionLinks {
+    path: Option<PathBuf>,
+    state: Arc<Mutex<LinkState>>,
+}
+
+impl SessionLinks {
+    /// Link store in `sessions_dir`, or in memory only when `None`.
+    pub fn new(sessions_dir: Option<PathBuf>) -> Self {
+        Self {
+            path: sessions_dir.map(|dir| dir.join(LINKS_FILE)),
+            state: Arc::new(Mutex::new(LinkState::default())),
+        }
+    }
+
+    /// Run `f` against the current state, re-reading it from disk first
+    /// (another process may have changed it) and saving it afterwards when
+    /// `f` reports a change.
+    fn with_state<R>(&self, f: impl FnOnce(&mut LinkState) -> (R, bool)) -> R {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(path) = &self.path {
+            *state = std::fs::read_to_string(path)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default();
+        }
+        let (result, changed) = f(&mut state);
+        if changed {
+            if let Some(path) = &self.path {
+                let saved = serde_json::to_string_pretty(&*state)
+                    .map_err(std::io::Error::other)
+                    .and_then(|json| std::fs::write(path, json));
+                if let Err(e) = saved {
+                    warn!(error = %e, "Failed to save session links");
+                }
+            }
+        }
+        result
+    }
+
+    /// The session `key` is linked to, or `key` itself.
+    pub fn resolve(&self, key: &str) -> String {
+        self.with_state(|state| (state.resolve(key), false))
+    }
+
+    /// A copy of `msg` pointed at its linked session, or `None` if the
+    /// message's session is not linked.
+    pub fn resolve_message(&self, msg: &InboundMessage) -> Option<InboundMessage> {
+        let key = self.resolve(&msg.session_key);
+        (key != msg.session_key).then(|| {
+            let mut linked = msg.clone();
+            linked.session_key = key;
+            linked
+        })
+    }
+
+    /// Issue a one-time code for joining `session_key`'s conversation.
+    pub fn issue_code(&self, session_key: &str) -> String {
+        let code = format!("{:06}", uuid::Uuid::new_v4().as_u128() % 1_000_000);
+        let now = chrono::Utc::now().timestamp();
+        self.with_state(|state| {
+            state.prune(now);
+            let pending = PendingLink {
+                session_key: state.resolve(session_key),
+                expires_at: now + LINK_CODE_TTL_SECS,
+            };
+            state.codes.insert(code.clone(), pending);
+            (code, true)
+        })
+    }
+
+    /// Redeem `code` for `destination`, returning the session it would
+    /// join. The link is only created by [`confirm`](Self::confirm).
+    pub fn request(&self, destination: &str, code: &str) -> Result<String> {
+        let now = chrono::Utc::now().timestamp();
+        self.with_state(|state| {
+            state.prune(now);
+            let Some(pending) = state.codes.remove(code.trim()) else {
+                return (
+                    Err(ZeptoError::Session(
+                        "That link code is invalid or has expired.".to_string(),
+                    )),
+                    false,
+                );
+            };
+            if state.resolve(destination) == pending.session_key {
+                return (
+                    Err(ZeptoError::Session(
+                        "This chat is already part of that conversation.".to_string(),
+                    )),
+                    true,
+                );
+            }
+            let source = pending.session_key.clone();
+            state.confirmations.insert(
+                destination.to_string(),
+                PendingLink {
+                    expires_at: now + LINK_CODE_TTL_SECS,
+                    ..pending
+                },
+            );
+            (Ok(source), true)
+        })
+    }
+
+    /// Link `destination` to the session it requested, returning that
+    /// session's key.
+    pub fn confirm(&self, destination: &str) -> Result<String> {
+        let now = chrono::Utc::now().timestamp();
+        self.with_state(|state| {
+            state.prune(now);
+            let Some(pending) = state.confirmations.remove(destination) else {
+                return (
+                    Err(ZeptoError::Session(
+                        "No link is waiting for confirmation. Send /link <code> first.".to_string(),
+                    )),
+                    false,
+                );
+            };
+            let source = state.resolve(&pending.session_key);
+            if source == destination {
+                return (
+                    Err(ZeptoError::Session(
+                        "This chat is already part of that conversation.".to_string(),
+                    )),
+                    true,
+                );
+            }
+            // Anything that followed the destination now follows the source.
+            for target in state.aliases.values_mut() {
+                if target == destination {
+                    *target = source.clone();
+                }
+            }
+            state
+                .aliases
+                .insert(destination.to_string(), source.clone());
+            (Ok(source), true)
+        })
+    }
+
+    /// Remove `destination`'s link. Returns whether it was linked.
+    pub fn unlink(&self, destination: &str) -> bool {
+        self.with_state(|state| {
+            let removed = state.aliases.remove(destination).is_some();
+            (removed, removed)
+        })
+    }
+}
+
+/// The user-facing text of a link error.
+fn user_message(err: ZeptoError) -> String {
+    match err {
+        ZeptoError::Session(message) => message,
+        other => other.to_string(),
+    }
+}
+
+/// Handle `/link` and `/unlink` sent from `session_key`.
+///
+/// Returns the reply, or `None` if `text` is not a link command.
+pub fn handle_link_command(links: &SessionLinks, session_key: &str, text: &str) -> Option<String> {
+    let mut words = text.split_whitespace();
+    let command = words.next()?;
+    let arg = words.next();
+    if words.next().is_some() {
+        return None;
+    }
+
+    let reply = match (command, arg) {
+        ("/unlink", None) => {
+            if links.unlink(session_key) {
+                "Unlinked. This chat has its own conversation again.".to_string()
+            } else {
+                "This chat is not linked to another conversation.".to_string()
+            }
+        }
+        ("/link", None) => {
+            let code = links.issue_code(session_key);
+            format!(
+                "To continue this conversation on another channel, send `/link {}` there within {} minutes.",
+                code,
+                LINK_CODE_TTL_SECS / 60
+            )
+        }
+        ("/link", Some("confirm")) => match links.confirm(session_key) {
+            Ok(source) => format!(
+                "Linked. This chat now continues the conversation from `{}`. Send /unlink to undo.",
+                source
+            ),
+            Err(e) => user_message(e),
+        },
+        ("/link", Some(code)) => match links.request(session_key, code) {
+            Ok(source) => format!(
+                "This will continue the conversation from `{}` in this chat; this chat's own history is set aside until you /unlink. Reply `/link confirm` to continue.",
+                source
+            ),
+            Err(e) => user_message(e),
+        },
+        _ => return None,
+    };
+    Some(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code_from(reply: &str) -> String {
+        reply
+            .split('`')
+            .nth(1)
+            .and_then(|cmd| cmd.strip_prefix("/link "))
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_link_handshake_aliases_destination() {
+        let links = SessionLinks::new(None);
+        let reply = handle_link_command(&links, "cli:cli", "/link").unwrap();
+        let code = code_from(&reply);
+        assert_eq!(code.len(), 6);
+
+        let reply = handle_link_command(&links, "telegram:42", &format!("/link {}", code)).unwrap();
+        assert!(reply.contains("/link confirm"));
+        // Not linked until confirmed.
+        assert_eq!(links.resolve("telegram:42"), "telegram:42");
+
+        let reply = handle_link_command(&links, "telegram:42", "/link confirm").unwrap();
+        assert!(reply.starts_with("Linked"));
+        assert_eq!(links.resolve("telegram:42"), "cli:cli");
+
+        let msg = InboundMessage::new("telegram", "u", "42", "hi");
+        let linked = links.resolve_message(&msg).unwrap();
+        assert_eq!(linked.session_key, "cli:cli");
+        assert_eq!(linked.channel, "telegram");
+
+        let reply = handle_link_command(&links, "telegram:42", "/unlink").unwrap();
+        assert!(reply.starts_with("Unlinked"));
+        assert!(links.resolve_message(&msg).is_none());
+    }
+
+    #[test]
+    fn test_codes_are_single_use_and_confirmation_is_required() {
+        let links = SessionLinks::new(None);
+        let code = links.issue_code("cli:cli");
+        assert!(links.request("telegram:1", "000000x").is_err());
+        assert!(links.confirm("telegram:1").is_err());
+
+        assert_eq!(links.request("telegram:1", &code).unwrap(), "cli:cli");
+        assert!(links.request("discord:2", &code).is_err());
+        assert!(links.confirm("discord:2").is_err());
+        assert_eq!(links.confirm("telegram:1").unwrap(), "cli:cli");
+    }
+
+    #[test]
+    fn test_links_follow_the_root_session() {
+        let links = SessionLinks::new(None);
+        let code = links.issue_code("cli:cli");
+        links.request("telegram:1", &code).unwrap();
+        links.confirm("telegram:1").unwrap();
+
+        // A code issued from the linked chat joins the root session.
+        let code = links.issue_code("telegram:1");
+        assert_eq!(links.request("discord:2", &code).unwrap(), "cli:cli");
+
+        let code = links.issue_code("cli:cli");
+        assert!(links.request("telegram:1", &code).is_err());
+    }
+
+    #[test]
+    fn test_links_persist_across_instances() {
+        let dir = tempfile::tempdir().unwrap();
+        let gateway = SessionLinks::new(Some(dir.path().to_path_buf()));
+        let cli = SessionLinks::new(Some(dir.path().to_path_buf()));
+
+        let code = cli.issue_code("cli:cli");
+        gateway.request("telegram:1", &code).unwrap();
+        gateway.confirm("telegram:1").unwrap();
+        assert_eq!(cli.resolve("telegram:1"), "cli:cli");
+    }
+
+    #[test]
+    fn test_other_text_is_not_a_link_command() {
+        let links = SessionLinks::new(None);
+        assert!(handle_link_command(&links, "cli:cli", "hello").is_none());
+        assert!(handle_link_command(&links, "cli:cli", "/linkage").is_none());
+        assert!(handle_link_command(&links, "cli:cli", "/link a b").is_none());
+    }
+}
@@ -123,6 +123,8 @@ pub fn mime_to_ext(mime_type: &str) -> &'static str {
         "image/png" => "png",
         "image/gif" => "gif",
         "image/webp" => "webp",
+        "application/pdf" => "pdf",
+        "image/svg+xml" => "svg",
         _ => "bin",
     }
 }
@@ -213,6 +215,8 @@ mod tests {
         assert_eq!(mime_to_ext("image/png"), "png");
         assert_eq!(mime_to_ext("image/gif"), "gif");
         assert_eq!(mime_to_ext("image/webp"), "webp");
+        assert_eq!(mime_to_ext("application/pdf"), "pdf");
+        assert_eq!(mime_to_ext("image/svg+xml"), "svg");
         assert_eq!(mime_to_ext("image/unknown"), "bin");
     }
 
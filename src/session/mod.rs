@@ -27,11 +27,16 @@
 //! ```
 
 pub mod history;
+pub mod ledger;
+pub mod links;
 pub mod media;
 pub mod repair;
+pub mod timeline;
+pub mod titles;
 pub mod types;
 
 pub use history::ConversationHistory;
+pub use links::SessionLinks;
 pub use repair::{repair_messages, RepairStats};
 pub use types::{ContentPart, ImageSource, Message, Role, Session, ToolCall};
 
@@ -64,6 +69,8 @@ pub struct SessionManager {
     sessions: Arc<RwLock<HashMap<String, Session>>>,
     /// Optional path for file-based persistence
     storage_path: Option<PathBuf>,
+    /// Cross-channel session aliases
+    links: SessionLinks,
 }
 
 impl SessionManager {
@@ -83,10 +90,11 @@ impl SessionManager {
     /// let manager = SessionManager::new().unwrap();
     /// ```
     pub fn new() -> Result<Self> {
-        let storage_path = Config::dir().join("sessions");
+        let storage_path = Config::data_dir().join("sessions");
         std::fs::create_dir_all(&storage_path)?;
         Ok(Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
+            links: SessionLinks::new(Some(storage_path.clone())),
             storage_path: Some(storage_path),
         })
     }
@@ -106,6 +114,7 @@ impl SessionManager {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             storage_path: None,
+            links: SessionLinks::new(None),
         }
     }
 
@@ -129,6 +138,7 @@ impl SessionManager {
         std::fs::create_dir_all(&path)?;
         Ok(Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
+            links: SessionLinks::new(Some(path.clone())),
             storage_path: Some(path),
         })
     }
@@ -249,22 +259,46 @@ impl SessionManager {
     /// }
     /// ```
     pub async fn save(&self, session: &Session) -> Result<()> {
-        // Update in-memory cache
-        {
+        // Update in-memory cache. A copy loaded before the title was
+        // generated keeps the cached title and tags.
+        let session = {
             let mut sessions = self.sessions.write().await;
+            let mut session = session.clone();
+            if session.title.is_none() {
+                if let Some(cached) = sessions.get(&session.key) {
+                    session.title = cached.title.clone();
+                    session.tags = cached.tags.clone();
+                }
+            }
             sessions.insert(session.key.clone(), session.clone());
-        }
+            session
+        };
 
         // Write to disk if persistence is enabled
         if let Some(ref storage_path) = self.storage_path {
             let file_path = storage_path.join(format!("{}.json", Self::sanitize_key(&session.key)));
-            let content = serde_json::to_string_pretty(session)?;
+            let content = serde_json::to_string_pretty(&session)?;
             tokio::fs::write(&file_path, content).await?;
         }
 
         Ok(())
     }
 
+    /// Set the title and tags of a stored session. Does nothing if the
+    /// session does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if loading or saving the session fails.
+    pub async fn set_title(&self, key: &str, title: &str, tags: Vec<String>) -> Result<()> {
+        let Some(mut session) = self.get(key).await? else {
+            return Ok(());
+        };
+        session.title = Some(title.to_string());
+        session.tags = tags;
+        self.save(&session).await
+    }
+
     /// Delete a session from both memory and disk.
     ///
     /// # Arguments
@@ -407,6 +441,11 @@ impl SessionManager {
         self.storage_path.as_deref()
     }
 
+    /// Cross-channel session links, persisted alongside the sessions.
+    pub fn links(&self) -> &SessionLinks {
+        &self.links
+    }
+
     /// Sanitize a session key for use as a filename.
     ///
     /// Uses percent-encoding to ensure the mapping is bijective (one-to-one).
@@ -417,11 +456,11 @@ impl SessionManager {
     /// - "discord/server" → "discord%2Fserver"
     ///
     /// This is reversible via `unsanitize_key`, ensuring keys round-trip correctly.
-    fn sanitize_key(key: &str) -> String {
+    pub(crate) fn sanitize_key(key: &str) -> String {
         // Characters that are problematic in filenames across platforms
         // We percent-encode them to make the mapping reversible
         let mut result = String::with_capacity(key.len() * 3);
-        for c in key.chars() {
+        for (i, c) in key.chars().enumerate() {
             match c {
                 '/' => result.push_str("%2F"),
                 '\\' => result.push_str("%5C"),
@@ -433,6 +472,13 @@ impl SessionManager {
                 '>' => result.push_str("%3E"),
                 '|' => result.push_str("%7C"),
                 '%' => result.push_str("%25"), // Escape % itself to make it reversible
+                // NTFS rejects control characters in filenames
+                c if c.is_ascii_control() => result.push_str(&format!("%{:02X}", c as u8)),
+                // NTFS treats `CON.json`, `nul.json`, ... as devices; encoding the
+                // first character keeps the name reversible and off the device list.
+                c if i == 0 && is_windows_reserved_name(key) => {
+                    result.push_str(&format!("%{:02X}", c as u8))
+                }
                 c => result.push(c),
             }
         }
@@ -494,6 +540,7 @@ impl Clone for SessionManager {
         Self {
             sessions: Arc::clone(&self.sessions),
             storage_path: self.storage_path.clone(),
+            links: self.links.clone(),
         }
     }
 }
@@ -507,6 +554,16 @@ impl Default for SessionManager {
     }
 }
 
+/// Whether `key` (up to its first `.`) is a Windows reserved device name.
+fn is_windows_reserved_name(key: &str) -> bool {
+    const RESERVED: &[&str] = &[
+        "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+        "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+    ];
+    let stem = key.split('.').next().unwrap_or(key).trim_end();
+    RESERVED.iter().any(|name| stem.eq_ignore_ascii_case(name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -695,6 +752,11 @@ mod tests {
         );
         // Percent itself is escaped to make encoding reversible
         assert_eq!(SessionManager::sanitize_key("100%done"), "100%25done");
+        // Windows device names and control characters
+        assert_eq!(SessionManager::sanitize_key("con"), "%63on");
+        assert_eq!(SessionManager::sanitize_key("COM1.backup"), "%43OM1.backup");
+        assert_eq!(SessionManager::sanitize_key("console"), "console");
+        assert_eq!(SessionManager::sanitize_key("a\tb"), "a%09b");
     }
 
     #[test]
@@ -707,6 +769,9 @@ mod tests {
             "a:b/c\\d*e?f\"g<h>i|j",
             "100%done",
             "multi%percent%%test",
+            "NUL",
+            "lpt9.log",
+            "tab\there",
         ];
         for key in &keys {
             let sanitized = SessionManager::sanitize_key(key);
@@ -0,0 +1,414 @@
+//! Per-turn timeline recording for diagnosing slow conversations.
+//!
+//! With `session.timeline` enabled, every agent turn records where its
+//! wall-clock time went: how long the message waited on the bus, each
+//! provider call, and each tool execution. Turns are appended as JSON lines
+//! to `~/.zeptoclaw/timelines/<key>.jsonl` and can be exported with
+//! `zeptoclaw history timeline <key>`, either as JSON or as a simple
+//! self-contained HTML Gantt view.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::config::Config;
+use crate::error::Result;
+
+/// Metadata key stamped on inbound messages when they enter the bus
+/// (milliseconds since the Unix epoch). Used to compute queue wait.
+pub const ENQUEUED_AT_METADATA_KEY: &str = "enqueued_at_ms";
+
+/// What a timeline span measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpanKind {
+    /// Time the inbound message spent queued before processing started.
+    QueueWait,
+    /// A single LLM provider call.
+    Provider,
+    /// A single tool execution.
+    Tool,
+}
+
+impl SpanKind {
+    fn css_class(self) -> &'static str {
+        match self {
+            SpanKind::QueueWait => "queue",
+            SpanKind::Provider => "provider",
+            SpanKind::Tool => "tool",
+        }
+    }
+}
+
+/// A single timed span within a turn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineSpan {
+    /// Span category.
+    pub kind: SpanKind,
+    /// Provider name or tool name.
+    pub label: String,
+    /// Offset from the start of the turn, in milliseconds. Queue wait spans
+    /// start at zero; everything else is shifted by the queue wait.
+    pub start_ms: u64,
+    /// Span duration in milliseconds.
+    pub duration_ms: u64,
+    /// Whether the underlying call succeeded.
+    pub ok: bool,
+}
+
+/// The recorded timeline of one agent turn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnTimeline {
+    /// Session the turn belongs to.
+    pub session_key: String,
+    /// When processing of the turn started.
+    pub started_at: DateTime<Utc>,
+    /// Total turn duration (including queue wait), in milliseconds.
+    pub total_ms: u64,
+    /// Ordered spans recorded during the turn.
+    pub spans: Vec<TimelineSpan>,
+}
+
+impl TurnTimeline {
+    /// Sum of span durations for the given kind.
+    pub fn total_for(&self, kind: SpanKind) -> u64 {
+        self.spans
+            .iter()
+            .filter(|s| s.kind == kind)
+            .map(|s| s.duration_ms)
+            .sum()
+    }
+}
+
+/// Collects spans while a turn is running.
+///
+/// Uses interior mutability so it can be shared (via `Arc`) with tool
+/// futures that run in parallel.
+pub struct TimelineRecorder {
+    session_key: String,
+    started_at: DateTime<Utc>,
+    origin: Instant,
+    queue_wait_ms: u64,
+    spans: Mutex<Vec<TimelineSpan>>,
+}
+
+impl TimelineRecorder {
+    /// Start recording a turn. `queue_wait_ms` is recorded as the first span
+    /// when non-zero.
+    pub fn start(session_key: &str, queue_wait_ms: u64) -> Self {
+        let mut spans = Vec::new();
+        if queue_wait_ms > 0 {
+            spans.push(TimelineSpan {
+                kind: SpanKind::QueueWait,
+                label: "queue".to_string(),
+                start_ms: 0,
+                duration_ms: queue_wait_ms,
+                ok: true,
+            });
+        }
+        Self {
+            session_key: session_key.to_string(),
+            started_at: Utc::now(),
+            origin: Instant::now(),
+            queue_wait_ms,
+            spans: Mutex::new(spans),
+        }
+    }
+
+    /// Record a span that began at `started` and ends now.
+    pub fn record(&self, kind: SpanKind, label: &str, started: Instant, ok: bool) {
+        let start_ms =
+            started.saturating_duration_since(self.origin).as_millis() as u64 + self.queue_wait_ms;
+        let duration_ms = started.elapsed().as_millis() as u64;
+        if let Ok(mut spans) = self.spans.lock() {
+            spans.push(TimelineSpan {
+                kind,
+                label: label.to_string(),
+                start_ms,
+                duration_ms,
+                ok,
+            });
+        }
+    }
+
+    /// Finish the turn and return the recorded timeline.
+    pub fn finish(&self) -> TurnTimeline {
+        let mut spans = self.spans.lock().map(|s| s.clone()).unwrap_or_default();
+        spans.sort_by_key(|s| s.start_ms);
+        TurnTimeline {
+            session_key: self.session_key.clone(),
+            started_at: self.started_at,
+            total_ms: self.origin.elapsed().as_millis() as u64 + self.queue_wait_ms,
+            spans,
+        }
+    }
+}
+
+/// Compute how long an inbound message waited since it was enqueued, based
+/// on the [`ENQUEUED_AT_METADATA_KEY`] stamp. Returns 0 when the stamp is
+/// missing or malformed.
+pub fn queue_wait_ms(metadata: &std::collections::HashMap<String, String>) -> u64 {
+    metadata
+        .get(ENQUEUED_AT_METADATA_KEY)
+        .and_then(|v| v.parse::<i64>().ok())
+        .map(|enqueued| (Utc::now().timestamp_millis() - enqueued).max(0) as u64)
+        .unwrap_or(0)
+}
+
+/// Append-only JSONL store of turn timelines, one file per session.
+pub struct TimelineStore {
+    dir: PathBuf,
+}
+
+impl TimelineStore {
+    /// Open the default store at `~/.zeptoclaw/timelines/`.
+    pub fn new() -> Result<Self> {
+        Self::with_path(Config::data_dir().join("timelines"))
+    }
+
+    /// Open a store rooted at a custom directory.
+    pub fn with_path(dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Open the store that sits next to a sessions directory
+    /// (`<parent>/timelines/`).
+    pub fn for_sessions_dir(sessions_dir: &Path) -> Result<Self> {
+        let parent = sessions_dir.parent().unwrap_or(sessions_dir);
+        Self::with_path(parent.join("timelines"))
+    }
+
+    fn file_for(&self, session_key: &str) -> PathBuf {
+        self.dir.join(format!(
+            "{}.jsonl",
+            crate::session::SessionManager::sanitize_key(session_key)
+        ))
+    }
+
+    /// Append one turn to the session's timeline file.
+    pub async fn append(&self, turn: &TurnTimeline) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut line = serde_json::to_string(turn)?;
+        line.push('\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.file_for(&turn.session_key))
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        Ok(())
+    }
+
+    /// Load all recorded turns for a session (oldest first). Malformed lines
+    /// are skipped.
+    pub fn load(&self, session_key: &str) -> Result<Vec<TurnTimeline>> {
+        let path = self.file_for(session_key);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect())
+    }
+}
+
+fn escape_html(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render turns as a self-contained HTML Gantt chart.
+///
+/// Each turn gets its own block; each span is a bar positioned relative to
+/// the turn's total duration.
+pub fn render_html(session_key: &str, turns: &[TurnTimeline]) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<title>Timeline: {}</title>\n",
+        escape_html(session_key)
+    ));
+    html.push_str(
+        "<style>\n\
+body{font-family:sans-serif;margin:2em;color:#222}\n\
+.turn{margin-bottom:2em}\n\
+.row{display:flex;align-items:center;height:22px}\n\
+.label{width:220px;font-size:12px;overflow:hidden;white-space:nowrap;text-overflow:ellipsis}\n\
+.track{position:relative;flex:1;height:16px;background:#f2f2f2}\n\
+.bar{position:absolute;height:16px;min-width:2px}\n\
+.queue{background:#bbb}.provider{background:#4a7fd6}.tool{background:#3aa55d}\n\
+.failed{background:#d64a4a}\n\
+.ms{width:90px;text-align:right;font-size:12px}\n\
+</style></head><body>\n",
+    );
+    html.push_str(&format!(
+        "<h1>Timeline: {}</h1>\n<p>{} turn(s)</p>\n",
+        escape_html(session_key),
+        turns.len()
+    ));
+
+    for (index, turn) in turns.iter().enumerate() {
+        let total = turn.total_ms.max(1) as f64;
+        html.push_str("<div class=\"turn\">\n");
+        html.push_str(&format!(
+            "<h3>Turn {} &mdash; {} &mdash; {} ms (provider {} ms, tools {} ms, queue {} ms)</h3>\n",
+            index + 1,
+            turn.started_at.to_rfc3339(),
+            turn.total_ms,
+            turn.total_for(SpanKind::Provider),
+            turn.total_for(SpanKind::Tool),
+            turn.total_for(SpanKind::QueueWait),
+        ));
+        for span in &turn.spans {
+            let left = span.start_ms as f64 / total * 100.0;
+            let width = span.duration_ms as f64 / total * 100.0;
+            let class = if span.ok {
+                span.kind.css_class()
+            } else {
+                "failed"
+            };
+            html.push_str(&format!(
+                "<div class=\"row\"><div class=\"label\">{}</div>\
+<div class=\"track\"><div class=\"bar {}\" style=\"left:{:.2}%;width:{:.2}%\"></div></div>\
+<div class=\"ms\">{} ms</div></div>\n",
+                escape_html(&span.label),
+                class,
+                left.min(100.0),
+                width.min(100.0 - left.min(100.0)),
+                span.duration_ms
+            ));
+        }
+        html.push_str("</div>\n");
+    }
+
+    html.push_str("</body></html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn sample_turn(key: &str) -> TurnTimeline {
+        TurnTimeline {
+            session_key: key.to_string(),
+            started_at: Utc::now(),
+            total_ms: 1000,
+            spans: vec![
+                TimelineSpan {
+                    kind: SpanKind::QueueWait,
+                    label: "queue".into(),
+                    start_ms: 0,
+                    duration_ms: 100,
+                    ok: true,
+                },
+                TimelineSpan {
+                    kind: SpanKind::Provider,
+                    label: "anthropic".into(),
+                    start_ms: 100,
+                    duration_ms: 600,
+                    ok: true,
+                },
+                TimelineSpan {
+                    kind: SpanKind::Tool,
+                    label: "web_fetch".into(),
+                    start_ms: 700,
+                    duration_ms: 300,
+                    ok: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_recorder_includes_queue_wait_and_spans() {
+        let recorder = TimelineRecorder::start("cli:test", 50);
+        let started = Instant::now();
+        recorder.record(SpanKind::Provider, "mock", started, true);
+        let turn = recorder.finish();
+
+        assert_eq!(turn.session_key, "cli:test");
+        assert_eq!(turn.spans.len(), 2);
+        assert_eq!(turn.spans[0].kind, SpanKind::QueueWait);
+        assert_eq!(turn.spans[0].duration_ms, 50);
+        assert_eq!(turn.spans[1].kind, SpanKind::Provider);
+        assert!(turn.spans[1].start_ms >= 50);
+        assert!(turn.total_ms >= 50);
+    }
+
+    #[test]
+    fn test_recorder_skips_zero_queue_wait() {
+        let recorder = TimelineRecorder::start("cli:test", 0);
+        assert!(recorder.finish().spans.is_empty());
+    }
+
+    #[test]
+    fn test_total_for_kind() {
+        let turn = sample_turn("k");
+        assert_eq!(turn.total_for(SpanKind::Provider), 600);
+        assert_eq!(turn.total_for(SpanKind::Tool), 300);
+        assert_eq!(turn.total_for(SpanKind::QueueWait), 100);
+    }
+
+    #[test]
+    fn test_queue_wait_from_metadata() {
+        let mut meta = HashMap::new();
+        assert_eq!(queue_wait_ms(&meta), 0);
+
+        meta.insert(ENQUEUED_AT_METADATA_KEY.to_string(), "garbage".to_string());
+        assert_eq!(queue_wait_ms(&meta), 0);
+
+        let past = Utc::now().timestamp_millis() - 2_000;
+        meta.insert(ENQUEUED_AT_METADATA_KEY.to_string(), past.to_string());
+        assert!(queue_wait_ms(&meta) >= 2_000);
+
+        let future = Utc::now().timestamp_millis() + 60_000;
+        meta.insert(ENQUEUED_AT_METADATA_KEY.to_string(), future.to_string());
+        assert_eq!(queue_wait_ms(&meta), 0);
+    }
+
+    #[tokio::test]
+    async fn test_store_append_and_load_roundtrip() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = TimelineStore::with_path(temp.path().join("timelines")).unwrap();
+
+        store.append(&sample_turn("telegram:42")).await.unwrap();
+        store.append(&sample_turn("telegram:42")).await.unwrap();
+        store.append(&sample_turn("other:1")).await.unwrap();
+
+        let turns = store.load("telegram:42").unwrap();
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].spans.len(), 3);
+        assert!(store.load("missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_store_for_sessions_dir_is_sibling() {
+        let temp = tempfile::tempdir().unwrap();
+        let sessions = temp.path().join("sessions");
+        let _store = TimelineStore::for_sessions_dir(&sessions).unwrap();
+        assert!(temp.path().join("timelines").is_dir());
+    }
+
+    #[test]
+    fn test_render_html_contains_bars_and_escapes() {
+        let html = render_html("cli:<script>", &[sample_turn("cli:<script>")]);
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("class=\"bar provider\""));
+        assert!(html.contains("class=\"bar failed\""));
+        assert!(html.contains("web_fetch"));
+        assert!(html.contains("Turn 1"));
+    }
+}
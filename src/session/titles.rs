@@ -0,0 +1,283 @@
+//! Generated session titles and topic tags.
+//!
+//! When `session.titles.enabled` is set, the agent loop hands a session to a
+//! [`SessionTitler`] once it has `after_turns` user messages and no title
+//! yet. The titler asks a (preferably cheap) model for a short title and a
+//! few topic tags and stores them on the session, where `zeptoclaw history`
+//! shows and searches them instead of raw session keys.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::error::{Result, ZeptoError};
+use crate::providers::{ChatOptions, LLMProvider};
+
+use super::{Message, Role, Session, SessionManager};
+
+/// Longest title kept, in characters.
+const MAX_TITLE_CHARS: usize = 60;
+
+/// Longest slice of each message sent to the titler.
+const MAX_MESSAGE_CHARS: usize = 500;
+
+/// Most messages sent to the titler.
+const MAX_MESSAGES: usize = 10;
+
+const TITLE_PROMPT: &str = "You name conversations. \
+Return ONLY a JSON object with fields: \
+\"title\" (at most 6 words, no quotes or trailing punctuation, in the conversation's language) and \
+\"tags\" (1-5 short lowercase topic tags, e.g. \"rust\", \"travel\", \"tax-return\").";
+
+/// Configuration for generated session titles (`session.titles`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct TitlesConfig {
+    /// Generate titles and tags for sessions.
+    pub enabled: bool,
+    /// Model for titling; the agent's model when unset.
+    pub model: Option<String>,
+    /// User messages a session needs before it is titled.
+    pub after_turns: usize,
+    /// Most tags kept per session.
+    pub max_tags: usize,
+    /// Timeout for the titling call (seconds).
+    pub timeout_secs: u64,
+}
+
+impl Default for TitlesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: None,
+            after_turns: 3,
+            max_tags: 5,
+            timeout_secs: 20,
+        }
+    }
+}
+
+/// Whether `session` is ready for a title: none yet and at least
+/// `after_turns` user messages.
+pub fn needs_title(config: &TitlesConfig, session: &Session) -> bool {
+    session.title.is_none()
+        && session
+            .messages
+            .iter()
+            .filter(|m| m.role == Role::User)
+            .count()
+            >= config.after_turns.max(1)
+}
+
+/// Title and tags from the model's reply. Tolerates prose or code fences
+/// around the JSON object; `None` when there is no usable title.
+pub fn parse_title(text: &str, max_tags: usize) -> Option<(String, Vec<String>)> {
+    let (start, end) = (text.find('{')?, text.rfind('}')?);
+    if end < start {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(&text[start..=end]).ok()?;
+
+    let raw = value.get("title")?.as_str()?;
+    let raw = raw
+        .trim()
+        .trim_matches(|c: char| c == '"' || c == '\'')
+        .trim_end_matches(['.', '!', '?'])
+        .trim();
+    if raw.is_empty() {
+        return None;
+    }
+    let title = match raw.char_indices().nth(MAX_TITLE_CHARS) {
+        Some((idx, _)) => format!("{}…", raw[..idx].trim_end()),
+        None => raw.to_string(),
+    };
+
+    let mut tags: Vec<String> = Vec::new();
+    let raw_tags = value.get("tags").and_then(|t| t.as_array());
+    for tag in raw_tags.into_iter().flatten().filter_map(|t| t.as_str()) {
+        let tag = tag
+            .trim()
+            .trim_start_matches('#')
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("-");
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+        if tags.len() >= max_tags {
+            break;
+        }
+    }
+    Some((title, tags))
+}
+
+fn clip(text: &str) -> String {
+    if text.chars().count() <= MAX_MESSAGE_CHARS {
+        text.to_string()
+    } else {
+        let clipped: String = text.chars().take(MAX_MESSAGE_CHARS).collect();
+        format!("{}…", clipped)
+    }
+}
+
+/// Generates titles for sessions.
+pub struct SessionTitler {
+    provider: Arc<dyn LLMProvider>,
+    model: String,
+    config: TitlesConfig,
+}
+
+impl SessionTitler {
+    /// Titler using `config.model`, else `default_model`.
+    pub fn new(provider: Arc<dyn LLMProvider>, default_model: &str, config: TitlesConfig) -> Self {
+        Self {
+            provider,
+            model: config
+                .model
+                .clone()
+                .unwrap_or_else(|| default_model.to_string()),
+            config,
+        }
+    }
+
+    /// Ask the model for a title and tags for the opening of a conversation.
+    pub async fn generate(&self, messages: &[Message]) -> Result<(String, Vec<String>)> {
+        let transcript = messages
+            .iter()
+            .filter(|m| matches!(m.role, Role::User | Role::Assistant))
+            .filter(|m| !m.content.trim().is_empty())
+            .take(MAX_MESSAGES)
+            .map(|m| {
+                let role = if m.role == Role::User {
+                    "User"
+                } else {
+                    "Assistant"
+                };
+                format!("{}: {}", role, clip(m.content.trim()))
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let messages = vec![Message::system(TITLE_PROMPT), Message::user(&transcript)];
+        let options = ChatOptions::new()
+            .with_max_tokens(100)
+            .with_temperature(0.0);
+        let response = tokio::time::timeout(
+            Duration::from_secs(self.config.timeout_secs.max(1)),
+            self.provider
+                .chat(messages, vec![], Some(self.model.as_str()), options),
+        )
+        .await
+        .map_err(|_| ZeptoError::Session("Session titling timed out".into()))??;
+        parse_title(&response.content, self.config.max_tags)
+            .ok_or_else(|| ZeptoError::Session("Titler returned no usable title".into()))
+    }
+
+    /// Title `session` in the background and store the result.
+    pub fn spawn(self, sessions: Arc<SessionManager>, session: Session) {
+        tokio::spawn(async move {
+            match self.generate(&session.messages).await {
+                Ok((title, tags)) => {
+                    debug!(session = %session.key, title = %title, "session titled");
+                    if let Err(e) = sessions.set_title(&session.key, &title, tags).await {
+                        warn!(error = %e, "failed to save session title");
+                    }
+                }
+                Err(e) => warn!(error = %e, "session titling failed"),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{LLMResponse, ToolDefinition};
+    use async_trait::async_trait;
+
+    struct CannedProvider(&'static str);
+
+    #[async_trait]
+    impl LLMProvider for CannedProvider {
+        async fn chat(
+            &self,
+            _messages: Vec<Message>,
+            _tools: Vec<ToolDefinition>,
+            _model: Option<&str>,
+            _options: ChatOptions,
+        ) -> Result<LLMResponse> {
+            Ok(LLMResponse::text(self.0))
+        }
+
+        fn default_model(&self) -> &str {
+            "canned"
+        }
+
+        fn name(&self) -> &str {
+            "canned"
+        }
+    }
+
+    #[test]
+    fn test_parse_title() {
+        let reply = "```json\n{\"title\": \"\\\"Planning a Lisbon trip.\\\"\", \
+                     \"tags\": [\"Travel\", \"#lisbon\", \"travel\", \"day trips\", \"a\", \"b\", \"c\"]}\n```";
+        let (title, tags) = parse_title(reply, 5).unwrap();
+        assert_eq!(title, "Planning a Lisbon trip");
+        assert_eq!(tags, vec!["travel", "lisbon", "day-trips", "a", "b"]);
+
+        let long = format!("{{\"title\": \"{}\"}}", "word ".repeat(30));
+        let (title, tags) = parse_title(&long, 5).unwrap();
+        assert!(title.ends_with('…'));
+        assert!(title.chars().count() <= MAX_TITLE_CHARS + 1);
+        assert!(tags.is_empty());
+
+        assert!(parse_title("no json here", 5).is_none());
+        assert!(parse_title("{\"title\": \"  \"}", 5).is_none());
+    }
+
+    #[test]
+    fn test_needs_title() {
+        let config = TitlesConfig {
+            after_turns: 2,
+            ..Default::default()
+        };
+        let mut session = Session::new("cli:1");
+        session.add_message(Message::user("hi"));
+        session.add_message(Message::assistant("hello"));
+        assert!(!needs_title(&config, &session));
+        session.add_message(Message::user("plan my trip"));
+        assert!(needs_title(&config, &session));
+        session.title = Some("Trip".to_string());
+        assert!(!needs_title(&config, &session));
+    }
+
+    #[tokio::test]
+    async fn test_titler_stores_title_and_save_keeps_it() {
+        let sessions = Arc::new(SessionManager::new_memory());
+        let mut session = sessions.get_or_create("cli:1").await.unwrap();
+        session.add_message(Message::user("How do I file my taxes in Portugal?"));
+        sessions.save(&session).await.unwrap();
+
+        let titler = SessionTitler::new(
+            Arc::new(CannedProvider(
+                "{\"title\": \"Portuguese tax filing\", \"tags\": [\"taxes\", \"portugal\"]}",
+            )),
+            "model",
+            TitlesConfig::default(),
+        );
+        let (title, tags) = titler.generate(&session.messages).await.unwrap();
+        sessions.set_title("cli:1", &title, tags).await.unwrap();
+
+        // A copy loaded before titling must not clear the title on save.
+        session.add_message(Message::assistant("Use the e-fatura portal."));
+        sessions.save(&session).await.unwrap();
+        let stored = sessions.get("cli:1").await.unwrap().unwrap();
+        assert_eq!(stored.title.as_deref(), Some("Portuguese tax filing"));
+        assert_eq!(stored.tags, vec!["taxes", "portugal"]);
+        assert_eq!(stored.messages.len(), 2);
+    }
+}
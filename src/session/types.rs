@@ -22,6 +22,18 @@ pub struct Session {
     pub created_at: DateTime<Utc>,
     /// When this session was last modified
     pub updated_at: DateTime<Utc>,
+    /// Generated title (`session.titles`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Generated topic tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Workspace the session runs in (`workspaces`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    /// Notes pinned with `/pin`, added to the system prompt
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pins: Vec<String>,
 }
 
 impl Session {
@@ -45,6 +57,10 @@ impl Session {
             summary: None,
             created_at: now,
             updated_at: now,
+            title: None,
+            tags: Vec::new(),
+            workspace: None,
+            pins: Vec::new(),
         }
     }
 
@@ -163,6 +179,10 @@ pub struct Message {
     /// ID of the tool call this message is responding to (for tool results)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
+    /// The text as the user sent it, when `content` is a translation
+    /// (`channels.auto_translate`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original: Option<String>,
 }
 
 impl Message {
@@ -187,6 +207,7 @@ impl Message {
             }],
             tool_calls: None,
             tool_call_id: None,
+            original: None,
         }
     }
 
@@ -211,6 +232,7 @@ impl Message {
             }],
             tool_calls: None,
             tool_call_id: None,
+            original: None,
         }
     }
 
@@ -237,6 +259,7 @@ impl Message {
             }],
             tool_calls: None,
             tool_call_id: None,
+            original: None,
         }
     }
 
@@ -265,6 +288,7 @@ impl Message {
             }],
             tool_calls: None,
             tool_call_id: Some(tool_call_id.to_string()),
+            original: None,
         }
     }
 
@@ -291,6 +315,7 @@ impl Message {
             }],
             tool_calls: Some(tool_calls),
             tool_call_id: None,
+            original: None,
         }
     }
 
@@ -306,6 +331,7 @@ impl Message {
             content_parts: parts,
             tool_calls: None,
             tool_call_id: None,
+            original: None,
         }
     }
 
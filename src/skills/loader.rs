@@ -27,10 +27,7 @@ impl SkillsLoader {
 
     /// Create loader with default directories.
     pub fn with_defaults() -> Self {
-        let workspace = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zeptoclaw")
-            .join("skills");
+        let workspace = crate::config::Config::data_dir().join("skills");
         Self::new(workspace, None)
     }
 
@@ -225,6 +222,11 @@ impl SkillsLoader {
         (SkillMetadata::default(), content.to_string())
     }
 
+    /// Parsed ZeptoClaw metadata (requirements, install options) for a skill.
+    pub fn zepto_metadata(&self, skill: &Skill) -> ZeptoMetadata {
+        self.get_zeptometa(skill)
+    }
+
     fn get_zeptometa(&self, skill: &Skill) -> ZeptoMetadata {
         skill
             .metadata
@@ -299,6 +301,16 @@ fn escape_xml(input: &str) -> String {
         .replace('>', "&gt;")
 }
 
+/// Read and parse the frontmatter of a `SKILL.md` file.
+///
+/// Returns `None` if the file cannot be read or has no frontmatter block.
+pub fn read_skill_metadata(skill_md: &Path) -> Option<SkillMetadata> {
+    let raw = std::fs::read_to_string(skill_md).ok()?;
+    let re = Regex::new(r"(?s)^---\n(.*?)\n---\n?").ok()?;
+    let frontmatter = re.captures(&raw)?.get(1)?;
+    Some(parse_frontmatter_metadata(frontmatter.as_str()))
+}
+
 fn parse_frontmatter_metadata(frontmatter: &str) -> SkillMetadata {
     match serde_yaml::from_str::<SkillMetadata>(frontmatter) {
         Ok(meta) => meta,
@@ -320,7 +332,7 @@ fn current_os() -> &'static str {
     }
 }
 
-fn binary_in_path(bin: &str) -> bool {
+pub(crate) fn binary_in_path(bin: &str) -> bool {
     if bin.trim().is_empty() {
         return false;
     }
@@ -0,0 +1,366 @@
+//! Installed-skill lockfile and semver helpers.
+//!
+//! `skills.lock.json` lives inside the skills directory and records, for each
+//! installed skill, where it came from, which version was installed, and a
+//! SHA-256 checksum of its files. `skills update` uses it to find newer
+//! releases, and reinstalling from the lockfile reproduces the same set.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{Result, ZeptoError};
+
+/// Lockfile name inside the skills directory.
+pub const LOCKFILE_NAME: &str = "skills.lock.json";
+
+/// Current lockfile format version.
+const LOCKFILE_VERSION: u32 = 1;
+
+/// A parsed semantic version (`MAJOR.MINOR.PATCH[-PRE]`).
+///
+/// Missing minor/patch components default to 0 and a leading `v` is
+/// accepted, so `v1.2` parses as `1.2.0`. Build metadata (`+...`) is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemVer {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Option<String>,
+}
+
+impl SemVer {
+    /// Parse a version string. Returns `None` if it is not semver-like.
+    pub fn parse(input: &str) -> Option<Self> {
+        let s = input.trim();
+        let s = s.strip_prefix('v').unwrap_or(s);
+        let s = s.split('+').next().unwrap_or(s);
+        let (core, pre) = match s.split_once('-') {
+            Some((core, pre)) if !pre.is_empty() => (core, Some(pre.to_string())),
+            Some(_) => return None,
+            None => (s, None),
+        };
+        let mut parts = core.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map(str::parse).transpose().ok()?.unwrap_or(0);
+        let patch = parts.next().map(str::parse).transpose().ok()?.unwrap_or(0);
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    /// Whether `other` is a newer version within the same major release
+    /// (or any newer version when `allow_major` is set).
+    pub fn is_upgrade_to(&self, other: &SemVer, allow_major: bool) -> bool {
+        other > self && (allow_major || other.major == self.major)
+    }
+}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                // A release sorts after its pre-releases.
+                (None, None) => std::cmp::Ordering::Equal,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+    }
+}
+
+impl fmt::Display for SemVer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where an installed skill came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LockSource {
+    /// A GitHub repository (`owner/repo`), optionally a subdirectory of it.
+    Github {
+        repo: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
+    /// A ClawHub registry slug.
+    Clawhub { slug: String },
+    /// Created or copied locally; never updated automatically.
+    Local,
+}
+
+impl fmt::Display for LockSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockSource::Github { repo, path: None } => write!(f, "github:{}", repo),
+            LockSource::Github {
+                repo,
+                path: Some(path),
+            } => write!(f, "github:{}/{}", repo, path),
+            LockSource::Clawhub { slug } => write!(f, "clawhub:{}", slug),
+            LockSource::Local => write!(f, "local"),
+        }
+    }
+}
+
+/// Lock entry for one installed skill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedSkill {
+    /// Installed version from SKILL.md frontmatter, if declared.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Install source.
+    pub source: LockSource,
+    /// SHA-256 of the installed skill files (see [`checksum_dir`]).
+    pub checksum: String,
+    /// When the skill was installed or last updated.
+    pub installed_at: DateTime<Utc>,
+}
+
+/// On-disk lockfile of installed skills.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillLockfile {
+    /// Format version.
+    pub lockfile_version: u32,
+    /// Locked skills keyed by install directory name.
+    #[serde(default)]
+    pub skills: BTreeMap<String, LockedSkill>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl SkillLockfile {
+    /// Load the lockfile from `skills_dir`, or start an empty one.
+    pub fn load(skills_dir: &Path) -> Result<Self> {
+        let path = skills_dir.join(LOCKFILE_NAME);
+        if !path.is_file() {
+            return Ok(Self {
+                lockfile_version: LOCKFILE_VERSION,
+                skills: BTreeMap::new(),
+                path,
+            });
+        }
+        let raw = std::fs::read_to_string(&path)?;
+        let mut lock: SkillLockfile = serde_json::from_str(&raw)?;
+        if lock.lockfile_version > LOCKFILE_VERSION {
+            return Err(ZeptoError::Config(format!(
+                "{} has unsupported lockfile_version {} (max {})",
+                path.display(),
+                lock.lockfile_version,
+                LOCKFILE_VERSION
+            )));
+        }
+        lock.path = path;
+        Ok(lock)
+    }
+
+    /// Write the lockfile back to disk.
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(&self.path, json)?;
+        Ok(())
+    }
+
+    /// Path to the lockfile.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record an installed skill from its directory, computing version and
+    /// checksum from disk.
+    pub fn record(&mut self, name: &str, skill_dir: &Path, source: LockSource) -> Result<()> {
+        let entry = LockedSkill {
+            version: read_skill_version(skill_dir),
+            source,
+            checksum: checksum_dir(skill_dir)?,
+            installed_at: Utc::now(),
+        };
+        self.skills.insert(name.to_string(), entry);
+        Ok(())
+    }
+
+    /// Look up a locked skill.
+    pub fn get(&self, name: &str) -> Option<&LockedSkill> {
+        self.skills.get(name)
+    }
+
+    /// Remove a skill from the lockfile.
+    pub fn remove(&mut self, name: &str) -> Option<LockedSkill> {
+        self.skills.remove(name)
+    }
+}
+
+/// Read the `version` field from a skill directory's SKILL.md frontmatter.
+pub fn read_skill_version(skill_dir: &Path) -> Option<String> {
+    crate::skills::read_skill_metadata(&skill_dir.join("SKILL.md"))
+        .and_then(|meta| meta.version)
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// SHA-256 over every file in `dir` (sorted relative paths and contents).
+///
+/// `.git` directories and the lockfile itself are excluded, so the checksum
+/// is stable across clones and unaffected by lockfile updates.
+pub fn checksum_dir(dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for rel in &files {
+        let bytes = std::fs::read(dir.join(rel))?;
+        hasher.update(rel.replace('\\', "/").as_bytes());
+        hasher.update([0u8]);
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(&bytes);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Collect file paths under `dir`, relative to `root`.
+pub(crate) fn collect_files(root: &Path, dir: &Path, out: &mut Vec<String>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+        if name == ".git" || name == LOCKFILE_NAME {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &path, out)?;
+        } else if let Ok(rel) = path.strip_prefix(root) {
+            out.push(rel.to_string_lossy().to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(dir: &Path, version: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            format!(
+                "---\nname: demo\nversion: {}\ndescription: Demo\n---\n\n# Demo\n",
+                version
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_semver_parse_and_order() {
+        let v = SemVer::parse("v1.2").unwrap();
+        assert_eq!(v.to_string(), "1.2.0");
+        assert!(SemVer::parse("1.2.3.4").is_none());
+        assert!(SemVer::parse("latest").is_none());
+        assert!(SemVer::parse("1.0.0-").is_none());
+
+        let a = SemVer::parse("1.2.3").unwrap();
+        let b = SemVer::parse("1.10.0").unwrap();
+        let pre = SemVer::parse("1.10.0-beta.1").unwrap();
+        assert!(a < b);
+        assert!(pre < b);
+        assert!(a < pre);
+        assert_eq!(
+            SemVer::parse("2.0.0+build.5").unwrap(),
+            SemVer::parse("2.0.0").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_semver_upgrade_respects_major() {
+        let cur = SemVer::parse("1.4.0").unwrap();
+        assert!(cur.is_upgrade_to(&SemVer::parse("1.5.0").unwrap(), false));
+        assert!(!cur.is_upgrade_to(&SemVer::parse("2.0.0").unwrap(), false));
+        assert!(cur.is_upgrade_to(&SemVer::parse("2.0.0").unwrap(), true));
+        assert!(!cur.is_upgrade_to(&SemVer::parse("1.4.0").unwrap(), true));
+        assert!(!cur.is_upgrade_to(&SemVer::parse("1.3.9").unwrap(), true));
+    }
+
+    #[test]
+    fn test_checksum_stable_and_sensitive() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("demo");
+        write_skill(&dir, "1.0.0");
+        let first = checksum_dir(&dir).unwrap();
+        assert_eq!(first.len(), 64);
+
+        // Lockfile and .git are ignored
+        std::fs::write(dir.join(LOCKFILE_NAME), "{}").unwrap();
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::write(dir.join(".git/HEAD"), "ref").unwrap();
+        assert_eq!(checksum_dir(&dir).unwrap(), first);
+
+        std::fs::write(dir.join("extra.txt"), "x").unwrap();
+        assert_ne!(checksum_dir(&dir).unwrap(), first);
+    }
+
+    #[test]
+    fn test_lockfile_record_save_load() {
+        let temp = tempfile::tempdir().unwrap();
+        let skills_dir = temp.path();
+        let dir = skills_dir.join("demo");
+        write_skill(&dir, "1.2.0");
+
+        let mut lock = SkillLockfile::load(skills_dir).unwrap();
+        assert!(lock.skills.is_empty());
+        lock.record(
+            "demo",
+            &dir,
+            LockSource::Github {
+                repo: "owner/skills".into(),
+                path: Some("demo".into()),
+            },
+        )
+        .unwrap();
+        lock.save().unwrap();
+
+        let loaded = SkillLockfile::load(skills_dir).unwrap();
+        let entry = loaded.get("demo").unwrap();
+        assert_eq!(entry.version.as_deref(), Some("1.2.0"));
+        assert_eq!(entry.source.to_string(), "github:owner/skills/demo");
+        assert_eq!(entry.checksum, checksum_dir(&dir).unwrap());
+    }
+
+    #[test]
+    fn test_lockfile_rejects_future_version() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join(LOCKFILE_NAME),
+            r#"{"lockfile_version": 99, "skills": {}}"#,
+        )
+        .unwrap();
+        assert!(SkillLockfile::load(temp.path()).is_err());
+    }
+}
@@ -3,9 +3,13 @@
 mod loader;
 mod types;
 
-pub use loader::SkillsLoader;
+pub use loader::{read_skill_metadata, SkillsLoader};
 pub use types::{
     EnvSpec, InstallOption, Skill, SkillInfo, SkillMetadata, SkillRequirements, ZeptoMetadata,
 };
 pub mod github_source;
+pub mod lockfile;
+pub mod package;
+pub mod provision;
 pub mod registry;
+pub mod watcher;
@@ -0,0 +1,210 @@
+//! Skill packaging for publishing.
+//!
+//! `skills publish` packages a skill directory into `<name>-<version>.zip`
+//! containing the skill files plus a `manifest.json` listing every file with
+//! its SHA-256. A `<archive>.sha256` sidecar holds the archive checksum so
+//! consumers can verify downloads before extracting.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{Result, ZeptoError};
+use crate::skills::lockfile::{checksum_dir, collect_files, SemVer};
+
+/// Manifest file name inside a packaged skill archive.
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+/// One file entry in a package manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackagedFile {
+    /// Path relative to the skill root (forward slashes).
+    pub path: String,
+    /// File size in bytes.
+    pub size: u64,
+    /// SHA-256 of the file contents.
+    pub sha256: String,
+}
+
+/// Manifest embedded in a packaged skill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageManifest {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Directory checksum, identical to the lockfile checksum after install.
+    pub checksum: String,
+    pub files: Vec<PackagedFile>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Result of packaging a skill.
+#[derive(Debug, Clone)]
+pub struct PackagedSkill {
+    pub manifest: PackageManifest,
+    pub archive_path: PathBuf,
+    /// SHA-256 of the archive file.
+    pub archive_sha256: String,
+}
+
+/// Build a manifest for the skill in `skill_dir`.
+///
+/// Requires SKILL.md frontmatter with a `name` and a semver `version`.
+pub fn build_manifest(skill_dir: &Path) -> Result<PackageManifest> {
+    let skill_md = skill_dir.join("SKILL.md");
+    let meta = crate::skills::read_skill_metadata(&skill_md).ok_or_else(|| {
+        ZeptoError::Config(format!(
+            "{} is missing or has no frontmatter",
+            skill_md.display()
+        ))
+    })?;
+
+    let name = meta.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ZeptoError::Config(
+            "SKILL.md frontmatter must declare a name to publish".into(),
+        ));
+    }
+    let version = meta.version.as_deref().unwrap_or("").trim();
+    let parsed = SemVer::parse(version).ok_or_else(|| {
+        ZeptoError::Config(format!(
+            "SKILL.md version {:?} is not a valid semver (e.g. 1.0.0)",
+            version
+        ))
+    })?;
+
+    let mut rel_paths = Vec::new();
+    collect_files(skill_dir, skill_dir, &mut rel_paths)?;
+    rel_paths.sort();
+    let mut files = Vec::with_capacity(rel_paths.len());
+    for rel in rel_paths {
+        let bytes = std::fs::read(skill_dir.join(&rel))?;
+        files.push(PackagedFile {
+            path: rel.replace('\\', "/"),
+            size: bytes.len() as u64,
+            sha256: hex::encode(Sha256::digest(&bytes)),
+        });
+    }
+
+    Ok(PackageManifest {
+        name,
+        version: parsed.to_string(),
+        description: meta.description,
+        author: meta.author,
+        license: meta.license,
+        tags: meta.tags,
+        checksum: checksum_dir(skill_dir)?,
+        files,
+        created_at: Utc::now(),
+    })
+}
+
+/// Package `skill_dir` into `out_dir/<name>-<version>.zip` and write the
+/// `.sha256` sidecar next to it.
+pub fn package_skill(skill_dir: &Path, out_dir: &Path) -> Result<PackagedSkill> {
+    let manifest = build_manifest(skill_dir)?;
+    std::fs::create_dir_all(out_dir)?;
+    let archive_path = out_dir.join(format!("{}-{}.zip", manifest.name, manifest.version));
+
+    let file = std::fs::File::create(&archive_path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let zip_err = |e: zip::result::ZipError| ZeptoError::Tool(format!("zip error: {}", e));
+
+    for entry in &manifest.files {
+        zip.start_file(entry.path.as_str(), options)
+            .map_err(zip_err)?;
+        zip.write_all(&std::fs::read(skill_dir.join(&entry.path))?)?;
+    }
+    zip.start_file(MANIFEST_NAME, options).map_err(zip_err)?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+    zip.finish().map_err(zip_err)?;
+
+    let archive_sha256 = hex::encode(Sha256::digest(std::fs::read(&archive_path)?));
+    let file_name = archive_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    std::fs::write(
+        archive_path.with_extension("zip.sha256"),
+        format!("{}  {}\n", archive_sha256, file_name),
+    )?;
+
+    Ok(PackagedSkill {
+        manifest,
+        archive_path,
+        archive_sha256,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(dir: &Path, version: &str) {
+        std::fs::create_dir_all(dir.join("scripts")).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            format!(
+                "---\nname: weather\nversion: {}\ndescription: Weather lookups\nauthor: Test\n---\n\n# Weather\n",
+                version
+            ),
+        )
+        .unwrap();
+        std::fs::write(dir.join("scripts/run.sh"), "echo hi\n").unwrap();
+    }
+
+    #[test]
+    fn test_build_manifest_lists_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("weather");
+        write_skill(&dir, "v1.2");
+        let manifest = build_manifest(&dir).unwrap();
+        assert_eq!(manifest.name, "weather");
+        assert_eq!(manifest.version, "1.2.0");
+        assert_eq!(manifest.author.as_deref(), Some("Test"));
+        let paths: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["SKILL.md", "scripts/run.sh"]);
+        assert_eq!(manifest.checksum, checksum_dir(&dir).unwrap());
+    }
+
+    #[test]
+    fn test_build_manifest_requires_semver() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("weather");
+        write_skill(&dir, "latest");
+        let err = build_manifest(&dir).unwrap_err().to_string();
+        assert!(err.contains("semver"));
+    }
+
+    #[test]
+    fn test_package_skill_writes_archive_and_sidecar() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("weather");
+        write_skill(&dir, "1.0.0");
+        let out = temp.path().join("dist");
+
+        let packaged = package_skill(&dir, &out).unwrap();
+        assert_eq!(packaged.archive_path, out.join("weather-1.0.0.zip"));
+        let sidecar = std::fs::read_to_string(out.join("weather-1.0.0.zip.sha256")).unwrap();
+        assert!(sidecar.starts_with(&packaged.archive_sha256));
+
+        let file = std::fs::File::open(&packaged.archive_path).unwrap();
+        let mut archive = zip::ZipArchive::new(file).unwrap();
+        let mut names: Vec<String> = (0..archive.len())
+            .map(|i| archive.by_index(i).unwrap().name().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["SKILL.md", MANIFEST_NAME, "scripts/run.sh"]);
+    }
+}
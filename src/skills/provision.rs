@@ -0,0 +1,388 @@
+//! Skill requirement provisioning.
+//!
+//! Turns a skill's declared requirements (`metadata.zeptoclaw.requires`) and
+//! install options (`metadata.zeptoclaw.install`) into concrete install
+//! commands for a target: the host package manager, or a Docker runtime
+//! image (emitted as a derived `Dockerfile`). Plans are verified afterwards
+//! with `command -v`, so a skill only counts as ready when its binaries are
+//! actually present.
+
+use std::fmt;
+
+use crate::skills::{InstallOption, ZeptoMetadata};
+
+/// Package manager used to install a requirement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Apk,
+    Apt,
+    Brew,
+    Cargo,
+    Npm,
+    Pip,
+    Go,
+}
+
+impl PackageManager {
+    /// Map an install-option `kind` to a package manager.
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        match kind.trim().to_ascii_lowercase().as_str() {
+            "apk" => Some(Self::Apk),
+            "apt" | "apt-get" => Some(Self::Apt),
+            "brew" => Some(Self::Brew),
+            "cargo" => Some(Self::Cargo),
+            "npm" | "node" => Some(Self::Npm),
+            "pip" | "pip3" | "uv" => Some(Self::Pip),
+            "go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a system package manager (vs. a language toolchain).
+    pub fn is_system(self) -> bool {
+        matches!(self, Self::Apk | Self::Apt | Self::Brew)
+    }
+
+    /// Shell command that installs `package`.
+    pub fn install_command(self, package: &str) -> String {
+        match self {
+            Self::Apk => format!("apk add --no-cache {}", package),
+            Self::Apt => format!(
+                "apt-get update && apt-get install -y --no-install-recommends {}",
+                package
+            ),
+            Self::Brew => format!("brew install {}", package),
+            Self::Cargo => format!("cargo install {}", package),
+            Self::Npm => format!("npm install -g {}", package),
+            Self::Pip => format!("pip install {}", package),
+            Self::Go => format!("go install {}", package),
+        }
+    }
+
+    /// Detect the host's system package manager.
+    pub fn detect_host() -> Option<Self> {
+        let bin_present = binary_available;
+        if cfg!(target_os = "macos") && bin_present("brew") {
+            Some(Self::Brew)
+        } else if bin_present("apk") {
+            Some(Self::Apk)
+        } else if bin_present("apt-get") {
+            Some(Self::Apt)
+        } else if bin_present("brew") {
+            Some(Self::Brew)
+        } else {
+            None
+        }
+    }
+
+    /// Guess the system package manager of a Docker image from its name.
+    pub fn for_docker_image(image: &str) -> Self {
+        if image.to_ascii_lowercase().contains("alpine") {
+            Self::Apk
+        } else {
+            Self::Apt
+        }
+    }
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Apk => "apk",
+            Self::Apt => "apt",
+            Self::Brew => "brew",
+            Self::Cargo => "cargo",
+            Self::Npm => "npm",
+            Self::Pip => "pip",
+            Self::Go => "go",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One install step resolving a missing binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvisionStep {
+    /// Binary this step provides.
+    pub bin: String,
+    pub manager: PackageManager,
+    pub package: String,
+    /// `true` when no install option matched and the package name was
+    /// guessed from the binary name.
+    pub guessed: bool,
+}
+
+impl ProvisionStep {
+    /// Shell command for this step.
+    pub fn command(&self) -> String {
+        self.manager.install_command(&self.package)
+    }
+}
+
+/// Install plan for one skill.
+#[derive(Debug, Clone, Default)]
+pub struct ProvisionPlan {
+    pub skill: String,
+    pub steps: Vec<ProvisionStep>,
+    /// Missing binaries with no way to install them on this target.
+    pub unresolved: Vec<String>,
+    /// Required environment variables that are not set (never auto-provisioned).
+    pub missing_env: Vec<String>,
+}
+
+impl ProvisionPlan {
+    /// Whether nothing needs installing.
+    pub fn is_satisfied(&self) -> bool {
+        self.steps.is_empty() && self.unresolved.is_empty() && self.missing_env.is_empty()
+    }
+
+    /// Binaries this plan installs, for verification.
+    pub fn bins(&self) -> Vec<&str> {
+        self.steps.iter().map(|s| s.bin.as_str()).collect()
+    }
+}
+
+/// Build an install plan for a skill's missing binaries.
+///
+/// `manager` is the target's system package manager; language toolchains
+/// (cargo/npm/pip/go) from install options are used when `toolchains` is set
+/// and the toolchain binary is present per `bin_present`.
+pub fn plan_for_skill(
+    skill: &str,
+    meta: &ZeptoMetadata,
+    manager: PackageManager,
+    toolchains: bool,
+    bin_present: impl Fn(&str) -> bool,
+) -> ProvisionPlan {
+    let mut missing: Vec<String> = meta
+        .requires
+        .bins
+        .iter()
+        .filter(|b| !bin_present(b))
+        .cloned()
+        .collect();
+    if !meta.requires.any_bins.is_empty() && !meta.requires.any_bins.iter().any(|b| bin_present(b))
+    {
+        missing.push(meta.requires.any_bins[0].clone());
+    }
+
+    let mut plan = ProvisionPlan {
+        skill: skill.to_string(),
+        missing_env: meta
+            .requires
+            .env
+            .iter()
+            .filter(|e| std::env::var(e).is_err())
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+
+    for bin in missing {
+        match resolve_step(&bin, &meta.install, manager, toolchains, &bin_present) {
+            Some(step) => plan.steps.push(step),
+            None => plan.unresolved.push(bin),
+        }
+    }
+    plan
+}
+
+fn resolve_step(
+    bin: &str,
+    options: &[InstallOption],
+    manager: PackageManager,
+    toolchains: bool,
+    bin_present: &impl Fn(&str) -> bool,
+) -> Option<ProvisionStep> {
+    let provides = |opt: &&InstallOption| opt.bins.is_empty() || opt.bins.iter().any(|b| b == bin);
+    let package_of = |opt: &InstallOption| {
+        opt.package
+            .clone()
+            .or_else(|| opt.formula.clone())
+            .unwrap_or_else(|| bin.to_string())
+    };
+
+    // 1. An install option for the target's own package manager.
+    if let Some(opt) = options
+        .iter()
+        .filter(provides)
+        .find(|o| PackageManager::from_kind(&o.kind) == Some(manager))
+    {
+        return Some(ProvisionStep {
+            bin: bin.to_string(),
+            manager,
+            package: package_of(opt),
+            guessed: false,
+        });
+    }
+
+    // 2. A language toolchain that is available on the target.
+    if toolchains {
+        for opt in options.iter().filter(provides) {
+            let Some(tool) = PackageManager::from_kind(&opt.kind) else {
+                continue;
+            };
+            let tool_bin = match tool {
+                PackageManager::Pip => "pip",
+                PackageManager::Cargo => "cargo",
+                PackageManager::Npm => "npm",
+                PackageManager::Go => "go",
+                _ => continue,
+            };
+            if bin_present(tool_bin) {
+                return Some(ProvisionStep {
+                    bin: bin.to_string(),
+                    manager: tool,
+                    package: package_of(opt),
+                    guessed: false,
+                });
+            }
+        }
+    }
+
+    // 3. Fall back to a same-named system package.
+    manager.is_system().then(|| ProvisionStep {
+        bin: bin.to_string(),
+        manager,
+        package: bin.to_string(),
+        guessed: true,
+    })
+}
+
+/// Whether `bin` is on the host `PATH`.
+pub fn binary_available(bin: &str) -> bool {
+    crate::skills::loader::binary_in_path(bin)
+}
+
+/// Shell snippet that succeeds only if every binary is on `PATH`.
+pub fn verify_command(bins: &[&str]) -> String {
+    if bins.is_empty() {
+        return "true".to_string();
+    }
+    bins.iter()
+        .map(|b| format!("command -v {} >/dev/null", b))
+        .collect::<Vec<_>>()
+        .join(" && ")
+}
+
+/// Derived Dockerfile that installs all plan steps on top of `base_image`.
+pub fn dockerfile(base_image: &str, plans: &[ProvisionPlan]) -> String {
+    let mut out = format!("FROM {}\n", base_image);
+    let mut seen = std::collections::HashSet::new();
+    for step in plans.iter().flat_map(|p| p.steps.iter()) {
+        let cmd = step.command();
+        if seen.insert(cmd.clone()) {
+            out.push_str(&format!("RUN {}\n", cmd));
+        }
+    }
+    let bins: Vec<&str> = plans.iter().flat_map(|p| p.bins()).collect();
+    out.push_str(&format!("RUN {}\n", verify_command(&bins)));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::SkillRequirements;
+
+    fn opt(kind: &str, package: &str, bins: &[&str]) -> InstallOption {
+        InstallOption {
+            id: format!("{}-{}", kind, package),
+            kind: kind.to_string(),
+            formula: None,
+            package: Some(package.to_string()),
+            bins: bins.iter().map(|b| b.to_string()).collect(),
+            label: format!("Install {}", package),
+        }
+    }
+
+    fn meta(bins: &[&str], install: Vec<InstallOption>) -> ZeptoMetadata {
+        ZeptoMetadata {
+            requires: SkillRequirements {
+                bins: bins.iter().map(|b| b.to_string()).collect(),
+                ..Default::default()
+            },
+            install,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_plan_prefers_matching_manager() {
+        let m = meta(
+            &["rg", "jq"],
+            vec![
+                opt("brew", "ripgrep", &["rg"]),
+                opt("apk", "ripgrep", &["rg"]),
+            ],
+        );
+        let plan = plan_for_skill("search", &m, PackageManager::Apk, false, |_| false);
+        assert_eq!(plan.steps.len(), 2);
+        assert_eq!(plan.steps[0].command(), "apk add --no-cache ripgrep");
+        assert!(!plan.steps[0].guessed);
+        // jq has no option: guessed same-name package
+        assert_eq!(plan.steps[1].package, "jq");
+        assert!(plan.steps[1].guessed);
+    }
+
+    #[test]
+    fn test_plan_uses_available_toolchain() {
+        let m = meta(&["yt-dlp"], vec![opt("pip", "yt-dlp", &["yt-dlp"])]);
+        let plan = plan_for_skill("media", &m, PackageManager::Brew, true, |b| b == "pip");
+        assert_eq!(plan.steps[0].manager, PackageManager::Pip);
+
+        // Without toolchains the system manager fallback is used
+        let plan = plan_for_skill("media", &m, PackageManager::Brew, false, |b| b == "pip");
+        assert_eq!(plan.steps[0].manager, PackageManager::Brew);
+        assert!(plan.steps[0].guessed);
+    }
+
+    #[test]
+    fn test_plan_satisfied_when_present() {
+        let m = meta(&["git"], vec![]);
+        let plan = plan_for_skill("git", &m, PackageManager::Apt, false, |_| true);
+        assert!(plan.is_satisfied());
+    }
+
+    #[test]
+    fn test_plan_any_bins_installs_first() {
+        let mut m = meta(&[], vec![]);
+        m.requires.any_bins = vec!["python3".into(), "python".into()];
+        let plan = plan_for_skill("py", &m, PackageManager::Apt, false, |_| false);
+        assert_eq!(plan.bins(), vec!["python3"]);
+    }
+
+    #[test]
+    fn test_unresolved_without_system_manager() {
+        let m = meta(&["tool"], vec![]);
+        let plan = plan_for_skill("x", &m, PackageManager::Cargo, false, |_| false);
+        assert_eq!(plan.unresolved, vec!["tool".to_string()]);
+    }
+
+    #[test]
+    fn test_dockerfile_dedupes_and_verifies() {
+        let m = meta(&["jq"], vec![]);
+        let a = plan_for_skill("a", &m, PackageManager::Apk, false, |_| false);
+        let b = plan_for_skill("b", &m, PackageManager::Apk, false, |_| false);
+        let df = dockerfile("alpine:latest", &[a, b]);
+        assert!(df.starts_with("FROM alpine:latest\n"));
+        assert_eq!(df.matches("apk add --no-cache jq").count(), 1);
+        assert!(df.contains("command -v jq >/dev/null"));
+    }
+
+    #[test]
+    fn test_manager_helpers() {
+        assert_eq!(PackageManager::from_kind("APT"), Some(PackageManager::Apt));
+        assert_eq!(PackageManager::from_kind("uv"), Some(PackageManager::Pip));
+        assert_eq!(PackageManager::from_kind("nix"), None);
+        assert_eq!(
+            PackageManager::for_docker_image("alpine:3.20"),
+            PackageManager::Apk
+        );
+        assert_eq!(
+            PackageManager::for_docker_image("debian:bookworm-slim"),
+            PackageManager::Apt
+        );
+        assert_eq!(verify_command(&[]), "true");
+    }
+}
@@ -323,6 +323,64 @@ impl ClawHubRegistry {
         Ok(results)
     }
 
+    /// Latest published version for `slug`, if the registry lists it.
+    pub async fn latest_version(&self, slug: &str) -> crate::error::Result<Option<String>> {
+        validate_slug(slug)?;
+        let results = self.search(slug, 20).await?;
+        Ok(results
+            .into_iter()
+            .find(|r| r.slug == slug)
+            .map(|r| r.version))
+    }
+
+    /// Upload a packaged skill archive to ClawHub.
+    ///
+    /// Requires an auth token. The manifest name, version, and archive
+    /// checksum are sent as headers so the registry can verify the upload.
+    pub async fn publish(
+        &self,
+        name: &str,
+        version: &str,
+        archive_sha256: &str,
+        archive: Vec<u8>,
+    ) -> crate::error::Result<()> {
+        validate_slug(name)?;
+        let Some(token) = &self.auth_token else {
+            return Err(crate::error::ZeptoError::Unauthorized(
+                "Publishing to ClawHub requires tools.skills.clawhub.auth_token".into(),
+            ));
+        };
+
+        let url = format!("{}/api/v1/publish", self.base_url);
+        let pinned = check_ssrf(&url, &self.allowed_hosts).await?;
+        let client = build_pinned_client(pinned)?;
+
+        let resp = client
+            .post(&url)
+            .bearer_auth(token)
+            .header("Content-Type", "application/zip")
+            .header("X-Skill-Name", name)
+            .header("X-Skill-Version", version)
+            .header("X-Skill-Sha256", archive_sha256)
+            .body(archive)
+            .send()
+            .await
+            .map_err(|e| crate::error::ZeptoError::Tool(e.to_string()))?;
+
+        check_redirect_ssrf(resp.url(), &self.allowed_hosts)?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(crate::error::ZeptoError::Tool(format!(
+                "ClawHub publish failed: {} {}",
+                status,
+                body.trim()
+            )));
+        }
+        Ok(())
+    }
+
     /// Download a skill archive from ClawHub and extract it into `skills_dir`.
     ///
     /// Returns the path to the installed skill directory on success.
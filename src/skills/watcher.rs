@@ -0,0 +1,190 @@
+//! File-mtime polling watcher for hot-reloading skills.
+//!
+//! Polls the skills workspace directory for added, edited, or removed
+//! `SKILL.md` files and publishes a `system` inbound message carrying
+//! [`SKILLS_RELOAD_METADATA_KEY`]. The agent loop treats that message as a
+//! reload notification: it rebuilds the skills prompt in place instead of
+//! running an LLM turn.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+use crate::bus::{InboundMessage, MessageBus};
+
+/// Metadata key marking an inbound message as a skills reload notification.
+/// The value is a comma-separated list of changed skill names.
+pub const SKILLS_RELOAD_METADATA_KEY: &str = "skills_reload";
+
+/// Channel used for skills reload notifications.
+pub const SKILLS_RELOAD_CHANNEL: &str = "system";
+
+/// Whether `msg` is a skills reload notification.
+pub fn is_reload_notification(msg: &InboundMessage) -> bool {
+    msg.channel == SKILLS_RELOAD_CHANNEL && msg.metadata.contains_key(SKILLS_RELOAD_METADATA_KEY)
+}
+
+/// Build a reload notification for the changed skill names.
+pub fn reload_notification(changed: &[String]) -> InboundMessage {
+    InboundMessage::new(SKILLS_RELOAD_CHANNEL, "skills-watcher", "skills", "")
+        .with_metadata(SKILLS_RELOAD_METADATA_KEY, &changed.join(","))
+}
+
+/// Skill name → `SKILL.md` mtime for every skill in a directory.
+type Snapshot = BTreeMap<String, Option<SystemTime>>;
+
+/// Polling-based skills watcher.
+pub struct SkillsWatcher {
+    dir: PathBuf,
+    poll_interval: Duration,
+    last: Snapshot,
+}
+
+impl SkillsWatcher {
+    pub fn new(dir: PathBuf, poll_interval: Duration) -> Self {
+        Self {
+            dir,
+            poll_interval,
+            last: Snapshot::new(),
+        }
+    }
+
+    /// Names of skills added, edited, or removed since the last poll.
+    fn poll(&mut self) -> Vec<String> {
+        let current = snapshot(&self.dir);
+        let changed = diff(&self.last, &current);
+        self.last = current;
+        changed
+    }
+
+    pub async fn watch(mut self, bus: Arc<MessageBus>, mut shutdown_rx: watch::Receiver<bool>) {
+        self.last = snapshot(&self.dir);
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.changed() => {
+                    if *shutdown_rx.borrow() {
+                        info!("Skills watcher shutting down");
+                        return;
+                    }
+                }
+                _ = tokio::time::sleep(self.poll_interval) => {}
+            }
+
+            if *shutdown_rx.borrow() {
+                return;
+            }
+
+            let changed = self.poll();
+            if changed.is_empty() {
+                continue;
+            }
+
+            debug!(
+                dir = %self.dir.display(),
+                skills = %changed.join(", "),
+                "Skills changed, publishing reload notification"
+            );
+            if let Err(e) = bus.publish_inbound(reload_notification(&changed)).await {
+                warn!("Skills watcher could not publish reload: {}", e);
+                return;
+            }
+        }
+    }
+}
+
+fn snapshot(dir: &Path) -> Snapshot {
+    let mut out = Snapshot::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return out;
+    };
+    for entry in entries.flatten() {
+        let skill_md = entry.path().join("SKILL.md");
+        if !skill_md.is_file() {
+            continue;
+        }
+        let mtime = std::fs::metadata(&skill_md)
+            .ok()
+            .and_then(|m| m.modified().ok());
+        out.insert(entry.file_name().to_string_lossy().to_string(), mtime);
+    }
+    out
+}
+
+fn diff(prev: &Snapshot, next: &Snapshot) -> Vec<String> {
+    let mut changed: Vec<String> = next
+        .iter()
+        .filter(|(name, mtime)| prev.get(*name) != Some(*mtime))
+        .map(|(name, _)| name.clone())
+        .collect();
+    changed.extend(prev.keys().filter(|n| !next.contains_key(*n)).cloned());
+    changed.sort();
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_skill(dir: &Path, name: &str, body: &str) {
+        std::fs::create_dir_all(dir.join(name)).unwrap();
+        std::fs::write(
+            dir.join(name).join("SKILL.md"),
+            format!("---\nname: {}\ndescription: test\n---\n{}", name, body),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_poll_detects_add_edit_remove() {
+        let tmp = TempDir::new().unwrap();
+        let mut watcher = SkillsWatcher::new(tmp.path().to_path_buf(), Duration::from_secs(1));
+        assert!(watcher.poll().is_empty());
+
+        write_skill(tmp.path(), "alpha", "v1");
+        assert_eq!(watcher.poll(), vec!["alpha".to_string()]);
+        assert!(watcher.poll().is_empty());
+
+        std::fs::remove_dir_all(tmp.path().join("alpha")).unwrap();
+        assert_eq!(watcher.poll(), vec!["alpha".to_string()]);
+    }
+
+    #[test]
+    fn test_reload_notification_roundtrip() {
+        let msg = reload_notification(&["a".into(), "b".into()]);
+        assert!(is_reload_notification(&msg));
+        assert_eq!(msg.metadata.get(SKILLS_RELOAD_METADATA_KEY).unwrap(), "a,b");
+        assert!(!is_reload_notification(&InboundMessage::new(
+            "system", "u", "c", "hi"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_watcher_publishes_on_change() {
+        let tmp = TempDir::new().unwrap();
+        let bus = Arc::new(MessageBus::new());
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let watcher = SkillsWatcher::new(tmp.path().to_path_buf(), Duration::from_millis(25));
+        let handle = tokio::spawn(watcher.watch(Arc::clone(&bus), shutdown_rx));
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        write_skill(tmp.path(), "beta", "hello");
+
+        let msg = tokio::time::timeout(Duration::from_secs(2), bus.consume_inbound())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(is_reload_notification(&msg));
+        assert_eq!(
+            msg.metadata.get(SKILLS_RELOAD_METADATA_KEY).unwrap(),
+            "beta"
+        );
+
+        let _ = shutdown_tx.send(true);
+        let _ = handle.await;
+    }
+}
@@ -0,0 +1,385 @@
+//! Apply-patch tool — apply a multi-file unified diff to the workspace.
+//!
+//! Every hunk is checked against the current file contents before anything
+//! is written. If any hunk is rejected, no file changes and the result lists
+//! which hunks failed and why. Otherwise all files are written, and files
+//! already written are restored if a later write fails. A file may appear
+//! only once per patch; its hunks must be in a single file section.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::error::{Result, ZeptoError};
+use crate::security::revalidate_path;
+use crate::tools::diff::{parse_patch, DiffSummary, HunkOutcome};
+use crate::tools::filesystem::{resolve_path, write_file_secure};
+use crate::tools::undo::record_before;
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// A file change that passed validation.
+struct PlannedChange {
+    path: PathBuf,
+    display: String,
+    /// Contents before the patch, `None` if the file is created.
+    original: Option<String>,
+    /// Contents after the patch, `None` if the file is deleted.
+    patched: Option<String>,
+}
+
+/// Tool for applying unified diffs across one or more files.
+pub struct ApplyPatchTool;
+
+impl ApplyPatchTool {
+    /// Put back the files in `done` after a failed write.
+    async fn roll_back(done: &[&PlannedChange], workspace: &str) {
+        for change in done.iter().rev() {
+            let _ = match &change.original {
+                Some(content) => {
+                    write_file_secure(&change.path, workspace, content.as_bytes()).await
+                }
+                None => tokio::fs::remove_file(&change.path)
+                    .await
+                    .map_err(ZeptoError::from),
+            };
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ApplyPatchTool {
+    fn name(&self) -> &str {
+        "apply_patch"
+    }
+
+    fn description(&self) -> &str {
+        "Apply a unified diff (like `git diff` output) to one or more files in the workspace. \
+         Each file needs '--- a/path' and '+++ b/path' headers; use /dev/null to create or \
+         delete files. Hunks may have moved a few lines. All hunks must apply or nothing is \
+         written; the result lists applied and rejected hunks. Prefer this over edit_file \
+         for multi-line code changes."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Apply unified diff"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::FilesystemWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff with ---/+++ file headers and @@ hunks"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Only check that the patch applies (default: false)"
+                }
+            },
+            "required": ["patch"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let patch = args
+            .get("patch")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'patch' argument".into()))?;
+        let dry_run = args
+            .get("dry_run")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let files =
+            parse_patch(patch).map_err(|e| ZeptoError::Tool(format!("Invalid patch: {}", e)))?;
+        if files.is_empty() {
+            return Err(ZeptoError::Tool(
+                "Patch names no files; include '--- a/path' and '+++ b/path' headers".into(),
+            ));
+        }
+
+        let mut changes = Vec::with_capacity(files.len());
+        let mut report = Vec::new();
+        let mut total = DiffSummary::default();
+        let (mut hunks, mut rejected) = (0usize, 0usize);
+        let mut seen = HashSet::new();
+
+        for file in &files {
+            let display = file.path().unwrap_or_default().to_string();
+            hunks += file.hunk_count();
+            if let (Some(old), Some(new)) = (&file.old_path, &file.new_path) {
+                if old != new {
+                    rejected += file.hunk_count();
+                    report.push(format!(
+                        "{}: rejected — renames are not supported (from {})",
+                        new, old
+                    ));
+                    continue;
+                }
+            }
+
+            let (full_path, workspace) = resolve_path(&display, ctx)?;
+            let path = PathBuf::from(&full_path);
+            revalidate_path(&path, &workspace)?;
+            if !seen.insert(path.clone()) {
+                rejected += file.hunk_count();
+                report.push(format!(
+                    "{}: rejected — file appears more than once in the patch; \
+                     put all of its hunks under one header",
+                    display
+                ));
+                continue;
+            }
+
+            let original = if file.is_new_file() {
+                if path.exists() {
+                    rejected += file.hunk_count();
+                    report.push(format!("{}: rejected — file already exists", display));
+                    continue;
+                }
+                None
+            } else {
+                match tokio::fs::read_to_string(&path).await {
+                    Ok(content) => Some(content),
+                    Err(e) => {
+                        rejected += file.hunk_count();
+                        report.push(format!("{}: rejected — cannot read file: {}", display, e));
+                        continue;
+                    }
+                }
+            };
+
+            let patched = file.apply(original.as_deref().unwrap_or(""));
+            rejected += patched.rejected();
+            report.push(format!(
+                "{}: {}/{} hunk(s) applied{}",
+                display,
+                patched.summary.hunks_applied,
+                file.hunk_count(),
+                match (file.is_new_file(), file.is_deletion()) {
+                    (true, _) => " (new file)",
+                    (_, true) => " (deleted)",
+                    _ => "",
+                }
+            ));
+            for (i, outcome) in patched.outcomes.iter().enumerate() {
+                match outcome {
+                    HunkOutcome::Rejected(reason) => {
+                        report.push(format!("  hunk {}: rejected — {}", i + 1, reason))
+                    }
+                    HunkOutcome::Applied { line, offset } if *offset != 0 => report.push(format!(
+                        "  hunk {}: applied at line {} (offset {:+})",
+                        i + 1,
+                        line,
+                        offset
+                    )),
+                    HunkOutcome::Applied { .. } => {}
+                }
+            }
+            if file.is_deletion() && patched.rejected() == 0 && !patched.content.is_empty() {
+                rejected += 1;
+                report.push(format!(
+                    "  deletion rejected — {} still has content after the patch",
+                    display
+                ));
+            }
+
+            total.lines_added += patched.summary.lines_added;
+            total.lines_removed += patched.summary.lines_removed;
+            total.hunks_applied += patched.summary.hunks_applied;
+            changes.push(PlannedChange {
+                path,
+                display,
+                original,
+                patched: (!file.is_deletion()).then_some(patched.content),
+            });
+        }
+
+        let report = report.join("\n");
+        if rejected > 0 {
+            return Ok(ToolOutput::error(format!(
+                "Patch not applied: {} of {} hunk(s) rejected; no files were changed.\n{}",
+                rejected, hunks, report
+            )));
+        }
+        if dry_run {
+            return Ok(ToolOutput::llm_only(format!(
+                "Patch applies cleanly to {} file(s) (dry run, nothing written): +{} -{}\n{}",
+                changes.len(),
+                total.lines_added,
+                total.lines_removed,
+                report
+            )));
+        }
+
+        let workspace = ctx.workspace.clone().unwrap_or_default();
+        let mut done: Vec<&PlannedChange> = Vec::with_capacity(changes.len());
+        for change in &changes {
+            record_before(ctx, &change.path).await;
+            let result = match &change.patched {
+                Some(content) => {
+                    write_file_secure(&change.path, &workspace, content.as_bytes()).await
+                }
+                None => remove_file_secure(&change.path, &workspace).await,
+            };
+            if let Err(e) = result {
+                Self::roll_back(&done, &workspace).await;
+                return Err(ZeptoError::Tool(format!(
+                    "Failed to write {}: {}; {} file(s) already written were restored",
+                    change.display,
+                    e,
+                    done.len()
+                )));
+            }
+            done.push(change);
+        }
+
+        Ok(ToolOutput::llm_only(format!(
+            "Applied {} hunk(s) to {} file(s): +{} -{}\n{}",
+            total.hunks_applied,
+            changes.len(),
+            total.lines_added,
+            total.lines_removed,
+            report
+        )))
+    }
+}
+
+async fn remove_file_secure(path: &Path, workspace: &str) -> Result<()> {
+    revalidate_path(path, workspace)?;
+    tokio::fs::remove_file(path)
+        .await
+        .map_err(|e| ZeptoError::Tool(format!("Failed to delete file '{}': {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn ctx(workspace: &Path) -> ToolContext {
+        ToolContext::new().with_workspace(&workspace.to_string_lossy())
+    }
+
+    #[tokio::test]
+    async fn test_apply_patch_across_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("main.rs"), "fn main() {\n    old();\n}\n").unwrap();
+        fs::write(dir.path().join("gone.txt"), "bye\n").unwrap();
+
+        let patch = "--- a/main.rs\n+++ b/main.rs\n@@ -1,3 +1,3 @@\n fn main() {\n-    old();\n+    new();\n }\n\
+                     --- /dev/null\n+++ b/src/lib.rs\n@@ -0,0 +1,1 @@\n+pub fn new() {}\n\
+                     --- a/gone.txt\n+++ /dev/null\n@@ -1 +0,0 @@\n-bye\n";
+        let out = ApplyPatchTool
+            .execute(json!({ "patch": patch }), &ctx(dir.path()))
+            .await
+            .unwrap();
+
+        assert!(!out.is_error, "{}", out.for_llm);
+        assert!(out
+            .for_llm
+            .starts_with("Applied 3 hunk(s) to 3 file(s): +2 -2"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("main.rs")).unwrap(),
+            "fn main() {\n    new();\n}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(),
+            "pub fn new() {}\n"
+        );
+        assert!(!dir.path().join("gone.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_apply_patch_is_all_or_nothing() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.txt"), "one\ntwo\n").unwrap();
+        fs::write(dir.path().join("b.txt"), "three\n").unwrap();
+
+        let patch = "--- a/a.txt\n+++ b/a.txt\n@@ -1,2 +1,2 @@\n one\n-two\n+TWO\n\
+                     --- a/b.txt\n+++ b/b.txt\n@@ -1 +1 @@\n-four\n+FOUR\n";
+        let out = ApplyPatchTool
+            .execute(json!({ "patch": patch }), &ctx(dir.path()))
+            .await
+            .unwrap();
+
+        assert!(out.is_error);
+        assert!(out.for_llm.contains("1 of 2 hunk(s) rejected"));
+        assert!(out.for_llm.contains("b.txt: 0/1 hunk(s) applied"));
+        assert!(out
+            .for_llm
+            .contains("hunk 1: rejected — context not found near line 1"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "one\ntwo\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_patch_dry_run_and_offset() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("f.txt"), "x\ny\nz\n").unwrap();
+        let patch = "--- a/f.txt\n+++ b/f.txt\n@@ -1,1 +1,1 @@\n-z\n+Z\n";
+
+        let out = ApplyPatchTool
+            .execute(json!({ "patch": patch, "dry_run": true }), &ctx(dir.path()))
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("dry run"));
+        assert!(out.for_llm.contains("applied at line 3 (offset +2)"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("f.txt")).unwrap(),
+            "x\ny\nz\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_patch_rejects_escape_and_missing_headers() {
+        let dir = tempdir().unwrap();
+        let escape = "--- a/../outside.txt\n+++ b/../outside.txt\n@@ -0,0 +1 @@\n+x\n";
+        assert!(ApplyPatchTool
+            .execute(json!({ "patch": escape }), &ctx(dir.path()))
+            .await
+            .is_err());
+
+        let err = ApplyPatchTool
+            .execute(
+                json!({ "patch": "@@ -1 +1 @@\n-a\n+b\n" }),
+                &ctx(dir.path()),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no ---/+++ file header"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_patch_rejects_repeated_file() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("f.txt"), "a\nb\n").unwrap();
+        let patch = "--- a/f.txt\n+++ b/f.txt\n@@ -1 +1 @@\n-a\n+A\n\
+                     --- a/./f.txt\n+++ b/./f.txt\n@@ -2 +2 @@\n-b\n+B\n";
+
+        let out = ApplyPatchTool
+            .execute(json!({ "patch": patch }), &ctx(dir.path()))
+            .await
+            .unwrap();
+        assert!(out.is_error);
+        assert!(out.for_llm.contains("1 of 2 hunk(s) rejected"));
+        assert!(out
+            .for_llm
+            .contains("./f.txt: rejected — file appears more than once"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("f.txt")).unwrap(),
+            "a\nb\n"
+        );
+    }
+}
@@ -21,7 +21,7 @@
 //!     "approval": {
 //!         "enabled": true,
 //!         "policy": "require_for_dangerous",
-//!         "dangerous_tools": ["shell", "write_file", "edit_file", "google"]
+//!         "dangerous_tools": ["shell", "write_file", "edit_file", "apply_patch", "google", "sql_execute", "kubectl_write", "github_write"]
 //!     }
 //! }
 //! ```
@@ -39,6 +39,7 @@
 //! ```
 
 use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -69,7 +70,7 @@ pub enum ApprovalPolicy {
 /// Serializable approval policy selector for `config.json`.
 ///
 /// Maps to the runtime `ApprovalPolicy` via `ApprovalGate::new()`.
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ApprovalPolicyConfig {
     /// All tools execute without approval.
@@ -97,9 +98,9 @@ pub enum ApprovalPolicyConfig {
 /// - `enabled`: `true`
 /// - `policy`: `RequireForDangerous`
 /// - `require_for`: empty
-/// - `dangerous_tools`: `["shell", "write_file", "edit_file", "google"]`
+/// - `dangerous_tools`: `["shell", "write_file", "edit_file", "apply_patch", "google", "sql_execute", "kubectl_write", "github_write"]`
 /// - `auto_approve_timeout_secs`: `0` (disabled)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ApprovalConfig {
     /// Master switch for interactive approval prompts.
@@ -327,7 +328,11 @@ impl ApprovalGate {
             "shell".to_string(),
             "write_file".to_string(),
             "edit_file".to_string(),
+            "apply_patch".to_string(),
             "google".to_string(),
+            "sql_execute".to_string(),
+            "kubectl_write".to_string(),
+            "github_write".to_string(),
         ]
     }
 
@@ -518,7 +523,16 @@ mod tests {
         assert!(config.require_for.is_empty());
         assert_eq!(
             config.dangerous_tools,
-            vec!["shell", "write_file", "edit_file", "google"]
+            vec![
+                "shell",
+                "write_file",
+                "edit_file",
+                "apply_patch",
+                "google",
+                "sql_execute",
+                "kubectl_write",
+                "github_write"
+            ]
         );
         assert_eq!(config.auto_approve_timeout_secs, 0);
     }
@@ -708,11 +722,15 @@ mod tests {
     #[test]
     fn test_default_dangerous_tools_list() {
         let defaults = ApprovalGate::default_dangerous_tools();
-        assert_eq!(defaults.len(), 4);
+        assert_eq!(defaults.len(), 8);
         assert!(defaults.contains(&"shell".to_string()));
         assert!(defaults.contains(&"write_file".to_string()));
         assert!(defaults.contains(&"edit_file".to_string()));
+        assert!(defaults.contains(&"apply_patch".to_string()));
         assert!(defaults.contains(&"google".to_string()));
+        assert!(defaults.contains(&"sql_execute".to_string()));
+        assert!(defaults.contains(&"kubectl_write".to_string()));
+        assert!(defaults.contains(&"github_write".to_string()));
     }
 
     #[test]
@@ -0,0 +1,507 @@
+//! Arithmetic expression evaluator.
+//!
+//! Supports `+ - * / ^` (also `**`, `×`, `÷`), `mod`, parentheses, implicit
+//! multiplication (`2pi`, `3(4+1)`), postfix `%` (percent, so `15% of 80`
+//! is 12) and `!` (factorial), the constants `pi`, `e` and `tau`, and common
+//! functions (`sqrt`, `ln`, `log`, `sin`, `round`, `min`, ...). A `%`
+//! followed by an operand is modulo (`10 % 3`).
+
+use crate::error::{Result, ZeptoError};
+
+/// Longest expression accepted.
+const MAX_LEN: usize = 1000;
+
+/// Deepest nesting of parentheses and unary operators.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn err(message: impl Into<String>) -> ZeptoError {
+    ZeptoError::Tool(message.into())
+}
+
+/// Spelled-out operators, replaced before tokenizing. Longer phrases first.
+const WORD_OPERATORS: &[(&str, &str)] = &[
+    ("to the power of", "^"),
+    ("multiplied by", "*"),
+    ("divided by", "/"),
+    ("squared", "^2"),
+    ("cubed", "^3"),
+    ("times", "*"),
+    ("plus", "+"),
+    ("minus", "-"),
+    ("over", "/"),
+    ("of", "*"),
+];
+
+/// Rewrite symbols and operator words into the tokenizer's syntax.
+fn normalize(input: &str) -> String {
+    let mut text = input
+        .replace(['×', '⋅', '·'], "*")
+        .replace('÷', "/")
+        .replace(['−', '–'], "-")
+        .replace("**", "^")
+        .replace('π', "pi");
+    let lower = text.to_lowercase();
+    if lower.chars().any(|c| c.is_ascii_alphabetic()) {
+        let mut words: Vec<String> = lower.split_whitespace().map(str::to_string).collect();
+        for (phrase, op) in WORD_OPERATORS {
+            let parts: Vec<&str> = phrase.split(' ').collect();
+            let mut i = 0;
+            while i + parts.len() <= words.len() {
+                if words[i..i + parts.len()]
+                    .iter()
+                    .zip(&parts)
+                    .all(|(w, p)| w == p)
+                {
+                    words.splice(i..i + parts.len(), [op.to_string()]);
+                }
+                i += 1;
+            }
+        }
+        text = words.join(" ");
+        // "3 x 4" means multiplication.
+        let chars: Vec<char> = text.chars().collect();
+        text = chars
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| {
+                let prev = chars[..i].iter().rev().find(|c| !c.is_whitespace());
+                let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+                let operand_before = prev.is_some_and(|p| p.is_ascii_digit() || *p == ')');
+                let operand_after =
+                    next.is_some_and(|n| n.is_ascii_digit() || *n == '(' || *n == '.');
+                let isolated = chars
+                    .get(i.wrapping_sub(1))
+                    .is_none_or(|p| !p.is_alphanumeric())
+                    && chars.get(i + 1).is_none_or(|n| !n.is_alphabetic());
+                if c == 'x' && operand_before && operand_after && isolated {
+                    '*'
+                } else {
+                    c
+                }
+            })
+            .collect();
+    }
+    text
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == '_')
+            {
+                i += 1;
+            }
+            // Exponent only when digits follow: `2e3`, `1.5e-4` (but `2e` is 2·e).
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..i].iter().filter(|c| **c != '_').collect();
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| err(format!("Invalid number '{}'", text)))?;
+            tokens.push(Token::Num(value));
+        } else if c.is_alphabetic() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect::<String>().to_lowercase();
+            tokens.push(if name == "mod" {
+                Token::Op('m')
+            } else {
+                Token::Ident(name)
+            });
+        } else {
+            tokens.push(match c {
+                '+' | '-' | '*' | '/' | '^' | '%' | '!' => Token::Op(c),
+                '(' | '[' => Token::LParen,
+                ')' | ']' => Token::RParen,
+                ',' | ';' => Token::Comma,
+                _ => return Err(err(format!("Unexpected character '{}'", c))),
+            });
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+fn factorial(n: f64) -> Result<f64> {
+    if n < 0.0 || n.fract() != 0.0 || n > 170.0 {
+        return Err(err("Factorial needs a whole number from 0 to 170"));
+    }
+    Ok((1..=n as u64).fold(1.0, |acc, k| acc * k as f64))
+}
+
+fn call(name: &str, args: &[f64]) -> Result<f64> {
+    let one = || -> Result<f64> {
+        match args {
+            [x] => Ok(*x),
+            _ => Err(err(format!("{}() takes one argument", name))),
+        }
+    };
+    Ok(match name {
+        "sqrt" => {
+            let x = one()?;
+            if x < 0.0 {
+                return Err(err("Square root of a negative number"));
+            }
+            x.sqrt()
+        }
+        "cbrt" => one()?.cbrt(),
+        "abs" => one()?.abs(),
+        "exp" => one()?.exp(),
+        "ln" => one()?.ln(),
+        "log" => match args {
+            [x] => x.log10(),
+            [x, base] => x.log(*base),
+            _ => return Err(err("log() takes a value and an optional base")),
+        },
+        "log10" => one()?.log10(),
+        "log2" => one()?.log2(),
+        "sin" => one()?.sin(),
+        "cos" => one()?.cos(),
+        "tan" => one()?.tan(),
+        "asin" => one()?.asin(),
+        "acos" => one()?.acos(),
+        "atan" => one()?.atan(),
+        "sinh" => one()?.sinh(),
+        "cosh" => one()?.cosh(),
+        "tanh" => one()?.tanh(),
+        "floor" => one()?.floor(),
+        "ceil" => one()?.ceil(),
+        "trunc" => one()?.trunc(),
+        "round" => match args {
+            [x] => x.round(),
+            [x, digits] => {
+                let scale = 10f64.powi(*digits as i32);
+                (x * scale).round() / scale
+            }
+            _ => return Err(err("round() takes a value and optional decimal places")),
+        },
+        "pow" => match args {
+            [x, y] => x.powf(*y),
+            _ => return Err(err("pow() takes two arguments")),
+        },
+        "hypot" => match args {
+            [x, y] => x.hypot(*y),
+            _ => return Err(err("hypot() takes two arguments")),
+        },
+        "min" | "max" if !args.is_empty() => {
+            let fold: fn(f64, f64) -> f64 = if name == "min" { f64::min } else { f64::max };
+            args.iter().copied().reduce(fold).unwrap_or(f64::NAN)
+        }
+        "min" | "max" => return Err(err(format!("{}() needs at least one argument", name))),
+        "deg" => one()?.to_degrees(),
+        "rad" => one()?.to_radians(),
+        _ => return Err(err(format!("Unknown function '{}'", name))),
+    })
+}
+
+fn constant(name: &str) -> Option<f64> {
+    match name {
+        "pi" => Some(std::f64::consts::PI),
+        "e" => Some(std::f64::consts::E),
+        "tau" => Some(std::f64::consts::TAU),
+        _ => None,
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn enter(&mut self) -> Result<()> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(err("Expression is nested too deeply"));
+        }
+        Ok(())
+    }
+
+    /// Whether the next token starts an operand (for implicit multiplication
+    /// and telling modulo from percent).
+    fn operand_follows(&self) -> bool {
+        matches!(
+            self.peek(),
+            Some(Token::Num(_) | Token::Ident(_) | Token::LParen)
+        )
+    }
+
+    fn expr(&mut self) -> Result<f64> {
+        let mut value = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek().cloned() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<f64> {
+        let mut value = self.unary()?;
+        loop {
+            match self.peek().cloned() {
+                Some(Token::Op(op @ ('*' | '/' | '%' | 'm'))) => {
+                    self.pos += 1;
+                    let rhs = self.unary()?;
+                    value = match op {
+                        '*' => value * rhs,
+                        '/' => {
+                            if rhs == 0.0 {
+                                return Err(err("Division by zero"));
+                            }
+                            value / rhs
+                        }
+                        _ => {
+                            if rhs == 0.0 {
+                                return Err(err("Modulo by zero"));
+                            }
+                            value % rhs
+                        }
+                    };
+                }
+                _ if self.operand_follows() => value *= self.unary()?,
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<f64> {
+        self.enter()?;
+        let value = match self.peek() {
+            Some(Token::Op('-')) => {
+                self.pos += 1;
+                -self.unary()?
+            }
+            Some(Token::Op('+')) => {
+                self.pos += 1;
+                self.unary()?
+            }
+            _ => self.power()?,
+        };
+        self.depth -= 1;
+        Ok(value)
+    }
+
+    fn power(&mut self) -> Result<f64> {
+        let base = self.postfix()?;
+        if let Some(Token::Op('^')) = self.peek() {
+            self.pos += 1;
+            let exponent = self.unary()?;
+            return Ok(base.powf(exponent));
+        }
+        Ok(base)
+    }
+
+    fn postfix(&mut self) -> Result<f64> {
+        let mut value = self.primary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Op('!')) => {
+                    self.pos += 1;
+                    value = factorial(value)?;
+                }
+                // Percent unless an operand follows, which makes it modulo.
+                Some(Token::Op('%')) => {
+                    self.pos += 1;
+                    if self.operand_follows() {
+                        self.pos -= 1;
+                        return Ok(value);
+                    }
+                    value /= 100.0;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<f64> {
+        match self.next() {
+            Some(Token::Num(value)) => Ok(value),
+            Some(Token::LParen) => {
+                self.enter()?;
+                let value = self.expr()?;
+                self.depth -= 1;
+                match self.next() {
+                    Some(Token::RParen) => Ok(value),
+                    _ => Err(err("Missing closing parenthesis")),
+                }
+            }
+            Some(Token::Ident(name)) => {
+                if let Some(Token::LParen) = self.peek() {
+                    self.pos += 1;
+                    self.enter()?;
+                    let mut args = Vec::new();
+                    if !matches!(self.peek(), Some(Token::RParen)) {
+                        loop {
+                            args.push(self.expr()?);
+                            match self.next() {
+                                Some(Token::Comma) => continue,
+                                Some(Token::RParen) => break,
+                                _ => return Err(err(format!("Missing ')' after {}(", name))),
+                            }
+                        }
+                    } else {
+                        self.pos += 1;
+                    }
+                    self.depth -= 1;
+                    return call(&name, &args);
+                }
+                constant(&name).ok_or_else(|| err(format!("Unknown name '{}'", name)))
+            }
+            Some(token) => Err(err(format!("Unexpected {:?}", token))),
+            None => Err(err("Incomplete expression")),
+        }
+    }
+}
+
+/// Evaluate an arithmetic expression.
+pub fn evaluate(input: &str) -> Result<f64> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(err("Empty expression"));
+    }
+    if input.len() > MAX_LEN {
+        return Err(err(format!(
+            "Expression is too long (max {} characters)",
+            MAX_LEN
+        )));
+    }
+    let mut parser = Parser {
+        tokens: tokenize(&normalize(input))?,
+        pos: 0,
+        depth: 0,
+    };
+    let value = parser.expr()?;
+    if let Some(token) = parser.peek() {
+        return Err(err(format!("Unexpected {:?}", token)));
+    }
+    if !value.is_finite() {
+        return Err(err("Result is not a finite number"));
+    }
+    Ok(value)
+}
+
+/// Format a result without float noise: up to 10 decimal places, trailing
+/// zeros dropped, scientific notation for very large or small values.
+pub fn format_number(value: f64) -> String {
+    let abs = value.abs();
+    if abs != 0.0 && !(1e-6..1e15).contains(&abs) {
+        return format!("{:e}", value);
+    }
+    let text = format!("{:.10}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(input: &str) -> f64 {
+        evaluate(input).unwrap_or_else(|e| panic!("{}: {}", input, e))
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        assert_eq!(eval("2 + 3 * 4"), 14.0);
+        assert_eq!(eval("(2 + 3) * 4"), 20.0);
+        assert_eq!(eval("2 ^ 3 ^ 2"), 512.0);
+        assert_eq!(eval("-2 ^ 2"), -4.0);
+        assert_eq!(eval("10 - 4 - 3"), 3.0);
+        assert_eq!(eval("2 ** 10"), 1024.0);
+        assert_eq!(eval("1.5e3 / 3"), 500.0);
+        assert_eq!(eval("1_000 × 3 ÷ 4"), 750.0);
+    }
+
+    #[test]
+    fn test_percent_modulo_factorial() {
+        assert_eq!(eval("15% of 80"), 12.0);
+        assert_eq!(eval("200 * 15%"), 30.0);
+        assert_eq!(eval("10 % 3"), 1.0);
+        assert_eq!(eval("17 mod 5"), 2.0);
+        assert_eq!(eval("5!"), 120.0);
+        assert!(evaluate("2.5!").is_err());
+    }
+
+    #[test]
+    fn test_functions_constants_and_words() {
+        assert_eq!(eval("sqrt(16) + abs(-2)"), 6.0);
+        assert_eq!(eval("max(1, 7, 3)"), 7.0);
+        assert_eq!(eval("round(2.34567, 2)"), 2.35);
+        assert_eq!(format_number(eval("log(1000)")), "3");
+        assert_eq!(format_number(eval("log(8, 2)")), "3");
+        assert_eq!(eval("2pi"), 2.0 * std::f64::consts::PI);
+        assert_eq!(eval("3(4 + 1)"), 15.0);
+        assert_eq!(eval("12 times 3 plus 4"), 40.0);
+        assert_eq!(eval("7 x 6"), 42.0);
+        assert_eq!(eval("9 squared"), 81.0);
+        assert_eq!(eval("2e"), 2.0 * std::f64::consts::E);
+    }
+
+    #[test]
+    fn test_errors() {
+        for bad in [
+            "", "1 / 0", "5 mod 0", "sqrt(-1)", "2 +", "(1 + 2", "foo(3)", "love", "1 $ 2",
+        ] {
+            assert!(evaluate(bad).is_err(), "{} should fail", bad);
+        }
+        let deep = format!("{}1{}", "(".repeat(100), ")".repeat(100));
+        assert!(evaluate(&deep).is_err());
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(0.1 + 0.2), "0.3");
+        assert_eq!(format_number(1081.0), "1081");
+        assert_eq!(format_number(-0.0), "0");
+        assert_eq!(format_number(2.0 / 3.0), "0.6666666667");
+        assert_eq!(format_number(1e20), "1e20");
+    }
+}
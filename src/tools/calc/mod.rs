@@ -0,0 +1,556 @@
+//! Calculator tool: arithmetic, unit conversion and currency conversion.
+//!
+//! Results are computed, never guessed by the model. Currency conversion
+//! uses exchange rates from `tools.calc.fx_url` (ECB rates via Frankfurter
+//! by default), cached for `tools.calc.fx_cache_secs`; when a refresh fails
+//! the last rates are used and flagged as stale.
+//!
+//! [`quick_answer`] lets the agent loop answer messages that are plainly a
+//! calculation ("what is 23*47?", "convert 10 km to miles") without an LLM
+//! call when `tools.calc.fast_path` is on (it is off by default).
+
+mod expr;
+mod units;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::config::CalcToolConfig;
+use crate::error::{Result, ZeptoError};
+use crate::utils::http::Client;
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+pub use expr::{evaluate, format_number};
+use units::{find_unit, Unit};
+
+/// Leading number of a conversion: `1,200`, `-3.5`, `2e6`, `.5`.
+static NUMBER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[-+]?(?:\d[\d,_]*(?:\.\d+)?|\.\d+)(?:[eE][-+]?\d+)?").unwrap());
+
+/// Words separating the source and target of a conversion.
+const SEPARATORS: &[&str] = &["to", "in", "into", "as"];
+
+/// Prefixes stripped before a message is tried as a calculation.
+const QUESTION_PREFIXES: &[&str] = &[
+    "how much is",
+    "what is",
+    "what's",
+    "whats",
+    "calculate",
+    "compute",
+    "evaluate",
+    "convert",
+    "calc",
+];
+
+/// Longest message the fast path considers.
+const QUICK_MAX_LEN: usize = 200;
+
+/// What a conversion converts between.
+#[derive(Debug, Clone)]
+enum Measure {
+    Unit(&'static Unit),
+    Currency(String),
+}
+
+/// ISO code for a currency symbol, common name or three-letter code.
+fn currency_code(name: &str) -> Option<String> {
+    let code = match name.trim().to_lowercase().as_str() {
+        "$" | "dollar" | "dollars" => "USD",
+        "€" | "euro" | "euros" => "EUR",
+        "£" => "GBP",
+        "¥" | "yen" => "JPY",
+        "₹" | "rupee" | "rupees" => "INR",
+        "₩" | "won" => "KRW",
+        other if other.len() == 3 && other.chars().all(|c| c.is_ascii_alphabetic()) => {
+            return Some(other.to_uppercase())
+        }
+        _ => return None,
+    };
+    Some(code.to_string())
+}
+
+fn measure(name: &str) -> Option<Measure> {
+    find_unit(name)
+        .map(Measure::Unit)
+        .or_else(|| currency_code(name).map(Measure::Currency))
+}
+
+/// A parsed "<value> <from> to <to>" request.
+#[derive(Debug, Clone)]
+struct Conversion {
+    value: f64,
+    from: Measure,
+    to: Measure,
+}
+
+/// Parse "10 km to mi", "100°F in C", "$250 to EUR" or "1,200 usd as myr".
+/// Units are tried before currency codes.
+fn parse_conversion(text: &str) -> Option<Conversion> {
+    let text = text.trim();
+    let (symbol, text) = match text.chars().next() {
+        Some(c) if "$€£¥₹₩".contains(c) => {
+            (Some(c.to_string()), text[c.len_utf8()..].trim_start())
+        }
+        _ => (None, text),
+    };
+    let number = NUMBER.find(text)?;
+    let value: f64 = number.as_str().replace([',', '_'], "").parse().ok()?;
+    let words: Vec<&str> = text[number.end()..].split_whitespace().collect();
+    (0..words.len()).find_map(|i| {
+        if !SEPARATORS.contains(&words[i].to_lowercase().as_str()) || i + 1 == words.len() {
+            return None;
+        }
+        let from = match (words[..i].join(" "), &symbol) {
+            (from, _) if !from.is_empty() => measure(&from)?,
+            (_, Some(symbol)) => Measure::Currency(currency_code(symbol)?),
+            _ => return None,
+        };
+        let to = measure(&words[i + 1..].join(" "))?;
+        match (&from, &to) {
+            (Measure::Unit(_), Measure::Unit(_)) | (Measure::Currency(_), Measure::Currency(_)) => {
+                Some(Conversion { value, from, to })
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Round to `places` decimals (unit conversion results).
+fn round_to(value: f64, places: i32) -> f64 {
+    let scale = 10f64.powi(places);
+    (value * scale).round() / scale
+}
+
+fn convert_units(value: f64, from: &Unit, to: &Unit) -> Result<String> {
+    let result = units::convert(value, from, to)?;
+    Ok(format!(
+        "{} {} = {} {}",
+        format_number(value),
+        from.names[0],
+        format_number(round_to(result, 6)),
+        to.names[0]
+    ))
+}
+
+/// Answer `message` if it is plainly a calculation or unit conversion:
+/// an arithmetic expression (optionally after "what is", "calculate", ...)
+/// or "<number> <unit> to <unit>". Currency is left to the tool, since it
+/// needs live rates. Returns `None` for anything else.
+pub fn quick_answer(message: &str) -> Option<String> {
+    let text = message.trim();
+    if text.is_empty() || text.len() > QUICK_MAX_LEN || text.contains('\n') {
+        return None;
+    }
+    let (asked, body) = QUESTION_PREFIXES
+        .iter()
+        .find_map(|prefix| {
+            let head = text.get(..prefix.len())?;
+            let rest = &text[prefix.len()..];
+            (head.eq_ignore_ascii_case(prefix) && rest.starts_with(char::is_whitespace))
+                .then_some(rest)
+        })
+        .map_or((false, text), |rest| (true, rest));
+    let body = body
+        .trim()
+        .trim_end_matches(['?', '=', '.', '!', ' '])
+        .trim();
+    if !body.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    if let Some(conversion) = parse_conversion(body) {
+        return match (conversion.from, conversion.to) {
+            (Measure::Unit(from), Measure::Unit(to)) => {
+                convert_units(conversion.value, from, to).ok()
+            }
+            _ => None,
+        };
+    }
+
+    // A bare number is not a question; without a question prefix, only
+    // symbol-only input with an unambiguous operator counts (so dates like
+    // 2026-10-16 and fractions like 3/4 are left alone).
+    if body.parse::<f64>().is_ok() {
+        return None;
+    }
+    if !asked {
+        let symbolic = body
+            .chars()
+            .all(|c| c.is_ascii_digit() || " .,_+-*/^%()×÷".contains(c));
+        let operator = body.chars().any(|c| "+*^×÷".contains(c));
+        if !symbolic || !operator {
+            return None;
+        }
+    }
+    let value = evaluate(body).ok()?;
+    Some(format!("{} = {}", body, format_number(value)))
+}
+
+/// Exchange rates against one base currency (which maps to 1.0).
+#[derive(Debug, Clone, PartialEq)]
+struct FxRates {
+    base: String,
+    date: String,
+    rates: HashMap<String, f64>,
+}
+
+impl FxRates {
+    /// Parse a `{"base", "date", "rates"}` response (`base_code` and
+    /// `time_last_update_utc` are accepted too).
+    fn parse(body: &Value) -> Result<Self> {
+        let base = body
+            .get("base")
+            .or_else(|| body.get("base_code"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| ZeptoError::Tool("Exchange-rate response has no base".into()))?
+            .to_uppercase();
+        let mut rates: HashMap<String, f64> = body
+            .get("rates")
+            .and_then(Value::as_object)
+            .ok_or_else(|| ZeptoError::Tool("Exchange-rate response has no rates".into()))?
+            .iter()
+            .filter_map(|(code, rate)| Some((code.to_uppercase(), rate.as_f64()?)))
+            .filter(|(_, rate)| *rate > 0.0)
+            .collect();
+        rates.insert(base.clone(), 1.0);
+        let date = body
+            .get("date")
+            .or_else(|| body.get("time_last_update_utc"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        Ok(Self { base, date, rates })
+    }
+
+    /// Units of `to` per one unit of `from`.
+    fn rate(&self, from: &str, to: &str) -> Result<f64> {
+        let lookup = |code: &str| {
+            self.rates.get(code).copied().ok_or_else(|| {
+                ZeptoError::Tool(format!(
+                    "No exchange rate for '{}' (rates are against {})",
+                    code, self.base
+                ))
+            })
+        };
+        Ok(lookup(to)? / lookup(from)?)
+    }
+}
+
+/// Deterministic arithmetic, unit and currency conversion.
+pub struct CalcTool {
+    config: CalcToolConfig,
+    client: Client,
+    /// Last fetched rates and when they were fetched.
+    fx: Mutex<Option<(Instant, Arc<FxRates>)>>,
+}
+
+impl CalcTool {
+    /// Create the tool from `tools.calc`.
+    pub fn new(config: CalcToolConfig) -> Self {
+        Self {
+            config,
+            client: crate::utils::http::client_builder()
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            fx: Mutex::new(None),
+        }
+    }
+
+    async fn fetch_rates(&self) -> Result<FxRates> {
+        let response = self
+            .client
+            .get(&self.config.fx_url)
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Exchange-rate request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(ZeptoError::Tool(format!(
+                "Exchange-rate request failed: HTTP {}",
+                response.status()
+            )));
+        }
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Invalid exchange-rate response: {}", e)))?;
+        FxRates::parse(&body)
+    }
+
+    /// Cached rates, refreshed once they are older than `fx_cache_secs`.
+    /// Returns whether the rates are stale (a refresh failed).
+    async fn rates(&self) -> Result<(Arc<FxRates>, bool)> {
+        let mut cached = self.fx.lock().await;
+        let ttl = Duration::from_secs(self.config.fx_cache_secs);
+        if let Some((fetched, rates)) = cached.as_ref() {
+            if fetched.elapsed() < ttl {
+                return Ok((Arc::clone(rates), false));
+            }
+        }
+        match self.fetch_rates().await {
+            Ok(rates) => {
+                let rates = Arc::new(rates);
+                *cached = Some((Instant::now(), Arc::clone(&rates)));
+                Ok((rates, false))
+            }
+            Err(e) => match cached.as_ref() {
+                Some((_, rates)) => {
+                    tracing::warn!(error = %e, "Using stale exchange rates");
+                    Ok((Arc::clone(rates), true))
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    async fn convert_currency(&self, value: f64, from: &str, to: &str) -> Result<String> {
+        let (rates, stale) = self.rates().await?;
+        let rate = rates.rate(from, to)?;
+        let mut out = format!(
+            "{:.2} {} = {:.2} {} (rate {}",
+            value,
+            from,
+            value * rate,
+            to,
+            format_number(round_to(rate, 6))
+        );
+        if !rates.date.is_empty() {
+            out.push_str(&format!(", {}", rates.date));
+        }
+        out.push(')');
+        if stale {
+            out.push_str(" - rates could not be refreshed and may be out of date");
+        }
+        Ok(out)
+    }
+
+    async fn run_conversion(&self, conversion: Conversion) -> Result<String> {
+        match (conversion.from, conversion.to) {
+            (Measure::Unit(from), Measure::Unit(to)) => convert_units(conversion.value, from, to),
+            (Measure::Currency(from), Measure::Currency(to)) => {
+                self.convert_currency(conversion.value, &from, &to).await
+            }
+            _ => Err(ZeptoError::Tool(
+                "Cannot convert between a unit and a currency".into(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CalcTool {
+    fn name(&self) -> &str {
+        "calc"
+    }
+
+    fn description(&self) -> &str {
+        "Exact arithmetic and conversions; use instead of computing in your head. \
+         'expression' is arithmetic (+ - * / ^ mod, %, !, parentheses, sqrt, ln, log, \
+         sin, round, min, max, pi, e) or a conversion such as '10 km to mi', \
+         '72 F to C', '5 GiB in MB' or '250 USD to EUR' (live exchange rates). \
+         Alternatively pass value, from and to."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Calculator and unit/currency conversion"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkRead
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "expression": {
+                    "type": "string",
+                    "description": "Arithmetic expression, or '<value> <unit> to <unit>'"
+                },
+                "value": {
+                    "type": "number",
+                    "description": "Amount to convert (with from and to)"
+                },
+                "from": {
+                    "type": "string",
+                    "description": "Source unit (e.g. 'km', 'lb', '°F') or currency code (e.g. 'USD')"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Target unit or currency code"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let text = |key: &str| {
+            args.get(key)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+        };
+
+        let output = if let (Some(from), Some(to)) = (text("from"), text("to")) {
+            let value = args.get("value").and_then(Value::as_f64).unwrap_or(1.0);
+            let resolve = |name: &str| {
+                measure(name)
+                    .ok_or_else(|| ZeptoError::Tool(format!("Unknown unit or currency '{}'", name)))
+            };
+            self.run_conversion(Conversion {
+                value,
+                from: resolve(from)?,
+                to: resolve(to)?,
+            })
+            .await?
+        } else if let Some(expression) = text("expression") {
+            match parse_conversion(expression) {
+                Some(conversion) => self.run_conversion(conversion).await?,
+                None => format!("{} = {}", expression, format_number(evaluate(expression)?)),
+            }
+        } else {
+            return Err(ZeptoError::Tool(
+                "Provide 'expression', or 'value' with 'from' and 'to'".into(),
+            ));
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_with_rates() -> CalcTool {
+        let tool = CalcTool::new(CalcToolConfig::default());
+        let rates = FxRates::parse(&json!({
+            "amount": 1.0, "base": "EUR", "date": "2026-10-15",
+            "rates": {"USD": 1.08, "MYR": 4.86, "JPY": 162.0}
+        }))
+        .unwrap();
+        *tool.fx.try_lock().unwrap() = Some((Instant::now(), Arc::new(rates)));
+        tool
+    }
+
+    #[test]
+    fn test_quick_answer_arithmetic() {
+        assert_eq!(quick_answer("23*47").as_deref(), Some("23*47 = 1081"));
+        assert_eq!(
+            quick_answer("What is 15% of 80?").as_deref(),
+            Some("15% of 80 = 12")
+        );
+        assert_eq!(
+            quick_answer("calculate (1.5 + 2.5) / 4").as_deref(),
+            Some("(1.5 + 2.5) / 4 = 1")
+        );
+        assert_eq!(quick_answer("2 ^ 10 =").as_deref(), Some("2 ^ 10 = 1024"));
+        assert_eq!(
+            quick_answer("what's sqrt(144)").as_deref(),
+            Some("sqrt(144) = 12")
+        );
+    }
+
+    #[test]
+    fn test_quick_answer_conversions() {
+        assert_eq!(
+            quick_answer("convert 10 km to miles").as_deref(),
+            Some("10 km = 6.213712 mi")
+        );
+        assert_eq!(
+            quick_answer("100°F in C").as_deref(),
+            Some("100 °F = 37.777778 °C")
+        );
+        // Currency needs live rates, so it goes to the model and the tool.
+        assert!(quick_answer("100 usd to eur").is_none());
+        assert!(quick_answer("convert 3 kg to m").is_none());
+    }
+
+    #[test]
+    fn test_quick_answer_leaves_other_messages() {
+        for message in [
+            "hello",
+            "what is love?",
+            "2026-10-16",
+            "3/4",
+            "42",
+            "what is 42",
+            "I have 2 cats and 3 dogs",
+            "meet at 5 to 6",
+            "what is the capital of France",
+            "calculate my taxes for 2025",
+        ] {
+            assert!(quick_answer(message).is_none(), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_parse_conversion() {
+        let c = parse_conversion("$1,250.50 to myr").unwrap();
+        assert_eq!(c.value, 1250.5);
+        assert!(matches!(c.from, Measure::Currency(ref code) if code == "USD"));
+        assert!(matches!(c.to, Measure::Currency(ref code) if code == "MYR"));
+
+        // "in" is both a unit and a separator.
+        let c = parse_conversion("12 in in cm").unwrap();
+        assert!(matches!(c.from, Measure::Unit(u) if u.names[0] == "in"));
+        assert!(matches!(c.to, Measure::Unit(u) if u.names[0] == "cm"));
+
+        assert!(parse_conversion("10 km to usd").is_none());
+        assert!(parse_conversion("km to mi").is_none());
+    }
+
+    #[test]
+    fn test_fx_rates() {
+        let rates = FxRates::parse(&json!({
+            "result": "success", "base_code": "USD",
+            "time_last_update_utc": "Thu, 15 Oct 2026 00:00:01 +0000",
+            "rates": {"EUR": 0.9, "BAD": "x"}
+        }))
+        .unwrap();
+        assert_eq!(rates.base, "USD");
+        assert_eq!(rates.rate("USD", "EUR").unwrap(), 0.9);
+        assert!((rates.rate("EUR", "USD").unwrap() - 1.0 / 0.9).abs() < 1e-12);
+        let err = rates.rate("USD", "BAD").unwrap_err().to_string();
+        assert!(err.contains("No exchange rate for 'BAD'"), "{}", err);
+        assert!(FxRates::parse(&json!({"rates": {}})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute() {
+        let tool = tool_with_rates();
+        let ctx = ToolContext::new();
+        let run = |args: Value| {
+            let tool = &tool;
+            let ctx = &ctx;
+            async move { tool.execute(args, ctx).await.map(|out| out.for_llm) }
+        };
+
+        assert_eq!(
+            run(json!({"expression": "0.1 + 0.2"})).await.unwrap(),
+            "0.1 + 0.2 = 0.3"
+        );
+        assert_eq!(
+            run(json!({"expression": "100 USD to MYR"})).await.unwrap(),
+            "100.00 USD = 450.00 MYR (rate 4.5, 2026-10-15)"
+        );
+        assert_eq!(
+            run(json!({"value": 2, "from": "lb", "to": "kg"}))
+                .await
+                .unwrap(),
+            "2 lb = 0.907185 kg"
+        );
+        assert!(run(json!({"expression": "1 / 0"})).await.is_err());
+        assert!(run(json!({"value": 1, "from": "kg", "to": "USD"}))
+            .await
+            .is_err());
+        assert!(run(json!({})).await.is_err());
+    }
+}
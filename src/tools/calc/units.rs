@@ -0,0 +1,300 @@
+//! Unit table and conversion for the calc tool.
+//!
+//! Every unit maps to its dimension's base unit as
+//! `base = value * factor + offset`; the offset is only non-zero for
+//! temperatures (base: kelvin).
+
+use crate::error::{Result, ZeptoError};
+
+/// Physical quantity a unit measures; units only convert within one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Volume,
+    Time,
+    Speed,
+    Area,
+    Data,
+    Energy,
+    Power,
+    Pressure,
+    Temperature,
+}
+
+impl Dimension {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Length => "length",
+            Self::Mass => "mass",
+            Self::Volume => "volume",
+            Self::Time => "time",
+            Self::Speed => "speed",
+            Self::Area => "area",
+            Self::Data => "data size",
+            Self::Energy => "energy",
+            Self::Power => "power",
+            Self::Pressure => "pressure",
+            Self::Temperature => "temperature",
+        }
+    }
+}
+
+/// A unit: accepted names (the first is used in results), dimension and
+/// mapping to the base unit.
+#[derive(Debug)]
+pub struct Unit {
+    pub names: &'static [&'static str],
+    pub dimension: Dimension,
+    factor: f64,
+    offset: f64,
+}
+
+const fn unit(names: &'static [&'static str], dimension: Dimension, factor: f64) -> Unit {
+    Unit {
+        names,
+        dimension,
+        factor,
+        offset: 0.0,
+    }
+}
+
+use Dimension::*;
+
+const UNITS: &[Unit] = &[
+    // Length (metre)
+    unit(&["m", "meter", "metre"], Length, 1.0),
+    unit(&["km", "kilometer", "kilometre"], Length, 1000.0),
+    unit(&["cm", "centimeter", "centimetre"], Length, 0.01),
+    unit(&["mm", "millimeter", "millimetre"], Length, 0.001),
+    unit(&["µm", "um", "micrometer", "micron"], Length, 1e-6),
+    unit(&["nm", "nanometer"], Length, 1e-9),
+    unit(&["mi", "mile"], Length, 1609.344),
+    unit(&["yd", "yard"], Length, 0.9144),
+    unit(&["ft", "foot", "feet"], Length, 0.3048),
+    unit(&["in", "inch", "inches", "\""], Length, 0.0254),
+    unit(&["nmi", "nautical mile"], Length, 1852.0),
+    // Mass (kilogram)
+    unit(&["kg", "kilogram", "kilo"], Mass, 1.0),
+    unit(&["g", "gram", "gramme"], Mass, 0.001),
+    unit(&["mg", "milligram"], Mass, 1e-6),
+    unit(&["µg", "ug", "microgram", "mcg"], Mass, 1e-9),
+    unit(&["t", "tonne", "metric ton"], Mass, 1000.0),
+    unit(&["lb", "lbs", "pound"], Mass, 0.453_592_37),
+    unit(&["oz", "ounce"], Mass, 0.028_349_523_125),
+    unit(&["st", "stone"], Mass, 6.350_293_18),
+    // Volume (cubic metre)
+    unit(&["L", "l", "liter", "litre"], Volume, 0.001),
+    unit(&["mL", "ml", "milliliter", "millilitre"], Volume, 1e-6),
+    unit(&["cL", "cl", "centiliter", "centilitre"], Volume, 1e-5),
+    unit(&["dL", "dl", "deciliter", "decilitre"], Volume, 1e-4),
+    unit(&["m³", "m3", "cubic meter", "cubic metre"], Volume, 1.0),
+    unit(&["cm³", "cm3", "cc", "cubic centimeter"], Volume, 1e-6),
+    unit(&["gal", "gallon", "us gallon"], Volume, 0.003_785_411_784),
+    unit(
+        &["imp gal", "imperial gallon", "uk gallon"],
+        Volume,
+        0.004_546_09,
+    ),
+    unit(&["qt", "quart"], Volume, 0.000_946_352_946),
+    unit(&["pt", "pint"], Volume, 0.000_473_176_473),
+    unit(&["cup"], Volume, 0.000_236_588_236_5),
+    unit(
+        &["fl oz", "floz", "fluid ounce"],
+        Volume,
+        2.957_352_956_25e-5,
+    ),
+    unit(&["tbsp", "tablespoon"], Volume, 1.478_676_478_125e-5),
+    unit(&["tsp", "teaspoon"], Volume, 4.928_921_593_75e-6),
+    // Time (second)
+    unit(&["s", "sec", "second"], Time, 1.0),
+    unit(&["ms", "millisecond"], Time, 0.001),
+    unit(&["µs", "us", "microsecond"], Time, 1e-6),
+    unit(&["ns", "nanosecond"], Time, 1e-9),
+    unit(&["min", "minute"], Time, 60.0),
+    unit(&["h", "hr", "hour"], Time, 3600.0),
+    unit(&["day", "d"], Time, 86_400.0),
+    unit(&["week", "wk"], Time, 604_800.0),
+    // Average Gregorian month and year.
+    unit(&["month", "mo"], Time, 2_629_746.0),
+    unit(&["year", "yr", "y"], Time, 31_556_952.0),
+    // Speed (metre per second)
+    unit(&["m/s", "mps", "meters per second"], Speed, 1.0),
+    unit(
+        &["km/h", "kph", "kmh", "kilometers per hour"],
+        Speed,
+        1.0 / 3.6,
+    ),
+    unit(&["mph", "miles per hour"], Speed, 0.447_04),
+    unit(&["kn", "knot", "kt"], Speed, 1852.0 / 3600.0),
+    unit(&["ft/s", "fps", "feet per second"], Speed, 0.3048),
+    // Area (square metre)
+    unit(
+        &["m²", "m2", "sq m", "square meter", "square metre"],
+        Area,
+        1.0,
+    ),
+    unit(&["km²", "km2", "sq km", "square kilometer"], Area, 1e6),
+    unit(&["cm²", "cm2", "sq cm", "square centimeter"], Area, 1e-4),
+    unit(&["ha", "hectare"], Area, 1e4),
+    unit(&["acre", "ac"], Area, 4046.8564224),
+    unit(
+        &["ft²", "ft2", "sq ft", "square foot", "square feet"],
+        Area,
+        0.092_903_04,
+    ),
+    unit(&["in²", "in2", "sq in", "square inch"], Area, 0.000_645_16),
+    unit(&["yd²", "yd2", "sq yd", "square yard"], Area, 0.836_127_36),
+    unit(
+        &["mi²", "mi2", "sq mi", "square mile"],
+        Area,
+        2_589_988.110_336,
+    ),
+    // Data (byte)
+    unit(&["B", "byte"], Data, 1.0),
+    unit(&["bit", "b"], Data, 0.125),
+    unit(&["KB", "kB", "kb", "kilobyte"], Data, 1e3),
+    unit(&["MB", "mb", "megabyte"], Data, 1e6),
+    unit(&["GB", "gb", "gigabyte"], Data, 1e9),
+    unit(&["TB", "tb", "terabyte"], Data, 1e12),
+    unit(&["PB", "pb", "petabyte"], Data, 1e15),
+    unit(&["KiB", "kib", "kibibyte"], Data, 1024.0),
+    unit(&["MiB", "mib", "mebibyte"], Data, 1_048_576.0),
+    unit(&["GiB", "gib", "gibibyte"], Data, 1_073_741_824.0),
+    unit(&["TiB", "tib", "tebibyte"], Data, 1_099_511_627_776.0),
+    unit(&["kbit", "kilobit"], Data, 125.0),
+    unit(&["Mbit", "megabit"], Data, 125_000.0),
+    unit(&["Gbit", "gigabit"], Data, 125_000_000.0),
+    // Energy (joule)
+    unit(&["J", "joule"], Energy, 1.0),
+    unit(&["kJ", "kilojoule"], Energy, 1e3),
+    unit(&["MJ", "megajoule"], Energy, 1e6),
+    unit(&["cal", "calorie"], Energy, 4.184),
+    unit(&["kcal", "Cal", "kilocalorie"], Energy, 4184.0),
+    unit(&["Wh", "watt hour"], Energy, 3600.0),
+    unit(&["kWh", "kilowatt hour"], Energy, 3.6e6),
+    unit(&["BTU", "btu"], Energy, 1055.05585262),
+    unit(&["eV", "electronvolt"], Energy, 1.602_176_634e-19),
+    // Power (watt)
+    unit(&["W", "watt"], Power, 1.0),
+    unit(&["kW", "kilowatt"], Power, 1e3),
+    unit(&["MW", "megawatt"], Power, 1e6),
+    unit(&["hp", "horsepower"], Power, 745.699_871_582_27),
+    // Pressure (pascal)
+    unit(&["Pa", "pascal"], Pressure, 1.0),
+    unit(&["hPa", "hectopascal"], Pressure, 100.0),
+    unit(&["kPa", "kilopascal"], Pressure, 1e3),
+    unit(&["MPa", "megapascal"], Pressure, 1e6),
+    unit(&["bar"], Pressure, 1e5),
+    unit(&["mbar", "millibar"], Pressure, 100.0),
+    unit(&["psi"], Pressure, 6894.757293168),
+    unit(&["atm", "atmosphere"], Pressure, 101_325.0),
+    unit(&["mmHg"], Pressure, 133.322_387_415),
+    unit(&["inHg"], Pressure, 3386.389),
+    // Temperature (kelvin)
+    Unit {
+        names: &["°C", "C", "celsius", "degC", "degrees celsius"],
+        dimension: Temperature,
+        factor: 1.0,
+        offset: 273.15,
+    },
+    Unit {
+        names: &["°F", "F", "fahrenheit", "degF", "degrees fahrenheit"],
+        dimension: Temperature,
+        factor: 5.0 / 9.0,
+        offset: 459.67 * 5.0 / 9.0,
+    },
+    unit(&["K", "kelvin"], Temperature, 1.0),
+];
+
+/// Find a unit by name: exact match first, then case-insensitive, then
+/// without a plural "s"/"es".
+pub fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = name.trim();
+    let name = name.strip_prefix("degrees ").unwrap_or(name);
+    if name.is_empty() {
+        return None;
+    }
+    let exact = |n: &str| UNITS.iter().find(|u| u.names.contains(&n));
+    let folded = |n: &str| {
+        UNITS.iter().find(|u| {
+            u.names
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(n))
+        })
+    };
+    exact(name)
+        .or_else(|| folded(name))
+        .or_else(|| {
+            let singular = name.strip_suffix("es").filter(|s| s.len() > 2)?;
+            folded(singular)
+        })
+        .or_else(|| folded(name.strip_suffix('s').filter(|s| s.len() > 1)?))
+}
+
+/// Convert `value` between two units of the same dimension.
+pub fn convert(value: f64, from: &Unit, to: &Unit) -> Result<f64> {
+    if from.dimension != to.dimension {
+        return Err(ZeptoError::Tool(format!(
+            "Cannot convert {} ({}) to {} ({})",
+            from.names[0],
+            from.dimension.name(),
+            to.names[0],
+            to.dimension.name()
+        )));
+    }
+    let base = value * from.factor + from.offset;
+    Ok((base - to.offset) / to.factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conv(value: f64, from: &str, to: &str) -> f64 {
+        convert(value, find_unit(from).unwrap(), find_unit(to).unwrap()).unwrap()
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_find_unit() {
+        assert_eq!(find_unit("miles").unwrap().names[0], "mi");
+        assert_eq!(find_unit("Inches").unwrap().names[0], "in");
+        assert_eq!(find_unit("km/h").unwrap().dimension, Speed);
+        assert_eq!(find_unit("sq ft").unwrap().names[0], "ft²");
+        assert_eq!(find_unit("degrees Fahrenheit").unwrap().names[0], "°F");
+        // Exact case wins: bits vs bytes.
+        assert_eq!(find_unit("b").unwrap().names[0], "bit");
+        assert_eq!(find_unit("B").unwrap().names[0], "B");
+        assert!(find_unit("usd").is_none());
+        assert!(find_unit("").is_none());
+    }
+
+    #[test]
+    fn test_convert() {
+        assert!(close(conv(10.0, "km", "mi"), 6.213_711_922_373_34));
+        assert!(close(conv(100.0, "°C", "°F"), 212.0));
+        assert!(close(conv(-40.0, "F", "C"), -40.0));
+        assert!(close(conv(0.0, "C", "K"), 273.15));
+        assert!(close(conv(1.0, "GiB", "MB"), 1073.741824));
+        assert!(close(conv(1.0, "lb", "g"), 453.592_37));
+        assert!(close(conv(60.0, "mph", "km/h"), 96.560_64));
+        assert!(close(conv(1.0, "cup", "ml"), 236.588_236_5));
+        assert!(close(conv(2.0, "hours", "min"), 120.0));
+    }
+
+    #[test]
+    fn test_convert_rejects_mixed_dimensions() {
+        let err = convert(1.0, find_unit("kg").unwrap(), find_unit("m").unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Cannot convert kg (mass) to m (length)"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,1422 @@
+//! Calendar tool for CalDAV servers and Google Calendar.
+//!
+//! One tool, two backends: Google Calendar (REST API v3) and any CalDAV
+//! server (Nextcloud, Fastmail, iCloud, Radicale, ...). Actions: `list`,
+//! `create`, `update`, `freebusy`.
+//!
+//! Google access tokens are resolved on every call through the auth
+//! subsystem: the encrypted token store written by
+//! `zeptoclaw auth login google` is refreshed when close to expiry, with
+//! `tools.google.access_token` as a static fallback.
+//!
+//! Times accept RFC 3339 or natural language ("tomorrow at 3pm", "next
+//! friday"). CalDAV times carrying a `TZID` are read as local time.
+
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use reqwest::Method;
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::auth::refresh::ensure_fresh_token;
+use crate::auth::store::TokenStore;
+use crate::config::{CalendarProvider, Config};
+use crate::cron::natural::{self, When};
+use crate::error::{Result, ZeptoError};
+use crate::security::encryption::resolve_master_key;
+use crate::utils::http::{Client, RequestBuilder};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+const GOOGLE_CALENDAR_API: &str = "https://www.googleapis.com/calendar/v3";
+
+/// Event length used by `create` when no end time is given.
+const DEFAULT_EVENT_MINUTES: i64 = 60;
+
+/// Actions that modify external state and require user confirmation.
+const DANGEROUS_ACTIONS: &[&str] = &["create", "update"];
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A calendar event, normalized across backends.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    /// Google event ID, or CalDAV resource href.
+    pub id: String,
+    pub summary: String,
+    pub start: EventTime,
+    pub end: Option<EventTime>,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    /// Whether the event blocks time (`TRANSP:TRANSPARENT` does not).
+    pub busy: bool,
+}
+
+/// Start or end of an event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventTime {
+    At(DateTime<FixedOffset>),
+    AllDay(NaiveDate),
+}
+
+impl EventTime {
+    /// The instant this time begins, all-day dates at local midnight.
+    fn instant(&self) -> Option<DateTime<FixedOffset>> {
+        match self {
+            EventTime::At(at) => Some(*at),
+            EventTime::AllDay(date) => Local
+                .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+                .earliest()
+                .map(|dt| dt.fixed_offset()),
+        }
+    }
+
+    fn display(&self) -> String {
+        match self {
+            EventTime::At(at) => at
+                .with_timezone(&Local)
+                .format("%a %Y-%m-%d %H:%M")
+                .to_string(),
+            EventTime::AllDay(date) => format!("{} (all day)", date.format("%a %Y-%m-%d")),
+        }
+    }
+}
+
+/// Source of Google OAuth access tokens.
+pub enum GoogleAuth {
+    /// A fixed bearer token.
+    Static(String),
+    /// The encrypted auth store, refreshed on demand, with an optional
+    /// static fallback token.
+    Store {
+        store: TokenStore,
+        fallback: Option<String>,
+    },
+}
+
+impl GoogleAuth {
+    async fn access_token(&self) -> Result<String> {
+        match self {
+            GoogleAuth::Static(token) => Ok(token.clone()),
+            GoogleAuth::Store { store, fallback } => {
+                match ensure_fresh_token(store, "google").await {
+                    Ok(token) => Ok(token),
+                    Err(e) => fallback.clone().ok_or_else(|| {
+                        ZeptoError::Tool(format!(
+                        "Google Calendar is not authorized ({}). Run `zeptoclaw auth login google`",
+                        e
+                    ))
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Calendar backend.
+pub enum CalendarBackend {
+    Google {
+        auth: GoogleAuth,
+        calendar_id: String,
+    },
+    CalDav {
+        /// Calendar collection URL, always ending in `/`.
+        url: Url,
+        username: Option<String>,
+        password: Option<String>,
+    },
+}
+
+/// Tool for reading and writing calendar events.
+pub struct CalendarTool {
+    client: Client,
+    backend: CalendarBackend,
+    lookahead_days: u32,
+    max_results: u32,
+}
+
+impl CalendarTool {
+    /// Google Calendar backend for `calendar_id` ("primary" for the user's
+    /// main calendar).
+    pub fn google(auth: GoogleAuth, calendar_id: &str) -> Self {
+        Self::with_backend(CalendarBackend::Google {
+            auth,
+            calendar_id: calendar_id.to_string(),
+        })
+    }
+
+    /// CalDAV backend for the calendar collection at `url`.
+    pub fn caldav(url: &str, username: Option<&str>, password: Option<&str>) -> Result<Self> {
+        let mut url = Url::parse(url.trim())
+            .map_err(|e| ZeptoError::Config(format!("Invalid CalDAV URL '{}': {}", url, e)))?;
+        if !url.path().ends_with('/') {
+            let path = format!("{}/", url.path());
+            url.set_path(&path);
+        }
+        Ok(Self::with_backend(CalendarBackend::CalDav {
+            url,
+            username: username.map(str::to_string),
+            password: password.map(str::to_string),
+        }))
+    }
+
+    fn with_backend(backend: CalendarBackend) -> Self {
+        Self {
+            client: crate::utils::http::client(),
+            backend,
+            lookahead_days: 7,
+            max_results: 25,
+        }
+    }
+
+    /// Build from `tools.calendar`. Returns `None` when the selected
+    /// backend has no credentials: no CalDAV URL, or no stored Google OAuth
+    /// token and no `tools.google.access_token`.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        let cal = &config.tools.calendar;
+        let tool = match cal.provider {
+            CalendarProvider::Caldav => {
+                let Some(url) = cal.caldav_url.as_deref().filter(|u| !u.trim().is_empty()) else {
+                    return Ok(None);
+                };
+                Self::caldav(
+                    url,
+                    cal.caldav_username.as_deref(),
+                    cal.caldav_password.as_deref(),
+                )?
+            }
+            CalendarProvider::Google => {
+                let fallback = config
+                    .tools
+                    .google
+                    .access_token
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string);
+                let store = resolve_master_key(false)
+                    .ok()
+                    .map(TokenStore::new)
+                    .filter(|store| matches!(store.load("google"), Ok(Some(_))));
+                let auth = match (store, fallback) {
+                    (Some(store), fallback) => GoogleAuth::Store { store, fallback },
+                    (None, Some(token)) => GoogleAuth::Static(token),
+                    (None, None) => return Ok(None),
+                };
+                let calendar_id = cal
+                    .calendar_id
+                    .as_deref()
+                    .filter(|c| !c.trim().is_empty())
+                    .unwrap_or(&config.tools.google.default_calendar);
+                Self::google(auth, calendar_id)
+            }
+        };
+        Ok(Some(
+            tool.with_lookahead_days(cal.lookahead_days)
+                .with_max_results(cal.max_results),
+        ))
+    }
+
+    /// Default window for `list`, in days.
+    pub fn with_lookahead_days(mut self, days: u32) -> Self {
+        self.lookahead_days = days.max(1);
+        self
+    }
+
+    /// Maximum events returned by `list`.
+    pub fn with_max_results(mut self, max: u32) -> Self {
+        self.max_results = max.max(1);
+        self
+    }
+
+    /// Return `true` when the given action modifies external state.
+    pub fn is_dangerous_action(action: &str) -> bool {
+        DANGEROUS_ACTIONS.contains(&action)
+    }
+}
+
+#[async_trait]
+impl Tool for CalendarTool {
+    fn name(&self) -> &str {
+        "calendar"
+    }
+
+    fn description(&self) -> &str {
+        "Read and manage calendar events (Google Calendar or CalDAV). Actions: list (upcoming events), create, update, freebusy. Times accept RFC3339 or natural language like 'tomorrow at 3pm'."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Calendar"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "create", "update", "freebusy"],
+                    "description": "Calendar operation to perform."
+                },
+                "time_min": {
+                    "type": "string",
+                    "description": "Start of the time window (default: now). For list and freebusy."
+                },
+                "time_max": {
+                    "type": "string",
+                    "description": "End of the time window (default: lookahead window for list; required for freebusy)."
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Only list events whose title, location, or description contains this text."
+                },
+                "event_id": {
+                    "type": "string",
+                    "description": "Event ID as returned by list. Required for update."
+                },
+                "summary": {
+                    "type": "string",
+                    "description": "Event title. Required for create."
+                },
+                "start": {
+                    "type": "string",
+                    "description": "Event start. Required for create."
+                },
+                "end": {
+                    "type": "string",
+                    "description": "Event end (default: one hour after start)."
+                },
+                "all_day": {
+                    "type": "boolean",
+                    "description": "Create an all-day event on the start date."
+                },
+                "location": {
+                    "type": "string",
+                    "description": "Event location."
+                },
+                "description": {
+                    "type": "string",
+                    "description": "Event notes."
+                },
+                "attendees": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Attendee email addresses (create only)."
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ZeptoError::Tool("Missing 'action' parameter".to_string()))?;
+
+        let output = match action {
+            "list" => self.list(&args).await?,
+            "create" => self.create(&args).await?,
+            "update" => self.update(&args).await?,
+            "freebusy" => self.freebusy(&args).await?,
+            other => {
+                return Err(ZeptoError::Tool(format!("Unknown action '{}'", other)));
+            }
+        };
+
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Actions
+// ---------------------------------------------------------------------------
+
+impl CalendarTool {
+    async fn list(&self, args: &Value) -> Result<String> {
+        let time_min = optional_time(args, "time_min")?.unwrap_or_else(now);
+        let time_max = optional_time(args, "time_max")?
+            .unwrap_or_else(|| time_min + Duration::days(self.lookahead_days as i64));
+        let query = str_arg(args, "query").map(str::to_lowercase);
+
+        let mut events = self.fetch_events(time_min, time_max).await?;
+        if let Some(q) = &query {
+            events.retain(|e| {
+                [
+                    Some(&e.summary),
+                    e.location.as_ref(),
+                    e.description.as_ref(),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|field| field.to_lowercase().contains(q))
+            });
+        }
+        events.truncate(self.max_results as usize);
+
+        if events.is_empty() {
+            return Ok(format!(
+                "No events between {} and {}.",
+                EventTime::At(time_min).display(),
+                EventTime::At(time_max).display()
+            ));
+        }
+        let mut lines = vec![format!("{} event(s):", events.len())];
+        lines.extend(events.iter().map(format_event));
+        Ok(lines.join("\n"))
+    }
+
+    async fn create(&self, args: &Value) -> Result<String> {
+        let summary = str_arg(args, "summary")
+            .ok_or_else(|| ZeptoError::Tool("Missing 'summary' for create".to_string()))?;
+        let start = optional_time(args, "start")?
+            .ok_or_else(|| ZeptoError::Tool("Missing 'start' for create".to_string()))?;
+        let all_day = args
+            .get("all_day")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let (start, end) = if all_day {
+            let day = start.with_timezone(&Local).date_naive();
+            (
+                EventTime::AllDay(day),
+                EventTime::AllDay(day + Duration::days(1)),
+            )
+        } else {
+            let end = optional_time(args, "end")?
+                .unwrap_or(start + Duration::minutes(DEFAULT_EVENT_MINUTES));
+            if end <= start {
+                return Err(ZeptoError::Tool("'end' must be after 'start'".to_string()));
+            }
+            (EventTime::At(start), EventTime::At(end))
+        };
+        let attendees: Vec<String> = args
+            .get("attendees")
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let event = CalendarEvent {
+            id: String::new(),
+            summary: summary.to_string(),
+            start,
+            end: Some(end),
+            location: str_arg(args, "location").map(str::to_string),
+            description: str_arg(args, "description").map(str::to_string),
+            busy: true,
+        };
+
+        let id = match &self.backend {
+            CalendarBackend::Google { auth, calendar_id } => {
+                let mut body = google_event_body(&event);
+                if !attendees.is_empty() {
+                    body["attendees"] = attendees
+                        .iter()
+                        .map(|email| json!({ "email": email }))
+                        .collect();
+                }
+                let url = google_url(&["calendars", calendar_id.as_str(), "events"])?;
+                let created = self
+                    .google_json(auth, self.client.post(url).json(&body))
+                    .await?;
+                created
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            }
+            CalendarBackend::CalDav { url, .. } => {
+                let uid = uuid::Uuid::new_v4().to_string();
+                let href = url
+                    .join(&format!("{}.ics", uid))
+                    .map_err(|e| ZeptoError::Tool(format!("Invalid event URL: {}", e)))?;
+                let ics = build_ics(&uid, &event, &attendees);
+                self.caldav_send(
+                    self.caldav_request(Method::PUT, href.clone())
+                        .header("Content-Type", "text/calendar; charset=utf-8")
+                        .header("If-None-Match", "*")
+                        .body(ics),
+                )
+                .await?;
+                href.path().to_string()
+            }
+        };
+
+        Ok(format!(
+            "Created event '{}' at {} [id: {}]",
+            event.summary,
+            event.start.display(),
+            id
+        ))
+    }
+
+    async fn update(&self, args: &Value) -> Result<String> {
+        let event_id = str_arg(args, "event_id")
+            .ok_or_else(|| ZeptoError::Tool("Missing 'event_id' for update".to_string()))?;
+        let summary = str_arg(args, "summary");
+        let start = optional_time(args, "start")?;
+        let end = optional_time(args, "end")?;
+        let location = str_arg(args, "location");
+        let description = str_arg(args, "description");
+        if summary.is_none()
+            && start.is_none()
+            && end.is_none()
+            && location.is_none()
+            && description.is_none()
+        {
+            return Err(ZeptoError::Tool(
+                "Nothing to update: pass summary, start, end, location, or description".to_string(),
+            ));
+        }
+
+        match &self.backend {
+            CalendarBackend::Google { auth, calendar_id } => {
+                let mut patch = json!({});
+                if let Some(summary) = summary {
+                    patch["summary"] = json!(summary);
+                }
+                if let Some(start) = start {
+                    patch["start"] = json!({ "dateTime": start.to_rfc3339() });
+                }
+                if let Some(end) = end {
+                    patch["end"] = json!({ "dateTime": end.to_rfc3339() });
+                }
+                if let Some(location) = location {
+                    patch["location"] = json!(location);
+                }
+                if let Some(description) = description {
+                    patch["description"] = json!(description);
+                }
+                let url = google_url(&["calendars", calendar_id.as_str(), "events", event_id])?;
+                self.google_json(auth, self.client.patch(url).json(&patch))
+                    .await?;
+            }
+            CalendarBackend::CalDav { url, .. } => {
+                let href = url
+                    .join(event_id)
+                    .map_err(|e| ZeptoError::Tool(format!("Invalid event id: {}", e)))?;
+                let response = self
+                    .caldav_send(self.caldav_request(Method::GET, href.clone()))
+                    .await?;
+                let etag = response
+                    .headers()
+                    .get("ETag")
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string);
+                let mut ics = response
+                    .text()
+                    .await
+                    .map_err(|e| ZeptoError::Tool(format!("CalDAV read failed: {}", e)))?;
+
+                if let Some(summary) = summary {
+                    ics = set_ical_property(&ics, "SUMMARY", &escape_ical_text(summary));
+                }
+                if let Some(start) = start {
+                    ics = set_ical_property(&ics, "DTSTART", &ical_utc(start));
+                }
+                if let Some(end) = end {
+                    ics = set_ical_property(&ics, "DTEND", &ical_utc(end));
+                }
+                if let Some(location) = location {
+                    ics = set_ical_property(&ics, "LOCATION", &escape_ical_text(location));
+                }
+                if let Some(description) = description {
+                    ics = set_ical_property(&ics, "DESCRIPTION", &escape_ical_text(description));
+                }
+                ics = set_ical_property(&ics, "DTSTAMP", &ical_utc(now()));
+
+                let mut put = self
+                    .caldav_request(Method::PUT, href)
+                    .header("Content-Type", "text/calendar; charset=utf-8")
+                    .body(ics);
+                if let Some(etag) = etag {
+                    put = put.header("If-Match", etag);
+                }
+                self.caldav_send(put).await?;
+            }
+        }
+
+        Ok(format!("Updated event {}", event_id))
+    }
+
+    async fn freebusy(&self, args: &Value) -> Result<String> {
+        let time_min = optional_time(args, "time_min")?.unwrap_or_else(now);
+        let time_max = optional_time(args, "time_max")?
+            .ok_or_else(|| ZeptoError::Tool("Missing 'time_max' for freebusy".to_string()))?;
+        if time_max <= time_min {
+            return Err(ZeptoError::Tool(
+                "'time_max' must be after 'time_min'".to_string(),
+            ));
+        }
+
+        let busy = match &self.backend {
+            CalendarBackend::Google { auth, calendar_id } => {
+                let body = json!({
+                    "timeMin": time_min.to_rfc3339(),
+                    "timeMax": time_max.to_rfc3339(),
+                    "items": [{ "id": calendar_id }],
+                });
+                let url = google_url(&["freeBusy"])?;
+                let response = self
+                    .google_json(auth, self.client.post(url).json(&body))
+                    .await?;
+                response["calendars"][calendar_id.as_str()]["busy"]
+                    .as_array()
+                    .map(|blocks| {
+                        blocks
+                            .iter()
+                            .filter_map(|b| {
+                                let start = DateTime::parse_from_rfc3339(b["start"].as_str()?);
+                                let end = DateTime::parse_from_rfc3339(b["end"].as_str()?);
+                                Some((start.ok()?, end.ok()?))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            CalendarBackend::CalDav { .. } => self
+                .fetch_events(time_min, time_max)
+                .await?
+                .iter()
+                .filter(|e| e.busy)
+                .filter_map(|e| {
+                    let start = e.start.instant()?;
+                    let end = e.end.as_ref().and_then(EventTime::instant)?;
+                    Some((start, end))
+                })
+                .collect(),
+        };
+
+        Ok(format_free_busy(busy, time_min, time_max))
+    }
+
+    /// Events overlapping `[time_min, time_max)`, sorted by start.
+    async fn fetch_events(
+        &self,
+        time_min: DateTime<FixedOffset>,
+        time_max: DateTime<FixedOffset>,
+    ) -> Result<Vec<CalendarEvent>> {
+        let mut events = match &self.backend {
+            CalendarBackend::Google { auth, calendar_id } => {
+                let mut url = google_url(&["calendars", calendar_id.as_str(), "events"])?;
+                url.query_pairs_mut()
+                    .append_pair("timeMin", &time_min.to_rfc3339())
+                    .append_pair("timeMax", &time_max.to_rfc3339())
+                    .append_pair("singleEvents", "true")
+                    .append_pair("orderBy", "startTime")
+                    .append_pair("maxResults", "250");
+                let body = self.google_json(auth, self.client.get(url)).await?;
+                body.get("items")
+                    .and_then(Value::as_array)
+                    .map(|items| items.iter().filter_map(parse_google_event).collect())
+                    .unwrap_or_default()
+            }
+            CalendarBackend::CalDav { url, .. } => {
+                let report = self
+                    .caldav_request(
+                        Method::from_bytes(b"REPORT").expect("valid method"),
+                        url.clone(),
+                    )
+                    .header("Depth", "1")
+                    .header("Content-Type", "application/xml; charset=utf-8")
+                    .body(calendar_query_body(time_min, time_max));
+                let xml = self
+                    .caldav_send(report)
+                    .await?
+                    .text()
+                    .await
+                    .map_err(|e| ZeptoError::Tool(format!("CalDAV read failed: {}", e)))?;
+                parse_multistatus(&xml, "calendar-data")?
+                    .into_iter()
+                    .flat_map(|(href, ics)| parse_ics_events(&href, &ics))
+                    .collect::<Vec<_>>()
+            }
+        };
+        events.sort_by_key(|e| e.start.instant());
+        Ok(events)
+    }
+
+    async fn google_json(&self, auth: &GoogleAuth, request: RequestBuilder) -> Result<Value> {
+        let token = auth.access_token().await?;
+        let response = request
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Google Calendar request failed: {}", e)))?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        if !status.is_success() {
+            return Err(ZeptoError::Tool(format!(
+                "Google Calendar API error {}: {}",
+                status, body
+            )));
+        }
+        Ok(body)
+    }
+
+    fn caldav_request(&self, method: Method, url: Url) -> RequestBuilder {
+        let request = self.client.request(method, url);
+        match &self.backend {
+            CalendarBackend::CalDav {
+                username: Some(user),
+                password,
+                ..
+            } => request.basic_auth(user, password.as_deref()),
+            _ => request,
+        }
+    }
+
+    async fn caldav_send(&self, request: RequestBuilder) -> Result<reqwest::Response> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("CalDAV request failed: {}", e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ZeptoError::Tool(format!(
+                "CalDAV error {}: {}",
+                status,
+                body.chars().take(500).collect::<String>()
+            )));
+        }
+        Ok(response)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn now() -> DateTime<FixedOffset> {
+    Local::now().fixed_offset()
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Parse a time argument as RFC 3339 or natural language.
+fn optional_time(args: &Value, key: &str) -> Result<Option<DateTime<FixedOffset>>> {
+    let Some(raw) = str_arg(args, key) else {
+        return Ok(None);
+    };
+    if let Ok(at) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(Some(at));
+    }
+    match natural::parse_when_local(raw)? {
+        When::Once(at) => Ok(Some(at)),
+        When::Recurring { .. } => Err(ZeptoError::Tool(format!(
+            "'{}' for '{}' must be a single time, not a recurrence",
+            raw, key
+        ))),
+    }
+}
+
+fn google_url(segments: &[&str]) -> Result<Url> {
+    let mut url = Url::parse(GOOGLE_CALENDAR_API).expect("valid base URL");
+    url.path_segments_mut()
+        .map_err(|_| ZeptoError::Tool("Invalid Google Calendar URL".to_string()))?
+        .extend(segments);
+    Ok(url)
+}
+
+fn format_event(event: &CalendarEvent) -> String {
+    let mut line = format!("- {}", event.start.display());
+    if let Some(EventTime::At(end)) = &event.end {
+        line.push_str(&format!(" – {}", end.with_timezone(&Local).format("%H:%M")));
+    }
+    line.push_str(&format!(": {}", event.summary));
+    if let Some(location) = &event.location {
+        line.push_str(&format!(" @ {}", location));
+    }
+    line.push_str(&format!(" [id: {}]", event.id));
+    line
+}
+
+/// Merge overlapping busy blocks and list busy and free periods in the window.
+fn format_free_busy(
+    mut busy: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+    time_min: DateTime<FixedOffset>,
+    time_max: DateTime<FixedOffset>,
+) -> String {
+    busy.retain(|(start, end)| end > start && *end > time_min && *start < time_max);
+    busy.sort();
+    let mut merged: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)> = Vec::new();
+    for (start, end) in busy {
+        let start = start.max(time_min);
+        let end = end.min(time_max);
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let span = |a: &DateTime<FixedOffset>, b: &DateTime<FixedOffset>| {
+        format!(
+            "- {} – {}",
+            a.with_timezone(&Local).format("%a %Y-%m-%d %H:%M"),
+            b.with_timezone(&Local).format("%a %Y-%m-%d %H:%M")
+        )
+    };
+    let mut free = Vec::new();
+    let mut cursor = time_min;
+    for (start, end) in &merged {
+        if *start > cursor {
+            free.push(span(&cursor, start));
+        }
+        cursor = cursor.max(*end);
+    }
+    if cursor < time_max {
+        free.push(span(&cursor, &time_max));
+    }
+
+    let mut lines = Vec::new();
+    if merged.is_empty() {
+        lines.push("Busy: none".to_string());
+    } else {
+        lines.push("Busy:".to_string());
+        lines.extend(merged.iter().map(|(a, b)| span(a, b)));
+    }
+    if free.is_empty() {
+        lines.push("Free: none".to_string());
+    } else {
+        lines.push("Free:".to_string());
+        lines.extend(free);
+    }
+    lines.join("\n")
+}
+
+// ---------------------------------------------------------------------------
+// Google Calendar
+// ---------------------------------------------------------------------------
+
+fn parse_google_time(value: &Value) -> Option<EventTime> {
+    if let Some(at) = value.get("dateTime").and_then(Value::as_str) {
+        return DateTime::parse_from_rfc3339(at).ok().map(EventTime::At);
+    }
+    let date = value.get("date").and_then(Value::as_str)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .map(EventTime::AllDay)
+}
+
+fn parse_google_event(item: &Value) -> Option<CalendarEvent> {
+    if item.get("status").and_then(Value::as_str) == Some("cancelled") {
+        return None;
+    }
+    let text = |key: &str| {
+        item.get(key)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    Some(CalendarEvent {
+        id: text("id")?,
+        summary: text("summary").unwrap_or_else(|| "(no title)".to_string()),
+        start: parse_google_time(item.get("start")?)?,
+        end: item.get("end").and_then(parse_google_time),
+        location: text("location"),
+        description: text("description"),
+        busy: text("transparency").as_deref() != Some("transparent"),
+    })
+}
+
+fn google_event_body(event: &CalendarEvent) -> Value {
+    let time = |t: &EventTime| match t {
+        EventTime::At(at) => json!({ "dateTime": at.to_rfc3339() }),
+        EventTime::AllDay(date) => json!({ "date": date.format("%Y-%m-%d").to_string() }),
+    };
+    let mut body = json!({
+        "summary": event.summary,
+        "start": time(&event.start),
+    });
+    if let Some(end) = &event.end {
+        body["end"] = time(end);
+    }
+    if let Some(location) = &event.location {
+        body["location"] = json!(location);
+    }
+    if let Some(description) = &event.description {
+        body["description"] = json!(description);
+    }
+    body
+}
+
+// ---------------------------------------------------------------------------
+// CalDAV / iCalendar
+// ---------------------------------------------------------------------------
+
+fn ical_utc(at: DateTime<FixedOffset>) -> String {
+    at.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn calendar_query_body(time_min: DateTime<FixedOffset>, time_max: DateTime<FixedOffset>) -> String {
+    let (start, end) = (ical_utc(time_min), ical_utc(time_max));
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <d:getetag/>
+    <c:calendar-data><c:expand start="{start}" end="{end}"/></c:calendar-data>
+  </d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR">
+      <c:comp-filter name="VEVENT">
+        <c:time-range start="{start}" end="{end}"/>
+      </c:comp-filter>
+    </c:comp-filter>
+  </c:filter>
+</c:calendar-query>"#
+    )
+}
+
+/// Extract `(href, data)` pairs from a WebDAV multistatus body, where
+/// `data_element` names the payload property (`calendar-data` for CalDAV,
+/// `address-data` for CardDAV).
+pub(crate) fn parse_multistatus(xml: &str, data_element: &str) -> Result<Vec<(String, String)>> {
+    use quick_xml::escape::resolve_xml_entity;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    #[derive(PartialEq)]
+    enum Field {
+        None,
+        Href,
+        Data,
+    }
+
+    fn target<'a>(field: &Field, href: &'a mut String, data: &'a mut String) -> &'a mut String {
+        if *field == Field::Href {
+            href
+        } else {
+            data
+        }
+    }
+
+    let mut reader = Reader::from_str(xml);
+    let mut out = Vec::new();
+    let mut field = Field::None;
+    let (mut href, mut data) = (String::new(), String::new());
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                b"response" => {
+                    href.clear();
+                    data.clear();
+                }
+                b"href" if href.is_empty() => field = Field::Href,
+                name if name == data_element.as_bytes() => field = Field::Data,
+                _ => {}
+            },
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"href" => field = Field::None,
+                name if name == data_element.as_bytes() => field = Field::None,
+                b"response" if !data.trim().is_empty() => {
+                    out.push((href.trim().to_string(), std::mem::take(&mut data)));
+                }
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) if field != Field::None => {
+                let text = e
+                    .xml_content()
+                    .map_err(|e| ZeptoError::Tool(format!("XML decode error: {e}")))?;
+                target(&field, &mut href, &mut data).push_str(&text);
+            }
+            Ok(Event::CData(ref e)) if field != Field::None => {
+                target(&field, &mut href, &mut data).push_str(&String::from_utf8_lossy(e));
+            }
+            Ok(Event::GeneralRef(ref e)) if field != Field::None => {
+                let out = target(&field, &mut href, &mut data);
+                if let Ok(Some(ch)) = e.resolve_char_ref() {
+                    out.push(ch);
+                } else {
+                    e.xml_content()
+                        .map(|d| resolve_xml_entity(d.as_ref()).map(|r| out.push_str(r)))
+                        .map_err(|e| ZeptoError::Tool(format!("XML decode error: {e}")))?;
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(ZeptoError::Tool(format!("XML parse error: {e}"))),
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(out)
+}
+
+/// Unfold RFC 5545 content lines (continuations start with space or tab).
+pub(crate) fn unfold_ical(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in ics.split('\n') {
+        let raw = raw.trim_end_matches('\r');
+        if let Some(cont) = raw.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(cont);
+                continue;
+            }
+        }
+        if !raw.is_empty() {
+            lines.push(raw.to_string());
+        }
+    }
+    lines
+}
+
+/// Fold a content line at 75 octets.
+pub(crate) fn fold_ical_line(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += len;
+    }
+    out
+}
+
+/// Split `NAME;PARAMS:VALUE` into (upper-case name, params, value).
+pub(crate) fn split_ical_line(line: &str) -> Option<(String, &str, &str)> {
+    let colon = line.find(':')?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let (name, params) = head.split_once(';').unwrap_or((head, ""));
+    Some((name.to_ascii_uppercase(), params, value))
+}
+
+pub(crate) fn escape_ical_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+pub(crate) fn unescape_ical_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+fn parse_ical_time(params: &str, value: &str) -> Option<EventTime> {
+    let value = value.trim();
+    if params.to_ascii_uppercase().contains("VALUE=DATE") && !value.contains('T') {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(EventTime::AllDay);
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(EventTime::At(Utc.from_utc_datetime(&naive).fixed_offset()));
+    }
+    if value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(EventTime::AllDay);
+    }
+    // Floating or TZID-qualified: read as local time.
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| EventTime::At(dt.fixed_offset()))
+}
+
+/// Parse the VEVENTs in an iCalendar object. Nested components (VALARM)
+/// are skipped.
+fn parse_ics_events(href: &str, ics: &str) -> Vec<CalendarEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<CalendarEvent> = None;
+    let mut nested = 0usize;
+
+    for line in unfold_ical(ics) {
+        let Some((name, params, value)) = split_ical_line(&line) else {
+            continue;
+        };
+        match (name.as_str(), value.to_ascii_uppercase().as_str()) {
+            ("BEGIN", "VEVENT") if current.is_none() => {
+                current = Some(CalendarEvent {
+                    id: href.to_string(),
+                    summary: "(no title)".to_string(),
+                    start: EventTime::AllDay(NaiveDate::MIN),
+                    end: None,
+                    location: None,
+                    description: None,
+                    busy: true,
+                });
+            }
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", "VEVENT") if nested == 0 => {
+                if let Some(event) = current.take() {
+                    if event.start != EventTime::AllDay(NaiveDate::MIN) {
+                        events.push(event);
+                    }
+                }
+            }
+            ("END", _) if nested > 0 => nested -= 1,
+            _ => {
+                let Some(event) = current.as_mut().filter(|_| nested == 0) else {
+                    continue;
+                };
+                match name.as_str() {
+                    "SUMMARY" => event.summary = unescape_ical_text(value),
+                    "LOCATION" => event.location = Some(unescape_ical_text(value)),
+                    "DESCRIPTION" => event.description = Some(unescape_ical_text(value)),
+                    "DTSTART" => {
+                        if let Some(start) = parse_ical_time(params, value) {
+                            event.start = start;
+                        }
+                    }
+                    "DTEND" => event.end = parse_ical_time(params, value),
+                    "TRANSP" => event.busy = !value.eq_ignore_ascii_case("TRANSPARENT"),
+                    _ => {}
+                }
+            }
+        }
+    }
+    events
+}
+
+fn build_ics(uid: &str, event: &CalendarEvent, attendees: &[String]) -> String {
+    let time = |name: &str, t: &EventTime| match t {
+        EventTime::At(at) => format!("{}:{}", name, ical_utc(*at)),
+        EventTime::AllDay(date) => format!("{};VALUE=DATE:{}", name, date.format("%Y%m%d")),
+    };
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//ZeptoClaw//Calendar Tool//EN".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", uid),
+        format!("DTSTAMP:{}", ical_utc(now())),
+        time("DTSTART", &event.start),
+    ];
+    if let Some(end) = &event.end {
+        lines.push(time("DTEND", end));
+    }
+    lines.push(format!("SUMMARY:{}", escape_ical_text(&event.summary)));
+    if let Some(location) = &event.location {
+        lines.push(format!("LOCATION:{}", escape_ical_text(location)));
+    }
+    if let Some(description) = &event.description {
+        lines.push(format!("DESCRIPTION:{}", escape_ical_text(description)));
+    }
+    for email in attendees {
+        lines.push(format!("ATTENDEE;RSVP=TRUE:mailto:{}", email));
+    }
+    lines.push("END:VEVENT".to_string());
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out: String = lines
+        .iter()
+        .map(|l| fold_ical_line(l))
+        .collect::<Vec<_>>()
+        .join("\r\n");
+    out.push_str("\r\n");
+    out
+}
+
+/// Replace (or add) property `name` on the first VEVENT of `ics`. Existing
+/// parameters are dropped, so the new value must be self-contained (UTC
+/// times, escaped text).
+fn set_ical_property(ics: &str, name: &str, value: &str) -> String {
+    let mut out: Vec<Cow<'_, str>> = Vec::new();
+    let mut in_event = false;
+    let mut nested = 0usize;
+    let mut done = false;
+    let replacement = format!("{}:{}", name, value);
+    let lines = unfold_ical(ics);
+
+    for line in &lines {
+        let parsed = split_ical_line(line);
+        let (prop, comp) = parsed
+            .as_ref()
+            .map(|(n, _, v)| (n.as_str(), v.to_ascii_uppercase()))
+            .unwrap_or(("", String::new()));
+        if !done && in_event {
+            match (prop, comp.as_str()) {
+                ("BEGIN", _) => nested += 1,
+                ("END", "VEVENT") if nested == 0 => {
+                    out.push(Cow::Owned(replacement.clone()));
+                    done = true;
+                    in_event = false;
+                }
+                ("END", _) => nested = nested.saturating_sub(1),
+                (p, _) if nested == 0 && p == name => {
+                    out.push(Cow::Owned(replacement.clone()));
+                    done = true;
+                    continue;
+                }
+                _ => {}
+            }
+        } else if !done && prop == "BEGIN" && comp == "VEVENT" {
+            in_event = true;
+        }
+        out.push(Cow::Borrowed(line.as_str()));
+    }
+
+    let mut result = out
+        .iter()
+        .map(|l| fold_ical_line(l))
+        .collect::<Vec<_>>()
+        .join("\r\n");
+    result.push_str("\r\n");
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool() -> CalendarTool {
+        CalendarTool::google(GoogleAuth::Static("token".to_string()), "primary")
+    }
+
+    fn utc(s: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(s).unwrap()
+    }
+
+    const SAMPLE_ICS: &str = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\n\
+        UID:abc\r\nDTSTART:20260304T100000Z\r\nDTEND:20260304T110000Z\r\n\
+        SUMMARY:Team sync\\, weekly\r\nLOCATION:Room 4\r\nDESCRIPTION:Agenda:\\n- demo\r\n \
+        s\r\nBEGIN:VALARM\r\nSUMMARY:alarm\r\nEND:VALARM\r\nEND:VEVENT\r\n\
+        BEGIN:VEVENT\r\nUID:def\r\nDTSTART;VALUE=DATE:20260305\r\nDTEND;VALUE=DATE:20260306\r\n\
+        SUMMARY:Holiday\r\nTRANSP:TRANSPARENT\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+    #[test]
+    fn test_tool_metadata() {
+        let tool = tool();
+        assert_eq!(tool.name(), "calendar");
+        assert_eq!(tool.category(), ToolCategory::NetworkWrite);
+        assert_eq!(tool.parameters()["required"], json!(["action"]));
+        assert!(CalendarTool::is_dangerous_action("create"));
+        assert!(CalendarTool::is_dangerous_action("update"));
+        assert!(!CalendarTool::is_dangerous_action("list"));
+        assert!(!CalendarTool::is_dangerous_action("freebusy"));
+    }
+
+    #[test]
+    fn test_from_config_requires_credentials() {
+        let mut config = Config::default();
+        config.tools.calendar.provider = CalendarProvider::Caldav;
+        assert!(CalendarTool::from_config(&config).unwrap().is_none());
+
+        config.tools.calendar.caldav_url = Some("https://dav.example.com/cal".to_string());
+        config.tools.calendar.max_results = 5;
+        let tool = CalendarTool::from_config(&config).unwrap().unwrap();
+        assert_eq!(tool.max_results, 5);
+
+        config.tools.calendar.provider = CalendarProvider::Google;
+        config.tools.google.access_token = Some("token".to_string());
+        assert!(CalendarTool::from_config(&config).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_caldav_url_gets_trailing_slash() {
+        let tool =
+            CalendarTool::caldav("https://dav.example.com/cal/personal", None, None).unwrap();
+        match tool.backend {
+            CalendarBackend::CalDav { url, .. } => {
+                assert_eq!(url.as_str(), "https://dav.example.com/cal/personal/")
+            }
+            _ => panic!("expected caldav backend"),
+        }
+        assert!(CalendarTool::caldav("not a url", None, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_argument_validation() {
+        let tool = tool();
+        let ctx = ToolContext::new();
+        let err = |args: Value| {
+            let tool = &tool;
+            let ctx = &ctx;
+            async move { tool.execute(args, ctx).await.unwrap_err().to_string() }
+        };
+        assert!(err(json!({})).await.contains("Missing 'action'"));
+        assert!(err(json!({"action": "delete"}))
+            .await
+            .contains("Unknown action"));
+        assert!(
+            err(json!({"action": "create", "start": "2026-03-04T10:00:00Z"}))
+                .await
+                .contains("summary")
+        );
+        assert!(err(json!({"action": "create", "summary": "x"}))
+            .await
+            .contains("start"));
+        assert!(err(json!({
+            "action": "create", "summary": "x",
+            "start": "2026-03-04T10:00:00Z", "end": "2026-03-04T09:00:00Z"
+        }))
+        .await
+        .contains("after"));
+        assert!(err(json!({"action": "update", "summary": "x"}))
+            .await
+            .contains("event_id"));
+        assert!(err(json!({"action": "update", "event_id": "e1"}))
+            .await
+            .contains("Nothing to update"));
+        assert!(err(json!({"action": "freebusy"}))
+            .await
+            .contains("time_max"));
+        assert!(err(json!({"action": "list", "time_min": "every day"}))
+            .await
+            .contains("recurrence"));
+    }
+
+    #[test]
+    fn test_parse_ics_events() {
+        let events = parse_ics_events("/cal/abc.ics", SAMPLE_ICS);
+        assert_eq!(events.len(), 2);
+
+        let sync = &events[0];
+        assert_eq!(sync.id, "/cal/abc.ics");
+        assert_eq!(sync.summary, "Team sync, weekly");
+        assert_eq!(sync.location.as_deref(), Some("Room 4"));
+        assert_eq!(sync.description.as_deref(), Some("Agenda:\n- demos"));
+        assert_eq!(sync.start, EventTime::At(utc("2026-03-04T10:00:00Z")));
+        assert!(sync.busy);
+
+        let holiday = &events[1];
+        assert_eq!(
+            holiday.start,
+            EventTime::AllDay(NaiveDate::from_ymd_opt(2026, 3, 5).unwrap())
+        );
+        assert!(!holiday.busy);
+    }
+
+    #[test]
+    fn test_parse_multistatus() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/cal/abc.ics</d:href>
+    <d:propstat><d:prop>
+      <d:getetag>"1"</d:getetag>
+      <cal:calendar-data>BEGIN:VCALENDAR&#13;
+BEGIN:VEVENT&#13;
+DTSTART:20260304T100000Z&#13;
+SUMMARY:Tom &amp; Jerry&#13;
+END:VEVENT&#13;
+END:VCALENDAR&#13;
+</cal:calendar-data>
+    </d:prop></d:propstat>
+  </d:response>
+  <d:response><d:href>/cal/</d:href></d:response>
+</d:multistatus>"#;
+        let parsed = parse_multistatus(xml, "calendar-data").unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].0, "/cal/abc.ics");
+        let events = parse_ics_events(&parsed[0].0, &parsed[0].1);
+        assert_eq!(events[0].summary, "Tom & Jerry");
+    }
+
+    #[test]
+    fn test_build_ics_roundtrip() {
+        let event = CalendarEvent {
+            id: String::new(),
+            summary: "Lunch; with team, maybe".to_string(),
+            start: EventTime::At(utc("2026-03-04T12:00:00Z")),
+            end: Some(EventTime::At(utc("2026-03-04T13:00:00Z"))),
+            location: None,
+            description: Some("x".repeat(200)),
+            busy: true,
+        };
+        let ics = build_ics("uid-1", &event, &["a@example.com".to_string()]);
+        assert!(ics.contains("UID:uid-1\r\n"));
+        assert!(ics.contains("DTSTART:20260304T120000Z"));
+        assert!(ics.contains("ATTENDEE;RSVP=TRUE:mailto:a@example.com"));
+        assert!(ics.lines().all(|l| l.trim_end_matches('\r').len() <= 75));
+
+        let parsed = parse_ics_events("id", &ics);
+        assert_eq!(parsed[0].summary, event.summary);
+        assert_eq!(parsed[0].description, event.description);
+        assert_eq!(parsed[0].end, event.end);
+    }
+
+    #[test]
+    fn test_set_ical_property_replaces_and_inserts() {
+        let updated = set_ical_property(SAMPLE_ICS, "SUMMARY", "Renamed");
+        let updated = set_ical_property(&updated, "DTSTART", "20260304T150000Z");
+        let updated = set_ical_property(&updated, "URL", "https://example.com");
+        let events = parse_ics_events("id", &updated);
+        assert_eq!(events[0].summary, "Renamed");
+        assert_eq!(events[0].start, EventTime::At(utc("2026-03-04T15:00:00Z")));
+        // Only the first event and not the nested alarm is touched.
+        assert_eq!(events[1].summary, "Holiday");
+        assert!(updated.contains("SUMMARY:alarm"));
+        assert_eq!(updated.matches("URL:https://example.com").count(), 1);
+    }
+
+    #[test]
+    fn test_parse_google_event() {
+        let item = json!({
+            "id": "evt1",
+            "summary": "Dentist",
+            "start": { "dateTime": "2026-03-04T10:00:00+08:00" },
+            "end": { "dateTime": "2026-03-04T11:00:00+08:00" },
+            "location": "Clinic"
+        });
+        let event = parse_google_event(&item).unwrap();
+        assert_eq!(event.id, "evt1");
+        assert_eq!(event.start, EventTime::At(utc("2026-03-04T02:00:00Z")));
+        assert!(parse_google_event(&json!({"id": "x", "status": "cancelled",
+            "start": {"date": "2026-03-04"}}))
+        .is_none());
+        let body = google_event_body(&event);
+        assert_eq!(body["location"], "Clinic");
+        assert!(body["start"]["dateTime"].is_string());
+    }
+
+    #[test]
+    fn test_format_free_busy_merges_blocks() {
+        let min = utc("2026-03-04T09:00:00Z");
+        let max = utc("2026-03-04T17:00:00Z");
+        let out = format_free_busy(
+            vec![
+                (utc("2026-03-04T10:00:00Z"), utc("2026-03-04T11:00:00Z")),
+                (utc("2026-03-04T10:30:00Z"), utc("2026-03-04T12:00:00Z")),
+                (utc("2026-03-04T16:00:00Z"), utc("2026-03-04T18:00:00Z")),
+            ],
+            min,
+            max,
+        );
+        let busy = out.split("Free:").next().unwrap();
+        assert_eq!(busy.matches("\n- ").count(), 2);
+        let free = out.split("Free:").nth(1).unwrap();
+        assert_eq!(free.matches("\n- ").count(), 2);
+        assert!(format_free_busy(Vec::new(), min, max).contains("Busy: none"));
+    }
+}
@@ -0,0 +1,612 @@
+//! Chart tool (feature-gated behind `tool-chart`).
+//!
+//! Renders bar, line and pie charts from data the agent supplies with the
+//! plotters SVG backend. The SVG is rasterized to PNG by `rsvg-convert` when
+//! it is installed; otherwise the SVG itself is sent. The chart is saved
+//! into the workspace media store and attached to the reply, so Telegram
+//! and Slack show it next to the answer.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::timeout;
+use tracing::warn;
+
+use crate::bus::{MediaAttachment, MediaType};
+use crate::error::{Result, ZeptoError};
+use crate::session::media::MediaStore;
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Default chart width in pixels.
+const DEFAULT_WIDTH: u32 = 800;
+
+/// Default chart height in pixels.
+const DEFAULT_HEIGHT: u32 = 500;
+
+/// Minimum chart dimension.
+const MIN_DIMENSION: u32 = 200;
+
+/// Maximum chart dimension.
+const MAX_DIMENSION: u32 = 2000;
+
+/// Maximum number of labels (points per series or pie slices).
+const MAX_POINTS: usize = 500;
+
+/// Maximum number of series; one palette colour each.
+const MAX_SERIES: usize = PALETTE.len();
+
+/// Maximum number of x-axis labels drawn; longer axes skip some.
+const MAX_X_LABELS: usize = 24;
+
+/// SVG-to-PNG converter from librsvg.
+const RSVG_CONVERT: &str = "rsvg-convert";
+
+/// Upper bound for rasterizing one chart.
+const RASTERIZE_TIMEOUT: Duration = Duration::from_secs(30);
+
+const SVG_MIME: &str = "image/svg+xml";
+
+/// Series colours, in order.
+const PALETTE: [RGBColor; 10] = [
+    RGBColor(31, 119, 180),
+    RGBColor(255, 127, 14),
+    RGBColor(44, 160, 44),
+    RGBColor(214, 39, 40),
+    RGBColor(148, 103, 189),
+    RGBColor(140, 86, 75),
+    RGBColor(227, 119, 194),
+    RGBColor(127, 127, 127),
+    RGBColor(188, 189, 34),
+    RGBColor(23, 190, 207),
+];
+
+type DrawResult = std::result::Result<(), Box<dyn std::error::Error>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ChartKind {
+    Bar,
+    Line,
+    Pie,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ChartFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Series {
+    #[serde(default)]
+    name: Option<String>,
+    values: Vec<f64>,
+}
+
+/// Chart description as supplied in the tool arguments.
+#[derive(Debug, Clone, Deserialize)]
+struct ChartSpec {
+    #[serde(rename = "type")]
+    kind: ChartKind,
+    #[serde(default)]
+    title: Option<String>,
+    labels: Vec<String>,
+    series: Vec<Series>,
+    #[serde(default)]
+    x_label: Option<String>,
+    #[serde(default)]
+    y_label: Option<String>,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+    #[serde(default)]
+    format: ChartFormat,
+}
+
+impl ChartSpec {
+    fn parse(args: Value) -> Result<Self> {
+        let spec: Self = serde_json::from_value(args)
+            .map_err(|e| ZeptoError::Tool(format!("Invalid chart arguments: {}", e)))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.labels.is_empty() {
+            return Err(ZeptoError::Tool("Chart needs at least one label".into()));
+        }
+        if self.labels.len() > MAX_POINTS {
+            return Err(ZeptoError::Tool(format!(
+                "Too many labels: {} (max {})",
+                self.labels.len(),
+                MAX_POINTS
+            )));
+        }
+        if self.series.is_empty() || self.series.len() > MAX_SERIES {
+            return Err(ZeptoError::Tool(format!(
+                "Chart needs 1 to {} series, got {}",
+                MAX_SERIES,
+                self.series.len()
+            )));
+        }
+        for (i, series) in self.series.iter().enumerate() {
+            if series.values.len() != self.labels.len() {
+                return Err(ZeptoError::Tool(format!(
+                    "Series '{}' has {} values for {} labels",
+                    self.series_name(i),
+                    series.values.len(),
+                    self.labels.len()
+                )));
+            }
+            if series.values.iter().any(|v| !v.is_finite()) {
+                return Err(ZeptoError::Tool(format!(
+                    "Series '{}' contains a non-finite value",
+                    self.series_name(i)
+                )));
+            }
+        }
+        if self.kind == ChartKind::Pie {
+            if self.series.len() != 1 {
+                return Err(ZeptoError::Tool(
+                    "A pie chart takes exactly one series".into(),
+                ));
+            }
+            let values = &self.series[0].values;
+            if values.iter().any(|v| *v < 0.0) || values.iter().sum::<f64>() <= 0.0 {
+                return Err(ZeptoError::Tool(
+                    "Pie values must be non-negative with a positive total".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (
+            self.width
+                .unwrap_or(DEFAULT_WIDTH)
+                .clamp(MIN_DIMENSION, MAX_DIMENSION),
+            self.height
+                .unwrap_or(DEFAULT_HEIGHT)
+                .clamp(MIN_DIMENSION, MAX_DIMENSION),
+        )
+    }
+
+    fn series_name(&self, index: usize) -> String {
+        self.series[index]
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("Series {}", index + 1))
+    }
+
+    /// Y range covering every value and zero, with a little headroom.
+    fn value_range(&self) -> (f64, f64) {
+        let values = self.series.iter().flat_map(|s| s.values.iter().copied());
+        let (min, max) = values.fold((0.0f64, 0.0f64), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        if min == max {
+            return (0.0, 1.0);
+        }
+        let pad = (max - min) * 0.05;
+        (
+            if min < 0.0 { min - pad } else { 0.0 },
+            if max > 0.0 { max + pad } else { 0.0 },
+        )
+    }
+
+    /// Label for an x-axis tick. Slot `i` is centred on `x = i`; ticks
+    /// between slots stay blank.
+    fn label_at(&self, x: f64) -> String {
+        if x < 0.0 || (x - x.round()).abs() > 1e-6 {
+            return String::new();
+        }
+        self.labels
+            .get(x.round() as usize)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Render `spec` as an SVG document.
+fn render_svg(spec: &ChartSpec) -> Result<String> {
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, spec.size()).into_drawing_area();
+        let drawn = match spec.kind {
+            ChartKind::Bar | ChartKind::Line => draw_xy(&root, spec),
+            ChartKind::Pie => draw_pie(&root, spec),
+        };
+        drawn
+            .and_then(|_| root.present().map_err(Into::into))
+            .map_err(|e| ZeptoError::Tool(format!("Failed to render chart: {}", e)))?;
+    }
+    Ok(svg)
+}
+
+/// Bar and line charts: one slot per label on the x axis.
+fn draw_xy(root: &DrawingArea<SVGBackend<'_>, Shift>, spec: &ChartSpec) -> DrawResult {
+    root.fill(&WHITE)?;
+    let slots = spec.labels.len() as f64;
+    let (lo, hi) = spec.value_range();
+
+    let mut builder = ChartBuilder::on(root);
+    builder
+        .margin(16)
+        .x_label_area_size(40)
+        .y_label_area_size(60);
+    if let Some(title) = &spec.title {
+        builder.caption(title, ("sans-serif", 24));
+    }
+    let mut chart = builder.build_cartesian_2d(-0.5..slots - 0.5, lo..hi)?;
+
+    let x_formatter = |x: &f64| spec.label_at(*x);
+    let mut mesh = chart.configure_mesh();
+    mesh.disable_x_mesh()
+        .x_labels(spec.labels.len().min(MAX_X_LABELS))
+        .x_label_formatter(&x_formatter);
+    if let Some(x_label) = &spec.x_label {
+        mesh.x_desc(x_label);
+    }
+    if let Some(y_label) = &spec.y_label {
+        mesh.y_desc(y_label);
+    }
+    mesh.draw()?;
+
+    let bar_width = 0.8 / spec.series.len() as f64;
+    for (s, series) in spec.series.iter().enumerate() {
+        let color = PALETTE[s % PALETTE.len()];
+        let drawn = match spec.kind {
+            ChartKind::Bar => {
+                chart.draw_series(series.values.iter().enumerate().map(|(i, &v)| {
+                    let x0 = i as f64 - 0.4 + s as f64 * bar_width;
+                    Rectangle::new([(x0, 0.0), (x0 + bar_width, v)], color.filled())
+                }))?
+            }
+            _ => {
+                let points = series
+                    .values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &v)| (i as f64, v));
+                chart.draw_series(points.clone().map(|p| Circle::new(p, 3, color.filled())))?;
+                chart.draw_series(LineSeries::new(points, color.stroke_width(2)))?
+            }
+        };
+        if spec.series.len() > 1 {
+            drawn.label(spec.series_name(s)).legend(move |(x, y)| {
+                Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled())
+            });
+        }
+    }
+
+    if spec.series.len() > 1 {
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+    }
+    Ok(())
+}
+
+fn draw_pie(root: &DrawingArea<SVGBackend<'_>, Shift>, spec: &ChartSpec) -> DrawResult {
+    root.fill(&WHITE)?;
+    let area = match &spec.title {
+        Some(title) => root.titled(title, ("sans-serif", 24))?,
+        None => root.clone(),
+    };
+    let (width, height) = area.dim_in_pixel();
+    let center = (width as i32 / 2, height as i32 / 2);
+    let radius = f64::from(width.min(height)) * 0.35;
+    let colors: Vec<RGBColor> = (0..spec.labels.len())
+        .map(|i| PALETTE[i % PALETTE.len()])
+        .collect();
+
+    let mut pie = Pie::new(
+        &center,
+        &radius,
+        &spec.series[0].values,
+        &colors,
+        &spec.labels,
+    );
+    pie.label_style(("sans-serif", 16).into_font().color(&BLACK));
+    pie.percentages(("sans-serif", radius * 0.08).into_font().color(&WHITE));
+    area.draw(&pie)?;
+    Ok(())
+}
+
+/// Convert an SVG chart to PNG with `rsvg-convert`.
+async fn rasterize(svg: &str) -> Result<Vec<u8>> {
+    let mut child = Command::new(RSVG_CONVERT)
+        .args(["--format", "png", "--background-color", "white"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| ZeptoError::Tool(format!("Failed to start {}: {}", RSVG_CONVERT, e)))?;
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| ZeptoError::Tool(format!("{} has no stdin", RSVG_CONVERT)))?;
+    stdin.write_all(svg.as_bytes()).await?;
+    drop(stdin);
+
+    let output = timeout(RASTERIZE_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| ZeptoError::Tool(format!("{} timed out", RSVG_CONVERT)))??;
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(ZeptoError::Tool(format!(
+            "{} failed: {}",
+            RSVG_CONVERT,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+/// Renders charts and sends them to the user as an image.
+pub struct ChartTool;
+
+#[async_trait]
+impl Tool for ChartTool {
+    fn name(&self) -> &str {
+        "chart"
+    }
+
+    fn description(&self) -> &str {
+        "Render a bar, line or pie chart from data and send it to the user as an image. \
+         Give one label per x position (or pie slice) and one or more series with a value \
+         per label. Use it for usage reports, trends and comparisons."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Render and send a chart image"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Messaging
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "type": {
+                    "type": "string",
+                    "enum": ["bar", "line", "pie"],
+                    "description": "Chart type"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Chart title, also used as the message caption"
+                },
+                "labels": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "X-axis labels, or slice names for a pie chart"
+                },
+                "series": {
+                    "type": "array",
+                    "description": "Data series; a pie chart takes exactly one",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string", "description": "Legend name" },
+                            "values": {
+                                "type": "array",
+                                "items": { "type": "number" },
+                                "description": "One value per label"
+                            }
+                        },
+                        "required": ["values"]
+                    }
+                },
+                "x_label": { "type": "string", "description": "X-axis title" },
+                "y_label": { "type": "string", "description": "Y-axis title" },
+                "width": {
+                    "type": "integer",
+                    "description": "Width in pixels (default: 800)"
+                },
+                "height": {
+                    "type": "integer",
+                    "description": "Height in pixels (default: 500)"
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["png", "svg"],
+                    "description": "Image format (default: png; falls back to svg without rsvg-convert)"
+                }
+            },
+            "required": ["type", "labels", "series"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let spec = ChartSpec::parse(args)?;
+        let svg = render_svg(&spec)?;
+
+        let (data, mime_type) = match spec.format {
+            ChartFormat::Svg => (svg.into_bytes(), SVG_MIME),
+            ChartFormat::Png => match rasterize(&svg).await {
+                Ok(png) => (png, "image/png"),
+                Err(e) => {
+                    warn!("Sending chart as SVG: {}", e);
+                    (svg.into_bytes(), SVG_MIME)
+                }
+            },
+        };
+        let (media_type, file_name) = if mime_type == SVG_MIME {
+            (MediaType::Document, "chart.svg")
+        } else {
+            (MediaType::Image, "chart.png")
+        };
+
+        let path = match &ctx.workspace {
+            Some(workspace) => Some(
+                MediaStore::new(PathBuf::from(workspace))
+                    .save(&data, mime_type)
+                    .await?,
+            ),
+            None => None,
+        };
+        let summary = json!({
+            "sent": true,
+            "type": spec.kind,
+            "format": file_name.rsplit('.').next(),
+            "bytes": data.len(),
+            "path": path,
+        });
+        let caption = spec.title.clone().unwrap_or_else(|| "Chart".to_string());
+        let attachment = MediaAttachment::new(media_type)
+            .with_data(data)
+            .with_filename(file_name)
+            .with_mime_type(mime_type);
+
+        Ok(ToolOutput::split(summary.to_string(), caption).with_media(attachment))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar_args() -> Value {
+        json!({
+            "type": "bar",
+            "title": "Tokens per day",
+            "labels": ["Mon", "Tue", "Wed"],
+            "series": [
+                {"name": "input", "values": [120, 340, 90]},
+                {"name": "output", "values": [40, 80, 25.5]}
+            ],
+            "y_label": "tokens"
+        })
+    }
+
+    #[test]
+    fn test_parse_valid_spec() {
+        let spec = ChartSpec::parse(bar_args()).unwrap();
+        assert_eq!(spec.kind, ChartKind::Bar);
+        assert_eq!(spec.format, ChartFormat::Png);
+        assert_eq!(spec.size(), (DEFAULT_WIDTH, DEFAULT_HEIGHT));
+        assert_eq!(spec.series_name(1), "output");
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_specs() {
+        let mut mismatched = bar_args();
+        mismatched["series"][0]["values"] = json!([1, 2]);
+        assert!(ChartSpec::parse(mismatched).is_err());
+
+        let mut no_labels = bar_args();
+        no_labels["labels"] = json!([]);
+        assert!(ChartSpec::parse(no_labels).is_err());
+
+        let mut unknown = bar_args();
+        unknown["type"] = json!("radar");
+        assert!(ChartSpec::parse(unknown).is_err());
+
+        let pie_two_series = json!({
+            "type": "pie",
+            "labels": ["a", "b", "c"],
+            "series": [{"values": [1, 2, 3]}, {"values": [1, 2, 3]}]
+        });
+        assert!(ChartSpec::parse(pie_two_series).is_err());
+
+        let pie_negative = json!({
+            "type": "pie",
+            "labels": ["a", "b"],
+            "series": [{"values": [3, -1]}]
+        });
+        assert!(ChartSpec::parse(pie_negative).is_err());
+    }
+
+    #[test]
+    fn test_size_is_clamped() {
+        let mut args = bar_args();
+        args["width"] = json!(10);
+        args["height"] = json!(100_000);
+        let spec = ChartSpec::parse(args).unwrap();
+        assert_eq!(spec.size(), (MIN_DIMENSION, MAX_DIMENSION));
+    }
+
+    #[test]
+    fn test_value_range_includes_zero() {
+        let spec = ChartSpec::parse(bar_args()).unwrap();
+        let (lo, hi) = spec.value_range();
+        assert_eq!(lo, 0.0);
+        assert!(hi > 340.0);
+
+        let mut negative = bar_args();
+        negative["series"] = json!([{"values": [-10, -20, -5]}]);
+        let (lo, hi) = ChartSpec::parse(negative).unwrap().value_range();
+        assert!(lo < -20.0);
+        assert_eq!(hi, 0.0);
+    }
+
+    #[test]
+    fn test_label_at_slot_centres() {
+        let spec = ChartSpec::parse(bar_args()).unwrap();
+        assert_eq!(spec.label_at(0.0), "Mon");
+        assert_eq!(spec.label_at(2.0), "Wed");
+        assert_eq!(spec.label_at(0.5), "");
+        assert_eq!(spec.label_at(3.0), "");
+        assert_eq!(spec.label_at(-1.0), "");
+    }
+
+    #[test]
+    fn test_render_svg_for_each_kind() {
+        let bar = render_svg(&ChartSpec::parse(bar_args()).unwrap()).unwrap();
+        assert!(bar.starts_with("<svg"));
+        assert!(bar.contains("Tokens per day"));
+        assert!(bar.contains("Tue"));
+
+        let mut line_args = bar_args();
+        line_args["type"] = json!("line");
+        let line = render_svg(&ChartSpec::parse(line_args).unwrap()).unwrap();
+        assert!(line.contains("<polyline"));
+
+        let pie = render_svg(
+            &ChartSpec::parse(json!({
+                "type": "pie",
+                "labels": ["openai", "anthropic"],
+                "series": [{"values": [30, 70]}]
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(pie.contains("anthropic"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_attaches_chart() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        let mut args = bar_args();
+        args["format"] = json!("svg");
+
+        let output = ChartTool.execute(args, &ctx).await.unwrap();
+        assert_eq!(output.for_user.as_deref(), Some("Tokens per day"));
+        assert_eq!(output.media.len(), 1);
+        let media = &output.media[0];
+        assert_eq!(media.mime_type.as_deref(), Some(SVG_MIME));
+        assert_eq!(media.filename.as_deref(), Some("chart.svg"));
+
+        let summary: Value = serde_json::from_str(&output.for_llm).unwrap();
+        let path = summary["path"].as_str().unwrap();
+        assert!(path.ends_with(".svg"));
+        assert!(dir.path().join(path).exists());
+    }
+}
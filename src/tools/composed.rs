@@ -85,10 +85,7 @@ pub struct ComposedToolStore;
 impl ComposedToolStore {
     /// Default storage path: `~/.zeptoclaw/composed_tools.json`.
     pub fn default_path() -> PathBuf {
-        dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zeptoclaw")
-            .join("composed_tools.json")
+        crate::config::Config::data_dir().join("composed_tools.json")
     }
 
     /// Load all definitions from disk. Returns empty vec if file missing.
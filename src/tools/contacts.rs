@@ -0,0 +1,1063 @@
+//! Contacts tool — workspace-local address book.
+//!
+//! Contacts live in `<workspace>/contacts.json`. vCard files can be imported
+//! and exported, and an optional CardDAV address book can be pulled into the
+//! store (remote → local; local aliases and channel handles are kept).
+//!
+//! `resolve` turns a name or alias ("mom") into a destination for a
+//! channel: a digits-only phone number for `whatsapp_send`'s `to`, or a
+//! `channel` + `chat_id` pair for `message`.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::config::ContactsToolConfig;
+use crate::error::{Result, ZeptoError};
+use crate::security::{revalidate_path, validate_path_in_workspace};
+use crate::utils::http::Client;
+
+use super::calendar::{
+    escape_ical_text, fold_ical_line, parse_multistatus, split_ical_line, unescape_ical_text,
+    unfold_ical,
+};
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// vCard property carrying a channel handle, e.g. `X-ZEPTOCLAW-TELEGRAM`.
+const HANDLE_PROPERTY_PREFIX: &str = "X-ZEPTOCLAW-";
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A single contact.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Contact {
+    pub id: String,
+    pub name: String,
+    /// Nicknames and relationships ("mom", "boss").
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phones: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub emails: Vec<String>,
+    /// Channel name → chat id on that channel (e.g. `telegram` → `123456`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub handles: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// vCard UID, used to merge imports and CardDAV syncs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+}
+
+impl Contact {
+    /// One-line summary for tool output.
+    fn summary(&self) -> String {
+        let mut parts = vec![format!("[{}] {}", self.id, self.name)];
+        if !self.aliases.is_empty() {
+            parts.push(format!("aka {}", self.aliases.join(", ")));
+        }
+        if !self.phones.is_empty() {
+            parts.push(format!("tel {}", self.phones.join(", ")));
+        }
+        if !self.emails.is_empty() {
+            parts.push(format!("email {}", self.emails.join(", ")));
+        }
+        for (channel, handle) in &self.handles {
+            parts.push(format!("{} {}", channel, handle));
+        }
+        parts.join(" | ")
+    }
+
+    /// Merge an imported/synced copy into this contact. Remote name, phones,
+    /// emails, and notes win; aliases and handles are unioned.
+    fn merge_from(&mut self, other: Contact) {
+        self.name = other.name;
+        self.phones = other.phones;
+        self.emails = other.emails;
+        if other.notes.is_some() {
+            self.notes = other.notes;
+        }
+        for alias in other.aliases {
+            if !self.aliases.iter().any(|a| a.eq_ignore_ascii_case(&alias)) {
+                self.aliases.push(alias);
+            }
+        }
+        self.handles.extend(other.handles);
+        self.uid = other.uid.or(self.uid.take());
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ContactsStore
+// ---------------------------------------------------------------------------
+
+/// Persistent contacts store backed by a JSON file.
+#[derive(Debug)]
+pub struct ContactsStore {
+    contacts: Vec<Contact>,
+    path: PathBuf,
+    next_id: u64,
+}
+
+impl ContactsStore {
+    /// Open (or create on first save) the store at `path`.
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let contacts: Vec<Contact> = match std::fs::read_to_string(&path) {
+            Ok(data) if !data.trim().is_empty() => serde_json::from_str(&data)?,
+            Ok(_) => Vec::new(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let max_id = contacts
+            .iter()
+            .filter_map(|c| c.id.strip_prefix('c').and_then(|n| n.parse::<u64>().ok()))
+            .max()
+            .unwrap_or(0);
+        Ok(Self {
+            contacts,
+            path,
+            next_id: max_id + 1,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.contacts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contacts.is_empty()
+    }
+
+    pub fn all(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Contact> {
+        self.contacts.iter().find(|c| c.id == id)
+    }
+
+    /// Add a contact, assigning a fresh id, and persist.
+    pub fn add(&mut self, mut contact: Contact) -> Result<Contact> {
+        contact.id = format!("c{}", self.next_id);
+        self.next_id += 1;
+        self.contacts.push(contact.clone());
+        self.save()?;
+        Ok(contact)
+    }
+
+    /// Insert or merge contacts by vCard UID (falling back to an exact,
+    /// case-insensitive name match). Returns `(added, updated)` and persists.
+    pub fn upsert_all(&mut self, incoming: Vec<Contact>) -> Result<(usize, usize)> {
+        let (mut added, mut updated) = (0, 0);
+        for contact in incoming {
+            let existing = self
+                .contacts
+                .iter_mut()
+                .find(|c| match (&c.uid, &contact.uid) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => c.name.eq_ignore_ascii_case(&contact.name),
+                });
+            match existing {
+                Some(existing) => {
+                    existing.merge_from(contact);
+                    updated += 1;
+                }
+                None => {
+                    let mut contact = contact;
+                    contact.id = format!("c{}", self.next_id);
+                    self.next_id += 1;
+                    self.contacts.push(contact);
+                    added += 1;
+                }
+            }
+        }
+        self.save()?;
+        Ok((added, updated))
+    }
+
+    /// Apply `edit` to the contact with `id` and persist.
+    pub fn update(&mut self, id: &str, edit: impl FnOnce(&mut Contact)) -> Result<Option<Contact>> {
+        let Some(contact) = self.contacts.iter_mut().find(|c| c.id == id) else {
+            return Ok(None);
+        };
+        edit(contact);
+        let contact = contact.clone();
+        self.save()?;
+        Ok(Some(contact))
+    }
+
+    pub fn remove(&mut self, id: &str) -> Result<Option<Contact>> {
+        let Some(index) = self.contacts.iter().position(|c| c.id == id) else {
+            return Ok(None);
+        };
+        let removed = self.contacts.remove(index);
+        self.save()?;
+        Ok(Some(removed))
+    }
+
+    /// Contacts matching `query`, best first.
+    pub fn search(&self, query: &str) -> Vec<&Contact> {
+        let query = query.trim().to_lowercase();
+        let mut scored: Vec<(usize, &Contact)> = self
+            .contacts
+            .iter()
+            .map(|c| (match_score(c, &query), c))
+            .filter(|(score, _)| *score > 0)
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+        scored.into_iter().map(|(_, c)| c).collect()
+    }
+
+    /// Resolve a single contact by id, name, or alias. Errors when several
+    /// contacts match equally well.
+    pub fn find(&self, query: &str) -> Result<Option<&Contact>> {
+        if let Some(contact) = self.get(query.trim()) {
+            return Ok(Some(contact));
+        }
+        let query_lc = query.trim().to_lowercase();
+        let matches = self.search(&query_lc);
+        let Some(best) = matches.first().map(|c| match_score(c, &query_lc)) else {
+            return Ok(None);
+        };
+        let top: Vec<&Contact> = matches
+            .into_iter()
+            .take_while(|c| match_score(c, &query_lc) == best)
+            .collect();
+        if top.len() > 1 {
+            let names: Vec<String> = top
+                .iter()
+                .map(|c| format!("[{}] {}", c.id, c.name))
+                .collect();
+            return Err(ZeptoError::Tool(format!(
+                "'{}' matches several contacts: {}. Use the id instead",
+                query.trim(),
+                names.join(", ")
+            )));
+        }
+        Ok(top.into_iter().next())
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.contacts)?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+fn words(s: &str) -> impl Iterator<Item = &str> {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+}
+
+/// How well `contact` matches `query` (lowercase): exact name or alias beats
+/// prefix, which beats substring, which beats shared words.
+fn match_score(contact: &Contact, query: &str) -> usize {
+    if query.is_empty() {
+        return 0;
+    }
+    std::iter::once(&contact.name)
+        .chain(contact.aliases.iter())
+        .map(|candidate| {
+            let candidate = candidate.to_lowercase();
+            if candidate == query {
+                1000
+            } else if candidate.starts_with(query) {
+                500
+            } else if candidate.contains(query) {
+                250
+            } else {
+                words(query)
+                    .filter(|q| words(&candidate).any(|w| w == *q))
+                    .count()
+            }
+        })
+        .chain(
+            contact
+                .emails
+                .iter()
+                .filter(|e| e.to_lowercase() == query)
+                .map(|_| 1000),
+        )
+        .max()
+        .unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// Destinations
+// ---------------------------------------------------------------------------
+
+/// Normalize a phone number to digits with country code. A leading `+` or
+/// `00` marks an international number; a single leading `0` is replaced by
+/// `default_country_code` when one is configured.
+pub fn normalize_phone(phone: &str, default_country_code: Option<&str>) -> String {
+    let trimmed = phone.trim();
+    let digits: String = trimmed.chars().filter(char::is_ascii_digit).collect();
+    if trimmed.starts_with('+') {
+        return digits;
+    }
+    if let Some(rest) = digits.strip_prefix("00") {
+        return rest.to_string();
+    }
+    match (digits.strip_prefix('0'), default_country_code) {
+        (Some(rest), Some(cc)) => {
+            let cc: String = cc.chars().filter(char::is_ascii_digit).collect();
+            format!("{}{}", cc, rest)
+        }
+        _ => digits,
+    }
+}
+
+/// Destination for `contact` on `channel`: an explicit handle wins, then
+/// phone numbers for WhatsApp/SMS and addresses for email.
+pub fn destination(
+    contact: &Contact,
+    channel: &str,
+    default_country_code: Option<&str>,
+) -> Option<String> {
+    let channel = channel.trim().to_lowercase();
+    let channel = match channel.as_str() {
+        "whatsapp_web" | "whatsapp_cloud" => "whatsapp",
+        other => other,
+    };
+    if let Some(handle) = contact.handles.get(channel) {
+        return Some(handle.clone());
+    }
+    match channel {
+        "whatsapp" | "sms" | "phone" => contact
+            .phones
+            .first()
+            .map(|p| normalize_phone(p, default_country_code)),
+        "email" => contact.emails.first().cloned(),
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// vCard
+// ---------------------------------------------------------------------------
+
+/// Parse every `VCARD` in `text`. Cards without a name are skipped.
+pub fn parse_vcards(text: &str) -> Vec<Contact> {
+    let mut out = Vec::new();
+    let mut current: Option<Contact> = None;
+    let mut structured_name: Option<String> = None;
+
+    for line in unfold_ical(text) {
+        let Some((name, _params, value)) = split_ical_line(&line) else {
+            continue;
+        };
+        // Strip Apple-style group prefixes ("ITEM1.TEL").
+        let name = name.rsplit('.').next().unwrap_or(&name).to_string();
+        match name.as_str() {
+            "BEGIN" if value.eq_ignore_ascii_case("VCARD") => {
+                current = Some(Contact::default());
+                structured_name = None;
+            }
+            "END" if value.eq_ignore_ascii_case("VCARD") => {
+                if let Some(mut contact) = current.take() {
+                    if contact.name.is_empty() {
+                        contact.name = structured_name.take().unwrap_or_default();
+                    }
+                    if !contact.name.is_empty() {
+                        out.push(contact);
+                    }
+                }
+            }
+            _ => {
+                let Some(contact) = current.as_mut() else {
+                    continue;
+                };
+                let text = unescape_ical_text(value).trim().to_string();
+                if text.is_empty() {
+                    continue;
+                }
+                match name.as_str() {
+                    "FN" => contact.name = text,
+                    "N" => {
+                        // Family;Given;Additional;Prefix;Suffix
+                        let parts: Vec<&str> = value.split(';').map(str::trim).collect();
+                        let given = parts.get(1).copied().unwrap_or_default();
+                        let family = parts.first().copied().unwrap_or_default();
+                        let full = format!("{} {}", given, family);
+                        structured_name = Some(unescape_ical_text(full.trim()));
+                    }
+                    "NICKNAME" => contact.aliases.extend(
+                        value
+                            .split(',')
+                            .map(|a| unescape_ical_text(a).trim().to_string())
+                            .filter(|a| !a.is_empty()),
+                    ),
+                    "TEL" => contact
+                        .phones
+                        .push(text.trim_start_matches("tel:").to_string()),
+                    "EMAIL" => contact.emails.push(text),
+                    "NOTE" => contact.notes = Some(text),
+                    "UID" => contact.uid = Some(text),
+                    other => {
+                        if let Some(channel) = other.strip_prefix(HANDLE_PROPERTY_PREFIX) {
+                            contact.handles.insert(channel.to_lowercase(), text);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Serialize a contact as a vCard 3.0 card.
+pub fn to_vcard(contact: &Contact) -> String {
+    let mut lines = vec![
+        "BEGIN:VCARD".to_string(),
+        "VERSION:3.0".to_string(),
+        format!("FN:{}", escape_ical_text(&contact.name)),
+        format!("N:;{};;;", escape_ical_text(&contact.name)),
+    ];
+    if let Some(uid) = &contact.uid {
+        lines.push(format!("UID:{}", uid));
+    }
+    if !contact.aliases.is_empty() {
+        let aliases: Vec<String> = contact
+            .aliases
+            .iter()
+            .map(|a| escape_ical_text(a))
+            .collect();
+        lines.push(format!("NICKNAME:{}", aliases.join(",")));
+    }
+    for phone in &contact.phones {
+        lines.push(format!("TEL:{}", phone));
+    }
+    for email in &contact.emails {
+        lines.push(format!("EMAIL:{}", email));
+    }
+    for (channel, handle) in &contact.handles {
+        lines.push(format!(
+            "{}{}:{}",
+            HANDLE_PROPERTY_PREFIX,
+            channel.to_uppercase(),
+            escape_ical_text(handle)
+        ));
+    }
+    if let Some(notes) = &contact.notes {
+        lines.push(format!("NOTE:{}", escape_ical_text(notes)));
+    }
+    lines.push("END:VCARD".to_string());
+    let mut out = lines
+        .iter()
+        .map(|l| fold_ical_line(l))
+        .collect::<Vec<_>>()
+        .join("\r\n");
+    out.push_str("\r\n");
+    out
+}
+
+// ---------------------------------------------------------------------------
+// ContactsTool
+// ---------------------------------------------------------------------------
+
+/// Agent tool for the workspace address book.
+pub struct ContactsTool {
+    store: Mutex<ContactsStore>,
+    workspace: String,
+    config: ContactsToolConfig,
+    client: Client,
+}
+
+impl ContactsTool {
+    /// Open the store at `<workspace>/contacts.json`.
+    pub fn new(workspace: &str, config: ContactsToolConfig) -> Result<Self> {
+        let store = ContactsStore::open(PathBuf::from(workspace).join("contacts.json"))?;
+        Ok(Self::with_store(store, workspace, config))
+    }
+
+    /// Create a tool around an existing store. Useful for testing.
+    pub fn with_store(store: ContactsStore, workspace: &str, config: ContactsToolConfig) -> Self {
+        Self {
+            store: Mutex::new(store),
+            workspace: workspace.to_string(),
+            config,
+            client: crate::utils::http::client(),
+        }
+    }
+
+    fn country_code(&self) -> Option<&str> {
+        self.config
+            .default_country_code
+            .as_deref()
+            .filter(|c| !c.trim().is_empty())
+    }
+}
+
+#[async_trait]
+impl Tool for ContactsTool {
+    fn name(&self) -> &str {
+        "contacts"
+    }
+
+    fn description(&self) -> &str {
+        "Address book. Actions: search, add, update, remove, resolve (name or alias like 'mom' → destination for a channel, e.g. a phone number for whatsapp_send), import/export (vCard file in workspace), sync (pull from CardDAV)."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Contacts"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Memory
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["search", "add", "update", "remove", "resolve", "import", "export", "sync"],
+                    "description": "Action to perform"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Contact id, name, or alias (for search, update, remove, resolve). Empty search lists everyone."
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Full name (for add, update)"
+                },
+                "aliases": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Nicknames or relationships like 'mom' (for add, update)"
+                },
+                "phones": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Phone numbers, ideally with country code (for add, update)"
+                },
+                "emails": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Email addresses (for add, update)"
+                },
+                "handles": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" },
+                    "description": "Channel → chat id, e.g. {\"telegram\": \"123456\"} (for add, update)"
+                },
+                "notes": {
+                    "type": "string",
+                    "description": "Free-form notes (for add, update)"
+                },
+                "channel": {
+                    "type": "string",
+                    "description": "Target channel for resolve: whatsapp, telegram, discord, slack, email, ..."
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Workspace-relative .vcf file (for import, export)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ZeptoError::Tool("Missing 'action' argument".into()))?;
+
+        let output = match action {
+            "search" | "list" => self.execute_search(&args).await?,
+            "add" => self.execute_add(&args).await?,
+            "update" => self.execute_update(&args).await?,
+            "remove" => self.execute_remove(&args).await?,
+            "resolve" => self.execute_resolve(&args).await?,
+            "import" => self.execute_import(&args).await?,
+            "export" => self.execute_export(&args).await?,
+            "sync" => self.execute_sync().await?,
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown contacts action '{}'",
+                    other
+                )))
+            }
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn list_arg(args: &Value, key: &str) -> Option<Vec<String>> {
+    args.get(key).and_then(Value::as_array).map(|items| {
+        items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+}
+
+fn handles_arg(args: &Value) -> Option<BTreeMap<String, String>> {
+    args.get("handles").and_then(Value::as_object).map(|map| {
+        map.iter()
+            .filter_map(|(k, v)| Some((k.trim().to_lowercase(), v.as_str()?.trim().to_string())))
+            .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+            .collect()
+    })
+}
+
+impl ContactsTool {
+    async fn execute_search(&self, args: &Value) -> Result<String> {
+        let store = self.store.lock().await;
+        let results: Vec<&Contact> = match str_arg(args, "query") {
+            Some(query) => store.search(query),
+            None => store.all().iter().collect(),
+        };
+        if results.is_empty() {
+            return Ok("No contacts found".to_string());
+        }
+        let lines: Vec<String> = results.iter().map(|c| c.summary()).collect();
+        Ok(format!(
+            "{} contact{}:\n{}",
+            lines.len(),
+            if lines.len() == 1 { "" } else { "s" },
+            lines.join("\n")
+        ))
+    }
+
+    async fn execute_add(&self, args: &Value) -> Result<String> {
+        let name = str_arg(args, "name")
+            .ok_or_else(|| ZeptoError::Tool("Missing 'name' for contacts add".into()))?;
+        let contact = Contact {
+            name: name.to_string(),
+            aliases: list_arg(args, "aliases").unwrap_or_default(),
+            phones: list_arg(args, "phones").unwrap_or_default(),
+            emails: list_arg(args, "emails").unwrap_or_default(),
+            handles: handles_arg(args).unwrap_or_default(),
+            notes: str_arg(args, "notes").map(str::to_string),
+            ..Default::default()
+        };
+        let mut store = self.store.lock().await;
+        let contact = store.add(contact)?;
+        Ok(format!("Added contact {}", contact.summary()))
+    }
+
+    async fn execute_update(&self, args: &Value) -> Result<String> {
+        let query = str_arg(args, "query")
+            .ok_or_else(|| ZeptoError::Tool("Missing 'query' for contacts update".into()))?;
+        let mut store = self.store.lock().await;
+        let Some(id) = store.find(query)?.map(|c| c.id.clone()) else {
+            return Ok(format!("No contact matching '{}'", query));
+        };
+        let updated = store.update(&id, |contact| {
+            if let Some(name) = str_arg(args, "name") {
+                contact.name = name.to_string();
+            }
+            if let Some(aliases) = list_arg(args, "aliases") {
+                contact.aliases = aliases;
+            }
+            if let Some(phones) = list_arg(args, "phones") {
+                contact.phones = phones;
+            }
+            if let Some(emails) = list_arg(args, "emails") {
+                contact.emails = emails;
+            }
+            if let Some(handles) = handles_arg(args) {
+                contact.handles.extend(handles);
+            }
+            if let Some(notes) = str_arg(args, "notes") {
+                contact.notes = Some(notes.to_string());
+            }
+        })?;
+        Ok(match updated {
+            Some(contact) => format!("Updated contact {}", contact.summary()),
+            None => format!("No contact matching '{}'", query),
+        })
+    }
+
+    async fn execute_remove(&self, args: &Value) -> Result<String> {
+        let query = str_arg(args, "query")
+            .ok_or_else(|| ZeptoError::Tool("Missing 'query' for contacts remove".into()))?;
+        let mut store = self.store.lock().await;
+        let Some(id) = store.find(query)?.map(|c| c.id.clone()) else {
+            return Ok(format!("No contact matching '{}'", query));
+        };
+        let removed = store.remove(&id)?;
+        Ok(match removed {
+            Some(contact) => format!("Removed contact [{}] {}", contact.id, contact.name),
+            None => format!("No contact matching '{}'", query),
+        })
+    }
+
+    async fn execute_resolve(&self, args: &Value) -> Result<String> {
+        let query = str_arg(args, "query")
+            .ok_or_else(|| ZeptoError::Tool("Missing 'query' for contacts resolve".into()))?;
+        let store = self.store.lock().await;
+        let Some(contact) = store.find(query)? else {
+            return Ok(format!("No contact matching '{}'", query));
+        };
+
+        let Some(channel) = str_arg(args, "channel") else {
+            return Ok(contact.summary());
+        };
+        match destination(contact, channel, self.country_code()) {
+            Some(dest) => {
+                let hint = match channel.to_lowercase().as_str() {
+                    "whatsapp" | "whatsapp_cloud" => format!("whatsapp_send with to={}", dest),
+                    "email" | "sms" | "phone" => dest.clone(),
+                    other => format!("message with channel={} chat_id={}", other, dest),
+                };
+                Ok(format!(
+                    "{} on {}: {} (use {})",
+                    contact.name, channel, dest, hint
+                ))
+            }
+            None => Ok(format!(
+                "{} has no {} destination. Known: {}",
+                contact.name,
+                channel,
+                contact.summary()
+            )),
+        }
+    }
+
+    fn vcf_path(&self, args: &Value, must_exist: bool) -> Result<PathBuf> {
+        let path = str_arg(args, "path")
+            .ok_or_else(|| ZeptoError::Tool("Missing 'path' (a .vcf file)".into()))?;
+        let safe = validate_path_in_workspace(path, &self.workspace)?;
+        let ext = safe
+            .as_path()
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        if !matches!(ext.as_deref(), Some("vcf") | Some("vcard")) {
+            return Err(ZeptoError::Tool(
+                "Only .vcf/.vcard files are supported".into(),
+            ));
+        }
+        revalidate_path(safe.as_path(), &self.workspace)?;
+        if must_exist && !safe.as_path().exists() {
+            return Err(ZeptoError::Tool(format!("File not found: {}", path)));
+        }
+        Ok(safe.into_path_buf())
+    }
+
+    async fn execute_import(&self, args: &Value) -> Result<String> {
+        let path = self.vcf_path(args, true)?;
+        let text = tokio::fs::read_to_string(&path).await?;
+        let cards = parse_vcards(&text);
+        if cards.is_empty() {
+            return Ok("No vCards found in file".to_string());
+        }
+        let mut store = self.store.lock().await;
+        let (added, updated) = store.upsert_all(cards)?;
+        Ok(format!(
+            "Imported contacts: {} added, {} updated",
+            added, updated
+        ))
+    }
+
+    async fn execute_export(&self, args: &Value) -> Result<String> {
+        let path = self.vcf_path(args, false)?;
+        let store = self.store.lock().await;
+        let body: String = store.all().iter().map(to_vcard).collect();
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, body).await?;
+        Ok(format!(
+            "Exported {} contacts to {}",
+            store.len(),
+            path.display()
+        ))
+    }
+
+    async fn execute_sync(&self) -> Result<String> {
+        let url = self
+            .config
+            .carddav_url
+            .as_deref()
+            .filter(|u| !u.trim().is_empty())
+            .ok_or_else(|| {
+                ZeptoError::Tool(
+                    "CardDAV sync is not configured (tools.contacts.carddav_url)".into(),
+                )
+            })?;
+        let url = Url::parse(url.trim())
+            .map_err(|e| ZeptoError::Tool(format!("Invalid CardDAV URL: {}", e)))?;
+
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:addressbook-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:carddav">
+  <d:prop><d:getetag/><c:address-data/></d:prop>
+</c:addressbook-query>"#;
+        let mut request = self
+            .client
+            .request(Method::from_bytes(b"REPORT").expect("valid method"), url)
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(body);
+        if let Some(user) = self.config.carddav_username.as_deref() {
+            request = request.basic_auth(user, self.config.carddav_password.as_deref());
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("CardDAV request failed: {}", e)))?;
+        let status = response.status();
+        let xml = response
+            .text()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("CardDAV read failed: {}", e)))?;
+        if !status.is_success() {
+            return Err(ZeptoError::Tool(format!(
+                "CardDAV error {}: {}",
+                status,
+                xml.chars().take(500).collect::<String>()
+            )));
+        }
+
+        let cards: Vec<Contact> = parse_multistatus(&xml, "address-data")?
+            .iter()
+            .flat_map(|(_, vcf)| parse_vcards(vcf))
+            .collect();
+        let mut store = self.store.lock().await;
+        let (added, updated) = store.upsert_all(cards)?;
+        Ok(format!(
+            "Synced contacts from CardDAV: {} added, {} updated",
+            added, updated
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn temp_tool(config: ContactsToolConfig) -> (ContactsTool, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let workspace = dir.path().to_string_lossy().to_string();
+        (ContactsTool::new(&workspace, config).unwrap(), dir)
+    }
+
+    async fn run(tool: &ContactsTool, args: Value) -> String {
+        tool.execute(args, &ToolContext::new())
+            .await
+            .unwrap()
+            .for_llm
+    }
+
+    #[test]
+    fn test_normalize_phone() {
+        assert_eq!(normalize_phone("+60 12-345 6789", None), "60123456789");
+        assert_eq!(normalize_phone("0044 20 7946 0000", None), "442079460000");
+        assert_eq!(normalize_phone("012-3456789", Some("+60")), "60123456789");
+        assert_eq!(normalize_phone("012-3456789", None), "0123456789");
+    }
+
+    #[test]
+    fn test_store_find_and_persistence() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("contacts.json");
+        let mut store = ContactsStore::open(path.clone()).unwrap();
+        store
+            .add(Contact {
+                name: "Aisha Rahman".into(),
+                aliases: vec!["Mom".into()],
+                ..Default::default()
+            })
+            .unwrap();
+        store
+            .add(Contact {
+                name: "Aiman Rahman".into(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let store = ContactsStore::open(path).unwrap();
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.find("mom").unwrap().unwrap().id, "c1");
+        assert_eq!(store.find("aiman").unwrap().unwrap().id, "c2");
+        assert_eq!(store.find("c2").unwrap().unwrap().name, "Aiman Rahman");
+        assert!(store.find("rahman").is_err());
+        assert!(store.find("bob").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_destination_prefers_handles() {
+        let mut contact = Contact {
+            name: "Mom".into(),
+            phones: vec!["+60 12 345 6789".into()],
+            emails: vec!["mom@example.com".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            destination(&contact, "whatsapp", None).as_deref(),
+            Some("60123456789")
+        );
+        assert_eq!(
+            destination(&contact, "email", None).as_deref(),
+            Some("mom@example.com")
+        );
+        assert!(destination(&contact, "telegram", None).is_none());
+        contact.handles.insert("telegram".into(), "42".into());
+        assert_eq!(
+            destination(&contact, "Telegram", None).as_deref(),
+            Some("42")
+        );
+    }
+
+    #[test]
+    fn test_vcard_roundtrip() {
+        let vcf = "BEGIN:VCARD\r\nVERSION:3.0\r\nUID:u-1\r\nN:Rahman;Aisha;;;\r\n\
+            NICKNAME:Mom,Ibu\r\nitem1.TEL;TYPE=CELL:+60 12 345 6789\r\n\
+            EMAIL;TYPE=INTERNET:aisha@example.com\r\nX-ZEPTOCLAW-TELEGRAM:42\r\n\
+            NOTE:Call after 6pm\\, weekdays\r\nEND:VCARD\r\n\
+            BEGIN:VCARD\r\nVERSION:3.0\r\nEND:VCARD\r\n";
+        let cards = parse_vcards(vcf);
+        assert_eq!(cards.len(), 1);
+        let card = &cards[0];
+        assert_eq!(card.name, "Aisha Rahman");
+        assert_eq!(card.aliases, vec!["Mom", "Ibu"]);
+        assert_eq!(card.phones, vec!["+60 12 345 6789"]);
+        assert_eq!(card.handles.get("telegram").map(String::as_str), Some("42"));
+        assert_eq!(card.notes.as_deref(), Some("Call after 6pm, weekdays"));
+        assert_eq!(card.uid.as_deref(), Some("u-1"));
+
+        let reparsed = parse_vcards(&to_vcard(card));
+        assert_eq!(reparsed[0], *card);
+    }
+
+    #[test]
+    fn test_upsert_merges_by_uid() {
+        let dir = TempDir::new().unwrap();
+        let mut store = ContactsStore::open(dir.path().join("contacts.json")).unwrap();
+        store
+            .add(Contact {
+                name: "Bob".into(),
+                aliases: vec!["boss".into()],
+                handles: [("slack".to_string(), "U1".to_string())].into(),
+                uid: Some("u-2".into()),
+                ..Default::default()
+            })
+            .unwrap();
+        let (added, updated) = store
+            .upsert_all(vec![
+                Contact {
+                    name: "Robert Smith".into(),
+                    phones: vec!["+1 555 0100".into()],
+                    uid: Some("u-2".into()),
+                    ..Default::default()
+                },
+                Contact {
+                    name: "Carol".into(),
+                    ..Default::default()
+                },
+            ])
+            .unwrap();
+        assert_eq!((added, updated), (1, 1));
+        let bob = store.get("c1").unwrap();
+        assert_eq!(bob.name, "Robert Smith");
+        assert_eq!(bob.aliases, vec!["boss"]);
+        assert_eq!(bob.handles.get("slack").map(String::as_str), Some("U1"));
+        assert_eq!(store.get("c2").unwrap().name, "Carol");
+    }
+
+    #[tokio::test]
+    async fn test_tool_add_and_resolve() {
+        let config = ContactsToolConfig {
+            default_country_code: Some("60".into()),
+            ..Default::default()
+        };
+        let (tool, _dir) = temp_tool(config);
+        run(
+            &tool,
+            json!({"action": "add", "name": "Aisha Rahman", "aliases": ["mom"], "phones": ["012-345 6789"]}),
+        )
+        .await;
+
+        let out = run(
+            &tool,
+            json!({"action": "resolve", "query": "mom", "channel": "whatsapp"}),
+        )
+        .await;
+        assert!(out.contains("60123456789"));
+        assert!(out.contains("whatsapp_send"));
+
+        let out = run(
+            &tool,
+            json!({"action": "resolve", "query": "mom", "channel": "telegram"}),
+        )
+        .await;
+        assert!(out.contains("no telegram destination"));
+
+        run(
+            &tool,
+            json!({"action": "update", "query": "mom", "handles": {"telegram": "42"}}),
+        )
+        .await;
+        let out = run(
+            &tool,
+            json!({"action": "resolve", "query": "mom", "channel": "telegram"}),
+        )
+        .await;
+        assert!(out.contains("channel=telegram chat_id=42"));
+    }
+
+    #[tokio::test]
+    async fn test_tool_import_export() {
+        let (tool, dir) = temp_tool(ContactsToolConfig::default());
+        std::fs::write(
+            dir.path().join("in.vcf"),
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Dana\r\nEMAIL:dana@example.com\r\nEND:VCARD\r\n",
+        )
+        .unwrap();
+        let out = run(&tool, json!({"action": "import", "path": "in.vcf"})).await;
+        assert!(out.contains("1 added"));
+
+        run(&tool, json!({"action": "export", "path": "out/all.vcf"})).await;
+        let exported = std::fs::read_to_string(dir.path().join("out/all.vcf")).unwrap();
+        assert!(exported.contains("FN:Dana"));
+
+        let err = tool
+            .execute(
+                json!({"action": "import", "path": "notes.txt"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(".vcf"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_requires_config() {
+        let (tool, _dir) = temp_tool(ContactsToolConfig::default());
+        let err = tool
+            .execute(json!({"action": "sync"}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("carddav_url"));
+    }
+}
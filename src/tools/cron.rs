@@ -6,9 +6,11 @@ use async_trait::async_trait;
 use serde_json::{json, Value};
 
 use crate::cron::{
-    is_valid_cron_expr, parse_at_datetime_ms, CronPayload, CronSchedule, CronService,
+    default_job_name, parse_schedule, CronJob, CronJobUpdate, CronPayload, CronSchedule,
+    CronService, MAX_ACTIVE_JOBS,
 };
 use crate::error::{Result, ZeptoError};
+use crate::utils::locale::format_timestamp;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
@@ -31,7 +33,9 @@ impl Tool for CronTool {
     }
 
     fn description(&self) -> &str {
-        "Schedule reminders and recurring tasks. Actions: add, list, remove."
+        "Schedule reminders and recurring tasks. Actions: add, list, remove, \
+         pause, resume, run_now (run a job immediately), update (change a job's \
+         schedule, message, name or target)."
     }
 
     fn compact_description(&self) -> &str {
@@ -48,16 +52,16 @@ impl Tool for CronTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["add", "list", "remove"],
+                    "enum": ["add", "list", "remove", "pause", "resume", "run_now", "update"],
                     "description": "Action to perform"
                 },
                 "message": {
                     "type": "string",
-                    "description": "Message for add action"
+                    "description": "Message for add action. May be 'routine:<name>' (a template from routines.templates) or embed {{tool key=value}} placeholders, e.g. {{web_fetch url=https://example.com}}, replaced with the tool's output before each run"
                 },
                 "name": {
                     "type": "string",
-                    "description": "Optional job name"
+                    "description": "Optional job name (add, update)"
                 },
                 "every_seconds": {
                     "type": "integer",
@@ -73,7 +77,7 @@ impl Tool for CronTool {
                 },
                 "job_id": {
                     "type": "string",
-                    "description": "Target job id for remove"
+                    "description": "Target job id for remove, pause, resume, run_now and update"
                 },
                 "channel": {
                     "type": "string",
@@ -82,6 +86,10 @@ impl Tool for CronTool {
                 "chat_id": {
                     "type": "string",
                     "description": "Optional target chat id (defaults to current)"
+                },
+                "include_disabled": {
+                    "type": "boolean",
+                    "description": "List paused jobs too (default false)"
                 }
             },
             "required": ["action"]
@@ -96,8 +104,12 @@ impl Tool for CronTool {
 
         let s = match action {
             "add" => self.execute_add(args, ctx).await?,
-            "list" => self.execute_list(args).await?,
+            "list" => self.execute_list(args, ctx).await?,
             "remove" => self.execute_remove(args).await?,
+            "pause" => self.execute_pause(args).await?,
+            "resume" => self.execute_resume(args).await?,
+            "run_now" => self.execute_run_now(args).await?,
+            "update" => self.execute_update(args, ctx).await?,
             other => return Err(ZeptoError::Tool(format!("Unknown cron action '{}'", other))),
         };
         Ok(ToolOutput::llm_only(s))
@@ -108,7 +120,7 @@ impl CronTool {
     async fn execute_add(&self, args: Value, ctx: &ToolContext) -> Result<String> {
         // Max job count
         let existing = self.cron.list_jobs(false).await;
-        if existing.len() >= 50 {
+        if existing.len() >= MAX_ACTIVE_JOBS {
             return Err(ZeptoError::Tool(
                 "Maximum of 50 active cron jobs reached. Remove some before adding new ones."
                     .to_string(),
@@ -124,76 +136,13 @@ impl CronTool {
             .get("name")
             .and_then(|v| v.as_str())
             .map(str::to_string)
-            .unwrap_or_else(|| {
-                // Use char_indices for UTF-8 safe truncation
-                if message.chars().count() > 30 {
-                    let end = message
-                        .char_indices()
-                        .nth(30)
-                        .map(|(i, _)| i)
-                        .unwrap_or(message.len());
-                    format!("{}...", &message[..end])
-                } else {
-                    message.to_string()
-                }
-            });
+            .unwrap_or_else(|| default_job_name(message));
 
-        let every_seconds = args.get("every_seconds").and_then(|v| v.as_i64());
-        let cron_expr = args.get("cron_expr").and_then(|v| v.as_str());
-        let at = args.get("at").and_then(|v| v.as_str());
-
-        let mut schedule_count = 0;
-        if every_seconds.is_some() {
-            schedule_count += 1;
-        }
-        if cron_expr.is_some() {
-            schedule_count += 1;
-        }
-        if at.is_some() {
-            schedule_count += 1;
-        }
-        if schedule_count != 1 {
-            return Err(ZeptoError::Tool(
-                "Specify exactly one of: every_seconds, cron_expr, at".to_string(),
-            ));
-        }
-
-        // Minimum interval rate limiting
-        if let Some(seconds) = every_seconds {
-            if seconds < 60 {
-                return Err(ZeptoError::Tool(
-                    "Minimum interval is 60 seconds".to_string(),
-                ));
-            }
-        }
-
-        let (schedule, delete_after_run) = if let Some(seconds) = every_seconds {
-            if seconds <= 0 {
-                return Err(ZeptoError::Tool(
-                    "'every_seconds' must be greater than zero".to_string(),
-                ));
-            }
-            (
-                CronSchedule::Every {
-                    every_ms: seconds * 1_000,
-                },
-                false,
-            )
-        } else if let Some(expr) = cron_expr {
-            let schedule = CronSchedule::Cron {
-                expr: expr.to_string(),
-            };
-            if !is_valid_cron_expr(expr) {
-                return Err(ZeptoError::Tool(format!(
-                    "Invalid or non-runnable cron expression '{}'",
-                    expr
-                )));
-            }
-            (schedule, false)
-        } else {
-            let at_ms = parse_at_datetime_ms(at.unwrap())?;
-            (CronSchedule::At { at_ms }, true)
-        };
+        let (schedule, delete_after_run) = parse_schedule(
+            args.get("every_seconds").and_then(|v| v.as_i64()),
+            args.get("cron_expr").and_then(|v| v.as_str()),
+            args.get("at").and_then(|v| v.as_str()),
+        )?;
 
         let channel = args
             .get("channel")
@@ -223,10 +172,23 @@ impl CronTool {
             )
             .await?;
 
-        Ok(format!("Created cron job '{}' (id: {})", job.name, job.id))
+        let next_run = job
+            .state
+            .next_run_at_ms
+            .map(|ms| {
+                format!(
+                    ", next run {}",
+                    format_timestamp(ctx.locale.as_ref(), ms / 1000)
+                )
+            })
+            .unwrap_or_default();
+        Ok(format!(
+            "Created cron job '{}' (id: {}{})",
+            job.name, job.id, next_run
+        ))
     }
 
-    async fn execute_list(&self, args: Value) -> Result<String> {
+    async fn execute_list(&self, args: Value, ctx: &ToolContext) -> Result<String> {
         let include_disabled = args
             .get("include_disabled")
             .and_then(|v| v.as_bool())
@@ -240,23 +202,36 @@ impl CronTool {
         let mut lines = Vec::new();
         for job in jobs {
             let schedule = match &job.schedule {
-                CronSchedule::At { at_ms } => format!("at({})", at_ms),
+                CronSchedule::At { at_ms } => {
+                    format!("at {}", format_timestamp(ctx.locale.as_ref(), at_ms / 1000))
+                }
                 CronSchedule::Every { every_ms } => format!("every({}ms)", every_ms),
                 CronSchedule::Cron { expr } => format!("cron({})", expr),
             };
+            let next_run = match (&job.schedule, job.state.next_run_at_ms) {
+                (CronSchedule::At { .. }, _) | (_, None) => String::new(),
+                (_, Some(ms)) => format!(
+                    " (next: {})",
+                    format_timestamp(ctx.locale.as_ref(), ms / 1000)
+                ),
+            };
+            let paused = if job.enabled { "" } else { " (paused)" };
             lines.push(format!(
-                "- {} [{}] {} -> {}:{}",
-                job.name, job.id, schedule, job.payload.channel, job.payload.chat_id
+                "- {} [{}] {}{}{} -> {}:{}",
+                job.name,
+                job.id,
+                schedule,
+                next_run,
+                paused,
+                job.payload.channel,
+                job.payload.chat_id
             ));
         }
         Ok(format!("Scheduled jobs:\n{}", lines.join("\n")))
     }
 
     async fn execute_remove(&self, args: Value) -> Result<String> {
-        let job_id = args
-            .get("job_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| ZeptoError::Tool("Missing 'job_id' for cron remove".into()))?;
+        let job_id = job_id(&args, "remove")?;
 
         if self.cron.remove_job(job_id).await? {
             Ok(format!("Removed cron job {}", job_id))
@@ -264,6 +239,105 @@ impl CronTool {
             Ok(format!("Cron job {} not found", job_id))
         }
     }
+
+    async fn execute_pause(&self, args: Value) -> Result<String> {
+        let job_id = job_id(&args, "pause")?;
+        Ok(match self.cron.pause_job(job_id).await? {
+            Some(job) => format!("Paused cron job '{}' ({})", job.name, job.id),
+            None => format!("Cron job {} not found", job_id),
+        })
+    }
+
+    async fn execute_resume(&self, args: Value) -> Result<String> {
+        let job_id = job_id(&args, "resume")?;
+        let Some(job) = self.cron.get_job(job_id).await else {
+            return Ok(format!("Cron job {} not found", job_id));
+        };
+        if !job.enabled && self.cron.list_jobs(false).await.len() >= MAX_ACTIVE_JOBS {
+            return Err(ZeptoError::Tool(
+                "Maximum of 50 active cron jobs reached. Remove or pause some before resuming."
+                    .to_string(),
+            ));
+        }
+        Ok(match self.cron.resume_job(job_id).await? {
+            Some(job) => format!("Resumed cron job '{}' ({})", job.name, job.id),
+            None => format!("Cron job {} not found", job_id),
+        })
+    }
+
+    async fn execute_run_now(&self, args: Value) -> Result<String> {
+        let job_id = job_id(&args, "run_now")?;
+        Ok(match self.cron.run_job_now(job_id).await? {
+            Some(CronJob {
+                name, id, state, ..
+            }) => match state.last_error {
+                None => format!("Started cron job '{}' ({})", name, id),
+                Some(err) => format!("Failed to run cron job '{}' ({}): {}", name, id, err),
+            },
+            None => format!("Cron job {} not found", job_id),
+        })
+    }
+
+    async fn execute_update(&self, args: Value, ctx: &ToolContext) -> Result<String> {
+        let job_id = job_id(&args, "update")?;
+        let text = |key: &str| args.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+        let every_seconds = args.get("every_seconds").and_then(|v| v.as_i64());
+        let cron_expr = args.get("cron_expr").and_then(|v| v.as_str());
+        let at = args.get("at").and_then(|v| v.as_str());
+        let (schedule, delete_after_run) =
+            if every_seconds.is_some() || cron_expr.is_some() || at.is_some() {
+                let (schedule, once) = parse_schedule(every_seconds, cron_expr, at)?;
+                (Some(schedule), Some(once))
+            } else {
+                (None, None)
+            };
+
+        let update = CronJobUpdate {
+            name: text("name"),
+            schedule,
+            delete_after_run,
+            message: text("message"),
+            channel: text("channel"),
+            chat_id: text("chat_id"),
+        };
+        if update.name.is_none()
+            && update.schedule.is_none()
+            && update.message.is_none()
+            && update.channel.is_none()
+            && update.chat_id.is_none()
+        {
+            return Err(ZeptoError::Tool(
+                "Nothing to update: give a new schedule, message, name, channel or chat_id"
+                    .to_string(),
+            ));
+        }
+
+        let Some(job) = self.cron.update_job(job_id, update).await? else {
+            return Ok(format!("Cron job {} not found", job_id));
+        };
+        let next_run = job
+            .state
+            .next_run_at_ms
+            .map(|ms| {
+                format!(
+                    ", next run {}",
+                    format_timestamp(ctx.locale.as_ref(), ms / 1000)
+                )
+            })
+            .unwrap_or_default();
+        Ok(format!(
+            "Updated cron job '{}' (id: {}{})",
+            job.name, job.id, next_run
+        ))
+    }
+}
+
+/// The required `job_id` argument of `action`.
+fn job_id<'a>(args: &'a Value, action: &str) -> Result<&'a str> {
+    args.get("job_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ZeptoError::Tool(format!("Missing 'job_id' for cron {}", action)))
 }
 
 #[cfg(test)]
@@ -431,6 +505,32 @@ mod tests {
         assert!(output.contains("heartbeat"));
     }
 
+    #[tokio::test]
+    async fn test_one_shot_time_follows_the_locale() {
+        let tool = make_cron_tool();
+        let ctx =
+            ctx_with_channel().with_locale(crate::utils::locale::Locale::parse("ja").unwrap());
+        tool.execute(
+            json!({"action": "add", "message": "ping", "at": "2030-01-15T10:00:00Z"}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+
+        let list = tool
+            .execute(json!({"action": "list"}), &ctx)
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(list.contains("at 2030年1月1"), "{}", list);
+        let list = tool
+            .execute(json!({"action": "list"}), &ctx_with_channel())
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(list.contains("at 2030-01-1"), "{}", list);
+    }
+
     #[tokio::test]
     async fn test_execute_list_empty() {
         let tool = make_cron_tool();
@@ -464,6 +564,65 @@ mod tests {
         assert!(result.unwrap().for_llm.contains("not found"));
     }
 
+    #[tokio::test]
+    async fn test_execute_pause_resume_update_run_now() {
+        let tool = make_cron_tool();
+        let ctx = ctx_with_channel();
+        tool.execute(
+            json!({"action": "add", "message": "heartbeat", "every_seconds": 120}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+        let job_id = tool.cron.list_jobs(true).await[0].id.clone();
+        let run = |action: &'static str, extra: Value| {
+            let mut args = json!({"action": action, "job_id": job_id});
+            args.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            let tool = &tool;
+            let ctx = &ctx;
+            async move { tool.execute(args, ctx).await }
+        };
+
+        let paused = run("pause", json!({})).await.unwrap().for_llm;
+        assert!(paused.contains("Paused cron job"), "{}", paused);
+        let list = tool
+            .execute(json!({"action": "list", "include_disabled": true}), &ctx)
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(list.contains("(paused)"), "{}", list);
+
+        let resumed = run("resume", json!({})).await.unwrap().for_llm;
+        assert!(resumed.contains("Resumed cron job"), "{}", resumed);
+
+        let updated = run(
+            "update",
+            json!({"message": "status report", "cron_expr": "0 9 * * *"}),
+        )
+        .await
+        .unwrap()
+        .for_llm;
+        assert!(updated.contains("Updated cron job"), "{}", updated);
+        let job = tool.cron.get_job(&job_id).await.unwrap();
+        assert_eq!(job.payload.message, "status report");
+        assert!(matches!(job.schedule, CronSchedule::Cron { .. }));
+
+        let err = run("update", json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("Nothing to update"));
+
+        let ran = run("run_now", json!({})).await.unwrap().for_llm;
+        assert!(ran.contains("Started cron job"), "{}", ran);
+
+        let missing = tool
+            .execute(json!({"action": "pause", "job_id": "no_such_id"}), &ctx)
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(missing.contains("not found"));
+    }
+
     #[tokio::test]
     async fn test_execute_add_no_channel_in_context() {
         let tool = make_cron_tool();
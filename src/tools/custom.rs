@@ -1,7 +1,8 @@
 //! Custom CLI-defined tool adapter for ZeptoClaw.
 //!
 //! Wraps a [`CustomToolDef`] from config and implements the [`Tool`] trait.
-//! Commands execute via `sh -c` with shell-escaped parameter interpolation.
+//! Commands execute via the host shell (`sh -c`, or `cmd /C` on Windows) with
+//! shell-escaped parameter interpolation.
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -9,8 +10,9 @@ use std::collections::HashMap;
 use std::time::Duration;
 use tracing::debug;
 
-use crate::config::CustomToolDef;
+use crate::config::{CustomToolDef, NativeShell};
 use crate::error::{Result, ZeptoError};
+use crate::runtime::shell_command;
 use crate::security::ShellSecurityConfig;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
@@ -133,8 +135,7 @@ impl Tool for CustomTool {
         let timeout = Duration::from_secs(timeout_secs);
 
         // Build command
-        let mut cmd = tokio::process::Command::new("sh");
-        cmd.arg("-c").arg(&command);
+        let mut cmd = shell_command(NativeShell::Auto, &command);
         cmd.stdout(std::process::Stdio::piped());
         cmd.stderr(std::process::Stdio::piped());
 
@@ -207,7 +208,13 @@ mod tests {
             workspace: Some(std::env::temp_dir().to_string_lossy().to_string()),
             channel: None,
             chat_id: None,
+            session_key: None,
             is_batch: false,
+            turn_id: None,
+            memory_namespace: None,
+            locale: None,
+            timezone: None,
+            cancel: Default::default(),
         }
     }
 
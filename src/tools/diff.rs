@@ -2,6 +2,10 @@
 //!
 //! Ported from pi-rs (pi-coding-agent). Applies standard unified diffs
 //! with `@@ -old,count +new,count @@` hunk headers.
+//!
+//! [`parse_patch`] splits a multi-file patch (`---`/`+++` headers per file)
+//! into [`FilePatch`]es whose hunks are applied one by one, tolerating hunks
+//! that moved since the diff was made.
 
 /// Summary of changes made when applying a diff.
 #[derive(Debug, Default, PartialEq)]
@@ -112,6 +116,222 @@ pub fn apply_unified_diff(original: &str, diff: &str) -> Result<(String, DiffSum
     Ok((out.join(""), summary))
 }
 
+/// One file's section of a multi-file patch.
+#[derive(Debug)]
+pub struct FilePatch {
+    /// Path before the change, or `None` when the patch creates the file.
+    pub old_path: Option<String>,
+    /// Path after the change, or `None` when the patch deletes the file.
+    pub new_path: Option<String>,
+    hunks: Vec<Hunk>,
+}
+
+/// What happened to one hunk of a [`FilePatch`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum HunkOutcome {
+    /// Applied at `line` (1-based, in the original file), `offset` lines
+    /// away from where the hunk header put it.
+    Applied { line: usize, offset: isize },
+    /// Not applied, with the reason.
+    Rejected(String),
+}
+
+/// Result of [`FilePatch::apply`]. `content` only contains the hunks that
+/// applied.
+#[derive(Debug)]
+pub struct PatchedFile {
+    pub content: String,
+    pub outcomes: Vec<HunkOutcome>,
+    pub summary: DiffSummary,
+}
+
+impl PatchedFile {
+    /// Number of rejected hunks.
+    pub fn rejected(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|o| matches!(o, HunkOutcome::Rejected(_)))
+            .count()
+    }
+}
+
+impl FilePatch {
+    /// The path this section writes to (or deletes).
+    pub fn path(&self) -> Option<&str> {
+        self.new_path.as_deref().or(self.old_path.as_deref())
+    }
+
+    /// Whether the patch creates the file.
+    pub fn is_new_file(&self) -> bool {
+        self.old_path.is_none()
+    }
+
+    /// Whether the patch deletes the file.
+    pub fn is_deletion(&self) -> bool {
+        self.new_path.is_none()
+    }
+
+    /// Number of hunks.
+    pub fn hunk_count(&self) -> usize {
+        self.hunks.len()
+    }
+
+    /// Apply every hunk that matches `original`. A hunk whose context is not
+    /// at its stated line is searched for nearby (closest match wins), and
+    /// later hunks keep the resulting offset.
+    pub fn apply(&self, original: &str) -> PatchedFile {
+        let orig_lines = split_lines_keep_terminator(original);
+        let mut out: Vec<String> = Vec::with_capacity(orig_lines.len());
+        let mut outcomes = Vec::with_capacity(self.hunks.len());
+        let mut summary = DiffSummary::default();
+        let mut pos = 0usize;
+        let mut offset = 0isize;
+
+        for hunk in &self.hunks {
+            let old: Vec<&str> = hunk
+                .lines
+                .iter()
+                .filter(|l| l.kind != HunkLineKind::Add)
+                .map(|l| l.content.as_str())
+                .collect();
+            // A pure insertion's start is the line it goes after.
+            let stated = if old.is_empty() {
+                hunk.old_start
+            } else {
+                hunk.old_start.saturating_sub(1)
+            };
+            let expected = (stated as isize + offset).max(pos as isize) as usize;
+            let Some(at) = find_hunk(&orig_lines, &old, expected, pos) else {
+                outcomes.push(HunkOutcome::Rejected(format!(
+                    "context not found near line {}",
+                    hunk.old_start
+                )));
+                continue;
+            };
+
+            out.extend(orig_lines[pos..at].iter().map(|l| l.to_string()));
+            pos = at;
+            for line in &hunk.lines {
+                match line.kind {
+                    HunkLineKind::Context => {
+                        out.push(orig_lines[pos].to_string());
+                        pos += 1;
+                    }
+                    HunkLineKind::Remove => {
+                        pos += 1;
+                        summary.lines_removed += 1;
+                    }
+                    HunkLineKind::Add => {
+                        out.push(format!("{}\n", line.content));
+                        summary.lines_added += 1;
+                    }
+                }
+            }
+            offset = at as isize - stated as isize;
+            summary.hunks_applied += 1;
+            outcomes.push(HunkOutcome::Applied {
+                line: at + 1,
+                offset,
+            });
+        }
+        out.extend(orig_lines[pos..].iter().map(|l| l.to_string()));
+
+        PatchedFile {
+            content: out.join(""),
+            outcomes,
+            summary,
+        }
+    }
+}
+
+/// Split a multi-file unified diff into per-file sections.
+///
+/// Each file starts with a `--- <old>` / `+++ <new>` header pair; `a/` and
+/// `b/` prefixes are stripped and `/dev/null` marks created or deleted
+/// files. `diff --git`, `index` and other extended header lines are ignored.
+/// Hunk bodies are read by their header counts, so removed lines that
+/// happen to start with `--` are not mistaken for file headers.
+pub fn parse_patch(patch: &str) -> Result<Vec<FilePatch>, String> {
+    let lines: Vec<&str> = patch.lines().collect();
+    let mut files: Vec<FilePatch> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let next = lines.get(i + 1).and_then(|l| l.strip_prefix("+++ "));
+        if let (Some(old), Some(new)) = (line.strip_prefix("--- "), next) {
+            let file = FilePatch {
+                old_path: header_path(old),
+                new_path: header_path(new),
+                hunks: Vec::new(),
+            };
+            if file.path().is_none() {
+                return Err(format!("file header at line {} names no file", i + 1));
+            }
+            files.push(file);
+            i += 2;
+            continue;
+        }
+        if !line.starts_with("@@") {
+            i += 1;
+            continue;
+        }
+
+        let file = files
+            .last_mut()
+            .ok_or_else(|| format!("hunk at line {} has no ---/+++ file header", i + 1))?;
+        let (old_start, old_count, new_count) = parse_full_hunk_header(line)?;
+        let mut hunk = Hunk {
+            old_start,
+            old_count,
+            lines: Vec::new(),
+        };
+        let (mut old_left, mut new_left) = (old_count, new_count);
+        i += 1;
+        while i < lines.len() && (old_left > 0 || new_left > 0) {
+            let raw = lines[i];
+            let (kind, content) = match raw.chars().next() {
+                Some('+') => (HunkLineKind::Add, &raw[1..]),
+                Some('-') => (HunkLineKind::Remove, &raw[1..]),
+                Some(' ') => (HunkLineKind::Context, &raw[1..]),
+                // Editors and models often drop the space of blank context lines.
+                None => (HunkLineKind::Context, ""),
+                Some('\\') => {
+                    i += 1;
+                    continue;
+                }
+                Some(_) => break,
+            };
+            match kind {
+                HunkLineKind::Add => new_left = new_left.saturating_sub(1),
+                HunkLineKind::Remove => old_left = old_left.saturating_sub(1),
+                HunkLineKind::Context => {
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                }
+            }
+            hunk.lines.push(HunkLine {
+                kind,
+                content: content.to_string(),
+            });
+            i += 1;
+        }
+        if old_left > 0 || new_left > 0 {
+            return Err(format!(
+                "hunk at old line {} in {} is shorter than its header says",
+                old_start,
+                file.path().unwrap_or("?")
+            ));
+        }
+        file.hunks.push(hunk);
+    }
+
+    if let Some(empty) = files.iter().find(|f| f.hunks.is_empty()) {
+        return Err(format!("{} has no hunks", empty.path().unwrap_or("?")));
+    }
+    Ok(files)
+}
+
 // --- Internal types ---
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -193,6 +413,63 @@ fn parse_hunks(diff: &str) -> Result<Vec<Hunk>, String> {
     Ok(hunks)
 }
 
+/// Path from a `---`/`+++` header: trailing timestamp and `a/`/`b/` prefix
+/// removed, `None` for `/dev/null`.
+fn header_path(raw: &str) -> Option<String> {
+    let path = raw.split('\t').next().unwrap_or(raw).trim();
+    if path == "/dev/null" || path.is_empty() {
+        return None;
+    }
+    let path = path
+        .strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path);
+    Some(path.to_string())
+}
+
+/// Parse `@@ -a,b +c,d @@` into `(a, b, d)`. Counts default to 1.
+fn parse_full_hunk_header(line: &str) -> Result<(usize, usize, usize), String> {
+    let err = || format!("malformed hunk header: {:?}", line);
+    let range = |prefix: char| -> Result<(usize, usize), String> {
+        let part = line
+            .split_whitespace()
+            .find_map(|s| s.strip_prefix(prefix))
+            .ok_or_else(err)?;
+        let mut it = part.splitn(2, ',');
+        let start = it.next().unwrap_or("").parse().map_err(|_| err())?;
+        let count = match it.next() {
+            Some(c) => c.parse().map_err(|_| err())?,
+            None => 1,
+        };
+        Ok((start, count))
+    };
+    let (old_start, old_count) = range('-')?;
+    let (_, new_count) = range('+')?;
+    Ok((old_start, old_count, new_count))
+}
+
+/// Index where `old` matches `lines`, searching outwards from `expected`
+/// but never before `min`.
+fn find_hunk(lines: &[&str], old: &[&str], expected: usize, min: usize) -> Option<usize> {
+    let fits = |at: usize| {
+        at >= min
+            && at + old.len() <= lines.len()
+            && old
+                .iter()
+                .zip(&lines[at..])
+                .all(|(want, have)| strip_terminator(have) == *want)
+    };
+    if old.is_empty() {
+        return Some(expected.min(lines.len()).max(min));
+    }
+    (0..=lines.len()).find_map(|d| {
+        [expected.checked_add(d), expected.checked_sub(d)]
+            .into_iter()
+            .flatten()
+            .find(|&at| fits(at))
+    })
+}
+
 fn parse_hunk_header(line: &str) -> Result<(usize, usize), String> {
     let err = || format!("malformed hunk header: {:?}", line);
     let inner = line.trim_start_matches('@').trim_start_matches(' ');
@@ -344,4 +621,61 @@ mod tests {
         assert!(result.contains("B\n"));
         assert!(result.contains('c'));
     }
+
+    #[test]
+    fn parse_patch_splits_files() {
+        let patch = "diff --git a/src/a.rs b/src/a.rs\n\
+                     index 123..456 100644\n\
+                     --- a/src/a.rs\n\
+                     +++ b/src/a.rs\n\
+                     @@ -1,2 +1,2 @@\n\
+                     --- old comment\n\
+                     +// new comment\n \
+                     fn a() {}\n\
+                     --- /dev/null\n\
+                     +++ b/new.txt\t2026-01-01 00:00:00\n\
+                     @@ -0,0 +1 @@\n\
+                     +hello\n";
+        let files = parse_patch(patch).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path(), Some("src/a.rs"));
+        assert_eq!(files[0].hunk_count(), 1);
+        assert!(files[1].is_new_file());
+        assert_eq!(files[1].path(), Some("new.txt"));
+
+        let patched = files[0].apply("-- old comment\nfn a() {}\n");
+        assert_eq!(patched.content, "// new comment\nfn a() {}\n");
+        assert_eq!(files[1].apply("").content, "hello\n");
+    }
+
+    #[test]
+    fn parse_patch_rejects_headerless_and_truncated_hunks() {
+        assert!(parse_patch("@@ -1 +1 @@\n-a\n+b\n")
+            .unwrap_err()
+            .contains("no ---/+++ file header"));
+        assert!(parse_patch("--- a/x\n+++ b/x\n@@ -1,3 +1,3 @@\n a\n")
+            .unwrap_err()
+            .contains("shorter than its header"));
+    }
+
+    #[test]
+    fn file_patch_applies_moved_hunks_and_rejects_missing_context() {
+        let original = (1..=10)
+            .map(|i| format!("line {}\n", i))
+            .collect::<String>();
+        // Both hunks claim lines two too early; the second does not match.
+        let patch = "--- a/f\n+++ b/f\n\
+                     @@ -2,2 +2,2 @@\n line 4\n-line 5\n+LINE 5\n\
+                     @@ -7,1 +7,1 @@\n-line 99\n+LINE 99\n";
+        let files = parse_patch(patch).unwrap();
+        let patched = files[0].apply(&original);
+        assert_eq!(
+            patched.outcomes[0],
+            HunkOutcome::Applied { line: 4, offset: 2 }
+        );
+        assert!(matches!(patched.outcomes[1], HunkOutcome::Rejected(_)));
+        assert_eq!(patched.rejected(), 1);
+        assert!(patched.content.contains("LINE 5\nline 6\n"));
+        assert_eq!(patched.summary.hunks_applied, 1);
+    }
 }
@@ -0,0 +1,1080 @@
+//! Email tool — IMAP mailbox search/read and SMTP drafting/sending.
+//!
+//! Separate from the email channel: this is a tool the agent calls on the
+//! user's own mailbox. Actions: `search`, `read`, `draft`, `send`,
+//! `discard`.
+//!
+//! Sending is always gated on the user. `draft` keeps the message in memory
+//! (and optionally APPENDs it to the drafts mailbox) and sends the user — not
+//! the model — a one-time confirmation code. `send` only succeeds with that
+//! code, so the agent cannot send mail the user has not approved, whatever
+//! the approval policy says.
+//!
+//! Authentication is an app password, or OAuth (`XOAUTH2`) with tokens from
+//! `zeptoclaw auth login <provider>` or a static `access_token`.
+//!
+//! IMAP/SMTP require the `channel-email` build feature; without it the tool
+//! still registers but network actions return a rebuild hint.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::auth::refresh::ensure_fresh_token;
+use crate::auth::store::TokenStore;
+use crate::config::{Config, EmailAuthMethod, EmailToolConfig};
+use crate::cron::natural::{self, When};
+use crate::error::{Result, ZeptoError};
+use crate::security::confirm::{CodeCheck, ConfirmationCode, MAX_CONFIRMATION_ATTEMPTS};
+use crate::security::encryption::resolve_master_key;
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Actions that modify external state and require user confirmation.
+const DANGEROUS_ACTIONS: &[&str] = &["send"];
+
+/// Drafts not sent within this window are dropped.
+const DRAFT_TTL: Duration = Duration::from_secs(60 * 60);
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Credentials for IMAP and SMTP.
+pub enum EmailAuth {
+    /// App password (plain LOGIN / SMTP AUTH).
+    Password(String),
+    /// OAuth bearer token via `XOAUTH2`, refreshed from the encrypted token
+    /// store when available, with an optional static fallback.
+    OAuth {
+        store: Option<TokenStore>,
+        provider: String,
+        fallback: Option<String>,
+    },
+}
+
+impl EmailAuth {
+    /// The secret to present: the password, or a fresh access token.
+    #[cfg_attr(not(feature = "channel-email"), allow(dead_code))]
+    async fn secret(&self) -> Result<String> {
+        match self {
+            EmailAuth::Password(password) => Ok(password.clone()),
+            EmailAuth::OAuth {
+                store,
+                provider,
+                fallback,
+            } => {
+                let refreshed = match store {
+                    Some(store) => ensure_fresh_token(store, provider)
+                        .await
+                        .map_err(|e| e.to_string()),
+                    None => Err("no stored token".to_string()),
+                };
+                match refreshed {
+                    Ok(token) => Ok(token),
+                    Err(e) => fallback.clone().ok_or_else(|| {
+                        ZeptoError::Tool(format!(
+                            "Email OAuth is not authorized ({}). Run `zeptoclaw auth login {}`",
+                            e, provider
+                        ))
+                    }),
+                }
+            }
+        }
+    }
+
+    #[cfg_attr(not(feature = "channel-email"), allow(dead_code))]
+    fn is_oauth(&self) -> bool {
+        matches!(self, EmailAuth::OAuth { .. })
+    }
+}
+
+/// An unsent message awaiting the user's confirmation code.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "channel-email"), allow(dead_code))]
+struct Draft {
+    to: Vec<String>,
+    cc: Vec<String>,
+    subject: String,
+    body: String,
+    in_reply_to: Option<String>,
+    references: Option<String>,
+    code: ConfirmationCode,
+    created: Instant,
+}
+
+impl Draft {
+    fn preview(&self, id: &str) -> String {
+        let mut out = format!("Draft {}\nTo: {}\n", id, self.to.join(", "));
+        if !self.cc.is_empty() {
+            out.push_str(&format!("Cc: {}\n", self.cc.join(", ")));
+        }
+        out.push_str(&format!("Subject: {}\n\n{}", self.subject, self.body));
+        out
+    }
+}
+
+/// Tool for reading the mailbox and sending user-approved replies.
+pub struct EmailTool {
+    config: EmailToolConfig,
+    auth: EmailAuth,
+    drafts: Mutex<HashMap<String, Draft>>,
+    next_draft: AtomicU64,
+}
+
+impl EmailTool {
+    pub fn new(config: EmailToolConfig, auth: EmailAuth) -> Self {
+        Self {
+            config,
+            auth,
+            drafts: Mutex::new(HashMap::new()),
+            next_draft: AtomicU64::new(1),
+        }
+    }
+
+    /// Build from `tools.email`. Returns `None` when no IMAP host/username is
+    /// set, or the selected auth method has no credentials.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        let cfg = &config.tools.email;
+        if cfg.imap_host.trim().is_empty() || cfg.username.trim().is_empty() {
+            return Ok(None);
+        }
+        let auth = match cfg.auth {
+            EmailAuthMethod::Password => match cfg.password.as_deref().filter(|p| !p.is_empty()) {
+                Some(password) => EmailAuth::Password(password.to_string()),
+                None => return Ok(None),
+            },
+            EmailAuthMethod::Oauth => {
+                let provider = cfg.oauth_provider.trim().to_string();
+                let fallback = cfg
+                    .access_token
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string);
+                let store = resolve_master_key(false)
+                    .ok()
+                    .map(TokenStore::new)
+                    .filter(|store| matches!(store.load(&provider), Ok(Some(_))));
+                if store.is_none() && fallback.is_none() {
+                    return Ok(None);
+                }
+                EmailAuth::OAuth {
+                    store,
+                    provider,
+                    fallback,
+                }
+            }
+        };
+        Ok(Some(Self::new(cfg.clone(), auth)))
+    }
+
+    /// Return `true` when the given action modifies external state.
+    pub fn is_dangerous_action(action: &str) -> bool {
+        DANGEROUS_ACTIONS.contains(&action)
+    }
+
+    fn mailbox<'a>(&'a self, args: &'a Value) -> &'a str {
+        str_arg(args, "mailbox").unwrap_or(&self.config.mailbox)
+    }
+}
+
+#[async_trait]
+impl Tool for EmailTool {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    fn description(&self) -> &str {
+        "Read and reply to the user's email over IMAP/SMTP. Actions: search (by text, from, subject, since, unread), read (full message by uid), draft (new message or reply_to_uid; the user receives a confirmation code), send (draft_id + the code the user gives you), discard."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Email"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Messaging
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["search", "read", "draft", "send", "discard"],
+                    "description": "Email operation to perform."
+                },
+                "mailbox": {
+                    "type": "string",
+                    "description": "IMAP mailbox (default: configured mailbox, usually INBOX)."
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Text to search for in headers and body. For search."
+                },
+                "from": {
+                    "type": "string",
+                    "description": "Sender address or name. For search."
+                },
+                "subject": {
+                    "type": "string",
+                    "description": "Subject text for search, or the subject of a draft."
+                },
+                "since": {
+                    "type": "string",
+                    "description": "Only messages on or after this date (YYYY-MM-DD or e.g. 'yesterday'). For search."
+                },
+                "unread": {
+                    "type": "boolean",
+                    "description": "Only unread messages. For search."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum messages to return (newest first). For search."
+                },
+                "uid": {
+                    "type": "integer",
+                    "description": "Message UID as returned by search. Required for read."
+                },
+                "to": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Recipients. Required for draft unless reply_to_uid is given."
+                },
+                "cc": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Cc recipients. For draft."
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Plain-text body. Required for draft."
+                },
+                "reply_to_uid": {
+                    "type": "integer",
+                    "description": "Draft a reply to this message UID (sets recipient, Re: subject, and threading headers)."
+                },
+                "draft_id": {
+                    "type": "string",
+                    "description": "Draft ID returned by draft. Required for send and discard."
+                },
+                "confirmation_code": {
+                    "type": "string",
+                    "description": "Code the user received with the draft. Required for send; ask the user for it."
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ZeptoError::Tool("Missing 'action' argument".into()))?;
+
+        match action {
+            "search" => {
+                let criteria = build_search_criteria(&args)?;
+                let limit = args
+                    .get("limit")
+                    .and_then(Value::as_u64)
+                    .map(|n| n.clamp(1, 100) as usize)
+                    .unwrap_or(self.config.max_results.max(1) as usize);
+                Ok(ToolOutput::llm_only(
+                    self.search(self.mailbox(&args), &criteria, limit).await?,
+                ))
+            }
+            "read" => {
+                let uid = uid_arg(&args, "uid")?
+                    .ok_or_else(|| ZeptoError::Tool("Missing 'uid' for read".into()))?;
+                Ok(ToolOutput::llm_only(
+                    self.read(self.mailbox(&args), uid).await?,
+                ))
+            }
+            "draft" => self.execute_draft(&args).await,
+            "send" => self.execute_send(&args).await,
+            "discard" => {
+                let id = str_arg(&args, "draft_id")
+                    .ok_or_else(|| ZeptoError::Tool("Missing 'draft_id' for discard".into()))?;
+                let removed = self.drafts.lock().await.remove(id);
+                Ok(ToolOutput::llm_only(match removed {
+                    Some(_) => format!("Discarded draft {}", id),
+                    None => format!("No draft '{}'", id),
+                }))
+            }
+            other => Err(ZeptoError::Tool(format!(
+                "Unknown email action '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+impl EmailTool {
+    async fn execute_draft(&self, args: &Value) -> Result<ToolOutput> {
+        let body = str_arg(args, "body")
+            .ok_or_else(|| ZeptoError::Tool("Missing 'body' for draft".into()))?
+            .to_string();
+        let mut to = list_arg(args, "to");
+        let cc = list_arg(args, "cc");
+        let mut subject = str_arg(args, "subject").map(str::to_string);
+        let (mut in_reply_to, mut references) = (None, None);
+
+        if let Some(uid) = uid_arg(args, "reply_to_uid")? {
+            let original = self.reply_headers(self.mailbox(args), uid).await?;
+            if to.is_empty() {
+                to.push(original.reply_to);
+            }
+            if subject.is_none() {
+                subject = Some(reply_subject(&original.subject));
+            }
+            if let Some(id) = original.message_id {
+                let id = format!("<{}>", id.trim_matches(|c| c == '<' || c == '>'));
+                references = Some(match original.references {
+                    Some(refs) if !refs.trim().is_empty() => format!("{} {}", refs.trim(), id),
+                    _ => id.clone(),
+                });
+                in_reply_to = Some(id);
+            }
+        }
+
+        if to.is_empty() {
+            return Err(ZeptoError::Tool(
+                "Missing 'to' for draft (or give reply_to_uid)".into(),
+            ));
+        }
+        for address in to.iter().chain(cc.iter()) {
+            validate_address(address)?;
+        }
+
+        let draft = Draft {
+            to,
+            cc,
+            subject: subject.unwrap_or_default(),
+            body,
+            in_reply_to,
+            references,
+            code: ConfirmationCode::new(),
+            created: Instant::now(),
+        };
+        let id = format!("d{}", self.next_draft.fetch_add(1, Ordering::Relaxed));
+        let preview = draft.preview(&id);
+
+        #[cfg(feature = "channel-email")]
+        if let Some(drafts_mailbox) = self
+            .config
+            .drafts_mailbox
+            .as_deref()
+            .filter(|m| !m.trim().is_empty())
+        {
+            if let Err(e) = self.append_draft(drafts_mailbox, &draft).await {
+                tracing::warn!("Failed to save email draft to {}: {}", drafts_mailbox, e);
+            }
+        }
+
+        let code = draft.code.as_str().to_string();
+        {
+            let mut drafts = self.drafts.lock().await;
+            drafts.retain(|_, d| d.created.elapsed() < DRAFT_TTL);
+            drafts.insert(id.clone(), draft);
+        }
+
+        Ok(ToolOutput::split(
+            format!(
+                "{}\n\nNot sent. The user was shown this draft with a confirmation code. \
+                 Only call send with draft_id={} and the code the user gives you.",
+                preview, id
+            ),
+            format!(
+                "{}\n\nTo send this email, reply with confirmation code {}.",
+                preview, code
+            ),
+        )
+        .with_pause())
+    }
+
+    async fn execute_send(&self, args: &Value) -> Result<ToolOutput> {
+        let id = str_arg(args, "draft_id")
+            .ok_or_else(|| ZeptoError::Tool("Missing 'draft_id' for send".into()))?;
+        let code = str_arg(args, "confirmation_code").ok_or_else(|| {
+            ZeptoError::Tool(
+                "Missing 'confirmation_code'. Ask the user for the code sent with the draft".into(),
+            )
+        })?;
+
+        let draft = {
+            let mut drafts = self.drafts.lock().await;
+            drafts.retain(|_, d| d.created.elapsed() < DRAFT_TTL);
+            let draft = drafts
+                .get_mut(id)
+                .ok_or_else(|| ZeptoError::Tool(format!("No draft '{}' (expired or sent)", id)))?;
+            match draft.code.check(code) {
+                CodeCheck::Matched => {}
+                CodeCheck::Wrong { remaining } => {
+                    return Err(ZeptoError::Tool(format!(
+                        "Confirmation code does not match ({} of {} attempts left). Ask the \
+                         user for the code sent with the draft",
+                        remaining, MAX_CONFIRMATION_ATTEMPTS
+                    )));
+                }
+                CodeCheck::Exhausted => {
+                    drafts.remove(id);
+                    return Err(ZeptoError::Tool(format!(
+                        "Confirmation code does not match; draft {} was discarded after {} \
+                         wrong codes",
+                        id, MAX_CONFIRMATION_ATTEMPTS
+                    )));
+                }
+            }
+            drafts.remove(id).expect("draft present")
+        };
+
+        if let Err(e) = self.send_draft(&draft).await {
+            // Keep the draft so the user can retry with the same code.
+            self.drafts.lock().await.insert(id.to_string(), draft);
+            return Err(e);
+        }
+        Ok(ToolOutput::user_visible(format!(
+            "Sent email '{}' to {}",
+            draft.subject,
+            draft.to.join(", ")
+        )))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// IMAP / SMTP (feature = "channel-email")
+// ---------------------------------------------------------------------------
+
+/// Headers needed to draft a reply.
+#[cfg_attr(not(feature = "channel-email"), allow(dead_code))]
+struct ReplyHeaders {
+    reply_to: String,
+    subject: String,
+    message_id: Option<String>,
+    references: Option<String>,
+}
+
+#[cfg(not(feature = "channel-email"))]
+fn feature_error() -> ZeptoError {
+    ZeptoError::Tool(
+        "Email tool requires the 'channel-email' build feature. \
+         Rebuild with: cargo build --features channel-email"
+            .into(),
+    )
+}
+
+#[cfg(not(feature = "channel-email"))]
+impl EmailTool {
+    async fn search(&self, _mailbox: &str, _criteria: &str, _limit: usize) -> Result<String> {
+        Err(feature_error())
+    }
+
+    async fn read(&self, _mailbox: &str, _uid: u32) -> Result<String> {
+        Err(feature_error())
+    }
+
+    async fn reply_headers(&self, _mailbox: &str, _uid: u32) -> Result<ReplyHeaders> {
+        Err(feature_error())
+    }
+
+    async fn send_draft(&self, _draft: &Draft) -> Result<()> {
+        Err(feature_error())
+    }
+}
+
+#[cfg(feature = "channel-email")]
+struct XOAuth2 {
+    user: String,
+    token: String,
+}
+
+#[cfg(feature = "channel-email")]
+impl async_imap::Authenticator for XOAuth2 {
+    type Response = String;
+
+    fn process(&mut self, _challenge: &[u8]) -> Self::Response {
+        format!("user={}\x01auth=Bearer {}\x01\x01", self.user, self.token)
+    }
+}
+
+#[cfg(feature = "channel-email")]
+impl EmailTool {
+    async fn connect(&self) -> Result<crate::channels::email_channel::ImapSession> {
+        let client = crate::channels::email_channel::imap_tls_client(
+            &self.config.imap_host,
+            self.config.imap_port,
+        )
+        .await?;
+        let secret = self.auth.secret().await?;
+        let session = if self.auth.is_oauth() {
+            let auth = XOAuth2 {
+                user: self.config.username.clone(),
+                token: secret,
+            };
+            client
+                .authenticate("XOAUTH2", auth)
+                .await
+                .map_err(|(e, _)| ZeptoError::Tool(format!("IMAP authentication failed: {e}")))?
+        } else {
+            client
+                .login(&self.config.username, &secret)
+                .await
+                .map_err(|(e, _)| ZeptoError::Tool(format!("IMAP login failed: {e}")))?
+        };
+        Ok(session)
+    }
+
+    /// Fetch one message (without marking it read) from `mailbox`.
+    async fn fetch_raw(&self, mailbox: &str, uid: u32, query: &str) -> Result<Vec<u8>> {
+        use futures::TryStreamExt;
+
+        let mut session = self.connect().await?;
+        session
+            .examine(mailbox)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("IMAP EXAMINE {} failed: {e}", mailbox)))?;
+        let fetched: Vec<async_imap::types::Fetch> = session
+            .uid_fetch(uid.to_string(), query)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("IMAP FETCH failed: {e}")))?
+            .try_collect()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("IMAP FETCH stream failed: {e}")))?;
+        let _ = session.logout().await;
+
+        fetched
+            .iter()
+            .find_map(|f| f.body().or_else(|| f.header()).map(<[u8]>::to_vec))
+            .ok_or_else(|| ZeptoError::Tool(format!("No message with UID {} in {}", uid, mailbox)))
+    }
+
+    async fn search(&self, mailbox: &str, criteria: &str, limit: usize) -> Result<String> {
+        use futures::TryStreamExt;
+        use mail_parser::MessageParser;
+
+        let mut session = self.connect().await?;
+        session
+            .examine(mailbox)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("IMAP EXAMINE {} failed: {e}", mailbox)))?;
+        let mut uids: Vec<u32> = session
+            .uid_search(criteria)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("IMAP SEARCH failed: {e}")))?
+            .into_iter()
+            .collect();
+        if uids.is_empty() {
+            let _ = session.logout().await;
+            return Ok(format!("No messages in {} match", mailbox));
+        }
+        let total = uids.len();
+        uids.sort_unstable_by(|a, b| b.cmp(a));
+        uids.truncate(limit);
+        let uid_set = uids
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let fetched: Vec<async_imap::types::Fetch> = session
+            .uid_fetch(&uid_set, "(UID FLAGS RFC822.HEADER)")
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("IMAP FETCH failed: {e}")))?
+            .try_collect()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("IMAP FETCH stream failed: {e}")))?;
+        let _ = session.logout().await;
+
+        let parser = MessageParser::default();
+        let mut rows: Vec<(u32, String)> = fetched
+            .iter()
+            .filter_map(|f| {
+                let uid = f.uid?;
+                let parsed = parser.parse(f.header()?)?;
+                let unread = !f.flags().any(|flag| flag == async_imap::types::Flag::Seen);
+                Some((
+                    uid,
+                    format!(
+                        "uid {} | {} | {} | {}{}",
+                        uid,
+                        parsed
+                            .date()
+                            .map(|d| d.to_rfc3339())
+                            .unwrap_or_else(|| "-".into()),
+                        format_addresses(parsed.from()),
+                        parsed.subject().unwrap_or("(no subject)"),
+                        if unread { " [unread]" } else { "" }
+                    ),
+                ))
+            })
+            .collect();
+        rows.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+
+        let lines: Vec<String> = rows.into_iter().map(|(_, line)| line).collect();
+        Ok(format!(
+            "{} of {} matching messages in {} (newest first):\n{}",
+            lines.len(),
+            total,
+            mailbox,
+            lines.join("\n")
+        ))
+    }
+
+    async fn read(&self, mailbox: &str, uid: u32) -> Result<String> {
+        use crate::channels::EmailChannel;
+        use mail_parser::{MessageParser, MimeHeaders};
+
+        let raw = self.fetch_raw(mailbox, uid, "(UID BODY.PEEK[])").await?;
+        let parsed = MessageParser::default()
+            .parse(&raw)
+            .ok_or_else(|| ZeptoError::Tool(format!("Could not parse message {}", uid)))?;
+
+        let mut out = format!(
+            "From: {}\nTo: {}\n",
+            format_addresses(parsed.from()),
+            format_addresses(parsed.to())
+        );
+        if parsed.cc().is_some() {
+            out.push_str(&format!("Cc: {}\n", format_addresses(parsed.cc())));
+        }
+        if let Some(date) = parsed.date() {
+            out.push_str(&format!("Date: {}\n", date.to_rfc3339()));
+        }
+        out.push_str(&format!(
+            "Subject: {}\n",
+            parsed.subject().unwrap_or("(no subject)")
+        ));
+        let attachments: Vec<String> = parsed
+            .attachments()
+            .filter_map(|a| a.attachment_name().map(str::to_string))
+            .collect();
+        if !attachments.is_empty() {
+            out.push_str(&format!("Attachments: {}\n", attachments.join(", ")));
+        }
+
+        let body = EmailChannel::extract_plain_text(&parsed);
+        let max = self.config.max_body_chars.max(1);
+        out.push('\n');
+        if body.chars().count() > max {
+            out.extend(body.chars().take(max));
+            out.push_str("\n[... truncated]");
+        } else {
+            out.push_str(&body);
+        }
+        Ok(out)
+    }
+
+    async fn reply_headers(&self, mailbox: &str, uid: u32) -> Result<ReplyHeaders> {
+        use mail_parser::{HeaderValue, MessageParser};
+
+        let raw = self.fetch_raw(mailbox, uid, "(UID RFC822.HEADER)").await?;
+        let parsed = MessageParser::default()
+            .parse(&raw)
+            .ok_or_else(|| ZeptoError::Tool(format!("Could not parse message {}", uid)))?;
+        let reply_to = parsed
+            .reply_to()
+            .or_else(|| parsed.from())
+            .and_then(|a| a.first())
+            .and_then(|a| a.address())
+            .map(str::to_string)
+            .ok_or_else(|| ZeptoError::Tool(format!("Message {} has no sender", uid)))?;
+        Ok(ReplyHeaders {
+            reply_to,
+            subject: parsed.subject().unwrap_or_default().to_string(),
+            message_id: parsed.message_id().map(str::to_string),
+            references: match parsed.references() {
+                HeaderValue::Text(id) => Some(format!("<{}>", id)),
+                HeaderValue::TextList(ids) => Some(
+                    ids.iter()
+                        .map(|id| format!("<{}>", id))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                _ => None,
+            },
+        })
+    }
+
+    fn build_message(&self, draft: &Draft) -> Result<lettre::Message> {
+        use lettre::message::{Mailbox, SinglePart};
+
+        let from = match self.config.display_name.as_deref() {
+            Some(name) => format!("{} <{}>", name, self.config.username),
+            None => self.config.username.clone(),
+        };
+        let parse = |addr: &str| -> Result<Mailbox> {
+            addr.parse()
+                .map_err(|e| ZeptoError::Tool(format!("Invalid address '{}': {e}", addr)))
+        };
+        let mut builder = lettre::Message::builder()
+            .from(parse(&from)?)
+            .subject(draft.subject.clone());
+        for to in &draft.to {
+            builder = builder.to(parse(to)?);
+        }
+        for cc in &draft.cc {
+            builder = builder.cc(parse(cc)?);
+        }
+        if let Some(id) = &draft.in_reply_to {
+            builder = builder.in_reply_to(id.clone());
+        }
+        if let Some(refs) = &draft.references {
+            builder = builder.references(refs.clone());
+        }
+        builder
+            .singlepart(SinglePart::plain(draft.body.clone()))
+            .map_err(|e| ZeptoError::Tool(format!("Failed to build email: {e}")))
+    }
+
+    async fn append_draft(&self, mailbox: &str, draft: &Draft) -> Result<()> {
+        let message = self.build_message(draft)?;
+        let mut session = self.connect().await?;
+        session
+            .append(mailbox, Some("(\\Draft)"), None, message.formatted())
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("IMAP APPEND failed: {e}")))?;
+        let _ = session.logout().await;
+        Ok(())
+    }
+
+    async fn send_draft(&self, draft: &Draft) -> Result<()> {
+        use lettre::transport::smtp::authentication::{Credentials, Mechanism};
+        use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+        let message = self.build_message(draft)?;
+        let creds = Credentials::new(self.config.username.clone(), self.auth.secret().await?);
+        let mut transport =
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.config.smtp_host)
+                .map_err(|e| ZeptoError::Tool(format!("SMTP relay error: {e}")))?
+                .port(self.config.smtp_port)
+                .credentials(creds);
+        if self.auth.is_oauth() {
+            transport = transport.authentication(vec![Mechanism::Xoauth2]);
+        }
+        transport
+            .build()
+            .send(message)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("SMTP send failed: {e}")))?;
+        tracing::info!("Email sent to {}", draft.to.join(", "));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "channel-email")]
+fn format_addresses(address: Option<&mail_parser::Address>) -> String {
+    let Some(address) = address else {
+        return "-".to_string();
+    };
+    let formatted: Vec<String> = address
+        .iter()
+        .map(|a| match (a.name(), a.address()) {
+            (Some(name), Some(addr)) => format!("{} <{}>", name, addr),
+            (None, Some(addr)) => addr.to_string(),
+            (Some(name), None) => name.to_string(),
+            (None, None) => String::new(),
+        })
+        .filter(|s| !s.is_empty())
+        .collect();
+    if formatted.is_empty() {
+        "-".to_string()
+    } else {
+        formatted.join(", ")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// A string or array of strings, trimmed, empties dropped.
+fn list_arg(args: &Value, key: &str) -> Vec<String> {
+    match args.get(key) {
+        Some(Value::String(s)) => s
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn uid_arg(args: &Value, key: &str) -> Result<Option<u32>> {
+    match args.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+            .and_then(|n| u32::try_from(n).ok())
+            .filter(|n| *n > 0)
+            .map(Some)
+            .ok_or_else(|| ZeptoError::Tool(format!("'{}' must be a positive integer UID", key))),
+    }
+}
+
+/// Quote a string for an IMAP SEARCH key.
+fn imap_quote(value: &str) -> Result<String> {
+    if value.contains(['\r', '\n']) {
+        return Err(ZeptoError::Tool(
+            "Search terms cannot contain line breaks".into(),
+        ));
+    }
+    Ok(format!(
+        "\"{}\"",
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    ))
+}
+
+/// Parse a `since` value: `YYYY-MM-DD` or natural language.
+fn parse_since(value: &str) -> Result<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    let lowered = value.to_lowercase();
+    let now = chrono::Local::now().fixed_offset();
+    let date = match lowered.as_str() {
+        "today" => Some(now.date_naive()),
+        "yesterday" => now.date_naive().pred_opt(),
+        _ => match natural::parse_when(&lowered, now) {
+            Ok(When::Once(at)) => Some(at.date_naive()),
+            _ => None,
+        },
+    };
+    date.ok_or_else(|| {
+        ZeptoError::Tool(format!(
+            "Could not understand since '{}'. Use YYYY-MM-DD",
+            value
+        ))
+    })
+}
+
+/// Build an IMAP SEARCH criteria string from tool arguments.
+fn build_search_criteria(args: &Value) -> Result<String> {
+    let mut keys = Vec::new();
+    if let Some(query) = str_arg(args, "query") {
+        keys.push(format!("TEXT {}", imap_quote(query)?));
+    }
+    if let Some(from) = str_arg(args, "from") {
+        keys.push(format!("FROM {}", imap_quote(from)?));
+    }
+    if let Some(subject) = str_arg(args, "subject") {
+        keys.push(format!("SUBJECT {}", imap_quote(subject)?));
+    }
+    if let Some(since) = str_arg(args, "since") {
+        keys.push(format!("SINCE {}", parse_since(since)?.format("%-d-%b-%Y")));
+    }
+    if args.get("unread").and_then(Value::as_bool) == Some(true) {
+        keys.push("UNSEEN".to_string());
+    }
+    if keys.is_empty() {
+        keys.push("ALL".to_string());
+    }
+    Ok(keys.join(" "))
+}
+
+/// `Re: <subject>`, without stacking prefixes.
+fn reply_subject(subject: &str) -> String {
+    let trimmed = subject.trim();
+    if trimmed.len() >= 3 && trimmed[..3].eq_ignore_ascii_case("re:") {
+        trimmed.to_string()
+    } else {
+        format!("Re: {}", trimmed)
+    }
+}
+
+/// Light sanity check on a recipient (`addr@host` or `Name <addr@host>`).
+fn validate_address(address: &str) -> Result<()> {
+    let addr = match (address.rfind('<'), address.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &address[start + 1..end],
+        _ => address,
+    };
+    let valid = addr
+        .split_once('@')
+        .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.'))
+        && !addr.contains(char::is_whitespace);
+    if valid {
+        Ok(())
+    } else {
+        Err(ZeptoError::Tool(format!(
+            "Invalid email address '{}'",
+            address
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool() -> EmailTool {
+        let config = EmailToolConfig {
+            imap_host: "imap.example.com".into(),
+            smtp_host: "smtp.example.com".into(),
+            username: "me@example.com".into(),
+            ..Default::default()
+        };
+        EmailTool::new(config, EmailAuth::Password("secret".into()))
+    }
+
+    #[test]
+    fn test_is_dangerous_action() {
+        assert!(EmailTool::is_dangerous_action("send"));
+        assert!(!EmailTool::is_dangerous_action("draft"));
+        assert!(!EmailTool::is_dangerous_action("search"));
+    }
+
+    #[test]
+    fn test_build_search_criteria() {
+        assert_eq!(build_search_criteria(&json!({})).unwrap(), "ALL");
+        let criteria = build_search_criteria(&json!({
+            "from": "alice@example.com",
+            "subject": "say \"hi\"",
+            "since": "2026-03-05",
+            "unread": true
+        }))
+        .unwrap();
+        assert_eq!(
+            criteria,
+            "FROM \"alice@example.com\" SUBJECT \"say \\\"hi\\\"\" SINCE 5-Mar-2026 UNSEEN"
+        );
+        assert!(build_search_criteria(&json!({"query": "a\r\nDELETE"})).is_err());
+        assert!(build_search_criteria(&json!({"since": "whenever"})).is_err());
+    }
+
+    #[test]
+    fn test_reply_subject_and_addresses() {
+        assert_eq!(reply_subject("Lunch?"), "Re: Lunch?");
+        assert_eq!(reply_subject("RE: Lunch?"), "RE: Lunch?");
+        assert!(validate_address("bob@example.com").is_ok());
+        assert!(validate_address("Bob <bob@example.com>").is_ok());
+        assert!(validate_address("bob").is_err());
+        assert!(validate_address("bob @example.com").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_draft_sends_code_to_user_only() {
+        let tool = tool();
+        let out = tool
+            .execute(
+                json!({"action": "draft", "to": ["bob@example.com"], "subject": "Hi", "body": "Hello"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap();
+        let code = tool.drafts.lock().await["d1"].code.as_str().to_string();
+        assert!(out.pause_for_input);
+        assert!(out.for_user.as_deref().unwrap().contains(&code));
+        assert!(!out.for_llm.contains(&code));
+        assert!(out.for_llm.contains("draft_id=d1"));
+    }
+
+    #[tokio::test]
+    async fn test_send_requires_matching_code() {
+        let tool = tool();
+        tool.execute(
+            json!({"action": "draft", "to": "bob@example.com", "body": "Hello"}),
+            &ToolContext::new(),
+        )
+        .await
+        .unwrap();
+
+        let err = tool
+            .execute(
+                json!({"action": "send", "draft_id": "d1"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("confirmation_code"));
+
+        let err = tool
+            .execute(
+                json!({"action": "send", "draft_id": "d1", "confirmation_code": "not-it"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+        assert!(tool.drafts.lock().await.contains_key("d1"));
+
+        let out = tool
+            .execute(
+                json!({"action": "discard", "draft_id": "d1"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("Discarded draft d1"));
+    }
+
+    #[tokio::test]
+    async fn test_draft_is_discarded_after_three_wrong_codes() {
+        let tool = tool();
+        tool.execute(
+            json!({"action": "draft", "to": "bob@example.com", "body": "Hello"}),
+            &ToolContext::new(),
+        )
+        .await
+        .unwrap();
+        let send = json!({"action": "send", "draft_id": "d1", "confirmation_code": "000000x"});
+        for left in ["2 of 3 attempts left", "1 of 3 attempts left"] {
+            let err = tool.execute(send.clone(), &ToolContext::new()).await;
+            assert!(err.unwrap_err().to_string().contains(left));
+        }
+        let err = tool.execute(send, &ToolContext::new()).await.unwrap_err();
+        assert!(err.to_string().contains("discarded"));
+        assert!(tool.drafts.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_draft_validates_arguments() {
+        let tool = tool();
+        let err = tool
+            .execute(
+                json!({"action": "draft", "body": "Hello"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("'to'"));
+
+        let err = tool
+            .execute(
+                json!({"action": "draft", "to": ["not-an-address"], "body": "Hello"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid email address"));
+
+        let err = tool
+            .execute(json!({"action": "read"}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("uid"));
+    }
+}
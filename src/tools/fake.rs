@@ -0,0 +1,186 @@
+//! In-memory tool for tests.
+//!
+//! [`FakeTool`] takes any arguments, answers with canned output or injected
+//! failures and records every call. Clones share state, so keep one clone
+//! to inspect calls after registering another with an agent or registry.
+//! See [`MockProvider`](crate::providers::MockProvider) for an example.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::error::{Result, ZeptoError};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// One scripted answer.
+#[derive(Debug, Clone)]
+enum FakeReply {
+    Output(String),
+    /// A result flagged as an error (`ToolOutput::error`).
+    ErrorOutput(String),
+    /// The call itself fails (`Err(ZeptoError::Tool)`).
+    Failure(String),
+}
+
+struct FakeState {
+    script: VecDeque<FakeReply>,
+    calls: Vec<Value>,
+}
+
+/// Tool that answers from a script and records its calls.
+#[derive(Clone)]
+pub struct FakeTool {
+    name: String,
+    description: String,
+    parameters: Value,
+    category: ToolCategory,
+    default: FakeReply,
+    state: Arc<Mutex<FakeState>>,
+}
+
+impl FakeTool {
+    /// Read-only tool that accepts any object and answers `ok`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: format!("Fake {} tool", name),
+            parameters: serde_json::json!({"type": "object"}),
+            category: ToolCategory::FilesystemRead,
+            default: FakeReply::Output("ok".to_string()),
+            state: Arc::new(Mutex::new(FakeState {
+                script: VecDeque::new(),
+                calls: Vec::new(),
+            })),
+        }
+    }
+
+    /// Set the description shown to the model.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Set the JSON schema of the parameters.
+    pub fn with_parameters(mut self, parameters: Value) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Set the category checked by agent modes and approvals.
+    pub fn with_category(mut self, category: ToolCategory) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Answer `output` whenever nothing is queued.
+    pub fn returning(mut self, output: &str) -> Self {
+        self.default = FakeReply::Output(output.to_string());
+        self
+    }
+
+    /// Queue one `output`.
+    pub fn then_return(self, output: &str) -> Self {
+        self.push(FakeReply::Output(output.to_string()))
+    }
+
+    /// Queue one result flagged as an error.
+    pub fn then_error_output(self, output: &str) -> Self {
+        self.push(FakeReply::ErrorOutput(output.to_string()))
+    }
+
+    /// Queue one failed call.
+    pub fn then_fail(self, error: &str) -> Self {
+        self.push(FakeReply::Failure(error.to_string()))
+    }
+
+    fn push(self, reply: FakeReply) -> Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.script.push_back(reply);
+        }
+        self
+    }
+
+    /// Arguments of every call so far, oldest first.
+    pub fn calls(&self) -> Vec<Value> {
+        self.state
+            .lock()
+            .map(|s| s.calls.clone())
+            .unwrap_or_default()
+    }
+
+    /// Number of calls so far.
+    pub fn call_count(&self) -> usize {
+        self.state.lock().map(|s| s.calls.len()).unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl Tool for FakeTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters(&self) -> Value {
+        self.parameters.clone()
+    }
+
+    fn category(&self) -> ToolCategory {
+        self.category
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let reply = {
+            let mut state = self
+                .state
+                .lock()
+                .map_err(|_| ZeptoError::Tool("FakeTool state poisoned".into()))?;
+            state.calls.push(args);
+            state
+                .script
+                .pop_front()
+                .unwrap_or_else(|| self.default.clone())
+        };
+        match reply {
+            FakeReply::Output(output) => Ok(ToolOutput::llm_only(output)),
+            FakeReply::ErrorOutput(output) => Ok(ToolOutput::error(output)),
+            FakeReply::Failure(error) => Err(ZeptoError::Tool(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_fake_tool_script_and_calls() {
+        let tool = FakeTool::new("lookup")
+            .returning("default")
+            .then_return("first")
+            .then_error_output("not found")
+            .then_fail("boom");
+        let handle = tool.clone();
+        let ctx = ToolContext::new();
+
+        let first = tool.execute(json!({"q": 1}), &ctx).await.unwrap();
+        assert_eq!(first.for_llm, "first");
+        let second = tool.execute(json!({"q": 2}), &ctx).await.unwrap();
+        assert!(second.is_error);
+        let third = tool.execute(json!({}), &ctx).await.unwrap_err();
+        assert!(matches!(third, ZeptoError::Tool(msg) if msg == "boom"));
+        let fourth = tool.execute(json!({}), &ctx).await.unwrap();
+        assert_eq!(fourth.for_llm, "default");
+
+        assert_eq!(handle.call_count(), 4);
+        assert_eq!(handle.calls()[1]["q"], 2);
+        assert_eq!(handle.category(), ToolCategory::FilesystemRead);
+    }
+}
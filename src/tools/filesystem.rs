@@ -17,6 +17,7 @@ use crate::error::{Result, ZeptoError};
 use crate::security::check_hardlink_write;
 use crate::security::{ensure_directory_chain_secure, revalidate_path, validate_path_in_workspace};
 use crate::tools::diff::apply_unified_diff;
+use crate::tools::undo::record_before;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
@@ -27,7 +28,7 @@ use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 /// filesystem tools must not operate outside a defined workspace.
 ///
 /// Returns `(resolved_path, workspace)` so callers can re-validate before I/O.
-fn resolve_path(path: &str, ctx: &ToolContext) -> Result<(String, String)> {
+pub(crate) fn resolve_path(path: &str, ctx: &ToolContext) -> Result<(String, String)> {
     let workspace = ctx.workspace.as_ref().ok_or_else(|| {
         ZeptoError::SecurityViolation(
             "Workspace not configured; filesystem tools require a workspace for safety".to_string(),
@@ -110,7 +111,7 @@ fn write_file_secure_blocking(path: &Path, workspace: &str, content: &[u8]) -> R
     Ok(())
 }
 
-async fn write_file_secure(path: &Path, workspace: &str, content: &[u8]) -> Result<()> {
+pub(crate) async fn write_file_secure(path: &Path, workspace: &str, content: &[u8]) -> Result<()> {
     let path = path.to_path_buf();
     let workspace = workspace.to_string();
     let content = content.to_vec();
@@ -262,6 +263,7 @@ impl Tool for WriteFileTool {
         let (full_path, workspace) = resolve_path(path, ctx)?;
         let full_path_ref = Path::new(&full_path);
 
+        record_before(ctx, full_path_ref).await;
         write_file_secure(full_path_ref, &workspace, content.as_bytes()).await?;
 
         Ok(ToolOutput::llm_only(format!(
@@ -470,6 +472,7 @@ impl Tool for EditFileTool {
             let (new_content, summary) = apply_unified_diff(&content, diff_str)
                 .map_err(|e| ZeptoError::Tool(format!("Diff apply failed: {}", e)))?;
 
+            record_before(ctx, full_path_ref).await;
             write_file_secure(full_path_ref, &workspace, new_content.as_bytes()).await?;
 
             Ok(ToolOutput::llm_only(format!(
@@ -494,6 +497,7 @@ impl Tool for EditFileTool {
 
             let new_content = content.replace(old_text, new_text);
 
+            record_before(ctx, full_path_ref).await;
             write_file_secure(full_path_ref, &workspace, new_content.as_bytes()).await?;
 
             let replacements = content.matches(old_text).count();
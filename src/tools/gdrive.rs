@@ -0,0 +1,901 @@
+//! Google Drive tool: list, search, download and upload files, and read
+//! Google Docs, Sheets and Slides as text.
+//!
+//! Shares [`GoogleAuth`] with the Sheets tool. Google-native files have no
+//! bytes of their own, so `export` and `download` convert them through the
+//! Drive export endpoint (Docs as markdown by default). Uploads use a single
+//! multipart request and are limited to [`MAX_UPLOAD_BYTES`].
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use reqwest::Response;
+use serde_json::{json, Value};
+
+use crate::error::{Result, ZeptoError};
+use crate::security::revalidate_path;
+use crate::tools::filesystem::{resolve_path, write_file_secure};
+use crate::tools::undo::record_before;
+use crate::utils::http::Client;
+
+use super::google_auth::GoogleAuth;
+use super::{Citation, Tool, ToolCategory, ToolContext, ToolOutput};
+
+const DRIVE_API_BASE: &str = "https://www.googleapis.com/drive/v3/files";
+const DRIVE_UPLOAD_BASE: &str = "https://www.googleapis.com/upload/drive/v3/files";
+
+/// Scopes requested for service accounts when none are configured: read
+/// everything shared with the account, write only files it created.
+const DEFAULT_SCOPES: &[&str] = &["drive.readonly", "drive.file"];
+
+/// File fields requested from the API.
+const FILE_FIELDS: &str = "id,name,mimeType,modifiedTime,size,owners(displayName,emailAddress),\
+                           sharingUser(displayName,emailAddress),webViewLink";
+
+/// Results returned by `list` and `search` unless `limit` says otherwise.
+const DEFAULT_LIMIT: u64 = 20;
+
+/// Upper bound for `limit`.
+const MAX_LIMIT: u64 = 100;
+
+/// Largest file downloaded (50 MB).
+const MAX_DOWNLOAD_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Largest file uploaded; bigger files need a resumable upload (5 MB).
+const MAX_UPLOAD_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Characters of exported text returned to the model.
+const MAX_EXPORT_CHARS: usize = 100_000;
+
+const DOC_MIME: &str = "application/vnd.google-apps.document";
+const SHEET_MIME: &str = "application/vnd.google-apps.spreadsheet";
+const SLIDES_MIME: &str = "application/vnd.google-apps.presentation";
+const DRAWING_MIME: &str = "application/vnd.google-apps.drawing";
+const FOLDER_MIME: &str = "application/vnd.google-apps.folder";
+const GOOGLE_MIME_PREFIX: &str = "application/vnd.google-apps.";
+
+const DOCX_MIME: &str = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+const XLSX_MIME: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+const PPTX_MIME: &str = "application/vnd.openxmlformats-officedocument.presentationml.presentation";
+
+/// Export formats per Google file type: (type, format, MIME type,
+/// extension). The first format listed for a type is its default.
+const EXPORT_FORMATS: &[(&str, &str, &str, &str)] = &[
+    (DOC_MIME, "markdown", "text/markdown", "md"),
+    (DOC_MIME, "text", "text/plain", "txt"),
+    (DOC_MIME, "html", "text/html", "html"),
+    (DOC_MIME, "docx", DOCX_MIME, "docx"),
+    (DOC_MIME, "pdf", "application/pdf", "pdf"),
+    (SHEET_MIME, "csv", "text/csv", "csv"),
+    (SHEET_MIME, "xlsx", XLSX_MIME, "xlsx"),
+    (SHEET_MIME, "pdf", "application/pdf", "pdf"),
+    (SLIDES_MIME, "text", "text/plain", "txt"),
+    (SLIDES_MIME, "pptx", PPTX_MIME, "pptx"),
+    (SLIDES_MIME, "pdf", "application/pdf", "pdf"),
+    (DRAWING_MIME, "png", "image/png", "png"),
+    (DRAWING_MIME, "svg", "image/svg+xml", "svg"),
+    (DRAWING_MIME, "pdf", "application/pdf", "pdf"),
+];
+
+/// Upload MIME types by extension, with the Google type each converts to.
+const UPLOAD_TYPES: &[(&str, &str, Option<&str>)] = &[
+    ("md", "text/markdown", Some(DOC_MIME)),
+    ("txt", "text/plain", Some(DOC_MIME)),
+    ("html", "text/html", Some(DOC_MIME)),
+    ("docx", DOCX_MIME, Some(DOC_MIME)),
+    ("csv", "text/csv", Some(SHEET_MIME)),
+    ("xlsx", XLSX_MIME, Some(SHEET_MIME)),
+    ("pptx", PPTX_MIME, Some(SLIDES_MIME)),
+    ("pdf", "application/pdf", None),
+    ("json", "application/json", None),
+    ("png", "image/png", None),
+    ("jpg", "image/jpeg", None),
+    ("jpeg", "image/jpeg", None),
+];
+
+/// Human-readable kind of a Drive MIME type.
+fn kind_label(mime: &str) -> &str {
+    match mime {
+        DOC_MIME => "Google Doc",
+        SHEET_MIME => "Google Sheet",
+        SLIDES_MIME => "Google Slides",
+        DRAWING_MIME => "Google Drawing",
+        FOLDER_MIME => "folder",
+        "application/pdf" => "PDF",
+        other => other,
+    }
+}
+
+/// MIME type for the `type` filter.
+fn type_mime(kind: &str) -> Result<&'static str> {
+    match kind.trim().to_ascii_lowercase().as_str() {
+        "document" | "doc" => Ok(DOC_MIME),
+        "spreadsheet" | "sheet" => Ok(SHEET_MIME),
+        "presentation" | "slides" => Ok(SLIDES_MIME),
+        "folder" => Ok(FOLDER_MIME),
+        "pdf" => Ok("application/pdf"),
+        other => Err(ZeptoError::Tool(format!(
+            "Unknown type '{}'. Use document, spreadsheet, presentation, folder or pdf",
+            other
+        ))),
+    }
+}
+
+/// Export MIME type and extension for a Google-native file, or `None` for
+/// files that are downloaded as-is.
+fn export_format(mime: &str, format: Option<&str>) -> Result<Option<(&'static str, &'static str)>> {
+    if !mime.starts_with(GOOGLE_MIME_PREFIX) {
+        return Ok(None);
+    }
+    let formats: Vec<_> = EXPORT_FORMATS.iter().filter(|f| f.0 == mime).collect();
+    let chosen = match format {
+        None => formats.first(),
+        Some(wanted) => formats
+            .iter()
+            .find(|f| f.1.eq_ignore_ascii_case(wanted.trim())),
+    };
+    match chosen {
+        Some(&&(_, _, export_mime, extension)) => Ok(Some((export_mime, extension))),
+        None if formats.is_empty() => Err(ZeptoError::Tool(format!(
+            "A {} cannot be exported",
+            kind_label(mime)
+        ))),
+        None => Err(ZeptoError::Tool(format!(
+            "Format '{}' is not available for a {}; use {}",
+            format.unwrap_or_default(),
+            kind_label(mime),
+            formats.iter().map(|f| f.1).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
+
+fn is_text_mime(mime: &str) -> bool {
+    mime.starts_with("text/")
+        || matches!(
+            mime,
+            "application/json" | "application/xml" | "application/x-yaml" | "application/yaml"
+        )
+}
+
+/// Validate that a file ID is safe to interpolate into a URL path.
+fn validate_file_id(id: &str) -> Result<()> {
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ZeptoError::Tool(format!(
+            "Invalid file ID '{}': only alphanumeric characters, hyphens, and underscores are allowed",
+            id
+        )));
+    }
+    Ok(())
+}
+
+/// Quote a value for a Drive `q` expression.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// RFC 3339 timestamp for `modified_after`, which may also be a plain date.
+fn parse_timestamp(value: &str) -> Result<String> {
+    let value = value.trim();
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(time
+            .with_timezone(&chrono::Utc)
+            .format("%Y-%m-%dT%H:%M:%SZ")
+            .to_string());
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| format!("{}T00:00:00Z", date))
+        .map_err(|_| {
+            ZeptoError::Tool(format!(
+                "Invalid modified_after '{}': use YYYY-MM-DD or an RFC 3339 timestamp",
+                value
+            ))
+        })
+}
+
+/// Drive `q` expression for `list` and `search`.
+fn build_query(args: &Value, text: Option<&str>) -> Result<String> {
+    let mut clauses = vec!["trashed = false".to_string()];
+    if let Some(text) = text {
+        clauses.push(format!(
+            "(name contains {0} or fullText contains {0})",
+            quote(text)
+        ));
+    }
+    if let Some(folder) = args.get("folder_id").and_then(Value::as_str) {
+        validate_file_id(folder.trim())?;
+        clauses.push(format!("{} in parents", quote(folder.trim())));
+    }
+    if let Some(kind) = args.get("type").and_then(Value::as_str) {
+        clauses.push(format!("mimeType = {}", quote(type_mime(kind)?)));
+    }
+    if args.get("shared_with_me").and_then(Value::as_bool) == Some(true) {
+        clauses.push("sharedWithMe = true".to_string());
+    }
+    if let Some(after) = args.get("modified_after").and_then(Value::as_str) {
+        clauses.push(format!(
+            "modifiedTime > {}",
+            quote(&parse_timestamp(after)?)
+        ));
+    }
+    Ok(clauses.join(" and "))
+}
+
+fn person(value: Option<&Value>) -> Option<String> {
+    let value = value?;
+    value
+        .get("displayName")
+        .or_else(|| value.get("emailAddress"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn modified(file: &Value) -> Option<String> {
+    let raw = file.get("modifiedTime").and_then(Value::as_str)?;
+    Some(
+        chrono::DateTime::parse_from_rfc3339(raw)
+            .map(|t| {
+                t.with_timezone(&chrono::Utc)
+                    .format("%Y-%m-%d %H:%M UTC")
+                    .to_string()
+            })
+            .unwrap_or_else(|_| raw.to_string()),
+    )
+}
+
+/// One line per file: name, kind, ID, then modification, owner, sharer and
+/// link.
+fn render_file(file: &Value) -> String {
+    let text = |key: &str| file.get(key).and_then(Value::as_str).unwrap_or("");
+    let mut details = vec![format!("id {}", text("id"))];
+    if let Some(time) = modified(file) {
+        details.push(format!("modified {}", time));
+    }
+    if let Some(owner) = person(file.get("owners").and_then(|o| o.get(0))) {
+        details.push(format!("owner {}", owner));
+    }
+    if let Some(sharer) = person(file.get("sharingUser")) {
+        details.push(format!("shared by {}", sharer));
+    }
+    if let Some(size) = file.get("size").and_then(|s| {
+        s.as_str()
+            .and_then(|s| s.parse::<u64>().ok())
+            .or(s.as_u64())
+    }) {
+        details.push(format!("{} bytes", size));
+    }
+    let mut line = format!(
+        "- {} ({}) — {}",
+        text("name"),
+        kind_label(text("mimeType")),
+        details.join(", ")
+    );
+    if !text("webViewLink").is_empty() {
+        line.push_str(&format!("\n  {}", text("webViewLink")));
+    }
+    line
+}
+
+fn render_files(files: &[Value], more: bool) -> String {
+    if files.is_empty() {
+        return "No files found.".to_string();
+    }
+    let mut out = format!(
+        "Found {} file{}:",
+        files.len(),
+        if files.len() == 1 { "" } else { "s" }
+    );
+    for file in files {
+        out.push('\n');
+        out.push_str(&render_file(file));
+    }
+    if more {
+        out.push_str("\n(more results; narrow the search or raise limit)");
+    }
+    out
+}
+
+/// Multipart body for an upload: JSON metadata, then the file.
+fn multipart_body(boundary: &str, metadata: &Value, mime: &str, content: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{boundary}\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{metadata}\r\n\
+         --{boundary}\r\nContent-Type: {mime}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+    body
+}
+
+/// File name safe to use in the workspace.
+fn safe_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim().trim_start_matches('.').to_string();
+    if cleaned.is_empty() {
+        "download".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Error for a failed Drive response, using the API's message when present.
+async fn api_error(response: Response) -> ZeptoError {
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    let message = body
+        .pointer("/error/message")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown error");
+    ZeptoError::Tool(format!("Google Drive API error {}: {}", status, message))
+}
+
+/// Tool for Google Drive files and Docs.
+#[derive(Debug)]
+pub struct GoogleDriveTool {
+    client: Client,
+    auth: GoogleAuth,
+}
+
+impl GoogleDriveTool {
+    /// Create with an OAuth access token.
+    pub fn new(access_token: &str) -> Self {
+        Self {
+            client: crate::utils::http::client(),
+            auth: GoogleAuth::token(access_token),
+        }
+    }
+
+    /// Create from a base64-encoded service account key file.
+    pub fn from_service_account(encoded_json: &str) -> Result<Self> {
+        Ok(Self {
+            client: crate::utils::http::client(),
+            auth: GoogleAuth::from_service_account(encoded_json, DEFAULT_SCOPES)?,
+        })
+    }
+
+    /// Request `scopes` instead of the defaults when authenticating as a
+    /// service account.
+    pub fn with_scopes(mut self, scopes: &[String]) -> Self {
+        self.auth = self.auth.with_scopes(scopes);
+        self
+    }
+
+    async fn get_json(&self, url: &str, query: &[(&str, String)]) -> Result<Value> {
+        let response = self
+            .auth
+            .send(&self.client, |client| client.get(url).query(query))
+            .await?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Invalid Drive response payload: {}", e)))
+    }
+
+    async fn list_files(&self, args: &Value, text: Option<&str>) -> Result<String> {
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+        let mut query = vec![
+            ("q", build_query(args, text)?),
+            ("pageSize", limit.to_string()),
+            ("fields", format!("nextPageToken,files({})", FILE_FIELDS)),
+            ("supportsAllDrives", "true".to_string()),
+            ("includeItemsFromAllDrives", "true".to_string()),
+        ];
+        // Full-text searches are ranked by relevance and cannot be sorted.
+        if text.is_none() {
+            query.push(("orderBy", "modifiedTime desc".to_string()));
+        }
+        let body = self.get_json(DRIVE_API_BASE, &query).await?;
+        let files = body
+            .get("files")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        Ok(render_files(&files, body.get("nextPageToken").is_some()))
+    }
+
+    async fn metadata(&self, file_id: &str) -> Result<Value> {
+        validate_file_id(file_id)?;
+        self.get_json(
+            &format!("{}/{}", DRIVE_API_BASE, file_id),
+            &[
+                ("fields", FILE_FIELDS.to_string()),
+                ("supportsAllDrives", "true".to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Bytes of a file, exported when Google-native. Returns the bytes,
+    /// their MIME type and, for exports, the extension to save them under.
+    async fn content(
+        &self,
+        file: &Value,
+        format: Option<&str>,
+    ) -> Result<(Vec<u8>, String, Option<&'static str>)> {
+        let id = file.get("id").and_then(Value::as_str).unwrap_or_default();
+        let mime = file
+            .get("mimeType")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let export = export_format(mime, format)?;
+        let (url, query, content_mime) = match export {
+            Some((export_mime, _)) => (
+                format!("{}/{}/export", DRIVE_API_BASE, id),
+                vec![("mimeType", export_mime.to_string())],
+                export_mime.to_string(),
+            ),
+            None => {
+                let size = file
+                    .get("size")
+                    .and_then(Value::as_str)
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(0);
+                if size > MAX_DOWNLOAD_BYTES {
+                    return Err(ZeptoError::Tool(format!(
+                        "File too large: {} bytes (max {}MB)",
+                        size,
+                        MAX_DOWNLOAD_BYTES / 1024 / 1024
+                    )));
+                }
+                (
+                    format!("{}/{}", DRIVE_API_BASE, id),
+                    vec![
+                        ("alt", "media".to_string()),
+                        ("supportsAllDrives", "true".to_string()),
+                    ],
+                    mime.to_string(),
+                )
+            }
+        };
+        let response = self
+            .auth
+            .send(&self.client, |client| client.get(&url).query(&query))
+            .await?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Failed to download file: {}", e)))?;
+        if bytes.len() as u64 > MAX_DOWNLOAD_BYTES {
+            return Err(ZeptoError::Tool(format!(
+                "File too large: {} bytes (max {}MB)",
+                bytes.len(),
+                MAX_DOWNLOAD_BYTES / 1024 / 1024
+            )));
+        }
+        Ok((bytes.to_vec(), content_mime, export.map(|(_, ext)| ext)))
+    }
+
+    async fn export(&self, args: &Value, file_id: &str) -> Result<ToolOutput> {
+        let file = self.metadata(file_id).await?;
+        let name = file.get("name").and_then(Value::as_str).unwrap_or(file_id);
+        let format = args.get("format").and_then(Value::as_str);
+        let (bytes, mime, _) = self.content(&file, format).await?;
+        if !is_text_mime(&mime) {
+            return Err(ZeptoError::Tool(format!(
+                "'{}' is a {} and has no text export; use action 'download' and a reader \
+                 tool such as pdf_read or docx_read",
+                name,
+                kind_label(&mime)
+            )));
+        }
+        let text = String::from_utf8_lossy(&bytes);
+        let mut out = format!(
+            "# {}\n{}\n\n",
+            name,
+            render_file(&file).trim_start_matches("- ")
+        );
+        if text.chars().count() > MAX_EXPORT_CHARS {
+            out.extend(text.chars().take(MAX_EXPORT_CHARS));
+            out.push_str(&format!(
+                "\n\n[Truncated at {} characters; download the file for the rest]",
+                MAX_EXPORT_CHARS
+            ));
+        } else {
+            out.push_str(&text);
+        }
+        let citations = file
+            .get("webViewLink")
+            .and_then(Value::as_str)
+            .map(|link| vec![Citation::web(link, Some(name.to_string()))])
+            .unwrap_or_default();
+        Ok(ToolOutput::llm_only(out).with_citations(citations))
+    }
+
+    async fn download(&self, args: &Value, ctx: &ToolContext, file_id: &str) -> Result<String> {
+        let file = self.metadata(file_id).await?;
+        let name = file.get("name").and_then(Value::as_str).unwrap_or(file_id);
+        let format = args.get("format").and_then(Value::as_str);
+        let (bytes, mime, extension) = self.content(&file, format).await?;
+
+        let target = match args.get("path").and_then(Value::as_str) {
+            Some(path) if !path.trim().is_empty() => path.trim().to_string(),
+            _ => {
+                let file_name = safe_file_name(name);
+                match extension {
+                    Some(ext)
+                        if !file_name
+                            .to_ascii_lowercase()
+                            .ends_with(&format!(".{}", ext)) =>
+                    {
+                        format!("{}.{}", file_name, ext)
+                    }
+                    _ => file_name,
+                }
+            }
+        };
+        let (full_path, workspace) = resolve_path(&target, ctx)?;
+        let path = Path::new(&full_path);
+        record_before(ctx, path).await;
+        write_file_secure(path, &workspace, &bytes).await?;
+        Ok(format!(
+            "Saved '{}' ({}) to {} ({} bytes)",
+            name,
+            kind_label(&mime),
+            target,
+            bytes.len()
+        ))
+    }
+
+    async fn upload(&self, args: &Value, ctx: &ToolContext) -> Result<String> {
+        if !self.auth.can_write() {
+            return Err(ZeptoError::Tool(
+                "Google Drive is configured with read-only scopes; add 'drive.file' to \
+                 tools.google_drive.scopes to upload"
+                    .to_string(),
+            ));
+        }
+        let path_arg = args
+            .get("path")
+            .and_then(Value::as_str)
+            .filter(|p| !p.trim().is_empty())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'path' for upload".to_string()))?;
+        let (full_path, workspace) = resolve_path(path_arg, ctx)?;
+        let path = Path::new(&full_path);
+        revalidate_path(path, &workspace)?;
+        let size = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Cannot open {}: {}", path_arg, e)))?
+            .len();
+        if size > MAX_UPLOAD_BYTES {
+            return Err(ZeptoError::Tool(format!(
+                "File too large to upload: {} bytes (max {}MB)",
+                size,
+                MAX_UPLOAD_BYTES / 1024 / 1024
+            )));
+        }
+        let content = tokio::fs::read(path).await?;
+
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "upload".to_string());
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+        let known = UPLOAD_TYPES.iter().find(|t| t.0 == extension);
+        let mime = known.map_or("application/octet-stream", |t| t.1);
+
+        let mut metadata = json!({
+            "name": args
+                .get("name")
+                .and_then(Value::as_str)
+                .filter(|n| !n.trim().is_empty())
+                .map_or(file_name.as_str(), str::trim),
+        });
+        if args.get("convert").and_then(Value::as_bool) == Some(true) {
+            let google_mime = known.and_then(|t| t.2).ok_or_else(|| {
+                ZeptoError::Tool(format!(
+                    "Cannot convert '{}' to a Google file; convert works for md, txt, html, \
+                     docx, csv, xlsx and pptx",
+                    file_name
+                ))
+            })?;
+            metadata["mimeType"] = json!(google_mime);
+        }
+        if let Some(folder) = args.get("folder_id").and_then(Value::as_str) {
+            validate_file_id(folder.trim())?;
+            metadata["parents"] = json!([folder.trim()]);
+        }
+
+        let boundary = format!("zeptoclaw-{}", uuid::Uuid::new_v4().simple());
+        let body = multipart_body(&boundary, &metadata, mime, &content);
+        let content_type = format!("multipart/related; boundary={}", boundary);
+        let response = self
+            .auth
+            .send(&self.client, |client| {
+                client
+                    .post(DRIVE_UPLOAD_BASE)
+                    .query(&[
+                        ("uploadType", "multipart"),
+                        ("supportsAllDrives", "true"),
+                        ("fields", "id,name,mimeType,webViewLink"),
+                    ])
+                    .header("Content-Type", &content_type)
+                    .body(body.clone())
+            })
+            .await?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        let created: Value = response
+            .json()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Invalid Drive response payload: {}", e)))?;
+        let text = |key: &str| created.get(key).and_then(Value::as_str).unwrap_or("");
+        let mut out = format!(
+            "Uploaded {} to Google Drive as '{}' ({}, id {})",
+            path_arg,
+            text("name"),
+            kind_label(text("mimeType")),
+            text("id")
+        );
+        if !text("webViewLink").is_empty() {
+            out.push_str(&format!("\n{}", text("webViewLink")));
+        }
+        Ok(out)
+    }
+}
+
+#[async_trait]
+impl Tool for GoogleDriveTool {
+    fn name(&self) -> &str {
+        "google_drive"
+    }
+
+    fn description(&self) -> &str {
+        "Google Drive files and Docs. Actions: list (recent files, optionally in a folder), \
+         search (name and full text), export (read a Doc as markdown, a Sheet's first tab as \
+         CSV, Slides as text, or any text file), download (save a file to the workspace), \
+         upload (send a workspace file to Drive). list/search accept type, shared_with_me and \
+         modified_after filters."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Google Drive/Docs"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "search", "export", "download", "upload"],
+                    "description": "Operation to perform"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "For search: text to find in file names and contents"
+                },
+                "file_id": {
+                    "type": "string",
+                    "description": "For export/download: Drive file ID from list or search"
+                },
+                "folder_id": {
+                    "type": "string",
+                    "description": "For list/search: only files in this folder. For upload: destination folder"
+                },
+                "type": {
+                    "type": "string",
+                    "enum": ["document", "spreadsheet", "presentation", "folder", "pdf"],
+                    "description": "For list/search: only files of this type"
+                },
+                "shared_with_me": {
+                    "type": "boolean",
+                    "description": "For list/search: only files others shared with you"
+                },
+                "modified_after": {
+                    "type": "string",
+                    "description": "For list/search: YYYY-MM-DD or RFC 3339 timestamp"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "For list/search: maximum results (default: 20, max: 100)"
+                },
+                "format": {
+                    "type": "string",
+                    "description": "For export/download of Google files: markdown, text, html, docx or pdf for Docs; csv, xlsx or pdf for Sheets; text, pptx or pdf for Slides"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "For download: workspace path to save to (default: the file name). For upload: workspace file to send"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "For upload: name in Drive (default: the file name)"
+                },
+                "convert": {
+                    "type": "boolean",
+                    "description": "For upload: convert to a Google Doc, Sheet or Slides"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ZeptoError::Tool("Missing 'action'".to_string()))?;
+        let file_id = || {
+            args.get("file_id")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .ok_or_else(|| ZeptoError::Tool(format!("Missing 'file_id' for {}", action)))
+        };
+
+        let output = match action {
+            "list" => self.list_files(&args, None).await?,
+            "search" => {
+                let query = args
+                    .get("query")
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .filter(|q| !q.is_empty())
+                    .ok_or_else(|| ZeptoError::Tool("Missing 'query' for search".to_string()))?;
+                self.list_files(&args, Some(query)).await?
+            }
+            "export" => return self.export(&args, file_id()?).await,
+            "download" => self.download(&args, ctx, file_id()?).await?,
+            "upload" => self.upload(&args, ctx).await?,
+            other => return Err(ZeptoError::Tool(format!("Unknown action '{}'", other))),
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_query() {
+        let query = build_query(
+            &json!({
+                "folder_id": "abc_123",
+                "type": "doc",
+                "shared_with_me": true,
+                "modified_after": "2024-05-01"
+            }),
+            Some("Dave's \\ plan"),
+        )
+        .unwrap();
+        assert_eq!(
+            query,
+            "trashed = false and (name contains 'Dave\\'s \\\\ plan' or fullText contains \
+             'Dave\\'s \\\\ plan') and 'abc_123' in parents and mimeType = \
+             'application/vnd.google-apps.document' and sharedWithMe = true and modifiedTime > \
+             '2024-05-01T00:00:00Z'"
+        );
+        assert_eq!(build_query(&json!({}), None).unwrap(), "trashed = false");
+    }
+
+    #[test]
+    fn test_build_query_rejects_bad_filters() {
+        assert!(build_query(&json!({"folder_id": "x' or 'a"}), None).is_err());
+        assert!(build_query(&json!({"type": "video"}), None).is_err());
+        assert!(build_query(&json!({"modified_after": "yesterday"}), None).is_err());
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(
+            parse_timestamp("2024-05-01T10:00:00+02:00").unwrap(),
+            "2024-05-01T08:00:00Z"
+        );
+        assert_eq!(
+            parse_timestamp("2024-05-01").unwrap(),
+            "2024-05-01T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_export_format() {
+        assert_eq!(
+            export_format(DOC_MIME, None).unwrap(),
+            Some(("text/markdown", "md"))
+        );
+        assert_eq!(
+            export_format(SHEET_MIME, Some("XLSX")).unwrap(),
+            Some((XLSX_MIME, "xlsx"))
+        );
+        assert_eq!(export_format("application/pdf", Some("csv")).unwrap(), None);
+        let err = export_format(SLIDES_MIME, Some("markdown"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("use text, pptx, pdf"), "got: {}", err);
+        assert!(export_format(FOLDER_MIME, None).is_err());
+    }
+
+    #[test]
+    fn test_render_files() {
+        let files = vec![json!({
+            "id": "1abc",
+            "name": "Q3 plan",
+            "mimeType": DOC_MIME,
+            "modifiedTime": "2024-05-01T14:03:09.512Z",
+            "owners": [{"displayName": "Dave Lee", "emailAddress": "dave@example.com"}],
+            "sharingUser": {"emailAddress": "dave@example.com"},
+            "webViewLink": "https://docs.google.com/document/d/1abc/edit"
+        })];
+        assert_eq!(
+            render_files(&files, true),
+            "Found 1 file:\n\
+             - Q3 plan (Google Doc) — id 1abc, modified 2024-05-01 14:03 UTC, owner Dave Lee, \
+             shared by dave@example.com\n  https://docs.google.com/document/d/1abc/edit\n\
+             (more results; narrow the search or raise limit)"
+        );
+        assert_eq!(render_files(&[], false), "No files found.");
+    }
+
+    #[test]
+    fn test_multipart_body() {
+        let body = multipart_body("b", &json!({"name": "a.txt"}), "text/plain", b"hi");
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "--b\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{\"name\":\"a.txt\"}\r\n\
+             --b\r\nContent-Type: text/plain\r\n\r\nhi\r\n--b--\r\n"
+        );
+    }
+
+    #[test]
+    fn test_safe_file_name() {
+        assert_eq!(safe_file_name("Q3: plan/draft"), "Q3_ plan_draft");
+        assert_eq!(safe_file_name("../secret"), "_secret");
+        assert_eq!(safe_file_name("  "), "download");
+    }
+
+    #[tokio::test]
+    async fn test_missing_arguments() {
+        let tool = GoogleDriveTool::new("token");
+        let ctx = ToolContext::new();
+        for (args, expected) in [
+            (json!({}), "Missing 'action'"),
+            (json!({"action": "search"}), "Missing 'query'"),
+            (json!({"action": "export"}), "Missing 'file_id'"),
+            (
+                json!({"action": "download", "file_id": "../x"}),
+                "Invalid file ID",
+            ),
+            (json!({"action": "upload"}), "Missing 'path'"),
+            (json!({"action": "delete"}), "Unknown action"),
+        ] {
+            let err = tool.execute(args, &ctx).await.unwrap_err().to_string();
+            assert!(
+                err.contains(expected),
+                "expected {}, got: {}",
+                expected,
+                err
+            );
+        }
+    }
+}
@@ -13,13 +13,18 @@
 //! - `commit`      — stage all tracked changes and commit (requires `message`)
 //! - `add`         — stage a file or directory (requires `path`)
 //! - `checkout`    — switch branches (requires `branch`)
+//!
+//! [`GitTool::snapshot_workspace`] reuses the same plumbing for the
+//! per-turn workspace snapshots enabled by `tools.git_snapshots`.
 
 use std::path::Path;
 use std::process::Command;
+use std::sync::Mutex;
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
+use crate::config::GitSnapshotConfig;
 use crate::error::{Result, ZeptoError};
 use crate::security::ShellSecurityConfig;
 
@@ -28,6 +33,12 @@ use super::{Tool, ToolContext, ToolOutput};
 const DEFAULT_LOG_COUNT: u64 = 10;
 const MAX_LOG_COUNT: u64 = 200;
 
+/// Snapshots stage everything in the workspace except the undo journal.
+const SNAPSHOT_EXCLUDE: &str = ":(exclude).zeptoclaw/undo";
+
+/// Keeps concurrent turns from racing on the same index.
+static SNAPSHOT_LOCK: Mutex<()> = Mutex::new(());
+
 /// Tool that exposes common `git` operations by shelling out to the `git` CLI.
 ///
 /// The tool is skipped at registration time when `git` is not found on PATH
@@ -97,6 +108,82 @@ impl GitTool {
             Err(ZeptoError::Tool(format!("git error: {}", detail)))
         }
     }
+
+    /// Commit every change in `workspace` as one snapshot with `message`.
+    ///
+    /// Initializes a repository first when the workspace is not in one and
+    /// `config.init_repo` is set. Returns the short commit hash, or `None`
+    /// when there was nothing to commit.
+    pub fn snapshot_workspace(
+        workspace: &str,
+        config: &GitSnapshotConfig,
+        message: &str,
+    ) -> Result<Option<String>> {
+        let _guard = SNAPSHOT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        if Self::run(&["rev-parse", "--is-inside-work-tree"], workspace).is_err() {
+            if !config.init_repo {
+                return Err(ZeptoError::Tool(format!(
+                    "Workspace '{}' is not a git repository",
+                    workspace
+                )));
+            }
+            Self::run(&["init", "--quiet"], workspace)?;
+        }
+
+        Self::run(&["add", "--all", "--", ".", SNAPSHOT_EXCLUDE], workspace)?;
+        let staged = Self::run(&["diff", "--cached", "--name-only", "--", "."], workspace)?;
+        if staged.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let name = format!("user.name={}", config.author_name);
+        let email = format!("user.email={}", config.author_email);
+        Self::run(
+            &[
+                "-c",
+                &name,
+                "-c",
+                &email,
+                "-c",
+                "commit.gpgsign=false",
+                "commit",
+                "--quiet",
+                "--no-verify",
+                "-m",
+                message,
+                "--",
+                ".",
+            ],
+            workspace,
+        )?;
+        let hash = Self::run(&["rev-parse", "--short", "HEAD"], workspace)?;
+        Ok(Some(hash.trim().to_string()))
+    }
+}
+
+/// Commit message for the snapshot of one agent turn: the start of the
+/// user's message as the subject, and the session, message and turn IDs as
+/// trailers.
+pub fn snapshot_message(
+    prompt: &str,
+    session_key: &str,
+    message_id: Option<&str>,
+    turn_id: &str,
+) -> String {
+    let first_line = prompt.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let subject = crate::utils::string::preview(first_line.trim(), 60);
+    let mut message = if subject.is_empty() {
+        "zeptoclaw: agent turn".to_string()
+    } else {
+        format!("zeptoclaw: {}", subject)
+    };
+    message.push_str(&format!("\n\nSession: {}", session_key));
+    if let Some(id) = message_id {
+        message.push_str(&format!("\nMessage-Id: {}", id));
+    }
+    message.push_str(&format!("\nTurn-Id: {}", turn_id));
+    message
 }
 
 impl Default for GitTool {
@@ -606,4 +693,51 @@ mod tests {
             result.err()
         );
     }
+
+    // --- snapshots ---
+
+    #[test]
+    fn test_snapshot_workspace_commits_only_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let ws = dir.path().to_string_lossy().to_string();
+        let config = GitSnapshotConfig::default();
+
+        std::fs::write(dir.path().join("notes.md"), "hello").unwrap();
+        std::fs::create_dir_all(dir.path().join(".zeptoclaw/undo/t1")).unwrap();
+        std::fs::write(dir.path().join(".zeptoclaw/undo/t1/journal.json"), "{}").unwrap();
+
+        let message = snapshot_message("Write notes\nplease", "cli:cli", Some("42"), "t1");
+        let first = GitTool::snapshot_workspace(&ws, &config, &message).unwrap();
+        assert!(first.is_some());
+        let tracked = GitTool::run(&["ls-files"], &ws).unwrap();
+        assert_eq!(tracked.trim(), "notes.md");
+        let body = GitTool::run(&["log", "-1", "--format=%an%n%B"], &ws).unwrap();
+        assert!(body.starts_with("ZeptoClaw\nzeptoclaw: Write notes\n"));
+        assert!(body.contains("Session: cli:cli\nMessage-Id: 42\nTurn-Id: t1"));
+
+        assert_eq!(
+            GitTool::snapshot_workspace(&ws, &config, &message).unwrap(),
+            None
+        );
+
+        std::fs::remove_file(dir.path().join("notes.md")).unwrap();
+        assert!(GitTool::snapshot_workspace(&ws, &config, "second")
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_snapshot_workspace_without_init() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = GitSnapshotConfig {
+            init_repo: false,
+            ..Default::default()
+        };
+        let result =
+            GitTool::snapshot_workspace(&dir.path().to_string_lossy(), &config, "snapshot");
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("not a git repository"));
+    }
 }
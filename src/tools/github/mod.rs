@@ -0,0 +1,911 @@
+//! GitHub tools — issues, pull requests, review threads and notifications.
+//!
+//! - `github` reads: `list_issues`, `list_prs`, `pr_diff` (metadata, per-file
+//!   stats and patches within `max_diff_chars`), `review_comments` (review
+//!   threads over GraphQL, with resolution state and comment IDs) and
+//!   `notifications` (the inbox grouped by what needs the user).
+//! - `github_write` posts: `create_issue` and `reply_review_comment`. It is
+//!   in the default approval list, so every post is shown to the user first.
+//!
+//! Both authenticate with `tools.github.token` or a GitHub App installation
+//! (`tools.github.app`); `client.rs` handles rate limits and pagination.
+
+mod client;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::config::GitHubToolConfig;
+use crate::error::{Result, ZeptoError};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+use client::GitHubClient;
+
+/// Notification reasons that ask something of the user.
+const ACTION_REASONS: &[&str] = &[
+    "review_requested",
+    "mention",
+    "team_mention",
+    "assign",
+    "approval_requested",
+    "security_alert",
+];
+
+/// Longest issue or PR body excerpt shown.
+const MAX_BODY_CHARS: usize = 500;
+
+/// Review threads fetched per GraphQL page.
+const REVIEW_THREAD_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!, $after: String) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      title
+      reviewThreads(first: 50, after: $after) {
+        pageInfo { hasNextPage endCursor }
+        nodes {
+          isResolved
+          isOutdated
+          path
+          line
+          comments(first: 30) {
+            nodes { databaseId author { login } body createdAt }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn required_str<'a>(args: &'a Value, key: &str) -> Result<&'a str> {
+    str_arg(args, key).ok_or_else(|| ZeptoError::Tool(format!("Missing '{}'", key)))
+}
+
+fn number_arg(args: &Value, key: &str) -> Result<u64> {
+    args.get(key)
+        .and_then(|v| {
+            v.as_u64()
+                .or_else(|| v.as_str()?.trim().trim_start_matches('#').parse().ok())
+        })
+        .ok_or_else(|| ZeptoError::Tool(format!("Missing or invalid '{}'", key)))
+}
+
+fn string_list(args: &Value, key: &str) -> Vec<String> {
+    args.get(key)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `owner/repo` from the argument or `default_repo`.
+pub fn repo_arg(config: &GitHubToolConfig, args: &Value) -> Result<String> {
+    let repo = str_arg(args, "repo")
+        .or(config.default_repo.as_deref().map(str::trim))
+        .filter(|r| !r.is_empty())
+        .ok_or_else(|| {
+            ZeptoError::Tool("No 'repo' given and no tools.github.default_repo set".into())
+        })?;
+    let valid = repo.split_once('/').is_some_and(|(owner, name)| {
+        [owner, name].iter().all(|part| {
+            !part.chars().all(|c| c == '.')
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+    });
+    if !valid {
+        return Err(ZeptoError::Tool(format!(
+            "Invalid repo '{}'; expected owner/name",
+            repo
+        )));
+    }
+    Ok(repo.to_string())
+}
+
+fn excerpt(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    format!("{}...", cut.trim_end())
+}
+
+fn login(value: &Value) -> &str {
+    value
+        .pointer("/user/login")
+        .or_else(|| value.pointer("/author/login"))
+        .and_then(Value::as_str)
+        .unwrap_or("ghost")
+}
+
+fn date(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.chars().take(10).collect())
+        .unwrap_or_default()
+}
+
+fn more_note(more: bool) -> &'static str {
+    if more {
+        "\n(more available — narrow the filter or raise 'limit')"
+    } else {
+        ""
+    }
+}
+
+/// One line per issue; pull requests in the issues feed are skipped.
+pub fn render_issues(items: &[Value]) -> String {
+    let lines: Vec<String> = items
+        .iter()
+        .filter(|item| item.get("pull_request").is_none())
+        .map(|item| {
+            let labels: Vec<&str> = item
+                .get("labels")
+                .and_then(Value::as_array)
+                .map(|l| {
+                    l.iter()
+                        .filter_map(|x| x.get("name").and_then(Value::as_str))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let mut line = format!(
+                "#{} [{}] {} — @{}, {} comment(s), updated {}",
+                item["number"],
+                item["state"].as_str().unwrap_or("?"),
+                item["title"].as_str().unwrap_or(""),
+                login(item),
+                item["comments"].as_u64().unwrap_or(0),
+                date(item, "updated_at")
+            );
+            if !labels.is_empty() {
+                line.push_str(&format!(", labels: {}", labels.join(", ")));
+            }
+            line
+        })
+        .collect();
+    if lines.is_empty() {
+        "No issues found.".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// One line per pull request.
+pub fn render_prs(items: &[Value]) -> String {
+    if items.is_empty() {
+        return "No pull requests found.".to_string();
+    }
+    items
+        .iter()
+        .map(|pr| {
+            let draft = if pr["draft"].as_bool() == Some(true) {
+                ", draft"
+            } else {
+                ""
+            };
+            format!(
+                "#{} [{}{}] {} — @{}, {} → {}, updated {}",
+                pr["number"],
+                pr["state"].as_str().unwrap_or("?"),
+                draft,
+                pr["title"].as_str().unwrap_or(""),
+                login(pr),
+                pr.pointer("/head/ref")
+                    .and_then(Value::as_str)
+                    .unwrap_or("?"),
+                pr.pointer("/base/ref")
+                    .and_then(Value::as_str)
+                    .unwrap_or("?"),
+                date(pr, "updated_at")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// PR summary, per-file stats, then patches until `max_chars` is used up.
+pub fn render_pr_diff(pr: &Value, files: &[Value], more_files: bool, max_chars: usize) -> String {
+    let mut out = format!(
+        "PR #{}: {} (@{}, {}{})\n{} ← {}, +{} −{} in {} file(s)\n",
+        pr["number"],
+        pr["title"].as_str().unwrap_or(""),
+        login(pr),
+        pr["state"].as_str().unwrap_or("?"),
+        if pr["merged"].as_bool() == Some(true) {
+            ", merged"
+        } else {
+            ""
+        },
+        pr.pointer("/base/ref")
+            .and_then(Value::as_str)
+            .unwrap_or("?"),
+        pr.pointer("/head/ref")
+            .and_then(Value::as_str)
+            .unwrap_or("?"),
+        pr["additions"].as_u64().unwrap_or(0),
+        pr["deletions"].as_u64().unwrap_or(0),
+        pr["changed_files"].as_u64().unwrap_or(files.len() as u64),
+    );
+    if let Some(body) = pr["body"].as_str().filter(|b| !b.trim().is_empty()) {
+        out.push_str(&format!("\n{}\n", excerpt(body, MAX_BODY_CHARS)));
+    }
+
+    out.push_str("\nFiles:\n");
+    for file in files {
+        let status = match file["status"].as_str().unwrap_or("") {
+            "added" => "A",
+            "removed" => "D",
+            "renamed" => "R",
+            _ => "M",
+        };
+        out.push_str(&format!(
+            "  {} {} (+{} −{})\n",
+            status,
+            file["filename"].as_str().unwrap_or("?"),
+            file["additions"].as_u64().unwrap_or(0),
+            file["deletions"].as_u64().unwrap_or(0)
+        ));
+    }
+    if more_files {
+        out.push_str("  ... more files not listed\n");
+    }
+
+    out.push_str("\nDiff:\n");
+    let mut budget = max_chars;
+    let mut omitted = Vec::new();
+    for file in files {
+        let name = file["filename"].as_str().unwrap_or("?");
+        let Some(patch) = file["patch"].as_str() else {
+            omitted.push(format!("{} (binary or too large)", name));
+            continue;
+        };
+        let chunk = format!("--- {}\n{}\n", name, patch);
+        if chunk.len() > budget {
+            omitted.push(name.to_string());
+            continue;
+        }
+        budget -= chunk.len();
+        out.push_str(&chunk);
+    }
+    if !omitted.is_empty() {
+        out.push_str(&format!(
+            "\n(patches omitted: {} — pass 'path' to see one file)\n",
+            omitted.join(", ")
+        ));
+    }
+    out
+}
+
+/// Review threads with comment IDs for `reply_review_comment`.
+pub fn render_review_threads(title: &str, threads: &[Value], unresolved_only: bool) -> String {
+    let shown: Vec<&Value> = threads
+        .iter()
+        .filter(|t| !unresolved_only || t["isResolved"].as_bool() != Some(true))
+        .collect();
+    let resolved = threads
+        .iter()
+        .filter(|t| t["isResolved"].as_bool() == Some(true))
+        .count();
+    let mut out = format!(
+        "{} — {} review thread(s), {} unresolved\n",
+        title,
+        threads.len(),
+        threads.len() - resolved
+    );
+    if shown.is_empty() {
+        out.push_str("Nothing to address.");
+        return out;
+    }
+    for thread in shown {
+        let location = match thread["line"].as_u64() {
+            Some(line) => format!("{}:{}", thread["path"].as_str().unwrap_or("?"), line),
+            None => thread["path"].as_str().unwrap_or("(general)").to_string(),
+        };
+        let mut flags = Vec::new();
+        if thread["isResolved"].as_bool() == Some(true) {
+            flags.push("resolved");
+        }
+        if thread["isOutdated"].as_bool() == Some(true) {
+            flags.push("outdated");
+        }
+        out.push_str(&format!("\n{}", location));
+        if !flags.is_empty() {
+            out.push_str(&format!(" [{}]", flags.join(", ")));
+        }
+        out.push('\n');
+        let comments = thread
+            .pointer("/comments/nodes")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for comment in comments {
+            out.push_str(&format!(
+                "  [{}] @{} ({}): {}\n",
+                comment["databaseId"],
+                login(comment),
+                date(comment, "createdAt"),
+                excerpt(comment["body"].as_str().unwrap_or(""), MAX_BODY_CHARS)
+            ));
+        }
+    }
+    out.push_str(
+        "\nReply with github_write reply_review_comment using a comment ID in [brackets].",
+    );
+    out
+}
+
+/// Inbox summary: counts by reason, items that need the user, then the
+/// rest grouped by repository.
+pub fn summarize_notifications(items: &[Value], more: bool) -> String {
+    if items.is_empty() {
+        return "No notifications.".to_string();
+    }
+    let mut by_reason: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut needs_you = Vec::new();
+    let mut by_repo: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for item in items {
+        let reason = item["reason"].as_str().unwrap_or("other");
+        *by_reason.entry(reason).or_default() += 1;
+        let repo = item
+            .pointer("/repository/full_name")
+            .and_then(Value::as_str)
+            .unwrap_or("?");
+        let line = format!(
+            "[{}] {}: {} ({})",
+            reason,
+            item.pointer("/subject/type")
+                .and_then(Value::as_str)
+                .unwrap_or("?"),
+            item.pointer("/subject/title")
+                .and_then(Value::as_str)
+                .unwrap_or(""),
+            date(item, "updated_at")
+        );
+        if ACTION_REASONS.contains(&reason) {
+            needs_you.push(format!("{} {}", repo, line));
+        } else {
+            by_repo.entry(repo).or_default().push(line);
+        }
+    }
+
+    let counts: Vec<String> = by_reason
+        .iter()
+        .map(|(reason, count)| format!("{} {}", reason, count))
+        .collect();
+    let mut out = format!(
+        "{} notification(s){}\nBy reason: {}\n",
+        items.len(),
+        if more { " (more not shown)" } else { "" },
+        counts.join(", ")
+    );
+    if !needs_you.is_empty() {
+        out.push_str("\nNeeds you:\n");
+        for line in &needs_you {
+            out.push_str(&format!("- {}\n", line));
+        }
+    }
+    for (repo, lines) in &by_repo {
+        out.push_str(&format!("\n{} ({}):\n", repo, lines.len()));
+        for line in lines {
+            out.push_str(&format!("- {}\n", line));
+        }
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// github (reads)
+// ---------------------------------------------------------------------------
+
+/// Read-only GitHub access: issues, pull requests, review threads and
+/// notifications.
+pub struct GitHubTool {
+    client: Arc<GitHubClient>,
+}
+
+impl GitHubTool {
+    pub fn new(config: GitHubToolConfig) -> Self {
+        Self {
+            client: Arc::new(GitHubClient::new(config)),
+        }
+    }
+
+    /// The `github_write` tool sharing this client.
+    pub fn write_tool(&self) -> GitHubWriteTool {
+        GitHubWriteTool {
+            client: Arc::clone(&self.client),
+        }
+    }
+
+    fn limit(&self, args: &Value) -> usize {
+        let max = self.client.config().max_items.max(1);
+        args.get("limit")
+            .and_then(Value::as_u64)
+            .map_or(max, |n| (n as usize).clamp(1, max))
+    }
+
+    fn state(args: &Value) -> Result<String> {
+        let state = str_arg(args, "state").unwrap_or("open");
+        if !matches!(state, "open" | "closed" | "all") {
+            return Err(ZeptoError::Tool(
+                "'state' must be open, closed or all".into(),
+            ));
+        }
+        Ok(state.to_string())
+    }
+
+    async fn list_issues(&self, args: &Value) -> Result<String> {
+        let repo = repo_arg(self.client.config(), args)?;
+        let mut query = vec![("state", Self::state(args)?)];
+        let labels = string_list(args, "labels");
+        if !labels.is_empty() {
+            query.push(("labels", labels.join(",")));
+        }
+        if let Some(assignee) = str_arg(args, "assignee") {
+            query.push(("assignee", assignee.to_string()));
+        }
+        let page = self
+            .client
+            .get_paginated(&format!("repos/{}/issues", repo), &query, self.limit(args))
+            .await?;
+        Ok(format!(
+            "Issues in {}:\n{}{}",
+            repo,
+            render_issues(&page.items),
+            more_note(page.more)
+        ))
+    }
+
+    async fn list_prs(&self, args: &Value) -> Result<String> {
+        let repo = repo_arg(self.client.config(), args)?;
+        let query = vec![
+            ("state", Self::state(args)?),
+            ("sort", "updated".to_string()),
+            ("direction", "desc".to_string()),
+        ];
+        let page = self
+            .client
+            .get_paginated(&format!("repos/{}/pulls", repo), &query, self.limit(args))
+            .await?;
+        Ok(format!(
+            "Pull requests in {}:\n{}{}",
+            repo,
+            render_prs(&page.items),
+            more_note(page.more)
+        ))
+    }
+
+    async fn pr_diff(&self, args: &Value) -> Result<String> {
+        let repo = repo_arg(self.client.config(), args)?;
+        let number = number_arg(args, "number")?;
+        let pr = self
+            .client
+            .get_json(&format!("repos/{}/pulls/{}", repo, number))
+            .await?;
+        let page = self
+            .client
+            .get_paginated(&format!("repos/{}/pulls/{}/files", repo, number), &[], 300)
+            .await?;
+        let mut files = page.items;
+        if let Some(path) = str_arg(args, "path") {
+            files.retain(|f| {
+                f["filename"]
+                    .as_str()
+                    .is_some_and(|name| name.contains(path))
+            });
+            if files.is_empty() {
+                return Err(ZeptoError::Tool(format!(
+                    "No changed file in PR #{} matches '{}'",
+                    number, path
+                )));
+            }
+        }
+        Ok(render_pr_diff(
+            &pr,
+            &files,
+            page.more,
+            self.client.config().max_diff_chars,
+        ))
+    }
+
+    async fn review_comments(&self, args: &Value) -> Result<String> {
+        let repo = repo_arg(self.client.config(), args)?;
+        let number = number_arg(args, "number")?;
+        let (owner, name) = repo.split_once('/').unwrap_or_default();
+        let unresolved_only = args.get("include_resolved").and_then(Value::as_bool) != Some(true);
+
+        let mut threads = Vec::new();
+        let mut title = format!("PR #{}", number);
+        let mut after = Value::Null;
+        for _ in 0..self.client.config().max_pages.max(1) {
+            let data = self
+                .client
+                .graphql(
+                    REVIEW_THREAD_QUERY,
+                    json!({"owner": owner, "name": name, "number": number, "after": after}),
+                )
+                .await?;
+            let pr = data.pointer("/repository/pullRequest").ok_or_else(|| {
+                ZeptoError::Tool(format!("Pull request #{} not found in {}", number, repo))
+            })?;
+            if let Some(t) = pr["title"].as_str() {
+                title = format!("PR #{}: {}", number, t);
+            }
+            if let Some(nodes) = pr.pointer("/reviewThreads/nodes").and_then(Value::as_array) {
+                threads.extend(nodes.iter().cloned());
+            }
+            let page_info = &pr["reviewThreads"]["pageInfo"];
+            if page_info["hasNextPage"].as_bool() != Some(true) {
+                break;
+            }
+            after = page_info["endCursor"].clone();
+        }
+        Ok(render_review_threads(&title, &threads, unresolved_only))
+    }
+
+    async fn notifications(&self, args: &Value) -> Result<String> {
+        if self.client.is_app() {
+            return Err(ZeptoError::Tool(
+                "Notifications need a personal access token (tools.github.token); \
+                 GitHub App tokens cannot read a user's inbox"
+                    .into(),
+            ));
+        }
+        let flag = |key: &str| (args.get(key).and_then(Value::as_bool) == Some(true)).to_string();
+        let query = vec![
+            ("all", flag("all")),
+            ("participating", flag("participating")),
+        ];
+        let page = self
+            .client
+            .get_paginated("notifications", &query, self.limit(args))
+            .await?;
+        Ok(summarize_notifications(&page.items, page.more))
+    }
+}
+
+#[async_trait]
+impl Tool for GitHubTool {
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    fn description(&self) -> &str {
+        "Read GitHub. Actions: 'list_issues' and 'list_prs' (state, labels, assignee), \
+         'pr_diff' (a PR's summary, changed files and patches; 'path' for one file), \
+         'review_comments' (unresolved review threads with comment IDs to reply to), \
+         'notifications' (inbox summary, unread by default). 'repo' is owner/name and \
+         defaults to the configured repo. Use github_write to create issues or reply."
+    }
+
+    fn compact_description(&self) -> &str {
+        "GitHub issues, PR diffs, reviews, notifications"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkRead
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list_issues", "list_prs", "pr_diff", "review_comments", "notifications"],
+                    "description": "What to read"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository as owner/name (default from config)"
+                },
+                "number": {
+                    "type": "integer",
+                    "description": "Pull request number for pr_diff and review_comments"
+                },
+                "state": {
+                    "type": "string",
+                    "enum": ["open", "closed", "all"],
+                    "description": "Filter for list actions (default: open)"
+                },
+                "labels": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only issues with all of these labels"
+                },
+                "assignee": {
+                    "type": "string",
+                    "description": "Only issues assigned to this login ('none' or '*' also work)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "pr_diff: only files whose path contains this"
+                },
+                "include_resolved": {
+                    "type": "boolean",
+                    "description": "review_comments: include resolved threads"
+                },
+                "all": {
+                    "type": "boolean",
+                    "description": "notifications: include already-read ones"
+                },
+                "participating": {
+                    "type": "boolean",
+                    "description": "notifications: only threads you participate in or are mentioned in"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum items (capped by config)",
+                    "minimum": 1
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let mut output = match required_str(&args, "action")? {
+            "list_issues" => self.list_issues(&args).await?,
+            "list_prs" => self.list_prs(&args).await?,
+            "pr_diff" => self.pr_diff(&args).await?,
+            "review_comments" => self.review_comments(&args).await?,
+            "notifications" => self.notifications(&args).await?,
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown action '{}'. Use list_issues, list_prs, pr_diff, \
+                     review_comments or notifications",
+                    other
+                )))
+            }
+        };
+        if let Some(note) = self.client.rate_limit_note() {
+            output.push_str(&format!("\n{}", note));
+        }
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// github_write (posts)
+// ---------------------------------------------------------------------------
+
+/// Creates issues and replies to review comments. Created through
+/// [`GitHubTool::write_tool`].
+pub struct GitHubWriteTool {
+    client: Arc<GitHubClient>,
+}
+
+#[async_trait]
+impl Tool for GitHubWriteTool {
+    fn name(&self) -> &str {
+        "github_write"
+    }
+
+    fn description(&self) -> &str {
+        "Post to GitHub. Actions: 'create_issue' (title, body, labels, assignees) and \
+         'reply_review_comment' (reply in a PR review thread; get comment IDs from github \
+         review_comments). Use the github tool for reads."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Create GitHub issues, reply to reviews"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["create_issue", "reply_review_comment"],
+                    "description": "What to post"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository as owner/name (default from config)"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Issue title for create_issue"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Issue body or reply text (Markdown)"
+                },
+                "labels": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Labels for create_issue"
+                },
+                "assignees": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Logins to assign for create_issue"
+                },
+                "number": {
+                    "type": "integer",
+                    "description": "Pull request number for reply_review_comment"
+                },
+                "comment_id": {
+                    "type": "integer",
+                    "description": "Review comment ID to reply to"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let repo = repo_arg(self.client.config(), &args)?;
+        let output = match required_str(&args, "action")? {
+            "create_issue" => {
+                let mut body = json!({
+                    "title": required_str(&args, "title")?,
+                    "body": str_arg(&args, "body").unwrap_or(""),
+                });
+                for key in ["labels", "assignees"] {
+                    let values = string_list(&args, key);
+                    if !values.is_empty() {
+                        body[key] = json!(values);
+                    }
+                }
+                let issue = self
+                    .client
+                    .post_json(&format!("repos/{}/issues", repo), &body)
+                    .await?;
+                format!(
+                    "Created issue #{} in {}: {}",
+                    issue["number"],
+                    repo,
+                    issue["html_url"].as_str().unwrap_or("")
+                )
+            }
+            "reply_review_comment" => {
+                let number = number_arg(&args, "number")?;
+                let comment_id = number_arg(&args, "comment_id")?;
+                let reply = self
+                    .client
+                    .post_json(
+                        &format!(
+                            "repos/{}/pulls/{}/comments/{}/replies",
+                            repo, number, comment_id
+                        ),
+                        &json!({"body": required_str(&args, "body")?}),
+                    )
+                    .await?;
+                format!(
+                    "Replied on PR #{}: {}",
+                    number,
+                    reply["html_url"].as_str().unwrap_or("")
+                )
+            }
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown action '{}'. Use create_issue or reply_review_comment",
+                    other
+                )))
+            }
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_arg_uses_default_and_validates() {
+        let config = GitHubToolConfig {
+            default_repo: Some("acme/api".into()),
+            ..Default::default()
+        };
+        assert_eq!(repo_arg(&config, &json!({})).unwrap(), "acme/api");
+        assert_eq!(
+            repo_arg(&config, &json!({"repo": "acme/web.site"})).unwrap(),
+            "acme/web.site"
+        );
+        for bad in ["acme", "acme/api/extra", "../x", "acme/ api"] {
+            assert!(repo_arg(&config, &json!({"repo": bad})).is_err(), "{bad}");
+        }
+        assert!(repo_arg(&GitHubToolConfig::default(), &json!({})).is_err());
+    }
+
+    #[test]
+    fn test_render_issues_skips_pull_requests() {
+        let items = vec![
+            json!({"number": 7, "state": "open", "title": "Crash on start", "user": {"login": "ana"},
+                   "comments": 2, "updated_at": "2026-10-01T10:00:00Z", "labels": [{"name": "bug"}]}),
+            json!({"number": 8, "state": "open", "title": "A PR", "pull_request": {}}),
+        ];
+        let out = render_issues(&items);
+        assert_eq!(
+            out,
+            "#7 [open] Crash on start — @ana, 2 comment(s), updated 2026-10-01, labels: bug"
+        );
+    }
+
+    #[test]
+    fn test_render_pr_diff_respects_budget() {
+        let pr = json!({"number": 3, "title": "Add cache", "user": {"login": "bo"}, "state": "open",
+                        "base": {"ref": "main"}, "head": {"ref": "cache"}, "additions": 12,
+                        "deletions": 1, "changed_files": 3});
+        let files = vec![
+            json!({"filename": "src/cache.rs", "status": "added", "additions": 10, "deletions": 0,
+                   "patch": "@@ -0,0 +1 @@\n+pub struct Cache;"}),
+            json!({"filename": "src/big.rs", "status": "modified", "additions": 2, "deletions": 1,
+                   "patch": "x".repeat(500)}),
+            json!({"filename": "logo.png", "status": "modified", "additions": 0, "deletions": 0}),
+        ];
+        let out = render_pr_diff(&pr, &files, false, 200);
+        assert!(out.starts_with("PR #3: Add cache (@bo, open)\nmain ← cache, +12 −1 in 3 file(s)"));
+        assert!(out.contains("  A src/cache.rs (+10 −0)"));
+        assert!(out.contains("+pub struct Cache;"));
+        assert!(out.contains("patches omitted: src/big.rs, logo.png (binary or too large)"));
+    }
+
+    #[test]
+    fn test_review_threads_and_notifications() {
+        let threads = vec![
+            json!({"isResolved": false, "isOutdated": false, "path": "src/lib.rs", "line": 42,
+                   "comments": {"nodes": [{"databaseId": 991, "author": {"login": "rev"},
+                                           "body": "Handle the error here", "createdAt": "2026-10-02T00:00:00Z"}]}}),
+            json!({"isResolved": true, "path": "README.md", "line": null, "comments": {"nodes": []}}),
+        ];
+        let out = render_review_threads("PR #3: Add cache", &threads, true);
+        assert!(out.starts_with("PR #3: Add cache — 2 review thread(s), 1 unresolved"));
+        assert!(out.contains("src/lib.rs:42\n  [991] @rev (2026-10-02): Handle the error here"));
+        assert!(!out.contains("README.md"));
+
+        let items = vec![
+            json!({"reason": "review_requested", "updated_at": "2026-10-03T00:00:00Z",
+                   "repository": {"full_name": "acme/api"},
+                   "subject": {"type": "PullRequest", "title": "Add cache"}}),
+            json!({"reason": "subscribed", "updated_at": "2026-10-01T00:00:00Z",
+                   "repository": {"full_name": "acme/web"},
+                   "subject": {"type": "Issue", "title": "Typo"}}),
+        ];
+        let out = summarize_notifications(&items, false);
+        assert!(out.starts_with("2 notification(s)\nBy reason: review_requested 1, subscribed 1"));
+        assert!(out.contains(
+            "Needs you:\n- acme/api [review_requested] PullRequest: Add cache (2026-10-03)"
+        ));
+        assert!(out.contains("acme/web (1):\n- [subscribed] Issue: Typo (2026-10-01)"));
+    }
+
+    #[tokio::test]
+    async fn test_requires_credentials() {
+        let tool = GitHubTool::new(GitHubToolConfig {
+            default_repo: Some("acme/api".into()),
+            ..Default::default()
+        });
+        let err = tool
+            .execute(json!({"action": "list_issues"}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("tools.github.token"));
+    }
+}
@@ -46,7 +46,7 @@ impl GoogleTool {
     /// * `max_search_results` - Maximum results to return for Gmail search
     pub fn new(access_token: &str, default_calendar: &str, max_search_results: u32) -> Self {
         Self {
-            client: Client::new(),
+            client: crate::utils::http::reqwest_client(),
             access_token: access_token.to_string(),
             default_calendar: default_calendar.to_string(),
             max_search_results,
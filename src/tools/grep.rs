@@ -1,6 +1,14 @@
 //! Grep tool — search file contents by regex pattern.
+//!
+//! Searches in-process with the `regex` crate (the syntax ripgrep uses), so
+//! patterns behave the same on every platform. Like `rg`, it skips hidden
+//! files and directories, `.git`, binary files and symlinks by default, and
+//! prints `path:line:text` for matches and `path-line-text` for context.
+
+use std::path::{Path, PathBuf};
 
 use async_trait::async_trait;
+use regex::{Regex, RegexBuilder};
 use serde_json::{json, Value};
 
 use crate::error::{Result, ZeptoError};
@@ -8,10 +16,55 @@ use crate::security::validate_path_in_workspace;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+const MAX_CONTEXT: usize = 10;
+/// Files larger than this are skipped.
+const MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+/// Stop walking after this many files.
+const MAX_FILES: usize = 20_000;
+/// Longer lines are cut in the output.
+const MAX_LINE_CHARS: usize = 300;
+
+/// Parsed search parameters.
+struct SearchOptions {
+    regex: Regex,
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+    context: usize,
+    limit: usize,
+    hidden: bool,
+}
+
+impl SearchOptions {
+    /// Whether the glob filters admit `rel`. Globs containing `/` match the
+    /// path relative to the workspace, others the file name.
+    fn admits(&self, rel: &str) -> bool {
+        let name = rel.rsplit('/').next().unwrap_or(rel);
+        let hit = |p: &glob::Pattern| {
+            if p.as_str().contains('/') {
+                p.matches(rel)
+            } else {
+                p.matches(name)
+            }
+        };
+        (self.include.is_empty() || self.include.iter().any(hit)) && !self.exclude.iter().any(hit)
+    }
+}
+
+#[derive(Default)]
+struct SearchOutput {
+    lines: Vec<String>,
+    /// Matching lines found, including those past the limit.
+    matches: usize,
+    files_scanned: usize,
+    hit_file_cap: bool,
+}
+
 /// Tool for searching file contents by pattern.
 ///
-/// Shells out to system `grep -rn` for performance. Supports regex patterns,
-/// glob file filters, case-insensitive search, and result limiting.
+/// Supports regex or literal patterns, include/exclude glob filters,
+/// context lines, case-insensitive search and a cap on reported matches.
 pub struct GrepTool;
 
 #[async_trait]
@@ -21,7 +74,10 @@ impl Tool for GrepTool {
     }
 
     fn description(&self) -> &str {
-        "Search for a pattern in files. Supports regex patterns and glob file filters."
+        "Search file contents in the workspace with a regex (ripgrep syntax). Skips hidden \
+         files, .git, binary and very large files. Supports glob filters ('*.rs', \
+         '!*_test.go'), context lines and a result cap. Output lines are path:line:text \
+         (context lines path-line-text)."
     }
 
     fn compact_description(&self) -> &str {
@@ -38,23 +94,38 @@ impl Tool for GrepTool {
             "properties": {
                 "pattern": {
                     "type": "string",
-                    "description": "Regex or literal pattern to search for"
+                    "description": "Regex (or literal with fixed_strings) to search for"
                 },
                 "path": {
                     "type": "string",
                     "description": "Directory or file to search (default: workspace root)"
                 },
                 "glob": {
-                    "type": "string",
-                    "description": "Glob pattern to filter files (e.g. '*.rs', '*.py')"
+                    "description": "Glob(s) filtering files, e.g. '*.rs' or ['src/**', '!*.lock']; a leading '!' excludes",
+                    "anyOf": [
+                        { "type": "string" },
+                        { "type": "array", "items": { "type": "string" } }
+                    ]
                 },
                 "ignore_case": {
                     "type": "boolean",
                     "description": "Case-insensitive search (default: false)"
                 },
+                "fixed_strings": {
+                    "type": "boolean",
+                    "description": "Treat pattern as a literal string (default: false)"
+                },
+                "context": {
+                    "type": "integer",
+                    "description": "Lines of context before and after each match (default: 0, max: 10)"
+                },
+                "hidden": {
+                    "type": "boolean",
+                    "description": "Also search hidden files and directories (default: false)"
+                },
                 "limit": {
                     "type": "integer",
-                    "description": "Maximum matches to return (default: 100)"
+                    "description": "Maximum matches to return (default: 100, max: 1000)"
                 }
             },
             "required": ["pattern"]
@@ -74,61 +145,194 @@ impl Tool for GrepTool {
         })?;
 
         let search_path = match args.get("path").and_then(|v| v.as_str()) {
-            Some(p) => {
-                let safe = validate_path_in_workspace(p, workspace)?;
-                safe.as_path().to_string_lossy().to_string()
-            }
-            None => workspace.clone(),
+            Some(p) => validate_path_in_workspace(p, workspace)?
+                .as_path()
+                .to_path_buf(),
+            None => PathBuf::from(workspace),
         };
 
-        let ignore_case = args
-            .get("ignore_case")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
-        let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(100) as usize;
-        let glob_pattern = args.get("glob").and_then(|v| v.as_str());
+        let flag = |key: &str| args.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        let source = if flag("fixed_strings") {
+            regex::escape(pattern)
+        } else {
+            pattern.to_string()
+        };
+        let regex = RegexBuilder::new(&source)
+            .case_insensitive(flag("ignore_case"))
+            .build()
+            .map_err(|e| ZeptoError::Tool(format!("Invalid regex pattern: {}", e)))?;
+
+        let globs: Vec<&str> = match args.get("glob") {
+            Some(Value::String(g)) => vec![g.as_str()],
+            Some(Value::Array(items)) => items.iter().filter_map(|v| v.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        let (mut include, mut exclude) = (Vec::new(), Vec::new());
+        for g in globs {
+            let (list, raw) = match g.strip_prefix('!') {
+                Some(rest) => (&mut exclude, rest),
+                None => (&mut include, g),
+            };
+            list.push(
+                glob::Pattern::new(raw)
+                    .map_err(|e| ZeptoError::Tool(format!("Invalid glob '{}': {}", g, e)))?,
+            );
+        }
+
+        let count = |key: &str| args.get(key).and_then(|v| v.as_u64()).map(|n| n as usize);
+        let options = SearchOptions {
+            regex,
+            include,
+            exclude,
+            context: count("context").unwrap_or(0).min(MAX_CONTEXT),
+            limit: count("limit").unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+            hidden: flag("hidden"),
+        };
 
-        // Validate regex before spawning grep
-        if let Err(e) = regex::Regex::new(pattern) {
-            return Err(ZeptoError::Tool(format!("Invalid regex pattern: {}", e)));
+        let limit = options.limit;
+        let root = PathBuf::from(workspace);
+        let output = tokio::task::spawn_blocking(move || search(&search_path, &root, &options))
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Search task failed: {}", e)))?;
+        Ok(ToolOutput::llm_only(render(&output, limit)))
+    }
+}
+
+fn render(output: &SearchOutput, limit: usize) -> String {
+    if output.matches == 0 {
+        let mut text = "No matches found".to_string();
+        if output.hit_file_cap {
+            text.push_str(&format!(" (stopped after {} files)", MAX_FILES));
+        }
+        return text;
+    }
+    let mut text = output.lines.join("\n");
+    if output.matches > limit {
+        text.push_str(&format!(
+            "\n... ({} more matches, capped at {})",
+            output.matches - limit,
+            limit
+        ));
+    }
+    if output.hit_file_cap {
+        text.push_str(&format!(
+            "\n... (stopped after {} files; narrow 'path' or 'glob')",
+            MAX_FILES
+        ));
+    }
+    text
+}
+
+/// Walk `start` (a file or directory) and collect matches. Paths in the
+/// output are relative to `workspace`.
+fn search(start: &Path, workspace: &Path, options: &SearchOptions) -> SearchOutput {
+    let mut output = SearchOutput::default();
+    let mut stack = vec![start.to_path_buf()];
+
+    while let Some(path) = stack.pop() {
+        // Symlinks below the search root are never followed.
+        let meta = if path == start {
+            std::fs::metadata(&path)
+        } else {
+            std::fs::symlink_metadata(&path)
+        };
+        let Ok(meta) = meta else {
+            continue;
+        };
+        if meta.file_type().is_symlink() {
+            continue;
+        }
+        if meta.is_dir() {
+            let Ok(entries) = std::fs::read_dir(&path) else {
+                continue;
+            };
+            let mut children: Vec<PathBuf> = entries
+                .filter_map(|e| e.ok())
+                .filter(|e| {
+                    let name = e.file_name();
+                    let name = name.to_string_lossy();
+                    name != ".git" && (options.hidden || !name.starts_with('.'))
+                })
+                .map(|e| e.path())
+                .collect();
+            // Reverse-sorted so popping visits entries in name order.
+            children.sort_unstable_by(|a, b| b.cmp(a));
+            stack.extend(children);
+            continue;
+        }
+        if !meta.is_file() || meta.len() > MAX_FILE_BYTES {
+            continue;
         }
 
-        let mut cmd_args = vec!["-rn".to_string()];
-        if ignore_case {
-            cmd_args.push("-i".to_string());
+        let rel = path
+            .strip_prefix(workspace)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        if !options.admits(&rel) {
+            continue;
         }
-        if let Some(glob_pat) = glob_pattern {
-            cmd_args.push("--include".to_string());
-            cmd_args.push(glob_pat.to_string());
+        if output.files_scanned >= MAX_FILES {
+            output.hit_file_cap = true;
+            break;
         }
-        cmd_args.push("--".to_string());
-        cmd_args.push(pattern.to_string());
-        cmd_args.push(search_path.clone());
+        output.files_scanned += 1;
+        search_file(&path, &rel, options, &mut output);
+    }
+    output
+}
 
-        let output = tokio::process::Command::new("grep")
-            .args(&cmd_args)
-            .output()
-            .await
-            .map_err(|e| ZeptoError::Tool(format!("Failed to run grep: {}", e)))?;
+fn search_file(path: &Path, rel: &str, options: &SearchOptions, output: &mut SearchOutput) {
+    let Ok(bytes) = std::fs::read(path) else {
+        return;
+    };
+    if bytes[..bytes.len().min(8192)].contains(&0) {
+        return;
+    }
+    let text = String::from_utf8_lossy(&bytes);
+    let lines: Vec<&str> = text.lines().collect();
+    let hits: Vec<usize> = (0..lines.len())
+        .filter(|&i| options.regex.is_match(lines[i]))
+        .collect();
+    if hits.is_empty() {
+        return;
+    }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let lines: Vec<&str> = stdout.lines().take(limit).collect();
+    let room = options.limit.saturating_sub(output.matches);
+    output.matches += hits.len();
+    if room == 0 {
+        return;
+    }
 
-        if lines.is_empty() {
-            return Ok(ToolOutput::llm_only("No matches found".to_string()));
+    let reported = &hits[..hits.len().min(room)];
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for &hit in reported {
+        let from = hit.saturating_sub(options.context);
+        let to = (hit + options.context).min(lines.len() - 1);
+        match groups.last_mut() {
+            Some(last) if from <= last.1 + 1 => last.1 = last.1.max(to),
+            _ => groups.push((from, to)),
         }
-
-        let total = stdout.lines().count();
-        let mut result = lines.join("\n");
-        if total > limit {
-            result.push_str(&format!(
-                "\n... ({} more matches, capped at {})",
-                total - limit,
-                limit
+    }
+    for (from, to) in groups {
+        if options.context > 0 && !output.lines.is_empty() {
+            output.lines.push("--".to_string());
+        }
+        for (i, line) in lines.iter().enumerate().take(to + 1).skip(from) {
+            let sep = if reported.binary_search(&i).is_ok() {
+                ':'
+            } else {
+                '-'
+            };
+            output.lines.push(format!(
+                "{}{}{}{}{}",
+                rel,
+                sep,
+                i + 1,
+                sep,
+                crate::utils::string::preview(line, MAX_LINE_CHARS)
             ));
         }
-
-        Ok(ToolOutput::llm_only(result))
     }
 }
 
@@ -255,4 +459,80 @@ mod tests {
         );
         assert!(result.for_llm.contains("more matches"));
     }
+
+    #[tokio::test]
+    async fn test_grep_context_lines_and_groups() {
+        let dir = tempfile::tempdir().unwrap();
+        let content: String = (1..=12).map(|i| format!("line {}\n", i)).collect();
+        std::fs::write(dir.path().join("a.txt"), content).unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        let result = GrepTool
+            .execute(json!({"pattern": "^line (3|4|10)$", "context": 1}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.for_llm,
+            "a.txt-2-line 2\na.txt:3:line 3\na.txt:4:line 4\na.txt-5-line 5\n--\n\
+             a.txt-9-line 9\na.txt:10:line 10\na.txt-11-line 11"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_grep_regex_syntax_and_fixed_strings() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "let x = 42;\nfoo(a|b)\n").unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        let result = GrepTool
+            .execute(json!({"pattern": r"\d+;"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(result.for_llm, "a.rs:1:let x = 42;");
+
+        let result = GrepTool
+            .execute(json!({"pattern": "(a|b)", "fixed_strings": true}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(result.for_llm, "a.rs:2:foo(a|b)");
+    }
+
+    #[tokio::test]
+    async fn test_grep_skips_hidden_binary_and_excluded_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join(".git/config"), "needle").unwrap();
+        std::fs::write(dir.path().join(".env"), "needle").unwrap();
+        std::fs::write(dir.path().join("blob.bin"), b"needle\0\x01").unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "needle").unwrap();
+        std::fs::write(dir.path().join("src/main_test.rs"), "needle").unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        let result = GrepTool
+            .execute(
+                json!({"pattern": "needle", "glob": ["src/**", "!*_test.rs"]}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.for_llm, "src/main.rs:1:needle");
+
+        let result = GrepTool
+            .execute(json!({"pattern": "needle", "hidden": true}), &ctx)
+            .await
+            .unwrap();
+        assert!(result.for_llm.contains(".env:1:needle"));
+        assert!(!result.for_llm.contains(".git"));
+        assert!(!result.for_llm.contains("blob.bin"));
+    }
+
+    #[tokio::test]
+    async fn test_grep_rejects_path_outside_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        let result = GrepTool
+            .execute(json!({"pattern": "root", "path": "../../etc"}), &ctx)
+            .await;
+        assert!(result.is_err());
+    }
 }
@@ -1,51 +1,57 @@
 //! Google Sheets API tool.
+//!
+//! Authenticates with an OAuth access token or a service account key; see
+//! [`GoogleAuth`](super::google_auth::GoogleAuth) for token refresh.
 
 use async_trait::async_trait;
-use base64::Engine;
-use reqwest::Client;
+use reqwest::StatusCode;
 use serde_json::{json, Value};
 
 use crate::error::{Result, ZeptoError};
+use crate::utils::http::Client;
 
+use super::google_auth::GoogleAuth;
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
 const SHEETS_API_BASE: &str = "https://sheets.googleapis.com/v4/spreadsheets";
 
+/// Scope requested for service accounts when none is configured.
+const DEFAULT_SCOPE: &str = "spreadsheets";
+
 /// Tool for reading/writing Google Sheets ranges.
 #[derive(Debug)]
 pub struct GoogleSheetsTool {
     client: Client,
-    access_token: String,
+    auth: GoogleAuth,
 }
 
 impl GoogleSheetsTool {
     /// Create with an OAuth access token.
     pub fn new(access_token: &str) -> Self {
         Self {
-            client: Client::new(),
-            access_token: access_token.to_string(),
+            client: crate::utils::http::client(),
+            auth: GoogleAuth::token(access_token),
         }
     }
 
-    /// Parse a base64-encoded JSON payload to extract an `access_token`.
+    /// Create from a base64-encoded service account key file.
     ///
-    /// This supports workflows where a short-lived token is provisioned in config.
+    /// Access tokens are minted from the key and refreshed as they expire.
+    /// A payload holding only an `access_token` is still accepted and used
+    /// as-is.
     pub fn from_service_account(encoded_json: &str) -> Result<Self> {
-        let decoded = base64::engine::general_purpose::STANDARD
-            .decode(encoded_json)
-            .map_err(|e| ZeptoError::Config(format!("Invalid base64 credentials: {}", e)))?;
-        let payload: Value = serde_json::from_slice(&decoded)
-            .map_err(|e| ZeptoError::Config(format!("Invalid credentials JSON: {}", e)))?;
-        let token = payload
-            .get("access_token")
-            .and_then(Value::as_str)
-            .filter(|token| !token.trim().is_empty())
-            .ok_or_else(|| {
-                ZeptoError::Config(
-                    "Service account payload must include non-empty 'access_token'".to_string(),
-                )
-            })?;
-        Ok(Self::new(token))
+        Ok(Self {
+            client: crate::utils::http::client(),
+            auth: GoogleAuth::from_service_account(encoded_json, &[DEFAULT_SCOPE])?,
+        })
+    }
+
+    /// Request `scopes` instead of full Sheets access when authenticating
+    /// as a service account. Short names such as `spreadsheets.readonly`
+    /// are expanded; an empty list keeps the default.
+    pub fn with_scopes(mut self, scopes: &[String]) -> Self {
+        self.auth = self.auth.with_scopes(scopes);
+        self
     }
 
     fn extract_values(args: &Value) -> Result<Vec<Vec<String>>> {
@@ -71,25 +77,39 @@ impl GoogleSheetsTool {
         Ok(parsed)
     }
 
-    async fn execute_read(&self, spreadsheet_id: &str, range: &str) -> Result<String> {
-        let encoded_range = encode_range(range);
-        let endpoint = format!(
-            "{}/{}/values/{}",
-            SHEETS_API_BASE, spreadsheet_id, encoded_range
-        );
-        let response = self
-            .client
-            .get(endpoint)
-            .header("Authorization", format!("Bearer {}", self.access_token))
-            .send()
-            .await
-            .map_err(|e| ZeptoError::Tool(format!("Google Sheets request failed: {}", e)))?;
+    /// Fail early when the service account only holds read-only scopes.
+    fn ensure_writable(&self) -> Result<()> {
+        if self.auth.can_write() {
+            return Ok(());
+        }
+        Err(ZeptoError::Tool(
+            "Google Sheets is configured with read-only scopes; add the 'spreadsheets' \
+             scope to tools.google_sheets.scopes to write"
+                .to_string(),
+        ))
+    }
 
+    /// Send an authenticated request and parse the JSON response.
+    async fn send(
+        &self,
+        request: impl Fn(&Client) -> crate::utils::http::RequestBuilder,
+    ) -> Result<(StatusCode, Value)> {
+        let response = self.auth.send(&self.client, request).await?;
         let status = response.status();
         let body: Value = response
             .json()
             .await
             .map_err(|e| ZeptoError::Tool(format!("Invalid Sheets response payload: {}", e)))?;
+        Ok((status, body))
+    }
+
+    async fn execute_read(&self, spreadsheet_id: &str, range: &str) -> Result<String> {
+        let encoded_range = encode_range(range);
+        let endpoint = format!(
+            "{}/{}/values/{}",
+            SHEETS_API_BASE, spreadsheet_id, encoded_range
+        );
+        let (status, body) = self.send(|client| client.get(&endpoint)).await?;
 
         if !status.is_success() {
             return Err(ZeptoError::Tool(format!(
@@ -160,30 +180,23 @@ impl GoogleSheetsTool {
         values: Vec<Vec<String>>,
     ) -> Result<String> {
         let body = json!({ "values": values });
-        let request = match method {
-            "POST" => self.client.post(endpoint),
-            "PUT" => self.client.put(endpoint),
-            _ => {
-                return Err(ZeptoError::Tool(format!(
-                    "Unsupported HTTP method for Sheets write: {}",
-                    method
-                )))
-            }
-        };
-
-        let response = request
-            .header("Authorization", format!("Bearer {}", self.access_token))
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| ZeptoError::Tool(format!("Google Sheets request failed: {}", e)))?;
+        if method != "POST" && method != "PUT" {
+            return Err(ZeptoError::Tool(format!(
+                "Unsupported HTTP method for Sheets write: {}",
+                method
+            )));
+        }
 
-        let status = response.status();
-        let payload: Value = response
-            .json()
-            .await
-            .map_err(|e| ZeptoError::Tool(format!("Invalid Sheets response payload: {}", e)))?;
+        let (status, payload) = self
+            .send(|client| {
+                let request = if method == "POST" {
+                    client.post(endpoint)
+                } else {
+                    client.put(endpoint)
+                };
+                request.json(&body)
+            })
+            .await?;
 
         if !status.is_success() {
             return Err(ZeptoError::Tool(format!(
@@ -313,10 +326,12 @@ impl Tool for GoogleSheetsTool {
         let s = match action {
             "read" => self.execute_read(spreadsheet_id, range).await?,
             "append" => {
+                self.ensure_writable()?;
                 let values = Self::extract_values(&args)?;
                 self.execute_append(spreadsheet_id, range, values).await?
             }
             "update" => {
+                self.ensure_writable()?;
                 let values = Self::extract_values(&args)?;
                 self.execute_update(spreadsheet_id, range, values).await?
             }
@@ -329,6 +344,7 @@ impl Tool for GoogleSheetsTool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use base64::Engine;
 
     #[test]
     fn test_google_sheets_tool_properties() {
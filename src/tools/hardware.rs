@@ -7,6 +7,12 @@
 //! - `send_command` -- send a command to a connected peripheral (placeholder)
 //! - `read_data` -- read data from a connected peripheral (placeholder)
 //! - `disconnect` -- disconnect a peripheral (placeholder)
+//! - `flash` -- build and flash firmware to a board, after user approval
+//!
+//! Flashing is a two-step workflow. The first call returns the exact build
+//! and flash commands; the user alone receives a one-time confirmation code.
+//! Nothing runs until the agent repeats the call with `plan_id` and the code
+//! the user gives it.
 //!
 //! When compiled WITHOUT the `hardware` feature, the tool returns an informative
 //! error directing the user to rebuild with the feature enabled.
@@ -21,13 +27,46 @@ use crate::tools::{Tool, ToolCategory, ToolContext, ToolOutput};
 // Feature-gated implementation (with hardware feature)
 // ============================================================================
 
+#[cfg(feature = "hardware")]
+use std::collections::HashMap;
+#[cfg(feature = "hardware")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "hardware")]
+use std::sync::Arc;
+#[cfg(feature = "hardware")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "hardware")]
+use tokio::sync::Mutex;
+
+#[cfg(feature = "hardware")]
+use crate::hardware::flash::FlashPlan;
 #[cfg(feature = "hardware")]
 use crate::hardware::HardwareManager;
+#[cfg(feature = "hardware")]
+use crate::runtime::ContainerRuntime;
+#[cfg(feature = "hardware")]
+use crate::security::confirm::{CodeCheck, ConfirmationCode, MAX_CONFIRMATION_ATTEMPTS};
+
+/// How long a flash plan stays approvable.
+#[cfg(feature = "hardware")]
+const PLAN_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// A flash plan waiting for the user's confirmation code.
+#[cfg(feature = "hardware")]
+struct PendingFlash {
+    plan: FlashPlan,
+    code: ConfirmationCode,
+    created: Instant,
+}
 
 /// Agent-facing hardware tool for USB discovery and peripheral interaction.
 #[cfg(feature = "hardware")]
 pub struct HardwareTool {
     manager: HardwareManager,
+    runtime: Option<Arc<dyn ContainerRuntime>>,
+    pending: Mutex<HashMap<String, PendingFlash>>,
+    next_plan: AtomicU64,
 }
 
 #[cfg(feature = "hardware")]
@@ -36,8 +75,146 @@ impl HardwareTool {
     pub fn new() -> Self {
         Self {
             manager: HardwareManager::new(),
+            runtime: None,
+            pending: Mutex::new(HashMap::new()),
+            next_plan: AtomicU64::new(1),
         }
     }
+
+    /// Enable the `flash` action, running build and flash commands in `runtime`.
+    pub fn with_runtime(mut self, runtime: Arc<dyn ContainerRuntime>) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+        args.get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+
+    async fn execute_flash(&self, args: &Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let runtime = self.runtime.as_ref().ok_or_else(|| {
+            ZeptoError::Tool("Flashing is not available: no container runtime configured".into())
+        })?;
+
+        if let Some(plan_id) = Self::str_arg(args, "plan_id") {
+            return self.execute_approved_flash(plan_id, args, runtime).await;
+        }
+
+        let device = Self::str_arg(args, "device").ok_or_else(|| {
+            ZeptoError::Tool("Missing 'device' parameter for flash action".into())
+        })?;
+        let artifact = Self::str_arg(args, "artifact").ok_or_else(|| {
+            ZeptoError::Tool("Missing 'artifact' parameter for flash action".into())
+        })?;
+        let workspace = ctx.workspace.as_deref().ok_or_else(|| {
+            ZeptoError::Tool("Flashing requires a workspace for the firmware artifact".into())
+        })?;
+        let artifact = crate::security::validate_path_in_workspace(artifact, workspace)?;
+
+        let plan = self.manager.flash_plan(
+            device,
+            artifact.into_path_buf(),
+            Self::str_arg(args, "port"),
+            Self::str_arg(args, "build_command"),
+            workspace.into(),
+        )?;
+        let summary = plan.summary();
+        let code = ConfirmationCode::new();
+        let id = format!("f{}", self.next_plan.fetch_add(1, Ordering::Relaxed));
+
+        {
+            let mut pending = self.pending.lock().await;
+            pending.retain(|_, p| p.created.elapsed() < PLAN_TTL);
+            pending.insert(
+                id.clone(),
+                PendingFlash {
+                    plan,
+                    code: code.clone(),
+                    created: Instant::now(),
+                },
+            );
+        }
+        let code = code.as_str();
+
+        Ok(ToolOutput::split(
+            format!(
+                "Flash plan {}\n{}\n\nNothing has run. The user was shown this plan with a \
+                 confirmation code. Only call flash with plan_id={} and the code the user gives you.",
+                id, summary, id
+            ),
+            format!(
+                "Flash plan {}\n{}\n\nTo approve, reply with confirmation code {}.",
+                id, summary, code
+            ),
+        )
+        .with_pause())
+    }
+
+    async fn execute_approved_flash(
+        &self,
+        plan_id: &str,
+        args: &Value,
+        runtime: &Arc<dyn ContainerRuntime>,
+    ) -> Result<ToolOutput> {
+        let code = Self::str_arg(args, "confirmation_code").ok_or_else(|| {
+            ZeptoError::Tool(
+                "Missing 'confirmation_code'. Ask the user for the code sent with the plan".into(),
+            )
+        })?;
+
+        let plan = {
+            let mut pending = self.pending.lock().await;
+            pending.retain(|_, p| p.created.elapsed() < PLAN_TTL);
+            let mut entry = pending.remove(plan_id).ok_or_else(|| {
+                ZeptoError::Tool(format!(
+                    "No flash plan '{}' (expired or already run)",
+                    plan_id
+                ))
+            })?;
+            match entry.code.check(code) {
+                CodeCheck::Matched => {}
+                CodeCheck::Wrong { remaining } => {
+                    pending.insert(plan_id.to_string(), entry);
+                    return Err(ZeptoError::Tool(format!(
+                        "Confirmation code does not match ({} of {} attempts left). Ask the \
+                         user for the code sent with the plan",
+                        remaining, MAX_CONFIRMATION_ATTEMPTS
+                    )));
+                }
+                CodeCheck::Exhausted => {
+                    return Err(ZeptoError::Tool(format!(
+                        "Confirmation code does not match; flash plan {} was discarded after \
+                         {} wrong codes",
+                        plan_id, MAX_CONFIRMATION_ATTEMPTS
+                    )));
+                }
+            }
+            entry.plan
+        };
+
+        let outcome = self.manager.flash(&plan, runtime.as_ref()).await?;
+        let mut report = Vec::new();
+        if let Some(build) = &outcome.build {
+            report.push(format!("Build:\n{}", build.format()));
+        }
+        match &outcome.flash {
+            Some(flash) => report.push(format!("Flash:\n{}", flash.format())),
+            None => report.push("Flash skipped: build failed.".to_string()),
+        }
+        let status = if outcome.success() {
+            format!("Flashed {} successfully.", plan.board)
+        } else {
+            format!("Flashing {} failed.", plan.board)
+        };
+        Ok(ToolOutput::user_visible(format!(
+            "{}\n\n{}",
+            status,
+            report.join("\n\n")
+        )))
+    }
 }
 
 #[cfg(feature = "hardware")]
@@ -56,11 +233,13 @@ impl Tool for HardwareTool {
 
     fn description(&self) -> &str {
         "Discover and interact with connected hardware devices (USB, serial peripherals). \
-         Actions: list_devices, device_info, connect, send_command, read_data, disconnect."
+         Actions: list_devices, device_info, connect, send_command, read_data, disconnect, flash. \
+         flash builds and writes firmware to a Nucleo/Arduino/ESP32 board and requires the user's \
+         confirmation code."
     }
 
     fn compact_description(&self) -> &str {
-        "Hardware discovery and peripheral control"
+        "Hardware discovery, peripheral control, firmware flashing"
     }
 
     fn category(&self) -> ToolCategory {
@@ -73,12 +252,32 @@ impl Tool for HardwareTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["list_devices", "device_info", "connect", "send_command", "read_data", "disconnect"],
+                    "enum": ["list_devices", "device_info", "connect", "send_command", "read_data", "disconnect", "flash"],
                     "description": "The hardware action to perform"
                 },
                 "device": {
                     "type": "string",
-                    "description": "Device name or VID:PID (for device_info, connect, disconnect)"
+                    "description": "Device name or VID:PID (for device_info, connect, disconnect, flash)"
+                },
+                "artifact": {
+                    "type": "string",
+                    "description": "Firmware image in the workspace: .elf/.hex/.bin for Nucleo, .hex for Arduino, .bin for ESP32 (for flash)"
+                },
+                "port": {
+                    "type": "string",
+                    "description": "Serial port of the board, e.g. /dev/ttyACM0 (for flash on Arduino/ESP32)"
+                },
+                "build_command": {
+                    "type": "string",
+                    "description": "Optional command run in the workspace before flashing, e.g. 'cargo build --release' (for flash)"
+                },
+                "plan_id": {
+                    "type": "string",
+                    "description": "Plan ID returned by a previous flash call; runs the plan (for flash)"
+                },
+                "confirmation_code": {
+                    "type": "string",
+                    "description": "Code the user received with the flash plan. Required with plan_id; ask the user for it."
                 },
                 "command": {
                     "type": "string",
@@ -93,13 +292,14 @@ impl Tool for HardwareTool {
         })
     }
 
-    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
         let action = args
             .get("action")
             .and_then(|v| v.as_str())
             .ok_or_else(|| ZeptoError::Tool("Missing 'action' parameter".into()))?;
 
         match action {
+            "flash" => self.execute_flash(&args, ctx).await,
             "list_devices" => {
                 let devices = self.manager.discover_devices();
                 if devices.is_empty() {
@@ -169,7 +369,7 @@ impl Tool for HardwareTool {
                 )))
             }
             other => Err(ZeptoError::Tool(format!(
-                "Unknown hardware action: '{}'. Valid actions: list_devices, device_info, connect, send_command, read_data, disconnect",
+                "Unknown hardware action: '{}'. Valid actions: list_devices, device_info, connect, send_command, read_data, disconnect, flash",
                 other
             ))),
         }
@@ -373,6 +573,87 @@ mod tests {
             .contains("Unknown hardware action"));
     }
 
+    #[cfg(feature = "hardware")]
+    #[tokio::test]
+    async fn test_hardware_tool_flash_requires_runtime() {
+        let tool = HardwareTool::new();
+        let ctx = ToolContext::new();
+        let err = tool
+            .execute(serde_json::json!({"action": "flash"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("runtime"));
+    }
+
+    #[cfg(feature = "hardware")]
+    #[tokio::test]
+    async fn test_hardware_tool_flash_requires_confirmation_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = HardwareTool::new().with_runtime(Arc::new(crate::runtime::NativeRuntime::new()));
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+
+        let out = tool
+            .execute(
+                serde_json::json!({
+                    "action": "flash",
+                    "device": "nucleo-f401re",
+                    "artifact": "fw.elf",
+                    "build_command": "exit 1"
+                }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        let user = out.for_user.clone().unwrap();
+        let code = user
+            .trim_end_matches('.')
+            .rsplit(' ')
+            .next()
+            .unwrap()
+            .to_string();
+        assert!(!out.for_llm.contains(&code));
+        assert!(out.for_llm.contains("plan_id=f1"));
+
+        let err = tool
+            .execute(
+                serde_json::json!({"action": "flash", "plan_id": "f1", "confirmation_code": "nope"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+
+        let out = tool
+            .execute(
+                serde_json::json!({"action": "flash", "plan_id": "f1", "confirmation_code": code}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("failed"));
+        assert!(out.for_llm.contains("build failed"));
+        assert!(tool.pending.lock().await.is_empty());
+    }
+
+    #[cfg(feature = "hardware")]
+    #[tokio::test]
+    async fn test_hardware_tool_flash_rejects_artifact_outside_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = HardwareTool::new().with_runtime(Arc::new(crate::runtime::NativeRuntime::new()));
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        let result = tool
+            .execute(
+                serde_json::json!({
+                    "action": "flash",
+                    "device": "nucleo-f401re",
+                    "artifact": "../../etc/fw.elf"
+                }),
+                &ctx,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
     #[cfg(feature = "hardware")]
     #[tokio::test]
     async fn test_hardware_tool_missing_action() {
@@ -9,8 +9,9 @@ use crate::tools::web::{
     validate_redirect_target_for_policy,
 };
 use crate::tools::{Tool, ToolContext, ToolOutput};
+use crate::utils::http::host_matches;
 use async_trait::async_trait;
-use reqwest::{Client, Method, Url};
+use reqwest::{Method, Url};
 use serde_json::{json, Value};
 use std::time::Duration;
 
@@ -62,6 +63,7 @@ impl HttpRequestTool {
                 "Blocked private/local host: {url}"
             )));
         }
+        crate::utils::http::check_url(&parsed).map_err(|e| ZeptoError::Tool(e.to_string()))?;
         let host = parsed.host_str().unwrap_or("").to_lowercase();
         if !self.allowed_domains.iter().any(|d| host_matches(d, &host)) {
             return Err(ZeptoError::Tool(format!(
@@ -81,16 +83,6 @@ impl HttpRequestTool {
     }
 }
 
-/// Check whether `host` matches `pattern`, supporting wildcard subdomains.
-/// `*.myco.com` matches `staging.myco.com` and `myco.com` itself.
-fn host_matches(pattern: &str, host: &str) -> bool {
-    if let Some(suffix) = pattern.strip_prefix("*.") {
-        host == suffix || host.ends_with(&format!(".{suffix}"))
-    } else {
-        host == pattern
-    }
-}
-
 fn http_request_redirect_policy() -> reqwest::redirect::Policy {
     reqwest::redirect::Policy::custom(|attempt| {
         if attempt.previous().len() >= MAX_HTTP_REQUEST_REDIRECTS {
@@ -169,7 +161,7 @@ impl Tool for HttpRequestTool {
 
         // Build a client that pins the DNS resolution to the IP we already
         // validated and checks every redirect hop before following.
-        let mut builder = Client::builder()
+        let mut builder = crate::utils::http::client_builder()
             .timeout(Duration::from_secs(self.timeout_secs))
             .redirect(http_request_redirect_policy());
         if let Some((host, addr)) = pinned {
@@ -216,17 +208,31 @@ impl Tool for HttpRequestTool {
         validate_redirect_target(response.url()).await?;
 
         let status = response.status().as_u16();
-        let body_bytes = response
-            .bytes()
+        // Read in chunks so an oversized body is never held in memory.
+        let limit = self
+            .max_response_bytes
+            .min(crate::utils::http::max_response_bytes());
+        let mut response = response;
+        let mut body_bytes: Vec<u8> = Vec::new();
+        let mut truncated = false;
+        while let Some(chunk) = response
+            .chunk()
             .await
-            .map_err(|e| ZeptoError::Tool(format!("Failed to read response body: {e}")))?;
+            .map_err(|e| ZeptoError::Tool(format!("Failed to read response body: {e}")))?
+        {
+            let take = chunk.len().min(limit - body_bytes.len());
+            body_bytes.extend_from_slice(&chunk[..take]);
+            if take < chunk.len() {
+                truncated = true;
+                break;
+            }
+        }
 
-        let body_str = if body_bytes.len() > self.max_response_bytes {
-            let truncated = &body_bytes[..self.max_response_bytes];
+        let body_str = if truncated {
             format!(
-                "{}\n[TRUNCATED — {} bytes total]",
-                String::from_utf8_lossy(truncated),
-                body_bytes.len()
+                "{}\n[TRUNCATED — response exceeded {} bytes]",
+                String::from_utf8_lossy(&body_bytes),
+                limit
             )
         } else {
             String::from_utf8_lossy(&body_bytes).into_owned()
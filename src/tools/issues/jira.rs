@@ -0,0 +1,617 @@
+//! Jira backend: REST API v3 on Cloud, v2 on Data Center, Agile API for
+//! sprints.
+//!
+//! Cloud is detected by a configured `email` (basic auth with an API token);
+//! without it the token is sent as a Data Center bearer token.
+
+use async_trait::async_trait;
+use reqwest::Method;
+use serde_json::{json, Value};
+
+use crate::config::JiraTrackerConfig;
+use crate::error::{Result, ZeptoError};
+use crate::utils::http::Client;
+
+use super::{
+    match_name, parse_date, Issue, IssueChanges, IssueDraft, IssueQuery, IssueTracker, Sprint,
+    StateCategory,
+};
+
+/// Sprint issues fetched per page, and the page cap.
+const SPRINT_PAGE_SIZE: usize = 100;
+const SPRINT_MAX_PAGES: usize = 5;
+
+/// Quote a value for JQL.
+fn jql_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// JQL for a structured query. `jql` in the query is used as-is.
+pub fn build_jql(query: &IssueQuery, default_project: Option<&str>) -> String {
+    if let Some(jql) = query.jql.as_deref() {
+        return jql.to_string();
+    }
+    let mut clauses = Vec::new();
+    if let Some(project) = query.project.as_deref().or(default_project) {
+        clauses.push(format!("project = {}", jql_quote(project)));
+    }
+    if let Some(text) = query.text.as_deref() {
+        clauses.push(format!("text ~ {}", jql_quote(text)));
+    }
+    match query.assignee.as_deref() {
+        Some(who) if who.eq_ignore_ascii_case("me") => {
+            clauses.push("assignee = currentUser()".to_string())
+        }
+        Some(who) if who.eq_ignore_ascii_case("none") => {
+            clauses.push("assignee is EMPTY".to_string())
+        }
+        Some(who) => clauses.push(format!("assignee = {}", jql_quote(who))),
+        None => {}
+    }
+    if let Some(state) = query.state.as_deref() {
+        clauses.push(format!("status = {}", jql_quote(state)));
+    } else if query.open_only {
+        clauses.push("statusCategory != Done".to_string());
+    }
+    format!("{} ORDER BY updated DESC", clauses.join(" AND "))
+        .trim_start()
+        .to_string()
+}
+
+/// Plain text from an Atlassian Document Format node (or a v2 string).
+pub fn adf_to_text(node: &Value) -> String {
+    fn walk(node: &Value, out: &mut String) {
+        match node {
+            Value::String(s) => out.push_str(s),
+            Value::Object(map) => {
+                if let Some(text) = map.get("text").and_then(Value::as_str) {
+                    out.push_str(text);
+                }
+                let kind = map.get("type").and_then(Value::as_str).unwrap_or("");
+                if kind == "hardBreak" {
+                    out.push('\n');
+                }
+                if kind == "listItem" {
+                    out.push_str("- ");
+                }
+                if let Some(children) = map.get("content").and_then(Value::as_array) {
+                    for child in children {
+                        walk(child, out);
+                    }
+                }
+                if matches!(kind, "paragraph" | "heading" | "codeBlock" | "blockquote") {
+                    out.push('\n');
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut out = String::new();
+    walk(node, &mut out);
+    out.trim().to_string()
+}
+
+fn adf_paragraphs(text: &str) -> Value {
+    let content: Vec<Value> = text
+        .split("\n\n")
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| json!({"type": "paragraph", "content": [{"type": "text", "text": p}]}))
+        .collect();
+    json!({"type": "doc", "version": 1, "content": content})
+}
+
+fn category(status: &Value) -> StateCategory {
+    match status
+        .pointer("/statusCategory/key")
+        .and_then(Value::as_str)
+    {
+        Some("done") => StateCategory::Done,
+        Some("indeterminate") => StateCategory::InProgress,
+        _ => StateCategory::Todo,
+    }
+}
+
+pub struct JiraTracker {
+    client: Client,
+    config: JiraTrackerConfig,
+}
+
+impl JiraTracker {
+    pub fn new(config: JiraTrackerConfig) -> Self {
+        Self {
+            client: crate::utils::http::client(),
+            config,
+        }
+    }
+
+    fn is_cloud(&self) -> bool {
+        self.config
+            .email
+            .as_deref()
+            .is_some_and(|e| !e.trim().is_empty())
+    }
+
+    fn api(&self) -> &'static str {
+        if self.is_cloud() {
+            "/rest/api/3"
+        } else {
+            "/rest/api/2"
+        }
+    }
+
+    async fn call(&self, method: Method, path: &str, body: Option<Value>) -> Result<Value> {
+        let base = self.config.url.trim().trim_end_matches('/');
+        if base.is_empty() {
+            return Err(ZeptoError::Config(
+                "Jira URL is not configured (tools.issues.jira.url)".into(),
+            ));
+        }
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", base, path))
+            .header("Accept", "application/json");
+        request = match self
+            .config
+            .email
+            .as_deref()
+            .filter(|e| !e.trim().is_empty())
+        {
+            Some(email) => request.basic_auth(email.trim(), Some(self.config.token.trim())),
+            None => request.bearer_auth(self.config.token.trim()),
+        };
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Jira request failed: {}", e)))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Invalid Jira response: {}", e)))?;
+        if !status.is_success() {
+            let body: Value = serde_json::from_str(&text).unwrap_or(Value::Null);
+            let mut messages: Vec<String> = body["errorMessages"]
+                .as_array()
+                .map(|m| {
+                    m.iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            if let Some(errors) = body["errors"].as_object() {
+                messages.extend(
+                    errors
+                        .iter()
+                        .map(|(field, e)| format!("{}: {}", field, e.as_str().unwrap_or_default())),
+                );
+            }
+            if messages.is_empty() {
+                messages.push(text.chars().take(300).collect());
+            }
+            return Err(ZeptoError::Tool(format!(
+                "Jira API error {}: {}",
+                status.as_u16(),
+                messages.join("; ")
+            )));
+        }
+        if text.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&text)
+            .map_err(|e| ZeptoError::Tool(format!("Invalid Jira response: {}", e)))
+    }
+
+    fn fields(&self) -> Vec<&str> {
+        let mut fields = vec![
+            "summary", "status", "assignee", "priority", "labels", "updated",
+        ];
+        if let Some(points) = self.config.story_points_field.as_deref() {
+            fields.push(points);
+        }
+        fields
+    }
+
+    fn parse_issue(&self, value: &Value) -> Issue {
+        let fields = &value["fields"];
+        let key = value["key"].as_str().unwrap_or("?").to_string();
+        let base = self.config.url.trim().trim_end_matches('/');
+        Issue {
+            url: Some(format!("{}/browse/{}", base, key)),
+            key,
+            title: fields["summary"].as_str().unwrap_or("").to_string(),
+            state: fields["status"]["name"].as_str().unwrap_or("?").to_string(),
+            category: category(&fields["status"]),
+            assignee: fields["assignee"]["displayName"]
+                .as_str()
+                .map(str::to_string),
+            priority: fields["priority"]["name"].as_str().map(str::to_string),
+            points: self
+                .config
+                .story_points_field
+                .as_deref()
+                .and_then(|f| fields[f].as_f64()),
+            labels: fields["labels"]
+                .as_array()
+                .map(|l| {
+                    l.iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            updated: fields["updated"]
+                .as_str()
+                .map(|s| s.chars().take(10).collect()),
+            description: (!fields["description"].is_null())
+                .then(|| adf_to_text(&fields["description"])),
+        }
+    }
+
+    fn project<'a>(&'a self, project: Option<&'a str>) -> Result<&'a str> {
+        project
+            .or(self.config.default_project.as_deref())
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| {
+                ZeptoError::Tool(
+                    "No 'project' given and no tools.issues.jira.default_project set".into(),
+                )
+            })
+    }
+
+    fn description_value(&self, text: &str) -> Value {
+        if self.is_cloud() {
+            adf_paragraphs(text)
+        } else {
+            json!(text)
+        }
+    }
+
+    /// The user reference Jira expects for an assignee.
+    async fn user_ref(&self, who: &str) -> Result<Value> {
+        let id_key = if self.is_cloud() { "accountId" } else { "name" };
+        if who.eq_ignore_ascii_case("none") {
+            return Ok(Value::Null);
+        }
+        let user = if who.eq_ignore_ascii_case("me") {
+            self.call(Method::GET, &format!("{}/myself", self.api()), None)
+                .await?
+        } else {
+            let param = if self.is_cloud() { "query" } else { "username" };
+            let encoded: String = url::form_urlencoded::Serializer::new(String::new())
+                .append_pair(param, who)
+                .finish();
+            let found = self
+                .call(
+                    Method::GET,
+                    &format!("{}/user/search?{}", self.api(), encoded),
+                    None,
+                )
+                .await?;
+            found
+                .as_array()
+                .and_then(|users| users.first().cloned())
+                .ok_or_else(|| ZeptoError::Tool(format!("No Jira user matches '{}'", who)))?
+        };
+        let mut reference = json!({});
+        reference[id_key] = user[id_key].clone();
+        Ok(reference)
+    }
+
+    async fn fields_for(
+        &self,
+        title: Option<&str>,
+        description: Option<&str>,
+        labels: Option<&[String]>,
+        assignee: Option<&str>,
+        priority: Option<&str>,
+    ) -> Result<Value> {
+        let mut fields = json!({});
+        if let Some(title) = title {
+            fields["summary"] = json!(title);
+        }
+        if let Some(description) = description {
+            fields["description"] = self.description_value(description);
+        }
+        if let Some(labels) = labels {
+            fields["labels"] = json!(labels);
+        }
+        if let Some(assignee) = assignee {
+            fields["assignee"] = self.user_ref(assignee).await?;
+        }
+        if let Some(priority) = priority {
+            fields["priority"] = json!({ "name": capitalize(priority) });
+        }
+        Ok(fields)
+    }
+
+    async fn transitions(&self, key: &str) -> Result<Vec<(String, String, String)>> {
+        let body = self
+            .call(
+                Method::GET,
+                &format!("{}/issue/{}/transitions", self.api(), key),
+                None,
+            )
+            .await?;
+        Ok(body["transitions"]
+            .as_array()
+            .map(|list| {
+                list.iter()
+                    .map(|t| {
+                        (
+                            t["id"].as_str().unwrap_or_default().to_string(),
+                            t["name"].as_str().unwrap_or_default().to_string(),
+                            t["to"]["name"].as_str().unwrap_or_default().to_string(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn board_id(&self, project: Option<&str>) -> Result<u64> {
+        if let Some(id) = self.config.board_id {
+            return Ok(id);
+        }
+        let project = self.project(project)?;
+        let boards = self
+            .call(
+                Method::GET,
+                &format!(
+                    "/rest/agile/1.0/board?type=scrum&projectKeyOrId={}",
+                    project
+                ),
+                None,
+            )
+            .await?;
+        boards["values"][0]["id"].as_u64().ok_or_else(|| {
+            ZeptoError::Tool(format!(
+                "No scrum board found for project {} (set tools.issues.jira.board_id)",
+                project
+            ))
+        })
+    }
+}
+
+fn capitalize(value: &str) -> String {
+    let mut chars = value.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[async_trait]
+impl IssueTracker for JiraTracker {
+    fn name(&self) -> &'static str {
+        "jira"
+    }
+
+    async fn search(&self, query: &IssueQuery) -> Result<Vec<Issue>> {
+        let jql = build_jql(query, self.config.default_project.as_deref());
+        let mut payload = json!({
+            "jql": jql,
+            "maxResults": query.limit,
+            "fields": self.fields(),
+        });
+        // Cloud retired POST /search in favour of /search/jql.
+        let path = if self.is_cloud() {
+            "/rest/api/3/search/jql"
+        } else {
+            payload["startAt"] = json!(0);
+            "/rest/api/2/search"
+        };
+        let body = self.call(Method::POST, path, Some(payload)).await?;
+        Ok(body["issues"]
+            .as_array()
+            .map(|issues| issues.iter().map(|i| self.parse_issue(i)).collect())
+            .unwrap_or_default())
+    }
+
+    async fn get(&self, key: &str) -> Result<Issue> {
+        let mut fields = self.fields();
+        fields.push("description");
+        let body = self
+            .call(
+                Method::GET,
+                &format!("{}/issue/{}?fields={}", self.api(), key, fields.join(",")),
+                None,
+            )
+            .await?;
+        Ok(self.parse_issue(&body))
+    }
+
+    async fn create(&self, draft: &IssueDraft) -> Result<Issue> {
+        let project = self.project(draft.project.as_deref())?;
+        let mut fields = self
+            .fields_for(
+                Some(&draft.title),
+                draft.description.as_deref(),
+                (!draft.labels.is_empty()).then_some(draft.labels.as_slice()),
+                draft.assignee.as_deref(),
+                draft.priority.as_deref(),
+            )
+            .await?;
+        fields["project"] = json!({ "key": project });
+        fields["issuetype"] = json!({ "name": self.config.issue_type });
+        let created = self
+            .call(
+                Method::POST,
+                &format!("{}/issue", self.api()),
+                Some(json!({ "fields": fields })),
+            )
+            .await?;
+        let key = created["key"]
+            .as_str()
+            .ok_or_else(|| ZeptoError::Tool("Jira returned no issue key".into()))?;
+        self.get(key).await
+    }
+
+    async fn update(&self, key: &str, changes: &IssueChanges) -> Result<()> {
+        let fields = self
+            .fields_for(
+                changes.title.as_deref(),
+                changes.description.as_deref(),
+                changes.labels.as_deref(),
+                changes.assignee.as_deref(),
+                changes.priority.as_deref(),
+            )
+            .await?;
+        self.call(
+            Method::PUT,
+            &format!("{}/issue/{}", self.api(), key),
+            Some(json!({ "fields": fields })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn states(&self, key: &str) -> Result<Vec<String>> {
+        Ok(self
+            .transitions(key)
+            .await?
+            .into_iter()
+            .map(|(_, name, to)| {
+                if name == to || to.is_empty() {
+                    name
+                } else {
+                    format!("{} (→ {})", name, to)
+                }
+            })
+            .collect())
+    }
+
+    async fn transition(&self, key: &str, state: &str) -> Result<String> {
+        let transitions = self.transitions(key).await?;
+        // Match the target status first, then the transition's own name.
+        let chosen = match_name(&transitions, |t| t.2.as_str(), state)
+            .or_else(|_| match_name(&transitions, |t| t.1.as_str(), state))?;
+        self.call(
+            Method::POST,
+            &format!("{}/issue/{}/transitions", self.api(), key),
+            Some(json!({ "transition": { "id": chosen.0 } })),
+        )
+        .await?;
+        Ok(if chosen.2.is_empty() {
+            chosen.1.clone()
+        } else {
+            chosen.2.clone()
+        })
+    }
+
+    async fn active_sprint(&self, project: Option<&str>) -> Result<Sprint> {
+        let board = self.board_id(project).await?;
+        let sprints = self
+            .call(
+                Method::GET,
+                &format!("/rest/agile/1.0/board/{}/sprint?state=active", board),
+                None,
+            )
+            .await?;
+        let sprint = &sprints["values"][0];
+        let sprint_id = sprint["id"]
+            .as_u64()
+            .ok_or_else(|| ZeptoError::Tool(format!("Board {} has no active sprint", board)))?;
+
+        let fields = self.fields().join(",");
+        let mut issues = Vec::new();
+        for page in 0..SPRINT_MAX_PAGES {
+            let body = self
+                .call(
+                    Method::GET,
+                    &format!(
+                        "/rest/agile/1.0/sprint/{}/issue?fields={}&maxResults={}&startAt={}",
+                        sprint_id,
+                        fields,
+                        SPRINT_PAGE_SIZE,
+                        page * SPRINT_PAGE_SIZE
+                    ),
+                    None,
+                )
+                .await?;
+            let batch = body["issues"].as_array().cloned().unwrap_or_default();
+            issues.extend(batch.iter().map(|i| self.parse_issue(i)));
+            let total = body["total"].as_u64().unwrap_or(0) as usize;
+            if batch.len() < SPRINT_PAGE_SIZE || issues.len() >= total {
+                break;
+            }
+        }
+
+        Ok(Sprint {
+            name: sprint["name"].as_str().unwrap_or("?").to_string(),
+            start: sprint["startDate"].as_str().and_then(parse_date),
+            end: sprint["endDate"].as_str().and_then(parse_date),
+            issues,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_jql() {
+        let query = IssueQuery {
+            text: Some("login \"fails\"".into()),
+            assignee: Some("me".into()),
+            open_only: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_jql(&query, Some("WEB")),
+            "project = \"WEB\" AND text ~ \"login \\\"fails\\\"\" AND assignee = currentUser() \
+             AND statusCategory != Done ORDER BY updated DESC"
+        );
+        let query = IssueQuery {
+            state: Some("In Review".into()),
+            open_only: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_jql(&query, None),
+            "status = \"In Review\" ORDER BY updated DESC"
+        );
+        assert_eq!(
+            build_jql(&IssueQuery::default(), None),
+            "ORDER BY updated DESC"
+        );
+    }
+
+    #[test]
+    fn test_adf_to_text_and_parse_issue() {
+        let adf = json!({"type": "doc", "content": [
+            {"type": "paragraph", "content": [{"type": "text", "text": "Steps:"}]},
+            {"type": "bulletList", "content": [
+                {"type": "listItem", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "open app"}]}]}
+            ]}
+        ]});
+        assert_eq!(adf_to_text(&adf), "Steps:\n- open app");
+
+        let tracker = JiraTracker::new(JiraTrackerConfig {
+            url: "https://acme.atlassian.net/".into(),
+            story_points_field: Some("customfield_10016".into()),
+            ..Default::default()
+        });
+        let issue = tracker.parse_issue(&json!({"key": "WEB-7", "fields": {
+            "summary": "Login fails", "updated": "2026-10-01T10:00:00.000+0000",
+            "status": {"name": "In Review", "statusCategory": {"key": "indeterminate"}},
+            "assignee": {"displayName": "Ana"}, "priority": {"name": "High"},
+            "labels": ["auth"], "customfield_10016": 3.0
+        }}));
+        assert_eq!(
+            issue.summary_line(),
+            "WEB-7 [In Review] Login fails — @Ana, high, 3 pts"
+        );
+        assert_eq!(issue.category, StateCategory::InProgress);
+        assert_eq!(
+            issue.url.as_deref(),
+            Some("https://acme.atlassian.net/browse/WEB-7")
+        );
+        assert_eq!(issue.description, None);
+    }
+}
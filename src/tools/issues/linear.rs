@@ -0,0 +1,499 @@
+//! Linear backend over the GraphQL API. Teams stand in for projects and the
+//! active cycle for the sprint.
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::config::LinearTrackerConfig;
+use crate::error::{Result, ZeptoError};
+use crate::utils::http::Client;
+
+use super::{
+    match_name, parse_date, Issue, IssueChanges, IssueDraft, IssueQuery, IssueTracker, Sprint,
+    StateCategory,
+};
+
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+/// Issue fields selected by every query.
+const ISSUE_FIELDS: &str = "identifier title url updatedAt priorityLabel estimate description \
+                            state { name type } assignee { name displayName } \
+                            labels { nodes { name } }";
+
+/// Issues read from a cycle.
+const CYCLE_ISSUE_LIMIT: usize = 250;
+
+/// Linear's numeric priority for a name.
+pub fn priority_value(name: &str) -> Result<u8> {
+    match name.trim().to_lowercase().as_str() {
+        "urgent" => Ok(1),
+        "high" => Ok(2),
+        "medium" | "normal" => Ok(3),
+        "low" => Ok(4),
+        "none" | "no priority" => Ok(0),
+        other => Err(ZeptoError::Tool(format!(
+            "Unknown Linear priority '{}'. Use urgent, high, medium, low or none",
+            other
+        ))),
+    }
+}
+
+/// `IssueFilter` for a structured query.
+pub fn linear_filter(query: &IssueQuery, default_team: Option<&str>) -> Result<Value> {
+    if query.jql.is_some() {
+        return Err(ZeptoError::Tool(
+            "'jql' is only supported by the Jira tracker".into(),
+        ));
+    }
+    let mut filter = json!({});
+    if let Some(team) = query.project.as_deref().or(default_team) {
+        filter["team"] = json!({ "key": { "eq": team } });
+    }
+    match query.assignee.as_deref() {
+        Some(who) if who.eq_ignore_ascii_case("me") => {
+            filter["assignee"] = json!({ "isMe": { "eq": true } })
+        }
+        Some(who) if who.eq_ignore_ascii_case("none") => {
+            filter["assignee"] = json!({ "null": true })
+        }
+        Some(who) => {
+            filter["assignee"] = json!({ "or": [
+                { "name": { "containsIgnoreCase": who } },
+                { "displayName": { "containsIgnoreCase": who } },
+            ] })
+        }
+        None => {}
+    }
+    if let Some(state) = query.state.as_deref() {
+        filter["state"] = json!({ "name": { "eqIgnoreCase": state } });
+    } else if query.open_only {
+        filter["state"] = json!({ "type": { "nin": ["completed", "canceled"] } });
+    }
+    if let Some(text) = query.text.as_deref() {
+        filter["or"] = json!([
+            { "title": { "containsIgnoreCase": text } },
+            { "description": { "containsIgnoreCase": text } },
+        ]);
+    }
+    Ok(filter)
+}
+
+fn category(state_type: &str) -> StateCategory {
+    match state_type {
+        "started" => StateCategory::InProgress,
+        "completed" | "canceled" => StateCategory::Done,
+        _ => StateCategory::Todo,
+    }
+}
+
+/// Normalize a Linear `Issue` node.
+pub fn parse_issue(node: &Value) -> Issue {
+    Issue {
+        key: node["identifier"].as_str().unwrap_or("?").to_string(),
+        title: node["title"].as_str().unwrap_or("").to_string(),
+        state: node["state"]["name"].as_str().unwrap_or("?").to_string(),
+        category: category(node["state"]["type"].as_str().unwrap_or("")),
+        assignee: node["assignee"]["displayName"]
+            .as_str()
+            .or_else(|| node["assignee"]["name"].as_str())
+            .map(str::to_string),
+        priority: node["priorityLabel"]
+            .as_str()
+            .filter(|p| *p != "No priority")
+            .map(str::to_string),
+        points: node["estimate"].as_f64(),
+        labels: node["labels"]["nodes"]
+            .as_array()
+            .map(|l| {
+                l.iter()
+                    .filter_map(|n| n["name"].as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        url: node["url"].as_str().map(str::to_string),
+        updated: node["updatedAt"]
+            .as_str()
+            .map(|s| s.chars().take(10).collect()),
+        description: node["description"].as_str().map(str::to_string),
+    }
+}
+
+pub struct LinearTracker {
+    client: Client,
+    config: LinearTrackerConfig,
+}
+
+impl LinearTracker {
+    pub fn new(config: LinearTrackerConfig) -> Self {
+        Self {
+            client: crate::utils::http::client(),
+            config,
+        }
+    }
+
+    async fn graphql(&self, query: &str, variables: Value) -> Result<Value> {
+        let response = self
+            .client
+            .post(LINEAR_API_URL)
+            .header("Authorization", self.config.api_key.trim())
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Linear request failed: {}", e)))?;
+        let status = response.status();
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Invalid Linear response: {}", e)))?;
+        if let Some(errors) = body["errors"].as_array().filter(|e| !e.is_empty()) {
+            let messages: Vec<&str> = errors
+                .iter()
+                .filter_map(|e| e["message"].as_str())
+                .collect();
+            return Err(ZeptoError::Tool(format!(
+                "Linear API error {}: {}",
+                status.as_u16(),
+                messages.join("; ")
+            )));
+        }
+        if !status.is_success() {
+            return Err(ZeptoError::Tool(format!(
+                "Linear API error {}",
+                status.as_u16()
+            )));
+        }
+        Ok(body["data"].clone())
+    }
+
+    fn team<'a>(&'a self, team: Option<&'a str>) -> Result<&'a str> {
+        team.or(self.config.default_team.as_deref())
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| {
+                ZeptoError::Tool(
+                    "No 'project' (team key) given and no tools.issues.linear.default_team set"
+                        .into(),
+                )
+            })
+    }
+
+    async fn team_id(&self, key: &str) -> Result<String> {
+        let data = self
+            .graphql(
+                "query($key: String!) { teams(filter: { key: { eq: $key } }) { nodes { id } } }",
+                json!({ "key": key }),
+            )
+            .await?;
+        data["teams"]["nodes"][0]["id"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| ZeptoError::Tool(format!("No Linear team with key '{}'", key)))
+    }
+
+    async fn user_id(&self, who: &str) -> Result<Option<String>> {
+        if who.eq_ignore_ascii_case("none") {
+            return Ok(None);
+        }
+        let (query, variables, pointer) = if who.eq_ignore_ascii_case("me") {
+            ("query { viewer { id } }", json!({}), "/viewer/id")
+        } else {
+            (
+                "query($who: String!) { users(filter: { or: [\
+                 { name: { containsIgnoreCase: $who } }, \
+                 { displayName: { containsIgnoreCase: $who } }] }) { nodes { id } } }",
+                json!({ "who": who }),
+                "/users/nodes/0/id",
+            )
+        };
+        let data = self.graphql(query, variables).await?;
+        data.pointer(pointer)
+            .and_then(Value::as_str)
+            .map(|id| Some(id.to_string()))
+            .ok_or_else(|| ZeptoError::Tool(format!("No Linear user matches '{}'", who)))
+    }
+
+    async fn label_ids(&self, names: &[String]) -> Result<Vec<String>> {
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+        let data = self
+            .graphql(
+                "query($names: [String!]) { issueLabels(filter: { name: { in: $names } }) \
+                 { nodes { id name } } }",
+                json!({ "names": names }),
+            )
+            .await?;
+        let found = data["issueLabels"]["nodes"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        names
+            .iter()
+            .map(|name| {
+                found
+                    .iter()
+                    .find(|l| l["name"].as_str() == Some(name.as_str()))
+                    .and_then(|l| l["id"].as_str())
+                    .map(str::to_string)
+                    .ok_or_else(|| ZeptoError::Tool(format!("No Linear label '{}'", name)))
+            })
+            .collect()
+    }
+
+    /// `IssueCreateInput`/`IssueUpdateInput` fields shared by create and update.
+    async fn input(
+        &self,
+        title: Option<&str>,
+        description: Option<&str>,
+        labels: Option<&[String]>,
+        assignee: Option<&str>,
+        priority: Option<&str>,
+    ) -> Result<Value> {
+        let mut input = json!({});
+        if let Some(title) = title {
+            input["title"] = json!(title);
+        }
+        if let Some(description) = description {
+            input["description"] = json!(description);
+        }
+        if let Some(labels) = labels {
+            input["labelIds"] = json!(self.label_ids(labels).await?);
+        }
+        if let Some(assignee) = assignee {
+            input["assigneeId"] = json!(self.user_id(assignee).await?);
+        }
+        if let Some(priority) = priority {
+            input["priority"] = json!(priority_value(priority)?);
+        }
+        Ok(input)
+    }
+
+    /// Workflow states of the issue's team as `(id, name)`.
+    async fn team_states(&self, key: &str) -> Result<Vec<(String, String)>> {
+        let data = self
+            .graphql(
+                "query($id: String!) { issue(id: $id) { team { states { nodes { id name position } } } } }",
+                json!({ "id": key }),
+            )
+            .await?;
+        let mut nodes = data["issue"]["team"]["states"]["nodes"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        nodes.sort_by(|a, b| {
+            let a = a["position"].as_f64().unwrap_or(0.0);
+            let b = b["position"].as_f64().unwrap_or(0.0);
+            a.total_cmp(&b)
+        });
+        Ok(nodes
+            .iter()
+            .map(|n| {
+                (
+                    n["id"].as_str().unwrap_or_default().to_string(),
+                    n["name"].as_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl IssueTracker for LinearTracker {
+    fn name(&self) -> &'static str {
+        "linear"
+    }
+
+    async fn search(&self, query: &IssueQuery) -> Result<Vec<Issue>> {
+        let filter = linear_filter(query, self.config.default_team.as_deref())?;
+        let data = self
+            .graphql(
+                &format!(
+                    "query($filter: IssueFilter, $first: Int) {{ issues(filter: $filter, \
+                     first: $first, orderBy: updatedAt) {{ nodes {{ {} }} }} }}",
+                    ISSUE_FIELDS
+                ),
+                json!({ "filter": filter, "first": query.limit }),
+            )
+            .await?;
+        Ok(data["issues"]["nodes"]
+            .as_array()
+            .map(|nodes| nodes.iter().map(parse_issue).collect())
+            .unwrap_or_default())
+    }
+
+    async fn get(&self, key: &str) -> Result<Issue> {
+        let data = self
+            .graphql(
+                &format!(
+                    "query($id: String!) {{ issue(id: $id) {{ {} }} }}",
+                    ISSUE_FIELDS
+                ),
+                json!({ "id": key }),
+            )
+            .await?;
+        if data["issue"].is_null() {
+            return Err(ZeptoError::Tool(format!("Linear issue {} not found", key)));
+        }
+        Ok(parse_issue(&data["issue"]))
+    }
+
+    async fn create(&self, draft: &IssueDraft) -> Result<Issue> {
+        let team = self.team(draft.project.as_deref())?;
+        let mut input = self
+            .input(
+                Some(&draft.title),
+                draft.description.as_deref(),
+                (!draft.labels.is_empty()).then_some(draft.labels.as_slice()),
+                draft.assignee.as_deref(),
+                draft.priority.as_deref(),
+            )
+            .await?;
+        input["teamId"] = json!(self.team_id(team).await?);
+        let data = self
+            .graphql(
+                &format!(
+                    "mutation($input: IssueCreateInput!) {{ issueCreate(input: $input) \
+                     {{ success issue {{ {} }} }} }}",
+                    ISSUE_FIELDS
+                ),
+                json!({ "input": input }),
+            )
+            .await?;
+        if data["issueCreate"]["success"].as_bool() != Some(true) {
+            return Err(ZeptoError::Tool("Linear did not create the issue".into()));
+        }
+        Ok(parse_issue(&data["issueCreate"]["issue"]))
+    }
+
+    async fn update(&self, key: &str, changes: &IssueChanges) -> Result<()> {
+        let input = self
+            .input(
+                changes.title.as_deref(),
+                changes.description.as_deref(),
+                changes.labels.as_deref(),
+                changes.assignee.as_deref(),
+                changes.priority.as_deref(),
+            )
+            .await?;
+        let data = self
+            .graphql(
+                "mutation($id: String!, $input: IssueUpdateInput!) \
+                 { issueUpdate(id: $id, input: $input) { success } }",
+                json!({ "id": key, "input": input }),
+            )
+            .await?;
+        if data["issueUpdate"]["success"].as_bool() != Some(true) {
+            return Err(ZeptoError::Tool(format!("Linear did not update {}", key)));
+        }
+        Ok(())
+    }
+
+    async fn states(&self, key: &str) -> Result<Vec<String>> {
+        Ok(self
+            .team_states(key)
+            .await?
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect())
+    }
+
+    async fn transition(&self, key: &str, state: &str) -> Result<String> {
+        let states = self.team_states(key).await?;
+        let (state_id, name) = match_name(&states, |s| s.1.as_str(), state)?;
+        let data = self
+            .graphql(
+                "mutation($id: String!, $state: String!) \
+                 { issueUpdate(id: $id, input: { stateId: $state }) { success } }",
+                json!({ "id": key, "state": state_id }),
+            )
+            .await?;
+        if data["issueUpdate"]["success"].as_bool() != Some(true) {
+            return Err(ZeptoError::Tool(format!("Linear did not move {}", key)));
+        }
+        Ok(name.clone())
+    }
+
+    async fn active_sprint(&self, project: Option<&str>) -> Result<Sprint> {
+        let team = self.team(project)?;
+        let data = self
+            .graphql(
+                &format!(
+                    "query($key: String!, $first: Int) {{ teams(filter: {{ key: {{ eq: $key }} }}) \
+                     {{ nodes {{ activeCycle {{ number name startsAt endsAt \
+                     issues(first: $first) {{ nodes {{ {} }} }} }} }} }} }}",
+                    ISSUE_FIELDS
+                ),
+                json!({ "key": team, "first": CYCLE_ISSUE_LIMIT }),
+            )
+            .await?;
+        let cycle = &data["teams"]["nodes"][0]["activeCycle"];
+        if cycle.is_null() {
+            return Err(ZeptoError::Tool(format!(
+                "Team {} has no active cycle",
+                team
+            )));
+        }
+        let name = match (cycle["name"].as_str(), cycle["number"].as_u64()) {
+            (Some(name), _) if !name.is_empty() => name.to_string(),
+            (_, Some(number)) => format!("{} cycle {}", team, number),
+            _ => format!("{} cycle", team),
+        };
+        Ok(Sprint {
+            name,
+            start: cycle["startsAt"].as_str().and_then(parse_date),
+            end: cycle["endsAt"].as_str().and_then(parse_date),
+            issues: cycle["issues"]["nodes"]
+                .as_array()
+                .map(|nodes| nodes.iter().map(parse_issue).collect())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_filter() {
+        let query = IssueQuery {
+            text: Some("crash".into()),
+            assignee: Some("me".into()),
+            open_only: true,
+            ..Default::default()
+        };
+        let filter = linear_filter(&query, Some("ENG")).unwrap();
+        assert_eq!(filter["team"]["key"]["eq"], "ENG");
+        assert_eq!(filter["assignee"]["isMe"]["eq"], true);
+        assert_eq!(filter["state"]["type"]["nin"][0], "completed");
+        assert_eq!(filter["or"][0]["title"]["containsIgnoreCase"], "crash");
+
+        let query = IssueQuery {
+            jql: Some("project = X".into()),
+            ..Default::default()
+        };
+        assert!(linear_filter(&query, None).is_err());
+        assert_eq!(priority_value("Normal").unwrap(), 3);
+        assert!(priority_value("asap").is_err());
+    }
+
+    #[test]
+    fn test_parse_issue() {
+        let issue = parse_issue(&json!({
+            "identifier": "ENG-45", "title": "Crash on start", "url": "https://linear.app/x/issue/ENG-45",
+            "updatedAt": "2026-10-02T08:00:00.000Z", "priorityLabel": "Urgent", "estimate": 2,
+            "state": {"name": "Started", "type": "started"},
+            "assignee": {"name": "bo", "displayName": "Bo"},
+            "labels": {"nodes": [{"name": "bug"}]}
+        }));
+        assert_eq!(
+            issue.summary_line(),
+            "ENG-45 [Started] Crash on start — @Bo, urgent, 2 pts"
+        );
+        assert_eq!(issue.category, StateCategory::InProgress);
+        assert_eq!(issue.labels, vec!["bug"]);
+        assert_eq!(issue.updated.as_deref(), Some("2026-10-02"));
+        assert_eq!(category("canceled"), StateCategory::Done);
+    }
+}
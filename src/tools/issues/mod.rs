@@ -0,0 +1,753 @@
+//! Issue tracker tool — one interface over Jira and Linear.
+//!
+//! Each backend implements [`IssueTracker`]; the `issues` tool picks one per
+//! call (`tracker`, else `tools.issues.default_tracker`, else the only one
+//! configured) and renders results the same way for both.
+//!
+//! # Actions
+//!
+//! - `search`         — issues by text, project/team, assignee and state
+//!   (Jira also takes raw `jql`)
+//! - `get`            — one issue with its description
+//! - `create`         — new issue with labels, assignee and priority
+//! - `update`         — change title, description, labels, assignee, priority
+//! - `transition`     — move to a workflow state; without `state`, lists them
+//! - `sprint_summary` — progress of the active Jira sprint or Linear cycle
+
+mod jira;
+mod linear;
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::{Local, NaiveDate};
+use serde_json::{json, Value};
+
+use crate::config::IssuesToolConfig;
+use crate::error::{Result, ZeptoError};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+pub use jira::JiraTracker;
+pub use linear::LinearTracker;
+
+/// Longest description shown by `get`.
+const MAX_DESCRIPTION_CHARS: usize = 4000;
+
+/// Open issues listed per section of a sprint summary.
+const SPRINT_LIST_LIMIT: usize = 15;
+
+/// Workflow stage shared by all trackers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StateCategory {
+    #[default]
+    Todo,
+    InProgress,
+    Done,
+}
+
+/// An issue, normalized across trackers.
+#[derive(Debug, Clone, Default)]
+pub struct Issue {
+    /// `PROJ-123` or `ENG-45`
+    pub key: String,
+    pub title: String,
+    /// Workflow state name as the tracker shows it
+    pub state: String,
+    pub category: StateCategory,
+    pub assignee: Option<String>,
+    pub priority: Option<String>,
+    /// Story points or estimate
+    pub points: Option<f64>,
+    pub labels: Vec<String>,
+    pub url: Option<String>,
+    /// Last update, `YYYY-MM-DD`
+    pub updated: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Issue {
+    /// One-line summary: key, state, title, assignee, priority, points.
+    pub fn summary_line(&self) -> String {
+        let mut line = format!("{} [{}] {}", self.key, self.state, self.title);
+        let mut extras = Vec::new();
+        if let Some(assignee) = &self.assignee {
+            extras.push(format!("@{}", assignee));
+        }
+        if let Some(priority) = &self.priority {
+            extras.push(priority.to_lowercase());
+        }
+        if let Some(points) = self.points {
+            extras.push(format!("{} pts", points));
+        }
+        if !extras.is_empty() {
+            line.push_str(&format!(" — {}", extras.join(", ")));
+        }
+        line
+    }
+
+    fn render(&self) -> String {
+        let mut out = self.summary_line();
+        if !self.labels.is_empty() {
+            out.push_str(&format!("\nLabels: {}", self.labels.join(", ")));
+        }
+        if let Some(updated) = &self.updated {
+            out.push_str(&format!("\nUpdated: {}", updated));
+        }
+        if let Some(url) = &self.url {
+            out.push_str(&format!("\n{}", url));
+        }
+        if let Some(description) = self.description.as_deref().map(str::trim) {
+            if !description.is_empty() {
+                let mut text: String = description.chars().take(MAX_DESCRIPTION_CHARS).collect();
+                if text.len() < description.len() {
+                    text.push_str("\n...");
+                }
+                out.push_str(&format!("\n\n{}", text));
+            }
+        }
+        out
+    }
+}
+
+/// Search filters; empty fields are not applied.
+#[derive(Debug, Clone, Default)]
+pub struct IssueQuery {
+    /// Free text matched against title and description
+    pub text: Option<String>,
+    /// Jira project key or Linear team key
+    pub project: Option<String>,
+    /// Assignee name, or `me`
+    pub assignee: Option<String>,
+    /// Exact workflow state name
+    pub state: Option<String>,
+    /// Skip done and cancelled issues
+    pub open_only: bool,
+    /// Raw JQL (Jira only); replaces the other filters
+    pub jql: Option<String>,
+    pub limit: usize,
+}
+
+/// A new issue.
+#[derive(Debug, Clone, Default)]
+pub struct IssueDraft {
+    pub project: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub assignee: Option<String>,
+    pub priority: Option<String>,
+}
+
+/// Field changes; `None` leaves a field as it is.
+#[derive(Debug, Clone, Default)]
+pub struct IssueChanges {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub assignee: Option<String>,
+    pub priority: Option<String>,
+}
+
+impl IssueChanges {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.labels.is_none()
+            && self.assignee.is_none()
+            && self.priority.is_none()
+    }
+}
+
+/// The active sprint (Jira) or cycle (Linear) and its issues.
+#[derive(Debug, Clone, Default)]
+pub struct Sprint {
+    pub name: String,
+    pub start: Option<NaiveDate>,
+    pub end: Option<NaiveDate>,
+    pub issues: Vec<Issue>,
+}
+
+/// An issue tracker backend.
+#[async_trait]
+pub trait IssueTracker: Send + Sync {
+    /// Backend name used in the `tracker` argument.
+    fn name(&self) -> &'static str;
+
+    async fn search(&self, query: &IssueQuery) -> Result<Vec<Issue>>;
+
+    async fn get(&self, key: &str) -> Result<Issue>;
+
+    async fn create(&self, draft: &IssueDraft) -> Result<Issue>;
+
+    async fn update(&self, key: &str, changes: &IssueChanges) -> Result<()>;
+
+    /// Workflow states the issue can move to.
+    async fn states(&self, key: &str) -> Result<Vec<String>>;
+
+    /// Move the issue to `state`; returns the state it ended up in.
+    async fn transition(&self, key: &str, state: &str) -> Result<String>;
+
+    /// The active sprint or cycle for a project or team.
+    async fn active_sprint(&self, project: Option<&str>) -> Result<Sprint>;
+}
+
+/// Pick the option named `wanted`: exact (case-insensitive) match first,
+/// then a unique partial match.
+pub fn match_name<'a, T>(
+    options: &'a [T],
+    name: impl Fn(&T) -> &str,
+    wanted: &str,
+) -> Result<&'a T> {
+    let wanted = wanted.trim().to_lowercase();
+    if let Some(exact) = options.iter().find(|o| name(o).to_lowercase() == wanted) {
+        return Ok(exact);
+    }
+    let partial: Vec<&T> = options
+        .iter()
+        .filter(|o| name(o).to_lowercase().contains(&wanted))
+        .collect();
+    if let [only] = partial.as_slice() {
+        return Ok(only);
+    }
+    let names: Vec<&str> = options.iter().map(&name).collect();
+    Err(ZeptoError::Tool(format!(
+        "No single state matches '{}'. Available: {}",
+        wanted,
+        names.join(", ")
+    )))
+}
+
+/// Date part of an ISO-8601 timestamp.
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+fn format_points(points: f64) -> String {
+    if points.fract() == 0.0 {
+        format!("{}", points as i64)
+    } else {
+        format!("{:.1}", points)
+    }
+}
+
+/// Sprint progress: totals, points, per-assignee counts and open work.
+pub fn summarize_sprint(sprint: &Sprint, today: NaiveDate) -> String {
+    let total = sprint.issues.len();
+    let count = |c: StateCategory| sprint.issues.iter().filter(|i| i.category == c).count();
+    let (todo, doing, done) = (
+        count(StateCategory::Todo),
+        count(StateCategory::InProgress),
+        count(StateCategory::Done),
+    );
+
+    let mut out = format!("Sprint \"{}\"", sprint.name);
+    match (sprint.start, sprint.end) {
+        (Some(start), Some(end)) => {
+            let left = (end - today).num_days();
+            let when = if left < 0 {
+                format!("ended {} day(s) ago", -left)
+            } else {
+                format!("{} day(s) left", left)
+            };
+            out.push_str(&format!(" ({} → {}, {})", start, end, when));
+        }
+        (_, Some(end)) => out.push_str(&format!(" (ends {})", end)),
+        _ => {}
+    }
+    out.push('\n');
+
+    let percent = (done * 100).checked_div(total).unwrap_or(0);
+    out.push_str(&format!(
+        "Progress: {}/{} issues done ({}%)",
+        done, total, percent
+    ));
+    let points: Vec<(f64, bool)> = sprint
+        .issues
+        .iter()
+        .filter_map(|i| Some((i.points?, i.category == StateCategory::Done)))
+        .collect();
+    if !points.is_empty() {
+        let all: f64 = points.iter().map(|(p, _)| p).sum();
+        let burned: f64 = points.iter().filter(|(_, d)| *d).map(|(p, _)| p).sum();
+        out.push_str(&format!(
+            ", {}/{} points",
+            format_points(burned),
+            format_points(all)
+        ));
+    }
+    out.push_str(&format!(
+        "\nTo do {} · In progress {} · Done {}\n",
+        todo, doing, done
+    ));
+
+    if let Some(end) = sprint.end {
+        let left = (end - today).num_days();
+        if (0..=2).contains(&left) && done < total {
+            out.push_str(&format!(
+                "At risk: {} issue(s) still open with {} day(s) left\n",
+                total - done,
+                left
+            ));
+        }
+    }
+
+    let mut by_assignee: BTreeMap<&str, [usize; 3]> = BTreeMap::new();
+    for issue in &sprint.issues {
+        let who = issue.assignee.as_deref().unwrap_or("unassigned");
+        by_assignee.entry(who).or_default()[issue.category as usize] += 1;
+    }
+    if !by_assignee.is_empty() {
+        out.push_str("\nBy assignee:\n");
+        for (who, [todo, doing, done]) in &by_assignee {
+            out.push_str(&format!(
+                "- {}: {}/{} done, {} in progress, {} to do\n",
+                who,
+                done,
+                todo + doing + done,
+                doing,
+                todo
+            ));
+        }
+    }
+
+    for (title, category) in [
+        ("In progress", StateCategory::InProgress),
+        ("Not started", StateCategory::Todo),
+    ] {
+        let issues: Vec<&Issue> = sprint
+            .issues
+            .iter()
+            .filter(|i| i.category == category)
+            .collect();
+        if issues.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n{}:\n", title));
+        for issue in issues.iter().take(SPRINT_LIST_LIMIT) {
+            out.push_str(&format!("- {}\n", issue.summary_line()));
+        }
+        if issues.len() > SPRINT_LIST_LIMIT {
+            out.push_str(&format!(
+                "- ... {} more\n",
+                issues.len() - SPRINT_LIST_LIMIT
+            ));
+        }
+    }
+    out
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn owned_arg(args: &Value, key: &str) -> Option<String> {
+    str_arg(args, key).map(str::to_string)
+}
+
+fn required_arg<'a>(args: &'a Value, key: &str) -> Result<&'a str> {
+    str_arg(args, key).ok_or_else(|| ZeptoError::Tool(format!("Missing '{}'", key)))
+}
+
+fn labels_arg(args: &Value) -> Option<Vec<String>> {
+    match args.get("labels")? {
+        Value::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+        ),
+        Value::String(s) => Some(
+            s.split(',')
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Jira and Linear issues behind one tool.
+pub struct IssuesTool {
+    trackers: Vec<Box<dyn IssueTracker>>,
+    default_tracker: Option<String>,
+    max_results: usize,
+}
+
+impl IssuesTool {
+    /// Create the tool with a tracker for each configured backend.
+    pub fn new(config: IssuesToolConfig) -> Self {
+        let mut trackers: Vec<Box<dyn IssueTracker>> = Vec::new();
+        if let Some(jira) = config.jira {
+            trackers.push(Box::new(JiraTracker::new(jira)));
+        }
+        if let Some(linear) = config.linear {
+            trackers.push(Box::new(LinearTracker::new(linear)));
+        }
+        Self {
+            trackers,
+            default_tracker: config.default_tracker,
+            max_results: config.max_results.max(1),
+        }
+    }
+
+    /// Replace the configured trackers.
+    pub fn with_trackers(mut self, trackers: Vec<Box<dyn IssueTracker>>) -> Self {
+        self.trackers = trackers;
+        self
+    }
+
+    fn tracker(&self, args: &Value) -> Result<&dyn IssueTracker> {
+        let names: Vec<&str> = self.trackers.iter().map(|t| t.name()).collect();
+        let wanted = str_arg(args, "tracker").or(self.default_tracker.as_deref());
+        let tracker = match wanted {
+            Some(name) => self
+                .trackers
+                .iter()
+                .find(|t| t.name().eq_ignore_ascii_case(name)),
+            None if self.trackers.len() == 1 => self.trackers.first(),
+            None => None,
+        };
+        tracker.map(|t| t.as_ref()).ok_or_else(|| {
+            ZeptoError::Tool(match wanted {
+                Some(name) => format!(
+                    "Tracker '{}' is not configured. Configured: {}",
+                    name,
+                    names.join(", ")
+                ),
+                None if names.is_empty() => {
+                    "No issue tracker configured (tools.issues.jira or tools.issues.linear)"
+                        .to_string()
+                }
+                None => format!("Pass 'tracker' ({})", names.join(" or ")),
+            })
+        })
+    }
+
+    async fn run(&self, action: &str, args: &Value) -> Result<String> {
+        let tracker = self.tracker(args)?;
+        match action {
+            "search" => {
+                let limit = args
+                    .get("limit")
+                    .and_then(Value::as_u64)
+                    .map_or(self.max_results, |n| {
+                        (n as usize).clamp(1, self.max_results)
+                    });
+                let query = IssueQuery {
+                    text: owned_arg(args, "query"),
+                    project: owned_arg(args, "project"),
+                    assignee: owned_arg(args, "assignee"),
+                    state: owned_arg(args, "state"),
+                    open_only: args.get("open_only").and_then(Value::as_bool) != Some(false),
+                    jql: owned_arg(args, "jql"),
+                    limit,
+                };
+                let issues = tracker.search(&query).await?;
+                if issues.is_empty() {
+                    return Ok("No issues found.".to_string());
+                }
+                let lines: Vec<String> = issues.iter().map(Issue::summary_line).collect();
+                Ok(lines.join("\n"))
+            }
+            "get" => Ok(tracker.get(required_arg(args, "key")?).await?.render()),
+            "create" => {
+                let draft = IssueDraft {
+                    project: owned_arg(args, "project"),
+                    title: required_arg(args, "title")?.to_string(),
+                    description: owned_arg(args, "description"),
+                    labels: labels_arg(args).unwrap_or_default(),
+                    assignee: owned_arg(args, "assignee"),
+                    priority: owned_arg(args, "priority"),
+                };
+                let issue = tracker.create(&draft).await?;
+                Ok(format!("Created {}", issue.render()))
+            }
+            "update" => {
+                let key = required_arg(args, "key")?;
+                let changes = IssueChanges {
+                    title: owned_arg(args, "title"),
+                    description: owned_arg(args, "description"),
+                    labels: labels_arg(args),
+                    assignee: owned_arg(args, "assignee"),
+                    priority: owned_arg(args, "priority"),
+                };
+                if changes.is_empty() {
+                    return Err(ZeptoError::Tool(
+                        "Nothing to update: pass title, description, labels, assignee or priority"
+                            .into(),
+                    ));
+                }
+                tracker.update(key, &changes).await?;
+                Ok(format!("Updated {}", key))
+            }
+            "transition" => {
+                let key = required_arg(args, "key")?;
+                match str_arg(args, "state") {
+                    Some(state) => {
+                        let reached = tracker.transition(key, state).await?;
+                        Ok(format!("{} moved to {}", key, reached))
+                    }
+                    None => {
+                        let states = tracker.states(key).await?;
+                        Ok(format!(
+                            "{} can move to: {}",
+                            key,
+                            if states.is_empty() {
+                                "(no transitions available)".to_string()
+                            } else {
+                                states.join(", ")
+                            }
+                        ))
+                    }
+                }
+            }
+            "sprint_summary" => {
+                let sprint = tracker.active_sprint(str_arg(args, "project")).await?;
+                Ok(summarize_sprint(&sprint, Local::now().date_naive()))
+            }
+            other => Err(ZeptoError::Tool(format!(
+                "Unknown action '{}'. Use search, get, create, update, transition or sprint_summary",
+                other
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for IssuesTool {
+    fn name(&self) -> &str {
+        "issues"
+    }
+
+    fn description(&self) -> &str {
+        "Work with Jira or Linear issues. Actions: 'search' (query text, project/team key, \
+         assignee ('me' for yourself), state, open_only; Jira also accepts raw 'jql'), 'get' \
+         (one issue by key), 'create', 'update', 'transition' (move to 'state'; omit it to \
+         list allowed states) and 'sprint_summary' (progress of the active sprint or cycle). \
+         Pass 'tracker' when both Jira and Linear are configured."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Jira/Linear issues and sprint summaries"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["search", "get", "create", "update", "transition", "sprint_summary"],
+                    "description": "What to do"
+                },
+                "tracker": {
+                    "type": "string",
+                    "enum": ["jira", "linear"],
+                    "description": "Backend; optional when only one is configured"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Issue key, e.g. PROJ-123 or ENG-45"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "search: text to match in title and description"
+                },
+                "jql": {
+                    "type": "string",
+                    "description": "search: raw JQL (Jira only)"
+                },
+                "project": {
+                    "type": "string",
+                    "description": "Jira project key or Linear team key (default from config)"
+                },
+                "assignee": {
+                    "type": "string",
+                    "description": "Assignee name or 'me'"
+                },
+                "state": {
+                    "type": "string",
+                    "description": "search: state filter; transition: target state"
+                },
+                "open_only": {
+                    "type": "boolean",
+                    "description": "search: skip done issues (default true)"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Issue title"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "Issue description"
+                },
+                "labels": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Label names"
+                },
+                "priority": {
+                    "type": "string",
+                    "description": "urgent, high, medium or low (Jira: any priority name)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "search: maximum issues (capped by config)",
+                    "minimum": 1
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = required_arg(&args, "action")?;
+        let output = self.run(action, &args).await?;
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(key: &str, category: StateCategory, assignee: Option<&str>, points: f64) -> Issue {
+        Issue {
+            key: key.to_string(),
+            title: format!("Task {}", key),
+            state: format!("{:?}", category),
+            category,
+            assignee: assignee.map(str::to_string),
+            points: Some(points),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summarize_sprint() {
+        let sprint = Sprint {
+            name: "Sprint 14".into(),
+            start: NaiveDate::from_ymd_opt(2026, 10, 6),
+            end: NaiveDate::from_ymd_opt(2026, 10, 20),
+            issues: vec![
+                issue("ENG-1", StateCategory::Done, Some("ana"), 3.0),
+                issue("ENG-2", StateCategory::InProgress, Some("ana"), 5.0),
+                issue("ENG-3", StateCategory::Todo, None, 2.0),
+                issue("ENG-4", StateCategory::Done, Some("bo"), 1.0),
+            ],
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let out = summarize_sprint(&sprint, today);
+        assert!(out.starts_with(
+            "Sprint \"Sprint 14\" (2026-10-06 → 2026-10-20, 2 day(s) left)\n\
+             Progress: 2/4 issues done (50%), 4/11 points\n\
+             To do 1 · In progress 1 · Done 2\n\
+             At risk: 2 issue(s) still open with 2 day(s) left\n"
+        ));
+        assert!(out.contains("- ana: 1/2 done, 1 in progress, 0 to do"));
+        assert!(out.contains("- unassigned: 0/1 done, 0 in progress, 1 to do"));
+        assert!(out.contains("In progress:\n- ENG-2 [InProgress] Task ENG-2 — @ana, 5 pts"));
+        assert!(out.contains("Not started:\n- ENG-3"));
+
+        let empty = summarize_sprint(&Sprint::default(), today);
+        assert!(empty.contains("Progress: 0/0 issues done (0%)"));
+    }
+
+    #[test]
+    fn test_match_name() {
+        let states = vec!["To Do", "In Progress", "In Review", "Done"];
+        assert_eq!(*match_name(&states, |s| *s, "done").unwrap(), "Done");
+        assert_eq!(*match_name(&states, |s| *s, "review").unwrap(), "In Review");
+        let err = match_name(&states, |s| *s, "in").unwrap_err();
+        assert!(err.to_string().contains("Available: To Do, In Progress"));
+    }
+
+    struct FakeTracker;
+
+    #[async_trait]
+    impl IssueTracker for FakeTracker {
+        fn name(&self) -> &'static str {
+            "linear"
+        }
+        async fn search(&self, query: &IssueQuery) -> Result<Vec<Issue>> {
+            assert!(query.open_only);
+            assert_eq!(query.limit, 5);
+            Ok(vec![issue("ENG-9", StateCategory::Todo, Some("ana"), 1.0)])
+        }
+        async fn get(&self, key: &str) -> Result<Issue> {
+            Ok(issue(key, StateCategory::Todo, None, 1.0))
+        }
+        async fn create(&self, draft: &IssueDraft) -> Result<Issue> {
+            Ok(Issue {
+                key: "ENG-10".into(),
+                title: draft.title.clone(),
+                state: "Backlog".into(),
+                ..Default::default()
+            })
+        }
+        async fn update(&self, _key: &str, _changes: &IssueChanges) -> Result<()> {
+            Ok(())
+        }
+        async fn states(&self, _key: &str) -> Result<Vec<String>> {
+            Ok(vec!["Todo".into(), "Done".into()])
+        }
+        async fn transition(&self, _key: &str, state: &str) -> Result<String> {
+            Ok(state.to_string())
+        }
+        async fn active_sprint(&self, _project: Option<&str>) -> Result<Sprint> {
+            Ok(Sprint::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_issues_tool_dispatch() {
+        let tool = IssuesTool::new(IssuesToolConfig {
+            max_results: 5,
+            ..Default::default()
+        });
+        let err = tool
+            .execute(json!({"action": "search"}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No issue tracker configured"));
+
+        let tool = tool.with_trackers(vec![Box::new(FakeTracker)]);
+        let ctx = ToolContext::new();
+        let out = tool
+            .execute(json!({"action": "search", "limit": 50}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "ENG-9 [Todo] Task ENG-9 — @ana, 1 pts");
+
+        let out = tool
+            .execute(json!({"action": "transition", "key": "ENG-9"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "ENG-9 can move to: Todo, Done");
+
+        let err = tool
+            .execute(json!({"action": "update", "key": "ENG-9"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Nothing to update"));
+
+        let err = tool
+            .execute(
+                json!({"action": "get", "key": "X-1", "tracker": "jira"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Configured: linear"));
+    }
+}
@@ -0,0 +1,1322 @@
+//! Kubernetes tools — read-only inspection and approval-gated changes.
+//!
+//! Both tools shell out to `kubectl` through the configured container
+//! runtime, so they use the runtime's kubeconfig (or `tools.kubernetes.kubeconfig`).
+//!
+//! - `kubectl` runs the verbs in `read_verbs` (get, describe, logs, top,
+//!   events, explain by default) plus `diagnose`, which gathers a pod's
+//!   status, condensed `describe` output and previous-container logs into one
+//!   report with likely causes ("why is pod X crashlooping").
+//! - `kubectl_write` runs the verbs in `write_verbs` (apply, delete, scale,
+//!   rollout by default). It is in the default approval list, so every change
+//!   is shown to the user first.
+//!
+//! Every call is scoped to one namespace: the requested one, or
+//! `default_namespace`. When `allowed_namespaces` is set, other namespaces
+//! and `all_namespaces` are refused. Flags that would change the cluster,
+//! credentials or namespace are rejected in free-form `args`.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::config::{expand_home, KubernetesToolConfig, NativeShell};
+use crate::error::{Result, ZeptoError};
+use crate::runtime::{CommandOutput, ContainerConfig, ContainerRuntime};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Flags callers may not pass in `args`: cluster, credentials and namespace
+/// selection are fixed by config.
+const BLOCKED_FLAGS: &[&str] = &[
+    "--kubeconfig",
+    "--context",
+    "--cluster",
+    "--server",
+    "--token",
+    "--user",
+    "--username",
+    "--password",
+    "--as",
+    "--as-group",
+    "--as-uid",
+    "--certificate-authority",
+    "--client-certificate",
+    "--client-key",
+    "--insecure-skip-tls-verify",
+    "--namespace",
+    "--all-namespaces",
+    "--filename",
+    "--kustomize",
+    "--all",
+    "--raw",
+];
+
+/// Short forms of [`BLOCKED_FLAGS`].
+const BLOCKED_SHORT_FLAGS: &[&str] = &["-s", "-n", "-A", "-f", "-k"];
+
+/// Verbs that take no namespace.
+const CLUSTER_VERBS: &[&str] = &["explain", "version", "api-resources", "api-versions"];
+
+/// Output formats accepted by `get`.
+const OUTPUT_FORMATS: &[&str] = &["wide", "yaml", "json", "name"];
+
+/// Log lines fetched by default.
+const DEFAULT_LOG_TAIL: u64 = 200;
+
+/// Log lines fetched by `diagnose`.
+const DIAGNOSE_LOG_TAIL: u64 = 50;
+
+/// Events kept in a condensed `describe`.
+const DESCRIBE_EVENT_LINES: usize = 15;
+
+/// Quote `value` as one word for `shell` (the runtime's, see
+/// [`ContainerRuntime::shell`]).
+fn shell_quote(shell: NativeShell, value: &str) -> Result<String> {
+    match shell {
+        NativeShell::Cmd => {
+            // cmd.exe has no escape inside double quotes and still expands
+            // `%VAR%` there, so such values cannot be passed safely.
+            if value.contains(['"', '%', '\n', '\r']) {
+                return Err(ZeptoError::Tool(format!(
+                    "kubectl argument {:?} cannot be passed through cmd.exe",
+                    value
+                )));
+            }
+            Ok(format!("\"{}\"", value))
+        }
+        // PowerShell also closes single-quoted strings on typographic
+        // quotes; any of them is escaped by doubling.
+        NativeShell::Powershell => {
+            let mut quoted = String::from("'");
+            for c in value.chars() {
+                if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+                    quoted.push(c);
+                }
+                quoted.push(c);
+            }
+            quoted.push('\'');
+            Ok(quoted)
+        }
+        _ => Ok(format!("'{}'", value.replace('\'', "'\\''"))),
+    }
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Check a resource type, name or container: no leading dash, no whitespace.
+fn check_identifier(kind: &str, value: &str) -> Result<()> {
+    let valid = !value.starts_with('-')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '/' | ':'));
+    if valid {
+        Ok(())
+    } else {
+        Err(ZeptoError::Tool(format!("Invalid {} '{}'", kind, value)))
+    }
+}
+
+/// Reject flags in free-form `args` that would escape the configured scope.
+pub fn check_extra_args(args: &[String]) -> Result<()> {
+    for arg in args {
+        let flag = arg.split('=').next().unwrap_or(arg);
+        let blocked = if flag.starts_with("--") {
+            BLOCKED_FLAGS.contains(&flag)
+        } else {
+            BLOCKED_SHORT_FLAGS
+                .iter()
+                .any(|short| flag.starts_with(short))
+        };
+        if blocked {
+            return Err(ZeptoError::SecurityViolation(format!(
+                "kubectl flag '{}' is not allowed; cluster, credentials and namespace come \
+                 from config and the namespace parameter",
+                flag
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Namespace flags for a call, enforcing `allowed_namespaces`.
+pub fn namespace_flags(config: &KubernetesToolConfig, args: &Value) -> Result<Vec<String>> {
+    if args.get("all_namespaces").and_then(Value::as_bool) == Some(true) {
+        if !config.allowed_namespaces.is_empty() {
+            return Err(ZeptoError::SecurityViolation(format!(
+                "all_namespaces is not allowed; allowed namespaces: {}",
+                config.allowed_namespaces.join(", ")
+            )));
+        }
+        return Ok(vec!["--all-namespaces".to_string()]);
+    }
+    let namespace = str_arg(args, "namespace").unwrap_or(&config.default_namespace);
+    check_identifier("namespace", namespace)?;
+    if !config.allowed_namespaces.is_empty()
+        && !config.allowed_namespaces.iter().any(|ns| ns == namespace)
+    {
+        return Err(ZeptoError::SecurityViolation(format!(
+            "Namespace '{}' is not allowed; allowed namespaces: {}",
+            namespace,
+            config.allowed_namespaces.join(", ")
+        )));
+    }
+    Ok(vec![format!("--namespace={}", namespace)])
+}
+
+fn extra_args(args: &Value) -> Result<Vec<String>> {
+    let extra: Vec<String> = match args.get("args") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect(),
+        Some(_) => {
+            return Err(ZeptoError::Tool(
+                "'args' must be an array of strings".into(),
+            ))
+        }
+    };
+    check_extra_args(&extra)?;
+    Ok(extra)
+}
+
+/// `resource` and `name` as kubectl positional arguments.
+fn target_args(args: &Value) -> Result<Vec<String>> {
+    let mut out = Vec::new();
+    if let Some(resource) = str_arg(args, "resource") {
+        check_identifier("resource", resource)?;
+        out.push(resource.to_string());
+    }
+    if let Some(name) = str_arg(args, "name") {
+        check_identifier("name", name)?;
+        out.push(name.to_string());
+    }
+    Ok(out)
+}
+
+fn selector_arg(args: &Value) -> Option<String> {
+    str_arg(args, "selector").map(|s| format!("--selector={}", s))
+}
+
+/// Arguments after `kubectl` for a read verb (everything but `diagnose`).
+pub fn read_argv(config: &KubernetesToolConfig, verb: &str, args: &Value) -> Result<Vec<String>> {
+    let mut argv = vec![verb.to_string()];
+    match verb {
+        "logs" => {
+            let name = str_arg(args, "name")
+                .ok_or_else(|| ZeptoError::Tool("logs needs 'name' (a pod)".into()))?;
+            check_identifier("name", name)?;
+            match str_arg(args, "resource") {
+                Some(resource) if !matches!(resource, "pod" | "pods" | "po") => {
+                    check_identifier("resource", resource)?;
+                    argv.push(format!("{}/{}", resource, name));
+                }
+                _ => argv.push(name.to_string()),
+            }
+            if let Some(container) = str_arg(args, "container") {
+                check_identifier("container", container)?;
+                argv.push(format!("--container={}", container));
+            }
+            let tail = args
+                .get("tail")
+                .and_then(Value::as_u64)
+                .unwrap_or(DEFAULT_LOG_TAIL);
+            argv.push(format!("--tail={}", tail));
+            if args.get("previous").and_then(Value::as_bool) == Some(true) {
+                argv.push("--previous".to_string());
+            }
+            if let Some(since) = str_arg(args, "since") {
+                check_identifier("since", since)?;
+                argv.push(format!("--since={}", since));
+            }
+        }
+        "events" => {
+            if let Some(name) = str_arg(args, "name") {
+                check_identifier("name", name)?;
+                let resource = str_arg(args, "resource").unwrap_or("pod");
+                check_identifier("resource", resource)?;
+                argv.push(format!("--for={}/{}", resource, name));
+            }
+        }
+        _ => {
+            let target = target_args(args)?;
+            if target.is_empty() && matches!(verb, "get" | "describe" | "explain") {
+                return Err(ZeptoError::Tool(format!(
+                    "{} needs 'resource' (e.g. pods, deployments)",
+                    verb
+                )));
+            }
+            argv.extend(target);
+            argv.extend(selector_arg(args));
+            if let Some(output) = str_arg(args, "output") {
+                if verb != "get" || !OUTPUT_FORMATS.contains(&output) {
+                    return Err(ZeptoError::Tool(format!(
+                        "'output' must be one of {} and only applies to get",
+                        OUTPUT_FORMATS.join(", ")
+                    )));
+                }
+                argv.push(format!("--output={}", output));
+            }
+        }
+    }
+    if !CLUSTER_VERBS.contains(&verb) {
+        argv.extend(namespace_flags(config, args)?);
+    }
+    argv.extend(extra_args(args)?);
+    if verb == "get" {
+        check_secret_output(&argv)?;
+    }
+    Ok(argv)
+}
+
+/// Whether a positional argument names Secrets (`secrets`, `secret/db`,
+/// `pods,secrets`, `secrets.v1`).
+fn names_secrets(arg: &str) -> bool {
+    arg.split(',').any(|target| {
+        let kind = target.split(['/', '.']).next().unwrap_or(target);
+        kind.eq_ignore_ascii_case("secret") || kind.eq_ignore_ascii_case("secrets")
+    })
+}
+
+/// Secret values are only base64-encoded, so `get` on Secrets is limited to
+/// output without `data`/`stringData`: the default table, `wide` and `name`.
+fn check_secret_output(argv: &[String]) -> Result<()> {
+    if !argv
+        .iter()
+        .any(|arg| !arg.starts_with('-') && names_secrets(arg))
+    {
+        return Ok(());
+    }
+    let mut args = argv.iter();
+    while let Some(arg) = args.next() {
+        let format = match arg.as_str() {
+            "-o" | "--output" => args.next().map_or("", String::as_str),
+            a if a.starts_with("--output=") => &a["--output=".len()..],
+            a if a.starts_with("-o") => a[2..].trim_start_matches('='),
+            a if a.starts_with("--template") => a,
+            _ => continue,
+        };
+        if !matches!(format, "wide" | "name") {
+            return Err(ZeptoError::SecurityViolation(
+                "Secrets can only be listed (default, wide or name output); \
+                 their values are not shown"
+                    .into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Arguments after `kubectl` for a write verb. Returns the manifest to feed
+/// on stdin, if any.
+pub fn write_argv(
+    config: &KubernetesToolConfig,
+    verb: &str,
+    args: &Value,
+) -> Result<(Vec<String>, Option<String>)> {
+    if args.get("all_namespaces").and_then(Value::as_bool) == Some(true) {
+        return Err(ZeptoError::Tool(
+            "all_namespaces is not supported for changes".into(),
+        ));
+    }
+    let manifest = str_arg(args, "manifest").map(str::to_string);
+    let mut argv = vec![verb.to_string()];
+    match verb {
+        "apply" if manifest.is_none() => {
+            return Err(ZeptoError::Tool("apply needs 'manifest' (YAML)".into()));
+        }
+        "rollout" => {
+            let subcommand = str_arg(args, "subcommand").ok_or_else(|| {
+                ZeptoError::Tool(
+                    "rollout needs 'subcommand' (restart, undo, pause, resume, status)".into(),
+                )
+            })?;
+            check_identifier("subcommand", subcommand)?;
+            argv.push(subcommand.to_string());
+        }
+        _ => {}
+    }
+    if manifest.is_some() {
+        argv.push("--filename=-".to_string());
+    } else {
+        let target = target_args(args)?;
+        let selector = selector_arg(args);
+        // Never act on a whole resource type at once.
+        if str_arg(args, "name").is_none() && selector.is_none() {
+            return Err(ZeptoError::Tool(format!(
+                "{} needs 'name', 'selector' or 'manifest'",
+                verb
+            )));
+        }
+        argv.extend(target);
+        argv.extend(selector);
+    }
+    if verb == "scale" {
+        let replicas = args
+            .get("replicas")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| ZeptoError::Tool("scale needs 'replicas'".into()))?;
+        argv.push(format!("--replicas={}", replicas));
+    }
+    if args.get("dry_run").and_then(Value::as_bool) == Some(true) {
+        argv.push("--dry-run=server".to_string());
+    }
+    argv.extend(namespace_flags(config, args)?);
+    argv.extend(extra_args(args)?);
+    Ok((argv, manifest))
+}
+
+/// Full shell command for `shell`: kubectl, context, arguments and an
+/// optional manifest on stdin (a heredoc, or a here-string piped in under
+/// PowerShell; cmd.exe cannot take one).
+pub fn build_command(
+    config: &KubernetesToolConfig,
+    argv: &[String],
+    stdin: Option<&str>,
+    shell: NativeShell,
+) -> Result<String> {
+    let mut parts = vec![shell_quote(shell, &config.kubectl_path)?];
+    if let Some(context) = config.context.as_deref().filter(|c| !c.is_empty()) {
+        parts.push(shell_quote(shell, &format!("--context={}", context))?);
+    }
+    for arg in argv {
+        parts.push(shell_quote(shell, arg)?);
+    }
+    let command = parts.join(" ");
+    match (shell, stdin) {
+        // A quoted program name needs the call operator.
+        (NativeShell::Powershell, None) => Ok(format!("& {}", command)),
+        (NativeShell::Powershell, Some(input)) => {
+            if input.lines().any(|line| line.starts_with("'@")) {
+                return Err(ZeptoError::Tool(
+                    "Manifest lines may not start with '@ under PowerShell".into(),
+                ));
+            }
+            Ok(format!("@'\n{}\n'@ | & {}", input, command))
+        }
+        (NativeShell::Cmd, Some(_)) => Err(ZeptoError::Tool(
+            "Manifests cannot be piped through cmd.exe; set runtime.native_shell to sh or powershell"
+                .into(),
+        )),
+        (_, None) => Ok(command),
+        (_, Some(input)) => {
+            let mut delimiter = "ZEPTOCLAW_MANIFEST".to_string();
+            while input.lines().any(|line| line == delimiter) {
+                delimiter.push('_');
+            }
+            Ok(format!("{} <<'{1}'\n{2}\n{1}", command, delimiter, input))
+        }
+    }
+}
+
+/// Trim `text` to about `max_chars`, keeping the start and (mostly) the end.
+pub fn clip(text: &str, max_chars: usize) -> String {
+    let total = text.chars().count();
+    if total <= max_chars {
+        return text.to_string();
+    }
+    let head_chars = max_chars / 4;
+    let tail_chars = max_chars - head_chars;
+    let head: String = text.chars().take(head_chars).collect();
+    let tail: String = text.chars().skip(total - tail_chars).collect();
+    let head = head.rfind('\n').map_or(head.as_str(), |i| &head[..i]);
+    let tail = tail.find('\n').map_or(tail.as_str(), |i| &tail[i + 1..]);
+    format!(
+        "{}\n... [{} chars trimmed] ...\n{}",
+        head,
+        total - head.chars().count() - tail.chars().count(),
+        tail
+    )
+}
+
+/// A pod row from `kubectl get pods` that needs attention.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PodIssue {
+    pub name: String,
+    pub status: String,
+    pub ready: String,
+    pub restarts: u64,
+}
+
+/// Unhealthy pods in a `kubectl get pods` table: not Running/Completed, not
+/// all containers ready, or restarted. `None` when `table` is not a pod list.
+pub fn pod_issues(table: &str) -> Option<(usize, Vec<PodIssue>)> {
+    let mut lines = table.lines();
+    let header = lines.next()?;
+    let column = |title: &str| {
+        header.match_indices(title).map(|(i, _)| i).find(|&i| {
+            header[i + title.len()..].starts_with(' ') || i + title.len() == header.len()
+        })
+    };
+    let name_col = column("NAME")?;
+    let ready_col = column("READY")?;
+    let status_col = column("STATUS")?;
+    let restarts_col = column("RESTARTS")?;
+    let cell = |line: &str, start: usize| -> String {
+        line.get(start..)
+            .and_then(|rest| rest.split_whitespace().next())
+            .unwrap_or("")
+            .to_string()
+    };
+
+    let mut total = 0;
+    let mut issues = Vec::new();
+    for line in lines.filter(|l| !l.trim().is_empty()) {
+        total += 1;
+        let ready = cell(line, ready_col);
+        let status = cell(line, status_col);
+        let restarts = cell(line, restarts_col).parse::<u64>().unwrap_or(0);
+        let not_ready = ready
+            .split_once('/')
+            .is_some_and(|(up, want)| up != want && status == "Running");
+        let bad_status = !matches!(status.as_str(), "Running" | "Completed" | "Succeeded");
+        if bad_status || not_ready || restarts > 0 {
+            issues.push(PodIssue {
+                name: cell(line, name_col),
+                status,
+                ready,
+                restarts,
+            });
+        }
+    }
+    Some((total, issues))
+}
+
+fn render_pod_issues(total: usize, issues: &[PodIssue]) -> String {
+    if issues.is_empty() {
+        return format!("All {} pod(s) running and ready.", total);
+    }
+    let mut out = format!("Attention ({} of {} pods):\n", issues.len(), total);
+    for issue in issues {
+        out.push_str(&format!(
+            "- {}: {}, ready {}, {} restart(s)\n",
+            issue.name, issue.status, issue.ready, issue.restarts
+        ));
+    }
+    out
+}
+
+/// Keep the parts of `kubectl describe pod` that explain failures: pod
+/// status, container states, exit codes, restart counts and recent events.
+pub fn condense_describe(text: &str) -> String {
+    const KEYS: &[&str] = &[
+        "Name:",
+        "Namespace:",
+        "Node:",
+        "Status:",
+        "Reason:",
+        "Message:",
+        "Image:",
+        "State:",
+        "Last State:",
+        "Exit Code:",
+        "Started:",
+        "Finished:",
+        "Ready:",
+        "Restart Count:",
+        "Limits:",
+        "memory:",
+        "cpu:",
+    ];
+    let mut out = Vec::new();
+    let mut lines = text.lines();
+    for line in lines.by_ref() {
+        if line.starts_with("Events:") {
+            break;
+        }
+        let trimmed = line.trim_start();
+        // Container names are two-space-indented headings ending in ':'.
+        let container_heading = line.starts_with("  ")
+            && !line.starts_with("   ")
+            && trimmed.ends_with(':')
+            && !trimmed.contains(' ');
+        if container_heading || KEYS.iter().any(|key| trimmed.starts_with(key)) {
+            out.push(line.to_string());
+        }
+    }
+    let events: Vec<&str> = lines.collect();
+    if !events.is_empty() {
+        out.push("Events (latest):".to_string());
+        let skip = events.len().saturating_sub(DESCRIBE_EVENT_LINES);
+        out.extend(events[skip..].iter().map(|l| l.to_string()));
+    }
+    out.join("\n")
+}
+
+/// Likely causes for the failure signals in a pod's describe output and logs.
+pub fn likely_causes(describe: &str, logs: &str) -> Vec<&'static str> {
+    const SIGNALS: &[(&str, &str)] = &[
+        (
+            "OOMKilled",
+            "Container ran out of memory (OOMKilled): raise resources.limits.memory or fix the leak.",
+        ),
+        (
+            "ImagePullBackOff",
+            "Image cannot be pulled: check the image name and tag, registry access and imagePullSecrets.",
+        ),
+        (
+            "ErrImagePull",
+            "Image cannot be pulled: check the image name and tag, registry access and imagePullSecrets.",
+        ),
+        (
+            "CreateContainerConfigError",
+            "Container config is invalid, usually a missing ConfigMap, Secret or key it references.",
+        ),
+        (
+            "FailedScheduling",
+            "Pod cannot be scheduled: not enough CPU/memory on nodes, or taints/affinity rules exclude them.",
+        ),
+        (
+            "FailedMount",
+            "A volume failed to mount: check the PVC, Secret or ConfigMap it refers to.",
+        ),
+        (
+            "probe failed",
+            "Liveness/readiness probe is failing: check the probe path, port and initialDelaySeconds.",
+        ),
+        (
+            "CrashLoopBackOff",
+            "Container starts and exits repeatedly; the previous container's logs show why it exited.",
+        ),
+    ];
+    let mut causes: Vec<&'static str> = Vec::new();
+    for (signal, cause) in SIGNALS {
+        if describe.contains(signal) && !causes.contains(cause) {
+            causes.push(cause);
+        }
+    }
+    let sigkilled = describe
+        .lines()
+        .any(|l| l.trim_start().starts_with("Exit Code:") && l.trim_end().ends_with(" 137"));
+    if sigkilled && !describe.contains("OOMKilled") {
+        causes.push(
+            "Container was SIGKILLed (exit 137), usually the memory limit or a failed liveness probe.",
+        );
+    }
+    let log_errors = logs.lines().filter(|l| is_error_line(l)).count();
+    if log_errors > 0 {
+        causes.push("Application logged errors before exiting; see the log excerpt.");
+    }
+    causes
+}
+
+fn is_error_line(line: &str) -> bool {
+    let lower = line.to_ascii_lowercase();
+    ["error", "panic", "fatal", "exception", "traceback"]
+        .iter()
+        .any(|word| lower.contains(word))
+}
+
+fn summarize_logs(logs: &str) -> String {
+    let total = logs.lines().count();
+    let errors = logs.lines().filter(|l| is_error_line(l)).count();
+    format!("[{} line(s), {} error-like]\n{}", total, errors, logs)
+}
+
+// ---------------------------------------------------------------------------
+// kubectl (reads)
+// ---------------------------------------------------------------------------
+
+/// Shared config and runtime for both tools.
+struct Kubectl {
+    config: KubernetesToolConfig,
+    runtime: Arc<dyn ContainerRuntime>,
+}
+
+impl Kubectl {
+    async fn run(&self, argv: &[String], stdin: Option<&str>) -> Result<CommandOutput> {
+        let command = build_command(&self.config, argv, stdin, self.runtime.shell())?;
+        let mut container = ContainerConfig::new().with_timeout(self.config.timeout_secs);
+        if let Some(kubeconfig) = self.config.kubeconfig.as_deref() {
+            let path = expand_home(kubeconfig);
+            container = container
+                .with_mount(path.clone(), path.clone(), true)
+                .with_env("KUBECONFIG", &path.to_string_lossy());
+        }
+        self.runtime
+            .execute(&command, &container)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("kubectl failed to run: {}", e)))
+    }
+
+    /// Run and return stdout, or the error kubectl reported.
+    async fn run_ok(&self, argv: &[String], stdin: Option<&str>) -> Result<String> {
+        let output = self.run(argv, stdin).await?;
+        if output.success() {
+            Ok(output.stdout)
+        } else {
+            let message = if output.stderr.trim().is_empty() {
+                output.stdout.trim()
+            } else {
+                output.stderr.trim()
+            };
+            Err(ZeptoError::Tool(format!(
+                "kubectl {} failed: {}",
+                argv.first().map(String::as_str).unwrap_or(""),
+                message
+            )))
+        }
+    }
+
+    fn verb<'a>(&self, args: &'a Value, allowed: &[String]) -> Result<&'a str> {
+        let verb =
+            str_arg(args, "verb").ok_or_else(|| ZeptoError::Tool("Missing 'verb'".into()))?;
+        if allowed.iter().any(|v| v == verb) {
+            Ok(verb)
+        } else {
+            Err(ZeptoError::Tool(format!(
+                "Verb '{}' is not allowed here; allowed: {}",
+                verb,
+                allowed.join(", ")
+            )))
+        }
+    }
+}
+
+/// Read-only kubectl: get, describe, logs and the other `read_verbs`, plus
+/// `diagnose` for a single pod.
+pub struct KubectlTool {
+    kubectl: Arc<Kubectl>,
+}
+
+impl KubectlTool {
+    pub fn new(config: KubernetesToolConfig, runtime: Arc<dyn ContainerRuntime>) -> Self {
+        Self {
+            kubectl: Arc::new(Kubectl { config, runtime }),
+        }
+    }
+
+    /// The `kubectl_write` tool sharing this config, or `None` when no write
+    /// verbs are configured.
+    pub fn write_tool(&self) -> Option<KubectlWriteTool> {
+        if self.kubectl.config.write_verbs.is_empty() {
+            return None;
+        }
+        Some(KubectlWriteTool {
+            kubectl: Arc::clone(&self.kubectl),
+        })
+    }
+
+    async fn diagnose(&self, args: &Value) -> Result<String> {
+        let config = &self.kubectl.config;
+        for needed in ["get", "describe", "logs"] {
+            if !config.read_verbs.iter().any(|v| v == needed) {
+                return Err(ZeptoError::Tool(format!(
+                    "diagnose needs '{}' in tools.kubernetes.read_verbs",
+                    needed
+                )));
+            }
+        }
+        let pod = str_arg(args, "name")
+            .ok_or_else(|| ZeptoError::Tool("diagnose needs 'name' (a pod)".into()))?;
+        check_identifier("name", pod)?;
+        let scope = namespace_flags(config, args)?;
+        let with_scope = |mut argv: Vec<String>| {
+            argv.extend(scope.iter().cloned());
+            argv
+        };
+        let pod = pod.to_string();
+
+        let status = self
+            .kubectl
+            .run_ok(
+                &with_scope(vec![
+                    "get".into(),
+                    "pod".into(),
+                    pod.clone(),
+                    "--output=wide".into(),
+                ]),
+                None,
+            )
+            .await?;
+        let describe = self
+            .kubectl
+            .run_ok(
+                &with_scope(vec!["describe".into(), "pod".into(), pod.clone()]),
+                None,
+            )
+            .await?;
+        let tail = format!("--tail={}", DIAGNOSE_LOG_TAIL);
+        let mut logs_label = "previous container";
+        let mut logs = self
+            .kubectl
+            .run_ok(
+                &with_scope(vec![
+                    "logs".into(),
+                    pod.clone(),
+                    "--previous".into(),
+                    tail.clone(),
+                ]),
+                None,
+            )
+            .await;
+        if logs.is_err() {
+            // No terminated container yet: fall back to the running one.
+            logs_label = "current container";
+            logs = self
+                .kubectl
+                .run_ok(&with_scope(vec!["logs".into(), pod.clone(), tail]), None)
+                .await;
+        }
+        let logs = logs.unwrap_or_else(|e| format!("(no logs: {})", e));
+
+        let mut report = format!("Pod {}\n\n## Status\n{}\n", pod, status.trim_end());
+        let causes = likely_causes(&describe, &logs);
+        if !causes.is_empty() {
+            report.push_str("\n## Likely causes\n");
+            for cause in causes {
+                report.push_str(&format!("- {}\n", cause));
+            }
+        }
+        report.push_str(&format!(
+            "\n## Describe (condensed)\n{}\n",
+            condense_describe(&describe)
+        ));
+        report.push_str(&format!(
+            "\n## Logs ({}, last {} lines)\n{}",
+            logs_label,
+            DIAGNOSE_LOG_TAIL,
+            summarize_logs(logs.trim_end())
+        ));
+        Ok(report)
+    }
+}
+
+#[async_trait]
+impl Tool for KubectlTool {
+    fn name(&self) -> &str {
+        "kubectl"
+    }
+
+    fn description(&self) -> &str {
+        "Inspect a Kubernetes cluster read-only. Verbs: get, describe, logs, top, events, \
+         explain, and 'diagnose' (a pod's status, condensed describe, previous-container logs \
+         and likely causes — use it for crashlooping or pending pods). Calls are scoped to \
+         one namespace unless all_namespaces is allowed. 'get pods' adds a list of pods that \
+         need attention. Use kubectl_write for changes."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Inspect Kubernetes (get/describe/logs/diagnose)"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkRead
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "verb": {
+                    "type": "string",
+                    "description": "get, describe, logs, top, events, explain or diagnose"
+                },
+                "resource": {
+                    "type": "string",
+                    "description": "Resource type, e.g. pods, deployments, nodes"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Resource name (required for logs and diagnose)"
+                },
+                "namespace": {
+                    "type": "string",
+                    "description": "Namespace (default from config)"
+                },
+                "all_namespaces": {
+                    "type": "boolean",
+                    "description": "Query every namespace, when config allows it"
+                },
+                "selector": {
+                    "type": "string",
+                    "description": "Label selector, e.g. app=web"
+                },
+                "output": {
+                    "type": "string",
+                    "enum": OUTPUT_FORMATS,
+                    "description": "Output format for get (Secrets: wide or name only)"
+                },
+                "container": {
+                    "type": "string",
+                    "description": "Container name for logs"
+                },
+                "tail": {
+                    "type": "integer",
+                    "description": "Log lines to fetch (default 200)",
+                    "minimum": 1
+                },
+                "previous": {
+                    "type": "boolean",
+                    "description": "Logs of the previous (crashed) container"
+                },
+                "since": {
+                    "type": "string",
+                    "description": "Only logs newer than this, e.g. 10m, 1h"
+                },
+                "args": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Extra kubectl flags, e.g. [\"--sort-by=.status.startTime\"]"
+                }
+            },
+            "required": ["verb"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let config = &self.kubectl.config;
+        let max_chars = config.max_output_chars;
+        if str_arg(&args, "verb") == Some("diagnose") {
+            let report = self.diagnose(&args).await?;
+            return Ok(ToolOutput::llm_only(clip(&report, max_chars)));
+        }
+
+        let verb = self.kubectl.verb(&args, &config.read_verbs)?;
+        let argv = read_argv(config, verb, &args)?;
+        let stdout = self.kubectl.run_ok(&argv, None).await?;
+        if stdout.trim().is_empty() {
+            return Ok(ToolOutput::llm_only("No resources found."));
+        }
+
+        let is_pod_table = verb == "get"
+            && str_arg(&args, "output").is_none_or(|o| o == "wide")
+            && matches!(str_arg(&args, "resource"), Some("pods" | "pod" | "po"));
+        let output = match (verb, is_pod_table) {
+            ("get", true) => match pod_issues(&stdout) {
+                Some((total, issues)) => format!(
+                    "{}\n{}",
+                    render_pod_issues(total, &issues),
+                    clip(&stdout, max_chars)
+                ),
+                None => clip(&stdout, max_chars),
+            },
+            ("logs", _) => clip(&summarize_logs(stdout.trim_end()), max_chars),
+            _ => clip(&stdout, max_chars),
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// kubectl_write (changes)
+// ---------------------------------------------------------------------------
+
+/// Cluster changes through the `write_verbs`. Created through
+/// [`KubectlTool::write_tool`].
+pub struct KubectlWriteTool {
+    kubectl: Arc<Kubectl>,
+}
+
+#[async_trait]
+impl Tool for KubectlWriteTool {
+    fn name(&self) -> &str {
+        "kubectl_write"
+    }
+
+    fn description(&self) -> &str {
+        "Change a Kubernetes cluster: apply a YAML 'manifest', delete, scale or rollout \
+         (restart, undo, status) a named resource. Scoped to one namespace. Set dry_run to \
+         validate against the server without changing anything. Use kubectl for reads."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Change Kubernetes resources (apply/delete/scale/rollout)"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "verb": {
+                    "type": "string",
+                    "description": "apply, delete, scale or rollout"
+                },
+                "resource": {
+                    "type": "string",
+                    "description": "Resource type, e.g. deployment"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Resource name"
+                },
+                "namespace": {
+                    "type": "string",
+                    "description": "Namespace (default from config)"
+                },
+                "selector": {
+                    "type": "string",
+                    "description": "Label selector instead of a name"
+                },
+                "manifest": {
+                    "type": "string",
+                    "description": "YAML manifest for apply (or delete)"
+                },
+                "replicas": {
+                    "type": "integer",
+                    "description": "Replica count for scale",
+                    "minimum": 0
+                },
+                "subcommand": {
+                    "type": "string",
+                    "description": "rollout subcommand: restart, undo, pause, resume, status"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Server-side dry run; nothing is changed"
+                },
+                "args": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Extra kubectl flags"
+                }
+            },
+            "required": ["verb"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let config = &self.kubectl.config;
+        let verb = self.kubectl.verb(&args, &config.write_verbs)?;
+        let (argv, manifest) = write_argv(config, verb, &args)?;
+        let stdout = self.kubectl.run_ok(&argv, manifest.as_deref()).await?;
+        let output = if stdout.trim().is_empty() {
+            format!("kubectl {} completed.", verb)
+        } else {
+            clip(stdout.trim_end(), config.max_output_chars)
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::NativeRuntime;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    /// A fake kubectl that prints its arguments, one per line.
+    fn fake_kubectl(dir: &std::path::Path, script: &str) -> KubernetesToolConfig {
+        let path = dir.join("kubectl");
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        KubernetesToolConfig {
+            enabled: true,
+            kubectl_path: path.to_string_lossy().into_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_read_argv_scopes_namespace_and_blocks_flags() {
+        let mut config = KubernetesToolConfig::default();
+        let argv = read_argv(
+            &config,
+            "get",
+            &json!({"resource": "pods", "selector": "app=web", "output": "wide"}),
+        )
+        .unwrap();
+        assert_eq!(
+            argv,
+            strings(&[
+                "get",
+                "pods",
+                "--selector=app=web",
+                "--output=wide",
+                "--namespace=default"
+            ])
+        );
+
+        let argv = read_argv(
+            &config,
+            "logs",
+            &json!({"name": "web-1", "previous": true, "tail": 20, "namespace": "prod"}),
+        )
+        .unwrap();
+        assert_eq!(
+            argv,
+            strings(&[
+                "logs",
+                "web-1",
+                "--tail=20",
+                "--previous",
+                "--namespace=prod"
+            ])
+        );
+
+        for args in [
+            json!({"resource": "pods", "args": ["--context=other"]}),
+            json!({"resource": "pods", "args": ["-nkube-system"]}),
+            json!({"resource": "pods", "args": ["--token", "x"]}),
+        ] {
+            let err = read_argv(&config, "get", &args).unwrap_err();
+            assert!(matches!(err, ZeptoError::SecurityViolation(_)), "{args}");
+        }
+        assert!(read_argv(&config, "get", &json!({"resource": "--raw"})).is_err());
+
+        config.allowed_namespaces = strings(&["default", "staging"]);
+        assert!(read_argv(
+            &config,
+            "get",
+            &json!({"resource": "pods", "namespace": "staging"})
+        )
+        .is_ok());
+        assert!(read_argv(
+            &config,
+            "get",
+            &json!({"resource": "pods", "namespace": "prod"})
+        )
+        .is_err());
+        assert!(read_argv(
+            &config,
+            "get",
+            &json!({"resource": "pods", "all_namespaces": true})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_read_argv_never_prints_secret_values() {
+        let config = KubernetesToolConfig::default();
+        for args in [
+            json!({"resource": "secrets"}),
+            json!({"resource": "secret", "name": "db", "output": "name"}),
+            json!({"resource": "pods", "output": "yaml"}),
+        ] {
+            assert!(read_argv(&config, "get", &args).is_ok(), "{}", args);
+        }
+        for args in [
+            json!({"resource": "secrets", "output": "yaml"}),
+            json!({"resource": "secret", "name": "db", "output": "json"}),
+            json!({"name": "secret/db", "output": "yaml"}),
+            json!({"resource": "Secrets.v1", "args": ["-ojson"]}),
+            json!({"resource": "secrets", "args": ["-o", "jsonpath={.items[*].data}"]}),
+            json!({"resource": "secrets", "args": ["--output=go-template"]}),
+            json!({"resource": "pods", "args": ["secrets,pods", "-oyaml"]}),
+        ] {
+            let err = read_argv(&config, "get", &args).unwrap_err();
+            assert!(
+                matches!(err, ZeptoError::SecurityViolation(_)),
+                "{}: {}",
+                args,
+                err
+            );
+        }
+        // describe only shows value sizes.
+        assert!(read_argv(&config, "describe", &json!({"resource": "secret"})).is_ok());
+    }
+
+    #[test]
+    fn test_write_argv_requires_target_and_uses_manifest() {
+        let config = KubernetesToolConfig::default();
+        let err = write_argv(&config, "delete", &json!({"resource": "pods"})).unwrap_err();
+        assert!(err.to_string().contains("needs 'name'"));
+
+        let (argv, manifest) = write_argv(
+            &config,
+            "apply",
+            &json!({"manifest": "kind: ConfigMap", "dry_run": true}),
+        )
+        .unwrap();
+        assert_eq!(
+            argv,
+            strings(&[
+                "apply",
+                "--filename=-",
+                "--dry-run=server",
+                "--namespace=default"
+            ])
+        );
+        assert_eq!(manifest.as_deref(), Some("kind: ConfigMap"));
+
+        let (argv, _) = write_argv(
+            &config,
+            "scale",
+            &json!({"resource": "deployment", "name": "web", "replicas": 3}),
+        )
+        .unwrap();
+        assert_eq!(
+            argv,
+            strings(&[
+                "scale",
+                "deployment",
+                "web",
+                "--replicas=3",
+                "--namespace=default"
+            ])
+        );
+
+        let command = build_command(
+            &KubernetesToolConfig {
+                context: Some("prod".into()),
+                ..config
+            },
+            &argv,
+            Some("a: 'b'\nZEPTOCLAW_MANIFEST"),
+            NativeShell::Sh,
+        )
+        .unwrap();
+        assert!(command.starts_with("'kubectl' '--context=prod' 'scale'"));
+        assert!(command
+            .ends_with("<<'ZEPTOCLAW_MANIFEST_'\na: 'b'\nZEPTOCLAW_MANIFEST\nZEPTOCLAW_MANIFEST_"));
+    }
+
+    #[test]
+    fn test_build_command_quotes_for_the_runtime_shell() {
+        let config = KubernetesToolConfig::default();
+        let argv = strings(&["get", "pods", "-l", "app='web'"]);
+
+        let command = build_command(&config, &argv, None, NativeShell::Powershell).unwrap();
+        assert_eq!(command, "& 'kubectl' 'get' 'pods' '-l' 'app=''web'''");
+        let command = build_command(&config, &argv, Some("a: b"), NativeShell::Powershell).unwrap();
+        assert!(command.starts_with("@'\na: b\n'@ | & 'kubectl'"));
+        assert!(build_command(&config, &argv, Some("'@"), NativeShell::Powershell).is_err());
+
+        let command = build_command(&config, &argv, None, NativeShell::Cmd).unwrap();
+        assert_eq!(command, "\"kubectl\" \"get\" \"pods\" \"-l\" \"app='web'\"");
+        let argv = strings(&["get", "pods", "-l", "app=%PATH%"]);
+        assert!(build_command(&config, &argv, None, NativeShell::Cmd).is_err());
+        assert!(build_command(&config, &argv[..2], Some("a: b"), NativeShell::Cmd).is_err());
+    }
+
+    #[test]
+    fn test_pod_issues_and_condensed_describe() {
+        let table = "\
+NAME                   READY   STATUS             RESTARTS      AGE
+web-7d9f-abcde         1/1     Running            0             2d
+web-7d9f-fghij         0/1     CrashLoopBackOff   7 (2m ago)    15m
+worker-1               1/2     Running            0             1h
+job-xyz                0/1     Completed          0             3h
+";
+        let (total, issues) = pod_issues(table).unwrap();
+        assert_eq!(total, 4);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].name, "web-7d9f-fghij");
+        assert_eq!(issues[0].status, "CrashLoopBackOff");
+        assert_eq!(issues[0].restarts, 7);
+        assert_eq!(issues[1].ready, "1/2");
+        assert!(pod_issues("NAME  AGE\nfoo   1d\n").is_none());
+
+        let describe = "\
+Name:         web-7d9f-fghij
+Namespace:    default
+Priority:     0
+Containers:
+  web:
+    Image:          example/web:1.2
+    Port:           8080/TCP
+    State:          Waiting
+      Reason:       CrashLoopBackOff
+    Last State:     Terminated
+      Reason:       OOMKilled
+      Exit Code:    137
+    Restart Count:  7
+    Environment:    <none>
+Events:
+  Type     Reason   Age  From     Message
+  Warning  BackOff  1m   kubelet  Back-off restarting failed container
+";
+        let condensed = condense_describe(describe);
+        assert!(condensed.contains("  web:"));
+        assert!(condensed.contains("OOMKilled"));
+        assert!(condensed.contains("Back-off restarting"));
+        assert!(!condensed.contains("Priority"));
+        assert!(!condensed.contains("Environment"));
+
+        let causes = likely_causes(describe, "panic: out of memory");
+        assert!(causes[0].contains("OOMKilled"));
+        assert!(causes.iter().any(|c| c.contains("logged errors")));
+    }
+
+    #[test]
+    fn test_clip_keeps_head_and_tail() {
+        let text: String = (0..200).map(|i| format!("line {}\n", i)).collect();
+        let clipped = clip(&text, 400);
+        assert!(clipped.starts_with("line 0\n"));
+        assert!(clipped.contains("chars trimmed"));
+        assert!(clipped.trim_end().ends_with("line 199"));
+        assert_eq!(clip("short", 400), "short");
+    }
+
+    #[tokio::test]
+    async fn test_kubectl_tool_runs_allowed_verbs_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = fake_kubectl(dir.path(), r#"for a in "$@"; do echo "$a"; done"#);
+        let tool = KubectlTool::new(config, Arc::new(NativeRuntime::new()));
+        let ctx = ToolContext::new();
+
+        let out = tool
+            .execute(
+                json!({"verb": "describe", "resource": "pod", "name": "web"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "describe\npod\nweb\n--namespace=default\n");
+
+        let err = tool
+            .execute(
+                json!({"verb": "delete", "resource": "pod", "name": "web"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not allowed"));
+
+        let write = tool.write_tool().unwrap();
+        let out = write
+            .execute(
+                json!({"verb": "rollout", "subcommand": "restart", "resource": "deployment", "name": "web"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(out.for_llm.starts_with("rollout\nrestart\ndeployment\nweb"));
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_falls_back_to_current_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = fake_kubectl(
+            dir.path(),
+            r#"case "$1" in
+  get) echo "NAME READY STATUS"; echo "web 0/1 CrashLoopBackOff" ;;
+  describe) printf 'Name: web\n    State: Waiting\n      Reason: CrashLoopBackOff\nEvents:\n  Warning BackOff\n' ;;
+  logs) case "$*" in *--previous*) echo "no previous" >&2; exit 1 ;; *) echo "Error: db unreachable" ;; esac ;;
+esac"#,
+        );
+        let tool = KubectlTool::new(config, Arc::new(NativeRuntime::new()));
+        let out = tool
+            .execute(
+                json!({"verb": "diagnose", "name": "web"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("## Likely causes"));
+        assert!(out.for_llm.contains("starts and exits repeatedly"));
+        assert!(out.for_llm.contains("Logs (current container"));
+        assert!(out.for_llm.contains("1 error-like"));
+    }
+}
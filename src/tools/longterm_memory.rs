@@ -12,6 +12,7 @@ use serde_json::{json, Value};
 
 use crate::error::{Result, ZeptoError};
 use crate::memory::longterm::LongTermMemory;
+use crate::memory::namespace::MemoryScope;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
@@ -90,26 +91,31 @@ impl Tool for LongTermMemoryTool {
                 "query": {
                     "type": "string",
                     "description": "Search query (searches across key, value, category, and tags)"
+                },
+                "shared": {
+                    "type": "boolean",
+                    "description": "For set/pin: store for everyone using this assistant instead of only the current person (default false)"
                 }
             },
             "required": ["action"]
         })
     }
 
-    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
         let action = args
             .get("action")
             .and_then(|v| v.as_str())
             .ok_or_else(|| ZeptoError::Tool("Missing 'action' parameter".to_string()))?;
 
+        let scope = ctx.memory_scope();
         let s = match action {
-            "set" => self.execute_set(&args).await?,
-            "get" => self.execute_get(&args).await?,
-            "search" => self.execute_search(&args).await?,
-            "delete" => self.execute_delete(&args).await?,
-            "list" => self.execute_list(&args).await?,
+            "set" => self.execute_set(&args, &scope).await?,
+            "get" => self.execute_get(&args, &scope).await?,
+            "search" => self.execute_search(&args, &scope).await?,
+            "delete" => self.execute_delete(&args, &scope).await?,
+            "list" => self.execute_list(&args, &scope).await?,
             "categories" => self.execute_categories().await?,
-            "pin" => self.execute_pin(&args).await?,
+            "pin" => self.execute_pin(&args, &scope).await?,
             other => return Err(ZeptoError::Tool(format!(
                 "Unknown longterm_memory action '{}'. Valid actions: set, get, search, delete, list, categories, pin",
                 other
@@ -119,8 +125,14 @@ impl Tool for LongTermMemoryTool {
     }
 }
 
+fn shared_arg(args: &Value) -> bool {
+    args.get("shared")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
 impl LongTermMemoryTool {
-    async fn execute_set(&self, args: &Value) -> Result<String> {
+    async fn execute_set(&self, args: &Value, scope: &MemoryScope) -> Result<String> {
         let key = args
             .get("key")
             .and_then(|v| v.as_str())
@@ -167,11 +179,14 @@ impl LongTermMemoryTool {
             .map(|f| f as f32)
             .unwrap_or(1.0);
 
+        let stored_key = scope.storage_key(key, shared_arg(args))?;
         let mut memory = self.memory.lock().await;
 
         // Check if this is an update or a new entry.
-        let is_update = memory.get_readonly(key).is_some();
-        memory.set(key, value, category, tags, importance).await?;
+        let is_update = memory.get_readonly(&stored_key).is_some();
+        memory
+            .set(&stored_key, value, category, tags, importance)
+            .await?;
 
         if is_update {
             Ok(format!("Updated memory '{}'", key))
@@ -183,7 +198,7 @@ impl LongTermMemoryTool {
         }
     }
 
-    async fn execute_get(&self, args: &Value) -> Result<String> {
+    async fn execute_get(&self, args: &Value, scope: &MemoryScope) -> Result<String> {
         let key = args
             .get("key")
             .and_then(|v| v.as_str())
@@ -194,8 +209,13 @@ impl LongTermMemoryTool {
             })?;
 
         let mut memory = self.memory.lock().await;
+        let stored_key = scope
+            .lookup_keys(key)?
+            .into_iter()
+            .find(|k| memory.get_readonly(k).is_some())
+            .unwrap_or_default();
 
-        match memory.get(key) {
+        match memory.get(&stored_key) {
             Some(entry) => {
                 let entry_json = serde_json::to_string_pretty(entry).map_err(|e| {
                     ZeptoError::Tool(format!("Failed to serialize memory entry: {}", e))
@@ -206,7 +226,7 @@ impl LongTermMemoryTool {
         }
     }
 
-    async fn execute_search(&self, args: &Value) -> Result<String> {
+    async fn execute_search(&self, args: &Value, scope: &MemoryScope) -> Result<String> {
         let query = args
             .get("query")
             .and_then(|v| v.as_str())
@@ -218,13 +238,16 @@ impl LongTermMemoryTool {
 
         let memory = self.memory.lock().await;
 
-        let results = memory.search(query);
+        let entries: Vec<&crate::memory::longterm::MemoryEntry> = memory
+            .search(query)
+            .into_iter()
+            .filter(|e| scope.can_see(&e.key))
+            .collect();
 
-        if results.is_empty() {
+        if entries.is_empty() {
             return Ok(format!("No memories found matching '{}'", query));
         }
 
-        let entries: Vec<&crate::memory::longterm::MemoryEntry> = results;
         let json = serde_json::to_string_pretty(&entries)
             .map_err(|e| ZeptoError::Tool(format!("Failed to serialize search results: {}", e)))?;
 
@@ -236,7 +259,7 @@ impl LongTermMemoryTool {
         ))
     }
 
-    async fn execute_delete(&self, args: &Value) -> Result<String> {
+    async fn execute_delete(&self, args: &Value, scope: &MemoryScope) -> Result<String> {
         let key = args
             .get("key")
             .and_then(|v| v.as_str())
@@ -247,15 +270,20 @@ impl LongTermMemoryTool {
             })?;
 
         let mut memory = self.memory.lock().await;
+        let stored_key = scope
+            .lookup_keys(key)?
+            .into_iter()
+            .find(|k| memory.get_readonly(k).is_some())
+            .unwrap_or_default();
 
-        if memory.delete(key).await? {
+        if memory.delete(&stored_key).await? {
             Ok(format!("Deleted memory '{}'", key))
         } else {
             Ok(format!("No memory found for key '{}'", key))
         }
     }
 
-    async fn execute_list(&self, args: &Value) -> Result<String> {
+    async fn execute_list(&self, args: &Value, scope: &MemoryScope) -> Result<String> {
         let category = args
             .get("category")
             .and_then(|v| v.as_str())
@@ -268,7 +296,10 @@ impl LongTermMemoryTool {
             memory.list_by_category(cat)
         } else {
             memory.list_all()
-        };
+        }
+        .into_iter()
+        .filter(|e| scope.can_see(&e.key))
+        .collect();
 
         if results.is_empty() {
             return if let Some(cat) = category {
@@ -315,7 +346,7 @@ impl LongTermMemoryTool {
         Ok(format!("{}\nCategories:\n{}", summary, json))
     }
 
-    async fn execute_pin(&self, args: &Value) -> Result<String> {
+    async fn execute_pin(&self, args: &Value, scope: &MemoryScope) -> Result<String> {
         let key = args
             .get("key")
             .and_then(|v| v.as_str())
@@ -346,9 +377,10 @@ impl LongTermMemoryTool {
             })
             .unwrap_or_default();
 
+        let stored_key = scope.storage_key(key, shared_arg(args))?;
         let mut memory = self.memory.lock().await;
 
-        memory.set(key, value, "pinned", tags, 1.0).await?;
+        memory.set(&stored_key, value, "pinned", tags, 1.0).await?;
         Ok(format!("Pinned memory '{}'", key))
     }
 }
@@ -932,4 +964,50 @@ mod tests {
         assert!(result.contains("Very important information"));
         assert!(result.contains("critical"));
     }
+
+    #[tokio::test]
+    async fn test_namespaced_contacts_do_not_see_each_other() {
+        let (tool, _dir) = temp_tool();
+        let anna = ToolContext::new().with_memory_namespace("anna");
+        let bob = ToolContext::new().with_memory_namespace("bob");
+
+        for (ctx, name) in [(&anna, "Anna"), (&bob, "Bob")] {
+            tool.execute(
+                json!({"action": "set", "key": "user:name", "value": name, "category": "user"}),
+                ctx,
+            )
+            .await
+            .unwrap();
+        }
+        tool.execute(
+            json!({"action": "set", "key": "home:wifi", "value": "zepto-net", "category": "fact", "shared": true}),
+            &anna,
+        )
+        .await
+        .unwrap();
+
+        let got = tool
+            .execute(json!({"action": "get", "key": "user:name"}), &anna)
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(got.contains("Anna") && !got.contains("Bob"));
+        let listed = tool
+            .execute(json!({"action": "list"}), &bob)
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(listed.contains("Bob") && listed.contains("zepto-net"));
+        assert!(!listed.contains("Anna"));
+        let shared = tool
+            .execute(json!({"action": "search", "query": "Anna"}), &ctx())
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(shared.contains("No memories found"));
+        assert!(tool
+            .execute(json!({"action": "get", "key": "@anna/user:name"}), &bob)
+            .await
+            .is_err());
+    }
 }
@@ -16,7 +16,7 @@ use crate::memory::builtin_searcher::BuiltinSearcher;
 use crate::memory::traits::MemorySearcher;
 use crate::memory::{read_workspace_memory, search_workspace_memory};
 
-use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+use super::{Citation, Tool, ToolCategory, ToolContext, ToolOutput};
 
 /// Tool for searching workspace memory files.
 pub struct MemorySearchTool {
@@ -152,7 +152,12 @@ impl Tool for MemorySearchTool {
             ));
         }
 
-        Ok(ToolOutput::llm_only(output.trim_end().to_string()))
+        let citations = results
+            .iter()
+            .filter_map(|item| item.citation.clone())
+            .map(Citation::memory)
+            .collect();
+        Ok(ToolOutput::llm_only(output.trim_end().to_string()).with_citations(citations))
     }
 }
 
@@ -0,0 +1,597 @@
+//! Memory write tools — `memory_set` and `memory_append`.
+//!
+//! Structured writes to the long-term memory store: a fixed set of
+//! categories, a confidence per entry, deduplication against what is already
+//! stored, and an audit trail ([`MemoryAudit`]) recording each write with the
+//! agent's reason.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::error::{Result, ZeptoError};
+use crate::memory::audit::{AuditAction, AuditRecord, MemoryAudit};
+use crate::memory::longterm::{value_similarity, LongTermMemory};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Categories the write tools accept.
+pub const MEMORY_CATEGORIES: &[&str] = &[
+    "user",
+    "preference",
+    "fact",
+    "learning",
+    "project",
+    "contact",
+    "pinned",
+];
+
+/// Values at least this similar to an existing entry are merged into it.
+const DUPLICATE_THRESHOLD: f32 = 0.85;
+
+/// Confidence recorded when the agent gives none.
+const DEFAULT_CONFIDENCE: f32 = 0.8;
+
+fn str_arg<'a>(args: &'a Value, name: &str) -> Option<&'a str> {
+    args.get(name)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn required<'a>(args: &'a Value, name: &str, tool: &str) -> Result<&'a str> {
+    str_arg(args, name)
+        .ok_or_else(|| ZeptoError::Tool(format!("Missing '{}' parameter for {}", name, tool)))
+}
+
+fn category_arg(args: &Value) -> Result<Option<&str>> {
+    match str_arg(args, "category") {
+        Some(category) if MEMORY_CATEGORIES.contains(&category) => Ok(Some(category)),
+        Some(other) => Err(ZeptoError::Tool(format!(
+            "Unknown memory category '{}'. Use one of: {}",
+            other,
+            MEMORY_CATEGORIES.join(", ")
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn confidence_arg(args: &Value) -> Result<Option<f32>> {
+    match args.get("confidence").and_then(Value::as_f64) {
+        Some(c) if (0.0..=1.0).contains(&c) => Ok(Some(c as f32)),
+        Some(c) => Err(ZeptoError::Tool(format!(
+            "'confidence' must be between 0.0 and 1.0, got {}",
+            c
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn tags_arg(args: &Value) -> Vec<String> {
+    args.get("tags")
+        .and_then(Value::as_array)
+        .map(|arr| {
+            arr.iter()
+                .filter_map(Value::as_str)
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn shared_arg(args: &Value) -> bool {
+    args.get("shared").and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn merge_tags(existing: &[String], new: &[String]) -> Vec<String> {
+    let mut tags = existing.to_vec();
+    for tag in new {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.clone());
+        }
+    }
+    tags
+}
+
+/// Shared by both tools: the store, its audit log and the write bookkeeping.
+struct MemoryWriter {
+    memory: Arc<Mutex<LongTermMemory>>,
+    audit: MemoryAudit,
+}
+
+impl MemoryWriter {
+    #[allow(clippy::too_many_arguments)]
+    fn audit(
+        &self,
+        action: AuditAction,
+        key: &str,
+        category: &str,
+        value: &str,
+        confidence: Option<f32>,
+        reason: &str,
+        ctx: &ToolContext,
+    ) {
+        let record = AuditRecord {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            action,
+            key: key.to_string(),
+            category: category.to_string(),
+            value: value.to_string(),
+            confidence,
+            reason: reason.to_string(),
+            channel: ctx.channel.clone(),
+            session: ctx.session_key.clone(),
+        };
+        // The write itself succeeded; a failed audit line should not undo it.
+        if let Err(e) = self.audit.record(&record) {
+            tracing::warn!("Failed to write memory audit record: {}", e);
+        }
+    }
+}
+
+/// Tool that stores one fact in long-term memory, deduplicating against
+/// existing entries.
+pub struct MemorySetTool {
+    writer: MemoryWriter,
+}
+
+impl MemorySetTool {
+    /// Create the tool over a shared long-term memory store.
+    pub fn new(memory: Arc<Mutex<LongTermMemory>>) -> Self {
+        Self {
+            writer: MemoryWriter {
+                memory,
+                audit: MemoryAudit::default(),
+            },
+        }
+    }
+
+    /// Write audit records to `audit` instead of the default log.
+    pub fn with_audit(mut self, audit: MemoryAudit) -> Self {
+        self.writer.audit = audit;
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for MemorySetTool {
+    fn name(&self) -> &str {
+        "memory_set"
+    }
+
+    fn description(&self) -> &str {
+        "Remember a fact from the conversation in long-term memory. Give a key, the value, a category, how confident you are and why it is worth remembering. If the same fact is already stored (under any key) it is merged instead of duplicated. Every write is kept in an audit trail."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Remember a fact (deduplicated, audited)"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Memory
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "key": {
+                    "type": "string",
+                    "description": "Memory key, e.g. 'user:city', 'preference:editor'"
+                },
+                "value": {
+                    "type": "string",
+                    "description": "The fact to remember, as a self-contained sentence"
+                },
+                "category": {
+                    "type": "string",
+                    "enum": MEMORY_CATEGORIES,
+                    "description": "Category; 'pinned' entries never decay and are always injected"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional tags"
+                },
+                "confidence": {
+                    "type": "number",
+                    "description": "How sure you are, 0.0-1.0 (default 0.8). Use 1.0 only for facts the user stated directly"
+                },
+                "importance": {
+                    "type": "number",
+                    "description": "Optional importance weight (default 1.0); higher decays slower"
+                },
+                "shared": {
+                    "type": "boolean",
+                    "description": "Store for everyone using this assistant instead of only the current person (default false)"
+                },
+                "reason": {
+                    "type": "string",
+                    "description": "Why this is worth remembering (kept in the audit trail)"
+                }
+            },
+            "required": ["key", "value", "category", "reason"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let key = required(&args, "key", "memory_set")?;
+        let value = required(&args, "value", "memory_set")?;
+        let category = category_arg(&args)?.ok_or_else(|| {
+            ZeptoError::Tool("Missing 'category' parameter for memory_set".into())
+        })?;
+        let reason = required(&args, "reason", "memory_set")?;
+        let confidence = confidence_arg(&args)?.unwrap_or(DEFAULT_CONFIDENCE);
+        let tags = tags_arg(&args);
+        let importance = args
+            .get("importance")
+            .and_then(Value::as_f64)
+            .map(|f| f as f32);
+        let scope = ctx.memory_scope();
+        let key = &scope.storage_key(key, shared_arg(&args))?;
+
+        let mut memory = self.writer.memory.lock().await;
+
+        // Same key: unchanged value only refreshes tags and confidence.
+        if let Some(existing) = memory.get_readonly(key).cloned() {
+            let unchanged = existing.value.trim() == value;
+            let tags = merge_tags(&existing.tags, &tags);
+            let importance = importance.unwrap_or(existing.importance);
+            if unchanged && tags == existing.tags && existing.confidence == Some(confidence) {
+                return Ok(ToolOutput::llm_only(format!(
+                    "Already remembered '{}' — nothing changed",
+                    key
+                )));
+            }
+            memory.set(key, value, category, tags, importance).await?;
+            memory.set_confidence(key, confidence)?;
+            drop(memory);
+            self.writer.audit(
+                AuditAction::Updated,
+                key,
+                category,
+                value,
+                Some(confidence),
+                reason,
+                ctx,
+            );
+            return Ok(ToolOutput::llm_only(format!("Updated memory '{}'", key)));
+        }
+
+        // Another key already holds this fact: merge into it.
+        if let Some((existing, score)) = memory
+            .find_similar_in(value, DUPLICATE_THRESHOLD, &scope)
+            .map(|(e, s)| (e.clone(), s))
+        {
+            let tags = merge_tags(&existing.tags, &tags);
+            let confidence = existing.confidence.unwrap_or(0.0).max(confidence);
+            // A restatement keeps the stored wording; a near-duplicate is
+            // taken as a refinement.
+            let merged_value = if score >= 1.0 { &existing.value } else { value };
+            memory
+                .set(
+                    &existing.key,
+                    merged_value,
+                    &existing.category,
+                    tags,
+                    importance.unwrap_or(existing.importance),
+                )
+                .await?;
+            memory.set_confidence(&existing.key, confidence)?;
+            drop(memory);
+            self.writer.audit(
+                AuditAction::Merged,
+                &existing.key,
+                &existing.category,
+                value,
+                Some(confidence),
+                reason,
+                ctx,
+            );
+            return Ok(ToolOutput::llm_only(if score >= 1.0 {
+                format!(
+                    "Already remembered as '{}' — merged instead of creating '{}'",
+                    existing.key, key
+                )
+            } else {
+                format!(
+                    "Updated similar memory '{}' instead of creating '{}' ({:.0}% similar)",
+                    existing.key,
+                    key,
+                    score * 100.0
+                )
+            }));
+        }
+
+        memory
+            .set(key, value, category, tags, importance.unwrap_or(1.0))
+            .await?;
+        memory.set_confidence(key, confidence)?;
+        drop(memory);
+        self.writer.audit(
+            AuditAction::Created,
+            key,
+            category,
+            value,
+            Some(confidence),
+            reason,
+            ctx,
+        );
+        Ok(ToolOutput::llm_only(format!(
+            "Stored memory '{}' in category '{}'",
+            key, category
+        )))
+    }
+}
+
+/// Tool that adds a line to a memory entry (a running list such as
+/// "projects the user is working on"), creating the entry if needed.
+pub struct MemoryAppendTool {
+    writer: MemoryWriter,
+}
+
+impl MemoryAppendTool {
+    /// Create the tool over a shared long-term memory store.
+    pub fn new(memory: Arc<Mutex<LongTermMemory>>) -> Self {
+        Self {
+            writer: MemoryWriter {
+                memory,
+                audit: MemoryAudit::default(),
+            },
+        }
+    }
+
+    /// Write audit records to `audit` instead of the default log.
+    pub fn with_audit(mut self, audit: MemoryAudit) -> Self {
+        self.writer.audit = audit;
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for MemoryAppendTool {
+    fn name(&self) -> &str {
+        "memory_append"
+    }
+
+    fn description(&self) -> &str {
+        "Add a line to a long-term memory entry (e.g. a list of the user's projects or contacts), creating it if it does not exist. Lines already in the entry are not added twice. Every write is kept in an audit trail."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Append to a memory entry (deduplicated, audited)"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Memory
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "key": {
+                    "type": "string",
+                    "description": "Memory key of the entry to extend"
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Line to add"
+                },
+                "category": {
+                    "type": "string",
+                    "enum": MEMORY_CATEGORIES,
+                    "description": "Category, required when the entry does not exist yet"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional tags added to the entry"
+                },
+                "confidence": {
+                    "type": "number",
+                    "description": "How sure you are, 0.0-1.0 (default 0.8)"
+                },
+                "shared": {
+                    "type": "boolean",
+                    "description": "Store for everyone using this assistant instead of only the current person (default false)"
+                },
+                "reason": {
+                    "type": "string",
+                    "description": "Why this is worth remembering (kept in the audit trail)"
+                }
+            },
+            "required": ["key", "text", "reason"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let key = required(&args, "key", "memory_append")?;
+        let text = required(&args, "text", "memory_append")?;
+        let reason = required(&args, "reason", "memory_append")?;
+        let category = category_arg(&args)?;
+        let confidence = confidence_arg(&args)?;
+        let tags = tags_arg(&args);
+        let scope = ctx.memory_scope();
+        let shared = shared_arg(&args);
+
+        let mut memory = self.writer.memory.lock().await;
+
+        // Extend the sender's own entry, else a shared one; create personal.
+        let candidates = if shared {
+            vec![scope.storage_key(key, true)?]
+        } else {
+            scope.lookup_keys(key)?
+        };
+        let key = &candidates
+            .iter()
+            .find(|k| memory.get_readonly(k).is_some())
+            .unwrap_or(&candidates[0])
+            .clone();
+        let Some(existing) = memory.get_readonly(key).cloned() else {
+            let category = category.ok_or_else(|| {
+                ZeptoError::Tool(format!(
+                    "Memory '{}' does not exist yet; give a 'category' to create it",
+                    key
+                ))
+            })?;
+            let confidence = confidence.unwrap_or(DEFAULT_CONFIDENCE);
+            memory.set(key, text, category, tags, 1.0).await?;
+            memory.set_confidence(key, confidence)?;
+            drop(memory);
+            self.writer.audit(
+                AuditAction::Created,
+                key,
+                category,
+                text,
+                Some(confidence),
+                reason,
+                ctx,
+            );
+            return Ok(ToolOutput::llm_only(format!(
+                "Created memory '{}' in category '{}'",
+                key, category
+            )));
+        };
+
+        let duplicate = existing
+            .value
+            .lines()
+            .any(|line| value_similarity(line, text) >= DUPLICATE_THRESHOLD);
+        if duplicate {
+            return Ok(ToolOutput::llm_only(format!(
+                "'{}' already contains this — nothing appended",
+                key
+            )));
+        }
+
+        let value = format!("{}\n{}", existing.value.trim_end(), text);
+        let tags = merge_tags(&existing.tags, &tags);
+        memory
+            .set(key, &value, &existing.category, tags, existing.importance)
+            .await?;
+        // An entry is only as certain as its least certain line.
+        let confidence = match (existing.confidence, confidence) {
+            (Some(old), Some(new)) => Some(old.min(new)),
+            (old, new) => new.or(old),
+        };
+        if let Some(confidence) = confidence {
+            memory.set_confidence(key, confidence)?;
+        }
+        let lines = value.lines().count();
+        drop(memory);
+        self.writer.audit(
+            AuditAction::Appended,
+            key,
+            &existing.category,
+            text,
+            confidence,
+            reason,
+            ctx,
+        );
+        Ok(ToolOutput::llm_only(format!(
+            "Appended to memory '{}' (now {} lines)",
+            key, lines
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (Arc<Mutex<LongTermMemory>>, MemoryAudit, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let memory = LongTermMemory::with_path(dir.path().join("longterm.json")).unwrap();
+        let audit = MemoryAudit::new(dir.path().join("audit.jsonl"));
+        (Arc::new(Mutex::new(memory)), audit, dir)
+    }
+
+    #[tokio::test]
+    async fn test_memory_set_dedup_and_audit() {
+        let (memory, audit, _dir) = setup();
+        let tool = MemorySetTool::new(memory.clone()).with_audit(audit.clone());
+        let ctx = ToolContext::new();
+
+        let out = tool
+            .execute(
+                json!({"key": "pref:theme", "value": "User prefers dark mode",
+                       "category": "preference", "confidence": 1.0, "reason": "user said so"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(out.for_llm.starts_with("Stored memory"));
+
+        // Same fact under another key is merged, not duplicated.
+        let out = tool
+            .execute(
+                json!({"key": "user:theme", "value": "user prefers Dark Mode.",
+                       "category": "user", "tags": ["ui"], "reason": "mentioned again"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("Already remembered as 'pref:theme'"));
+        {
+            let memory = memory.lock().await;
+            assert_eq!(memory.count(), 1);
+            let entry = memory.get_readonly("pref:theme").unwrap();
+            assert_eq!(entry.tags, vec!["ui"]);
+            assert_eq!(entry.confidence, Some(1.0));
+        }
+
+        let err = tool
+            .execute(
+                json!({"key": "k", "value": "v", "category": "misc", "reason": "r"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown memory category"));
+
+        let records = audit.recent(10).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].action, AuditAction::Merged);
+        assert_eq!(records[1].key, "pref:theme");
+        assert_eq!(records[1].reason, "mentioned again");
+    }
+
+    #[tokio::test]
+    async fn test_memory_append() {
+        let (memory, audit, _dir) = setup();
+        let tool = MemoryAppendTool::new(memory.clone()).with_audit(audit.clone());
+        let ctx = ToolContext::new();
+
+        let err = tool
+            .execute(
+                json!({"key": "user:projects", "text": "zeptoclaw", "reason": "r"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("give a 'category'"));
+
+        for text in ["zeptoclaw", "garden planner", "Garden planner!"] {
+            tool.execute(
+                json!({"key": "user:projects", "text": text, "category": "project",
+                       "confidence": 0.9, "reason": "user mentioned it"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        }
+
+        let memory = memory.lock().await;
+        let entry = memory.get_readonly("user:projects").unwrap();
+        assert_eq!(entry.value, "zeptoclaw\ngarden planner");
+        assert_eq!(entry.category, "project");
+        let actions: Vec<AuditAction> =
+            audit.recent(10).unwrap().iter().map(|r| r.action).collect();
+        assert_eq!(actions, vec![AuditAction::Created, AuditAction::Appended]);
+    }
+}
@@ -11,8 +11,9 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
-use crate::bus::{MessageBus, OutboundMessage};
+use crate::bus::{MessageBus, NotificationOutcome, OutboundMessage, Priority};
 use crate::error::{Result, ZeptoError};
+use crate::heartbeat::QuietHours;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
@@ -34,12 +35,23 @@ const ALLOWED_CHANNELS: &[&str] = &[
 /// keyboards (Telegram).
 pub struct MessageTool {
     bus: Arc<MessageBus>,
+    quiet_hours: Option<QuietHours>,
 }
 
 impl MessageTool {
     /// Create a new message tool.
     pub fn new(bus: Arc<MessageBus>) -> Self {
-        Self { bus }
+        Self {
+            bus,
+            quiet_hours: None,
+        }
+    }
+
+    /// Refuse sends to other chats during `quiet_hours`. Replies into the
+    /// current conversation are not proactive and are always allowed.
+    pub fn with_quiet_hours(mut self, quiet_hours: Option<QuietHours>) -> Self {
+        self.quiet_hours = quiet_hours;
+        self
     }
 }
 
@@ -97,6 +109,12 @@ impl Tool for MessageTool {
                     "type": "integer",
                     "description": "Discord only: auto archive duration in minutes for new thread (send action only)."
                 },
+                "priority": {
+                    "type": "string",
+                    "enum": ["low", "normal", "high"],
+                    "description": "Notification priority (send action only). 'low' may be batched into a digest; 'high' bypasses quiet hours and rate limits — use only for urgent alerts. Default: 'normal'.",
+                    "default": "normal"
+                },
                 "action": {
                     "type": "string",
                     "description": "Action to perform. Default: 'send'. Options: 'send', 'react', 'rich_message', 'inline_keyboard'",
@@ -177,6 +195,29 @@ impl Tool for MessageTool {
             )));
         }
 
+        // During quiet hours only replies into the current conversation go out.
+        if let Some(quiet) = self.quiet_hours.filter(|q| q.is_quiet_now()) {
+            let current = ctx.channel.as_deref() == Some(channel.as_str())
+                && ctx.chat_id.as_deref() == Some(chat_id.as_str());
+            if !current {
+                return Err(ZeptoError::Tool(format!(
+                    "Quiet hours ({}): proactive messages to other chats are paused until {}",
+                    quiet,
+                    quiet.end_label()
+                )));
+            }
+        }
+
+        let priority = match args.get("priority").and_then(|v| v.as_str()) {
+            Some(p) => Priority::parse(p).ok_or_else(|| {
+                ZeptoError::Tool(format!(
+                    "Invalid priority '{}'. Use 'low', 'normal' or 'high'",
+                    p
+                ))
+            })?,
+            None => Priority::Normal,
+        };
+
         // Determine action — default to "send" when absent.
         let action = args
             .get("action")
@@ -225,13 +266,22 @@ impl Tool for MessageTool {
                     );
                 }
 
-                self.bus
-                    .publish_outbound(outbound)
+                let outcome = self
+                    .bus
+                    .publish_notification(outbound, priority)
                     .await
                     .map_err(|e| {
                         ZeptoError::Tool(format!("Failed to publish message: {}", e))
                     })?;
-                Ok(ToolOutput::llm_only(format!("Message sent to {}:{}", channel, chat_id)))
+                Ok(ToolOutput::llm_only(match outcome {
+                    NotificationOutcome::Delivered => {
+                        format!("Message sent to {}:{}", channel, chat_id)
+                    }
+                    NotificationOutcome::Digested => format!(
+                        "Message queued for the next notification digest to {}:{}",
+                        channel, chat_id
+                    ),
+                }))
             }
 
             "react" => {
@@ -362,6 +412,64 @@ mod tests {
         assert!(tool.description().contains("inline_keyboard"));
     }
 
+    #[tokio::test]
+    async fn test_message_tool_quiet_hours_block_other_chats() {
+        let bus = Arc::new(MessageBus::new());
+        // A window covering the whole day except one minute.
+        let now = chrono::Local::now().time();
+        let start = (now - chrono::Duration::minutes(1))
+            .format("%H:%M")
+            .to_string();
+        let end = (now - chrono::Duration::minutes(2))
+            .format("%H:%M")
+            .to_string();
+        let quiet = QuietHours::parse(&start, &end);
+        let tool = MessageTool::new(bus.clone()).with_quiet_hours(quiet);
+        let ctx = ToolContext::new().with_channel("telegram", "12345");
+
+        let result = tool
+            .execute(
+                json!({"content": "Ping", "channel": "telegram", "chat_id": "999"}),
+                &ctx,
+            )
+            .await;
+        assert!(result.unwrap_err().to_string().contains("Quiet hours"));
+
+        let result = tool.execute(json!({"content": "Reply"}), &ctx).await;
+        assert!(result.is_ok());
+        assert_eq!(bus.consume_outbound().await.unwrap().content, "Reply");
+    }
+
+    #[tokio::test]
+    async fn test_message_tool_priority_goes_through_policy() {
+        let policy =
+            crate::bus::NotificationPolicy::from_config(&crate::config::NotificationsConfig {
+                enabled: true,
+                ..Default::default()
+            });
+        let bus = Arc::new(MessageBus::new().with_notification_policy(policy));
+        let tool = MessageTool::new(bus.clone());
+        let ctx = ToolContext::new().with_channel("telegram", "12345");
+
+        let output = tool
+            .execute(json!({"content": "FYI", "priority": "low"}), &ctx)
+            .await
+            .unwrap();
+        assert!(output.for_llm.contains("digest"));
+
+        let output = tool
+            .execute(json!({"content": "Now", "priority": "high"}), &ctx)
+            .await
+            .unwrap();
+        assert!(output.for_llm.starts_with("Message sent"));
+        assert_eq!(bus.consume_outbound().await.unwrap().content, "Now");
+
+        let result = tool
+            .execute(json!({"content": "?", "priority": "max"}), &ctx)
+            .await;
+        assert!(result.unwrap_err().to_string().contains("Invalid priority"));
+    }
+
     #[tokio::test]
     async fn test_message_tool_with_context_target() {
         let bus = Arc::new(MessageBus::new());
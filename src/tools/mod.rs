@@ -19,6 +19,8 @@
 //! - `WriteFileTool`: Write content to a file
 //! - `ListDirTool`: List directory contents
 //! - `EditFileTool`: Edit a file by replacing text
+//! - `ApplyPatchTool`: Apply a multi-file unified diff atomically
+//! - `UndoTool`: Revert file changes from recent turns
 //! - `ShellTool`: Execute shell commands
 //! - `WebSearchTool`: Search the web via Brave Search API
 //! - `DdgSearchTool`: Free web search via DuckDuckGo HTML scraping (fallback)
@@ -26,10 +28,22 @@
 //! - `WebFetchTool`: Fetch URL content and extract text
 //! - `MessageTool`: Send proactive outbound chat messages
 //! - `MemorySearchTool`: Search workspace markdown memory files
+//! - `MemorySetTool` / `MemoryAppendTool`: Deduplicated, audited long-term memory writes
 //! - `MemoryGetTool`: Read memory files with line windows
 //! - `WhatsAppTool`: Send WhatsApp Cloud API messages
 //! - `GoogleSheetsTool`: Read and write Google Sheets ranges
+//! - `CalendarTool`: Google Calendar / CalDAV events and free/busy
+//! - `ContactsTool`: Workspace address book with vCard and CardDAV sync
+//! - `EmailTool`: IMAP mailbox search/read and user-confirmed SMTP replies
+//! - `RssTool`: RSS/Atom subscriptions with a scheduled digest
+//! - `SqlTool` / `SqlExecuteTool`: Read-only SQL queries and approval-gated writes (feature: `tool-sql`)
+//! - `GitHubTool` / `GitHubWriteTool`: Issues, PR diffs, review threads, notifications; approval-gated posts
+//! - `IssuesTool`: Jira/Linear issue search, create/update, transitions and sprint summaries
+//! - `KubectlTool` / `KubectlWriteTool`: Kubernetes inspection and pod diagnosis, approval-gated changes
+//! - `SerialTool`: Plain-text UART send/read on USB serial ports (feature: `hardware`)
+//! - `MqttPublishTool`: Publish to the `channels.mqtt` broker (feature: `mqtt`)
 //! - `R8rTool`: Execute r8r workflows for deterministic automation
+//! - `ApiTool`: One typed tool per OpenAPI operation (`tools.http_request.apis`)
 //!
 //! # Example
 //!
@@ -58,81 +72,136 @@
 
 #[cfg(feature = "android")]
 pub mod android;
+pub mod apply_patch;
 pub mod approval;
 pub mod binary_plugin;
+pub mod calc;
+pub mod calendar;
+#[cfg(feature = "tool-chart")]
+pub mod chart;
 pub mod clarification;
 pub mod composed;
+pub mod contacts;
 pub mod cron;
 pub mod custom;
 pub mod delegate;
 pub mod diff;
 pub mod docx_read;
+pub mod email;
+pub mod fake;
 pub mod filesystem;
 pub mod find;
+pub mod gdrive;
 pub mod git;
+pub mod github;
 #[cfg(feature = "google")]
 pub mod google;
+pub mod google_auth;
 pub mod grep;
 pub mod gsheets;
 pub mod hardware;
 pub mod http_request;
+pub mod issues;
+pub mod kubectl;
 pub mod longterm_memory;
 pub mod mcp;
 pub mod memory;
+pub mod memory_write;
 pub mod message;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+pub mod news;
+pub mod openapi;
 pub mod pdf_read;
 pub mod plugin;
 pub mod project;
+pub mod project_tasks;
 pub mod r8r;
+pub mod rank;
 mod registry;
 pub mod reminder;
+pub mod rss;
 #[cfg(feature = "screenshot")]
 pub mod screenshot;
+pub mod serial;
+pub mod session_link;
 pub mod shell;
 pub mod skills_install;
 pub mod skills_search;
 pub mod spawn;
+pub mod spreadsheet;
+pub mod sql;
 pub mod stripe;
 #[cfg(feature = "panel")]
 pub mod task;
+pub mod time;
 pub mod transcribe;
 mod types;
+pub mod undo;
+pub mod weather;
 pub mod web;
 pub mod whatsapp;
 
 #[cfg(feature = "android")]
 pub use android::AndroidTool;
+pub use apply_patch::ApplyPatchTool;
 pub use binary_plugin::BinaryPluginTool;
+pub use calc::CalcTool;
+pub use calendar::CalendarTool;
+#[cfg(feature = "tool-chart")]
+pub use chart::ChartTool;
 pub use clarification::AskClarificationTool;
 pub use composed::{ComposedTool, CreateToolTool};
+pub use contacts::ContactsTool;
 pub use custom::CustomTool;
 pub use delegate::DelegateTool;
 pub use docx_read::DocxReadTool;
+pub use email::EmailTool;
+pub use fake::FakeTool;
 pub use find::FindTool;
+pub use gdrive::GoogleDriveTool;
 pub use git::GitTool;
+pub use github::{GitHubTool, GitHubWriteTool};
 #[cfg(feature = "google")]
 pub use google::GoogleTool;
+pub use google_auth::GoogleAuth;
 pub use grep::GrepTool;
 pub use gsheets::GoogleSheetsTool;
 pub use hardware::HardwareTool;
 pub use http_request::HttpRequestTool;
+pub use issues::IssuesTool;
+pub use kubectl::{KubectlTool, KubectlWriteTool};
 pub use longterm_memory::LongTermMemoryTool;
 pub use memory::{MemoryGetTool, MemorySearchTool};
+pub use memory_write::{MemoryAppendTool, MemorySetTool};
 pub use message::MessageTool;
+#[cfg(feature = "mqtt")]
+pub use mqtt::MqttPublishTool;
+pub use news::NewsTool;
+pub use openapi::ApiTool;
 pub use pdf_read::PdfReadTool;
 pub use project::ProjectTool;
 pub use r8r::R8rTool;
+pub use rank::RankTool;
 pub use registry::ToolRegistry;
 pub use reminder::ReminderTool;
+pub use rss::RssTool;
 #[cfg(feature = "screenshot")]
 pub use screenshot::WebScreenshotTool;
+pub use serial::SerialTool;
+pub use session_link::SessionLinkTool;
 pub use skills_install::InstallSkillTool;
 pub use skills_search::FindSkillsTool;
+pub use spreadsheet::SpreadsheetTool;
+pub use sql::{SqlExecuteTool, SqlTool};
 pub use stripe::StripeTool;
 #[cfg(feature = "panel")]
 pub use task::TaskTool;
+pub use time::TimeTool;
 pub use transcribe::TranscribeTool;
-pub use types::{Tool, ToolCategory, ToolContext, ToolOutput};
+pub use types::{Citation, CitationKind, Tool, ToolCategory, ToolContext, ToolLimits, ToolOutput};
+pub use undo::UndoTool;
+pub use weather::WeatherTool;
 pub use web::{
     is_blocked_host, resolve_and_check_host, DdgSearchTool, SearxngSearchTool, WebFetchTool,
     WebSearchTool,
@@ -0,0 +1,254 @@
+//! MQTT publish tool.
+//!
+//! Publishes a single message to the broker configured under
+//! `channels.mqtt`, so the agent can drive devices on an existing IoT bus
+//! (e.g. `home/kitchen/light/set`). Each call opens a short-lived connection
+//! with its own client ID and waits for the broker acknowledgement at the
+//! requested QoS. Topics are restricted to `tool_publish_topics` filters, or
+//! to `publish_prefix/#` when none are configured.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rumqttc::{AsyncClient, Event, Outgoing, Packet, QoS};
+use serde_json::{json, Value};
+
+use crate::channels::mqtt::{config_qos, mqtt_options, topic_matches};
+use crate::config::MqttChannelConfig;
+use crate::error::{Result, ZeptoError};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// How long to wait for the broker to accept a publish.
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest payload accepted from the LLM.
+const MAX_PAYLOAD_BYTES: usize = 64 * 1024;
+
+/// Tool that publishes messages to the configured MQTT broker.
+pub struct MqttPublishTool {
+    config: MqttChannelConfig,
+}
+
+impl MqttPublishTool {
+    /// Create a tool publishing through the broker in `config`.
+    pub fn new(config: MqttChannelConfig) -> Self {
+        Self { config }
+    }
+
+    /// Topic filters the tool may publish to.
+    fn allowed_filters(&self) -> Vec<String> {
+        if self.config.tool_publish_topics.is_empty() {
+            vec![format!(
+                "{}/#",
+                self.config.publish_prefix.trim_end_matches('/')
+            )]
+        } else {
+            self.config.tool_publish_topics.clone()
+        }
+    }
+
+    fn check_topic(&self, topic: &str) -> Result<()> {
+        if topic.is_empty() || topic.contains(['+', '#']) {
+            return Err(ZeptoError::Tool(format!(
+                "Invalid MQTT topic '{}': wildcards are not allowed when publishing",
+                topic
+            )));
+        }
+        let filters = self.allowed_filters();
+        if filters.iter().any(|f| topic_matches(f, topic)) {
+            Ok(())
+        } else {
+            Err(ZeptoError::SecurityViolation(format!(
+                "Topic '{}' is not allowed (allowed: {})",
+                topic,
+                filters.join(", ")
+            )))
+        }
+    }
+
+    async fn publish(&self, topic: &str, payload: Vec<u8>, qos: QoS, retain: bool) -> Result<()> {
+        let client_id = format!("{}-tool-{}", self.config.client_id, std::process::id());
+        let options = mqtt_options(&self.config, &client_id)?;
+        let (client, mut eventloop) = AsyncClient::new(options, 8);
+
+        client
+            .publish(topic, qos, retain, payload)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("MQTT publish error: {e}")))?;
+
+        // Drive the event loop until the broker has the message.
+        let acked = tokio::time::timeout(PUBLISH_TIMEOUT, async {
+            loop {
+                match eventloop.poll().await {
+                    Ok(Event::Outgoing(Outgoing::Publish(_))) if qos == QoS::AtMostOnce => {
+                        return Ok(());
+                    }
+                    Ok(Event::Incoming(Packet::PubAck(_))) if qos == QoS::AtLeastOnce => {
+                        return Ok(());
+                    }
+                    Ok(Event::Incoming(Packet::PubComp(_))) => return Ok(()),
+                    Ok(_) => {}
+                    Err(e) => {
+                        return Err(ZeptoError::Tool(format!("MQTT connection error: {e}")));
+                    }
+                }
+            }
+        })
+        .await;
+
+        let _ = client.disconnect().await;
+        // Flush the DISCONNECT packet; errors here are irrelevant.
+        let _ = tokio::time::timeout(Duration::from_secs(1), eventloop.poll()).await;
+
+        match acked {
+            Ok(result) => result,
+            Err(_) => Err(ZeptoError::Tool(format!(
+                "MQTT broker did not acknowledge publish within {}s",
+                PUBLISH_TIMEOUT.as_secs()
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for MqttPublishTool {
+    fn name(&self) -> &str {
+        "mqtt_publish"
+    }
+
+    fn description(&self) -> &str {
+        "Publish a message to an MQTT topic on the configured broker (IoT devices, home automation). Payload is sent as-is; objects are JSON-encoded."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Publish MQTT message"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "topic": {
+                    "type": "string",
+                    "description": "Topic to publish to (no wildcards)"
+                },
+                "payload": {
+                    "description": "Message payload: a string, or a JSON value that is encoded before sending"
+                },
+                "qos": {
+                    "type": "integer",
+                    "enum": [0, 1, 2],
+                    "description": "Quality of service (defaults to channels.mqtt.qos)"
+                },
+                "retain": {
+                    "type": "boolean",
+                    "description": "Ask the broker to retain the message (default: false)"
+                }
+            },
+            "required": ["topic", "payload"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let topic = args
+            .get("topic")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .ok_or_else(|| ZeptoError::Tool("Missing 'topic' argument".into()))?;
+        let payload = match args.get("payload") {
+            Some(Value::String(s)) => s.clone().into_bytes(),
+            Some(Value::Null) | None => {
+                return Err(ZeptoError::Tool("Missing 'payload' argument".into()))
+            }
+            Some(other) => other.to_string().into_bytes(),
+        };
+        if payload.len() > MAX_PAYLOAD_BYTES {
+            return Err(ZeptoError::Tool(format!(
+                "Payload too large ({} bytes, max {})",
+                payload.len(),
+                MAX_PAYLOAD_BYTES
+            )));
+        }
+        let qos_level = args
+            .get("qos")
+            .and_then(Value::as_u64)
+            .map(|q| q.min(2) as u8)
+            .unwrap_or(self.config.qos);
+        let retain = args.get("retain").and_then(Value::as_bool).unwrap_or(false);
+
+        self.check_topic(topic)?;
+        let size = payload.len();
+        self.publish(topic, payload, config_qos(qos_level), retain)
+            .await?;
+
+        Ok(ToolOutput::llm_only(format!(
+            "Published {} bytes to '{}' (qos {}{})",
+            size,
+            topic,
+            qos_level,
+            if retain { ", retained" } else { "" }
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(filters: &[&str]) -> MqttPublishTool {
+        MqttPublishTool::new(MqttChannelConfig {
+            tool_publish_topics: filters.iter().map(|f| f.to_string()).collect(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_default_allows_publish_prefix_only() {
+        let tool = tool(&[]);
+        assert!(tool.check_topic("zeptoclaw/outbox/node-1").is_ok());
+        assert!(tool.check_topic("home/kitchen/light").is_err());
+    }
+
+    #[test]
+    fn test_configured_filters() {
+        let tool = tool(&["home/+/light/set"]);
+        assert!(tool.check_topic("home/kitchen/light/set").is_ok());
+        assert!(tool.check_topic("home/kitchen/lock/set").is_err());
+    }
+
+    #[test]
+    fn test_rejects_wildcard_topics() {
+        let tool = tool(&["#"]);
+        assert!(tool.check_topic("home/#").is_err());
+        assert!(tool.check_topic("home/+/light").is_err());
+        assert!(tool.check_topic("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_disallowed_topic() {
+        let tool = tool(&["home/#"]);
+        let err = tool
+            .execute(
+                json!({"topic": "factory/press/stop", "payload": "1"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_requires_payload() {
+        let tool = tool(&[]);
+        let err = tool
+            .execute(json!({"topic": "zeptoclaw/outbox/x"}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("payload"));
+    }
+}
@@ -0,0 +1,447 @@
+//! News tool: top headlines and article search.
+//!
+//! The provider is set by `tools.news.provider`:
+//! - `google` (default): Google News RSS feeds, no API key
+//! - `newsapi`: NewsAPI.org (`api_key` required)
+//! - `gnews`: GNews.io (`api_key` required)
+//!
+//! Headlines default to `tools.news.country` and `tools.news.language`.
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::config::{NewsProvider, NewsToolConfig};
+use crate::error::{Result, ZeptoError};
+use crate::utils::http::{Client, RequestBuilder};
+
+use super::rss::parse_feed;
+use super::web::{read_body_limited, WEB_USER_AGENT};
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+const GOOGLE_NEWS_URL: &str = "https://news.google.com/rss";
+const NEWSAPI_URL: &str = "https://newsapi.org/v2";
+const GNEWS_URL: &str = "https://gnews.io/api/v4";
+
+/// Hard cap on articles per call.
+const MAX_RESULTS: usize = 50;
+
+/// Largest feed or response body read.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Topics accepted by every provider.
+const TOPICS: &[&str] = &[
+    "world",
+    "nation",
+    "business",
+    "technology",
+    "entertainment",
+    "sports",
+    "science",
+    "health",
+];
+
+/// One headline, whatever the provider.
+#[derive(Debug, Clone, PartialEq)]
+struct Article {
+    title: String,
+    source: Option<String>,
+    url: Option<String>,
+    published: Option<String>,
+    summary: Option<String>,
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Articles of a NewsAPI or GNews response; both use `articles` with a
+/// `source.name`.
+fn parse_articles(body: &Value) -> Vec<Article> {
+    body.get("articles")
+        .and_then(Value::as_array)
+        .map(|articles| {
+            articles
+                .iter()
+                .filter_map(|a| {
+                    let title = str_field(a, "title")?;
+                    // NewsAPI keeps removed stories as "[Removed]" placeholders.
+                    (title != "[Removed]").then(|| Article {
+                        title,
+                        source: a.get("source").and_then(|s| str_field(s, "name")),
+                        url: str_field(a, "url"),
+                        published: str_field(a, "publishedAt"),
+                        summary: str_field(a, "description"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Articles of a Google News feed. Item titles end in " - Source".
+fn parse_google_feed(xml: &str) -> Result<Vec<Article>> {
+    Ok(parse_feed(xml)?
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let (title, source) = match entry.title.rsplit_once(" - ") {
+                Some((title, source)) => (title.to_string(), Some(source.to_string())),
+                None => (entry.title, None),
+            };
+            Article {
+                title,
+                source,
+                url: entry.link,
+                published: entry.published,
+                summary: None,
+            }
+        })
+        .collect())
+}
+
+fn render_articles(heading: &str, articles: &[Article]) -> String {
+    if articles.is_empty() {
+        return format!("{}: no articles found.", heading);
+    }
+    let mut out = format!("{}:\n", heading);
+    for (i, article) in articles.iter().enumerate() {
+        out.push_str(&format!("{}. {}", i + 1, article.title));
+        if let Some(source) = &article.source {
+            out.push_str(&format!(" ({})", source));
+        }
+        if let Some(published) = &article.published {
+            out.push_str(&format!(" - {}", published));
+        }
+        if let Some(summary) = &article.summary {
+            out.push_str(&format!("\n   {}", summary));
+        }
+        if let Some(url) = &article.url {
+            out.push_str(&format!("\n   {}", url));
+        }
+        out.push('\n');
+    }
+    out.trim_end().to_string()
+}
+
+/// Top headlines and news search from a configurable provider.
+pub struct NewsTool {
+    config: NewsToolConfig,
+    client: Client,
+}
+
+impl NewsTool {
+    /// Create the tool from `tools.news`.
+    pub fn new(config: NewsToolConfig) -> Self {
+        Self {
+            config,
+            client: crate::utils::http::client_builder()
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+        }
+    }
+
+    fn api_key(&self) -> Result<&str> {
+        self.config
+            .api_key
+            .as_deref()
+            .filter(|k| !k.trim().is_empty())
+            .ok_or_else(|| {
+                ZeptoError::Tool(
+                    "The news provider needs tools.news.api_key (or use provider 'google')".into(),
+                )
+            })
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<String> {
+        let response = request
+            .header("User-Agent", WEB_USER_AGENT)
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("News request failed: {}", e)))?;
+        let status = response.status();
+        let max_bytes = MAX_BODY_BYTES.min(crate::utils::http::max_response_bytes());
+        let body = read_body_limited(response, max_bytes).await?;
+        if !status.is_success() {
+            let reason = serde_json::from_str::<Value>(&body)
+                .ok()
+                .and_then(|v| {
+                    str_field(&v, "message").or_else(|| {
+                        v.get("errors")
+                            .and_then(|e| e.get(0))
+                            .and_then(Value::as_str)
+                            .map(str::to_string)
+                    })
+                })
+                .unwrap_or_else(|| status.to_string());
+            return Err(ZeptoError::Tool(format!(
+                "News provider error ({}): {}",
+                status, reason
+            )));
+        }
+        Ok(body)
+    }
+
+    async fn fetch(
+        &self,
+        query: Option<&str>,
+        topic: Option<&str>,
+        country: &str,
+        language: &str,
+        limit: usize,
+    ) -> Result<Vec<Article>> {
+        let mut articles = match self.config.provider {
+            NewsProvider::Google => {
+                let country = country.to_uppercase();
+                let locale = [
+                    ("hl", format!("{}-{}", language, country)),
+                    ("gl", country.clone()),
+                    ("ceid", format!("{}:{}", country, language)),
+                ];
+                let request = match (query, topic) {
+                    (Some(q), _) => self
+                        .client
+                        .get(format!("{}/search", GOOGLE_NEWS_URL))
+                        .query(&[("q", q)]),
+                    (None, Some(topic)) => self.client.get(format!(
+                        "{}/headlines/section/topic/{}",
+                        GOOGLE_NEWS_URL,
+                        topic.to_uppercase()
+                    )),
+                    (None, None) => self.client.get(GOOGLE_NEWS_URL),
+                };
+                parse_google_feed(&self.send(request.query(&locale)).await?)?
+            }
+            NewsProvider::NewsApi => {
+                let request = match query {
+                    Some(q) => self
+                        .client
+                        .get(format!("{}/everything", NEWSAPI_URL))
+                        .query(&[("q", q), ("language", language), ("sortBy", "publishedAt")]),
+                    None => {
+                        let request = self
+                            .client
+                            .get(format!("{}/top-headlines", NEWSAPI_URL))
+                            .query(&[("country", country)]);
+                        // NewsAPI's closest category to "world"/"nation" is
+                        // "general".
+                        let category = match topic {
+                            Some("world" | "nation") => Some("general"),
+                            other => other,
+                        };
+                        match category {
+                            Some(category) => request.query(&[("category", category)]),
+                            None => request,
+                        }
+                    }
+                };
+                let request = request
+                    .query(&[("pageSize", limit.to_string())])
+                    .header("X-Api-Key", self.api_key()?);
+                let body: Value = serde_json::from_str(&self.send(request).await?)
+                    .map_err(|e| ZeptoError::Tool(format!("Invalid news response: {}", e)))?;
+                parse_articles(&body)
+            }
+            NewsProvider::GNews => {
+                let request = match query {
+                    Some(q) => self
+                        .client
+                        .get(format!("{}/search", GNEWS_URL))
+                        .query(&[("q", q)]),
+                    None => self
+                        .client
+                        .get(format!("{}/top-headlines", GNEWS_URL))
+                        .query(&[("category", topic.unwrap_or("general"))]),
+                };
+                let request = request.query(&[
+                    ("lang", language.to_string()),
+                    ("country", country.to_string()),
+                    ("max", limit.to_string()),
+                    ("apikey", self.api_key()?.to_string()),
+                ]);
+                let body: Value = serde_json::from_str(&self.send(request).await?)
+                    .map_err(|e| ZeptoError::Tool(format!("Invalid news response: {}", e)))?;
+                parse_articles(&body)
+            }
+        };
+        articles.truncate(limit);
+        Ok(articles)
+    }
+}
+
+#[async_trait]
+impl Tool for NewsTool {
+    fn name(&self) -> &str {
+        "news"
+    }
+
+    fn description(&self) -> &str {
+        "Current news. Without 'query', returns top headlines (optionally for a 'topic'); \
+         with 'query', searches recent articles. Returns titles, sources, dates and links."
+    }
+
+    fn compact_description(&self) -> &str {
+        "News headlines and search"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkRead
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Search terms. Omit for top headlines"
+                },
+                "topic": {
+                    "type": "string",
+                    "enum": TOPICS,
+                    "description": "Headline section (ignored with query)"
+                },
+                "country": {
+                    "type": "string",
+                    "description": "Two-letter country code (default: configured country)"
+                },
+                "language": {
+                    "type": "string",
+                    "description": "Two-letter language code (default: configured language)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Articles to return (default: configured max_results)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let query = str_field(&args, "query");
+        let topic = str_field(&args, "topic").map(|t| t.to_lowercase());
+        if let Some(topic) = &topic {
+            if !TOPICS.contains(&topic.as_str()) {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown topic '{}'. Use one of: {}",
+                    topic,
+                    TOPICS.join(", ")
+                )));
+            }
+        }
+        let country = str_field(&args, "country")
+            .unwrap_or_else(|| self.config.country.clone())
+            .to_lowercase();
+        let language = str_field(&args, "language")
+            .unwrap_or_else(|| self.config.language.clone())
+            .to_lowercase();
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .map(|l| l as usize)
+            .unwrap_or(self.config.max_results)
+            .clamp(1, MAX_RESULTS);
+
+        let articles = self
+            .fetch(
+                query.as_deref(),
+                topic.as_deref(),
+                &country,
+                &language,
+                limit,
+            )
+            .await?;
+        let heading = match (&query, &topic) {
+            (Some(q), _) => format!("News for '{}'", q),
+            (None, Some(t)) => format!("Top {} headlines ({})", t, country.to_uppercase()),
+            (None, None) => format!("Top headlines ({})", country.to_uppercase()),
+        };
+        Ok(ToolOutput::llm_only(render_articles(&heading, &articles)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_articles() {
+        let body = json!({"status": "ok", "articles": [
+            {"source": {"id": null, "name": "Reuters"}, "title": "Markets rally",
+             "description": "Stocks rose.", "url": "https://example.com/a",
+             "publishedAt": "2026-10-16T08:00:00Z"},
+            {"source": {"name": "[Removed]"}, "title": "[Removed]"},
+            {"title": "  "}
+        ]});
+        let articles = parse_articles(&body);
+        assert_eq!(articles.len(), 1);
+        assert_eq!(articles[0].title, "Markets rally");
+        assert_eq!(articles[0].source.as_deref(), Some("Reuters"));
+        assert_eq!(articles[0].summary.as_deref(), Some("Stocks rose."));
+        assert!(parse_articles(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_parse_google_feed() {
+        let xml = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Top stories</title>
+            <item><title>Rates held steady - Financial Times</title>
+              <link>https://news.google.com/articles/1</link>
+              <pubDate>Fri, 16 Oct 2026 07:00:00 GMT</pubDate></item>
+            <item><title>No source here</title></item>
+            </channel></rss>"#;
+        let articles = parse_google_feed(xml).unwrap();
+        assert_eq!(articles.len(), 2);
+        assert_eq!(articles[0].title, "Rates held steady");
+        assert_eq!(articles[0].source.as_deref(), Some("Financial Times"));
+        assert_eq!(
+            articles[0].url.as_deref(),
+            Some("https://news.google.com/articles/1")
+        );
+        assert_eq!(articles[1].source, None);
+    }
+
+    #[test]
+    fn test_render_articles() {
+        let articles = vec![Article {
+            title: "Rates held steady".to_string(),
+            source: Some("FT".to_string()),
+            url: Some("https://example.com".to_string()),
+            published: Some("2026-10-16".to_string()),
+            summary: None,
+        }];
+        assert_eq!(
+            render_articles("Top headlines (US)", &articles),
+            "Top headlines (US):\n1. Rates held steady (FT) - 2026-10-16\n   https://example.com"
+        );
+        assert_eq!(
+            render_articles("News for 'x'", &[]),
+            "News for 'x': no articles found."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_validates_before_fetching() {
+        let tool = NewsTool::new(NewsToolConfig::default());
+        let err = tool
+            .execute(json!({"topic": "gossip"}), &ToolContext::new())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown topic 'gossip'"), "got: {}", err);
+
+        let tool = NewsTool::new(NewsToolConfig {
+            provider: NewsProvider::NewsApi,
+            ..Default::default()
+        });
+        let err = tool
+            .execute(json!({}), &ToolContext::new())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("tools.news.api_key"), "got: {}", err);
+    }
+}
@@ -0,0 +1,836 @@
+//! OpenAPI-driven API tools.
+//!
+//! Each spec in `tools.http_request.apis` is loaded at startup (OpenAPI 3.x,
+//! JSON or YAML) and every operation becomes its own tool named
+//! `{api}_{operation_id}`, so the agent calls typed operations instead of
+//! guessing endpoints with `http_request`:
+//!
+//! - the tool's parameters are the operation's path, query and header
+//!   parameters plus a `body` for the JSON request body (`$ref`s resolved)
+//! - arguments are checked against those schemas before any request is sent
+//! - the configured auth header is added to every request
+//! - JSON responses are trimmed to the properties declared in the
+//!   operation's success response schema, then capped at
+//!   `max_response_bytes`
+//!
+//! Requests go through the shared egress client ([`crate::utils::http`]).
+//!
+//! # Example
+//!
+//! ```
+//! use zeptoclaw::config::ApiSpecConfig;
+//! use zeptoclaw::tools::openapi::api_tools_from_spec;
+//!
+//! let spec = serde_json::json!({
+//!     "openapi": "3.0.0",
+//!     "servers": [{"url": "https://api.example.com/v1"}],
+//!     "paths": {"/pets/{petId}": {"get": {
+//!         "operationId": "getPet",
+//!         "parameters": [{"name": "petId", "in": "path", "required": true,
+//!                         "schema": {"type": "integer"}}]
+//!     }}}
+//! });
+//! let tools = api_tools_from_spec("petstore", &spec, &ApiSpecConfig::default(), 30, 65536)
+//!     .unwrap();
+//! assert_eq!(tools[0].tool_name(), "petstore_get_pet");
+//! ```
+
+use async_trait::async_trait;
+use reqwest::{Method, Url};
+use serde_json::{json, Map, Value};
+use std::time::Duration;
+
+use crate::config::ApiSpecConfig;
+use crate::error::{Result, ZeptoError};
+use crate::tools::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// HTTP methods read from a path item.
+const METHODS: &[&str] = &["get", "post", "put", "patch", "delete"];
+
+/// Longest tool name accepted by LLM providers.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Longest tool description taken from the spec.
+const MAX_DESCRIPTION_CHARS: usize = 500;
+
+/// How deep `$ref`s are followed before a schema is left open.
+const MAX_REF_DEPTH: usize = 8;
+
+/// Where an operation parameter goes in the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParamLocation {
+    Path,
+    Query,
+    Header,
+}
+
+/// One operation parameter.
+#[derive(Debug, Clone)]
+struct ApiParam {
+    name: String,
+    location: ParamLocation,
+    required: bool,
+    schema: Value,
+}
+
+/// A single OpenAPI operation exposed as a tool.
+pub struct ApiTool {
+    tool_name: String,
+    description: String,
+    parameters: Value,
+    method: Method,
+    base_url: Url,
+    path: String,
+    params: Vec<ApiParam>,
+    body_schema: Option<Value>,
+    body_required: bool,
+    response_schema: Option<Value>,
+    auth: Option<(String, String)>,
+    timeout_secs: u64,
+    max_response_bytes: usize,
+}
+
+/// Load the spec for `api` from disk and build its tools.
+pub fn load_api_tools(
+    api: &str,
+    config: &ApiSpecConfig,
+    timeout_secs: u64,
+    max_response_bytes: usize,
+) -> Result<Vec<ApiTool>> {
+    if config.spec.trim().is_empty() {
+        return Err(ZeptoError::Config(format!(
+            "tools.http_request.apis.{api}.spec is not set"
+        )));
+    }
+    let path = crate::config::expand_home(&config.spec);
+    let raw = std::fs::read_to_string(&path).map_err(|e| {
+        ZeptoError::Config(format!(
+            "Failed to read OpenAPI spec {}: {e}",
+            path.display()
+        ))
+    })?;
+    let spec: Value = match serde_json::from_str(&raw) {
+        Ok(spec) => spec,
+        Err(_) => serde_yaml::from_str(&raw).map_err(|e| {
+            ZeptoError::Config(format!(
+                "Failed to parse OpenAPI spec {}: {e}",
+                path.display()
+            ))
+        })?,
+    };
+    api_tools_from_spec(api, &spec, config, timeout_secs, max_response_bytes)
+}
+
+/// Build one tool per operation in a parsed OpenAPI 3 `spec`.
+pub fn api_tools_from_spec(
+    api: &str,
+    spec: &Value,
+    config: &ApiSpecConfig,
+    timeout_secs: u64,
+    max_response_bytes: usize,
+) -> Result<Vec<ApiTool>> {
+    let version = spec.get("openapi").and_then(Value::as_str).unwrap_or("");
+    if !version.starts_with('3') {
+        return Err(ZeptoError::Config(format!(
+            "API '{api}': only OpenAPI 3.x specs are supported"
+        )));
+    }
+    let base_url = base_url(api, spec, config)?;
+    let auth = auth_header(config);
+    let prefix = snake_case(api);
+
+    let mut tools = Vec::new();
+    let paths = spec.get("paths").and_then(Value::as_object);
+    for (path, item) in paths.into_iter().flatten() {
+        let item = resolve_refs(item, spec, 0);
+        for method in METHODS {
+            let Some(op) = item.get(*method) else {
+                continue;
+            };
+            let op_id = op
+                .get("operationId")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{method}_{path}"));
+            if !config.operations.is_empty() && !config.operations.contains(&op_id) {
+                continue;
+            }
+
+            let params = operation_params(item.get("parameters"), op.get("parameters"));
+            let (body_schema, body_required) = request_body(op);
+            let tool_name = tool_name(&prefix, &op_id);
+            let method = method.to_uppercase();
+            let summary = op
+                .get("summary")
+                .or_else(|| op.get("description"))
+                .and_then(Value::as_str)
+                .unwrap_or("");
+            let description = format!(
+                "{} ({method} {path})",
+                truncate_chars(summary.trim(), MAX_DESCRIPTION_CHARS)
+            )
+            .trim()
+            .to_string();
+
+            tools.push(ApiTool {
+                parameters: parameter_schema(&params, body_schema.as_ref(), body_required),
+                tool_name,
+                description,
+                method: Method::from_bytes(method.as_bytes()).unwrap_or(Method::GET),
+                base_url: base_url.clone(),
+                path: path.clone(),
+                params,
+                body_schema,
+                body_required,
+                response_schema: response_schema(op),
+                auth: auth.clone(),
+                timeout_secs,
+                max_response_bytes,
+            });
+        }
+    }
+
+    if tools.is_empty() {
+        return Err(ZeptoError::Config(format!(
+            "API '{api}': spec has no matching operations"
+        )));
+    }
+    Ok(tools)
+}
+
+impl ApiTool {
+    /// Tool name as exposed to the agent.
+    pub fn tool_name(&self) -> &str {
+        &self.tool_name
+    }
+
+    /// Check `args` against the operation's parameter and body schemas.
+    pub fn validate_args(&self, args: &Value) -> Result<()> {
+        let empty = Map::new();
+        let args = match args {
+            Value::Object(map) => map,
+            Value::Null => &empty,
+            _ => return Err(ZeptoError::Tool("Arguments must be an object".into())),
+        };
+
+        for key in args.keys() {
+            if key != "body" && !self.params.iter().any(|p| p.name == *key) {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown parameter '{key}' for {}",
+                    self.tool_name
+                )));
+            }
+        }
+        for param in &self.params {
+            match args.get(&param.name) {
+                Some(value) => check_value(&param.schema, value, &param.name)?,
+                None if param.required => {
+                    return Err(ZeptoError::Tool(format!(
+                        "Missing required parameter: {}",
+                        param.name
+                    )))
+                }
+                None => {}
+            }
+        }
+        match (args.get("body"), &self.body_schema) {
+            (Some(body), Some(schema)) => check_value(schema, body, "body")?,
+            (Some(_), None) => {
+                return Err(ZeptoError::Tool(format!(
+                    "{} does not take a request body",
+                    self.tool_name
+                )))
+            }
+            (None, Some(_)) if self.body_required => {
+                return Err(ZeptoError::Tool("Missing required parameter: body".into()))
+            }
+            (None, _) => {}
+        }
+        Ok(())
+    }
+
+    /// The request URL for validated `args`.
+    fn request_url(&self, args: &Value) -> Result<Url> {
+        let mut url = self.base_url.clone();
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .map_err(|_| ZeptoError::Tool("API base URL cannot have a path".into()))?;
+            segments.pop_if_empty();
+            for segment in self.path.split('/').filter(|s| !s.is_empty()) {
+                let mut segment = segment.to_string();
+                for param in self.params_at(ParamLocation::Path) {
+                    let placeholder = format!("{{{}}}", param.name);
+                    if let Some(value) = args.get(&param.name) {
+                        segment = segment.replace(&placeholder, &value_to_string(value));
+                    }
+                }
+                segments.push(&segment);
+            }
+        }
+
+        let query: Vec<(String, String)> = self
+            .params_at(ParamLocation::Query)
+            .filter_map(|p| args.get(&p.name).map(|v| (p, v)))
+            .flat_map(|(p, value)| match value {
+                Value::Array(items) => items
+                    .iter()
+                    .map(|item| (p.name.clone(), value_to_string(item)))
+                    .collect::<Vec<_>>(),
+                other => vec![(p.name.clone(), value_to_string(other))],
+            })
+            .collect();
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        Ok(url)
+    }
+
+    fn params_at(&self, location: ParamLocation) -> impl Iterator<Item = &ApiParam> {
+        self.params.iter().filter(move |p| p.location == location)
+    }
+}
+
+#[async_trait]
+impl Tool for ApiTool {
+    fn name(&self) -> &str {
+        &self.tool_name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn compact_description(&self) -> &str {
+        self.description()
+    }
+
+    fn category(&self) -> ToolCategory {
+        if self.method == Method::GET {
+            ToolCategory::NetworkRead
+        } else {
+            ToolCategory::NetworkWrite
+        }
+    }
+
+    fn parameters(&self) -> Value {
+        self.parameters.clone()
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        self.validate_args(&args)?;
+        let url = self.request_url(&args)?;
+        crate::utils::http::check_url(&url)?;
+
+        let client = crate::utils::http::client_builder()
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .build()
+            .map_err(|e| ZeptoError::Tool(format!("HTTP client error: {e}")))?;
+        let mut req = client.request(self.method.clone(), url);
+        for param in self.params_at(ParamLocation::Header) {
+            if let Some(value) = args.get(&param.name) {
+                req = req.header(&param.name, value_to_string(value));
+            }
+        }
+        if let Some((header, value)) = &self.auth {
+            req = req.header(header, value);
+        }
+        if let Some(body) = args.get("body") {
+            req = req.json(body);
+        }
+
+        let mut response = req
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Request failed: {e}")))?;
+        let status = response.status();
+
+        let limit = self
+            .max_response_bytes
+            .min(crate::utils::http::max_response_bytes());
+        let mut body_bytes: Vec<u8> = Vec::new();
+        let mut truncated = false;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Failed to read response body: {e}")))?
+        {
+            let take = chunk.len().min(limit - body_bytes.len());
+            body_bytes.extend_from_slice(&chunk[..take]);
+            if take < chunk.len() {
+                truncated = true;
+                break;
+            }
+        }
+
+        let body = match (
+            status.is_success() && !truncated,
+            &self.response_schema,
+            serde_json::from_slice::<Value>(&body_bytes),
+        ) {
+            (true, Some(schema), Ok(value)) => trim_to_schema(&value, schema).to_string(),
+            (_, _, Ok(value)) if !truncated => value.to_string(),
+            _ => String::from_utf8_lossy(&body_bytes).into_owned(),
+        };
+        let body = if truncated {
+            format!("{body}\n[TRUNCATED — response exceeded {limit} bytes]")
+        } else {
+            body
+        };
+
+        Ok(ToolOutput::llm_only(format!(
+            "Status: {}\n\n{body}",
+            status.as_u16()
+        )))
+    }
+}
+
+/// The API base URL: `base_url` from config, else the spec's first server
+/// with its variables set to their defaults.
+fn base_url(api: &str, spec: &Value, config: &ApiSpecConfig) -> Result<Url> {
+    let raw = match &config.base_url {
+        Some(url) => url.clone(),
+        None => {
+            let server = spec.pointer("/servers/0").ok_or_else(|| {
+                ZeptoError::Config(format!("API '{api}': spec has no servers; set base_url"))
+            })?;
+            let mut url = server
+                .get("url")
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_string();
+            let variables = server.get("variables").and_then(Value::as_object);
+            for (name, var) in variables.into_iter().flatten() {
+                if let Some(default) = var.get("default").and_then(Value::as_str) {
+                    url = url.replace(&format!("{{{name}}}"), default);
+                }
+            }
+            url
+        }
+    };
+    let url = Url::parse(&raw).map_err(|e| {
+        ZeptoError::Config(format!(
+            "API '{api}': invalid base URL '{raw}' ({e}); set base_url"
+        ))
+    })?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(ZeptoError::Config(format!(
+            "API '{api}': base URL must be http(s)"
+        )));
+    }
+    Ok(url)
+}
+
+fn auth_header(config: &ApiSpecConfig) -> Option<(String, String)> {
+    let value = config
+        .auth_env
+        .as_deref()
+        .and_then(|name| std::env::var(name).ok())
+        .or_else(|| config.auth_value.clone())
+        .filter(|value| !value.is_empty())?;
+    let header = config
+        .auth_header
+        .clone()
+        .unwrap_or_else(|| "Authorization".to_string());
+    Some((header, value))
+}
+
+/// Path-level parameters overridden by operation-level ones (same name and
+/// location). Cookie parameters are skipped.
+fn operation_params(path_level: Option<&Value>, op_level: Option<&Value>) -> Vec<ApiParam> {
+    let mut params: Vec<ApiParam> = Vec::new();
+    let all = [path_level, op_level];
+    for raw in all.iter().flatten().filter_map(|v| v.as_array()).flatten() {
+        let Some(name) = raw.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let location = match raw.get("in").and_then(Value::as_str) {
+            Some("path") => ParamLocation::Path,
+            Some("query") => ParamLocation::Query,
+            Some("header") => ParamLocation::Header,
+            _ => continue,
+        };
+        let mut schema = raw.get("schema").cloned().unwrap_or_else(|| json!({}));
+        if let (Some(description), Some(obj)) = (
+            raw.get("description").and_then(Value::as_str),
+            schema.as_object_mut(),
+        ) {
+            obj.entry("description")
+                .or_insert_with(|| Value::String(description.to_string()));
+        }
+        let param = ApiParam {
+            name: name.to_string(),
+            location,
+            required: location == ParamLocation::Path
+                || raw
+                    .get("required")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+            schema,
+        };
+        params.retain(|p| !(p.name == param.name && p.location == param.location));
+        params.push(param);
+    }
+    params
+}
+
+/// The JSON request body schema and whether the body is required.
+fn request_body(op: &Value) -> (Option<Value>, bool) {
+    let Some(body) = op.get("requestBody") else {
+        return (None, false);
+    };
+    let required = body
+        .get("required")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    (json_schema(body.get("content")), required)
+}
+
+/// The JSON schema of the first success (or default) response.
+fn response_schema(op: &Value) -> Option<Value> {
+    let responses = op.get("responses")?.as_object()?;
+    let status = ["200", "201", "202", "2XX", "default"]
+        .into_iter()
+        .find(|code| responses.contains_key(*code))?;
+    json_schema(responses[status].get("content"))
+}
+
+fn json_schema(content: Option<&Value>) -> Option<Value> {
+    content?
+        .as_object()?
+        .iter()
+        .find(|(media, _)| media.contains("json"))
+        .and_then(|(_, media)| media.get("schema").cloned())
+}
+
+/// Tool input schema: one property per parameter plus `body`.
+fn parameter_schema(params: &[ApiParam], body: Option<&Value>, body_required: bool) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for param in params {
+        properties.insert(param.name.clone(), param.schema.clone());
+        if param.required {
+            required.push(Value::String(param.name.clone()));
+        }
+    }
+    if let Some(body) = body {
+        properties.insert("body".to_string(), body.clone());
+        if body_required {
+            required.push(Value::String("body".to_string()));
+        }
+    }
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Replace local `$ref`s (`#/components/...`) with their targets. Refs deeper
+/// than [`MAX_REF_DEPTH`] (e.g. recursive schemas) become open schemas.
+fn resolve_refs(value: &Value, root: &Value, depth: usize) -> Value {
+    match value {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                if depth >= MAX_REF_DEPTH {
+                    return json!({});
+                }
+                return reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| root.pointer(pointer))
+                    .map(|target| resolve_refs(target, root, depth + 1))
+                    .unwrap_or_else(|| json!({}));
+            }
+            Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), resolve_refs(v, root, depth)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| resolve_refs(item, root, depth))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Check `value` against the `type`, `enum`, `required`, `properties` and
+/// `items` keywords of `schema`.
+fn check_value(schema: &Value, value: &Value, at: &str) -> Result<()> {
+    if value.is_null() && schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        return Ok(());
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            return Err(ZeptoError::Tool(format!(
+                "{at} must be one of: {}",
+                allowed.join(", ")
+            )));
+        }
+    }
+    if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+        let ok = match expected {
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => true,
+        };
+        if !ok {
+            return Err(ZeptoError::Tool(format!("{at} must be of type {expected}")));
+        }
+    }
+    if let Some(obj) = value.as_object() {
+        let required = schema.get("required").and_then(Value::as_array);
+        for key in required.into_iter().flatten().filter_map(Value::as_str) {
+            if !obj.contains_key(key) {
+                return Err(ZeptoError::Tool(format!("{at}.{key} is required")));
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (key, item) in obj {
+                if let Some(item_schema) = properties.get(key) {
+                    check_value(item_schema, item, &format!("{at}.{key}"))?;
+                }
+            }
+        }
+    }
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            check_value(item_schema, item, &format!("{at}[{i}]"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Drop object properties not declared in `schema`. Objects without
+/// `properties` (or with `additionalProperties`) are kept whole.
+fn trim_to_schema(value: &Value, schema: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let open = schema
+                .get("additionalProperties")
+                .is_some_and(|extra| extra != &Value::Bool(false));
+            match properties {
+                Some(properties) if !open => Value::Object(
+                    map.iter()
+                        .filter_map(|(k, v)| {
+                            properties.get(k).map(|s| (k.clone(), trim_to_schema(v, s)))
+                        })
+                        .collect(),
+                ),
+                _ => value.clone(),
+            }
+        }
+        Value::Array(items) => match schema.get("items") {
+            Some(item_schema) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| trim_to_schema(item, item_schema))
+                    .collect(),
+            ),
+            None => value.clone(),
+        },
+        other => other.clone(),
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// `{prefix}_{operation}` in snake case, cut to [`MAX_TOOL_NAME_LEN`].
+fn tool_name(prefix: &str, operation: &str) -> String {
+    let mut name = format!("{prefix}_{}", snake_case(operation));
+    name.truncate(MAX_TOOL_NAME_LEN);
+    name.trim_end_matches('_').to_string()
+}
+
+/// `listPets` → `list_pets`, `get_/pets/{id}` → `get_pets_id`.
+fn snake_case(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len() + 4);
+    let mut prev_lower = false;
+    for c in raw.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && prev_lower {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !out.ends_with('_') {
+                out.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    out.trim_matches('_').to_string()
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((idx, _)) => format!("{}...", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "servers": [{"url": "https://{region}.api.example.com/v1",
+                         "variables": {"region": {"default": "eu"}}}],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "summary": "List pets",
+                        "parameters": [
+                            {"name": "limit", "in": "query", "schema": {"type": "integer"}},
+                            {"name": "tag", "in": "query",
+                             "schema": {"type": "string", "enum": ["cat", "dog"]}}
+                        ],
+                        "responses": {"200": {"content": {"application/json": {
+                            "schema": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}}
+                        }}}}
+                    },
+                    "post": {
+                        "operationId": "createPet",
+                        "requestBody": {"required": true, "content": {"application/json": {
+                            "schema": {"$ref": "#/components/schemas/Pet"}
+                        }}}
+                    }
+                },
+                "/pets/{petId}": {
+                    "parameters": [{"name": "petId", "in": "path", "schema": {"type": "string"}}],
+                    "delete": {}
+                }
+            },
+            "components": {"schemas": {"Pet": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {"id": {"type": "integer"}, "name": {"type": "string"}}
+            }}}
+        })
+    }
+
+    fn tools() -> Vec<ApiTool> {
+        let config = ApiSpecConfig {
+            auth_value: Some("Bearer secret".to_string()),
+            ..Default::default()
+        };
+        api_tools_from_spec("Pet Store", &spec(), &config, 30, 1024).unwrap()
+    }
+
+    fn find(tools: &[ApiTool], name: &str) -> usize {
+        tools.iter().position(|t| t.tool_name() == name).unwrap()
+    }
+
+    #[test]
+    fn test_operations_become_tools() {
+        let tools = tools();
+        let mut names: Vec<&str> = tools.iter().map(|t| t.tool_name()).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "pet_store_create_pet",
+                "pet_store_delete_pets_pet_id",
+                "pet_store_list_pets"
+            ]
+        );
+
+        let list = &tools[find(&tools, "pet_store_list_pets")];
+        assert_eq!(list.description(), "List pets (GET /pets)");
+        assert_eq!(list.category(), ToolCategory::NetworkRead);
+        assert_eq!(list.base_url.as_str(), "https://eu.api.example.com/v1");
+        assert_eq!(
+            list.auth,
+            Some(("Authorization".to_string(), "Bearer secret".to_string()))
+        );
+        let create = &tools[find(&tools, "pet_store_create_pet")];
+        assert_eq!(create.parameters()["required"], json!(["body"]));
+        assert_eq!(create.category(), ToolCategory::NetworkWrite);
+    }
+
+    #[test]
+    fn test_validate_args() {
+        let tools = tools();
+        let list = &tools[find(&tools, "pet_store_list_pets")];
+        assert!(list
+            .validate_args(&json!({"limit": 5, "tag": "cat"}))
+            .is_ok());
+        assert!(list.validate_args(&json!({})).is_ok());
+        assert!(list.validate_args(&json!({"limit": "5"})).is_err());
+        assert!(list.validate_args(&json!({"tag": "fish"})).is_err());
+        assert!(list.validate_args(&json!({"species": "cat"})).is_err());
+
+        let create = &tools[find(&tools, "pet_store_create_pet")];
+        assert!(create
+            .validate_args(&json!({"body": {"name": "Rex"}}))
+            .is_ok());
+        assert!(create.validate_args(&json!({})).is_err());
+        assert!(create.validate_args(&json!({"body": {"id": 1}})).is_err());
+
+        let delete = &tools[find(&tools, "pet_store_delete_pets_pet_id")];
+        assert!(delete.validate_args(&json!({})).is_err());
+    }
+
+    #[test]
+    fn test_request_url() {
+        let tools = tools();
+        let list = &tools[find(&tools, "pet_store_list_pets")];
+        let url = list
+            .request_url(&json!({"limit": 5, "tag": "dog"}))
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://eu.api.example.com/v1/pets?limit=5&tag=dog"
+        );
+        let delete = &tools[find(&tools, "pet_store_delete_pets_pet_id")];
+        let url = delete.request_url(&json!({"petId": "a/b c"})).unwrap();
+        assert_eq!(url.as_str(), "https://eu.api.example.com/v1/pets/a%2Fb%20c");
+    }
+
+    #[test]
+    fn test_trim_to_schema() {
+        let tools = tools();
+        let list = &tools[find(&tools, "pet_store_list_pets")];
+        let response = json!([{"id": 1, "name": "Rex", "internal": {"big": true}}]);
+        assert_eq!(
+            trim_to_schema(&response, list.response_schema.as_ref().unwrap()),
+            json!([{"id": 1, "name": "Rex"}])
+        );
+    }
+
+    #[test]
+    fn test_spec_errors_and_operation_filter() {
+        let config = ApiSpecConfig::default();
+        let swagger = json!({"swagger": "2.0", "paths": {}});
+        assert!(api_tools_from_spec("x", &swagger, &config, 30, 1024).is_err());
+
+        let no_server = json!({"openapi": "3.1.0", "paths": {"/a": {"get": {}}}});
+        assert!(api_tools_from_spec("x", &no_server, &config, 30, 1024).is_err());
+
+        let filtered = ApiSpecConfig {
+            operations: vec!["createPet".to_string()],
+            ..Default::default()
+        };
+        let tools = api_tools_from_spec("pets", &spec(), &filtered, 30, 1024).unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].tool_name(), "pets_create_pet");
+    }
+}
@@ -38,8 +38,10 @@ use async_trait::async_trait;
 use serde_json::Value;
 use std::time::Duration;
 
+use crate::config::NativeShell;
 use crate::error::{Result, ZeptoError};
 use crate::plugins::PluginToolDef;
+use crate::runtime::shell_command;
 use crate::security::ShellSecurityConfig;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
@@ -161,8 +163,7 @@ impl Tool for PluginTool {
         );
 
         // Build the command
-        let mut cmd = tokio::process::Command::new("sh");
-        cmd.arg("-c").arg(&command);
+        let mut cmd = shell_command(NativeShell::Auto, &command);
 
         // Apply working directory: tool def > workspace from context
         if let Some(ref wd) = self.def.working_dir {
@@ -11,6 +11,15 @@
 //! - `search`        — search issues by query / JQL
 //! - `transitions`   — list available transitions for an issue (Jira only)
 //!
+//! Local actions work on the workspace's `PROJECT.md` and need no backend
+//! credentials (see [`super::project_tasks`]):
+//!
+//! - `init_project`   — scaffold `PROJECT.md` from a task template
+//! - `add_task`       — add a task with optional priority and deadline
+//! - `update_task`    — change a task's fields, progress or status, or add a note
+//! - `list_tasks`     — list tasks, open ones by default
+//! - `project_status` — progress summary with overdue and due-soon tasks
+//!
 //! # Backends
 //!
 //! | Backend  | Auth header           | API base                                          |
@@ -19,17 +28,34 @@
 //! | `jira`   | `Basic {TOKEN}`       | `{JIRA_URL}/rest/api/3/...`                       |
 //! | `linear` | `{LINEAR_API_KEY}`    | `https://api.linear.app/graphql`                  |
 
+use std::path::PathBuf;
+
 use async_trait::async_trait;
-use reqwest::Client;
+use chrono::{Local, NaiveDate};
 use serde_json::{json, Value};
 
 use crate::config::{ProjectBackend, ProjectConfig};
 use crate::error::{Result, ZeptoError};
+use crate::tools::filesystem::{resolve_path, write_file_secure};
+use crate::tools::project_tasks::{
+    parse_task_id, ProjectFile, TaskChanges, TaskPriority, PROJECT_FILE,
+};
+use crate::tools::undo::record_before;
+use crate::utils::http::Client;
 
 use super::{Tool, ToolContext, ToolOutput};
 
 const DEFAULT_LIMIT: u64 = 10;
 
+/// Actions served from the workspace's `PROJECT.md` instead of a backend.
+const LOCAL_ACTIONS: &[&str] = &[
+    "init_project",
+    "add_task",
+    "update_task",
+    "list_tasks",
+    "project_status",
+];
+
 /// Tool for project management (GitHub Issues, Jira, Linear).
 #[derive(Debug)]
 pub struct ProjectTool {
@@ -41,7 +67,7 @@ impl ProjectTool {
     /// Create a new ProjectTool from a `ProjectConfig`.
     pub fn new(config: ProjectConfig) -> Self {
         Self {
-            client: Client::new(),
+            client: crate::utils::http::client(),
             config,
         }
     }
@@ -758,11 +784,13 @@ impl Tool for ProjectTool {
     }
 
     fn description(&self) -> &str {
-        "Manage issues on GitHub, Jira, or Linear (list_issues, get_issue, create_issue, update_issue, search, transitions)."
+        "Manage issues on GitHub, Jira, or Linear (list_issues, get_issue, create_issue, update_issue, search, transitions), \
+         and a local task list in the workspace's PROJECT.md (init_project, add_task, update_task, list_tasks, project_status) \
+         with priorities, deadlines and progress for follow-ups."
     }
 
     fn compact_description(&self) -> &str {
-        "Project issue management (GitHub/Jira/Linear)"
+        "Project issues (GitHub/Jira/Linear) and PROJECT.md tasks"
     }
 
     fn parameters(&self) -> Value {
@@ -771,7 +799,10 @@ impl Tool for ProjectTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["list_issues", "get_issue", "create_issue", "update_issue", "search", "transitions"],
+                    "enum": [
+                        "list_issues", "get_issue", "create_issue", "update_issue", "search", "transitions",
+                        "init_project", "add_task", "update_task", "list_tasks", "project_status"
+                    ],
                     "description": "Action to perform."
                 },
                 "project": {
@@ -784,15 +815,42 @@ impl Tool for ProjectTool {
                 },
                 "title": {
                     "type": "string",
-                    "description": "Issue title for create_issue or update_issue."
+                    "description": "Issue title for create_issue or update_issue; project or task title for init_project, add_task and update_task."
                 },
                 "description": {
                     "type": "string",
-                    "description": "Issue description/body for create_issue or update_issue."
+                    "description": "Issue description/body for create_issue or update_issue; project summary for init_project."
                 },
                 "status": {
                     "type": "string",
-                    "description": "Issue status to set (e.g., 'open', 'closed') for update_issue."
+                    "description": "Issue status to set (e.g., 'open', 'closed') for update_issue. For update_task: todo, in_progress, blocked or done. For list_tasks: open (default), done, blocked or all."
+                },
+                "task_id": {
+                    "type": "string",
+                    "description": "Task ID (e.g. 'T3') for update_task."
+                },
+                "template": {
+                    "type": "string",
+                    "description": "Starter tasks for init_project: blank (default), software, research or event."
+                },
+                "priority": {
+                    "type": "string",
+                    "enum": ["low", "medium", "high", "urgent"],
+                    "description": "Task priority for add_task or update_task (default medium)."
+                },
+                "due": {
+                    "type": "string",
+                    "description": "Task deadline as YYYY-MM-DD for add_task or update_task; empty string clears it."
+                },
+                "progress": {
+                    "type": "integer",
+                    "description": "Percent complete (0-100) for update_task; 100 marks the task done.",
+                    "minimum": 0,
+                    "maximum": 100
+                },
+                "note": {
+                    "type": "string",
+                    "description": "Progress note appended to the task for update_task."
                 },
                 "query": {
                     "type": "string",
@@ -817,7 +875,7 @@ impl Tool for ProjectTool {
         })
     }
 
-    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
         let action = args
             .get("action")
             .and_then(Value::as_str)
@@ -831,6 +889,13 @@ impl Tool for ProjectTool {
             .unwrap_or(DEFAULT_LIMIT)
             .clamp(1, 100);
 
+        if LOCAL_ACTIONS.contains(&action) {
+            return self
+                .execute_local(action, &args, limit, ctx)
+                .await
+                .map(ToolOutput::llm_only);
+        }
+
         // `transitions` is Jira-only
         if action == "transitions" {
             if self.config.backend != ProjectBackend::Jira {
@@ -862,6 +927,168 @@ impl Tool for ProjectTool {
 }
 
 impl ProjectTool {
+    // -------------------------------------------------------------------------
+    // Local PROJECT.md tasks
+    // -------------------------------------------------------------------------
+
+    async fn execute_local(
+        &self,
+        action: &str,
+        args: &Value,
+        limit: u64,
+        ctx: &ToolContext,
+    ) -> Result<String> {
+        let (full_path, workspace) = resolve_path(PROJECT_FILE, ctx)?;
+        let path = PathBuf::from(full_path);
+        let existing = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => Some(ProjectFile::parse(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                return Err(ZeptoError::Tool(format!(
+                    "Failed to read {}: {}",
+                    PROJECT_FILE, e
+                )))
+            }
+        };
+        let limit = limit as usize;
+
+        let (file, message) = match action {
+            "init_project" => {
+                if existing.is_some() {
+                    return Err(ZeptoError::Tool(format!(
+                        "{} already exists; use add_task to extend it",
+                        PROJECT_FILE
+                    )));
+                }
+                let title = str_arg(args, "title").ok_or_else(|| {
+                    ZeptoError::Tool("'title' is required for init_project".to_string())
+                })?;
+                let template = str_arg(args, "template").unwrap_or("blank");
+                let file = ProjectFile::scaffold(
+                    title,
+                    str_arg(args, "description").unwrap_or(""),
+                    template,
+                )
+                .map_err(ZeptoError::Tool)?;
+                let message = format!(
+                    "Created {} for '{}' with {} task(s) from the '{}' template",
+                    PROJECT_FILE,
+                    title,
+                    file.tasks().count(),
+                    template
+                );
+                (file, message)
+            }
+            "add_task" => {
+                let title = str_arg(args, "title").ok_or_else(|| {
+                    ZeptoError::Tool("'title' is required for add_task".to_string())
+                })?;
+                let priority = priority_arg(args)?.unwrap_or_default();
+                let due = due_arg(args)?.flatten();
+                let mut file = existing.unwrap_or_else(|| ProjectFile::parse("# Project\n"));
+                let task = file.add(title, priority, due);
+                let message = format!("Added {} to {}", task.summary(), PROJECT_FILE);
+                (file, message)
+            }
+            "update_task" => {
+                let mut file = existing.ok_or_else(|| {
+                    ZeptoError::Tool(format!(
+                        "{} does not exist; use init_project or add_task first",
+                        PROJECT_FILE
+                    ))
+                })?;
+                let id = str_arg(args, "task_id")
+                    .and_then(parse_task_id)
+                    .ok_or_else(|| {
+                        ZeptoError::Tool(
+                            "'task_id' (e.g. 'T3') is required for update_task".to_string(),
+                        )
+                    })?;
+                let mut changes = TaskChanges {
+                    title: str_arg(args, "title").map(str::to_string),
+                    priority: priority_arg(args)?,
+                    due: due_arg(args)?,
+                    progress: args
+                        .get("progress")
+                        .and_then(Value::as_u64)
+                        .map(|p| p.min(100) as u8),
+                    note: str_arg(args, "note")
+                        .map(|n| format!("{}: {}", Local::now().date_naive(), n)),
+                    ..Default::default()
+                };
+                match str_arg(args, "status")
+                    .map(str::to_ascii_lowercase)
+                    .as_deref()
+                {
+                    None => {}
+                    Some("done") => changes.done = Some(true),
+                    Some("blocked") => changes.blocked = Some(true),
+                    Some("todo" | "open" | "in_progress") => {
+                        changes.done = Some(false);
+                        changes.blocked = Some(false);
+                    }
+                    Some(other) => {
+                        return Err(ZeptoError::Tool(format!(
+                            "Unknown task status '{}'. Use todo, in_progress, blocked or done",
+                            other
+                        )))
+                    }
+                }
+                let task = file.update(id, changes).ok_or_else(|| {
+                    ZeptoError::Tool(format!("Task T{} not found in {}", id, PROJECT_FILE))
+                })?;
+                let message = format!("Updated {}", task.summary());
+                (file, message)
+            }
+            "list_tasks" => {
+                let Some(file) = existing else {
+                    return Ok(format!("No {} in the workspace yet.", PROJECT_FILE));
+                };
+                let filter = str_arg(args, "status")
+                    .unwrap_or("open")
+                    .to_ascii_lowercase();
+                let tasks: Vec<_> = file
+                    .tasks()
+                    .filter(|t| match filter.as_str() {
+                        "all" => true,
+                        "done" => t.done,
+                        "blocked" => t.blocked && !t.done,
+                        _ => !t.done,
+                    })
+                    .collect();
+                if tasks.is_empty() {
+                    return Ok(format!("No {} tasks in {}.", filter, PROJECT_FILE));
+                }
+                let mut lines = vec![format!("{} {} task(s):", tasks.len(), filter)];
+                lines.extend(
+                    tasks
+                        .iter()
+                        .take(limit)
+                        .map(|t| format!("- {}", t.summary())),
+                );
+                if tasks.len() > limit {
+                    lines.push(format!("- ... and {} more", tasks.len() - limit));
+                }
+                return Ok(lines.join("\n"));
+            }
+            _ => {
+                let Some(file) = existing else {
+                    return Ok(format!("No {} in the workspace yet.", PROJECT_FILE));
+                };
+                let today = Local::now().date_naive();
+                return Ok(format!(
+                    "Project status as of {}: {}",
+                    today,
+                    file.status(today).render(limit)
+                ));
+            }
+        };
+
+        record_before(ctx, &path).await;
+        write_file_secure(&path, &workspace, file.render().as_bytes()).await?;
+        Ok(message)
+    }
+
     async fn execute_github(&self, action: &str, args: &Value, limit: u64) -> Result<String> {
         match action {
             "list_issues" => {
@@ -1019,6 +1246,38 @@ impl ProjectTool {
     }
 }
 
+/// Non-empty, trimmed string argument.
+fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn priority_arg(args: &Value) -> Result<Option<TaskPriority>> {
+    str_arg(args, "priority")
+        .map(|p| {
+            TaskPriority::parse(p).ok_or_else(|| {
+                ZeptoError::Tool(format!(
+                    "Unknown priority '{}'. Use low, medium, high or urgent",
+                    p
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// `None` when absent, `Some(None)` when given as an empty string.
+fn due_arg(args: &Value) -> Result<Option<Option<NaiveDate>>> {
+    match args.get("due").and_then(Value::as_str).map(str::trim) {
+        None => Ok(None),
+        Some("") => Ok(Some(None)),
+        Some(due) => NaiveDate::parse_from_str(due, "%Y-%m-%d")
+            .map(|d| Some(Some(d)))
+            .map_err(|_| ZeptoError::Tool(format!("Invalid due date '{}'; use YYYY-MM-DD", due))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1331,4 +1590,112 @@ mod tests {
         assert_eq!(required.len(), 1);
         assert_eq!(required[0], "action");
     }
+
+    // ---- Local PROJECT.md tasks ---------------------------------------------
+
+    #[tokio::test]
+    async fn test_local_tasks_without_backend_credentials() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = ToolContext::new().with_workspace(&dir.path().to_string_lossy());
+        let tool = ProjectTool::new(ProjectConfig::default());
+
+        let out = tool
+            .execute(
+                json!({"action": "init_project", "title": "Launch", "template": "software"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("with 5 task(s)"), "{}", out.for_llm);
+
+        let out = tool
+            .execute(
+                json!({"action": "add_task", "title": "Announce", "priority": "high", "due": "2000-01-01"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("Added T6 Announce"), "{}", out.for_llm);
+
+        tool.execute(
+            json!({"action": "update_task", "task_id": "T1", "progress": 50, "note": "scope agreed"}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+        tool.execute(
+            json!({"action": "update_task", "task_id": "t2", "status": "done"}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join("PROJECT.md")).unwrap();
+        assert!(
+            content.contains("- [ ] T1 Define scope and acceptance criteria (progress: 50%)\n  - ")
+        );
+        assert!(content.contains("- [x] T2 Implement\n"));
+        assert!(content.contains("- [ ] T6 Announce (priority: high, due: 2000-01-01)"));
+
+        let out = tool
+            .execute(json!({"action": "list_tasks", "status": "done"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            out.for_llm,
+            "1 done task(s):\n- T2 Implement [done, medium]"
+        );
+
+        let out = tool
+            .execute(json!({"action": "project_status"}), &ctx)
+            .await
+            .unwrap();
+        assert!(
+            out.for_llm.contains("6 task(s), 25% complete"),
+            "{}",
+            out.for_llm
+        );
+        assert!(out.for_llm.contains("Overdue:\n- T6 Announce"));
+    }
+
+    #[tokio::test]
+    async fn test_local_task_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = ToolContext::new().with_workspace(&dir.path().to_string_lossy());
+        let tool = ProjectTool::new(ProjectConfig::default());
+
+        let err = tool
+            .execute(json!({"action": "update_task", "task_id": "T1"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not exist"), "{}", err);
+
+        let err = tool
+            .execute(
+                json!({"action": "add_task", "title": "x", "due": "soon"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("YYYY-MM-DD"), "{}", err);
+
+        tool.execute(json!({"action": "add_task", "title": "x"}), &ctx)
+            .await
+            .unwrap();
+        let err = tool
+            .execute(json!({"action": "update_task", "task_id": "T9"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("T9 not found"), "{}", err);
+
+        let err = tool
+            .execute(json!({"action": "list_tasks"}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Workspace not configured"),
+            "{}",
+            err
+        );
+    }
 }
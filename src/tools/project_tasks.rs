@@ -0,0 +1,631 @@
+//! Local task database kept in the workspace's `PROJECT.md`.
+//!
+//! The file stays plain markdown so people (and the heartbeat prompt) can read
+//! and edit it directly. Tasks live under [`TASKS_HEADING`] as checkbox lines
+//! with an ID, a title and optional metadata, followed by indented notes:
+//!
+//! ```text
+//! ## Tasks
+//!
+//! - [ ] T1 Write release notes (priority: high, due: 2026-10-20, progress: 40%)
+//!   - 2026-10-16: outline drafted
+//! - [x] T2 Tag the release
+//! ```
+//!
+//! Lines the parser does not recognise are kept as they are, so hand edits
+//! survive every rewrite.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+
+/// File name of the project file, relative to the workspace.
+pub const PROJECT_FILE: &str = "PROJECT.md";
+
+/// Heading of the section that holds tasks.
+pub const TASKS_HEADING: &str = "## Tasks";
+
+/// Tasks due within this many days count as "due soon" in the status summary.
+const DUE_SOON_DAYS: i64 = 7;
+
+/// Starter task templates for `init`, by name.
+const TEMPLATES: &[(&str, &[&str])] = &[
+    ("blank", &[]),
+    (
+        "software",
+        &[
+            "Define scope and acceptance criteria",
+            "Implement",
+            "Write tests",
+            "Review",
+            "Release",
+        ],
+    ),
+    (
+        "research",
+        &[
+            "Frame the question",
+            "Collect sources",
+            "Summarise findings",
+            "Write report",
+        ],
+    ),
+    (
+        "event",
+        &[
+            "Fix date and venue",
+            "Send invitations",
+            "Confirm attendees",
+            "Prepare agenda",
+            "Follow up",
+        ],
+    ),
+];
+
+/// Names of the built-in task templates.
+pub fn template_names() -> Vec<&'static str> {
+    TEMPLATES.iter().map(|(name, _)| *name).collect()
+}
+
+/// Task priority, highest last so it sorts naturally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum TaskPriority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
+impl TaskPriority {
+    /// Parse "low", "medium", "high" or "urgent" (case-insensitive).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "low" => Some(Self::Low),
+            "medium" | "normal" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            "urgent" | "critical" => Some(Self::Urgent),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TaskPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Urgent => "urgent",
+        })
+    }
+}
+
+/// A task parsed from the project file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectTask {
+    /// Numeric ID, shown as `T<id>`.
+    pub id: u32,
+    pub title: String,
+    pub done: bool,
+    pub priority: TaskPriority,
+    pub due: Option<NaiveDate>,
+    /// Percent complete, 0–100.
+    pub progress: u8,
+    pub blocked: bool,
+    /// Indented note lines, without the leading `- `.
+    pub notes: Vec<String>,
+}
+
+impl ProjectTask {
+    /// Human-readable state: done, blocked, in progress or todo.
+    pub fn state(&self) -> &'static str {
+        if self.done {
+            "done"
+        } else if self.blocked {
+            "blocked"
+        } else if self.progress > 0 {
+            "in progress"
+        } else {
+            "todo"
+        }
+    }
+
+    fn render(&self) -> Vec<String> {
+        let mut meta = Vec::new();
+        if self.priority != TaskPriority::default() {
+            meta.push(format!("priority: {}", self.priority));
+        }
+        if let Some(due) = self.due {
+            meta.push(format!("due: {}", due));
+        }
+        if self.progress > 0 && !self.done {
+            meta.push(format!("progress: {}%", self.progress));
+        }
+        if self.blocked && !self.done {
+            meta.push("blocked".to_string());
+        }
+        let mut line = format!(
+            "- [{}] T{} {}",
+            if self.done { 'x' } else { ' ' },
+            self.id,
+            self.title
+        );
+        if !meta.is_empty() {
+            line.push_str(&format!(" ({})", meta.join(", ")));
+        }
+        let mut lines = vec![line];
+        lines.extend(self.notes.iter().map(|n| format!("  - {}", n)));
+        lines
+    }
+
+    /// One-line summary used in tool output.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![self.state().to_string(), self.priority.to_string()];
+        if let Some(due) = self.due {
+            parts.push(format!("due {}", due));
+        }
+        if self.progress > 0 && !self.done {
+            parts.push(format!("{}%", self.progress));
+        }
+        format!("T{} {} [{}]", self.id, self.title, parts.join(", "))
+    }
+}
+
+/// Metadata parsed from the trailing `(...)` of a task line.
+#[derive(Default)]
+struct Meta {
+    priority: TaskPriority,
+    due: Option<NaiveDate>,
+    progress: u8,
+    blocked: bool,
+}
+
+/// Parse `priority: high, due: 2026-10-20, progress: 40%, blocked`. Returns
+/// `None` if any part is not recognised, so ordinary parentheses in a title
+/// are left alone.
+fn parse_meta(s: &str) -> Option<Meta> {
+    let mut meta = Meta::default();
+    for part in s.split(',').map(str::trim) {
+        if part.eq_ignore_ascii_case("blocked") {
+            meta.blocked = true;
+            continue;
+        }
+        let (key, value) = part.split_once(':')?;
+        let value = value.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "priority" => meta.priority = TaskPriority::parse(value)?,
+            "due" => meta.due = Some(NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?),
+            "progress" => meta.progress = value.trim_end_matches('%').parse::<u8>().ok()?.min(100),
+            _ => return None,
+        }
+    }
+    Some(meta)
+}
+
+/// Parse `- [ ] T3 Title (meta)`.
+fn parse_task_line(line: &str) -> Option<ProjectTask> {
+    let body = line
+        .strip_prefix("- [")
+        .or_else(|| line.strip_prefix("* ["))?;
+    let done = match body.as_bytes().first() {
+        Some(b' ') => false,
+        Some(b'x') | Some(b'X') => true,
+        _ => return None,
+    };
+    let rest = body.get(1..)?.strip_prefix(']')?.trim();
+    let (id, title) = rest.strip_prefix('T')?.split_once(' ')?;
+    let id = id.parse::<u32>().ok()?;
+    let mut title = title.trim();
+
+    let mut meta = Meta::default();
+    if let Some(open) = title.strip_suffix(')').and_then(|t| t.rfind(" (")) {
+        if let Some(parsed) = parse_meta(&title[open + 2..title.len() - 1]) {
+            meta = parsed;
+            title = title[..open].trim_end();
+        }
+    }
+    Some(ProjectTask {
+        id,
+        title: title.to_string(),
+        done,
+        priority: meta.priority,
+        due: meta.due,
+        progress: if done { 100 } else { meta.progress },
+        blocked: meta.blocked,
+        notes: Vec::new(),
+    })
+}
+
+/// A line in the tasks section.
+#[derive(Debug, Clone)]
+enum Item {
+    Task(ProjectTask),
+    Text(String),
+}
+
+/// Parsed `PROJECT.md`.
+#[derive(Debug, Clone)]
+pub struct ProjectFile {
+    /// Lines before the tasks section, heading included.
+    head: Vec<String>,
+    /// Lines of the tasks section.
+    items: Vec<Item>,
+    /// Lines from the next `## ` heading on.
+    tail: Vec<String>,
+}
+
+/// Fields that can be set on a task. `None` leaves a field alone.
+#[derive(Debug, Clone, Default)]
+pub struct TaskChanges {
+    pub title: Option<String>,
+    pub priority: Option<TaskPriority>,
+    /// `Some(None)` clears the deadline.
+    pub due: Option<Option<NaiveDate>>,
+    pub progress: Option<u8>,
+    pub done: Option<bool>,
+    pub blocked: Option<bool>,
+    /// Appended to the task's notes.
+    pub note: Option<String>,
+}
+
+/// Counts and highlights for [`ProjectFile::status`].
+#[derive(Debug, Clone, Default)]
+pub struct ProjectStatus {
+    pub total: usize,
+    pub done: usize,
+    pub in_progress: usize,
+    pub blocked: usize,
+    pub todo: usize,
+    /// Average progress over all tasks, 0–100.
+    pub percent: u8,
+    pub overdue: Vec<ProjectTask>,
+    pub due_soon: Vec<ProjectTask>,
+    /// Open tasks, most important first.
+    pub next_up: Vec<ProjectTask>,
+}
+
+impl ProjectFile {
+    /// Parse a project file. A file without a tasks section gets an empty one.
+    pub fn parse(content: &str) -> Self {
+        let mut head = Vec::new();
+        let mut items = Vec::new();
+        let mut tail = Vec::new();
+        let mut section = 0;
+        for line in content.lines() {
+            match section {
+                0 => {
+                    head.push(line.to_string());
+                    if line.trim() == TASKS_HEADING {
+                        section = 1;
+                    }
+                }
+                1 if line.starts_with("## ") || line.starts_with("# ") => {
+                    section = 2;
+                    tail.push(line.to_string());
+                }
+                1 => {
+                    let note = line
+                        .strip_prefix("  - ")
+                        .or_else(|| line.strip_prefix("  * "));
+                    match (note, items.last_mut()) {
+                        (Some(note), Some(Item::Task(task))) => task.notes.push(note.to_string()),
+                        _ => items.push(match parse_task_line(line.trim_end()) {
+                            Some(task) => Item::Task(task),
+                            None => Item::Text(line.to_string()),
+                        }),
+                    }
+                }
+                _ => tail.push(line.to_string()),
+            }
+        }
+        if section == 0 {
+            if head.last().is_some_and(|l| !l.trim().is_empty()) {
+                head.push(String::new());
+            }
+            head.push(TASKS_HEADING.to_string());
+        }
+        Self { head, items, tail }
+    }
+
+    /// Scaffold a new project file from a template.
+    pub fn scaffold(
+        title: &str,
+        description: &str,
+        template: &str,
+    ) -> std::result::Result<Self, String> {
+        let tasks = TEMPLATES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(template.trim()))
+            .map(|(_, tasks)| *tasks)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown template '{}'. Available: {}",
+                    template,
+                    template_names().join(", ")
+                )
+            })?;
+        let description = description.trim();
+        let mut content = format!("# {}\n\n", title.trim());
+        if !description.is_empty() {
+            content.push_str(description);
+            content.push_str("\n\n");
+        }
+        content.push_str(TASKS_HEADING);
+        content.push('\n');
+        let mut file = Self::parse(&content);
+        for task in tasks {
+            file.add(task, TaskPriority::default(), None);
+        }
+        Ok(file)
+    }
+
+    /// Render back to markdown.
+    pub fn render(&self) -> String {
+        let mut lines = self.head.clone();
+        let mut items: Vec<String> = Vec::new();
+        for item in &self.items {
+            match item {
+                Item::Task(task) => items.extend(task.render()),
+                Item::Text(text) => items.push(text.clone()),
+            }
+        }
+        // Exactly one blank line after the heading and before the next section.
+        while items.first().is_some_and(|l| l.trim().is_empty()) {
+            items.remove(0);
+        }
+        while items.last().is_some_and(|l| l.trim().is_empty()) {
+            items.pop();
+        }
+        lines.push(String::new());
+        if !items.is_empty() {
+            lines.extend(items);
+            lines.push(String::new());
+        }
+        lines.extend(self.tail.iter().cloned());
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Tasks in file order.
+    pub fn tasks(&self) -> impl Iterator<Item = &ProjectTask> {
+        self.items.iter().filter_map(|item| match item {
+            Item::Task(task) => Some(task),
+            Item::Text(_) => None,
+        })
+    }
+
+    fn task_mut(&mut self, id: u32) -> Option<&mut ProjectTask> {
+        self.items.iter_mut().find_map(|item| match item {
+            Item::Task(task) if task.id == id => Some(task),
+            _ => None,
+        })
+    }
+
+    /// Append a task after the last existing one and return it.
+    pub fn add(
+        &mut self,
+        title: &str,
+        priority: TaskPriority,
+        due: Option<NaiveDate>,
+    ) -> ProjectTask {
+        let id = self.tasks().map(|t| t.id).max().unwrap_or(0) + 1;
+        let task = ProjectTask {
+            id,
+            title: title.trim().to_string(),
+            done: false,
+            priority,
+            due,
+            progress: 0,
+            blocked: false,
+            notes: Vec::new(),
+        };
+        let at = self
+            .items
+            .iter()
+            .rposition(|item| matches!(item, Item::Task(_)))
+            .map_or(self.items.len(), |i| i + 1);
+        self.items.insert(at, Item::Task(task.clone()));
+        task
+    }
+
+    /// Apply `changes` to task `id`. Marking a task done sets progress to
+    /// 100; setting progress to 100 marks it done.
+    pub fn update(&mut self, id: u32, changes: TaskChanges) -> Option<&ProjectTask> {
+        let task = self.task_mut(id)?;
+        if let Some(title) = changes.title {
+            task.title = title.trim().to_string();
+        }
+        if let Some(priority) = changes.priority {
+            task.priority = priority;
+        }
+        if let Some(due) = changes.due {
+            task.due = due;
+        }
+        if let Some(blocked) = changes.blocked {
+            task.blocked = blocked;
+        }
+        if let Some(progress) = changes.progress {
+            task.progress = progress.min(100);
+            task.done = task.progress == 100;
+        }
+        if let Some(done) = changes.done {
+            task.done = done;
+            task.progress = if done { 100 } else { task.progress.min(99) };
+        }
+        if let Some(note) = changes.note.filter(|n| !n.trim().is_empty()) {
+            task.notes.push(note.trim().replace('\n', " "));
+        }
+        Some(task)
+    }
+
+    /// Summarise progress as of `today`.
+    pub fn status(&self, today: NaiveDate) -> ProjectStatus {
+        let mut status = ProjectStatus::default();
+        let mut progress_sum = 0usize;
+        for task in self.tasks() {
+            status.total += 1;
+            progress_sum += task.progress as usize;
+            match task.state() {
+                "done" => status.done += 1,
+                "blocked" => status.blocked += 1,
+                "in progress" => status.in_progress += 1,
+                _ => status.todo += 1,
+            }
+            if task.done {
+                continue;
+            }
+            match task.due.map(|d| (d - today).num_days()) {
+                Some(days) if days < 0 => status.overdue.push(task.clone()),
+                Some(days) if days <= DUE_SOON_DAYS => status.due_soon.push(task.clone()),
+                _ => {}
+            }
+            status.next_up.push(task.clone());
+        }
+        status.percent = progress_sum.checked_div(status.total).unwrap_or(0) as u8;
+        status.overdue.sort_by_key(|t| t.due);
+        status.due_soon.sort_by_key(|t| t.due);
+        status.next_up.sort_by_key(|t| {
+            (
+                t.blocked,
+                std::cmp::Reverse(t.priority),
+                t.due.is_none(),
+                t.due,
+            )
+        });
+        status
+    }
+}
+
+impl ProjectStatus {
+    /// Render as a short report, listing at most `limit` tasks per section.
+    pub fn render(&self, limit: usize) -> String {
+        if self.total == 0 {
+            return "No tasks yet.".to_string();
+        }
+        let mut out = format!(
+            "{} task(s), {}% complete: {} done, {} in progress, {} blocked, {} todo",
+            self.total, self.percent, self.done, self.in_progress, self.blocked, self.todo
+        );
+        for (label, tasks) in [
+            ("Overdue", &self.overdue),
+            ("Due soon", &self.due_soon),
+            ("Next up", &self.next_up),
+        ] {
+            if tasks.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n\n{}:", label));
+            for task in tasks.iter().take(limit) {
+                out.push_str("\n- ");
+                out.push_str(&task.summary());
+            }
+            if tasks.len() > limit {
+                out.push_str(&format!("\n- ... and {} more", tasks.len() - limit));
+            }
+        }
+        out
+    }
+}
+
+/// Parse a task ID given as `T3`, `t3` or `3`.
+pub fn parse_task_id(s: &str) -> Option<u32> {
+    let s = s.trim();
+    s.strip_prefix(['T', 't']).unwrap_or(s).parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    const SAMPLE: &str = "# Launch\n\nShip v2.\n\n## Tasks\n\n\
+        - [ ] T1 Write notes (priority: high, due: 2026-10-20, progress: 40%)\n  - outline drafted\n\
+        - [x] T2 Tag release\n\
+        - [ ] T3 Fix bug (see #12) (blocked)\n\
+        Free text stays.\n\n## Notes\n\nKeep me.\n";
+
+    #[test]
+    fn test_parse_and_render_round_trip() {
+        let file = ProjectFile::parse(SAMPLE);
+        let tasks: Vec<_> = file.tasks().collect();
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].priority, TaskPriority::High);
+        assert_eq!(tasks[0].due, Some(date("2026-10-20")));
+        assert_eq!(tasks[0].progress, 40);
+        assert_eq!(tasks[0].notes, vec!["outline drafted"]);
+        assert!(tasks[1].done);
+        assert_eq!(tasks[2].title, "Fix bug (see #12)");
+        assert!(tasks[2].blocked);
+        assert_eq!(file.render(), SAMPLE);
+    }
+
+    #[test]
+    fn test_add_and_update() {
+        let mut file = ProjectFile::parse(SAMPLE);
+        let id = file.add("Announce", TaskPriority::Low, None).id;
+        assert_eq!(id, 4);
+        file.update(
+            1,
+            TaskChanges {
+                progress: Some(100),
+                note: Some("published".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(file.update(99, TaskChanges::default()).is_none());
+
+        let rendered = file.render();
+        assert!(rendered.contains(
+            "- [x] T1 Write notes (priority: high, due: 2026-10-20)\n  - outline drafted\n  - published\n"
+        ));
+        assert!(rendered.contains("- [ ] T4 Announce (priority: low)\nFree text stays."));
+    }
+
+    #[test]
+    fn test_scaffold_and_missing_section() {
+        let file = ProjectFile::scaffold("Site", "Rebuild the site.", "software").unwrap();
+        assert_eq!(file.tasks().count(), 5);
+        assert!(file
+            .render()
+            .starts_with("# Site\n\nRebuild the site.\n\n## Tasks\n\n- [ ] T1 Define scope"));
+        assert!(ProjectFile::scaffold("x", "", "nope").is_err());
+
+        let mut file = ProjectFile::parse("# Notes only\n");
+        file.add("First", TaskPriority::default(), None);
+        assert_eq!(
+            file.render(),
+            "# Notes only\n\n## Tasks\n\n- [ ] T1 First\n"
+        );
+    }
+
+    #[test]
+    fn test_status_summary() {
+        let mut file = ProjectFile::parse(SAMPLE);
+        file.add(
+            "Urgent thing",
+            TaskPriority::Urgent,
+            Some(date("2026-10-10")),
+        );
+        let status = file.status(date("2026-10-16"));
+        assert_eq!((status.total, status.done, status.blocked), (4, 1, 1));
+        assert_eq!((status.in_progress, status.todo), (1, 1));
+        assert_eq!(status.percent, 35);
+        assert_eq!(status.overdue[0].id, 4);
+        assert_eq!(status.due_soon[0].id, 1);
+        let order: Vec<u32> = status.next_up.iter().map(|t| t.id).collect();
+        assert_eq!(order, vec![4, 1, 3]);
+
+        let report = status.render(5);
+        assert!(report.starts_with("4 task(s), 35% complete"));
+        assert!(report.contains("Overdue:\n- T4 Urgent thing [todo, urgent, due 2026-10-10]"));
+        assert_eq!(parse_task_id("T7"), Some(7));
+        assert_eq!(parse_task_id("7"), Some(7));
+    }
+}
@@ -28,13 +28,13 @@
 //! ```
 
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::time::Duration;
 use tracing::{debug, info, warn};
 
 use crate::error::{Result, ZeptoError};
+use crate::utils::http::Client;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
@@ -60,7 +60,7 @@ impl R8rTool {
     /// # Arguments
     /// * `endpoint` - The r8r server endpoint (e.g., "http://localhost:8080")
     pub fn new(endpoint: &str) -> Self {
-        let client = match Client::builder()
+        let client = match crate::utils::http::client_builder()
             .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
             .build()
         {
@@ -0,0 +1,899 @@
+//! Ranking tool: score and rank a batch of items against weighted criteria.
+//!
+//! Criteria come from named rubrics in `tools.ranking.rubrics`, or inline
+//! in the call. Each criterion reads a numeric field of every item (text
+//! values can be mapped to numbers), normalizes it to 0..1 between the
+//! configured or observed bounds, and contributes its weight to a 0-100
+//! score. Every scoring run is appended to
+//! `<workspace>/.zeptoclaw/ranking/history.jsonl` so later calls can show
+//! how an item's score moved over time.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::config::{RankingCriterion, RankingDirection, RankingToolConfig};
+use crate::error::{Result, ZeptoError};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// History file, relative to the workspace.
+const HISTORY_FILE: &str = ".zeptoclaw/ranking/history.jsonl";
+
+/// Largest batch accepted by one `score` call.
+const MAX_ITEMS: usize = 1000;
+
+/// Ranked items shown when `top` is not given.
+const DEFAULT_TOP: usize = 50;
+
+/// History entries shown when `limit` is not given.
+const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+/// Label recorded for runs scored with inline criteria and no rubric name.
+const INLINE_RUBRIC: &str = "inline";
+
+/// Fields tried, in order, to name an item when `id_field` is not given.
+const LABEL_FIELDS: &[&str] = &["id", "name", "title"];
+
+/// One scored item in one run, as stored in the history file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct HistoryRecord {
+    at: DateTime<Utc>,
+    rubric: String,
+    item: String,
+    score: f64,
+    rank: usize,
+    of: usize,
+}
+
+/// Append-only JSONL log of scoring runs.
+struct RankingHistory {
+    path: PathBuf,
+}
+
+impl RankingHistory {
+    fn new(workspace: &Path) -> Self {
+        Self {
+            path: workspace.join(HISTORY_FILE),
+        }
+    }
+
+    /// All readable records, oldest first. Malformed lines are skipped.
+    fn load(&self) -> Result<Vec<HistoryRecord>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(raw) => Ok(raw
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Append `records`, dropping the oldest so at most `max` remain.
+    fn append(&self, records: &[HistoryRecord], max: usize) -> Result<()> {
+        let mut all = self.load()?;
+        all.extend_from_slice(records);
+        let excess = all.len().saturating_sub(max);
+        let mut out = String::new();
+        for record in &all[excess..] {
+            out.push_str(&serde_json::to_string(record)?);
+            out.push('\n');
+        }
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, out)?;
+        Ok(())
+    }
+}
+
+/// An item's position and score in one run.
+#[derive(Debug)]
+struct Scored {
+    label: String,
+    score: f64,
+    /// Normalized value per criterion; `None` where the item had no value
+    parts: Vec<Option<f64>>,
+}
+
+/// Follow a dotted path into objects (and arrays, by index).
+fn lookup<'a>(item: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(item, |value, key| match value {
+        Value::Object(map) => map.get(key),
+        Value::Array(list) => key.parse::<usize>().ok().and_then(|i| list.get(i)),
+        _ => None,
+    })
+}
+
+/// Numeric value of `criterion` for `item`: numbers and booleans directly,
+/// text through the criterion's `values` map or as a number ("1,200", "85%").
+fn criterion_value(item: &Value, criterion: &RankingCriterion) -> Option<f64> {
+    let value = match lookup(item, &criterion.field)? {
+        Value::Number(n) => n.as_f64(),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        Value::String(text) => {
+            let text = text.trim();
+            criterion
+                .values
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(text))
+                .map(|(_, v)| *v)
+                .or_else(|| text.trim_end_matches('%').replace(',', "").parse().ok())
+        }
+        _ => None,
+    };
+    value.filter(|v| v.is_finite())
+}
+
+/// Display name of an item: `id_field`, else the first of [`LABEL_FIELDS`]
+/// present, else its 1-based position.
+fn item_label(item: &Value, index: usize, id_field: Option<&str>) -> String {
+    let fields: Vec<&str> = match id_field {
+        Some(field) => vec![field],
+        None => LABEL_FIELDS.to_vec(),
+    };
+    fields
+        .iter()
+        .find_map(|field| match lookup(item, field)? {
+            Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+        .unwrap_or_else(|| format!("#{}", index + 1))
+}
+
+fn validate_criteria(criteria: &[RankingCriterion]) -> Result<()> {
+    if criteria.is_empty() {
+        return Err(ZeptoError::Tool(
+            "At least one criterion is required".into(),
+        ));
+    }
+    for c in criteria {
+        if c.field.trim().is_empty() {
+            return Err(ZeptoError::Tool(
+                "Criterion 'field' must not be empty".into(),
+            ));
+        }
+        if !c.weight.is_finite() || c.weight < 0.0 {
+            return Err(ZeptoError::Tool(format!(
+                "Criterion '{}' has an invalid weight {}",
+                c.field, c.weight
+            )));
+        }
+        if let (Some(min), Some(max)) = (c.min, c.max) {
+            if min >= max {
+                return Err(ZeptoError::Tool(format!(
+                    "Criterion '{}' needs min < max",
+                    c.field
+                )));
+            }
+        }
+    }
+    if criteria.iter().all(|c| c.weight == 0.0) {
+        return Err(ZeptoError::Tool(
+            "At least one criterion needs a positive weight".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Score every item and return them best first. Ties keep input order.
+fn score_items(
+    items: &[Value],
+    criteria: &[RankingCriterion],
+    id_field: Option<&str>,
+) -> Vec<Scored> {
+    let raw: Vec<Vec<Option<f64>>> = criteria
+        .iter()
+        .map(|c| items.iter().map(|item| criterion_value(item, c)).collect())
+        .collect();
+    let bounds: Vec<(f64, f64)> = criteria
+        .iter()
+        .zip(&raw)
+        .map(|(c, values)| {
+            let present = values.iter().flatten().copied();
+            let lo = c
+                .min
+                .unwrap_or_else(|| present.clone().fold(f64::INFINITY, f64::min));
+            let hi = c
+                .max
+                .unwrap_or_else(|| present.fold(f64::NEG_INFINITY, f64::max));
+            (lo, hi)
+        })
+        .collect();
+    let total_weight: f64 = criteria.iter().map(|c| c.weight).sum();
+
+    let mut scored: Vec<Scored> = items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let parts: Vec<Option<f64>> = criteria
+                .iter()
+                .enumerate()
+                .map(|(k, c)| {
+                    let (lo, hi) = bounds[k];
+                    raw[k][i].map(|v| {
+                        let n = if hi > lo {
+                            ((v - lo) / (hi - lo)).clamp(0.0, 1.0)
+                        } else {
+                            1.0
+                        };
+                        match c.direction {
+                            RankingDirection::Higher => n,
+                            RankingDirection::Lower => 1.0 - n,
+                        }
+                    })
+                })
+                .collect();
+            let weighted: f64 = criteria
+                .iter()
+                .zip(&parts)
+                .map(|(c, part)| c.weight * part.unwrap_or(0.0))
+                .sum();
+            Scored {
+                label: item_label(item, i, id_field),
+                score: (weighted / total_weight * 1000.0).round() / 10.0,
+                parts,
+            }
+        })
+        .collect();
+    scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+    scored
+}
+
+fn describe_criteria(criteria: &[RankingCriterion]) -> String {
+    criteria
+        .iter()
+        .map(|c| {
+            let mut text = c.field.clone();
+            if c.weight != 1.0 {
+                text.push_str(&format!(" x{}", c.weight));
+            }
+            if c.direction == RankingDirection::Lower {
+                text.push_str(" (lower is better)");
+            }
+            text
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_ranking(
+    rubric: &str,
+    criteria: &[RankingCriterion],
+    scored: &[Scored],
+    top: usize,
+) -> String {
+    let mut out = format!(
+        "Ranked {} item(s) by '{}': {}\n",
+        scored.len(),
+        rubric,
+        describe_criteria(criteria)
+    );
+    for (rank, item) in scored.iter().take(top).enumerate() {
+        let parts: Vec<String> = criteria
+            .iter()
+            .zip(&item.parts)
+            .map(|(c, part)| match part {
+                Some(v) => format!("{} {:.2}", c.field, v),
+                None => format!("{} -", c.field),
+            })
+            .collect();
+        out.push_str(&format!(
+            "{}. {} - {:.1} [{}]\n",
+            rank + 1,
+            item.label,
+            item.score,
+            parts.join(", ")
+        ));
+    }
+    if scored.len() > top {
+        out.push_str(&format!("... {} more\n", scored.len() - top));
+    }
+    let missing: Vec<String> = scored
+        .iter()
+        .filter_map(|item| {
+            let fields: Vec<&str> = criteria
+                .iter()
+                .zip(&item.parts)
+                .filter(|(_, part)| part.is_none())
+                .map(|(c, _)| c.field.as_str())
+                .collect();
+            (!fields.is_empty()).then(|| format!("{} ({})", item.label, fields.join(", ")))
+        })
+        .collect();
+    if !missing.is_empty() {
+        out.push_str(&format!(
+            "Missing values scored 0: {}\n",
+            missing.join("; ")
+        ));
+    }
+    out
+}
+
+/// Score history of one item: each run, then the overall trend.
+fn render_item_history(item: &str, records: &[&HistoryRecord], limit: usize) -> String {
+    let skip = records.len().saturating_sub(limit);
+    let mut out = format!("History of '{}' ({} run(s)):\n", item, records.len());
+    for r in &records[skip..] {
+        out.push_str(&format!(
+            "{} [{}] {:.1} (rank {}/{})\n",
+            r.at.format("%Y-%m-%d %H:%M"),
+            r.rubric,
+            r.score,
+            r.rank,
+            r.of
+        ));
+    }
+    let scores: Vec<f64> = records.iter().map(|r| r.score).collect();
+    if let (Some(first), Some(last)) = (scores.first(), scores.last()) {
+        let best = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let average = scores.iter().sum::<f64>() / scores.len() as f64;
+        out.push_str(&format!(
+            "Trend: {:.1} -> {:.1} ({:+.1}); best {:.1}, average {:.1}\n",
+            first,
+            last,
+            last - first,
+            best,
+            average
+        ));
+    }
+    out
+}
+
+/// Latest score per item and rubric, with the change since the run before.
+fn render_latest(records: &[&HistoryRecord], limit: usize) -> String {
+    let mut series: Vec<((&str, &str), Vec<&HistoryRecord>)> = Vec::new();
+    let mut index: HashMap<(&str, &str), usize> = HashMap::new();
+    for r in records {
+        let key = (r.rubric.as_str(), r.item.as_str());
+        let slot = *index.entry(key).or_insert_with(|| {
+            series.push((key, Vec::new()));
+            series.len() - 1
+        });
+        series[slot].1.push(r);
+    }
+    series.sort_by(|(a_key, a), (b_key, b)| {
+        a_key.0.cmp(b_key.0).then_with(|| {
+            let a_score = a.last().map_or(0.0, |r| r.score);
+            let b_score = b.last().map_or(0.0, |r| r.score);
+            b_score.total_cmp(&a_score)
+        })
+    });
+
+    let mut out = format!("Latest scores ({} item(s)):\n", series.len());
+    for ((rubric, item), runs) in series.iter().take(limit) {
+        let last = runs[runs.len() - 1];
+        let change = match runs.len() {
+            1 => "first run".to_string(),
+            n => format!("{:+.1} vs previous", last.score - runs[n - 2].score),
+        };
+        out.push_str(&format!(
+            "{} [{}] {:.1} (rank {}/{}), {}, {} run(s)\n",
+            item,
+            rubric,
+            last.score,
+            last.rank,
+            last.of,
+            change,
+            runs.len()
+        ));
+    }
+    if series.len() > limit {
+        out.push_str(&format!("... {} more\n", series.len() - limit));
+    }
+    out
+}
+
+/// Score, rank and track items against configurable weighted criteria.
+pub struct RankTool {
+    rubrics: HashMap<String, Vec<RankingCriterion>>,
+    max_history: usize,
+    /// Serializes history rewrites between concurrent calls.
+    history_lock: Mutex<()>,
+}
+
+impl RankTool {
+    /// Create the tool from `tools.ranking`.
+    pub fn new(config: RankingToolConfig) -> Self {
+        Self {
+            rubrics: config.rubrics,
+            max_history: config.max_history.max(1),
+            history_lock: Mutex::new(()),
+        }
+    }
+
+    fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+        args.get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+
+    fn usize_arg(args: &Value, key: &str, default: usize) -> usize {
+        args.get(key)
+            .and_then(Value::as_u64)
+            .map(|v| (v as usize).max(1))
+            .unwrap_or(default)
+    }
+
+    /// Items to score: a JSON array, or a string holding one.
+    fn items_arg(args: &Value) -> Result<Vec<Value>> {
+        let items = match args.get("items") {
+            Some(Value::Array(items)) => items.clone(),
+            Some(Value::String(text)) => serde_json::from_str::<Vec<Value>>(text)
+                .map_err(|e| ZeptoError::Tool(format!("'items' is not a JSON array: {}", e)))?,
+            _ => {
+                return Err(ZeptoError::Tool(
+                    "Missing required field: items (a JSON array of objects)".into(),
+                ))
+            }
+        };
+        if items.is_empty() {
+            return Err(ZeptoError::Tool("'items' must not be empty".into()));
+        }
+        if items.len() > MAX_ITEMS {
+            return Err(ZeptoError::Tool(format!(
+                "Too many items ({}); at most {} can be scored at once",
+                items.len(),
+                MAX_ITEMS
+            )));
+        }
+        Ok(items)
+    }
+
+    /// Rubric name and criteria for a call: inline `criteria`, else the
+    /// configured `rubric`.
+    fn criteria_arg(&self, args: &Value) -> Result<(String, Vec<RankingCriterion>)> {
+        let rubric = Self::str_arg(args, "rubric");
+        let (name, criteria) = match (args.get("criteria"), rubric) {
+            (Some(inline), _) if !inline.is_null() => {
+                let criteria: Vec<RankingCriterion> = serde_json::from_value(inline.clone())
+                    .map_err(|e| ZeptoError::Tool(format!("Invalid criteria: {}", e)))?;
+                (rubric.unwrap_or(INLINE_RUBRIC).to_string(), criteria)
+            }
+            (_, Some(name)) => {
+                let criteria = self.rubrics.get(name).cloned().ok_or_else(|| {
+                    ZeptoError::Tool(format!(
+                        "Unknown rubric '{}'. Configured rubrics: {}",
+                        name,
+                        self.rubric_names().join(", ")
+                    ))
+                })?;
+                (name.to_string(), criteria)
+            }
+            _ => {
+                return Err(ZeptoError::Tool(
+                    "Provide 'rubric' (a configured rubric) or inline 'criteria'".into(),
+                ))
+            }
+        };
+        validate_criteria(&criteria)?;
+        Ok((name, criteria))
+    }
+
+    fn rubric_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.rubrics.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        if names.is_empty() {
+            names.push("(none)");
+        }
+        names
+    }
+
+    fn list_rubrics(&self) -> String {
+        if self.rubrics.is_empty() {
+            return "No rubrics configured. Add them under tools.ranking.rubrics, or pass \
+                    inline 'criteria'."
+                .to_string();
+        }
+        let mut out = String::from("Configured rubrics:\n");
+        for name in self.rubric_names() {
+            out.push_str(&format!(
+                "- {}: {}\n",
+                name,
+                describe_criteria(&self.rubrics[name])
+            ));
+        }
+        out
+    }
+
+    async fn score(&self, args: &Value, ctx: &ToolContext) -> Result<String> {
+        let items = Self::items_arg(args)?;
+        let (rubric, criteria) = self.criteria_arg(args)?;
+        let scored = score_items(&items, &criteria, Self::str_arg(args, "id_field"));
+        let mut out = render_ranking(
+            &rubric,
+            &criteria,
+            &scored,
+            Self::usize_arg(args, "top", DEFAULT_TOP),
+        );
+
+        if args.get("record").and_then(Value::as_bool) == Some(false) {
+            return Ok(out);
+        }
+        let Some(workspace) = ctx.workspace.as_deref() else {
+            out.push_str("History not recorded: no workspace.");
+            return Ok(out);
+        };
+        let at = Utc::now();
+        let records: Vec<HistoryRecord> = scored
+            .iter()
+            .enumerate()
+            .map(|(rank, item)| HistoryRecord {
+                at,
+                rubric: rubric.clone(),
+                item: item.label.clone(),
+                score: item.score,
+                rank: rank + 1,
+                of: scored.len(),
+            })
+            .collect();
+        let history = RankingHistory::new(Path::new(workspace));
+        let max = self.max_history;
+        let _guard = self.history_lock.lock().await;
+        tokio::task::spawn_blocking(move || history.append(&records, max))
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("History task failed: {}", e)))??;
+        out.push_str(&format!("Recorded in history under '{}'.", rubric));
+        Ok(out)
+    }
+
+    async fn history(&self, args: &Value, ctx: &ToolContext) -> Result<String> {
+        let workspace = ctx
+            .workspace
+            .as_deref()
+            .ok_or_else(|| ZeptoError::Tool("Ranking history requires a workspace".into()))?;
+        let history = RankingHistory::new(Path::new(workspace));
+        let records = {
+            let _guard = self.history_lock.lock().await;
+            tokio::task::spawn_blocking(move || history.load())
+                .await
+                .map_err(|e| ZeptoError::Tool(format!("History task failed: {}", e)))??
+        };
+        let rubric = Self::str_arg(args, "rubric");
+        let item = Self::str_arg(args, "item");
+        let matching: Vec<&HistoryRecord> = records
+            .iter()
+            .filter(|r| rubric.is_none_or(|name| r.rubric == name))
+            .filter(|r| item.is_none_or(|name| r.item.eq_ignore_ascii_case(name)))
+            .collect();
+        if matching.is_empty() {
+            return Ok("No matching ranking history.".to_string());
+        }
+        let limit = Self::usize_arg(args, "limit", DEFAULT_HISTORY_LIMIT);
+        Ok(match item {
+            Some(name) => render_item_history(name, &matching, limit),
+            None => render_latest(&matching, limit),
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for RankTool {
+    fn name(&self) -> &str {
+        "rank"
+    }
+
+    fn description(&self) -> &str {
+        "Score and rank a list of items (JSON objects) against weighted criteria. 'score' \
+         ranks the items by a configured rubric or inline criteria and records the run; \
+         'rubrics' lists configured rubrics; 'history' shows the latest scores per item, \
+         or one item's scores over time with its trend."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Score and rank items by criteria"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::FilesystemWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["score", "rubrics", "history"],
+                    "description": "Operation to perform (default: score)"
+                },
+                "items": {
+                    "type": "array",
+                    "items": { "type": "object" },
+                    "description": "For score: the items to rank (JSON objects)"
+                },
+                "rubric": {
+                    "type": "string",
+                    "description": "Configured rubric to score with; for history, only this rubric"
+                },
+                "criteria": {
+                    "type": "array",
+                    "description": "For score: inline criteria instead of a configured rubric",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "field": { "type": "string", "description": "Item field; dots reach nested fields" },
+                            "weight": { "type": "number", "description": "Relative weight (default 1)" },
+                            "direction": { "type": "string", "enum": ["higher", "lower"] },
+                            "min": { "type": "number", "description": "Value scoring 0 (default: batch minimum)" },
+                            "max": { "type": "number", "description": "Value scoring 1 (default: batch maximum)" },
+                            "values": { "type": "object", "description": "Numbers for text values, e.g. {\"high\": 3}" }
+                        },
+                        "required": ["field"]
+                    }
+                },
+                "id_field": {
+                    "type": "string",
+                    "description": "For score: field naming each item (default: id, name or title)"
+                },
+                "top": {
+                    "type": "integer",
+                    "description": "For score: ranked items to show (default: 50)"
+                },
+                "record": {
+                    "type": "boolean",
+                    "description": "For score: record the run in history (default: true)"
+                },
+                "item": {
+                    "type": "string",
+                    "description": "For history: show this item's scores over time"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "For history: entries to show (default: 20)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let output = match Self::str_arg(&args, "action").unwrap_or("score") {
+            "score" => self.score(&args, ctx).await?,
+            "rubrics" => self.list_rubrics(),
+            "history" => self.history(&args, ctx).await?,
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown action '{}'. Use score, rubrics or history",
+                    other
+                )))
+            }
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn criterion(field: &str) -> RankingCriterion {
+        serde_json::from_value(json!({ "field": field })).unwrap()
+    }
+
+    fn vendors() -> Value {
+        json!([
+            {"name": "acme", "price": 100, "rating": 4.0, "support": "high"},
+            {"name": "globex", "price": 80, "rating": 3.0, "support": "low"},
+            {"name": "initech", "price": 120, "rating": 5.0, "support": "medium"}
+        ])
+    }
+
+    fn tool() -> RankTool {
+        let mut config = RankingToolConfig::default();
+        config.rubrics.insert(
+            "vendors".to_string(),
+            serde_json::from_value(json!([
+                {"field": "price", "direction": "lower"},
+                {"field": "rating", "weight": 2, "min": 0, "max": 5}
+            ]))
+            .unwrap(),
+        );
+        RankTool::new(config)
+    }
+
+    fn ctx(dir: &TempDir) -> ToolContext {
+        ToolContext::new().with_workspace(dir.path().to_str().unwrap())
+    }
+
+    #[test]
+    fn test_criterion_value() {
+        let mut c = criterion("meta.support");
+        c.values.insert("High".to_string(), 3.0);
+        assert_eq!(
+            criterion_value(&json!({"meta": {"support": "high"}}), &c),
+            Some(3.0)
+        );
+        let c = criterion("share");
+        assert_eq!(
+            criterion_value(&json!({"share": "1,250%"}), &c),
+            Some(1250.0)
+        );
+        assert_eq!(criterion_value(&json!({"share": true}), &c), Some(1.0));
+        assert_eq!(criterion_value(&json!({"share": "n/a"}), &c), None);
+        assert_eq!(criterion_value(&json!({}), &c), None);
+        let c = criterion("tags.1");
+        assert_eq!(criterion_value(&json!({"tags": [1, 7]}), &c), Some(7.0));
+    }
+
+    #[test]
+    fn test_score_items_weights_and_direction() {
+        let tool = tool();
+        let items = vendors().as_array().unwrap().clone();
+        let scored = score_items(&items, &tool.rubrics["vendors"], None);
+        let order: Vec<&str> = scored.iter().map(|s| s.label.as_str()).collect();
+        // price (lower): acme 0.5, globex 1.0, initech 0.0
+        // rating (x2, 0..5): acme 0.8, globex 0.6, initech 1.0
+        assert_eq!(order, vec!["globex", "acme", "initech"]);
+        assert_eq!(scored[0].score, 73.3);
+        assert_eq!(scored[1].score, 70.0);
+        assert_eq!(scored[2].score, 66.7);
+    }
+
+    #[test]
+    fn test_score_items_missing_and_labels() {
+        let items = vec![
+            json!({"v": 5}),
+            json!({"sku": "b"}),
+            json!({"sku": "c", "v": 1}),
+        ];
+        let scored = score_items(&items, &[criterion("v")], Some("sku"));
+        assert_eq!(scored[0].label, "#1");
+        assert_eq!(scored[0].score, 100.0);
+        // Ties keep input order; a missing value scores 0.
+        assert_eq!(scored[1].label, "b");
+        assert!(scored[1].parts[0].is_none());
+        assert_eq!(scored[2].label, "c");
+        assert_eq!(scored[2].score, 0.0);
+    }
+
+    #[test]
+    fn test_validate_criteria() {
+        assert!(validate_criteria(&[]).is_err());
+        let mut c = criterion("x");
+        c.weight = 0.0;
+        assert!(validate_criteria(std::slice::from_ref(&c)).is_err());
+        c.weight = 1.0;
+        c.min = Some(5.0);
+        c.max = Some(5.0);
+        assert!(validate_criteria(&[c]).is_err());
+        assert!(validate_criteria(&[criterion("x")]).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_score_and_history() {
+        let dir = TempDir::new().unwrap();
+        let tool = tool();
+        let out = tool
+            .execute(
+                json!({"action": "score", "rubric": "vendors", "items": vendors()}),
+                &ctx(&dir),
+            )
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(out.contains("1. globex - 73.3"), "got: {}", out);
+        assert!(out.contains("Recorded in history under 'vendors'"));
+
+        // A second run where acme improves, passed as a JSON string.
+        let items = json!([
+            {"name": "acme", "price": 80, "rating": 5.0},
+            {"name": "globex", "price": 100, "rating": 3.0}
+        ]);
+        tool.execute(
+            json!({"rubric": "vendors", "items": items.to_string()}),
+            &ctx(&dir),
+        )
+        .await
+        .unwrap();
+
+        let out = tool
+            .execute(json!({"action": "history", "item": "ACME"}), &ctx(&dir))
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(out.contains("History of 'ACME' (2 run(s))"), "got: {}", out);
+        assert!(out.contains("Trend: 70.0 -> 100.0 (+30.0)"), "got: {}", out);
+
+        let out = tool
+            .execute(json!({"action": "history"}), &ctx(&dir))
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(out.starts_with("Latest scores (3 item(s))"), "got: {}", out);
+        assert!(out.contains("acme [vendors] 100.0 (rank 1/2), +30.0 vs previous"));
+        assert!(out.contains("initech [vendors] 66.7 (rank 3/3), first run"));
+    }
+
+    #[tokio::test]
+    async fn test_inline_criteria_without_recording() {
+        let dir = TempDir::new().unwrap();
+        let tool = RankTool::new(RankingToolConfig::default());
+        let out = tool
+            .execute(
+                json!({
+                    "items": vendors(),
+                    "criteria": [{"field": "support", "values": {"low": 1, "medium": 2, "high": 3}}],
+                    "record": false
+                }),
+                &ctx(&dir),
+            )
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(out.starts_with("Ranked 3 item(s) by 'inline': support"));
+        assert!(out.contains("1. acme - 100.0"));
+        assert!(!dir.path().join(HISTORY_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn test_score_errors() {
+        let tool = tool();
+        let ctx = ToolContext::new();
+        let err = tool
+            .execute(json!({"rubric": "nope", "items": vendors()}), &ctx)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown rubric 'nope'"), "got: {}", err);
+        assert!(err.contains("vendors"));
+
+        let err = tool
+            .execute(json!({"items": vendors()}), &ctx)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'rubric'"), "got: {}", err);
+
+        let err = tool
+            .execute(json!({"rubric": "vendors", "items": "{}"}), &ctx)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not a JSON array"), "got: {}", err);
+
+        // Without a workspace the ranking still works, unrecorded.
+        let out = tool
+            .execute(json!({"rubric": "vendors", "items": vendors()}), &ctx)
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(out.ends_with("History not recorded: no workspace."));
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let dir = TempDir::new().unwrap();
+        let history = RankingHistory::new(dir.path());
+        let record = |score: f64| HistoryRecord {
+            at: Utc::now(),
+            rubric: "r".to_string(),
+            item: "a".to_string(),
+            score,
+            rank: 1,
+            of: 1,
+        };
+        history.append(&[record(1.0), record(2.0)], 3).unwrap();
+        history.append(&[record(3.0), record(4.0)], 3).unwrap();
+        let scores: Vec<f64> = history.load().unwrap().iter().map(|r| r.score).collect();
+        assert_eq!(scores, vec![2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_list_rubrics() {
+        assert!(tool()
+            .list_rubrics()
+            .contains("- vendors: price (lower is better), rating x2"));
+        assert!(RankTool::new(RankingToolConfig::default())
+            .list_rubrics()
+            .starts_with("No rubrics configured"));
+    }
+}
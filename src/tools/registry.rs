@@ -9,10 +9,14 @@ use std::time::Instant;
 use serde_json::Value;
 use tracing::{error, info};
 
+use crate::audit::{log_audit_event, AuditCategory, AuditSeverity};
+use crate::config::ToolLimitsConfig;
 use crate::error::Result;
 use crate::providers::ToolDefinition;
+use crate::security::users::User;
+use crate::security::{CategoryPermission, RolePolicy};
 
-use super::{Tool, ToolContext, ToolOutput};
+use super::{Tool, ToolContext, ToolLimits, ToolOutput};
 
 /// Returns a setup hint for tools that are opt-in (not registered by default).
 fn opt_in_tool_hint(name: &str) -> &'static str {
@@ -47,6 +51,8 @@ fn opt_in_tool_hint(name: &str) -> &'static str {
 /// ```
 pub struct ToolRegistry {
     tools: HashMap<String, Box<dyn Tool>>,
+    /// Refuse calls that [`Tool::is_finance_write`] flags.
+    finance_read_only: bool,
 }
 
 impl ToolRegistry {
@@ -62,9 +68,21 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            finance_read_only: false,
         }
     }
 
+    /// Refuse finance writes (`safety.finance_read_only`). Blocked calls are
+    /// audit-logged and returned to the model as errors.
+    pub fn set_finance_read_only(&mut self, enabled: bool) {
+        self.finance_read_only = enabled;
+    }
+
+    /// Whether finance writes are refused.
+    pub fn finance_read_only(&self) -> bool {
+        self.finance_read_only
+    }
+
     /// Register a new tool in the registry.
     ///
     /// If a tool with the same name already exists, it will be replaced.
@@ -86,6 +104,30 @@ impl ToolRegistry {
         self.tools.insert(name, tool);
     }
 
+    /// Limits for a call of `name`: the `overrides` entry, then the tool's
+    /// own `timeout_secs`/`retries`, then `default_timeout_secs` and no
+    /// retries.
+    pub fn limits_for(
+        &self,
+        name: &str,
+        overrides: &HashMap<String, ToolLimitsConfig>,
+        default_timeout_secs: u64,
+    ) -> ToolLimits {
+        let tool = self.get(name);
+        let configured = overrides.get(name).copied().unwrap_or_default();
+        let timeout_secs = configured
+            .timeout_secs
+            .or_else(|| tool.and_then(|t| t.timeout_secs()))
+            .unwrap_or(default_timeout_secs);
+        let retries = configured
+            .retries
+            .unwrap_or_else(|| tool.map_or(0, |t| t.retries()));
+        ToolLimits {
+            timeout: std::time::Duration::from_secs(timeout_secs.max(1)),
+            retries,
+        }
+    }
+
     /// Get a tool by name.
     ///
     /// # Arguments
@@ -180,6 +222,23 @@ impl ToolRegistry {
             }
         };
 
+        if self.finance_read_only && tool.is_finance_write(&args) {
+            let action = args.get("action").and_then(Value::as_str).unwrap_or("?");
+            log_audit_event(
+                AuditCategory::FinanceReadOnly,
+                AuditSeverity::Critical,
+                "finance_write_blocked",
+                &format!("tool={} action={}", name, action),
+                true,
+            );
+            return Ok(ToolOutput::error(format!(
+                "Blocked: '{}' action '{}' would modify financial data, and finance read-only \
+                 mode is on (safety.finance_read_only). Only read actions are allowed; tell the \
+                 user the change was not made.",
+                name, action
+            )));
+        }
+
         let start = Instant::now();
 
         match tool.execute(args, ctx).await {
@@ -252,6 +311,26 @@ impl ToolRegistry {
             .collect()
     }
 
+    /// Get tool definitions the caller may use: tools whose category the
+    /// `role` blocks, or that the `user` is not permitted to run, are left
+    /// out. `None` applies no restriction.
+    pub fn definitions_for_caller(
+        &self,
+        compact: bool,
+        role: Option<&RolePolicy>,
+        user: Option<&User>,
+    ) -> Vec<ToolDefinition> {
+        let mut definitions = self.definitions_with_options(compact);
+        definitions.retain(|definition| {
+            let role_allows = role.is_none_or(|role| {
+                self.get(&definition.name)
+                    .is_some_and(|tool| role.check(tool.category()) != CategoryPermission::Blocked)
+            });
+            role_allows && user.is_none_or(|user| user.may_use(&definition.name))
+        });
+        definitions
+    }
+
     /// Get tool definitions for specific tool names only.
     ///
     /// Returns definitions only for tools whose names are in the provided list.
@@ -371,9 +450,11 @@ impl ToolRegistry {
     /// Drain all tools from `other` into this registry, consuming the other registry.
     ///
     /// Tools in `other` that have the same name as tools in `self` will replace
-    /// the existing tool.
+    /// the existing tool. Finance read-only mode carries over if either registry
+    /// has it.
     pub fn merge(&mut self, other: ToolRegistry) {
         self.tools.extend(other.tools);
+        self.finance_read_only |= other.finance_read_only;
     }
 }
 
@@ -454,6 +535,44 @@ mod tests {
         assert_eq!(result.unwrap().for_llm, "world");
     }
 
+    #[tokio::test]
+    async fn test_finance_read_only_blocks_writes() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(EchoTool));
+        registry.register(Box::new(crate::tools::StripeTool::new(
+            "sk_test_abc",
+            "usd",
+        )));
+        registry.set_finance_read_only(true);
+
+        let output = registry
+            .execute(
+                "stripe",
+                json!({"action": "create_refund", "payment_intent_id": "pi_123"}),
+            )
+            .await
+            .unwrap();
+        assert!(output.is_error);
+        assert!(
+            output.for_llm.contains("Blocked"),
+            "got: {}",
+            output.for_llm
+        );
+        assert!(output.for_llm.contains("create_refund"));
+
+        // Non-finance tools are unaffected.
+        let output = registry
+            .execute("echo", json!({"message": "hi"}))
+            .await
+            .unwrap();
+        assert_eq!(output.for_llm, "hi");
+
+        // The flag survives a merge into a registry without it.
+        let mut base = ToolRegistry::new();
+        base.merge(registry);
+        assert!(base.finance_read_only());
+    }
+
     #[test]
     fn test_registry_definitions() {
         let mut registry = ToolRegistry::new();
@@ -524,6 +643,28 @@ mod tests {
         assert!(registry.has("echo"));
     }
 
+    #[test]
+    fn test_limits_for() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(EchoTool));
+        let mut overrides = HashMap::new();
+
+        let limits = registry.limits_for("echo", &overrides, 60);
+        assert_eq!(limits.timeout, std::time::Duration::from_secs(60));
+        assert_eq!(limits.retries, 0);
+
+        overrides.insert(
+            "echo".to_string(),
+            ToolLimitsConfig {
+                timeout_secs: Some(5),
+                retries: Some(2),
+            },
+        );
+        let limits = registry.limits_for("echo", &overrides, 60);
+        assert_eq!(limits.timeout, std::time::Duration::from_secs(5));
+        assert_eq!(limits.retries, 2);
+    }
+
     #[test]
     fn test_definitions_for_tools() {
         let mut registry = ToolRegistry::new();
@@ -562,6 +703,53 @@ mod tests {
         assert_eq!(defs[0].description, "Echo message");
     }
 
+    #[test]
+    fn test_definitions_for_caller_hides_blocked_tools() {
+        use crate::security::RoleDefinition;
+        use crate::tools::ToolCategory;
+
+        let mut registry = ToolRegistry::new();
+        registry.register(Box::new(EchoTool));
+        assert_eq!(registry.definitions_for_caller(false, None, None).len(), 1);
+
+        let reader = RolePolicy::new(
+            "reader",
+            RoleDefinition {
+                allowed_categories: vec![ToolCategory::FilesystemRead],
+                approval_categories: vec![],
+            },
+        );
+        assert!(registry
+            .definitions_for_caller(false, Some(&reader), None)
+            .is_empty());
+
+        // Approval-gated categories stay visible.
+        let approver = RolePolicy::new(
+            "approver",
+            RoleDefinition {
+                allowed_categories: vec![],
+                approval_categories: vec![EchoTool.category()],
+            },
+        );
+        assert_eq!(
+            registry.definitions_for_caller(true, Some(&approver), None)[0].description,
+            "Echo message"
+        );
+
+        // Tools the user is denied are not advertised either.
+        let user = User {
+            name: "ben".into(),
+            known: true,
+            role: crate::security::users::UserRole::Family,
+            allowed_tools: None,
+            denied_tools: vec![EchoTool.name().to_string()],
+            daily_token_quota: None,
+        };
+        assert!(registry
+            .definitions_for_caller(false, Some(&approver), Some(&user))
+            .is_empty());
+    }
+
     #[test]
     fn test_opt_in_tool_hint_grep() {
         assert!(opt_in_tool_hint("grep").contains("--template coder"));
@@ -2,7 +2,12 @@
 //!
 //! Provides a `ReminderStore` for CRUD operations on reminders with
 //! JSON persistence, and a `ReminderTool` implementing the `Tool` trait
-//! with 6 actions: add, list, complete, snooze, remove, overdue.
+//! with 7 actions: add, list, complete, snooze, remove, cancel, overdue.
+//!
+//! Times accept ISO 8601 or natural language ("in 45 min", "next Tuesday at
+//! 9"); recurrences accept cron expressions or phrases such as "every
+//! weekday at 8am" (see [`crate::cron::natural`]). Reminders can be targeted
+//! by id or by fuzzy title match.
 
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -15,8 +20,10 @@ use serde_json::{json, Value};
 use tokio::sync::Mutex;
 
 use crate::config::Config;
-use crate::cron::{CronPayload, CronSchedule, CronService};
+use crate::cron::natural::{self, When};
+use crate::cron::{is_valid_cron_expr, next_run_ms, CronPayload, CronSchedule, CronService};
 use crate::error::{Result, ZeptoError};
+use crate::utils::locale::{format_timestamp, Locale};
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
@@ -73,6 +80,80 @@ fn parse_iso_to_epoch(s: &str) -> Result<u64> {
     )))
 }
 
+/// Parse a time argument: ISO 8601, a 5-field cron expression, or natural
+/// language relative to the local clock.
+fn parse_when_arg(s: &str) -> Result<When> {
+    let s = s.trim();
+    if let Ok(ts) = parse_iso_to_epoch(s) {
+        if let Some(at) = chrono::DateTime::from_timestamp(ts as i64, 0) {
+            return Ok(When::Once(at.fixed_offset()));
+        }
+    }
+    if is_valid_cron_expr(s) {
+        return Ok(When::Recurring {
+            schedule: CronSchedule::Cron {
+                expr: s.to_string(),
+            },
+            description: s.to_string(),
+        });
+    }
+    natural::parse_when_local(s)
+}
+
+/// Parse a one-shot due time into a unix epoch timestamp (seconds).
+fn parse_due(s: &str) -> Result<u64> {
+    match parse_when_arg(s)? {
+        When::Once(at) if at.timestamp() >= 0 => Ok(at.timestamp() as u64),
+        When::Once(_) => Err(ZeptoError::Tool(format!(
+            "Date '{}' is before Unix epoch",
+            s
+        ))),
+        When::Recurring { description, .. } => Err(ZeptoError::Tool(format!(
+            "'{}' is recurring ({}); expected a single time",
+            s, description
+        ))),
+    }
+}
+
+/// Parse a recurrence into a schedule and a human-readable label.
+fn parse_recurrence(s: &str) -> Result<(CronSchedule, String)> {
+    match parse_when_arg(s)? {
+        When::Recurring {
+            schedule,
+            description,
+        } => Ok((schedule, description)),
+        When::Once(_) => Err(ZeptoError::Tool(format!(
+            "'{}' is a single time, not a recurrence (try 'every day at 9am')",
+            s
+        ))),
+    }
+}
+
+/// Render a unix epoch timestamp in local time, formatted for the
+/// conversation's locale.
+fn format_due(epoch: u64, locale: Option<&Locale>) -> String {
+    format_timestamp(locale, epoch as i64)
+}
+
+fn words(s: &str) -> impl Iterator<Item = &str> {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+}
+
+/// How well `title` matches `query` (both lowercase): exact title beats
+/// substring, which beats the number of shared words. Zero means no match.
+fn title_match_score(title: &str, query: &str) -> usize {
+    if title == query {
+        usize::MAX
+    } else if title.contains(query) {
+        usize::MAX - 1
+    } else {
+        words(query)
+            .filter(|q| words(title).any(|t| t == *q))
+            .count()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Types
 // ---------------------------------------------------------------------------
@@ -121,7 +202,7 @@ pub struct ReminderStore {
 impl ReminderStore {
     /// Create a new store at the default path (`~/.zeptoclaw/reminders.json`).
     pub fn new() -> Result<Self> {
-        let path = Config::dir().join("reminders.json");
+        let path = Config::data_dir().join("reminders.json");
         Self::with_path(path)
     }
 
@@ -181,6 +262,48 @@ impl ReminderStore {
         self.entries.get(id)
     }
 
+    /// Resolve a reminder by id or fuzzy title.
+    ///
+    /// Tries the exact id, then a case-insensitive title, then a title
+    /// substring, then shared words. Ties prefer reminders that are not
+    /// done; a remaining tie is an error listing the candidates.
+    pub fn find(&self, query: &str) -> Result<Option<&ReminderEntry>> {
+        let query = query.trim();
+        if let Some(entry) = self.entries.get(query) {
+            return Ok(Some(entry));
+        }
+        let query = query.to_lowercase();
+        let scored: Vec<(usize, &ReminderEntry)> = self
+            .list(None, None)
+            .into_iter()
+            .map(|e| (title_match_score(&e.title.to_lowercase(), &query), e))
+            .filter(|(score, _)| *score > 0)
+            .collect();
+        let Some(best) = scored.iter().map(|(score, _)| *score).max() else {
+            return Ok(None);
+        };
+        let mut matches: Vec<&ReminderEntry> = scored
+            .into_iter()
+            .filter(|(score, _)| *score == best)
+            .map(|(_, e)| e)
+            .collect();
+        if matches.len() > 1 && matches.iter().any(|e| e.status != ReminderStatus::Done) {
+            matches.retain(|e| e.status != ReminderStatus::Done);
+        }
+        if matches.len() > 1 {
+            let candidates: Vec<String> = matches
+                .iter()
+                .map(|e| format!("[{}] {}", e.id, e.title))
+                .collect();
+            return Err(ZeptoError::Tool(format!(
+                "'{}' matches several reminders: {}. Use the id instead",
+                query,
+                candidates.join(", ")
+            )));
+        }
+        Ok(matches.pop())
+    }
+
     /// List reminders with optional status and category filters.
     pub fn list(
         &self,
@@ -362,7 +485,9 @@ impl Tool for ReminderTool {
     }
 
     fn description(&self) -> &str {
-        "Manage persistent reminders. Actions: add, list, complete, snooze, remove, overdue."
+        "Manage persistent reminders. Actions: add, list, complete, snooze, remove, cancel, overdue. \
+         Times accept natural language ('in 45 min', 'next tuesday at 9', \
+         'every weekday at 8am'); reminders can be referenced by id or name."
     }
 
     fn compact_description(&self) -> &str {
@@ -379,7 +504,7 @@ impl Tool for ReminderTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["add", "list", "complete", "snooze", "remove", "overdue"],
+                    "enum": ["add", "list", "complete", "snooze", "remove", "cancel", "overdue"],
                     "description": "Action to perform"
                 },
                 "title": {
@@ -394,17 +519,25 @@ impl Tool for ReminderTool {
                     "type": "string",
                     "description": "Category for grouping (default: general)"
                 },
+                "when": {
+                    "type": "string",
+                    "description": "When to remind, in local time (for add, snooze): 'in 45 min', 'tomorrow at 9', 'next tuesday at 6pm', 'every weekday at 8am', or ISO 8601"
+                },
                 "due_at": {
                     "type": "string",
-                    "description": "ISO 8601 datetime or date (e.g. 2026-03-01T09:00:00Z or 2026-03-01)"
+                    "description": "ISO 8601 datetime or date (e.g. 2026-03-01T09:00:00Z or 2026-03-01); natural language is also accepted"
                 },
                 "recurrence": {
                     "type": "string",
-                    "description": "Cron expression for recurring reminders (e.g. '0 9 * * 1' for every Monday 9am)"
+                    "description": "Recurring schedule: natural language ('every monday at 9am', 'every 2 hours') or a UTC cron expression ('0 9 * * 1')"
                 },
                 "id": {
                     "type": "string",
-                    "description": "Reminder id (for complete, snooze, remove)"
+                    "description": "Reminder id (for complete, snooze, remove, cancel)"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Fuzzy reminder title, instead of id (for complete, snooze, remove, cancel); filters titles for list"
                 },
                 "status": {
                     "type": "string",
@@ -424,11 +557,11 @@ impl Tool for ReminderTool {
 
         let s = match action {
             "add" => self.execute_add(&args, ctx).await?,
-            "list" => self.execute_list(&args).await?,
+            "list" => self.execute_list(&args, ctx).await?,
             "complete" => self.execute_complete(&args).await?,
-            "snooze" => self.execute_snooze(&args).await?,
-            "remove" => self.execute_remove(&args).await?,
-            "overdue" => self.execute_overdue().await?,
+            "snooze" => self.execute_snooze(&args, ctx).await?,
+            "remove" | "cancel" => self.execute_remove(&args, action).await?,
+            "overdue" => self.execute_overdue(ctx).await?,
             other => {
                 return Err(ZeptoError::Tool(format!(
                     "Unknown reminder action '{}'",
@@ -441,6 +574,49 @@ impl Tool for ReminderTool {
 }
 
 impl ReminderTool {
+    /// Resolve the target reminder from `id` or, failing that, fuzzy `name`.
+    /// Returns the query alongside the entry so callers can report misses.
+    async fn resolve(&self, args: &Value, action: &str) -> Result<(String, Option<ReminderEntry>)> {
+        let query = ["id", "name"]
+            .iter()
+            .find_map(|key| {
+                args.get(*key)
+                    .and_then(|v| v.as_str())
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+            })
+            .ok_or_else(|| {
+                ZeptoError::Tool(format!("Missing 'id' or 'name' for reminder {}", action))
+            })?;
+        let store = self.store.lock().await;
+        let entry = store.find(query)?.cloned();
+        Ok((query.to_string(), entry))
+    }
+
+    /// Schedule a cron job delivering `entry` to the current chat. Returns
+    /// the job id, or `None` without a cron service or channel context.
+    async fn schedule_job(
+        &self,
+        entry: &ReminderEntry,
+        schedule: CronSchedule,
+        ctx: &ToolContext,
+    ) -> Option<String> {
+        let cron = self.cron.as_ref()?;
+        let (channel, chat_id) = (ctx.channel.as_ref()?, ctx.chat_id.as_ref()?);
+        let delete_after_run = matches!(schedule, CronSchedule::At { .. });
+        let payload = CronPayload {
+            message: format!("Reminder: {}", entry.title),
+            channel: channel.clone(),
+            chat_id: chat_id.clone(),
+        };
+        // Cron scheduling is best-effort; the reminder is still persisted
+        // even if the cron job fails.
+        cron.add_job(entry.title.clone(), schedule, payload, delete_after_run)
+            .await
+            .ok()
+            .map(|job| job.id)
+    }
+
     async fn execute_add(&self, args: &Value, ctx: &ToolContext) -> Result<String> {
         let title = args
             .get("title")
@@ -462,64 +638,84 @@ impl ReminderTool {
             .filter(|s| !s.is_empty())
             .unwrap_or("general");
 
-        let due_at = if let Some(due_str) = args.get("due_at").and_then(|v| v.as_str()) {
-            Some(parse_iso_to_epoch(due_str)?)
-        } else {
-            None
-        };
-
-        let recurrence = args
+        let when = ["when", "due_at"].iter().find_map(|key| {
+            args.get(*key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+        });
+
+        let mut due_at = None;
+        let mut recurring: Option<(CronSchedule, String)> = None;
+        if let Some(when) = when {
+            match parse_when_arg(when)? {
+                When::Once(at) => {
+                    due_at = Some(u64::try_from(at.timestamp()).map_err(|_| {
+                        ZeptoError::Tool(format!("Date '{}' is before Unix epoch", when))
+                    })?)
+                }
+                When::Recurring {
+                    schedule,
+                    description,
+                } => recurring = Some((schedule, description)),
+            }
+        }
+        if let Some(recurrence) = args
             .get("recurrence")
             .and_then(|v| v.as_str())
             .map(str::trim)
-            .filter(|s| !s.is_empty());
+            .filter(|s| !s.is_empty())
+        {
+            recurring = Some(parse_recurrence(recurrence)?);
+        }
+        // Recurring reminders are due at their next occurrence.
+        if let (None, Some((schedule, _))) = (due_at, &recurring) {
+            due_at = next_run_ms(schedule).map(|ms| (ms / 1000) as u64);
+        }
 
         let entry = {
             let mut store = self.store.lock().await;
-            store.add(title, description, category, due_at, recurrence)?
+            store.add(
+                title,
+                description,
+                category,
+                due_at,
+                recurring.as_ref().map(|(_, label)| label.as_str()),
+            )?
         };
 
-        // If we have a cron service, a due_at, and a channel context, schedule
-        // a one-shot cron job to deliver the reminder.
-        if let (Some(cron), Some(due_epoch)) = (&self.cron, due_at) {
-            if let (Some(channel), Some(chat_id)) = (&ctx.channel, &ctx.chat_id) {
-                let schedule = CronSchedule::At {
-                    at_ms: (due_epoch as i64) * 1000,
-                };
-                let payload = CronPayload {
-                    message: format!("Reminder: {}", entry.title),
-                    channel: channel.clone(),
-                    chat_id: chat_id.clone(),
-                };
-                match cron
-                    .add_job(entry.title.clone(), schedule, payload, true)
-                    .await
-                {
-                    Ok(job) => {
-                        let mut store = self.store.lock().await;
-                        let _ = store.set_cron_job_id(&entry.id, &job.id);
-                    }
-                    Err(_) => {
-                        // Cron scheduling is best-effort; the reminder is still
-                        // persisted even if the cron job fails.
-                    }
-                }
+        // If we have a cron service and a channel context, schedule a
+        // recurring job, or a one-shot job for the due time.
+        let schedule = match (&recurring, due_at) {
+            (Some((schedule, _)), _) => Some(schedule.clone()),
+            (None, Some(due_epoch)) => Some(CronSchedule::At {
+                at_ms: (due_epoch as i64) * 1000,
+            }),
+            (None, None) => None,
+        };
+        if let Some(schedule) = schedule {
+            if let Some(job_id) = self.schedule_job(&entry, schedule, ctx).await {
+                let mut store = self.store.lock().await;
+                let _ = store.set_cron_job_id(&entry.id, &job_id);
             }
         }
 
         let due_info = if let Some(d) = due_at {
-            format!(" (due at {})", d)
+            format!(" (due at {})", format_due(d, ctx.locale.as_ref()))
         } else {
             String::new()
         };
+        let repeat_info = recurring
+            .map(|(_, label)| format!(", repeats {}", label))
+            .unwrap_or_default();
 
         Ok(format!(
-            "Created reminder '{}' [{}]{}",
-            entry.title, entry.id, due_info
+            "Created reminder '{}' [{}]{}{}",
+            entry.title, entry.id, due_info, repeat_info
         ))
     }
 
-    async fn execute_list(&self, args: &Value) -> Result<String> {
+    async fn execute_list(&self, args: &Value, ctx: &ToolContext) -> Result<String> {
         let status_filter = args
             .get("status")
             .and_then(|v| v.as_str())
@@ -536,8 +732,17 @@ impl ReminderTool {
             .map(str::trim)
             .filter(|s| !s.is_empty());
 
+        let name_filter = args
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty());
+
         let store = self.store.lock().await;
-        let items = store.list(status_filter.as_ref(), category_filter);
+        let mut items = store.list(status_filter.as_ref(), category_filter);
+        if let Some(name) = &name_filter {
+            items.retain(|e| title_match_score(&e.title.to_lowercase(), name) > 0);
+        }
 
         if items.is_empty() {
             return Ok("No reminders found".to_string());
@@ -552,11 +757,16 @@ impl ReminderTool {
             };
             let due_info = item
                 .due_at
-                .map(|d| format!(" (due: {})", d))
+                .map(|d| format!(" (due: {})", format_due(d, ctx.locale.as_ref())))
+                .unwrap_or_default();
+            let repeat_info = item
+                .recurrence
+                .as_ref()
+                .map(|r| format!(" (repeats {})", r))
                 .unwrap_or_default();
             lines.push(format!(
-                "{} [{}] {}{}  [{}]",
-                emoji, item.id, item.title, due_info, item.category
+                "{} [{}] {}{}{}  [{}]",
+                emoji, item.id, item.title, due_info, repeat_info, item.category
             ));
         }
 
@@ -569,73 +779,80 @@ impl ReminderTool {
     }
 
     async fn execute_complete(&self, args: &Value) -> Result<String> {
-        let id = args
-            .get("id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| ZeptoError::Tool("Missing 'id' for reminder complete".into()))?;
+        let (query, entry) = self.resolve(args, "complete").await?;
+        let Some(entry) = entry else {
+            return Ok(format!("Reminder {} not found", query));
+        };
 
         // Cancel associated cron job if present.
-        let cron_job_id = {
-            let store = self.store.lock().await;
-            store.get(id).and_then(|e| e.cron_job_id.clone())
-        };
-        if let (Some(cron), Some(job_id)) = (&self.cron, cron_job_id) {
-            let _ = cron.remove_job(&job_id).await;
+        if let (Some(cron), Some(job_id)) = (&self.cron, &entry.cron_job_id) {
+            let _ = cron.remove_job(job_id).await;
         }
 
         let mut store = self.store.lock().await;
-        if store.complete(id)? {
-            Ok(format!("Completed reminder {}", id))
-        } else {
-            Ok(format!("Reminder {} not found", id))
-        }
+        store.complete(&entry.id)?;
+        Ok(format!(
+            "Completed reminder {} ('{}')",
+            entry.id, entry.title
+        ))
     }
 
-    async fn execute_snooze(&self, args: &Value) -> Result<String> {
-        let id = args
-            .get("id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| ZeptoError::Tool("Missing 'id' for reminder snooze".into()))?;
+    async fn execute_snooze(&self, args: &Value, ctx: &ToolContext) -> Result<String> {
+        let (query, entry) = self.resolve(args, "snooze").await?;
 
-        let due_str = args
-            .get("due_at")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| ZeptoError::Tool("Missing 'due_at' for reminder snooze".into()))?;
+        let when = ["when", "due_at"]
+            .iter()
+            .find_map(|key| args.get(*key).and_then(|v| v.as_str()))
+            .ok_or_else(|| ZeptoError::Tool("Missing 'when' for reminder snooze".into()))?;
+        let new_due_at = parse_due(when)?;
+
+        let Some(entry) = entry else {
+            return Ok(format!("Reminder {} not found", query));
+        };
 
-        let new_due_at = parse_iso_to_epoch(due_str)?;
+        // A one-shot reminder moves its delivery; a recurring one keeps its
+        // schedule and gets an extra one-shot delivery at the snooze time.
+        let one_shot = entry.recurrence.is_none();
+        if one_shot {
+            if let (Some(cron), Some(job_id)) = (&self.cron, &entry.cron_job_id) {
+                let _ = cron.remove_job(job_id).await;
+            }
+        }
+        let schedule = CronSchedule::At {
+            at_ms: (new_due_at as i64) * 1000,
+        };
+        let job_id = self.schedule_job(&entry, schedule, ctx).await;
 
         let mut store = self.store.lock().await;
-        if store.snooze(id, new_due_at)? {
-            Ok(format!("Snoozed reminder {} until {}", id, new_due_at))
-        } else {
-            Ok(format!("Reminder {} not found", id))
+        store.snooze(&entry.id, new_due_at)?;
+        if let (true, Some(job_id)) = (one_shot, job_id) {
+            let _ = store.set_cron_job_id(&entry.id, &job_id);
         }
+        Ok(format!(
+            "Snoozed reminder {} ('{}') until {}",
+            entry.id,
+            entry.title,
+            format_due(new_due_at, ctx.locale.as_ref())
+        ))
     }
 
-    async fn execute_remove(&self, args: &Value) -> Result<String> {
-        let id = args
-            .get("id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| ZeptoError::Tool("Missing 'id' for reminder remove".into()))?;
+    async fn execute_remove(&self, args: &Value, action: &str) -> Result<String> {
+        let (query, entry) = self.resolve(args, action).await?;
+        let Some(entry) = entry else {
+            return Ok(format!("Reminder {} not found", query));
+        };
 
         // Cancel associated cron job if present.
-        let cron_job_id = {
-            let store = self.store.lock().await;
-            store.get(id).and_then(|e| e.cron_job_id.clone())
-        };
-        if let (Some(cron), Some(job_id)) = (&self.cron, cron_job_id) {
-            let _ = cron.remove_job(&job_id).await;
+        if let (Some(cron), Some(job_id)) = (&self.cron, &entry.cron_job_id) {
+            let _ = cron.remove_job(job_id).await;
         }
 
         let mut store = self.store.lock().await;
-        if store.remove(id)? {
-            Ok(format!("Removed reminder {}", id))
-        } else {
-            Ok(format!("Reminder {} not found", id))
-        }
+        store.remove(&entry.id)?;
+        Ok(format!("Removed reminder {} ('{}')", entry.id, entry.title))
     }
 
-    async fn execute_overdue(&self) -> Result<String> {
+    async fn execute_overdue(&self, ctx: &ToolContext) -> Result<String> {
         let store = self.store.lock().await;
         let items = store.overdue();
 
@@ -647,7 +864,7 @@ impl ReminderTool {
         for item in &items {
             let due_info = item
                 .due_at
-                .map(|d| format!(" (was due: {})", d))
+                .map(|d| format!(" (was due: {})", format_due(d, ctx.locale.as_ref())))
                 .unwrap_or_default();
             lines.push(format!(
                 "\u{26a0}\u{fe0f} [{}] {}{}  [{}]",
@@ -1238,6 +1455,179 @@ mod tests {
         assert!(entry.due_at.unwrap() > 1700000000);
     }
 
+    #[tokio::test]
+    async fn test_due_dates_follow_the_locale() {
+        let (tool, _dir) = temp_tool();
+        let c = ctx().with_locale(Locale::parse("de").unwrap());
+        let result = tool
+            .execute(
+                json!({"action": "add", "title": "Steuer", "due_at": "2026-12-25T10:00:00Z"}),
+                &c,
+            )
+            .await
+            .unwrap()
+            .for_llm;
+        // dd.mm.yyyy whatever the local timezone shifts the day to.
+        assert!(result.contains(".12.2026 "), "{}", result);
+
+        let list = tool
+            .execute(json!({"action": "list"}), &ctx())
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(list.contains("2026-12-2"), "{}", list);
+    }
+
+    #[test]
+    fn test_store_find_fuzzy() {
+        let (mut store, _dir) = temp_store();
+        store
+            .add("Call the dentist", None, "health", None, None)
+            .unwrap();
+        store.add("Pay rent", None, "home", None, None).unwrap();
+        store
+            .add("Pay electricity bill", None, "home", None, None)
+            .unwrap();
+
+        assert_eq!(store.find("r2").unwrap().unwrap().title, "Pay rent");
+        assert_eq!(store.find("pay RENT").unwrap().unwrap().id, "r2");
+        assert_eq!(store.find("dentist").unwrap().unwrap().id, "r1");
+        assert_eq!(store.find("electricity payment").unwrap().unwrap().id, "r3");
+        assert!(store.find("groceries").unwrap().is_none());
+
+        let err = store.find("pay").unwrap_err().to_string();
+        assert!(err.contains("r2") && err.contains("r3"));
+
+        // Ties prefer reminders that are not done.
+        store.complete("r2").unwrap();
+        assert_eq!(store.find("pay").unwrap().unwrap().id, "r3");
+    }
+
+    #[test]
+    fn test_parse_when_arg_forms() {
+        assert!(matches!(
+            parse_when_arg("2026-03-01T09:00:00Z").unwrap(),
+            When::Once(_)
+        ));
+        assert!(matches!(
+            parse_when_arg("0 9 * * 1").unwrap(),
+            When::Recurring {
+                schedule: CronSchedule::Cron { .. },
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse_when_arg("in 45 min").unwrap(),
+            When::Once(_)
+        ));
+        assert!(parse_recurrence("tomorrow").is_err());
+        assert!(parse_due("every day at 9").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_add_natural_language_time() {
+        let (tool, _dir) = temp_tool();
+        let c = ctx();
+
+        let before = now_secs();
+        tool.execute(
+            json!({"action": "add", "title": "Stretch", "when": "in 45 min"}),
+            &c,
+        )
+        .await
+        .unwrap();
+
+        let store = tool.store.lock().await;
+        let due = store.get("r1").unwrap().due_at.unwrap();
+        assert!(due >= before + 45 * 60 && due <= now_secs() + 45 * 60);
+    }
+
+    #[tokio::test]
+    async fn test_execute_add_recurring_natural_language() {
+        let (tool, _dir) = temp_tool();
+        let c = ctx();
+
+        let result = tool
+            .execute(
+                json!({"action": "add", "title": "Standup", "recurrence": "every weekday at 8am"}),
+                &c,
+            )
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(result.contains("repeats every weekday at 08:00"));
+
+        let store = tool.store.lock().await;
+        let entry = store.get("r1").unwrap();
+        assert_eq!(entry.recurrence.as_deref(), Some("every weekday at 08:00"));
+        assert!(entry.due_at.unwrap() > now_secs());
+    }
+
+    #[tokio::test]
+    async fn test_execute_add_invalid_recurrence() {
+        let (tool, _dir) = temp_tool();
+        let result = tool
+            .execute(
+                json!({"action": "add", "title": "X", "recurrence": "every blue moon"}),
+                &ctx(),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_snooze_and_cancel_by_name() {
+        let (tool, _dir) = temp_tool();
+        let c = ctx();
+
+        tool.execute(json!({"action": "add", "title": "Water the plants"}), &c)
+            .await
+            .unwrap();
+        let result = tool
+            .execute(
+                json!({"action": "snooze", "name": "plants", "when": "in 2 hours"}),
+                &c,
+            )
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(result.contains("Snoozed reminder r1"));
+        {
+            let store = tool.store.lock().await;
+            let entry = store.get("r1").unwrap();
+            assert_eq!(entry.status, ReminderStatus::Snoozed);
+            assert!(entry.due_at.unwrap() >= now_secs() + 2 * 3600 - 5);
+        }
+
+        let result = tool
+            .execute(json!({"action": "cancel", "name": "water plants"}), &c)
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(result.contains("Removed reminder r1"));
+        assert!(tool.store.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_execute_list_name_filter() {
+        let (tool, _dir) = temp_tool();
+        let c = ctx();
+        tool.execute(json!({"action": "add", "title": "Buy milk"}), &c)
+            .await
+            .unwrap();
+        tool.execute(json!({"action": "add", "title": "Book flights"}), &c)
+            .await
+            .unwrap();
+
+        let result = tool
+            .execute(json!({"action": "list", "name": "milk"}), &c)
+            .await
+            .unwrap()
+            .for_llm;
+        assert!(result.contains("Buy milk"));
+        assert!(!result.contains("Book flights"));
+    }
+
     #[test]
     fn test_parse_iso_negative_epoch() {
         assert!(parse_iso_to_epoch("1960-01-01T00:00:00Z").is_err());
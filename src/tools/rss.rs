@@ -0,0 +1,1007 @@
+//! RSS/Atom feed tool and digest routine.
+//!
+//! Subscriptions and the keys of already-seen entries live in
+//! `<workspace>/rss/feeds.json`, so each entry is reported once across
+//! restarts. `digest` fetches every feed and returns only unseen entries.
+//!
+//! The digest routine is a regular cron job (named [`DIGEST_JOB_NAME`])
+//! whose message asks the agent to call `digest` and summarize the result,
+//! so the LLM-written digest is delivered to the job's channel. It is
+//! created from `tools.rss.digest` at startup or by `schedule_digest`.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::config::{RssDigestConfig, RssToolConfig};
+use crate::cron::natural::{self, When};
+use crate::cron::{is_valid_cron_expr, CronPayload, CronSchedule, CronService};
+use crate::error::{Result, ZeptoError};
+use crate::utils::http::Client;
+
+use super::web::{
+    decode_html_entities, is_blocked_host, read_body_limited, resolve_and_check_host,
+    validate_redirect_target, web_fetch_redirect_policy, WEB_USER_AGENT,
+};
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Name of the cron job that delivers the digest.
+pub const DIGEST_JOB_NAME: &str = "rss-digest";
+
+/// Largest feed document we read.
+const MAX_FEED_BYTES: usize = 2 * 1024 * 1024;
+
+/// Seen-entry keys remembered per feed.
+const MAX_SEEN_PER_FEED: usize = 1000;
+
+/// Entry summaries are cut to this many characters.
+const SUMMARY_CHARS: usize = 300;
+
+// ---------------------------------------------------------------------------
+// Feed parsing
+// ---------------------------------------------------------------------------
+
+/// A parsed feed document.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Feed {
+    pub title: Option<String>,
+    pub entries: Vec<FeedEntry>,
+}
+
+/// One RSS item or Atom entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedEntry {
+    pub title: String,
+    pub link: Option<String>,
+    /// `guid` / `id`, when present.
+    pub id: Option<String>,
+    /// `pubDate` / `published` / `updated`, as written in the feed.
+    pub published: Option<String>,
+    pub summary: String,
+}
+
+impl FeedEntry {
+    /// Stable dedupe key: id, then link, then title.
+    pub fn key(&self) -> String {
+        self.id
+            .clone()
+            .or_else(|| self.link.clone())
+            .unwrap_or_else(|| self.title.clone())
+    }
+}
+
+/// Parse an RSS 2.0, RSS 1.0 (RDF), or Atom document.
+pub fn parse_feed(xml: &str) -> Result<Feed> {
+    use quick_xml::escape::resolve_xml_entity;
+    use quick_xml::events::{BytesStart, Event};
+    use quick_xml::Reader;
+
+    /// Atom `<link href rel>`: take the alternate (or rel-less) link.
+    fn atom_link(e: &BytesStart<'_>) -> Option<String> {
+        let mut href = None;
+        let mut alternate = true;
+        for attr in e.attributes().flatten() {
+            let raw = String::from_utf8_lossy(&attr.value).into_owned();
+            let value = quick_xml::escape::unescape(&raw)
+                .map(|v| v.into_owned())
+                .unwrap_or(raw);
+            match attr.key.local_name().as_ref() {
+                b"href" => href = Some(value),
+                b"rel" => alternate = value == "alternate",
+                _ => {}
+            }
+        }
+        href.filter(|_| alternate)
+    }
+
+    /// Elements whose text content we record.
+    fn is_field(name: &[u8]) -> bool {
+        matches!(
+            name,
+            b"title"
+                | b"link"
+                | b"guid"
+                | b"id"
+                | b"pubDate"
+                | b"published"
+                | b"date"
+                | b"updated"
+                | b"description"
+                | b"summary"
+                | b"encoded"
+                | b"content"
+        )
+    }
+
+    let mut reader = Reader::from_str(xml);
+    let mut feed = Feed::default();
+    let mut saw_root = false;
+    let mut entry: Option<FeedEntry> = None;
+    // Element whose text is being captured, and nesting depth inside it.
+    let mut capture: Option<(Vec<u8>, usize)> = None;
+    let mut text = String::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                let name = e.local_name().as_ref().to_vec();
+                if !saw_root {
+                    saw_root = true;
+                    if !matches!(name.as_slice(), b"rss" | b"feed" | b"RDF") {
+                        return Err(ZeptoError::Tool("Not an RSS or Atom feed".to_string()));
+                    }
+                }
+                if let Some((_, depth)) = capture.as_mut() {
+                    *depth += 1;
+                } else if matches!(name.as_slice(), b"item" | b"entry") {
+                    entry = Some(FeedEntry::default());
+                } else if is_field(&name) {
+                    if name == b"link" {
+                        if let (Some(entry), Some(href)) = (entry.as_mut(), atom_link(e)) {
+                            entry.link.get_or_insert(href);
+                        }
+                    }
+                    capture = Some((name, 0));
+                    text.clear();
+                }
+            }
+            Ok(Event::Empty(ref e)) => {
+                if capture.is_none() && e.local_name().as_ref() == b"link" {
+                    if let (Some(entry), Some(href)) = (entry.as_mut(), atom_link(e)) {
+                        entry.link.get_or_insert(href);
+                    }
+                }
+            }
+            Ok(Event::End(ref e)) => {
+                let name = e.local_name();
+                let name = name.as_ref();
+                let closes = match capture.as_mut() {
+                    Some((_, depth)) if *depth > 0 => {
+                        *depth -= 1;
+                        false
+                    }
+                    Some((field, _)) => field.as_slice() == name,
+                    None => false,
+                };
+                if closes {
+                    capture = None;
+                    let value = text.trim().to_string();
+                    if value.is_empty() {
+                        // Nothing to record.
+                    } else if let Some(entry) = entry.as_mut() {
+                        match name {
+                            b"title" => entry.title = value,
+                            b"link" => {
+                                entry.link.get_or_insert(value);
+                            }
+                            b"guid" | b"id" => entry.id = Some(value),
+                            b"pubDate" | b"published" | b"date" => entry.published = Some(value),
+                            b"updated" => {
+                                entry.published.get_or_insert(value);
+                            }
+                            b"description" | b"summary" | b"encoded" | b"content" => {
+                                if entry.summary.is_empty() || name == b"description" {
+                                    entry.summary = value;
+                                }
+                            }
+                            _ => {}
+                        }
+                    } else if name == b"title" && feed.title.is_none() {
+                        feed.title = Some(value);
+                    }
+                } else if capture.is_none() && matches!(name, b"item" | b"entry") {
+                    if let Some(entry) = entry.take() {
+                        feed.entries.push(entry);
+                    }
+                }
+            }
+            Ok(Event::Text(ref e)) if capture.is_some() => {
+                let decoded = e
+                    .xml_content()
+                    .map_err(|e| ZeptoError::Tool(format!("XML decode error: {e}")))?;
+                text.push_str(&decoded);
+            }
+            Ok(Event::CData(ref e)) if capture.is_some() => {
+                text.push_str(&String::from_utf8_lossy(e));
+            }
+            Ok(Event::GeneralRef(ref e)) if capture.is_some() => {
+                if let Ok(Some(ch)) = e.resolve_char_ref() {
+                    text.push(ch);
+                } else {
+                    let name = e
+                        .xml_content()
+                        .map_err(|e| ZeptoError::Tool(format!("XML decode error: {e}")))?;
+                    match resolve_xml_entity(name.as_ref()) {
+                        Some(resolved) => text.push_str(resolved),
+                        // HTML entities in feed text; decoded with the markup.
+                        None => text.push_str(&format!("&{};", name)),
+                    }
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(ZeptoError::Tool(format!("Feed parse error: {e}"))),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    if !saw_root {
+        return Err(ZeptoError::Tool("Empty feed document".to_string()));
+    }
+    for entry in &mut feed.entries {
+        entry.title = plain_text(&entry.title, usize::MAX);
+        entry.summary = plain_text(&entry.summary, SUMMARY_CHARS);
+        if entry.title.is_empty() {
+            entry.title = "(untitled)".to_string();
+        }
+    }
+    Ok(feed)
+}
+
+/// Strip HTML tags, decode entities, collapse whitespace, and cut to
+/// `max_chars`.
+fn plain_text(html: &str, max_chars: usize) -> String {
+    let mut stripped = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                stripped.push(' ');
+            }
+            _ if !in_tag => stripped.push(ch),
+            _ => {}
+        }
+    }
+    let text = decode_html_entities(&stripped)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.chars().count() > max_chars {
+        let mut cut: String = text.chars().take(max_chars).collect();
+        cut.push('…');
+        cut
+    } else {
+        text
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Subscription store
+// ---------------------------------------------------------------------------
+
+/// A subscribed feed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subscription {
+    pub id: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub added_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_checked_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Keys of entries already reported, oldest first.
+    #[serde(default)]
+    pub seen: VecDeque<String>,
+}
+
+impl Subscription {
+    fn label(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.url)
+    }
+
+    /// Entries not seen before, in feed order. Marks every entry of `feed`
+    /// as seen.
+    fn take_unseen(&mut self, feed: &Feed) -> Vec<FeedEntry> {
+        let fresh: Vec<FeedEntry> = feed
+            .entries
+            .iter()
+            .filter(|entry| !self.seen.contains(&entry.key()))
+            .cloned()
+            .collect();
+        for entry in &fresh {
+            self.seen.push_back(entry.key());
+        }
+        while self.seen.len() > MAX_SEEN_PER_FEED {
+            self.seen.pop_front();
+        }
+        fresh
+    }
+}
+
+/// Persistent subscriptions backed by a JSON file.
+#[derive(Debug)]
+pub struct RssStore {
+    feeds: Vec<Subscription>,
+    path: PathBuf,
+    next_id: u64,
+}
+
+impl RssStore {
+    /// Open (or create on first save) the store at `path`.
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let feeds: Vec<Subscription> = match std::fs::read_to_string(&path) {
+            Ok(data) if !data.trim().is_empty() => serde_json::from_str(&data)?,
+            Ok(_) => Vec::new(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let max_id = feeds
+            .iter()
+            .filter_map(|f| f.id.strip_prefix('f').and_then(|n| n.parse::<u64>().ok()))
+            .max()
+            .unwrap_or(0);
+        Ok(Self {
+            feeds,
+            path,
+            next_id: max_id + 1,
+        })
+    }
+
+    pub fn feeds(&self) -> &[Subscription] {
+        &self.feeds
+    }
+
+    /// Find a subscription by id or URL.
+    pub fn find(&self, query: &str) -> Option<&Subscription> {
+        let query = query.trim();
+        self.feeds.iter().find(|f| f.id == query || f.url == query)
+    }
+
+    fn add(
+        &mut self,
+        url: &str,
+        title: Option<String>,
+        seen: VecDeque<String>,
+    ) -> Result<Subscription> {
+        let sub = Subscription {
+            id: format!("f{}", self.next_id),
+            url: url.to_string(),
+            title,
+            added_at: now_secs(),
+            last_checked_at: Some(now_secs()),
+            last_error: None,
+            seen,
+        };
+        self.next_id += 1;
+        self.feeds.push(sub.clone());
+        self.save()?;
+        Ok(sub)
+    }
+
+    fn remove(&mut self, query: &str) -> Result<Option<Subscription>> {
+        let query = query.trim();
+        let Some(index) = self
+            .feeds
+            .iter()
+            .position(|f| f.id == query || f.url == query)
+        else {
+            return Ok(None);
+        };
+        let removed = self.feeds.remove(index);
+        self.save()?;
+        Ok(Some(removed))
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.feeds)?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// RssTool
+// ---------------------------------------------------------------------------
+
+/// Agent tool for feed subscriptions and digests.
+pub struct RssTool {
+    store: Mutex<RssStore>,
+    config: RssToolConfig,
+    cron: Option<Arc<CronService>>,
+    client: Client,
+}
+
+impl RssTool {
+    /// Open the store at `<workspace>/rss/feeds.json`.
+    pub fn new(
+        workspace: &str,
+        config: RssToolConfig,
+        cron: Option<Arc<CronService>>,
+    ) -> Result<Self> {
+        let store = RssStore::open(PathBuf::from(workspace).join("rss").join("feeds.json"))?;
+        Ok(Self::with_store(store, config, cron))
+    }
+
+    /// Create a tool around an existing store. Useful for testing.
+    pub fn with_store(
+        store: RssStore,
+        config: RssToolConfig,
+        cron: Option<Arc<CronService>>,
+    ) -> Self {
+        let client = crate::utils::http::client_builder()
+            .redirect(web_fetch_redirect_policy())
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        Self {
+            store: Mutex::new(store),
+            config,
+            cron,
+            client,
+        }
+    }
+
+    /// Create or replace the digest cron job from `tools.rss.digest`.
+    /// Does nothing when the digest is disabled or has no destination.
+    pub async fn ensure_digest_job(&self) -> Result<Option<String>> {
+        let digest: &RssDigestConfig = &self.config.digest;
+        if !digest.enabled || digest.channel.trim().is_empty() || digest.chat_id.trim().is_empty() {
+            return Ok(None);
+        }
+        let schedule = parse_digest_schedule(&digest.schedule)?;
+        self.schedule_digest(schedule, digest.channel.trim(), digest.chat_id.trim())
+            .await
+            .map(Some)
+    }
+
+    /// Replace any existing digest job with one on `schedule`.
+    async fn schedule_digest(
+        &self,
+        schedule: CronSchedule,
+        channel: &str,
+        chat_id: &str,
+    ) -> Result<String> {
+        let cron = self.cron.as_ref().ok_or_else(|| {
+            ZeptoError::Tool("Scheduling a digest requires the cron service".into())
+        })?;
+        self.remove_digest_jobs(cron).await?;
+        let payload = CronPayload {
+            message: DIGEST_PROMPT.to_string(),
+            channel: channel.to_string(),
+            chat_id: chat_id.to_string(),
+        };
+        let job = cron
+            .add_job(DIGEST_JOB_NAME.to_string(), schedule, payload, false)
+            .await?;
+        Ok(job.id)
+    }
+
+    async fn remove_digest_jobs(&self, cron: &CronService) -> Result<usize> {
+        let mut removed = 0;
+        for job in cron.list_jobs(true).await {
+            if job.name == DIGEST_JOB_NAME && cron.remove_job(&job.id).await? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Fetch and parse a feed, with the same SSRF protections as `web_fetch`.
+    async fn fetch(&self, url: &str) -> Result<Feed> {
+        let parsed = Url::parse(url)
+            .map_err(|e| ZeptoError::Tool(format!("Invalid feed URL '{}': {}", url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(ZeptoError::Tool(
+                "Only http/https feed URLs are allowed".to_string(),
+            ));
+        }
+        if is_blocked_host(&parsed) {
+            return Err(ZeptoError::SecurityViolation(
+                "Blocked URL host (local or private network)".to_string(),
+            ));
+        }
+        crate::utils::http::check_url(&parsed)?;
+        let client = match resolve_and_check_host(&parsed).await? {
+            Some((host, addr)) => crate::utils::http::client_builder()
+                .redirect(web_fetch_redirect_policy())
+                .resolve(&host, addr)
+                .build()
+                .unwrap_or_else(|_| self.client.clone()),
+            None => self.client.clone(),
+        };
+
+        let response = client
+            .get(parsed)
+            .header("User-Agent", WEB_USER_AGENT)
+            .header(
+                "Accept",
+                "application/rss+xml, application/atom+xml, application/xml;q=0.9, */*;q=0.5",
+            )
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Feed fetch failed: {}", e)))?;
+        validate_redirect_target(response.url()).await?;
+        if !response.status().is_success() {
+            return Err(ZeptoError::Tool(format!(
+                "Feed fetch failed: HTTP {}",
+                response.status()
+            )));
+        }
+        let max_bytes = MAX_FEED_BYTES.min(crate::utils::http::max_response_bytes());
+        let body = read_body_limited(response, max_bytes).await?;
+        parse_feed(&body)
+    }
+}
+
+/// Message the digest cron job sends to the agent.
+const DIGEST_PROMPT: &str = "[RSS digest] Call the rss tool with action \"digest\". \
+Summarize the new entries as a short digest grouped by feed: one line per story with \
+its link, most important first. If there is nothing new, reply with one short sentence.";
+
+/// Parse a digest schedule: a cron expression or a recurring phrase like
+/// "every day at 8am".
+fn parse_digest_schedule(input: &str) -> Result<CronSchedule> {
+    let input = input.trim();
+    if is_valid_cron_expr(input) {
+        return Ok(CronSchedule::Cron {
+            expr: input.to_string(),
+        });
+    }
+    match natural::parse_when_local(input)? {
+        When::Recurring { schedule, .. } => Ok(schedule),
+        When::Once(_) => Err(ZeptoError::Tool(format!(
+            "Digest schedule '{}' must repeat (e.g. 'every day at 8am' or a cron expression)",
+            input
+        ))),
+    }
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn format_entry(entry: &FeedEntry) -> String {
+    let mut line = format!("- {}", entry.title);
+    if let Some(published) = &entry.published {
+        line.push_str(&format!(" ({})", published));
+    }
+    if let Some(link) = &entry.link {
+        line.push_str(&format!("\n  {}", link));
+    }
+    if !entry.summary.is_empty() {
+        line.push_str(&format!("\n  {}", entry.summary));
+    }
+    line
+}
+
+#[async_trait]
+impl Tool for RssTool {
+    fn name(&self) -> &str {
+        "rss"
+    }
+
+    fn description(&self) -> &str {
+        "RSS/Atom feeds. Actions: subscribe (url), unsubscribe (feed id or url), list, latest (recent entries of one feed), digest (new entries across feeds since the last digest), schedule_digest (when, e.g. 'every day at 8am'; delivered to this chat), unschedule_digest."
+    }
+
+    fn compact_description(&self) -> &str {
+        "RSS feeds"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkRead
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["subscribe", "unsubscribe", "list", "latest", "digest", "schedule_digest", "unschedule_digest"],
+                    "description": "Action to perform"
+                },
+                "url": {
+                    "type": "string",
+                    "description": "Feed URL (for subscribe)"
+                },
+                "feed": {
+                    "type": "string",
+                    "description": "Feed id or URL (for unsubscribe, latest; optional for digest)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum entries per feed (for latest, digest)"
+                },
+                "when": {
+                    "type": "string",
+                    "description": "Recurring schedule for schedule_digest: 'every day at 8am', 'every monday at 9am', or a cron expression"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ZeptoError::Tool("Missing 'action' argument".into()))?;
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .map(|n| n.clamp(1, 100) as usize)
+            .unwrap_or(self.config.max_items_per_feed.max(1));
+
+        let output = match action {
+            "subscribe" => self.execute_subscribe(&args).await?,
+            "unsubscribe" => {
+                let feed = str_arg(&args, "feed")
+                    .or_else(|| str_arg(&args, "url"))
+                    .ok_or_else(|| ZeptoError::Tool("Missing 'feed' for unsubscribe".into()))?;
+                match self.store.lock().await.remove(feed)? {
+                    Some(sub) => format!("Unsubscribed from {} ({})", sub.label(), sub.url),
+                    None => format!("No subscription '{}'", feed),
+                }
+            }
+            "list" => {
+                let store = self.store.lock().await;
+                if store.feeds().is_empty() {
+                    "No feed subscriptions".to_string()
+                } else {
+                    let lines: Vec<String> = store
+                        .feeds()
+                        .iter()
+                        .map(|f| {
+                            let mut line = format!("[{}] {} — {}", f.id, f.label(), f.url);
+                            if let Some(err) = &f.last_error {
+                                line.push_str(&format!(" (last error: {})", err));
+                            }
+                            line
+                        })
+                        .collect();
+                    format!("{} feeds:\n{}", lines.len(), lines.join("\n"))
+                }
+            }
+            "latest" => {
+                let query = str_arg(&args, "feed")
+                    .or_else(|| str_arg(&args, "url"))
+                    .ok_or_else(|| ZeptoError::Tool("Missing 'feed' for latest".into()))?;
+                let url = {
+                    let store = self.store.lock().await;
+                    store
+                        .find(query)
+                        .map(|f| f.url.clone())
+                        .unwrap_or_else(|| query.to_string())
+                };
+                let feed = self.fetch(&url).await?;
+                let lines: Vec<String> =
+                    feed.entries.iter().take(limit).map(format_entry).collect();
+                format!(
+                    "{} — latest {} entries:\n{}",
+                    feed.title.as_deref().unwrap_or(&url),
+                    lines.len(),
+                    lines.join("\n")
+                )
+            }
+            "digest" => self.execute_digest(str_arg(&args, "feed"), limit).await?,
+            "schedule_digest" => {
+                let when = str_arg(&args, "when")
+                    .ok_or_else(|| ZeptoError::Tool("Missing 'when' for schedule_digest".into()))?;
+                let (Some(channel), Some(chat_id)) =
+                    (ctx.channel.as_deref(), ctx.chat_id.as_deref())
+                else {
+                    return Err(ZeptoError::Tool(
+                        "schedule_digest needs a chat to deliver to".into(),
+                    ));
+                };
+                let schedule = parse_digest_schedule(when)?;
+                let job_id = self.schedule_digest(schedule, channel, chat_id).await?;
+                format!(
+                    "Scheduled feed digest '{}' to {}:{} (cron job {})",
+                    when, channel, chat_id, job_id
+                )
+            }
+            "unschedule_digest" => {
+                let cron = self.cron.as_ref().ok_or_else(|| {
+                    ZeptoError::Tool("Scheduling a digest requires the cron service".into())
+                })?;
+                match self.remove_digest_jobs(cron).await? {
+                    0 => "No feed digest was scheduled".to_string(),
+                    _ => "Feed digest unscheduled".to_string(),
+                }
+            }
+            other => return Err(ZeptoError::Tool(format!("Unknown rss action '{}'", other))),
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+impl RssTool {
+    async fn execute_subscribe(&self, args: &Value) -> Result<String> {
+        let url = str_arg(args, "url")
+            .ok_or_else(|| ZeptoError::Tool("Missing 'url' for subscribe".into()))?;
+        {
+            let store = self.store.lock().await;
+            if let Some(existing) = store.find(url) {
+                return Ok(format!(
+                    "Already subscribed to {} as [{}]",
+                    existing.label(),
+                    existing.id
+                ));
+            }
+            if store.feeds().len() >= self.config.max_feeds {
+                return Err(ZeptoError::Tool(format!(
+                    "Feed limit reached ({}). Unsubscribe from a feed first",
+                    self.config.max_feeds
+                )));
+            }
+        }
+
+        // Validate the feed and mark current entries as seen, so the first
+        // digest only reports what is published after subscribing.
+        let feed = self.fetch(url).await?;
+        let seen: VecDeque<String> = feed.entries.iter().map(FeedEntry::key).collect();
+        let sub = self.store.lock().await.add(url, feed.title.clone(), seen)?;
+
+        let recent: Vec<String> = feed
+            .entries
+            .iter()
+            .take(3)
+            .map(|e| format!("- {}", e.title))
+            .collect();
+        Ok(format!(
+            "Subscribed to {} as [{}] ({} entries). Most recent:\n{}",
+            sub.label(),
+            sub.id,
+            feed.entries.len(),
+            recent.join("\n")
+        ))
+    }
+
+    async fn execute_digest(&self, only: Option<&str>, limit: usize) -> Result<String> {
+        let targets: Vec<(String, String)> = {
+            let store = self.store.lock().await;
+            match only {
+                Some(query) => {
+                    let feed = store
+                        .find(query)
+                        .ok_or_else(|| ZeptoError::Tool(format!("No subscription '{}'", query)))?;
+                    vec![(feed.id.clone(), feed.url.clone())]
+                }
+                None => store
+                    .feeds()
+                    .iter()
+                    .map(|f| (f.id.clone(), f.url.clone()))
+                    .collect(),
+            }
+        };
+        if targets.is_empty() {
+            return Ok("No feed subscriptions. Use subscribe first".to_string());
+        }
+
+        // Fetch without holding the store lock.
+        let mut fetched = Vec::with_capacity(targets.len());
+        for (id, url) in targets {
+            fetched.push((id, self.fetch(&url).await));
+        }
+
+        let mut store = self.store.lock().await;
+        let mut sections = Vec::new();
+        let mut errors = Vec::new();
+        let mut total = 0;
+        for (id, result) in fetched {
+            let Some(sub) = store.feeds.iter_mut().find(|f| f.id == id) else {
+                continue;
+            };
+            sub.last_checked_at = Some(now_secs());
+            match result {
+                Ok(feed) => {
+                    sub.last_error = None;
+                    if sub.title.is_none() {
+                        sub.title = feed.title.clone();
+                    }
+                    let fresh = sub.take_unseen(&feed);
+                    if fresh.is_empty() {
+                        continue;
+                    }
+                    total += fresh.len();
+                    let mut lines: Vec<String> =
+                        fresh.iter().take(limit).map(format_entry).collect();
+                    if fresh.len() > limit {
+                        lines.push(format!("  (+{} more)", fresh.len() - limit));
+                    }
+                    sections.push(format!("## {}\n{}", sub.label(), lines.join("\n")));
+                }
+                Err(e) => {
+                    sub.last_error = Some(e.to_string());
+                    errors.push(format!("{}: {}", sub.label(), e));
+                }
+            }
+        }
+        store.save()?;
+
+        let mut out = if total == 0 {
+            "No new feed entries since the last digest.".to_string()
+        } else {
+            format!("{} new entries:\n\n{}", total, sections.join("\n\n"))
+        };
+        if !errors.is_empty() {
+            out.push_str(&format!("\n\nFailed feeds:\n{}", errors.join("\n")));
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+  <channel>
+    <title>Example News</title>
+    <link>https://example.com/</link>
+    <item>
+      <title>First &amp; foremost</title>
+      <link>https://example.com/1</link>
+      <guid>urn:1</guid>
+      <pubDate>Tue, 13 Oct 2026 08:00:00 GMT</pubDate>
+      <description><![CDATA[<p>Hello <b>world</b>&nbsp;!</p>]]></description>
+    </item>
+    <item>
+      <title>Second</title>
+      <link>https://example.com/2</link>
+    </item>
+  </channel>
+</rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Example Blog</title>
+  <link href="https://blog.example.com/"/>
+  <entry>
+    <title type="html">Post &lt;one&gt;</title>
+    <link rel="edit" href="https://blog.example.com/edit/1"/>
+    <link href="https://blog.example.com/1"/>
+    <id>tag:blog.example.com,2026:1</id>
+    <updated>2026-10-13T08:00:00Z</updated>
+    <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml">Body <em>text</em></div></content>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn test_parse_rss() {
+        let feed = parse_feed(RSS).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Example News"));
+        assert_eq!(feed.entries.len(), 2);
+        let first = &feed.entries[0];
+        assert_eq!(first.title, "First & foremost");
+        assert_eq!(first.link.as_deref(), Some("https://example.com/1"));
+        assert_eq!(first.key(), "urn:1");
+        assert_eq!(first.summary, "Hello world !");
+        assert_eq!(feed.entries[1].key(), "https://example.com/2");
+    }
+
+    #[test]
+    fn test_parse_atom() {
+        let feed = parse_feed(ATOM).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Example Blog"));
+        assert_eq!(feed.entries.len(), 1);
+        let entry = &feed.entries[0];
+        assert_eq!(entry.title, "Post");
+        assert_eq!(entry.link.as_deref(), Some("https://blog.example.com/1"));
+        assert_eq!(entry.published.as_deref(), Some("2026-10-13T08:00:00Z"));
+        assert_eq!(entry.summary, "Body text");
+    }
+
+    #[test]
+    fn test_parse_rejects_non_feed() {
+        assert!(parse_feed("<html><body>hi</body></html>").is_err());
+        assert!(parse_feed("").is_err());
+    }
+
+    #[test]
+    fn test_take_unseen_dedupes_and_caps() {
+        let feed = parse_feed(RSS).unwrap();
+        let mut sub = Subscription {
+            id: "f1".into(),
+            url: "https://example.com/feed".into(),
+            title: None,
+            added_at: 0,
+            last_checked_at: None,
+            last_error: None,
+            seen: VecDeque::from(vec!["urn:1".to_string()]),
+        };
+        let fresh = sub.take_unseen(&feed);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].title, "Second");
+        assert!(sub.take_unseen(&feed).is_empty());
+
+        sub.seen = (0..MAX_SEEN_PER_FEED).map(|i| i.to_string()).collect();
+        sub.take_unseen(&feed);
+        assert_eq!(sub.seen.len(), MAX_SEEN_PER_FEED);
+        assert_eq!(
+            sub.seen.back().map(String::as_str),
+            Some("https://example.com/2")
+        );
+    }
+
+    #[test]
+    fn test_store_persists_subscriptions() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("rss").join("feeds.json");
+        let mut store = RssStore::open(path.clone()).unwrap();
+        store
+            .add(
+                "https://example.com/feed",
+                Some("Example".into()),
+                VecDeque::new(),
+            )
+            .unwrap();
+        let store = RssStore::open(path).unwrap();
+        assert_eq!(store.feeds().len(), 1);
+        assert_eq!(store.find("f1").unwrap().url, "https://example.com/feed");
+        assert!(store.find("https://example.com/feed").is_some());
+    }
+
+    #[test]
+    fn test_parse_digest_schedule() {
+        assert!(matches!(
+            parse_digest_schedule("0 8 * * *").unwrap(),
+            CronSchedule::Cron { .. }
+        ));
+        assert!(parse_digest_schedule("every day at 8am").is_ok());
+        assert!(parse_digest_schedule("tomorrow at 8am").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tool_rejects_private_feed_urls() {
+        let dir = TempDir::new().unwrap();
+        let tool = RssTool::new(
+            &dir.path().to_string_lossy(),
+            RssToolConfig::default(),
+            None,
+        )
+        .unwrap();
+        let err = tool
+            .execute(
+                json!({"action": "subscribe", "url": "http://127.0.0.1/feed.xml"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Blocked"));
+
+        let out = tool
+            .execute(json!({"action": "digest"}), &ToolContext::new())
+            .await
+            .unwrap();
+        assert!(out.for_llm.contains("No feed subscriptions"));
+
+        let err = tool
+            .execute(
+                json!({"action": "schedule_digest", "when": "every day at 8am"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("chat"));
+    }
+}
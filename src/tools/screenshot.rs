@@ -3,8 +3,14 @@
 //! Captures screenshots of web pages using a headless Chromium browser
 //! via the Chrome DevTools Protocol. Includes full SSRF protection by
 //! reusing the validation from [`super::web`].
+//!
+//! Captures the viewport, the full scrollable page, or a single element
+//! (CSS selector), as PNG/JPEG or printed to PDF, optionally emulating a
+//! device preset. Without `output_path` the artifact is saved into the
+//! workspace media store and its workspace-relative path is returned.
 
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -15,6 +21,7 @@ use chromiumoxide::cdp::browser_protocol::fetch::{
     EventRequestPaused, FailRequestParams, RequestPattern,
 };
 use chromiumoxide::cdp::browser_protocol::network::ErrorReason;
+use chromiumoxide::cdp::browser_protocol::page::{CaptureScreenshotFormat, PrintToPdfParams};
 use chromiumoxide::handler::viewport::Viewport;
 use chromiumoxide::page::ScreenshotParams;
 use futures::StreamExt;
@@ -23,6 +30,7 @@ use serde_json::{json, Value};
 use tokio::time::timeout;
 
 use crate::error::{Result, ZeptoError};
+use crate::session::media::MediaStore;
 
 use super::web::{is_blocked_host, resolve_and_check_host};
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
@@ -48,6 +56,161 @@ const MAX_DIMENSION: u32 = 3840;
 /// Maximum allowed redirect hops for the main document navigation.
 const MAX_SCREENSHOT_REDIRECT_HOPS: usize = 5;
 
+/// Viewport and user agent emulated for a `device` preset.
+#[derive(Debug)]
+struct DevicePreset {
+    name: &'static str,
+    width: u32,
+    height: u32,
+    scale: f64,
+    mobile: bool,
+    user_agent: Option<&'static str>,
+}
+
+const DEVICE_PRESETS: &[DevicePreset] = &[
+    DevicePreset {
+        name: "desktop",
+        width: DEFAULT_WIDTH,
+        height: DEFAULT_HEIGHT,
+        scale: 1.0,
+        mobile: false,
+        user_agent: None,
+    },
+    DevicePreset {
+        name: "laptop",
+        width: 1440,
+        height: 900,
+        scale: 2.0,
+        mobile: false,
+        user_agent: None,
+    },
+    DevicePreset {
+        name: "tablet",
+        width: 820,
+        height: 1180,
+        scale: 2.0,
+        mobile: true,
+        user_agent: Some(
+            "Mozilla/5.0 (iPad; CPU OS 17_0 like Mac OS X) AppleWebKit/605.1.15 \
+             (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1",
+        ),
+    },
+    DevicePreset {
+        name: "mobile",
+        width: 390,
+        height: 844,
+        scale: 3.0,
+        mobile: true,
+        user_agent: Some(
+            "Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) AppleWebKit/605.1.15 \
+             (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1",
+        ),
+    },
+];
+
+/// Look up a device preset by name (case-insensitive).
+fn device_preset(name: &str) -> Result<&'static DevicePreset> {
+    DEVICE_PRESETS
+        .iter()
+        .find(|preset| preset.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| {
+            let names: Vec<&str> = DEVICE_PRESETS.iter().map(|p| p.name).collect();
+            ZeptoError::Tool(format!(
+                "Unknown device '{}'. Available: {}",
+                name,
+                names.join(", ")
+            ))
+        })
+}
+
+/// Artifact format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Png,
+    Jpeg,
+    Pdf,
+}
+
+impl OutputFormat {
+    fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "png" => Ok(Self::Png),
+            "jpeg" | "jpg" => Ok(Self::Jpeg),
+            "pdf" => Ok(Self::Pdf),
+            other => Err(ZeptoError::Tool(format!(
+                "Unknown format '{}'. Use png, jpeg or pdf",
+                other
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpeg",
+            Self::Pdf => "pdf",
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Pdf => "application/pdf",
+        }
+    }
+
+    fn screenshot_format(self) -> CaptureScreenshotFormat {
+        match self {
+            Self::Jpeg => CaptureScreenshotFormat::Jpeg,
+            _ => CaptureScreenshotFormat::Png,
+        }
+    }
+}
+
+/// What part of the page to capture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CaptureTarget {
+    Viewport,
+    FullPage,
+    Element(String),
+}
+
+/// Parse `format`, `full_page` and `selector`. PDF always prints the whole
+/// document, so it combines with neither.
+fn parse_capture(args: &Value) -> Result<(CaptureTarget, OutputFormat)> {
+    let format = match args.get("format").and_then(|v| v.as_str()) {
+        Some(value) => OutputFormat::parse(value)?,
+        None => OutputFormat::Png,
+    };
+    let full_page = args
+        .get("full_page")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let selector = args
+        .get("selector")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+
+    let target = match (selector, full_page) {
+        (Some(_), true) => {
+            return Err(ZeptoError::Tool(
+                "Use either 'selector' or 'full_page', not both".to_string(),
+            ));
+        }
+        (Some(selector), false) => CaptureTarget::Element(selector.to_string()),
+        (None, true) => CaptureTarget::FullPage,
+        (None, false) => CaptureTarget::Viewport,
+    };
+    if format == OutputFormat::Pdf && target != CaptureTarget::Viewport {
+        return Err(ZeptoError::Tool(
+            "PDF output prints the whole page; 'selector' and 'full_page' don't apply".to_string(),
+        ));
+    }
+    Ok((target, format))
+}
+
 /// Web screenshot tool that captures full-page screenshots of URLs.
 ///
 /// Uses a headless Chromium browser via the Chrome DevTools Protocol.
@@ -189,7 +352,9 @@ impl Tool for WebScreenshotTool {
 
     /// Describe what this tool does.
     fn description(&self) -> &str {
-        "Take a screenshot of a web page. Returns base64-encoded PNG or saves to a file path."
+        "Take a screenshot of a web page: viewport, full page or one element (CSS selector), \
+         as PNG/JPEG or PDF, optionally emulating a device. Saves into the workspace media \
+         store and returns the path."
     }
 
     /// Provide a compact description for constrained UIs.
@@ -214,7 +379,25 @@ impl Tool for WebScreenshotTool {
                 },
                 "output_path": {
                     "type": "string",
-                    "description": "File path to save the screenshot PNG. If omitted, returns base64-encoded data."
+                    "description": "File path to save the artifact. If omitted, it is saved into the workspace media store (base64 data without a workspace)."
+                },
+                "full_page": {
+                    "type": "boolean",
+                    "description": "Capture the full scrollable page instead of the viewport (default: false)"
+                },
+                "selector": {
+                    "type": "string",
+                    "description": "CSS selector of a single element to capture"
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["png", "jpeg", "pdf"],
+                    "description": "Output format (default: png). pdf prints the page"
+                },
+                "device": {
+                    "type": "string",
+                    "enum": ["desktop", "laptop", "tablet", "mobile"],
+                    "description": "Viewport/device emulation preset; width/height override its size"
                 },
                 "timeout_secs": {
                     "type": "integer",
@@ -224,13 +407,13 @@ impl Tool for WebScreenshotTool {
                 },
                 "width": {
                     "type": "integer",
-                    "description": "Viewport width in pixels (default: 1280)",
+                    "description": "Viewport width in pixels (default: 1280 or the device's)",
                     "minimum": MIN_DIMENSION,
                     "maximum": MAX_DIMENSION
                 },
                 "height": {
                     "type": "integer",
-                    "description": "Viewport height in pixels (default: 720)",
+                    "description": "Viewport height in pixels (default: 720 or the device's)",
                     "minimum": MIN_DIMENSION,
                     "maximum": MAX_DIMENSION
                 }
@@ -240,7 +423,7 @@ impl Tool for WebScreenshotTool {
     }
 
     /// Execute screenshot capture with browser-native SSRF redirect checks.
-    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
         // ---- Parse and validate URL ----
         let url_str = args
             .get("url")
@@ -258,6 +441,13 @@ impl Tool for WebScreenshotTool {
             .unwrap_or(DEFAULT_TIMEOUT_SECS)
             .clamp(1, MAX_TIMEOUT_SECS);
 
+        let (target, format) = parse_capture(&args)?;
+
+        let device = match args.get("device").and_then(|v| v.as_str()) {
+            Some(name) => device_preset(name)?,
+            None => &DEVICE_PRESETS[0],
+        };
+
         match parsed.scheme() {
             "http" | "https" => {}
             other => {
@@ -292,27 +482,31 @@ impl Tool for WebScreenshotTool {
             .get("width")
             .and_then(|v| v.as_u64())
             .map(|v| (v as u32).clamp(MIN_DIMENSION, MAX_DIMENSION))
-            .unwrap_or(DEFAULT_WIDTH);
+            .unwrap_or(device.width);
 
         let height = args
             .get("height")
             .and_then(|v| v.as_u64())
             .map(|v| (v as u32).clamp(MIN_DIMENSION, MAX_DIMENSION))
-            .unwrap_or(DEFAULT_HEIGHT);
+            .unwrap_or(device.height);
 
         // ---- Launch headless browser ----
-        let browser_config = BrowserConfig::builder()
+        let mut browser_config = BrowserConfig::builder()
             .no_sandbox()
             .viewport(Some(Viewport {
                 width,
                 height,
-                device_scale_factor: None,
-                emulating_mobile: false,
-                is_landscape: false,
-                has_touch: false,
+                device_scale_factor: Some(device.scale),
+                emulating_mobile: device.mobile,
+                is_landscape: device.mobile && width > height,
+                has_touch: device.mobile,
             }))
             .arg("--disable-gpu")
-            .arg("--disable-dev-shm-usage")
+            .arg("--disable-dev-shm-usage");
+        if let Some(user_agent) = device.user_agent {
+            browser_config = browser_config.arg(format!("--user-agent={}", user_agent));
+        }
+        let browser_config = browser_config
             .build()
             .map_err(|e| ZeptoError::Tool(format!("Failed to configure browser: {}", e)))?;
 
@@ -361,16 +555,44 @@ impl Tool for WebScreenshotTool {
             .map_err(|e| ZeptoError::Tool(format!("Failed to enable request interception: {}", e)))?;
 
             let nav_page = page.clone();
+            let capture_target = target.clone();
             let screenshot_future = async move {
                 nav_page
                     .goto(url_str)
                     .await
                     .map_err(|e| ZeptoError::Tool(format!("Failed to open page: {}", e)))?;
 
-                let screenshot_bytes = nav_page
-                    .screenshot(ScreenshotParams::builder().full_page(false).build())
-                    .await
-                    .map_err(|e| ZeptoError::Tool(format!("Failed to capture screenshot: {}", e)))?;
+                let screenshot_bytes = match (&capture_target, format) {
+                    (_, OutputFormat::Pdf) => nav_page
+                        .pdf(PrintToPdfParams {
+                            print_background: Some(true),
+                            ..Default::default()
+                        })
+                        .await
+                        .map_err(|e| ZeptoError::Tool(format!("Failed to print PDF: {}", e)))?,
+                    (CaptureTarget::Element(selector), _) => nav_page
+                        .find_element(selector.as_str())
+                        .await
+                        .map_err(|e| {
+                            ZeptoError::Tool(format!("No element matches '{}': {}", selector, e))
+                        })?
+                        .screenshot(format.screenshot_format())
+                        .await
+                        .map_err(|e| {
+                            ZeptoError::Tool(format!("Failed to capture element: {}", e))
+                        })?,
+                    (target, _) => nav_page
+                        .screenshot(
+                            ScreenshotParams::builder()
+                                .format(format.screenshot_format())
+                                .full_page(*target == CaptureTarget::FullPage)
+                                .build(),
+                        )
+                        .await
+                        .map_err(|e| {
+                            ZeptoError::Tool(format!("Failed to capture screenshot: {}", e))
+                        })?,
+                };
 
                 Ok::<Vec<u8>, ZeptoError>(screenshot_bytes)
             };
@@ -449,35 +671,41 @@ impl Tool for WebScreenshotTool {
 
         let screenshot_result = screenshot_result?;
 
-        // ---- Output: save or encode ----
-        let result = if let Some(path) = output_path {
+        // ---- Output: save to path, media store, or encode ----
+        let mut result = json!({
+            "url": url_str,
+            "format": format.name(),
+            "device": device.name,
+            "size_bytes": screenshot_result.len(),
+            "width": width,
+            "height": height,
+        });
+        if let CaptureTarget::Element(selector) = &target {
+            result["selector"] = json!(selector);
+        }
+        if target == CaptureTarget::FullPage {
+            result["full_page"] = json!(true);
+        }
+
+        if let Some(path) = output_path {
             tokio::fs::write(&path, &screenshot_result)
                 .await
                 .map_err(|e| {
                     ZeptoError::Tool(format!("Failed to write screenshot to '{}': {}", path, e))
                 })?;
-
-            json!({
-                "url": url_str,
-                "output_path": path,
-                "size_bytes": screenshot_result.len(),
-                "width": width,
-                "height": height,
-            })
-            .to_string()
+            result["output_path"] = json!(path);
+        } else if let Some(workspace) = &ctx.workspace {
+            let path = MediaStore::new(PathBuf::from(workspace))
+                .save(&screenshot_result, format.mime_type())
+                .await
+                .map_err(|e| ZeptoError::Tool(format!("Failed to save screenshot: {}", e)))?;
+            result["path"] = json!(path);
         } else {
-            let encoded = base64::engine::general_purpose::STANDARD.encode(&screenshot_result);
-            json!({
-                "url": url_str,
-                "format": "png",
-                "encoding": "base64",
-                "size_bytes": screenshot_result.len(),
-                "width": width,
-                "height": height,
-                "data": encoded,
-            })
-            .to_string()
-        };
+            result["encoding"] = json!("base64");
+            result["data"] =
+                json!(base64::engine::general_purpose::STANDARD.encode(&screenshot_result));
+        }
+        let result = result.to_string();
 
         Ok(ToolOutput::llm_only(result))
     }
@@ -520,6 +748,10 @@ mod tests {
         assert!(params["properties"]["timeout_secs"].is_object());
         assert!(params["properties"]["width"].is_object());
         assert!(params["properties"]["height"].is_object());
+        assert!(params["properties"]["full_page"].is_object());
+        assert!(params["properties"]["selector"].is_object());
+        assert!(params["properties"]["format"].is_object());
+        assert!(params["properties"]["device"].is_object());
 
         // "url" is required
         let required = params["required"]
@@ -819,6 +1051,72 @@ mod tests {
         assert_eq!(clamp_timeout(60), 60);
     }
 
+    #[test]
+    fn test_parse_capture_targets() {
+        assert_eq!(
+            parse_capture(&json!({})).unwrap(),
+            (CaptureTarget::Viewport, OutputFormat::Png)
+        );
+        assert_eq!(
+            parse_capture(&json!({"full_page": true, "format": "jpg"})).unwrap(),
+            (CaptureTarget::FullPage, OutputFormat::Jpeg)
+        );
+        assert_eq!(
+            parse_capture(&json!({"selector": " #chart "})).unwrap(),
+            (
+                CaptureTarget::Element("#chart".to_string()),
+                OutputFormat::Png
+            )
+        );
+        assert_eq!(
+            parse_capture(&json!({"format": "PDF", "selector": ""})).unwrap(),
+            (CaptureTarget::Viewport, OutputFormat::Pdf)
+        );
+    }
+
+    #[test]
+    fn test_parse_capture_rejects_conflicts() {
+        for args in [
+            json!({"selector": "main", "full_page": true}),
+            json!({"format": "pdf", "full_page": true}),
+            json!({"format": "pdf", "selector": "main"}),
+            json!({"format": "gif"}),
+        ] {
+            assert!(parse_capture(&args).is_err(), "{}", args);
+        }
+    }
+
+    #[test]
+    fn test_device_presets() {
+        let mobile = device_preset("Mobile").unwrap();
+        assert!(mobile.mobile);
+        assert!(mobile.user_agent.unwrap().contains("iPhone"));
+        assert_eq!(device_preset("desktop").unwrap().width, DEFAULT_WIDTH);
+        let err = device_preset("watch").unwrap_err().to_string();
+        assert!(err.contains("desktop, laptop, tablet, mobile"), "{}", err);
+    }
+
+    #[test]
+    fn test_output_format_mime_types() {
+        assert_eq!(OutputFormat::Png.mime_type(), "image/png");
+        assert_eq!(OutputFormat::Jpeg.mime_type(), "image/jpeg");
+        assert_eq!(OutputFormat::Pdf.mime_type(), "application/pdf");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_device_rejected_before_launch() {
+        let tool = WebScreenshotTool::new();
+        let ctx = ToolContext::new();
+
+        let result = tool
+            .execute(
+                json!({"url": "https://example.com", "device": "fridge"}),
+                &ctx,
+            )
+            .await;
+        assert!(result.unwrap_err().to_string().contains("Unknown device"));
+    }
+
     // Note: We intentionally do NOT test actual browser launching here.
     // That requires Chrome/Chromium to be installed and is covered by
     // integration tests, not unit tests.
@@ -0,0 +1,435 @@
+//! Serial tool -- raw UART access to discovered boards.
+//!
+//! The `SerialTool` dispatches based on the `action` parameter:
+//! - `ports` -- list serial ports, enriched with USB VID/PID and board names
+//! - `open` -- open a port at a baud rate (ports are also opened on first use)
+//! - `send` -- write data, then read the response until a terminator or timeout
+//! - `read` -- read pending output until a terminator or timeout
+//! - `close` -- close an open port
+//!
+//! Unlike the JSON-RPC peripherals in `crate::peripherals`, this speaks plain
+//! text, so it works with AT-command modems, REPL firmware (MicroPython,
+//! Arduino sketches), and GPS/sensor modules. Paths are restricted to the
+//! same serial device prefixes as the peripherals, plus the optional
+//! `tools.serial.allowed_ports` list. The tool is `ToolCategory::Hardware`,
+//! so agent modes that gate hardware require approval before it runs.
+//!
+//! When compiled WITHOUT the `hardware` feature, the tool returns an informative
+//! error directing the user to rebuild with the feature enabled.
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::config::SerialToolConfig;
+use crate::error::{Result, ZeptoError};
+use crate::tools::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Default response timeout (milliseconds).
+#[cfg(feature = "hardware")]
+const DEFAULT_TIMEOUT_MS: u64 = 2_000;
+
+/// Upper bound for a caller-supplied timeout (milliseconds).
+#[cfg(feature = "hardware")]
+const MAX_TIMEOUT_MS: u64 = 30_000;
+
+/// Maximum bytes collected per read (64 KB).
+#[cfg(feature = "hardware")]
+const MAX_READ_BYTES: usize = 64 * 1024;
+
+/// Check a port path against the serial prefix allowlist and the configured
+/// port list.
+#[cfg_attr(not(feature = "hardware"), allow(dead_code))]
+fn check_port(config: &SerialToolConfig, path: &str) -> Result<()> {
+    crate::peripherals::validate_serial_path(path).map_err(ZeptoError::SecurityViolation)?;
+    if !config.allowed_ports.is_empty() && !config.allowed_ports.iter().any(|p| p == path) {
+        return Err(ZeptoError::SecurityViolation(format!(
+            "Serial port {} is not in tools.serial.allowed_ports",
+            path
+        )));
+    }
+    Ok(())
+}
+
+/// Decode the `line_ending` argument.
+#[cfg_attr(not(feature = "hardware"), allow(dead_code))]
+fn line_ending(value: Option<&str>) -> Result<&'static str> {
+    match value.unwrap_or("lf") {
+        "lf" => Ok("\n"),
+        "crlf" => Ok("\r\n"),
+        "cr" => Ok("\r"),
+        "none" => Ok(""),
+        other => Err(ZeptoError::Tool(format!(
+            "Invalid line_ending '{}'. Use lf, crlf, cr, or none",
+            other
+        ))),
+    }
+}
+
+// ============================================================================
+// Feature-gated implementation (with hardware feature)
+// ============================================================================
+
+#[cfg(feature = "hardware")]
+mod imp {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::sync::Mutex;
+    use tokio::time::Instant;
+    use tokio_serial::{SerialPortBuilderExt, SerialPortType, SerialStream};
+
+    use super::*;
+
+    /// An open port and its baud rate.
+    pub(super) struct OpenPort {
+        pub(super) stream: SerialStream,
+        pub(super) baud: u32,
+    }
+
+    /// Open ports keyed by path.
+    pub(super) type PortMap = Mutex<HashMap<String, OpenPort>>;
+
+    pub(super) fn open_port(path: &str, baud: u32) -> Result<OpenPort> {
+        let stream = tokio_serial::new(path, baud)
+            .open_native_async()
+            .map_err(|e| ZeptoError::Tool(format!("Failed to open {}: {}", path, e)))?;
+        Ok(OpenPort { stream, baud })
+    }
+
+    /// List serial ports that pass the path allowlist.
+    pub(super) fn list_ports(config: &SerialToolConfig) -> Result<Vec<Value>> {
+        let ports = tokio_serial::available_ports()
+            .map_err(|e| ZeptoError::Tool(format!("Serial port enumeration failed: {e}")))?;
+        Ok(ports
+            .into_iter()
+            .filter(|p| check_port(config, &p.port_name).is_ok())
+            .map(|p| match p.port_type {
+                SerialPortType::UsbPort(usb) => {
+                    let board = crate::hardware::registry::lookup_board(usb.vid, usb.pid);
+                    serde_json::json!({
+                        "path": p.port_name,
+                        "vid": format!("{:04x}", usb.vid),
+                        "pid": format!("{:04x}", usb.pid),
+                        "product": usb.product,
+                        "board": board.map(|b| b.name),
+                        "architecture": board.and_then(|b| b.architecture),
+                    })
+                }
+                _ => serde_json::json!({ "path": p.port_name }),
+            })
+            .collect())
+    }
+
+    /// Read until `until` appears in the output, `MAX_READ_BYTES` is reached,
+    /// or `timeout` expires. An empty `until` reads for the full timeout.
+    pub(super) async fn read_response(
+        stream: &mut SerialStream,
+        until: &str,
+        timeout: Duration,
+    ) -> Result<(String, bool)> {
+        let deadline = Instant::now() + timeout;
+        let mut collected: Vec<u8> = Vec::new();
+        let mut chunk = [0u8; 1024];
+        loop {
+            if !until.is_empty() && contains(&collected, until.as_bytes()) {
+                return Ok((String::from_utf8_lossy(&collected).into_owned(), true));
+            }
+            if collected.len() >= MAX_READ_BYTES {
+                collected.truncate(MAX_READ_BYTES);
+                return Ok((String::from_utf8_lossy(&collected).into_owned(), false));
+            }
+            match tokio::time::timeout_at(deadline, stream.read(&mut chunk)).await {
+                Ok(Ok(0)) => break,
+                Ok(Ok(n)) => collected.extend_from_slice(&chunk[..n]),
+                Ok(Err(e)) => return Err(ZeptoError::Tool(format!("Serial read failed: {e}"))),
+                Err(_) => break,
+            }
+        }
+        Ok((String::from_utf8_lossy(&collected).into_owned(), false))
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    pub(super) async fn write_all(stream: &mut SerialStream, data: &[u8]) -> Result<()> {
+        stream
+            .write_all(data)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Serial write failed: {e}")))?;
+        stream
+            .flush()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Serial flush failed: {e}")))
+    }
+}
+
+/// Agent-facing serial tool for talking to boards over UART.
+pub struct SerialTool {
+    #[cfg_attr(not(feature = "hardware"), allow(dead_code))]
+    config: SerialToolConfig,
+    #[cfg(feature = "hardware")]
+    ports: imp::PortMap,
+}
+
+impl SerialTool {
+    /// Create a new SerialTool.
+    pub fn new(config: SerialToolConfig) -> Self {
+        Self {
+            config,
+            #[cfg(feature = "hardware")]
+            ports: Default::default(),
+        }
+    }
+
+    #[cfg(feature = "hardware")]
+    fn path_arg<'a>(&self, args: &'a Value, action: &str) -> Result<&'a str> {
+        let path = args
+            .get("port")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| {
+                ZeptoError::Tool(format!("Missing 'port' parameter for {} action", action))
+            })?;
+        check_port(&self.config, path)?;
+        Ok(path)
+    }
+}
+
+impl Default for SerialTool {
+    fn default() -> Self {
+        Self::new(SerialToolConfig::default())
+    }
+}
+
+#[async_trait]
+impl Tool for SerialTool {
+    fn name(&self) -> &str {
+        "serial"
+    }
+
+    fn description(&self) -> &str {
+        "Talk to boards over a serial/UART port (AT modems, MicroPython/Arduino REPLs, sensors). \
+         Actions: ports, open, send, read, close. send writes data then reads the reply until \
+         'until' (default: newline) or the timeout."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Serial port send/read"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Hardware
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["ports", "open", "send", "read", "close"],
+                    "description": "The serial action to perform"
+                },
+                "port": {
+                    "type": "string",
+                    "description": "Serial port path, e.g. /dev/ttyACM0 or COM3 (for open, send, read, close)"
+                },
+                "baud": {
+                    "type": "integer",
+                    "description": "Baud rate (for open; default from tools.serial.default_baud_rate)"
+                },
+                "data": {
+                    "type": "string",
+                    "description": "Text to write (for send)"
+                },
+                "line_ending": {
+                    "type": "string",
+                    "enum": ["lf", "crlf", "cr", "none"],
+                    "description": "Appended to data (for send; default: lf)"
+                },
+                "until": {
+                    "type": "string",
+                    "description": "Stop reading once this text is received (default: newline; empty string reads until timeout)"
+                },
+                "timeout_ms": {
+                    "type": "integer",
+                    "description": "Read timeout in milliseconds (default 2000, max 30000)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    #[cfg(feature = "hardware")]
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        use std::time::Duration;
+
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZeptoError::Tool("Missing 'action' parameter".into()))?;
+        let timeout = Duration::from_millis(
+            args.get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_TIMEOUT_MS)
+                .clamp(1, MAX_TIMEOUT_MS),
+        );
+        let until = args.get("until").and_then(|v| v.as_str()).unwrap_or("\n");
+
+        match action {
+            "ports" => {
+                let ports = imp::list_ports(&self.config)?;
+                if ports.is_empty() {
+                    Ok(ToolOutput::llm_only(
+                        "No serial ports found. Connect a board via USB and try again.".to_string(),
+                    ))
+                } else {
+                    serde_json::to_string_pretty(&ports)
+                        .map(ToolOutput::llm_only)
+                        .map_err(|e| ZeptoError::Tool(format!("JSON serialize error: {e}")))
+                }
+            }
+            "open" => {
+                let path = self.path_arg(&args, "open")?;
+                let baud = args
+                    .get("baud")
+                    .and_then(|v| v.as_u64())
+                    .map(|b| b as u32)
+                    .unwrap_or(self.config.default_baud_rate);
+                let port = imp::open_port(path, baud)?;
+                let replaced = self.ports.lock().await.insert(path.to_string(), port);
+                Ok(ToolOutput::llm_only(format!(
+                    "{} {} at {} baud",
+                    if replaced.is_some() {
+                        "Reopened"
+                    } else {
+                        "Opened"
+                    },
+                    path,
+                    baud
+                )))
+            }
+            "send" | "read" => {
+                let path = self.path_arg(&args, action)?;
+                let mut ports = self.ports.lock().await;
+                if !ports.contains_key(path) {
+                    let port = imp::open_port(path, self.config.default_baud_rate)?;
+                    ports.insert(path.to_string(), port);
+                }
+                let port = ports
+                    .get_mut(path)
+                    .ok_or_else(|| ZeptoError::Tool(format!("Port {} is not open", path)))?;
+
+                if action == "send" {
+                    let data = args.get("data").and_then(|v| v.as_str()).ok_or_else(|| {
+                        ZeptoError::Tool("Missing 'data' parameter for send action".into())
+                    })?;
+                    let ending = line_ending(args.get("line_ending").and_then(|v| v.as_str()))?;
+                    imp::write_all(&mut port.stream, format!("{}{}", data, ending).as_bytes())
+                        .await?;
+                }
+
+                let (text, matched) = imp::read_response(&mut port.stream, until, timeout).await?;
+                let note = if matched || until.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (no {:?} within {}ms)", until, timeout.as_millis())
+                };
+                Ok(ToolOutput::llm_only(if text.is_empty() {
+                    format!("No data from {} @ {} baud{}", path, port.baud, note)
+                } else {
+                    format!("{} @ {} baud{}:\n{}", path, port.baud, note, text)
+                }))
+            }
+            "close" => {
+                let path = self.path_arg(&args, "close")?;
+                match self.ports.lock().await.remove(path) {
+                    Some(_) => Ok(ToolOutput::llm_only(format!("Closed {}", path))),
+                    None => Ok(ToolOutput::llm_only(format!("{} was not open", path))),
+                }
+            }
+            other => Err(ZeptoError::Tool(format!(
+                "Unknown serial action: '{}'. Valid actions: ports, open, send, read, close",
+                other
+            ))),
+        }
+    }
+
+    #[cfg(not(feature = "hardware"))]
+    async fn execute(&self, _args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        Err(ZeptoError::Tool(
+            "Serial tool requires 'hardware' build feature. \
+             Rebuild with: cargo build --features hardware"
+                .into(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serial_tool_metadata() {
+        let tool = SerialTool::default();
+        assert_eq!(tool.name(), "serial");
+        assert_eq!(tool.category(), ToolCategory::Hardware);
+        assert_eq!(tool.parameters()["properties"]["action"]["type"], "string");
+    }
+
+    #[test]
+    fn test_check_port_uses_serial_allowlist() {
+        let config = SerialToolConfig::default();
+        assert!(check_port(&config, "/dev/ttyACM0").is_ok());
+        assert!(check_port(&config, "/dev/sda1").is_err());
+        assert!(check_port(&config, "/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_check_port_configured_ports() {
+        let config = SerialToolConfig {
+            allowed_ports: vec!["/dev/ttyUSB0".to_string()],
+            ..Default::default()
+        };
+        assert!(check_port(&config, "/dev/ttyUSB0").is_ok());
+        assert!(check_port(&config, "/dev/ttyUSB1").is_err());
+    }
+
+    #[test]
+    fn test_line_ending() {
+        assert_eq!(line_ending(None).unwrap(), "\n");
+        assert_eq!(line_ending(Some("crlf")).unwrap(), "\r\n");
+        assert_eq!(line_ending(Some("none")).unwrap(), "");
+        assert!(line_ending(Some("tab")).is_err());
+    }
+
+    #[cfg(not(feature = "hardware"))]
+    #[tokio::test]
+    async fn test_serial_tool_stub_returns_error() {
+        let tool = SerialTool::default();
+        let err = tool
+            .execute(serde_json::json!({"action": "ports"}), &ToolContext::new())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("cargo build --features hardware"));
+    }
+
+    #[cfg(feature = "hardware")]
+    #[tokio::test]
+    async fn test_serial_tool_rejects_disallowed_path() {
+        let tool = SerialTool::default();
+        let err = tool
+            .execute(
+                serde_json::json!({"action": "send", "port": "/etc/passwd", "data": "x"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not allowed"));
+    }
+}
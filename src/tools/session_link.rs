@@ -0,0 +1,104 @@
+//! Session link tool — lets the agent start a cross-channel handoff.
+//!
+//! The tool only issues a link code for the current conversation. Joining
+//! it still takes `/link <code>` and `/link confirm` from the user on the
+//! other channel, so the agent can never link sessions on its own.
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::error::{Result, ZeptoError};
+use crate::session::links::LINK_CODE_TTL_SECS;
+use crate::session::SessionLinks;
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Tool for issuing session link codes.
+pub struct SessionLinkTool {
+    links: SessionLinks,
+}
+
+impl SessionLinkTool {
+    /// Create a new session link tool backed by `links`.
+    pub fn new(links: SessionLinks) -> Self {
+        Self { links }
+    }
+}
+
+#[async_trait]
+impl Tool for SessionLinkTool {
+    fn name(&self) -> &str {
+        "session_link"
+    }
+
+    fn description(&self) -> &str {
+        "Issue a one-time code that lets the user continue this conversation on another \
+         channel (e.g. start on CLI, continue on Telegram). The user sends '/link <code>' \
+         on the other channel and then '/link confirm'. Use when the user asks to move \
+         or continue the conversation elsewhere."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Issue cross-channel session link code"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Messaging
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let session_key = ctx
+            .session_key
+            .clone()
+            .or_else(|| match (&ctx.channel, &ctx.chat_id) {
+                (Some(channel), Some(chat_id)) => Some(format!("{}:{}", channel, chat_id)),
+                _ => None,
+            })
+            .ok_or_else(|| ZeptoError::Tool("No session to link".to_string()))?;
+
+        let code = self.links.issue_code(&session_key);
+        Ok(ToolOutput::llm_only(format!(
+            "Link code {code} issued. Tell the user to send `/link {code}` on the other \
+             channel within {} minutes, then `/link confirm` there to continue this conversation.",
+            LINK_CODE_TTL_SECS / 60
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_session_link_issues_code_for_current_session() {
+        let links = SessionLinks::new(None);
+        let tool = SessionLinkTool::new(links.clone());
+        let ctx = ToolContext::new()
+            .with_channel("cli", "cli")
+            .with_session_key("cli:cli");
+
+        let output = tool.execute(json!({}), &ctx).await.unwrap();
+        let code = output
+            .for_llm
+            .split('`')
+            .nth(1)
+            .and_then(|cmd| cmd.strip_prefix("/link "))
+            .unwrap()
+            .to_string();
+
+        assert_eq!(links.request("telegram:1", &code).unwrap(), "cli:cli");
+    }
+
+    #[tokio::test]
+    async fn test_session_link_requires_session() {
+        let tool = SessionLinkTool::new(SessionLinks::new(None));
+        assert!(tool.execute(json!({}), &ToolContext::new()).await.is_err());
+    }
+}
@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use serde_json::Value;
 
 use crate::error::Result;
+use crate::skills::lockfile::{LockSource, SkillLockfile};
 use crate::skills::registry::ClawHubRegistry;
 use crate::tools::{Tool, ToolContext, ToolOutput};
 
@@ -73,10 +74,26 @@ impl Tool for InstallSkillTool {
             .download_and_install(slug, &self.skills_dir)
             .await
         {
-            Ok(path) => Ok(ToolOutput::user_visible(format!(
-                "Skill '{}' installed to {}. Restart the agent to use it.",
-                slug, path
-            ))),
+            Ok(path) => {
+                let skills_dir = std::path::Path::new(&self.skills_dir);
+                let locked = SkillLockfile::load(skills_dir).and_then(|mut lock| {
+                    lock.record(
+                        slug,
+                        std::path::Path::new(&path),
+                        LockSource::Clawhub {
+                            slug: slug.to_string(),
+                        },
+                    )?;
+                    lock.save()
+                });
+                if let Err(e) = locked {
+                    tracing::warn!(slug = %slug, error = %e, "Failed to update skills lockfile");
+                }
+                Ok(ToolOutput::user_visible(format!(
+                    "Skill '{}' installed to {}. Restart the agent to use it.",
+                    slug, path
+                )))
+            }
             Err(e) => Ok(ToolOutput::error(format!("Install failed: {}", e))),
         }
     }
@@ -0,0 +1,113 @@
+//! RFC 4180 CSV reading and writing (quoted fields, doubled quotes and
+//! embedded newlines). Also used for TSV with a tab delimiter.
+
+use std::borrow::Cow;
+
+/// Split `text` into rows of fields. A leading UTF-8 BOM is ignored and a
+/// trailing newline does not produce an empty row.
+pub(super) fn parse(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' && field.is_empty() {
+            in_quotes = true;
+        } else if c == delimiter {
+            row.push(std::mem::take(&mut field));
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            row.push(std::mem::take(&mut field));
+            rows.push(std::mem::take(&mut row));
+        } else {
+            field.push(c);
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Serialize rows, quoting fields only where needed. Every row ends with
+/// a newline.
+pub(super) fn write(rows: &[Vec<String>], delimiter: char) -> String {
+    let separator = delimiter.to_string();
+    let mut out = String::new();
+    for row in rows {
+        let fields: Vec<Cow<'_, str>> = row.iter().map(|f| quote(f, delimiter)).collect();
+        out.push_str(&fields.join(&separator));
+        out.push('\n');
+    }
+    out
+}
+
+fn quote(field: &str, delimiter: char) -> Cow<'_, str> {
+    let needs_quotes = field.contains([delimiter, '"', '\n', '\r'])
+        || field.starts_with(' ')
+        || field.ends_with(' ');
+    if needs_quotes {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let rows = parse(
+            "\u{feff}name,note\r\n\"Doe, Jane\",\"said \"\"hi\"\"\nthen left\"\n,\n",
+            ',',
+        );
+        assert_eq!(
+            rows,
+            vec![
+                vec!["name", "note"],
+                vec!["Doe, Jane", "said \"hi\"\nthen left"],
+                vec!["", ""],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_without_trailing_newline() {
+        assert_eq!(
+            parse("a\tb\n1\t2", '\t'),
+            vec![vec!["a", "b"], vec!["1", "2"]]
+        );
+        assert!(parse("", ',').is_empty());
+    }
+
+    #[test]
+    fn test_write_round_trips() {
+        let rows = vec![
+            vec!["id".to_string(), "text".to_string()],
+            vec!["1".to_string(), "a, \"b\"\nc".to_string()],
+            vec!["2".to_string(), " padded ".to_string()],
+        ];
+        let text = write(&rows, ',');
+        assert_eq!(text, "id,text\n1,\"a, \"\"b\"\"\nc\"\n2,\" padded \"\n");
+        assert_eq!(parse(&text, ','), rows);
+    }
+}
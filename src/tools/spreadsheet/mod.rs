@@ -0,0 +1,1295 @@
+//! Spreadsheet tool for CSV, TSV and XLSX files in the workspace.
+//!
+//! - `sheets` lists the sheets with their size and header row.
+//! - `read` returns the cells of an A1 range (`B2:D20`, `A:C`, `2:10`).
+//! - `query` filters, groups, aggregates and sorts a range whose first row
+//!   is the header.
+//! - `write` replaces or appends the rows of a sheet, creating the file or
+//!   sheet if needed. The previous file is journaled for `undo`.
+//!
+//! XLSX is handled with the zip and quick-xml crates that `docx_read`
+//! already depends on; see [`xlsx`] for what a write keeps. Legacy `.xls`
+//! files are not supported.
+
+mod csv;
+mod xlsx;
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Result, ZeptoError};
+use crate::security::revalidate_path;
+use crate::tools::filesystem::{resolve_path, write_file_secure};
+use crate::tools::undo::record_before;
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Largest spreadsheet file read (50 MB).
+const MAX_FILE_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Rows returned by `read` and `query` unless `limit` says otherwise.
+const DEFAULT_MAX_ROWS: usize = 100;
+
+/// Upper bound for `limit`.
+const HARD_MAX_ROWS: usize = 1_000;
+
+/// Output size cap; rows beyond it are counted, not shown.
+const MAX_OUTPUT_BYTES: usize = 32 * 1024;
+
+/// Longest value shown in a cell.
+const MAX_CELL_CHARS: usize = 200;
+
+/// Sheet name for new workbooks.
+const DEFAULT_SHEET: &str = "Sheet1";
+
+static EMPTY_CELL: Cell = Cell::Empty;
+
+/// A cell value.
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Empty,
+    Number(f64),
+    Bool(bool),
+    Text(String),
+}
+
+impl Cell {
+    fn from_text(text: String) -> Self {
+        if text.is_empty() {
+            Cell::Empty
+        } else {
+            Cell::Text(text)
+        }
+    }
+
+    fn from_json(value: &Value) -> Self {
+        match value {
+            Value::Null => Cell::Empty,
+            Value::Bool(b) => Cell::Bool(*b),
+            Value::Number(n) => n.as_f64().map_or(Cell::Empty, Cell::Number),
+            Value::String(s) => Cell::from_text(s.clone()),
+            other => Cell::Text(other.to_string()),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(self, Cell::Empty)
+    }
+
+    /// Numeric value. Text that parses as a number counts, since CSV
+    /// cells are all text.
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Cell::Number(n) => Some(*n),
+            Cell::Text(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Empty => Ok(()),
+            Cell::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => write!(f, "{}", *n as i64),
+            Cell::Number(n) => write!(f, "{}", n),
+            Cell::Bool(b) => write!(f, "{}", if *b { "TRUE" } else { "FALSE" }),
+            Cell::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+/// A named grid of cells; row and column 0 are `A1`.
+#[derive(Debug, Clone)]
+struct Sheet {
+    name: String,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Sheet {
+    fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+}
+
+/// Column letters for a 0-based index (0 → `A`, 26 → `AA`).
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Split an A1 reference into a 0-based column and row, either of which
+/// may be absent (`C`, `12`). `$` anchors are ignored; returns `None` for
+/// anything else.
+fn split_ref(reference: &str) -> Option<(Option<usize>, Option<usize>)> {
+    let reference = reference.replace('$', "");
+    let digits_at = reference
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(reference.len());
+    let (letters, digits) = reference.split_at(digits_at);
+    if letters.is_empty() && digits.is_empty() {
+        return None;
+    }
+    let column = if letters.is_empty() {
+        None
+    } else {
+        let number = letters.chars().try_fold(0usize, |acc, c| {
+            acc.checked_mul(26)?
+                .checked_add(usize::from(c.to_ascii_uppercase() as u8 - b'A') + 1)
+        })?;
+        Some(number - 1)
+    };
+    let row = if digits.is_empty() {
+        None
+    } else {
+        Some(digits.parse::<usize>().ok()?.checked_sub(1)?)
+    };
+    Some((column, row))
+}
+
+/// A rectangular A1 range; `None` ends are open.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CellRange {
+    first_row: usize,
+    first_col: usize,
+    last_row: Option<usize>,
+    last_col: Option<usize>,
+}
+
+impl CellRange {
+    const ALL: CellRange = CellRange {
+        first_row: 0,
+        first_col: 0,
+        last_row: None,
+        last_col: None,
+    };
+
+    /// Parse `B2:D20`, `A:C`, `2:10` or `B2`, optionally prefixed with a
+    /// sheet name (`Sales!A:C`).
+    fn parse(range: &str) -> Result<(Option<String>, Self)> {
+        let invalid = || {
+            ZeptoError::Tool(format!(
+                "Invalid range '{}'. Use A1 notation like A1:D20, A:C or 2:10",
+                range
+            ))
+        };
+        let (sheet, cells) = match range.rsplit_once('!') {
+            Some((sheet, cells)) => (Some(sheet.trim().trim_matches('\'').to_string()), cells),
+            None => (None, range),
+        };
+        let (start, end) = cells.split_once(':').unwrap_or((cells, cells));
+        let (start_col, start_row) = split_ref(start.trim()).ok_or_else(invalid)?;
+        let (end_col, end_row) = split_ref(end.trim()).ok_or_else(invalid)?;
+        let parsed = CellRange {
+            first_row: start_row.unwrap_or(0),
+            first_col: start_col.unwrap_or(0),
+            last_row: end_row,
+            last_col: end_col,
+        };
+        if parsed.last_row.is_some_and(|r| r < parsed.first_row)
+            || parsed.last_col.is_some_and(|c| c < parsed.first_col)
+        {
+            return Err(invalid());
+        }
+        Ok((sheet, parsed))
+    }
+
+    /// The cells of `rows` inside the range.
+    fn slice(&self, rows: &[Vec<Cell>]) -> Vec<Vec<Cell>> {
+        let last_row = self
+            .last_row
+            .map_or(rows.len(), |r| (r + 1).min(rows.len()));
+        rows.get(self.first_row..last_row.max(self.first_row))
+            .unwrap_or_default()
+            .iter()
+            .map(|row| {
+                let last_col = self.last_col.map_or(row.len(), |c| (c + 1).min(row.len()));
+                row.get(self.first_col..last_col.max(self.first_col))
+                    .unwrap_or_default()
+                    .to_vec()
+            })
+            .collect()
+    }
+}
+
+/// Where a spreadsheet file's format comes from: its extension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Csv(char),
+    Xlsx,
+}
+
+impl Format {
+    fn from_path(path: &str) -> Result<Self> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("csv") => Ok(Format::Csv(',')),
+            Some("tsv" | "tab") => Ok(Format::Csv('\t')),
+            Some("xlsx" | "xlsm") => Ok(Format::Xlsx),
+            Some("xls") => Err(ZeptoError::Tool(
+                "Legacy .xls files are not supported; save the file as .xlsx".to_string(),
+            )),
+            _ => Err(ZeptoError::Tool(
+                "Unsupported spreadsheet type; use .csv, .tsv or .xlsx".to_string(),
+            )),
+        }
+    }
+}
+
+fn parse_file(bytes: &[u8], format: Format, file_stem: &str) -> Result<Vec<Sheet>> {
+    match format {
+        Format::Csv(delimiter) => Ok(vec![Sheet {
+            name: file_stem.to_string(),
+            rows: csv::parse(&String::from_utf8_lossy(bytes), delimiter)
+                .into_iter()
+                .map(|row| row.into_iter().map(Cell::from_text).collect())
+                .collect(),
+        }]),
+        Format::Xlsx => xlsx::read(bytes),
+    }
+}
+
+/// Sheet `name`, or the first sheet. CSV files have a single sheet and
+/// ignore the name.
+fn pick_sheet<'a>(sheets: &'a [Sheet], name: Option<&str>, format: Format) -> Result<&'a Sheet> {
+    match name.filter(|_| format == Format::Xlsx) {
+        Some(name) => sheets
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                ZeptoError::Tool(format!(
+                    "No sheet '{}'. Sheets: {}",
+                    name,
+                    sheets
+                        .iter()
+                        .map(|s| s.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            }),
+        None => sheets
+            .first()
+            .ok_or_else(|| ZeptoError::Tool("The workbook has no sheets".to_string())),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Queries
+// ---------------------------------------------------------------------------
+
+/// Compare numerically when both sides are numbers, else as
+/// case-insensitive text (ISO dates sort correctly as text).
+fn compare(a: &Cell, b: &Cell) -> Ordering {
+    match (a.as_number(), b.as_number()) {
+        (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+        _ => a
+            .to_string()
+            .to_lowercase()
+            .cmp(&b.to_string().to_lowercase()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Contains,
+}
+
+impl FilterOp {
+    fn parse(op: &str) -> Result<Self> {
+        match op.trim() {
+            "=" | "==" => Ok(Self::Eq),
+            "!=" | "<>" => Ok(Self::Ne),
+            ">" => Ok(Self::Gt),
+            ">=" => Ok(Self::Ge),
+            "<" => Ok(Self::Lt),
+            "<=" => Ok(Self::Le),
+            "contains" => Ok(Self::Contains),
+            other => Err(ZeptoError::Tool(format!(
+                "Unknown filter op '{}'. Use =, !=, >, >=, <, <= or contains",
+                other
+            ))),
+        }
+    }
+
+    fn matches(self, cell: &Cell, value: &Cell) -> bool {
+        match self {
+            Self::Eq => compare(cell, value) == Ordering::Equal,
+            Self::Ne => compare(cell, value) != Ordering::Equal,
+            Self::Contains => cell
+                .to_string()
+                .to_lowercase()
+                .contains(&value.to_string().to_lowercase()),
+            // Empty cells never pass an ordering test.
+            _ if cell.is_empty() => false,
+            Self::Gt => compare(cell, value) == Ordering::Greater,
+            Self::Ge => compare(cell, value) != Ordering::Less,
+            Self::Lt => compare(cell, value) == Ordering::Less,
+            Self::Le => compare(cell, value) != Ordering::Greater,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum AggregateFn {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggregateFn {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Avg => "avg",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
+
+    /// Aggregate the non-empty `cells`; non-numeric cells only count.
+    fn apply<'a>(self, cells: impl Iterator<Item = &'a Cell>) -> Cell {
+        let cells: Vec<&Cell> = cells.filter(|c| !c.is_empty()).collect();
+        if self == Self::Count {
+            return Cell::Number(cells.len() as f64);
+        }
+        let numbers: Vec<f64> = cells.iter().filter_map(|c| c.as_number()).collect();
+        if numbers.is_empty() {
+            return Cell::Empty;
+        }
+        let sum: f64 = numbers.iter().sum();
+        Cell::Number(match self {
+            Self::Sum => sum,
+            Self::Avg => sum / numbers.len() as f64,
+            Self::Min => numbers.iter().copied().fold(f64::INFINITY, f64::min),
+            _ => numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Filter {
+    column: String,
+    #[serde(default = "default_op")]
+    op: String,
+    #[serde(default)]
+    value: Value,
+}
+
+fn default_op() -> String {
+    "=".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct Aggregate {
+    #[serde(rename = "fn")]
+    func: AggregateFn,
+    #[serde(default)]
+    column: Option<String>,
+}
+
+/// `query` arguments.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Query {
+    filter: Vec<Filter>,
+    select: Vec<String>,
+    group_by: Option<String>,
+    aggregate: Vec<Aggregate>,
+    sort_by: Option<String>,
+    descending: bool,
+}
+
+/// Result rows under named columns.
+#[derive(Debug, PartialEq)]
+struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+}
+
+/// Run `query` over `data`, whose first row is the header. `first_col` is
+/// the sheet column of `data[_][0]`, so columns can also be named by letter.
+fn run_query(data: &[Vec<Cell>], first_col: usize, query: &Query) -> Result<Table> {
+    let (header, body) = data
+        .split_first()
+        .ok_or_else(|| ZeptoError::Tool("The range is empty".to_string()))?;
+    let width = data.iter().map(Vec::len).max().unwrap_or(0);
+    let names: Vec<String> = (0..width)
+        .map(|i| match header.get(i).map(|c| c.to_string()) {
+            Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+            _ => column_name(first_col + i),
+        })
+        .collect();
+    let resolve = |name: &str| -> Result<usize> {
+        let wanted = name.trim();
+        if let Some(index) = names.iter().position(|n| n.eq_ignore_ascii_case(wanted)) {
+            return Ok(index);
+        }
+        if let Some((Some(col), None)) = split_ref(wanted) {
+            if col >= first_col && col - first_col < width {
+                return Ok(col - first_col);
+            }
+        }
+        Err(ZeptoError::Tool(format!(
+            "Unknown column '{}'. Columns: {}",
+            name,
+            names.join(", ")
+        )))
+    };
+    let cell =
+        |row: &'_ [Cell], index: usize| -> Cell { row.get(index).unwrap_or(&EMPTY_CELL).clone() };
+
+    let filters = query
+        .filter
+        .iter()
+        .map(|f| {
+            Ok((
+                resolve(&f.column)?,
+                FilterOp::parse(&f.op)?,
+                Cell::from_json(&f.value),
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let matching: Vec<&Vec<Cell>> = body
+        .iter()
+        .filter(|row| {
+            filters
+                .iter()
+                .all(|(col, op, value)| op.matches(row.get(*col).unwrap_or(&EMPTY_CELL), value))
+        })
+        .collect();
+
+    let mut table = if query.group_by.is_none() && query.aggregate.is_empty() {
+        let columns: Vec<usize> = if query.select.is_empty() {
+            (0..width).collect()
+        } else {
+            query
+                .select
+                .iter()
+                .map(|c| resolve(c))
+                .collect::<Result<_>>()?
+        };
+        Table {
+            columns: columns.iter().map(|&c| names[c].clone()).collect(),
+            rows: matching
+                .iter()
+                .map(|row| columns.iter().map(|&c| cell(row, c)).collect())
+                .collect(),
+        }
+    } else {
+        let group_col = query.group_by.as_deref().map(&resolve).transpose()?;
+        let aggregates: Vec<(AggregateFn, Option<usize>)> = if query.aggregate.is_empty() {
+            vec![(AggregateFn::Count, None)]
+        } else {
+            query
+                .aggregate
+                .iter()
+                .map(|a| match (&a.column, a.func) {
+                    (Some(column), func) => Ok((func, Some(resolve(column)?))),
+                    (None, AggregateFn::Count) => Ok((AggregateFn::Count, None)),
+                    (None, func) => Err(ZeptoError::Tool(format!(
+                        "Aggregate '{}' needs a column",
+                        func.name()
+                    ))),
+                })
+                .collect::<Result<_>>()?
+        };
+
+        // Groups in order of first appearance.
+        let mut groups: Vec<(Cell, Vec<&Vec<Cell>>)> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        match group_col {
+            Some(col) => {
+                for row in &matching {
+                    let key = cell(row, col);
+                    let slot = *index.entry(key.to_string()).or_insert_with(|| {
+                        groups.push((key, Vec::new()));
+                        groups.len() - 1
+                    });
+                    groups[slot].1.push(row);
+                }
+            }
+            None => groups.push((Cell::Empty, matching.clone())),
+        }
+
+        let mut columns: Vec<String> = group_col.iter().map(|&c| names[c].clone()).collect();
+        columns.extend(aggregates.iter().map(|(func, col)| match col {
+            Some(col) => format!("{}({})", func.name(), names[*col]),
+            None => func.name().to_string(),
+        }));
+        let rows = groups
+            .into_iter()
+            .map(|(key, rows)| {
+                let mut out: Vec<Cell> = group_col.map(|_| key).into_iter().collect();
+                out.extend(aggregates.iter().map(|(func, col)| match col {
+                    Some(col) => {
+                        func.apply(rows.iter().map(|r| r.get(*col).unwrap_or(&EMPTY_CELL)))
+                    }
+                    None => Cell::Number(rows.len() as f64),
+                }));
+                out
+            })
+            .collect();
+        Table { columns, rows }
+    };
+
+    if let Some(sort_by) = &query.sort_by {
+        let col = table
+            .columns
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case(sort_by.trim()))
+            .ok_or_else(|| {
+                ZeptoError::Tool(format!(
+                    "Cannot sort by '{}'. Result columns: {}",
+                    sort_by,
+                    table.columns.join(", ")
+                ))
+            })?;
+        table.rows.sort_by(|a, b| {
+            let ordering = compare(&a[col], &b[col]);
+            if query.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+    Ok(table)
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+fn clip_cell(cell: &Cell) -> String {
+    let value = cell.to_string().replace(['\n', '\r'], " ");
+    if value.chars().count() <= MAX_CELL_CHARS {
+        return value;
+    }
+    let clipped: String = value.chars().take(MAX_CELL_CHARS).collect();
+    format!("{}…", clipped)
+}
+
+/// Join cells with ` | `, dropping trailing empty cells.
+fn render_row(row: &[Cell]) -> String {
+    let end = row.iter().rposition(|c| !c.is_empty()).map_or(0, |i| i + 1);
+    row[..end]
+        .iter()
+        .map(clip_cell)
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Append `lines` to `out` up to `limit` lines and [`MAX_OUTPUT_BYTES`];
+/// returns how many were added.
+fn push_capped(out: &mut String, lines: impl Iterator<Item = String>, limit: usize) -> usize {
+    let mut shown = 0;
+    for line in lines.take(limit) {
+        if out.len() + line.len() + 1 > MAX_OUTPUT_BYTES {
+            break;
+        }
+        out.push('\n');
+        out.push_str(&line);
+        shown += 1;
+    }
+    shown
+}
+
+fn render_read(sheet: &Sheet, range: &CellRange, limit: usize) -> String {
+    let cells = range.slice(&sheet.rows);
+    let used = cells
+        .iter()
+        .rposition(|row| row.iter().any(|c| !c.is_empty()))
+        .map_or(0, |i| i + 1);
+    if used == 0 {
+        return format!("{}: no data in the requested range.", sheet.name);
+    }
+    let width = cells.iter().map(Vec::len).max().unwrap_or(1).max(1);
+    let mut out = format!(
+        "{}!{}{}:{}{} ({} rows)",
+        sheet.name,
+        column_name(range.first_col),
+        range.first_row + 1,
+        column_name(range.first_col + width - 1),
+        range.first_row + used,
+        used
+    );
+    let lines = cells[..used]
+        .iter()
+        .enumerate()
+        .map(|(i, row)| format!("Row {}: {}", range.first_row + i + 1, render_row(row)));
+    let shown = push_capped(&mut out, lines, limit);
+    if shown < used {
+        out.push_str(&format!(
+            "\n({} of {} rows shown; read a narrower range to see more)",
+            shown, used
+        ));
+    }
+    out
+}
+
+fn render_table(table: &Table, limit: usize) -> String {
+    if table.rows.is_empty() {
+        return format!("{}\n(0 rows)", table.columns.join(" | "));
+    }
+    let mut out = table.columns.join(" | ");
+    let lines = table
+        .rows
+        .iter()
+        .map(|row| row.iter().map(clip_cell).collect::<Vec<_>>().join(" | "));
+    let shown = push_capped(&mut out, lines, limit);
+    if shown < table.rows.len() {
+        out.push_str(&format!(
+            "\n({} of {} rows shown; filter, aggregate or raise limit)",
+            shown,
+            table.rows.len()
+        ));
+    } else {
+        out.push_str(&format!("\n({} rows)", shown));
+    }
+    out
+}
+
+fn render_sheets(file: &str, sheets: &[Sheet]) -> String {
+    let mut out = format!(
+        "{}: {} sheet{}",
+        file,
+        sheets.len(),
+        if sheets.len() == 1 { "" } else { "s" }
+    );
+    for sheet in sheets {
+        let rows = sheet.rows.len();
+        let width = sheet.width();
+        if rows == 0 || width == 0 {
+            out.push_str(&format!("\n- {}: empty", sheet.name));
+            continue;
+        }
+        out.push_str(&format!(
+            "\n- {}: {} rows x {} columns (A1:{}{}); first row: {}",
+            sheet.name,
+            rows,
+            width,
+            column_name(width - 1),
+            rows,
+            render_row(&sheet.rows[0])
+        ));
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Tool
+// ---------------------------------------------------------------------------
+
+/// Read, query and write local CSV/TSV/XLSX files in the workspace.
+pub struct SpreadsheetTool;
+
+impl SpreadsheetTool {
+    async fn load(path: &Path, workspace: &str, format: Format) -> Result<Vec<Sheet>> {
+        revalidate_path(path, workspace)?;
+        let meta = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Cannot open {}: {}", path.display(), e)))?;
+        if meta.len() > MAX_FILE_BYTES {
+            return Err(ZeptoError::Tool(format!(
+                "Spreadsheet too large: {} bytes (max {}MB)",
+                meta.len(),
+                MAX_FILE_BYTES / 1024 / 1024
+            )));
+        }
+        let bytes = tokio::fs::read(path).await?;
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        tokio::task::spawn_blocking(move || parse_file(&bytes, format, &stem))
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Task panicked: {e}")))?
+    }
+
+    fn rows_arg(args: &Value) -> Result<Vec<Vec<Cell>>> {
+        args.get("rows")
+            .and_then(Value::as_array)
+            .ok_or_else(|| ZeptoError::Tool("Missing 'rows' for write".to_string()))?
+            .iter()
+            .map(|row| {
+                row.as_array()
+                    .map(|cells| cells.iter().map(Cell::from_json).collect())
+                    .ok_or_else(|| ZeptoError::Tool("Each row must be an array".to_string()))
+            })
+            .collect()
+    }
+
+    async fn write(
+        &self,
+        args: &Value,
+        ctx: &ToolContext,
+        path_arg: &str,
+        format: Format,
+        sheet_name: Option<&str>,
+    ) -> Result<String> {
+        let rows = Self::rows_arg(args)?;
+        let append = match args
+            .get("mode")
+            .and_then(Value::as_str)
+            .unwrap_or("replace")
+        {
+            "replace" => false,
+            "append" => true,
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown mode '{}'. Use 'replace' or 'append'",
+                    other
+                )))
+            }
+        };
+        let (full_path, workspace) = resolve_path(path_arg, ctx)?;
+        let path = Path::new(&full_path);
+        let sheets = if path.exists() {
+            Self::load(path, &workspace, format).await?
+        } else {
+            Vec::new()
+        };
+        let target = sheet_name
+            .filter(|_| format == Format::Xlsx)
+            .map(|s| s.trim().to_string())
+            .or_else(|| sheets.first().map(|s| s.name.clone()))
+            .unwrap_or_else(|| DEFAULT_SHEET.to_string());
+        let existing = sheets.iter().find(|s| s.name.eq_ignore_ascii_case(&target));
+
+        let written = rows.len();
+        let all_rows = match existing {
+            Some(sheet) if append => {
+                let mut all = sheet.rows.clone();
+                all.extend(rows);
+                all
+            }
+            _ => rows,
+        };
+        let total = all_rows.len();
+        let bytes = match format {
+            Format::Csv(delimiter) => {
+                let text_rows: Vec<Vec<String>> = all_rows
+                    .iter()
+                    .map(|row| row.iter().map(Cell::to_string).collect())
+                    .collect();
+                csv::write(&text_rows, delimiter).into_bytes()
+            }
+            Format::Xlsx => {
+                let original = if path.exists() {
+                    Some(tokio::fs::read(path).await?)
+                } else {
+                    None
+                };
+                let name = target.clone();
+                tokio::task::spawn_blocking(move || {
+                    xlsx::write_sheet(original.as_deref(), &name, &all_rows)
+                })
+                .await
+                .map_err(|e| ZeptoError::Tool(format!("Task panicked: {e}")))??
+            }
+        };
+
+        record_before(ctx, path).await;
+        write_file_secure(path, &workspace, &bytes).await?;
+
+        let verb = if append && existing.is_some() {
+            "Appended"
+        } else {
+            "Wrote"
+        };
+        Ok(match format {
+            Format::Csv(_) => format!(
+                "{} {} rows to {} ({} rows total)",
+                verb, written, path_arg, total
+            ),
+            Format::Xlsx => format!(
+                "{} {} rows to sheet '{}' of {} ({} rows total)",
+                verb, written, target, path_arg, total
+            ),
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for SpreadsheetTool {
+    fn name(&self) -> &str {
+        "spreadsheet"
+    }
+
+    fn description(&self) -> &str {
+        "Work with CSV, TSV and XLSX files in the workspace. 'sheets' lists sheets and their \
+         header rows; 'read' returns cells of an A1 range; 'query' filters, groups, \
+         aggregates (count/sum/avg/min/max) and sorts a range whose first row is the header; \
+         'write' replaces or appends rows of a sheet, creating the file or sheet if needed."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Read, query and write CSV/XLSX files"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::FilesystemWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["sheets", "read", "query", "write"],
+                    "description": "Operation to perform"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Workspace path of a .csv, .tsv or .xlsx file"
+                },
+                "sheet": {
+                    "type": "string",
+                    "description": "XLSX sheet name (default: the first sheet; for write, created if missing)"
+                },
+                "range": {
+                    "type": "string",
+                    "description": "For read/query: A1 range such as A1:D20, A:C or 2:10 (default: whole sheet)"
+                },
+                "filter": {
+                    "type": "array",
+                    "description": "For query: conditions that must all hold",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "column": { "type": "string", "description": "Header name or column letter" },
+                            "op": {
+                                "type": "string",
+                                "enum": ["=", "!=", ">", ">=", "<", "<=", "contains"]
+                            },
+                            "value": { "description": "Value to compare with" }
+                        },
+                        "required": ["column", "value"]
+                    }
+                },
+                "select": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "For query without aggregates: columns to return"
+                },
+                "group_by": {
+                    "type": "string",
+                    "description": "For query: column to group rows by"
+                },
+                "aggregate": {
+                    "type": "array",
+                    "description": "For query: aggregates per group (count needs no column)",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "fn": { "type": "string", "enum": ["count", "sum", "avg", "min", "max"] },
+                            "column": { "type": "string" }
+                        },
+                        "required": ["fn"]
+                    }
+                },
+                "sort_by": {
+                    "type": "string",
+                    "description": "For query: result column to sort by, e.g. 'Region' or 'sum(Amount)'"
+                },
+                "descending": {
+                    "type": "boolean",
+                    "description": "For query: sort descending"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum rows to return (default: 100, max: 1000)"
+                },
+                "rows": {
+                    "type": "array",
+                    "items": { "type": "array" },
+                    "description": "For write: rows of cell values (strings, numbers, booleans or null)"
+                },
+                "mode": {
+                    "type": "string",
+                    "enum": ["replace", "append"],
+                    "description": "For write: replace the sheet (default) or append below its rows"
+                }
+            },
+            "required": ["action", "path"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ZeptoError::Tool("Missing required field: action".to_string()))?;
+        let path_arg = args
+            .get("path")
+            .and_then(Value::as_str)
+            .filter(|p| !p.trim().is_empty())
+            .ok_or_else(|| ZeptoError::Tool("Missing required field: path".to_string()))?;
+        let format = Format::from_path(path_arg)?;
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .map(|v| v as usize)
+            .unwrap_or(DEFAULT_MAX_ROWS)
+            .clamp(1, HARD_MAX_ROWS);
+        let (range_sheet, range) = match args.get("range").and_then(Value::as_str) {
+            Some(range) if !range.trim().is_empty() => CellRange::parse(range.trim())?,
+            _ => (None, CellRange::ALL),
+        };
+        let sheet_name = args
+            .get("sheet")
+            .and_then(Value::as_str)
+            .filter(|s| !s.trim().is_empty())
+            .or(range_sheet.as_deref());
+
+        if action == "write" {
+            let summary = self.write(&args, ctx, path_arg, format, sheet_name).await?;
+            return Ok(ToolOutput::llm_only(summary));
+        }
+
+        let (full_path, workspace) = resolve_path(path_arg, ctx)?;
+        let sheets = Self::load(Path::new(&full_path), &workspace, format).await?;
+        let output = match action {
+            "sheets" => render_sheets(path_arg, &sheets),
+            "read" => render_read(pick_sheet(&sheets, sheet_name, format)?, &range, limit),
+            "query" => {
+                let query: Query = serde_json::from_value(args.clone())
+                    .map_err(|e| ZeptoError::Tool(format!("Invalid query: {}", e)))?;
+                let sheet = pick_sheet(&sheets, sheet_name, format)?;
+                let table = run_query(&range.slice(&sheet.rows), range.first_col, &query)?;
+                render_table(&table, limit)
+            }
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown action '{}'. Use sheets, read, query or write",
+                    other
+                )))
+            }
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn text(s: &str) -> Cell {
+        Cell::Text(s.to_string())
+    }
+
+    fn sales() -> Vec<Vec<Cell>> {
+        csv::parse(
+            "date,region,amount\n\
+             2024-01-02,north,100\n\
+             2024-01-05,south,40.5\n\
+             2024-01-09,north,60\n\
+             2024-02-01,west,\n",
+            ',',
+        )
+        .into_iter()
+        .map(|row| row.into_iter().map(Cell::from_text).collect())
+        .collect()
+    }
+
+    fn query(value: Value) -> Query {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_column_names_and_refs() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+        assert_eq!(split_ref("AA10"), Some((Some(26), Some(9))));
+        assert_eq!(split_ref("$C$3"), Some((Some(2), Some(2))));
+        assert_eq!(split_ref("c"), Some((Some(2), None)));
+        assert_eq!(split_ref("12"), Some((None, Some(11))));
+        assert_eq!(split_ref(""), None);
+        assert_eq!(split_ref("A0"), None);
+        assert_eq!(split_ref("A1B"), None);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let (sheet, range) = CellRange::parse("'Q1 Sales'!B2:D20").unwrap();
+        assert_eq!(sheet.as_deref(), Some("Q1 Sales"));
+        assert_eq!(
+            range,
+            CellRange {
+                first_row: 1,
+                first_col: 1,
+                last_row: Some(19),
+                last_col: Some(3)
+            }
+        );
+        let (_, columns) = CellRange::parse("A:C").unwrap();
+        assert_eq!((columns.last_row, columns.last_col), (None, Some(2)));
+        let (_, rows) = CellRange::parse("2:10").unwrap();
+        assert_eq!(
+            (rows.first_row, rows.first_col, rows.last_col),
+            (1, 0, None)
+        );
+        let (_, single) = CellRange::parse("C3").unwrap();
+        assert_eq!((single.last_row, single.last_col), (Some(2), Some(2)));
+        assert!(CellRange::parse("D1:A1").is_err());
+        assert!(CellRange::parse("A1:?").is_err());
+    }
+
+    #[test]
+    fn test_slice_and_read() {
+        let sheet = Sheet {
+            name: "sales".to_string(),
+            rows: sales(),
+        };
+        let (_, range) = CellRange::parse("B2:C3").unwrap();
+        assert_eq!(
+            range.slice(&sheet.rows),
+            vec![
+                vec![text("north"), text("100")],
+                vec![text("south"), text("40.5")]
+            ]
+        );
+        assert_eq!(
+            render_read(&sheet, &range, 100),
+            "sales!B2:C3 (2 rows)\nRow 2: north | 100\nRow 3: south | 40.5"
+        );
+        let capped = render_read(&sheet, &CellRange::ALL, 2);
+        assert!(capped.contains("Row 2: 2024-01-02 | north | 100"));
+        assert!(capped.ends_with("(2 of 5 rows shown; read a narrower range to see more)"));
+        let (_, outside) = CellRange::parse("A50:B60").unwrap();
+        assert!(render_read(&sheet, &outside, 100).contains("no data"));
+    }
+
+    #[test]
+    fn test_query_filter_select_sort() {
+        let table = run_query(
+            &sales(),
+            0,
+            &query(json!({
+                "filter": [{"column": "amount", "op": ">=", "value": 50}],
+                "select": ["region", "C"],
+                "sort_by": "amount"
+            })),
+        )
+        .unwrap();
+        assert_eq!(table.columns, vec!["region", "amount"]);
+        assert_eq!(
+            table.rows,
+            vec![
+                vec![text("north"), text("60")],
+                vec![text("north"), text("100")]
+            ]
+        );
+
+        let contains = run_query(
+            &sales(),
+            0,
+            &query(json!({"filter": [{"column": "date", "op": "contains", "value": "2024-01"}]})),
+        )
+        .unwrap();
+        assert_eq!(contains.rows.len(), 3);
+    }
+
+    #[test]
+    fn test_query_group_and_aggregate() {
+        let table = run_query(
+            &sales(),
+            0,
+            &query(json!({
+                "group_by": "Region",
+                "aggregate": [{"fn": "count"}, {"fn": "sum", "column": "amount"}, {"fn": "avg", "column": "amount"}],
+                "sort_by": "sum(amount)",
+                "descending": true
+            })),
+        )
+        .unwrap();
+        assert_eq!(
+            table.columns,
+            vec!["region", "count", "sum(amount)", "avg(amount)"]
+        );
+        assert_eq!(
+            table.rows,
+            vec![
+                vec![
+                    text("north"),
+                    Cell::Number(2.0),
+                    Cell::Number(160.0),
+                    Cell::Number(80.0)
+                ],
+                vec![
+                    text("south"),
+                    Cell::Number(1.0),
+                    Cell::Number(40.5),
+                    Cell::Number(40.5)
+                ],
+                vec![text("west"), Cell::Number(1.0), Cell::Empty, Cell::Empty],
+            ]
+        );
+
+        let totals = run_query(
+            &sales(),
+            0,
+            &query(json!({"aggregate": [{"fn": "max", "column": "amount"}, {"fn": "count", "column": "amount"}]})),
+        )
+        .unwrap();
+        assert_eq!(
+            totals.rows,
+            vec![vec![Cell::Number(100.0), Cell::Number(3.0)]]
+        );
+    }
+
+    #[test]
+    fn test_query_errors() {
+        assert!(run_query(&sales(), 0, &query(json!({"select": ["price"]}))).is_err());
+        assert!(run_query(&sales(), 0, &query(json!({"aggregate": [{"fn": "sum"}]}))).is_err());
+        assert!(run_query(
+            &sales(),
+            0,
+            &query(json!({"filter": [{"column": "amount", "op": "~", "value": 1}]}))
+        )
+        .is_err());
+        assert!(run_query(&[], 0, &Query::default()).is_err());
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path("a.CSV").unwrap(), Format::Csv(','));
+        assert_eq!(Format::from_path("a.tsv").unwrap(), Format::Csv('\t'));
+        assert_eq!(Format::from_path("dir/a.xlsx").unwrap(), Format::Xlsx);
+        assert!(Format::from_path("a.xls").is_err());
+        assert!(Format::from_path("a.txt").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_csv_write_and_query() {
+        let dir = TempDir::new().unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        let tool = SpreadsheetTool;
+
+        let out = tool
+            .execute(
+                json!({"action": "write", "path": "costs.csv", "rows": [["item", "cost"], ["tea", 3.5]]}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "Wrote 2 rows to costs.csv (2 rows total)");
+        let out = tool
+            .execute(
+                json!({"action": "write", "path": "costs.csv", "mode": "append", "rows": [["cake, large", 12]]}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "Appended 1 rows to costs.csv (3 rows total)");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("costs.csv")).unwrap(),
+            "item,cost\ntea,3.5\n\"cake, large\",12\n"
+        );
+
+        let out = tool
+            .execute(
+                json!({"action": "query", "path": "costs.csv", "aggregate": [{"fn": "sum", "column": "cost"}]}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm, "sum(cost)\n15.5\n(1 rows)");
+    }
+
+    #[tokio::test]
+    async fn test_xlsx_sheets_and_read() {
+        let dir = TempDir::new().unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        let tool = SpreadsheetTool;
+
+        for (sheet, rows) in [
+            ("Data", json!([["name", "qty"], ["bolt", 10], ["nut", 25]])),
+            ("Summary", json!([["total", 35]])),
+        ] {
+            tool.execute(
+                json!({"action": "write", "path": "stock.xlsx", "sheet": sheet, "rows": rows}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        }
+
+        let out = tool
+            .execute(json!({"action": "sheets", "path": "stock.xlsx"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            out.for_llm,
+            "stock.xlsx: 2 sheets\n\
+             - Data: 3 rows x 2 columns (A1:B3); first row: name | qty\n\
+             - Summary: 1 rows x 2 columns (A1:B1); first row: total | 35"
+        );
+
+        let out = tool
+            .execute(
+                json!({"action": "read", "path": "stock.xlsx", "range": "Data!A2:B3"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            out.for_llm,
+            "Data!A2:B3 (2 rows)\nRow 2: bolt | 10\nRow 3: nut | 25"
+        );
+
+        let err = tool
+            .execute(
+                json!({"action": "read", "path": "stock.xlsx", "sheet": "Nope"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Sheets: Data, Summary"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_paths_outside_workspace() {
+        let dir = TempDir::new().unwrap();
+        let ctx = ToolContext::new().with_workspace(dir.path().to_str().unwrap());
+        let result = SpreadsheetTool
+            .execute(
+                json!({"action": "read", "path": "../../etc/data.csv"}),
+                &ctx,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
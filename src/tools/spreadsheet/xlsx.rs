@@ -0,0 +1,930 @@
+//! Minimal XLSX reader and writer on zip + quick-xml.
+//!
+//! Reading resolves shared and inline strings, booleans, error values,
+//! cached formula results and date-formatted numbers (shown as ISO dates).
+//! Writing stores one worksheet with inline strings and keeps every other
+//! part of an existing workbook, so other sheets keep their formatting,
+//! formulas and charts; the written sheet holds plain values.
+
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
+
+use chrono::{Duration, NaiveDate};
+use once_cell::sync::Lazy;
+use quick_xml::escape::{escape, resolve_xml_entity};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::Regex;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::{column_name, split_ref, Cell, Sheet};
+use crate::error::{Result, ZeptoError};
+
+/// Largest decompressed part read from a workbook (zip bomb guard).
+const MAX_PART_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Excel's row limit.
+const MAX_ROWS: usize = 1_048_576;
+
+/// Excel's column limit.
+const MAX_COLUMNS: usize = 16_384;
+
+/// Excel's sheet name length limit.
+const MAX_SHEET_NAME_CHARS: usize = 31;
+
+const SHEET_NS: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+const REL_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const WORKSHEET_REL: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet";
+const WORKSHEET_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml";
+
+const XML_DECL: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+static SHEETS_END: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"</([A-Za-z_][\w.-]*:)?sheets>").unwrap());
+static CALC_CHAIN_REL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"<Relationship\b[^>]*calcChain[^>]*/>"#).unwrap());
+static CALC_CHAIN_TYPE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"<Override\b[^>]*calcChain[^>]*/>"#).unwrap());
+
+type Archive<'a> = ZipArchive<Cursor<&'a [u8]>>;
+
+/// A `<sheet>` of `xl/workbook.xml` with its part path.
+#[derive(Debug)]
+struct SheetEntry {
+    name: String,
+    sheet_id: u32,
+    path: Option<String>,
+}
+
+#[derive(Debug)]
+struct Workbook {
+    sheets: Vec<SheetEntry>,
+    date1904: bool,
+    /// Prefix bound to the relationships namespace (usually `r`).
+    rel_prefix: Option<String>,
+}
+
+fn xml_err(e: impl std::fmt::Display) -> ZeptoError {
+    ZeptoError::Tool(format!("XLSX parse error: {}", e))
+}
+
+fn zip_err(e: zip::result::ZipError) -> ZeptoError {
+    ZeptoError::Tool(format!("XLSX zip error: {}", e))
+}
+
+/// Read every sheet of the workbook in `bytes`.
+pub(super) fn read(bytes: &[u8]) -> Result<Vec<Sheet>> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| ZeptoError::Tool(format!("Not a valid XLSX file: {}", e)))?;
+    let workbook = parse_workbook(
+        &required_part(&mut archive, "xl/workbook.xml")?,
+        &required_part(&mut archive, "xl/_rels/workbook.xml.rels")?,
+    )?;
+    let strings = match read_part(&mut archive, "xl/sharedStrings.xml")? {
+        Some(xml) => shared_strings(&xml)?,
+        None => Vec::new(),
+    };
+    let date_styles = match read_part(&mut archive, "xl/styles.xml")? {
+        Some(xml) => date_styles(&xml)?,
+        None => Vec::new(),
+    };
+
+    let mut sheets = Vec::new();
+    for entry in &workbook.sheets {
+        let rows = match &entry.path {
+            Some(path) => match read_part(&mut archive, path)? {
+                Some(xml) => parse_sheet(&xml, &strings, &date_styles, workbook.date1904)?,
+                None => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+        sheets.push(Sheet {
+            name: entry.name.clone(),
+            rows,
+        });
+    }
+    Ok(sheets)
+}
+
+/// Store `rows` as sheet `name`, replacing a sheet of that name or adding
+/// a new one. Without `existing` a new workbook is created.
+pub(super) fn write_sheet(
+    existing: Option<&[u8]>,
+    name: &str,
+    rows: &[Vec<Cell>],
+) -> Result<Vec<u8>> {
+    validate_sheet_name(name)?;
+    let mut parts = match existing {
+        Some(bytes) => read_parts(bytes)?,
+        None => empty_workbook(),
+    };
+    let mut workbook_xml = part_text(&parts, "xl/workbook.xml")?;
+    let mut rels_xml = part_text(&parts, "xl/_rels/workbook.xml.rels")?;
+    let mut types_xml = part_text(&parts, "[Content_Types].xml")?;
+    let workbook = parse_workbook(&workbook_xml, &rels_xml)?;
+
+    let sheet_path = match workbook
+        .sheets
+        .iter()
+        .find(|s| s.name.eq_ignore_ascii_case(name))
+    {
+        Some(entry) => {
+            let path = entry
+                .path
+                .clone()
+                .filter(|p| p.contains("/worksheets/"))
+                .ok_or_else(|| {
+                    ZeptoError::Tool(format!("Sheet '{}' is not a worksheet", entry.name))
+                })?;
+            // The calculation chain may point at formulas that are gone;
+            // Excel rebuilds it on open.
+            parts.retain(|(part, _)| part != "xl/calcChain.xml");
+            rels_xml = CALC_CHAIN_REL.replace_all(&rels_xml, "").into_owned();
+            types_xml = CALC_CHAIN_TYPE.replace_all(&types_xml, "").into_owned();
+            path
+        }
+        None => {
+            let number = (1..)
+                .find(|n| {
+                    let candidate = format!("xl/worksheets/sheet{}.xml", n);
+                    !parts.iter().any(|(part, _)| *part == candidate)
+                })
+                .unwrap_or(1);
+            let rel_ids = relationships(&rels_xml)?;
+            let rel_id = (1..)
+                .map(|n| format!("rId{}", n))
+                .find(|id| !rel_ids.contains_key(id))
+                .unwrap_or_default();
+            let sheet_id = workbook
+                .sheets
+                .iter()
+                .map(|s| s.sheet_id)
+                .max()
+                .unwrap_or(0)
+                + 1;
+            let rel_attr = match &workbook.rel_prefix {
+                Some(prefix) => format!("{}:id=\"{}\"", prefix, rel_id),
+                None => format!("xmlns:r=\"{}\" r:id=\"{}\"", REL_NS, rel_id),
+            };
+
+            // `</sheets>`, possibly with a namespace prefix.
+            let (start, prefix) = SHEETS_END
+                .captures(&workbook_xml)
+                .and_then(|c| {
+                    let prefix = c.get(1).map_or("", |m| m.as_str()).to_string();
+                    Some((c.get(0)?.start(), prefix))
+                })
+                .ok_or_else(|| {
+                    ZeptoError::Tool("Unsupported workbook: no <sheets> element".to_string())
+                })?;
+            workbook_xml.insert_str(
+                start,
+                &format!(
+                    "<{}sheet name=\"{}\" sheetId=\"{}\" {}/>",
+                    prefix,
+                    escape(name),
+                    sheet_id,
+                    rel_attr
+                ),
+            );
+            rels_xml = insert_before(
+                &rels_xml,
+                "</Relationships>",
+                &format!(
+                    "<Relationship Id=\"{}\" Type=\"{}\" Target=\"worksheets/sheet{}.xml\"/>",
+                    rel_id, WORKSHEET_REL, number
+                ),
+            )?;
+            types_xml = insert_before(
+                &types_xml,
+                "</Types>",
+                &format!(
+                    "<Override PartName=\"/xl/worksheets/sheet{}.xml\" ContentType=\"{}\"/>",
+                    number, WORKSHEET_CONTENT_TYPE
+                ),
+            )?;
+            format!("xl/worksheets/sheet{}.xml", number)
+        }
+    };
+
+    set_part(&mut parts, "xl/workbook.xml", workbook_xml.into_bytes());
+    set_part(
+        &mut parts,
+        "xl/_rels/workbook.xml.rels",
+        rels_xml.into_bytes(),
+    );
+    set_part(&mut parts, "[Content_Types].xml", types_xml.into_bytes());
+    set_part(&mut parts, &sheet_path, sheet_xml(rows).into_bytes());
+    zip_parts(&parts)
+}
+
+fn validate_sheet_name(name: &str) -> Result<()> {
+    let invalid = name.trim().is_empty()
+        || name.chars().count() > MAX_SHEET_NAME_CHARS
+        || name.contains(['[', ']', ':', '*', '?', '/', '\\'])
+        || name.starts_with('\'')
+        || name.ends_with('\'');
+    if invalid {
+        return Err(ZeptoError::Tool(format!(
+            "Invalid sheet name '{}': 1-{} characters, none of []:*?/\\",
+            name, MAX_SHEET_NAME_CHARS
+        )));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Package parts
+// ---------------------------------------------------------------------------
+
+fn read_part(archive: &mut Archive<'_>, name: &str) -> Result<Option<String>> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(zip_err(e)),
+    };
+    if entry.size() > MAX_PART_BYTES {
+        return Err(ZeptoError::Tool(format!(
+            "XLSX part {} is too large ({} bytes)",
+            name,
+            entry.size()
+        )));
+    }
+    let mut xml = String::new();
+    (&mut entry)
+        .take(MAX_PART_BYTES)
+        .read_to_string(&mut xml)
+        .map_err(|e| ZeptoError::Tool(format!("Failed to read {}: {}", name, e)))?;
+    Ok(Some(xml))
+}
+
+fn required_part(archive: &mut Archive<'_>, name: &str) -> Result<String> {
+    read_part(archive, name)?
+        .ok_or_else(|| ZeptoError::Tool(format!("Not a valid XLSX file: {} is missing", name)))
+}
+
+/// Every file of the package, in archive order.
+fn read_parts(bytes: &[u8]) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| ZeptoError::Tool(format!("Not a valid XLSX file: {}", e)))?;
+    let mut parts = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(zip_err)?;
+        if entry.is_dir() {
+            continue;
+        }
+        if entry.size() > MAX_PART_BYTES {
+            return Err(ZeptoError::Tool(format!(
+                "XLSX part {} is too large ({} bytes)",
+                entry.name(),
+                entry.size()
+            )));
+        }
+        let name = entry.name().to_string();
+        let mut data = Vec::new();
+        (&mut entry)
+            .take(MAX_PART_BYTES)
+            .read_to_end(&mut data)
+            .map_err(|e| ZeptoError::Tool(format!("Failed to read {}: {}", name, e)))?;
+        parts.push((name, data));
+    }
+    Ok(parts)
+}
+
+fn part_text(parts: &[(String, Vec<u8>)], name: &str) -> Result<String> {
+    parts
+        .iter()
+        .find(|(part, _)| part == name)
+        .map(|(_, data)| String::from_utf8_lossy(data).into_owned())
+        .ok_or_else(|| ZeptoError::Tool(format!("Not a valid XLSX file: {} is missing", name)))
+}
+
+fn set_part(parts: &mut Vec<(String, Vec<u8>)>, name: &str, data: Vec<u8>) {
+    match parts.iter_mut().find(|(part, _)| part == name) {
+        Some((_, existing)) => *existing = data,
+        None => parts.push((name.to_string(), data)),
+    }
+}
+
+fn insert_before(xml: &str, marker: &str, fragment: &str) -> Result<String> {
+    let at = xml
+        .rfind(marker)
+        .ok_or_else(|| ZeptoError::Tool(format!("Unsupported workbook: no {}", marker)))?;
+    let mut out = String::with_capacity(xml.len() + fragment.len());
+    out.push_str(&xml[..at]);
+    out.push_str(fragment);
+    out.push_str(&xml[at..]);
+    Ok(out)
+}
+
+fn zip_parts(parts: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, data) in parts {
+        zip.start_file(name.as_str(), options).map_err(zip_err)?;
+        zip.write_all(data)?;
+    }
+    Ok(zip.finish().map_err(zip_err)?.into_inner())
+}
+
+/// Package of a workbook without sheets, for [`write_sheet`] to add to.
+fn empty_workbook() -> Vec<(String, Vec<u8>)> {
+    let types = format!(
+        "{}<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+         <Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+         <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+         <Override PartName=\"/xl/workbook.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml\"/>\
+         </Types>",
+        XML_DECL
+    );
+    let root_rels = format!(
+        "{}<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+         <Relationship Id=\"rId1\" Type=\"{}/officeDocument\" Target=\"xl/workbook.xml\"/>\
+         </Relationships>",
+        XML_DECL, REL_NS
+    );
+    let workbook = format!(
+        "{}<workbook xmlns=\"{}\" xmlns:r=\"{}\"><sheets></sheets></workbook>",
+        XML_DECL, SHEET_NS, REL_NS
+    );
+    let workbook_rels = format!(
+        "{}<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+         </Relationships>",
+        XML_DECL
+    );
+    vec![
+        ("[Content_Types].xml".to_string(), types.into_bytes()),
+        ("_rels/.rels".to_string(), root_rels.into_bytes()),
+        ("xl/workbook.xml".to_string(), workbook.into_bytes()),
+        (
+            "xl/_rels/workbook.xml.rels".to_string(),
+            workbook_rels.into_bytes(),
+        ),
+    ]
+}
+
+fn sheet_xml(rows: &[Vec<Cell>]) -> String {
+    let mut xml = format!("{}<worksheet xmlns=\"{}\"><sheetData>", XML_DECL, SHEET_NS);
+    for (r, row) in rows.iter().enumerate() {
+        if row.iter().all(Cell::is_empty) {
+            continue;
+        }
+        xml.push_str(&format!("<row r=\"{}\">", r + 1));
+        for (c, cell) in row.iter().enumerate() {
+            let reference = format!("{}{}", column_name(c), r + 1);
+            match cell {
+                Cell::Empty => {}
+                Cell::Number(n) => {
+                    xml.push_str(&format!("<c r=\"{}\"><v>{}</v></c>", reference, n));
+                }
+                Cell::Bool(b) => {
+                    xml.push_str(&format!(
+                        "<c r=\"{}\" t=\"b\"><v>{}</v></c>",
+                        reference,
+                        u8::from(*b)
+                    ));
+                }
+                Cell::Text(text) => {
+                    // XML 1.0 has no representation for most control characters.
+                    let text: String = text
+                        .chars()
+                        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+                        .collect();
+                    xml.push_str(&format!(
+                        "<c r=\"{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+                        reference,
+                        escape(text.as_str())
+                    ));
+                }
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+// ---------------------------------------------------------------------------
+// XML parsing
+// ---------------------------------------------------------------------------
+
+/// Unescaped value of the attribute with local name `name`.
+fn attr(e: &BytesStart<'_>, name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == name)
+        .map(|a| {
+            let raw = String::from_utf8_lossy(&a.value).into_owned();
+            quick_xml::escape::unescape(&raw)
+                .map(|v| v.into_owned())
+                .unwrap_or(raw)
+        })
+}
+
+/// Append the text carried by `event` to `text`.
+fn push_text(event: &Event<'_>, text: &mut String) -> Result<()> {
+    match event {
+        Event::Text(e) => text.push_str(&e.xml_content().map_err(xml_err)?),
+        Event::CData(e) => text.push_str(&String::from_utf8_lossy(e)),
+        Event::GeneralRef(e) => {
+            if let Ok(Some(ch)) = e.resolve_char_ref() {
+                text.push(ch);
+            } else {
+                let name = e.xml_content().map_err(xml_err)?;
+                if let Some(resolved) = resolve_xml_entity(name.as_ref()) {
+                    text.push_str(resolved);
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Relationship id → part path within the package.
+fn relationships(xml: &str) -> Result<HashMap<String, String>> {
+    let mut reader = Reader::from_str(xml);
+    let mut targets = HashMap::new();
+    loop {
+        match reader.read_event().map_err(xml_err)? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"Relationship" => {
+                if let (Some(id), Some(target)) = (attr(&e, b"Id"), attr(&e, b"Target")) {
+                    let path = match target.strip_prefix('/') {
+                        Some(absolute) => absolute.to_string(),
+                        None => format!("xl/{}", target),
+                    };
+                    targets.insert(id, path);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(targets)
+}
+
+fn parse_workbook(workbook_xml: &str, rels_xml: &str) -> Result<Workbook> {
+    let targets = relationships(rels_xml)?;
+    let mut reader = Reader::from_str(workbook_xml);
+    let mut workbook = Workbook {
+        sheets: Vec::new(),
+        date1904: false,
+        rel_prefix: None,
+    };
+    loop {
+        match reader.read_event().map_err(xml_err)? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"workbook" => {
+                    for a in e.attributes().flatten() {
+                        if a.value.as_ref() == REL_NS.as_bytes() {
+                            if let Some(prefix) = a.key.as_ref().strip_prefix(b"xmlns:") {
+                                workbook.rel_prefix =
+                                    Some(String::from_utf8_lossy(prefix).into_owned());
+                            }
+                        }
+                    }
+                }
+                b"workbookPr" => {
+                    workbook.date1904 =
+                        matches!(attr(&e, b"date1904").as_deref(), Some("1" | "true"));
+                }
+                b"sheet" => {
+                    let name = attr(&e, b"name").unwrap_or_default();
+                    workbook.sheets.push(SheetEntry {
+                        name,
+                        sheet_id: attr(&e, b"sheetId")
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or(0),
+                        path: attr(&e, b"id").and_then(|id| targets.get(&id).cloned()),
+                    });
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(workbook)
+}
+
+fn shared_strings(xml: &str) -> Result<Vec<String>> {
+    let mut reader = Reader::from_str(xml);
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    // Phonetic runs (<rPh>) repeat the text as a reading guide.
+    let mut in_phonetic = false;
+    loop {
+        let event = reader.read_event().map_err(xml_err)?;
+        match &event {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"si" => current.clear(),
+                b"rPh" => in_phonetic = true,
+                b"t" if !in_phonetic => in_text = true,
+                _ => {}
+            },
+            Event::Empty(e) if e.local_name().as_ref() == b"si" => strings.push(String::new()),
+            Event::End(e) => match e.local_name().as_ref() {
+                b"si" => strings.push(std::mem::take(&mut current)),
+                b"rPh" => in_phonetic = false,
+                b"t" => in_text = false,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ if in_text => push_text(&event, &mut current)?,
+            _ => {}
+        }
+    }
+    Ok(strings)
+}
+
+/// For each cell style (`cellXfs` index), whether it formats a date.
+fn date_styles(xml: &str) -> Result<Vec<bool>> {
+    let mut reader = Reader::from_str(xml);
+    let mut custom: HashMap<u32, String> = HashMap::new();
+    let mut styles = Vec::new();
+    let mut in_cell_xfs = false;
+    loop {
+        match reader.read_event().map_err(xml_err)? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"numFmt" => {
+                    if let (Some(id), Some(code)) = (
+                        attr(&e, b"numFmtId").and_then(|v| v.parse().ok()),
+                        attr(&e, b"formatCode"),
+                    ) {
+                        custom.insert(id, code);
+                    }
+                }
+                b"cellXfs" => in_cell_xfs = true,
+                b"xf" if in_cell_xfs => {
+                    let id = attr(&e, b"numFmtId")
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or(0);
+                    styles.push(is_date_format(id, &custom));
+                }
+                _ => {}
+            },
+            Event::End(e) if e.local_name().as_ref() == b"cellXfs" => in_cell_xfs = false,
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(styles)
+}
+
+fn is_date_format(id: u32, custom: &HashMap<u32, String>) -> bool {
+    match id {
+        14..=22 | 45..=47 => true,
+        _ => custom.get(&id).is_some_and(|code| looks_like_date(code)),
+    }
+}
+
+/// Whether a custom number format has date or time parts, ignoring quoted
+/// literals, `[...]` sections and escaped characters.
+fn looks_like_date(code: &str) -> bool {
+    let mut quoted = false;
+    let mut bracket = false;
+    let mut skip_next = false;
+    for c in code.chars() {
+        if skip_next {
+            skip_next = false;
+            continue;
+        }
+        match c {
+            '"' => quoted = !quoted,
+            _ if quoted => {}
+            '[' => bracket = true,
+            ']' => bracket = false,
+            _ if bracket => {}
+            '\\' | '_' | '*' => skip_next = true,
+            'd' | 'D' | 'y' | 'Y' | 'h' | 'H' | 's' | 'S' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Render an Excel serial date as `YYYY-MM-DD`, with the time when it has
+/// one. Serials before March 1900 are off by one day (Excel's 1900 leap
+/// year bug), as in most readers.
+fn serial_to_date(serial: f64, date1904: bool) -> Option<String> {
+    if !(0.0..2_958_466.0).contains(&serial) {
+        return None;
+    }
+    let base = if date1904 {
+        NaiveDate::from_ymd_opt(1904, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(1899, 12, 30)?
+    };
+    let days = serial.floor();
+    let seconds = ((serial - days) * 86_400.0).round() as i64;
+    let datetime =
+        base.and_hms_opt(0, 0, 0)? + Duration::days(days as i64) + Duration::seconds(seconds);
+    let format = if seconds == 0 {
+        "%Y-%m-%d"
+    } else if days == 0.0 {
+        "%H:%M:%S"
+    } else {
+        "%Y-%m-%d %H:%M:%S"
+    };
+    Some(datetime.format(format).to_string())
+}
+
+/// A `<c>` element being read.
+struct PendingCell {
+    column: usize,
+    kind: Option<String>,
+    style: Option<usize>,
+}
+
+fn parse_sheet(
+    xml: &str,
+    strings: &[String],
+    date_styles: &[bool],
+    date1904: bool,
+) -> Result<Vec<Vec<Cell>>> {
+    let mut reader = Reader::from_str(xml);
+    let mut rows: Vec<Vec<Cell>> = Vec::new();
+    let mut row_index = 0usize;
+    let mut next_row = 0usize;
+    let mut next_column = 0usize;
+    let mut pending: Option<PendingCell> = None;
+    let mut value = String::new();
+    let mut capture = false;
+
+    loop {
+        let event = reader.read_event().map_err(xml_err)?;
+        match &event {
+            Event::Start(e) | Event::Empty(e) => {
+                let is_start = matches!(event, Event::Start(_));
+                match e.local_name().as_ref() {
+                    b"row" => {
+                        row_index = attr(e, b"r")
+                            .and_then(|r| r.parse::<usize>().ok())
+                            .and_then(|r| r.checked_sub(1))
+                            .unwrap_or(next_row);
+                        next_row = row_index + 1;
+                        next_column = 0;
+                    }
+                    b"c" => {
+                        let column = attr(e, b"r")
+                            .and_then(|r| split_ref(&r)?.0)
+                            .unwrap_or(next_column);
+                        next_column = column + 1;
+                        if is_start {
+                            pending = Some(PendingCell {
+                                column,
+                                kind: attr(e, b"t"),
+                                style: attr(e, b"s").and_then(|s| s.parse().ok()),
+                            });
+                            value.clear();
+                        }
+                    }
+                    b"v" | b"t" if is_start && pending.is_some() => capture = true,
+                    _ => {}
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"v" | b"t" => capture = false,
+                b"c" => {
+                    if let Some(cell) = pending.take() {
+                        let parsed = cell_value(&cell, &value, strings, date_styles, date1904);
+                        if !parsed.is_empty() && row_index < MAX_ROWS && cell.column < MAX_COLUMNS {
+                            if rows.len() <= row_index {
+                                rows.resize_with(row_index + 1, Vec::new);
+                            }
+                            let row = &mut rows[row_index];
+                            if row.len() <= cell.column {
+                                row.resize(cell.column + 1, Cell::Empty);
+                            }
+                            row[cell.column] = parsed;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ if capture => push_text(&event, &mut value)?,
+            _ => {}
+        }
+    }
+    Ok(rows)
+}
+
+fn cell_value(
+    cell: &PendingCell,
+    value: &str,
+    strings: &[String],
+    date_styles: &[bool],
+    date1904: bool,
+) -> Cell {
+    let text = |s: &str| {
+        if s.is_empty() {
+            Cell::Empty
+        } else {
+            Cell::Text(s.to_string())
+        }
+    };
+    match cell.kind.as_deref() {
+        Some("s") => value
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| strings.get(i))
+            .map_or(Cell::Empty, |s| text(s.as_str())),
+        Some("b") => Cell::Bool(value.trim() == "1"),
+        Some("inlineStr" | "str" | "e" | "d") => text(value),
+        _ => match value.trim().parse::<f64>() {
+            Ok(n) => {
+                let is_date = cell
+                    .style
+                    .and_then(|s| date_styles.get(s))
+                    .copied()
+                    .unwrap_or(false);
+                match is_date.then(|| serial_to_date(n, date1904)).flatten() {
+                    Some(date) => Cell::Text(date),
+                    None => Cell::Number(n),
+                }
+            }
+            Err(_) => text(value),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> Cell {
+        Cell::Text(s.to_string())
+    }
+
+    /// A workbook as Excel writes it: shared strings, a date style and a
+    /// formula with its cached result.
+    fn excel_workbook() -> Vec<u8> {
+        let parts = vec![
+            (
+                "[Content_Types].xml",
+                format!(
+                    "{}<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+                     <Override PartName=\"/xl/worksheets/sheet1.xml\" ContentType=\"{}\"/>\
+                     <Override PartName=\"/xl/calcChain.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.calcChain+xml\"/>\
+                     </Types>",
+                    XML_DECL, WORKSHEET_CONTENT_TYPE
+                ),
+            ),
+            (
+                "xl/workbook.xml",
+                format!(
+                    "<workbook xmlns=\"{}\" xmlns:r=\"{}\"><sheets>\
+                     <sheet name=\"Sales &amp; Costs\" sheetId=\"3\" r:id=\"rId7\"/>\
+                     </sheets></workbook>",
+                    SHEET_NS, REL_NS
+                ),
+            ),
+            (
+                "xl/_rels/workbook.xml.rels",
+                format!(
+                    "<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+                     <Relationship Id=\"rId7\" Type=\"{}\" Target=\"worksheets/sheet1.xml\"/>\
+                     <Relationship Id=\"rId8\" Type=\"x/calcChain\" Target=\"calcChain.xml\"/>\
+                     </Relationships>",
+                    WORKSHEET_REL
+                ),
+            ),
+            (
+                "xl/sharedStrings.xml",
+                "<sst><si><t>Date</t></si><si><r><t>Amo</t></r><r><t>unt</t></r></si>\
+                 <si><t>Tom &amp; Jerry</t><rPh><t>tomu</t></rPh></si></sst>"
+                    .to_string(),
+            ),
+            (
+                "xl/styles.xml",
+                "<styleSheet><numFmts><numFmt numFmtId=\"164\" formatCode=\"yyyy/mm/dd\"/></numFmts>\
+                 <cellStyleXfs><xf numFmtId=\"14\"/></cellStyleXfs>\
+                 <cellXfs><xf numFmtId=\"0\"/><xf numFmtId=\"164\"/><xf numFmtId=\"4\"/></cellXfs></styleSheet>"
+                    .to_string(),
+            ),
+            (
+                "xl/worksheets/sheet1.xml",
+                "<worksheet><sheetData>\
+                 <row r=\"1\"><c r=\"A1\" t=\"s\"><v>0</v></c><c r=\"B1\" t=\"s\"><v>1</v></c></row>\
+                 <row r=\"2\"><c r=\"A2\" s=\"1\"><v>45322</v></c><c r=\"B2\" s=\"2\"><v>12.5</v></c>\
+                 <c r=\"D2\" t=\"s\"><v>2</v></c></row>\
+                 <row r=\"4\"><c r=\"A4\" t=\"b\"><v>1</v></c><c r=\"B4\"><f>SUM(B2:B3)</f><v>12.5</v></c>\
+                 <c r=\"C4\" t=\"inlineStr\"><is><t>note</t></is></c><c r=\"D4\" s=\"1\"/></row>\
+                 </sheetData></worksheet>"
+                    .to_string(),
+            ),
+            ("xl/calcChain.xml", "<calcChain/>".to_string()),
+        ];
+        zip_parts(
+            &parts
+                .into_iter()
+                .map(|(name, xml)| (name.to_string(), xml.into_bytes()))
+                .collect::<Vec<_>>(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_read_excel_workbook() {
+        let sheets = read(&excel_workbook()).unwrap();
+        assert_eq!(sheets.len(), 1);
+        assert_eq!(sheets[0].name, "Sales & Costs");
+        assert_eq!(
+            sheets[0].rows,
+            vec![
+                vec![text("Date"), text("Amount")],
+                vec![
+                    text("2024-01-31"),
+                    Cell::Number(12.5),
+                    Cell::Empty,
+                    text("Tom & Jerry")
+                ],
+                vec![],
+                vec![Cell::Bool(true), Cell::Number(12.5), text("note")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_new_workbook_round_trips() {
+        let rows = vec![
+            vec![text("name"), text("score")],
+            vec![text("<Ann> & \"Bo\""), Cell::Number(3.25)],
+            vec![Cell::Empty, Cell::Bool(false)],
+        ];
+        let bytes = write_sheet(None, "Results", &rows).unwrap();
+        let sheets = read(&bytes).unwrap();
+        assert_eq!(sheets.len(), 1);
+        assert_eq!(sheets[0].name, "Results");
+        assert_eq!(sheets[0].rows, rows);
+    }
+
+    #[test]
+    fn test_write_adds_and_replaces_sheets() {
+        let original = excel_workbook();
+        let added = write_sheet(Some(&original), "Summary", &[vec![text("total")]]).unwrap();
+        let sheets = read(&added).unwrap();
+        assert_eq!(
+            sheets.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            vec!["Sales & Costs", "Summary"]
+        );
+        assert_eq!(sheets[0].rows[0], vec![text("Date"), text("Amount")]);
+        assert_eq!(sheets[1].rows, vec![vec![text("total")]]);
+        let parts = read_parts(&added).unwrap();
+        let workbook = part_text(&parts, "xl/workbook.xml").unwrap();
+        assert!(workbook.contains("sheetId=\"4\" r:id=\"rId1\""));
+        assert!(part_text(&parts, "[Content_Types].xml")
+            .unwrap()
+            .contains("/xl/worksheets/sheet2.xml"));
+
+        let replaced =
+            write_sheet(Some(&added), "sales & costs", &[vec![Cell::Number(1.0)]]).unwrap();
+        let sheets = read(&replaced).unwrap();
+        assert_eq!(sheets[0].rows, vec![vec![Cell::Number(1.0)]]);
+        assert_eq!(sheets[1].rows, vec![vec![text("total")]]);
+        let parts = read_parts(&replaced).unwrap();
+        assert!(!parts.iter().any(|(name, _)| name == "xl/calcChain.xml"));
+        assert!(!part_text(&parts, "xl/_rels/workbook.xml.rels")
+            .unwrap()
+            .contains("calcChain"));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(write_sheet(None, "a/b", &[]).is_err());
+        assert!(write_sheet(None, &"x".repeat(32), &[]).is_err());
+        assert!(read(b"not a zip").is_err());
+    }
+
+    #[test]
+    fn test_date_formats() {
+        let custom = HashMap::from([
+            (164, "[$-409]d-mmm-yy".to_string()),
+            (165, "#,##0.00_);[Red]\\(#,##0.00\\)".to_string()),
+            (166, "0.0\" days\"".to_string()),
+        ]);
+        assert!(is_date_format(14, &custom));
+        assert!(is_date_format(164, &custom));
+        assert!(!is_date_format(165, &custom));
+        assert!(!is_date_format(166, &custom));
+        assert!(!is_date_format(2, &custom));
+
+        assert_eq!(
+            serial_to_date(45322.0, false).as_deref(),
+            Some("2024-01-31")
+        );
+        assert_eq!(
+            serial_to_date(45322.75, false).as_deref(),
+            Some("2024-01-31 18:00:00")
+        );
+        assert_eq!(serial_to_date(0.5, false).as_deref(), Some("12:00:00"));
+        assert_eq!(serial_to_date(0.0, true).as_deref(), Some("1904-01-01"));
+        assert_eq!(serial_to_date(-1.0, false), None);
+    }
+}
@@ -0,0 +1,355 @@
+//! Database access for the SQL tools (`tool-sql` feature).
+//!
+//! Postgres and MySQL go through sqlx, SQLite through rusqlite on a blocking
+//! thread. Every call opens its own connection and closes it afterwards, so
+//! a timed-out or failed statement never leaves state behind in a pool.
+
+use std::time::{Duration, Instant};
+
+use futures::TryStreamExt;
+use serde_json::Value;
+use sqlx::{Column, Connection, Executor, Row, TypeInfo};
+
+use crate::error::{Result, ZeptoError};
+
+use super::{QueryRows, SqlBackend};
+
+fn db_err(e: impl std::fmt::Display) -> ZeptoError {
+    ZeptoError::Tool(format!("Database error: {}", e))
+}
+
+/// Bind JSON parameters to a sqlx query.
+macro_rules! bind_params {
+    ($query:expr, $params:expr) => {{
+        let mut query = $query;
+        for param in $params {
+            query = match param {
+                Value::Null => query.bind(None::<String>),
+                Value::Bool(b) => query.bind(*b),
+                Value::Number(n) => match n.as_i64() {
+                    Some(i) => query.bind(i),
+                    None => query.bind(n.as_f64().unwrap_or_default()),
+                },
+                Value::String(s) => query.bind(s.clone()),
+                other => query.bind(other.to_string()),
+            };
+        }
+        query
+    }};
+}
+
+/// Render column `$i` of `$row` with the first listed type that decodes.
+macro_rules! decode_cell {
+    ($row:expr, $i:expr; $($ty:ty),+) => {{
+        let row = $row;
+        let i = $i;
+        let mut cell: Option<String> = None;
+        $(
+            if cell.is_none() {
+                if let Ok(value) = row.try_get::<Option<$ty>, _>(i) {
+                    cell = Some(value.map_or_else(|| "NULL".to_string(), |v| v.to_string()));
+                }
+            }
+        )+
+        if cell.is_none() {
+            if let Ok(value) = row.try_get::<Option<Vec<u8>>, _>(i) {
+                cell = Some(value.map_or_else(
+                    || "NULL".to_string(),
+                    |v| format!("<{} bytes>", v.len()),
+                ));
+            }
+        }
+        cell
+    }};
+}
+
+/// Fetch up to `max_rows` rows of a read-only statement.
+pub(super) async fn fetch(
+    backend: SqlBackend,
+    url: &str,
+    sql: &str,
+    params: &[Value],
+    max_rows: usize,
+    timeout: Duration,
+) -> Result<QueryRows> {
+    match backend {
+        SqlBackend::Postgres => fetch_postgres(url, sql, params, max_rows, timeout).await,
+        SqlBackend::Mysql => fetch_mysql(url, sql, params, max_rows, timeout).await,
+        SqlBackend::Sqlite => {
+            let (url, sql, params) = (url.to_string(), sql.to_string(), params.to_vec());
+            tokio::task::spawn_blocking(move || {
+                sqlite_run(&url, &sql, &params, Some(max_rows), timeout).map(|(rows, _)| rows)
+            })
+            .await
+            .map_err(db_err)?
+        }
+    }
+}
+
+/// Run a write statement and return the number of affected rows.
+pub(super) async fn execute(
+    backend: SqlBackend,
+    url: &str,
+    sql: &str,
+    params: &[Value],
+    timeout: Duration,
+) -> Result<u64> {
+    match backend {
+        SqlBackend::Postgres => {
+            let mut conn = sqlx::PgConnection::connect(url).await.map_err(db_err)?;
+            conn.execute(format!("SET statement_timeout = {}", timeout.as_millis()).as_str())
+                .await
+                .map_err(db_err)?;
+            let result = bind_params!(sqlx::query(sql), params)
+                .execute(&mut conn)
+                .await
+                .map_err(db_err)?;
+            let _ = conn.close().await;
+            Ok(result.rows_affected())
+        }
+        SqlBackend::Mysql => {
+            let mut conn = sqlx::MySqlConnection::connect(url).await.map_err(db_err)?;
+            let result = bind_params!(sqlx::query(sql), params)
+                .execute(&mut conn)
+                .await
+                .map_err(db_err)?;
+            let _ = conn.close().await;
+            Ok(result.rows_affected())
+        }
+        SqlBackend::Sqlite => {
+            let (url, sql, params) = (url.to_string(), sql.to_string(), params.to_vec());
+            tokio::task::spawn_blocking(move || {
+                sqlite_run(&url, &sql, &params, None, timeout).map(|(_, affected)| affected)
+            })
+            .await
+            .map_err(db_err)?
+        }
+    }
+}
+
+async fn fetch_postgres(
+    url: &str,
+    sql: &str,
+    params: &[Value],
+    max_rows: usize,
+    timeout: Duration,
+) -> Result<QueryRows> {
+    let mut conn = sqlx::PgConnection::connect(url).await.map_err(db_err)?;
+    conn.execute(
+        format!(
+            "BEGIN READ ONLY; SET LOCAL statement_timeout = {}",
+            timeout.as_millis()
+        )
+        .as_str(),
+    )
+    .await
+    .map_err(db_err)?;
+
+    let mut result = QueryRows::default();
+    {
+        let mut rows = bind_params!(sqlx::query(sql), params).fetch(&mut conn);
+        while let Some(row) = rows.try_next().await.map_err(db_err)? {
+            if result.rows.len() == max_rows {
+                result.more = true;
+                break;
+            }
+            if result.columns.is_empty() {
+                result.columns = row.columns().iter().map(|c| c.name().to_string()).collect();
+            }
+            let cells = (0..row.columns().len())
+                .map(|i| {
+                    use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+                    decode_cell!(&row, i; bool, i16, i32, i64, f32, f64, String,
+                        sqlx::types::Decimal, NaiveDate, NaiveTime, NaiveDateTime, DateTime<Utc>,
+                        sqlx::types::Uuid, sqlx::types::JsonValue)
+                    .unwrap_or_else(|| {
+                        format!("<{}: cast to text>", row.columns()[i].type_info().name())
+                    })
+                })
+                .collect();
+            result.rows.push(cells);
+        }
+    }
+    let _ = conn.execute("ROLLBACK").await;
+    let _ = conn.close().await;
+    Ok(result)
+}
+
+async fn fetch_mysql(
+    url: &str,
+    sql: &str,
+    params: &[Value],
+    max_rows: usize,
+    timeout: Duration,
+) -> Result<QueryRows> {
+    let mut conn = sqlx::MySqlConnection::connect(url).await.map_err(db_err)?;
+    // MySQL only; MariaDB rejects it and relies on the client-side timeout.
+    let _ = conn
+        .execute(format!("SET SESSION MAX_EXECUTION_TIME = {}", timeout.as_millis()).as_str())
+        .await;
+    conn.execute("START TRANSACTION READ ONLY")
+        .await
+        .map_err(db_err)?;
+
+    let mut result = QueryRows::default();
+    {
+        let mut rows = bind_params!(sqlx::query(sql), params).fetch(&mut conn);
+        while let Some(row) = rows.try_next().await.map_err(db_err)? {
+            if result.rows.len() == max_rows {
+                result.more = true;
+                break;
+            }
+            if result.columns.is_empty() {
+                result.columns = row.columns().iter().map(|c| c.name().to_string()).collect();
+            }
+            let cells = (0..row.columns().len())
+                .map(|i| {
+                    use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+                    decode_cell!(&row, i; i64, u64, i32, u32, i16, u16, i8, u8, f64, f32, bool,
+                        String, sqlx::types::Decimal, NaiveDate, NaiveTime, NaiveDateTime,
+                        DateTime<Utc>, sqlx::types::JsonValue)
+                    .unwrap_or_else(|| {
+                        format!("<{}: cast to CHAR>", row.columns()[i].type_info().name())
+                    })
+                })
+                .collect();
+            result.rows.push(cells);
+        }
+    }
+    let _ = conn.execute("ROLLBACK").await;
+    let _ = conn.close().await;
+    Ok(result)
+}
+
+/// Path of an SQLite URL (`sqlite:path`, `sqlite://path`, `sqlite:///abs`).
+fn sqlite_path(url: &str) -> &str {
+    let path = url.trim();
+    let path = path
+        .strip_prefix("sqlite://")
+        .or_else(|| path.strip_prefix("sqlite:"))
+        .unwrap_or(path);
+    path.split('?').next().unwrap_or(path)
+}
+
+/// Run one statement on SQLite. With `max_rows` the database is opened
+/// read-only and rows are returned; otherwise the statement is executed and
+/// the affected row count returned.
+fn sqlite_run(
+    url: &str,
+    sql: &str,
+    params: &[Value],
+    max_rows: Option<usize>,
+    timeout: Duration,
+) -> Result<(QueryRows, u64)> {
+    use rusqlite::types::{Value as SqlValue, ValueRef};
+    use rusqlite::{Connection, OpenFlags};
+
+    let flags = match max_rows {
+        Some(_) => OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        None => OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    };
+    let conn = Connection::open_with_flags(sqlite_path(url), flags).map_err(db_err)?;
+    conn.busy_timeout(timeout).map_err(db_err)?;
+    let deadline = Instant::now() + timeout;
+    conn.progress_handler(10_000, Some(move || Instant::now() > deadline));
+
+    let values: Vec<SqlValue> = params
+        .iter()
+        .map(|param| match param {
+            Value::Null => SqlValue::Null,
+            Value::Bool(b) => SqlValue::Integer(i64::from(*b)),
+            Value::Number(n) => match n.as_i64() {
+                Some(i) => SqlValue::Integer(i),
+                None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+            },
+            Value::String(s) => SqlValue::Text(s.clone()),
+            other => SqlValue::Text(other.to_string()),
+        })
+        .collect();
+
+    let mut stmt = conn.prepare(sql).map_err(db_err)?;
+    let Some(max_rows) = max_rows else {
+        let affected = stmt
+            .execute(rusqlite::params_from_iter(values))
+            .map_err(db_err)?;
+        return Ok((QueryRows::default(), affected as u64));
+    };
+    if !stmt.readonly() {
+        return Err(ZeptoError::Tool(
+            "Statement would modify the database; the sql tool is read-only".to_string(),
+        ));
+    }
+
+    let mut result = QueryRows {
+        columns: stmt.column_names().into_iter().map(String::from).collect(),
+        ..Default::default()
+    };
+    let width = result.columns.len();
+    let mut rows = stmt
+        .query(rusqlite::params_from_iter(values))
+        .map_err(db_err)?;
+    while let Some(row) = rows.next().map_err(db_err)? {
+        if result.rows.len() == max_rows {
+            result.more = true;
+            break;
+        }
+        let cells = (0..width)
+            .map(|i| match row.get_ref(i) {
+                Ok(ValueRef::Null) => "NULL".to_string(),
+                Ok(ValueRef::Integer(v)) => v.to_string(),
+                Ok(ValueRef::Real(v)) => v.to_string(),
+                Ok(ValueRef::Text(v)) => String::from_utf8_lossy(v).into_owned(),
+                Ok(ValueRef::Blob(v)) => format!("<{} bytes>", v.len()),
+                Err(e) => format!("<{}>", e),
+            })
+            .collect();
+        result.rows.push(cells);
+    }
+    Ok((result, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sqlite_read_only_and_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite:{}", dir.path().join("t.db").display());
+        rusqlite::Connection::open(sqlite_path(&url))
+            .unwrap()
+            .execute_batch("CREATE TABLE t (id INTEGER, name TEXT, data BLOB)")
+            .unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let affected = execute(
+            SqlBackend::Sqlite,
+            &url,
+            "INSERT INTO t VALUES (?, ?, x'0102'), (2, NULL, NULL)",
+            &[serde_json::json!(1), serde_json::json!("one")],
+            timeout,
+        )
+        .await
+        .unwrap();
+        assert_eq!(affected, 2);
+
+        let rows = fetch(
+            SqlBackend::Sqlite,
+            &url,
+            "SELECT * FROM t ORDER BY id",
+            &[],
+            1,
+            timeout,
+        )
+        .await
+        .unwrap();
+        assert_eq!(rows.columns, vec!["id", "name", "data"]);
+        assert_eq!(rows.rows, vec![vec!["1", "one", "<2 bytes>"]]);
+        assert!(rows.more);
+
+        let err = fetch(SqlBackend::Sqlite, &url, "DELETE FROM t", &[], 10, timeout)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("read-only"), "{}", err);
+    }
+}
@@ -0,0 +1,917 @@
+//! SQL database tools — read-only queries and approval-gated writes.
+//!
+//! Connections are named in `tools.sql.connections` and identified by URL
+//! scheme: `postgres://`, `mysql://` or `sqlite:`.
+//!
+//! - `sql` runs parameterized read queries (`query`), describes tables
+//!   (`schema`, cached per connection) and lists connections. Statements are
+//!   checked to be a single read statement, and the database enforces it too:
+//!   Postgres and MySQL run them in a `READ ONLY` transaction that is rolled
+//!   back, SQLite opens the file read-only.
+//! - `sql_execute` runs write statements on connections with
+//!   `allow_writes`. It is in the default approval list, so each write is
+//!   shown to the user first.
+//!
+//! Results are capped at `max_rows` rows and `max_output_bytes` bytes, and
+//! every statement runs under `timeout_secs`.
+//!
+//! Database access requires the `tool-sql` build feature; without it the
+//! tools still register but return a rebuild hint.
+
+#[cfg(feature = "tool-sql")]
+mod driver;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::config::{SqlConnectionConfig, SqlToolConfig};
+use crate::error::{Result, ZeptoError};
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Longest value shown in a result cell.
+const MAX_CELL_CHARS: usize = 200;
+
+/// Statement keywords accepted by the read-only `sql` tool.
+const READ_KEYWORDS: &[&str] = &[
+    "select", "with", "show", "explain", "describe", "desc", "values", "table",
+];
+
+/// Database engine, from the connection URL scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlBackend {
+    Postgres,
+    Mysql,
+    Sqlite,
+}
+
+impl SqlBackend {
+    /// Backend for a connection URL, `None` for unsupported schemes.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let scheme = url.trim().split(':').next()?.to_ascii_lowercase();
+        match scheme.as_str() {
+            "postgres" | "postgresql" => Some(Self::Postgres),
+            "mysql" | "mariadb" => Some(Self::Mysql),
+            "sqlite" => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+
+    /// Placeholder syntax for bound parameters.
+    fn placeholder_hint(self) -> &'static str {
+        match self {
+            Self::Postgres => "$1, $2, ...",
+            Self::Mysql | Self::Sqlite => "?",
+        }
+    }
+
+    /// Query returning (table, column, type, nullable) for every column.
+    fn schema_query(self) -> &'static str {
+        match self {
+            Self::Postgres => {
+                "SELECT (CASE WHEN table_schema = 'public' THEN '' ELSE table_schema::text || '.' END) \
+                 || table_name::text, column_name::text, data_type::text, is_nullable::text \
+                 FROM information_schema.columns \
+                 WHERE table_schema NOT IN ('pg_catalog', 'information_schema') \
+                 ORDER BY table_schema, table_name, ordinal_position"
+            }
+            Self::Mysql => {
+                "SELECT CAST(table_name AS CHAR), CAST(column_name AS CHAR), \
+                 CAST(column_type AS CHAR), CAST(is_nullable AS CHAR) \
+                 FROM information_schema.columns WHERE table_schema = DATABASE() \
+                 ORDER BY table_name, ordinal_position"
+            }
+            Self::Sqlite => {
+                "SELECT m.name, p.name, p.type, CASE WHEN p.\"notnull\" THEN 'NO' ELSE 'YES' END \
+                 FROM sqlite_master m JOIN pragma_table_info(m.name) p \
+                 WHERE m.type IN ('table', 'view') AND m.name NOT LIKE 'sqlite_%' \
+                 ORDER BY m.name, p.cid"
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SqlBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Postgres => "postgres",
+            Self::Mysql => "mysql",
+            Self::Sqlite => "sqlite",
+        })
+    }
+}
+
+/// Rows returned by a query, already rendered as text.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct QueryRows {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// More rows were available beyond the row limit.
+    pub more: bool,
+}
+
+/// First keyword and number of statements in `sql`, ignoring comments and
+/// the contents of quoted strings and identifiers.
+fn scan_statements(sql: &str) -> (Option<String>, usize) {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut keyword = String::new();
+    let mut keyword_done = false;
+    let mut statements = 0;
+    let mut in_statement = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c == '-' && next == Some('-') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+            continue;
+        }
+        if c == ';' {
+            in_statement = false;
+            keyword_done = keyword_done || !keyword.is_empty();
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            keyword_done = keyword_done || !keyword.is_empty();
+            i += 1;
+            continue;
+        }
+        if !in_statement {
+            in_statement = true;
+            statements += 1;
+        }
+        match c {
+            '\'' | '"' | '`' => {
+                keyword_done = true;
+                i += 1;
+                while i < chars.len() {
+                    if chars[i] == c {
+                        // A doubled quote is an escaped quote.
+                        if chars.get(i + 1) == Some(&c) {
+                            i += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    if chars[i] == '\\' && c == '\'' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            '$' => {
+                keyword_done = true;
+                // Postgres dollar quoting: $tag$ ... $tag$ (not $1 placeholders).
+                let rest = &chars[i + 1..];
+                let tag_len = rest.iter().position(|ch| *ch == '$').filter(|&n| {
+                    !rest[0].is_ascii_digit()
+                        && rest[..n]
+                            .iter()
+                            .all(|ch| ch.is_alphanumeric() || *ch == '_')
+                });
+                match tag_len {
+                    Some(n) => {
+                        let tag = &chars[i..i + n + 2];
+                        let mut j = i + tag.len();
+                        while j < chars.len() && !chars[j..].starts_with(tag) {
+                            j += 1;
+                        }
+                        i = j + tag.len();
+                    }
+                    None => i += 1,
+                }
+            }
+            _ => {
+                if !keyword_done {
+                    if c.is_alphabetic() || c == '_' {
+                        keyword.push(c.to_ascii_lowercase());
+                    } else {
+                        keyword_done = true;
+                    }
+                }
+                i += 1;
+            }
+        }
+    }
+    ((!keyword.is_empty()).then_some(keyword), statements)
+}
+
+/// Check that `sql` is a single statement the read-only tool may run.
+pub fn check_read_only(sql: &str) -> std::result::Result<(), String> {
+    let (keyword, statements) = scan_statements(sql);
+    if statements > 1 {
+        return Err("Only one statement per call is allowed".to_string());
+    }
+    match keyword {
+        None => Err("Query is empty".to_string()),
+        Some(k) if READ_KEYWORDS.contains(&k.as_str()) => Ok(()),
+        Some(k) => Err(format!(
+            "'{}' statements are not allowed here; the sql tool is read-only. \
+             Use sql_execute on a connection with allow_writes",
+            k.to_ascii_uppercase()
+        )),
+    }
+}
+
+/// Check that `sql` is a single statement for `sql_execute`.
+pub fn check_single_statement(sql: &str) -> std::result::Result<(), String> {
+    match scan_statements(sql) {
+        (None, _) => Err("Statement is empty".to_string()),
+        (_, n) if n > 1 => Err("Only one statement per call is allowed".to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Shorten a cell for display.
+fn clip_cell(value: &str) -> String {
+    let value = value.replace(['\n', '\r'], " ");
+    if value.chars().count() <= MAX_CELL_CHARS {
+        return value;
+    }
+    let clipped: String = value.chars().take(MAX_CELL_CHARS).collect();
+    format!("{}…", clipped)
+}
+
+/// Render rows as a `|`-separated table, stopping at `max_bytes`.
+pub(crate) fn render_rows(result: &QueryRows, max_bytes: usize) -> String {
+    if result.rows.is_empty() {
+        return "(0 rows)".to_string();
+    }
+    let mut out = result.columns.join(" | ");
+    let mut shown = 0;
+    for row in &result.rows {
+        let line = row
+            .iter()
+            .map(|c| clip_cell(c))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        if out.len() + line.len() + 1 > max_bytes {
+            break;
+        }
+        out.push('\n');
+        out.push_str(&line);
+        shown += 1;
+    }
+    if shown < result.rows.len() {
+        out.push_str(&format!(
+            "\n({} of {} rows shown; output capped at {} bytes — select fewer columns or rows)",
+            shown,
+            result.rows.len(),
+            max_bytes
+        ));
+    } else if result.more {
+        out.push_str(&format!(
+            "\n({} rows shown; more available — add LIMIT/WHERE or aggregate)",
+            shown
+        ));
+    } else {
+        out.push_str(&format!("\n({} rows)", shown));
+    }
+    out
+}
+
+/// Render schema rows (table, column, type, nullable) as one line per table,
+/// optionally only tables whose name contains `filter`.
+fn render_schema(rows: &[Vec<String>], filter: Option<&str>) -> String {
+    let filter = filter.map(str::to_ascii_lowercase);
+    let mut tables: Vec<(String, Vec<String>)> = Vec::new();
+    for row in rows {
+        let [table, column, ty, nullable] = &row[..] else {
+            continue;
+        };
+        if filter
+            .as_deref()
+            .is_some_and(|f| !table.to_ascii_lowercase().contains(f))
+        {
+            continue;
+        }
+        let mut col = format!("{} {}", column, ty.to_ascii_lowercase());
+        if nullable.eq_ignore_ascii_case("no") {
+            col.push_str(" not null");
+        }
+        match tables.last_mut() {
+            Some((name, cols)) if name == table => cols.push(col),
+            _ => tables.push((table.clone(), vec![col])),
+        }
+    }
+    if tables.is_empty() {
+        return match filter {
+            Some(f) => format!("No tables matching '{}'.", f),
+            None => "No tables found.".to_string(),
+        };
+    }
+    tables
+        .iter()
+        .map(|(table, cols)| format!("{}({})", table, cols.join(", ")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Configured connections shared by the `sql` and `sql_execute` tools.
+#[derive(Debug)]
+struct SqlConnections {
+    config: SqlToolConfig,
+    /// Connection name → schema rows (table, column, type, nullable).
+    schema_cache: Mutex<HashMap<String, Vec<Vec<String>>>>,
+}
+
+impl SqlConnections {
+    /// Resolve the `connection` argument; it may be omitted when only one
+    /// connection is configured.
+    fn resolve(&self, args: &Value) -> Result<(String, &SqlConnectionConfig, SqlBackend)> {
+        let requested = args
+            .get("connection")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+        let name = match requested {
+            Some(name) => name.to_string(),
+            None if self.config.connections.len() == 1 => self
+                .config
+                .connections
+                .keys()
+                .next()
+                .cloned()
+                .unwrap_or_default(),
+            None => {
+                return Err(ZeptoError::Tool(format!(
+                    "Specify 'connection': one of {}",
+                    self.names().join(", ")
+                )))
+            }
+        };
+        let conn = self.config.connections.get(&name).ok_or_else(|| {
+            ZeptoError::Tool(format!(
+                "Unknown connection '{}'. Configured: {}",
+                name,
+                self.names().join(", ")
+            ))
+        })?;
+        let backend = SqlBackend::from_url(&conn.url).ok_or_else(|| {
+            ZeptoError::Tool(format!(
+                "Connection '{}' has an unsupported URL; use postgres://, mysql:// or sqlite:",
+                name
+            ))
+        })?;
+        Ok((name, conn, backend))
+    }
+
+    fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.config.connections.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.config.timeout_secs.max(1))
+    }
+
+    fn cached_schema(&self, name: &str) -> Option<Vec<Vec<String>>> {
+        self.schema_cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .cloned()
+    }
+
+    fn cache_schema(&self, name: &str, schema: Option<Vec<Vec<String>>>) {
+        let mut cache = self.schema_cache.lock().unwrap_or_else(|e| e.into_inner());
+        match schema {
+            Some(schema) => cache.insert(name.to_string(), schema),
+            None => cache.remove(name),
+        };
+    }
+}
+
+/// Parameters from the `params` argument.
+fn params_arg(args: &Value) -> Result<Vec<Value>> {
+    match args.get("params") {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Array(params)) => Ok(params.clone()),
+        Some(_) => Err(ZeptoError::Tool(
+            "'params' must be an array of values".to_string(),
+        )),
+    }
+}
+
+fn statement_arg<'a>(args: &'a Value, key: &str) -> Result<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| ZeptoError::Tool(format!("Missing '{}' argument", key)))
+}
+
+#[cfg(feature = "tool-sql")]
+async fn run_query(
+    backend: SqlBackend,
+    url: &str,
+    sql: &str,
+    params: &[Value],
+    max_rows: usize,
+    timeout: Duration,
+) -> Result<QueryRows> {
+    tokio::time::timeout(
+        timeout,
+        driver::fetch(backend, url, sql, params, max_rows, timeout),
+    )
+    .await
+    .map_err(|_| ZeptoError::Tool(format!("Query timed out after {}s", timeout.as_secs())))?
+}
+
+#[cfg(feature = "tool-sql")]
+async fn run_statement(
+    backend: SqlBackend,
+    url: &str,
+    sql: &str,
+    params: &[Value],
+    timeout: Duration,
+) -> Result<u64> {
+    tokio::time::timeout(timeout, driver::execute(backend, url, sql, params, timeout))
+        .await
+        .map_err(|_| {
+            ZeptoError::Tool(format!("Statement timed out after {}s", timeout.as_secs()))
+        })?
+}
+
+#[cfg(not(feature = "tool-sql"))]
+async fn run_query(
+    _backend: SqlBackend,
+    _url: &str,
+    _sql: &str,
+    _params: &[Value],
+    _max_rows: usize,
+    _timeout: Duration,
+) -> Result<QueryRows> {
+    Err(feature_missing())
+}
+
+#[cfg(not(feature = "tool-sql"))]
+async fn run_statement(
+    _backend: SqlBackend,
+    _url: &str,
+    _sql: &str,
+    _params: &[Value],
+    _timeout: Duration,
+) -> Result<u64> {
+    Err(feature_missing())
+}
+
+#[cfg(not(feature = "tool-sql"))]
+fn feature_missing() -> ZeptoError {
+    ZeptoError::Tool(
+        "SQL access requires the 'tool-sql' build feature. \
+         Rebuild with: cargo build --features tool-sql"
+            .to_string(),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// sql (read-only)
+// ---------------------------------------------------------------------------
+
+/// Read-only SQL queries and schema introspection over named connections.
+pub struct SqlTool {
+    connections: Arc<SqlConnections>,
+}
+
+impl SqlTool {
+    /// Create the tool from config.
+    pub fn new(config: SqlToolConfig) -> Self {
+        Self {
+            connections: Arc::new(SqlConnections {
+                config,
+                schema_cache: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// The write tool sharing these connections, if any connection allows
+    /// writes.
+    pub fn execute_tool(&self) -> Option<SqlExecuteTool> {
+        self.connections
+            .config
+            .connections
+            .values()
+            .any(|c| c.allow_writes)
+            .then(|| SqlExecuteTool {
+                connections: Arc::clone(&self.connections),
+            })
+    }
+
+    fn list_connections(&self) -> String {
+        let config = &self.connections.config;
+        if config.connections.is_empty() {
+            return "No SQL connections configured (tools.sql.connections).".to_string();
+        }
+        let mut lines = vec![format!("{} connection(s):", config.connections.len())];
+        for name in self.connections.names() {
+            let conn = &config.connections[&name];
+            let backend = SqlBackend::from_url(&conn.url);
+            let mut line = format!(
+                "- {} ({}, {}, placeholders {})",
+                name,
+                backend.map_or("unsupported".to_string(), |b| b.to_string()),
+                if conn.allow_writes {
+                    "writes via sql_execute"
+                } else {
+                    "read-only"
+                },
+                backend.map_or("-", SqlBackend::placeholder_hint)
+            );
+            if !conn.description.trim().is_empty() {
+                line.push_str(&format!(": {}", conn.description.trim()));
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+
+    async fn schema(&self, args: &Value) -> Result<String> {
+        let (name, conn, backend) = self.connections.resolve(args)?;
+        let table = args
+            .get("table")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+        let refresh = args
+            .get("refresh")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        let rows = match self.connections.cached_schema(&name).filter(|_| !refresh) {
+            Some(rows) => rows,
+            None => {
+                let rows = run_query(
+                    backend,
+                    &conn.url,
+                    backend.schema_query(),
+                    &[],
+                    usize::MAX,
+                    self.connections.timeout(),
+                )
+                .await?
+                .rows;
+                self.connections.cache_schema(&name, Some(rows.clone()));
+                rows
+            }
+        };
+        let rendered = render_schema(&rows, table);
+        let max_bytes = self.connections.config.max_output_bytes;
+        if rendered.len() > max_bytes {
+            let mut cut = max_bytes;
+            while !rendered.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            let cut = rendered[..cut].rfind('\n').unwrap_or(cut);
+            return Ok(format!(
+                "Schema of '{}' ({}):\n{}\n(truncated — pass 'table' to filter)",
+                name,
+                backend,
+                &rendered[..cut]
+            ));
+        }
+        Ok(format!("Schema of '{}' ({}):\n{}", name, backend, rendered))
+    }
+
+    async fn query(&self, args: &Value) -> Result<String> {
+        let (name, conn, backend) = self.connections.resolve(args)?;
+        let sql = statement_arg(args, "query")?;
+        check_read_only(sql).map_err(ZeptoError::Tool)?;
+        let params = params_arg(args)?;
+        let config = &self.connections.config;
+        let max_rows = args
+            .get("max_rows")
+            .and_then(Value::as_u64)
+            .map_or(config.max_rows, |n| (n as usize).min(config.max_rows))
+            .max(1);
+
+        let rows = run_query(
+            backend,
+            &conn.url,
+            sql,
+            &params,
+            max_rows,
+            self.connections.timeout(),
+        )
+        .await
+        .map_err(|e| {
+            ZeptoError::Tool(format!(
+                "{} (connection '{}', {}; use the schema action to check table and column names)",
+                e, name, backend
+            ))
+        })?;
+        Ok(render_rows(&rows, config.max_output_bytes))
+    }
+}
+
+#[async_trait]
+impl Tool for SqlTool {
+    fn name(&self) -> &str {
+        "sql"
+    }
+
+    fn description(&self) -> &str {
+        "Query configured SQL databases (Postgres, MySQL, SQLite) read-only. Actions: \
+         'connections' lists databases, 'schema' describes tables (call it before writing \
+         queries), 'query' runs one SELECT/WITH/SHOW/EXPLAIN statement with optional bound \
+         'params' ($1 placeholders for Postgres, ? for MySQL/SQLite). Results are row- and \
+         size-limited; aggregate or add LIMIT for large tables."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Read-only SQL queries and schema"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkRead
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["query", "schema", "connections"],
+                    "description": "What to do (default: query)"
+                },
+                "connection": {
+                    "type": "string",
+                    "description": "Connection name; optional when only one is configured"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "One read-only SQL statement for the query action"
+                },
+                "params": {
+                    "type": "array",
+                    "description": "Values bound to the query's placeholders, in order"
+                },
+                "max_rows": {
+                    "type": "integer",
+                    "description": "Return at most this many rows (capped by config)",
+                    "minimum": 1
+                },
+                "table": {
+                    "type": "string",
+                    "description": "Only describe tables whose name contains this (schema action)"
+                },
+                "refresh": {
+                    "type": "boolean",
+                    "description": "Re-read the schema instead of using the cached copy"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .unwrap_or("query");
+        let output = match action {
+            "connections" => self.list_connections(),
+            "schema" => self.schema(&args).await?,
+            "query" => self.query(&args).await?,
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown action '{}'. Use query, schema or connections",
+                    other
+                )))
+            }
+        };
+        Ok(ToolOutput::llm_only(output))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// sql_execute (writes)
+// ---------------------------------------------------------------------------
+
+/// Write statements (INSERT/UPDATE/DELETE/DDL) on connections with
+/// `allow_writes`. Created through [`SqlTool::execute_tool`].
+pub struct SqlExecuteTool {
+    connections: Arc<SqlConnections>,
+}
+
+#[async_trait]
+impl Tool for SqlExecuteTool {
+    fn name(&self) -> &str {
+        "sql_execute"
+    }
+
+    fn description(&self) -> &str {
+        "Run one write statement (INSERT, UPDATE, DELETE, CREATE, ...) on a SQL connection that \
+         allows writes, with optional bound 'params'. Returns the number of affected rows. \
+         Use the sql tool for reads."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Run SQL write statement"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "connection": {
+                    "type": "string",
+                    "description": "Connection name; optional when only one is configured"
+                },
+                "statement": {
+                    "type": "string",
+                    "description": "One SQL statement"
+                },
+                "params": {
+                    "type": "array",
+                    "description": "Values bound to the statement's placeholders, in order"
+                }
+            },
+            "required": ["statement"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let (name, conn, backend) = self.connections.resolve(&args)?;
+        if !conn.allow_writes {
+            return Err(ZeptoError::Tool(format!(
+                "Connection '{}' is read-only (set allow_writes to enable sql_execute)",
+                name
+            )));
+        }
+        let sql = statement_arg(&args, "statement")?;
+        check_single_statement(sql).map_err(ZeptoError::Tool)?;
+        let params = params_arg(&args)?;
+
+        let affected =
+            run_statement(backend, &conn.url, sql, &params, self.connections.timeout()).await?;
+        // DDL may have changed the schema.
+        self.connections.cache_schema(&name, None);
+        Ok(ToolOutput::llm_only(format!(
+            "Statement executed on '{}': {} row(s) affected",
+            name, affected
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(connections: &[(&str, &str, bool)]) -> SqlTool {
+        SqlTool::new(SqlToolConfig {
+            connections: connections
+                .iter()
+                .map(|(name, url, allow_writes)| {
+                    (
+                        name.to_string(),
+                        SqlConnectionConfig {
+                            url: url.to_string(),
+                            allow_writes: *allow_writes,
+                            description: String::new(),
+                        },
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_check_read_only() {
+        assert!(check_read_only("SELECT * FROM t WHERE a = ';'").is_ok());
+        assert!(check_read_only("  -- note\n with x as (select 1) select * from x;").is_ok());
+        assert!(check_read_only("/* c */ EXPLAIN SELECT 1").is_ok());
+        assert!(check_read_only("select $$;drop$$").is_ok());
+
+        let err = check_read_only("DELETE FROM t").unwrap_err();
+        assert!(
+            err.contains("'DELETE' statements are not allowed"),
+            "{}",
+            err
+        );
+        let err = check_read_only("SELECT 1; DROP TABLE t").unwrap_err();
+        assert!(err.contains("one statement"), "{}", err);
+        assert!(check_read_only("  ;  ").is_err());
+        assert!(check_single_statement("UPDATE t SET a = 'x;y'").is_ok());
+        assert!(check_single_statement("UPDATE t SET a = 1; DELETE FROM t").is_err());
+    }
+
+    #[test]
+    fn test_backend_from_url() {
+        assert_eq!(
+            SqlBackend::from_url("postgresql://u@h/db"),
+            Some(SqlBackend::Postgres)
+        );
+        assert_eq!(
+            SqlBackend::from_url("mysql://h/db"),
+            Some(SqlBackend::Mysql)
+        );
+        assert_eq!(
+            SqlBackend::from_url("sqlite:data.db"),
+            Some(SqlBackend::Sqlite)
+        );
+        assert_eq!(SqlBackend::from_url("mongodb://h"), None);
+    }
+
+    #[test]
+    fn test_render_rows_limits() {
+        let result = QueryRows {
+            columns: vec!["id".into(), "name".into()],
+            rows: vec![
+                vec!["1".into(), "a\nb".into()],
+                vec!["2".into(), "x".repeat(300)],
+            ],
+            more: true,
+        };
+        let out = render_rows(&result, 10_000);
+        assert!(out.starts_with("id | name\n1 | a b\n2 | "));
+        assert!(out.contains("…\n(2 rows shown; more available"));
+
+        let out = render_rows(&result, 20);
+        assert!(out.contains("(1 of 2 rows shown; output capped at 20 bytes"));
+        assert_eq!(render_rows(&QueryRows::default(), 100), "(0 rows)");
+    }
+
+    #[test]
+    fn test_render_schema() {
+        let rows: Vec<Vec<String>> = [
+            ["users", "id", "INTEGER", "NO"],
+            ["users", "email", "TEXT", "YES"],
+            ["orders", "total", "numeric", "YES"],
+        ]
+        .iter()
+        .map(|r| r.iter().map(|s| s.to_string()).collect())
+        .collect();
+        assert_eq!(
+            render_schema(&rows, None),
+            "users(id integer not null, email text)\norders(total numeric)"
+        );
+        assert_eq!(render_schema(&rows, Some("ORD")), "orders(total numeric)");
+        assert_eq!(render_schema(&rows, Some("x")), "No tables matching 'x'.");
+    }
+
+    #[tokio::test]
+    async fn test_connection_resolution_and_write_gate() {
+        let sql = tool(&[
+            ("app", "postgres://localhost/app", false),
+            ("local", "sqlite:local.db", true),
+        ]);
+        let list = sql
+            .execute(json!({"action": "connections"}), &ToolContext::new())
+            .await
+            .unwrap();
+        assert!(list
+            .for_llm
+            .contains("- app (postgres, read-only, placeholders $1, $2, ...)"));
+
+        let err = sql
+            .execute(json!({"query": "select 1"}), &ToolContext::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("one of app, local"), "{}", err);
+
+        let err = sql
+            .execute(
+                json!({"connection": "app", "query": "drop table users"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("read-only"), "{}", err);
+
+        let write = sql.execute_tool().unwrap();
+        let err = write
+            .execute(
+                json!({"connection": "app", "statement": "delete from users"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("'app' is read-only"), "{}", err);
+        assert!(tool(&[("app", "postgres://h/db", false)])
+            .execute_tool()
+            .is_none());
+    }
+}
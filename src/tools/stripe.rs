@@ -20,16 +20,19 @@
 //! - `create_refund` — Refund a charge or PaymentIntent
 //! - `get_balance` — Retrieve current account balance
 //! - `verify_webhook` — Verify a Stripe webhook signature (HMAC-SHA256 + timestamp)
+//!
+//! With `safety.finance_read_only` set, the tool registry refuses the
+//! `create_*` actions before they reach Stripe.
 
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
-use reqwest::Client;
 use serde_json::{json, Value};
 use sha2::Digest;
 
 use crate::error::{Result, ZeptoError};
+use crate::utils::http::Client;
 
 use super::{Tool, ToolContext, ToolOutput};
 
@@ -38,6 +41,17 @@ static IDEM_KEY_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 const STRIPE_API_BASE: &str = "https://api.stripe.com/v1";
 
+/// Actions allowed in finance read-only mode; every other action counts as
+/// a write.
+const READ_ACTIONS: &[&str] = &[
+    "get_payment",
+    "list_payments",
+    "get_customer",
+    "list_customers",
+    "get_balance",
+    "verify_webhook",
+];
+
 // ---------------------------------------------------------------------------
 // HMAC-SHA256 implementation using the sha2 crate
 // ---------------------------------------------------------------------------
@@ -139,7 +153,7 @@ impl StripeTool {
             secret_key: secret_key.to_string(),
             default_currency: default_currency.to_string(),
             webhook_secret: None,
-            client: Client::new(),
+            client: crate::utils::http::client(),
         }
     }
 
@@ -172,7 +186,7 @@ impl StripeTool {
             secret_key: secret_key.to_string(),
             default_currency: stripe_cfg.default_currency.clone(),
             webhook_secret: stripe_cfg.webhook_secret.clone(),
-            client: Client::new(),
+            client: crate::utils::http::client(),
         })
     }
 
@@ -598,6 +612,13 @@ impl Tool for StripeTool {
         "Stripe payments: create/get payment, customer, refund, balance, verify webhook"
     }
 
+    fn is_finance_write(&self, args: &Value) -> bool {
+        !args
+            .get("action")
+            .and_then(Value::as_str)
+            .is_some_and(|action| READ_ACTIONS.contains(&action))
+    }
+
     fn parameters(&self) -> Value {
         json!({
             "type": "object",
@@ -784,6 +805,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_finance_write() {
+        let tool = StripeTool::new("sk_test_abc", "usd");
+        for action in [
+            "create_payment",
+            "create_customer",
+            "create_refund",
+            "bogus",
+        ] {
+            assert!(
+                tool.is_finance_write(&json!({"action": action})),
+                "{}",
+                action
+            );
+        }
+        // A missing action is treated as a write.
+        assert!(tool.is_finance_write(&json!({})));
+        for action in READ_ACTIONS {
+            assert!(
+                !tool.is_finance_write(&json!({"action": action})),
+                "{}",
+                action
+            );
+        }
+    }
+
     #[test]
     fn test_stripe_tool_parameters_schema() {
         let tool = StripeTool::new("sk_test_abc", "usd");
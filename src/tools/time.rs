@@ -0,0 +1,252 @@
+//! Time tool — current time and conversions between timezones.
+//!
+//! Timezones are IANA names (`Europe/Berlin`) or offsets (`+08:00`); see
+//! [`crate::utils::timezone`]. Without an explicit zone the sender's
+//! timezone from the tool context is used, then the system clock.
+
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use serde_json::{json, Value};
+
+use crate::error::{Result, ZeptoError};
+use crate::tools::{Tool, ToolCategory, ToolContext, ToolOutput};
+use crate::utils::timezone::Tz;
+
+/// Tool that reports the time in a timezone and converts between zones.
+pub struct TimeTool;
+
+impl TimeTool {
+    fn zone(name: Option<&str>, ctx: &ToolContext) -> Result<Tz> {
+        let name = match name.or(ctx.timezone.as_deref()) {
+            Some(name) => name,
+            None => return Tz::load(&Local::now().format("%:z").to_string()),
+        };
+        Tz::load(name).map_err(|_| {
+            ZeptoError::Tool(format!(
+                "Unknown timezone '{}'. Use an IANA name like Europe/Berlin or an offset like +08:00",
+                name
+            ))
+        })
+    }
+
+    fn format(dt: &DateTime<FixedOffset>, tz: &Tz, ctx: &ToolContext) -> String {
+        let time = match &ctx.locale {
+            Some(locale) => locale.format_datetime(dt),
+            None => dt.format("%a %Y-%m-%d %H:%M %:z").to_string(),
+        };
+        format!("{}: {}", tz.name(), time)
+    }
+
+    /// Parse `time` as a wall-clock time in `from`, or as an instant when it
+    /// carries its own offset.
+    fn parse_time(time: &str, from: &Tz) -> Result<DateTime<FixedOffset>> {
+        let time = time.trim();
+        if let Ok(dt) = DateTime::parse_from_rfc3339(time) {
+            return Ok(dt);
+        }
+        for pattern in [
+            "%Y-%m-%d %H:%M",
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%dT%H:%M:%S",
+        ] {
+            if let Ok(local) = NaiveDateTime::parse_from_str(time, pattern) {
+                return Ok(from.from_local(local));
+            }
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(time, "%Y-%m-%d") {
+            return Ok(from.from_local(date.and_time(NaiveTime::MIN)));
+        }
+        for pattern in ["%H:%M", "%H:%M:%S"] {
+            if let Ok(clock) = NaiveTime::parse_from_str(time, pattern) {
+                let today = from.now().date_naive();
+                return Ok(from.from_local(today.and_time(clock)));
+            }
+        }
+        Err(ZeptoError::Tool(format!(
+            "Cannot read time '{}'. Use 'YYYY-MM-DD HH:MM', 'HH:MM' or RFC 3339",
+            time
+        )))
+    }
+}
+
+#[async_trait]
+impl Tool for TimeTool {
+    fn name(&self) -> &str {
+        "time"
+    }
+
+    fn description(&self) -> &str {
+        "Get the current time in a timezone, or convert a time between timezones. \
+         Timezones are IANA names (Europe/Berlin) or UTC offsets (+08:00); \
+         the user's own timezone is the default."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Current time and timezone conversion"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Memory
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["now", "convert"],
+                    "description": "'now' for the current time, 'convert' to convert a time"
+                },
+                "timezone": {
+                    "type": "string",
+                    "description": "For 'now': timezone(s) to show, comma-separated"
+                },
+                "time": {
+                    "type": "string",
+                    "description": "For 'convert': 'YYYY-MM-DD HH:MM', 'HH:MM' (today) or RFC 3339"
+                },
+                "from": {
+                    "type": "string",
+                    "description": "For 'convert': timezone the time is in (default: the user's)"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "For 'convert': target timezone(s), comma-separated"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZeptoError::Tool("Missing required field: action".into()))?;
+        let zones = |key: &str| -> Result<Vec<Tz>> {
+            match args.get(key).and_then(|v| v.as_str()) {
+                Some(names) => names
+                    .split(',')
+                    .filter(|name| !name.trim().is_empty())
+                    .map(|name| Self::zone(Some(name), ctx))
+                    .collect(),
+                None => Ok(vec![Self::zone(None, ctx)?]),
+            }
+        };
+
+        let lines = match action {
+            "now" => zones("timezone")?
+                .iter()
+                .map(|tz| Self::format(&tz.now(), tz, ctx))
+                .collect::<Vec<_>>(),
+            "convert" => {
+                let time = args.get("time").and_then(|v| v.as_str()).ok_or_else(|| {
+                    ZeptoError::Tool("Missing required field for convert: time".into())
+                })?;
+                if args.get("to").and_then(|v| v.as_str()).is_none() {
+                    return Err(ZeptoError::Tool(
+                        "Missing required field for convert: to".into(),
+                    ));
+                }
+                let from = Self::zone(args.get("from").and_then(|v| v.as_str()), ctx)?;
+                let instant = Self::parse_time(time, &from)?;
+                let mut lines = vec![Self::format(&from.from_utc(instant.to_utc()), &from, ctx)];
+                for tz in zones("to")? {
+                    lines.push(Self::format(&tz.from_utc(instant.to_utc()), &tz, ctx));
+                }
+                lines
+            }
+            other => {
+                return Err(ZeptoError::Tool(format!(
+                    "Unknown action '{}'. Use 'now' or 'convert'",
+                    other
+                )))
+            }
+        };
+        Ok(ToolOutput::llm_only(lines.join("\n")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::locale::Locale;
+
+    #[test]
+    fn test_name_and_category() {
+        assert_eq!(TimeTool.name(), "time");
+        assert_eq!(TimeTool.category(), ToolCategory::Memory);
+    }
+
+    #[tokio::test]
+    async fn test_now_defaults_to_context_timezone() {
+        let ctx = ToolContext::new().with_timezone("+05:30");
+        let out = TimeTool
+            .execute(json!({"action": "now"}), &ctx)
+            .await
+            .unwrap();
+        assert!(out.for_llm.starts_with("+05:30: "), "{}", out.for_llm);
+        assert!(out.for_llm.ends_with("+05:30"));
+
+        let out = TimeTool
+            .execute(json!({"action": "now", "timezone": "UTC, -03:00"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(out.for_llm.lines().count(), 2);
+        assert!(out.for_llm.contains("\n-03:00: "));
+    }
+
+    #[tokio::test]
+    async fn test_convert_between_zones() {
+        let out = TimeTool
+            .execute(
+                json!({"action": "convert", "time": "2026-03-01 09:00", "from": "+08:00", "to": "UTC,-05:00"}),
+                &ToolContext::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            out.for_llm,
+            "+08:00: Sun 2026-03-01 09:00 +08:00\n\
+             UTC: Sun 2026-03-01 01:00 +00:00\n\
+             -05:00: Sat 2026-02-28 20:00 -05:00"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_convert_rfc3339_and_locale() {
+        let ctx = ToolContext::new().with_locale(Locale::parse("de").unwrap());
+        let out = TimeTool
+            .execute(
+                json!({"action": "convert", "time": "2026-03-01T09:00:00Z", "to": "+01:00"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(
+            out.for_llm.ends_with("+01:00: 01.03.2026 10:00 +01:00"),
+            "{}",
+            out.for_llm
+        );
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let ctx = ToolContext::new();
+        for args in [
+            json!({}),
+            json!({"action": "later"}),
+            json!({"action": "now", "timezone": "Mars/Base"}),
+            json!({"action": "convert", "time": "09:00"}),
+            json!({"action": "convert", "time": "soon", "to": "UTC"}),
+        ] {
+            assert!(
+                TimeTool.execute(args.clone(), &ctx).await.is_err(),
+                "{}",
+                args
+            );
+        }
+    }
+}
@@ -7,6 +7,12 @@ use std::path::Path;
 use crate::error::{Result, ZeptoError};
 use crate::security::{revalidate_path, validate_path_in_workspace};
 use crate::tools::{Tool, ToolContext, ToolOutput};
+use crate::transcription::{
+    OpenAiWhisperBackend, Transcript, TranscriptionBackend, TranscriptionCandidate,
+};
+
+/// Groq's OpenAI-compatible API.
+const GROQ_API_BASE: &str = "https://api.groq.com/openai/v1";
 
 /// Maximum file size accepted for transcription (25 MiB).
 const MAX_FILE_BYTES: u64 = 25 * 1024 * 1024;
@@ -27,9 +33,7 @@ fn mime_for_extension(ext: &str) -> &'static str {
 }
 
 pub struct TranscribeTool {
-    api_key: String,
-    model: String,
-    client: reqwest::Client,
+    backend: OpenAiWhisperBackend,
 }
 
 impl TranscribeTool {
@@ -37,19 +41,22 @@ impl TranscribeTool {
     ///
     /// Returns `Err` if the underlying HTTP client cannot be built.
     pub fn new(api_key: impl Into<String>, model: impl Into<String>) -> Result<Self> {
-        let client = reqwest::Client::builder()
+        let client = crate::utils::http::client_builder()
             .timeout(std::time::Duration::from_secs(60))
             .build()
             .map_err(|e| ZeptoError::Tool(format!("Failed to build HTTP client: {}", e)))?;
 
-        Ok(Self {
+        let candidate = TranscriptionCandidate {
+            provider_name: "groq".to_string(),
             api_key: api_key.into(),
-            model: model.into(),
-            client,
+            api_base: GROQ_API_BASE.to_string(),
+        };
+        Ok(Self {
+            backend: OpenAiWhisperBackend::new(candidate, &model.into()).with_client(client),
         })
     }
 
-    async fn transcribe_file(&self, path: &str) -> Result<String> {
+    async fn transcribe_file(&self, path: &str) -> Result<Transcript> {
         // --- File size guard ---
         let metadata = tokio::fs::metadata(path)
             .await
@@ -81,45 +88,7 @@ impl TranscribeTool {
             .await
             .map_err(|e| ZeptoError::Tool(format!("Failed to read audio file: {}", e)))?;
 
-        let filename = Path::new(path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("audio.ogg")
-            .to_string();
-
-        let part = reqwest::multipart::Part::bytes(file_bytes)
-            .file_name(filename)
-            .mime_str(mime)
-            .map_err(|e| ZeptoError::Tool(e.to_string()))?;
-
-        let form = reqwest::multipart::Form::new()
-            .part("file", part)
-            .text("model", self.model.clone());
-
-        let resp = self
-            .client
-            .post("https://api.groq.com/openai/v1/audio/transcriptions")
-            .bearer_auth(&self.api_key)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(|e| ZeptoError::Tool(e.to_string()))?;
-
-        if !resp.status().is_success() {
-            let status = resp.status().as_u16();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(ZeptoError::Tool(format!(
-                "Groq transcription failed ({}): {}",
-                status, body
-            )));
-        }
-
-        let json: Value = resp
-            .json()
-            .await
-            .map_err(|e| ZeptoError::Tool(e.to_string()))?;
-
-        Ok(json["text"].as_str().unwrap_or("").to_string())
+        self.backend.transcribe(file_bytes, mime, None).await
     }
 }
 
@@ -210,10 +179,27 @@ impl Tool for TranscribeTool {
         }
 
         match self.transcribe_file(&resolved).await {
-            Ok(text) if text.is_empty() => Ok(ToolOutput::llm_only(
+            Ok(transcript) if transcript.text.is_empty() => Ok(ToolOutput::llm_only(
                 "Transcription returned empty (no speech detected)",
             )),
-            Ok(text) => Ok(ToolOutput::user_visible(format!("Transcription: {}", text))),
+            Ok(transcript) => {
+                let mut details = Vec::new();
+                if let Some(language) = &transcript.language {
+                    details.push(format!("language: {}", language));
+                }
+                if let Some(confidence) = transcript.confidence {
+                    details.push(format!("confidence: {:.0}%", confidence * 100.0));
+                }
+                let details = if details.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", details.join(", "))
+                };
+                Ok(ToolOutput::user_visible(format!(
+                    "Transcription{}: {}",
+                    details, transcript.text
+                )))
+            }
             Err(e) => Ok(ToolOutput::error(format!("Transcription failed: {}", e))),
         }
     }
@@ -5,17 +5,21 @@
 //! execution context to tools.
 
 use async_trait::async_trait;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 
+use crate::bus::MediaAttachment;
 use crate::error::Result;
+use crate::safety::quarantine::UntrustedSource;
 
 /// Category for agent mode enforcement.
 ///
 /// Each tool is assigned a category that determines whether it is allowed,
 /// requires approval, or is blocked under a given agent mode (Observer,
 /// Assistant, Autonomous).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ToolCategory {
     /// Read-only filesystem operations (read, list, glob).
@@ -89,6 +93,12 @@ pub struct ToolOutput {
     /// When true, the agent loop should break after this tool result
     /// and wait for the next user message before continuing.
     pub pause_for_input: bool,
+    /// Set by the kernel gate when this output was quarantined as untrusted.
+    pub untrusted: Option<UntrustedSource>,
+    /// Sources this output drew from, listed under the final response.
+    pub citations: Vec<Citation>,
+    /// Files sent to the user with `for_user` (e.g. a rendered chart).
+    pub media: Vec<MediaAttachment>,
 }
 
 impl ToolOutput {
@@ -100,6 +110,9 @@ impl ToolOutput {
             is_error: false,
             is_async: false,
             pause_for_input: false,
+            untrusted: None,
+            citations: Vec::new(),
+            media: Vec::new(),
         }
     }
 
@@ -112,6 +125,9 @@ impl ToolOutput {
             is_error: false,
             is_async: false,
             pause_for_input: false,
+            untrusted: None,
+            citations: Vec::new(),
+            media: Vec::new(),
         }
     }
 
@@ -123,6 +139,9 @@ impl ToolOutput {
             is_error: true,
             is_async: false,
             pause_for_input: false,
+            untrusted: None,
+            citations: Vec::new(),
+            media: Vec::new(),
         }
     }
 
@@ -134,6 +153,9 @@ impl ToolOutput {
             is_error: false,
             is_async: true,
             pause_for_input: false,
+            untrusted: None,
+            citations: Vec::new(),
+            media: Vec::new(),
         }
     }
 
@@ -149,6 +171,9 @@ impl ToolOutput {
             is_error: false,
             is_async: false,
             pause_for_input: false,
+            untrusted: None,
+            citations: Vec::new(),
+            media: Vec::new(),
         }
     }
 
@@ -160,6 +185,58 @@ impl ToolOutput {
         self.pause_for_input = true;
         self
     }
+
+    /// Attach the sources this output drew from.
+    pub fn with_citations(mut self, citations: Vec<Citation>) -> Self {
+        self.citations = citations;
+        self
+    }
+
+    /// Send a file to the user along with `for_user`.
+    pub fn with_media(mut self, media: MediaAttachment) -> Self {
+        self.media.push(media);
+        self
+    }
+}
+
+/// What a citation points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CitationKind {
+    /// A web page.
+    Web,
+    /// A workspace memory snippet (`path#Lx-Ly`).
+    Memory,
+}
+
+/// A source a tool result drew from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Citation {
+    /// What the source is.
+    pub kind: CitationKind,
+    /// URL or memory location.
+    pub source: String,
+    /// Display title, when known.
+    pub title: Option<String>,
+}
+
+impl Citation {
+    /// A web page citation.
+    pub fn web(url: impl Into<String>, title: Option<String>) -> Self {
+        Self {
+            kind: CitationKind::Web,
+            source: url.into(),
+            title: title.filter(|t| !t.trim().is_empty()),
+        }
+    }
+
+    /// A memory snippet citation.
+    pub fn memory(location: impl Into<String>) -> Self {
+        Self {
+            kind: CitationKind::Memory,
+            source: location.into(),
+            title: None,
+        }
+    }
 }
 
 /// Trait that all tools must implement.
@@ -243,6 +320,32 @@ pub trait Tool: Send + Sync {
     fn category(&self) -> ToolCategory {
         ToolCategory::Shell
     }
+
+    /// Time limit for one call, in seconds. `None` uses the agent's
+    /// `tool_timeout_secs`. Overridden by `tools.limits` in the config.
+    fn timeout_secs(&self) -> Option<u64> {
+        None
+    }
+
+    /// Extra attempts after a call returns an error or times out. Only
+    /// tools whose calls are safe to repeat should return more than 0.
+    fn retries(&self) -> u32 {
+        0
+    }
+
+    /// Whether this call would move money or change financial records.
+    /// Such calls are refused while `safety.finance_read_only` is set, so
+    /// finance tools should answer for every action that is not a read.
+    fn is_finance_write(&self, _args: &Value) -> bool {
+        false
+    }
+}
+
+/// Time limit and retry count the agent loop applies to one tool call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolLimits {
+    pub timeout: std::time::Duration,
+    pub retries: u32,
 }
 
 /// Context provided to tools during execution.
@@ -255,10 +358,23 @@ pub struct ToolContext {
     pub channel: Option<String>,
     /// The chat/conversation ID within the channel
     pub chat_id: Option<String>,
+    /// The session the tool runs in (after resolving session links)
+    pub session_key: Option<String>,
     /// The workspace directory for file operations
     pub workspace: Option<String>,
     /// Whether the tool is running in batch mode (no interactive user).
     pub is_batch: bool,
+    /// The agent turn the tool runs in (groups file changes for undo)
+    pub turn_id: Option<String>,
+    /// Memory namespace of the sender (see `memory.namespaces`)
+    pub memory_namespace: Option<String>,
+    /// Locale of the conversation, for dates and fixed strings in output
+    pub locale: Option<crate::utils::locale::Locale>,
+    /// Timezone of the sender (IANA name or offset), for resolving local times
+    pub timezone: Option<String>,
+    /// Cancelled when the agent turn is stopped; long-running tools should
+    /// watch it and return early
+    pub cancel: CancellationToken,
 }
 
 impl ToolContext {
@@ -296,6 +412,53 @@ impl ToolContext {
         self
     }
 
+    /// Set the session key.
+    pub fn with_session_key(mut self, session_key: &str) -> Self {
+        self.session_key = Some(session_key.to_string());
+        self
+    }
+
+    /// Set the turn ID.
+    pub fn with_turn_id(mut self, turn_id: &str) -> Self {
+        self.turn_id = Some(turn_id.to_string());
+        self
+    }
+
+    /// Set the sender's memory namespace.
+    pub fn with_memory_namespace(mut self, namespace: &str) -> Self {
+        self.memory_namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Set the locale of the conversation.
+    pub fn with_locale(mut self, locale: crate::utils::locale::Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Set the sender's timezone.
+    pub fn with_timezone(mut self, timezone: &str) -> Self {
+        self.timezone = Some(timezone.to_string());
+        self
+    }
+
+    /// Set the token that signals cancellation of the turn.
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Whether the turn this call belongs to has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Long-term memory scope for this call: the sender's namespace plus
+    /// shared entries.
+    pub fn memory_scope(&self) -> crate::memory::namespace::MemoryScope {
+        crate::memory::namespace::MemoryScope::new(self.memory_namespace.clone())
+    }
+
     /// Set the workspace directory.
     ///
     /// # Arguments
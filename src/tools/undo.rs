@@ -0,0 +1,534 @@
+//! Per-turn undo journal for filesystem tools.
+//!
+//! Before `write_file` or `edit_file` changes a file, the file's current
+//! contents (its "before-image") are copied into
+//! `<workspace>/.zeptoclaw/undo/<turn_id>/`. Each agent turn gets its own
+//! directory with a `journal.json` manifest, so the changes of the last N
+//! turns can be reverted with the `undo` tool or `zeptoclaw undo`.
+//!
+//! Only the first change to a path in a turn is recorded; that is the state
+//! the turn started from. Files the turn created are deleted on undo.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::warn;
+
+use crate::error::{Result, ZeptoError};
+use crate::security::validate_path_in_workspace;
+
+use super::filesystem::write_file_secure;
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+/// Journal directory, relative to the workspace.
+pub const UNDO_DIR: &str = ".zeptoclaw/undo";
+
+/// Turns kept in the journal; older ones are pruned.
+pub const MAX_UNDO_TURNS: usize = 50;
+
+const MANIFEST_FILE: &str = "journal.json";
+
+/// Serializes manifest updates from tools running in parallel.
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+
+/// A new journal turn ID. IDs sort in creation order.
+pub fn new_turn_id() -> String {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    format!(
+        "{}-{}",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+        &suffix[..8]
+    )
+}
+
+/// One file touched in a turn.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UndoEntry {
+    /// Path relative to the workspace (absolute if it could not be made relative).
+    pub path: String,
+    /// Before-image file in the turn directory, or `None` if the file did
+    /// not exist before the turn.
+    pub backup: Option<String>,
+}
+
+/// The files one turn changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UndoTurn {
+    pub turn_id: String,
+    #[serde(default)]
+    pub session_key: Option<String>,
+    #[serde(default)]
+    pub entries: Vec<UndoEntry>,
+}
+
+/// What undoing a turn did to each of its files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UndoAction {
+    /// Contents put back from the before-image.
+    Restored(String),
+    /// File did not exist before the turn and was removed.
+    Deleted(String),
+}
+
+impl std::fmt::Display for UndoAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Restored(path) => write!(f, "restored {}", path),
+            Self::Deleted(path) => write!(f, "deleted {}", path),
+        }
+    }
+}
+
+/// Undo journal of one workspace.
+#[derive(Debug, Clone)]
+pub struct UndoJournal {
+    workspace: PathBuf,
+    root: PathBuf,
+}
+
+impl UndoJournal {
+    /// Journal stored under `<workspace>/.zeptoclaw/undo`.
+    pub fn new(workspace: impl Into<PathBuf>) -> Self {
+        let workspace = workspace.into();
+        let root = workspace.join(UNDO_DIR);
+        Self { workspace, root }
+    }
+
+    /// Whether anything has ever been journaled in this workspace.
+    pub fn exists(&self) -> bool {
+        self.root.is_dir()
+    }
+
+    fn turn_dir(&self, turn_id: &str) -> PathBuf {
+        self.root.join(turn_id)
+    }
+
+    fn read_turn(&self, turn_id: &str) -> Option<UndoTurn> {
+        let raw = std::fs::read_to_string(self.turn_dir(turn_id).join(MANIFEST_FILE)).ok()?;
+        serde_json::from_str(&raw).ok()
+    }
+
+    fn write_turn(&self, turn: &UndoTurn) -> Result<()> {
+        let json = serde_json::to_string_pretty(turn)?;
+        std::fs::write(self.turn_dir(&turn.turn_id).join(MANIFEST_FILE), json)?;
+        Ok(())
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(&self.workspace)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Record the before-image of `path` for `turn_id`. Does nothing if the
+    /// path was already recorded in that turn or lies inside the journal.
+    pub fn record(&self, turn_id: &str, session_key: Option<&str>, path: &Path) -> Result<()> {
+        if path.starts_with(&self.root) {
+            return Ok(());
+        }
+        let _guard = JOURNAL_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let dir = self.turn_dir(turn_id);
+        let is_new = !dir.exists();
+        std::fs::create_dir_all(&dir)?;
+        let mut turn = self.read_turn(turn_id).unwrap_or_else(|| UndoTurn {
+            turn_id: turn_id.to_string(),
+            session_key: session_key.map(str::to_string),
+            entries: Vec::new(),
+        });
+
+        let rel = self.relative(path);
+        if turn.entries.iter().any(|e| e.path == rel) {
+            return Ok(());
+        }
+        let backup = match std::fs::read(path) {
+            Ok(content) => {
+                let name = format!("{}.before", turn.entries.len());
+                std::fs::write(dir.join(&name), content)?;
+                Some(name)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        turn.entries.push(UndoEntry { path: rel, backup });
+        self.write_turn(&turn)?;
+
+        if is_new {
+            self.prune(MAX_UNDO_TURNS);
+        }
+        Ok(())
+    }
+
+    /// Journaled turns, newest first.
+    pub fn turns(&self) -> Vec<UndoTurn> {
+        let Ok(dir) = std::fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+        let mut ids: Vec<String> = dir
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        ids.iter().filter_map(|id| self.read_turn(id)).collect()
+    }
+
+    fn prune(&self, keep: usize) {
+        for turn in self.turns().into_iter().skip(keep) {
+            let _ = std::fs::remove_dir_all(self.turn_dir(&turn.turn_id));
+        }
+    }
+
+    /// Journaled turns of `session_key` (every session when `None`), newest
+    /// first, without `current` (the turn the caller itself runs in).
+    pub fn session_turns(&self, session_key: Option<&str>, current: Option<&str>) -> Vec<UndoTurn> {
+        self.turns()
+            .into_iter()
+            .filter(|t| Some(t.turn_id.as_str()) != current)
+            .filter(|t| session_key.is_none() || t.session_key.as_deref() == session_key)
+            .collect()
+    }
+
+    /// Revert the newest `count` turns of `session_key` (every session when
+    /// `None`), skipping `current`. Undone turns are removed from the journal.
+    pub async fn undo(
+        &self,
+        count: usize,
+        session_key: Option<&str>,
+        current: Option<&str>,
+    ) -> Result<Vec<(String, Vec<UndoAction>)>> {
+        let workspace = self.workspace.to_string_lossy().to_string();
+        let mut undone = Vec::new();
+        for turn in self
+            .session_turns(session_key, current)
+            .into_iter()
+            .take(count)
+        {
+            let dir = self.turn_dir(&turn.turn_id);
+            let mut actions = Vec::new();
+            for entry in turn.entries.iter().rev() {
+                let target = validate_path_in_workspace(&entry.path, &workspace)?;
+                let target = target.as_path();
+                match &entry.backup {
+                    Some(name) => {
+                        let content = tokio::fs::read(dir.join(name)).await.map_err(|e| {
+                            ZeptoError::Tool(format!(
+                                "Missing before-image for '{}' in turn {}: {}",
+                                entry.path, turn.turn_id, e
+                            ))
+                        })?;
+                        write_file_secure(target, &workspace, &content).await?;
+                        actions.push(UndoAction::Restored(entry.path.clone()));
+                    }
+                    None => match tokio::fs::remove_file(target).await {
+                        Ok(()) => actions.push(UndoAction::Deleted(entry.path.clone())),
+                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(e) => {
+                            return Err(ZeptoError::Tool(format!(
+                                "Failed to remove '{}': {}",
+                                entry.path, e
+                            )))
+                        }
+                    },
+                }
+            }
+            let _ = std::fs::remove_dir_all(&dir);
+            undone.push((turn.turn_id, actions));
+        }
+        Ok(undone)
+    }
+}
+
+/// Journal the before-image of `path` for the turn in `ctx`. Failures are
+/// logged and never block the write itself.
+pub(crate) async fn record_before(ctx: &ToolContext, path: &Path) {
+    let (Some(turn_id), Some(workspace)) = (ctx.turn_id.clone(), ctx.workspace.clone()) else {
+        return;
+    };
+    let session_key = ctx.session_key.clone();
+    let path = path.to_path_buf();
+    let result = tokio::task::spawn_blocking(move || {
+        UndoJournal::new(workspace).record(&turn_id, session_key.as_deref(), &path)
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => warn!(error = %e, "Failed to record undo before-image"),
+        Err(e) => warn!(error = %e, "Undo journal task failed"),
+    }
+}
+
+/// Render `turns` for listing.
+pub fn format_turns(turns: &[UndoTurn]) -> String {
+    if turns.is_empty() {
+        return "No file changes to undo.".to_string();
+    }
+    let mut out = String::new();
+    for (i, turn) in turns.iter().enumerate() {
+        let paths: Vec<&str> = turn.entries.iter().map(|e| e.path.as_str()).collect();
+        out.push_str(&format!(
+            "{}. {} ({} file(s)): {}\n",
+            i + 1,
+            turn.turn_id,
+            paths.len(),
+            paths.join(", ")
+        ));
+    }
+    out.trim_end().to_string()
+}
+
+/// Render the result of [`UndoJournal::undo`].
+pub fn format_undone(undone: &[(String, Vec<UndoAction>)]) -> String {
+    if undone.is_empty() {
+        return "No file changes to undo.".to_string();
+    }
+    let mut out = format!("Undid {} turn(s):", undone.len());
+    for (turn_id, actions) in undone {
+        out.push_str(&format!("\n- {}", turn_id));
+        for action in actions {
+            out.push_str(&format!("\n  {}", action));
+        }
+    }
+    out
+}
+
+/// Tool for reverting file changes made in earlier turns.
+pub struct UndoTool;
+
+#[async_trait]
+impl Tool for UndoTool {
+    fn name(&self) -> &str {
+        "undo"
+    }
+
+    fn description(&self) -> &str {
+        "Revert the file changes that write_file and edit_file made in the last N turns \
+         (files created in those turns are deleted), or list the turns that can be undone. \
+         Use when the user asks to undo or roll back recent edits."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Undo recent file changes"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::FilesystemWrite
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["undo", "list"],
+                    "description": "'undo' (default) reverts turns, 'list' shows what can be undone"
+                },
+                "turns": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Number of most recent turns to revert (default 1)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolOutput> {
+        let workspace = ctx.workspace.as_ref().ok_or_else(|| {
+            ZeptoError::SecurityViolation(
+                "Workspace not configured; undo requires a workspace".to_string(),
+            )
+        })?;
+        let journal = UndoJournal::new(workspace);
+        let current = ctx.turn_id.as_deref();
+        // A conversation only sees and reverts its own changes.
+        let session_key = ctx.session_key.as_deref();
+
+        match args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("undo")
+        {
+            "list" => {
+                let turns = journal.session_turns(session_key, current);
+                Ok(ToolOutput::llm_only(format_turns(&turns)))
+            }
+            "undo" => {
+                let count = args.get("turns").and_then(|v| v.as_u64()).unwrap_or(1);
+                let count = usize::try_from(count.max(1)).unwrap_or(usize::MAX);
+                let undone = journal.undo(count, session_key, current).await?;
+                Ok(ToolOutput::llm_only(format_undone(&undone)))
+            }
+            other => Err(ZeptoError::Tool(format!(
+                "Unknown action '{}'; expected 'undo' or 'list'",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::filesystem::{EditFileTool, WriteFileTool};
+    use tempfile::tempdir;
+
+    fn ctx(workspace: &Path, turn_id: &str) -> ToolContext {
+        ToolContext::new()
+            .with_workspace(&workspace.to_string_lossy())
+            .with_turn_id(turn_id)
+    }
+
+    #[tokio::test]
+    async fn test_undo_restores_edits_and_deletes_created_files() {
+        let dir = tempdir().unwrap();
+        let ws = dir.path();
+        std::fs::write(ws.join("a.txt"), "original").unwrap();
+
+        let turn1 = ctx(ws, "20260101T000000.000Z-aaaaaaaa");
+        WriteFileTool
+            .execute(json!({"path": "a.txt", "content": "first"}), &turn1)
+            .await
+            .unwrap();
+        WriteFileTool
+            .execute(json!({"path": "a.txt", "content": "second"}), &turn1)
+            .await
+            .unwrap();
+        WriteFileTool
+            .execute(json!({"path": "new/b.txt", "content": "b"}), &turn1)
+            .await
+            .unwrap();
+
+        let turn2 = ctx(ws, "20260101T000001.000Z-bbbbbbbb");
+        EditFileTool
+            .execute(
+                json!({"path": "a.txt", "old_text": "second", "new_text": "third"}),
+                &turn2,
+            )
+            .await
+            .unwrap();
+
+        let journal = UndoJournal::new(ws);
+        let turns = journal.turns();
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].turn_id, "20260101T000001.000Z-bbbbbbbb");
+        assert_eq!(turns[1].entries.len(), 2);
+
+        let undone = journal.undo(1, None, None).await.unwrap();
+        assert_eq!(undone.len(), 1);
+        assert_eq!(std::fs::read_to_string(ws.join("a.txt")).unwrap(), "second");
+
+        let undone = journal.undo(5, None, None).await.unwrap();
+        assert_eq!(
+            undone[0].1,
+            vec![
+                UndoAction::Deleted("new/b.txt".to_string()),
+                UndoAction::Restored("a.txt".to_string()),
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(ws.join("a.txt")).unwrap(),
+            "original"
+        );
+        assert!(!ws.join("new/b.txt").exists());
+        assert!(journal.turns().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_undo_tool_skips_current_turn() {
+        let dir = tempdir().unwrap();
+        let ws = dir.path();
+        let earlier = ctx(ws, "20260101T000000.000Z-aaaaaaaa");
+        WriteFileTool
+            .execute(json!({"path": "notes.md", "content": "draft"}), &earlier)
+            .await
+            .unwrap();
+
+        let now = ctx(ws, "20260101T000005.000Z-cccccccc");
+        WriteFileTool
+            .execute(json!({"path": "other.md", "content": "x"}), &now)
+            .await
+            .unwrap();
+
+        let listed = UndoTool
+            .execute(json!({"action": "list"}), &now)
+            .await
+            .unwrap();
+        assert!(listed.for_llm.contains("notes.md"));
+        assert!(!listed.for_llm.contains("other.md"));
+
+        let result = UndoTool.execute(json!({}), &now).await.unwrap();
+        assert!(result.for_llm.contains("deleted notes.md"));
+        assert!(!ws.join("notes.md").exists());
+        assert!(ws.join("other.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_undo_tool_only_sees_its_own_session() {
+        let dir = tempdir().unwrap();
+        let ws = dir.path();
+        let alice = ctx(ws, "20260101T000000.000Z-aaaaaaaa").with_session_key("telegram:alice");
+        WriteFileTool
+            .execute(json!({"path": "alice.md", "content": "a"}), &alice)
+            .await
+            .unwrap();
+        let bob = ctx(ws, "20260101T000001.000Z-bbbbbbbb").with_session_key("telegram:bob");
+        WriteFileTool
+            .execute(json!({"path": "bob.md", "content": "b"}), &bob)
+            .await
+            .unwrap();
+
+        let now = ctx(ws, "20260101T000005.000Z-cccccccc").with_session_key("telegram:alice");
+        let listed = UndoTool
+            .execute(json!({"action": "list"}), &now)
+            .await
+            .unwrap();
+        assert!(listed.for_llm.contains("alice.md"));
+        assert!(!listed.for_llm.contains("bob.md"));
+
+        // Bob's turn is newer, but Alice's undo reverts her own.
+        let result = UndoTool.execute(json!({"turns": 5}), &now).await.unwrap();
+        assert!(result.for_llm.contains("deleted alice.md"));
+        assert!(!ws.join("alice.md").exists());
+        assert!(ws.join("bob.md").exists());
+        assert_eq!(UndoJournal::new(ws).turns().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_journal_without_turn_id() {
+        let dir = tempdir().unwrap();
+        let ws = dir.path();
+        let ctx = ToolContext::new().with_workspace(&ws.to_string_lossy());
+        WriteFileTool
+            .execute(json!({"path": "a.txt", "content": "x"}), &ctx)
+            .await
+            .unwrap();
+        assert!(!UndoJournal::new(ws).exists());
+        assert_eq!(
+            UndoTool.execute(json!({}), &ctx).await.unwrap().for_llm,
+            "No file changes to undo."
+        );
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_turns() {
+        let dir = tempdir().unwrap();
+        let journal = UndoJournal::new(dir.path());
+        let file = dir.path().join("f.txt");
+        for i in 0..5 {
+            journal
+                .record(&format!("2026010{}T000000.000Z-x", i), None, &file)
+                .unwrap();
+        }
+        journal.prune(2);
+        let ids: Vec<String> = journal.turns().into_iter().map(|t| t.turn_id).collect();
+        assert_eq!(
+            ids,
+            vec!["20260104T000000.000Z-x", "20260103T000000.000Z-x"]
+        );
+    }
+}
@@ -0,0 +1,483 @@
+//! Weather tool backed by Open-Meteo (no API key).
+//!
+//! A place name is geocoded with the Open-Meteo geocoding API, then the
+//! forecast API returns current conditions and a daily forecast. When the
+//! request names no location, the tool uses the user's location from
+//! long-term memory (`tools.weather.location_memory_key`), then
+//! `tools.weather.default_location`.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::config::{WeatherToolConfig, WeatherUnits};
+use crate::error::{Result, ZeptoError};
+use crate::memory::longterm::LongTermMemory;
+use crate::utils::http::Client;
+
+use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+
+const GEOCODING_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+const FORECAST_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+/// Forecast days when `days` is not given, and the most Open-Meteo allows.
+const DEFAULT_DAYS: u64 = 3;
+const MAX_DAYS: u64 = 16;
+
+/// Geocoding candidates considered when a place has a qualifier
+/// ("Portland, Maine").
+const GEOCODING_CANDIDATES: u32 = 10;
+
+/// A resolved place.
+#[derive(Debug, Clone, PartialEq)]
+struct Place {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+}
+
+/// Description of a WMO weather interpretation code.
+fn weather_code_text(code: i64) -> &'static str {
+    match code {
+        0 => "clear sky",
+        1 => "mainly clear",
+        2 => "partly cloudy",
+        3 => "overcast",
+        45 | 48 => "fog",
+        51 | 53 | 55 => "drizzle",
+        56 | 57 => "freezing drizzle",
+        61 => "light rain",
+        63 => "rain",
+        65 => "heavy rain",
+        66 | 67 => "freezing rain",
+        71 => "light snow",
+        73 => "snow",
+        75 => "heavy snow",
+        77 => "snow grains",
+        80 | 81 => "rain showers",
+        82 => "violent rain showers",
+        85 | 86 => "snow showers",
+        95 => "thunderstorm",
+        96 | 99 => "thunderstorm with hail",
+        _ => "unknown conditions",
+    }
+}
+
+/// Parse "lat,lon" coordinates.
+fn parse_coordinates(location: &str) -> Option<Place> {
+    let (lat, lon) = location.split_once(',')?;
+    let latitude: f64 = lat.trim().parse().ok()?;
+    let longitude: f64 = lon.trim().parse().ok()?;
+    ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)).then(|| Place {
+        name: format!("{:.4}, {:.4}", latitude, longitude),
+        latitude,
+        longitude,
+    })
+}
+
+/// Pick the geocoding result for `location`. Text after the first comma
+/// ("Portland, Maine, US") must match the result's region or country when
+/// any candidate matches; otherwise the top result wins.
+fn pick_place(location: &str, body: &Value) -> Option<Place> {
+    let results = body.get("results")?.as_array()?;
+    let qualifiers: Vec<String> = location
+        .split(',')
+        .skip(1)
+        .map(|q| q.trim().to_lowercase())
+        .filter(|q| !q.is_empty())
+        .collect();
+    let text = |r: &Value, key: &str| {
+        r.get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let matches = |r: &Value| {
+        let fields = [
+            text(r, "admin1"),
+            text(r, "country"),
+            text(r, "country_code"),
+        ];
+        qualifiers
+            .iter()
+            .all(|q| fields.iter().any(|f| f.to_lowercase() == *q))
+    };
+    let result = results
+        .iter()
+        .find(|r| matches(r))
+        .or_else(|| results.first())?;
+    let name = [
+        text(result, "name"),
+        text(result, "admin1"),
+        text(result, "country"),
+    ]
+    .into_iter()
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join(", ");
+    Some(Place {
+        name,
+        latitude: result.get("latitude")?.as_f64()?,
+        longitude: result.get("longitude")?.as_f64()?,
+    })
+}
+
+/// Render an Open-Meteo forecast response.
+fn render_forecast(place: &Place, body: &Value, units: WeatherUnits) -> String {
+    let (temp, wind, rain) = match units {
+        WeatherUnits::Metric => ("°C", "km/h", "mm"),
+        WeatherUnits::Imperial => ("°F", "mph", "in"),
+    };
+    let num = |v: &Value, key: &str| v.get(key).and_then(Value::as_f64);
+    let mut out = format!("Weather for {}", place.name);
+    if let Some(tz) = body.get("timezone").and_then(Value::as_str) {
+        out.push_str(&format!(" ({})", tz));
+    }
+    out.push('\n');
+
+    if let Some(current) = body.get("current") {
+        let code = current
+            .get("weather_code")
+            .and_then(Value::as_i64)
+            .unwrap_or(-1);
+        out.push_str(&format!("Now: {}", weather_code_text(code)));
+        if let Some(t) = num(current, "temperature_2m") {
+            out.push_str(&format!(", {:.1}{}", t, temp));
+        }
+        if let Some(t) = num(current, "apparent_temperature") {
+            out.push_str(&format!(" (feels like {:.1}{})", t, temp));
+        }
+        if let Some(h) = num(current, "relative_humidity_2m") {
+            out.push_str(&format!(", humidity {:.0}%", h));
+        }
+        if let Some(w) = num(current, "wind_speed_10m") {
+            out.push_str(&format!(", wind {:.0} {}", w, wind));
+        }
+        if let Some(p) = num(current, "precipitation").filter(|p| *p > 0.0) {
+            out.push_str(&format!(", precipitation {:.1} {}", p, rain));
+        }
+        out.push('\n');
+    }
+
+    if let Some(daily) = body.get("daily") {
+        let column = |key: &str| {
+            daily
+                .get(key)
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default()
+        };
+        let (dates, codes, highs, lows, chances, sums) = (
+            column("time"),
+            column("weather_code"),
+            column("temperature_2m_max"),
+            column("temperature_2m_min"),
+            column("precipitation_probability_max"),
+            column("precipitation_sum"),
+        );
+        for (i, date) in dates.iter().enumerate() {
+            let Some(date) = date.as_str() else { continue };
+            let code = codes.get(i).and_then(Value::as_i64).unwrap_or(-1);
+            out.push_str(&format!("{}: {}", date, weather_code_text(code)));
+            if let (Some(lo), Some(hi)) = (
+                lows.get(i).and_then(Value::as_f64),
+                highs.get(i).and_then(Value::as_f64),
+            ) {
+                out.push_str(&format!(", {:.0}-{:.0}{}", lo, hi, temp));
+            }
+            if let Some(chance) = chances.get(i).and_then(Value::as_f64) {
+                out.push_str(&format!(", {:.0}% chance of precipitation", chance));
+            }
+            if let Some(sum) = sums.get(i).and_then(Value::as_f64).filter(|s| *s > 0.0) {
+                out.push_str(&format!(" ({:.1} {})", sum, rain));
+            }
+            out.push('\n');
+        }
+    }
+    out.trim_end().to_string()
+}
+
+/// Current conditions and daily forecast for a place.
+pub struct WeatherTool {
+    config: WeatherToolConfig,
+    memory: Option<Arc<Mutex<LongTermMemory>>>,
+    client: Client,
+}
+
+impl WeatherTool {
+    /// Create the tool from `tools.weather`.
+    pub fn new(config: WeatherToolConfig) -> Self {
+        Self {
+            config,
+            memory: None,
+            client: crate::utils::http::client_builder()
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+        }
+    }
+
+    /// Look up the user's location in long-term memory when a request
+    /// names none.
+    pub fn with_memory(mut self, memory: Arc<Mutex<LongTermMemory>>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Location for a request: the argument, then memory, then config.
+    async fn resolve_location(&self, args: &Value) -> Option<String> {
+        if let Some(location) = args
+            .get("location")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+        {
+            return Some(location.to_string());
+        }
+        if let Some(memory) = &self.memory {
+            let memory = memory.lock().await;
+            if let Some(entry) = memory.get_readonly(&self.config.location_memory_key) {
+                let value = entry.value.trim();
+                if !value.is_empty() {
+                    return Some(value.to_string());
+                }
+            }
+        }
+        self.config
+            .default_location
+            .as_deref()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+    }
+
+    async fn get_json(&self, url: &str, query: &[(&str, String)]) -> Result<Value> {
+        let response = self
+            .client
+            .get(url)
+            .query(query)
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Weather request failed: {}", e)))?;
+        let status = response.status();
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| ZeptoError::Tool(format!("Invalid weather response: {}", e)))?;
+        if !status.is_success() {
+            let reason = body
+                .get("reason")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(ZeptoError::Tool(format!(
+                "Weather service error ({}): {}",
+                status, reason
+            )));
+        }
+        Ok(body)
+    }
+
+    async fn geocode(&self, location: &str) -> Result<Place> {
+        if let Some(place) = parse_coordinates(location) {
+            return Ok(place);
+        }
+        let name = location.split(',').next().unwrap_or(location).trim();
+        let body = self
+            .get_json(
+                GEOCODING_URL,
+                &[
+                    ("name", name.to_string()),
+                    ("count", GEOCODING_CANDIDATES.to_string()),
+                    ("format", "json".to_string()),
+                ],
+            )
+            .await?;
+        pick_place(location, &body)
+            .ok_or_else(|| ZeptoError::Tool(format!("Location '{}' not found", location)))
+    }
+}
+
+#[async_trait]
+impl Tool for WeatherTool {
+    fn name(&self) -> &str {
+        "weather"
+    }
+
+    fn description(&self) -> &str {
+        "Current weather and daily forecast for a place (city name, 'City, Region' or \
+         'lat,lon'). Omit location to use the user's saved location."
+    }
+
+    fn compact_description(&self) -> &str {
+        "Weather forecast"
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::NetworkRead
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "location": {
+                    "type": "string",
+                    "description": "Place name (e.g. 'Kuala Lumpur', 'Portland, Maine') or 'lat,lon'. Default: the user's saved location"
+                },
+                "days": {
+                    "type": "integer",
+                    "description": "Forecast days including today (default: 3, max: 16)"
+                },
+                "units": {
+                    "type": "string",
+                    "enum": ["metric", "imperial"],
+                    "description": "Unit system (default: configured units)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, _ctx: &ToolContext) -> Result<ToolOutput> {
+        let location = self.resolve_location(&args).await.ok_or_else(|| {
+            ZeptoError::Tool(
+                "No location given and none saved. Ask the user where they are, or set \
+                 tools.weather.default_location."
+                    .into(),
+            )
+        })?;
+        let days = args
+            .get("days")
+            .and_then(Value::as_u64)
+            .unwrap_or(DEFAULT_DAYS)
+            .clamp(1, MAX_DAYS);
+        let units = match args.get("units").and_then(Value::as_str) {
+            Some("imperial") => WeatherUnits::Imperial,
+            Some("metric") => WeatherUnits::Metric,
+            _ => self.config.units,
+        };
+
+        let place = self.geocode(&location).await?;
+        let mut query = vec![
+            ("latitude", place.latitude.to_string()),
+            ("longitude", place.longitude.to_string()),
+            (
+                "current",
+                "temperature_2m,apparent_temperature,relative_humidity_2m,precipitation,\
+                 weather_code,wind_speed_10m"
+                    .to_string(),
+            ),
+            (
+                "daily",
+                "weather_code,temperature_2m_max,temperature_2m_min,\
+                 precipitation_probability_max,precipitation_sum"
+                    .to_string(),
+            ),
+            ("timezone", "auto".to_string()),
+            ("forecast_days", days.to_string()),
+        ];
+        if units == WeatherUnits::Imperial {
+            query.push(("temperature_unit", "fahrenheit".to_string()));
+            query.push(("wind_speed_unit", "mph".to_string()));
+            query.push(("precipitation_unit", "inch".to_string()));
+        }
+        let body = self.get_json(FORECAST_URL, &query).await?;
+        Ok(ToolOutput::llm_only(render_forecast(&place, &body, units)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_coordinates() {
+        let place = parse_coordinates("3.139, 101.6869").unwrap();
+        assert_eq!(place.latitude, 3.139);
+        assert_eq!(place.longitude, 101.6869);
+        assert!(parse_coordinates("Paris, France").is_none());
+        assert!(parse_coordinates("95, 10").is_none());
+    }
+
+    #[test]
+    fn test_pick_place_uses_qualifier() {
+        let body = json!({"results": [
+            {"name": "Portland", "admin1": "Oregon", "country": "United States",
+             "country_code": "US", "latitude": 45.52, "longitude": -122.68},
+            {"name": "Portland", "admin1": "Maine", "country": "United States",
+             "country_code": "US", "latitude": 43.66, "longitude": -70.26}
+        ]});
+        let place = pick_place("Portland, maine", &body).unwrap();
+        assert_eq!(place.name, "Portland, Maine, United States");
+        assert_eq!(place.latitude, 43.66);
+        // Without a qualifier (or an unknown one) the top result wins.
+        assert_eq!(pick_place("Portland", &body).unwrap().latitude, 45.52);
+        assert_eq!(pick_place("Portland, Mars", &body).unwrap().latitude, 45.52);
+        assert!(pick_place("Nowhere", &json!({})).is_none());
+    }
+
+    #[test]
+    fn test_render_forecast() {
+        let place = Place {
+            name: "Kuala Lumpur, Malaysia".to_string(),
+            latitude: 3.15,
+            longitude: 101.69,
+        };
+        let body = json!({
+            "timezone": "Asia/Kuala_Lumpur",
+            "current": {"temperature_2m": 31.24, "apparent_temperature": 36.0,
+                        "relative_humidity_2m": 70, "precipitation": 0.0,
+                        "weather_code": 2, "wind_speed_10m": 7.6},
+            "daily": {"time": ["2026-10-16", "2026-10-17"], "weather_code": [95, 61],
+                      "temperature_2m_max": [33.1, 32.0], "temperature_2m_min": [24.2, 24.0],
+                      "precipitation_probability_max": [80, 55],
+                      "precipitation_sum": [12.5, 0.0]}
+        });
+        let out = render_forecast(&place, &body, WeatherUnits::Metric);
+        assert_eq!(
+            out,
+            "Weather for Kuala Lumpur, Malaysia (Asia/Kuala_Lumpur)\n\
+             Now: partly cloudy, 31.2°C (feels like 36.0°C), humidity 70%, wind 8 km/h\n\
+             2026-10-16: thunderstorm, 24-33°C, 80% chance of precipitation (12.5 mm)\n\
+             2026-10-17: light rain, 24-32°C, 55% chance of precipitation"
+        );
+        let out = render_forecast(&place, &body, WeatherUnits::Imperial);
+        assert!(out.contains("31.2°F"));
+        assert!(out.contains("wind 8 mph"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_location_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut memory = LongTermMemory::with_path(dir.path().join("ltm.json")).unwrap();
+        memory
+            .set("user:location", "Penang", "user", vec![], 1.0)
+            .await
+            .unwrap();
+        let config = WeatherToolConfig {
+            default_location: Some("Ipoh".to_string()),
+            ..Default::default()
+        };
+
+        let tool = WeatherTool::new(config.clone());
+        assert_eq!(
+            tool.resolve_location(&json!({})).await.as_deref(),
+            Some("Ipoh")
+        );
+        let tool = tool.with_memory(Arc::new(Mutex::new(memory)));
+        assert_eq!(
+            tool.resolve_location(&json!({})).await.as_deref(),
+            Some("Penang")
+        );
+        assert_eq!(
+            tool.resolve_location(&json!({"location": " Tokyo "}))
+                .await
+                .as_deref(),
+            Some("Tokyo")
+        );
+        assert!(WeatherTool::new(WeatherToolConfig::default())
+            .resolve_location(&json!({"location": ""}))
+            .await
+            .is_none());
+    }
+}
@@ -10,7 +10,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use once_cell::sync::Lazy;
-use reqwest::{Client, Url};
+use reqwest::Url;
 use scraper::node::Node;
 use scraper::{ElementRef, Html, Selector};
 use serde::Deserialize;
@@ -18,12 +18,13 @@ use serde_json::{json, Value};
 use tokio::net::lookup_host;
 
 use crate::error::{Result, ZeptoError};
+use crate::utils::http::Client;
 
-use super::{Tool, ToolCategory, ToolContext, ToolOutput};
+use super::{Citation, Tool, ToolCategory, ToolContext, ToolOutput};
 
 const BRAVE_API_URL: &str = "https://api.search.brave.com/res/v1/web/search";
 const DDG_HTML_URL: &str = "https://html.duckduckgo.com/html/";
-const WEB_USER_AGENT: &str = "zeptoclaw/0.1 (+https://github.com/zeptoclaw/zeptoclaw)";
+pub(crate) const WEB_USER_AGENT: &str = "zeptoclaw/0.1 (+https://github.com/zeptoclaw/zeptoclaw)";
 const MAX_WEB_SEARCH_COUNT: usize = 10;
 const DEFAULT_MAX_FETCH_CHARS: usize = 50_000;
 const MAX_FETCH_CHARS: usize = 200_000;
@@ -63,7 +64,7 @@ impl WebSearchTool {
     pub fn new(api_key: &str) -> Self {
         Self {
             api_key: api_key.to_string(),
-            client: Client::new(),
+            client: crate::utils::http::client(),
             max_results: 5,
         }
     }
@@ -72,7 +73,7 @@ impl WebSearchTool {
     pub fn with_max_results(api_key: &str, max_results: usize) -> Self {
         Self {
             api_key: api_key.to_string(),
-            client: Client::new(),
+            client: crate::utils::http::client(),
             max_results: max_results.clamp(1, MAX_WEB_SEARCH_COUNT),
         }
     }
@@ -284,7 +285,7 @@ impl DdgSearchTool {
     /// Create a new DDG search tool with default settings.
     pub fn new() -> Self {
         Self {
-            client: Client::new(),
+            client: crate::utils::http::client(),
             max_results: 5,
         }
     }
@@ -292,7 +293,7 @@ impl DdgSearchTool {
     /// Create with custom max results.
     pub fn with_max_results(max_results: usize) -> Self {
         Self {
-            client: Client::new(),
+            client: crate::utils::http::client(),
             max_results: max_results.clamp(1, MAX_WEB_SEARCH_COUNT),
         }
     }
@@ -460,7 +461,7 @@ impl SearxngSearchTool {
         let parsed = validate_searxng_url(api_url)?;
         Ok(Self {
             api_url: parsed,
-            client: Client::new(),
+            client: crate::utils::http::client(),
             max_results: max_results.clamp(1, MAX_WEB_SEARCH_COUNT),
         })
     }
@@ -580,9 +581,8 @@ pub struct WebFetchTool {
 impl WebFetchTool {
     /// Create a new web fetch tool.
     pub fn new() -> Self {
-        let client = Client::builder()
+        let client = crate::utils::http::client_builder()
             .redirect(web_fetch_redirect_policy())
-            .timeout(Duration::from_secs(30))
             .build()
             .unwrap_or_else(|_| Client::new());
 
@@ -710,6 +710,7 @@ impl Tool for WebFetchTool {
                 "Blocked URL host (local or private network)".to_string(),
             ));
         }
+        crate::utils::http::check_url(&parsed)?;
 
         // DNS-based SSRF check: resolve the hostname before making the
         // request and verify none of the resolved IPs are private/local.
@@ -728,9 +729,8 @@ impl Tool for WebFetchTool {
         // validated, so the HTTP library cannot re-resolve to a different
         // (potentially private) address.
         let client = if let Some((host, addr)) = pinned {
-            Client::builder()
+            crate::utils::http::client_builder()
                 .redirect(web_fetch_redirect_policy())
-                .timeout(Duration::from_secs(30))
                 .resolve(&host, addr)
                 .build()
                 .unwrap_or_else(|_| self.client.clone())
@@ -764,23 +764,24 @@ impl Tool for WebFetchTool {
 
         // Read body in chunks with a size limit to prevent unbounded memory
         // allocation from malicious or oversized responses.
-        let body = read_body_limited(response, MAX_FETCH_BYTES).await?;
+        let max_bytes = MAX_FETCH_BYTES.min(crate::utils::http::max_response_bytes());
+        let body = read_body_limited(response, max_bytes).await?;
 
         let include_links = args
             .get("include_links")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
+        let mut title = None;
         let (extractor, mut text) = if content_type.contains("application/json") {
             ("json", body)
         } else if content_type.contains("text/html") || body.trim_start().starts_with('<') {
             let document = Html::parse_document(&body);
-            let title = self.extract_title_from_doc(&document).unwrap_or_default();
+            title = self.extract_title_from_doc(&document);
             let extracted = self.extract_text_from_doc(&document, include_links, &final_url);
-            if title.is_empty() {
-                ("html", extracted)
-            } else {
-                ("html", format!("# {}\n\n{}", title, extracted))
+            match title.as_deref() {
+                Some(title) => ("html", format!("# {}\n\n{}", title, extracted)),
+                None => ("html", extracted),
             }
         } else {
             ("raw", body)
@@ -807,7 +808,8 @@ impl Tool for WebFetchTool {
                 "text": text,
             })
             .to_string(),
-        ))
+        )
+        .with_citations(vec![Citation::web(final_url, title)]))
     }
 }
 
@@ -820,7 +822,7 @@ fn normalize_whitespace(input: &str) -> String {
         .to_string()
 }
 
-fn decode_html_entities(input: &str) -> String {
+pub(crate) fn decode_html_entities(input: &str) -> String {
     let mut output = String::with_capacity(input.len());
     let mut chars = input.chars().peekable();
 
@@ -1100,7 +1102,7 @@ fn dom_walk(element: ElementRef<'_>, output: &mut String) {
     }
 }
 
-fn web_fetch_redirect_policy() -> reqwest::redirect::Policy {
+pub(crate) fn web_fetch_redirect_policy() -> reqwest::redirect::Policy {
     reqwest::redirect::Policy::custom(|attempt| {
         if attempt.previous().len() >= MAX_WEB_FETCH_REDIRECTS {
             return attempt.error(format!(
@@ -1134,7 +1136,7 @@ pub fn validate_redirect_target_basic(url: &Url) -> Result<()> {
         )));
     }
 
-    Ok(())
+    crate::utils::http::check_url(url)
 }
 
 pub fn validate_redirect_target_for_policy(url: &Url) -> Result<()> {
@@ -1237,7 +1239,10 @@ fn extract_links(document: &Html, base_url: &str) -> String {
 /// extremely large response (intentional or otherwise).  The bytes are
 /// accumulated in chunks and converted to a UTF-8 string (lossy) once
 /// the limit is reached or the stream ends.
-async fn read_body_limited(response: reqwest::Response, max_bytes: usize) -> Result<String> {
+pub(crate) async fn read_body_limited(
+    response: reqwest::Response,
+    max_bytes: usize,
+) -> Result<String> {
     let mut buf: Vec<u8> = Vec::new();
     let mut stream = response;
 
@@ -1337,7 +1342,7 @@ pub async fn resolve_and_check_host(url: &Url) -> Result<Option<(String, std::ne
         .map(|addr| (host.to_string(), addr)))
 }
 
-fn is_private_or_local_ip(ip: IpAddr) -> bool {
+pub(crate) fn is_private_or_local_ip(ip: IpAddr) -> bool {
     match ip {
         IpAddr::V4(addr) => is_private_or_local_ipv4(addr),
         IpAddr::V6(addr) => is_private_or_local_ipv6(addr),
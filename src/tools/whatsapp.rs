@@ -1,10 +1,10 @@
 //! WhatsApp Cloud API tool.
 
 use async_trait::async_trait;
-use reqwest::Client;
 use serde_json::{json, Value};
 
 use crate::error::{Result, ZeptoError};
+use crate::utils::http::Client;
 
 use super::{Tool, ToolCategory, ToolContext, ToolOutput};
 
@@ -25,7 +25,7 @@ impl WhatsAppTool {
             phone_number_id: phone_number_id.to_string(),
             access_token: access_token.to_string(),
             default_language: "ms".to_string(),
-            client: Client::new(),
+            client: crate::utils::http::client(),
         }
     }
 
@@ -39,7 +39,7 @@ impl WhatsAppTool {
             phone_number_id: phone_number_id.to_string(),
             access_token: access_token.to_string(),
             default_language: default_language.to_string(),
-            client: Client::new(),
+            client: crate::utils::http::client(),
         }
     }
 }
@@ -0,0 +1,148 @@
+//! Deepgram pre-recorded audio (`/v1/listen`).
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{Transcript, TranscriptionBackend};
+use crate::error::{Result, ZeptoError};
+
+const DEEPGRAM_API_BASE: &str = "https://api.deepgram.com/v1";
+
+/// Transcription through Deepgram.
+#[derive(Debug, Clone)]
+pub struct DeepgramBackend {
+    api_key: String,
+    model: String,
+    api_base: String,
+    client: reqwest::Client,
+}
+
+impl DeepgramBackend {
+    pub fn new(api_key: &str, model: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+            api_base: DEEPGRAM_API_BASE.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Use another API base (self-hosted Deepgram).
+    pub fn with_api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.trim_end_matches('/').to_string();
+        self
+    }
+
+    fn listen_url(&self, language: Option<&str>) -> Result<String> {
+        let mut params = vec![("model", self.model.as_str()), ("smart_format", "true")];
+        match language {
+            Some(language) => params.push(("language", language)),
+            None => params.push(("detect_language", "true")),
+        }
+        reqwest::Url::parse_with_params(&format!("{}/listen", self.api_base), &params)
+            .map(String::from)
+            .map_err(|e| ZeptoError::Provider(format!("Invalid Deepgram URL: {}", e)))
+    }
+}
+
+#[async_trait]
+impl TranscriptionBackend for DeepgramBackend {
+    fn name(&self) -> &str {
+        "deepgram"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        content_type: &str,
+        language: Option<&str>,
+    ) -> Result<Transcript> {
+        let resp = self
+            .client
+            .post(self.listen_url(language)?)
+            .header("Authorization", format!("Token {}", self.api_key))
+            .header("Content-Type", content_type)
+            .body(audio)
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Provider(e.to_string()))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(ZeptoError::Provider(format!(
+                "Deepgram transcription failed ({}): {}",
+                status, body
+            )));
+        }
+
+        let json: Value = resp
+            .json()
+            .await
+            .map_err(|e| ZeptoError::Provider(e.to_string()))?;
+        parse_response(&json, language)
+    }
+}
+
+/// Best alternative of the first channel. Deepgram reports the detected
+/// language only when detection was requested, so the hint fills in.
+fn parse_response(json: &Value, language: Option<&str>) -> Result<Transcript> {
+    let channel = &json["results"]["channels"][0];
+    let alternative = &channel["alternatives"][0];
+    let text = alternative["transcript"]
+        .as_str()
+        .ok_or_else(|| ZeptoError::Provider("Deepgram response has no transcript".to_string()))?;
+    Ok(Transcript {
+        text: text.trim().to_string(),
+        language: channel["detected_language"]
+            .as_str()
+            .or(language)
+            .map(str::to_string),
+        confidence: alternative["confidence"].as_f64().map(|c| c as f32),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_listen_url() {
+        let backend = DeepgramBackend::new("key", "nova-2");
+        assert_eq!(
+            backend.listen_url(Some("de")).unwrap(),
+            "https://api.deepgram.com/v1/listen?model=nova-2&smart_format=true&language=de"
+        );
+        let backend = backend.with_api_base("http://dg.local/v1/");
+        assert_eq!(
+            backend.listen_url(None).unwrap(),
+            "http://dg.local/v1/listen?model=nova-2&smart_format=true&detect_language=true"
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let body = json!({"results": {"channels": [{
+            "detected_language": "es",
+            "alternatives": [{"transcript": "hola ", "confidence": 0.97}]
+        }]}});
+        let transcript = parse_response(&body, None).unwrap();
+        assert_eq!(transcript.text, "hola");
+        assert_eq!(transcript.language.as_deref(), Some("es"));
+        assert_eq!(transcript.confidence, Some(0.97));
+
+        let hinted = json!({"results": {"channels": [{
+            "alternatives": [{"transcript": "hallo"}]
+        }]}});
+        let transcript = parse_response(&hinted, Some("de")).unwrap();
+        assert_eq!(transcript.language.as_deref(), Some("de"));
+        assert_eq!(transcript.confidence, None);
+
+        assert!(parse_response(&json!({"err_code": "INVALID_AUTH"}), None).is_err());
+    }
+}
@@ -0,0 +1,407 @@
+//! Provider-agnostic audio transcription service.
+//!
+//! [`TranscriptionBackend`] is implemented for the OpenAI-compatible
+//! `/audio/transcriptions` endpoint of each configured provider
+//! ([`OpenAiWhisperBackend`]), Deepgram ([`DeepgramBackend`]) and a local
+//! whisper.cpp CLI (`WhisperCppBackend`, feature `whisper-cpp`).
+//! `transcription.backend` selects which one [`TranscriberService`] uses;
+//! it tries its backends in order and falls back to `[Voice Message]` if all
+//! fail or none are configured.
+
+mod deepgram;
+mod openai;
+#[cfg(feature = "whisper-cpp")]
+mod whisper_cpp;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{debug, warn};
+
+use crate::config::{Config, TranscriptionProvider};
+use crate::error::Result;
+
+pub use deepgram::DeepgramBackend;
+pub use openai::{OpenAiWhisperBackend, TranscriptionCandidate};
+#[cfg(feature = "whisper-cpp")]
+pub use whisper_cpp::WhisperCppBackend;
+
+/// Message content used when audio could not be transcribed.
+pub const VOICE_FALLBACK: &str = "[Voice Message]";
+
+/// Result of transcribing one audio clip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcript {
+    pub text: String,
+    /// Spoken language as reported by the backend (code or name).
+    pub language: Option<String>,
+    /// Confidence between 0.0 and 1.0, when the backend reports one.
+    pub confidence: Option<f32>,
+}
+
+impl Transcript {
+    /// A transcript without language or confidence.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            language: None,
+            confidence: None,
+        }
+    }
+
+    /// Message content for the agent: `[Voice: <text>]`, with the language
+    /// and confidence when known (`[Voice (de, 93% confidence): <text>]`).
+    pub fn to_message(&self) -> String {
+        let text = self.text.trim();
+        if text.is_empty() {
+            return VOICE_FALLBACK.to_string();
+        }
+        let mut details = Vec::new();
+        if let Some(language) = &self.language {
+            details.push(language.clone());
+        }
+        if let Some(confidence) = self.confidence {
+            details.push(format!("{:.0}% confidence", confidence * 100.0));
+        }
+        if details.is_empty() {
+            format!("[Voice: {}]", text)
+        } else {
+            format!("[Voice ({}): {}]", details.join(", "), text)
+        }
+    }
+}
+
+/// A speech-to-text engine.
+#[async_trait]
+pub trait TranscriptionBackend: Send + Sync + std::fmt::Debug {
+    /// Short name for logs (e.g. "openai", "groq", "deepgram").
+    fn name(&self) -> &str;
+
+    /// Model the backend transcribes with.
+    fn model(&self) -> &str;
+
+    /// Transcribe `audio` of MIME type `content_type`. `language` is an
+    /// ISO 639-1 hint; `None` lets the backend detect the language.
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        content_type: &str,
+        language: Option<&str>,
+    ) -> Result<Transcript>;
+}
+
+/// Service that transcribes audio bytes with the configured backend(s).
+///
+/// Built from config at startup. Tries backends in order and falls back to
+/// `[Voice Message]` on total failure.
+#[derive(Debug, Clone)]
+pub struct TranscriberService {
+    backends: Vec<Arc<dyn TranscriptionBackend>>,
+    language: Option<String>,
+}
+
+impl TranscriberService {
+    /// Build from config. Returns `None` if transcription is disabled or the
+    /// selected backend is not configured.
+    ///
+    /// The `openai` backend uses every OpenAI-compatible provider with an API
+    /// key, in registry order (Anthropic has no audio API and is skipped).
+    /// There is no fallback across backends, so a local whisper.cpp setup
+    /// never sends audio to a cloud service.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let transcription = &config.transcription;
+        if !transcription.enabled {
+            return None;
+        }
+
+        let backends: Vec<Arc<dyn TranscriptionBackend>> = match transcription.backend {
+            TranscriptionProvider::Openai => OpenAiWhisperBackend::from_config(config)
+                .into_iter()
+                .map(|backend| Arc::new(backend) as Arc<dyn TranscriptionBackend>)
+                .collect(),
+            TranscriptionProvider::Deepgram => match &transcription.deepgram_api_key {
+                Some(key) if !key.trim().is_empty() => vec![Arc::new(DeepgramBackend::new(
+                    key,
+                    &transcription.deepgram_model,
+                ))],
+                _ => {
+                    warn!("Deepgram transcription selected but no deepgram_api_key is set");
+                    Vec::new()
+                }
+            },
+            TranscriptionProvider::WhisperCpp => Self::whisper_cpp(config),
+        };
+        if backends.is_empty() {
+            return None;
+        }
+
+        // Hint: configured language, else the agent's reply language.
+        let language = transcription.language.clone().or_else(|| {
+            config
+                .agents
+                .defaults
+                .language
+                .as_deref()
+                .and_then(crate::utils::locale::Locale::parse)
+                .and_then(|locale| locale.tag().split('-').next().map(str::to_lowercase))
+        });
+
+        Some(Self::new(backends, language))
+    }
+
+    #[cfg(feature = "whisper-cpp")]
+    fn whisper_cpp(config: &Config) -> Vec<Arc<dyn TranscriptionBackend>> {
+        match &config.transcription.whisper_cpp_model {
+            Some(model) => vec![Arc::new(WhisperCppBackend::new(
+                &config.transcription.whisper_cpp_binary,
+                model,
+            ))],
+            None => {
+                warn!("whisper.cpp transcription selected but no whisper_cpp_model is set");
+                Vec::new()
+            }
+        }
+    }
+
+    #[cfg(not(feature = "whisper-cpp"))]
+    fn whisper_cpp(_config: &Config) -> Vec<Arc<dyn TranscriptionBackend>> {
+        warn!("whisper.cpp transcription requires a build with the `whisper-cpp` feature");
+        Vec::new()
+    }
+
+    /// Service over explicit backends, tried in order.
+    pub fn new(backends: Vec<Arc<dyn TranscriptionBackend>>, language: Option<String>) -> Self {
+        Self { backends, language }
+    }
+
+    /// Names of the backends, in the order they are tried.
+    pub fn backend_names(&self) -> Vec<&str> {
+        self.backends.iter().map(|backend| backend.name()).collect()
+    }
+
+    /// Language hint passed to the backends.
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Transcribe raw audio bytes with the first backend that succeeds.
+    pub async fn transcribe_detailed(
+        &self,
+        audio: Vec<u8>,
+        content_type: &str,
+    ) -> Option<Transcript> {
+        for backend in &self.backends {
+            match backend
+                .transcribe(audio.clone(), content_type, self.language.as_deref())
+                .await
+            {
+                Ok(transcript) => {
+                    debug!(backend = %backend.name(), "Transcription succeeded");
+                    return Some(transcript);
+                }
+                Err(e) => {
+                    warn!(
+                        backend = %backend.name(),
+                        error = %e,
+                        "Transcription failed, trying next backend"
+                    );
+                }
+            }
+        }
+        None
+    }
+
+    /// Transcribe raw audio bytes. Returns transcript or `"[Voice Message]"` on total failure.
+    pub async fn transcribe(&self, audio: Vec<u8>, content_type: &str) -> String {
+        match self.transcribe_detailed(audio, content_type).await {
+            Some(transcript) => transcript.text,
+            None => VOICE_FALLBACK.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProviderConfig;
+    use crate::error::ZeptoError;
+
+    fn make_config_with_provider(name: &str, api_key: &str, api_base: &str) -> Config {
+        let mut config = Config::default();
+        let pc = ProviderConfig {
+            api_key: Some(api_key.to_string()),
+            api_base: Some(api_base.to_string()),
+            ..Default::default()
+        };
+        match name {
+            "openai" => config.providers.openai = Some(pc),
+            "groq" => config.providers.groq = Some(pc),
+            "anthropic" => config.providers.anthropic = Some(pc),
+            _ => {}
+        }
+        config
+    }
+
+    /// Backend with a canned result.
+    #[derive(Debug)]
+    struct FakeBackend(Option<&'static str>);
+
+    #[async_trait]
+    impl TranscriptionBackend for FakeBackend {
+        fn name(&self) -> &str {
+            if self.0.is_some() {
+                "ok"
+            } else {
+                "failing"
+            }
+        }
+
+        fn model(&self) -> &str {
+            "fake"
+        }
+
+        async fn transcribe(
+            &self,
+            _audio: Vec<u8>,
+            _content_type: &str,
+            language: Option<&str>,
+        ) -> Result<Transcript> {
+            match self.0 {
+                Some(text) => Ok(Transcript {
+                    text: text.to_string(),
+                    language: language.map(str::to_string),
+                    confidence: Some(0.9),
+                }),
+                None => Err(ZeptoError::Provider("down".into())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_config_openai_included() {
+        let config = make_config_with_provider("openai", "sk-test", "https://api.openai.com/v1");
+        let svc = TranscriberService::from_config(&config).unwrap();
+        assert_eq!(svc.backend_names(), vec!["openai"]);
+    }
+
+    #[test]
+    fn test_from_config_groq_included() {
+        let config =
+            make_config_with_provider("groq", "gsk_test", "https://api.groq.com/openai/v1");
+        let svc = TranscriberService::from_config(&config).unwrap();
+        assert_eq!(svc.backend_names(), vec!["groq"]);
+    }
+
+    #[test]
+    fn test_from_config_anthropic_excluded() {
+        let config =
+            make_config_with_provider("anthropic", "sk-ant-test", "https://api.anthropic.com");
+        let svc = TranscriberService::from_config(&config);
+        assert!(
+            svc.is_none(),
+            "Anthropic should be excluded from transcription"
+        );
+    }
+
+    #[test]
+    fn test_from_config_disabled() {
+        let mut config =
+            make_config_with_provider("openai", "sk-test", "https://api.openai.com/v1");
+        config.transcription.enabled = false;
+        assert!(TranscriberService::from_config(&config).is_none());
+    }
+
+    #[test]
+    fn test_from_config_no_providers() {
+        let config = Config::default();
+        assert!(TranscriberService::from_config(&config).is_none());
+    }
+
+    #[test]
+    fn test_from_config_uses_configured_model() {
+        let mut config =
+            make_config_with_provider("openai", "sk-test", "https://api.openai.com/v1");
+        config.transcription.model = "whisper-large-v3".to_string();
+        let svc = TranscriberService::from_config(&config).unwrap();
+        assert_eq!(svc.backends[0].model(), "whisper-large-v3");
+    }
+
+    #[test]
+    fn test_from_config_deepgram() {
+        let mut config =
+            make_config_with_provider("openai", "sk-test", "https://api.openai.com/v1");
+        config.transcription.backend = TranscriptionProvider::Deepgram;
+        // No cross-backend fallback: without a key there is no service.
+        assert!(TranscriberService::from_config(&config).is_none());
+
+        config.transcription.deepgram_api_key = Some("dg-key".into());
+        let svc = TranscriberService::from_config(&config).unwrap();
+        assert_eq!(svc.backend_names(), vec!["deepgram"]);
+        assert_eq!(svc.backends[0].model(), "nova-2");
+    }
+
+    #[cfg(not(feature = "whisper-cpp"))]
+    #[test]
+    fn test_from_config_whisper_cpp_needs_feature() {
+        let mut config = Config::default();
+        config.transcription.backend = TranscriptionProvider::WhisperCpp;
+        config.transcription.whisper_cpp_model = Some("/models/ggml-base.bin".into());
+        assert!(TranscriberService::from_config(&config).is_none());
+    }
+
+    #[test]
+    fn test_language_hint_defaults_to_agent_language() {
+        let mut config =
+            make_config_with_provider("openai", "sk-test", "https://api.openai.com/v1");
+        assert_eq!(
+            TranscriberService::from_config(&config).unwrap().language(),
+            None
+        );
+        config.agents.defaults.language = Some("de-AT".into());
+        assert_eq!(
+            TranscriberService::from_config(&config).unwrap().language(),
+            Some("de")
+        );
+        config.transcription.language = Some("fr".into());
+        assert_eq!(
+            TranscriberService::from_config(&config).unwrap().language(),
+            Some("fr")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_empty_candidates_returns_fallback() {
+        let svc = TranscriberService::new(vec![], None);
+        let result = svc.transcribe(vec![1, 2, 3], "audio/ogg").await;
+        assert_eq!(result, "[Voice Message]");
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_falls_through_failing_backends() {
+        let svc = TranscriberService::new(
+            vec![
+                Arc::new(FakeBackend(None)),
+                Arc::new(FakeBackend(Some("hallo"))),
+            ],
+            Some("de".into()),
+        );
+        let transcript = svc.transcribe_detailed(vec![1], "audio/ogg").await.unwrap();
+        assert_eq!(transcript.text, "hallo");
+        assert_eq!(transcript.language.as_deref(), Some("de"));
+        assert_eq!(
+            transcript.to_message(),
+            "[Voice (de, 90% confidence): hallo]"
+        );
+    }
+
+    #[test]
+    fn test_transcript_message() {
+        assert_eq!(Transcript::new(" hi ").to_message(), "[Voice: hi]");
+        assert_eq!(Transcript::new("  ").to_message(), VOICE_FALLBACK);
+        let transcript = Transcript {
+            confidence: Some(0.426),
+            ..Transcript::new("hi")
+        };
+        assert_eq!(transcript.to_message(), "[Voice (43% confidence): hi]");
+    }
+}
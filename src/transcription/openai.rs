@@ -0,0 +1,226 @@
+//! OpenAI-compatible `/audio/transcriptions` (OpenAI, Groq, ...).
+
+use async_trait::async_trait;
+use reqwest::multipart;
+use serde_json::Value;
+
+use super::{Transcript, TranscriptionBackend};
+use crate::config::Config;
+use crate::error::{Result, ZeptoError};
+use crate::providers::{provider_config_by_name, PROVIDER_REGISTRY};
+use crate::utils::http::Client;
+
+/// A single transcription endpoint candidate.
+#[derive(Debug, Clone)]
+pub struct TranscriptionCandidate {
+    pub provider_name: String,
+    pub api_key: String,
+    pub api_base: String,
+}
+
+/// Whisper through an OpenAI-compatible provider.
+#[derive(Debug, Clone)]
+pub struct OpenAiWhisperBackend {
+    candidate: TranscriptionCandidate,
+    model: String,
+    client: Client,
+}
+
+impl OpenAiWhisperBackend {
+    pub fn new(candidate: TranscriptionCandidate, model: &str) -> Self {
+        Self {
+            candidate,
+            model: model.to_string(),
+            client: crate::utils::http::client(),
+        }
+    }
+
+    /// Use `client` for requests (e.g. one with a timeout).
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// One backend per configured OpenAI-compatible provider with an API key,
+    /// in registry order. Skips providers with `backend == "anthropic"`.
+    pub fn from_config(config: &Config) -> Vec<Self> {
+        PROVIDER_REGISTRY
+            .iter()
+            .filter(|spec| spec.backend == "openai")
+            .filter_map(|spec| {
+                let pc = provider_config_by_name(config, spec.name)?;
+                let api_key = pc.api_key.clone()?;
+                let api_base = pc
+                    .api_base
+                    .clone()
+                    .or_else(|| spec.default_base_url.map(|s| s.to_string()))
+                    .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
+                Some(Self::new(
+                    TranscriptionCandidate {
+                        provider_name: spec.name.to_string(),
+                        api_key,
+                        api_base,
+                    },
+                    &config.transcription.model,
+                ))
+            })
+            .collect()
+    }
+
+    /// Whisper models return segments with log-probabilities in
+    /// `verbose_json`; newer models (`gpt-4o-transcribe`) only do `json`.
+    fn verbose(&self) -> bool {
+        self.model.contains("whisper")
+    }
+}
+
+#[async_trait]
+impl TranscriptionBackend for OpenAiWhisperBackend {
+    fn name(&self) -> &str {
+        &self.candidate.provider_name
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        content_type: &str,
+        language: Option<&str>,
+    ) -> Result<Transcript> {
+        let file_part = multipart::Part::bytes(audio)
+            .file_name(file_name(content_type))
+            .mime_str(content_type)
+            .map_err(|e| ZeptoError::Provider(e.to_string()))?;
+
+        let mut form = multipart::Form::new()
+            .part("file", file_part)
+            .text("model", self.model.clone());
+        if self.verbose() {
+            form = form.text("response_format", "verbose_json");
+        }
+        if let Some(language) = language {
+            form = form.text("language", language.to_string());
+        }
+
+        let url = format!("{}/audio/transcriptions", self.candidate.api_base);
+        let resp = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.candidate.api_key)
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Provider(e.to_string()))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(ZeptoError::Provider(format!(
+                "{} transcription failed ({}): {}",
+                self.candidate.provider_name, status, body
+            )));
+        }
+
+        let body = resp
+            .text()
+            .await
+            .map_err(|e| ZeptoError::Provider(e.to_string()))?;
+        Ok(parse_response(&body))
+    }
+}
+
+/// Upload file name; the API infers the audio format from its extension.
+fn file_name(content_type: &str) -> &'static str {
+    match content_type {
+        "audio/mpeg" | "audio/mp3" => "voice.mp3",
+        "audio/mp4" | "audio/m4a" | "audio/x-m4a" => "voice.m4a",
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "voice.wav",
+        "audio/webm" => "voice.webm",
+        _ => "voice.ogg",
+    }
+}
+
+/// Parse a transcription response. Some providers return JSON
+/// (`{"text": ...}`, with `language` and `segments` in `verbose_json`),
+/// others plain text.
+fn parse_response(body: &str) -> Transcript {
+    let trimmed = body.trim();
+    let Ok(json) = serde_json::from_str::<Value>(trimmed) else {
+        return Transcript::new(trimmed);
+    };
+    let Some(text) = json.get("text").and_then(|v| v.as_str()) else {
+        return Transcript::new(trimmed);
+    };
+    // Confidence: mean per-segment probability, exp(avg_logprob).
+    let probabilities: Vec<f64> = json
+        .get("segments")
+        .and_then(|v| v.as_array())
+        .map(|segments| {
+            segments
+                .iter()
+                .filter_map(|s| s.get("avg_logprob").and_then(|v| v.as_f64()))
+                .map(f64::exp)
+                .collect()
+        })
+        .unwrap_or_default();
+    let confidence = (!probabilities.is_empty()).then(|| {
+        (probabilities.iter().sum::<f64>() / probabilities.len() as f64).clamp(0.0, 1.0) as f32
+    });
+    Transcript {
+        text: text.trim().to_string(),
+        language: json
+            .get("language")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        confidence,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_and_json() {
+        assert_eq!(parse_response(" hello \n"), Transcript::new("hello"));
+        assert_eq!(
+            parse_response(r#"{"text": " hello "}"#),
+            Transcript::new("hello")
+        );
+    }
+
+    #[test]
+    fn test_parse_verbose_json() {
+        let body = r#"{
+            "text": "Guten Morgen",
+            "language": "german",
+            "segments": [{"avg_logprob": -0.1}, {"avg_logprob": -0.3}]
+        }"#;
+        let transcript = parse_response(body);
+        assert_eq!(transcript.text, "Guten Morgen");
+        assert_eq!(transcript.language.as_deref(), Some("german"));
+        let confidence = transcript.confidence.unwrap();
+        assert!((confidence - 0.8228).abs() < 0.001, "{}", confidence);
+    }
+
+    #[test]
+    fn test_file_name_follows_content_type() {
+        assert_eq!(file_name("audio/wav"), "voice.wav");
+        assert_eq!(file_name("audio/mpeg"), "voice.mp3");
+        assert_eq!(file_name("audio/ogg"), "voice.ogg");
+    }
+
+    #[test]
+    fn test_verbose_only_for_whisper_models() {
+        let candidate = TranscriptionCandidate {
+            provider_name: "openai".into(),
+            api_key: "sk".into(),
+            api_base: "https://api.openai.com/v1".into(),
+        };
+        assert!(OpenAiWhisperBackend::new(candidate.clone(), "whisper-1").verbose());
+        assert!(!OpenAiWhisperBackend::new(candidate, "gpt-4o-transcribe").verbose());
+    }
+}
@@ -0,0 +1,215 @@
+//! Local transcription with the whisper.cpp CLI (`whisper-cli`).
+//!
+//! Audio that isn't WAV is converted to 16 kHz mono WAV with `ffmpeg`
+//! first; whisper.cpp reads nothing else. Both binaries must be on `PATH`
+//! (or configured by path). Audio never leaves the machine.
+
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tokio::process::Command;
+
+use super::{Transcript, TranscriptionBackend};
+use crate::error::{Result, ZeptoError};
+
+/// Upper bound for conversion plus transcription of one clip.
+const WHISPER_CPP_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Transcription with a local whisper.cpp build.
+#[derive(Debug, Clone)]
+pub struct WhisperCppBackend {
+    binary: String,
+    model: String,
+    ffmpeg: String,
+}
+
+impl WhisperCppBackend {
+    /// `binary` is the whisper.cpp CLI, `model` the path to a ggml model.
+    pub fn new(binary: &str, model: &str) -> Self {
+        Self {
+            binary: binary.to_string(),
+            model: model.to_string(),
+            ffmpeg: "ffmpeg".to_string(),
+        }
+    }
+
+    async fn run(command: &mut Command, what: &str) -> Result<()> {
+        let output = command
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| ZeptoError::Provider(format!("Failed to run {}: {}", what, e)))?;
+        if !output.status.success() {
+            return Err(ZeptoError::Provider(format!(
+                "{} failed ({}): {}",
+                what,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_in(
+        &self,
+        dir: &Path,
+        audio: Vec<u8>,
+        content_type: &str,
+        language: Option<&str>,
+    ) -> Result<Transcript> {
+        let input = dir.join(format!("input.{}", extension_for(content_type)));
+        tokio::fs::write(&input, audio)
+            .await
+            .map_err(|e| ZeptoError::Provider(format!("Failed to write audio: {}", e)))?;
+
+        let wav = if is_wav(content_type) {
+            input
+        } else {
+            let wav = dir.join("audio.wav");
+            Self::run(
+                Command::new(&self.ffmpeg)
+                    .args(["-y", "-loglevel", "error", "-i"])
+                    .arg(&input)
+                    .args(["-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"])
+                    .arg(&wav),
+                "ffmpeg",
+            )
+            .await?;
+            wav
+        };
+
+        let out = dir.join("out");
+        Self::run(
+            Command::new(&self.binary)
+                .arg("-m")
+                .arg(&self.model)
+                .arg("-f")
+                .arg(&wav)
+                .args(["-l", language.unwrap_or("auto"), "-ojf", "-np", "-of"])
+                .arg(&out),
+            "whisper.cpp",
+        )
+        .await?;
+
+        let json = tokio::fs::read_to_string(out.with_extension("json"))
+            .await
+            .map_err(|e| ZeptoError::Provider(format!("whisper.cpp wrote no output: {}", e)))?;
+        let json: Value = serde_json::from_str(&json)
+            .map_err(|e| ZeptoError::Provider(format!("Invalid whisper.cpp output: {}", e)))?;
+        Ok(parse_output(&json))
+    }
+}
+
+#[async_trait]
+impl TranscriptionBackend for WhisperCppBackend {
+    fn name(&self) -> &str {
+        "whisper_cpp"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        content_type: &str,
+        language: Option<&str>,
+    ) -> Result<Transcript> {
+        let dir = tempfile::tempdir()
+            .map_err(|e| ZeptoError::Provider(format!("Failed to create temp dir: {}", e)))?;
+        tokio::time::timeout(
+            WHISPER_CPP_TIMEOUT,
+            self.transcribe_in(dir.path(), audio, content_type, language),
+        )
+        .await
+        .map_err(|_| ZeptoError::Provider("whisper.cpp timed out".to_string()))?
+    }
+}
+
+fn is_wav(content_type: &str) -> bool {
+    matches!(content_type, "audio/wav" | "audio/x-wav" | "audio/wave")
+}
+
+fn extension_for(content_type: &str) -> &'static str {
+    match content_type {
+        "audio/ogg" | "audio/opus" => "ogg",
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/mp4" | "audio/m4a" | "audio/x-m4a" => "m4a",
+        "audio/webm" => "webm",
+        ct if is_wav(ct) => "wav",
+        // ffmpeg probes the content anyway.
+        _ => "bin",
+    }
+}
+
+/// Parse `-ojf` output: segment texts, the detected language and the mean
+/// probability of the text tokens (special tokens look like `[_BEG_]`).
+fn parse_output(json: &Value) -> Transcript {
+    let segments = json["transcription"].as_array();
+    let text = segments
+        .into_iter()
+        .flatten()
+        .filter_map(|segment| segment["text"].as_str())
+        .collect::<String>();
+    let probabilities: Vec<f64> = segments
+        .into_iter()
+        .flatten()
+        .filter_map(|segment| segment["tokens"].as_array())
+        .flatten()
+        .filter(|token| !token["text"].as_str().unwrap_or("").starts_with("[_"))
+        .filter_map(|token| token["p"].as_f64())
+        .collect();
+    let confidence = (!probabilities.is_empty())
+        .then(|| (probabilities.iter().sum::<f64>() / probabilities.len() as f64) as f32);
+    Transcript {
+        text: text.trim().to_string(),
+        language: json["result"]["language"].as_str().map(str::to_string),
+        confidence,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_output() {
+        let output = json!({
+            "result": {"language": "en"},
+            "transcription": [
+                {"text": " Hello", "tokens": [
+                    {"text": "[_BEG_]", "p": 0.1},
+                    {"text": " Hello", "p": 0.9}
+                ]},
+                {"text": " world.", "tokens": [{"text": " world.", "p": 0.7}]}
+            ]
+        });
+        let transcript = parse_output(&output);
+        assert_eq!(transcript.text, "Hello world.");
+        assert_eq!(transcript.language.as_deref(), Some("en"));
+        assert!((transcript.confidence.unwrap() - 0.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_extension_for() {
+        assert_eq!(extension_for("audio/ogg"), "ogg");
+        assert_eq!(extension_for("audio/x-wav"), "wav");
+        assert_eq!(extension_for("application/octet-stream"), "bin");
+        assert!(is_wav("audio/wav"));
+        assert!(!is_wav("audio/ogg"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_binary_is_an_error() {
+        let backend = WhisperCppBackend::new("/nonexistent/whisper-cli", "/models/ggml.bin");
+        let err = backend
+            .transcribe(vec![0; 16], "audio/wav", None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("whisper.cpp"), "{}", err);
+    }
+}
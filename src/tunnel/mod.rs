@@ -5,6 +5,10 @@
 //! - **ngrok** — free or paid tunnels with optional custom domains
 //! - **Tailscale** (`tailscale funnel/serve`) — Tailscale Funnel (public) or Serve (tailnet-only)
 //!
+//! The gateway runs its tunnel under a [`TunnelSupervisor`], which health-checks
+//! the public URL, restarts the tunnel when it stops answering, and re-registers
+//! channel webhooks ([`WebhookRegistrar`]) when the URL changes.
+//!
 //! # Usage
 //!
 //! ```rust,no_run
@@ -23,13 +27,19 @@
 
 pub mod cloudflare;
 pub mod ngrok;
+pub mod supervisor;
 pub mod tailscale;
 pub mod types;
+pub mod webhooks;
 
 pub use cloudflare::CloudflareTunnel;
 pub use ngrok::NgrokTunnel;
+pub use supervisor::{read_tunnel_state, TunnelOptions, TunnelState, TunnelSupervisor};
 pub use tailscale::TailscaleTunnel;
 pub use types::TunnelProvider;
+pub use webhooks::{
+    configured_registrars, TelegramRegistrar, WebhookRegistrar, WhatsAppCloudRegistrar,
+};
 
 use crate::config::TunnelConfig;
 use crate::error::{Result, ZeptoError};
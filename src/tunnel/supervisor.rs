@@ -0,0 +1,323 @@
+//! Tunnel lifecycle supervision for the gateway.
+//!
+//! [`TunnelSupervisor`] owns a running [`TunnelProvider`] for as long as the
+//! gateway runs: it health-checks the public URL on an interval, restarts
+//! the tunnel after repeated failures, re-registers channel webhooks when
+//! the URL changes, and stops the tunnel on shutdown. The current state is
+//! written to `~/.zeptoclaw/tunnel_state.json` so `zeptoclaw status` can
+//! show the URL from another process.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::config::Config;
+use crate::error::Result;
+
+use super::types::TunnelProvider;
+use super::webhooks::WebhookRegistrar;
+
+/// Timeout for one public URL probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How the supervisor runs a tunnel.
+#[derive(Debug, Clone)]
+pub struct TunnelOptions {
+    /// Local port the tunnel exposes.
+    pub local_port: u16,
+    /// Time between health checks; zero disables checking.
+    pub check_interval: Duration,
+    /// Consecutive failed checks before a restart.
+    pub max_failures: u32,
+}
+
+/// Tunnel state shared with `zeptoclaw status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TunnelState {
+    pub provider: String,
+    pub url: String,
+    pub local_port: u16,
+    pub healthy: bool,
+    pub started_at: String,
+    pub last_checked_at: Option<String>,
+    pub restarts: u32,
+}
+
+/// Path of the tunnel state file.
+pub fn tunnel_state_path() -> PathBuf {
+    Config::state_dir().join("tunnel_state.json")
+}
+
+/// Read the running gateway's tunnel state, if any.
+pub fn read_tunnel_state() -> Option<TunnelState> {
+    let content = std::fs::read_to_string(tunnel_state_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write_tunnel_state(state: &TunnelState) {
+    let path = tunnel_state_path();
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| {
+            let json = serde_json::to_string_pretty(state).map_err(std::io::Error::other)?;
+            std::fs::write(&path, json)
+        });
+    if let Err(e) = result {
+        warn!(error = %e, "Failed to write tunnel state");
+    }
+}
+
+fn remove_tunnel_state() {
+    let _ = std::fs::remove_file(tunnel_state_path());
+}
+
+/// Whether a response status from the public URL means the tunnel reaches
+/// the gateway. Any answer from the origin counts, including 404; gateway
+/// errors from the tunnel edge (502/503/504, Cloudflare's 530) do not.
+pub fn is_reachable_status(status: u16) -> bool {
+    !matches!(status, 502..=504 | 530)
+}
+
+/// Keeps a tunnel up for the gateway's lifetime.
+pub struct TunnelSupervisor {
+    url: watch::Receiver<String>,
+    shutdown: watch::Sender<bool>,
+    handle: JoinHandle<()>,
+}
+
+impl TunnelSupervisor {
+    /// Start `provider`, register webhooks for its URL, and begin supervising.
+    ///
+    /// Fails if the tunnel cannot be started the first time.
+    pub async fn start(
+        mut provider: Box<dyn TunnelProvider>,
+        options: TunnelOptions,
+        registrars: Vec<Box<dyn WebhookRegistrar>>,
+    ) -> Result<Self> {
+        let url = provider.start(options.local_port).await?;
+        let state = TunnelState {
+            provider: provider.name().to_string(),
+            url: url.clone(),
+            local_port: options.local_port,
+            healthy: true,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            last_checked_at: None,
+            restarts: 0,
+        };
+        write_tunnel_state(&state);
+        register_webhooks(&registrars, &url).await;
+
+        let (url_tx, url_rx) = watch::channel(url);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handle = tokio::spawn(supervise(
+            provider,
+            options,
+            registrars,
+            state,
+            url_tx,
+            shutdown_rx,
+        ));
+        Ok(Self {
+            url: url_rx,
+            shutdown: shutdown_tx,
+            handle,
+        })
+    }
+
+    /// Current public URL.
+    pub fn url(&self) -> String {
+        self.url.borrow().clone()
+    }
+
+    /// Watch the public URL for changes after restarts.
+    pub fn subscribe(&self) -> watch::Receiver<String> {
+        self.url.clone()
+    }
+
+    /// Stop the tunnel and wait (briefly) for the provider to exit.
+    pub async fn stop(self) {
+        let _ = self.shutdown.send(true);
+        let _ = tokio::time::timeout(Duration::from_secs(10), self.handle).await;
+    }
+}
+
+async fn register_webhooks(registrars: &[Box<dyn WebhookRegistrar>], url: &str) {
+    for registrar in registrars {
+        match registrar.register(url).await {
+            Ok(()) => info!(webhook = registrar.name(), url = %url, "Registered webhook"),
+            Err(e) => warn!(webhook = registrar.name(), error = %e, "Webhook registration failed"),
+        }
+    }
+}
+
+async fn probe(client: &reqwest::Client, url: &str) -> bool {
+    match client.get(url).send().await {
+        Ok(resp) => is_reachable_status(resp.status().as_u16()),
+        Err(_) => false,
+    }
+}
+
+async fn supervise(
+    mut provider: Box<dyn TunnelProvider>,
+    options: TunnelOptions,
+    registrars: Vec<Box<dyn WebhookRegistrar>>,
+    mut state: TunnelState,
+    url_tx: watch::Sender<String>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    if options.check_interval.is_zero() {
+        let _ = shutdown.changed().await;
+    } else {
+        let client = reqwest::Client::builder()
+            .timeout(PROBE_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        let mut interval = tokio::time::interval(options.check_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        interval.tick().await;
+        let mut failures = 0u32;
+
+        loop {
+            tokio::select! {
+                _ = shutdown.changed() => break,
+                _ = interval.tick() => {}
+            }
+
+            let alive = provider.health_check().await.unwrap_or(false);
+            let healthy = alive && probe(&client, &state.url).await;
+            state.healthy = healthy;
+            state.last_checked_at = Some(chrono::Utc::now().to_rfc3339());
+            if healthy {
+                failures = 0;
+            } else {
+                failures += 1;
+                warn!(
+                    url = %state.url,
+                    process_alive = alive,
+                    failures = failures,
+                    "Tunnel health check failed"
+                );
+            }
+
+            if failures >= options.max_failures.max(1) {
+                warn!(provider = provider.name(), "Restarting unhealthy tunnel");
+                let _ = provider.stop().await;
+                match provider.start(options.local_port).await {
+                    Ok(url) => {
+                        failures = 0;
+                        state.healthy = true;
+                        state.restarts += 1;
+                        if url != state.url {
+                            info!(old = %state.url, new = %url, "Tunnel URL changed");
+                            state.url = url.clone();
+                            let _ = url_tx.send(url.clone());
+                            register_webhooks(&registrars, &url).await;
+                        }
+                    }
+                    Err(e) => error!(error = %e, "Tunnel restart failed; retrying next check"),
+                }
+            }
+            write_tunnel_state(&state);
+        }
+    }
+
+    info!(provider = provider.name(), "Stopping tunnel");
+    if let Err(e) = provider.stop().await {
+        warn!(error = %e, "Failed to stop tunnel cleanly");
+    }
+    remove_tunnel_state();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Provider whose process is "dead" and which hands out a new URL per start.
+    struct FlakyTunnel {
+        starts: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl TunnelProvider for FlakyTunnel {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn start(&mut self, _local_port: u16) -> Result<String> {
+            let n = self.starts.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("https://tunnel-{}.invalid", n))
+        }
+
+        async fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> Result<bool> {
+            Ok(false)
+        }
+    }
+
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl WebhookRegistrar for Recorder {
+        fn name(&self) -> &str {
+            "recorder"
+        }
+
+        async fn register(&self, public_url: &str) -> Result<()> {
+            self.0.lock().unwrap().push(public_url.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_reachable_status() {
+        assert!(is_reachable_status(200));
+        assert!(is_reachable_status(404));
+        assert!(is_reachable_status(401));
+        assert!(!is_reachable_status(502));
+        assert!(!is_reachable_status(504));
+        assert!(!is_reachable_status(530));
+    }
+
+    #[tokio::test]
+    async fn test_restart_reregisters_changed_url() {
+        let starts = Arc::new(AtomicU32::new(0));
+        let registered = Arc::new(Mutex::new(Vec::new()));
+        let supervisor = TunnelSupervisor::start(
+            Box::new(FlakyTunnel {
+                starts: Arc::clone(&starts),
+            }),
+            TunnelOptions {
+                local_port: 8080,
+                check_interval: Duration::from_millis(10),
+                max_failures: 1,
+            },
+            vec![Box::new(Recorder(Arc::clone(&registered)))],
+        )
+        .await
+        .unwrap();
+        assert_eq!(supervisor.url(), "https://tunnel-0.invalid");
+
+        let mut url = supervisor.subscribe();
+        tokio::time::timeout(Duration::from_secs(5), url.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(*url.borrow(), "https://tunnel-1.invalid");
+        supervisor.stop().await;
+
+        let registered = registered.lock().unwrap();
+        assert_eq!(registered[0], "https://tunnel-0.invalid");
+        assert_eq!(registered[1], "https://tunnel-1.invalid");
+    }
+}
@@ -0,0 +1,230 @@
+//! Channel webhook registration against a tunnel's public URL.
+//!
+//! Quick tunnels hand out a new hostname on every start, so webhook-based
+//! channels must be told where to deliver callbacks each time the
+//! [`TunnelSupervisor`](super::supervisor::TunnelSupervisor) obtains a URL.
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use crate::channels::telegram_webhook;
+use crate::channels::whatsapp_cloud::WHATSAPP_API_BASE;
+use crate::config::{Config, TelegramConfig, WhatsAppCloudConfig};
+use crate::error::{Result, ZeptoError};
+
+/// Registers a channel's webhook callback with its upstream service.
+#[async_trait]
+pub trait WebhookRegistrar: Send + Sync {
+    /// Channel name, for logs.
+    fn name(&self) -> &str;
+
+    /// Point the channel's webhook at `public_url`.
+    async fn register(&self, public_url: &str) -> Result<()>;
+}
+
+/// Join a tunnel base URL and a webhook path.
+pub fn callback_url(public_url: &str, path: &str) -> String {
+    format!(
+        "{}/{}",
+        public_url.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    )
+}
+
+/// Points the Telegram bot's webhook at the tunnel URL.
+pub struct TelegramRegistrar {
+    token: String,
+    path: String,
+    secret: String,
+    client: reqwest::Client,
+}
+
+impl TelegramRegistrar {
+    /// Build a registrar, or `None` when Telegram is not in webhook mode.
+    pub fn from_config(config: &TelegramConfig) -> Option<Self> {
+        let webhook = config.webhook.as_ref()?;
+        Some(Self {
+            token: config.token.clone(),
+            path: webhook.path.clone(),
+            secret: telegram_webhook::webhook_secret(config),
+            client: reqwest::Client::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl WebhookRegistrar for TelegramRegistrar {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    async fn register(&self, public_url: &str) -> Result<()> {
+        let url = callback_url(public_url, &self.path);
+        telegram_webhook::set_webhook(&self.client, &self.token, &url, &self.secret).await
+    }
+}
+
+/// Re-subscribes the Meta app's WhatsApp webhook to the tunnel URL.
+pub struct WhatsAppCloudRegistrar {
+    app_id: String,
+    app_secret: String,
+    verify_token: String,
+    path: String,
+    client: reqwest::Client,
+}
+
+impl WhatsAppCloudRegistrar {
+    /// Build a registrar, or `None` when the app ID or secret is missing.
+    pub fn from_config(config: &WhatsAppCloudConfig) -> Option<Self> {
+        Some(Self {
+            app_id: config.app_id.clone().filter(|s| !s.is_empty())?,
+            app_secret: config.app_secret.clone().filter(|s| !s.is_empty())?,
+            verify_token: config.webhook_verify_token.clone(),
+            path: config.path.clone(),
+            client: reqwest::Client::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl WebhookRegistrar for WhatsAppCloudRegistrar {
+    fn name(&self) -> &str {
+        "whatsapp_cloud"
+    }
+
+    async fn register(&self, public_url: &str) -> Result<()> {
+        let url = format!("{}/{}/subscriptions", WHATSAPP_API_BASE, self.app_id);
+        let app_token = format!("{}|{}", self.app_id, self.app_secret);
+        let callback = callback_url(public_url, &self.path);
+        let response = self
+            .client
+            .post(&url)
+            .form(&[
+                ("object", "whatsapp_business_account"),
+                ("callback_url", callback.as_str()),
+                ("verify_token", self.verify_token.as_str()),
+                ("fields", "messages"),
+                ("access_token", app_token.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| ZeptoError::Channel(format!("WhatsApp webhook registration: {}", e)))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ZeptoError::Channel(format!(
+                "WhatsApp webhook registration failed ({}): {}",
+                status, body
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Registrars for enabled webhook channels reachable through a tunnel that
+/// exposes `local_port`.
+pub fn configured_registrars(config: &Config, local_port: u16) -> Vec<Box<dyn WebhookRegistrar>> {
+    let mut registrars: Vec<Box<dyn WebhookRegistrar>> = Vec::new();
+
+    // Telegram posts to the gateway HTTP server. A fixed `public_url` means
+    // the channel registers itself.
+    if let Some(tg) = config
+        .channels
+        .telegram
+        .as_ref()
+        .filter(|c| c.enabled && !c.token.is_empty())
+        .filter(|c| c.webhook.as_ref().is_some_and(|w| w.public_url.is_none()))
+    {
+        if config.gateway.port != local_port {
+            warn!(
+                tunnel_port = local_port,
+                gateway_port = config.gateway.port,
+                "Telegram webhook is served by the gateway, which the tunnel does not expose; not registering"
+            );
+        } else if let Some(registrar) = TelegramRegistrar::from_config(tg) {
+            registrars.push(Box::new(registrar));
+        }
+    }
+
+    if let Some(wa) = config
+        .channels
+        .whatsapp_cloud
+        .as_ref()
+        .filter(|c| c.enabled)
+    {
+        if wa.port != local_port {
+            warn!(
+                tunnel_port = local_port,
+                webhook_port = wa.port,
+                "WhatsApp Cloud webhook listens on a different port than the tunnel; not registering"
+            );
+        } else if let Some(registrar) = WhatsAppCloudRegistrar::from_config(wa) {
+            registrars.push(Box::new(registrar));
+        }
+    }
+
+    registrars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_callback_url_joins_slashes() {
+        assert_eq!(
+            callback_url("https://a.trycloudflare.com/", "/whatsapp"),
+            "https://a.trycloudflare.com/whatsapp"
+        );
+        assert_eq!(
+            callback_url("https://a.trycloudflare.com", "whatsapp"),
+            "https://a.trycloudflare.com/whatsapp"
+        );
+    }
+
+    #[test]
+    fn test_whatsapp_registrar_requires_app_credentials() {
+        let mut wa = WhatsAppCloudConfig {
+            enabled: true,
+            app_secret: Some("secret".into()),
+            ..Default::default()
+        };
+        assert!(WhatsAppCloudRegistrar::from_config(&wa).is_none());
+        wa.app_id = Some("123".into());
+        assert!(WhatsAppCloudRegistrar::from_config(&wa).is_some());
+    }
+
+    #[test]
+    fn test_telegram_registrar_needs_webhook_mode_without_public_url() {
+        let mut config = Config::default();
+        config.channels.telegram = Some(TelegramConfig {
+            enabled: true,
+            token: "123:abc".into(),
+            webhook: Some(Default::default()),
+            ..Default::default()
+        });
+        let port = config.gateway.port;
+        assert_eq!(configured_registrars(&config, port).len(), 1);
+        assert!(configured_registrars(&config, port + 1).is_empty());
+
+        let telegram = config.channels.telegram.as_mut().unwrap();
+        telegram.webhook.as_mut().unwrap().public_url = Some("https://bot.example.com".into());
+        assert!(configured_registrars(&config, port).is_empty());
+        config.channels.telegram.as_mut().unwrap().webhook = None;
+        assert!(configured_registrars(&config, port).is_empty());
+    }
+
+    #[test]
+    fn test_configured_registrars_skips_other_ports() {
+        let mut config = Config::default();
+        config.channels.whatsapp_cloud = Some(WhatsAppCloudConfig {
+            enabled: true,
+            app_id: Some("123".into()),
+            app_secret: Some("secret".into()),
+            ..Default::default()
+        });
+        let port = config.channels.whatsapp_cloud.as_ref().unwrap().port;
+        assert_eq!(configured_registrars(&config, port).len(), 1);
+        assert!(configured_registrars(&config, port + 1).is_empty());
+    }
+}
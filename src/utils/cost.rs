@@ -8,10 +8,11 @@
 use std::collections::HashMap;
 use std::sync::Mutex;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Pricing for a single LLM model, expressed in USD per million tokens.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModelPricing {
     /// Cost per 1 000 000 input (prompt) tokens in USD.
     pub input_cost_per_million: f64,
@@ -231,7 +232,7 @@ impl Default for CostTracker {
 
 /// Configuration for cost tracking, suitable for embedding in the main
 /// ZeptoClaw config file.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(default)]
 pub struct CostConfig {
     /// Whether cost tracking is enabled.
@@ -0,0 +1,481 @@
+//! Shared HTTP client factory with the network egress policy.
+//!
+//! HTTP-based tools build their clients here instead of calling
+//! `reqwest::Client::new()`, so `tools.egress` applies to all of them:
+//!
+//! - host allowlist/denylist, checked before every request, at DNS
+//!   resolution and on every redirect hop
+//! - SSRF guard: private or local IP literals and hosts resolving to such
+//!   addresses are refused (except `private_hosts`)
+//! - global timeout, redirect limit and response size limit
+//!
+//! [`Client`] wraps `reqwest::Client` so that [`check_url`] runs on every
+//! request URL before it is sent; IP-literal URLs never reach the DNS
+//! resolver, so the resolver alone cannot guard them.
+//!
+//! The policy is process-wide; [`set_policy`] installs it at kernel boot.
+//! Clients built earlier pick up host rules at request time.
+//!
+//! # Example
+//!
+//! ```
+//! use zeptoclaw::config::EgressConfig;
+//! use zeptoclaw::utils::http;
+//!
+//! http::set_policy(EgressConfig {
+//!     denied_domains: vec!["*.tracker.example".to_string()],
+//!     ..Default::default()
+//! });
+//! let url = reqwest::Url::parse("https://ads.tracker.example/pixel").unwrap();
+//! assert!(http::check_url(&url).is_err());
+//! # http::set_policy(EgressConfig::default());
+//! ```
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Body, IntoUrl, Method, Response, Url};
+use serde::Serialize;
+
+use crate::config::EgressConfig;
+use crate::error::{Result, ZeptoError};
+use crate::tools::web::is_private_or_local_ip;
+
+/// Connect timeout for every client.
+const CONNECT_TIMEOUT_SECS: u64 = 10;
+
+static POLICY: Lazy<RwLock<Arc<EgressConfig>>> =
+    Lazy::new(|| RwLock::new(Arc::new(EgressConfig::default())));
+
+/// Install the egress policy for all clients.
+pub fn set_policy(config: EgressConfig) {
+    if let Ok(mut policy) = POLICY.write() {
+        *policy = Arc::new(config);
+    }
+}
+
+/// The current egress policy.
+pub fn policy() -> Arc<EgressConfig> {
+    POLICY
+        .read()
+        .map(|policy| Arc::clone(&policy))
+        .unwrap_or_default()
+}
+
+/// Maximum response body size under the current policy.
+pub fn max_response_bytes() -> usize {
+    policy().max_response_bytes
+}
+
+/// A client builder with the egress policy applied. Callers may tighten the
+/// timeout or replace the redirect policy (see [`check_url`] for theirs).
+pub fn client_builder() -> ClientBuilder {
+    let policy = policy();
+    ClientBuilder {
+        inner: reqwest::Client::builder()
+            .timeout(Duration::from_secs(policy.timeout_secs))
+            .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+            .redirect(redirect_policy(policy.max_redirects))
+            .dns_resolver(Arc::new(EgressResolver)),
+    }
+}
+
+/// A client with the egress policy applied.
+pub fn client() -> Client {
+    client_builder().build().unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "Failed to build HTTP client; using defaults");
+        Client {
+            inner: reqwest::Client::new(),
+        }
+    })
+}
+
+/// A bare `reqwest::Client` with the DNS and redirect checks, for
+/// libraries that take one. Requests skip [`check_url`], so use it only
+/// for fixed, hostname-based API endpoints.
+pub fn reqwest_client() -> reqwest::Client {
+    client().inner
+}
+
+/// Builder for [`Client`], from [`client_builder`].
+#[derive(Debug)]
+pub struct ClientBuilder {
+    inner: reqwest::ClientBuilder,
+}
+
+impl ClientBuilder {
+    /// Set the total request timeout.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.timeout(timeout),
+        }
+    }
+
+    /// Replace the redirect policy. Custom policies should run
+    /// [`check_url`] on each hop.
+    pub fn redirect(self, policy: reqwest::redirect::Policy) -> Self {
+        Self {
+            inner: self.inner.redirect(policy),
+        }
+    }
+
+    /// Pin `domain` to `addr`, bypassing DNS for it.
+    pub fn resolve(self, domain: &str, addr: SocketAddr) -> Self {
+        Self {
+            inner: self.inner.resolve(domain, addr),
+        }
+    }
+
+    /// Build the client.
+    pub fn build(self) -> reqwest::Result<Client> {
+        Ok(Client {
+            inner: self.inner.build()?,
+        })
+    }
+}
+
+/// An HTTP client that checks every request URL against the egress policy
+/// before sending it.
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: reqwest::Client,
+}
+
+impl Client {
+    /// Same as [`client`].
+    pub fn new() -> Self {
+        client()
+    }
+
+    /// Start a GET request.
+    pub fn get<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::GET, url)
+    }
+
+    /// Start a POST request.
+    pub fn post<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::POST, url)
+    }
+
+    /// Start a PUT request.
+    pub fn put<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::PUT, url)
+    }
+
+    /// Start a PATCH request.
+    pub fn patch<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::PATCH, url)
+    }
+
+    /// Start a DELETE request.
+    pub fn delete<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::DELETE, url)
+    }
+
+    /// Start a request with any method.
+    pub fn request<U: IntoUrl>(&self, method: Method, url: U) -> RequestBuilder {
+        RequestBuilder {
+            inner: self.inner.request(method, url),
+            invalid_header: None,
+        }
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A request being built on a [`Client`]. Mirrors `reqwest::RequestBuilder`;
+/// [`send`](Self::send) refuses URLs that fail [`check_url`].
+#[derive(Debug)]
+pub struct RequestBuilder {
+    inner: reqwest::RequestBuilder,
+    invalid_header: Option<String>,
+}
+
+impl RequestBuilder {
+    fn map(self, f: impl FnOnce(reqwest::RequestBuilder) -> reqwest::RequestBuilder) -> Self {
+        Self {
+            inner: f(self.inner),
+            ..self
+        }
+    }
+
+    /// Add a header. An invalid name or value fails the request at
+    /// [`send`](Self::send).
+    pub fn header<K, V>(self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: std::fmt::Display,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: std::fmt::Display,
+    {
+        let header = HeaderName::try_from(key)
+            .map_err(|e| e.to_string())
+            .and_then(|name| {
+                Ok((
+                    name,
+                    HeaderValue::try_from(value).map_err(|e| e.to_string())?,
+                ))
+            });
+        match header {
+            Ok((name, value)) => self.map(|b| with_header(b, name, value)),
+            Err(e) => Self {
+                invalid_header: self.invalid_header.or(Some(e)),
+                ..self
+            },
+        }
+    }
+
+    /// Add several headers.
+    pub fn headers(self, headers: HeaderMap) -> Self {
+        self.map(|b| b.headers(headers))
+    }
+
+    /// Set a bearer token.
+    pub fn bearer_auth<T: std::fmt::Display>(self, token: T) -> Self {
+        self.map(|b| b.bearer_auth(token))
+    }
+
+    /// Set basic auth credentials.
+    pub fn basic_auth<U, P>(self, username: U, password: Option<P>) -> Self
+    where
+        U: std::fmt::Display,
+        P: std::fmt::Display,
+    {
+        self.map(|b| b.basic_auth(username, password))
+    }
+
+    /// Append query parameters.
+    pub fn query<T: Serialize + ?Sized>(self, query: &T) -> Self {
+        self.map(|b| b.query(query))
+    }
+
+    /// Send a form body.
+    pub fn form<T: Serialize + ?Sized>(self, form: &T) -> Self {
+        self.map(|b| b.form(form))
+    }
+
+    /// Send a JSON body.
+    pub fn json<T: Serialize + ?Sized>(self, json: &T) -> Self {
+        self.map(|b| b.json(json))
+    }
+
+    /// Send a raw body.
+    pub fn body<T: Into<Body>>(self, body: T) -> Self {
+        self.map(|b| b.body(body))
+    }
+
+    /// Send a multipart form.
+    pub fn multipart(self, form: reqwest::multipart::Form) -> Self {
+        self.map(|b| b.multipart(form))
+    }
+
+    /// Override the client timeout for this request.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.map(|b| b.timeout(timeout))
+    }
+
+    /// Check the URL against the egress policy, then send the request.
+    pub async fn send(self) -> Result<Response> {
+        if let Some(e) = self.invalid_header {
+            return Err(ZeptoError::Tool(format!("Invalid HTTP header: {}", e)));
+        }
+        let (client, request) = self.inner.build_split();
+        let request = request?;
+        check_url(request.url())?;
+        Ok(client.execute(request).await?)
+    }
+}
+
+/// Check `url` against the egress policy: http(s) only, host allowed and
+/// not denied, and not a private or local IP literal.
+pub fn check_url(url: &Url) -> Result<()> {
+    match url.scheme() {
+        "http" | "https" => {}
+        scheme => {
+            return Err(ZeptoError::SecurityViolation(format!(
+                "URL scheme '{}' is not allowed",
+                scheme
+            )))
+        }
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| ZeptoError::SecurityViolation("URL has no host".to_string()))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_ascii_lowercase();
+    let policy = policy();
+    check_host(&policy, &host)?;
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        check_addr(&policy, &host, ip)?;
+    }
+    Ok(())
+}
+
+fn check_host(policy: &EgressConfig, host: &str) -> Result<()> {
+    if policy.denied_domains.iter().any(|d| host_matches(d, host)) {
+        return Err(ZeptoError::SecurityViolation(format!(
+            "Host '{}' is in tools.egress.denied_domains",
+            host
+        )));
+    }
+    if !policy.allowed_domains.is_empty()
+        && !policy.allowed_domains.iter().any(|d| host_matches(d, host))
+    {
+        return Err(ZeptoError::SecurityViolation(format!(
+            "Host '{}' is not in tools.egress.allowed_domains",
+            host
+        )));
+    }
+    Ok(())
+}
+
+fn check_addr(policy: &EgressConfig, host: &str, ip: IpAddr) -> Result<()> {
+    if policy.block_private_networks
+        && is_private_or_local_ip(ip)
+        && !policy.private_hosts.iter().any(|h| host_matches(h, host))
+    {
+        return Err(ZeptoError::SecurityViolation(format!(
+            "Host '{}' resolves to private/local address {}",
+            host, ip
+        )));
+    }
+    Ok(())
+}
+
+/// Whether `host` matches `pattern`. `*.example.com` matches
+/// `example.com` and its subdomains; other patterns match exactly.
+pub fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    if let Some(suffix) = pattern.strip_prefix("*.") {
+        host == suffix || host.ends_with(&format!(".{suffix}"))
+    } else {
+        host == pattern
+    }
+}
+
+fn redirect_policy(max_redirects: usize) -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= max_redirects {
+            return attempt.error(format!("Too many redirects (max {})", max_redirects));
+        }
+        match check_url(attempt.url()) {
+            Ok(()) => attempt.follow(),
+            Err(err) => attempt.error(err),
+        }
+    })
+}
+
+// Outside `RequestBuilder::header` so its `TryFrom` bounds don't steer
+// inference of reqwest's own `header` bounds.
+fn with_header(
+    builder: reqwest::RequestBuilder,
+    name: HeaderName,
+    value: HeaderValue,
+) -> reqwest::RequestBuilder {
+    builder.header(name, value)
+}
+
+/// DNS resolver that applies the host rules and refuses private answers.
+struct EgressResolver;
+
+impl Resolve for EgressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_ascii_lowercase();
+        Box::pin(async move {
+            let policy = policy();
+            check_host(&policy, &host)?;
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            for addr in &addrs {
+                check_addr(&policy, &host, addr.ip())?;
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(raw: &str) -> Url {
+        Url::parse(raw).unwrap()
+    }
+
+    #[test]
+    fn test_check_host_allow_and_deny() {
+        let policy = EgressConfig {
+            allowed_domains: vec!["*.example.com".to_string(), "api.other.io".to_string()],
+            denied_domains: vec!["admin.example.com".to_string()],
+            ..Default::default()
+        };
+        assert!(check_host(&policy, "example.com").is_ok());
+        assert!(check_host(&policy, "docs.example.com").is_ok());
+        assert!(check_host(&policy, "api.other.io").is_ok());
+        assert!(check_host(&policy, "admin.example.com").is_err());
+        assert!(check_host(&policy, "www.other.io").is_err());
+        assert!(check_host(&policy, "notexample.com").is_err());
+        assert!(check_host(&EgressConfig::default(), "anything.net").is_ok());
+    }
+
+    #[test]
+    fn test_check_addr_private_networks() {
+        let policy = EgressConfig {
+            private_hosts: vec!["localhost".to_string(), "*.lan".to_string()],
+            ..Default::default()
+        };
+        let private: IpAddr = "192.168.1.10".parse().unwrap();
+        let public: IpAddr = "93.184.216.34".parse().unwrap();
+        assert!(check_addr(&policy, "evil.com", private).is_err());
+        assert!(check_addr(&policy, "caldav.lan", private).is_ok());
+        assert!(check_addr(&policy, "localhost", "127.0.0.1".parse().unwrap()).is_ok());
+        assert!(check_addr(&policy, "example.com", public).is_ok());
+
+        let open = EgressConfig {
+            block_private_networks: false,
+            ..Default::default()
+        };
+        assert!(check_addr(&open, "evil.com", private).is_ok());
+    }
+
+    #[test]
+    fn test_check_url_default_policy() {
+        assert!(check_url(&url("https://example.com/a")).is_ok());
+        assert!(check_url(&url("ftp://example.com/a")).is_err());
+        assert!(check_url(&url("http://169.254.169.254/latest")).is_err());
+        assert!(check_url(&url("http://[::1]:8080/")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_client_refuses_private_ip_literals() {
+        for raw in [
+            "http://127.0.0.1:9/",
+            "http://169.254.169.254/latest/meta-data",
+        ] {
+            let err = client().get(raw).send().await.unwrap_err();
+            assert!(
+                matches!(err, ZeptoError::SecurityViolation(_)),
+                "{}: {}",
+                raw,
+                err
+            );
+        }
+        let err = client()
+            .get("https://example.com")
+            .header("bad header", "x")
+            .send()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid HTTP header"), "{}", err);
+    }
+}
@@ -0,0 +1,417 @@
+//! Locale for replies, status strings and dates.
+//!
+//! A locale is a language tag such as `de`, `en-GB` or `zh-CN`, set in
+//! `agents.defaults.language` or per channel in `channels.prompts`. It is
+//! injected into the system prompt so the model answers in that language,
+//! selects the translation of the gateway's own replies and `zeptoclaw
+//! status`, and sets how tools like `reminder` and `cron` print dates.
+//!
+//! Without a configured locale everything stays English and dates stay ISO
+//! (`2026-03-01 09:00 +08:00`).
+
+use chrono::{DateTime, Local};
+
+/// Languages with translated strings and date formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+    Malay,
+    Chinese,
+    Japanese,
+}
+
+impl Language {
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "en" => Some(Self::English),
+            "de" => Some(Self::German),
+            "fr" => Some(Self::French),
+            "es" => Some(Self::Spanish),
+            "ms" => Some(Self::Malay),
+            "zh" => Some(Self::Chinese),
+            "ja" => Some(Self::Japanese),
+            _ => None,
+        }
+    }
+
+    /// English name, used in the system prompt.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "German",
+            Self::French => "French",
+            Self::Spanish => "Spanish",
+            Self::Malay => "Malay",
+            Self::Chinese => "Chinese",
+            Self::Japanese => "Japanese",
+        }
+    }
+}
+
+/// Language codes accepted in locale tags.
+pub const SUPPORTED_LANGUAGES: &str = "en, de, fr, es, ms, zh, ja";
+
+/// A parsed locale tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale {
+    tag: String,
+    language: Language,
+}
+
+impl Locale {
+    /// Parse a tag like `de`, `en-GB` or `zh_CN`. Returns `None` for
+    /// languages without translations.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let mut parts = tag.trim().split(['-', '_']);
+        let code = parts.next()?.to_ascii_lowercase();
+        let language = Language::from_code(&code)?;
+        let region = parts.next().map(str::to_ascii_uppercase);
+        if parts.next().is_some() || region.as_deref().is_some_and(str::is_empty) {
+            return None;
+        }
+        let tag = match region {
+            Some(region) => format!("{}-{}", code, region),
+            None => code,
+        };
+        Some(Self { tag, language })
+    }
+
+    /// Normalized tag (`en-GB`).
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// System prompt section asking for replies in this locale.
+    pub fn prompt_section(&self) -> String {
+        format!(
+            "## Language\n\nReply in {} ({}) unless the user writes in another language. \
+             Write dates, times and numbers the way {} readers expect.",
+            self.language.name(),
+            self.tag,
+            self.language.name()
+        )
+    }
+
+    /// Translation of a fixed string.
+    pub fn text(&self, text: Text) -> &'static str {
+        text.translations()[self.language as usize]
+    }
+
+    /// `strftime` pattern for a date and time, without the UTC offset.
+    fn datetime_pattern(&self) -> &'static str {
+        match self.language {
+            Language::English if matches!(self.tag.as_str(), "en" | "en-US") => {
+                "%b %-d, %Y %-I:%M %p"
+            }
+            Language::English => "%-d %b %Y %H:%M",
+            Language::German => "%d.%m.%Y %H:%M",
+            Language::French | Language::Spanish | Language::Malay => "%d/%m/%Y %H:%M",
+            Language::Chinese | Language::Japanese => "%Y年%-m月%-d日 %H:%M",
+        }
+    }
+
+    /// Format `dt` for this locale, with its UTC offset.
+    pub fn format_datetime<Tz: chrono::TimeZone>(&self, dt: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        dt.format(&format!("{} %:z", self.datetime_pattern()))
+            .to_string()
+    }
+}
+
+/// Format a unix timestamp (seconds) in local time: for `locale`, or ISO
+/// when none is configured.
+pub fn format_timestamp(locale: Option<&Locale>, epoch_secs: i64) -> String {
+    let Some(dt) = DateTime::from_timestamp(epoch_secs, 0) else {
+        return epoch_secs.to_string();
+    };
+    let dt = dt.with_timezone(&Local);
+    match locale {
+        Some(locale) => locale.format_datetime(&dt),
+        None => dt.format("%Y-%m-%d %H:%M %:z").to_string(),
+    }
+}
+
+/// Translate `text` for `locale`, English when none is configured.
+pub fn text(locale: Option<&Locale>, text: Text) -> &'static str {
+    match locale {
+        Some(locale) => locale.text(text),
+        None => text.translations()[Language::English as usize],
+    }
+}
+
+/// Fixed strings shown to users by the gateway and the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    /// Reply to `/stop`.
+    Stopped,
+    /// Reply to `/stop` with nothing running.
+    NothingToStop,
+    /// Turn timeout; `{}` is the number of seconds.
+    TimedOut,
+    /// Provider outage, retries used up.
+    ProviderUnavailable,
+    /// Provider outage, retry queue full.
+    ProviderQueueFull,
+    StatusTitle,
+    StatusConfiguration,
+    StatusWorkspace,
+    StatusSessions,
+    StatusAgentDefaults,
+    StatusGateway,
+    StatusTunnel,
+    StatusDaemon,
+    StatusContainerAgent,
+    StatusRuntime,
+    StatusMemory,
+    StatusHeartbeat,
+    StatusSkills,
+    StatusTools,
+}
+
+impl Text {
+    /// Translations, in `Language` order.
+    fn translations(self) -> [&'static str; 7] {
+        match self {
+            Self::Stopped => [
+                "Stopped.",
+                "Gestoppt.",
+                "Arrêté.",
+                "Detenido.",
+                "Dihentikan.",
+                "已停止。",
+                "停止しました。",
+            ],
+            Self::NothingToStop => [
+                "Nothing to stop.",
+                "Nichts zu stoppen.",
+                "Rien à arrêter.",
+                "No hay nada que detener.",
+                "Tiada apa untuk dihentikan.",
+                "没有可停止的任务。",
+                "停止するものはありません。",
+            ],
+            Self::TimedOut => [
+                "Agent run timed out after {}s. Try a simpler request.",
+                "Zeitüberschreitung nach {}s. Versuche eine einfachere Anfrage.",
+                "Délai dépassé après {}s. Essayez une demande plus simple.",
+                "Se agotó el tiempo tras {}s. Prueba con una petición más sencilla.",
+                "Masa tamat selepas {}s. Cuba permintaan yang lebih mudah.",
+                "运行在 {} 秒后超时。请尝试更简单的请求。",
+                "{}秒後にタイムアウトしました。もっと簡単なリクエストをお試しください。",
+            ],
+            Self::ProviderUnavailable => [
+                "Sorry, I still can't reach my language model. Please try again later.",
+                "Entschuldigung, ich erreiche mein Sprachmodell immer noch nicht. Bitte versuche es später noch einmal.",
+                "Désolé, je n'arrive toujours pas à joindre mon modèle de langage. Veuillez réessayer plus tard.",
+                "Lo siento, sigo sin poder acceder a mi modelo de lenguaje. Inténtalo de nuevo más tarde.",
+                "Maaf, saya masih tidak dapat menghubungi model bahasa saya. Sila cuba lagi nanti.",
+                "抱歉，我仍然无法连接到语言模型。请稍后再试。",
+                "申し訳ありません。まだ言語モデルに接続できません。後でもう一度お試しください。",
+            ],
+            Self::ProviderQueueFull => [
+                "Sorry, I can't reach my language model right now and my queue is full. Please try again later.",
+                "Entschuldigung, ich erreiche mein Sprachmodell gerade nicht und meine Warteschlange ist voll. Bitte versuche es später noch einmal.",
+                "Désolé, je ne peux pas joindre mon modèle de langage pour le moment et ma file d'attente est pleine. Veuillez réessayer plus tard.",
+                "Lo siento, ahora mismo no puedo acceder a mi modelo de lenguaje y mi cola está llena. Inténtalo de nuevo más tarde.",
+                "Maaf, saya tidak dapat menghubungi model bahasa saya sekarang dan baris gilir saya penuh. Sila cuba lagi nanti.",
+                "抱歉，我现在无法连接到语言模型，而且队列已满。请稍后再试。",
+                "申し訳ありません。現在言語モデルに接続できず、キューも満杯です。後でもう一度お試しください。",
+            ],
+            Self::StatusTitle => [
+                "ZeptoClaw Status",
+                "ZeptoClaw-Status",
+                "État de ZeptoClaw",
+                "Estado de ZeptoClaw",
+                "Status ZeptoClaw",
+                "ZeptoClaw 状态",
+                "ZeptoClaw ステータス",
+            ],
+            Self::StatusConfiguration => [
+                "Configuration",
+                "Konfiguration",
+                "Configuration",
+                "Configuración",
+                "Konfigurasi",
+                "配置",
+                "設定",
+            ],
+            Self::StatusWorkspace => [
+                "Workspace",
+                "Arbeitsbereich",
+                "Espace de travail",
+                "Espacio de trabajo",
+                "Ruang kerja",
+                "工作区",
+                "ワークスペース",
+            ],
+            Self::StatusSessions => [
+                "Sessions",
+                "Sitzungen",
+                "Sessions",
+                "Sesiones",
+                "Sesi",
+                "会话",
+                "セッション",
+            ],
+            Self::StatusAgentDefaults => [
+                "Agent Defaults",
+                "Agent-Standardwerte",
+                "Paramètres par défaut de l'agent",
+                "Valores predeterminados del agente",
+                "Tetapan lalai ejen",
+                "代理默认设置",
+                "エージェントの既定値",
+            ],
+            Self::StatusGateway => [
+                "Gateway",
+                "Gateway",
+                "Passerelle",
+                "Pasarela",
+                "Gerbang",
+                "网关",
+                "ゲートウェイ",
+            ],
+            Self::StatusTunnel => [
+                "Tunnel",
+                "Tunnel",
+                "Tunnel",
+                "Túnel",
+                "Terowong",
+                "隧道",
+                "トンネル",
+            ],
+            Self::StatusDaemon => [
+                "Daemon",
+                "Daemon",
+                "Démon",
+                "Demonio",
+                "Daemon",
+                "守护进程",
+                "デーモン",
+            ],
+            Self::StatusContainerAgent => [
+                "Container Agent",
+                "Container-Agent",
+                "Agent conteneur",
+                "Agente en contenedor",
+                "Ejen kontena",
+                "容器代理",
+                "コンテナエージェント",
+            ],
+            Self::StatusRuntime => [
+                "Runtime",
+                "Laufzeit",
+                "Environnement d'exécution",
+                "Entorno de ejecución",
+                "Masa jalan",
+                "运行时",
+                "ランタイム",
+            ],
+            Self::StatusMemory => [
+                "Memory",
+                "Gedächtnis",
+                "Mémoire",
+                "Memoria",
+                "Memori",
+                "记忆",
+                "メモリ",
+            ],
+            Self::StatusHeartbeat => [
+                "Heartbeat",
+                "Heartbeat",
+                "Heartbeat",
+                "Heartbeat",
+                "Heartbeat",
+                "心跳",
+                "ハートビート",
+            ],
+            Self::StatusSkills => [
+                "Skills",
+                "Skills",
+                "Compétences",
+                "Habilidades",
+                "Kemahiran",
+                "技能",
+                "スキル",
+            ],
+            Self::StatusTools => [
+                "Available Tools",
+                "Verfügbare Tools",
+                "Outils disponibles",
+                "Herramientas disponibles",
+                "Alat tersedia",
+                "可用工具",
+                "利用可能なツール",
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone};
+
+    #[test]
+    fn test_parse_normalizes_tags() {
+        assert_eq!(Locale::parse("de").unwrap().tag(), "de");
+        assert_eq!(Locale::parse("en_gb").unwrap().tag(), "en-GB");
+        assert_eq!(
+            Locale::parse(" ZH-cn ").unwrap().language(),
+            Language::Chinese
+        );
+        for tag in ["", "xx", "de-", "en-US-x"] {
+            assert!(Locale::parse(tag).is_none(), "{}", tag);
+        }
+    }
+
+    #[test]
+    fn test_format_datetime_per_locale() {
+        let dt = FixedOffset::east_opt(8 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2026, 3, 1, 14, 5, 0)
+            .unwrap();
+        let format = |tag: &str| Locale::parse(tag).unwrap().format_datetime(&dt);
+        assert_eq!(format("en"), "Mar 1, 2026 2:05 PM +08:00");
+        assert_eq!(format("en-GB"), "1 Mar 2026 14:05 +08:00");
+        assert_eq!(format("de"), "01.03.2026 14:05 +08:00");
+        assert_eq!(format("fr-FR"), "01/03/2026 14:05 +08:00");
+        assert_eq!(format("ja"), "2026年3月1日 14:05 +08:00");
+    }
+
+    #[test]
+    fn test_unconfigured_locale_keeps_english_and_iso() {
+        assert_eq!(text(None, Text::Stopped), "Stopped.");
+        let de = Locale::parse("de-AT").unwrap();
+        assert_eq!(text(Some(&de), Text::Stopped), "Gestoppt.");
+        let iso = format_timestamp(None, 0);
+        assert!(iso.starts_with("1970-01-01") || iso.starts_with("1969-12-31"));
+    }
+
+    #[test]
+    fn test_every_text_is_translated() {
+        for text in [Text::TimedOut, Text::StatusTitle, Text::StatusTools] {
+            assert!(text.translations().iter().all(|t| !t.is_empty()));
+        }
+        assert!(Text::TimedOut
+            .translations()
+            .iter()
+            .all(|t| t.contains("{}")));
+    }
+
+    #[test]
+    fn test_prompt_section() {
+        let section = Locale::parse("es-MX").unwrap().prompt_section();
+        assert!(section.starts_with("## Language"));
+        assert!(section.contains("Reply in Spanish (es-MX)"));
+    }
+}
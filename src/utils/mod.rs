@@ -1,9 +1,14 @@
 //! Utils module - Utility functions and helpers
 
 pub mod cost;
+pub mod http;
+pub mod locale;
 pub mod logging;
 pub mod metrics;
+#[cfg(any(feature = "whatsapp-web", feature = "pairing-qr"))]
+pub mod qr;
 pub mod sanitize;
 pub mod slo;
 pub mod string;
 pub mod telemetry;
+pub mod timezone;
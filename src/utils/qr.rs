@@ -0,0 +1,184 @@
+//! QR code rendering for terminal display and PNG export.
+//!
+//! Used by WhatsApp Web login and device pairing. PNG output is encoded
+//! by hand (grayscale, stored deflate blocks) to avoid pulling in an image
+//! stack for a few kilobytes of black and white pixels.
+
+use qrcode::QrCode;
+
+/// Light modules around the code, per the QR spec's recommended quiet zone.
+const PNG_QUIET_ZONE: usize = 4;
+
+/// Encode `data` and return the module grid (row-major, `true` = dark).
+fn modules(data: &str) -> Option<(usize, Vec<bool>)> {
+    let code = QrCode::new(data.as_bytes()).ok()?;
+    let width = code.width();
+    let colors = code
+        .into_colors()
+        .into_iter()
+        .map(|c| c == qrcode::Color::Dark)
+        .collect();
+    Some((width, colors))
+}
+
+/// Render a QR code string as unicode block characters in the terminal.
+///
+/// Uses 2x1 half-block characters for compact display:
+/// - `█` (U+2588) = both pixels dark
+/// - `▀` (U+2580) = top dark, bottom light
+/// - `▄` (U+2584) = top light, bottom dark
+/// - ` ` (space)  = both pixels light
+pub fn render_qr_terminal(data: &str) -> Option<String> {
+    let (width, colors) = modules(data)?;
+
+    // Add 1-module quiet zone on each side
+    let padded_width = width + 2;
+    let padded_height = width + 2;
+
+    let pixel = |row: usize, col: usize| -> bool {
+        if row == 0 || row > width || col == 0 || col > width {
+            false // quiet zone
+        } else {
+            colors[(row - 1) * width + (col - 1)]
+        }
+    };
+
+    let mut output = String::new();
+    let mut y = 0;
+    while y < padded_height {
+        for x in 0..padded_width {
+            let top = pixel(y, x);
+            let bottom = if y + 1 < padded_height {
+                pixel(y + 1, x)
+            } else {
+                false
+            };
+            output.push(match (top, bottom) {
+                (true, true) => '\u{2588}',
+                (true, false) => '\u{2580}',
+                (false, true) => '\u{2584}',
+                (false, false) => ' ',
+            });
+        }
+        output.push('\n');
+        y += 2;
+    }
+    Some(output)
+}
+
+/// Render a QR code as an 8-bit grayscale PNG, `scale` pixels per module.
+pub fn render_qr_png(data: &str, scale: usize) -> Option<Vec<u8>> {
+    let (width, colors) = modules(data)?;
+    let scale = scale.max(1);
+    let side = (width + 2 * PNG_QUIET_ZONE) * scale;
+
+    // Raw scanlines: filter byte 0 followed by one byte per pixel.
+    let mut raw = Vec::with_capacity(side * (side + 1));
+    for y in 0..side {
+        raw.push(0);
+        let my = (y / scale).checked_sub(PNG_QUIET_ZONE);
+        for x in 0..side {
+            let mx = (x / scale).checked_sub(PNG_QUIET_ZONE);
+            let dark = match (my, mx) {
+                (Some(my), Some(mx)) if my < width && mx < width => colors[my * width + mx],
+                _ => false,
+            };
+            raw.push(if dark { 0x00 } else { 0xFF });
+        }
+    }
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&(side as u32).to_be_bytes());
+    ihdr.extend_from_slice(&(side as u32).to_be_bytes());
+    // Bit depth 8, color type 0 (grayscale), deflate, adaptive filter, no interlace
+    ihdr.extend_from_slice(&[8, 0, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    push_chunk(&mut png, b"IHDR", &ihdr);
+    push_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    push_chunk(&mut png, b"IEND", &[]);
+    Some(png)
+}
+
+fn push_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wrap `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65_535;
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_BLOCK * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(u8::from(last));
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= 65_521;
+        b %= 65_521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_qr_terminal() {
+        let out = render_qr_terminal("zeptoclaw://pair?code=123456").unwrap();
+        assert!(out.lines().count() > 10);
+        assert!(out.contains('\u{2588}'));
+    }
+
+    #[test]
+    fn test_render_qr_png_header_and_size() {
+        let png = render_qr_png("zeptoclaw://pair?code=123456", 4).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        let side = u32::from_be_bytes([png[16], png[17], png[18], png[19]]) as usize;
+        assert_eq!(side % 4, 0);
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]));
+    }
+
+    #[test]
+    fn test_checksums_known_values() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+}
@@ -4,6 +4,7 @@
 //! This module provides only rendering functions — no HTTP server or
 //! transport logic.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -14,7 +15,7 @@ use super::metrics::MetricsCollector;
 // ---------------------------------------------------------------------------
 
 /// Telemetry output format.
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TelemetryFormat {
     #[default]
@@ -23,7 +24,7 @@ pub enum TelemetryFormat {
 }
 
 /// Telemetry configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct TelemetryConfig {
     /// Whether telemetry export is enabled.
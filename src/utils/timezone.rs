@@ -0,0 +1,221 @@
+//! Timezones by name.
+//!
+//! [`Tz::load`] accepts `UTC`, fixed offsets (`+08:00`, `UTC-5`) and IANA
+//! names such as `Asia/Kuala_Lumpur`, resolved from the `chrono-tz`
+//! database compiled into the binary, so it works the same on every
+//! platform whether or not the system ships zoneinfo files.
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, Offset, TimeZone, Utc};
+
+use crate::error::{Result, ZeptoError};
+
+/// Long-term memory key holding a user's timezone.
+pub const USER_TIMEZONE_KEY: &str = "user:timezone";
+
+/// A named timezone.
+#[derive(Debug, Clone)]
+pub struct Tz {
+    name: String,
+    zone: Zone,
+}
+
+#[derive(Debug, Clone)]
+enum Zone {
+    /// UTC offset in seconds.
+    Fixed(i32),
+    Iana(chrono_tz::Tz),
+}
+
+impl Tz {
+    /// Look up a timezone by name.
+    pub fn load(name: &str) -> Result<Self> {
+        let name = name.trim();
+        let zone = match parse_fixed(name) {
+            Some(offset) => Zone::Fixed(offset),
+            None => Zone::Iana(
+                name.parse()
+                    .map_err(|_| ZeptoError::Config(format!("Unknown timezone '{}'", name)))?,
+            ),
+        };
+        Ok(Self {
+            name: name.to_string(),
+            zone,
+        })
+    }
+
+    /// The name the timezone was loaded with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// UTC offset in effect at a UTC instant.
+    pub fn offset_at(&self, utc: DateTime<Utc>) -> FixedOffset {
+        match &self.zone {
+            Zone::Fixed(secs) => {
+                FixedOffset::east_opt(*secs).unwrap_or_else(|| FixedOffset::east_opt(0).unwrap())
+            }
+            Zone::Iana(tz) => tz.offset_from_utc_datetime(&utc.naive_utc()).fix(),
+        }
+    }
+
+    /// A UTC instant in this timezone.
+    pub fn from_utc(&self, utc: DateTime<Utc>) -> DateTime<FixedOffset> {
+        utc.with_timezone(&self.offset_at(utc))
+    }
+
+    /// The current time in this timezone.
+    pub fn now(&self) -> DateTime<FixedOffset> {
+        self.from_utc(Utc::now())
+    }
+
+    /// System prompt section giving the user's local time.
+    pub fn prompt_section(&self) -> String {
+        format!(
+            "## User Time\n\nThe user is in {}; their local time is {}. \
+             Resolve relative times (\"tomorrow morning\", \"tonight\") in their timezone.",
+            self.name,
+            self.now().format("%a %Y-%m-%d %H:%M %:z")
+        )
+    }
+
+    /// Interpret a wall-clock time in this timezone. In a DST gap the time
+    /// is read with the offset before the change.
+    pub fn from_local(&self, local: NaiveDateTime) -> DateTime<FixedOffset> {
+        let guess = local.and_utc();
+        let offset = self.offset_at(guess);
+        let offset = self.offset_at(guess - Duration::seconds(offset.local_minus_utc() as i64));
+        let utc = local - Duration::seconds(offset.local_minus_utc() as i64);
+        self.from_utc(utc.and_utc())
+    }
+}
+
+/// `UTC`, `Z`, `+08:00`, `-0530`, `UTC+8`, `GMT-3`.
+fn parse_fixed(name: &str) -> Option<i32> {
+    let upper = name.to_ascii_uppercase();
+    if matches!(upper.as_str(), "UTC" | "GMT" | "Z") {
+        return Some(0);
+    }
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper);
+    let (sign, digits) = match rest.as_bytes().first()? {
+        b'+' => (1, &rest[1..]),
+        b'-' => (-1, &rest[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if digits.len() == 4 => digits.split_at(2),
+        None => (digits, "0"),
+    };
+    let hours: i32 = hours.parse().ok().filter(|h| *h <= 14)?;
+    let minutes: i32 = minutes.parse().ok().filter(|m| *m < 60)?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_fixed_offsets() {
+        for (name, secs) in [
+            ("UTC", 0),
+            ("+08:00", 8 * 3600),
+            ("-0530", -(5 * 3600 + 30 * 60)),
+            ("UTC+8", 8 * 3600),
+            ("gmt-3", -3 * 3600),
+        ] {
+            let tz = Tz::load(name).unwrap();
+            assert_eq!(
+                tz.offset_at(utc(2026, 1, 1, 0)).local_minus_utc(),
+                secs,
+                "{}",
+                name
+            );
+        }
+        assert!(Tz::load("+15:00").is_err());
+    }
+
+    #[test]
+    fn test_rejects_paths() {
+        for name in [
+            "",
+            "../etc/passwd",
+            "/etc/localtime",
+            "Europe//Berlin",
+            "Mars/Base",
+        ] {
+            assert!(Tz::load(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_iana_zones_follow_dst() {
+        let kl = Tz::load("Asia/Kuala_Lumpur").unwrap();
+        assert_eq!(kl.name(), "Asia/Kuala_Lumpur");
+        assert_eq!(kl.offset_at(utc(2026, 6, 1, 0)).local_minus_utc(), 8 * 3600);
+
+        let berlin = Tz::load("Europe/Berlin").unwrap();
+        assert_eq!(
+            berlin.offset_at(utc(2031, 1, 15, 12)).local_minus_utc(),
+            3600
+        );
+        assert_eq!(
+            berlin.offset_at(utc(2031, 7, 15, 12)).local_minus_utc(),
+            7200
+        );
+        // 2031-03-30 is the last Sunday of March: DST from 01:00 UTC.
+        assert_eq!(
+            berlin.offset_at(utc(2031, 3, 30, 0)).local_minus_utc(),
+            3600
+        );
+        assert_eq!(
+            berlin.offset_at(utc(2031, 3, 30, 1)).local_minus_utc(),
+            7200
+        );
+
+        let sydney = Tz::load("Australia/Sydney").unwrap();
+        assert_eq!(
+            sydney.offset_at(utc(2031, 1, 15, 0)).local_minus_utc(),
+            11 * 3600
+        );
+        assert_eq!(
+            sydney.offset_at(utc(2031, 7, 15, 0)).local_minus_utc(),
+            10 * 3600
+        );
+    }
+
+    #[test]
+    fn test_from_local_round_trips() {
+        let local = NaiveDate::from_ymd_opt(2026, 3, 1)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let dt = Tz::load("+05:30").unwrap().from_local(local);
+        assert_eq!(dt.naive_local(), local);
+        assert_eq!(dt.with_timezone(&Utc).format("%H:%M").to_string(), "03:30");
+
+        let dt = Tz::load("America/New_York").unwrap().from_local(
+            NaiveDate::from_ymd_opt(2026, 7, 1)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+        );
+        assert_eq!(dt.with_timezone(&Utc).format("%H:%M").to_string(), "13:00");
+    }
+
+    #[test]
+    fn test_prompt_section() {
+        let section = Tz::load("+08:00").unwrap().prompt_section();
+        assert!(section.starts_with("## User Time"));
+        assert!(section.contains("The user is in +08:00"));
+        assert!(section.contains(" +08:00."));
+    }
+}
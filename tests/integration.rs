@@ -1465,3 +1465,68 @@ async fn test_agent_loop_streaming_accessors() {
     agent.set_streaming(true);
     assert!(agent.is_streaming());
 }
+
+// ============================================================================
+// Mock Provider and Fake Tool Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_mock_provider_and_fake_tools_drive_agent_loop() {
+    use zeptoclaw::providers::MockProvider;
+    use zeptoclaw::tools::FakeTool;
+
+    let provider = Arc::new(
+        MockProvider::new()
+            .with_tool_calls(vec![
+                ("search", serde_json::json!({"q": "rust"})),
+                ("fetch", serde_json::json!({"url": "https://example.com"})),
+            ])
+            .with_text("Found it."),
+    );
+    let search = FakeTool::new("search").returning("3 results");
+    let fetch = FakeTool::new("fetch").then_fail("connection refused");
+
+    let agent = zeptoclaw::agent::AgentLoop::new(
+        Config::default(),
+        SessionManager::new_memory(),
+        Arc::new(MessageBus::new()),
+    );
+    agent.set_provider_arc(provider.clone()).await;
+    agent.register_tool(Box::new(search.clone())).await;
+    agent.register_tool(Box::new(fetch.clone())).await;
+
+    let msg = InboundMessage::new("test", "user", "chat1", "find rust docs");
+    let reply = agent.process_message(&msg).await.unwrap();
+    assert_eq!(reply, "Found it.");
+
+    assert_eq!(search.calls(), vec![serde_json::json!({"q": "rust"})]);
+    assert_eq!(fetch.call_count(), 1);
+    let requests = provider.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].tools.contains(&"search".to_string()));
+    assert_eq!(requests[0].last_user_message(), Some("find rust docs"));
+    // The failed call is reported back to the model.
+    assert!(requests[1]
+        .messages
+        .iter()
+        .any(|m| m.content.contains("connection refused")));
+}
+
+#[tokio::test]
+async fn test_mock_provider_error_injection_surfaces() {
+    use zeptoclaw::error::ProviderError;
+    use zeptoclaw::providers::MockProvider;
+
+    let agent = zeptoclaw::agent::AgentLoop::new(
+        Config::default(),
+        SessionManager::new_memory(),
+        Arc::new(MessageBus::new()),
+    );
+    agent
+        .set_provider(Box::new(MockProvider::new().with_error(
+            ZeptoError::ProviderTyped(ProviderError::Auth("bad key".into())),
+        )))
+        .await;
+    let msg = InboundMessage::new("test", "user", "chat1", "hello");
+    assert!(agent.process_message(&msg).await.is_err());
+}